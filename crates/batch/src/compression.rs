@@ -0,0 +1,171 @@
+//! Whole-file compression of batch files.
+//!
+//! A batch written with [`BatchCompression::Zstd`] is a single zstd frame
+//! wrapping the complete batch stream (header, file list, deltas, stats).
+//! Readers recognise it by the zstd frame magic, so the same `--read-batch`
+//! invocation replays compressed and plain batches alike.
+//!
+//! Compressed batches are an oc-rsync extension: upstream rsync cannot read
+//! them, so compression is opt-in and plain batches stay the default.
+
+use std::fs::File;
+use std::io::{self, Read, Write};
+
+/// Little-endian zstd frame magic (`0xFD2FB528`).
+pub const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// Default zstd level for compressed batch files.
+pub const DEFAULT_ZSTD_LEVEL: i32 = 3;
+
+/// Compression applied to the whole batch file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BatchCompression {
+    /// Plain batch file, byte-compatible with upstream rsync.
+    #[default]
+    None,
+    /// Batch stream wrapped in a single zstd frame.
+    Zstd {
+        /// zstd compression level.
+        level: i32,
+    },
+}
+
+impl BatchCompression {
+    /// zstd compression at [`DEFAULT_ZSTD_LEVEL`].
+    pub const fn zstd() -> Self {
+        Self::Zstd {
+            level: DEFAULT_ZSTD_LEVEL,
+        }
+    }
+
+    /// Returns `true` when the batch is written compressed.
+    pub const fn is_compressed(self) -> bool {
+        !matches!(self, Self::None)
+    }
+}
+
+/// Returns `true` when `prefix` starts with the zstd frame magic.
+pub fn is_zstd_magic(prefix: &[u8]) -> bool {
+    prefix.starts_with(&ZSTD_MAGIC)
+}
+
+/// Destination of a [`BatchWriter`](crate::BatchWriter): the batch file
+/// itself or a zstd encoder in front of it.
+pub(crate) enum BatchSink {
+    Plain(File),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::stream::write::Encoder<'static, File>),
+}
+
+impl std::fmt::Debug for BatchSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Plain(file) => f.debug_tuple("Plain").field(file).finish(),
+            #[cfg(feature = "zstd")]
+            Self::Zstd(_) => f.write_str("Zstd"),
+        }
+    }
+}
+
+impl BatchSink {
+    /// Wraps `file` according to `compression`.
+    pub(crate) fn new(file: File, compression: BatchCompression) -> io::Result<Self> {
+        match compression {
+            BatchCompression::None => Ok(Self::Plain(file)),
+            #[cfg(feature = "zstd")]
+            BatchCompression::Zstd { level } => {
+                zstd::stream::write::Encoder::new(file, level).map(Self::Zstd)
+            }
+            #[cfg(not(feature = "zstd"))]
+            BatchCompression::Zstd { .. } => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "zstd batch compression is not available in this build",
+            )),
+        }
+    }
+
+    /// Ends the zstd frame (if any) and flushes the file.
+    pub(crate) fn finish(self) -> io::Result<()> {
+        match self {
+            Self::Plain(mut file) => file.flush(),
+            #[cfg(feature = "zstd")]
+            Self::Zstd(encoder) => encoder.finish()?.flush(),
+        }
+    }
+}
+
+impl Write for BatchSink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Plain(file) => file.write(buf),
+            #[cfg(feature = "zstd")]
+            Self::Zstd(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Plain(file) => file.flush(),
+            #[cfg(feature = "zstd")]
+            Self::Zstd(encoder) => encoder.flush(),
+        }
+    }
+}
+
+/// Wraps a zstd-compressed batch stream in a streaming decoder, so replay
+/// never holds more than the decoder's window of the batch in memory.
+#[cfg(feature = "zstd")]
+pub(crate) fn decompress_batch<R: Read + Send + 'static>(
+    compressed: R,
+) -> io::Result<Box<dyn Read + Send>> {
+    Ok(Box::new(zstd::stream::read::Decoder::new(compressed)?))
+}
+
+/// Without zstd support a compressed batch cannot be replayed.
+#[cfg(not(feature = "zstd"))]
+pub(crate) fn decompress_batch<R: Read + Send + 'static>(
+    _compressed: R,
+) -> io::Result<Box<dyn Read + Send>> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "batch file is zstd-compressed but zstd support is not available in this build",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_is_uncompressed() {
+        assert_eq!(BatchCompression::default(), BatchCompression::None);
+        assert!(!BatchCompression::None.is_compressed());
+        assert!(BatchCompression::zstd().is_compressed());
+    }
+
+    #[test]
+    fn zstd_magic_detection() {
+        assert!(is_zstd_magic(&[0x28, 0xB5, 0x2F, 0xFD, 0x00]));
+        assert!(!is_zstd_magic(&[0x1F, 0x00, 0x00, 0x00]));
+        assert!(!is_zstd_magic(&[0x28, 0xB5]));
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn zstd_sink_roundtrip() {
+        let temp = tempfile::NamedTempFile::new().unwrap();
+        let file = temp.reopen().unwrap();
+        let mut sink = BatchSink::new(file, BatchCompression::zstd()).unwrap();
+        sink.write_all(b"batch payload").unwrap();
+        sink.finish().unwrap();
+
+        let compressed = std::fs::read(temp.path()).unwrap();
+        assert!(is_zstd_magic(&compressed));
+        let mut decoded = Vec::new();
+        decompress_batch(io::Cursor::new(compressed))
+            .unwrap()
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, b"batch payload");
+    }
+}
@@ -30,6 +30,13 @@ pub enum BatchError {
     /// `RERR_SYNTAX`.
     #[error("{0}")]
     FlagMismatch(String),
+    /// A replayed file's content does not match the whole-file checksum
+    /// recorded in the batch.
+    ///
+    /// upstream: receiver.c:970-985 - "%s failed verification"; the transfer
+    /// ends with `RERR_PARTIAL`.
+    #[error("{0}")]
+    VerificationFailed(String),
 }
 
 #[cfg(test)]
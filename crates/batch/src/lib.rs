@@ -149,6 +149,8 @@
 //! - [`BatchError::Unsupported`] - Features not yet implemented
 //! - [`BatchError::FlagMismatch`] - A batch stream flag cannot be reconciled
 //!   with the active options (a fatal `--iconv` mismatch)
+//! - [`BatchError::VerificationFailed`] - Replayed content does not match the
+//!   whole-file checksum recorded in the batch
 //!
 //! # Thread Safety
 //!
@@ -158,6 +160,11 @@
 
 mod error;
 
+/// Optional whole-file compression of batch files.
+///
+/// See [`BatchCompression`] and [`BatchConfig::with_compression`].
+pub mod compression;
+
 /// Binary format definitions for batch files.
 ///
 /// This module contains the low-level structures for reading and writing
@@ -197,6 +204,12 @@ mod tests;
 /// See [`error::BatchError`] for detailed error variants.
 pub use error::BatchError;
 
+/// Compression applied to a whole batch file.
+///
+/// Readers detect compressed batches from the zstd frame magic, so only the
+/// writer needs to be configured.
+pub use compression::BatchCompression;
+
 /// Result type alias for batch operations.
 ///
 /// Equivalent to `Result<T, BatchError>`.
@@ -446,6 +459,19 @@ pub struct BatchConfig {
     /// (`numeric_ids <= 0 && !inc_recurse`) and `uidlist.c:465,473`
     /// (`numeric_ids <= 0`).
     pub numeric_ids: bool,
+
+    /// Whether `--dry-run` was active for a `--read-batch` invocation.
+    ///
+    /// Replay still decodes and validates the whole batch against the
+    /// destination but makes no filesystem changes. Only consulted in read
+    /// mode.
+    pub dry_run: bool,
+
+    /// Compression applied to the batch file when writing.
+    ///
+    /// Ignored in read mode: [`BatchReader`] detects a compressed batch from
+    /// its leading zstd frame magic.
+    pub compression: BatchCompression,
 }
 
 impl BatchConfig {
@@ -501,6 +527,8 @@ impl BatchConfig {
             active_flags: BatchFlags::default(),
             eol_nulls: false,
             numeric_ids: false,
+            dry_run: false,
+            compression: BatchCompression::None,
         }
     }
 
@@ -695,6 +723,46 @@ impl BatchConfig {
         self
     }
 
+    /// Set whether `--dry-run` is active for a `--read-batch` replay.
+    ///
+    /// A dry-run replay walks the batch and checks it against the destination
+    /// tree without creating, modifying, or deleting anything.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use batch::{BatchConfig, BatchMode};
+    ///
+    /// let config = BatchConfig::new(BatchMode::Read, "/tmp/batch".to_string(), 31)
+    ///     .with_dry_run(true);
+    ///
+    /// assert!(config.dry_run);
+    /// ```
+    pub const fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Set the compression applied to a written batch file.
+    ///
+    /// A compressed batch is an oc-rsync extension that upstream rsync cannot
+    /// replay. Creating the writer fails when zstd support is not compiled in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use batch::{BatchCompression, BatchConfig, BatchMode};
+    ///
+    /// let config = BatchConfig::new(BatchMode::Write, "/tmp/batch".to_string(), 31)
+    ///     .with_compression(BatchCompression::zstd());
+    ///
+    /// assert!(config.compression.is_compressed());
+    /// ```
+    pub const fn with_compression(mut self, compression: BatchCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
     /// Get the path to the binary batch file.
     ///
     /// Returns the path where the binary batch data is stored. This is
@@ -7,9 +7,11 @@
 //! - `flist` - file list deserialization (protocol and local formats)
 //! - `delta` - delta token and operation reading
 //! - `stats` - transfer statistics reading
+//! - `source` - seekable byte source (file or standard input)
 
 mod delta;
 mod flist;
+mod source;
 mod stats;
 
 #[cfg(test)]
@@ -20,8 +22,10 @@ use crate::error::{BatchError, BatchResult};
 use crate::format::{BatchFlags, BatchHeader};
 use protocol::codec::NdxCodecEnum;
 use protocol::flist::FileListReader;
-use std::fs::File;
 use std::io::{self, BufReader, Read};
+use std::path::Path;
+
+pub use source::{BatchSource, BatchStream, RewindableStream, STDIN_BATCH_NAME};
 
 /// Reader for batch mode operations.
 ///
@@ -31,7 +35,7 @@ pub struct BatchReader {
     /// Configuration for this batch operation.
     config: BatchConfig,
     /// Reader for the binary batch file.
-    batch_file: Option<BufReader<File>>,
+    batch_file: Option<BatchStream>,
     /// The header read from the file.
     header: Option<BatchHeader>,
     /// Accumulated I/O error code from the file list sender.
@@ -62,9 +66,11 @@ pub struct BatchReader {
 
 impl BatchReader {
     /// Create a new batch reader.
+    ///
+    /// A batch name of `-` reads the batch from standard input.
     pub fn new(config: BatchConfig) -> BatchResult<Self> {
         let batch_path = config.batch_file_path();
-        let file = File::open(batch_path).map_err(|e| {
+        let source = BatchSource::open(batch_path).map_err(|e| {
             BatchError::Io(io::Error::new(
                 e.kind(),
                 format!(
@@ -75,15 +81,20 @@ impl BatchReader {
             ))
         })?;
 
-        Ok(Self {
+        Ok(Self::with_source(config, source))
+    }
+
+    /// Create a batch reader over an already opened [`BatchSource`].
+    pub fn with_source(config: BatchConfig, source: BatchSource) -> Self {
+        Self {
             config,
-            batch_file: Some(BufReader::new(file)),
+            batch_file: Some(BufReader::new(source)),
             header: None,
             io_error: 0,
             ndx_codec: None,
             flist_reader: None,
             flist_next_ndx_start: 0,
-        })
+        }
     }
 
     /// Read and validate the batch header.
@@ -208,10 +219,25 @@ impl BatchReader {
     /// example to pass it to protocol-level decoders like `read_delta`.
     ///
     /// Returns `None` if the batch file has not been opened or has been closed.
-    pub fn inner_reader(&mut self) -> Option<&mut BufReader<File>> {
+    pub fn inner_reader(&mut self) -> Option<&mut BatchStream> {
         self.batch_file.as_mut()
     }
 
+    /// Walk the whole batch and check it against `dest_root` without
+    /// modifying anything.
+    ///
+    /// Validates the header (protocol version, stream flags against
+    /// [`BatchConfig::active_flags`]), decodes the file list and every delta,
+    /// and checks that each block match is backed by a basis file at the
+    /// destination. The returned [`ReplayResult`](crate::ReplayResult)
+    /// reports what a real replay would create and rewrite.
+    ///
+    /// Must be called on a freshly opened reader.
+    pub fn verify(mut self, dest_root: &Path) -> BatchResult<crate::ReplayResult> {
+        self.config.dry_run = true;
+        crate::replay::replay_from_reader(&mut self, dest_root, 0)
+    }
+
     /// Returns the NDX codec initialized during flist reading.
     ///
     /// The codec carries state from reading incremental flist segment NDX
@@ -0,0 +1,206 @@
+//! Byte source backing a [`BatchReader`](super::BatchReader).
+//!
+//! Batch replay peeks ahead and seeks back (compression codec detection), so
+//! the source must be seekable. A regular batch file is read directly. The
+//! `--read-batch=-` form and zstd-compressed batches are consumed as streams
+//! that retain a bounded window of recently read bytes, so those seeks back
+//! succeed without holding the whole batch in memory.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, Cursor, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::compression::{ZSTD_MAGIC, decompress_batch, is_zstd_magic};
+
+/// Batch name that selects standard input instead of a file.
+///
+/// upstream: main.c - `if (strcmp(batch_name, "-") == 0) batch_fd =
+/// STDIN_FILENO;` in the `read_batch` branch of `start_server()`/`main()`.
+pub const STDIN_BATCH_NAME: &str = "-";
+
+/// Buffered batch stream handed to the replay decoders.
+pub type BatchStream = BufReader<BatchSource>;
+
+/// Bytes of already-read stream data kept for seeking back.
+///
+/// Codec detection scans at most 64 KiB ahead before rewinding, and the
+/// [`BatchStream`] buffer adds its own read-ahead on top of that.
+const REWIND_WINDOW: usize = 256 * 1024;
+
+/// Seekable byte source for a batch being replayed.
+#[derive(Debug)]
+pub enum BatchSource {
+    /// Batch file opened from disk.
+    File(File),
+    /// Forward-only stream (standard input or a decompressing decoder).
+    Stream(RewindableStream),
+}
+
+impl BatchSource {
+    /// Opens the batch named by `path`, reading standard input for `-`.
+    ///
+    /// A batch starting with the zstd frame magic is transparently
+    /// decompressed.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        if path == Path::new(STDIN_BATCH_NAME) {
+            return Self::from_reader(io::stdin());
+        }
+
+        let mut file = File::open(path)?;
+        let mut magic = [0u8; ZSTD_MAGIC.len()];
+        let peeked = read_prefix(&mut file, &mut magic)?;
+        file.seek(SeekFrom::Start(0))?;
+        if is_zstd_magic(&magic[..peeked]) {
+            let decoder = decompress_batch(file)?;
+            return Ok(Self::Stream(RewindableStream::new(decoder)));
+        }
+        Ok(Self::File(file))
+    }
+
+    /// Streams the batch from `reader`, decompressing a zstd batch on the fly.
+    pub fn from_reader<R: Read + Send + 'static>(mut reader: R) -> io::Result<Self> {
+        let mut magic = [0u8; ZSTD_MAGIC.len()];
+        let peeked = read_prefix(&mut reader, &mut magic)?;
+        let stream = Cursor::new(magic[..peeked].to_vec()).chain(reader);
+        let inner: Box<dyn Read + Send> = if is_zstd_magic(&magic[..peeked]) {
+            decompress_batch(stream)?
+        } else {
+            Box::new(stream)
+        };
+        Ok(Self::Stream(RewindableStream::new(inner)))
+    }
+}
+
+/// Forward-only reader that can seek back within the last
+/// [`REWIND_WINDOW`] bytes it produced.
+///
+/// Seeking forward reads and discards; seeking before the retained window
+/// or relative to the end fails with [`io::ErrorKind::Unsupported`].
+pub struct RewindableStream {
+    inner: Box<dyn Read + Send>,
+    /// Most recently read bytes, ending at `end`.
+    window: VecDeque<u8>,
+    /// Stream offset one past the last byte read from `inner`.
+    end: u64,
+    /// Stream offset of the next byte handed to the caller.
+    position: u64,
+}
+
+impl RewindableStream {
+    fn new(inner: Box<dyn Read + Send>) -> Self {
+        Self {
+            inner,
+            window: VecDeque::new(),
+            end: 0,
+            position: 0,
+        }
+    }
+
+    fn window_start(&self) -> u64 {
+        self.end - self.window.len() as u64
+    }
+
+    fn seek_to(&mut self, target: u64) -> io::Result<u64> {
+        if target < self.window_start() {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "cannot seek before the retained window of a streamed batch",
+            ));
+        }
+        if target > self.end {
+            self.position = self.end;
+            let skip = target - self.end;
+            let skipped = io::copy(&mut self.by_ref().take(skip), &mut io::sink())?;
+            if skipped < skip {
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+            }
+        }
+        self.position = target;
+        Ok(target)
+    }
+}
+
+impl fmt::Debug for RewindableStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RewindableStream")
+            .field("retained", &self.window.len())
+            .field("position", &self.position)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Read for RewindableStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position < self.end {
+            let offset = (self.position - self.window_start()) as usize;
+            let (front, back) = self.window.as_slices();
+            let chunk = if offset < front.len() {
+                &front[offset..]
+            } else {
+                &back[offset - front.len()..]
+            };
+            let n = chunk.len().min(buf.len());
+            buf[..n].copy_from_slice(&chunk[..n]);
+            self.position += n as u64;
+            return Ok(n);
+        }
+
+        let n = self.inner.read(buf)?;
+        self.window.extend(&buf[..n]);
+        let excess = self.window.len().saturating_sub(REWIND_WINDOW);
+        self.window.drain(..excess);
+        self.end += n as u64;
+        self.position = self.end;
+        Ok(n)
+    }
+}
+
+impl Seek for RewindableStream {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match pos {
+            SeekFrom::Start(offset) => self.seek_to(offset),
+            SeekFrom::Current(delta) => match self.position.checked_add_signed(delta) {
+                Some(target) => self.seek_to(target),
+                None => Err(io::Error::from(io::ErrorKind::InvalidInput)),
+            },
+            SeekFrom::End(_) => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "cannot seek relative to the end of a streamed batch",
+            )),
+        }
+    }
+}
+
+/// Reads up to `buf.len()` bytes, stopping early only at end of file.
+fn read_prefix(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+impl Read for BatchSource {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::File(file) => file.read(buf),
+            Self::Stream(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Seek for BatchSource {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match self {
+            Self::File(file) => file.seek(pos),
+            Self::Stream(stream) => stream.seek(pos),
+        }
+    }
+}
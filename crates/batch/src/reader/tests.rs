@@ -63,6 +63,48 @@ mod reader_creation_tests {
         let reader = BatchReader::new(config).unwrap();
         assert!(reader.header().is_none());
     }
+
+    #[test]
+    fn stream_source_reads_same_header_as_file() {
+        // `--read-batch=-` streams standard input through a rewindable
+        // source; the header must decode identically to the on-disk batch.
+        let temp_dir = TempDir::new().unwrap();
+        let batch_path = temp_dir.path().join("test.batch");
+        create_test_batch(&batch_path);
+
+        let config = BatchConfig::new(BatchMode::Read, STDIN_BATCH_NAME.to_owned(), 30);
+        let bytes = std::fs::read(&batch_path).unwrap();
+        let source = BatchSource::from_reader(std::io::Cursor::new(bytes)).unwrap();
+        let mut reader = BatchReader::with_source(config, source);
+
+        let flags = reader.read_header().unwrap();
+        assert!(flags.recurse);
+        assert_eq!(reader.header().unwrap().checksum_seed, 12345);
+    }
+
+    #[test]
+    fn stream_source_seeks_back_within_window() {
+        use std::io::{Read, Seek, SeekFrom};
+
+        let bytes: Vec<u8> = (0..=255u8).cycle().take(600 * 1024).collect();
+        let mut source = BatchSource::from_reader(std::io::Cursor::new(bytes.clone())).unwrap();
+
+        let mut head = vec![0u8; 100_000];
+        source.read_exact(&mut head).unwrap();
+        assert_eq!(source.seek(SeekFrom::Start(10)).unwrap(), 10);
+        let mut again = [0u8; 4];
+        source.read_exact(&mut again).unwrap();
+        assert_eq!(again, bytes[10..14]);
+
+        // Seeking forward past what was read skips the intervening bytes.
+        source.seek(SeekFrom::Start(500_000)).unwrap();
+        source.read_exact(&mut again).unwrap();
+        assert_eq!(again, bytes[500_000..500_004]);
+
+        // The start of the stream has left the retained window.
+        let err = source.seek(SeekFrom::Start(0)).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::Unsupported);
+    }
 }
 
 mod header_tests {
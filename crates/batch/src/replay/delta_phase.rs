@@ -24,15 +24,19 @@ use super::ReplayResult;
 use super::codec::detect_compression_codec;
 use super::codec::{CompressionCodec, create_compressed_decoder};
 use super::delta::{choose_block_length, default_xfer_sum_len};
+use super::digest::FileDigest;
 use super::dispatch::{
-    ITEM_TRANSFER, apply_file_delta, read_and_discard_file_checksum,
-    read_compressed_deltas_streaming, read_iflags_and_skip_meta, read_sum_head,
+    ITEM_TRANSFER, apply_file_delta, check_file_delta, read_compressed_deltas_streaming,
+    read_file_checksum, read_iflags_and_skip_meta, read_sum_head, verify_file_digest,
 };
 
 /// Phase 2: drive the NDX loop and apply per-file deltas.
 ///
 /// upstream: receiver.c:recv_files() reads NDX + iflags + sum_head per file,
 /// then delta tokens, then file checksum. NDX_DONE signals phase transitions.
+///
+/// Under `dry_run` the stream is decoded and checked against the destination
+/// exactly as for a real replay, but no file is written.
 pub(super) fn apply_delta_phase(
     reader: &mut BatchReader,
     entries: &mut Vec<protocol::flist::FileEntry>,
@@ -40,6 +44,7 @@ pub(super) fn apply_delta_phase(
     flags: &BatchFlags,
     result: &mut ReplayResult,
     verbosity: i32,
+    dry_run: bool,
 ) -> BatchResult<()> {
     let proto = reader.config().protocol_version;
     let mut codec_state = CodecState::new(flags)?;
@@ -83,12 +88,12 @@ pub(super) fn apply_delta_phase(
         }
 
         if ndx <= NDX_FLIST_OFFSET {
-            handle_inc_recurse_segment(reader, entries, dest_root, &mut flist_segments)?;
+            handle_inc_recurse_segment(reader, entries, dest_root, dry_run, &mut flist_segments)?;
             result.file_count = entries.len() as u64;
             continue;
         }
 
-        process_file_ndx(
+        let transferred = process_file_ndx(
             reader,
             entries,
             dest_root,
@@ -97,7 +102,11 @@ pub(super) fn apply_delta_phase(
             ndx,
             proto,
             verbosity,
+            dry_run,
         )?;
+        if transferred {
+            result.files_transferred += 1;
+        }
     }
 
     Ok(())
@@ -224,6 +233,11 @@ fn consume_del_stats(reader: &mut BatchReader) -> BatchResult<()> {
 
 /// Process a single per-file NDX entry: read iflags, sum_head, delta tokens,
 /// transfer checksum, and commit the file via [`apply_file_delta`].
+///
+/// The rebuilt content is checked against the recorded whole-file checksum
+/// with [`verify_file_digest`] before anything is written. Under `dry_run` the
+/// decoded deltas are also checked with [`check_file_delta`] and never
+/// committed. Returns whether a regular file was (or would be) rewritten.
 #[allow(clippy::too_many_arguments)]
 fn process_file_ndx(
     reader: &mut BatchReader,
@@ -234,7 +248,8 @@ fn process_file_ndx(
     ndx: i32,
     proto: i32,
     verbosity: i32,
-) -> BatchResult<()> {
+    dry_run: bool,
+) -> BatchResult<bool> {
     let stream = reader
         .inner_reader()
         .ok_or_else(|| BatchError::Io(std::io::Error::other("batch file not open")))?;
@@ -242,14 +257,14 @@ fn process_file_ndx(
 
     if iflags & ITEM_TRANSFER == 0 {
         // Metadata-only change, no delta data follows.
-        return Ok(());
+        return Ok(false);
     }
 
     // upstream: rsync.c:flist_for_ndx() + receiver.c:700 - map global NDX
     // to the flat entries Vec index by finding the segment it belongs to.
     let flat_index = match lookup_flat_index(ndx, flist_segments, entries.len())? {
         Some(idx) => idx,
-        None => return Ok(()), // INC_RECURSE parent-dir metadata update; skip.
+        None => return Ok(false), // INC_RECURSE parent-dir metadata update; skip.
     };
 
     let entry_name = entries[flat_index].name().to_owned();
@@ -299,13 +314,13 @@ fn process_file_ndx(
         remainder,
     )?;
 
-    {
+    let file_sum = {
         let xfer_sum_len = default_xfer_sum_len(proto);
         let stream = reader
             .inner_reader()
             .ok_or_else(|| BatchError::Io(std::io::Error::other("batch file not open")))?;
-        read_and_discard_file_checksum(stream, xfer_sum_len)?;
-    }
+        read_file_checksum(stream, xfer_sum_len)?
+    };
 
     if verbosity > 0 {
         println!("  {} delta operations", delta_ops.len());
@@ -314,7 +329,30 @@ fn process_file_ndx(
     // The per-file stream is now fully drained. Only materialise regular
     // files; directories and symlinks were created in the flist phase and must
     // not be overwritten with a delta-reconstructed file.
-    if is_regular {
+    if !is_regular {
+        return Ok(false);
+    }
+    if dry_run {
+        check_file_delta(
+            &dest_path,
+            basis_exists,
+            &delta_ops,
+            block_length,
+            block_count as u32,
+            remainder,
+        )?;
+    }
+    verify_file_digest(
+        &dest_path,
+        basis_exists,
+        &delta_ops,
+        block_length,
+        block_count as u32,
+        remainder,
+        FileDigest::new(proto, reader.config().checksum_seed),
+        &file_sum,
+    )?;
+    if !dry_run {
         apply_file_delta(
             &dest_path,
             basis_exists,
@@ -322,10 +360,9 @@ fn process_file_ndx(
             block_length,
             block_count as u32,
             remainder,
-        )
-    } else {
-        Ok(())
+        )?;
     }
+    Ok(true)
 }
 
 /// Reads delta tokens for one file, dispatching by compression codec.
@@ -399,6 +436,7 @@ fn handle_inc_recurse_segment(
     reader: &mut BatchReader,
     entries: &mut Vec<protocol::flist::FileEntry>,
     dest_root: &Path,
+    dry_run: bool,
     flist_segments: &mut Vec<(i32, usize, usize)>,
 ) -> BatchResult<()> {
     let prev_len = entries.len();
@@ -416,6 +454,10 @@ fn handle_inc_recurse_segment(
     let seg_count = entries.len() - prev_len;
     flist_segments.push((seg_ndx_start, prev_len, seg_count));
 
+    if dry_run {
+        return Ok(());
+    }
+
     // Create directories and symlinks for newly discovered entries.
     for entry in &entries[prev_len..] {
         let dest_path = dest_root.join(entry.name());
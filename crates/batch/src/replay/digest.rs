@@ -0,0 +1,54 @@
+//! Whole-file checksum verification for batch replay.
+//!
+//! Every transferred file in the batch body ends with the sender's whole-file
+//! checksum. The batch does not record which checksum algorithm the recording
+//! session negotiated, only that the digest is 16 bytes long, so the replayed
+//! content is hashed with each algorithm that produces a 16-byte whole-file
+//! digest for the batch's protocol and accepted if any of them matches.
+//!
+//! upstream: receiver.c:receive_data() - `sum_end()` over the reconstructed
+//! file is compared against the `sender_file_sum` read after the tokens; a
+//! mismatch fails verification.
+
+use checksums::strong::{Md4, Md5, StrongDigest, Xxh3_128};
+
+/// Running whole-file digests for one replayed file.
+pub(super) struct FileDigest {
+    md4: Md4,
+    md5: Md5,
+    xxh128: Xxh3_128,
+}
+
+impl FileDigest {
+    /// Starts the candidate digests for a file in a batch recorded at
+    /// `protocol_version` with `checksum_seed`.
+    ///
+    /// upstream: checksum.c:600-611 `sum_init()` - only the protocol < 30
+    /// MD4 forms prepend the seed; MD5, XXH3-128 and the negotiated MD4 are
+    /// unseeded.
+    pub(super) fn new(protocol_version: i32, checksum_seed: i32) -> Self {
+        let mut md4 = Md4::new();
+        if protocol_version < 30 {
+            md4.update(&checksum_seed.to_le_bytes());
+        }
+        Self {
+            md4,
+            md5: Md5::new(),
+            xxh128: Xxh3_128::with_seed(0),
+        }
+    }
+
+    /// Feeds reconstructed file bytes into every candidate digest.
+    pub(super) fn update(&mut self, data: &[u8]) {
+        self.md4.update(data);
+        self.md5.update(data);
+        self.xxh128.update(data);
+    }
+
+    /// Returns whether any candidate digest equals the recorded `expected`.
+    pub(super) fn matches(self, expected: &[u8]) -> bool {
+        self.md5.finalize().as_ref() == expected
+            || self.xxh128.finalize().as_ref() == expected
+            || self.md4.finalize().as_ref() == expected
+    }
+}
@@ -10,16 +10,22 @@
 //!   with dictionary synchronization via `see_token()`.
 //! - [`apply_file_delta`] applies a decoded delta sequence to a destination
 //!   path, using a temp file + rename for the basis-present path.
+//! - [`check_file_delta`] validates a decoded delta sequence against the
+//!   destination basis without writing (dry-run replay).
+//! - [`verify_file_digest`] compares the content a delta sequence rebuilds
+//!   with the whole-file checksum recorded in the batch.
 
 use std::fs::{self, File};
-use std::io::{BufReader, Read};
+use std::io::{BufReader, Read, Seek, SeekFrom};
 use std::path::Path;
 
 use protocol::wire::{CompressedToken, CompressedTokenDecoder};
 
 use crate::error::{BatchError, BatchResult};
+use crate::reader::BatchStream;
 
 use super::delta::{apply_delta_ops, write_literals_to_file};
+use super::digest::FileDigest;
 
 /// ITEM_BASIS_TYPE_FOLLOWS - 1-byte fnamecmp_type follows iflags.
 /// upstream: rsync.c:403-418
@@ -41,10 +47,7 @@ pub(super) const ITEM_TRANSFER: u16 = 0x8000;
 /// upstream: rsync.c:403-418 - consume optional trailing fields after iflags.
 /// `ITEM_BASIS_TYPE_FOLLOWS` (0x0800): 1 byte fnamecmp_type.
 /// `ITEM_XNAME_FOLLOWS` (0x1000): vstring (1-2 byte length + data).
-pub(super) fn read_iflags_and_skip_meta(
-    stream: &mut BufReader<File>,
-    proto: i32,
-) -> BatchResult<u16> {
+pub(super) fn read_iflags_and_skip_meta(stream: &mut BatchStream, proto: i32) -> BatchResult<u16> {
     let iflags = if proto >= 29 {
         let mut buf = [0u8; 2];
         stream.read_exact(&mut buf).map_err(|e| {
@@ -108,7 +111,7 @@ pub(super) fn read_iflags_and_skip_meta(
 /// upstream: receiver.c:338 - `read_sum_head()` reads 4 x i32.
 /// The `s2length` field is read and discarded - oc-rsync derives the strong
 /// checksum length from the negotiated checksum algorithm, not from the wire.
-pub(super) fn read_sum_head(stream: &mut BufReader<File>) -> BatchResult<(i32, i32, i32)> {
+pub(super) fn read_sum_head(stream: &mut BatchStream) -> BatchResult<(i32, i32, i32)> {
     let mut sum_buf = [0u8; 16];
     stream.read_exact(&mut sum_buf).map_err(|e| {
         BatchError::Io(std::io::Error::new(
@@ -123,17 +126,17 @@ pub(super) fn read_sum_head(stream: &mut BufReader<File>) -> BatchResult<(i32, i
     Ok((block_count, block_length_wire, remainder_wire))
 }
 
-/// Read the per-file transfer checksum and discard it.
+/// Read the per-file transfer checksum recorded after the delta tokens.
 ///
 /// upstream: receiver.c:515 - `read_buf(f_in, sender_file_sum, xfer_sum_len)`.
 /// The sender ALWAYS writes `xfer_sum_len` bytes of file checksum after the
 /// delta stream, regardless of `sum_head.s2length`. For protocol 32 the
 /// default xfer checksum is XXH3-128 or MD5 - both 16 bytes. For protocol
 /// 28-31 it is MD4 or MD5 - also 16 bytes.
-pub(super) fn read_and_discard_file_checksum(
-    stream: &mut BufReader<File>,
+pub(super) fn read_file_checksum(
+    stream: &mut BatchStream,
     xfer_sum_len: usize,
-) -> BatchResult<()> {
+) -> BatchResult<Vec<u8>> {
     let mut checksum_buf = vec![0u8; xfer_sum_len];
     stream.read_exact(&mut checksum_buf).map_err(|e| {
         BatchError::Io(std::io::Error::new(
@@ -141,7 +144,7 @@ pub(super) fn read_and_discard_file_checksum(
             format!("failed to read file checksum ({xfer_sum_len} bytes): {e}"),
         ))
     })?;
-    Ok(())
+    Ok(checksum_buf)
 }
 
 /// CPRES_ZLIB streaming read with dictionary synchronization.
@@ -153,7 +156,7 @@ pub(super) fn read_and_discard_file_checksum(
 /// upstream: receiver.c:receive_data() + token.c:see_deflate_token()
 pub(super) fn read_compressed_deltas_streaming(
     decoder: &mut CompressedTokenDecoder,
-    stream: &mut BufReader<File>,
+    stream: &mut BatchStream,
     basis_data: &[u8],
     entry_name: &str,
     block_length: usize,
@@ -241,3 +244,160 @@ pub(super) fn apply_file_delta(
     })?;
     Ok(())
 }
+
+/// Check a decoded delta sequence against `dest_path` without writing it.
+///
+/// Used by dry-run replay. Every block match must name a block within the
+/// recorded `sum_head` and be backed by a basis file at the destination long
+/// enough to supply it; otherwise the destination is not the tree the batch
+/// was recorded against and a real replay would produce a corrupt file.
+pub(super) fn check_file_delta(
+    dest_path: &Path,
+    basis_exists: bool,
+    delta_ops: &[protocol::wire::DeltaOp],
+    block_length: usize,
+    block_count: u32,
+    remainder: usize,
+) -> BatchResult<()> {
+    let mut basis_len = None;
+    for op in delta_ops {
+        let protocol::wire::DeltaOp::Copy {
+            block_index,
+            length,
+        } = *op
+        else {
+            continue;
+        };
+
+        if !basis_exists {
+            return Err(BatchError::InvalidFormat(format!(
+                "'{}' copies basis block {block_index} but has no basis file",
+                dest_path.display()
+            )));
+        }
+        if block_index >= block_count {
+            return Err(BatchError::InvalidFormat(format!(
+                "'{}' copies basis block {block_index} beyond the {block_count} recorded blocks",
+                dest_path.display()
+            )));
+        }
+
+        // Same block sizing as `apply_delta_ops`: the last block is `remainder`.
+        let len = if length > 0 {
+            u64::from(length)
+        } else if block_index == block_count - 1 {
+            remainder as u64
+        } else {
+            block_length as u64
+        };
+        let needed = u64::from(block_index) * block_length as u64 + len;
+        let actual = match basis_len {
+            Some(actual) => actual,
+            None => {
+                let actual = fs::metadata(dest_path)
+                    .map_err(|e| {
+                        BatchError::Io(std::io::Error::new(
+                            e.kind(),
+                            format!("failed to stat basis file '{}': {e}", dest_path.display()),
+                        ))
+                    })?
+                    .len();
+                basis_len = Some(actual);
+                actual
+            }
+        };
+        if needed > actual {
+            return Err(BatchError::InvalidFormat(format!(
+                "basis file '{}' is {actual} bytes but the batch needs {needed}",
+                dest_path.display()
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Check the content a decoded delta sequence rebuilds against the file's
+/// recorded whole-file checksum, reading block matches from the basis at
+/// `dest_path`.
+///
+/// Runs before anything is written, so a batch that does not reproduce the
+/// recorded file never replaces the destination.
+///
+/// upstream: receiver.c:970-985 - a `sum_end()` that differs from
+/// `sender_file_sum` fails verification.
+pub(super) fn verify_file_digest(
+    dest_path: &Path,
+    basis_exists: bool,
+    delta_ops: &[protocol::wire::DeltaOp],
+    block_length: usize,
+    block_count: u32,
+    remainder: usize,
+    mut digest: FileDigest,
+    expected: &[u8],
+) -> BatchResult<()> {
+    let copies = delta_ops
+        .iter()
+        .any(|op| matches!(op, protocol::wire::DeltaOp::Copy { .. }));
+    let mut basis = if basis_exists && copies {
+        let file = File::open(dest_path).map_err(|e| {
+            BatchError::Io(std::io::Error::new(
+                e.kind(),
+                format!("failed to open basis file '{}': {e}", dest_path.display()),
+            ))
+        })?;
+        Some(BufReader::new(file))
+    } else {
+        None
+    };
+    let mut buffer = vec![0u8; 8192];
+    for op in delta_ops {
+        match op {
+            protocol::wire::DeltaOp::Literal(data) => digest.update(data),
+            protocol::wire::DeltaOp::Copy {
+                block_index,
+                length,
+            } => {
+                let Some(basis) = basis.as_mut() else {
+                    return Err(BatchError::InvalidFormat(format!(
+                        "'{}' copies basis block {block_index} but has no basis file",
+                        dest_path.display()
+                    )));
+                };
+
+                // Same block sizing as `apply_delta_ops`: the last block is `remainder`.
+                let mut remaining = if *length > 0 {
+                    *length as usize
+                } else if block_count > 0 && *block_index == block_count - 1 {
+                    remainder
+                } else {
+                    block_length
+                };
+                basis
+                    .seek(SeekFrom::Start(
+                        u64::from(*block_index) * block_length as u64,
+                    ))
+                    .map_err(BatchError::Io)?;
+                while remaining > 0 {
+                    let chunk = remaining.min(buffer.len());
+                    basis.read_exact(&mut buffer[..chunk]).map_err(|e| {
+                        BatchError::Io(std::io::Error::new(
+                            e.kind(),
+                            format!("failed to read basis file '{}': {e}", dest_path.display()),
+                        ))
+                    })?;
+                    digest.update(&buffer[..chunk]);
+                    remaining -= chunk;
+                }
+            }
+        }
+    }
+
+    if digest.matches(expected) {
+        Ok(())
+    } else {
+        Err(BatchError::VerificationFailed(format!(
+            "'{}' failed verification: rebuilt content does not match the batch checksum",
+            dest_path.display()
+        )))
+    }
+}
@@ -20,6 +20,12 @@
 //!    entries, with directories updated last so earlier file writes do not
 //!    disturb their directory timestamps.
 //!
+//! Every replayed file is checked against the whole-file checksum recorded
+//! after its delta before it is written. Under `--dry-run`
+//! ([`BatchConfig::dry_run`]) every phase still decodes the batch, checks block
+//! matches against the destination basis files and verifies the checksums,
+//! but nothing is created, written, or modified.
+//!
 //! # Submodules
 //!
 //! - `codec` - compression codec detection (`zlib` vs `zstd`) and decoder
@@ -29,6 +35,7 @@
 //! - `dispatch` - per-file helpers used by the main loop: iflags decoding,
 //!   sum-head reading, compressed-token streaming, temp-file commit.
 //! - `delta_phase` - the NDX-stream loop that drives per-file delta application.
+//! - `digest` - whole-file checksum verification of replayed content.
 //! - `fs_ops` - symlink creation and metadata application primitives.
 //!
 //! # Upstream Reference
@@ -40,6 +47,7 @@
 mod codec;
 mod delta;
 mod delta_phase;
+mod digest;
 mod dispatch;
 mod fs_ops;
 
@@ -78,6 +86,8 @@ pub struct ReplayResult {
     pub dirs_created: u64,
     /// Number of symlinks created during replay.
     pub symlinks_created: u64,
+    /// Number of regular files whose content was rewritten from the batch.
+    pub files_transferred: u64,
 }
 
 /// Replay a batch file, applying recorded delta operations to a destination.
@@ -112,7 +122,19 @@ pub fn replay(
     verbosity: i32,
 ) -> BatchResult<ReplayResult> {
     let mut reader = BatchReader::new((*batch_cfg).clone())?;
+    replay_from_reader(&mut reader, dest_root, verbosity)
+}
 
+/// Replay the batch behind a freshly opened `reader`.
+///
+/// Shared by [`replay`] and [`BatchReader::verify`]; the reader's
+/// [`BatchConfig::dry_run`] decides whether the destination is touched.
+pub(crate) fn replay_from_reader(
+    reader: &mut BatchReader,
+    dest_root: &Path,
+    verbosity: i32,
+) -> BatchResult<ReplayResult> {
+    let dry_run = reader.config().dry_run;
     let flags = reader.read_header()?;
 
     // upstream: batch.c:120 check_batch_flags() - reconcile the active options
@@ -121,7 +143,7 @@ pub fn replay(
     // an --iconv mismatch is fatal.
     for message in crate::format::check_batch_flags(
         flags,
-        batch_cfg.active_flags,
+        reader.config().active_flags,
         reader.config().protocol_version,
     )? {
         if verbosity >= 1 {
@@ -150,26 +172,29 @@ pub fn replay(
     // joined to a missing dest_root expands to `dest_root/.`, which
     // `fs::create_dir_all` cannot materialise because the parent does not
     // exist; creating dest_root first sidesteps that path-component edge.
-    ensure_dest_root(dest_root, &entries, &mut result)?;
+    ensure_dest_root(dest_root, &entries, dry_run, &mut result)?;
 
     // Phase 1: Create directories and symlinks, ensure parent dirs for regular files.
-    prepare_directories_and_symlinks(&entries, dest_root, verbosity, &mut result)?;
+    prepare_directories_and_symlinks(&entries, dest_root, verbosity, dry_run, &mut result)?;
 
     // Phase 2: Apply delta operations for regular files.
     apply_delta_phase(
-        &mut reader,
+        reader,
         &mut entries,
         dest_root,
         &flags,
         &mut result,
         verbosity,
+        dry_run,
     )?;
 
     // Phase 3: Apply metadata. Directories are done last because setting
     // timestamps on a directory before writing its contents would cause the
     // mtime to be updated by the file writes. Regular files and symlinks get
     // metadata immediately.
-    apply_all_metadata(&entries, dest_root, &flags, verbosity);
+    if !dry_run {
+        apply_all_metadata(&entries, dest_root, &flags, verbosity);
+    }
 
     Ok(result)
 }
@@ -177,10 +202,12 @@ pub fn replay(
 /// Phase 1: Create directories and symlinks, ensure parent dirs for regular files.
 ///
 /// Directories must be created before files so that parent paths exist.
+/// Under `dry_run` the missing directories and symlinks are only counted.
 fn prepare_directories_and_symlinks(
     entries: &[protocol::flist::FileEntry],
     dest_root: &Path,
     verbosity: i32,
+    dry_run: bool,
     result: &mut ReplayResult,
 ) -> BatchResult<()> {
     for entry in entries {
@@ -191,6 +218,19 @@ fn prepare_directories_and_symlinks(
             println!("{}", entry.name());
         }
 
+        if dry_run {
+            match entry.file_type() {
+                protocol::flist::FileType::Directory if !dest_path.exists() => {
+                    result.dirs_created += 1;
+                }
+                protocol::flist::FileType::Symlink if entry.link_target().is_some() => {
+                    result.symlinks_created += 1;
+                }
+                _ => {}
+            }
+            continue;
+        }
+
         match entry.file_type() {
             protocol::flist::FileType::Directory => {
                 if !dest_path.exists() {
@@ -232,6 +272,7 @@ fn prepare_directories_and_symlinks(
 fn ensure_dest_root(
     dest_root: &Path,
     entries: &[protocol::flist::FileEntry],
+    dry_run: bool,
     result: &mut ReplayResult,
 ) -> BatchResult<()> {
     if dest_root.as_os_str().is_empty() || dest_root.exists() {
@@ -244,6 +285,10 @@ fn ensure_dest_root(
     if !needs_dir {
         return Ok(());
     }
+    if dry_run {
+        result.dirs_created += 1;
+        return Ok(());
+    }
     fs::create_dir_all(dest_root).map_err(|e| {
         BatchError::Io(std::io::Error::new(
             e.kind(),
@@ -10,6 +10,7 @@ use tempfile::TempDir;
 
 use super::codec::{CompressionCodec, create_compressed_decoder};
 use super::delta::{apply_delta_ops, choose_block_length, write_literals_to_file};
+use super::dispatch::check_file_delta;
 
 #[test]
 fn choose_block_length_small_file() {
@@ -199,3 +200,42 @@ fn cpres_zlib_false_for_zstd_codec() {
     let codec = CompressionCodec::Zstd;
     assert!(Some(codec) != Some(CompressionCodec::Zlib));
 }
+
+#[test]
+fn check_file_delta_accepts_literal_only_without_basis() {
+    let temp = TempDir::new().unwrap();
+    let dest_path = temp.path().join("new.txt");
+
+    let ops = vec![protocol::wire::DeltaOp::Literal(b"fresh".to_vec())];
+    check_file_delta(&dest_path, false, &ops, 700, 0, 700).unwrap();
+    assert!(!dest_path.exists());
+}
+
+#[test]
+fn check_file_delta_rejects_block_beyond_sum_head() {
+    let temp = TempDir::new().unwrap();
+    let dest_path = temp.path().join("basis.txt");
+    fs::write(&dest_path, b"0123456789").unwrap();
+
+    let ops = vec![protocol::wire::DeltaOp::Copy {
+        block_index: 2,
+        length: 0,
+    }];
+    let err = check_file_delta(&dest_path, true, &ops, 5, 2, 5).unwrap_err();
+    assert!(err.to_string().contains("beyond the 2 recorded blocks"));
+}
+
+#[test]
+fn check_file_delta_uses_remainder_for_last_block() {
+    let temp = TempDir::new().unwrap();
+    let dest_path = temp.path().join("basis.txt");
+    fs::write(&dest_path, b"0123456").unwrap();
+
+    let ops = vec![protocol::wire::DeltaOp::Copy {
+        block_index: 1,
+        length: 0,
+    }];
+    check_file_delta(&dest_path, true, &ops, 5, 2, 2).unwrap();
+    let err = check_file_delta(&dest_path, true, &ops, 5, 2, 3).unwrap_err();
+    assert!(err.to_string().contains("needs 8"));
+}
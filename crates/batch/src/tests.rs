@@ -6,9 +6,16 @@ mod integration {
     use crate::reader::BatchReader;
     use crate::writer::BatchWriter;
     use crate::{BatchConfig, BatchMode};
+    use checksums::strong::{Md5, StrongDigest};
     use std::fs;
     use tempfile::TempDir;
 
+    /// Whole-file checksum the sender records after each file's delta: plain
+    /// MD5 of the file content for protocol 30 and newer.
+    fn file_sum(content: &[u8]) -> [u8; 16] {
+        Md5::digest(content)
+    }
+
     #[test]
     #[allow(clippy::field_reassign_with_default)]
     fn test_batch_roundtrip() {
@@ -861,7 +868,7 @@ mod integration {
 
             // File-level checksum (16 bytes) - upstream always writes this after delta stream
             // upstream: receiver.c - sender writes xfer_sum_len bytes of file checksum
-            writer.write_data(&[0u8; 16]).unwrap();
+            writer.write_data(&file_sum(b"Hello, batch!")).unwrap();
 
             // NDX_DONE for phase 1 -> phase 2 transition
             ndx_buf.clear();
@@ -1070,7 +1077,11 @@ mod integration {
 
             encoder.finish(&mut token_buf).unwrap();
             writer.write_data(&token_buf).unwrap();
-            writer.write_data(&[0u8; 16]).unwrap();
+            writer
+                .write_data(&file_sum(
+                    &[&basis_data[..1400], &patch[..], &basis_data[1400..]].concat(),
+                ))
+                .unwrap();
 
             // NDX_DONE for phase 1 -> phase 2
             ndx_buf.clear();
@@ -1178,7 +1189,7 @@ mod integration {
             encoder.send_literal(&mut token_buf, file_data).unwrap();
             encoder.finish(&mut token_buf).unwrap();
             writer.write_data(&token_buf).unwrap();
-            writer.write_data(&[0u8; 16]).unwrap();
+            writer.write_data(&file_sum(file_data)).unwrap();
 
             // NDX_DONE for phase 1 -> phase 2 transition
             ndx_buf.clear();
@@ -1287,7 +1298,7 @@ mod integration {
             encoder.send_literal(&mut token_buf, file_data).unwrap();
             encoder.finish(&mut token_buf).unwrap();
             writer.write_data(&token_buf).unwrap();
-            writer.write_data(&[0u8; 16]).unwrap();
+            writer.write_data(&file_sum(file_data)).unwrap();
 
             // NDX_DONE for phase 1 -> phase 2
             ndx_buf.clear();
@@ -1415,7 +1426,11 @@ mod integration {
 
             encoder.finish(&mut token_buf).unwrap();
             writer.write_data(&token_buf).unwrap();
-            writer.write_data(&[0u8; 16]).unwrap();
+            writer
+                .write_data(&file_sum(
+                    &[&basis_data[..1400], &patch[..], &basis_data[1400..]].concat(),
+                ))
+                .unwrap();
 
             // NDX_DONE phase 1 -> phase 2
             ndx_buf.clear();
@@ -1533,7 +1548,7 @@ mod integration {
             encoder.send_literal(&mut token_buf, file1_data).unwrap();
             encoder.finish(&mut token_buf).unwrap();
             writer.write_data(&token_buf).unwrap();
-            writer.write_data(&[0u8; 16]).unwrap(); // file checksum
+            writer.write_data(&file_sum(file1_data)).unwrap(); // file checksum
 
             // File 2: NDX=2, compressed literal
             ndx_buf.clear();
@@ -1551,7 +1566,7 @@ mod integration {
             encoder2.send_literal(&mut token_buf, file2_data).unwrap();
             encoder2.finish(&mut token_buf).unwrap();
             writer.write_data(&token_buf).unwrap();
-            writer.write_data(&[0u8; 16]).unwrap(); // file checksum
+            writer.write_data(&file_sum(file2_data)).unwrap(); // file checksum
 
             // NDX_DONE phase 1 -> phase 2
             ndx_buf.clear();
@@ -1697,7 +1712,11 @@ mod integration {
 
             enc1.finish(&mut token_buf).unwrap();
             writer.write_data(&token_buf).unwrap();
-            writer.write_data(&[0u8; 16]).unwrap();
+            writer
+                .write_data(&file_sum(
+                    &[&basis1[..700], &patch1[..], &basis1[700..]].concat(),
+                ))
+                .unwrap();
 
             // --- File 2: copy block0 + copy block1 + literal + copy block2 ---
             ndx_buf.clear();
@@ -1726,7 +1745,11 @@ mod integration {
 
             enc2.finish(&mut token_buf).unwrap();
             writer.write_data(&token_buf).unwrap();
-            writer.write_data(&[0u8; 16]).unwrap();
+            writer
+                .write_data(&file_sum(
+                    &[&basis2[..1400], &patch2[..], &basis2[1400..]].concat(),
+                ))
+                .unwrap();
 
             // NDX_DONE phase 1 -> phase 2
             ndx_buf.clear();
@@ -1876,7 +1899,11 @@ mod integration {
 
             enc.finish(&mut token_buf).unwrap();
             writer.write_data(&token_buf).unwrap();
-            writer.write_data(&[0u8; 16]).unwrap();
+            writer
+                .write_data(&file_sum(
+                    &[&basis_data[..1400], &patch_data[..], &basis_data[1400..]].concat(),
+                ))
+                .unwrap();
 
             // --- new.txt: whole-file literal (no basis, uses eager path) ---
             ndx_buf.clear();
@@ -1894,7 +1921,7 @@ mod integration {
             enc2.send_literal(&mut token_buf, new_file_data).unwrap();
             enc2.finish(&mut token_buf).unwrap();
             writer.write_data(&token_buf).unwrap();
-            writer.write_data(&[0u8; 16]).unwrap();
+            writer.write_data(&file_sum(new_file_data)).unwrap();
 
             // NDX_DONE phase 1 -> phase 2
             ndx_buf.clear();
@@ -2027,7 +2054,7 @@ mod integration {
             encoder.send_literal(&mut token_buf, file_data).unwrap();
             encoder.finish(&mut token_buf).unwrap();
             writer.write_data(&token_buf).unwrap();
-            writer.write_data(&[0u8; 16]).unwrap();
+            writer.write_data(&file_sum(file_data)).unwrap();
 
             // NDX_DONE phase 1 -> phase 2
             ndx_buf.clear();
@@ -2145,7 +2172,7 @@ mod integration {
             protocol::wire::delta::write_token_end(&mut delta_buf).unwrap();
             writer.write_data(&delta_buf).unwrap();
 
-            writer.write_data(&[0u8; 16]).unwrap();
+            writer.write_data(&file_sum(b"data")).unwrap();
 
             ndx_buf.clear();
             ndx_codec.write_ndx_done(&mut ndx_buf).unwrap();
@@ -2189,4 +2216,263 @@ mod integration {
              the link"
         );
     }
+
+    /// Writes a batch with one directory and one regular file `subdir/a.txt`
+    /// whose delta copies basis block 0 (4 bytes) and appends a literal.
+    fn write_block_match_batch(batch_path: &std::path::Path, protocol_version: i32) {
+        use protocol::codec::{NdxCodec, NdxCodecEnum};
+        use protocol::flist::{FileEntry, FileListWriter};
+
+        let write_config = BatchConfig::new(
+            BatchMode::Write,
+            batch_path.to_string_lossy().to_string(),
+            protocol_version,
+        );
+        let mut writer = BatchWriter::new(write_config).unwrap();
+        let flags = BatchFlags {
+            recurse: true,
+            ..Default::default()
+        };
+        writer.write_header(flags).unwrap();
+
+        let protocol = protocol::ProtocolVersion::try_from(protocol_version as u8).unwrap();
+        let mut flist_writer = FileListWriter::new(protocol);
+        let mut buf = Vec::new();
+        flist_writer
+            .write_entry(&mut buf, &FileEntry::new_directory("subdir".into(), 0o755))
+            .unwrap();
+        flist_writer
+            .write_entry(
+                &mut buf,
+                &FileEntry::new_file("subdir/a.txt".into(), 7, 0o644),
+            )
+            .unwrap();
+        flist_writer.write_end(&mut buf, None).unwrap();
+        writer.write_data(&buf).unwrap();
+
+        let mut ndx_codec = NdxCodecEnum::new(protocol_version as u8);
+        let mut ndx_buf = Vec::new();
+        ndx_codec.write_ndx(&mut ndx_buf, 1).unwrap();
+        writer.write_data(&ndx_buf).unwrap();
+        writer.write_data(&0x8000u16.to_le_bytes()).unwrap();
+
+        // sum_head: one 4-byte block, remainder 4.
+        for value in [1i32, 4, 16, 4] {
+            writer.write_data(&value.to_le_bytes()).unwrap();
+        }
+
+        let mut delta_buf = Vec::new();
+        protocol::wire::delta::write_token_block_match(&mut delta_buf, 0).unwrap();
+        protocol::wire::delta::write_token_literal(&mut delta_buf, b"xyz").unwrap();
+        protocol::wire::delta::write_token_end(&mut delta_buf).unwrap();
+        writer.write_data(&delta_buf).unwrap();
+        writer.write_data(&file_sum(b"abcdxyz")).unwrap();
+
+        for _ in 0..2 {
+            ndx_buf.clear();
+            ndx_codec.write_ndx_done(&mut ndx_buf).unwrap();
+            writer.write_data(&ndx_buf).unwrap();
+        }
+        writer.finalize().unwrap();
+    }
+
+    /// `--read-batch --dry-run` must decode the whole batch but leave the
+    /// destination exactly as it was.
+    #[test]
+    fn test_replay_dry_run_leaves_destination_untouched() {
+        let temp_dir = TempDir::new().unwrap();
+        let batch_path = temp_dir.path().join("dry_run.batch");
+        let dest_dir = temp_dir.path().join("dest");
+        fs::create_dir_all(dest_dir.join("subdir")).unwrap();
+        fs::write(dest_dir.join("subdir/a.txt"), b"abcd").unwrap();
+        write_block_match_batch(&batch_path, 31);
+
+        let read_config = BatchConfig::new(
+            BatchMode::Read,
+            batch_path.to_string_lossy().to_string(),
+            31,
+        )
+        .with_dry_run(true);
+        let result = crate::replay::replay(&read_config, &dest_dir, 0).unwrap();
+
+        assert_eq!(result.file_count, 2);
+        assert_eq!(result.files_transferred, 1);
+        assert_eq!(fs::read(dest_dir.join("subdir/a.txt")).unwrap(), b"abcd");
+
+        // The same batch applied for real produces the recorded content.
+        let read_config = BatchConfig::new(
+            BatchMode::Read,
+            batch_path.to_string_lossy().to_string(),
+            31,
+        );
+        crate::replay::replay(&read_config, &dest_dir, 0).unwrap();
+        assert_eq!(fs::read(dest_dir.join("subdir/a.txt")).unwrap(), b"abcdxyz");
+    }
+
+    /// Verifying against a missing destination fails on the unbacked block
+    /// match and never creates the destination.
+    #[test]
+    fn test_verify_into_missing_destination() {
+        let temp_dir = TempDir::new().unwrap();
+        let batch_path = temp_dir.path().join("dry_run.batch");
+        let dest_dir = temp_dir.path().join("missing");
+        write_block_match_batch(&batch_path, 31);
+
+        let reader = BatchReader::new(BatchConfig::new(
+            BatchMode::Read,
+            batch_path.to_string_lossy().to_string(),
+            31,
+        ))
+        .unwrap();
+        let err = reader.verify(&dest_dir).unwrap_err();
+
+        // The block match has no basis in the empty destination.
+        assert!(err.to_string().contains("no basis file"), "{err}");
+        assert!(!dest_dir.exists());
+    }
+
+    /// `verify()` rejects a destination whose basis is too short for the
+    /// recorded block matches.
+    #[test]
+    fn test_verify_rejects_mismatched_basis() {
+        let temp_dir = TempDir::new().unwrap();
+        let batch_path = temp_dir.path().join("verify.batch");
+        let dest_dir = temp_dir.path().join("dest");
+        fs::create_dir_all(dest_dir.join("subdir")).unwrap();
+        fs::write(dest_dir.join("subdir/a.txt"), b"ab").unwrap();
+        write_block_match_batch(&batch_path, 31);
+
+        let config = BatchConfig::new(
+            BatchMode::Read,
+            batch_path.to_string_lossy().to_string(),
+            31,
+        );
+        let err = BatchReader::new(config.clone())
+            .unwrap()
+            .verify(&dest_dir)
+            .unwrap_err();
+        assert!(err.to_string().contains("is 2 bytes"), "{err}");
+
+        fs::write(dest_dir.join("subdir/a.txt"), b"abcd").unwrap();
+        let result = BatchReader::new(config).unwrap().verify(&dest_dir).unwrap();
+        assert_eq!(result.files_transferred, 1);
+        assert_eq!(result.dirs_created, 0);
+        assert_eq!(fs::read(dest_dir.join("subdir/a.txt")).unwrap(), b"abcd");
+    }
+
+    /// A single corrupted literal byte still decodes, but the rebuilt file no
+    /// longer matches the recorded checksum: both `verify()` and a real replay
+    /// must fail verification and leave the destination untouched.
+    #[test]
+    fn test_corrupted_literal_fails_verification() {
+        let temp_dir = TempDir::new().unwrap();
+        let batch_path = temp_dir.path().join("corrupt.batch");
+        let dest_dir = temp_dir.path().join("dest");
+        fs::create_dir_all(dest_dir.join("subdir")).unwrap();
+        fs::write(dest_dir.join("subdir/a.txt"), b"abcd").unwrap();
+        write_block_match_batch(&batch_path, 31);
+
+        let mut bytes = fs::read(&batch_path).unwrap();
+        let at = bytes
+            .windows(3)
+            .position(|window| window == b"xyz")
+            .expect("literal present in batch");
+        bytes[at] = b'X';
+        fs::write(&batch_path, &bytes).unwrap();
+
+        let config = BatchConfig::new(
+            BatchMode::Read,
+            batch_path.to_string_lossy().to_string(),
+            31,
+        );
+        let err = BatchReader::new(config.clone())
+            .unwrap()
+            .verify(&dest_dir)
+            .unwrap_err();
+        assert!(
+            matches!(err, crate::BatchError::VerificationFailed(_)),
+            "{err}"
+        );
+
+        let err = crate::replay::replay(&config, &dest_dir, 0).unwrap_err();
+        assert!(
+            matches!(err, crate::BatchError::VerificationFailed(_)),
+            "{err}"
+        );
+        assert_eq!(fs::read(dest_dir.join("subdir/a.txt")).unwrap(), b"abcd");
+    }
+
+    /// A zstd-compressed batch is detected from its frame magic and replays
+    /// exactly like the plain batch it wraps.
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_compressed_batch_is_detected_on_read() {
+        use crate::BatchCompression;
+
+        let temp_dir = TempDir::new().unwrap();
+        let batch_path = temp_dir.path().join("compressed.batch");
+        let config = BatchConfig::new(
+            BatchMode::Write,
+            batch_path.to_string_lossy().to_string(),
+            31,
+        )
+        .with_checksum_seed(7)
+        .with_compression(BatchCompression::zstd());
+
+        let mut writer = BatchWriter::new(config).unwrap();
+        let flags = BatchFlags {
+            recurse: true,
+            ..Default::default()
+        };
+        writer.write_header(flags).unwrap();
+        writer.write_data(&[0x42; 4096]).unwrap();
+        writer.finalize().unwrap();
+
+        let on_disk = fs::read(&batch_path).unwrap();
+        assert!(crate::compression::is_zstd_magic(&on_disk));
+        assert!(on_disk.len() < 4096);
+
+        let read_config = BatchConfig::new(
+            BatchMode::Read,
+            batch_path.to_string_lossy().to_string(),
+            31,
+        );
+        let mut reader = BatchReader::new(read_config).unwrap();
+        assert!(reader.read_header().unwrap().recurse);
+        assert_eq!(reader.header().unwrap().checksum_seed, 7);
+        let mut data = vec![0u8; 4096];
+        reader.read_exact(&mut data).unwrap();
+        assert!(data.iter().all(|&b| b == 0x42));
+    }
+
+    /// Closing through a shared lock ends the compression frame, so the batch
+    /// is readable even while the writer itself is still alive.
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_compressed_batch_close_ends_frame() {
+        use crate::BatchCompression;
+
+        let temp_dir = TempDir::new().unwrap();
+        let batch_path = temp_dir.path().join("closed.batch");
+        let config = BatchConfig::new(
+            BatchMode::Write,
+            batch_path.to_string_lossy().to_string(),
+            31,
+        )
+        .with_compression(BatchCompression::zstd());
+
+        let mut writer = BatchWriter::new(config).unwrap();
+        writer.write_header(BatchFlags::default()).unwrap();
+        writer.close().unwrap();
+        assert!(writer.write_data(b"late").is_err());
+
+        let read_config = BatchConfig::new(
+            BatchMode::Read,
+            batch_path.to_string_lossy().to_string(),
+            31,
+        );
+        let mut reader = BatchReader::new(read_config).unwrap();
+        reader.read_header().unwrap();
+        drop(writer);
+    }
 }
@@ -1,6 +1,7 @@
 //! Batch file writer for recording transfers.
 
 use crate::BatchConfig;
+use crate::compression::BatchSink;
 use crate::error::{BatchError, BatchResult};
 use crate::format::{BatchFlags, BatchHeader, BatchStats, FileEntry};
 use std::fs::File;
@@ -14,8 +15,8 @@ use std::io::{self, BufWriter, Write};
 pub struct BatchWriter {
     /// Configuration for this batch operation.
     config: BatchConfig,
-    /// Writer for the binary batch file.
-    batch_file: Option<BufWriter<File>>,
+    /// Writer for the binary batch file, compressed when configured.
+    batch_file: Option<BufWriter<BatchSink>>,
     /// Whether the header has been written.
     header_written: bool,
     /// Stream flags recorded in the batch header.
@@ -40,10 +41,20 @@ impl BatchWriter {
                 ),
             ))
         })?;
+        let sink = BatchSink::new(file, config.compression).map_err(|e| {
+            BatchError::Io(io::Error::new(
+                e.kind(),
+                format!(
+                    "Failed to set up compression for batch file '{}': {}",
+                    batch_path.display(),
+                    e
+                ),
+            ))
+        })?;
 
         Ok(Self {
             config,
-            batch_file: Some(BufWriter::new(file)),
+            batch_file: Some(BufWriter::new(sink)),
             header_written: false,
             stream_flags: BatchFlags::default(),
         })
@@ -193,11 +204,28 @@ impl BatchWriter {
     /// This ensures all data is written and the file is properly closed.
     /// After calling this, the writer can no longer be used.
     pub fn finalize(mut self) -> BatchResult<()> {
-        self.flush()?;
-        if let Some(writer) = self.batch_file.take() {
-            drop(writer);
-        }
-        Ok(())
+        self.close()
+    }
+
+    /// Flush the batch file, end its compression frame, and close it.
+    ///
+    /// Unlike [`finalize`](Self::finalize) this does not consume the writer,
+    /// so it also works for writers held behind a lock. Later writes fail with
+    /// "Batch file not open"; closing twice is a no-op.
+    pub fn close(&mut self) -> BatchResult<()> {
+        let Some(writer) = self.batch_file.take() else {
+            return Ok(());
+        };
+        writer
+            .into_inner()
+            .map_err(io::IntoInnerError::into_error)
+            .and_then(BatchSink::finish)
+            .map_err(|e| {
+                BatchError::Io(io::Error::new(
+                    e.kind(),
+                    format!("Failed to close batch file: {e}"),
+                ))
+            })
     }
 
     /// Get a reference to the batch configuration.
@@ -217,7 +245,7 @@ impl BatchWriter {
 
 impl Drop for BatchWriter {
     fn drop(&mut self) {
-        let _ = self.flush();
+        let _ = self.close();
     }
 }
 
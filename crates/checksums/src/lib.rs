@@ -441,6 +441,23 @@ pub use strong::xxh3_simd_available;
 /// without depending on the module path.
 pub use cpu_features::{SimdLevel, set_simd_override, simd_override};
 
+/// Exact MD5 batch backend selection (set via the `--checksum-backend` CLI
+/// flag) and the `--debug=CHECKSUM` backend report.
+///
+/// Where `--simd` caps the dispatch ladder, [`force_md5_backend`] pins the
+/// MD5 dispatcher to a single backend and fails if the host cannot run it.
+pub use simd_batch::{
+    Backend as Md5Backend, ForceBackendError, force_backend as force_md5_backend,
+    log_backend_selection,
+};
+
+/// Incremental MD5 hashers backed by the SIMD batch engine.
+///
+/// [`Md5BatchContext`] hashes several files chunk by chunk, folding whole
+/// blocks of every lane through the active SIMD backend together;
+/// [`Md5Context`] is the single-stream form.
+pub use simd_batch::{Md5BatchContext, Md5Context};
+
 /// Cross-validate every SIMD checksum implementation against scalar references.
 ///
 /// See [`simd_self_test`] for full coverage details. Re-exported so callers
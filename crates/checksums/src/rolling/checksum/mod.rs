@@ -446,6 +446,75 @@ impl RollingChecksum {
         Ok(())
     }
 
+    /// Computes the checksum value at each of several successive rolls
+    /// without changing the current state.
+    ///
+    /// `values[k]` receives the packed [`value`](Self::value) the checksum
+    /// would hold after rolling `outgoing[..=k]` out of the window and
+    /// `incoming[..=k]` into it. This lets the sender's hash search compute a
+    /// run of window offsets at once and probe them together. On x86 the
+    /// SSE2 path computes eight offsets per iteration; other targets and the
+    /// remainder use the scalar recurrence of [`roll`](Self::roll).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use checksums::RollingChecksum;
+    ///
+    /// let data = b"ABCDEFGH";
+    /// let mut rolling = RollingChecksum::new();
+    /// rolling.update(&data[0..4]);
+    ///
+    /// let mut values = [0u32; 4];
+    /// rolling.rolled_values(&data[0..4], &data[4..8], &mut values).unwrap();
+    ///
+    /// let mut fresh = RollingChecksum::new();
+    /// fresh.update(&data[2..6]); // "CDEF", two rolls in
+    /// assert_eq!(values[1], fresh.value());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - [`RollingError::MismatchedSliceLength`] if the input slices differ in length.
+    /// - [`RollingError::EmptyWindow`] if no bytes have been processed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` is shorter than `outgoing`.
+    pub fn rolled_values(
+        &self,
+        outgoing: &[u8],
+        incoming: &[u8],
+        values: &mut [u32],
+    ) -> Result<(), RollingError> {
+        if outgoing.len() != incoming.len() {
+            return Err(RollingError::MismatchedSliceLength {
+                outgoing: outgoing.len(),
+                incoming: incoming.len(),
+            });
+        }
+
+        let window_len = self.window_len_u32()?;
+        let values = &mut values[..outgoing.len()];
+
+        let (mut s1, mut s2, done) =
+            rolled_values_arch(self.s1, self.s2, window_len, outgoing, incoming, values)
+                .unwrap_or((self.s1, self.s2, 0));
+
+        let tail = outgoing[done..].iter().zip(&incoming[done..]);
+        for ((&out, &inn), value) in tail.zip(&mut values[done..]) {
+            // upstream: checksum.c - schar interpretation sign-extends bytes
+            let out = sign_extend_byte(out);
+            s1 = s1.wrapping_sub(out).wrapping_add(sign_extend_byte(inn)) & 0xffff;
+            s2 = s2
+                .wrapping_sub(window_len.wrapping_mul(out))
+                .wrapping_add(s1)
+                & 0xffff;
+            *value = (s2 << 16) | s1;
+        }
+        Ok(())
+    }
+
     /// Returns the rolling checksum value in rsync's packed 32-bit representation.
     ///
     /// The format is `(s2 << 16) | s1`, matching upstream `checksum.c:get_checksum1()`.
@@ -553,6 +622,34 @@ fn accumulate_chunk_arch(
     None
 }
 
+/// Multi-offset roll fast path; returns `None` when the caller should run
+/// the scalar recurrence for every offset.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[inline]
+fn rolled_values_arch(
+    s1: u32,
+    s2: u32,
+    window_len: u32,
+    outgoing: &[u8],
+    incoming: &[u8],
+    values: &mut [u32],
+) -> Option<(u32, u32, usize)> {
+    x86::try_rolled_values(s1, s2, window_len, outgoing, incoming, values)
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+#[inline]
+fn rolled_values_arch(
+    _s1: u32,
+    _s2: u32,
+    _window_len: u32,
+    _outgoing: &[u8],
+    _incoming: &[u8],
+    _values: &mut [u32],
+) -> Option<(u32, u32, usize)> {
+    None
+}
+
 #[inline]
 const fn mask_result((s1, s2, len): (u32, u32, usize)) -> (u32, u32, usize) {
     (s1 & 0xffff, s2 & 0xffff, len)
@@ -431,6 +431,34 @@ fn roll_many_equals_repeated_roll() {
     assert_eq!(rolling_many.value(), rolling_single.value());
 }
 
+#[test]
+fn rolled_values_on_empty_window_fails() {
+    let checksum = RollingChecksum::new();
+    let mut values = [0u32; 2];
+    let result = checksum.rolled_values(&[1, 2], &[3, 4], &mut values);
+    assert!(matches!(result, Err(RollingError::EmptyWindow)));
+}
+
+#[test]
+fn rolled_values_leave_state_unchanged() {
+    let data: Vec<u8> = (0..96u8).map(|b| b.wrapping_mul(37) ^ 0x80).collect();
+    let mut rolling = RollingChecksum::new();
+    rolling.update(&data[..32]);
+    let before = rolling.clone();
+
+    let mut values = [0u32; 64];
+    rolling
+        .rolled_values(&data[..64], &data[32..96], &mut values)
+        .unwrap();
+    assert_eq!(rolling, before);
+
+    for (k, &value) in values.iter().enumerate() {
+        let mut fresh = RollingChecksum::new();
+        fresh.update(&data[k + 1..k + 33]);
+        assert_eq!(value, fresh.value(), "offset {}", k + 1);
+    }
+}
+
 #[test]
 fn value_format_is_s2_high_s1_low() {
     let mut checksum = RollingChecksum::new();
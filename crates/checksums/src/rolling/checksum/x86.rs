@@ -53,17 +53,21 @@
 use super::accumulate_chunk_scalar_raw;
 #[cfg(target_arch = "x86")]
 use core::arch::x86::{
-    __m128i, __m256i, _mm_add_epi32, _mm_cmplt_epi8, _mm_cvtsi32_si128, _mm_cvtsi128_si32,
-    _mm_loadu_si128, _mm_madd_epi16, _mm_set_epi16, _mm_set1_epi16, _mm_setzero_si128,
-    _mm_shuffle_epi32, _mm_slli_epi32, _mm_unpackhi_epi8, _mm_unpacklo_epi8, _mm256_add_epi32,
+    __m128i, __m256i, _mm_add_epi16, _mm_add_epi32, _mm_cmplt_epi8, _mm_cvtsi32_si128,
+    _mm_cvtsi128_si32, _mm_loadl_epi64, _mm_loadu_si128, _mm_madd_epi16, _mm_mullo_epi16,
+    _mm_set_epi16, _mm_set1_epi16, _mm_setzero_si128, _mm_shuffle_epi32, _mm_shufflehi_epi16,
+    _mm_slli_epi32, _mm_slli_si128, _mm_storeu_si128, _mm_sub_epi16, _mm_unpackhi_epi8,
+    _mm_unpackhi_epi16, _mm_unpacklo_epi8, _mm_unpacklo_epi16, _mm256_add_epi32,
     _mm256_castsi256_si128, _mm256_cvtepi8_epi16, _mm256_extracti128_si256, _mm256_loadu_si256,
     _mm256_madd_epi16, _mm256_set_epi16, _mm256_set1_epi16,
 };
 #[cfg(target_arch = "x86_64")]
 use core::arch::x86_64::{
-    __m128i, __m256i, _mm_add_epi32, _mm_cmplt_epi8, _mm_cvtsi32_si128, _mm_cvtsi128_si32,
-    _mm_loadu_si128, _mm_madd_epi16, _mm_set_epi16, _mm_set1_epi16, _mm_setzero_si128,
-    _mm_shuffle_epi32, _mm_slli_epi32, _mm_unpackhi_epi8, _mm_unpacklo_epi8, _mm256_add_epi32,
+    __m128i, __m256i, _mm_add_epi16, _mm_add_epi32, _mm_cmplt_epi8, _mm_cvtsi32_si128,
+    _mm_cvtsi128_si32, _mm_loadl_epi64, _mm_loadu_si128, _mm_madd_epi16, _mm_mullo_epi16,
+    _mm_set_epi16, _mm_set1_epi16, _mm_setzero_si128, _mm_shuffle_epi32, _mm_shufflehi_epi16,
+    _mm_slli_epi32, _mm_slli_si128, _mm_storeu_si128, _mm_sub_epi16, _mm_unpackhi_epi8,
+    _mm_unpackhi_epi16, _mm_unpacklo_epi8, _mm_unpacklo_epi16, _mm256_add_epi32,
     _mm256_castsi256_si128, _mm256_cvtepi8_epi16, _mm256_extracti128_si256, _mm256_loadu_si256,
     _mm256_madd_epi16, _mm256_set_epi16, _mm256_set1_epi16,
 };
@@ -79,6 +83,9 @@ const SSE2_BLOCK_SHIFT: i32 = 4;
 // log2(AVX2_BLOCK_LEN); used by `_mm_slli_epi32(ss1, AVX2_BLOCK_SHIFT)` to
 // add `AVX2_BLOCK_LEN * s1` to s2 each iteration.
 const AVX2_BLOCK_SHIFT: i32 = 5;
+// Window offsets computed per iteration of the multi-offset roll: one
+// 16-bit lane of an `__m128i` per offset.
+const SSE2_ROLL_LANES: usize = 8;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 struct FeatureLevel {
@@ -134,6 +141,32 @@ pub(super) fn try_accumulate_chunk(
     None
 }
 
+/// Computes the packed checksum values for successive rolls, eight window
+/// offsets per iteration, when SSE2 is available.
+///
+/// Returns the `(s1, s2)` state after the last computed offset and the number
+/// of offsets written to `values`; the caller finishes any remainder with the
+/// scalar recurrence.
+#[inline]
+pub(super) fn try_rolled_values(
+    s1: u32,
+    s2: u32,
+    window_len: u32,
+    outgoing: &[u8],
+    incoming: &[u8],
+    values: &mut [u32],
+) -> Option<(u32, u32, usize)> {
+    if outgoing.len() < SSE2_ROLL_LANES || !effective_features().sse2 {
+        return None;
+    }
+    debug_assert_eq!(outgoing.len(), incoming.len());
+    debug_assert!(values.len() >= outgoing.len());
+
+    // SAFETY: SSE2 is available (checked above), and the slice lengths satisfy
+    // `rolled_values_sse2`'s contract (asserted by the caller).
+    Some(unsafe { rolled_values_sse2(s1, s2, window_len, outgoing, incoming, values) })
+}
+
 #[cfg(test)]
 pub(super) fn load_cpu_features_for_tests() {
     let _ = cpu_features();
@@ -396,6 +429,80 @@ unsafe fn prefix_sum_pairs_sse2(
     _mm_add_epi32(weighted_high, weighted_low)
 }
 
+/// Multi-offset roll using SSE2, eight consecutive window offsets per iteration.
+///
+/// Rolling the window by one byte is `s1' = s1 - out + in` followed by
+/// `s2' = s2 - len * out + s1'`, so the state after each of `k` rolls is a
+/// prefix sum. Each iteration sign-extends eight outgoing and eight incoming
+/// bytes into 16-bit lanes, forms `in - out` and runs a log-step inclusive
+/// scan to get `s1` at every offset, then scans `s1 - len * out` the same way
+/// to get `s2`. Both accumulators are 16 bits wide in upstream's packed value
+/// (`checksum.c:get_checksum1()`), so wrapping 16-bit lanes give exact
+/// results. The last lane of each accumulator seeds the next iteration.
+///
+/// # Safety
+///
+/// SSE2 must be available, `incoming` must be at least as long as `outgoing`,
+/// and `values` must have room for every whole group of eight offsets.
+#[target_feature(enable = "sse2")]
+unsafe fn rolled_values_sse2(
+    s1: u32,
+    s2: u32,
+    window_len: u32,
+    outgoing: &[u8],
+    incoming: &[u8],
+    values: &mut [u32],
+) -> (u32, u32, usize) {
+    let zero = _mm_setzero_si128();
+    let len_lanes = _mm_set1_epi16(window_len as i16);
+    let mut s1_lanes = _mm_set1_epi16(s1 as i16);
+    let mut s2_lanes = _mm_set1_epi16(s2 as i16);
+    let mut done = 0usize;
+
+    while outgoing.len() - done >= SSE2_ROLL_LANES {
+        let out_bytes = _mm_loadl_epi64(outgoing.as_ptr().add(done).cast::<__m128i>());
+        let in_bytes = _mm_loadl_epi64(incoming.as_ptr().add(done).cast::<__m128i>());
+        // Sign-extend to i16, matching upstream's `schar *buf`.
+        let out_signed = _mm_unpacklo_epi8(out_bytes, _mm_cmplt_epi8(out_bytes, zero));
+        let in_signed = _mm_unpacklo_epi8(in_bytes, _mm_cmplt_epi8(in_bytes, zero));
+
+        let delta = _mm_sub_epi16(in_signed, out_signed);
+        let sum1 = _mm_add_epi16(s1_lanes, prefix_sum_epi16_sse2(delta));
+        let step = _mm_sub_epi16(sum1, _mm_mullo_epi16(len_lanes, out_signed));
+        let sum2 = _mm_add_epi16(s2_lanes, prefix_sum_epi16_sse2(step));
+
+        // Interleaving s1 (low half) with s2 (high half) yields the packed
+        // `(s2 << 16) | s1` value for each offset.
+        let dst = values.as_mut_ptr().add(done).cast::<__m128i>();
+        _mm_storeu_si128(dst, _mm_unpacklo_epi16(sum1, sum2));
+        _mm_storeu_si128(dst.add(1), _mm_unpackhi_epi16(sum1, sum2));
+
+        s1_lanes = broadcast_last_epi16_sse2(sum1);
+        s2_lanes = broadcast_last_epi16_sse2(sum2);
+        done += SSE2_ROLL_LANES;
+    }
+
+    let s1 = extract_lane0(s1_lanes) & 0xffff;
+    let s2 = extract_lane0(s2_lanes) & 0xffff;
+    (s1, s2, done)
+}
+
+/// Inclusive prefix sum across the eight 16-bit lanes of `v`.
+#[inline]
+#[target_feature(enable = "sse2")]
+unsafe fn prefix_sum_epi16_sse2(v: __m128i) -> __m128i {
+    let v = _mm_add_epi16(v, _mm_slli_si128::<2>(v));
+    let v = _mm_add_epi16(v, _mm_slli_si128::<4>(v));
+    _mm_add_epi16(v, _mm_slli_si128::<8>(v))
+}
+
+/// Copies the last 16-bit lane of `v` into every lane.
+#[inline]
+#[target_feature(enable = "sse2")]
+unsafe fn broadcast_last_epi16_sse2(v: __m128i) -> __m128i {
+    _mm_shuffle_epi32::<0xFF>(_mm_shufflehi_epi16::<0xFF>(v))
+}
+
 #[cfg(test)]
 pub(crate) fn accumulate_chunk_sse2_for_tests(
     s1: u32,
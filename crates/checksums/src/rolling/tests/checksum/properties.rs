@@ -91,6 +91,28 @@ proptest! {
         proptest::prop_assert_eq!(optimized.value(), reference.value());
     }
 
+    #[test]
+    fn rolled_values_match_single_rolls_for_random_sequences(
+        (seed, pairs) in roll_many_sequences(),
+    ) {
+        let mut reference = RollingChecksum::new();
+        reference.update(&seed);
+        let before = reference.clone();
+
+        let (outgoing, incoming): (Vec<u8>, Vec<u8>) = pairs.into_iter().unzip();
+        let mut values = vec![0u32; outgoing.len()];
+        before
+            .rolled_values(&outgoing, &incoming, &mut values)
+            .expect("multi-offset roll succeeds");
+
+        for ((&out, &inn), &value) in outgoing.iter().zip(incoming.iter()).zip(&values) {
+            reference
+                .roll(out, inn)
+                .expect("single-byte roll succeeds");
+            proptest::prop_assert_eq!(value, reference.value());
+        }
+    }
+
     #[test]
     fn from_digest_round_trips(data in proptest::collection::vec(proptest::prelude::any::<u8>(), 0..=256)) {
         let mut checksum = RollingChecksum::new();
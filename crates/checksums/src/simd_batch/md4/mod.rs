@@ -57,6 +57,11 @@ pub fn digest(input: &[u8]) -> Digest {
     scalar::digest(input)
 }
 
+/// Returns the backend the MD4 batch dispatcher selected for this host.
+pub fn active_backend() -> Backend {
+    md4_dispatcher().backend
+}
+
 /// MD4 dispatcher that selects the optimal backend at runtime.
 struct Md4Dispatcher {
     backend: Backend,
@@ -16,6 +16,13 @@
 //! `simd_parity_tests::md5_simd_parity` via RFC 1321 vectors, lane-boundary
 //! sweeps, partial-batch coverage, large inputs (up to 100 KiB), and proptest
 //! property checks against arbitrary byte vectors.
+//!
+//! [`force_backend`] bypasses the ladder and pins the global dispatcher to one
+//! backend (the CLI `--checksum-backend` flag). Unlike the `--simd` cap it
+//! selects exactly that backend, and fails instead of degrading when the host
+//! cannot run it.
+
+use std::sync::OnceLock;
 
 use super::Digest;
 use super::md5_scalar as scalar;
@@ -62,6 +69,46 @@ impl Backend {
         }
     }
 
+    /// Parses a backend from its `--checksum-backend` spelling.
+    ///
+    /// Accepts the canonical lower-case names returned by
+    /// [`Backend::as_cli_str`], ignoring case and `-`/`_`/`.` separators so
+    /// `SSE4.1` and `avx-512` are understood. Returns `None` for unknown names.
+    pub fn parse_cli(value: &str) -> Option<Self> {
+        let normalized: String = value
+            .chars()
+            .filter(|c| !matches!(c, '-' | '_' | '.'))
+            .flat_map(char::to_lowercase)
+            .collect();
+
+        match normalized.as_str() {
+            "avx512" => Some(Self::Avx512),
+            "avx2" => Some(Self::Avx2),
+            "sse41" => Some(Self::Sse41),
+            "ssse3" => Some(Self::Ssse3),
+            "sse2" => Some(Self::Sse2),
+            "neon" => Some(Self::Neon),
+            "wasm" => Some(Self::Wasm),
+            "scalar" => Some(Self::Scalar),
+            _ => None,
+        }
+    }
+
+    /// Returns the canonical `--checksum-backend` spelling for this backend.
+    #[must_use]
+    pub const fn as_cli_str(self) -> &'static str {
+        match self {
+            Backend::Avx512 => "avx512",
+            Backend::Avx2 => "avx2",
+            Backend::Sse41 => "sse41",
+            Backend::Ssse3 => "ssse3",
+            Backend::Sse2 => "sse2",
+            Backend::Neon => "neon",
+            Backend::Wasm => "wasm",
+            Backend::Scalar => "scalar",
+        }
+    }
+
     /// Returns the human-readable name of this backend for diagnostics.
     #[allow(dead_code)] // REASON: public API exercised by simd_parity_tests
     pub const fn name(self) -> &'static str {
@@ -123,6 +170,21 @@ impl Dispatcher {
         Backend::Scalar
     }
 
+    /// Reports whether `backend` can run on this host under the active
+    /// `--simd` cap. [`Backend::Scalar`] is always supported.
+    pub fn supports(backend: Backend) -> bool {
+        match backend {
+            Backend::Avx512 => Self::has_avx512(),
+            Backend::Avx2 => Self::has_avx2(),
+            Backend::Sse41 => Self::has_sse41(),
+            Backend::Ssse3 => Self::has_ssse3(),
+            Backend::Sse2 => Self::has_sse2(),
+            Backend::Neon => Self::has_neon(),
+            Backend::Wasm => Self::has_wasm_simd(),
+            Backend::Scalar => true,
+        }
+    }
+
     fn has_avx512() -> bool {
         if !feature_allowed(SimdFeature::Avx512) {
             return false;
@@ -562,15 +624,128 @@ impl Dispatcher {
     pub fn digest(&self, input: &[u8]) -> Digest {
         scalar::digest(input)
     }
+
+    /// Folds whole 64-byte blocks into one running MD5 state.
+    ///
+    /// `blocks` must be a multiple of 64 bytes. This is the update step behind
+    /// [`Md5Context`](super::Md5Context). Every block of a single stream
+    /// depends on the previous one, so no backend can spread it across lanes
+    /// and all of them run the scalar compression here.
+    pub(crate) fn compress(&self, state: &mut [u32; 4], blocks: &[u8]) {
+        scalar::compress_blocks(state, blocks);
+    }
+
+    /// Folds whole 64-byte blocks into running MD5 states, one lane each.
+    ///
+    /// `blocks[i]` must be a multiple of 64 bytes and is folded into
+    /// `states[i]`; lanes may carry different amounts of data. This is the
+    /// update step behind [`Md5BatchContext`](super::Md5BatchContext). AVX-512
+    /// hosts run the AVX2 kernel and the SSE4.1/SSSE3 tiers run the SSE2
+    /// kernel, since the incremental path gains nothing from their extra
+    /// instructions. WASM has no incremental kernel and uses the scalar path.
+    pub(crate) fn compress_batch(&self, states: &mut [[u32; 4]], blocks: &[&[u8]]) {
+        debug_assert_eq!(states.len(), blocks.len());
+
+        match self.backend {
+            #[cfg(target_arch = "x86_64")]
+            Backend::Avx512 | Backend::Avx2 if Self::has_avx2() => {
+                compress_groups::<8>(states, blocks, |group, prefix| {
+                    // SAFETY: AVX2 availability was verified by `has_avx2()`.
+                    unsafe { simd::avx2::compress_x8(group, prefix) }
+                });
+            }
+            #[cfg(target_arch = "x86_64")]
+            Backend::Avx512 | Backend::Avx2 | Backend::Sse41 | Backend::Ssse3 | Backend::Sse2 => {
+                compress_groups::<4>(states, blocks, |group, prefix| {
+                    // SAFETY: SSE2 is part of the x86_64 baseline.
+                    unsafe { simd::sse2::compress_x4(group, prefix) }
+                });
+            }
+            #[cfg(target_arch = "aarch64")]
+            Backend::Neon => {
+                compress_groups::<4>(states, blocks, |group, prefix| {
+                    // SAFETY: NEON is mandatory on aarch64.
+                    unsafe { simd::neon::compress_x4(group, prefix) }
+                });
+            }
+            _ => {
+                for (state, lane_blocks) in states.iter_mut().zip(blocks) {
+                    scalar::compress_blocks(state, lane_blocks);
+                }
+            }
+        }
+    }
 }
 
+/// Runs `kernel` over groups of `N` lanes, then finishes each lane on the
+/// scalar path.
+///
+/// The kernel advances a group over the block prefix all of its lanes share.
+/// A short trailing group is padded with idle lanes that replay lane 0's data
+/// and are discarded afterwards.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+fn compress_groups<const N: usize>(
+    states: &mut [[u32; 4]],
+    blocks: &[&[u8]],
+    kernel: impl Fn(&mut [[u32; 4]; N], &[&[u8]; N]),
+) {
+    for (state_group, block_group) in states.chunks_mut(N).zip(blocks.chunks(N)) {
+        let shared = block_group.iter().map(|b| b.len()).min().unwrap_or(0);
+        if shared > 0 {
+            let mut group = [scalar::INIT_STATE; N];
+            group[..state_group.len()].copy_from_slice(state_group);
+            let prefix: [&[u8]; N] = std::array::from_fn(|lane| {
+                &block_group.get(lane).unwrap_or(&block_group[0])[..shared]
+            });
+            kernel(&mut group, &prefix);
+            state_group.copy_from_slice(&group[..state_group.len()]);
+        }
+        for (state, lane_blocks) in state_group.iter_mut().zip(block_group) {
+            scalar::compress_blocks(state, &lane_blocks[shared..]);
+        }
+    }
+}
+
+static DISPATCHER: OnceLock<Dispatcher> = OnceLock::new();
+
 /// Global dispatcher instance, initialized on first use.
 pub fn global() -> &'static Dispatcher {
-    use std::sync::OnceLock;
-    static DISPATCHER: OnceLock<Dispatcher> = OnceLock::new();
     DISPATCHER.get_or_init(Dispatcher::detect)
 }
 
+/// Error returned by [`force_backend`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum ForceBackendError {
+    /// The host CPU (or the `--simd` cap) cannot run the requested backend.
+    #[error("MD5 backend '{}' is not available on this host", .0.as_cli_str())]
+    Unsupported(Backend),
+    /// The global dispatcher was already initialized with another backend.
+    #[error("MD5 backend already initialized as '{}'", .0.as_cli_str())]
+    AlreadySelected(Backend),
+}
+
+/// Pins the global dispatcher to `backend`.
+///
+/// Must run before the first batch hash, like the `--simd` override. Calling
+/// it again with the backend already in use succeeds.
+///
+/// # Errors
+///
+/// Returns [`ForceBackendError::Unsupported`] when the host cannot execute
+/// `backend`, and [`ForceBackendError::AlreadySelected`] when the dispatcher
+/// was already initialized with a different backend.
+pub fn force_backend(backend: Backend) -> Result<(), ForceBackendError> {
+    if !Dispatcher::supports(backend) {
+        return Err(ForceBackendError::Unsupported(backend));
+    }
+    let active = DISPATCHER.get_or_init(|| Dispatcher { backend }).backend;
+    if active == backend {
+        Ok(())
+    } else {
+        Err(ForceBackendError::AlreadySelected(active))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -619,6 +794,50 @@ mod tests {
         }
     }
 
+    #[test]
+    fn backend_cli_names_round_trip() {
+        for backend in [
+            Backend::Avx512,
+            Backend::Avx2,
+            Backend::Sse41,
+            Backend::Ssse3,
+            Backend::Sse2,
+            Backend::Neon,
+            Backend::Wasm,
+            Backend::Scalar,
+        ] {
+            assert_eq!(Backend::parse_cli(backend.as_cli_str()), Some(backend));
+        }
+        assert_eq!(Backend::parse_cli("SSE4.1"), Some(Backend::Sse41));
+        assert_eq!(Backend::parse_cli("AVX-512"), Some(Backend::Avx512));
+        assert_eq!(Backend::parse_cli("avx1024"), None);
+    }
+
+    #[test]
+    fn force_backend_rejects_foreign_architecture() {
+        #[cfg(target_arch = "aarch64")]
+        let foreign = Backend::Avx2;
+        #[cfg(not(target_arch = "aarch64"))]
+        let foreign = Backend::Neon;
+        assert!(Dispatcher::supports(Backend::Scalar));
+        assert_eq!(
+            force_backend(foreign),
+            Err(ForceBackendError::Unsupported(foreign))
+        );
+    }
+
+    #[test]
+    fn force_backend_accepts_active_backend() {
+        let active = global().backend();
+        assert_eq!(force_backend(active), Ok(()));
+        if active != Backend::Scalar {
+            assert_eq!(
+                force_backend(Backend::Scalar),
+                Err(ForceBackendError::AlreadySelected(active))
+            );
+        }
+    }
+
     #[test]
     fn digest_batch_partial_batch() {
         // Test with exactly 3 inputs (partial batch)
@@ -0,0 +1,231 @@
+//! Incremental (streaming) MD5 hashing.
+//!
+//! [`Md5Context`] hashes one stream chunk by chunk through the dispatcher's
+//! single-stream step; the transfer engine's MD5 whole-file verifier uses it.
+//! [`Md5BatchContext`] runs several independent streams side by side and
+//! folds their whole blocks through the dispatcher's SIMD kernels, so a
+//! batch of files can be hashed in fixed-size reads without buffering any
+//! of them whole. Both produce the same digest as [`digest`](super::digest)
+//! over the concatenated input.
+//!
+//! A single MD5 stream is inherently serial - every block depends on the
+//! previous one - so the SIMD gain comes only from hashing several streams at
+//! once through [`Md5BatchContext`].
+
+use super::Digest;
+use super::md5_dispatcher;
+use super::md5_scalar as scalar;
+
+/// MD5 block size in bytes.
+const BLOCK_LEN: usize = 64;
+
+/// Incremental MD5 hasher for a single stream.
+#[derive(Clone, Debug)]
+pub struct Md5Context {
+    /// Running chaining values.
+    state: [u32; 4],
+    /// Bytes of an incomplete block awaiting more input.
+    buffer: [u8; BLOCK_LEN],
+    /// Number of valid bytes in `buffer`.
+    buffered: usize,
+    /// Total number of bytes fed so far.
+    length: u64,
+}
+
+impl Default for Md5Context {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Md5Context {
+    /// Creates a hasher with no input.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            state: scalar::INIT_STATE,
+            buffer: [0; BLOCK_LEN],
+            buffered: 0,
+            length: 0,
+        }
+    }
+
+    /// Feeds `data` into the hash.
+    pub fn update(&mut self, data: &[u8]) {
+        let (blocks, tail) = self.take_blocks(data);
+        md5_dispatcher::global().compress(&mut self.state, blocks);
+        self.stash(tail);
+    }
+
+    /// Pads the input and returns the digest.
+    #[must_use]
+    pub fn finalize(mut self) -> Digest {
+        let bit_len = self.length.wrapping_mul(8);
+
+        // MD5 padding: append 0x80, then zeros, then 64-bit length.
+        let mut padded = [0u8; 2 * BLOCK_LEN];
+        padded[..self.buffered].copy_from_slice(&self.buffer[..self.buffered]);
+        padded[self.buffered] = 0x80;
+        let pad_len = if self.buffered < BLOCK_LEN - 8 {
+            BLOCK_LEN
+        } else {
+            2 * BLOCK_LEN
+        };
+        padded[pad_len - 8..pad_len].copy_from_slice(&bit_len.to_le_bytes());
+
+        md5_dispatcher::global().compress(&mut self.state, &padded[..pad_len]);
+        scalar::state_to_digest(&self.state)
+    }
+
+    /// Completes the pending block from the front of `data` and splits the
+    /// rest into whole blocks and a tail shorter than one block.
+    ///
+    /// The caller folds the whole blocks into `state` and then passes the
+    /// tail to [`Self::stash`].
+    fn take_blocks<'a>(&mut self, mut data: &'a [u8]) -> (&'a [u8], &'a [u8]) {
+        self.length = self.length.wrapping_add(data.len() as u64);
+
+        if self.buffered > 0 {
+            let take = (BLOCK_LEN - self.buffered).min(data.len());
+            self.buffer[self.buffered..self.buffered + take].copy_from_slice(&data[..take]);
+            self.buffered += take;
+            data = &data[take..];
+            if self.buffered < BLOCK_LEN {
+                return (&[], &[]);
+            }
+            md5_dispatcher::global().compress(&mut self.state, &self.buffer);
+            self.buffered = 0;
+        }
+
+        data.split_at(data.len() - data.len() % BLOCK_LEN)
+    }
+
+    /// Buffers a tail left over by [`Self::take_blocks`].
+    fn stash(&mut self, tail: &[u8]) {
+        self.buffer[self.buffered..self.buffered + tail.len()].copy_from_slice(tail);
+        self.buffered += tail.len();
+    }
+}
+
+/// Incremental MD5 hasher for several independent streams at once.
+///
+/// Each lane is its own MD5 computation. [`update`](Self::update) takes one
+/// chunk per lane - chunks may differ in length and may be empty - and folds
+/// the whole blocks of every lane through the active SIMD backend together.
+#[derive(Clone, Debug)]
+pub struct Md5BatchContext {
+    lanes: Vec<Md5Context>,
+}
+
+impl Md5BatchContext {
+    /// Creates a batch of `lanes` empty hashers.
+    #[must_use]
+    pub fn new(lanes: usize) -> Self {
+        Self {
+            lanes: vec![Md5Context::new(); lanes],
+        }
+    }
+
+    /// Returns the number of streams in the batch.
+    #[must_use]
+    pub fn lanes(&self) -> usize {
+        self.lanes.len()
+    }
+
+    /// Feeds `chunks[i]` into lane `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunks` does not hold exactly one entry per lane.
+    pub fn update<T: AsRef<[u8]>>(&mut self, chunks: &[T]) {
+        assert_eq!(
+            chunks.len(),
+            self.lanes.len(),
+            "Md5BatchContext::update needs one chunk per lane"
+        );
+
+        let mut states = Vec::with_capacity(self.lanes.len());
+        let mut blocks = Vec::with_capacity(self.lanes.len());
+        let mut tails = Vec::with_capacity(self.lanes.len());
+        for (lane, chunk) in self.lanes.iter_mut().zip(chunks) {
+            let (lane_blocks, tail) = lane.take_blocks(chunk.as_ref());
+            states.push(lane.state);
+            blocks.push(lane_blocks);
+            tails.push(tail);
+        }
+
+        md5_dispatcher::global().compress_batch(&mut states, &blocks);
+
+        for ((lane, state), tail) in self.lanes.iter_mut().zip(states).zip(tails) {
+            lane.state = state;
+            lane.stash(tail);
+        }
+    }
+
+    /// Pads every lane and returns the digests in lane order.
+    #[must_use]
+    pub fn finalize(self) -> Vec<Digest> {
+        self.lanes.into_iter().map(Md5Context::finalize).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(len: usize, seed: u8) -> Vec<u8> {
+        (0..len)
+            .map(|i| (i as u8).wrapping_mul(31).wrapping_add(seed))
+            .collect()
+    }
+
+    #[test]
+    fn context_matches_one_shot_across_chunk_sizes() {
+        for len in [0, 1, 55, 56, 63, 64, 65, 127, 128, 1000] {
+            let data = sample(len, 7);
+            let expected = scalar::digest(&data);
+            for chunk in [1, 3, 63, 64, 65, 200] {
+                let mut context = Md5Context::new();
+                for piece in data.chunks(chunk) {
+                    context.update(piece);
+                }
+                assert_eq!(context.finalize(), expected, "len {len}, chunk {chunk}");
+            }
+        }
+    }
+
+    #[test]
+    fn batch_matches_one_shot_with_uneven_lanes() {
+        let lengths = [0usize, 1, 64, 100, 1000, 4096, 130, 63, 65, 5000, 17];
+        let data: Vec<Vec<u8>> = lengths
+            .iter()
+            .enumerate()
+            .map(|(lane, &len)| sample(len, lane as u8))
+            .collect();
+
+        let mut batch = Md5BatchContext::new(data.len());
+        let mut offset = 0;
+        while data.iter().any(|lane| lane.len() > offset) {
+            // Vary the step so lanes straddle block boundaries differently.
+            let step = 37 + offset % 91;
+            let chunks: Vec<&[u8]> = data
+                .iter()
+                .map(|lane| &lane[offset.min(lane.len())..(offset + step).min(lane.len())])
+                .collect();
+            batch.update(&chunks);
+            offset += step;
+        }
+
+        let digests = batch.finalize();
+        for (lane, input) in data.iter().enumerate() {
+            assert_eq!(digests[lane], scalar::digest(input), "lane {lane}");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "one chunk per lane")]
+    fn batch_rejects_wrong_chunk_count() {
+        let mut batch = Md5BatchContext::new(2);
+        batch.update(&[b"only one"]);
+    }
+}
@@ -10,6 +10,9 @@ const INIT_B: u32 = 0xefcd_ab89;
 const INIT_C: u32 = 0x98ba_dcfe;
 const INIT_D: u32 = 0x1032_5476;
 
+/// Chaining values an MD5 computation starts from.
+pub(crate) const INIT_STATE: [u32; 4] = [INIT_A, INIT_B, INIT_C, INIT_D];
+
 /// Per-round shift amounts (RFC 1321).
 const S: [u32; 64] = [
     7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9,
@@ -87,13 +90,10 @@ const K: [u32; 64] = [
 
 /// Compute MD5 digest for input data.
 pub fn digest(input: &[u8]) -> Digest {
-    let mut state = [INIT_A, INIT_B, INIT_C, INIT_D];
+    let mut state = INIT_STATE;
 
-    let mut offset = 0;
-    while offset + 64 <= input.len() {
-        process_block(&mut state, &input[offset..offset + 64]);
-        offset += 64;
-    }
+    let offset = input.len() - input.len() % 64;
+    compress_blocks(&mut state, &input[..offset]);
 
     let remaining = &input[offset..];
     let bit_len = (input.len() as u64) * 8;
@@ -111,6 +111,11 @@ pub fn digest(input: &[u8]) -> Digest {
         process_block(&mut state, &padded[64..128]);
     }
 
+    state_to_digest(&state)
+}
+
+/// Serializes final chaining values as the little-endian MD5 digest.
+pub(crate) fn state_to_digest(state: &[u32; 4]) -> Digest {
     let mut output = [0u8; 16];
     output[0..4].copy_from_slice(&state[0].to_le_bytes());
     output[4..8].copy_from_slice(&state[1].to_le_bytes());
@@ -119,6 +124,16 @@ pub fn digest(input: &[u8]) -> Digest {
     output
 }
 
+/// Folds whole 64-byte blocks into `state` without padding.
+///
+/// `blocks.len()` must be a multiple of 64.
+pub(crate) fn compress_blocks(state: &mut [u32; 4], blocks: &[u8]) {
+    debug_assert_eq!(blocks.len() % 64, 0);
+    for block in blocks.chunks_exact(64) {
+        process_block(state, block);
+    }
+}
+
 /// Process a single 64-byte block.
 fn process_block(state: &mut [u32; 4], block: &[u8]) {
     debug_assert_eq!(block.len(), 64);
@@ -205,52 +205,7 @@ pub unsafe fn digest_x8(inputs: &[&[u8]; 8]) -> [Digest; 8] {
         let cc = c;
         let dd = d;
 
-        macro_rules! round {
-            ($i:expr, $f:expr, $g:expr) => {{
-                let k_i = _mm256_set1_epi32(K[$i] as i32);
-                let temp = _mm256_add_epi32(_mm256_add_epi32(a, $f), _mm256_add_epi32(k_i, m[$g]));
-
-                let rotated = rotl(temp, S[$i] as i32);
-
-                a = d;
-                d = c;
-                c = b;
-                b = _mm256_add_epi32(b, rotated);
-            }};
-        }
-
-        // Rounds 0-15: F = (B & C) | (~B & D)
-        for i in 0..16 {
-            let f = _mm256_or_si256(_mm256_and_si256(b, c), _mm256_andnot_si256(b, d));
-            round!(i, f, i);
-        }
-
-        // Rounds 16-31: G = (D & B) | (~D & C)
-        for i in 16..32 {
-            let f = _mm256_or_si256(_mm256_and_si256(d, b), _mm256_andnot_si256(d, c));
-            let g = (5 * i + 1) % 16;
-            round!(i, f, g);
-        }
-
-        // Rounds 32-47: H = B ^ C ^ D
-        for i in 32..48 {
-            let f = _mm256_xor_si256(_mm256_xor_si256(b, c), d);
-            let g = (3 * i + 5) % 16;
-            round!(i, f, g);
-        }
-
-        // Rounds 48-63: I = C ^ (B | ~D)
-        for i in 48..64 {
-            let not_d = _mm256_xor_si256(d, _mm256_set1_epi32(-1));
-            let f = _mm256_xor_si256(c, _mm256_or_si256(b, not_d));
-            let g = (7 * i) % 16;
-            round!(i, f, g);
-        }
-
-        let new_a = _mm256_add_epi32(a, aa);
-        let new_b = _mm256_add_epi32(b, bb);
-        let new_c = _mm256_add_epi32(c, cc);
-        let new_d = _mm256_add_epi32(d, dd);
+        let [new_a, new_b, new_c, new_d] = md5_block([a, b, c, d], &m);
 
         // blendv selects from `new_*` where the mask sign bit is set, otherwise `aa`/`bb`/...
         a = _mm256_blendv_epi8(aa, new_a, mask);
@@ -284,6 +239,116 @@ pub unsafe fn digest_x8(inputs: &[&[u8]; 8]) -> [Digest; 8] {
     results
 }
 
+/// Runs the 64 MD5 rounds over one transposed block and returns the
+/// chaining values with the block folded in.
+///
+/// # Safety
+///
+/// Requires AVX2; enforced by the `#[target_feature]` attribute.
+#[target_feature(enable = "avx2")]
+unsafe fn md5_block(state: [__m256i; 4], m: &[__m256i; 16]) -> [__m256i; 4] {
+    let [aa, bb, cc, dd] = state;
+    let [mut a, mut b, mut c, mut d] = state;
+
+    macro_rules! round {
+        ($i:expr, $f:expr, $g:expr) => {{
+            let k_i = _mm256_set1_epi32(K[$i] as i32);
+            let temp = _mm256_add_epi32(_mm256_add_epi32(a, $f), _mm256_add_epi32(k_i, m[$g]));
+
+            let rotated = rotl(temp, S[$i] as i32);
+
+            a = d;
+            d = c;
+            c = b;
+            b = _mm256_add_epi32(b, rotated);
+        }};
+    }
+
+    // Rounds 0-15: F = (B & C) | (~B & D)
+    for i in 0..16 {
+        let f = _mm256_or_si256(_mm256_and_si256(b, c), _mm256_andnot_si256(b, d));
+        round!(i, f, i);
+    }
+
+    // Rounds 16-31: G = (D & B) | (~D & C)
+    for i in 16..32 {
+        let f = _mm256_or_si256(_mm256_and_si256(d, b), _mm256_andnot_si256(d, c));
+        let g = (5 * i + 1) % 16;
+        round!(i, f, g);
+    }
+
+    // Rounds 32-47: H = B ^ C ^ D
+    for i in 32..48 {
+        let f = _mm256_xor_si256(_mm256_xor_si256(b, c), d);
+        let g = (3 * i + 5) % 16;
+        round!(i, f, g);
+    }
+
+    // Rounds 48-63: I = C ^ (B | ~D)
+    for i in 48..64 {
+        let not_d = _mm256_xor_si256(d, _mm256_set1_epi32(-1));
+        let f = _mm256_xor_si256(c, _mm256_or_si256(b, not_d));
+        let g = (7 * i) % 16;
+        round!(i, f, g);
+    }
+
+    [
+        _mm256_add_epi32(a, aa),
+        _mm256_add_epi32(b, bb),
+        _mm256_add_epi32(c, cc),
+        _mm256_add_epi32(d, dd),
+    ]
+}
+
+/// Folds whole 64-byte blocks into 8 running MD5 states in parallel.
+///
+/// This is the unpadded update step behind the incremental batch hasher:
+/// every slice in `blocks` must hold the same number of bytes, a multiple
+/// of 64, and `states` carries each lane's chaining values in and out.
+///
+/// # Safety
+///
+/// Caller must ensure AVX2 is available; verify at runtime with
+/// `is_x86_feature_detected!("avx2")` before calling.
+#[target_feature(enable = "avx2")]
+pub unsafe fn compress_x8(states: &mut [[u32; 4]; 8], blocks: &[&[u8]; 8]) {
+    #[repr(C, align(32))]
+    struct Aligned([i32; 8]);
+
+    let len = blocks[0].len();
+    debug_assert!(len % 64 == 0 && blocks.iter().all(|block| block.len() == len));
+
+    let mut state = [_mm256_setzero_si256(); 4];
+    for (word, vector) in state.iter_mut().enumerate() {
+        let lanes = Aligned(std::array::from_fn(|lane| states[lane][word] as i32));
+        *vector = _mm256_load_si256(lanes.0.as_ptr() as *const __m256i);
+    }
+
+    for block_offset in (0..len).step_by(64) {
+        let mut m = [_mm256_setzero_si256(); 16];
+        for (word_idx, m_word) in m.iter_mut().enumerate() {
+            let offset = block_offset + word_idx * 4;
+            let words = Aligned(std::array::from_fn(|lane| {
+                i32::from_le_bytes(
+                    blocks[lane][offset..offset + 4]
+                        .try_into()
+                        .expect("4-byte word slice"),
+                )
+            }));
+            *m_word = _mm256_load_si256(words.0.as_ptr() as *const __m256i);
+        }
+        state = md5_block(state, &m);
+    }
+
+    for (word, vector) in state.iter().enumerate() {
+        let mut lanes = Aligned([0; 8]);
+        _mm256_store_si256(lanes.0.as_mut_ptr() as *mut __m256i, *vector);
+        for (lane, value) in lanes.0.iter().enumerate() {
+            states[lane][word] = *value as u32;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::super::md5_scalar;
@@ -418,4 +483,28 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn avx2_compress_matches_scalar_blocks() {
+        if !is_x86_feature_detected!("avx2") {
+            eprintln!("AVX2 not available, skipping test");
+            return;
+        }
+
+        let data: Vec<Vec<u8>> = (0..8u8)
+            .map(|lane| (0..128).map(|i| (i as u8).wrapping_mul(lane + 1)).collect())
+            .collect();
+        let blocks: [&[u8]; 8] = std::array::from_fn(|lane| data[lane].as_slice());
+
+        let mut states = [md5_scalar::INIT_STATE; 8];
+        // SAFETY: AVX2 availability was verified above. Every lane holds
+        // exactly two 64-byte blocks, as `compress_x8` requires.
+        unsafe { compress_x8(&mut states, &blocks) };
+
+        for (lane, state) in states.iter().enumerate() {
+            let mut expected = md5_scalar::INIT_STATE;
+            md5_scalar::compress_blocks(&mut expected, blocks[lane]);
+            assert_eq!(*state, expected, "state mismatch at lane {lane}");
+        }
+    }
 }
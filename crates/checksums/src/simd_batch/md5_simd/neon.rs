@@ -179,118 +179,7 @@ pub unsafe fn digest_x4(inputs: &[&[u8]; 4]) -> [Digest; 4] {
         let cc = c;
         let dd = d;
 
-        // Round 1: F = (B & C) | (~B & D)
-        macro_rules! round1 {
-            ($a:ident, $b:ident, $c:ident, $d:ident, $mi:expr, $ki:expr, $s:expr) => {{
-                let f = vorrq_u32(vandq_u32($b, $c), vbicq_u32($d, $b));
-                let k = vdupq_n_u32(K[$ki]);
-                let temp = vaddq_u32(vaddq_u32($a, f), vaddq_u32(k, m[$mi]));
-                $a = vaddq_u32($b, rotl_const!(temp, $s));
-            }};
-        }
-
-        round1!(a, b, c, d, 0, 0, 7);
-        round1!(d, a, b, c, 1, 1, 12);
-        round1!(c, d, a, b, 2, 2, 17);
-        round1!(b, c, d, a, 3, 3, 22);
-        round1!(a, b, c, d, 4, 4, 7);
-        round1!(d, a, b, c, 5, 5, 12);
-        round1!(c, d, a, b, 6, 6, 17);
-        round1!(b, c, d, a, 7, 7, 22);
-        round1!(a, b, c, d, 8, 8, 7);
-        round1!(d, a, b, c, 9, 9, 12);
-        round1!(c, d, a, b, 10, 10, 17);
-        round1!(b, c, d, a, 11, 11, 22);
-        round1!(a, b, c, d, 12, 12, 7);
-        round1!(d, a, b, c, 13, 13, 12);
-        round1!(c, d, a, b, 14, 14, 17);
-        round1!(b, c, d, a, 15, 15, 22);
-
-        // Round 2: G = (B & D) | (C & ~D)
-        macro_rules! round2 {
-            ($a:ident, $b:ident, $c:ident, $d:ident, $mi:expr, $ki:expr, $s:expr) => {{
-                let g = vorrq_u32(vandq_u32($b, $d), vbicq_u32($c, $d));
-                let k = vdupq_n_u32(K[$ki]);
-                let temp = vaddq_u32(vaddq_u32($a, g), vaddq_u32(k, m[$mi]));
-                $a = vaddq_u32($b, rotl_const!(temp, $s));
-            }};
-        }
-
-        round2!(a, b, c, d, 1, 16, 5);
-        round2!(d, a, b, c, 6, 17, 9);
-        round2!(c, d, a, b, 11, 18, 14);
-        round2!(b, c, d, a, 0, 19, 20);
-        round2!(a, b, c, d, 5, 20, 5);
-        round2!(d, a, b, c, 10, 21, 9);
-        round2!(c, d, a, b, 15, 22, 14);
-        round2!(b, c, d, a, 4, 23, 20);
-        round2!(a, b, c, d, 9, 24, 5);
-        round2!(d, a, b, c, 14, 25, 9);
-        round2!(c, d, a, b, 3, 26, 14);
-        round2!(b, c, d, a, 8, 27, 20);
-        round2!(a, b, c, d, 13, 28, 5);
-        round2!(d, a, b, c, 2, 29, 9);
-        round2!(c, d, a, b, 7, 30, 14);
-        round2!(b, c, d, a, 12, 31, 20);
-
-        // Round 3: H = B ^ C ^ D
-        macro_rules! round3 {
-            ($a:ident, $b:ident, $c:ident, $d:ident, $mi:expr, $ki:expr, $s:expr) => {{
-                let h = veorq_u32(veorq_u32($b, $c), $d);
-                let k = vdupq_n_u32(K[$ki]);
-                let temp = vaddq_u32(vaddq_u32($a, h), vaddq_u32(k, m[$mi]));
-                $a = vaddq_u32($b, rotl_const!(temp, $s));
-            }};
-        }
-
-        round3!(a, b, c, d, 5, 32, 4);
-        round3!(d, a, b, c, 8, 33, 11);
-        round3!(c, d, a, b, 11, 34, 16);
-        round3!(b, c, d, a, 14, 35, 23);
-        round3!(a, b, c, d, 1, 36, 4);
-        round3!(d, a, b, c, 4, 37, 11);
-        round3!(c, d, a, b, 7, 38, 16);
-        round3!(b, c, d, a, 10, 39, 23);
-        round3!(a, b, c, d, 13, 40, 4);
-        round3!(d, a, b, c, 0, 41, 11);
-        round3!(c, d, a, b, 3, 42, 16);
-        round3!(b, c, d, a, 6, 43, 23);
-        round3!(a, b, c, d, 9, 44, 4);
-        round3!(d, a, b, c, 12, 45, 11);
-        round3!(c, d, a, b, 15, 46, 16);
-        round3!(b, c, d, a, 2, 47, 23);
-
-        // Round 4: I = C ^ (B | ~D)
-        macro_rules! round4 {
-            ($a:ident, $b:ident, $c:ident, $d:ident, $mi:expr, $ki:expr, $s:expr) => {{
-                let i_val = veorq_u32($c, vornq_u32($b, $d));
-                let k = vdupq_n_u32(K[$ki]);
-                let temp = vaddq_u32(vaddq_u32($a, i_val), vaddq_u32(k, m[$mi]));
-                $a = vaddq_u32($b, rotl_const!(temp, $s));
-            }};
-        }
-
-        round4!(a, b, c, d, 0, 48, 6);
-        round4!(d, a, b, c, 7, 49, 10);
-        round4!(c, d, a, b, 14, 50, 15);
-        round4!(b, c, d, a, 5, 51, 21);
-        round4!(a, b, c, d, 12, 52, 6);
-        round4!(d, a, b, c, 3, 53, 10);
-        round4!(c, d, a, b, 10, 54, 15);
-        round4!(b, c, d, a, 1, 55, 21);
-        round4!(a, b, c, d, 8, 56, 6);
-        round4!(d, a, b, c, 15, 57, 10);
-        round4!(c, d, a, b, 6, 58, 15);
-        round4!(b, c, d, a, 13, 59, 21);
-        round4!(a, b, c, d, 4, 60, 6);
-        round4!(d, a, b, c, 11, 61, 10);
-        round4!(c, d, a, b, 2, 62, 15);
-        round4!(b, c, d, a, 9, 63, 21);
-
-        let new_a = vaddq_u32(a, aa);
-        let new_b = vaddq_u32(b, bb);
-        let new_c = vaddq_u32(c, cc);
-        let new_d = vaddq_u32(d, dd);
+        let [new_a, new_b, new_c, new_d] = md5_block([a, b, c, d], &m);
 
         a = vbslq_u32(mask, new_a, aa);
         b = vbslq_u32(mask, new_b, bb);
@@ -323,6 +212,182 @@ pub unsafe fn digest_x4(inputs: &[&[u8]; 4]) -> [Digest; 4] {
     results
 }
 
+/// Runs the 64 MD5 rounds over one transposed block and returns the
+/// chaining values with the block folded in.
+///
+/// # Safety
+///
+/// Requires NEON; enforced by the `#[target_feature]` attribute.
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+#[allow(unsafe_op_in_unsafe_fn)]
+unsafe fn md5_block(state: [uint32x4_t; 4], m: &[uint32x4_t; 16]) -> [uint32x4_t; 4] {
+    let [aa, bb, cc, dd] = state;
+    let [mut a, mut b, mut c, mut d] = state;
+
+    // Round 1: F = (B & C) | (~B & D)
+    macro_rules! round1 {
+        ($a:ident, $b:ident, $c:ident, $d:ident, $mi:expr, $ki:expr, $s:expr) => {{
+            let f = vorrq_u32(vandq_u32($b, $c), vbicq_u32($d, $b));
+            let k = vdupq_n_u32(K[$ki]);
+            let temp = vaddq_u32(vaddq_u32($a, f), vaddq_u32(k, m[$mi]));
+            $a = vaddq_u32($b, rotl_const!(temp, $s));
+        }};
+    }
+
+    round1!(a, b, c, d, 0, 0, 7);
+    round1!(d, a, b, c, 1, 1, 12);
+    round1!(c, d, a, b, 2, 2, 17);
+    round1!(b, c, d, a, 3, 3, 22);
+    round1!(a, b, c, d, 4, 4, 7);
+    round1!(d, a, b, c, 5, 5, 12);
+    round1!(c, d, a, b, 6, 6, 17);
+    round1!(b, c, d, a, 7, 7, 22);
+    round1!(a, b, c, d, 8, 8, 7);
+    round1!(d, a, b, c, 9, 9, 12);
+    round1!(c, d, a, b, 10, 10, 17);
+    round1!(b, c, d, a, 11, 11, 22);
+    round1!(a, b, c, d, 12, 12, 7);
+    round1!(d, a, b, c, 13, 13, 12);
+    round1!(c, d, a, b, 14, 14, 17);
+    round1!(b, c, d, a, 15, 15, 22);
+
+    // Round 2: G = (B & D) | (C & ~D)
+    macro_rules! round2 {
+        ($a:ident, $b:ident, $c:ident, $d:ident, $mi:expr, $ki:expr, $s:expr) => {{
+            let g = vorrq_u32(vandq_u32($b, $d), vbicq_u32($c, $d));
+            let k = vdupq_n_u32(K[$ki]);
+            let temp = vaddq_u32(vaddq_u32($a, g), vaddq_u32(k, m[$mi]));
+            $a = vaddq_u32($b, rotl_const!(temp, $s));
+        }};
+    }
+
+    round2!(a, b, c, d, 1, 16, 5);
+    round2!(d, a, b, c, 6, 17, 9);
+    round2!(c, d, a, b, 11, 18, 14);
+    round2!(b, c, d, a, 0, 19, 20);
+    round2!(a, b, c, d, 5, 20, 5);
+    round2!(d, a, b, c, 10, 21, 9);
+    round2!(c, d, a, b, 15, 22, 14);
+    round2!(b, c, d, a, 4, 23, 20);
+    round2!(a, b, c, d, 9, 24, 5);
+    round2!(d, a, b, c, 14, 25, 9);
+    round2!(c, d, a, b, 3, 26, 14);
+    round2!(b, c, d, a, 8, 27, 20);
+    round2!(a, b, c, d, 13, 28, 5);
+    round2!(d, a, b, c, 2, 29, 9);
+    round2!(c, d, a, b, 7, 30, 14);
+    round2!(b, c, d, a, 12, 31, 20);
+
+    // Round 3: H = B ^ C ^ D
+    macro_rules! round3 {
+        ($a:ident, $b:ident, $c:ident, $d:ident, $mi:expr, $ki:expr, $s:expr) => {{
+            let h = veorq_u32(veorq_u32($b, $c), $d);
+            let k = vdupq_n_u32(K[$ki]);
+            let temp = vaddq_u32(vaddq_u32($a, h), vaddq_u32(k, m[$mi]));
+            $a = vaddq_u32($b, rotl_const!(temp, $s));
+        }};
+    }
+
+    round3!(a, b, c, d, 5, 32, 4);
+    round3!(d, a, b, c, 8, 33, 11);
+    round3!(c, d, a, b, 11, 34, 16);
+    round3!(b, c, d, a, 14, 35, 23);
+    round3!(a, b, c, d, 1, 36, 4);
+    round3!(d, a, b, c, 4, 37, 11);
+    round3!(c, d, a, b, 7, 38, 16);
+    round3!(b, c, d, a, 10, 39, 23);
+    round3!(a, b, c, d, 13, 40, 4);
+    round3!(d, a, b, c, 0, 41, 11);
+    round3!(c, d, a, b, 3, 42, 16);
+    round3!(b, c, d, a, 6, 43, 23);
+    round3!(a, b, c, d, 9, 44, 4);
+    round3!(d, a, b, c, 12, 45, 11);
+    round3!(c, d, a, b, 15, 46, 16);
+    round3!(b, c, d, a, 2, 47, 23);
+
+    // Round 4: I = C ^ (B | ~D)
+    macro_rules! round4 {
+        ($a:ident, $b:ident, $c:ident, $d:ident, $mi:expr, $ki:expr, $s:expr) => {{
+            let i_val = veorq_u32($c, vornq_u32($b, $d));
+            let k = vdupq_n_u32(K[$ki]);
+            let temp = vaddq_u32(vaddq_u32($a, i_val), vaddq_u32(k, m[$mi]));
+            $a = vaddq_u32($b, rotl_const!(temp, $s));
+        }};
+    }
+
+    round4!(a, b, c, d, 0, 48, 6);
+    round4!(d, a, b, c, 7, 49, 10);
+    round4!(c, d, a, b, 14, 50, 15);
+    round4!(b, c, d, a, 5, 51, 21);
+    round4!(a, b, c, d, 12, 52, 6);
+    round4!(d, a, b, c, 3, 53, 10);
+    round4!(c, d, a, b, 10, 54, 15);
+    round4!(b, c, d, a, 1, 55, 21);
+    round4!(a, b, c, d, 8, 56, 6);
+    round4!(d, a, b, c, 15, 57, 10);
+    round4!(c, d, a, b, 6, 58, 15);
+    round4!(b, c, d, a, 13, 59, 21);
+    round4!(a, b, c, d, 4, 60, 6);
+    round4!(d, a, b, c, 11, 61, 10);
+    round4!(c, d, a, b, 2, 62, 15);
+    round4!(b, c, d, a, 9, 63, 21);
+
+    [
+        vaddq_u32(a, aa),
+        vaddq_u32(b, bb),
+        vaddq_u32(c, cc),
+        vaddq_u32(d, dd),
+    ]
+}
+
+/// Folds whole 64-byte blocks into 4 running MD5 states in parallel.
+///
+/// This is the unpadded update step behind the incremental batch hasher:
+/// every slice in `blocks` must hold the same number of bytes, a multiple
+/// of 64, and `states` carries each lane's chaining values in and out.
+///
+/// # Safety
+///
+/// Caller must ensure NEON is available, which always holds on aarch64.
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+#[allow(unsafe_op_in_unsafe_fn)]
+pub unsafe fn compress_x4(states: &mut [[u32; 4]; 4], blocks: &[&[u8]; 4]) {
+    let len = blocks[0].len();
+    debug_assert!(len % 64 == 0 && blocks.iter().all(|block| block.len() == len));
+
+    let mut state = [vdupq_n_u32(0); 4];
+    for (word, vector) in state.iter_mut().enumerate() {
+        let lanes: [u32; 4] = std::array::from_fn(|lane| states[lane][word]);
+        *vector = vld1q_u32(lanes.as_ptr());
+    }
+
+    for block_offset in (0..len).step_by(64) {
+        let mut m = [vdupq_n_u32(0); 16];
+        for (word_idx, m_word) in m.iter_mut().enumerate() {
+            let offset = block_offset + word_idx * 4;
+            let words: [u32; 4] = std::array::from_fn(|lane| {
+                u32::from_le_bytes(
+                    blocks[lane][offset..offset + 4]
+                        .try_into()
+                        .expect("4-byte word slice"),
+                )
+            });
+            *m_word = vld1q_u32(words.as_ptr());
+        }
+        state = md5_block(state, &m);
+    }
+
+    for (word, vector) in state.iter().enumerate() {
+        let mut lanes = [0u32; 4];
+        vst1q_u32(lanes.as_mut_ptr(), *vector);
+        for (lane, value) in lanes.iter().enumerate() {
+            states[lane][word] = *value;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::super::md5_scalar;
@@ -410,4 +475,23 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn neon_md5_compress_matches_scalar_blocks() {
+        let data: Vec<Vec<u8>> = (0..4u8)
+            .map(|lane| (0..128).map(|i| (i as u8).wrapping_mul(lane + 1)).collect())
+            .collect();
+        let blocks: [&[u8]; 4] = std::array::from_fn(|lane| data[lane].as_slice());
+
+        let mut states = [md5_scalar::INIT_STATE; 4];
+        // SAFETY: NEON is mandatory on aarch64. Every lane holds exactly
+        // two 64-byte blocks, as `compress_x4` requires.
+        unsafe { compress_x4(&mut states, &blocks) };
+
+        for (lane, state) in states.iter().enumerate() {
+            let mut expected = md5_scalar::INIT_STATE;
+            md5_scalar::compress_blocks(&mut expected, blocks[lane]);
+            assert_eq!(*state, expected, "state mismatch at lane {lane}");
+        }
+    }
 }
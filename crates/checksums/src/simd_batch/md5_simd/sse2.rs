@@ -228,135 +228,7 @@ pub unsafe fn digest_x4(inputs: &[&[u8]; 4]) -> [Digest; 4] {
         let cc = c;
         let dd = d;
 
-        // Round 1: F = (B & C) | (~B & D), shifts: 7,12,17,22
-        macro_rules! round1 {
-            ($i:expr, $g:expr, $s:tt) => {{
-                let f = _mm_or_si128(_mm_and_si128(b, c), _mm_andnot_si128(b, d));
-                let k_i = _mm_set1_epi32(K[$i] as i32);
-                let temp = _mm_add_epi32(_mm_add_epi32(a, f), _mm_add_epi32(k_i, m[$g]));
-                let rotated = rotl!(temp, $s);
-                a = d;
-                d = c;
-                c = b;
-                b = _mm_add_epi32(b, rotated);
-            }};
-        }
-
-        round1!(0, 0, 7);
-        round1!(1, 1, 12);
-        round1!(2, 2, 17);
-        round1!(3, 3, 22);
-        round1!(4, 4, 7);
-        round1!(5, 5, 12);
-        round1!(6, 6, 17);
-        round1!(7, 7, 22);
-        round1!(8, 8, 7);
-        round1!(9, 9, 12);
-        round1!(10, 10, 17);
-        round1!(11, 11, 22);
-        round1!(12, 12, 7);
-        round1!(13, 13, 12);
-        round1!(14, 14, 17);
-        round1!(15, 15, 22);
-
-        // Round 2: G = (D & B) | (~D & C), shifts: 5,9,14,20
-        macro_rules! round2 {
-            ($i:expr, $g:expr, $s:tt) => {{
-                let f = _mm_or_si128(_mm_and_si128(d, b), _mm_andnot_si128(d, c));
-                let k_i = _mm_set1_epi32(K[$i] as i32);
-                let temp = _mm_add_epi32(_mm_add_epi32(a, f), _mm_add_epi32(k_i, m[$g]));
-                let rotated = rotl!(temp, $s);
-                a = d;
-                d = c;
-                c = b;
-                b = _mm_add_epi32(b, rotated);
-            }};
-        }
-
-        round2!(16, 1, 5);
-        round2!(17, 6, 9);
-        round2!(18, 11, 14);
-        round2!(19, 0, 20);
-        round2!(20, 5, 5);
-        round2!(21, 10, 9);
-        round2!(22, 15, 14);
-        round2!(23, 4, 20);
-        round2!(24, 9, 5);
-        round2!(25, 14, 9);
-        round2!(26, 3, 14);
-        round2!(27, 8, 20);
-        round2!(28, 13, 5);
-        round2!(29, 2, 9);
-        round2!(30, 7, 14);
-        round2!(31, 12, 20);
-
-        // Round 3: H = B ^ C ^ D, shifts: 4,11,16,23
-        macro_rules! round3 {
-            ($i:expr, $g:expr, $s:tt) => {{
-                let f = _mm_xor_si128(_mm_xor_si128(b, c), d);
-                let k_i = _mm_set1_epi32(K[$i] as i32);
-                let temp = _mm_add_epi32(_mm_add_epi32(a, f), _mm_add_epi32(k_i, m[$g]));
-                let rotated = rotl!(temp, $s);
-                a = d;
-                d = c;
-                c = b;
-                b = _mm_add_epi32(b, rotated);
-            }};
-        }
-
-        round3!(32, 5, 4);
-        round3!(33, 8, 11);
-        round3!(34, 11, 16);
-        round3!(35, 14, 23);
-        round3!(36, 1, 4);
-        round3!(37, 4, 11);
-        round3!(38, 7, 16);
-        round3!(39, 10, 23);
-        round3!(40, 13, 4);
-        round3!(41, 0, 11);
-        round3!(42, 3, 16);
-        round3!(43, 6, 23);
-        round3!(44, 9, 4);
-        round3!(45, 12, 11);
-        round3!(46, 15, 16);
-        round3!(47, 2, 23);
-
-        // Round 4: I = C ^ (B | ~D), shifts: 6,10,15,21
-        macro_rules! round4 {
-            ($i:expr, $g:expr, $s:tt) => {{
-                let not_d = _mm_xor_si128(d, _mm_set1_epi32(-1));
-                let f = _mm_xor_si128(c, _mm_or_si128(b, not_d));
-                let k_i = _mm_set1_epi32(K[$i] as i32);
-                let temp = _mm_add_epi32(_mm_add_epi32(a, f), _mm_add_epi32(k_i, m[$g]));
-                let rotated = rotl!(temp, $s);
-                a = d;
-                d = c;
-                c = b;
-                b = _mm_add_epi32(b, rotated);
-            }};
-        }
-
-        round4!(48, 0, 6);
-        round4!(49, 7, 10);
-        round4!(50, 14, 15);
-        round4!(51, 5, 21);
-        round4!(52, 12, 6);
-        round4!(53, 3, 10);
-        round4!(54, 10, 15);
-        round4!(55, 1, 21);
-        round4!(56, 8, 6);
-        round4!(57, 15, 10);
-        round4!(58, 6, 15);
-        round4!(59, 13, 21);
-        round4!(60, 4, 6);
-        round4!(61, 11, 10);
-        round4!(62, 2, 15);
-        round4!(63, 9, 21);
-
-        let new_a = _mm_add_epi32(a, aa);
-        let new_b = _mm_add_epi32(b, bb);
-        let new_c = _mm_add_epi32(c, cc);
-        let new_d = _mm_add_epi32(d, dd);
+        let [new_a, new_b, new_c, new_d] = md5_block([a, b, c, d], &m);
 
         // Blend using mask (SSE2 doesn't have blendv, use AND/ANDNOT/OR)
         let not_mask = _mm_xor_si128(mask, _mm_set1_epi32(-1));
@@ -391,6 +263,198 @@ pub unsafe fn digest_x4(inputs: &[&[u8]; 4]) -> [Digest; 4] {
     results
 }
 
+/// Runs the 64 MD5 rounds over one transposed block and returns the
+/// chaining values with the block folded in.
+///
+/// # Safety
+///
+/// Requires SSE2; enforced by the `#[target_feature]` attribute.
+#[target_feature(enable = "sse2")]
+unsafe fn md5_block(state: [__m128i; 4], m: &[__m128i; 16]) -> [__m128i; 4] {
+    let [aa, bb, cc, dd] = state;
+    let [mut a, mut b, mut c, mut d] = state;
+
+    // Round 1: F = (B & C) | (~B & D), shifts: 7,12,17,22
+    macro_rules! round1 {
+        ($i:expr, $g:expr, $s:tt) => {{
+            let f = _mm_or_si128(_mm_and_si128(b, c), _mm_andnot_si128(b, d));
+            let k_i = _mm_set1_epi32(K[$i] as i32);
+            let temp = _mm_add_epi32(_mm_add_epi32(a, f), _mm_add_epi32(k_i, m[$g]));
+            let rotated = rotl!(temp, $s);
+            a = d;
+            d = c;
+            c = b;
+            b = _mm_add_epi32(b, rotated);
+        }};
+    }
+
+    round1!(0, 0, 7);
+    round1!(1, 1, 12);
+    round1!(2, 2, 17);
+    round1!(3, 3, 22);
+    round1!(4, 4, 7);
+    round1!(5, 5, 12);
+    round1!(6, 6, 17);
+    round1!(7, 7, 22);
+    round1!(8, 8, 7);
+    round1!(9, 9, 12);
+    round1!(10, 10, 17);
+    round1!(11, 11, 22);
+    round1!(12, 12, 7);
+    round1!(13, 13, 12);
+    round1!(14, 14, 17);
+    round1!(15, 15, 22);
+
+    // Round 2: G = (D & B) | (~D & C), shifts: 5,9,14,20
+    macro_rules! round2 {
+        ($i:expr, $g:expr, $s:tt) => {{
+            let f = _mm_or_si128(_mm_and_si128(d, b), _mm_andnot_si128(d, c));
+            let k_i = _mm_set1_epi32(K[$i] as i32);
+            let temp = _mm_add_epi32(_mm_add_epi32(a, f), _mm_add_epi32(k_i, m[$g]));
+            let rotated = rotl!(temp, $s);
+            a = d;
+            d = c;
+            c = b;
+            b = _mm_add_epi32(b, rotated);
+        }};
+    }
+
+    round2!(16, 1, 5);
+    round2!(17, 6, 9);
+    round2!(18, 11, 14);
+    round2!(19, 0, 20);
+    round2!(20, 5, 5);
+    round2!(21, 10, 9);
+    round2!(22, 15, 14);
+    round2!(23, 4, 20);
+    round2!(24, 9, 5);
+    round2!(25, 14, 9);
+    round2!(26, 3, 14);
+    round2!(27, 8, 20);
+    round2!(28, 13, 5);
+    round2!(29, 2, 9);
+    round2!(30, 7, 14);
+    round2!(31, 12, 20);
+
+    // Round 3: H = B ^ C ^ D, shifts: 4,11,16,23
+    macro_rules! round3 {
+        ($i:expr, $g:expr, $s:tt) => {{
+            let f = _mm_xor_si128(_mm_xor_si128(b, c), d);
+            let k_i = _mm_set1_epi32(K[$i] as i32);
+            let temp = _mm_add_epi32(_mm_add_epi32(a, f), _mm_add_epi32(k_i, m[$g]));
+            let rotated = rotl!(temp, $s);
+            a = d;
+            d = c;
+            c = b;
+            b = _mm_add_epi32(b, rotated);
+        }};
+    }
+
+    round3!(32, 5, 4);
+    round3!(33, 8, 11);
+    round3!(34, 11, 16);
+    round3!(35, 14, 23);
+    round3!(36, 1, 4);
+    round3!(37, 4, 11);
+    round3!(38, 7, 16);
+    round3!(39, 10, 23);
+    round3!(40, 13, 4);
+    round3!(41, 0, 11);
+    round3!(42, 3, 16);
+    round3!(43, 6, 23);
+    round3!(44, 9, 4);
+    round3!(45, 12, 11);
+    round3!(46, 15, 16);
+    round3!(47, 2, 23);
+
+    // Round 4: I = C ^ (B | ~D), shifts: 6,10,15,21
+    macro_rules! round4 {
+        ($i:expr, $g:expr, $s:tt) => {{
+            let not_d = _mm_xor_si128(d, _mm_set1_epi32(-1));
+            let f = _mm_xor_si128(c, _mm_or_si128(b, not_d));
+            let k_i = _mm_set1_epi32(K[$i] as i32);
+            let temp = _mm_add_epi32(_mm_add_epi32(a, f), _mm_add_epi32(k_i, m[$g]));
+            let rotated = rotl!(temp, $s);
+            a = d;
+            d = c;
+            c = b;
+            b = _mm_add_epi32(b, rotated);
+        }};
+    }
+
+    round4!(48, 0, 6);
+    round4!(49, 7, 10);
+    round4!(50, 14, 15);
+    round4!(51, 5, 21);
+    round4!(52, 12, 6);
+    round4!(53, 3, 10);
+    round4!(54, 10, 15);
+    round4!(55, 1, 21);
+    round4!(56, 8, 6);
+    round4!(57, 15, 10);
+    round4!(58, 6, 15);
+    round4!(59, 13, 21);
+    round4!(60, 4, 6);
+    round4!(61, 11, 10);
+    round4!(62, 2, 15);
+    round4!(63, 9, 21);
+
+    [
+        _mm_add_epi32(a, aa),
+        _mm_add_epi32(b, bb),
+        _mm_add_epi32(c, cc),
+        _mm_add_epi32(d, dd),
+    ]
+}
+
+/// Folds whole 64-byte blocks into 4 running MD5 states in parallel.
+///
+/// This is the unpadded update step behind the incremental batch hasher:
+/// every slice in `blocks` must hold the same number of bytes, a multiple
+/// of 64, and `states` carries each lane's chaining values in and out.
+///
+/// # Safety
+///
+/// Caller must ensure SSE2 is available, which always holds on x86_64.
+#[target_feature(enable = "sse2")]
+pub unsafe fn compress_x4(states: &mut [[u32; 4]; 4], blocks: &[&[u8]; 4]) {
+    #[repr(C, align(16))]
+    struct Aligned([i32; 4]);
+
+    let len = blocks[0].len();
+    debug_assert!(len % 64 == 0 && blocks.iter().all(|block| block.len() == len));
+
+    let mut state = [_mm_setzero_si128(); 4];
+    for (word, vector) in state.iter_mut().enumerate() {
+        let lanes = Aligned(std::array::from_fn(|lane| states[lane][word] as i32));
+        *vector = _mm_load_si128(lanes.0.as_ptr() as *const __m128i);
+    }
+
+    for block_offset in (0..len).step_by(64) {
+        let mut m = [_mm_setzero_si128(); 16];
+        for (word_idx, m_word) in m.iter_mut().enumerate() {
+            let offset = block_offset + word_idx * 4;
+            let words = Aligned(std::array::from_fn(|lane| {
+                i32::from_le_bytes(
+                    blocks[lane][offset..offset + 4]
+                        .try_into()
+                        .expect("4-byte word slice"),
+                )
+            }));
+            *m_word = _mm_load_si128(words.0.as_ptr() as *const __m128i);
+        }
+        state = md5_block(state, &m);
+    }
+
+    for (word, vector) in state.iter().enumerate() {
+        let mut lanes = Aligned([0; 4]);
+        _mm_store_si128(lanes.0.as_mut_ptr() as *mut __m128i, *vector);
+        for (lane, value) in lanes.0.iter().enumerate() {
+            states[lane][word] = *value as u32;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::super::md5_scalar;
@@ -477,4 +541,23 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn sse2_compress_matches_scalar_blocks() {
+        let data: Vec<Vec<u8>> = (0..4u8)
+            .map(|lane| (0..128).map(|i| (i as u8).wrapping_mul(lane + 1)).collect())
+            .collect();
+        let blocks: [&[u8]; 4] = std::array::from_fn(|lane| data[lane].as_slice());
+
+        let mut states = [md5_scalar::INIT_STATE; 4];
+        // SAFETY: SSE2 is part of the x86_64 baseline. Every lane holds
+        // exactly two 64-byte blocks, as `compress_x4` requires.
+        unsafe { compress_x4(&mut states, &blocks) };
+
+        for (lane, state) in states.iter().enumerate() {
+            let mut expected = md5_scalar::INIT_STATE;
+            md5_scalar::compress_blocks(&mut expected, blocks[lane]);
+            assert_eq!(*state, expected, "state mismatch at lane {lane}");
+        }
+    }
 }
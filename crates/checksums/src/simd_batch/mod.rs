@@ -11,10 +11,14 @@
 //! - **NEON**: 4 parallel lanes (aarch64)
 //! - **WASM SIMD**: 4 parallel lanes (wasm32)
 //! - **Scalar**: Fallback for other platforms
+//!
+//! [`Md5Context`] and [`Md5BatchContext`] expose the same MD5 engine as an
+//! incremental update/finalize API for chunked reads.
 
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 pub(crate) mod md5_dispatcher;
+mod md5_incremental;
 mod md5_scalar;
 #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
 mod md5_simd;
@@ -22,7 +26,8 @@ mod md5_simd;
 /// MD4 hashing implementations with optional SIMD batch acceleration.
 pub mod md4;
 
-pub use md5_dispatcher::Backend;
+pub use md5_dispatcher::{Backend, ForceBackendError, force_backend};
+pub use md5_incremental::{Md5BatchContext, Md5Context};
 
 /// MD5 digest type (16 bytes / 128 bits).
 /// Also used for MD4 (same output size).
@@ -76,3 +81,32 @@ pub fn simd_available() -> bool {
 pub fn parallel_lanes() -> usize {
     active_backend().lanes()
 }
+
+/// Emits the `--debug=CHECKSUM` line naming the batch-hash backends in use.
+///
+/// Reports the MD5 and MD4 batch backends and whether the rolling checksum
+/// runs a SIMD loop, so checksum mismatches between hosts can be traced to
+/// the code paths that produced them. Resolving the backends initializes the
+/// dispatchers, so call this only after any `--simd` or `--checksum-backend`
+/// override has been installed.
+pub fn log_backend_selection() {
+    if !logging::debug_gte(logging::DebugFlag::Checksum, 1) {
+        return;
+    }
+    let md5 = active_backend();
+    let md4 = md4::active_backend();
+    let rolling = if crate::simd_acceleration_available() {
+        "simd"
+    } else {
+        "scalar"
+    };
+    logging::debug_log!(
+        Checksum,
+        1,
+        "checksum backends: md5={} ({} lanes), md4={} ({} lanes), rolling={rolling}",
+        md5.as_cli_str(),
+        md5.lanes(),
+        md4.as_cli_str(),
+        md4.lanes()
+    );
+}
@@ -221,6 +221,43 @@ pub fn digest_batch<T: AsRef<[u8]>>(inputs: &[T]) -> Vec<[u8; 16]> {
     crate::simd_batch::md4::digest_batch(inputs)
 }
 
+/// Batch form of [`Md4::digest_with_seed`].
+///
+/// Each input is copied into a scratch buffer with the 4-byte little-endian
+/// seed appended, then hashed through [`digest_batch`]. A zero seed hashes
+/// the inputs unchanged, as upstream does.
+///
+/// # Upstream Reference
+///
+/// - `checksum.c:377-380` - `get_checksum2()` appends the seed after the data
+#[must_use]
+pub fn digest_batch_seeded<T: AsRef<[u8]>>(inputs: &[T], seed: i32) -> Vec<[u8; 16]> {
+    if seed == 0 {
+        return digest_batch(inputs);
+    }
+
+    let seed_bytes = seed.to_le_bytes();
+    let total: usize = inputs.iter().map(|i| i.as_ref().len() + 4).sum();
+    let mut scratch = Vec::with_capacity(total);
+    let mut ends = Vec::with_capacity(inputs.len());
+    for input in inputs {
+        scratch.extend_from_slice(input.as_ref());
+        scratch.extend_from_slice(&seed_bytes);
+        ends.push(scratch.len());
+    }
+
+    let mut start = 0;
+    let seeded: Vec<&[u8]> = ends
+        .iter()
+        .map(|&end| {
+            let slice = &scratch[start..end];
+            start = end;
+            slice
+        })
+        .collect();
+    digest_batch(&seeded)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -388,4 +425,15 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn digest_batch_seeded_matches_per_block_digest() {
+        let inputs: &[&[u8]] = &[b"", b"abc", &[0x11_u8; 60], &[0x22_u8; 64], &[0x33_u8; 700]];
+        for seed in [0, 0x1234_5678, -1] {
+            let batch = digest_batch_seeded(inputs, seed);
+            for (input, digest) in inputs.iter().zip(&batch) {
+                assert_eq!(*digest, Md4::digest_with_seed(seed, input), "seed {seed}");
+            }
+        }
+    }
 }
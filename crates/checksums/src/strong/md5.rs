@@ -450,6 +450,35 @@ pub fn digest_batch<T: AsRef<[u8]>>(inputs: &[T]) -> Vec<[u8; 16]> {
     crate::simd_batch::digest_batch(inputs)
 }
 
+/// Batch form of [`StrongDigest::digest_with_seed`] for MD5.
+///
+/// Every input becomes one lane of an incremental SIMD batch, with the seed
+/// fed before or after the data according to `seed.proper_order`, so blocks
+/// are hashed in place without copying. A missing or zero seed uses
+/// [`digest_batch`] directly.
+///
+/// # Upstream Reference
+///
+/// - `checksum.c:get_checksum2()` - seed ordering for `CSUM_MD5`
+#[must_use]
+pub fn digest_batch_seeded<T: AsRef<[u8]>>(inputs: &[T], seed: Md5Seed) -> Vec<[u8; 16]> {
+    let Some(value) = seed.value.filter(|&value| value != 0) else {
+        return digest_batch(inputs);
+    };
+
+    let seed_bytes = value.to_le_bytes();
+    let seed_lanes = vec![seed_bytes.as_slice(); inputs.len()];
+    let mut batch = crate::simd_batch::Md5BatchContext::new(inputs.len());
+    if seed.proper_order {
+        batch.update(&seed_lanes);
+        batch.update(inputs);
+    } else {
+        batch.update(inputs);
+        batch.update(&seed_lanes);
+    }
+    batch.finalize()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -713,4 +742,20 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn digest_batch_seeded_matches_per_block_digest() {
+        let inputs: &[&[u8]] = &[b"", b"abc", &[0x11_u8; 60], &[0x22_u8; 64], &[0x33_u8; 700]];
+        for seed in [
+            Md5Seed::none(),
+            Md5Seed::proper(0),
+            Md5Seed::proper(0x1234_5678),
+            Md5Seed::legacy(0x1234_5678),
+        ] {
+            let batch = digest_batch_seeded(inputs, seed);
+            for (input, digest) in inputs.iter().zip(&batch) {
+                assert_eq!(*digest, Md5::digest_with_seed(seed, input), "{seed:?}");
+            }
+        }
+    }
 }
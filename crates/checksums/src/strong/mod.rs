@@ -70,14 +70,20 @@ mod xxhash;
 /// MD4 streaming hasher and batch digest function.
 ///
 /// `md4_digest_batch` computes MD4 digests for multiple inputs, using SIMD
-/// acceleration when available via runtime CPUID detection.
-pub use md4::{Md4, digest_batch as md4_digest_batch};
+/// acceleration when available via runtime CPUID detection;
+/// `md4_digest_batch_seeded` does the same with rsync's block checksum seed.
+pub use md4::{
+    Md4, digest_batch as md4_digest_batch, digest_batch_seeded as md4_digest_batch_seeded,
+};
 
 /// MD5 streaming hasher, seed configuration, and batch digest function.
 ///
 /// `md5_digest_batch` computes MD5 digests for multiple inputs, using SIMD
-/// acceleration when available via runtime CPUID detection.
-pub use md5::{Md5, Md5Seed, digest_batch as md5_digest_batch};
+/// acceleration when available via runtime CPUID detection;
+/// `md5_digest_batch_seeded` does the same with rsync's block checksum seed.
+pub use md5::{
+    Md5, Md5Seed, digest_batch as md5_digest_batch, digest_batch_seeded as md5_digest_batch_seeded,
+};
 
 #[cfg(feature = "openssl")]
 pub use openssl_support::openssl_acceleration_available;
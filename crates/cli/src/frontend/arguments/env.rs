@@ -49,6 +49,16 @@ pub(crate) fn env_max_alloc_default() -> Option<std::ffi::OsString> {
     env::var_os("RSYNC_MAX_ALLOC").filter(|value| !value.is_empty())
 }
 
+/// Returns the default `--checksum-backend` derived from
+/// `OC_RSYNC_CHECKSUM_BACKEND`.
+///
+/// oc-rsync extension: lets operators pin the MD5 batch backend on hosts
+/// where the command line is not under their control. Returns `Some(value)`
+/// when the variable is set and non-empty.
+pub(crate) fn env_checksum_backend_default() -> Option<std::ffi::OsString> {
+    env::var_os("OC_RSYNC_CHECKSUM_BACKEND").filter(|value| !value.is_empty())
+}
+
 #[cfg(test)]
 #[allow(unsafe_code)]
 mod tests {
@@ -17,7 +17,10 @@ mod stop;
 mod tests;
 
 pub(crate) use bandwidth::BandwidthArgument;
-pub(crate) use env::{env_iconv_default, env_max_alloc_default, env_protect_args_default};
+pub(crate) use env::{
+    env_checksum_backend_default, env_iconv_default, env_max_alloc_default,
+    env_protect_args_default,
+};
 pub use parsed_args::ParsedArgs; // Changed to pub for test_utils
 pub(crate) use parser::ChecksumThreadsSetting;
 pub use parser::parse_args; // Changed to pub for test_utils
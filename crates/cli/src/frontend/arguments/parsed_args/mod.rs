@@ -2,9 +2,11 @@
 
 use std::ffi::OsString;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use core::client::{
-    AddressMode, DeleteMode, HumanReadableMode, StrongChecksumChoice, TcpFastOpenMode,
+    AddressMode, DeleteMode, DestinationFormat, HumanReadableMode, StrongChecksumChoice,
+    TcpFastOpenMode,
 };
 
 use super::bandwidth::BandwidthArgument;
@@ -172,6 +174,18 @@ pub struct ParsedArgs {
     /// `--prune-empty-dirs`, `-m` / `--no-prune-empty-dirs`.
     pub prune_empty_dirs: Option<bool>,
 
+    /// `--case-collision=[off|warn|rename]` / `--ignore-case` - handling of
+    /// names that collide after case folding (oc-rsync extension).
+    pub case_collision: Option<engine::CaseCollisionPolicy>,
+
+    /// `--windows-names=[off|sanitize|skip|fail]` - handling of names that
+    /// cannot be created on Windows (oc-rsync extension).
+    pub windows_names: Option<engine::WindowsNamesPolicy>,
+
+    /// `--order=[name|mtime|size]` - order regular files are queued for
+    /// transfer (oc-rsync extension).
+    pub transfer_order: Option<engine::TransferOrder>,
+
     /// `--checksum`, `-c` / `--no-checksum` - skip based on checksum, not mtime+size.
     pub checksum: Option<bool>,
 
@@ -310,6 +324,10 @@ pub struct ParsedArgs {
     /// `--crtimes`, `-N` / `--no-crtimes` - preserve creation times (macOS/Windows).
     pub crtimes: Option<bool>,
 
+    /// `--fileflags` / `--no-fileflags` - preserve BSD file flags (oc-rsync
+    /// extension).
+    pub fileflags: Option<bool>,
+
     /// `--acls`, `-A` / `--no-acls` - preserve Access Control Lists.
     pub acls: Option<bool>,
 
@@ -417,6 +435,9 @@ pub struct ParsedArgs {
     /// `--apple-double-skip` - exclude macOS AppleDouble (`._*`) sidecar files.
     pub apple_double_skip: bool,
 
+    /// `--no-selinux-context` - exclude `security.selinux` from `--xattrs`.
+    pub no_selinux_context: bool,
+
     /// `-F` (repeatable) - rsync filter shortcut count.
     pub rsync_filter_shortcuts: usize,
 
@@ -480,6 +501,11 @@ pub struct ParsedArgs {
     /// `--fsync` - sync files to disk after writing.
     pub fsync: Option<bool>,
 
+    /// `--fsync-dir` - sync the parent directory after each file is renamed
+    /// into place. oc-rsync extension. Forwarded to the remote receiver on a
+    /// push.
+    pub fsync_dir: bool,
+
     /// `--io-uring` / `--no-io-uring` / `--no-io-uring-sqpoll` - io_uring
     /// policy for file I/O. `--no-io-uring-sqpoll` keeps io_uring on but
     /// suppresses `IORING_SETUP_SQPOLL` for rootless-container deployments
@@ -508,6 +534,27 @@ pub struct ParsedArgs {
     /// bytes at a range boundary.
     pub parallel_delta_scan: bool,
 
+    /// `--mmap-source` - opt-in, default-off local sender-side read mode that
+    /// scans large delta sources through a memory mapping. Local-only: it is
+    /// never forwarded to a remote peer. Off by default because a source
+    /// truncated during the scan raises `SIGBUS`.
+    pub mmap_source: bool,
+
+    /// `--parallel-commit=N` - commit up to `N` received files to disk
+    /// concurrently. oc-rsync extension; `None` keeps the single disk commit
+    /// thread. Forwarded to the remote receiver on a push.
+    pub parallel_commit: Option<usize>,
+
+    /// `--direct-write` - land received file data with `O_DIRECT` so it
+    /// bypasses the page cache. oc-rsync extension; Linux only. Forwarded to
+    /// the remote receiver on a push.
+    pub direct_write: bool,
+
+    /// `--drop-cache` - evict each file from the page cache once it has been
+    /// sent or committed. oc-rsync extension; Linux only. Forwarded to the
+    /// remote peer in both directions.
+    pub drop_cache: bool,
+
     /// `--cow` / `--no-cow` / `--reflink=<MODE>` - copy-on-write reflink
     /// policy for whole-file copies. The binary `--cow`/`--no-cow` flags
     /// map onto `Auto`/`Disabled`; the tri-state `--reflink=<MODE>` adds
@@ -522,12 +569,55 @@ pub struct ParsedArgs {
     /// [`checksums::SimdLevel::None`]).
     pub simd_override: Option<checksums::SimdLevel>,
 
+    /// `--checksum-backend=<backend>` - pins MD5 batch hashing to exactly
+    /// this backend (falls back to `OC_RSYNC_CHECKSUM_BACKEND`). `None`
+    /// keeps the dispatch ladder.
+    pub checksum_backend: Option<checksums::Md5Backend>,
+
     /// `--delay-updates` - use temp files and rename after all transfers complete.
     pub delay_updates: bool,
 
+    /// `--atomic` - oc-rsync extension: stage the whole destination in a
+    /// sibling directory and swap it into place once the transfer succeeds.
+    pub atomic: bool,
+
+    /// `--dest-format` - oc-rsync extension: write the destination as a
+    /// directory tree (the default) or as a `tar` / `tar.zst` archive file.
+    pub dest_format: DestinationFormat,
+
+    /// `--watch` - oc-rsync extension: after the initial transfer, keep
+    /// re-syncing the local sources whenever they change. Change detection
+    /// uses inotify on Linux and polls the source tree everywhere else.
+    pub watch: bool,
+
+    /// `--watch-debounce=MSEC` - quiet period that closes a batch of source
+    /// changes in watch mode.
+    pub watch_debounce: Option<Duration>,
+
     /// `--temp-dir`, `-T` - directory for temporary files during transfer.
     pub temp_dir: Option<PathBuf>,
 
+    /// `--cache-dir=DIR` - keep a destination scan cache in `DIR` so
+    /// `--checksum` re-runs skip reading unchanged destination files.
+    /// oc-rsync extension. Forwarded to the remote receiver on a remote-shell
+    /// push.
+    pub cache_dir: Option<PathBuf>,
+
+    /// `--checkpoint=FILE` - log confirmed commits to `FILE` so an
+    /// interrupted run can be resumed. oc-rsync extension. Forwarded to the
+    /// remote receiver on a remote-shell push.
+    pub checkpoint: Option<PathBuf>,
+
+    /// `--resume=FILE` - skip files the checkpoint in `FILE` records as
+    /// already committed. oc-rsync extension. Forwarded to the remote
+    /// receiver on a remote-shell push.
+    pub resume: Option<PathBuf>,
+
+    /// `--remaining-files=FILE` - write the paths a deadline-stopped
+    /// transfer did not reach to `FILE`, ready for `--files-from`.
+    /// oc-rsync extension.
+    pub remaining_files: Option<PathBuf>,
+
     /// `--max-alloc=SIZE` - soft byte budget on buffer-pool retention.
     ///
     /// Stored as the raw user-supplied string. The downstream parser in
@@ -592,6 +682,10 @@ pub struct ParsedArgs {
     /// `--debug` - fine-grained debug output control.
     pub debug: Vec<OsString>,
 
+    /// `--debug-protocol-dump` - record every multiplexed frame to this
+    /// file as JSON lines. oc-rsync extension.
+    pub debug_protocol_dump: Option<OsString>,
+
     /// `--msgs2stderr` / `--no-msgs2stderr` - send info messages to stderr.
     pub msgs_to_stderr: Option<bool>,
 
@@ -607,6 +701,14 @@ pub struct ParsedArgs {
     /// `--log-file-format` - format string for log file entries.
     pub log_file_format: Option<OsString>,
 
+    /// `--json` - emit per-file events, errors, and the final summary as
+    /// JSON lines on stdout instead of text. oc-rsync extension.
+    pub json: bool,
+
+    /// `--json-log` - append the JSON-lines records to this file.
+    /// oc-rsync extension.
+    pub json_log: Option<OsString>,
+
     /// `--write-batch` - write a batch file for later replay.
     pub write_batch: Option<OsString>,
 
@@ -7,6 +7,7 @@
 use std::env;
 use std::ffi::OsString;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use compress::algorithm::CompressionAlgorithm;
 
@@ -18,7 +19,8 @@ use crate::frontend::execution::{parse_checksum_seed_argument, parse_compress_le
 use crate::frontend::filter_rules::{FilterOrderToken, build_filter_order};
 use crate::frontend::progress::{NameOutputLevel, ProgressSetting, StderrMode};
 use core::client::{
-    AddressMode, DeleteMode, HumanReadableMode, StrongChecksumChoice, TcpFastOpenMode,
+    AddressMode, DeleteMode, DestinationFormat, HumanReadableMode, StrongChecksumChoice,
+    TcpFastOpenMode,
 };
 
 use super::coerce::{parse_checksum_threads, parse_spill_threshold_bytes, parse_thread_count};
@@ -29,8 +31,8 @@ use super::flags::{
 };
 use super::values::join_os_values;
 use super::{
-    BandwidthArgument, ParsedArgs, detect_program_name, env_iconv_default, env_max_alloc_default,
-    env_protect_args_default,
+    BandwidthArgument, ParsedArgs, detect_program_name, env_checksum_backend_default,
+    env_iconv_default, env_max_alloc_default, env_protect_args_default,
 };
 
 /// Maximum number of alt-dest directories rsync accepts across `--compare-dest`,
@@ -149,6 +151,7 @@ where
         tri_state_flag_negative_first(&matches, "omit-link-times", "no-omit-link-times");
     let atimes = leveled_flag_pair(&matches, "atimes", "no-atimes");
     let crtimes = tri_state_flag_negative_first(&matches, "crtimes", "no-crtimes");
+    let fileflags = tri_state_flag_negative_first(&matches, "fileflags", "no-fileflags");
     // upstream: options.c:2366-2367 - only `dry_run` sets `do_xfers = 0` (and
     // thus the compact `n` letter); `list_only` does NOT (options.c:2634 "Note:
     // NOT dry_run!"). The receiver skips destination writes under `list_only`
@@ -472,6 +475,60 @@ where
         }
         None => None,
     };
+    // oc-rsync extension: an explicit --case-collision wins over the
+    // --ignore-case shorthand for `warn`.
+    let case_collision = match matches.remove_one::<OsString>("case-collision") {
+        Some(value) => {
+            let text = value.to_string_lossy().into_owned();
+            match engine::CaseCollisionPolicy::parse(&text) {
+                Ok(policy) => Some(policy),
+                Err(_) => {
+                    return Err(clap::Error::raw(
+                        clap::error::ErrorKind::ValueValidation,
+                        format!(
+                            "invalid value for --case-collision: '{text}' (expected off, warn, or rename)\n",
+                        ),
+                    ));
+                }
+            }
+        }
+        None if matches.get_flag("ignore-case") => Some(engine::CaseCollisionPolicy::Warn),
+        None => None,
+    };
+    let windows_names = match matches.remove_one::<OsString>("windows-names") {
+        Some(value) => {
+            let text = value.to_string_lossy().into_owned();
+            match engine::WindowsNamesPolicy::parse(&text) {
+                Ok(policy) => Some(policy),
+                Err(_) => {
+                    return Err(clap::Error::raw(
+                        clap::error::ErrorKind::ValueValidation,
+                        format!(
+                            "invalid value for --windows-names: '{text}' (expected off, sanitize, skip, or fail)\n",
+                        ),
+                    ));
+                }
+            }
+        }
+        None => None,
+    };
+    let transfer_order = match matches.remove_one::<OsString>("order") {
+        Some(value) => {
+            let text = value.to_string_lossy().into_owned();
+            match engine::TransferOrder::parse(&text) {
+                Ok(order) => Some(order),
+                Err(_) => {
+                    return Err(clap::Error::raw(
+                        clap::error::ErrorKind::ValueValidation,
+                        format!(
+                            "invalid value for --order: '{text}' (expected name, mtime, or size)\n",
+                        ),
+                    ));
+                }
+            }
+        }
+        None => None,
+    };
     let fuzzy = {
         let count = matches.get_count("fuzzy");
         let negated = matches.get_flag("no-fuzzy");
@@ -568,6 +625,7 @@ where
     } else {
         None
     };
+    let fsync_dir = matches.get_flag("fsync-dir");
     let io_uring_policy = if matches.get_flag("io-uring") {
         fast_io::IoUringPolicy::Enabled
     } else if matches.get_flag("no-io-uring") {
@@ -610,6 +668,26 @@ where
     };
     // Local-only sender optimization; default off, never forwarded to a peer.
     let parallel_delta_scan = matches.get_flag("parallel-delta-scan");
+    let mmap_source = matches.get_flag("mmap-source");
+    let parallel_commit = match matches.remove_one::<OsString>("parallel-commit") {
+        Some(value) => {
+            let s = value.to_string_lossy();
+            match s.parse::<usize>() {
+                Ok(count) if count > 0 => Some(count),
+                _ => {
+                    return Err(clap::Error::raw(
+                        clap::error::ErrorKind::ValueValidation,
+                        format!(
+                            "invalid --parallel-commit value '{s}': must be a positive integer\n"
+                        ),
+                    ));
+                }
+            }
+        }
+        None => None,
+    };
+    let direct_write = matches.get_flag("direct-write");
+    let drop_cache = matches.get_flag("drop-cache");
     // Capture the reflink index before remove_one drains the match data;
     // resolve_cow_policy needs it to break ties against --cow / --no-cow.
     let reflink_index = last_occurrence(&matches, "reflink");
@@ -649,7 +727,57 @@ where
         }
         None => None,
     };
+    let checksum_backend = match matches
+        .remove_one::<OsString>("checksum-backend")
+        .or_else(env_checksum_backend_default)
+    {
+        Some(value) => {
+            let text = value.to_string_lossy();
+            match checksums::Md5Backend::parse_cli(text.as_ref()) {
+                Some(backend) => Some(backend),
+                None => {
+                    return Err(clap::Error::raw(
+                        clap::error::ErrorKind::InvalidValue,
+                        format!(
+                            "invalid value '{text}' for '--checksum-backend <BACKEND>': \
+                             expected one of scalar, sse2, ssse3, sse41, avx2, avx512, neon, wasm\n"
+                        ),
+                    ));
+                }
+            }
+        }
+        None => None,
+    };
     let delay_updates = matches.get_flag("delay-updates") && !matches.get_flag("no-delay-updates");
+    let atomic = matches.get_flag("atomic");
+    let dest_format = match matches.remove_one::<OsString>("dest-format") {
+        Some(value) => value
+            .to_string_lossy()
+            .parse::<DestinationFormat>()
+            .map_err(|error| {
+                clap::Error::raw(
+                    clap::error::ErrorKind::ValueValidation,
+                    format!("{error}\n"),
+                )
+            })?,
+        None => DestinationFormat::default(),
+    };
+    let watch = matches.get_flag("watch");
+    let watch_debounce = match matches.remove_one::<OsString>("watch-debounce") {
+        Some(value) => {
+            let s = value.to_string_lossy();
+            let millis = s.parse::<u64>().map_err(|_| {
+                clap::Error::raw(
+                    clap::error::ErrorKind::ValueValidation,
+                    format!(
+                        "invalid --watch-debounce value '{s}': must be a number of milliseconds\n"
+                    ),
+                )
+            })?;
+            Some(Duration::from_millis(millis))
+        }
+        None => None,
+    };
     let partial_dir_cli = matches
         .remove_one::<OsString>("partial-dir")
         .map(PathBuf::from);
@@ -675,8 +803,20 @@ where
     let temp_dir = matches
         .remove_one::<OsString>("temp-dir")
         .map(PathBuf::from);
+    let cache_dir = matches
+        .remove_one::<OsString>("cache-dir")
+        .map(PathBuf::from);
+    let checkpoint = matches
+        .remove_one::<OsString>("checkpoint")
+        .map(PathBuf::from);
+    let resume = matches.remove_one::<OsString>("resume").map(PathBuf::from);
+    let remaining_files = matches
+        .remove_one::<OsString>("remaining-files")
+        .map(PathBuf::from);
     let log_file = matches.remove_one::<OsString>("log-file");
     let log_file_format = matches.remove_one::<OsString>("log-file-format");
+    let json = matches.get_flag("json");
+    let json_log = matches.remove_one::<OsString>("json-log");
     let write_batch = matches.remove_one::<OsString>("write-batch");
     let only_write_batch = matches.remove_one::<OsString>("only-write-batch");
     let read_batch = matches.remove_one::<OsString>("read-batch");
@@ -846,6 +986,7 @@ where
     }
     let cvs_exclude = matches.get_flag("cvs-exclude");
     let apple_double_skip = matches.get_flag("apple-double-skip");
+    let no_selinux_context = matches.get_flag("no-selinux-context");
     let files_from: Vec<OsString> = matches
         .remove_many::<OsString>("files-from")
         .map(Iterator::collect)
@@ -892,6 +1033,7 @@ where
         .remove_many::<OsString>("debug")
         .map(Iterator::collect)
         .unwrap_or_default();
+    let debug_protocol_dump = matches.remove_one::<OsString>("debug-protocol-dump");
     let ignore_existing = matches.get_flag("ignore-existing");
     let existing = matches.get_flag("existing");
     let update = matches.get_flag("update");
@@ -1083,6 +1225,7 @@ where
         omit_link_times,
         atimes,
         crtimes,
+        fileflags,
         acls,
         numeric_ids,
         hard_links,
@@ -1113,6 +1256,9 @@ where
         implied_dirs,
         mkpath,
         prune_empty_dirs,
+        case_collision,
+        windows_names,
+        transfer_order,
         verbosity,
         quiet,
         progress: progress_setting,
@@ -1123,17 +1269,33 @@ where
         partial,
         preallocate,
         fsync,
+        fsync_dir,
         io_uring_policy,
         io_uring_depth,
         zero_copy_policy,
         parallel_delta_scan,
+        mmap_source,
+        parallel_commit,
+        direct_write,
+        drop_cache,
         cow_policy,
         simd_override,
+        checksum_backend,
         delay_updates,
+        atomic,
+        dest_format,
+        watch,
+        watch_debounce,
         partial_dir,
         temp_dir,
+        cache_dir,
+        checkpoint,
+        resume,
+        remaining_files,
         log_file,
         log_file_format,
+        json,
+        json_log,
         write_batch,
         only_write_batch,
         read_batch,
@@ -1163,11 +1325,13 @@ where
         filter_order,
         cvs_exclude,
         apple_double_skip,
+        no_selinux_context,
         rsync_filter_shortcuts,
         files_from,
         from0,
         info,
         debug,
+        debug_protocol_dump,
         xattrs,
         no_motd,
         password_file,
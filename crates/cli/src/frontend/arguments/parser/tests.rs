@@ -622,6 +622,14 @@ fn parallel_delta_scan_flag_sets_true() {
     assert!(parsed.parallel_delta_scan);
 }
 
+#[test]
+fn mmap_source_is_opt_in() {
+    let parsed = parse_test_args(["src/", "dst/"]).expect("parse");
+    assert!(!parsed.mmap_source);
+    let parsed = parse_test_args(["--mmap-source", "src/", "dst/"]).expect("parse");
+    assert!(parsed.mmap_source);
+}
+
 #[test]
 fn zero_copy_then_no_zero_copy_last_wins() {
     let parsed = parse_test_args(["--zero-copy", "--no-zero-copy", "src/", "dst/"]).expect("parse");
@@ -688,6 +696,42 @@ fn simd_rejects_unknown_levels() {
     assert!(err.to_string().contains("avx1024"));
 }
 
+#[test]
+fn checksum_backend_defaults_to_none() {
+    let parsed = parse_test_args(["src/", "dst/"]).expect("parse");
+    assert_eq!(parsed.checksum_backend, None);
+}
+
+#[test]
+fn checksum_backend_accepts_backend_names() {
+    for (input, expected) in [
+        ("scalar", checksums::Md5Backend::Scalar),
+        ("sse2", checksums::Md5Backend::Sse2),
+        ("SSE4.1", checksums::Md5Backend::Sse41),
+        ("avx-512", checksums::Md5Backend::Avx512),
+        ("neon", checksums::Md5Backend::Neon),
+        ("wasm", checksums::Md5Backend::Wasm),
+    ] {
+        let arg = format!("--checksum-backend={input}");
+        let parsed = parse_test_args([arg.as_str(), "src/", "dst/"])
+            .unwrap_or_else(|err| panic!("parse failed for {input}: {err}"));
+        assert_eq!(parsed.checksum_backend, Some(expected));
+    }
+}
+
+#[test]
+fn checksum_backend_rejects_unknown_names() {
+    let result = parse_test_args(["--checksum-backend=avx1024", "src/", "dst/"]);
+    let err = result.expect_err("unknown backend should fail");
+    assert!(err.to_string().contains("--checksum-backend"));
+    assert!(err.to_string().contains("avx1024"));
+    assert!(
+        err.to_string()
+            .contains("expected one of scalar, sse2, ssse3, sse41, avx2, avx512, neon, wasm"),
+        "{err}"
+    );
+}
+
 #[test]
 fn spill_dir_flag_default_is_none() {
     let parsed = parse_test_args(["src/", "dst/"]).expect("parse");
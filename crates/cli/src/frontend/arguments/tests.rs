@@ -561,6 +561,49 @@ mod long_options {
         );
     }
 
+    #[test]
+    fn case_collision_values_and_ignore_case_shorthand() {
+        let parsed = parse_test_args(["src/", "dst/"]).expect("parse");
+        assert_eq!(parsed.case_collision, None);
+        let parsed = parse_test_args(["--ignore-case", "src/", "dst/"]).expect("parse");
+        assert_eq!(
+            parsed.case_collision,
+            Some(engine::CaseCollisionPolicy::Warn)
+        );
+        let parsed = parse_test_args(["--ignore-case", "--case-collision=rename", "src/", "dst/"])
+            .expect("parse");
+        assert_eq!(
+            parsed.case_collision,
+            Some(engine::CaseCollisionPolicy::Rename)
+        );
+        assert!(parse_test_args(["--case-collision=skip", "src/", "dst/"]).is_err());
+    }
+
+    #[test]
+    fn windows_names_values() {
+        let parsed = parse_test_args(["src/", "dst/"]).expect("parse");
+        assert_eq!(parsed.windows_names, None);
+        let parsed = parse_test_args(["--windows-names=sanitize", "src/", "dst/"]).expect("parse");
+        assert_eq!(
+            parsed.windows_names,
+            Some(engine::WindowsNamesPolicy::Sanitize)
+        );
+        let parsed = parse_test_args(["--windows-names", "FAIL", "src/", "dst/"]).expect("parse");
+        assert_eq!(parsed.windows_names, Some(engine::WindowsNamesPolicy::Fail));
+        assert!(parse_test_args(["--windows-names=rename", "src/", "dst/"]).is_err());
+    }
+
+    #[test]
+    fn transfer_order_values() {
+        let parsed = parse_test_args(["src/", "dst/"]).expect("parse");
+        assert_eq!(parsed.transfer_order, None);
+        let parsed = parse_test_args(["--order=mtime", "src/", "dst/"]).expect("parse");
+        assert_eq!(parsed.transfer_order, Some(engine::TransferOrder::Mtime));
+        let parsed = parse_test_args(["--order", "SIZE", "src/", "dst/"]).expect("parse");
+        assert_eq!(parsed.transfer_order, Some(engine::TransferOrder::Size));
+        assert!(parse_test_args(["--order=atime", "src/", "dst/"]).is_err());
+    }
+
     #[test]
     fn checksum_long_flag() {
         let parsed = parse_test_args(["--checksum", "src/", "dst/"]).expect("parse");
@@ -867,6 +910,15 @@ mod long_options {
         assert_eq!(parsed.fsync, Some(true));
     }
 
+    #[test]
+    fn fsync_dir_long_flag() {
+        let parsed = parse_test_args(["--fsync-dir", "src/", "dst/"]).expect("parse");
+        assert!(parsed.fsync_dir);
+        assert_eq!(parsed.fsync, None);
+        let parsed = parse_test_args(["src/", "dst/"]).expect("parse");
+        assert!(!parsed.fsync_dir);
+    }
+
     #[test]
     fn qsort_long_flag() {
         let parsed = parse_test_args(["--qsort", "src/", "dst/"]).expect("parse");
@@ -1008,6 +1060,14 @@ mod negation_flags {
         assert!(parsed.xxh64_dedup);
     }
 
+    #[test]
+    fn atomic_flag_is_opt_in() {
+        let parsed = parse_test_args(["src/", "dst/"]).expect("parse");
+        assert!(!parsed.atomic);
+        let parsed = parse_test_args(["--atomic", "src/", "dst/"]).expect("parse");
+        assert!(parsed.atomic);
+    }
+
     #[test]
     fn no_one_file_system_flag() {
         let parsed = parse_test_args(["--no-one-file-system", "src/", "dst/"]).expect("parse");
@@ -1392,6 +1452,82 @@ mod option_values {
         assert!(err.to_string().contains("--io-uring-depth"));
     }
 
+    #[test]
+    fn parallel_commit_parses_count() {
+        let parsed = parse_test_args(["--parallel-commit=4", "src/", "dst/"]).expect("parse");
+        assert_eq!(parsed.parallel_commit, Some(4));
+        let parsed = parse_test_args(["src/", "dst/"]).expect("parse");
+        assert_eq!(parsed.parallel_commit, None);
+    }
+
+    #[test]
+    fn parallel_commit_rejects_zero() {
+        let err = parse_test_args(["--parallel-commit=0", "src/", "dst/"])
+            .expect_err("zero workers must be rejected");
+        assert!(err.to_string().contains("--parallel-commit"));
+    }
+
+    #[test]
+    fn direct_write_flag() {
+        let parsed = parse_test_args(["--direct-write", "src/", "dst/"]).expect("parse");
+        assert!(parsed.direct_write);
+        let parsed = parse_test_args(["src/", "dst/"]).expect("parse");
+        assert!(!parsed.direct_write);
+    }
+
+    #[test]
+    fn drop_cache_flag() {
+        let parsed = parse_test_args(["--drop-cache", "src/", "dst/"]).expect("parse");
+        assert!(parsed.drop_cache);
+        let parsed = parse_test_args(["src/", "dst/"]).expect("parse");
+        assert!(!parsed.drop_cache);
+    }
+
+    #[test]
+    fn cache_dir_parses_path() {
+        let parsed = parse_test_args(["--cache-dir=/var/cache/oc", "src/", "dst/"]).expect("parse");
+        assert_eq!(
+            parsed.cache_dir,
+            Some(std::path::PathBuf::from("/var/cache/oc"))
+        );
+        let parsed = parse_test_args(["src/", "dst/"]).expect("parse");
+        assert_eq!(parsed.cache_dir, None);
+    }
+
+    #[test]
+    fn checkpoint_and_resume_parse_paths() {
+        let parsed = parse_test_args([
+            "--checkpoint=/var/lib/oc/run.ckpt",
+            "--resume=/var/lib/oc/run.ckpt",
+            "src/",
+            "dst/",
+        ])
+        .expect("parse");
+        let expected = Some(std::path::PathBuf::from("/var/lib/oc/run.ckpt"));
+        assert_eq!(parsed.checkpoint, expected);
+        assert_eq!(parsed.resume, expected);
+        let parsed = parse_test_args(["src/", "dst/"]).expect("parse");
+        assert_eq!(parsed.checkpoint, None);
+        assert_eq!(parsed.resume, None);
+    }
+
+    #[test]
+    fn remaining_files_parses_path() {
+        let parsed = parse_test_args([
+            "--stop-after=60",
+            "--remaining-files=left.txt",
+            "src/",
+            "dst/",
+        ])
+        .expect("parse");
+        assert_eq!(
+            parsed.remaining_files,
+            Some(std::path::PathBuf::from("left.txt"))
+        );
+        let parsed = parse_test_args(["src/", "dst/"]).expect("parse");
+        assert_eq!(parsed.remaining_files, None);
+    }
+
     /// The `--no-io-uring-sqpoll` flag must parse to the dedicated
     /// `IoUringPolicy::SqpollOff` variant and leave io_uring active. This
     /// is the explicit opt-out for rootless containers and Kubernetes pods
@@ -1540,6 +1676,29 @@ mod option_values {
         assert_eq!(parsed.log_file_format, Some(OsString::from("%t %n")));
     }
 
+    #[test]
+    fn json_and_json_log_parse() {
+        let parsed = parse_test_args(["--json", "--json-log=/var/log/oc.jsonl", "src/", "dst/"])
+            .expect("parse");
+        assert!(parsed.json);
+        assert_eq!(parsed.json_log, Some(OsString::from("/var/log/oc.jsonl")));
+        let parsed = parse_test_args(["src/", "dst/"]).expect("parse");
+        assert!(!parsed.json);
+        assert_eq!(parsed.json_log, None);
+    }
+
+    #[test]
+    fn debug_protocol_dump_parse() {
+        let parsed =
+            parse_test_args(["--debug-protocol-dump=frames.jsonl", "src/", "dst/"]).expect("parse");
+        assert_eq!(
+            parsed.debug_protocol_dump,
+            Some(OsString::from("frames.jsonl"))
+        );
+        let parsed = parse_test_args(["src/", "dst/"]).expect("parse");
+        assert_eq!(parsed.debug_protocol_dump, None);
+    }
+
     #[test]
     fn out_format_with_equals() {
         let parsed = parse_test_args(["--out-format=%n%L", "src/", "dst/"]).expect("parse");
@@ -1589,6 +1748,45 @@ mod option_values {
         assert_eq!(parsed.tcp_fastopen, core::client::TcpFastOpenMode::On);
     }
 
+    #[test]
+    fn dest_format_defaults_to_directory() {
+        let parsed = parse_test_args(["src/", "dst/"]).expect("parse");
+        assert_eq!(
+            parsed.dest_format,
+            core::client::DestinationFormat::Directory
+        );
+        let parsed = parse_test_args(["--dest-format=tar.zst", "src/", "out.tzst"]).expect("parse");
+        assert_eq!(parsed.dest_format, core::client::DestinationFormat::TarZstd);
+    }
+
+    #[test]
+    fn dest_format_rejects_unknown_value() {
+        let error = parse_test_args(["--dest-format=zip", "src/", "out.zip"])
+            .expect_err("parse should fail");
+        assert!(error.to_string().contains("--dest-format"));
+    }
+
+    #[test]
+    fn watch_parses_flag_and_debounce() {
+        let parsed = parse_test_args(["src/", "dst/"]).expect("parse");
+        assert!(!parsed.watch);
+        assert_eq!(parsed.watch_debounce, None);
+        let parsed =
+            parse_test_args(["--watch", "--watch-debounce=250", "src/", "dst/"]).expect("parse");
+        assert!(parsed.watch);
+        assert_eq!(
+            parsed.watch_debounce,
+            Some(std::time::Duration::from_millis(250))
+        );
+    }
+
+    #[test]
+    fn watch_debounce_rejects_non_numeric_value() {
+        let error = parse_test_args(["--watch", "--watch-debounce=soon", "src/", "dst/"])
+            .expect_err("parse should fail");
+        assert!(error.to_string().contains("--watch-debounce"));
+    }
+
     #[test]
     fn tcp_fastopen_rejects_unknown_value() {
         let error = parse_test_args(["--tcp-fastopen=maybe", "src/", "dst/"])
@@ -2836,6 +3034,17 @@ mod time_option_tests {
         let parsed = parse_test_args(["--no-crtimes", "src/", "dst/"]).expect("parse");
         assert_eq!(parsed.crtimes, Some(false));
     }
+
+    #[test]
+    fn fileflags_flag_pair() {
+        let parsed = parse_test_args(["--fileflags", "src/", "dst/"]).expect("parse");
+        assert_eq!(parsed.fileflags, Some(true));
+        let parsed =
+            parse_test_args(["--fileflags", "--no-fileflags", "src/", "dst/"]).expect("parse");
+        assert_eq!(parsed.fileflags, Some(false));
+        let parsed = parse_test_args(["src/", "dst/"]).expect("parse");
+        assert_eq!(parsed.fileflags, None);
+    }
 }
 
 mod delay_updates_tests {
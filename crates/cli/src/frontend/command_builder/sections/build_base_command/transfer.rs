@@ -1,7 +1,9 @@
 //! Transfer behavior arguments: archive, recursive, dirs, inc-recursive,
 //! relative, one-file-system, implied-dirs, checksum, size-only, ignore-times,
 //! ignore-existing, existing, update, modify-window, sparse, fuzzy, force,
-//! qsort, mkpath, prune-empty-dirs, partial, and delay-updates.
+//! qsort, mkpath, prune-empty-dirs, case-collision, ignore-case, windows-names,
+//! order, partial, delay-updates, atomic, dest-format, watch, and
+//! watch-debounce.
 
 use super::{Arg, ArgAction, ClapCommand, OsStringValueParser};
 
@@ -301,6 +303,50 @@ pub(super) fn add_transfer_args(command: ClapCommand) -> ClapCommand {
                 .action(ArgAction::SetTrue)
                 .overrides_with("prune-empty-dirs"),
         )
+        .arg(
+            Arg::new("case-collision")
+                .long("case-collision")
+                .value_name("MODE")
+                .help(
+                    "Detect entries whose names collide after case folding. Values: off \
+                     (default), warn (report each collision), rename (also write the later \
+                     file as NAME~N).",
+                )
+                .num_args(1)
+                .action(ArgAction::Set)
+                .value_parser(OsStringValueParser::new()),
+        )
+        .arg(
+            Arg::new("ignore-case")
+                .long("ignore-case")
+                .help("Warn about names that collide on a case-insensitive destination.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("windows-names")
+                .long("windows-names")
+                .value_name("MODE")
+                .help(
+                    "Handle names that are invalid on Windows (<>:\"|?*, control characters, \
+                     trailing dots or spaces). Values: off (default), sanitize (replace with _), \
+                     skip, fail.",
+                )
+                .num_args(1)
+                .action(ArgAction::Set)
+                .value_parser(OsStringValueParser::new()),
+        )
+        .arg(
+            Arg::new("order")
+                .long("order")
+                .value_name("MODE")
+                .help(
+                    "Order regular files are queued for transfer. Values: name (default), \
+                     mtime (newest first), size (smallest first).",
+                )
+                .num_args(1)
+                .action(ArgAction::Set)
+                .value_parser(OsStringValueParser::new()),
+        )
         .arg(
             Arg::new("partial")
                 .long("partial")
@@ -329,4 +375,44 @@ pub(super) fn add_transfer_args(command: ClapCommand) -> ClapCommand {
                 .action(ArgAction::SetTrue)
                 .overrides_with("delay-updates"),
         )
+        .arg(
+            Arg::new("atomic")
+                .long("atomic")
+                .help(
+                    "Build the destination in a hidden sibling directory and swap it \
+                     into place only when the whole transfer succeeds (oc-rsync \
+                     extension; local copies only).",
+                )
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("dest-format")
+                .long("dest-format")
+                .value_name("FORMAT")
+                .help(
+                    "Write the destination as a directory tree (dir, the default) or \
+                     as a pax tar archive file (tar, tar.zst) (oc-rsync extension).",
+                )
+                .value_parser(OsStringValueParser::new()),
+        )
+        .arg(
+            Arg::new("watch")
+                .long("watch")
+                .help(
+                    "After the initial transfer, keep watching the local sources and \
+                     re-sync whatever changes (oc-rsync extension). Uses inotify on \
+                     Linux; other platforms rescan the source tree once a second.",
+                )
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("watch-debounce")
+                .long("watch-debounce")
+                .value_name("MSEC")
+                .help(
+                    "With --watch, wait for MSEC milliseconds without changes before \
+                     re-syncing (default 500).",
+                )
+                .value_parser(OsStringValueParser::new()),
+        )
 }
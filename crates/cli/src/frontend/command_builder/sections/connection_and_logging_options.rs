@@ -209,6 +209,14 @@ pub(crate) fn add_connection_and_logging_options(command: ClapCommand) -> ClapCo
                 .value_parser(OsStringValueParser::new())
                 .value_delimiter(','),
         )
+        .arg(
+            Arg::new("debug-protocol-dump")
+                .long("debug-protocol-dump")
+                .value_name("FILE")
+                .help("Record every multiplexed protocol frame to FILE as JSON lines.")
+                .num_args(1)
+                .value_parser(OsStringValueParser::new()),
+        )
         .arg(
             Arg::new("dparam")
                 .long("dparam")
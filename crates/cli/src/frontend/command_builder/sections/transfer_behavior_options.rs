@@ -23,6 +23,50 @@ pub(crate) fn add_transfer_behavior_options(command: ClapCommand) -> ClapCommand
                     .help("Store temporary files in DIR while transferring.")
                     .value_parser(OsStringValueParser::new()),
             )
+            .arg(
+                Arg::new("cache-dir")
+                    .long("cache-dir")
+                    .value_name("DIR")
+                    .help(
+                        "Keep a destination scan cache in DIR so --checksum \
+                         skips re-reading unchanged destination files.",
+                    )
+                    .num_args(1)
+                    .value_parser(OsStringValueParser::new()),
+            )
+            .arg(
+                Arg::new("checkpoint")
+                    .long("checkpoint")
+                    .value_name("FILE")
+                    .help(
+                        "Periodically record committed files in FILE so an \
+                         interrupted transfer can be continued with --resume.",
+                    )
+                    .num_args(1)
+                    .value_parser(OsStringValueParser::new()),
+            )
+            .arg(
+                Arg::new("resume")
+                    .long("resume")
+                    .value_name("FILE")
+                    .help(
+                        "Skip files the checkpoint in FILE records as already \
+                         committed and unchanged since.",
+                    )
+                    .num_args(1)
+                    .value_parser(OsStringValueParser::new()),
+            )
+            .arg(
+                Arg::new("remaining-files")
+                    .long("remaining-files")
+                    .value_name("FILE")
+                    .help(
+                        "When --stop-at/--stop-after ends the transfer, list the \
+                         paths not yet transferred in FILE for --files-from.",
+                    )
+                    .num_args(1)
+                    .value_parser(OsStringValueParser::new()),
+            )
             .arg(
                 Arg::new("log-file")
                     .long("log-file")
@@ -37,6 +81,23 @@ pub(crate) fn add_transfer_behavior_options(command: ClapCommand) -> ClapCommand
                     .help("Customise the format used when appending to --log-file.")
                     .value_parser(OsStringValueParser::new()),
             )
+            .arg(
+                Arg::new("json")
+                    .long("json")
+                    .help(
+                        "Emit per-file events, errors, and the final summary as JSON lines on \
+                         stdout instead of text.",
+                    )
+                    .action(ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("json-log")
+                    .long("json-log")
+                    .value_name("FILE")
+                    .help("Append the JSON-lines transfer records to FILE.")
+                    .num_args(1)
+                    .value_parser(OsStringValueParser::new()),
+            )
             .arg(
                 Arg::new("write-batch")
                     .long("write-batch")
@@ -155,6 +216,15 @@ pub(crate) fn add_transfer_behavior_options(command: ClapCommand) -> ClapCommand
                     .help("Fsync updated destination files after writing.")
                     .action(ArgAction::SetTrue),
             )
+            .arg(
+                Arg::new("fsync-dir")
+                    .long("fsync-dir")
+                    .help(
+                        "Fsync each destination directory after a file is \
+                         renamed into it, so the new entry survives a crash.",
+                    )
+                    .action(ArgAction::SetTrue),
+            )
             .arg(
                 Arg::new("io-uring")
                     .long("io-uring")
@@ -239,6 +309,18 @@ pub(crate) fn add_transfer_behavior_options(command: ClapCommand) -> ClapCommand
                     )
                     .value_parser(OsStringValueParser::new()),
             )
+            .arg(
+                Arg::new("checksum-backend")
+                    .long("checksum-backend")
+                    .value_name("BACKEND")
+                    .help(
+                        "Pin MD5 batch hashing to one backend: scalar, sse2, \
+                         ssse3, sse41, avx2, avx512, neon, or wasm. Unlike --simd, \
+                         the transfer fails if the host cannot run BACKEND. \
+                         Defaults to OC_RSYNC_CHECKSUM_BACKEND when set.",
+                    )
+                    .value_parser(OsStringValueParser::new()),
+            )
             .arg(
                 Arg::new("cow")
                     .long("cow")
@@ -324,6 +406,51 @@ pub(crate) fn add_transfer_behavior_options(command: ClapCommand) -> ClapCommand
                     )
                     .action(ArgAction::SetTrue),
             )
+            .arg(
+                Arg::new("mmap-source")
+                    .long("mmap-source")
+                    .help(
+                        "Opt-in: scan large delta sources through a memory \
+                         mapping instead of buffered reads (sender side). A \
+                         source truncated during the scan raises SIGBUS, so \
+                         this is off by default. Local-only, never forwarded \
+                         to a remote peer.",
+                    )
+                    .action(ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("parallel-commit")
+                    .long("parallel-commit")
+                    .value_name("N")
+                    .help(
+                        "Commit up to N received files to disk concurrently \
+                         (receiver side): writes, checksum verification, fsync \
+                         and rename overlap, while delta reconstruction stays \
+                         sequential. Results are still reported in file-list \
+                         order. Default 1.",
+                    )
+                    .num_args(1)
+                    .value_parser(OsStringValueParser::new()),
+            )
+            .arg(
+                Arg::new("direct-write")
+                    .long("direct-write")
+                    .help(
+                        "Write received file data with O_DIRECT, bypassing the \
+                         page cache (Linux, receiver side). Falls back to \
+                         buffered writes where the filesystem lacks support.",
+                    )
+                    .action(ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("drop-cache")
+                    .long("drop-cache")
+                    .help(
+                        "Evict each file from the page cache once it has been \
+                         sent or committed (Linux).",
+                    )
+                    .action(ArgAction::SetTrue),
+            )
             .arg(
                 Arg::new("inplace")
                     .long("inplace")
@@ -628,6 +755,12 @@ pub(crate) fn add_transfer_behavior_options(command: ClapCommand) -> ClapCommand
                     .help("Skip macOS AppleDouble (._foo) sidecar files.")
                     .action(ArgAction::SetTrue),
             )
+            .arg(
+                Arg::new("no-selinux-context")
+                    .long("no-selinux-context")
+                    .help("Do not copy SELinux labels (security.selinux) under --xattrs.")
+                    .action(ArgAction::SetTrue),
+            )
             .arg(
                 Arg::new("filter")
                     .long("filter")
@@ -875,6 +1008,20 @@ pub(crate) fn add_transfer_behavior_options(command: ClapCommand) -> ClapCommand
                     .action(ArgAction::SetTrue)
                     .overrides_with("crtimes"),
             )
+            .arg(
+                Arg::new("fileflags")
+                    .long("fileflags")
+                    .help("Preserve file flags (chflags) on macOS and FreeBSD.")
+                    .action(ArgAction::SetTrue)
+                    .overrides_with("no-fileflags"),
+            )
+            .arg(
+                Arg::new("no-fileflags")
+                    .long("no-fileflags")
+                    .help("Disable file flag preservation.")
+                    .action(ArgAction::SetTrue)
+                    .overrides_with("fileflags"),
+            )
             .arg(
                 Arg::new("acls")
                     .long("acls")
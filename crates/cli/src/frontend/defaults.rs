@@ -12,23 +12,23 @@ pub(super) const SUPPORTED_OPTIONS_LIST: &str = concat!(
     "--suffix, --checksum/-c, --checksum-choice, --checksum-seed, --size-only, --ignore-times, --ignore-existing, --existing, ",
     "--ignore-missing-args, --delete-missing-args, --update/-u, --modify-window, --exclude, --exclude-from, ",
     "--include, --include-from, --compare-dest, --copy-dest, --link-dest, --hard-links/-H, --no-hard-links, ",
-    "--cvs-exclude/-C, --apple-double-skip, --filter/-F (including exclude-if-present=FILE), --files-from, --password-file, --password-command, --no-motd, ",
+    "--cvs-exclude/-C, --apple-double-skip, --no-selinux-context, --filter/-F (including exclude-if-present=FILE), --files-from, --password-file, --password-command, --no-motd, ",
     "--from0, --no-from0, --bwlimit, --no-bwlimit, --timeout, --contimeout, --stop-after/--time-limit, --stop-at, --sockopts, ",
     "--tcp-fastopen, --blocking-io, --no-blocking-io, --protocol, --compress/-z, --no-compress, --compress-level, --compress-choice, --compress-threads, ",
-    "--skip-compress, --open-noatime, --no-open-noatime, --iconv, --no-iconv, --info, --debug, --verbose/-v, --no-verbose, ",
+    "--skip-compress, --open-noatime, --no-open-noatime, --iconv, --no-iconv, --info, --debug, --debug-protocol-dump, --verbose/-v, --no-verbose, ",
     "--relative/-R, --no-relative, --one-file-system/-x, --no-one-file-system, --implied-dirs, --no-implied-dirs, ",
-    "--mkpath, --no-mkpath, --old-dirs/--old-d, --prune-empty-dirs/-m, --no-prune-empty-dirs, --progress, --no-progress, --quiet, --no-quiet, ",
+    "--mkpath, --no-mkpath, --old-dirs/--old-d, --prune-empty-dirs/-m, --no-prune-empty-dirs, --case-collision, --ignore-case, --windows-names, --order, --progress, --no-progress, --quiet, --no-quiet, ",
     "--force, --no-force, --fuzzy/-y, --no-fuzzy, --msgs2stderr, --no-msgs2stderr, --8-bit-output, --outbuf, ",
-    "--itemize-changes/-i, --no-itemize-changes, --out-format, --stats, --partial, --no-partial, --partial-dir, --temp-dir, --log-file, ",
-    "--log-file-format, --delay-updates, --no-delay-updates, --whole-file/-W, --no-whole-file, --xxh64-dedup, --remove-source-files, ",
-    "--remove-sent-files, --append, --no-append, --append-verify, --preallocate, --fsync, --io-uring, --no-io-uring, --no-io-uring-sqpoll, --io-uring-depth, --io-uring-status, --lsm-status, --simd, --cow, --no-cow, --reflink, --zero-copy, --no-zero-copy, --parallel-delta-scan, --inplace, --no-inplace, ",
+    "--itemize-changes/-i, --no-itemize-changes, --out-format, --stats, --partial, --no-partial, --partial-dir, --temp-dir, --cache-dir, --checkpoint, --resume, --remaining-files, --log-file, ",
+    "--log-file-format, --json, --json-log, --delay-updates, --no-delay-updates, --atomic, --dest-format, --watch, --watch-debounce, --whole-file/-W, --no-whole-file, --xxh64-dedup, --remove-source-files, ",
+    "--remove-sent-files, --append, --no-append, --append-verify, --preallocate, --fsync, --fsync-dir, --io-uring, --no-io-uring, --no-io-uring-sqpoll, --io-uring-depth, --io-uring-status, --lsm-status, --simd, --checksum-backend, --cow, --no-cow, --reflink, --zero-copy, --no-zero-copy, --parallel-delta-scan, --mmap-source, --parallel-commit, --direct-write, --drop-cache, --inplace, --no-inplace, ",
     "--human-readable/-h, --no-human-readable, -P, --sparse/-S, --no-sparse/--no-S, --sparse-detect, --links/-l, --no-links/--no-l, ",
     "--copy-links/-L, ",
     "--copy-unsafe-links, --safe-links, --copy-dirlinks/-k, --keep-dirlinks/-K, ",
     "-D, --devices, --copy-devices, --no-devices, --specials, --no-specials, --super, --no-super, --owner, --no-owner, --group, --no-group, ",
     "--chown, --usermap, --groupmap, --chmod, --executability/-E, --perms/-p, --no-perms, --times/-t, --no-times, ",
     "--atimes/-U, --no-atimes, --crtimes/-N, --no-crtimes, --omit-dir-times, --no-omit-dir-times, --omit-link-times, --no-omit-link-times, ",
-    "--acls/-A, --no-acls, --xattrs/-X, --no-xattrs, ",
+    "--fileflags, --no-fileflags, --acls/-A, --no-acls, --xattrs/-X, --no-xattrs, ",
     "--numeric-ids, --no-numeric-ids, --rayon-threads, --checksum-threads, --tokio-threads"
 );
 
@@ -49,6 +49,12 @@ pub(super) use filters::DEFAULT_CVSIGNORE as CVS_EXCLUDE_PATTERNS;
 /// destinations.
 pub(super) const APPLE_DOUBLE_EXCLUDE_PATTERNS: &[&str] = &["._*"];
 
+/// Xattr names excluded by `--no-selinux-context`.
+///
+/// Only the SELinux label is dropped; `security.capability` and the other
+/// `security.*` attributes still follow `--xattrs`.
+pub(super) const SELINUX_CONTEXT_XATTR_PATTERNS: &[&str] = &["security.selinux"];
+
 /// Timestamp format used for `--list-only` and `--out-format` placeholders.
 pub(crate) const LIST_TIMESTAMP_FORMAT: &[FormatItem<'static>] = format_description!(
     "[year]/[month padding:zero]/[day padding:zero] [hour padding:zero]:[minute padding:zero]:[second padding:zero]"
@@ -121,6 +127,11 @@ mod tests {
         assert!(SUPPORTED_OPTIONS_LIST.contains("--apple-double-skip"));
     }
 
+    #[test]
+    fn supported_options_list_contains_no_selinux_context() {
+        assert!(SUPPORTED_OPTIONS_LIST.contains("--no-selinux-context"));
+    }
+
     #[test]
     fn supported_options_list_contains_zero_copy() {
         assert!(SUPPORTED_OPTIONS_LIST.contains("--zero-copy"));
@@ -9,8 +9,8 @@ use ::metadata::{ChmodModifiers, GroupMapping, UserMapping};
 use compress::algorithm::CompressionAlgorithm;
 use core::client::{
     AddressMode, BandwidthLimit, BatchConfig, ClientConfig, ClientConfigBuilder,
-    CompressionSetting, DeleteMode, FilesFromSource, IconvSetting, SkipCompressList,
-    StrongChecksumChoice, TcpFastOpenMode, TransferTimeout,
+    CompressionSetting, DeleteMode, DestinationFormat, FilesFromSource, IconvSetting,
+    SkipCompressList, StrongChecksumChoice, TcpFastOpenMode, TransferTimeout,
 };
 use rsync_io::ssh;
 
@@ -79,6 +79,8 @@ pub(crate) struct ConfigInputs {
     /// Access-time preservation level (0 = off, 1 = `-U`, 2 = `-UU`).
     pub(crate) atimes: u8,
     pub(crate) crtimes: bool,
+    /// `--fileflags`: preserve BSD file flags (oc-rsync extension).
+    pub(crate) fileflags: bool,
     pub(crate) modify_window_setting: Option<i64>,
     pub(crate) omit_dir_times: bool,
     pub(crate) omit_link_times: bool,
@@ -114,6 +116,12 @@ pub(crate) struct ConfigInputs {
     pub(crate) human_readable: bool,
     pub(crate) mkpath: bool,
     pub(crate) prune_empty_dirs: bool,
+    /// `--case-collision` / `--ignore-case` policy (oc-rsync extension).
+    pub(crate) case_collision: engine::CaseCollisionPolicy,
+    /// `--windows-names` policy (oc-rsync extension).
+    pub(crate) windows_names: engine::WindowsNamesPolicy,
+    /// `--order` transfer queue order (oc-rsync extension).
+    pub(crate) transfer_order: engine::TransferOrder,
     pub(crate) qsort: bool,
     /// Resolved tri-state for `--inc-recursive` / `--no-inc-recursive`.
     ///
@@ -130,16 +138,37 @@ pub(crate) struct ConfigInputs {
     pub(crate) partial: bool,
     pub(crate) preallocate: bool,
     pub(crate) fsync: bool,
+    /// `--fsync-dir` - parent directories are synced after each rename.
+    pub(crate) fsync_dir: bool,
     pub(crate) io_uring_policy: fast_io::IoUringPolicy,
     pub(crate) io_uring_depth: Option<u32>,
     pub(crate) zero_copy_policy: fast_io::ZeroCopyPolicy,
     /// `--parallel-delta-scan` - opt-in, default-off local sender-side delta
     /// scan across multiple cores. Local-only; never forwarded to a peer.
     pub(crate) parallel_delta_scan: bool,
+    /// `--mmap-source` - opt-in, default-off memory-mapped reads of large
+    /// delta sources. Local-only; never forwarded to a peer.
+    pub(crate) mmap_source: bool,
+    /// `--parallel-commit=N` - received files committed concurrently.
+    pub(crate) parallel_commit: usize,
+    /// `--direct-write` - received file data lands with `O_DIRECT`.
+    pub(crate) direct_write: bool,
+    /// `--drop-cache` - sent and committed files leave the page cache.
+    pub(crate) drop_cache: bool,
     pub(crate) cow_policy: fast_io::CowPolicy,
     pub(crate) partial_dir: Option<PathBuf>,
     pub(crate) temp_dir: Option<PathBuf>,
+    /// `--cache-dir=DIR` - destination scan cache directory.
+    pub(crate) cache_dir: Option<PathBuf>,
+    /// `--checkpoint=FILE` - confirmed-commit log for a later resume.
+    pub(crate) checkpoint: Option<PathBuf>,
+    /// `--resume=FILE` - checkpoint of the interrupted run to continue.
+    pub(crate) resume: Option<PathBuf>,
+    /// `--remaining-files=FILE` - hand-off list for a deadline-stopped run.
+    pub(crate) remaining_files: Option<PathBuf>,
     pub(crate) delay_updates: bool,
+    pub(crate) atomic: bool,
+    pub(crate) dest_format: DestinationFormat,
     pub(crate) link_dests: Vec<PathBuf>,
     pub(crate) remove_source_files: bool,
     /// `--remove-sent-files` - deprecated alias; forwarded verbatim on the wire.
@@ -185,6 +214,8 @@ pub(crate) struct ConfigInputs {
     pub(crate) itemize_changes: bool,
     pub(crate) out_format_template: Option<crate::frontend::out_format::OutFormat>,
     pub(crate) log_file_template: Option<crate::frontend::out_format::OutFormat>,
+    /// `--json` or `--json-log` is active, so per-file records are emitted.
+    pub(crate) json_records: bool,
     pub(crate) name_level: NameOutputLevel,
     pub(crate) iconv: IconvSetting,
     pub(crate) remote_shell: Option<OsString>,
@@ -287,6 +318,7 @@ pub(crate) fn build_base_config(mut inputs: ConfigInputs) -> ClientConfigBuilder
         .times(inputs.times)
         .atimes(inputs.atimes)
         .crtimes(inputs.crtimes)
+        .fileflags(inputs.fileflags)
         .modify_window(inputs.modify_window_setting)
         .omit_dir_times(inputs.omit_dir_times)
         .omit_link_times(inputs.omit_link_times)
@@ -322,6 +354,9 @@ pub(crate) fn build_base_config(mut inputs: ConfigInputs) -> ClientConfigBuilder
         .human_readable(inputs.human_readable)
         .mkpath(inputs.mkpath)
         .prune_empty_dirs(inputs.prune_empty_dirs)
+        .case_collision(inputs.case_collision)
+        .windows_names(inputs.windows_names)
+        .transfer_order(inputs.transfer_order)
         .qsort(inputs.qsort);
     // Only override the builder's upstream default when the user supplied
     // `--inc-recursive` or `--no-inc-recursive`. Mirrors upstream
@@ -338,14 +373,25 @@ pub(crate) fn build_base_config(mut inputs: ConfigInputs) -> ClientConfigBuilder
         .partial(inputs.partial)
         .preallocate(inputs.preallocate)
         .fsync(inputs.fsync)
+        .fsync_dir(inputs.fsync_dir)
         .io_uring_policy(inputs.io_uring_policy)
         .io_uring_depth(inputs.io_uring_depth)
         .zero_copy_policy(inputs.zero_copy_policy)
         .parallel_delta_scan(inputs.parallel_delta_scan)
+        .mmap_source(inputs.mmap_source)
+        .parallel_commit(inputs.parallel_commit)
+        .direct_write(inputs.direct_write)
+        .drop_cache(inputs.drop_cache)
         .cow_policy(inputs.cow_policy)
         .partial_directory(inputs.partial_dir.clone())
         .temp_directory(inputs.temp_dir.clone())
+        .cache_dir(inputs.cache_dir.clone())
+        .checkpoint(inputs.checkpoint.clone())
+        .resume(inputs.resume.clone())
+        .remaining_files(inputs.remaining_files.clone())
         .delay_updates(inputs.delay_updates)
+        .atomic(inputs.atomic)
+        .dest_format(inputs.dest_format)
         .extend_link_dests(inputs.link_dests.clone())
         .remove_source_files(inputs.remove_source_files)
         .remove_sent_files(inputs.remove_sent_files)
@@ -450,6 +496,7 @@ pub(crate) fn build_base_config(mut inputs: ConfigInputs) -> ClientConfigBuilder
     let force_event_collection = inputs.itemize_changes
         || inputs.out_format_template.is_some()
         || inputs.log_file_template.is_some()
+        || inputs.json_records
         || !matches!(inputs.name_level, NameOutputLevel::Disabled);
 
     builder = builder.files_from(inputs.files_from).from0(inputs.from0);
@@ -12,8 +12,9 @@ use logging_sink::MessageSink;
 use super::messages::fail_with_message;
 use crate::frontend::filter_rules::{
     FilterDirective, FilterOrderToken, append_apple_double_exclude_rules, append_cvs_exclude_rules,
-    append_filter_rules_from_files, apply_merge_directive, cvs_default_exclude_rules,
-    merge_directive_options, os_string_to_pattern, parse_filter_directive, parse_old_prefix_rule,
+    append_filter_rules_from_files, append_selinux_context_exclude_rules, apply_merge_directive,
+    cvs_default_exclude_rules, merge_directive_options, os_string_to_pattern,
+    parse_filter_directive, parse_old_prefix_rule,
 };
 
 /// Filter configuration supplied by the command line.
@@ -78,6 +79,9 @@ where
             FilterOrderToken::AppleDoubleSkip => {
                 append_apple_double_exclude_rules(&mut filter_rules)
             }
+            FilterOrderToken::NoSelinuxContext => {
+                append_selinux_context_exclude_rules(&mut filter_rules)
+            }
         };
         if let Err(message) = result {
             return Err(fail_with_message(message, stderr));
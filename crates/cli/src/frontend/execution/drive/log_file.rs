@@ -0,0 +1,90 @@
+#![deny(unsafe_code)]
+
+//! Line framing for the client `--log-file`.
+//!
+//! upstream: log.c:logit() writes every log-file line as
+//! `YYYY/MM/DD HH:MM:SS [pid] text`, independent of the verbosity routed to
+//! stdout, and log.c:log_exit() closes the run with either the
+//! `sent/received/total size` totals or the `rsync error:` line.
+
+use std::io::{self, Write};
+use std::time::SystemTime;
+
+use core::client::{ClientSummary, HumanReadableMode};
+
+use crate::LIST_TIMESTAMP_FORMAT;
+use crate::frontend::local_time::to_local;
+use crate::frontend::progress::format_size;
+
+/// Builds the `YYYY/MM/DD HH:MM:SS [pid] ` prefix for one log line.
+///
+/// upstream: log.c:283-288 - `strftime("%Y/%m/%d %H:%M:%S ")` in local time
+/// followed by `"[%d] "` with `getpid()`.
+pub(super) fn log_line_prefix(now: SystemTime, pid: u32) -> String {
+    let stamp = to_local(now)
+        .format(LIST_TIMESTAMP_FORMAT)
+        .unwrap_or_default();
+    format!("{stamp} [{pid}] ")
+}
+
+/// Writes each line of `rendered` to `writer` behind `prefix`.
+///
+/// A final line without a newline is terminated so the next record starts on
+/// its own line.
+pub(super) fn write_prefixed_lines<W: Write + ?Sized>(
+    writer: &mut W,
+    prefix: &str,
+    rendered: &[u8],
+) -> io::Result<()> {
+    for line in rendered.split_inclusive(|&byte| byte == b'\n') {
+        writer.write_all(prefix.as_bytes())?;
+        writer.write_all(line)?;
+        if !line.ends_with(b"\n") {
+            writer.write_all(b"\n")?;
+        }
+    }
+    Ok(())
+}
+
+/// Renders the closing totals line of a successful run.
+///
+/// upstream: log.c:log_exit() - `"sent %s bytes  received %s bytes  total
+/// size %s\n"` through `big_num()`, so `-h` applies as it does on stdout.
+pub(super) fn totals_line(summary: &ClientSummary, human_readable: HumanReadableMode) -> String {
+    format!(
+        "sent {} bytes  received {} bytes  total size {}\n",
+        format_size(summary.bytes_sent(), human_readable),
+        format_size(summary.bytes_received(), human_readable),
+        format_size(summary.total_source_bytes(), human_readable)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn prefix_has_timestamp_and_pid() {
+        let prefix = log_line_prefix(UNIX_EPOCH + Duration::from_secs(86_400 * 365), 4321);
+        assert!(prefix.ends_with(" [4321] "), "{prefix:?}");
+        let stamp = prefix.trim_end_matches(" [4321] ");
+        assert_eq!(stamp.len(), "1971/01/01 00:00:00".len(), "{stamp:?}");
+        assert_eq!(&stamp[4..5], "/");
+        assert_eq!(&stamp[10..11], " ");
+    }
+
+    #[test]
+    fn every_line_gets_the_prefix() {
+        let mut out = Vec::new();
+        write_prefixed_lines(&mut out, "P ", b"a\nb\nc").expect("write");
+        assert_eq!(out, b"P a\nP b\nP c\n");
+    }
+
+    #[test]
+    fn empty_render_writes_nothing() {
+        let mut out = Vec::new();
+        write_prefixed_lines(&mut out, "P ", b"").expect("write");
+        assert!(out.is_empty());
+    }
+}
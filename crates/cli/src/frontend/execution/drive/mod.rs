@@ -1,5 +1,6 @@
 mod config;
 mod filters;
+mod log_file;
 mod messages;
 mod metadata;
 mod module_listing;
@@ -7,6 +8,7 @@ mod options;
 mod summary;
 mod thread_tunables;
 mod validation;
+mod watch;
 #[cfg(test)]
 pub(crate) use validation::CONNECT_PROGRAM_DAEMON_ONLY_MESSAGE;
 mod workflow;
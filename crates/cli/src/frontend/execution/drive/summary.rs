@@ -2,6 +2,7 @@
 
 use std::fs::File;
 use std::io::{self, Write};
+use std::time::SystemTime;
 
 use core::{
     client::{
@@ -14,13 +15,15 @@ use logging::{InfoFlag, info_gte};
 use logging_sink::MessageSink;
 
 use crate::frontend::{
-    out_format::{OutFormat, OutFormatContext},
+    json_output::emit_json_summary,
+    out_format::{OutFormat, OutFormatContext, emit_out_format},
     progress::{
         LiveProgress, NameOutputLevel, ProgressMode, ProgressOutputConfig, StderrMode,
         emit_transfer_summary,
     },
 };
 
+use super::log_file::{log_line_prefix, totals_line, write_prefixed_lines};
 use super::messages::emit_message_with_fallback;
 use super::with_output_writer;
 
@@ -65,6 +68,32 @@ pub(crate) struct TransferExecutionInputs<'a> {
     /// filenames are escaped as `\#ooo` matching upstream log.c:filtered_fwrite.
     pub(crate) eight_bit_output: bool,
     pub(crate) log_file: Option<LogFileConfig>,
+    /// `--json`: replace the text summary on stdout with JSON-lines records.
+    pub(crate) json_output: bool,
+    /// `--json-log=FILE`: also append the JSON-lines records to this file.
+    pub(crate) json_log: Option<File>,
+}
+
+impl TransferExecutionInputs<'_> {
+    /// Returns these inputs driving `config` instead, for another run of the
+    /// same invocation (`--watch`). Log files are shared through duplicated
+    /// descriptors.
+    pub(crate) fn for_config(&self, config: ClientConfig) -> io::Result<Self> {
+        let log_file = match &self.log_file {
+            Some(log) => Some(LogFileConfig {
+                file: log.file.try_clone()?,
+                format: log.format.clone(),
+            }),
+            None => None,
+        };
+        let json_log = self.json_log.as_ref().map(File::try_clone).transpose()?;
+        Ok(Self {
+            config,
+            log_file,
+            json_log,
+            ..*self
+        })
+    }
 }
 
 /// Drives the client transfer and final summaries.
@@ -99,12 +128,16 @@ where
         name_overridden,
         eight_bit_output,
         log_file,
+        json_output,
+        mut json_log,
     } = inputs;
 
     // `StderrMode::All` is handled by the caller setting `msgs_to_stderr = true`.
     // `Client` mode applies to remote transfers only (server-side message routing);
     // for local transfers it behaves identically to `Errors`.
     let _ = stderr_mode;
+    // Live progress lines would interleave with the JSON records on stdout.
+    let requested_progress_mode = requested_progress_mode.filter(|_| !json_output);
 
     let mut live_progress = requested_progress_mode.map(|mode| {
         with_output_writer(stdout, stderr, msgs_to_stderr, |writer| {
@@ -189,30 +222,49 @@ where
                 .with_eight_bit_output(eight_bit_output)
                 .with_preserve_links(preserve_links)
                 .with_full_checksum(full_checksum_algorithm, always_checksum);
-            if let Err(error) = with_output_writer(stdout, stderr, msgs_to_stderr, |writer| {
-                emit_transfer_summary(
-                    &summary,
-                    verbosity,
-                    requested_progress_mode,
-                    stats_level,
-                    progress_rendered_live,
-                    list_only,
-                    dry_run,
-                    only_write_batch,
-                    out_format_template,
-                    &out_format_context,
-                    name_level,
-                    name_overridden,
-                    human_readable_mode,
-                    suppress_updated_only_totals,
-                    emit_flist_banner,
-                    show_copy_method,
-                    show_atimes,
-                    show_crtimes,
-                    eight_bit_output,
-                    writer,
-                )
-            }) {
+            let exit_code = summary.io_error_exit_code().unwrap_or(0);
+            if let Some(file) = json_log.as_mut()
+                && let Err(error) = emit_json_summary(&summary, is_sender, dry_run, exit_code, file)
+            {
+                let _ = with_output_writer(stdout, stderr, msgs_to_stderr, |writer| {
+                    writeln!(writer, "warning: failed to append to JSON log: {error}")
+                });
+            }
+            if json_output {
+                if let Err(error) =
+                    emit_json_summary(&summary, is_sender, dry_run, exit_code, stdout)
+                {
+                    let _ = writeln!(
+                        stderr.writer_mut(),
+                        "warning: failed to render JSON summary: {error}"
+                    );
+                }
+            } else if let Err(error) =
+                with_output_writer(stdout, stderr, msgs_to_stderr, |writer| {
+                    emit_transfer_summary(
+                        &summary,
+                        verbosity,
+                        requested_progress_mode,
+                        stats_level,
+                        progress_rendered_live,
+                        list_only,
+                        dry_run,
+                        only_write_batch,
+                        out_format_template,
+                        &out_format_context,
+                        name_level,
+                        name_overridden,
+                        human_readable_mode,
+                        suppress_updated_only_totals,
+                        emit_flist_banner,
+                        show_copy_method,
+                        show_atimes,
+                        show_crtimes,
+                        eight_bit_output,
+                        writer,
+                    )
+                })
+            {
                 let _ = with_output_writer(stdout, stderr, msgs_to_stderr, |writer| {
                     writeln!(
                         writer,
@@ -225,19 +277,14 @@ where
                 && let Err(error) = emit_log_output(EmitLogOutputParams {
                     summary: &summary,
                     log: &mut log,
-                    verbosity,
-                    stats_level,
                     list_only,
                     name_level,
-                    name_overridden,
                     human_readable_mode,
                     is_sender,
                     is_pull,
                     preserve_owner,
                     preserve_group,
                     itemize_repeated,
-                    show_atimes,
-                    show_crtimes,
                     eight_bit_output,
                     preserve_links,
                     full_checksum_algorithm,
@@ -253,7 +300,7 @@ where
             // its summary yet still owe a non-zero code (e.g. a receiver that
             // discarded a file because its output mkstemp() failed reports exit
             // 23 via MSG_ERROR_XFER). Honour it here instead of forcing 0.
-            summary.io_error_exit_code().unwrap_or(0)
+            exit_code
         }
         Err(error) => {
             if let Some(observer) = live_progress
@@ -265,6 +312,22 @@ where
             }
 
             let message: &Message = error.message();
+            // upstream: log.c:log_exit() records the error line in the log
+            // file as well as on stderr.
+            if let Some(mut log) = log_file {
+                let prefix = log_line_prefix(SystemTime::now(), std::process::id());
+                let _ =
+                    write_prefixed_lines(&mut log.file, &prefix, message.to_string().as_bytes())
+                        .and_then(|()| log.file.flush());
+            }
+            // The text diagnostic still goes to stderr for the operator; the
+            // JSON record carries the same message and exit code for tools.
+            if let Some(file) = json_log.as_mut() {
+                let _ = MessageSink::new(file).write_json(message);
+            }
+            if json_output {
+                let _ = MessageSink::new(&mut *stdout).write_json(message);
+            }
             emit_message_with_fallback(
                 message,
                 "rsync error: client functionality is unavailable in this build (code 1)",
@@ -279,11 +342,9 @@ where
 struct EmitLogOutputParams<'a> {
     summary: &'a ClientSummary,
     log: &'a mut LogFileConfig,
-    verbosity: u8,
-    stats_level: u8,
+    /// `--list-only` transfers nothing, so only the totals are logged.
     list_only: bool,
     name_level: NameOutputLevel,
-    name_overridden: bool,
     human_readable_mode: HumanReadableMode,
     /// Whether the local client is the sender. Threaded through so the
     /// itemize direction arrow matches upstream `log.c:701-704`.
@@ -296,15 +357,10 @@ struct EmitLogOutputParams<'a> {
     /// `-ii` (the `-i` flag repeated) - upstream `stdout_format_has_i > 1`.
     /// Forces unchanged itemize rows in the log file as it does on stdout.
     itemize_repeated: bool,
-    /// `-U`/`--atimes`: render the ATIME column in `--list-only` log output.
-    show_atimes: bool,
-    /// `--crtimes`: render the CRTIME column in `--list-only` log output.
-    show_crtimes: bool,
     /// `--8-bit-output` / `-8`: pass high-bit characters through without
     /// octal escaping in log-file output.
     eight_bit_output: bool,
-    /// `--links` / `-l`: whether symlink rows in `--list-only` log output get
-    /// the ` -> <target>` arrow (upstream: generator.c:1183).
+    /// `--links` / `-l`: whether `%L` renders the ` -> <target>` suffix.
     preserve_links: bool,
     /// Negotiated `%C` checksum algorithm (upstream: log.c:687-690).
     full_checksum_algorithm: StrongChecksumAlgorithm,
@@ -313,24 +369,24 @@ struct EmitLogOutputParams<'a> {
     always_checksum: bool,
 }
 
-/// Writes the transfer summary to the configured log file.
+/// Writes the per-file lines and closing totals to the configured log file.
+///
+/// Every line carries the upstream `YYYY/MM/DD HH:MM:SS [pid] ` prefix, and
+/// the per-file lines follow `--log-file-format` whatever the stdout
+/// verbosity - upstream logs each transferred item through `log_item(FLOG)`
+/// independently of `-v`.
 fn emit_log_output(params: EmitLogOutputParams<'_>) -> io::Result<()> {
     let EmitLogOutputParams {
         summary,
         log,
-        verbosity,
-        stats_level,
         list_only,
         name_level,
-        name_overridden,
         human_readable_mode,
         is_sender,
         is_pull,
         preserve_owner,
         preserve_group,
         itemize_repeated,
-        show_atimes,
-        show_crtimes,
         eight_bit_output,
         preserve_links,
         full_checksum_algorithm,
@@ -349,32 +405,13 @@ fn emit_log_output(params: EmitLogOutputParams<'_>) -> io::Result<()> {
         .with_eight_bit_output(eight_bit_output)
         .with_preserve_links(preserve_links)
         .with_full_checksum(full_checksum_algorithm, always_checksum);
-    // The FCLIENT "sending incremental file list" banner is stdout only;
-    // upstream's parallel "building file list" line (flist.c:2248) is an FLOG
-    // log-file message that a plain client without --log-file discards.
-    emit_transfer_summary(
-        summary,
-        verbosity,
-        None,
-        stats_level,
-        false,
-        list_only,
-        false, // dry_run
-        false, // only_write_batch
-        Some(&log.format),
-        &context,
-        name_level,
-        name_overridden,
-        human_readable_mode,
-        false,
-        false,
-        // The `Copy method` line is a stdout-only `--info=copy` nicety; keep it
-        // out of the log file.
-        false,
-        show_atimes,
-        show_crtimes,
-        eight_bit_output,
-        &mut log.file,
-    )?;
+    let mut rendered = Vec::new();
+    if !list_only {
+        emit_out_format(summary.events(), &log.format, &context, &mut rendered)?;
+    }
+    rendered.extend_from_slice(totals_line(summary, human_readable_mode).as_bytes());
+
+    let prefix = log_line_prefix(SystemTime::now(), std::process::id());
+    write_prefixed_lines(&mut log.file, &prefix, &rendered)?;
     log.file.flush()
 }
@@ -0,0 +1,66 @@
+#![deny(unsafe_code)]
+
+//! `--watch` loop: the initial transfer followed by one re-sync per batch of
+//! source changes (oc-rsync extension).
+
+use std::io::Write;
+use std::time::Duration;
+
+use core::client::watch::{DEFAULT_DEBOUNCE, SourceWatcher};
+use core::{exit_code::ExitCode, message::Role, rsync_error};
+use logging_sink::MessageSink;
+
+use super::messages::fail_with_message;
+use super::summary::{TransferExecutionInputs, execute_transfer};
+
+/// Runs the initial transfer, then re-syncs after every batch of changes
+/// until the process is interrupted.
+///
+/// Returns early only when the watch cannot be set up, the initial transfer
+/// fails, or change notification breaks down. A failed re-sync is reported
+/// like any transfer error and the loop keeps watching, so a file caught
+/// mid-write does not end the session.
+pub(super) fn execute_watch<Out, Err>(
+    stdout: &mut Out,
+    stderr: &mut MessageSink<Err>,
+    inputs: TransferExecutionInputs<'_>,
+    debounce: Option<Duration>,
+) -> i32
+where
+    Out: Write,
+    Err: Write,
+{
+    // Watch before the initial transfer so edits made while it runs are
+    // picked up by the first re-sync.
+    let mut watcher = match SourceWatcher::new(&inputs.config) {
+        Ok(watcher) => watcher,
+        Err(error) => return fail_with_message(error.message().clone(), stderr),
+    };
+    let debounce = debounce.unwrap_or(DEFAULT_DEBOUNCE);
+
+    let mut config = inputs.config.clone();
+    let mut initial = true;
+    loop {
+        let run = match inputs.for_config(config) {
+            Ok(run) => run,
+            Err(error) => {
+                let code = ExitCode::FileIo.as_i32();
+                let message = rsync_error!(code, "watch: failed to reopen log files: {error}")
+                    .with_role(Role::Client);
+                return fail_with_message(message, stderr);
+            }
+        };
+        let status = execute_transfer(stdout, stderr, run);
+        if initial && status != 0 {
+            return status;
+        }
+        initial = false;
+        let _ = stdout.flush();
+
+        let batch = match watcher.wait(debounce) {
+            Ok(batch) => batch,
+            Err(error) => return fail_with_message(error.message().clone(), stderr),
+        };
+        config = watcher.resync_config(&batch);
+    }
+}
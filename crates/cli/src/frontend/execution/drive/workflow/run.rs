@@ -10,7 +10,9 @@ use crate::frontend::execution::drive::metadata::MetadataSettings;
 use crate::frontend::execution::drive::module_listing::{
     ModuleListingInputs, maybe_handle_module_listing,
 };
-use crate::frontend::execution::drive::{config, filters, metadata, options, summary, validation};
+use crate::frontend::execution::drive::{
+    config, filters, metadata, options, summary, validation, watch as watch_mode,
+};
 use crate::frontend::log_format_has;
 use crate::frontend::outbuf::parse_outbuf_mode;
 use crate::frontend::progress::{ProgressOutputConfig, StderrMode};
@@ -123,6 +125,7 @@ where
         omit_link_times,
         atimes,
         crtimes,
+        fileflags,
         acls,
         excludes: _,
         includes: _,
@@ -135,11 +138,13 @@ where
         filter_order,
         cvs_exclude,
         apple_double_skip: _,
+        no_selinux_context: _,
         rsync_filter_shortcuts: _,
         files_from,
         from0,
         info,
         debug,
+        debug_protocol_dump,
         numeric_ids,
         hard_links,
         links,
@@ -169,6 +174,9 @@ where
         implied_dirs,
         mkpath,
         prune_empty_dirs,
+        case_collision,
+        windows_names,
+        transfer_order,
         verbosity,
         quiet,
         progress: initial_progress,
@@ -179,17 +187,33 @@ where
         partial,
         preallocate,
         fsync: fsync_option,
+        fsync_dir,
         io_uring_policy,
         io_uring_depth,
         zero_copy_policy,
         parallel_delta_scan,
+        mmap_source,
+        parallel_commit,
+        direct_write,
+        drop_cache,
         cow_policy,
         simd_override,
+        checksum_backend,
         delay_updates,
+        atomic,
+        dest_format,
+        watch,
+        watch_debounce,
         partial_dir,
         temp_dir,
+        cache_dir,
+        checkpoint,
+        resume,
+        remaining_files,
         log_file,
         log_file_format,
+        json,
+        json_log,
         write_batch,
         only_write_batch,
         read_batch,
@@ -254,6 +278,27 @@ where
         return fail_with_message(message, stderr);
     }
 
+    if let Some(backend) = checksum_backend
+        && let Err(error) = checksums::force_md5_backend(backend)
+    {
+        let message =
+            rsync_error!(1, format!("--checksum-backend: {error}")).with_role(Role::Client);
+        return fail_with_message(message, stderr);
+    }
+
+    // oc-rsync extension: `--debug-protocol-dump` captures the multiplexed
+    // frames this process exchanges; `cargo xtask trace-dump` renders them.
+    if let Some(path) = debug_protocol_dump.as_ref().map(PathBuf::from)
+        && let Err(error) = protocol::frame_dump::start(&path)
+    {
+        let message = rsync_error!(
+            1,
+            format!("failed to open protocol dump {}: {error}", path.display())
+        )
+        .with_role(Role::Client);
+        return fail_with_message(message, stderr);
+    }
+
     let password_file = password_file.map(PathBuf::from);
     let human_readable_setting = human_readable;
     let human_readable_mode = human_readable_setting.unwrap_or(HumanReadableMode::Grouped);
@@ -469,6 +514,7 @@ where
         options::SettingsOutcome::Proceed(settings) => *settings,
         options::SettingsOutcome::Exit(code) => return code,
     };
+    checksums::log_backend_selection();
 
     let log_file_path_buf = log_file_path.as_ref().map(PathBuf::from);
 
@@ -673,6 +719,21 @@ where
         }
     }
 
+    // oc-rsync extension: `--json-log` appends the same JSON-lines records
+    // `--json` prints, so it shares the `--log-file` open mode.
+    let mut json_log_file = None;
+    if let Some(path) = json_log.as_ref().map(PathBuf::from) {
+        match open_log_file(&path) {
+            Ok(file) => json_log_file = Some(file),
+            Err(error) => {
+                let message =
+                    rsync_error!(1, "failed to open JSON log {}: {error}", path.display())
+                        .with_role(Role::Client);
+                let _ = stderr.write(&message);
+            }
+        }
+    }
+
     // Build transfer operands early so we can check if this is a daemon transfer.
     // upstream: main.c:780-790 - source dir is chdir target, not a transfer source
     // `has_remote_operand` was computed above (protocol resolution needs it).
@@ -753,11 +814,11 @@ where
     // it is never forwarded. Capture the explicit bit before the OR.
     let list_only_arg = list_only;
     // upstream: options.c:2194-2195 - `if (argc < 2 && !read_batch && !am_server)
-    // list_only |= 1;`. A single remote source with no destination (e.g.
-    // `host::module` or `rsync://host/module`) implies list-only mode: list the
-    // module's contents instead of erroring "need source and destination".
-    let list_only =
-        list_only || (transfer_operands.len() == 1 && read_batch.is_none() && is_daemon_transfer);
+    // list_only |= 1;`. A single source with no destination implies list-only
+    // mode: a daemon module (`host::module`, `rsync://host/module`), an ssh
+    // source (`host:path`), or a local path is listed instead of erroring
+    // "need source and destination".
+    let list_only = list_only || (transfer_operands.len() == 1 && read_batch.is_none());
 
     // upstream: options.c:2187-2188 - relative_paths defaults to 1 when files_from
     let effective_relative = if files_from_active && relative.is_none() {
@@ -925,6 +986,7 @@ where
         // application.
         atimes: atimes.unwrap_or(0),
         crtimes: preserve_crtimes,
+        fileflags: fileflags.unwrap_or(false),
         modify_window_setting,
         omit_dir_times: omit_dir_times_setting,
         omit_link_times: omit_link_times_setting,
@@ -960,6 +1022,9 @@ where
         human_readable: human_readable_enabled,
         mkpath,
         prune_empty_dirs: prune_empty_dirs_flag,
+        case_collision: case_collision.unwrap_or_default(),
+        windows_names: windows_names.unwrap_or_default(),
+        transfer_order: transfer_order.unwrap_or_default(),
         qsort,
         inc_recursive_send: inc_recursive,
         verbosity,
@@ -970,14 +1035,25 @@ where
         partial,
         preallocate,
         fsync: fsync_flag,
+        fsync_dir,
         io_uring_policy,
         io_uring_depth,
         zero_copy_policy,
         parallel_delta_scan,
+        mmap_source,
+        parallel_commit: parallel_commit.unwrap_or(1),
+        direct_write,
+        drop_cache,
         cow_policy,
         partial_dir,
         temp_dir,
+        cache_dir,
+        checkpoint,
+        resume,
+        remaining_files,
         delay_updates,
+        atomic,
+        dest_format,
         link_dests,
         remove_source_files,
         remove_sent_files,
@@ -1007,6 +1083,7 @@ where
         itemize_changes,
         out_format_template: out_format_template.clone(),
         log_file_template,
+        json_records: json || json_log.is_some(),
         name_level,
         iconv: iconv_setting,
         remote_shell: parsed.remote_shell.clone(),
@@ -1050,6 +1127,12 @@ where
 
     let config = builder.build();
 
+    if watch && list_only {
+        let message =
+            rsync_error!(1, "--watch cannot be combined with --list-only").with_role(Role::Client);
+        return fail_with_message(message, stderr);
+    }
+
     // upstream: progress.c:234-238 checks `tcgetpgrp(STDOUT_FILENO)` to
     // suppress progress when the process is not in the foreground terminal
     // group. We detect terminal status on the output destination (stdout
@@ -1068,38 +1151,41 @@ where
         outbuf_mode,
     };
 
-    summary::execute_transfer(
-        stdout,
-        stderr,
-        summary::TransferExecutionInputs {
-            config,
-            msgs_to_stderr: msgs_to_stderr_enabled,
-            stderr_mode: stderr_mode_setting,
-            progress_mode,
-            progress_output_config,
-            human_readable_mode,
-            itemize_changes,
-            itemize_repeated,
-            stats_level,
-            verbosity,
-            list_only,
-            dry_run,
-            // `--only-write-batch` (upstream `write_batch < 0`) drives the
-            // `" (BATCH ONLY)"` speedup suffix in the summary trailer.
-            only_write_batch: only_write_batch.is_some(),
-            // `--info=copy` opts into the oc-rsync `Copy method` stats line.
-            show_copy_method,
-            // `-U`/`--atimes` and `--crtimes` add the ATIME/CRTIME columns to
-            // `--list-only` output (upstream: generator.c list_file_entry()).
-            show_atimes: preserve_atimes,
-            show_crtimes: preserve_crtimes,
-            out_format_template: out_format_template.as_ref(),
-            name_level,
-            name_overridden,
-            eight_bit_output,
-            log_file: log_file_for_local,
-        },
-    )
+    let inputs = summary::TransferExecutionInputs {
+        config,
+        msgs_to_stderr: msgs_to_stderr_enabled,
+        stderr_mode: stderr_mode_setting,
+        progress_mode,
+        progress_output_config,
+        human_readable_mode,
+        itemize_changes,
+        itemize_repeated,
+        stats_level,
+        verbosity,
+        list_only,
+        dry_run,
+        // `--only-write-batch` (upstream `write_batch < 0`) drives the
+        // `" (BATCH ONLY)"` speedup suffix in the summary trailer.
+        only_write_batch: only_write_batch.is_some(),
+        // `--info=copy` opts into the oc-rsync `Copy method` stats line.
+        show_copy_method,
+        // `-U`/`--atimes` and `--crtimes` add the ATIME/CRTIME columns to
+        // `--list-only` output (upstream: generator.c list_file_entry()).
+        show_atimes: preserve_atimes,
+        show_crtimes: preserve_crtimes,
+        out_format_template: out_format_template.as_ref(),
+        name_level,
+        name_overridden,
+        eight_bit_output,
+        log_file: log_file_for_local,
+        json_output: json,
+        json_log: json_log_file,
+    };
+    if watch {
+        watch_mode::execute_watch(stdout, stderr, inputs, watch_debounce)
+    } else {
+        summary::execute_transfer(stdout, stderr, inputs)
+    }
 }
 
 /// Resolves the effective `--old-args` setting from the CLI flag and env var.
@@ -37,6 +37,10 @@ pub(crate) struct DebugFlagSettings {
     pub(crate) clone: Option<u8>,
     pub(crate) sockopt: Option<u8>,
     pub(crate) iocp: Option<u8>,
+    // oc-specific checksum backend selection visibility.
+    pub(crate) checksum: Option<u8>,
+    // oc-specific request pipeline window sizing.
+    pub(crate) pipeline: Option<u8>,
     pub(crate) help_requested: bool,
 }
 
@@ -76,6 +80,8 @@ impl DebugFlagSettings {
             ("clone", self.clone),
             ("sockopt", self.sockopt),
             ("iocp", self.iocp),
+            ("checksum", self.checksum),
+            ("pipeline", self.pipeline),
         ]
         .into_iter()
         .filter_map(|(name, level)| level.filter(|&l| l > 0).map(|l| (name, l)))
@@ -112,6 +118,8 @@ impl DebugFlagSettings {
         self.clone = Some(level);
         self.sockopt = Some(level);
         self.iocp = Some(level);
+        self.checksum = Some(level);
+        self.pipeline = Some(level);
     }
 
     const fn disable_all(&mut self) {
@@ -143,6 +151,8 @@ impl DebugFlagSettings {
         self.clone = Some(0);
         self.sockopt = Some(0);
         self.iocp = Some(0);
+        self.checksum = Some(0);
+        self.pipeline = Some(0);
     }
 
     pub(super) fn apply(&mut self, token: &str, display: &str) -> Result<(), Message> {
@@ -202,6 +212,8 @@ impl DebugFlagSettings {
             "clone" => self.clone = Some(level),
             "sockopt" => self.sockopt = Some(level),
             "iocp" => self.iocp = Some(level),
+            "checksum" => self.checksum = Some(level),
+            "pipeline" => self.pipeline = Some(level),
             _ => return Err(debug_flag_error(display)),
         }
 
@@ -213,7 +225,7 @@ impl DebugFlagSettings {
     const KNOWN_FLAGS: &'static [&'static str] = &[
         "acl", "backup", "bind", "chdir", "connect", "cmd", "del", "deltasum", "dup", "exit",
         "filter", "flist", "fuzzy", "genr", "hash", "hlink", "iconv", "io", "nstr", "own", "proto",
-        "recv", "send", "time", "iouring", "clone", "sockopt", "iocp",
+        "recv", "send", "time", "iouring", "clone", "sockopt", "iocp", "checksum", "pipeline",
     ];
 
     pub(super) fn parse_flag_and_level<'a>(&self, input: &'a str) -> (&'a str, u8) {
@@ -326,8 +338,10 @@ Options added at each level of verbosity:\n\
 4) CMD2,DEL3,DELTASUM3,EXIT2,FLIST3,ICONV2,OWN2,PROTO,TIME2\n\
 5) CHDIR,DELTASUM4,FLIST4,FUZZY2,HASH,HLINK\n\
 \n\
-oc-rsync extensions (accelerated-I/O fallback visibility):\n\
+oc-rsync extensions (accelerated-I/O, checksum backend and pipeline visibility):\n\
 IOURING    Debug io_uring probe and dispatch-vs-fallback decisions\n\
 CLONE      Debug clonefile/reflink/copy_file_range CoW dispatch and fallback\n\
 SOCKOPT    Debug TCP/socket tuning apply-or-skip decisions\n\
-IOCP       Debug Windows IOCP dispatch and fallback\n";
+IOCP       Debug Windows IOCP dispatch and fallback\n\
+CHECKSUM   Debug checksum SIMD backend selection\n\
+PIPELINE   Debug adaptive request pipeline window sizing (levels 1-2)\n";
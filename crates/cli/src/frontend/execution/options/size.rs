@@ -1,41 +1,19 @@
 //! Size specification parsing for arguments with optional unit suffixes.
 //!
 //! Handles `--block-size`, `--max-size`, `--min-size`, and `--max-alloc` arguments.
-//! Supports binary (K/M/G/T/P = powers of 1024) and decimal (KB/MB/GB = powers of 1000)
-//! suffixes, as well as explicit binary suffixes (KiB/MiB/GiB).
-//! Mirrors upstream rsync's size parsing behavior.
+//! The suffix grammar lives in [`core::client::parse_size_limit`], shared with
+//! the server-side argument parser; this module renders its errors as
+//! flag-specific diagnostics and applies the per-option bounds.
 
 use std::ffi::OsStr;
 use std::num::NonZeroU32;
 
-use bandwidth::{SizeArgError, parse_size_arg};
 use core::{
+    client::{SizeLimitParseError, parse_size_limit},
     message::{Message, Role},
     rsync_error,
 };
 
-/// Error variants for size specification parsing.
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-pub(crate) enum SizeParseError {
-    /// Input string is empty or contains only a sign character.
-    Empty,
-    /// Input is a negative number.
-    Negative,
-    /// Input has invalid format or unrecognized suffix.
-    Invalid,
-    /// Parsed value exceeds representable range.
-    TooLarge,
-}
-
-impl From<SizeArgError> for SizeParseError {
-    fn from(error: SizeArgError) -> Self {
-        match error {
-            SizeArgError::Invalid => SizeParseError::Invalid,
-            SizeArgError::TooLarge => SizeParseError::TooLarge,
-        }
-    }
-}
-
 /// Parses a size argument with an optional unit suffix (K/M/G/T/P/E).
 ///
 /// The `flag` parameter is used in error messages (e.g. `"--max-size"`).
@@ -48,24 +26,24 @@ pub(crate) fn parse_size_limit_argument(value: &OsStr, flag: &str) -> Result<u64
         trimmed
     };
 
-    match parse_size_spec(trimmed) {
+    match parse_size_limit(trimmed) {
         Ok(limit) => Ok(limit),
-        Err(SizeParseError::Empty) => {
+        Err(SizeLimitParseError::Empty) => {
             Err(rsync_error!(1, format!("{flag} value must not be empty")).with_role(Role::Client))
         }
-        Err(SizeParseError::Negative) => Err(rsync_error!(
+        Err(SizeLimitParseError::Negative) => Err(rsync_error!(
             1,
             format!("invalid {flag} '{display}': size must be non-negative")
         )
         .with_role(Role::Client)),
-        Err(SizeParseError::Invalid) => Err(rsync_error!(
+        Err(SizeLimitParseError::Invalid) => Err(rsync_error!(
             1,
             format!(
                 "invalid {flag} '{display}': expected a size with an optional K/M/G/T/P suffix"
             )
         )
         .with_role(Role::Client)),
-        Err(SizeParseError::TooLarge) => Err(rsync_error!(
+        Err(SizeLimitParseError::TooLarge) => Err(rsync_error!(
             1,
             format!("invalid {flag} '{display}': size exceeds the supported range")
         )
@@ -201,34 +179,6 @@ pub(crate) fn parse_block_size_argument(value: &OsStr) -> Result<Option<NonZeroU
     ))
 }
 
-/// Parses a size specification string into a byte count.
-///
-/// Delegates the numeric-and-suffix grammar to the shared
-/// [`bandwidth::parse_size_arg`] (upstream's single `options.c:parse_size_arg()`)
-/// with the byte default suffix used by the size limits, and layers the CLI's
-/// sign diagnostics and 64-bit narrowing on top. Supports plain integers,
-/// fractional values (`.`/`,`), binary suffixes (K/M/G/T/P), decimal suffixes
-/// (KB/MB/...), explicit binary suffixes (KiB/...), the byte suffix `B`, and a
-/// single trailing `+1`/`-1` adjustment. A leading `+` is rejected and there is
-/// no exa (`E`) suffix, matching upstream's suffix switch which stops at `P`.
-fn parse_size_spec(text: &str) -> Result<u64, SizeParseError> {
-    if text.is_empty() {
-        return Err(SizeParseError::Empty);
-    }
-
-    // upstream: options.c:parse_size_arg() never strips a leading '+', so
-    // "+100" is rejected. A leading '-' is a negative size, which we reject
-    // with a dedicated diagnostic rather than a generic parse error.
-    let unsigned = match text.strip_prefix('-') {
-        Some("") => return Err(SizeParseError::Empty),
-        Some(_) => return Err(SizeParseError::Negative),
-        None => text,
-    };
-
-    let parsed = parse_size_arg(unsigned, b'b').map_err(SizeParseError::from)?;
-    u64::try_from(parsed.bytes).map_err(|_| SizeParseError::TooLarge)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -238,179 +188,6 @@ mod tests {
         OsString::from(s)
     }
 
-    #[test]
-    fn parse_size_spec_empty() {
-        assert_eq!(parse_size_spec(""), Err(SizeParseError::Empty));
-    }
-
-    #[test]
-    fn parse_size_spec_just_sign() {
-        // upstream: options.c:parse_size_arg() rejects a bare leading '+'.
-        assert_eq!(parse_size_spec("+"), Err(SizeParseError::Invalid));
-        assert_eq!(parse_size_spec("-"), Err(SizeParseError::Empty));
-    }
-
-    #[test]
-    fn parse_size_spec_negative() {
-        assert_eq!(parse_size_spec("-100"), Err(SizeParseError::Negative));
-        assert_eq!(parse_size_spec("-1K"), Err(SizeParseError::Negative));
-    }
-
-    #[test]
-    fn parse_size_spec_plain_number() {
-        assert_eq!(parse_size_spec("0"), Ok(0));
-        assert_eq!(parse_size_spec("1"), Ok(1));
-        assert_eq!(parse_size_spec("100"), Ok(100));
-        assert_eq!(parse_size_spec("12345"), Ok(12345));
-    }
-
-    #[test]
-    fn parse_size_spec_leading_plus_rejected() {
-        // upstream rsync rejects a leading '+' for size args: `--max-size=+100
-        // is invalid`. Only bare digits (optionally with a suffix) are valid.
-        assert_eq!(parse_size_spec("+100"), Err(SizeParseError::Invalid));
-        assert_eq!(parse_size_spec("+1K"), Err(SizeParseError::Invalid));
-    }
-
-    #[test]
-    fn parse_size_spec_trailing_adjustment() {
-        // upstream: a single trailing "+1"/"-1" adjusts the byte count so that
-        // "--max-size=1K-1" (1023) or "1K+1" (1025) can target a boundary.
-        assert_eq!(parse_size_spec("1K-1"), Ok(1023));
-        assert_eq!(parse_size_spec("1K+1"), Ok(1025));
-        assert_eq!(parse_size_spec("1-1"), Ok(0));
-        assert_eq!(parse_size_spec("1KB-1"), Ok(999));
-        assert_eq!(parse_size_spec("1.5K-1"), Ok(1535));
-    }
-
-    #[test]
-    fn parse_size_spec_rejects_non_unit_adjustment() {
-        // Only exactly "+1"/"-1" is accepted; anything else is invalid, and a
-        // "-1" that would drive the size negative is rejected too.
-        assert_eq!(parse_size_spec("1K-2"), Err(SizeParseError::Invalid));
-        assert_eq!(parse_size_spec("1K+2"), Err(SizeParseError::Invalid));
-        assert_eq!(parse_size_spec("1K-10"), Err(SizeParseError::Invalid));
-        assert_eq!(parse_size_spec("1K-0"), Err(SizeParseError::Invalid));
-        assert_eq!(parse_size_spec("1K+0"), Err(SizeParseError::Invalid));
-        assert_eq!(parse_size_spec("1K-1x"), Err(SizeParseError::Invalid));
-        assert_eq!(parse_size_spec("0-1"), Err(SizeParseError::TooLarge));
-    }
-
-    #[test]
-    fn parse_size_spec_kibibytes() {
-        assert_eq!(parse_size_spec("1K"), Ok(1024));
-        assert_eq!(parse_size_spec("1k"), Ok(1024));
-        assert_eq!(parse_size_spec("2K"), Ok(2048));
-        assert_eq!(parse_size_spec("10K"), Ok(10240));
-    }
-
-    #[test]
-    fn parse_size_spec_kilobytes_decimal() {
-        assert_eq!(parse_size_spec("1KB"), Ok(1000));
-        assert_eq!(parse_size_spec("1Kb"), Ok(1000));
-        assert_eq!(parse_size_spec("2KB"), Ok(2000));
-    }
-
-    #[test]
-    fn parse_size_spec_kilobytes_binary_explicit() {
-        assert_eq!(parse_size_spec("1KiB"), Ok(1024));
-        assert_eq!(parse_size_spec("1kib"), Ok(1024));
-    }
-
-    #[test]
-    fn parse_size_spec_mebibytes() {
-        assert_eq!(parse_size_spec("1M"), Ok(1024 * 1024));
-        assert_eq!(parse_size_spec("1m"), Ok(1024 * 1024));
-    }
-
-    #[test]
-    fn parse_size_spec_megabytes_decimal() {
-        assert_eq!(parse_size_spec("1MB"), Ok(1000 * 1000));
-    }
-
-    #[test]
-    fn parse_size_spec_gibibytes() {
-        assert_eq!(parse_size_spec("1G"), Ok(1024 * 1024 * 1024));
-    }
-
-    #[test]
-    fn parse_size_spec_gigabytes_decimal() {
-        assert_eq!(parse_size_spec("1GB"), Ok(1000 * 1000 * 1000));
-    }
-
-    #[test]
-    fn parse_size_spec_tebibytes() {
-        assert_eq!(parse_size_spec("1T"), Ok(1024u64.pow(4)));
-    }
-
-    #[test]
-    fn parse_size_spec_pebibytes() {
-        assert_eq!(parse_size_spec("1P"), Ok(1024u64.pow(5)));
-    }
-
-    #[test]
-    fn parse_size_spec_exa_suffix_rejected() {
-        // upstream's suffix switch stops at 'p'/'P'; there is no exa suffix.
-        assert_eq!(parse_size_spec("1E"), Err(SizeParseError::Invalid));
-        assert_eq!(parse_size_spec("1e"), Err(SizeParseError::Invalid));
-    }
-
-    #[test]
-    fn parse_size_spec_bytes_suffix() {
-        assert_eq!(parse_size_spec("100B"), Ok(100));
-        assert_eq!(parse_size_spec("100b"), Ok(100));
-    }
-
-    #[test]
-    fn parse_size_spec_fractional() {
-        assert_eq!(parse_size_spec("1.5K"), Ok(1536));
-        assert_eq!(parse_size_spec("2.5M"), Ok(2621440));
-    }
-
-    #[test]
-    fn parse_size_spec_fractional_comma() {
-        assert_eq!(parse_size_spec("1,5K"), Ok(1536));
-    }
-
-    #[test]
-    fn parse_size_spec_invalid_suffix() {
-        assert_eq!(parse_size_spec("100X"), Err(SizeParseError::Invalid));
-        assert_eq!(parse_size_spec("100Q"), Err(SizeParseError::Invalid));
-    }
-
-    #[test]
-    fn parse_size_spec_invalid_format() {
-        assert_eq!(parse_size_spec("abc"), Err(SizeParseError::Invalid));
-        assert_eq!(parse_size_spec("."), Err(SizeParseError::Invalid));
-        assert_eq!(parse_size_spec(","), Err(SizeParseError::Invalid));
-    }
-
-    #[test]
-    fn parse_size_spec_incomplete_binary_suffix() {
-        assert_eq!(parse_size_spec("1Ki"), Err(SizeParseError::Invalid));
-    }
-
-    #[test]
-    fn size_parse_error_eq() {
-        assert_eq!(SizeParseError::Empty, SizeParseError::Empty);
-        assert_eq!(SizeParseError::Negative, SizeParseError::Negative);
-        assert_eq!(SizeParseError::Invalid, SizeParseError::Invalid);
-        assert_eq!(SizeParseError::TooLarge, SizeParseError::TooLarge);
-    }
-
-    #[test]
-    fn size_parse_error_ne() {
-        assert_ne!(SizeParseError::Empty, SizeParseError::Negative);
-        assert_ne!(SizeParseError::Invalid, SizeParseError::TooLarge);
-    }
-
-    #[test]
-    fn size_parse_error_clone() {
-        let err = SizeParseError::Empty;
-        let cloned = err;
-        assert_eq!(err, cloned);
-    }
-
     #[test]
     fn parse_size_limit_argument_valid() {
         assert_eq!(
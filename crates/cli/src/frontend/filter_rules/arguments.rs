@@ -28,6 +28,8 @@ pub(crate) enum FilterOrderToken {
     CvsExclude,
     /// `--apple-double-skip`
     AppleDoubleSkip,
+    /// `--no-selinux-context`
+    NoSelinuxContext,
 }
 
 /// Builds the filter directive stream in true command-line (argv) order.
@@ -106,6 +108,10 @@ pub(crate) fn build_filter_order(matches: &ArgMatches, args: &[OsString]) -> Vec
             tokens.push(FilterOrderToken::AppleDoubleSkip);
             continue;
         }
+        if text == "--no-selinux-context" {
+            tokens.push(FilterOrderToken::NoSelinuxContext);
+            continue;
+        }
 
         // Short-option cluster: `-F` and `-C` may ride alongside other flags.
         // upstream: options.c:1605-1608 - each `-F` expands to a filter
@@ -6,6 +6,7 @@ mod cvs;
 mod directive;
 mod merge;
 mod parsing;
+mod selinux;
 mod sources;
 
 pub(crate) use apple_double::append_apple_double_exclude_rules;
@@ -14,6 +15,7 @@ pub(crate) use cvs::{append_cvs_exclude_rules, cvs_default_exclude_rules};
 pub(crate) use directive::{FilterDirective, merge_directive_options, os_string_to_pattern};
 pub(crate) use merge::apply_merge_directive;
 pub(crate) use parsing::{parse_filter_directive, parse_old_prefix_rule};
+pub(crate) use selinux::append_selinux_context_exclude_rules;
 pub(crate) use sources::append_filter_rules_from_files;
 
 #[cfg(test)]
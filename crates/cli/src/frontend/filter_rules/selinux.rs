@@ -0,0 +1,56 @@
+//! SELinux label exclusion rule for `--no-selinux-context`.
+//!
+//! `--no-selinux-context` is an oc-rsync extension and a shorthand for
+//! `--filter='-x security.selinux'`: an xattr-only exclude that keeps `-X` from
+//! copying SELinux labels while leaving every other `security.*` attribute,
+//! such as `security.capability`, in the transfer. Useful when the destination
+//! runs a different policy and should assign its own default labels.
+//!
+//! See [`crate::frontend::defaults::SELINUX_CONTEXT_XATTR_PATTERNS`] for the
+//! authoritative pattern list.
+
+use core::client::FilterRuleSpec;
+use core::message::Message;
+
+use crate::frontend::defaults::SELINUX_CONTEXT_XATTR_PATTERNS;
+
+/// Appends the xattr-only SELinux label exclusion rules to the destination
+/// chain.
+///
+/// The function is infallible but returns [`Result`] to keep its signature
+/// aligned with the other filter-producing options.
+pub(crate) fn append_selinux_context_exclude_rules(
+    destination: &mut Vec<FilterRuleSpec>,
+) -> Result<(), Message> {
+    for pattern in SELINUX_CONTEXT_XATTR_PATTERNS {
+        destination.push(FilterRuleSpec::exclude((*pattern).to_owned()).with_xattr_only(true));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::client::FilterRuleKind;
+
+    #[test]
+    fn append_selinux_context_adds_xattr_only_exclude() {
+        let mut rules = Vec::new();
+        append_selinux_context_exclude_rules(&mut rules).unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].kind(), FilterRuleKind::Exclude);
+        assert_eq!(rules[0].pattern(), "security.selinux");
+        assert!(rules[0].is_xattr_only());
+    }
+
+    #[test]
+    fn append_selinux_context_leaves_capabilities_alone() {
+        let mut rules = Vec::new();
+        append_selinux_context_exclude_rules(&mut rules).unwrap();
+        assert!(
+            rules
+                .iter()
+                .all(|rule| !"security.capability".starts_with(rule.pattern()))
+        );
+    }
+}
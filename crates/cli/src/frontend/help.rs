@@ -76,6 +76,7 @@ pub(super) fn help_text(program_name: ProgramName) -> String {
             "      --no-hard-links  Disable hard link preservation.\n",
             "  -C, --cvs-exclude  Auto-ignore files using CVS-style ignore rules.\n",
             "      --apple-double-skip  Skip macOS AppleDouble (._foo) sidecar files.\n",
+            "      --no-selinux-context  Do not copy SELinux labels (security.selinux) under --xattrs.\n",
             "      --filter=RULE  Apply filter RULE (supports '+' include, '-' exclude, '!' clear, 'include PATTERN', 'exclude PATTERN', 'show PATTERN'/'S PATTERN', 'hide PATTERN'/'H PATTERN', 'protect PATTERN'/'P PATTERN', 'risk PATTERN'/'R PATTERN', 'exclude-if-present=FILE', 'merge[,MODS] FILE' or '.[,MODS] FILE' with MODS drawn from '+', '-', 'C', 'e', 'n', 'w', 's', 'r', 'p', '/', and 'dir-merge[,MODS] FILE' or ':[,MODS] FILE' with MODS drawn from '+', '-', 'n', 'e', 'w', 's', 'r', 'p', '/', and 'C').\n",
             "  -F            Alias for per-directory .rsync-filter handling (repeat to also load receiver-side files).\n",
             "      --files-from=FILE  Read additional source operands from FILE.\n",
@@ -106,6 +107,7 @@ pub(super) fn help_text(program_name: ProgramName) -> String {
             "      --no-iconv    Disable charset conversion.\n",
             "      --info=FLAGS  Adjust informational messages; use --info=help for details.\n",
             "      --debug=FLAGS  Adjust diagnostic output; use --debug=help for details.\n",
+            "      --debug-protocol-dump=FILE  Record every multiplexed protocol frame to FILE as JSON lines.\n",
             "  -v, --verbose    Increase verbosity; repeat for more detail.\n",
             "      --no-verbose  Disable verbosity (equivalent to --quiet).\n",
             "  -R, --relative   Preserve source path components relative to the current directory.\n",
@@ -119,6 +121,10 @@ pub(super) fn help_text(program_name: ProgramName) -> String {
             "      --old-dirs, --old-d  Transfer only top-level entries (subdirectories come across empty).\n",
             "  -m, --prune-empty-dirs  Skip creating directories that remain empty after filters.\n",
             "      --no-prune-empty-dirs  Disable pruning of empty directories.\n",
+            "      --case-collision=MODE  Detect names that collide after case folding (off, warn, rename).\n",
+            "      --ignore-case  Warn about names that collide on a case-insensitive destination.\n",
+            "      --windows-names=MODE  Handle names invalid on Windows (off, sanitize, skip, fail).\n",
+            "      --order=MODE  Queue regular files for transfer by name, mtime, or size.\n",
             "      --progress   Show progress information during transfers.\n",
             "      --no-progress  Disable progress reporting.\n",
             "      --msgs2stderr  Send messages to standard error instead of standard output.\n",
@@ -133,10 +139,20 @@ pub(super) fn help_text(program_name: ProgramName) -> String {
             "      --no-partial Discard partially transferred files on errors.\n",
             "      --partial-dir=DIR  Store partially transferred files in DIR.\n",
             "      --temp-dir=DIR  Store temporary files in DIR while transferring.\n",
+            "      --cache-dir=DIR  Keep a destination scan cache in DIR so --checksum skips re-reading unchanged destination files.\n",
+            "      --checkpoint=FILE  Periodically record committed files in FILE so an interrupted transfer can be continued with --resume.\n",
+            "      --resume=FILE  Skip files the checkpoint in FILE records as already committed and unchanged since.\n",
+            "      --remaining-files=FILE  When --stop-at/--stop-after ends the transfer, list the paths not yet transferred in FILE for --files-from.\n",
             "      --log-file=FILE  Write transfer events to FILE.\n",
             "      --log-file-format=FORMAT  Customise entries written via --log-file.\n",
+            "      --json       Emit per-file events, errors, and the final summary as JSON lines.\n",
+            "      --json-log=FILE  Append the JSON-lines transfer records to FILE.\n",
             "      --delay-updates  Put completed updates in place after transfers finish.\n",
             "      --no-delay-updates  Disable delayed updates.\n",
+            "      --atomic    Swap the finished destination into place in one step (local copies only).\n",
+            "      --dest-format=FORMAT  Write the destination as dir (default), tar or tar.zst.\n",
+            "      --watch     Keep re-syncing local sources as they change (inotify on Linux, 1s polling elsewhere).\n",
+            "      --watch-debounce=MSEC  Quiet period before each --watch re-sync (default 500).\n",
             "  -W, --whole-file  Copy files without using the delta-transfer algorithm.\n",
             "      --no-whole-file  Enable the delta-transfer algorithm (disable whole-file copies).\n",
             "      --xxh64-dedup  Internal-only: xxh64-hash source and existing destination before computing a delta; matching digests bypass delta computation. Off by default.\n",
@@ -147,6 +163,7 @@ pub(super) fn help_text(program_name: ProgramName) -> String {
             "      --append-verify  Append data while verifying that existing bytes match the sender.\n",
             "      --preallocate  Preallocate destination files before writing.\n",
             "      --fsync    Fsync updated files after writing completes.\n",
+            "      --fsync-dir  Fsync each destination directory after a file is renamed into it, so the new entry survives a crash.\n",
             "      --io-uring   Force io_uring for file I/O (policy=enabled); error if unavailable. Default policy is auto: probe kernel and fall back to standard I/O.\n",
             "      --no-io-uring  Disable io_uring (policy=disabled); always use standard buffered I/O even when the kernel supports io_uring.\n",
             "      --no-io-uring-sqpoll  Keep io_uring on but suppress IORING_SETUP_SQPOLL (policy=sqpoll-off). For rootless containers and K8s pods that cannot grant CAP_SYS_NICE.\n",
@@ -154,12 +171,17 @@ pub(super) fn help_text(program_name: ProgramName) -> String {
             "      --io-uring-status  Print the io_uring capability matrix and exit.\n",
             "      --lsm-status  Print the Linux Security Module diagnostic (active LSMs, Landlock, seccomp, io_uring SQPOLL) and exit.\n",
             "      --simd=LEVEL Force the SIMD level used by checksum dispatch (auto, avx512, avx2, sse4, neon, none).\n",
+            "      --checksum-backend=BACKEND Pin MD5 batch hashing to one backend; fail if the CPU lacks it.\n",
             "      --cow        Allow copy-on-write reflinks for whole-file copies (default).\n",
             "      --no-cow     Disable copy-on-write reflinks; always use the portable std::fs::copy fallback.\n",
             "      --reflink=MODE Copy-on-write reflink policy (auto, always, never).\n",
             "      --zero-copy  Allow I/O-level zero-copy (sendfile, splice, copy_file_range; io_uring SEND_ZC only when built with the iouring-send-zc cargo feature, otherwise downgrades to plain io_uring SEND) when supported by the kernel. This is the default (policy=auto/enabled).\n",
             "      --no-zero-copy  Disable I/O-level zero-copy; route through portable userspace read/write loops. Does not affect filesystem-level reflink/CoW cloning.\n",
             "      --parallel-delta-scan  Opt-in: scan a large file's delta across multiple cores (sender side). Only engages for large, duplicate-free basis files (duplicate-content basis files fall back to the sequential scan). Reconstruction and matched/literal stats are unaffected; the literal-token wire framing may differ by a few bytes at a range boundary. Local-only, never forwarded to a remote peer. Default off.\n",
+            "      --mmap-source  Opt-in: scan large delta sources through a memory mapping instead of buffered reads (sender side). A source truncated during the scan raises SIGBUS, so this is off by default. Local-only, never forwarded to a remote peer.\n",
+            "      --parallel-commit=N  Commit up to N received files to disk concurrently (receiver side); delta reconstruction stays sequential and results are still reported in file-list order. Default 1.\n",
+            "      --direct-write  Write received file data with O_DIRECT, bypassing the page cache (Linux, receiver side); falls back to buffered writes where the filesystem lacks support.\n",
+            "      --drop-cache    Evict each file from the page cache once it has been sent or committed (Linux).\n",
             "      --inplace    Write updated data directly to destination files.\n",
             "      --no-inplace Use temporary files when updating regular files.\n",
             "  -h, --human-readable  Output numbers in a human-readable format.\n",
@@ -198,6 +220,8 @@ pub(super) fn help_text(program_name: ProgramName) -> String {
             "      --no-omit-dir-times  Preserve directory modification times.\n",
             "      --omit-link-times  Skip preserving symlink modification times.\n",
             "      --no-omit-link-times  Preserve symlink modification times.\n",
+            "      --fileflags  Preserve file flags (chflags) on macOS and FreeBSD.\n",
+            "      --no-fileflags  Disable file flag preservation.\n",
             "  -A, --acls      Preserve POSIX ACLs when supported.\n",
             "      --no-acls   Disable POSIX ACL preservation.\n",
             "  -X, --xattrs     Preserve extended attributes when supported.\n",
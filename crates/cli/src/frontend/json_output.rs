@@ -0,0 +1,166 @@
+#![deny(unsafe_code)]
+
+//! JSON-lines rendering of transfer results for `--json` / `--json-log`.
+//!
+//! oc-rsync extension. Instead of the upstream itemize, `--out-format`, and
+//! `--stats` text, every per-file event becomes one `"type":"file"` record,
+//! followed by a single `"type":"summary"` record. A failed transfer emits its
+//! diagnostic through [`logging_sink::MessageSink::write_json`], giving an
+//! `"type":"error"` record with the rsync exit code.
+
+use std::io::{self, Write};
+
+use core::client::{ClientEntryKind, ClientEvent, ClientEventKind, ClientSummary};
+use logging_sink::JsonLine;
+
+use crate::frontend::out_format::itemize_string;
+
+/// Returns the stable `action` name recorded for an event kind.
+const fn action_name(kind: &ClientEventKind) -> &'static str {
+    match kind {
+        ClientEventKind::DataCopied => "transferred",
+        ClientEventKind::ReferenceCopied => "copied_from_reference",
+        ClientEventKind::MetadataReused => "uptodate",
+        ClientEventKind::HardLink => "hard_linked",
+        ClientEventKind::SymlinkCopied => "symlink",
+        ClientEventKind::FifoCopied => "fifo",
+        ClientEventKind::DeviceCopied => "device",
+        ClientEventKind::DirectoryCreated => "directory",
+        ClientEventKind::SkippedExisting => "skipped_existing",
+        ClientEventKind::SkippedMissingDestination => "skipped_missing_destination",
+        ClientEventKind::SkippedNewerDestination => "skipped_newer_destination",
+        ClientEventKind::SkippedOverMaxSize => "skipped_over_max_size",
+        ClientEventKind::SkippedUnderMinSize => "skipped_under_min_size",
+        ClientEventKind::SkippedNonRegular => "skipped_non_regular",
+        ClientEventKind::SkippedDirectory => "skipped_directory",
+        ClientEventKind::SkippedUnsafeSymlink => "skipped_unsafe_symlink",
+        ClientEventKind::SkippedMountPoint => "skipped_mount_point",
+        ClientEventKind::EntryDeleted => "deleted",
+        ClientEventKind::SourceRemoved => "source_removed",
+    }
+}
+
+/// Returns the `file_type` name for an entry kind.
+const fn entry_kind_name(kind: ClientEntryKind) -> &'static str {
+    match kind {
+        ClientEntryKind::File => "file",
+        ClientEntryKind::Directory => "directory",
+        ClientEntryKind::Symlink => "symlink",
+        ClientEntryKind::Fifo => "fifo",
+        ClientEntryKind::CharDevice => "char_device",
+        ClientEntryKind::BlockDevice => "block_device",
+        ClientEntryKind::Socket => "socket",
+        ClientEntryKind::Other => "other",
+    }
+}
+
+/// Builds the `"type":"file"` record for one event.
+fn file_record(event: &ClientEvent, is_sender: bool) -> JsonLine {
+    let mut line = JsonLine::new("file")
+        .string("path", &event.relative_path().to_string_lossy())
+        .string("action", action_name(event.kind()))
+        .string("itemize", &itemize_string(event, is_sender));
+    if let Some(metadata) = event.metadata() {
+        line = line
+            .string("file_type", entry_kind_name(metadata.kind()))
+            .unsigned("size", metadata.length());
+    } else if event.is_directory() {
+        line = line.string("file_type", entry_kind_name(ClientEntryKind::Directory));
+    }
+    line.unsigned("bytes_transferred", event.bytes_transferred())
+        .boolean("created", event.was_created())
+        .boolean("uptodate", event.is_uptodate())
+}
+
+/// Builds the closing `"type":"summary"` record.
+fn summary_record(summary: &ClientSummary, dry_run: bool, exit_code: i32) -> JsonLine {
+    JsonLine::new("summary")
+        .signed("exit_code", i64::from(exit_code))
+        .boolean("dry_run", dry_run)
+        .unsigned("files_total", summary.regular_files_total())
+        .unsigned("files_transferred", summary.files_copied())
+        .unsigned("files_matched", summary.regular_files_matched())
+        .unsigned("directories_created", summary.directories_created())
+        .unsigned("deleted", summary.items_deleted())
+        .unsigned("total_size", summary.total_source_bytes())
+        .unsigned("transferred_size", summary.transferred_file_size())
+        .unsigned("literal_bytes", summary.bytes_copied())
+        .unsigned("matched_bytes", summary.matched_bytes())
+        .unsigned("bytes_sent", summary.bytes_sent())
+        .unsigned("bytes_received", summary.bytes_received())
+        .seconds(
+            "elapsed_seconds",
+            summary.wall_clock_elapsed().max(summary.total_elapsed()),
+        )
+}
+
+/// Writes one record per event followed by the summary record.
+pub(crate) fn emit_json_summary<W: Write + ?Sized>(
+    summary: &ClientSummary,
+    is_sender: bool,
+    dry_run: bool,
+    exit_code: i32,
+    writer: &mut W,
+) -> io::Result<()> {
+    for event in summary.events() {
+        file_record(event, is_sender).write_to(writer)?;
+    }
+    summary_record(summary, dry_run, exit_code).write_to(writer)?;
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use engine::local_copy::LocalCopyChangeSet;
+    use std::path::PathBuf;
+
+    fn render(summary: &ClientSummary) -> Vec<String> {
+        let mut output = Vec::new();
+        emit_json_summary(summary, false, false, 0, &mut output).expect("emit");
+        String::from_utf8(output)
+            .expect("utf8")
+            .lines()
+            .map(str::to_owned)
+            .collect()
+    }
+
+    #[test]
+    fn empty_summary_emits_only_the_summary_record() {
+        let lines = render(&ClientSummary::default());
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].starts_with(r#"{"type":"summary","exit_code":0,"dry_run":false,"#));
+    }
+
+    #[test]
+    fn transferred_file_carries_itemize_and_size() {
+        let event = ClientEvent::for_test(
+            PathBuf::from("dir/new \"file\".txt"),
+            ClientEventKind::DataCopied,
+            true,
+            Some(ClientEvent::test_metadata(ClientEntryKind::File)),
+            LocalCopyChangeSet::new(),
+        );
+        let line = file_record(&event, false).finish();
+        assert!(line.starts_with(
+            r#"{"type":"file","path":"dir/new \"file\".txt","action":"transferred","#
+        ));
+        assert!(line.contains(r#""itemize":">f+++++++++""#));
+        assert!(line.contains(r#""file_type":"file""#));
+        assert!(line.contains(r#""created":true"#));
+    }
+
+    #[test]
+    fn deleted_entry_without_metadata_omits_file_type() {
+        let event = ClientEvent::for_test(
+            PathBuf::from("gone"),
+            ClientEventKind::EntryDeleted,
+            false,
+            None,
+            LocalCopyChangeSet::new(),
+        );
+        let line = file_record(&event, false).finish();
+        assert!(line.contains(r#""action":"deleted","itemize":"*deleting  ""#));
+        assert!(!line.contains("file_type"));
+    }
+}
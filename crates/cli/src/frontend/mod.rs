@@ -118,8 +118,13 @@ mod help;
 pub mod info_output;
 /// Upstream rsync `--itemize-changes` (`-i`) output format.
 pub mod itemize;
+mod json_output;
 mod local_time;
 mod lsm_status;
+#[cfg(all(target_os = "linux", feature = "fuse"))]
+pub(crate) mod mount;
+#[cfg(feature = "otel")]
+mod otel;
 mod out_format;
 pub(crate) mod password;
 /// Progress and verbose output helpers extracted from the CLI front-end.
@@ -258,6 +263,10 @@ where
     Out: Write,
     Err: Write,
 {
+    // Held until the run returns so queued spans are flushed on exit.
+    #[cfg(feature = "otel")]
+    let _otel = otel::install_from_env();
+
     let mut args: Vec<OsString> = arguments.into_iter().map(Into::into).collect();
     if args.is_empty() {
         args.push(OsString::from(ProgramName::OcRsync.as_str()));
@@ -0,0 +1,164 @@
+//! Front-end for the `oc-rsync-mount` binary.
+//!
+//! oc-rsync extension with no upstream equivalent. Parses
+//! `oc-rsync-mount [OPTIONS] SOURCE MOUNTPOINT` and hands the request to
+//! [`core::client::mount::mount_module`], which blocks until the mount point
+//! is unmounted.
+
+use std::ffi::OsString;
+use std::io::Write;
+use std::path::PathBuf;
+
+use clap::{Arg, ArgAction, Command, value_parser};
+use core::client::mount::{MountOptions, mount_module};
+use core::exit_code::ExitCode;
+use logging_sink::MessageSink;
+
+use super::password::load_password_file;
+use super::write_message;
+
+const PROGRAM_NAME: &str = "oc-rsync-mount";
+
+fn mount_command() -> Command {
+    Command::new(PROGRAM_NAME)
+        .about("Mount an rsync daemon module as a read-only filesystem")
+        .version(core::version::RUST_VERSION)
+        .arg(
+            Arg::new("cache-dir")
+                .long("cache-dir")
+                .value_name("DIR")
+                .value_parser(value_parser!(PathBuf))
+                .help("create the fetched-file cache under DIR"),
+        )
+        .arg(
+            Arg::new("password-file")
+                .long("password-file")
+                .value_name("FILE")
+                .value_parser(value_parser!(PathBuf))
+                .help("read daemon-access password from FILE"),
+        )
+        .arg(
+            Arg::new("allow-other")
+                .long("allow-other")
+                .action(ArgAction::SetTrue)
+                .help("let other users access the mount"),
+        )
+        .arg(
+            Arg::new("source")
+                .value_name("SOURCE")
+                .required(true)
+                .value_parser(value_parser!(OsString))
+                .help("rsync://[USER@]HOST[:PORT]/MODULE[/DIR] or [USER@]HOST::MODULE[/DIR]"),
+        )
+        .arg(
+            Arg::new("mountpoint")
+                .value_name("MOUNTPOINT")
+                .required(true)
+                .value_parser(value_parser!(PathBuf))
+                .help("local directory to mount on"),
+        )
+}
+
+/// Runs the `oc-rsync-mount` front-end against the supplied arguments.
+///
+/// Returns once the mount ends, with `0` after a clean unmount and the
+/// rsync exit code of the failure otherwise. Diagnostics go to `stderr`.
+pub fn run_mount<I, S, Out, Err>(arguments: I, stdout: &mut Out, stderr: &mut Err) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+    Out: Write,
+    Err: Write,
+{
+    let matches = match mount_command().try_get_matches_from(arguments.into_iter().map(Into::into))
+    {
+        Ok(matches) => matches,
+        Err(error) if !error.use_stderr() => {
+            let _ = write!(stdout, "{}", error.render());
+            return 0;
+        }
+        Err(error) => {
+            let _ = write!(stderr, "{}", error.render());
+            return ExitCode::Syntax.as_i32();
+        }
+    };
+
+    let mut sink = MessageSink::new(stderr);
+    let password = match matches.get_one::<PathBuf>("password-file") {
+        Some(path) => match load_password_file(path) {
+            Ok(password) => Some(password),
+            Err(message) => {
+                if write_message(&message, &mut sink).is_err() {
+                    let _ = writeln!(sink.writer_mut(), "{message}");
+                }
+                return ExitCode::Syntax.as_i32();
+            }
+        },
+        None => None,
+    };
+
+    let source = matches
+        .get_one::<OsString>("source")
+        .cloned()
+        .unwrap_or_default();
+    let mountpoint = matches
+        .get_one::<PathBuf>("mountpoint")
+        .cloned()
+        .unwrap_or_default();
+    let options = MountOptions::new(source, mountpoint)
+        .with_cache_dir(matches.get_one::<PathBuf>("cache-dir").cloned())
+        .with_password(password)
+        .allow_other(matches.get_flag("allow-other"));
+
+    match mount_module(&options) {
+        Ok(()) => 0,
+        Err(error) => {
+            if write_message(error.message(), &mut sink).is_err() {
+                let _ = writeln!(sink.writer_mut(), "{}", error.message());
+            }
+            error.exit_code()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(args: &[&str]) -> (i32, String, String) {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut argv = vec![PROGRAM_NAME];
+        argv.extend_from_slice(args);
+        let code = run_mount(argv, &mut stdout, &mut stderr);
+        (
+            code,
+            String::from_utf8(stdout).unwrap(),
+            String::from_utf8(stderr).unwrap(),
+        )
+    }
+
+    #[test]
+    fn help_goes_to_stdout() {
+        let (code, stdout, stderr) = run(&["--help"]);
+        assert_eq!(code, 0);
+        assert!(stdout.contains("MOUNTPOINT"));
+        assert!(stderr.is_empty());
+    }
+
+    #[test]
+    fn missing_operands_are_a_syntax_error() {
+        let (code, _, stderr) = run(&["rsync://host/module"]);
+        assert_eq!(code, ExitCode::Syntax.as_i32());
+        assert!(stderr.contains("MOUNTPOINT"));
+    }
+
+    #[test]
+    fn local_source_is_rejected_before_mounting() {
+        let dir = tempfile::tempdir().unwrap();
+        let mountpoint = dir.path().to_str().unwrap();
+        let (code, _, stderr) = run(&["/srv/data", mountpoint]);
+        assert_eq!(code, ExitCode::Syntax.as_i32());
+        assert!(stderr.contains("rsync daemon directory"), "{stderr}");
+    }
+}
@@ -0,0 +1,99 @@
+#![deny(unsafe_code)]
+
+//! Opt-in OTLP export of the transfer pipeline's tracing spans.
+//!
+//! oc-rsync extension, compiled only with the `otel` feature. When the
+//! standard `OTEL_EXPORTER_OTLP_ENDPOINT` or
+//! `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT` variable is set, [`install_from_env`]
+//! registers a global subscriber that forwards the `handshake`,
+//! `flist_receive`, `file_transfer`, `delta_apply`, and `disk_commit` spans to
+//! an OTLP/HTTP collector. Span start and end times carry the per-stage
+//! durations; ndx, sizes, and byte counts ride as span attributes.
+//!
+//! The same binary serves both ends of a session, so a client and a daemon
+//! built with the feature export to the collector independently and can be
+//! correlated by module, peer, and time. A daemon that detaches forks after
+//! the exporter's background thread starts, which loses that thread; run the
+//! daemon with `--no-detach` (as service managers do) to export its spans.
+
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+/// Environment variables that opt a run into OTLP export.
+const ENDPOINT_VARS: [&str; 2] = [
+    "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+    "OTEL_EXPORTER_OTLP_ENDPOINT",
+];
+
+/// Service name reported when `OTEL_SERVICE_NAME` is unset.
+const DEFAULT_SERVICE_NAME: &str = "oc-rsync";
+
+/// Flushes and shuts down the exporter when the run ends.
+pub(crate) struct OtelGuard {
+    provider: SdkTracerProvider,
+}
+
+impl Drop for OtelGuard {
+    fn drop(&mut self) {
+        let _ = self.provider.shutdown();
+    }
+}
+
+/// Returns whether any OTLP endpoint variable is set to a non-empty value.
+fn endpoint_configured(lookup: impl Fn(&str) -> Option<String>) -> bool {
+    ENDPOINT_VARS
+        .iter()
+        .any(|name| lookup(name).is_some_and(|value| !value.trim().is_empty()))
+}
+
+/// Installs the OTLP span exporter when an endpoint is configured.
+///
+/// Returns `None` - and leaves tracing untouched - when no endpoint is set,
+/// the exporter cannot be built, or another global subscriber is already
+/// installed. Export failures never affect the transfer.
+pub(crate) fn install_from_env() -> Option<OtelGuard> {
+    if !endpoint_configured(|name| std::env::var(name).ok()) {
+        return None;
+    }
+
+    // The exporter reads the endpoint, headers, and timeout from the
+    // standard OTEL_EXPORTER_OTLP_* variables itself.
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .build()
+        .ok()?;
+    let service_name =
+        std::env::var("OTEL_SERVICE_NAME").unwrap_or_else(|_| DEFAULT_SERVICE_NAME.to_owned());
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(service_name).build())
+        .build();
+    let tracer = provider.tracer(DEFAULT_SERVICE_NAME);
+
+    tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(tracer))
+        .try_init()
+        .ok()?;
+    Some(OtelGuard { provider })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endpoint_required_to_enable_export() {
+        assert!(!endpoint_configured(|_| None));
+        assert!(!endpoint_configured(|_| Some("  ".to_owned())));
+        assert!(endpoint_configured(|name| {
+            (name == "OTEL_EXPORTER_OTLP_ENDPOINT").then(|| "http://collector:4318".to_owned())
+        }));
+        assert!(endpoint_configured(|name| {
+            (name == "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT")
+                .then(|| "http://collector:4318/v1/traces".to_owned())
+        }));
+    }
+}
@@ -7,5 +7,5 @@ mod render;
 mod tokens;
 
 pub(crate) use parser::{log_format_has, parse_out_format};
-pub(crate) use render::emit_out_format;
+pub(crate) use render::{emit_out_format, itemize_string};
 pub(crate) use tokens::{OutFormat, OutFormatContext};
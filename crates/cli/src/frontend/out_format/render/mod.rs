@@ -153,6 +153,17 @@ fn should_suppress_event(event: &ClientEvent, context: &OutFormatContext) -> boo
             .is_none()
 }
 
+/// Returns the 11-character `%i` itemize string for `event`.
+///
+/// A remote transfer supplies the sender's already-correct string; a local
+/// event derives it from its change set.
+pub(crate) fn itemize_string(event: &ClientEvent, is_sender: bool) -> String {
+    match event.itemize_override() {
+        Some(itemize) => itemize.to_owned(),
+        None => itemize::format_itemized_changes(event, is_sender),
+    }
+}
+
 /// Emits each event using the supplied `--out-format` specification.
 pub(crate) fn emit_out_format<W: Write + ?Sized>(
     events: &[ClientEvent],
@@ -723,6 +723,14 @@ pub(crate) fn emit_totals<W: Write + ?Sized>(
             .collect::<Vec<_>>()
             .join(", ");
         writeln!(stdout, "Copy method: {methods}")?;
+        let cloned = summary.cloned_bytes();
+        if cloned > 0 {
+            writeln!(
+                stdout,
+                "Cloned bytes: {}",
+                format_size(cloned, human_readable)
+            )?;
+        }
     }
 
     writeln!(
@@ -48,9 +48,32 @@ pub(super) struct ServerLongFlags {
     pub(super) is_receiver: bool,
     pub(super) ignore_errors: bool,
     pub(super) fsync: bool,
+    /// `--fsync-dir` forwarded by the client.
+    pub(super) fsync_dir: bool,
     pub(super) io_uring_policy: fast_io::IoUringPolicy,
     /// Optional `--io-uring-depth=N` value forwarded by the client.
     pub(super) io_uring_depth: Option<String>,
+    /// Optional `--parallel-commit=N` value forwarded by the client.
+    pub(super) parallel_commit: Option<String>,
+    /// `--direct-write` forwarded by the client.
+    pub(super) direct_write: bool,
+    /// `--drop-cache` forwarded by the client.
+    pub(super) drop_cache: bool,
+    /// Optional `--cache-dir=DIR` destination scan cache forwarded by the client.
+    pub(super) cache_dir: Option<String>,
+    /// Optional `--checkpoint=FILE` commit log forwarded by the client.
+    pub(super) checkpoint: Option<String>,
+    /// Optional `--resume=FILE` checkpoint forwarded by the client.
+    pub(super) resume: Option<String>,
+    /// Optional `--case-collision=MODE` policy forwarded by the client
+    /// (oc-rsync extension).
+    pub(super) case_collision: Option<String>,
+    /// Optional `--windows-names=MODE` policy forwarded by the client
+    /// (oc-rsync extension).
+    pub(super) windows_names: Option<String>,
+    /// Optional `--order=MODE` transfer order forwarded by the client
+    /// (oc-rsync extension).
+    pub(super) transfer_order: Option<String>,
     pub(super) zero_copy_policy: fast_io::ZeroCopyPolicy,
     pub(super) write_devices: bool,
     pub(super) trust_sender: bool,
@@ -312,6 +335,13 @@ pub(super) struct ServerLongFlags {
     /// source file with `O_NOATIME`, avoiding an atime update on read.
     pub(super) open_noatime: bool,
 
+    /// Whether the client forwarded `--fileflags` (oc-rsync extension).
+    ///
+    /// upstream: patches/fileflags.diff - `if (preserve_fileflags)
+    /// args[ac++] = "--fileflags"`. Both sides must agree because the flag
+    /// adds a BSD file-flags word to every file-list entry.
+    pub(super) fileflags: bool,
+
     /// Whether the client forwarded `--delete-missing-args` (upstream
     /// `missing_args == 2`).
     ///
@@ -328,6 +358,13 @@ pub(super) struct ServerLongFlags {
     /// silently dropped from the file list rather than raising an error.
     pub(super) ignore_missing_args: bool,
 
+    /// Whether the client forwarded `--force` (upstream `force_delete`).
+    ///
+    /// upstream: options.c:2848-2849 - `if (force_delete) args[ac++] =
+    /// "--force"`. A non-empty destination directory standing where a
+    /// non-directory must go is removed recursively even without `--delete`.
+    pub(super) force_delete: bool,
+
     /// Backup suffix forwarded by the client (upstream: `--suffix=SUFFIX`).
     ///
     /// upstream: options.c:2812-2813 - `server_options()` emits
@@ -363,6 +400,8 @@ pub(super) struct ServerLongFlags {
     /// upstream: options.c:2787-2790 - `asprintf(&arg, "-B%u", block_size)`
     /// emits a standalone `-B<digits>` token after the compact flag string.
     /// Recognised here so it is not mistaken for a positional destination path.
+    /// oc-rsync clients send the long `--block-size=N` spelling, which lands
+    /// in the same slot.
     pub(super) block_size: Option<String>,
 }
 
@@ -377,8 +416,18 @@ pub(super) fn parse_server_long_flags(args: &[OsString]) -> ServerLongFlags {
         is_receiver: false,
         ignore_errors: false,
         fsync: false,
+        fsync_dir: false,
         io_uring_policy: fast_io::IoUringPolicy::Auto,
         io_uring_depth: None,
+        parallel_commit: None,
+        direct_write: false,
+        drop_cache: false,
+        cache_dir: None,
+        checkpoint: None,
+        resume: None,
+        case_collision: None,
+        windows_names: None,
+        transfer_order: None,
         zero_copy_policy: fast_io::ZeroCopyPolicy::Auto,
         write_devices: false,
         trust_sender: false,
@@ -428,8 +477,10 @@ pub(super) fn parse_server_long_flags(args: &[OsString]) -> ServerLongFlags {
         no_whole_file: false,
         relative: None,
         open_noatime: false,
+        fileflags: false,
         delete_missing_args: false,
         ignore_missing_args: false,
+        force_delete: false,
         backup_suffix: None,
         usermap: None,
         groupmap: None,
@@ -447,6 +498,9 @@ pub(super) fn parse_server_long_flags(args: &[OsString]) -> ServerLongFlags {
             "--receiver" => flags.is_receiver = true,
             "--ignore-errors" => flags.ignore_errors = true,
             "--fsync" => flags.fsync = true,
+            "--fsync-dir" => flags.fsync_dir = true,
+            "--direct-write" => flags.direct_write = true,
+            "--drop-cache" => flags.drop_cache = true,
             "--io-uring" => flags.io_uring_policy = fast_io::IoUringPolicy::Enabled,
             "--no-io-uring" => flags.io_uring_policy = fast_io::IoUringPolicy::Disabled,
             "--zero-copy" => flags.zero_copy_policy = fast_io::ZeroCopyPolicy::Enabled,
@@ -469,6 +523,9 @@ pub(super) fn parse_server_long_flags(args: &[OsString]) -> ServerLongFlags {
             // upstream: options.c:2993-2994 - `--open-noatime` forwarded to the
             // sender so it opens source files with O_NOATIME (do_open).
             "--open-noatime" => flags.open_noatime = true,
+            // upstream: patches/fileflags.diff - `--fileflags` adds the BSD
+            // file-flags word to the file list; both sides must agree.
+            "--fileflags" => flags.fileflags = true,
             // upstream: options.c:2868-2869 - `--delete-missing-args`
             // (missing_args == 2): a vanished top-level source arg becomes a
             // mode-0 sentinel the receiver deletes at the destination.
@@ -479,15 +536,10 @@ pub(super) fn parse_server_long_flags(args: &[OsString]) -> ServerLongFlags {
             "--ignore-missing-args" => flags.ignore_missing_args = true,
             // upstream: options.c:2848-2849 - `if (force_delete) args[ac++] =
             // "--force"`, emitted in the am_sender block so it reaches a server
-            // acting as the receiver. force_delete only changes behavior when a
-            // non-empty directory must be replaced by a non-directory while
-            // deletions are inactive (delete.c). Under an active --delete pass -
-            // the trigger server_options() ships it alongside - oc already
-            // removes extraneous non-empty directories recursively
-            // (receiver/directory/deletion.rs), matching upstream 2.6.7+
-            // (`--delete` no longer needs `--force`). Recognized here so the arg
-            // does not leak into the positional path list.
-            "--force" => {}
+            // acting as the receiver. force_delete lets the receiver remove a
+            // non-empty directory that must be replaced by a non-directory
+            // while deletions are inactive (generator.c del_opts).
+            "--force" => flags.force_delete = true,
             // upstream: options.c:2852-2853 - `if (am_root > 1) args[ac++] =
             // "--super"`, forcing super-user metadata semantics (chown/mknod)
             // even when the receiver is not literally uid 0. oc gates those
@@ -730,6 +782,8 @@ fn parse_value_bearing_flag(s: &str, flags: &mut ServerLongFlags) {
         flags.max_size = Some(value.to_owned());
     } else if let Some(value) = s.strip_prefix("--max-alloc=") {
         flags.max_alloc = Some(value.to_owned());
+    } else if let Some(value) = s.strip_prefix("--block-size=") {
+        flags.block_size = Some(value.to_owned());
     } else if let Some(value) = s.strip_prefix("--stop-at=") {
         flags.stop_at = Some(value.to_owned());
     } else if let Some(value) = s.strip_prefix("--stop-after=") {
@@ -758,6 +812,20 @@ fn parse_value_bearing_flag(s: &str, flags: &mut ServerLongFlags) {
         flags.timeout = Some(value.to_owned());
     } else if let Some(value) = s.strip_prefix("--io-uring-depth=") {
         flags.io_uring_depth = Some(value.to_owned());
+    } else if let Some(value) = s.strip_prefix("--parallel-commit=") {
+        flags.parallel_commit = Some(value.to_owned());
+    } else if let Some(value) = s.strip_prefix("--cache-dir=") {
+        flags.cache_dir = Some(value.to_owned());
+    } else if let Some(value) = s.strip_prefix("--checkpoint=") {
+        flags.checkpoint = Some(value.to_owned());
+    } else if let Some(value) = s.strip_prefix("--resume=") {
+        flags.resume = Some(value.to_owned());
+    } else if let Some(value) = s.strip_prefix("--case-collision=") {
+        flags.case_collision = Some(value.to_owned());
+    } else if let Some(value) = s.strip_prefix("--windows-names=") {
+        flags.windows_names = Some(value.to_owned());
+    } else if let Some(value) = s.strip_prefix("--order=") {
+        flags.transfer_order = Some(value.to_owned());
     // upstream: options.c:2800-2805 - `--compress-choice=ALGO` / `--zc=ALGO`
     // names the negotiated codec when it is not the default CPRES_ZLIB.
     } else if let Some(value) = s
@@ -822,6 +890,9 @@ pub(super) fn is_known_server_long_flag(arg: &str) -> bool {
             | "--receiver"
             | "--ignore-errors"
             | "--fsync"
+            | "--fsync-dir"
+            | "--direct-write"
+            | "--drop-cache"
             | "--io-uring"
             | "--no-io-uring"
             | "--zero-copy"
@@ -841,6 +912,8 @@ pub(super) fn is_known_server_long_flag(arg: &str) -> bool {
             | "--use-qsort"
             // upstream: options.c:2993-2994 - `--open-noatime` (sender O_NOATIME).
             | "--open-noatime"
+            // upstream: patches/fileflags.diff - `--fileflags` (oc-rsync extension).
+            | "--fileflags"
             // upstream: options.c:2848-2849 - `--force` (force_delete), am_sender
             // block so it reaches a server receiver.
             | "--force"
@@ -923,9 +996,17 @@ pub(super) fn is_known_server_long_flag(arg: &str) -> bool {
         || (arg.starts_with("-B")
             && arg.len() > 2
             && arg.as_bytes()[2..].iter().all(u8::is_ascii_digit))
+        || arg.starts_with("--block-size=")
         || arg.starts_with("--iconv=")
         || arg.starts_with("--timeout=")
         || arg.starts_with("--io-uring-depth=")
+        || arg.starts_with("--parallel-commit=")
+        || arg.starts_with("--cache-dir=")
+        || arg.starts_with("--checkpoint=")
+        || arg.starts_with("--resume=")
+        || arg.starts_with("--case-collision=")
+        || arg.starts_with("--windows-names=")
+        || arg.starts_with("--order=")
         || arg.starts_with("--log-format=")
         || arg.starts_with("--info=")
         // upstream: options.c:1777 - `--debug=FLAGS` parsed via
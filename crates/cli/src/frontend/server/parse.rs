@@ -81,18 +81,23 @@ pub(super) fn parse_server_flag_string_and_args(args: &[OsString]) -> (String, V
 
 /// Parses a `--checksum-seed=NUM` value from the server argument list.
 ///
-/// upstream: options.c - `--checksum-seed=NUM` parsed in `server_options()`.
+/// upstream: options.c:2880-2884 - `server_options()` forwards the seed with
+/// `--checksum-seed=%d`, so an upstream client sends a seed above
+/// `i32::MAX` as a negative number. Both spellings map to the same bits.
 pub(super) fn parse_server_checksum_seed(value: &str) -> Result<u32, String> {
     let trimmed = value.trim();
     if trimmed.is_empty() {
         return Err("--checksum-seed value must not be empty".to_owned());
     }
-    trimmed.parse::<u32>().map_err(|_| {
-        format!(
-            "invalid --checksum-seed value '{value}': must be 0..{}",
-            u32::MAX
-        )
-    })
+    trimmed
+        .parse::<u32>()
+        .or_else(|_| trimmed.parse::<i32>().map(|seed| seed as u32))
+        .map_err(|_| {
+            format!(
+                "invalid --checksum-seed value '{value}': must be 0..{}",
+                u32::MAX
+            )
+        })
 }
 
 /// Parses a `--min-size=SIZE` or `--max-size=SIZE` value from the server argument list.
@@ -190,7 +190,11 @@ where
 
     // Boolean and move-only flags applied after value parsing releases its borrow.
     config.deletion.ignore_errors = long_flags.ignore_errors;
+    config.deletion.force_delete = long_flags.force_delete;
     config.write.fsync = long_flags.fsync;
+    config.write.fsync_dir = long_flags.fsync_dir;
+    config.write.direct_write = long_flags.direct_write;
+    config.write.drop_cache = long_flags.drop_cache;
     config.write.io_uring_policy = long_flags.io_uring_policy;
     config.write.zero_copy_policy = long_flags.zero_copy_policy;
     config.write.write_devices = long_flags.write_devices;
@@ -214,6 +218,9 @@ where
     // upstream: options.c:2993-2994 - `--open-noatime` forwarded to the sender so
     // it opens source files with O_NOATIME (do_open), leaving atime untouched.
     config.write.open_noatime = long_flags.open_noatime;
+    // upstream: patches/fileflags.diff - `--fileflags` adds a file-flags word to
+    // every file-list entry, so the server must read and write it in lockstep.
+    config.flags.fileflags = long_flags.fileflags;
     // upstream: options.c:2868-2871 - `--delete-missing-args` (missing_args == 2)
     // and `--ignore-missing-args` (missing_args == 1) govern how a vanished
     // top-level source arg is handled when building the file list. Mirrors the
@@ -654,7 +661,8 @@ fn apply_value_flags<Err: Write>(
             window_str,
         )) {
             Ok(window) => {
-                config.file_selection.modify_window = ::metadata::ModifyWindow::from_secs(window)
+                config.file_selection.modify_window = ::metadata::ModifyWindow::from_secs(window);
+                config.file_selection.modify_window_set = true;
             }
             Err(msg) => {
                 write_server_error(stderr, brand, msg.text().to_owned());
@@ -663,6 +671,19 @@ fn apply_value_flags<Err: Write>(
         }
     }
 
+    // upstream: options.c:1692-1695 - the forwarded block size goes through
+    // the same MAX_BLOCK_SIZE check as on the client; generator.c
+    // sum_sizes_sqroot() then uses it for every basis signature.
+    if let Some(size_str) = &long_flags.block_size {
+        match super::super::execution::parse_block_size_argument(std::ffi::OsStr::new(size_str)) {
+            Ok(size) => config.block_size = size,
+            Err(msg) => {
+                write_server_error(stderr, brand, msg.text().to_owned());
+                return Err(1);
+            }
+        }
+    }
+
     // upstream: options.c:1943-1950 - server-side `--max-alloc` is parsed and
     // applied to the local allocator. We forward it from the client and
     // enforce the cap on the server's buffer pool.
@@ -751,6 +772,65 @@ fn apply_value_flags<Err: Write>(
         }
     }
 
+    if let Some(dir) = &long_flags.cache_dir {
+        config.file_selection.cache_dir = Some(std::path::PathBuf::from(dir));
+    }
+    if let Some(file) = &long_flags.checkpoint {
+        config.file_selection.checkpoint = Some(std::path::PathBuf::from(file));
+    }
+    if let Some(file) = &long_flags.resume {
+        config.file_selection.resume = Some(std::path::PathBuf::from(file));
+    }
+    if let Some(mode) = &long_flags.case_collision {
+        match engine::CaseCollisionPolicy::parse(mode) {
+            Ok(policy) => config.file_selection.case_collision = policy,
+            Err(_) => {
+                write_server_error(
+                    stderr,
+                    brand,
+                    format!("invalid --case-collision value '{mode}'"),
+                );
+                return Err(1);
+            }
+        }
+    }
+    if let Some(mode) = &long_flags.windows_names {
+        match engine::WindowsNamesPolicy::parse(mode) {
+            Ok(policy) => config.file_selection.windows_names = policy,
+            Err(_) => {
+                write_server_error(
+                    stderr,
+                    brand,
+                    format!("invalid --windows-names value '{mode}'"),
+                );
+                return Err(1);
+            }
+        }
+    }
+    if let Some(mode) = &long_flags.transfer_order {
+        match engine::TransferOrder::parse(mode) {
+            Ok(order) => config.file_selection.transfer_order = order,
+            Err(_) => {
+                write_server_error(stderr, brand, format!("invalid --order value '{mode}'"));
+                return Err(1);
+            }
+        }
+    }
+
+    if let Some(count_str) = &long_flags.parallel_commit {
+        match count_str.parse::<usize>() {
+            Ok(count) if count > 0 => config.write.parallel_commit = count,
+            _ => {
+                write_server_error(
+                    stderr,
+                    brand,
+                    format!("invalid --parallel-commit value '{count_str}'"),
+                );
+                return Err(1);
+            }
+        }
+    }
+
     Ok(())
 }
 
@@ -715,6 +715,31 @@ fn long_flags_fsync() {
     assert!(flags.fsync);
 }
 
+#[test]
+fn long_flags_fsync_dir() {
+    let args = vec![OsString::from("--server"), OsString::from("--fsync-dir")];
+    let flags = parse_server_long_flags(&args);
+    assert!(flags.fsync_dir);
+    assert!(!flags.fsync);
+    assert!(is_known_server_long_flag("--fsync-dir"));
+}
+
+#[test]
+fn long_flags_direct_write() {
+    let args = vec![OsString::from("--server"), OsString::from("--direct-write")];
+    let flags = parse_server_long_flags(&args);
+    assert!(flags.direct_write);
+    assert!(is_known_server_long_flag("--direct-write"));
+}
+
+#[test]
+fn long_flags_drop_cache() {
+    let args = vec![OsString::from("--server"), OsString::from("--drop-cache")];
+    let flags = parse_server_long_flags(&args);
+    assert!(flags.drop_cache);
+    assert!(is_known_server_long_flag("--drop-cache"));
+}
+
 #[test]
 fn long_flags_io_uring_enabled() {
     let args = vec![OsString::from("--server"), OsString::from("--io-uring")];
@@ -788,6 +813,74 @@ fn long_flags_io_uring_depth_default_is_none() {
     assert!(flags.io_uring_depth.is_none());
 }
 
+#[test]
+fn long_flags_parallel_commit_value() {
+    let args = vec![
+        OsString::from("--server"),
+        OsString::from("--parallel-commit=4"),
+    ];
+    let flags = parse_server_long_flags(&args);
+    assert_eq!(flags.parallel_commit.as_deref(), Some("4"));
+    assert!(is_known_server_long_flag("--parallel-commit=4"));
+}
+
+#[test]
+fn long_flags_cache_dir_value() {
+    let args = vec![
+        OsString::from("--server"),
+        OsString::from("--cache-dir=/var/cache/oc"),
+    ];
+    let flags = parse_server_long_flags(&args);
+    assert_eq!(flags.cache_dir.as_deref(), Some("/var/cache/oc"));
+    assert!(is_known_server_long_flag("--cache-dir=/var/cache/oc"));
+}
+
+#[test]
+fn long_flags_case_collision_value() {
+    let args = vec![
+        OsString::from("--server"),
+        OsString::from("--case-collision=rename"),
+    ];
+    let flags = parse_server_long_flags(&args);
+    assert_eq!(flags.case_collision.as_deref(), Some("rename"));
+    assert!(is_known_server_long_flag("--case-collision=rename"));
+}
+
+#[test]
+fn long_flags_windows_names_value() {
+    let args = vec![
+        OsString::from("--server"),
+        OsString::from("--windows-names=sanitize"),
+    ];
+    let flags = parse_server_long_flags(&args);
+    assert_eq!(flags.windows_names.as_deref(), Some("sanitize"));
+    assert!(is_known_server_long_flag("--windows-names=skip"));
+}
+
+#[test]
+fn long_flags_transfer_order_value() {
+    let args = vec![OsString::from("--server"), OsString::from("--order=size")];
+    let flags = parse_server_long_flags(&args);
+    assert_eq!(flags.transfer_order.as_deref(), Some("size"));
+    assert!(is_known_server_long_flag("--order=mtime"));
+}
+
+#[test]
+fn long_flags_checkpoint_and_resume_values() {
+    let args = vec![
+        OsString::from("--server"),
+        OsString::from("--checkpoint=/var/lib/oc/run.ckpt"),
+        OsString::from("--resume=/var/lib/oc/old.ckpt"),
+    ];
+    let flags = parse_server_long_flags(&args);
+    assert_eq!(flags.checkpoint.as_deref(), Some("/var/lib/oc/run.ckpt"));
+    assert_eq!(flags.resume.as_deref(), Some("/var/lib/oc/old.ckpt"));
+    assert!(is_known_server_long_flag(
+        "--checkpoint=/var/lib/oc/run.ckpt"
+    ));
+    assert!(is_known_server_long_flag("--resume=/var/lib/oc/old.ckpt"));
+}
+
 // upstream: options.c:2928-2931 - server_options() forwards --info=FLAGS so
 // the server must recognise it as a long flag and not let it leak into the
 // positional path list.
@@ -1038,6 +1131,17 @@ fn long_flags_capture_block_size() {
     assert_eq!(flags.block_size.as_deref(), Some("131072"));
 }
 
+#[test]
+fn long_flags_capture_long_block_size() {
+    assert!(is_known_server_long_flag("--block-size=4096"));
+    let args = vec![
+        OsString::from("--server"),
+        OsString::from("--block-size=4096"),
+    ];
+    let flags = parse_server_long_flags(&args);
+    assert_eq!(flags.block_size.as_deref(), Some("4096"));
+}
+
 // The `-B` guard is digit-anchored: a non-block-size `-B...` token (no such
 // token is emitted by upstream, but the guard must not over-match) is neither
 // recognised nor captured as a block size.
@@ -1368,6 +1472,15 @@ fn checksum_seed_rejects_overflow() {
     assert!(parse_server_checksum_seed("4294967296").is_err());
 }
 
+#[test]
+fn checksum_seed_accepts_upstream_signed_form() {
+    assert_eq!(parse_server_checksum_seed("-1").unwrap(), u32::MAX);
+    assert_eq!(
+        parse_server_checksum_seed("-2147483648").unwrap(),
+        0x8000_0000
+    );
+}
+
 #[test]
 fn checksum_seed_trims_whitespace() {
     assert_eq!(parse_server_checksum_seed("  42  ").unwrap(), 42);
@@ -2069,6 +2182,16 @@ fn long_flags_open_noatime() {
     assert!(is_known_server_long_flag("--open-noatime"));
 }
 
+/// upstream: patches/fileflags.diff - `--fileflags` is forwarded to the server
+/// so both sides carry the file-flags word in the file list.
+#[test]
+fn long_flags_fileflags() {
+    let args = vec![OsString::from("--server"), OsString::from("--fileflags")];
+    let flags = parse_server_long_flags(&args);
+    assert!(flags.fileflags);
+    assert!(is_known_server_long_flag("--fileflags"));
+}
+
 /// upstream: options.c:2868-2871 - `--delete-missing-args` (missing_args == 2)
 /// and `--ignore-missing-args` (missing_args == 1). Mirrors the daemon
 /// long-form parser (long_form_args.rs).
@@ -2094,10 +2217,11 @@ fn long_flags_missing_args_variants() {
 
 /// upstream: options.c:2848-2853 / 2990-2991 - `--force` (force_delete),
 /// `--super` (am_root > 1), and `--preallocate` (preallocate_files) are emitted
-/// in the am_sender block and reach a server acting as the receiver. oc has no
-/// content-affecting server sink for these (recursive delete already happens,
-/// root is already privileged, preallocation is content-invisible), but they
-/// MUST be recognised so they never surface as a positional destination path.
+/// in the am_sender block and reach a server acting as the receiver. `--force`
+/// feeds the receiver's make-room removal; the other two have no
+/// content-affecting server sink (root is already privileged, preallocation is
+/// content-invisible), but all MUST be recognised so they never surface as a
+/// positional destination path.
 #[test]
 fn force_super_preallocate_recognised_as_known_long_flags() {
     assert!(is_known_server_long_flag("--force"));
@@ -2105,6 +2229,16 @@ fn force_super_preallocate_recognised_as_known_long_flags() {
     assert!(is_known_server_long_flag("--preallocate"));
 }
 
+/// upstream: options.c:2848-2849 - `--force` sets force_delete on the server
+/// receiver.
+#[test]
+fn long_flags_force_sets_force_delete() {
+    let flags = parse_server_long_flags(&[OsString::from("--server"), OsString::from("--force")]);
+    assert!(flags.force_delete);
+    let flags = parse_server_long_flags(&[OsString::from("--server")]);
+    assert!(!flags.force_delete);
+}
+
 /// upstream: options.c:2990-2991 / receiver.c:320 - a server receiver invoked
 /// with `--preallocate` must fallocate() each destination file. The flag has no
 /// compact letter, so it arrives only as the long-form arg; parsing must set
@@ -232,6 +232,28 @@ fn debug_all_includes_oc_accelerated_io_categories() {
     assert_eq!(settings.iocp, Some(1));
 }
 
+#[test]
+fn debug_accepts_checksum_category() {
+    let flags = vec![OsString::from("CHECKSUM2")];
+    let settings = parse_debug_flags(&flags).expect("flags parse");
+    assert_eq!(settings.checksum, Some(2));
+
+    let flags = vec![OsString::from("all")];
+    let settings = parse_debug_flags(&flags).expect("flags parse");
+    assert_eq!(settings.checksum, Some(1));
+}
+
+#[test]
+fn debug_accepts_pipeline_category() {
+    let flags = vec![OsString::from("PIPELINE2")];
+    let settings = parse_debug_flags(&flags).expect("flags parse");
+    assert_eq!(settings.pipeline, Some(2));
+
+    let flags = vec![OsString::from("all")];
+    let settings = parse_debug_flags(&flags).expect("flags parse");
+    assert_eq!(settings.pipeline, Some(1));
+}
+
 #[test]
 fn debug_flist_levels() {
     let flags = vec![OsString::from("flist")];
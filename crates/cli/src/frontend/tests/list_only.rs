@@ -112,6 +112,34 @@ fn list_only_lists_bare_directory_without_recursion_or_slash() {
     assert!(!rendered.contains("file.txt"));
 }
 
+/// A single local operand with no destination implies `--list-only`
+/// (upstream options.c:2194 `if (argc < 2 && !read_batch && !am_server)
+/// list_only |= 1;`), so `rsync src/` lists the directory's contents.
+#[test]
+fn single_local_operand_implies_list_only() {
+    use std::fs;
+    use tempfile::tempdir;
+
+    let tmp = tempdir().expect("tempdir");
+    let source_dir = tmp.path().join("src");
+    fs::create_dir(&source_dir).expect("create src dir");
+    fs::write(source_dir.join("file.txt"), b"contents").expect("write source file");
+
+    let mut operand = source_dir.into_os_string();
+    operand.push("/");
+    let (code, stdout, stderr) = run_with_args([OsString::from(RSYNC), operand]);
+
+    assert_eq!(code, 0);
+    assert!(stderr.is_empty());
+    let rendered = String::from_utf8(stdout).expect("utf8 stdout");
+    let file_line = rendered
+        .lines()
+        .find(|line| line.ends_with("file.txt"))
+        .expect("source file listed");
+    assert!(file_line.starts_with('-'));
+    assert!(!tmp.path().join("file.txt").exists());
+}
+
 #[cfg(unix)]
 #[test]
 fn list_only_matches_rsync_format_for_regular_file() {
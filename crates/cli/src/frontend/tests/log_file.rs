@@ -39,6 +39,42 @@ fn local_transfer_appends_default_log_entries() {
     );
 }
 
+#[test]
+fn split_local_transfer_appends_default_log_entries() {
+    use tempfile::tempdir;
+
+    let temp = tempdir().expect("tempdir");
+    let source = temp.path().join("split.txt");
+    let destination_dir = temp.path().join("dest");
+    std::fs::write(&source, b"split").expect("write source");
+    std::fs::create_dir(&destination_dir).expect("create destination dir");
+
+    let log_path = temp.path().join("split.log");
+
+    let (code, stdout, stderr) = run_with_args([
+        OsString::from(RSYNC),
+        OsString::from("--no-whole-file"),
+        OsString::from("--log-file"),
+        log_path.clone().into_os_string(),
+        source.into_os_string(),
+        destination_dir.clone().into_os_string(),
+    ]);
+
+    assert_eq!(code, 0);
+    assert!(stdout.is_empty(), "{stdout:?}");
+    assert!(stderr.is_empty(), "{stderr:?}");
+
+    let logged = std::fs::read_to_string(&log_path).expect("read log file");
+    assert!(
+        logged.contains("split.txt"),
+        "missing file entry: {logged:?}"
+    );
+    assert_eq!(
+        std::fs::read(destination_dir.join("split.txt")).expect("read destination"),
+        b"split"
+    );
+}
+
 #[test]
 fn local_transfer_respects_custom_log_format() {
     use tempfile::tempdir;
@@ -65,7 +101,91 @@ fn local_transfer_respects_custom_log_format() {
     assert!(stderr.is_empty());
 
     let logged = std::fs::read_to_string(&log_path).expect("read log file");
-    assert_eq!(logged, "custom.txt 6\n");
+    let lines: Vec<&str> = logged.lines().collect();
+    assert_eq!(
+        lines.len(),
+        2,
+        "expected entry and totals lines: {logged:?}"
+    );
+    let pid_tag = format!(" [{}] ", std::process::id());
+    assert!(
+        lines[0].ends_with(&format!("{pid_tag}custom.txt 6")),
+        "{logged:?}"
+    );
+    assert!(lines[1].contains(&format!("{pid_tag}sent ")), "{logged:?}");
+    assert!(lines[1].contains(" bytes  total size 6"), "{logged:?}");
+}
+
+#[test]
+fn log_file_lines_carry_timestamp_and_pid_prefix() {
+    use tempfile::tempdir;
+
+    let temp = tempdir().expect("tempdir");
+    let source = temp.path().join("stamped.txt");
+    let destination_dir = temp.path().join("dest");
+    std::fs::write(&source, b"stamp").expect("write source");
+    std::fs::create_dir(&destination_dir).expect("create destination dir");
+
+    let log_path = temp.path().join("stamped.log");
+
+    let (code, _stdout, _stderr) = run_with_args([
+        OsString::from(RSYNC),
+        OsString::from("--log-file"),
+        log_path.clone().into_os_string(),
+        source.into_os_string(),
+        destination_dir.into_os_string(),
+    ]);
+    assert_eq!(code, 0);
+
+    // upstream: log.c:logit() - "YYYY/MM/DD HH:MM:SS [pid] " on every line.
+    let logged = std::fs::read_to_string(&log_path).expect("read log file");
+    let pid_tag = format!(" [{}] ", std::process::id());
+    for line in logged.lines() {
+        let (stamp, rest) = line.split_at("YYYY/MM/DD HH:MM:SS".len());
+        assert_eq!(&stamp[4..5], "/", "{line:?}");
+        assert_eq!(&stamp[13..14], ":", "{line:?}");
+        assert!(rest.starts_with(&pid_tag), "{line:?}");
+    }
+    assert!(
+        logged.contains("f+++++++++ stamped.txt\n"),
+        "default format is %i %n%L: {logged:?}"
+    );
+}
+
+#[test]
+fn log_file_entries_do_not_depend_on_stdout_verbosity() {
+    use tempfile::tempdir;
+
+    let temp = tempdir().expect("tempdir");
+    let source = temp.path().join("verbose.txt");
+    let destination_dir = temp.path().join("dest");
+    std::fs::write(&source, b"verbose").expect("write source");
+    std::fs::create_dir(&destination_dir).expect("create destination dir");
+
+    let log_path = temp.path().join("verbose.log");
+
+    let (code, stdout, _stderr) = run_with_args([
+        OsString::from(RSYNC),
+        OsString::from("-v"),
+        OsString::from("--log-file"),
+        log_path.clone().into_os_string(),
+        OsString::from("--log-file-format=%f"),
+        source.into_os_string(),
+        destination_dir.into_os_string(),
+    ]);
+    assert_eq!(code, 0);
+    assert!(!stdout.is_empty());
+
+    let logged = std::fs::read_to_string(&log_path).expect("read log file");
+    assert_eq!(
+        logged.lines().count(),
+        2,
+        "entry and totals only: {logged:?}"
+    );
+    assert!(
+        !logged.contains("bytes/sec"),
+        "stdout trailer leaked: {logged:?}"
+    );
 }
 
 #[test]
@@ -279,3 +399,46 @@ fn log_file_successive_transfers_append() {
         "second transfer should be appended: {logged:?}"
     );
 }
+
+#[test]
+fn local_transfer_json_emits_file_records() {
+    use tempfile::tempdir;
+
+    let temp = tempdir().expect("tempdir");
+    let source = temp.path().join("record.txt");
+    let destination_dir = temp.path().join("dest");
+    std::fs::write(&source, b"json").expect("write source");
+    std::fs::create_dir(&destination_dir).expect("create destination dir");
+
+    let json_log = temp.path().join("transfer.jsonl");
+
+    let (code, stdout, stderr) = run_with_args([
+        OsString::from(RSYNC),
+        OsString::from("--json"),
+        OsString::from("--json-log"),
+        json_log.clone().into_os_string(),
+        source.into_os_string(),
+        destination_dir.into_os_string(),
+    ]);
+
+    assert_eq!(code, 0);
+    assert!(stderr.is_empty());
+
+    let printed = String::from_utf8(stdout).expect("stdout utf8");
+    let logged = std::fs::read_to_string(&json_log).expect("read JSON log");
+    for records in [&printed, &logged] {
+        assert!(
+            records
+                .lines()
+                .any(|line| line.starts_with(r#"{"type":"file""#) && line.contains("record.txt")),
+            "missing file record: {records:?}"
+        );
+        assert!(
+            records
+                .lines()
+                .last()
+                .is_some_and(|line| line.starts_with(r#"{"type":"summary""#)),
+            "missing summary record: {records:?}"
+        );
+    }
+}
@@ -189,6 +189,8 @@ mod parse_args_recognises_modify_tests;
 mod parse_args_recognises_msgs2stderr_tests;
 #[path = "parse_args_recognises_no.rs"]
 mod parse_args_recognises_no_tests;
+#[path = "parse_args_recognises_no_selinux_context.rs"]
+mod parse_args_recognises_no_selinux_context_tests;
 #[path = "parse_args_recognises_numeric.rs"]
 mod parse_args_recognises_numeric_tests;
 #[path = "parse_args_recognises_one.rs"]
@@ -0,0 +1,33 @@
+use super::common::*;
+use super::*;
+
+#[test]
+fn parse_args_recognises_no_selinux_context_flag() {
+    let parsed = parse_args([
+        OsString::from(RSYNC),
+        OsString::from("-X"),
+        OsString::from("--no-selinux-context"),
+        OsString::from("source"),
+        OsString::from("dest"),
+    ])
+    .expect("parse");
+
+    assert!(parsed.no_selinux_context);
+    assert!(
+        parsed
+            .filter_order
+            .contains(&filter_rules::FilterOrderToken::NoSelinuxContext)
+    );
+}
+
+#[test]
+fn parse_args_no_selinux_context_defaults_off() {
+    let parsed = parse_args([
+        OsString::from(RSYNC),
+        OsString::from("source"),
+        OsString::from("dest"),
+    ])
+    .expect("parse");
+
+    assert!(!parsed.no_selinux_context);
+}
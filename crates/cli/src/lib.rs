@@ -23,6 +23,8 @@ pub use frontend::dry_run::{
 };
 pub use frontend::info_output;
 pub use frontend::itemize::{FileType, ItemizeChange, UpdateType, format_itemize};
+#[cfg(all(target_os = "linux", feature = "fuse"))]
+pub use frontend::mount::run_mount;
 pub use frontend::progress_format;
 pub use frontend::stats_format;
 pub use frontend::{exit_code_from, run};
@@ -159,6 +159,18 @@ impl ClientConfigBuilder {
         self
     }
 
+    /// Requests that BSD file flags (`chflags`) be preserved.
+    ///
+    /// Only macOS and FreeBSD carry file flags; elsewhere the flags word is
+    /// exchanged but never applied. Corresponds to the `--fileflags` option, an
+    /// oc-rsync extension modelled on the rsync-patches `fileflags.diff`.
+    #[must_use]
+    #[doc(alias = "--fileflags")]
+    pub const fn fileflags(mut self, preserve: bool) -> Self {
+        self.preserve_fileflags = preserve;
+        self
+    }
+
     builder_setter! {
         /// Requests that directory timestamps be skipped when preserving times.
         #[doc(alias = "--omit-dir-times")]
@@ -88,8 +88,8 @@ macro_rules! builder_setter {
 
 use super::{
     AddressMode, BandwidthLimit, BindAddress, ClientConfig, CompressionSetting, DeleteMode,
-    FilesFromSource, FilterRuleSpec, IconvSetting, ReferenceDirectory, ReferenceDirectoryKind,
-    StrongChecksumChoice, TcpFastOpenMode, TransferTimeout,
+    DestinationFormat, FilesFromSource, FilterRuleSpec, IconvSetting, ReferenceDirectory,
+    ReferenceDirectoryKind, StrongChecksumChoice, TcpFastOpenMode, TransferTimeout,
 };
 use ::metadata::{ChmodModifiers, GroupMapping, UserMapping};
 use compress::algorithm::CompressionAlgorithm;
@@ -171,6 +171,7 @@ pub struct ClientConfigBuilder {
     preserve_times: bool,
     preserve_atimes: u8,
     preserve_crtimes: bool,
+    preserve_fileflags: bool,
     owner_override: Option<u32>,
     group_override: Option<u32>,
     copy_as: Option<OsString>,
@@ -204,11 +205,16 @@ pub struct ClientConfigBuilder {
     numeric_ids: bool,
     preallocate: bool,
     fsync: bool,
+    fsync_dir: bool,
     io_uring_policy: fast_io::IoUringPolicy,
     io_uring_depth: Option<u32>,
+    parallel_commit: usize,
+    direct_write: bool,
+    drop_cache: bool,
     cow_policy: fast_io::CowPolicy,
     zero_copy_policy: fast_io::ZeroCopyPolicy,
     parallel_delta_scan: bool,
+    mmap_source: bool,
     preserve_hard_links: bool,
     preserve_symlinks: bool,
     filter_rules: Vec<FilterRuleSpec>,
@@ -231,6 +237,9 @@ pub struct ClientConfigBuilder {
     implied_dirs: Option<bool>,
     mkpath: bool,
     prune_empty_dirs: bool,
+    case_collision: engine::CaseCollisionPolicy,
+    windows_names: engine::WindowsNamesPolicy,
+    transfer_order: engine::TransferOrder,
     qsort: bool,
     inc_recursive_send: Option<bool>,
     verbosity: u8,
@@ -240,10 +249,16 @@ pub struct ClientConfigBuilder {
     partial: bool,
     partial_dir: Option<PathBuf>,
     temp_directory: Option<PathBuf>,
+    cache_dir: Option<PathBuf>,
+    checkpoint: Option<PathBuf>,
+    resume: Option<PathBuf>,
+    remaining_files: Option<PathBuf>,
     backup: bool,
     backup_dir: Option<PathBuf>,
     backup_suffix: Option<OsString>,
     delay_updates: bool,
+    atomic: bool,
+    dest_format: DestinationFormat,
     inplace: bool,
     append: bool,
     append_verify: bool,
@@ -419,6 +434,7 @@ impl ClientConfigBuilder {
             preserve_times: self.preserve_times,
             preserve_atimes: self.preserve_atimes,
             preserve_crtimes: self.preserve_crtimes,
+            preserve_fileflags: self.preserve_fileflags,
             owner_override: self.owner_override,
             group_override: self.group_override,
             copy_as: self.copy_as,
@@ -452,11 +468,16 @@ impl ClientConfigBuilder {
             numeric_ids: self.numeric_ids,
             preallocate: self.preallocate,
             fsync: self.fsync,
+            fsync_dir: self.fsync_dir,
             io_uring_policy: self.io_uring_policy,
             io_uring_depth: self.io_uring_depth,
+            parallel_commit: self.parallel_commit.max(1),
+            direct_write: self.direct_write,
+            drop_cache: self.drop_cache,
             cow_policy: self.cow_policy,
             zero_copy_policy: self.zero_copy_policy,
             parallel_delta_scan: self.parallel_delta_scan,
+            mmap_source: self.mmap_source,
             preserve_hard_links: self.preserve_hard_links,
             preserve_symlinks: self.preserve_symlinks,
             filter_rules: self.filter_rules,
@@ -479,6 +500,9 @@ impl ClientConfigBuilder {
             implied_dirs: self.implied_dirs.unwrap_or(true),
             mkpath: self.mkpath,
             prune_empty_dirs: self.prune_empty_dirs,
+            case_collision: self.case_collision,
+            windows_names: self.windows_names,
+            transfer_order: self.transfer_order,
             qsort: self.qsort,
             // ISI.h: sender-side INC_RECURSE is default-on, matching
             // upstream rsync 3.4.x. CLI `--no-inc-recursive` still overrides
@@ -491,10 +515,16 @@ impl ClientConfigBuilder {
             partial: self.partial,
             partial_dir: self.partial_dir,
             temp_directory: self.temp_directory,
+            cache_dir: self.cache_dir,
+            checkpoint: self.checkpoint,
+            resume: self.resume,
+            remaining_files: self.remaining_files,
             backup: self.backup,
             backup_dir: self.backup_dir,
             backup_suffix: self.backup_suffix,
             delay_updates: self.delay_updates,
+            atomic: self.atomic,
+            dest_format: self.dest_format,
             inplace: self.inplace,
             append: self.append,
             append_verify: self.append_verify,
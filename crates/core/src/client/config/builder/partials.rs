@@ -19,6 +19,26 @@ impl ClientConfigBuilder {
         self
     }
 
+    /// Enables or disables the all-or-nothing `--atomic` tree swap.
+    ///
+    /// oc-rsync extension with no upstream equivalent.
+    #[must_use]
+    #[doc(alias = "--atomic")]
+    pub const fn atomic(mut self, atomic: bool) -> Self {
+        self.atomic = atomic;
+        self
+    }
+
+    /// Selects whether the destination is a directory tree or an archive.
+    ///
+    /// oc-rsync extension with no upstream equivalent.
+    #[must_use]
+    #[doc(alias = "--dest-format")]
+    pub const fn dest_format(mut self, format: DestinationFormat) -> Self {
+        self.dest_format = format;
+        self
+    }
+
     /// Configures the directory used to store partial files when transfers fail.
     #[must_use]
     #[doc(alias = "--partial-dir")]
@@ -39,6 +59,51 @@ impl ClientConfigBuilder {
         self
     }
 
+    /// Configures the directory holding the destination scan cache.
+    ///
+    /// oc-rsync extension. Under `--checksum` the receiver reuses the previous
+    /// run's digests for destination files whose stat is unchanged.
+    #[must_use]
+    #[doc(alias = "--cache-dir")]
+    pub fn cache_dir<P: Into<PathBuf>>(mut self, directory: Option<P>) -> Self {
+        self.cache_dir = directory.map(Into::into);
+        self
+    }
+
+    /// Configures the file the receiver logs confirmed commits to.
+    ///
+    /// oc-rsync extension. The log is rewritten periodically so an
+    /// interrupted transfer can be continued with [`Self::resume`].
+    #[must_use]
+    #[doc(alias = "--checkpoint")]
+    pub fn checkpoint<P: Into<PathBuf>>(mut self, file: Option<P>) -> Self {
+        self.checkpoint = file.map(Into::into);
+        self
+    }
+
+    /// Configures the checkpoint of an interrupted transfer to continue.
+    ///
+    /// oc-rsync extension. Files the checkpoint records as committed, whose
+    /// source entry and destination are unchanged since, are skipped.
+    #[must_use]
+    #[doc(alias = "--resume")]
+    pub fn resume<P: Into<PathBuf>>(mut self, file: Option<P>) -> Self {
+        self.resume = file.map(Into::into);
+        self
+    }
+
+    /// Configures the file that receives the paths a deadline-stopped
+    /// transfer did not reach.
+    ///
+    /// oc-rsync extension. The list suits `--files-from` on the next run, so
+    /// a transfer too large for one `--stop-at` window is finished in chunks.
+    #[must_use]
+    #[doc(alias = "--remaining-files")]
+    pub fn remaining_files<P: Into<PathBuf>>(mut self, file: Option<P>) -> Self {
+        self.remaining_files = file.map(Into::into);
+        self
+    }
+
     /// Enables or disables in-place updates for destination files.
     #[must_use]
     #[doc(alias = "--inplace")]
@@ -80,6 +145,18 @@ impl ClientConfigBuilder {
         self
     }
 
+    /// Requests that each destination directory be synchronised with storage
+    /// after a file is renamed into it.
+    ///
+    /// oc-rsync extension: `--fsync` alone leaves the new directory entry in
+    /// the page cache, so a crash can still lose a fully synced file.
+    #[must_use]
+    #[doc(alias = "--fsync-dir")]
+    pub const fn fsync_dir(mut self, enabled: bool) -> Self {
+        self.fsync_dir = enabled;
+        self
+    }
+
     /// Sets the io_uring usage policy.
     #[must_use]
     #[doc(alias = "--io-uring")]
@@ -100,6 +177,40 @@ impl ClientConfigBuilder {
         self
     }
 
+    /// Sets how many received files are committed to disk concurrently.
+    ///
+    /// oc-rsync extension. `1` keeps the single disk commit thread; values
+    /// below 1 are treated as 1.
+    #[must_use]
+    #[doc(alias = "--parallel-commit")]
+    pub const fn parallel_commit(mut self, count: usize) -> Self {
+        self.parallel_commit = count;
+        self
+    }
+
+    /// Enables `O_DIRECT` writes of received file data.
+    ///
+    /// oc-rsync extension for backup targets that should not fill the page
+    /// cache. Linux only; ignored on other platforms.
+    #[must_use]
+    #[doc(alias = "--direct-write")]
+    pub const fn direct_write(mut self, enabled: bool) -> Self {
+        self.direct_write = enabled;
+        self
+    }
+
+    /// Evicts each file from the page cache once it has been sent or
+    /// committed.
+    ///
+    /// oc-rsync extension for one-shot backups whose data should not displace
+    /// the host's working set. Linux only; ignored on other platforms.
+    #[must_use]
+    #[doc(alias = "--drop-cache")]
+    pub const fn drop_cache(mut self, enabled: bool) -> Self {
+        self.drop_cache = enabled;
+        self
+    }
+
     /// Sets the copy-on-write reflink policy for whole-file copies.
     #[must_use]
     #[doc(alias = "--cow")]
@@ -137,4 +248,16 @@ impl ClientConfigBuilder {
         self.parallel_delta_scan = enabled;
         self
     }
+
+    /// Enables the opt-in, default-off `--mmap-source` read mode.
+    ///
+    /// The local sender scans large delta sources through a memory mapping
+    /// instead of buffered reads. Never forwarded to a remote peer. Off by
+    /// default because a source truncated during the scan raises `SIGBUS`.
+    #[must_use]
+    #[doc(alias = "--mmap-source")]
+    pub const fn mmap_source(mut self, enabled: bool) -> Self {
+        self.mmap_source = enabled;
+        self
+    }
 }
@@ -46,6 +46,15 @@ impl ClientConfigBuilder {
         force_replacements: bool,
         /// Enables or disables pruning of empty directories after filters apply.
         prune_empty_dirs: bool,
+        /// Sets how entries that collide after case folding are handled
+        /// (`--case-collision`, oc-rsync extension).
+        case_collision: engine::CaseCollisionPolicy,
+        /// Sets how names that are invalid on Windows are handled
+        /// (`--windows-names`, oc-rsync extension).
+        windows_names: engine::WindowsNamesPolicy,
+        /// Sets the order regular files are queued for transfer
+        /// (`--order`, oc-rsync extension).
+        transfer_order: engine::TransferOrder,
         /// Requests `-C` / `--cvs-exclude`; forwarded to the peer as the compact
         /// `C` letter (upstream options.c:2709).
         cvs_exclude: bool,
@@ -437,6 +437,12 @@ fn crtimes_false_clears_flag() {
     assert!(!config.preserve_crtimes());
 }
 
+#[test]
+fn fileflags_sets_flag() {
+    let config = builder().fileflags(true).build();
+    assert!(config.preserve_fileflags());
+}
+
 #[test]
 fn omit_dir_times_sets_flag() {
     let config = builder().omit_dir_times(true).build();
@@ -1048,6 +1054,45 @@ fn temp_directory_none_clears_path() {
     assert!(config.temp_directory().is_none());
 }
 
+#[test]
+fn cache_dir_sets_path() {
+    let config = builder().cache_dir(Some("/var/cache/oc")).build();
+    assert_eq!(
+        config.cache_dir(),
+        Some(std::path::Path::new("/var/cache/oc"))
+    );
+    assert!(builder().build().cache_dir().is_none());
+}
+
+#[test]
+fn checkpoint_and_resume_set_paths() {
+    let config = builder()
+        .checkpoint(Some("/var/lib/oc/run.ckpt"))
+        .resume(Some("/var/lib/oc/old.ckpt"))
+        .build();
+    assert_eq!(
+        config.checkpoint(),
+        Some(std::path::Path::new("/var/lib/oc/run.ckpt"))
+    );
+    assert_eq!(
+        config.resume(),
+        Some(std::path::Path::new("/var/lib/oc/old.ckpt"))
+    );
+    let config = builder().build();
+    assert!(config.checkpoint().is_none());
+    assert!(config.resume().is_none());
+}
+
+#[test]
+fn remaining_files_sets_path() {
+    let config = builder().remaining_files(Some("left.txt")).build();
+    assert_eq!(
+        config.remaining_files(),
+        Some(std::path::Path::new("left.txt"))
+    );
+    assert!(builder().build().remaining_files().is_none());
+}
+
 #[test]
 fn inplace_sets_flag() {
     let config = builder().inplace(true).build();
@@ -1164,6 +1209,31 @@ fn io_uring_depth_clears_to_none() {
     assert_eq!(config.io_uring_depth(), None);
 }
 
+#[test]
+fn direct_write_round_trips() {
+    assert!(!builder().build().direct_write());
+    assert!(builder().direct_write(true).build().direct_write());
+}
+
+#[test]
+fn drop_cache_round_trips() {
+    assert!(!builder().build().drop_cache());
+    assert!(builder().drop_cache(true).build().drop_cache());
+}
+
+#[test]
+fn fsync_dir_round_trips() {
+    assert!(!builder().build().fsync_dir());
+    assert!(builder().fsync_dir(true).build().fsync_dir());
+}
+
+#[test]
+fn parallel_commit_clamps_to_one() {
+    assert_eq!(builder().build().parallel_commit(), 1);
+    assert_eq!(builder().parallel_commit(4).build().parallel_commit(), 4);
+    assert_eq!(builder().parallel_commit(0).build().parallel_commit(), 1);
+}
+
 // Mutual exclusion validation tests
 // (upstream: options.c:2424-2432 - inplace/append conflicts with partial-dir/delay-updates)
 
@@ -2161,6 +2231,30 @@ fn prune_empty_dirs_sets_flag() {
     assert!(config.prune_empty_dirs());
 }
 
+#[test]
+fn case_collision_sets_policy() {
+    let config = builder()
+        .case_collision(engine::CaseCollisionPolicy::Warn)
+        .build();
+    assert_eq!(config.case_collision(), engine::CaseCollisionPolicy::Warn);
+}
+
+#[test]
+fn windows_names_sets_policy() {
+    let config = builder()
+        .windows_names(engine::WindowsNamesPolicy::Sanitize)
+        .build();
+    assert_eq!(config.windows_names(), engine::WindowsNamesPolicy::Sanitize);
+}
+
+#[test]
+fn transfer_order_sets_order() {
+    let config = builder()
+        .transfer_order(engine::TransferOrder::Mtime)
+        .build();
+    assert_eq!(config.transfer_order(), engine::TransferOrder::Mtime);
+}
+
 #[test]
 fn default_min_file_size_is_none() {
     let config = builder().build();
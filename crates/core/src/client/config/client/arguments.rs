@@ -7,6 +7,48 @@ impl ClientConfig {
         &self.transfer_args
     }
 
+    /// Returns a copy whose destination operand is replaced by `destination`
+    /// and whose output is an ordinary directory tree.
+    ///
+    /// Archive output (`--dest-format`) receives into a staging directory
+    /// through this copy before packing the tree. `fake_super` turns on
+    /// `--fake-super` for the copy, so the file list's ownership, modes and
+    /// device numbers are recorded even where they cannot be applied.
+    pub(crate) fn redirected_to_directory(&self, destination: OsString, fake_super: bool) -> Self {
+        let mut config = self.clone();
+        if let Some(last) = config.transfer_args.last_mut() {
+            *last = destination;
+        }
+        config.dest_format = DestinationFormat::Directory;
+        config.fake_super |= fake_super;
+        config
+    }
+
+    /// Returns a copy that transfers only `sources` into the same
+    /// destination under `--relative`.
+    ///
+    /// Watch mode re-syncs changed paths through this copy; each source
+    /// marks its transfer root with `/./` so the paths land where the full
+    /// transfer would put them.
+    pub(crate) fn narrowed_to(&self, sources: Vec<OsString>) -> Self {
+        let mut config = self.clone();
+        let destination = config.transfer_args.pop();
+        config.transfer_args = sources;
+        config.transfer_args.extend(destination);
+        config.relative_paths = true;
+        config.implied_dirs = true;
+        config
+    }
+
+    /// Returns a copy that writes no `--remaining-files` list.
+    ///
+    /// The list is written once, around the transfer this copy runs.
+    pub(crate) fn without_remaining_files(&self) -> Self {
+        let mut config = self.clone();
+        config.remaining_files = None;
+        config
+    }
+
     /// Returns the ordered reference directories supplied via `--compare-dest`,
     /// `--copy-dest`, or `--link-dest`.
     #[must_use]
@@ -123,6 +165,19 @@ impl ClientConfig {
         let (sources, _destination) = self.transfer_args.split_at(self.transfer_args.len() - 1);
         sources.iter().any(|s| operand_is_remote(s))
     }
+
+    /// Whether there are operands and every one is a local path, so no
+    /// remote shell or daemon is involved.
+    ///
+    /// upstream: main.c:start_client() - with no host in any operand the
+    /// client forks a `local_server` child instead of connecting out.
+    #[must_use]
+    pub fn is_local_copy(&self) -> bool {
+        use crate::client::remote::operand_is_remote;
+
+        !self.transfer_args.is_empty()
+            && !self.transfer_args.iter().any(|arg| operand_is_remote(arg))
+    }
 }
 
 #[cfg(test)]
@@ -214,6 +269,26 @@ mod tests {
         assert!(!local.is_pull());
     }
 
+    #[test]
+    fn is_local_copy_false_when_any_operand_is_remote() {
+        let local = ClientConfig::builder()
+            .transfer_args([OsString::from("src/"), OsString::from("dst/")])
+            .build();
+        assert!(local.is_local_copy());
+        assert!(!ClientConfig::builder().build().is_local_copy());
+        for args in [
+            ["host:src/", "dst/"],
+            ["src/", "host:dst/"],
+            ["src/", "host::mod/dst/"],
+            ["rsync://host/mod/src/", "dst/"],
+        ] {
+            let config = ClientConfig::builder()
+                .transfer_args(args.map(OsString::from))
+                .build();
+            assert!(!config.is_local_copy(), "{args:?} is not a local copy");
+        }
+    }
+
     #[test]
     fn is_local_sender_rsync_url_pull_reports_receiver() {
         // Daemon pull via rsync:// URL.
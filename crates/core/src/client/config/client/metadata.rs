@@ -131,6 +131,15 @@ impl ClientConfig {
         self.preserve_crtimes
     }
 
+    /// Reports whether BSD file flags (`chflags`) should be preserved.
+    ///
+    /// Corresponds to the `--fileflags` option (an oc-rsync extension).
+    #[must_use]
+    #[doc(alias = "--fileflags")]
+    pub const fn preserve_fileflags(&self) -> bool {
+        self.preserve_fileflags
+    }
+
     /// Reports whether directory timestamps should be skipped when preserving times.
     #[must_use]
     #[doc(alias = "--omit-dir-times")]
@@ -325,6 +334,12 @@ mod tests {
         assert!(!config.preserve_crtimes());
     }
 
+    #[test]
+    fn preserve_fileflags_default_is_false() {
+        let config = default_config();
+        assert!(!config.preserve_fileflags());
+    }
+
     #[test]
     fn omit_dir_times_default_is_false() {
         let config = default_config();
@@ -10,9 +10,9 @@ use engine::SkipCompressList;
 
 use super::builder::ClientConfigBuilder;
 use super::{
-    AddressMode, BandwidthLimit, BindAddress, CompressionSetting, DeleteMode, FilesFromSource,
-    FilterRuleSpec, IconvSetting, ReferenceDirectory, StrongChecksumChoice, TcpFastOpenMode,
-    TransferTimeout,
+    AddressMode, BandwidthLimit, BindAddress, CompressionSetting, DeleteMode, DestinationFormat,
+    FilesFromSource, FilterRuleSpec, IconvSetting, ReferenceDirectory, StrongChecksumChoice,
+    TcpFastOpenMode, TransferTimeout,
 };
 
 /// Configuration describing the requested client operation.
@@ -118,6 +118,7 @@ pub struct ClientConfig {
     /// Access-time preservation level: 0 = off, 1 = `-U`, 2 = `-UU`.
     pub(super) preserve_atimes: u8,
     pub(super) preserve_crtimes: bool,
+    pub(super) preserve_fileflags: bool,
     pub(super) owner_override: Option<u32>,
     pub(super) group_override: Option<u32>,
     pub(super) copy_as: Option<OsString>,
@@ -203,6 +204,9 @@ pub struct ClientConfig {
     pub(super) implied_dirs: bool,
     pub(super) mkpath: bool,
     pub(super) prune_empty_dirs: bool,
+    pub(super) case_collision: engine::CaseCollisionPolicy,
+    pub(super) windows_names: engine::WindowsNamesPolicy,
+    pub(super) transfer_order: engine::TransferOrder,
     pub(super) qsort: bool,
     pub(super) inc_recursive_send: bool,
     pub(super) verbosity: u8,
@@ -212,20 +216,35 @@ pub struct ClientConfig {
     pub(super) partial: bool,
     pub(super) partial_dir: Option<PathBuf>,
     pub(super) temp_directory: Option<PathBuf>,
+    pub(super) cache_dir: Option<PathBuf>,
+    pub(super) checkpoint: Option<PathBuf>,
+    pub(super) resume: Option<PathBuf>,
+    pub(super) remaining_files: Option<PathBuf>,
     pub(super) backup: bool,
     pub(super) backup_dir: Option<PathBuf>,
     pub(super) backup_suffix: Option<OsString>,
     pub(super) delay_updates: bool,
+    /// oc-rsync extension: receive into a sibling staging tree and swap it
+    /// into place once the whole transfer succeeds (`--atomic`).
+    pub(super) atomic: bool,
+    /// oc-rsync extension: write the destination as an archive file rather
+    /// than a directory tree (`--dest-format`).
+    pub(super) dest_format: DestinationFormat,
     pub(super) inplace: bool,
     pub(super) append: bool,
     pub(super) append_verify: bool,
     pub(super) force_replacements: bool,
     pub(super) fsync: bool,
+    pub(super) fsync_dir: bool,
     pub(super) io_uring_policy: fast_io::IoUringPolicy,
     pub(super) io_uring_depth: Option<u32>,
+    pub(super) parallel_commit: usize,
+    pub(super) direct_write: bool,
+    pub(super) drop_cache: bool,
     pub(super) cow_policy: fast_io::CowPolicy,
     pub(super) zero_copy_policy: fast_io::ZeroCopyPolicy,
     pub(super) parallel_delta_scan: bool,
+    pub(super) mmap_source: bool,
     pub(super) itemize_changes: bool,
     pub(super) itemize_unchanged: bool,
     pub(super) force_event_collection: bool,
@@ -377,6 +396,7 @@ impl Default for ClientConfig {
             preserve_times: false,
             preserve_atimes: 0,
             preserve_crtimes: false,
+            preserve_fileflags: false,
             owner_override: None,
             group_override: None,
             copy_as: None,
@@ -429,6 +449,9 @@ impl Default for ClientConfig {
             implied_dirs: true,
             mkpath: false,
             prune_empty_dirs: false,
+            case_collision: engine::CaseCollisionPolicy::Off,
+            windows_names: engine::WindowsNamesPolicy::Off,
+            transfer_order: engine::TransferOrder::Name,
             qsort: false,
             inc_recursive_send: true,
             verbosity: 0,
@@ -438,20 +461,31 @@ impl Default for ClientConfig {
             partial: false,
             partial_dir: None,
             temp_directory: None,
+            cache_dir: None,
+            checkpoint: None,
+            resume: None,
+            remaining_files: None,
             backup: false,
             backup_dir: None,
             backup_suffix: None,
             delay_updates: false,
+            atomic: false,
+            dest_format: DestinationFormat::Directory,
             inplace: false,
             append: false,
             append_verify: false,
             force_replacements: false,
             fsync: false,
+            fsync_dir: false,
             io_uring_policy: fast_io::IoUringPolicy::Auto,
             io_uring_depth: None,
+            parallel_commit: 1,
+            direct_write: false,
+            drop_cache: false,
             cow_policy: fast_io::CowPolicy::Auto,
             zero_copy_policy: fast_io::ZeroCopyPolicy::Auto,
             parallel_delta_scan: false,
+            mmap_source: false,
             itemize_changes: false,
             itemize_unchanged: false,
             force_event_collection: false,
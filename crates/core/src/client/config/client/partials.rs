@@ -16,6 +16,27 @@ impl ClientConfig {
         self.delay_updates
     }
 
+    /// Reports whether the destination tree is swapped into place atomically.
+    ///
+    /// oc-rsync extension (`--atomic`): the transfer is received into a
+    /// sibling staging directory that replaces the destination only after
+    /// every file has been committed.
+    #[must_use]
+    #[doc(alias = "--atomic")]
+    pub const fn atomic(&self) -> bool {
+        self.atomic
+    }
+
+    /// Returns the format the destination is written in.
+    ///
+    /// oc-rsync extension (`--dest-format`): with an archive format the
+    /// destination operand names a tar file that receives the whole tree.
+    #[must_use]
+    #[doc(alias = "--dest-format")]
+    pub const fn dest_format(&self) -> DestinationFormat {
+        self.dest_format
+    }
+
     /// Returns the optional directory used to store partial files.
     #[doc(alias = "--partial-dir")]
     pub fn partial_directory(&self) -> Option<&Path> {
@@ -29,6 +50,31 @@ impl ClientConfig {
         self.temp_directory.as_deref()
     }
 
+    /// Returns the destination scan cache directory (`--cache-dir`), if any.
+    #[doc(alias = "--cache-dir")]
+    pub fn cache_dir(&self) -> Option<&Path> {
+        self.cache_dir.as_deref()
+    }
+
+    /// Returns the file confirmed commits are logged to (`--checkpoint`).
+    #[doc(alias = "--checkpoint")]
+    pub fn checkpoint(&self) -> Option<&Path> {
+        self.checkpoint.as_deref()
+    }
+
+    /// Returns the checkpoint of the transfer to resume (`--resume`).
+    #[doc(alias = "--resume")]
+    pub fn resume(&self) -> Option<&Path> {
+        self.resume.as_deref()
+    }
+
+    /// Returns the file the not-yet-transferred paths are written to
+    /// (`--remaining-files`).
+    #[doc(alias = "--remaining-files")]
+    pub fn remaining_files(&self) -> Option<&Path> {
+        self.remaining_files.as_deref()
+    }
+
     /// Reports whether destination updates should be performed in place.
     #[must_use]
     #[doc(alias = "--inplace")]
@@ -57,6 +103,13 @@ impl ClientConfig {
         self.fsync
     }
 
+    /// Reports whether destination directories are fsynced after each rename.
+    #[must_use]
+    #[doc(alias = "--fsync-dir")]
+    pub const fn fsync_dir(&self) -> bool {
+        self.fsync_dir
+    }
+
     /// Returns the io_uring usage policy.
     #[must_use]
     #[doc(alias = "--io-uring")]
@@ -75,6 +128,28 @@ impl ClientConfig {
         self.io_uring_depth
     }
 
+    /// Returns how many received files are committed to disk concurrently.
+    #[must_use]
+    #[doc(alias = "--parallel-commit")]
+    pub const fn parallel_commit(&self) -> usize {
+        self.parallel_commit
+    }
+
+    /// Reports whether received file data is written with `O_DIRECT`.
+    #[must_use]
+    #[doc(alias = "--direct-write")]
+    pub const fn direct_write(&self) -> bool {
+        self.direct_write
+    }
+
+    /// Reports whether sent and committed files are evicted from the page
+    /// cache.
+    #[must_use]
+    #[doc(alias = "--drop-cache")]
+    pub const fn drop_cache(&self) -> bool {
+        self.drop_cache
+    }
+
     /// Returns the copy-on-write reflink policy for whole-file copies.
     #[must_use]
     #[doc(alias = "--cow")]
@@ -108,6 +183,17 @@ impl ClientConfig {
     pub const fn parallel_delta_scan(&self) -> bool {
         self.parallel_delta_scan
     }
+
+    /// Returns whether the opt-in `--mmap-source` read mode is on.
+    ///
+    /// Local sender-side only: large delta sources are scanned through a
+    /// memory mapping instead of buffered reads. Never forwarded to a remote
+    /// peer. Default off.
+    #[must_use]
+    #[doc(alias = "--mmap-source")]
+    pub const fn mmap_source(&self) -> bool {
+        self.mmap_source
+    }
 }
 
 #[cfg(test)]
@@ -130,6 +216,23 @@ mod tests {
         assert!(!config.delay_updates());
     }
 
+    #[test]
+    fn atomic_default_is_false() {
+        let config = default_config();
+        assert!(!config.atomic());
+        assert!(ClientConfig::builder().atomic(true).build().atomic());
+    }
+
+    #[test]
+    fn dest_format_default_is_directory() {
+        let config = default_config();
+        assert_eq!(config.dest_format(), DestinationFormat::Directory);
+        let config = ClientConfig::builder()
+            .dest_format(DestinationFormat::Tar)
+            .build();
+        assert_eq!(config.dest_format(), DestinationFormat::Tar);
+    }
+
     #[test]
     fn partial_directory_default_is_none() {
         let config = default_config();
@@ -183,4 +286,10 @@ mod tests {
         let config = default_config();
         assert_eq!(config.io_uring_depth(), None);
     }
+
+    #[test]
+    fn parallel_commit_default_is_one() {
+        let config = default_config();
+        assert_eq!(config.parallel_commit(), 1);
+    }
 }
@@ -86,16 +86,28 @@ impl ClientConfig {
 
     /// Reports whether whole-file transfers should be used.
     ///
-    /// Returns `true` when explicitly forced or when auto-detecting for local
-    /// copies. Returns `false` when explicitly forced to delta mode.
+    /// An explicit `--whole-file` / `--no-whole-file` wins. Otherwise
+    /// whole-file is the default only when both source and destination are
+    /// local paths and no batch file is being written; transfers through a
+    /// remote shell or daemon default to the delta algorithm, so the remote
+    /// argument builders send `W` only for an explicit `--whole-file`.
+    ///
+    /// upstream: main.c:start_client() - `if (whole_file < 0 && !write_batch)
+    /// whole_file = 1;` on the `local_server` branch only.
     #[must_use]
     #[doc(alias = "--whole-file")]
     #[doc(alias = "-W")]
     #[doc(alias = "--no-whole-file")]
-    pub const fn whole_file(&self) -> bool {
+    pub fn whole_file(&self) -> bool {
         match self.whole_file {
             Some(v) => v,
-            None => true,
+            None => {
+                self.is_local_copy()
+                    && !self
+                        .batch_config
+                        .as_ref()
+                        .is_some_and(engine::batch::BatchConfig::is_write_mode)
+            }
         }
     }
 
@@ -266,11 +278,38 @@ mod tests {
     }
 
     #[test]
-    fn whole_file_default_is_true() {
-        let config = default_config();
+    fn whole_file_default_is_true_for_local_operands() {
+        let config = ClientConfig::builder()
+            .transfer_args([OsString::from("src/"), OsString::from("dst/")])
+            .build();
         assert!(config.whole_file());
     }
 
+    #[test]
+    fn whole_file_default_follows_operand_locality() {
+        let local = ClientConfig::builder()
+            .transfer_args([OsString::from("src/"), OsString::from("dst/")])
+            .build();
+        assert!(local.whole_file());
+
+        let remote = ClientConfig::builder()
+            .transfer_args([OsString::from("src/"), OsString::from("host:dst/")])
+            .build();
+        assert!(!remote.whole_file());
+
+        let forced = ClientConfig::builder()
+            .transfer_args([OsString::from("src/"), OsString::from("host:dst/")])
+            .whole_file(true)
+            .build();
+        assert!(forced.whole_file());
+
+        let delta = ClientConfig::builder()
+            .transfer_args([OsString::from("src/"), OsString::from("dst/")])
+            .whole_file(false)
+            .build();
+        assert!(!delta.whole_file());
+    }
+
     #[test]
     fn xxh64_dedup_default_is_disabled() {
         let config = default_config();
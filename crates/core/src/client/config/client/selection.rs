@@ -32,6 +32,20 @@ impl ClientConfig {
             .map_or(ModifyWindow::ZERO, ModifyWindow::from_secs)
     }
 
+    /// Returns the window for a local copy, probing the operands' filesystems.
+    ///
+    /// An explicit `--modify-window` is returned unchanged. Otherwise the
+    /// window widens to [`ModifyWindow::FAT`] when any source or the
+    /// destination sits on a FAT-like filesystem, whose two-second mtime
+    /// granularity would otherwise re-transfer every file.
+    #[must_use]
+    pub fn local_modify_window(&self) -> ModifyWindow {
+        ModifyWindow::resolve(
+            self.modify_window,
+            self.transfer_args.iter().map(std::path::Path::new),
+        )
+    }
+
     /// Returns whether the sender should remove source files after transfer.
     #[must_use]
     #[doc(alias = "--remove-source-files")]
@@ -158,6 +172,35 @@ impl ClientConfig {
         self.prune_empty_dirs
     }
 
+    /// Returns how entries that collide after case folding are handled.
+    ///
+    /// Defaults to [`engine::CaseCollisionPolicy::Off`]; `--ignore-case`
+    /// selects [`engine::CaseCollisionPolicy::Warn`].
+    #[must_use]
+    #[doc(alias = "--case-collision")]
+    #[doc(alias = "--ignore-case")]
+    pub const fn case_collision(&self) -> engine::CaseCollisionPolicy {
+        self.case_collision
+    }
+
+    /// Returns how names that are invalid on Windows are handled.
+    ///
+    /// Defaults to [`engine::WindowsNamesPolicy::Off`].
+    #[must_use]
+    #[doc(alias = "--windows-names")]
+    pub const fn windows_names(&self) -> engine::WindowsNamesPolicy {
+        self.windows_names
+    }
+
+    /// Returns the order regular files are queued for transfer.
+    ///
+    /// Defaults to [`engine::TransferOrder::Name`].
+    #[must_use]
+    #[doc(alias = "--order")]
+    pub const fn transfer_order(&self) -> engine::TransferOrder {
+        self.transfer_order
+    }
+
     /// Returns the `--files-from` source configuration.
     ///
     /// When active, the file list is read from the specified source rather
@@ -251,6 +294,15 @@ mod tests {
         assert_eq!(config.modify_window_setting(), ModifyWindow::ZERO);
     }
 
+    #[test]
+    fn local_modify_window_keeps_explicit_value() {
+        let config = ClientConfig::builder()
+            .transfer_args([std::env::temp_dir()])
+            .modify_window(Some(0))
+            .build();
+        assert_eq!(config.local_modify_window(), ModifyWindow::ZERO);
+    }
+
     #[test]
     fn remove_source_files_default_is_false() {
         let config = default_config();
@@ -336,6 +388,24 @@ mod tests {
         assert!(!config.prune_empty_dirs());
     }
 
+    #[test]
+    fn case_collision_default_is_off() {
+        let config = default_config();
+        assert_eq!(config.case_collision(), engine::CaseCollisionPolicy::Off);
+    }
+
+    #[test]
+    fn windows_names_default_is_off() {
+        let config = default_config();
+        assert_eq!(config.windows_names(), engine::WindowsNamesPolicy::Off);
+    }
+
+    #[test]
+    fn transfer_order_default_is_name() {
+        let config = default_config();
+        assert_eq!(config.transfer_order(), engine::TransferOrder::Name);
+    }
+
     #[test]
     fn files_from_default_is_none() {
         let config = default_config();
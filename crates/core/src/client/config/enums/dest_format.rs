@@ -0,0 +1,134 @@
+//! Destination format selection for `--dest-format`.
+//!
+//! oc-rsync extension with no upstream equivalent. `dir` is the ordinary
+//! directory tree. `tar` and `tar.zst` name an archive file as the
+//! destination: the received tree is written into a pax-format tar stream,
+//! optionally zstd-compressed, instead of being left unpacked on disk.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// CLI selection for the `--dest-format` flag.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[doc(alias = "--dest-format")]
+pub enum DestinationFormat {
+    /// Write an ordinary directory tree. This is the default.
+    #[default]
+    Directory,
+    /// Write an uncompressed tar archive.
+    Tar,
+    /// Write a zstd-compressed tar archive.
+    TarZstd,
+}
+
+impl DestinationFormat {
+    /// Returns `true` when the destination operand names an archive file.
+    #[must_use]
+    pub const fn is_archive(self) -> bool {
+        matches!(self, Self::Tar | Self::TarZstd)
+    }
+
+    /// Returns the token accepted by the CLI parser.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Directory => "dir",
+            Self::Tar => "tar",
+            Self::TarZstd => "tar.zst",
+        }
+    }
+}
+
+/// Error returned when parsing an unknown `--dest-format` value.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseDestinationFormatError {
+    value: String,
+}
+
+impl ParseDestinationFormatError {
+    /// Returns the raw value that failed to parse.
+    #[must_use]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl fmt::Display for ParseDestinationFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid --dest-format value '{}': expected 'dir', 'tar', or 'tar.zst'",
+            self.value
+        )
+    }
+}
+
+impl std::error::Error for ParseDestinationFormatError {}
+
+impl FromStr for DestinationFormat {
+    type Err = ParseDestinationFormatError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_ascii_lowercase().as_str() {
+            "dir" | "directory" => Ok(Self::Directory),
+            "tar" => Ok(Self::Tar),
+            "tar.zst" | "tar.zstd" | "tzst" => Ok(Self::TarZstd),
+            _ => Err(ParseDestinationFormatError {
+                value: input.to_string(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for DestinationFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_is_directory() {
+        assert_eq!(DestinationFormat::default(), DestinationFormat::Directory);
+        assert!(!DestinationFormat::Directory.is_archive());
+    }
+
+    #[test]
+    fn parses_tokens_and_aliases() {
+        assert_eq!(
+            "tar".parse::<DestinationFormat>().unwrap(),
+            DestinationFormat::Tar
+        );
+        assert_eq!(
+            "TAR.ZST".parse::<DestinationFormat>().unwrap(),
+            DestinationFormat::TarZstd
+        );
+        assert_eq!(
+            "tzst".parse::<DestinationFormat>().unwrap(),
+            DestinationFormat::TarZstd
+        );
+        assert!(DestinationFormat::Tar.is_archive());
+    }
+
+    #[test]
+    fn rejects_unknown_token() {
+        let err = "zip".parse::<DestinationFormat>().unwrap_err();
+        assert_eq!(err.value(), "zip");
+        assert!(err.to_string().contains("--dest-format"));
+    }
+
+    #[test]
+    fn round_trip_display() {
+        for format in [
+            DestinationFormat::Directory,
+            DestinationFormat::Tar,
+            DestinationFormat::TarZstd,
+        ] {
+            let parsed: DestinationFormat = format.to_string().parse().unwrap();
+            assert_eq!(parsed, format);
+        }
+    }
+}
@@ -2,12 +2,14 @@
 //!
 //! Each submodule owns a single logical concern - timeout policy, human-readable
 //! output formatting, checksum algorithm selection, address family preference,
-//! compression level, file-list source, and deletion scheduling.
+//! compression level, file-list source, deletion scheduling, and destination
+//! format.
 
 mod address;
 mod checksum;
 mod compression;
 mod delete;
+mod dest_format;
 mod files_from;
 mod human_readable;
 mod tcp_fastopen;
@@ -17,6 +19,7 @@ pub use address::AddressMode;
 pub use checksum::{StrongChecksumAlgorithm, StrongChecksumChoice};
 pub use compression::CompressionSetting;
 pub use delete::DeleteMode;
+pub use dest_format::{DestinationFormat, ParseDestinationFormatError};
 pub use files_from::{FilesFromPlan, FilesFromSource};
 pub use human_readable::{HumanReadableMode, HumanReadableModeParseError};
 pub use tcp_fastopen::{ParseTcpFastOpenModeError, TcpFastOpenMode};
@@ -26,7 +26,9 @@ mod filters;
 mod iconv;
 mod network;
 mod reference;
+mod size_limit;
 mod skip_compress;
+mod stop_at;
 
 pub use bandwidth::BandwidthLimit;
 pub use builder::{ClientConfigBuilder, ConfigConflict};
@@ -35,12 +37,15 @@ pub use client::ClientConfig;
 pub use client::EmbeddedSshOptions;
 pub use compress_env::force_no_compress_from_env;
 pub use enums::{
-    AddressMode, CompressionSetting, DeleteMode, FilesFromPlan, FilesFromSource, HumanReadableMode,
-    HumanReadableModeParseError, ParseTcpFastOpenModeError, StrongChecksumAlgorithm,
-    StrongChecksumChoice, TcpFastOpenMode, TransferTimeout,
+    AddressMode, CompressionSetting, DeleteMode, DestinationFormat, FilesFromPlan, FilesFromSource,
+    HumanReadableMode, HumanReadableModeParseError, ParseDestinationFormatError,
+    ParseTcpFastOpenModeError, StrongChecksumAlgorithm, StrongChecksumChoice, TcpFastOpenMode,
+    TransferTimeout,
 };
 pub use filters::{FilterRuleKind, FilterRuleSpec};
 pub use iconv::{IconvParseError, IconvSetting};
 pub use network::BindAddress;
 pub use reference::{ReferenceDirectory, ReferenceDirectoryKind};
+pub use size_limit::{SizeLimitParseError, parse_size_limit};
 pub use skip_compress::{parse_skip_compress_list, skip_compress_from_env};
+pub use stop_at::parse_forwarded_stop_at;
@@ -0,0 +1,239 @@
+//! Size-limit parsing shared by `--min-size`, `--max-size`, `--block-size`,
+//! and `--max-alloc`.
+//!
+//! The numeric-and-suffix grammar is upstream's single
+//! `options.c:parse_size_arg()`, implemented by [`bandwidth::parse_size_arg`]:
+//! plain integers, fractional values (`.`/`,`), binary suffixes (K/M/G/T/P),
+//! decimal suffixes (KB/MB/...), explicit binary suffixes (KiB/...), the byte
+//! suffix `B`, and a single trailing `+1`/`-1` adjustment. This module layers
+//! the sign handling and 64-bit narrowing on top so the client front-end and
+//! the server-side argument parser reject exactly the same inputs.
+
+use thiserror::Error;
+
+use crate::bandwidth::{SizeArgError, parse_size_arg};
+
+/// Errors raised while parsing a size limit.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Error)]
+pub enum SizeLimitParseError {
+    /// The input was empty or only a sign character.
+    #[error("size must not be empty")]
+    Empty,
+    /// The input was a negative number.
+    #[error("size must be non-negative")]
+    Negative,
+    /// The input was malformed or used an unrecognised suffix.
+    #[error("expected a size with an optional K/M/G/T/P suffix")]
+    Invalid,
+    /// The value does not fit in 64 bits.
+    #[error("size exceeds the supported range")]
+    TooLarge,
+}
+
+impl From<SizeArgError> for SizeLimitParseError {
+    fn from(error: SizeArgError) -> Self {
+        match error {
+            SizeArgError::Invalid => Self::Invalid,
+            SizeArgError::TooLarge => Self::TooLarge,
+        }
+    }
+}
+
+/// Parses a size limit into a byte count.
+///
+/// A leading `+` is rejected and there is no exa (`E`) suffix, matching
+/// upstream's suffix switch which stops at `P`. The caller trims surrounding
+/// whitespace.
+///
+/// # Errors
+///
+/// Returns a [`SizeLimitParseError`] describing why `text` is not a valid,
+/// non-negative size.
+///
+/// # Examples
+///
+/// ```
+/// use core::client::parse_size_limit;
+///
+/// assert_eq!(parse_size_limit("1.5K"), Ok(1536));
+/// assert_eq!(parse_size_limit("1MB-1"), Ok(999_999));
+/// assert!(parse_size_limit("-1").is_err());
+/// ```
+pub fn parse_size_limit(text: &str) -> Result<u64, SizeLimitParseError> {
+    if text.is_empty() {
+        return Err(SizeLimitParseError::Empty);
+    }
+
+    // upstream: options.c:parse_size_arg() never strips a leading '+', so
+    // "+100" is rejected. A leading '-' is a negative size, which we reject
+    // with a dedicated diagnostic rather than a generic parse error.
+    let unsigned = match text.strip_prefix('-') {
+        Some("") => return Err(SizeLimitParseError::Empty),
+        Some(_) => return Err(SizeLimitParseError::Negative),
+        None => text,
+    };
+
+    let parsed = parse_size_arg(unsigned, b'b')?;
+    u64::try_from(parsed.bytes).map_err(|_| SizeLimitParseError::TooLarge)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty() {
+        assert_eq!(parse_size_limit(""), Err(SizeLimitParseError::Empty));
+    }
+
+    #[test]
+    fn just_sign() {
+        // upstream: options.c:parse_size_arg() rejects a bare leading '+'.
+        assert_eq!(parse_size_limit("+"), Err(SizeLimitParseError::Invalid));
+        assert_eq!(parse_size_limit("-"), Err(SizeLimitParseError::Empty));
+    }
+
+    #[test]
+    fn negative() {
+        assert_eq!(parse_size_limit("-100"), Err(SizeLimitParseError::Negative));
+        assert_eq!(parse_size_limit("-1K"), Err(SizeLimitParseError::Negative));
+    }
+
+    #[test]
+    fn plain_number() {
+        assert_eq!(parse_size_limit("0"), Ok(0));
+        assert_eq!(parse_size_limit("1"), Ok(1));
+        assert_eq!(parse_size_limit("100"), Ok(100));
+        assert_eq!(parse_size_limit("12345"), Ok(12345));
+    }
+
+    #[test]
+    fn leading_plus_rejected() {
+        // upstream rsync rejects a leading '+' for size args: `--max-size=+100
+        // is invalid`. Only bare digits (optionally with a suffix) are valid.
+        assert_eq!(parse_size_limit("+100"), Err(SizeLimitParseError::Invalid));
+        assert_eq!(parse_size_limit("+1K"), Err(SizeLimitParseError::Invalid));
+    }
+
+    #[test]
+    fn trailing_adjustment() {
+        // upstream: a single trailing "+1"/"-1" adjusts the byte count so that
+        // "--max-size=1K-1" (1023) or "1K+1" (1025) can target a boundary.
+        assert_eq!(parse_size_limit("1K-1"), Ok(1023));
+        assert_eq!(parse_size_limit("1K+1"), Ok(1025));
+        assert_eq!(parse_size_limit("1-1"), Ok(0));
+        assert_eq!(parse_size_limit("1KB-1"), Ok(999));
+        assert_eq!(parse_size_limit("1.5K-1"), Ok(1535));
+    }
+
+    #[test]
+    fn rejects_non_unit_adjustment() {
+        // Only exactly "+1"/"-1" is accepted; anything else is invalid, and a
+        // "-1" that would drive the size negative is rejected too.
+        assert_eq!(parse_size_limit("1K-2"), Err(SizeLimitParseError::Invalid));
+        assert_eq!(parse_size_limit("1K+2"), Err(SizeLimitParseError::Invalid));
+        assert_eq!(parse_size_limit("1K-10"), Err(SizeLimitParseError::Invalid));
+        assert_eq!(parse_size_limit("1K-0"), Err(SizeLimitParseError::Invalid));
+        assert_eq!(parse_size_limit("1K+0"), Err(SizeLimitParseError::Invalid));
+        assert_eq!(parse_size_limit("1K-1x"), Err(SizeLimitParseError::Invalid));
+        assert_eq!(parse_size_limit("0-1"), Err(SizeLimitParseError::TooLarge));
+    }
+
+    #[test]
+    fn kibibytes() {
+        assert_eq!(parse_size_limit("1K"), Ok(1024));
+        assert_eq!(parse_size_limit("1k"), Ok(1024));
+        assert_eq!(parse_size_limit("2K"), Ok(2048));
+        assert_eq!(parse_size_limit("10K"), Ok(10240));
+    }
+
+    #[test]
+    fn kilobytes_decimal() {
+        assert_eq!(parse_size_limit("1KB"), Ok(1000));
+        assert_eq!(parse_size_limit("1kB"), Ok(1000));
+        assert_eq!(parse_size_limit("1Kb"), Ok(1000));
+        assert_eq!(parse_size_limit("2KB"), Ok(2000));
+    }
+
+    #[test]
+    fn kilobytes_binary_explicit() {
+        assert_eq!(parse_size_limit("1KiB"), Ok(1024));
+        assert_eq!(parse_size_limit("1kib"), Ok(1024));
+    }
+
+    #[test]
+    fn mebibytes() {
+        assert_eq!(parse_size_limit("1M"), Ok(1024 * 1024));
+        assert_eq!(parse_size_limit("1m"), Ok(1024 * 1024));
+        assert_eq!(parse_size_limit("1MiB"), Ok(1024 * 1024));
+    }
+
+    #[test]
+    fn megabytes_decimal() {
+        assert_eq!(parse_size_limit("1MB"), Ok(1000 * 1000));
+    }
+
+    #[test]
+    fn gibibytes() {
+        assert_eq!(parse_size_limit("1G"), Ok(1024 * 1024 * 1024));
+    }
+
+    #[test]
+    fn gigabytes_decimal() {
+        assert_eq!(parse_size_limit("1GB"), Ok(1000 * 1000 * 1000));
+    }
+
+    #[test]
+    fn tebibytes() {
+        assert_eq!(parse_size_limit("1T"), Ok(1024u64.pow(4)));
+        assert_eq!(parse_size_limit("1TB"), Ok(1000u64.pow(4)));
+    }
+
+    #[test]
+    fn pebibytes() {
+        assert_eq!(parse_size_limit("1P"), Ok(1024u64.pow(5)));
+        assert_eq!(parse_size_limit("1PB"), Ok(1000u64.pow(5)));
+    }
+
+    #[test]
+    fn exa_suffix_rejected() {
+        // upstream's suffix switch stops at 'p'/'P'; there is no exa suffix.
+        assert_eq!(parse_size_limit("1E"), Err(SizeLimitParseError::Invalid));
+        assert_eq!(parse_size_limit("1e"), Err(SizeLimitParseError::Invalid));
+    }
+
+    #[test]
+    fn bytes_suffix() {
+        assert_eq!(parse_size_limit("100B"), Ok(100));
+        assert_eq!(parse_size_limit("100b"), Ok(100));
+    }
+
+    #[test]
+    fn fractional() {
+        assert_eq!(parse_size_limit("1.5K"), Ok(1536));
+        assert_eq!(parse_size_limit("2.5M"), Ok(2621440));
+        assert_eq!(parse_size_limit("1,5K"), Ok(1536));
+    }
+
+    #[test]
+    fn invalid_suffix() {
+        assert_eq!(parse_size_limit("100X"), Err(SizeLimitParseError::Invalid));
+        assert_eq!(parse_size_limit("100Q"), Err(SizeLimitParseError::Invalid));
+        assert_eq!(parse_size_limit("1Ki"), Err(SizeLimitParseError::Invalid));
+    }
+
+    #[test]
+    fn invalid_format() {
+        assert_eq!(parse_size_limit("abc"), Err(SizeLimitParseError::Invalid));
+        assert_eq!(parse_size_limit("."), Err(SizeLimitParseError::Invalid));
+        assert_eq!(parse_size_limit(","), Err(SizeLimitParseError::Invalid));
+    }
+
+    #[test]
+    fn exceeds_u64() {
+        assert_eq!(
+            parse_size_limit("99999999P"),
+            Err(SizeLimitParseError::TooLarge)
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! Parsing of the `--stop-at` deadline a client forwards to its server.
+//!
+//! The client converts both `--stop-at` and `--stop-after` to an absolute
+//! deadline and forwards it in UTC as `YYYY/MM/DDTHH:MM`, the spelling
+//! upstream's `server_options()` emits for `stop_at_utime`. A daemon has no
+//! client-side front-end to reuse, so it reads the forwarded value with this
+//! parser instead of the interactive grammar.
+
+use std::time::{Duration, SystemTime};
+
+/// Parses a forwarded `YYYY/MM/DDTHH:MM` deadline, interpreted as UTC.
+///
+/// `-` is accepted in place of `/` and an optional `:SS` may follow the
+/// minutes. Returns `None` for anything else, including out-of-range fields
+/// and dates before the epoch.
+///
+/// # Examples
+///
+/// ```
+/// use core::client::parse_forwarded_stop_at;
+/// use std::time::{Duration, SystemTime};
+///
+/// let deadline = parse_forwarded_stop_at("2000/01/01T00:00").unwrap();
+/// assert_eq!(deadline, SystemTime::UNIX_EPOCH + Duration::from_secs(946_684_800));
+/// assert!(parse_forwarded_stop_at("12:00").is_none());
+/// ```
+#[must_use]
+pub fn parse_forwarded_stop_at(text: &str) -> Option<SystemTime> {
+    let (date, time) = text.split_once(['T', 't'])?;
+    let mut date_fields = date.split(['/', '-']);
+    let year: i64 = parse_field(date_fields.next()?, 4)?;
+    let month: u32 = parse_field(date_fields.next()?, 2)?;
+    let day: u32 = parse_field(date_fields.next()?, 2)?;
+    if date_fields.next().is_some() {
+        return None;
+    }
+
+    let mut time_fields = time.split(':');
+    let hour: u64 = parse_field(time_fields.next()?, 2)?;
+    let minute: u64 = parse_field(time_fields.next()?, 2)?;
+    let second: u64 = match time_fields.next() {
+        Some(field) => parse_field(field, 2)?,
+        None => 0,
+    };
+    if time_fields.next().is_some() {
+        return None;
+    }
+
+    if !(1..=12).contains(&month)
+        || day == 0
+        || day > days_in_month(year, month)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        return None;
+    }
+
+    let days = u64::try_from(days_from_civil(year, month, day)).ok()?;
+    let secs = days * 86_400 + hour * 3_600 + minute * 60 + second;
+    SystemTime::UNIX_EPOCH.checked_add(Duration::from_secs(secs))
+}
+
+/// Parses an all-digit field of at most `max_digits` digits.
+fn parse_field<T: std::str::FromStr>(field: &str, max_digits: usize) -> Option<T> {
+    if field.is_empty() || field.len() > max_digits || !field.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    field.parse().ok()
+}
+
+const fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Converts a Gregorian date to days since 1970-01-01.
+///
+/// Howard Hinnant's `days_from_civil` (public domain), the inverse of the
+/// `civil_from_days` step the client uses to format the deadline.
+const fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400; // year of era [0, 399]
+    let mp = (if month > 2 { month - 3 } else { month + 9 }) as i64; // month prime [0, 11]
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1; // day of year [0, 365]
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy; // day of era [0, 146096]
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: u64) -> Option<SystemTime> {
+        Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+    }
+
+    #[test]
+    fn parses_the_forwarded_spelling_as_utc() {
+        assert_eq!(parse_forwarded_stop_at("1970/01/01T00:00"), at(0));
+        assert_eq!(parse_forwarded_stop_at("2000/03/01T12:34"), at(951_914_040));
+        assert_eq!(
+            parse_forwarded_stop_at("2024-02-29T23:59:30"),
+            at(1_709_251_170)
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_and_out_of_range_values() {
+        for text in [
+            "",
+            "12:00",
+            "2024/02/30T00:00",
+            "2023/02/29T00:00",
+            "2024/13/01T00:00",
+            "2024/01/01T24:00",
+            "2024/01/01T00:60",
+            "2024/01/01/01T00:00",
+            "2024/01/01T00:00:00:00",
+            "1969/12/31T23:59",
+            "2024/1x/01T00:00",
+        ] {
+            assert!(parse_forwarded_stop_at(text).is_none(), "{text}");
+        }
+    }
+}
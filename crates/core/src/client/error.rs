@@ -291,6 +291,15 @@ pub(crate) fn map_local_copy_error(error: LocalCopyError) -> ClientError {
             ClientError::with_code(code, msg)
         }
         LocalCopyErrorKind::Interrupted => signal_interrupt_error(),
+        LocalCopyErrorKind::InvalidWindowsName { path } => {
+            let code = ExitCode::PartialTransfer;
+            let text = format!(
+                "\"{}\" is not a valid Windows file name (--windows-names=fail)",
+                path.display()
+            );
+            let message = rsync_error!(code.as_i32(), text).with_role(Role::Client);
+            ClientError::with_code(code, message)
+        }
     }
 }
 
@@ -92,21 +92,28 @@
 mod config;
 mod error;
 mod module_list;
+/// Read-only FUSE mounts of daemon modules (oc-rsync extension).
+#[cfg(all(target_os = "linux", feature = "fuse"))]
+pub mod mount;
 mod outcome;
 mod progress;
 /// Remote transfer orchestration for SSH and daemon transports.
 pub mod remote;
 mod run;
 mod summary;
+/// Watch mode: re-sync local sources as they change (oc-rsync extension).
+pub mod watch;
 
 #[cfg(feature = "embedded-ssh")]
 pub use self::config::EmbeddedSshOptions;
 pub use self::config::{
     AddressMode, BandwidthLimit, BindAddress, ClientConfig, ClientConfigBuilder,
-    CompressionSetting, ConfigConflict, DeleteMode, FilesFromPlan, FilesFromSource, FilterRuleKind,
-    FilterRuleSpec, HumanReadableMode, HumanReadableModeParseError, IconvParseError, IconvSetting,
-    ParseTcpFastOpenModeError, ReferenceDirectory, ReferenceDirectoryKind, StrongChecksumAlgorithm,
-    StrongChecksumChoice, TcpFastOpenMode, TransferTimeout, force_no_compress_from_env,
+    CompressionSetting, ConfigConflict, DeleteMode, DestinationFormat, FilesFromPlan,
+    FilesFromSource, FilterRuleKind, FilterRuleSpec, HumanReadableMode,
+    HumanReadableModeParseError, IconvParseError, IconvSetting, ParseDestinationFormatError,
+    ParseTcpFastOpenModeError, ReferenceDirectory, ReferenceDirectoryKind, SizeLimitParseError,
+    StrongChecksumAlgorithm, StrongChecksumChoice, TcpFastOpenMode, TransferTimeout,
+    force_no_compress_from_env, parse_forwarded_stop_at, parse_size_limit,
     parse_skip_compress_list, skip_compress_from_env,
 };
 pub use self::error::{
@@ -117,8 +124,8 @@ pub use self::error::{
 };
 pub use self::module_list::{
     DaemonAddress, ModuleList, ModuleListEntry, ModuleListOptions, ModuleListRequest,
-    run_module_list, run_module_list_with_options, run_module_list_with_password,
-    run_module_list_with_password_and_options,
+    SocketOptionToken, run_module_list, run_module_list_with_options,
+    run_module_list_with_password, run_module_list_with_password_and_options, socket_option_tokens,
 };
 pub use self::outcome::ClientOutcome;
 pub use self::progress::{ClientProgressObserver, ClientProgressUpdate};
@@ -127,7 +134,7 @@ pub use self::summary::{
     ClientEntryKind, ClientEntryMetadata, ClientEvent, ClientEventKind, ClientSummary,
     ListOnlyEntryFields, RemoteItemizeFields,
 };
-pub use engine::SkipCompressList;
+pub use engine::{CaseCollisionPolicy, SkipCompressList, TransferOrder, WindowsNamesPolicy};
 pub use engine::batch::{BatchConfig, BatchMode};
 pub use engine::local_copy::{DirMergeEnforcedKind, DirMergeOptions};
 
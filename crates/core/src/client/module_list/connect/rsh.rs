@@ -45,6 +45,9 @@ pub(crate) struct RshDaemonSpawn<'a> {
     /// Forced address family (`--ipv4`/`--ipv6`), appended as `-4`/`-6` when
     /// the remote-shell program is exactly `ssh`.
     pub address_mode: AddressMode,
+    /// Tri-state `--blocking-io`/`--no-blocking-io` preference; `None` lets
+    /// the `rsh`/`remsh` auto-enable apply.
+    pub blocking_io: Option<bool>,
 }
 
 /// Returns whether the remote-shell program is ssh (or an ssh-family binary
@@ -172,13 +175,30 @@ pub(crate) fn spawn_rsh_daemon_stream(
     spec: RshDaemonSpawn<'_>,
 ) -> Result<DaemonStream, ClientError> {
     let (program, args) = build_rsh_command_argv(&spec);
+
+    // upstream: main.c:600-601 do_cmd() forces blocking_io for rsh/remsh when
+    // unset; pipe.c:80-82 then sets the child stdout blocking.
+    let blocking_io = rsync_io::ssh::resolve_remote_shell_blocking_io(&program, spec.blocking_io);
+    logging::debug_log!(
+        Connect,
+        2,
+        "remote-shell blocking_io resolved to {}",
+        blocking_io
+    );
+
     let mut cmd = Command::new(&program);
     cmd.args(&args);
 
     // upstream: main.c:1593-1594 - set_env_num("RSYNC_PORT", env_port)
     cmd.env("RSYNC_PORT", spec.port.to_string());
     cmd.stdin(Stdio::piped());
-    cmd.stdout(Stdio::piped());
+    let parent_stdout =
+        rsync_io::ssh::pipe_remote_shell_stdout(&mut cmd, blocking_io).map_err(|e| {
+            invalid_argument_error(
+                &format!("failed to create remote shell stdout pipe: {e}"),
+                IPC_EXIT_CODE,
+            )
+        })?;
     cmd.stderr(Stdio::inherit());
 
     let mut child = cmd.spawn().map_err(|e| {
@@ -187,13 +207,19 @@ pub(crate) fn spawn_rsh_daemon_stream(
             IPC_EXIT_CODE,
         )
     })?;
+    // Release the child's stdout end held by the command so reads see EOF
+    // once the remote shell exits.
+    drop(cmd);
 
     let stdin = child.stdin.take().ok_or_else(|| {
         invalid_argument_error("remote shell process did not expose stdin", IPC_EXIT_CODE)
     })?;
-    let stdout = child.stdout.take().ok_or_else(|| {
-        invalid_argument_error("remote shell process did not expose stdout", IPC_EXIT_CODE)
-    })?;
+    let stdout = match parent_stdout {
+        Some(stdout) => stdout,
+        None => child.stdout.take().ok_or_else(|| {
+            invalid_argument_error("remote shell process did not expose stdout", IPC_EXIT_CODE)
+        })?,
+    };
 
     Ok(DaemonStream::from_child_process(child, stdin, stdout))
 }
@@ -224,6 +250,7 @@ mod tests {
             jump_hosts: None,
             connect_timeout: None,
             address_mode: AddressMode::Default,
+            blocking_io: None,
         };
 
         // `DaemonStream` is not `Debug`, so match instead of `expect_err`.
@@ -254,6 +281,7 @@ mod tests {
             jump_hosts: None,
             connect_timeout: None,
             address_mode: mode,
+            blocking_io: None,
         };
         let (_, args) = build_rsh_command_argv(&spec);
         args.iter()
@@ -356,6 +384,7 @@ mod tests {
             jump_hosts: None,
             connect_timeout: None,
             address_mode: AddressMode::Default,
+            blocking_io: None,
         };
         let (_, args) = build_rsh_command_argv(&spec);
         args.iter()
@@ -443,6 +472,7 @@ mod tests {
             jump_hosts: None,
             connect_timeout: None,
             address_mode: AddressMode::Default,
+            blocking_io: None,
         };
         let (_, args) = build_rsh_command_argv(&spec);
         let rendered: Vec<String> = args
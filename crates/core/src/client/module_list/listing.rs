@@ -212,6 +212,7 @@ pub fn run_module_list_with_password_and_options(
             jump_hosts: None,
             connect_timeout: connect_duration,
             address_mode,
+            blocking_io: options.blocking_io(),
         })?
     } else {
         open_daemon_stream(
@@ -226,7 +227,7 @@ pub fn run_module_list_with_password_and_options(
         )?
     };
 
-    configure_daemon_stream(&mut stream, &options, addr)?;
+    configure_daemon_stream(&mut stream, &options);
 
     let handshake = negotiate_legacy_daemon_session(stream, request.protocol())
         .map_err(|error| map_daemon_handshake_error(error, addr))?;
@@ -462,11 +463,7 @@ fn reconstruct_daemon_greeting_line(greeting: &LegacyDaemonGreetingOwned) -> Str
     banner
 }
 
-fn configure_daemon_stream(
-    stream: &mut super::connect::DaemonStream,
-    options: &ModuleListOptions,
-    addr: &DaemonAddress,
-) -> Result<(), ClientError> {
+fn configure_daemon_stream(stream: &mut super::connect::DaemonStream, options: &ModuleListOptions) {
     if let super::connect::DaemonStream::Tcp(socket) = stream {
         // --sockopts is applied pre-connect inside open_daemon_stream (upstream:
         // socket.c:279-280 - set_socket_options() must run before connect(2)).
@@ -474,19 +471,10 @@ fn configure_daemon_stream(
         // Module listing has no transfer, so no bwlimit pacing applies.
         super::tcp_perf::apply_client_tcp_perf_options(socket, options.tcp_fastopen(), None);
 
-        if let Some(blocking) = options.blocking_io() {
-            socket.set_nonblocking(!blocking).map_err(|error| {
-                let action = if blocking {
-                    "set blocking mode for"
-                } else {
-                    "set nonblocking mode for"
-                };
-                socket_error(action, addr.socket_addr_display(), error)
-            })?;
-        }
+        // `--blocking-io` only governs the remote-shell child pipes (upstream:
+        // main.c:600-601 do_cmd(), pipe.c:80-82); the daemon socket keeps the
+        // blocking mode the reads below rely on.
     }
-
-    Ok(())
 }
 
 const fn effective_timeout(timeout: TransferTimeout, default: Duration) -> Option<Duration> {
@@ -40,6 +40,7 @@ pub use listing::{
     run_module_list_with_password, run_module_list_with_password_and_options,
 };
 pub use request::{ModuleListOptions, ModuleListRequest};
+pub use socket_options::{SocketOptionToken, socket_option_tokens};
 pub use types::DaemonAddress;
 
 #[allow(unused_imports)] // REASON: convenience re-export for sibling modules
@@ -254,7 +254,10 @@ impl ModuleListOptions {
         self.tcp_fastopen
     }
 
-    /// Configures the desired blocking I/O mode for daemon TCP sockets.
+    /// Configures the `--blocking-io` preference for a daemon-over-rsh listing.
+    ///
+    /// Only the spawned remote-shell pipes are affected; plain TCP daemon
+    /// sockets always stay blocking, as upstream.
     #[must_use]
     #[doc(alias = "--blocking-io")]
     #[doc(alias = "--no-blocking-io")]
@@ -7,6 +7,7 @@
 use std::ffi::OsStr;
 
 use super::lookup::{intern_name, lookup_socket_option, parse_socket_option_value};
+use super::tokens::{SocketOptionToken, socket_option_tokens};
 use super::types::ParsedSocketOption;
 #[cfg(not(target_family = "windows"))]
 use super::types::SocketOptionKind;
@@ -40,15 +41,11 @@ pub(crate) fn apply_socket_options(socket: &socket2::Socket, options: &OsStr) {
 
     let mut parsed = Vec::new();
 
-    for token in list
-        .split(|ch: char| ch == ',' || ch.is_ascii_whitespace())
-        .filter(|token| !token.is_empty())
+    for SocketOptionToken {
+        name,
+        value: value_str,
+    } in socket_option_tokens(&list)
     {
-        let (name, value_str) = match token.split_once('=') {
-            Some((name, value)) => (name, Some(value)),
-            None => (token, None),
-        };
-
         let Some(kind) = lookup_socket_option(name) else {
             // upstream: socket.c:704-707 - an unknown option name reports
             // `rprintf(FERROR,"Unknown socket option %s\n",tok)` and `continue`s.
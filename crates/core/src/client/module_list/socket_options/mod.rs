@@ -7,9 +7,11 @@
 mod apply;
 mod consts;
 mod lookup;
+mod tokens;
 mod types;
 
 pub(crate) use apply::apply_socket_options;
+pub use tokens::{SocketOptionToken, socket_option_tokens};
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,141 @@
+//! Tokenizer shared by every `--sockopts` / `socket options` consumer.
+//!
+//! Splits an option string into `NAME[=VALUE]` entries without interpreting
+//! them, so the client pre-connect applier and the daemon listener parser
+//! agree on the grammar.
+
+/// One `NAME[=VALUE]` entry from a socket options string.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SocketOptionToken<'a> {
+    /// Option name as written, e.g. `SO_SNDBUF`.
+    pub name: &'a str,
+    /// Text after the first `=`, or `None` when the entry has no value.
+    pub value: Option<&'a str>,
+}
+
+/// Splits a socket options string into its `NAME[=VALUE]` entries.
+///
+/// Entries are separated by commas, spaces, or tabs, so
+/// `SO_KEEPALIVE TCP_NODELAY` and `SO_KEEPALIVE,TCP_NODELAY` are equivalent.
+/// Empty entries are skipped. Unlike upstream, whitespace around the `=` of a
+/// single entry (`SO_SNDBUF = 65536`, common in hand-written rsyncd.conf
+/// files) is tolerated as long as it stays within one comma-separated group
+/// and the detached value is numeric. A detached word that is not a number
+/// is the next option name, so `SO_RCVBUF= TCP_NODELAY` leaves `SO_RCVBUF`
+/// with an empty value exactly as upstream's tokenizer does.
+///
+/// upstream: socket.c:set_socket_options() - `strtok(options, " \t,")`
+/// followed by `strchr(tok, '=')` to split off the value.
+pub fn socket_option_tokens(options: &str) -> Vec<SocketOptionToken<'_>> {
+    let mut tokens = Vec::new();
+
+    for group in options.split(',') {
+        let mut words = group.split([' ', '\t']).filter(|word| !word.is_empty());
+        let mut pending = words.next();
+
+        while let Some(word) = pending.take() {
+            let next = words.next();
+            let (name, value, consumed) = match (word.split_once('='), next) {
+                (Some((name, "")), Some(value)) if is_detached_value(value) => {
+                    (name, Some(value), true)
+                }
+                (Some((name, value)), _) => (name, Some(value), false),
+                (None, Some("=")) => {
+                    let following = words.next();
+                    match following {
+                        Some(value) if is_detached_value(value) => {
+                            tokens.push(SocketOptionToken {
+                                name: word,
+                                value: Some(value),
+                            });
+                            pending = words.next();
+                        }
+                        _ => {
+                            tokens.push(SocketOptionToken {
+                                name: word,
+                                value: Some(""),
+                            });
+                            pending = following;
+                        }
+                    }
+                    continue;
+                }
+                (None, Some(rest)) if rest.starts_with('=') => (word, rest.get(1..), true),
+                (None, _) => (word, None, false),
+            };
+
+            tokens.push(SocketOptionToken { name, value });
+            pending = if consumed { words.next() } else { next };
+        }
+    }
+
+    tokens
+}
+
+/// Reports whether a word split off from `NAME=` by whitespace is its value.
+///
+/// Socket option values are integers (`65536`, `0x10`, `-1`), while option
+/// names start with a letter, so a leading digit or sign tells them apart.
+fn is_detached_value(word: &str) -> bool {
+    word.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '+')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(options: &str) -> Vec<(&str, Option<&str>)> {
+        socket_option_tokens(options)
+            .into_iter()
+            .map(|token| (token.name, token.value))
+            .collect()
+    }
+
+    #[test]
+    fn splits_on_commas_spaces_and_tabs() {
+        assert_eq!(
+            collect("SO_KEEPALIVE, TCP_NODELAY\tSO_SNDBUF=4194304"),
+            vec![
+                ("SO_KEEPALIVE", None),
+                ("TCP_NODELAY", None),
+                ("SO_SNDBUF", Some("4194304")),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_empty_entries() {
+        assert!(collect("").is_empty());
+        assert_eq!(collect(",, TCP_NODELAY ,"), vec![("TCP_NODELAY", None)]);
+    }
+
+    #[test]
+    fn tolerates_spaces_around_equals() {
+        assert_eq!(
+            collect("TCP_NODELAY , SO_SNDBUF = 4096, SO_RCVBUF= 8192 IP_TOS =0x10"),
+            vec![
+                ("TCP_NODELAY", None),
+                ("SO_SNDBUF", Some("4096")),
+                ("SO_RCVBUF", Some("8192")),
+                ("IP_TOS", Some("0x10")),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_value_does_not_swallow_next_option() {
+        assert_eq!(
+            collect("SO_RCVBUF= TCP_NODELAY"),
+            vec![("SO_RCVBUF", Some("")), ("TCP_NODELAY", None)]
+        );
+        assert_eq!(
+            collect("SO_SNDBUF = SO_KEEPALIVE"),
+            vec![("SO_SNDBUF", Some("")), ("SO_KEEPALIVE", None)]
+        );
+    }
+
+    #[test]
+    fn keeps_empty_value_after_equals() {
+        assert_eq!(collect("SO_RCVBUF="), vec![("SO_RCVBUF", Some(""))]);
+    }
+}
@@ -0,0 +1,338 @@
+//! The slice of the Linux FUSE kernel protocol a read-only filesystem needs.
+//!
+//! Requests arrive from `/dev/fuse` as a `fuse_in_header` followed by an
+//! opcode-specific body; replies are a `fuse_out_header` followed by the
+//! opcode's `*_out` structure. Everything is native-endian and laid out as in
+//! `<linux/fuse.h>`; the encoders here write those structures field by field
+//! so no `unsafe` transmutes are needed.
+
+use std::ffi::OsStr;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Protocol major version spoken by this implementation.
+pub(super) const KERNEL_VERSION: u32 = 7;
+
+/// Highest protocol minor version this implementation understands. Minor 12
+/// is the oldest whose `fuse_attr` and `fuse_entry_out` layouts match ours.
+pub(super) const KERNEL_MINOR_VERSION: u32 = 31;
+const MIN_KERNEL_MINOR_VERSION: u32 = 12;
+
+/// Node id the kernel uses for the mount root.
+pub(super) const ROOT_ID: u64 = 1;
+
+/// Largest write the kernel may send. The filesystem rejects writes, so this
+/// only sizes the request buffer.
+pub(super) const MAX_WRITE: u32 = 4096;
+
+/// Request buffer size: the kernel refuses reads into anything smaller than
+/// its largest possible request.
+pub(super) const REQUEST_BUFFER_SIZE: usize = 64 * 1024;
+
+const IN_HEADER_SIZE: usize = 40;
+const OUT_HEADER_SIZE: usize = 16;
+const DIRENT_HEADER_SIZE: usize = 24;
+
+/// `fuse_init_out` size before minor 23 added `time_gran` and friends.
+const COMPAT_22_INIT_OUT_SIZE: usize = 24;
+
+/// `FOPEN_KEEP_CACHE`: the file's pages stay valid across opens.
+pub(super) const FOPEN_KEEP_CACHE: u32 = 1 << 1;
+
+// Request opcodes (`enum fuse_opcode`).
+pub(super) const LOOKUP: u32 = 1;
+pub(super) const FORGET: u32 = 2;
+pub(super) const GETATTR: u32 = 3;
+pub(super) const SETATTR: u32 = 4;
+pub(super) const READLINK: u32 = 5;
+pub(super) const SYMLINK: u32 = 6;
+pub(super) const MKNOD: u32 = 8;
+pub(super) const MKDIR: u32 = 9;
+pub(super) const UNLINK: u32 = 10;
+pub(super) const RMDIR: u32 = 11;
+pub(super) const RENAME: u32 = 12;
+pub(super) const LINK: u32 = 13;
+pub(super) const OPEN: u32 = 14;
+pub(super) const READ: u32 = 15;
+pub(super) const WRITE: u32 = 16;
+pub(super) const STATFS: u32 = 17;
+pub(super) const RELEASE: u32 = 18;
+pub(super) const FLUSH: u32 = 25;
+pub(super) const INIT: u32 = 26;
+pub(super) const OPENDIR: u32 = 27;
+pub(super) const READDIR: u32 = 28;
+pub(super) const RELEASEDIR: u32 = 29;
+pub(super) const ACCESS: u32 = 34;
+pub(super) const CREATE: u32 = 35;
+pub(super) const INTERRUPT: u32 = 36;
+pub(super) const DESTROY: u32 = 38;
+pub(super) const BATCH_FORGET: u32 = 42;
+pub(super) const RENAME2: u32 = 45;
+
+/// One request read from the FUSE device.
+#[derive(Debug)]
+pub(super) struct Request<'a> {
+    pub(super) opcode: u32,
+    pub(super) unique: u64,
+    pub(super) nodeid: u64,
+    pub(super) body: &'a [u8],
+}
+
+impl<'a> Request<'a> {
+    /// Splits a raw request into its header fields and body.
+    pub(super) fn parse(buf: &'a [u8]) -> io::Result<Self> {
+        let len = read_u32(buf, 0).ok_or_else(|| malformed("short request header"))?;
+        if buf.len() < IN_HEADER_SIZE || len as usize != buf.len() {
+            return Err(malformed("request length does not match its header"));
+        }
+        Ok(Self {
+            opcode: read_u32(buf, 4).unwrap_or_default(),
+            unique: read_u64(buf, 8).unwrap_or_default(),
+            nodeid: read_u64(buf, 16).unwrap_or_default(),
+            body: &buf[IN_HEADER_SIZE..],
+        })
+    }
+
+    /// Reads a native-endian `u32` at `offset` within the body.
+    pub(super) fn u32_at(&self, offset: usize) -> Option<u32> {
+        read_u32(self.body, offset)
+    }
+
+    /// Reads a native-endian `u64` at `offset` within the body.
+    pub(super) fn u64_at(&self, offset: usize) -> Option<u64> {
+        read_u64(self.body, offset)
+    }
+
+    /// The NUL-terminated name carried by `LOOKUP`.
+    pub(super) fn name(&self) -> Option<&'a OsStr> {
+        let end = self.body.iter().position(|&byte| byte == 0)?;
+        Some(OsStr::from_bytes(&self.body[..end]))
+    }
+}
+
+/// Attributes reported for one node (`struct fuse_attr`).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) struct Attr {
+    pub(super) ino: u64,
+    pub(super) size: u64,
+    pub(super) mtime: SystemTime,
+    /// File type and permission bits, as in `st_mode`.
+    pub(super) mode: u32,
+    pub(super) nlink: u32,
+    pub(super) uid: u32,
+    pub(super) gid: u32,
+}
+
+impl Attr {
+    fn encode(&self, out: &mut Vec<u8>) {
+        let (secs, nanos) = split_time(self.mtime);
+        put_u64(out, self.ino);
+        put_u64(out, self.size);
+        put_u64(out, self.size.div_ceil(512));
+        // atime, mtime and ctime all report the modification time.
+        for _ in 0..3 {
+            put_u64(out, secs as u64);
+        }
+        for _ in 0..3 {
+            put_u32(out, nanos);
+        }
+        put_u32(out, self.mode);
+        put_u32(out, self.nlink);
+        put_u32(out, self.uid);
+        put_u32(out, self.gid);
+        put_u32(out, 0); // rdev
+        put_u32(out, 4096); // blksize
+        put_u32(out, 0); // flags
+    }
+}
+
+/// Encodes the reply header for a successful request carrying `payload`.
+pub(super) fn reply(unique: u64, payload: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(OUT_HEADER_SIZE + payload.len());
+    put_u32(&mut out, (OUT_HEADER_SIZE + payload.len()) as u32);
+    put_i32(&mut out, 0);
+    put_u64(&mut out, unique);
+    out.extend_from_slice(payload);
+    out
+}
+
+/// Encodes an error reply; `errno` is the positive error number.
+pub(super) fn reply_error(unique: u64, errno: i32) -> Vec<u8> {
+    let mut out = Vec::with_capacity(OUT_HEADER_SIZE);
+    put_u32(&mut out, OUT_HEADER_SIZE as u32);
+    put_i32(&mut out, -errno);
+    put_u64(&mut out, unique);
+    out
+}
+
+/// Returns the error number an error reply carries, or `None` for success.
+#[cfg(test)]
+pub(super) fn reply_errno(reply: &[u8]) -> Option<i32> {
+    let error = read_u32(reply, 4)? as i32;
+    (error != 0).then_some(-error)
+}
+
+/// The payload of a reply, past its header.
+#[cfg(test)]
+pub(super) fn reply_payload(reply: &[u8]) -> &[u8] {
+    &reply[OUT_HEADER_SIZE..]
+}
+
+/// `fuse_init_out` for a kernel speaking `major.minor`, or `None` when the
+/// kernel is too old to talk to. A newer major gets our version alone, which
+/// asks it to retry `INIT` at major 7.
+pub(super) fn init_out(major: u32, minor: u32, max_readahead: u32) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(64);
+    put_u32(&mut out, KERNEL_VERSION);
+    put_u32(&mut out, KERNEL_MINOR_VERSION);
+    if major > KERNEL_VERSION {
+        return Some(out);
+    }
+    if major < KERNEL_VERSION || minor < MIN_KERNEL_MINOR_VERSION {
+        return None;
+    }
+    put_u32(&mut out, max_readahead);
+    put_u32(&mut out, 0); // flags
+    put_u16(&mut out, 0); // max_background
+    put_u16(&mut out, 0); // congestion_threshold
+    put_u32(&mut out, MAX_WRITE);
+    put_u32(&mut out, 1); // time_gran: nanosecond timestamps
+    out.resize(64, 0);
+    if minor < 23 {
+        out.truncate(COMPAT_22_INIT_OUT_SIZE);
+    }
+    Some(out)
+}
+
+/// `fuse_entry_out` for a successful `LOOKUP`.
+pub(super) fn entry_out(attr: &Attr, ttl: Duration) -> Vec<u8> {
+    let mut out = Vec::with_capacity(128);
+    put_u64(&mut out, attr.ino);
+    put_u64(&mut out, 0); // generation
+    put_u64(&mut out, ttl.as_secs());
+    put_u64(&mut out, ttl.as_secs());
+    put_u32(&mut out, ttl.subsec_nanos());
+    put_u32(&mut out, ttl.subsec_nanos());
+    attr.encode(&mut out);
+    out
+}
+
+/// `fuse_attr_out` for a successful `GETATTR`.
+pub(super) fn attr_out(attr: &Attr, ttl: Duration) -> Vec<u8> {
+    let mut out = Vec::with_capacity(104);
+    put_u64(&mut out, ttl.as_secs());
+    put_u32(&mut out, ttl.subsec_nanos());
+    put_u32(&mut out, 0);
+    attr.encode(&mut out);
+    out
+}
+
+/// `fuse_open_out` for `OPEN` and `OPENDIR`.
+pub(super) fn open_out(fh: u64, open_flags: u32) -> Vec<u8> {
+    let mut out = Vec::with_capacity(16);
+    put_u64(&mut out, fh);
+    put_u32(&mut out, open_flags);
+    put_u32(&mut out, 0);
+    out
+}
+
+/// `fuse_statfs_out` describing a full, read-only filesystem of `files`
+/// entries totalling `bytes`.
+pub(super) fn statfs_out(files: u64, bytes: u64) -> Vec<u8> {
+    const BLOCK_SIZE: u32 = 4096;
+    let mut out = Vec::with_capacity(80);
+    put_u64(&mut out, bytes.div_ceil(u64::from(BLOCK_SIZE)));
+    put_u64(&mut out, 0); // bfree
+    put_u64(&mut out, 0); // bavail
+    put_u64(&mut out, files);
+    put_u64(&mut out, 0); // ffree
+    put_u32(&mut out, BLOCK_SIZE);
+    put_u32(&mut out, 255); // namelen
+    put_u32(&mut out, BLOCK_SIZE);
+    out.resize(80, 0);
+    out
+}
+
+/// Appends one `fuse_dirent` to a `READDIR` reply unless it would grow the
+/// reply past `limit` bytes. `offset` is the cookie the kernel passes back to
+/// resume after this entry; `mode` supplies the `DT_*` type.
+pub(super) fn push_dirent(
+    out: &mut Vec<u8>,
+    limit: usize,
+    ino: u64,
+    offset: u64,
+    mode: u32,
+    name: &OsStr,
+) -> bool {
+    let name = name.as_bytes();
+    let len = (DIRENT_HEADER_SIZE + name.len()).next_multiple_of(8);
+    if out.len() + len > limit {
+        return false;
+    }
+    put_u64(out, ino);
+    put_u64(out, offset);
+    put_u32(out, name.len() as u32);
+    put_u32(out, (mode >> 12) & 0o17);
+    out.extend_from_slice(name);
+    out.resize(out.len() + len - DIRENT_HEADER_SIZE - name.len(), 0);
+    true
+}
+
+/// Whole seconds and nanoseconds since the epoch, negative before it.
+fn split_time(time: SystemTime) -> (i64, u32) {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(after) => (after.as_secs() as i64, after.subsec_nanos()),
+        Err(before) => {
+            let before = before.duration();
+            let secs = -(before.as_secs() as i64);
+            match before.subsec_nanos() {
+                0 => (secs, 0),
+                nanos => (secs - 1, 1_000_000_000 - nanos),
+            }
+        }
+    }
+}
+
+fn read_u32(buf: &[u8], offset: usize) -> Option<u32> {
+    let bytes = buf.get(offset..offset + 4)?;
+    Some(u32::from_ne_bytes(bytes.try_into().ok()?))
+}
+
+fn read_u64(buf: &[u8], offset: usize) -> Option<u64> {
+    let bytes = buf.get(offset..offset + 8)?;
+    Some(u64::from_ne_bytes(bytes.try_into().ok()?))
+}
+
+fn put_u16(out: &mut Vec<u8>, value: u16) {
+    out.extend_from_slice(&value.to_ne_bytes());
+}
+
+fn put_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_ne_bytes());
+}
+
+fn put_i32(out: &mut Vec<u8>, value: i32) {
+    out.extend_from_slice(&value.to_ne_bytes());
+}
+
+fn put_u64(out: &mut Vec<u8>, value: u64) {
+    out.extend_from_slice(&value.to_ne_bytes());
+}
+
+fn malformed(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_owned())
+}
+
+/// Builds a raw request the way the kernel lays one out, for tests.
+#[cfg(test)]
+pub(super) fn encode_request(opcode: u32, unique: u64, nodeid: u64, body: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(IN_HEADER_SIZE + body.len());
+    put_u32(&mut out, (IN_HEADER_SIZE + body.len()) as u32);
+    put_u32(&mut out, opcode);
+    put_u64(&mut out, unique);
+    put_u64(&mut out, nodeid);
+    out.resize(IN_HEADER_SIZE, 0);
+    out.extend_from_slice(body);
+    out
+}
@@ -0,0 +1,156 @@
+//! The `/dev/fuse` connection for one mount point.
+//!
+//! Mounting is delegated to the setuid `fusermount3` helper (falling back to
+//! the FUSE 2 `fusermount`), exactly as libfuse does for unprivileged users:
+//! the helper opens `/dev/fuse`, mounts it and hands the descriptor back over
+//! a socket named by `_FUSE_COMMFD`. This keeps the crate free of `unsafe`
+//! and of a libfuse dependency.
+
+use std::ffi::OsStr;
+use std::fs::File;
+use std::io::{self, IoSliceMut, Read, Write};
+use std::mem::MaybeUninit;
+use std::os::fd::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use rustix::io::{FdFlags, fcntl_setfd};
+use rustix::net::{
+    AddressFamily, RecvAncillaryBuffer, RecvAncillaryMessage, RecvFlags, SocketFlags, SocketType,
+    recvmsg, socketpair,
+};
+
+/// Mount helpers to try, newest first.
+const FUSERMOUNT_PROGRAMS: [&str; 2] = ["fusermount3", "fusermount"];
+
+/// An open FUSE device mounted on a directory. Dropping it unmounts.
+#[derive(Debug)]
+pub(super) struct FuseChannel {
+    device: File,
+    mountpoint: PathBuf,
+    program: &'static str,
+}
+
+impl FuseChannel {
+    /// Mounts a read-only FUSE filesystem named `fsname` on `mountpoint`.
+    pub(super) fn mount(mountpoint: &Path, fsname: &str, allow_other: bool) -> io::Result<Self> {
+        let mut options = format!(
+            "ro,nosuid,nodev,default_permissions,subtype=oc-rsync,fsname={}",
+            escape_option(fsname)
+        );
+        if allow_other {
+            options.push_str(",allow_other");
+        }
+
+        let mut last_error = None;
+        for program in FUSERMOUNT_PROGRAMS {
+            match mount_with(program, mountpoint, &options) {
+                Ok(device) => {
+                    return Ok(Self {
+                        device,
+                        mountpoint: mountpoint.to_path_buf(),
+                        program,
+                    });
+                }
+                Err(error) if error.kind() == io::ErrorKind::NotFound => last_error = Some(error),
+                Err(error) => return Err(error),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| io::Error::from(io::ErrorKind::NotFound)))
+    }
+
+    /// Reads the next request into `buf`, returning its length, or `None`
+    /// once the filesystem has been unmounted.
+    pub(super) fn receive(&self, buf: &mut [u8]) -> io::Result<Option<usize>> {
+        loop {
+            match (&self.device).read(buf) {
+                Ok(len) => return Ok(Some(len)),
+                Err(error) => match error.raw_os_error() {
+                    // The request was interrupted before we read it.
+                    Some(libc::ENOENT | libc::EINTR | libc::EAGAIN) => {}
+                    Some(libc::ENODEV) => return Ok(None),
+                    _ => return Err(error),
+                },
+            }
+        }
+    }
+
+    /// Writes one reply. Replies to requests the kernel has since abandoned
+    /// are dropped silently.
+    pub(super) fn send(&self, reply: &[u8]) -> io::Result<()> {
+        match (&self.device).write(reply) {
+            Ok(_) => Ok(()),
+            Err(error) if error.raw_os_error() == Some(libc::ENOENT) => Ok(()),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+impl Drop for FuseChannel {
+    fn drop(&mut self) {
+        // Lazy unmount: fails harmlessly when someone else already unmounted.
+        let _ = Command::new(self.program)
+            .args([OsStr::new("-u"), OsStr::new("-z"), OsStr::new("--")])
+            .arg(&self.mountpoint)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+}
+
+/// Runs one mount helper and receives the FUSE device it opened.
+fn mount_with(program: &str, mountpoint: &Path, options: &str) -> io::Result<File> {
+    let (ours, theirs) = socketpair(
+        AddressFamily::UNIX,
+        SocketType::STREAM,
+        SocketFlags::empty(),
+        None,
+    )?;
+    // Only the helper's end may survive into the child.
+    fcntl_setfd(&ours, FdFlags::CLOEXEC)?;
+
+    let status = Command::new(program)
+        .args(["-o", options, "--"])
+        .arg(mountpoint)
+        .env("_FUSE_COMMFD", theirs.as_raw_fd().to_string())
+        .stdin(Stdio::null())
+        .status()?;
+    drop(theirs);
+    if !status.success() {
+        return Err(io::Error::other(format!(
+            "{program} failed to mount {}: {status}",
+            mountpoint.display()
+        )));
+    }
+
+    let mut space = [MaybeUninit::uninit(); rustix::cmsg_space!(ScmRights(1))];
+    let mut control = RecvAncillaryBuffer::new(&mut space);
+    let mut byte = [0u8; 1];
+    recvmsg(
+        &ours,
+        &mut [IoSliceMut::new(&mut byte)],
+        &mut control,
+        RecvFlags::CMSG_CLOEXEC,
+    )?;
+    control
+        .drain()
+        .find_map(|message| match message {
+            RecvAncillaryMessage::ScmRights(mut fds) => fds.next(),
+            _ => None,
+        })
+        .map(File::from)
+        .ok_or_else(|| io::Error::other(format!("{program} did not pass back /dev/fuse")))
+}
+
+/// Escapes the characters `fusermount` treats as option separators.
+fn escape_option(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        if matches!(ch, ',' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
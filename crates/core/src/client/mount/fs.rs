@@ -0,0 +1,253 @@
+//! Request dispatch for the read-only module filesystem.
+//!
+//! Metadata requests are answered from the [`Tree`]. The first `OPEN` of a
+//! regular file fetches it whole into the cache directory; later opens and
+//! every `READ` are served from that local copy. Requests that would modify
+//! the tree fail with `EROFS`.
+
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs::File;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::FileExt;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use logging::debug_log;
+
+use super::super::summary::ClientEntryKind;
+use super::abi::{self, Request};
+use super::source::ModuleSource;
+use super::tree::Tree;
+
+/// How long the kernel may cache entries and attributes. The tree is a
+/// snapshot that never changes, so this only bounds memory in the kernel.
+const TTL: Duration = Duration::from_secs(60);
+
+/// The filesystem behind one mount.
+pub(super) struct ModuleFs<S> {
+    source: S,
+    tree: Tree,
+    cache_dir: PathBuf,
+    /// Local copies of the files fetched so far, by node id.
+    cached: HashMap<u64, PathBuf>,
+    /// Files opened by the kernel, by file handle.
+    handles: HashMap<u64, File>,
+    next_handle: u64,
+    uid: u32,
+    gid: u32,
+}
+
+impl<S: ModuleSource> ModuleFs<S> {
+    /// Serves `tree`, fetching file contents from `source` into `cache_dir`.
+    /// Every node is reported as owned by `uid`:`gid`.
+    pub(super) fn new(source: S, tree: Tree, cache_dir: PathBuf, uid: u32, gid: u32) -> Self {
+        Self {
+            source,
+            tree,
+            cache_dir,
+            cached: HashMap::new(),
+            handles: HashMap::new(),
+            next_handle: 1,
+            uid,
+            gid,
+        }
+    }
+
+    /// The source files are fetched from.
+    #[cfg(test)]
+    pub(super) fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// Handles one request, returning the reply to write back, or `None` for
+    /// the opcodes the kernel expects no reply to.
+    pub(super) fn handle(&mut self, request: &Request<'_>) -> Option<Vec<u8>> {
+        let unique = request.unique;
+        let result = match request.opcode {
+            abi::FORGET | abi::BATCH_FORGET | abi::INTERRUPT => return None,
+            abi::INIT => self.init(request),
+            abi::LOOKUP => self.lookup(request),
+            abi::GETATTR => self.getattr(request),
+            abi::READLINK => self.readlink(request),
+            abi::OPEN => self.open(request),
+            abi::READ => self.read(request),
+            abi::RELEASE => self.release(request),
+            abi::OPENDIR => self.opendir(request),
+            abi::READDIR => self.readdir(request),
+            abi::STATFS => Ok(abi::statfs_out(
+                self.tree.len() as u64,
+                self.tree.total_bytes(),
+            )),
+            abi::ACCESS => self.access(request),
+            abi::FLUSH | abi::RELEASEDIR | abi::DESTROY => Ok(Vec::new()),
+            abi::SETATTR
+            | abi::SYMLINK
+            | abi::MKNOD
+            | abi::MKDIR
+            | abi::UNLINK
+            | abi::RMDIR
+            | abi::RENAME
+            | abi::LINK
+            | abi::WRITE
+            | abi::CREATE
+            | abi::RENAME2 => Err(libc::EROFS),
+            _ => Err(libc::ENOSYS),
+        };
+        Some(match result {
+            Ok(payload) => abi::reply(unique, &payload),
+            Err(errno) => abi::reply_error(unique, errno),
+        })
+    }
+
+    fn init(&self, request: &Request<'_>) -> Result<Vec<u8>, i32> {
+        let major = request.u32_at(0).ok_or(libc::EINVAL)?;
+        let minor = request.u32_at(4).ok_or(libc::EINVAL)?;
+        let max_readahead = request.u32_at(8).ok_or(libc::EINVAL)?;
+        abi::init_out(major, minor, max_readahead).ok_or(libc::EPROTO)
+    }
+
+    fn lookup(&self, request: &Request<'_>) -> Result<Vec<u8>, i32> {
+        let name = request.name().ok_or(libc::EINVAL)?;
+        let ino = self.tree.lookup(request.nodeid, name).ok_or(libc::ENOENT)?;
+        let attr = self.attr(ino)?;
+        Ok(abi::entry_out(&attr, TTL))
+    }
+
+    fn getattr(&self, request: &Request<'_>) -> Result<Vec<u8>, i32> {
+        let attr = self.attr(request.nodeid)?;
+        Ok(abi::attr_out(&attr, TTL))
+    }
+
+    fn readlink(&self, request: &Request<'_>) -> Result<Vec<u8>, i32> {
+        let node = self.tree.get(request.nodeid).ok_or(libc::ENOENT)?;
+        let target = node.link_target.as_ref().ok_or(libc::EINVAL)?;
+        Ok(target.as_os_str().as_bytes().to_vec())
+    }
+
+    fn open(&mut self, request: &Request<'_>) -> Result<Vec<u8>, i32> {
+        let flags = request.u32_at(0).ok_or(libc::EINVAL)? as i32;
+        if flags & libc::O_ACCMODE != libc::O_RDONLY {
+            return Err(libc::EROFS);
+        }
+        let node = self.tree.get(request.nodeid).ok_or(libc::ENOENT)?;
+        match node.kind {
+            ClientEntryKind::File => {}
+            ClientEntryKind::Directory => return Err(libc::EISDIR),
+            _ => return Err(libc::EINVAL),
+        }
+
+        let local = self.fetch(request.nodeid)?;
+        let file = File::open(local).map_err(|error| errno(&error))?;
+        let handle = self.next_handle;
+        self.next_handle += 1;
+        self.handles.insert(handle, file);
+        Ok(abi::open_out(handle, abi::FOPEN_KEEP_CACHE))
+    }
+
+    /// Returns the local copy of file `ino`, fetching it on first use.
+    fn fetch(&mut self, ino: u64) -> Result<&PathBuf, i32> {
+        if !self.cached.contains_key(&ino) {
+            let node = self.tree.get(ino).ok_or(libc::ENOENT)?;
+            let local = self.cache_dir.join(ino.to_string());
+            if let Err(error) = self.source.fetch(&node.path, &local) {
+                debug_log!(
+                    Recv,
+                    1,
+                    "mount: fetching {} failed: {error}",
+                    node.path.display()
+                );
+                return Err(libc::EIO);
+            }
+            debug_log!(Recv, 2, "mount: fetched {}", node.path.display());
+            self.cached.insert(ino, local);
+        }
+        self.cached.get(&ino).ok_or(libc::EIO)
+    }
+
+    fn read(&self, request: &Request<'_>) -> Result<Vec<u8>, i32> {
+        let handle = request.u64_at(0).ok_or(libc::EINVAL)?;
+        let offset = request.u64_at(8).ok_or(libc::EINVAL)?;
+        let size = request.u32_at(16).ok_or(libc::EINVAL)? as usize;
+        let file = self.handles.get(&handle).ok_or(libc::EBADF)?;
+
+        let mut data = vec![0; size];
+        let mut filled = 0;
+        while filled < size {
+            match file.read_at(&mut data[filled..], offset + filled as u64) {
+                Ok(0) => break,
+                Ok(read) => filled += read,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return Err(errno(&error)),
+            }
+        }
+        data.truncate(filled);
+        Ok(data)
+    }
+
+    fn release(&mut self, request: &Request<'_>) -> Result<Vec<u8>, i32> {
+        let handle = request.u64_at(0).ok_or(libc::EINVAL)?;
+        self.handles.remove(&handle);
+        Ok(Vec::new())
+    }
+
+    fn opendir(&self, request: &Request<'_>) -> Result<Vec<u8>, i32> {
+        let node = self.tree.get(request.nodeid).ok_or(libc::ENOENT)?;
+        if node.kind != ClientEntryKind::Directory {
+            return Err(libc::ENOTDIR);
+        }
+        Ok(abi::open_out(0, 0))
+    }
+
+    /// Lists `.`, `..` and the children in name order. The kernel's offset
+    /// is the index of the next entry to return.
+    fn readdir(&self, request: &Request<'_>) -> Result<Vec<u8>, i32> {
+        let offset = request.u64_at(8).ok_or(libc::EINVAL)?;
+        let size = request.u32_at(16).ok_or(libc::EINVAL)? as usize;
+        let node = self.tree.get(request.nodeid).ok_or(libc::ENOENT)?;
+        if node.kind != ClientEntryKind::Directory {
+            return Err(libc::ENOTDIR);
+        }
+
+        let dots = [
+            (OsStr::new("."), request.nodeid),
+            (OsStr::new(".."), node.parent),
+        ];
+        let children = node
+            .children
+            .iter()
+            .map(|(name, &ino)| (name.as_os_str(), ino));
+        let mut out = Vec::with_capacity(size);
+        for (index, (name, ino)) in dots
+            .into_iter()
+            .chain(children)
+            .enumerate()
+            .skip(usize::try_from(offset).unwrap_or(usize::MAX))
+        {
+            let mode = self.tree.get(ino).map_or(0, |child| child.mode);
+            if !abi::push_dirent(&mut out, size, ino, index as u64 + 1, mode, name) {
+                break;
+            }
+        }
+        Ok(out)
+    }
+
+    fn access(&self, request: &Request<'_>) -> Result<Vec<u8>, i32> {
+        let mask = request.u32_at(0).ok_or(libc::EINVAL)? as i32;
+        self.tree.get(request.nodeid).ok_or(libc::ENOENT)?;
+        if mask & libc::W_OK != 0 {
+            return Err(libc::EROFS);
+        }
+        Ok(Vec::new())
+    }
+
+    fn attr(&self, ino: u64) -> Result<abi::Attr, i32> {
+        self.tree.attr(ino, self.uid, self.gid).ok_or(libc::ENOENT)
+    }
+}
+
+/// The error number to report for a local I/O failure.
+fn errno(error: &io::Error) -> i32 {
+    error.raw_os_error().unwrap_or(libc::EIO)
+}
@@ -0,0 +1,204 @@
+//! Read-only FUSE mounts of rsync daemon modules.
+//!
+//! oc-rsync extension with no upstream equivalent. [`mount_module`] lists a
+//! daemon directory once through the ordinary client (`--list-only
+//! --recursive`), builds an inode table from the listing and serves it over
+//! `/dev/fuse` until the mount point is unmounted. Directory walks, `stat`
+//! and `readlink` are answered from the listing; the first `open` of a file
+//! pulls it whole into a private cache directory and later reads come from
+//! that copy.
+//!
+//! Limitations worth knowing before relying on a mount:
+//!
+//! - The listing is a snapshot. Files added or changed on the daemon after
+//!   mounting are not seen until the module is remounted.
+//! - Requests are served one at a time, so opening a large file blocks every
+//!   other access to the mount while it is fetched.
+//! - The cache only grows; it is removed when the mount ends.
+//! - A process killed by a signal leaves the mount in place; clear it with
+//!   `fusermount3 -u <mountpoint>`.
+//!
+//! Mounting needs the `fusermount3` (or FUSE 2 `fusermount`) helper and a
+//! kernel with FUSE support. Only Linux is supported.
+
+mod abi;
+mod channel;
+mod fs;
+mod source;
+#[cfg(test)]
+mod tests;
+mod tree;
+
+use std::ffi::OsString;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use logging::debug_log;
+
+use crate::exit_code::ExitCode;
+use crate::message::Role;
+use crate::rsync_error;
+
+use self::abi::Request;
+use self::channel::FuseChannel;
+use self::fs::ModuleFs;
+use self::source::{DaemonSource, ModuleSource};
+use self::tree::Tree;
+use super::error::{ClientError, invalid_argument_error_typed};
+use super::run::is_daemon_operand;
+
+/// What to mount, where, and how.
+#[derive(Clone)]
+pub struct MountOptions {
+    source: OsString,
+    mountpoint: PathBuf,
+    cache_dir: Option<PathBuf>,
+    password: Option<Vec<u8>>,
+    allow_other: bool,
+}
+
+impl MountOptions {
+    /// Mounts the daemon directory `source` (`rsync://host/module/dir` or
+    /// `host::module/dir`) on the local directory `mountpoint`.
+    #[must_use]
+    pub fn new(source: impl Into<OsString>, mountpoint: impl Into<PathBuf>) -> Self {
+        Self {
+            source: source.into(),
+            mountpoint: mountpoint.into(),
+            cache_dir: None,
+            password: None,
+            allow_other: false,
+        }
+    }
+
+    /// Places the fetched-file cache under `dir` instead of the system
+    /// temporary directory.
+    #[must_use]
+    pub fn with_cache_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.cache_dir = dir;
+        self
+    }
+
+    /// Supplies the daemon password, as read from `--password-file`.
+    #[must_use]
+    #[doc(alias = "--password-file")]
+    pub fn with_password(mut self, password: Option<Vec<u8>>) -> Self {
+        self.password = password;
+        self
+    }
+
+    /// Lets users other than the one mounting access the mount. Requires
+    /// `user_allow_other` in `/etc/fuse.conf` for unprivileged users.
+    #[must_use]
+    pub const fn allow_other(mut self, allow: bool) -> Self {
+        self.allow_other = allow;
+        self
+    }
+
+    /// Returns the daemon directory being mounted.
+    #[must_use]
+    pub fn source(&self) -> &OsString {
+        &self.source
+    }
+
+    /// Returns the local mount point.
+    #[must_use]
+    pub fn mountpoint(&self) -> &Path {
+        &self.mountpoint
+    }
+
+    /// Returns the directory the cache is created under, if one was given.
+    #[must_use]
+    pub fn cache_dir(&self) -> Option<&Path> {
+        self.cache_dir.as_deref()
+    }
+
+    /// Returns whether other users may access the mount.
+    #[must_use]
+    pub const fn allows_other(&self) -> bool {
+        self.allow_other
+    }
+}
+
+impl fmt::Debug for MountOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MountOptions")
+            .field("source", &self.source)
+            .field("mountpoint", &self.mountpoint)
+            .field("cache_dir", &self.cache_dir)
+            .field("allow_other", &self.allow_other)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Mounts a daemon directory read-only and serves it until it is unmounted.
+///
+/// Blocks for the lifetime of the mount. Returns once the mount point is
+/// unmounted (`fusermount3 -u`, `umount`), or with an error when the daemon
+/// cannot be listed or the mount cannot be set up.
+pub fn mount_module(options: &MountOptions) -> Result<(), ClientError> {
+    if !is_daemon_operand(&options.source) {
+        return Err(invalid_argument_error_typed(
+            "mount: the source must be an rsync daemon directory \
+             (rsync://host/module or host::module)",
+            ExitCode::Syntax,
+        ));
+    }
+    if !options.mountpoint.is_dir() {
+        return Err(invalid_argument_error_typed(
+            &format!(
+                "mount: mount point {} is not a directory",
+                options.mountpoint.display()
+            ),
+            ExitCode::Syntax,
+        ));
+    }
+
+    let source = DaemonSource::new(&options.source, options.password.clone());
+    let tree = Tree::build(source.list()?);
+    debug_log!(Flist, 1, "mount: listed {} entries", tree.len() - 1);
+
+    let cache = match &options.cache_dir {
+        Some(dir) => tempfile::Builder::new()
+            .prefix("oc-rsync-mount.")
+            .tempdir_in(dir),
+        None => tempfile::Builder::new().prefix("oc-rsync-mount.").tempdir(),
+    }
+    .map_err(|error| mount_error(ExitCode::FileIo, "create the cache directory", error))?;
+
+    let fsname = options.source.to_string_lossy();
+    let channel =
+        FuseChannel::mount(&options.mountpoint, &fsname, options.allow_other).map_err(|error| {
+            let action = format!("mount {}", options.mountpoint.display());
+            mount_error(ExitCode::FileIo, &action, error)
+        })?;
+
+    let uid = rustix::process::getuid().as_raw();
+    let gid = rustix::process::getgid().as_raw();
+    let mut fs = ModuleFs::new(source, tree, cache.path().to_path_buf(), uid, gid);
+    serve(&channel, &mut fs)
+        .map_err(|error| mount_error(ExitCode::Ipc, "serve the FUSE device", error))
+}
+
+/// Answers kernel requests until the filesystem is unmounted.
+fn serve<S: ModuleSource>(channel: &FuseChannel, fs: &mut ModuleFs<S>) -> io::Result<()> {
+    let mut buf = vec![0; abi::REQUEST_BUFFER_SIZE];
+    while let Some(len) = channel.receive(&mut buf)? {
+        let request = Request::parse(&buf[..len])?;
+        if let Some(reply) = fs.handle(&request) {
+            channel.send(&reply)?;
+        }
+        if request.opcode == abi::DESTROY {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cold]
+fn mount_error(code: ExitCode, action: &str, error: io::Error) -> ClientError {
+    let message =
+        rsync_error!(code.as_i32(), "mount: failed to {action}: {error}").with_role(Role::Client);
+    ClientError::with_code(code, message)
+}
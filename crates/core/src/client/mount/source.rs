@@ -0,0 +1,143 @@
+//! Where the mounted tree comes from: the module's file list, and whole-file
+//! fetches of individual entries on first open.
+
+use std::ffi::{OsStr, OsString};
+use std::path::{Component, Path, PathBuf};
+use std::time::SystemTime;
+
+use super::super::config::ClientConfig;
+use super::super::error::{ClientError, invalid_argument_error_typed};
+use super::super::run::run_client;
+use super::super::summary::{ClientEntryKind, ClientEvent};
+use crate::exit_code::ExitCode;
+
+/// One entry of the module's file list.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(super) struct RemoteEntry {
+    /// Path relative to the mounted module directory; `.` is the root.
+    pub(super) path: PathBuf,
+    pub(super) kind: ClientEntryKind,
+    pub(super) size: u64,
+    /// Permission bits, when the listing carried them.
+    pub(super) mode: Option<u32>,
+    pub(super) modified: Option<SystemTime>,
+    pub(super) link_target: Option<PathBuf>,
+}
+
+impl RemoteEntry {
+    /// Converts a `--list-only` event, skipping events without metadata.
+    fn from_event(event: &ClientEvent) -> Option<Self> {
+        let metadata = event.metadata()?;
+        Some(Self {
+            path: event.relative_path().to_path_buf(),
+            kind: metadata.kind(),
+            size: metadata.length(),
+            mode: metadata.mode(),
+            modified: metadata.modified(),
+            link_target: metadata.symlink_target().map(Path::to_path_buf),
+        })
+    }
+}
+
+/// The remote side of a mount.
+pub(super) trait ModuleSource {
+    /// Lists every entry below the mounted directory.
+    fn list(&self) -> Result<Vec<RemoteEntry>, ClientError>;
+
+    /// Copies the file at `path` (relative to the mounted directory) to the
+    /// local file `destination`.
+    fn fetch(&self, path: &Path, destination: &Path) -> Result<(), ClientError>;
+}
+
+/// A directory on an rsync daemon, reached through the ordinary client.
+#[derive(Debug)]
+pub(super) struct DaemonSource {
+    /// `rsync://host/module/dir` or `host::module/dir`, as given.
+    base: OsString,
+    password: Option<Vec<u8>>,
+}
+
+impl DaemonSource {
+    pub(super) fn new(url: &OsStr, password: Option<Vec<u8>>) -> Self {
+        Self {
+            base: url.to_owned(),
+            password,
+        }
+    }
+
+    /// The operand naming `relative` below the mounted directory, with a
+    /// trailing `/` when `directory` is set so a listing is of its contents.
+    ///
+    /// Each path component is appended as one `/`-separated segment, whatever
+    /// the local separator, so only names taken from the file list can be
+    /// addressed.
+    ///
+    /// # Errors
+    ///
+    /// Fails when `relative` is absolute or climbs out with `..`.
+    pub(super) fn operand(
+        &self,
+        relative: &Path,
+        directory: bool,
+    ) -> Result<OsString, ClientError> {
+        let mut operand = self.base.clone();
+        for component in relative.components() {
+            match component {
+                Component::Normal(name) => {
+                    push_separator(&mut operand);
+                    operand.push(name);
+                }
+                Component::CurDir => {}
+                Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                    return Err(invalid_argument_error_typed(
+                        &format!(
+                            "mount: '{}' is not a path inside the module",
+                            relative.display()
+                        ),
+                        ExitCode::Syntax,
+                    ));
+                }
+            }
+        }
+        if directory {
+            push_separator(&mut operand);
+        }
+        Ok(operand)
+    }
+}
+
+/// Appends a `/` unless `operand` already ends in one.
+fn push_separator(operand: &mut OsString) {
+    if !operand.as_encoded_bytes().ends_with(b"/") {
+        operand.push("/");
+    }
+}
+
+impl ModuleSource for DaemonSource {
+    fn list(&self) -> Result<Vec<RemoteEntry>, ClientError> {
+        let config = ClientConfig::builder()
+            .transfer_args([self.operand(Path::new(""), true)?])
+            .list_only(true)
+            .recursive(true)
+            .force_event_collection(true)
+            .password_override(self.password.clone())
+            .build();
+        let summary = run_client(config)?;
+        Ok(summary
+            .events()
+            .iter()
+            .filter_map(RemoteEntry::from_event)
+            .collect())
+    }
+
+    fn fetch(&self, path: &Path, destination: &Path) -> Result<(), ClientError> {
+        let source = self.operand(path, false)?;
+        let config = ClientConfig::builder()
+            .transfer_args([source, destination.as_os_str().to_owned()])
+            .whole_file(true)
+            .times(true)
+            .password_override(self.password.clone())
+            .build();
+        run_client(config).map(drop)
+    }
+}
@@ -0,0 +1,402 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use super::abi::{self, Request};
+use super::fs::ModuleFs;
+use super::source::{DaemonSource, ModuleSource, RemoteEntry};
+use super::tree::Tree;
+use crate::client::ClientEntryKind;
+use crate::client::error::{ClientError, invalid_argument_error_typed};
+use crate::exit_code::ExitCode;
+
+const UID: u32 = 1000;
+const GID: u32 = 100;
+
+/// A listing held in memory; fetches copy from `contents` and are counted.
+#[derive(Default)]
+struct FakeSource {
+    contents: HashMap<PathBuf, Vec<u8>>,
+    fetches: Cell<usize>,
+}
+
+impl ModuleSource for FakeSource {
+    fn list(&self) -> Result<Vec<RemoteEntry>, ClientError> {
+        Ok(Vec::new())
+    }
+
+    fn fetch(&self, path: &Path, destination: &Path) -> Result<(), ClientError> {
+        self.fetches.set(self.fetches.get() + 1);
+        let data = self
+            .contents
+            .get(path)
+            .ok_or_else(|| invalid_argument_error_typed("no such file", ExitCode::FileIo))?;
+        fs::write(destination, data).unwrap();
+        Ok(())
+    }
+}
+
+fn entry(path: &str, kind: ClientEntryKind, size: u64) -> RemoteEntry {
+    RemoteEntry {
+        path: PathBuf::from(path),
+        kind,
+        size,
+        mode: None,
+        modified: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+        link_target: None,
+    }
+}
+
+fn sample_listing() -> Vec<RemoteEntry> {
+    let mut listing = vec![
+        entry(".", ClientEntryKind::Directory, 0),
+        entry("docs", ClientEntryKind::Directory, 0),
+        entry("docs/readme.txt", ClientEntryKind::File, 11),
+        entry("top.bin", ClientEntryKind::File, 4),
+        entry("fifo", ClientEntryKind::Fifo, 0),
+    ];
+    let mut link = entry("latest", ClientEntryKind::Symlink, 0);
+    link.link_target = Some(PathBuf::from("docs/readme.txt"));
+    listing.push(link);
+    listing[3].mode = Some(0o600);
+    listing
+}
+
+fn sample_fs(cache: &Path) -> ModuleFs<FakeSource> {
+    let mut source = FakeSource::default();
+    source
+        .contents
+        .insert(PathBuf::from("docs/readme.txt"), b"hello world".to_vec());
+    source
+        .contents
+        .insert(PathBuf::from("top.bin"), vec![1, 2, 3, 4]);
+    ModuleFs::new(
+        source,
+        Tree::build(sample_listing()),
+        cache.to_path_buf(),
+        UID,
+        GID,
+    )
+}
+
+fn call(fs: &mut ModuleFs<FakeSource>, opcode: u32, nodeid: u64, body: &[u8]) -> Vec<u8> {
+    let raw = abi::encode_request(opcode, 7, nodeid, body);
+    fs.handle(&Request::parse(&raw).unwrap()).unwrap()
+}
+
+fn name_body(name: &str) -> Vec<u8> {
+    let mut body = name.as_bytes().to_vec();
+    body.push(0);
+    body
+}
+
+/// A `fuse_read_in` (also used by `READDIR`).
+fn read_body(fh: u64, offset: u64, size: u32) -> Vec<u8> {
+    let mut body = Vec::new();
+    body.extend_from_slice(&fh.to_ne_bytes());
+    body.extend_from_slice(&offset.to_ne_bytes());
+    body.extend_from_slice(&size.to_ne_bytes());
+    body.resize(40, 0);
+    body
+}
+
+fn u32_at(buf: &[u8], offset: usize) -> u32 {
+    u32::from_ne_bytes(buf[offset..offset + 4].try_into().unwrap())
+}
+
+fn u64_at(buf: &[u8], offset: usize) -> u64 {
+    u64::from_ne_bytes(buf[offset..offset + 8].try_into().unwrap())
+}
+
+fn lookup(fs: &mut ModuleFs<FakeSource>, parent: u64, name: &str) -> u64 {
+    let reply = call(fs, abi::LOOKUP, parent, &name_body(name));
+    assert_eq!(abi::reply_errno(&reply), None, "lookup {name}");
+    u64_at(abi::reply_payload(&reply), 0)
+}
+
+/// `(ino, next offset, type, name)` for each `fuse_dirent` in a reply.
+fn dirents(payload: &[u8]) -> Vec<(u64, u64, u32, String)> {
+    let mut entries = Vec::new();
+    let mut pos = 0;
+    while pos < payload.len() {
+        let namelen = u32_at(payload, pos + 16) as usize;
+        let name = &payload[pos + 24..pos + 24 + namelen];
+        entries.push((
+            u64_at(payload, pos),
+            u64_at(payload, pos + 8),
+            u32_at(payload, pos + 20),
+            String::from_utf8(name.to_vec()).unwrap(),
+        ));
+        pos += (24 + namelen).next_multiple_of(8);
+    }
+    entries
+}
+
+#[test]
+fn tree_synthesises_parents_the_listing_omits() {
+    let tree = Tree::build(vec![entry("a/b/c.txt", ClientEntryKind::File, 3)]);
+    let a = tree.lookup(abi::ROOT_ID, OsStr::new("a")).unwrap();
+    let b = tree.lookup(a, OsStr::new("b")).unwrap();
+    let c = tree.lookup(b, OsStr::new("c.txt")).unwrap();
+
+    assert_eq!(tree.get(b).unwrap().kind, ClientEntryKind::Directory);
+    assert_eq!(tree.get(b).unwrap().path, Path::new("a/b"));
+    assert_eq!(tree.get(c).unwrap().parent, b);
+    assert_eq!(tree.attr(a, 0, 0).unwrap().mode, 0o040_755);
+    assert_eq!(tree.attr(a, 0, 0).unwrap().nlink, 3);
+    assert_eq!(tree.len(), 4);
+    assert_eq!(tree.total_bytes(), 3);
+}
+
+#[test]
+fn tree_skips_special_files() {
+    let tree = Tree::build(sample_listing());
+    assert_eq!(tree.lookup(abi::ROOT_ID, OsStr::new("fifo")), None);
+    assert!(tree.lookup(abi::ROOT_ID, OsStr::new("latest")).is_some());
+}
+
+#[test]
+fn lookup_and_getattr_report_listing_metadata() {
+    let cache = tempfile::tempdir().unwrap();
+    let mut fs = sample_fs(cache.path());
+
+    let top = lookup(&mut fs, abi::ROOT_ID, "top.bin");
+    let reply = call(&mut fs, abi::GETATTR, top, &[0; 16]);
+    assert_eq!(abi::reply_errno(&reply), None);
+    let attr = &abi::reply_payload(&reply)[16..];
+    assert_eq!(u64_at(attr, 0), top);
+    assert_eq!(u64_at(attr, 8), 4);
+    assert_eq!(u64_at(attr, 32), 1_700_000_000);
+    assert_eq!(u32_at(attr, 60), 0o100_600);
+    assert_eq!(u32_at(attr, 68), UID);
+    assert_eq!(u32_at(attr, 72), GID);
+
+    let missing = call(&mut fs, abi::LOOKUP, abi::ROOT_ID, &name_body("nope"));
+    assert_eq!(abi::reply_errno(&missing), Some(libc::ENOENT));
+}
+
+#[test]
+fn readlink_returns_the_listed_target() {
+    let cache = tempfile::tempdir().unwrap();
+    let mut fs = sample_fs(cache.path());
+
+    let link = lookup(&mut fs, abi::ROOT_ID, "latest");
+    let reply = call(&mut fs, abi::READLINK, link, &[]);
+    assert_eq!(abi::reply_payload(&reply), b"docs/readme.txt");
+}
+
+#[test]
+fn readdir_lists_dots_then_children_and_resumes_at_offset() {
+    let cache = tempfile::tempdir().unwrap();
+    let mut fs = sample_fs(cache.path());
+
+    let reply = call(&mut fs, abi::READDIR, abi::ROOT_ID, &read_body(0, 0, 4096));
+    let names: Vec<_> = dirents(abi::reply_payload(&reply))
+        .into_iter()
+        .map(|(_, offset, kind, name)| (offset, kind, name))
+        .collect();
+    assert_eq!(
+        names,
+        [
+            (1, libc::DT_DIR as u32, ".".to_owned()),
+            (2, libc::DT_DIR as u32, "..".to_owned()),
+            (3, libc::DT_DIR as u32, "docs".to_owned()),
+            (4, libc::DT_LNK as u32, "latest".to_owned()),
+            (5, libc::DT_REG as u32, "top.bin".to_owned()),
+        ]
+    );
+
+    let reply = call(&mut fs, abi::READDIR, abi::ROOT_ID, &read_body(0, 3, 4096));
+    let rest = dirents(abi::reply_payload(&reply));
+    assert_eq!(rest.len(), 2);
+    assert_eq!(rest[0].3, "latest");
+
+    // A buffer with room for only one entry returns just that one.
+    let reply = call(&mut fs, abi::READDIR, abi::ROOT_ID, &read_body(0, 0, 40));
+    assert_eq!(dirents(abi::reply_payload(&reply)).len(), 1);
+}
+
+#[test]
+fn open_fetches_once_and_reads_from_the_cache() {
+    let cache = tempfile::tempdir().unwrap();
+    let mut fs = sample_fs(cache.path());
+    let docs = lookup(&mut fs, abi::ROOT_ID, "docs");
+    let readme = lookup(&mut fs, docs, "readme.txt");
+
+    let mut handles = Vec::new();
+    for _ in 0..2 {
+        let reply = call(&mut fs, abi::OPEN, readme, &[0; 8]);
+        assert_eq!(abi::reply_errno(&reply), None);
+        let payload = abi::reply_payload(&reply);
+        assert_eq!(u32_at(payload, 8), abi::FOPEN_KEEP_CACHE);
+        handles.push(u64_at(payload, 0));
+    }
+    assert_ne!(handles[0], handles[1]);
+
+    let reply = call(&mut fs, abi::READ, readme, &read_body(handles[1], 6, 100));
+    assert_eq!(abi::reply_payload(&reply), b"world");
+
+    let reply = call(&mut fs, abi::RELEASE, readme, &read_body(handles[1], 0, 0));
+    assert_eq!(abi::reply_errno(&reply), None);
+    let reply = call(&mut fs, abi::READ, readme, &read_body(handles[1], 0, 100));
+    assert_eq!(abi::reply_errno(&reply), Some(libc::EBADF));
+
+    let reply = call(&mut fs, abi::READ, readme, &read_body(handles[0], 0, 5));
+    assert_eq!(abi::reply_payload(&reply), b"hello");
+
+    assert_eq!(fs.source().fetches.get(), 1);
+
+    let reply = call(&mut fs, abi::OPEN, docs, &[0; 8]);
+    assert_eq!(abi::reply_errno(&reply), Some(libc::EISDIR));
+}
+
+#[test]
+fn failed_fetch_reports_eio_and_is_retried() {
+    let cache = tempfile::tempdir().unwrap();
+    let mut fs = ModuleFs::new(
+        FakeSource::default(),
+        Tree::build(vec![entry("gone", ClientEntryKind::File, 1)]),
+        cache.path().to_path_buf(),
+        UID,
+        GID,
+    );
+    let gone = lookup(&mut fs, abi::ROOT_ID, "gone");
+    for _ in 0..2 {
+        let reply = call(&mut fs, abi::OPEN, gone, &[0; 8]);
+        assert_eq!(abi::reply_errno(&reply), Some(libc::EIO));
+    }
+    assert_eq!(fs.source().fetches.get(), 2);
+}
+
+#[test]
+fn modifying_requests_fail_read_only() {
+    let cache = tempfile::tempdir().unwrap();
+    let mut fs = sample_fs(cache.path());
+    let top = lookup(&mut fs, abi::ROOT_ID, "top.bin");
+
+    let write_open = (libc::O_WRONLY as u32).to_ne_bytes();
+    let reply = call(&mut fs, abi::OPEN, top, &write_open);
+    assert_eq!(abi::reply_errno(&reply), Some(libc::EROFS));
+
+    for opcode in [
+        abi::MKDIR,
+        abi::UNLINK,
+        abi::SETATTR,
+        abi::CREATE,
+        abi::RENAME,
+    ] {
+        let reply = call(&mut fs, opcode, abi::ROOT_ID, &[0; 16]);
+        assert_eq!(
+            abi::reply_errno(&reply),
+            Some(libc::EROFS),
+            "opcode {opcode}"
+        );
+    }
+
+    let reply = call(
+        &mut fs,
+        abi::ACCESS,
+        top,
+        &(libc::W_OK as u32).to_ne_bytes(),
+    );
+    assert_eq!(abi::reply_errno(&reply), Some(libc::EROFS));
+    let reply = call(
+        &mut fs,
+        abi::ACCESS,
+        top,
+        &(libc::R_OK as u32).to_ne_bytes(),
+    );
+    assert_eq!(abi::reply_errno(&reply), None);
+
+    let raw = abi::encode_request(abi::FORGET, 9, top, &[0; 8]);
+    assert!(fs.handle(&Request::parse(&raw).unwrap()).is_none());
+}
+
+#[test]
+fn init_negotiates_the_protocol_version() {
+    let cache = tempfile::tempdir().unwrap();
+    let mut fs = sample_fs(cache.path());
+    let init = |major: u32, minor: u32| {
+        let mut body = Vec::new();
+        for value in [major, minor, 128 * 1024, 0] {
+            body.extend_from_slice(&value.to_ne_bytes());
+        }
+        body
+    };
+
+    let reply = call(&mut fs, abi::INIT, 0, &init(7, 38));
+    let payload = abi::reply_payload(&reply);
+    assert_eq!(payload.len(), 64);
+    assert_eq!(u32_at(payload, 0), 7);
+    assert_eq!(u32_at(payload, 4), abi::KERNEL_MINOR_VERSION);
+    assert_eq!(u32_at(payload, 8), 128 * 1024);
+
+    let reply = call(&mut fs, abi::INIT, 0, &init(7, 19));
+    assert_eq!(abi::reply_payload(&reply).len(), 24);
+
+    let reply = call(&mut fs, abi::INIT, 0, &init(8, 0));
+    assert_eq!(abi::reply_payload(&reply).len(), 8);
+
+    let reply = call(&mut fs, abi::INIT, 0, &init(7, 5));
+    assert_eq!(abi::reply_errno(&reply), Some(libc::EPROTO));
+}
+
+#[test]
+fn request_parse_rejects_length_mismatch() {
+    let mut raw = abi::encode_request(abi::GETATTR, 1, abi::ROOT_ID, &[0; 16]);
+    raw.push(0);
+    assert!(Request::parse(&raw).is_err());
+    assert!(Request::parse(&raw[..8]).is_err());
+}
+
+#[test]
+fn push_dirent_pads_entries_and_respects_the_limit() {
+    let mut out = Vec::new();
+    assert!(abi::push_dirent(
+        &mut out,
+        64,
+        5,
+        1,
+        0o100_644,
+        OsStr::new("abc")
+    ));
+    assert_eq!(out.len(), 32);
+    assert!(!abi::push_dirent(
+        &mut out,
+        64,
+        6,
+        2,
+        0o040_755,
+        OsStr::new("longer-name")
+    ));
+    assert_eq!(out.len(), 32);
+    assert_eq!(
+        dirents(&out),
+        [(5, 1, libc::DT_REG as u32, "abc".to_owned())]
+    );
+}
+
+#[test]
+fn daemon_operands_join_one_segment_per_component() {
+    let source = DaemonSource::new(OsStr::new("rsync://host/module/dir"), None);
+    let listing = source.operand(Path::new(""), true).unwrap();
+    assert_eq!(listing, "rsync://host/module/dir/");
+    let file = source.operand(Path::new("./sub/a b.txt"), false).unwrap();
+    assert_eq!(file, "rsync://host/module/dir/sub/a b.txt");
+
+    let source = DaemonSource::new(OsStr::new("host::module/"), None);
+    assert_eq!(
+        source.operand(Path::new("f"), false).unwrap(),
+        "host::module/f"
+    );
+    assert_eq!(
+        source.operand(Path::new(""), true).unwrap(),
+        "host::module/"
+    );
+
+    assert!(source.operand(Path::new("../escape"), false).is_err());
+    assert!(source.operand(Path::new("/etc/passwd"), false).is_err());
+}
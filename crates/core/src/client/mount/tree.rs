@@ -0,0 +1,205 @@
+//! The inode table built from the module's file list.
+//!
+//! The listing is taken once at mount time, so the table never changes: node
+//! ids are indices into a vector and the kernel's lookup counts need no
+//! bookkeeping. Directories the listing implies but does not name (an
+//! excluded parent, say) are synthesised so every entry stays reachable.
+
+use std::collections::BTreeMap;
+use std::ffi::{OsStr, OsString};
+use std::path::{Component, PathBuf};
+use std::time::SystemTime;
+
+use logging::debug_log;
+
+use super::super::summary::ClientEntryKind;
+use super::abi::{Attr, ROOT_ID};
+use super::source::RemoteEntry;
+
+const S_IFDIR: u32 = 0o040_000;
+const S_IFREG: u32 = 0o100_000;
+const S_IFLNK: u32 = 0o120_000;
+
+/// One file, directory or symlink in the mounted tree.
+#[derive(Debug)]
+pub(super) struct Node {
+    pub(super) parent: u64,
+    /// Path relative to the mounted directory; empty for the root.
+    pub(super) path: PathBuf,
+    pub(super) kind: ClientEntryKind,
+    pub(super) size: u64,
+    /// File type and permission bits, as in `st_mode`.
+    pub(super) mode: u32,
+    pub(super) mtime: SystemTime,
+    pub(super) link_target: Option<PathBuf>,
+    pub(super) children: BTreeMap<OsString, u64>,
+}
+
+impl Node {
+    /// A node with default permissions, used until the listing supplies
+    /// its own and for directories the listing only implies.
+    fn new(parent: u64, path: PathBuf, kind: ClientEntryKind) -> Self {
+        let mode = match kind {
+            ClientEntryKind::Directory => S_IFDIR | 0o755,
+            ClientEntryKind::Symlink => S_IFLNK | 0o777,
+            _ => S_IFREG | 0o644,
+        };
+        Self {
+            parent,
+            path,
+            kind,
+            size: 0,
+            mode,
+            mtime: SystemTime::UNIX_EPOCH,
+            link_target: None,
+            children: BTreeMap::new(),
+        }
+    }
+}
+
+/// The inode table. Node id `n` lives at index `n - 1`.
+#[derive(Debug)]
+pub(super) struct Tree {
+    nodes: Vec<Node>,
+}
+
+impl Tree {
+    /// Builds the table from a listing. Entries other than regular files,
+    /// directories and symlinks are left out.
+    pub(super) fn build(entries: Vec<RemoteEntry>) -> Self {
+        let root = Node::new(ROOT_ID, PathBuf::new(), ClientEntryKind::Directory);
+        let mut tree = Self { nodes: vec![root] };
+        for entry in entries {
+            if !matches!(
+                entry.kind,
+                ClientEntryKind::File | ClientEntryKind::Directory | ClientEntryKind::Symlink
+            ) {
+                debug_log!(
+                    Flist,
+                    2,
+                    "mount: skipping special file {}",
+                    entry.path.display()
+                );
+                continue;
+            }
+            tree.insert(entry);
+        }
+        tree
+    }
+
+    fn insert(&mut self, entry: RemoteEntry) {
+        let names: Vec<&OsStr> = entry
+            .path
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => Some(name),
+                _ => None,
+            })
+            .collect();
+
+        let mut ino = ROOT_ID;
+        let mut path = PathBuf::new();
+        for name in names {
+            path.push(name);
+            ino = match self.node(ino).children.get(name).copied() {
+                Some(child) => child,
+                None => self.add_child(ino, name, path.clone()),
+            };
+        }
+
+        let node = self.node_mut(ino);
+        if node.kind != entry.kind {
+            // The root, and a directory with entries beneath it, stay
+            // directories whatever the listing says about them.
+            if ino == ROOT_ID || !node.children.is_empty() {
+                return;
+            }
+            *node = Node::new(node.parent, node.path.clone(), entry.kind);
+        }
+        if let Some(mode) = entry.mode {
+            node.mode = (node.mode & !0o7777) | (mode & 0o7777);
+        }
+        node.size = entry.size;
+        node.mtime = entry.modified.unwrap_or(SystemTime::UNIX_EPOCH);
+        node.link_target = entry.link_target;
+    }
+
+    fn add_child(&mut self, parent: u64, name: &OsStr, path: PathBuf) -> u64 {
+        let ino = self.nodes.len() as u64 + 1;
+        self.nodes
+            .push(Node::new(parent, path, ClientEntryKind::Directory));
+        let parent = self.node_mut(parent);
+        if parent.kind != ClientEntryKind::Directory {
+            // Something is listed beneath a file; the listing wins.
+            *parent = Node::new(
+                parent.parent,
+                parent.path.clone(),
+                ClientEntryKind::Directory,
+            );
+        }
+        parent.children.insert(name.to_owned(), ino);
+        ino
+    }
+
+    fn node(&self, ino: u64) -> &Node {
+        &self.nodes[ino as usize - 1]
+    }
+
+    fn node_mut(&mut self, ino: u64) -> &mut Node {
+        &mut self.nodes[ino as usize - 1]
+    }
+
+    /// Returns the node with id `ino`.
+    pub(super) fn get(&self, ino: u64) -> Option<&Node> {
+        let index = usize::try_from(ino).ok()?.checked_sub(1)?;
+        self.nodes.get(index)
+    }
+
+    /// Returns the id of `name` within the directory `parent`.
+    pub(super) fn lookup(&self, parent: u64, name: &OsStr) -> Option<u64> {
+        self.get(parent)?.children.get(name).copied()
+    }
+
+    /// The attributes the kernel sees for `ino`, owned by `uid`:`gid`.
+    pub(super) fn attr(&self, ino: u64, uid: u32, gid: u32) -> Option<Attr> {
+        let node = self.get(ino)?;
+        let nlink = match node.kind {
+            ClientEntryKind::Directory => {
+                let subdirs = node
+                    .children
+                    .values()
+                    .filter(|&&child| self.node(child).kind == ClientEntryKind::Directory)
+                    .count();
+                2 + subdirs as u32
+            }
+            _ => 1,
+        };
+        let size = match &node.link_target {
+            Some(target) => target.as_os_str().len() as u64,
+            None => node.size,
+        };
+        Some(Attr {
+            ino,
+            size,
+            mtime: node.mtime,
+            mode: node.mode,
+            nlink,
+            uid,
+            gid,
+        })
+    }
+
+    /// Number of nodes, the root included.
+    pub(super) fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Total size of the regular files in the tree.
+    pub(super) fn total_bytes(&self) -> u64 {
+        self.nodes
+            .iter()
+            .filter(|node| node.kind == ClientEntryKind::File)
+            .map(|node| node.size)
+            .sum()
+    }
+}
@@ -603,7 +603,7 @@ fn run_blocking_server(
     match transfer_result {
         Ok(stats) => Ok((stats, start.elapsed(), negotiated_protocol)),
         Err(err) => {
-            let exit = ExitCode::from_io_error(&err);
+            let exit = ExitCode::from_transfer_error(&err);
             Err(invalid_argument_error_typed(
                 &format!("async SSH transfer failed: {err}"),
                 exit,
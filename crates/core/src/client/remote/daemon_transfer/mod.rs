@@ -323,6 +323,7 @@ pub fn run_daemon_over_remote_shell(
         jump_hosts: config.jump_hosts(),
         connect_timeout: config.connect_timeout().effective(Duration::from_secs(30)),
         address_mode: config.address_mode(),
+        blocking_io: config.blocking_io(),
     })?;
 
     let transfer_timeout = config
@@ -15,6 +15,7 @@ use crate::client::config::{
 use crate::client::error::{ClientError, socket_error};
 use crate::client::remote::daemon_transfer::connection::DaemonTransferRequest;
 use crate::client::remote::flags;
+use crate::client::remote::invocation::format_system_time_for_stop_at;
 use crate::client::remote::output_option::{OutputWordKind, make_output_option};
 
 /// Sends daemon-mode arguments to the server.
@@ -408,6 +409,15 @@ pub(super) fn build_full_daemon_args(
         args.push(format!("--timeout={}", secs.get()));
     }
 
+    // upstream: options.c:server_options() - stop_at_utime travels as
+    // --stop-at=YYYY/MM/DDTHH:MM (UTC) so the daemon stops at the same
+    // deadline; --stop-after was already resolved to an absolute time.
+    if let Some(deadline) = config.stop_at() {
+        if let Some(formatted) = format_system_time_for_stop_at(deadline) {
+            args.push(format!("--stop-at={formatted}"));
+        }
+    }
+
     // upstream: options.c:2799 - `--bwlimit=%d` forwards the rate in whole KiB
     // (options.c:1718), NOT bytes: the remote peer re-parses the value with a
     // default `K` suffix, so a byte count would be scaled up 1024x and the
@@ -567,9 +577,18 @@ pub(super) fn build_full_daemon_args(
         if config.fsync() {
             args.push("--fsync".to_owned());
         }
+        if config.fsync_dir() {
+            args.push("--fsync-dir".to_owned());
+        }
         if let Some(depth) = config.io_uring_depth() {
             args.push(format!("--io-uring-depth={depth}"));
         }
+        if config.parallel_commit() > 1 {
+            args.push(format!("--parallel-commit={}", config.parallel_commit()));
+        }
+        if config.direct_write() {
+            args.push("--direct-write".to_owned());
+        }
 
         // upstream: options.c:2933-2941 - --compare-dest/copy-dest/link-dest
         // sent only when client is sender (push).
@@ -722,6 +741,12 @@ pub(super) fn build_full_daemon_args(
         args.push("--open-noatime".to_owned());
     }
 
+    // oc-rsync extension: --drop-cache is not role gated either; the daemon
+    // evicts the files it sends on a pull and the files it commits on a push.
+    if config.drop_cache() {
+        args.push("--drop-cache".to_owned());
+    }
+
     // upstream: options.c:2962-2980 - server_options() forwards the
     // files-from arg only when the remote peer reads the list. `is_sender`
     // here means the daemon is the sender (PULL), so the local side pushes
@@ -1520,6 +1545,25 @@ mod server_option_fidelity_tests {
         assert!(args(&config, false).iter().any(|a| a == "--timeout=60"));
     }
 
+    // upstream: options.c:server_options() - the deadline is forwarded in the
+    // absolute UTC form the daemon parses back.
+    #[test]
+    fn stop_at_forwarded() {
+        let deadline =
+            std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(951_914_040);
+        let config = ClientConfig::builder().stop_at(Some(deadline)).build();
+        assert!(
+            args(&config, false)
+                .iter()
+                .any(|a| a == "--stop-at=2000/03/01T12:34")
+        );
+        assert!(
+            !args(&ClientConfig::builder().build(), true)
+                .iter()
+                .any(|a| a.starts_with("--stop-at"))
+        );
+    }
+
     // WHY: upstream options.c:2799 forwards `--bwlimit=%d` in whole KiB
     // (options.c:1718 `bwlimit = (size + 512) / 1024`), NOT bytes/sec. The
     // remote peer re-parses the value with a default `K` suffix (options.c:1714
@@ -1686,6 +1730,15 @@ mod server_option_fidelity_tests {
         let config = ClientConfig::builder().open_noatime(true).build();
         assert!(args(&config, false).iter().any(|a| a == "--open-noatime"));
     }
+
+    #[test]
+    fn drop_cache_forwarded_in_both_directions() {
+        let config = ClientConfig::builder().drop_cache(true).build();
+        assert!(args(&config, false).iter().any(|a| a == "--drop-cache"));
+        assert!(args(&config, true).iter().any(|a| a == "--drop-cache"));
+        let config = ClientConfig::builder().build();
+        assert!(!args(&config, false).iter().any(|a| a == "--drop-cache"));
+    }
 }
 
 #[cfg(test)]
@@ -223,10 +223,20 @@ fn apply_common_daemon_config(
     // Local-only sender optimization; never emitted onto the wire, so it is
     // carried directly onto the in-process generator's ParsedServerFlags.
     server_config.flags.parallel_delta_scan = config.parallel_delta_scan();
+    server_config.flags.mmap_source = config.mmap_source();
 
     server_config.write.fsync = config.fsync();
+    server_config.write.fsync_dir = config.fsync_dir();
     server_config.write.io_uring_policy = config.io_uring_policy();
     server_config.write.io_uring_depth = config.io_uring_depth();
+    server_config.write.parallel_commit = config.parallel_commit();
+    server_config.write.direct_write = config.direct_write();
+    server_config.file_selection.cache_dir = config.cache_dir().map(std::path::Path::to_path_buf);
+    server_config.file_selection.checkpoint = config.checkpoint().map(std::path::Path::to_path_buf);
+    server_config.file_selection.resume = config.resume().map(std::path::Path::to_path_buf);
+    server_config.file_selection.case_collision = config.case_collision();
+    server_config.file_selection.windows_names = config.windows_names();
+    server_config.file_selection.transfer_order = config.transfer_order();
     server_config.write.zero_copy_policy = config.zero_copy_policy();
     // checksum_choice is set once in `apply_common_server_flags` (called above
     // for both receiver and generator), shared with the SSH transfer paths.
@@ -8,6 +8,7 @@ use std::time::Duration;
 use crate::client::summary::{
     ClientEntryMetadata, ClientEvent, ClientSummary, ListOnlyEntryFields,
 };
+use crate::exit_code::ExitCode;
 
 /// Converts server-side statistics to a client summary.
 ///
@@ -48,8 +49,8 @@ pub(super) fn convert_server_stats_to_summary(
         ServerStats::Generator(_) => Vec::new(),
     };
 
-    let (local_summary, io_error, error_count) = match stats {
-        ServerStats::Receiver(ref transfer_stats) => {
+    let (local_summary, io_error, error_count, stop_limit_reached, remaining_files) = match stats {
+        ServerStats::Receiver(transfer_stats) => {
             // Daemon-pull: local side ran the receiver and its `--delete`
             // sweep. The per-type counters live on `delete_stats`.
             let s = LocalCopySummary::from_receiver_stats(
@@ -71,9 +72,15 @@ pub(super) fn convert_server_stats_to_summary(
                     specials: transfer_stats.num_specials,
                 },
             );
-            (s, transfer_stats.io_error, transfer_stats.error_count)
+            (
+                s,
+                transfer_stats.io_error,
+                transfer_stats.error_count,
+                transfer_stats.stop_limit_reached,
+                transfer_stats.remaining_files,
+            )
         }
-        ServerStats::Generator(ref generator_stats) => {
+        ServerStats::Generator(generator_stats) => {
             // Daemon-upload: local side ran the sender/generator. The remote
             // receiver ran the `--delete` sweep and reported the per-type
             // counters via `NDX_DEL_STATS` during the goodbye phase
@@ -97,7 +104,13 @@ pub(super) fn convert_server_stats_to_summary(
                     specials: generator_stats.num_specials,
                 },
             );
-            (s, generator_stats.io_error, 0u32)
+            (
+                s,
+                generator_stats.io_error,
+                0u32,
+                generator_stats.stop_limit_reached,
+                generator_stats.remaining_files,
+            )
         }
     };
 
@@ -111,9 +124,17 @@ pub(super) fn convert_server_stats_to_summary(
     if exit_code != 0 {
         summary.set_io_error_exit_code(exit_code);
     } else if error_count > 0 {
-        // Remote sender reported errors via MSG_ERROR - treat as RERR_PARTIAL (23).
+        // upstream: log.c:311, main.c:1635 - the remote sender forwarded
+        // MSG_ERROR_XFER, so got_xfer_error yields RERR_PARTIAL (23).
         summary.set_io_error_exit_code(23);
     }
+    // upstream: io.c:check_timeout() - `--stop-at` / `--stop-after` end the
+    // run with RERR_TIMEOUT. Here the transfer wound down cleanly first, so
+    // only the exit code carries the early stop.
+    if stop_limit_reached {
+        summary.set_io_error_exit_code(ExitCode::Timeout.as_i32());
+        summary.set_remaining_files(remaining_files);
+    }
 
     summary
 }
@@ -13,7 +13,7 @@ use protocol::ProtocolVersion;
 use super::server_config::{build_server_config_for_generator, build_server_config_for_receiver};
 use super::stats::convert_server_stats_to_summary;
 use crate::client::config::ClientConfig;
-use crate::client::error::{ClientError, invalid_argument_error, remote_exit_error};
+use crate::client::error::{ClientError, invalid_argument_error_typed, remote_exit_error};
 use crate::client::module_list::{
     DaemonStreamGuard, DaemonStreamReader, DaemonStreamWriter, build_io_timeout_reapply,
 };
@@ -24,6 +24,7 @@ use crate::client::remote::implied_source::implied_source_args_for_pull;
 use crate::client::summary::ClientSummary;
 use crate::exit_code::ExitCode;
 use crate::message::Role;
+use crate::server::error::remote_exit_code;
 use crate::server::handshake::HandshakeResult;
 use crate::server::{TransferProgressCallback, TransferProgressEvent};
 
@@ -239,7 +240,9 @@ pub(crate) fn run_push_transfer(
 /// reader already delivered it to stderr in wire order.
 ///
 /// Failures with no embedded remote code (local I/O, protocol desync) keep the
-/// prior generic `transfer failed: ...` (23) diagnostic.
+/// generic `transfer failed: ...` diagnostic, with the exit code chosen by
+/// [`ExitCode::from_transfer_error`] (e.g. 12 for a truncated stream, 30 for a
+/// timeout).
 ///
 /// upstream: io.c:1663-1701 - `MSG_ERROR_EXIT` drives the NORETURN
 /// `_exit_cleanup(val)`, so the client's final exit code is the peer's code.
@@ -248,21 +251,10 @@ fn map_server_transfer_error(error: std::io::Error, role: Role) -> ClientError {
         let exit = ExitCode::from_i32(code).unwrap_or(ExitCode::PartialTransfer);
         return remote_exit_error(exit, role, "");
     }
-    invalid_argument_error(&format!("transfer failed: {error}"), 23)
-}
-
-/// Walks the source chain of an `io::Error` looking for a
-/// [`transfer::RemoteExitError`], returning the peer-supplied exit code when the
-/// failure originated from a remote `MSG_ERROR_EXIT` frame.
-fn remote_exit_code(error: &std::io::Error) -> Option<i32> {
-    let mut source: Option<&(dyn std::error::Error + 'static)> = Some(error.get_ref()?);
-    while let Some(err) = source {
-        if let Some(remote) = err.downcast_ref::<crate::server::RemoteExitError>() {
-            return Some(remote.code);
-        }
-        source = err.source();
-    }
-    None
+    invalid_argument_error_typed(
+        &format!("transfer failed: {error}"),
+        ExitCode::from_transfer_error(&error),
+    )
 }
 
 /// Builds a `HandshakeResult` for daemon transfers where the protocol version
@@ -433,15 +425,19 @@ mod map_server_transfer_error_tests {
         assert!(err.to_string().contains("[receiver="), "{err}");
     }
 
-    /// Failures with no embedded remote code keep the prior generic
-    /// `transfer failed: ...` (23) behaviour.
+    /// Failures with no embedded remote code keep the generic
+    /// `transfer failed: ...` prefix; a broken stream is `RERR_STREAMIO` (12)
+    /// and an unclassified failure stays partial-transfer (23).
     #[test]
-    fn maps_plain_failure_to_generic_partial_transfer() {
+    fn maps_plain_failure_to_transfer_phase_exit_code() {
         let err = map_server_transfer_error(
             std::io::Error::new(std::io::ErrorKind::BrokenPipe, "pipe"),
             Role::Receiver,
         );
-        assert_eq!(err.exit_code(), 23);
+        assert_eq!(err.exit_code(), 12);
         assert!(err.to_string().contains("transfer failed"), "{err}");
+
+        let err = map_server_transfer_error(std::io::Error::other("desync"), Role::Receiver);
+        assert_eq!(err.exit_code(), 23);
     }
 }
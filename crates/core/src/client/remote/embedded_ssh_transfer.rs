@@ -411,7 +411,7 @@ fn run_transfer_over_embedded_ssh(
             )),
         },
         Err(e) => {
-            let exit = ExitCode::from_io_error(&e);
+            let exit = ExitCode::from_transfer_error(&e);
             Err(invalid_argument_error(
                 &format!("transfer failed: {e}"),
                 exit.as_i32(),
@@ -614,6 +614,15 @@ fn build_server_config_for_receiver(
     // already sets this in apply_common_daemon_config; both ssh builders dropped
     // it, so the ssh:// pull never fsync'd its writes.
     server_config.write.fsync = config.fsync();
+    server_config.write.fsync_dir = config.fsync_dir();
+    server_config.write.parallel_commit = config.parallel_commit();
+    server_config.write.direct_write = config.direct_write();
+    server_config.file_selection.cache_dir = config.cache_dir().map(std::path::Path::to_path_buf);
+    server_config.file_selection.checkpoint = config.checkpoint().map(std::path::Path::to_path_buf);
+    server_config.file_selection.resume = config.resume().map(std::path::Path::to_path_buf);
+    server_config.file_selection.case_collision = config.case_collision();
+    server_config.file_selection.windows_names = config.windows_names();
+    server_config.file_selection.transfer_order = config.transfer_order();
     // upstream: options.c:2979-2980 - `if (write_devices && am_sender)
     // --write-devices`. --write-devices makes the receiver write file content
     // in-place into an existing device node (receiver.c: write_devices &&
@@ -682,6 +691,7 @@ fn build_server_config_for_generator(
     // Local-only sender optimization; never emitted onto the wire, so it is
     // carried directly onto the in-process generator's ParsedServerFlags.
     server_config.flags.parallel_delta_scan = config.parallel_delta_scan();
+    server_config.flags.mmap_source = config.mmap_source();
     // upstream: --chmod is parsed into `chmod_modes` (options.c:1762) and is
     // never placed in server_options, so it is never forwarded to the remote
     // receiver. On a push the local client IS the sender and applies the
@@ -912,6 +922,18 @@ mod tests {
         assert!(server_config.write.fsync);
     }
 
+    /// `--fsync-dir` follows `--fsync`: on a pull the local receiver syncs
+    /// the directories it renames into.
+    #[test]
+    fn embedded_receiver_config_propagates_fsync_dir() {
+        let config = ClientConfig::builder().fsync_dir(true).build();
+        let server_config =
+            build_server_config_for_receiver(&config, &["dest".to_owned()]).unwrap();
+
+        assert!(server_config.write.fsync_dir);
+        assert!(!server_config.write.fsync);
+    }
+
     /// The embedded (russh) pull receiver must carry --write-devices onto its
     /// ServerConfig. Upstream options.c:2979-2980 forwards it to the remote only
     /// when am_sender.
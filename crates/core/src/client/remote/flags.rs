@@ -98,13 +98,14 @@ pub(crate) fn build_server_flag_string(config: &ClientConfig) -> String {
     // `flags.copy_dirlinks` directly in apply_common_server_flags so a push
     // generator still dereferences.
 
-    // upstream: options.c:2662-2666 - only send 'W' when explicitly set
-    // (whole_file > 0). The default for remote transfers is no-whole-file
-    // (delta mode); upstream never sends --no-whole-file because it is the
-    // default. Sending 'W' unconditionally when the tri-state defaults to
-    // true forces the remote generator to skip basis-file checksums, so the
-    // sender falls back to whole-file even when a basis exists.
-    if config.whole_file_raw() == Some(true) && !config.append() {
+    // upstream: options.c:2662-2666 - only send 'W' when whole_file > 0.
+    // `whole_file()` resolves the default as start_client() does: delta for
+    // any transfer with a remote operand, so only an explicit --whole-file
+    // sends 'W'; upstream never sends --no-whole-file because it is the
+    // default. Sending 'W' by default would make the remote generator skip
+    // basis-file checksums, so the sender falls back to whole-file even when
+    // a basis exists.
+    if config.whole_file() && !config.append() {
         flags.push('W');
     }
 
@@ -387,7 +388,8 @@ fn split_pattern_modifiers(raw: &str) -> (String, bool, bool) {
 ///
 /// Sets the fields that are shared across both SSH and daemon transfer paths
 /// for both receiver and generator roles: `trust_sender`, `qsort`, `inplace`,
-/// `min_file_size`, `max_file_size`, `do_stats`, `late_delete`, and `itemize`.
+/// `min_file_size`, `max_file_size`, `block_size`, `do_stats`, `late_delete`,
+/// and `itemize`.
 pub(crate) fn apply_common_server_flags(config: &ClientConfig, server_config: &mut ServerConfig) {
     // upstream: options.c:846 / compat.c:604-607 - `--protocol=N` lowers the
     // advertised `protocol_version`, capping the negotiated version. Carry the
@@ -429,6 +431,10 @@ pub(crate) fn apply_common_server_flags(config: &ClientConfig, server_config: &m
     server_config.partial_dir = config.partial_directory().map(std::path::Path::to_path_buf);
     server_config.file_selection.min_file_size = config.min_file_size();
     server_config.file_selection.max_file_size = config.max_file_size();
+    // upstream: generator.c:sum_sizes_sqroot() - on a pull the local client
+    // generates the basis signatures, so `--block-size` must reach its config;
+    // a push forwards it to the remote receiver as `--block-size=N` instead.
+    server_config.block_size = config.block_size_override();
     // upstream: generator.c:quick_check_ok() -> same_time() applies the
     // `--modify-window` tolerance on the receiver. For a remote-shell pull the
     // local client IS the receiver, so carry the window onto its config; the
@@ -438,6 +444,7 @@ pub(crate) fn apply_common_server_flags(config: &ClientConfig, server_config: &m
         ::metadata::ModifyWindow::ZERO,
         ::metadata::ModifyWindow::from_secs,
     );
+    server_config.file_selection.modify_window_set = config.modify_window().is_some();
     // upstream: options.c:2064-2066 - do_stats sets INFO_STATS to level 2+
     server_config.do_stats = config.stats();
     // upstream: generator.c:124 - EARLY_DELETE_DONE_MSG = !(delete_during==2 || delete_after)
@@ -461,6 +468,13 @@ pub(crate) fn apply_common_server_flags(config: &ClientConfig, server_config: &m
     // but a pull sender never deletes, so the cap must be carried onto this
     // local receiver config or it is silently ignored (unbounded deletion).
     server_config.deletion.max_delete = config.max_delete();
+    // upstream: generator.c:298-305 - delete_in_dir() skips deletion after an
+    // I/O error unless --ignore-errors; for a pull that check runs here, on
+    // the local receiver.
+    server_config.deletion.ignore_errors = config.ignore_errors();
+    // upstream: generator.c del_opts - `--force` lets the local pull receiver
+    // remove a non-empty directory blocking a non-directory.
+    server_config.deletion.force_delete = config.force_replacements();
     logging::debug_log!(
         Del,
         2,
@@ -499,6 +513,12 @@ pub(crate) fn apply_common_server_flags(config: &ClientConfig, server_config: &m
     server_config.flags.copy_devices = config.copy_devices();
     // upstream: syscall.c do_open / do_open_nofollow propagate O_NOATIME when set.
     server_config.write.open_noatime = config.open_noatime();
+    // upstream: patches/fileflags.diff - the local half must encode or decode the
+    // file-flags word exactly as the forwarded `--fileflags` tells the peer to.
+    server_config.flags.fileflags = config.preserve_fileflags();
+    // oc-rsync extension: --drop-cache acts on whichever half runs locally -
+    // the sender evicts what it read, the receiver what it committed.
+    server_config.write.drop_cache = config.drop_cache();
     // upstream: options.c:2768-2780 - itemize_changes is forwarded to the remote
     // as --log-format=%i, but the local ServerConfig also needs the flag set so
     // the generator's maybe_emit_itemize() produces client-side output via callback.
@@ -787,6 +807,17 @@ mod tests {
         assert!(!server_config.fake_super);
     }
 
+    // upstream: generator.c:sum_sizes_sqroot() - on a pull the local client
+    // is the generator, so the fixed block length must reach its config.
+    #[test]
+    fn apply_common_server_flags_carries_block_size() {
+        let size = std::num::NonZeroU32::new(4096);
+        let config = ClientConfig::builder().block_size_override(size).build();
+        let mut server_config = ServerConfig::default();
+        apply_common_server_flags(&config, &mut server_config);
+        assert_eq!(server_config.block_size, size);
+    }
+
     #[test]
     fn server_flag_string_omits_itemize_compact_flag() {
         // upstream: options.c:2768-2780 - itemize is sent via --log-format=%i,
@@ -840,6 +871,25 @@ mod tests {
         assert!(!server_config.file_selection.delete_missing_args);
     }
 
+    #[test]
+    fn apply_common_server_flags_propagates_ignore_errors() {
+        let mut server_config = ServerConfig::default();
+        apply_common_server_flags(&ClientConfig::default(), &mut server_config);
+        assert!(!server_config.deletion.ignore_errors);
+
+        let config = ClientConfig::builder().ignore_errors(true).build();
+        apply_common_server_flags(&config, &mut server_config);
+        assert!(server_config.deletion.ignore_errors);
+    }
+
+    #[test]
+    fn apply_common_server_flags_propagates_force() {
+        let config = ClientConfig::builder().force_replacements(true).build();
+        let mut server_config = ServerConfig::default();
+        apply_common_server_flags(&config, &mut server_config);
+        assert!(server_config.deletion.force_delete);
+    }
+
     #[test]
     fn apply_common_server_flags_propagates_compression_threads() {
         let threads = std::num::NonZeroU8::new(4).unwrap();
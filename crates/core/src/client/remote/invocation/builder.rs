@@ -320,11 +320,80 @@ impl<'a> RemoteInvocationBuilder<'a> {
         if self.config.fsync() && self.role == RemoteRole::Sender {
             args.push(OsString::from("--fsync"));
         }
+        // oc-rsync extension: directory fsync follows the same receiver-only
+        // rule as --fsync.
+        if self.config.fsync_dir() && self.role == RemoteRole::Sender {
+            args.push(OsString::from("--fsync-dir"));
+        }
 
         if let Some(depth) = self.config.io_uring_depth() {
             args.push(OsString::from(format!("--io-uring-depth={depth}")));
         }
 
+        // oc-rsync extension: only a remote receiver commits files, so the
+        // worker count rides the wire on a push alone.
+        if self.config.parallel_commit() > 1 && self.role == RemoteRole::Sender {
+            args.push(OsString::from(format!(
+                "--parallel-commit={}",
+                self.config.parallel_commit()
+            )));
+        }
+        // oc-rsync extension: O_DIRECT only changes how the receiver lands
+        // data, so it is forwarded on a push alone as well.
+        if self.config.direct_write() && self.role == RemoteRole::Sender {
+            args.push(OsString::from("--direct-write"));
+        }
+        // oc-rsync extension: page-cache eviction applies to both halves, so
+        // the remote sender (pull) and remote receiver (push) both get it.
+        if self.config.drop_cache() {
+            args.push(OsString::from("--drop-cache"));
+        }
+
+        // oc-rsync extension: the scan cache belongs to the receiver's
+        // destination, so it is forwarded on a push alone.
+        if let Some(dir) = self.config.cache_dir()
+            && self.role == RemoteRole::Sender
+        {
+            let mut arg = OsString::from("--cache-dir=");
+            arg.push(dir);
+            args.push(arg);
+        }
+        // oc-rsync extension: case folding is checked by the receiver against
+        // its own destination, so the policy is forwarded on a push alone.
+        if self.config.case_collision().is_enabled() && self.role == RemoteRole::Sender {
+            let mut arg = OsString::from("--case-collision=");
+            arg.push(self.config.case_collision().as_str());
+            args.push(arg);
+        }
+        // oc-rsync extension: names are checked by the receiver, so
+        // `--windows-names` likewise rides a push alone.
+        if self.config.windows_names().is_enabled() && self.role == RemoteRole::Sender {
+            let mut arg = OsString::from("--windows-names=");
+            arg.push(self.config.windows_names().as_str());
+            args.push(arg);
+        }
+        // oc-rsync extension: the receiver decides the order files are
+        // requested in, so `--order` only matters to a remote receiver.
+        if self.config.transfer_order().reorders() && self.role == RemoteRole::Sender {
+            let mut arg = OsString::from("--order=");
+            arg.push(self.config.transfer_order().as_str());
+            args.push(arg);
+        }
+        // oc-rsync extension: the checkpoint records the receiver's commits,
+        // so both files name paths on the remote host and ride a push alone.
+        if self.role == RemoteRole::Sender {
+            for (option, file) in [
+                ("--checkpoint=", self.config.checkpoint()),
+                ("--resume=", self.config.resume()),
+            ] {
+                if let Some(file) = file {
+                    let mut arg = OsString::from(option);
+                    arg.push(file);
+                    args.push(arg);
+                }
+            }
+        }
+
         // upstream: options.c:2747-2748 - `if (list_only > 1) "--list-only"`.
         // Only the EXPLICIT `--list-only` (list_only == 2) is forwarded; the
         // implicit single-source listing (list_only == 1) is not. The compact
@@ -795,6 +864,13 @@ impl<'a> RemoteInvocationBuilder<'a> {
             args.push(OsString::from("--preallocate"));
         }
 
+        // upstream: patches/fileflags.diff - `if (preserve_fileflags)
+        // args[ac++] = "--fileflags"`. Sent in both directions because the flag
+        // changes the file-list wire format on both sides.
+        if self.config.preserve_fileflags() {
+            args.push(OsString::from("--fileflags"));
+        }
+
         // upstream: options.c:2768-2780 - `if (stdout_format && am_sender)` the
         // server is told a little about the client's out-format via a
         // `--log-format` arg, in a first-match-wins chain. The `%i` branches key
@@ -1005,10 +1081,11 @@ impl<'a> RemoteInvocationBuilder<'a> {
                 flags.push('k');
             }
         }
-        // upstream: options.c:2662-2663 - only send 'W' when explicitly set
-        // (whole_file > 0). The default for remote transfers is no-whole-file;
-        // upstream never sends --no-whole-file because it's the default.
-        if self.config.whole_file_raw() == Some(true) {
+        // upstream: options.c:2662-2663 - only send 'W' when whole_file > 0.
+        // `whole_file()` defaults to delta for remote transfers, so only an
+        // explicit --whole-file sends it; upstream never sends --no-whole-file
+        // because it's the default.
+        if self.config.whole_file() {
             flags.push('W');
         }
         // upstream: options.c:2668-2672 - preserve_hard_links.
@@ -1234,7 +1311,7 @@ pub(super) fn compression_level_to_numeric(level: compress::zlib::CompressionLev
 ///
 /// Calendar conversion uses Howard Hinnant's `civil_from_days` algorithm for
 /// correct Gregorian date computation without external crate dependencies.
-pub(super) fn format_system_time_for_stop_at(time: SystemTime) -> Option<String> {
+pub(crate) fn format_system_time_for_stop_at(time: SystemTime) -> Option<String> {
     let secs = time.duration_since(SystemTime::UNIX_EPOCH).ok()?.as_secs();
     let (year, month, day, hour, minute) = unix_secs_to_utc_components(secs);
     Some(format!(
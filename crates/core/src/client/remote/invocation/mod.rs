@@ -22,6 +22,7 @@ mod transfer_role;
 use std::ffi::OsString;
 
 pub use builder::RemoteInvocationBuilder;
+pub(crate) use builder::format_system_time_for_stop_at;
 pub use transfer_role::{determine_transfer_role, operand_is_remote};
 
 /// Role of the local rsync process in an SSH transfer.
@@ -497,6 +497,23 @@ fn omits_fsync_flag_when_not_set() {
     );
 }
 
+#[test]
+fn fsync_dir_forwarded_on_push_only() {
+    let config = ClientConfig::builder().fsync_dir(true).build();
+
+    let push = RemoteInvocationBuilder::new(&config, RemoteRole::Sender).build("/path");
+    assert!(
+        push.iter().any(|a| a == "--fsync-dir"),
+        "push must forward --fsync-dir: {push:?}"
+    );
+
+    let pull = RemoteInvocationBuilder::new(&config, RemoteRole::Receiver).build("/path");
+    assert!(
+        !pull.iter().any(|a| a == "--fsync-dir"),
+        "pull must not forward --fsync-dir to the remote sender: {pull:?}"
+    );
+}
+
 /// Allowlist of long-form argument prefixes that upstream rsync 3.x recognises
 /// in `--server` mode.  Any long flag emitted by `RemoteInvocationBuilder`
 /// whose prefix is NOT on this list would break interop with stock rsync.
@@ -1530,7 +1547,7 @@ fn receiver_flag_string(config: &ClientConfig) -> String {
 #[test]
 fn default_config_produces_expected_flags() {
     // ClientConfig::builder() sets recursive=true by default, and
-    // whole_file() returns true when the raw option is None (auto-detect).
+    // whole_file() resolves to delta when no operand is local.
     let config = ClientConfig::builder().build();
     let flags = sender_flag_string(&config);
     assert!(
@@ -2586,6 +2603,63 @@ fn includes_preallocate_long_arg() {
     );
 }
 
+#[test]
+fn includes_fileflags_long_arg_in_both_directions() {
+    let config = ClientConfig::builder().fileflags(true).build();
+    for args in [build_sender_args(&config), build_receiver_args(&config)] {
+        assert!(
+            args.iter().any(|a| a == "--fileflags"),
+            "expected --fileflags in args: {args:?}"
+        );
+    }
+}
+
+#[test]
+fn case_collision_forwarded_on_push_only() {
+    let config = ClientConfig::builder()
+        .case_collision(engine::CaseCollisionPolicy::Rename)
+        .build();
+    let push = build_sender_args(&config);
+    assert!(
+        push.iter().any(|a| a == "--case-collision=rename"),
+        "expected --case-collision=rename in args: {push:?}"
+    );
+    let pull = build_receiver_args(&config);
+    assert!(!pull.iter().any(|a| a.starts_with("--case-collision")));
+    let off = build_sender_args(&ClientConfig::builder().build());
+    assert!(!off.iter().any(|a| a.starts_with("--case-collision")));
+}
+
+#[test]
+fn windows_names_forwarded_on_push_only() {
+    let config = ClientConfig::builder()
+        .windows_names(engine::WindowsNamesPolicy::Fail)
+        .build();
+    let push = build_sender_args(&config);
+    assert!(
+        push.iter().any(|a| a == "--windows-names=fail"),
+        "expected --windows-names=fail in args: {push:?}"
+    );
+    let pull = build_receiver_args(&config);
+    assert!(!pull.iter().any(|a| a.starts_with("--windows-names")));
+}
+
+#[test]
+fn transfer_order_forwarded_on_push_only() {
+    let config = ClientConfig::builder()
+        .transfer_order(engine::TransferOrder::Size)
+        .build();
+    let push = build_sender_args(&config);
+    assert!(
+        push.iter().any(|a| a == "--order=size"),
+        "expected --order=size in args: {push:?}"
+    );
+    let pull = build_receiver_args(&config);
+    assert!(!pull.iter().any(|a| a.starts_with("--order")));
+    let name = build_sender_args(&ClientConfig::builder().build());
+    assert!(!name.iter().any(|a| a.starts_with("--order")));
+}
+
 #[test]
 fn custom_rsync_path_used_as_program_name() {
     let config = ClientConfig::builder()
@@ -0,0 +1,228 @@
+//! Local transfers split into an in-process client and server.
+//!
+//! Upstream never copies locally in a single process: `main.c:do_cmd()`
+//! forks a `local_child()` receiver connected to the client's sender over a
+//! socketpair, so file-list generation, delta computation, and disk writes
+//! overlap across the two processes. This module reproduces that model with
+//! two threads joined by [`crate::server::run_loopback_with_hooks`]: the
+//! calling thread runs the sender exactly as an SSH push does, and a worker
+//! thread runs the receiver exactly as a remote `rsync --server` would.
+//!
+//! The split is used for `--no-whole-file` local copies, where the delta
+//! algorithm runs and benefits from the overlap. Whole-file copies stay on
+//! the [`engine::local_copy::LocalCopyPlan`] path, which can hand them to
+//! the platform's zero-copy primitives instead.
+//!
+//! # Upstream Reference
+//!
+//! - `main.c:do_cmd()` - `local_server` forks `local_child()`
+//! - `pipe.c:local_child()` - socketpair between client and server halves
+
+use std::time::Instant;
+
+use transfer::setup::build_capability_string_suffix;
+
+use super::super::config::ClientConfig;
+use super::super::error::{ClientError, invalid_argument_error, invalid_argument_error_typed};
+use super::super::progress::ClientProgressObserver;
+use super::super::summary::ClientSummary;
+use super::flags;
+use super::ssh_transfer::{
+    ItemizeEventSink, ServerProgressAdapter, build_server_config_for_generator,
+    build_server_config_for_receiver, convert_server_stats_to_summary,
+};
+use crate::exit_code::ExitCode;
+use crate::server::{ServerTransferHooks, TransferProgressCallback, run_loopback_with_hooks};
+
+/// Reports whether a local copy should run through the client/server split.
+///
+/// Only an explicit `--no-whole-file` qualifies. Batch recording, `--atomic`,
+/// and `--list-only` keep the single-process path, whose support for them
+/// has no wire equivalent here. Operands must be valid UTF-8 because the
+/// server configuration carries them as strings.
+pub(crate) fn applies(config: &ClientConfig) -> bool {
+    config.whole_file_raw() == Some(false)
+        && config.batch_config().is_none()
+        && !config.atomic()
+        && !config.list_only()
+        && config.transfer_args().len() >= 2
+        && config
+            .transfer_args()
+            .iter()
+            .all(|arg| arg.to_str().is_some())
+}
+
+/// Runs a local copy as a sender client and a receiver server in-process.
+///
+/// The client half is configured as an SSH push would configure its local
+/// sender, and the server half as the remote receiver of that push, so every
+/// option reaches the side that applies it the same way it does over a
+/// remote shell.
+///
+/// # Errors
+///
+/// Returns an error when either configuration cannot be built or when the
+/// transfer fails on either half; the exit code follows the failing error.
+pub(crate) fn run_local_split_transfer(
+    config: &ClientConfig,
+    observer: Option<&mut dyn ClientProgressObserver>,
+) -> Result<ClientSummary, ClientError> {
+    let operands: Vec<String> = config
+        .transfer_args()
+        .iter()
+        .map(|arg| arg.to_string_lossy().into_owned())
+        .collect();
+    let (sources, destination) = operands.split_at(operands.len() - 1);
+
+    let mut client_config = build_server_config_for_generator(config, sources)?;
+    client_config.connection.filter_rules =
+        flags::build_wire_format_rules(config.filter_rules(), config.delete_excluded()).map_err(
+            |e| invalid_argument_error(&format!("failed to build filter rules: {e}"), 12),
+        )?;
+    client_config.stop_at = config.stop_at();
+    // upstream: options.c:3021-3068 maybe_add_e_option() - the server half
+    // reads the client's capabilities from the `e.xxx` suffix of its compact
+    // flag string, exactly as a remote `--server` receiver would.
+    let mut server_config = build_server_config_for_receiver(config, destination)?;
+    server_config
+        .flag_string
+        .push_str(&build_capability_string_suffix(config.inc_recursive_send()));
+
+    // upstream: sender.c:449-461 log_item(FCLIENT) - as on an SSH push, the
+    // client sender produces each file's client-visible line; the receiver
+    // server never forwards it (log.c:822 gates FCLIENT on `!am_server`).
+    // When the caller collects events (`-v`, `--log-file`, `--json`, ...) the
+    // sender emits a row per logged entry and the summary carries them, as a
+    // single-process local copy's does, so nothing is printed here.
+    let collect_events = config.collect_events() || config.render_out_format_locally();
+    if collect_events {
+        client_config.flags.info_flags.out_format_active = true;
+    }
+    let wants_client_output =
+        client_config.flags.info_flags.itemize || client_config.flags.verbose || collect_events;
+    let mut itemize_sink = ItemizeEventSink::new(collect_events);
+
+    let start = Instant::now();
+    let mut adapter = observer.map(|obs| ServerProgressAdapter::new(obs, start));
+    let hooks = ServerTransferHooks {
+        progress: adapter
+            .as_mut()
+            .map(|a| a as &mut dyn TransferProgressCallback),
+        itemize: if wants_client_output {
+            Some(&mut itemize_sink as &mut dyn crate::server::ItemizeCallback)
+        } else {
+            None
+        },
+        ..ServerTransferHooks::default()
+    };
+
+    let stats = run_loopback_with_hooks(client_config, server_config, hooks).map_err(|e| {
+        invalid_argument_error_typed(
+            &format!("transfer failed: {e}"),
+            ExitCode::from_transfer_error(&e),
+        )
+    })?;
+    let elapsed = start.elapsed();
+
+    let mut summary = convert_server_stats_to_summary(stats.client, elapsed);
+    // upstream: main.c:client_run() - wait_process_with_flush() takes the
+    // receiver child's exit status, so errors only the server half saw (its
+    // `io_error` and `MSG_ERROR_XFER` count) still end the run with 23.
+    if summary.io_error_exit_code().is_none()
+        && let Some(code) =
+            convert_server_stats_to_summary(stats.server, elapsed).io_error_exit_code()
+    {
+        summary.set_io_error_exit_code(code);
+    }
+    let events = itemize_sink.take_events();
+    if !events.is_empty() {
+        summary = summary.with_events(events);
+    }
+    summary.set_protocol_version(stats.protocol.as_u8());
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ffi::OsString;
+    use std::fs;
+
+    fn config_for(args: Vec<OsString>, whole_file: Option<bool>) -> ClientConfig {
+        ClientConfig::builder()
+            .transfer_args(args)
+            .recursive(true)
+            .times(true)
+            .whole_file_option(whole_file)
+            .build()
+    }
+
+    #[test]
+    fn applies_only_to_explicit_no_whole_file() {
+        let args = vec![OsString::from("src/"), OsString::from("dst")];
+        assert!(applies(&config_for(args.clone(), Some(false))));
+        assert!(!applies(&config_for(args.clone(), Some(true))));
+        assert!(!applies(&config_for(args, None)));
+        assert!(!applies(&config_for(
+            vec![OsString::from("src/")],
+            Some(false)
+        )));
+    }
+
+    #[test]
+    fn split_transfer_copies_and_updates_tree() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let src = temp.path().join("src");
+        let dst = temp.path().join("dst");
+        fs::create_dir_all(src.join("nested")).expect("mkdir");
+        let mut large: Vec<u8> = (0..=250u8).cycle().take(256 * 1024).collect();
+        fs::write(src.join("nested/large.bin"), &large).expect("write");
+        fs::write(src.join("small.txt"), b"small").expect("write");
+
+        let mut src_arg = src.clone().into_os_string();
+        src_arg.push("/");
+        let args = vec![src_arg, dst.clone().into_os_string()];
+
+        run_local_split_transfer(&config_for(args.clone(), Some(false)), None)
+            .expect("initial copy");
+        assert_eq!(fs::read(dst.join("nested/large.bin")).unwrap(), large);
+        assert_eq!(fs::read(dst.join("small.txt")).unwrap(), b"small");
+
+        // A small edit that also changes the size, so the quick check cannot
+        // skip it, exercises the delta path against the copied basis.
+        large[100_000] ^= 0xFF;
+        large.push(0x42);
+        fs::write(src.join("nested/large.bin"), &large).expect("rewrite");
+        run_local_split_transfer(&config_for(args, Some(false)), None).expect("update");
+        assert_eq!(fs::read(dst.join("nested/large.bin")).unwrap(), large);
+    }
+
+    #[test]
+    fn split_transfer_collects_events_for_the_log_file() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let src = temp.path().join("src");
+        fs::create_dir_all(&src).expect("mkdir");
+        fs::write(src.join("logged.txt"), b"logged").expect("write");
+
+        let mut src_arg = src.into_os_string();
+        src_arg.push("/");
+        // `--log-file` and `--json` force event collection without `-v`.
+        let config = ClientConfig::builder()
+            .transfer_args(vec![src_arg, temp.path().join("dst").into_os_string()])
+            .recursive(true)
+            .whole_file_option(Some(false))
+            .force_event_collection(true)
+            .build();
+
+        let summary = run_local_split_transfer(&config, None).expect("copy");
+        assert!(
+            summary
+                .events()
+                .iter()
+                .any(|event| event.relative_path() == std::path::Path::new("logged.txt")),
+            "missing file event: {:?}",
+            summary.events()
+        );
+    }
+}
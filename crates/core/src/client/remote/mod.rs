@@ -11,6 +11,7 @@
 //! - `ssh_transfer` - SSH-based remote transfers via `--rsh`/`-e`
 //! - `invocation` - Remote rsync `--server` argument construction and role detection
 //! - `flags` - Shared flag builder functions for compact server option strings
+//! - `local_split` - In-process client/server split for delta-mode local copies
 //! - `remote_to_remote` - Two-host proxy relay via local machine
 //!
 //! # Upstream Reference
@@ -34,6 +35,8 @@ pub(crate) mod flags;
 pub(crate) mod implied_source;
 /// Remote rsync `--server` invocation argument builder.
 pub mod invocation;
+/// In-process sender/receiver split for `--no-whole-file` local copies.
+pub(crate) mod local_split;
 /// `--info=`/`--debug=` server-argument construction (make_output_option).
 pub(crate) mod output_option;
 /// Remote-to-remote transfer via local proxy relay.
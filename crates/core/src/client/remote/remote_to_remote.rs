@@ -205,6 +205,10 @@ fn spawn_ssh_connection(
     // child as -4/-6 (only honoured when the remote shell is `ssh`).
     ssh.set_address_family(super::ssh_address_family(config.address_mode()));
 
+    // upstream: main.c:600-601 do_cmd() - both remote-shell children honour
+    // --blocking-io/--no-blocking-io with the rsh/remsh auto-enable.
+    ssh.set_blocking_io(config.blocking_io());
+
     ssh.set_prefer_aes_gcm(config.prefer_aes_gcm());
 
     let connect_timeout = config
@@ -296,19 +296,24 @@ fn run_proxy_transfer(
 /// out-format path as a local transfer. Otherwise the server's pre-formatted
 /// line is written straight to stdout, preserving the default `-v`/`-i` output
 /// byte-for-byte (upstream `log_item(FCLIENT, ...)`).
-struct ItemizeEventSink {
+pub(in crate::client::remote) struct ItemizeEventSink {
     collect: bool,
     events: Vec<ClientEvent>,
 }
 
 impl ItemizeEventSink {
-    const fn new(collect: bool) -> Self {
+    pub(in crate::client::remote) const fn new(collect: bool) -> Self {
         Self {
             collect,
             events: Vec::new(),
         }
     }
 
+    /// Drains the rows captured under a custom `--out-format`.
+    pub(in crate::client::remote) fn take_events(&mut self) -> Vec<ClientEvent> {
+        std::mem::take(&mut self.events)
+    }
+
     fn write_line(line: &str) {
         let mut out = std::io::stdout().lock();
         let _ = out.write_all(line.as_bytes());
@@ -493,7 +498,7 @@ fn run_server_over_ssh_connection(
     // Close the writer to signal EOF so the remote process can exit.
     drop(writer);
 
-    let collected_events = std::mem::take(&mut itemize_sink.events);
+    let collected_events = itemize_sink.take_events();
 
     // upstream: main.c wait_process_with_flush() - wait for child and map status.
     let (child_exit_code, stderr_text) = match child_handle.wait_with_stderr() {
@@ -516,7 +521,7 @@ fn run_server_over_ssh_connection(
             }
         }
         Err(transfer_error) => {
-            let transfer_exit = ExitCode::from_io_error(&transfer_error);
+            let transfer_exit = ExitCode::from_transfer_error(&transfer_error);
             if child_exit_code.as_i32() > transfer_exit.as_i32() {
                 Err(remote_exit_error(child_exit_code, local_role, &stderr_text))
             } else {
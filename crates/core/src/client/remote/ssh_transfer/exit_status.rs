@@ -26,8 +26,8 @@ pub(in crate::client::remote) fn convert_server_stats_to_summary(
     use engine::local_copy::LocalCopySummary;
     use transfer::io_error_flags;
 
-    let (local_summary, io_error, error_count) = match stats {
-        ServerStats::Receiver(ref transfer_stats) => {
+    let (local_summary, io_error, error_count, stop_limit_reached, remaining_files) = match stats {
+        ServerStats::Receiver(transfer_stats) => {
             // SSH-pull: local side ran the receiver and its `--delete` sweep.
             let s = LocalCopySummary::from_receiver_stats(
                 transfer_stats.files_listed,
@@ -48,9 +48,15 @@ pub(in crate::client::remote) fn convert_server_stats_to_summary(
                     specials: transfer_stats.num_specials,
                 },
             );
-            (s, transfer_stats.io_error, transfer_stats.error_count)
+            (
+                s,
+                transfer_stats.io_error,
+                transfer_stats.error_count,
+                transfer_stats.stop_limit_reached,
+                transfer_stats.remaining_files,
+            )
         }
-        ServerStats::Generator(ref generator_stats) => {
+        ServerStats::Generator(generator_stats) => {
             // SSH-push: local side ran the sender/generator; the remote
             // receiver reported its delete counters via `NDX_DEL_STATS`.
             let s = LocalCopySummary::from_generator_stats(
@@ -72,7 +78,13 @@ pub(in crate::client::remote) fn convert_server_stats_to_summary(
                     specials: generator_stats.num_specials,
                 },
             );
-            (s, generator_stats.io_error, 0u32)
+            (
+                s,
+                generator_stats.io_error,
+                0u32,
+                generator_stats.stop_limit_reached,
+                generator_stats.remaining_files,
+            )
         }
     };
 
@@ -83,9 +95,17 @@ pub(in crate::client::remote) fn convert_server_stats_to_summary(
     if exit_code != 0 {
         summary.set_io_error_exit_code(exit_code);
     } else if error_count > 0 {
-        // Remote sender reported errors via MSG_ERROR - treat as RERR_PARTIAL.
+        // upstream: log.c:311, main.c:1635 - the remote sender forwarded
+        // MSG_ERROR_XFER, so got_xfer_error yields RERR_PARTIAL (23).
         summary.set_io_error_exit_code(23);
     }
+    // upstream: io.c:check_timeout() - `--stop-at` / `--stop-after` end the
+    // run with RERR_TIMEOUT. Here the transfer wound down cleanly first, so
+    // only the exit code carries the early stop.
+    if stop_limit_reached {
+        summary.set_io_error_exit_code(ExitCode::Timeout.as_i32());
+        summary.set_remaining_files(remaining_files);
+    }
 
     summary
 }
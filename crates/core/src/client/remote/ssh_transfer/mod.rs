@@ -33,14 +33,15 @@ mod parse;
 mod progress;
 mod server_config;
 
+pub(super) use drive::ItemizeEventSink;
 pub use drive::run_ssh_transfer;
-pub(super) use exit_status::{format_stderr_context, map_child_exit_status};
+pub(super) use exit_status::{
+    convert_server_stats_to_summary, format_stderr_context, map_child_exit_status,
+};
+pub(super) use progress::ServerProgressAdapter;
 
-#[cfg(any(feature = "async-ssh", feature = "embedded-ssh"))]
-pub(super) use exit_status::convert_server_stats_to_summary;
 #[cfg(feature = "async-ssh")]
 pub(super) use parse::{parse_remote_operands, parse_single_remote, remote_operand_source_paths};
-#[cfg(any(test, feature = "async-ssh"))]
 pub(super) use server_config::{
     build_server_config_for_generator, build_server_config_for_receiver,
 };
@@ -91,6 +92,35 @@ mod tests {
         assert!(!server_config.flags.prune_empty_dirs);
     }
 
+    #[test]
+    fn stop_limit_maps_to_timeout_exit_code() {
+        use crate::server::{GeneratorStats, ServerStats, TransferStats};
+        use std::time::Duration;
+
+        let pull = ServerStats::Receiver(TransferStats {
+            stop_limit_reached: true,
+            ..Default::default()
+        });
+        let summary = convert_server_stats_to_summary(pull, Duration::ZERO);
+        assert_eq!(summary.io_error_exit_code(), Some(30));
+
+        let push = ServerStats::Generator(GeneratorStats {
+            stop_limit_reached: true,
+            remaining_files: vec!["dir/b.txt".into()],
+            ..Default::default()
+        });
+        let summary = convert_server_stats_to_summary(push, Duration::ZERO);
+        assert_eq!(summary.io_error_exit_code(), Some(30));
+        assert_eq!(
+            summary.remaining_files(),
+            [std::path::PathBuf::from("dir/b.txt")]
+        );
+
+        let complete = ServerStats::Receiver(TransferStats::default());
+        let summary = convert_server_stats_to_summary(complete, Duration::ZERO);
+        assert_eq!(summary.io_error_exit_code(), None);
+    }
+
     #[test]
     fn builds_generator_server_config() {
         let config = ClientConfig::builder().recursive(true).times(true).build();
@@ -12,14 +12,17 @@ use crate::server::{TransferProgressCallback, TransferProgressEvent};
 ///
 /// Converts server-side per-file progress events into client-side progress
 /// updates, enabling live progress display during SSH and daemon transfers.
-pub(super) struct ServerProgressAdapter<'a> {
+pub(in crate::client::remote) struct ServerProgressAdapter<'a> {
     observer: &'a mut dyn ClientProgressObserver,
     start: Instant,
     overall_transferred: u64,
 }
 
 impl<'a> ServerProgressAdapter<'a> {
-    pub(super) fn new(observer: &'a mut dyn ClientProgressObserver, start: Instant) -> Self {
+    pub(in crate::client::remote) fn new(
+        observer: &'a mut dyn ClientProgressObserver,
+        start: Instant,
+    ) -> Self {
         Self {
             observer,
             start,
@@ -140,6 +140,15 @@ pub(in crate::client::remote) fn build_server_config_for_receiver(
     // already sets this in apply_common_daemon_config; both ssh builders dropped
     // it, so the ssh pull never fsync'd its writes.
     server_config.write.fsync = config.fsync();
+    server_config.write.fsync_dir = config.fsync_dir();
+    server_config.write.parallel_commit = config.parallel_commit();
+    server_config.write.direct_write = config.direct_write();
+    server_config.file_selection.cache_dir = config.cache_dir().map(std::path::Path::to_path_buf);
+    server_config.file_selection.checkpoint = config.checkpoint().map(std::path::Path::to_path_buf);
+    server_config.file_selection.resume = config.resume().map(std::path::Path::to_path_buf);
+    server_config.file_selection.case_collision = config.case_collision();
+    server_config.file_selection.windows_names = config.windows_names();
+    server_config.file_selection.transfer_order = config.transfer_order();
     // upstream: options.c:2979-2980 - `if (write_devices && am_sender)
     // --write-devices`. --write-devices makes the receiver write file content
     // in-place into an existing device node (receiver.c: write_devices &&
@@ -232,6 +241,8 @@ pub(in crate::client::remote) fn build_server_config_for_generator(
     // config here; without this the ssh push left every file at its source mode
     // while local copies and pulls applied --chmod correctly.
     server_config.chmod = config.chmod().cloned();
+    // Local-only sender read mode; never emitted onto the wire.
+    server_config.flags.mmap_source = config.mmap_source();
 
     // A custom `--out-format` makes the sender emit an itemize row per logged
     // entry so the client can render the template (see InfoFlags::out_format_active).
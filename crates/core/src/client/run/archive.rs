@@ -0,0 +1,723 @@
+//! Archive destinations for `--dest-format=tar` and `--dest-format=tar.zst`.
+//!
+//! oc-rsync extension with no upstream equivalent. The destination operand
+//! names an archive file rather than a directory. The transfer - a local
+//! copy, a pull over a remote shell, or a pull from a daemon - is received
+//! by the ordinary receiver into a hidden staging directory beside the
+//! archive (`.<name>.~tar~`), so filters, `--checksum`, hard links and every
+//! other option behave exactly as they would for a directory destination.
+//! The staged tree is then walked and each entry streamed through
+//! [`engine::vfs::TarWriter`] into a pax-format tar, written to
+//! `.<name>.~tar-part~` and renamed over the destination, and the staging
+//! directory is removed. Entries keep their owner and group (ids and names),
+//! device numbers, hard links and, under `--xattrs`, extended attributes;
+//! sockets have no tar representation and are left out.
+//!
+//! Ownership, modes and device numbers come from the file list, not from
+//! the staged inodes. A receiver that is not root cannot apply them, so the
+//! staged transfer runs with `--fake-super`: the receiver records what the
+//! file list says in each entry's `user.rsync.%stat` attribute (devices and
+//! FIFOs become placeholder files) and the packer reads it back. As root the
+//! receiver applies them and the staged inodes are authoritative.
+//!
+//! A transfer that fails or finishes with I/O errors (exit codes 23-25), or
+//! an entry that cannot be packed, removes both siblings and leaves any
+//! previous archive untouched. Either sibling already existing - another run
+//! in progress, or one interrupted before it could clean up - is reported
+//! rather than removed. The staging directory needs room for the unpacked
+//! tree; the archive is always rebuilt whole, so there is no delta against a
+//! previous archive.
+
+use std::collections::HashMap;
+use std::ffi::{OsStr, OsString};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use ::metadata::FakeSuperStat;
+use engine::vfs::{TarEntry, TarEntryKind, TarWriter};
+use logging::debug_log;
+
+use crate::exit_code::ExitCode;
+use crate::message::Role;
+use crate::rsync_error;
+
+use super::super::config::{ClientConfig, DestinationFormat};
+use super::super::error::{ClientError, invalid_argument_error_typed, missing_operands_error};
+use super::super::progress::ClientProgressObserver;
+use super::super::remote;
+use super::super::summary::ClientSummary;
+use super::{is_daemon_operand, run_client_internal};
+
+/// Suffix of the hidden directory the transfer is received into.
+const STAGING_SUFFIX: &str = ".~tar~";
+
+/// Suffix of the hidden file the archive is written to before the rename.
+const PART_SUFFIX: &str = ".~tar-part~";
+
+/// Runs the transfer described by `config` into the archive its destination
+/// operand names.
+pub(super) fn run_into_archive(
+    config: ClientConfig,
+    observer: Option<&mut dyn ClientProgressObserver>,
+) -> Result<ClientSummary, ClientError> {
+    let operands = config.transfer_args();
+    let Some(destination) = operands.last().filter(|_| operands.len() >= 2) else {
+        return Err(missing_operands_error());
+    };
+    if remote::operand_is_remote(destination) || is_daemon_operand(destination) {
+        return Err(invalid_argument_error_typed(
+            "--dest-format archives must be written to a local path",
+            ExitCode::Syntax,
+        ));
+    }
+
+    let output = ArchiveOutput::prepare(Path::new(destination), config.dest_format())?;
+    let staged = config.redirected_to_directory(output.staging_operand(), !running_as_root());
+    let summary = match run_client_internal(staged, observer) {
+        Ok(summary) => summary,
+        Err(error) => {
+            output.abort();
+            return Err(error);
+        }
+    };
+
+    if config.dry_run() || config.list_only() {
+        output.abort();
+    } else if let Some(code) = summary.io_error_exit_code() {
+        // Some entries were not received; packing the staged tree would
+        // replace the previous archive with an incomplete one.
+        let target = output.target.clone();
+        output.abort();
+        return Err(incomplete_error(&target, code));
+    } else {
+        output.commit(config.preserve_xattrs())?;
+    }
+    Ok(summary)
+}
+
+/// Staging state for one archive destination.
+#[derive(Debug)]
+struct ArchiveOutput {
+    /// The archive file the caller asked for.
+    target: PathBuf,
+    /// Hidden sibling directory the transfer writes into.
+    staging: PathBuf,
+    /// Hidden sibling the archive is written to before the final rename.
+    part: PathBuf,
+    format: DestinationFormat,
+}
+
+impl ArchiveOutput {
+    /// Creates an empty staging directory beside `target`.
+    ///
+    /// An existing staging directory or part file is never removed: it may
+    /// belong to a run still in progress, so the caller is told to clear it.
+    fn prepare(target: &Path, format: DestinationFormat) -> Result<Self, ClientError> {
+        let name = match target.file_name() {
+            Some(name) if !has_trailing_separator(target.as_os_str()) => name,
+            _ => {
+                return Err(invalid_argument_error_typed(
+                    "--dest-format needs an archive file name as the destination",
+                    ExitCode::Syntax,
+                ));
+            }
+        };
+        if target.is_dir() {
+            return Err(archive_error(
+                "write",
+                target,
+                io::Error::new(io::ErrorKind::IsADirectory, "destination is a directory"),
+            ));
+        }
+        if format == DestinationFormat::TarZstd && !cfg!(feature = "zstd") {
+            return Err(invalid_argument_error_typed(
+                "--dest-format=tar.zst requires zstd support, which this build lacks",
+                ExitCode::Unsupported,
+            ));
+        }
+
+        let staging = sibling(target, name, STAGING_SUFFIX);
+        let part = sibling(target, name, PART_SUFFIX);
+        for sibling in [&staging, &part] {
+            if fs::symlink_metadata(sibling).is_ok() {
+                return Err(leftover_error(sibling));
+            }
+        }
+        fs::create_dir(&staging).map_err(|error| match error.kind() {
+            io::ErrorKind::AlreadyExists => leftover_error(&staging),
+            _ => archive_error("create", &staging, error),
+        })?;
+
+        debug_log!(
+            Recv,
+            1,
+            "archive: staging {} in {}",
+            target.display(),
+            staging.display()
+        );
+
+        Ok(Self {
+            target: target.to_path_buf(),
+            staging,
+            part,
+            format,
+        })
+    }
+
+    /// The destination operand that lands the transfer in the staging
+    /// directory. The trailing separator makes a lone file source land
+    /// inside it rather than replace it.
+    fn staging_operand(&self) -> OsString {
+        let mut operand = self.staging.clone().into_os_string();
+        operand.push(std::path::MAIN_SEPARATOR_STR);
+        operand
+    }
+
+    /// Packs the staged tree into the archive and renames it into place.
+    ///
+    /// `xattrs` records each entry's extended attributes, which the staged
+    /// tree only carries under `--xattrs`.
+    fn commit(self, xattrs: bool) -> Result<(), ClientError> {
+        let result = self.write_archive(xattrs);
+        if result.is_err() {
+            let _ = remove_entry(&self.part);
+        }
+        let cleanup = remove_entry(&self.staging);
+        result?;
+        cleanup.map_err(|error| archive_error("remove", &self.staging, error))
+    }
+
+    /// Discards the staging directory after a failed or dry run.
+    fn abort(self) {
+        if let Err(error) = remove_entry(&self.staging) {
+            debug_log!(
+                Recv,
+                1,
+                "archive: failed to remove {}: {error}",
+                self.staging.display()
+            );
+        }
+    }
+
+    fn write_archive(&self, xattrs: bool) -> Result<(), ClientError> {
+        let file = File::options()
+            .write(true)
+            .create_new(true)
+            .open(&self.part)
+            .map_err(|error| archive_error("create", &self.part, error))?;
+        let sink = ArchiveSink::new(BufWriter::new(file), self.format)
+            .map_err(|error| archive_error("start", &self.part, error))?;
+
+        // Entries are recorded as the transfer left them in the staging tree.
+        let mut packer = Packer {
+            archive: TarWriter::new(sink),
+            staging: &self.staging,
+            xattrs,
+            links: HashMap::new(),
+        };
+        packer.pack_directory(Path::new(""))?;
+
+        let file = packer
+            .archive
+            .finish()
+            .and_then(ArchiveSink::finish)
+            .map_err(|error| archive_error("write", &self.part, error))?;
+        file.sync_all()
+            .map_err(|error| archive_error("sync", &self.part, error))?;
+        fs::rename(&self.part, &self.target)
+            .map_err(|error| archive_error("rename", &self.part, error))?;
+        debug_log!(Recv, 1, "archive: wrote {}", self.target.display());
+        Ok(())
+    }
+}
+
+/// Streams the staged tree into the archive, one entry at a time.
+struct Packer<'a> {
+    archive: TarWriter<ArchiveSink>,
+    staging: &'a Path,
+    xattrs: bool,
+    /// Archive path of the first entry seen for each multiply-linked inode.
+    links: HashMap<(u64, u64), PathBuf>,
+}
+
+impl Packer<'_> {
+    /// Appends the children of `relative`, sorted by name, each directory
+    /// ahead of its contents as `tar` itself writes them. Extractors defer a
+    /// directory's times until its contents are in place.
+    fn pack_directory(&mut self, relative: &Path) -> Result<(), ClientError> {
+        let directory = self.staging.join(relative);
+        let mut names = fs::read_dir(&directory)
+            .and_then(|entries| {
+                entries
+                    .map(|entry| entry.map(|entry| entry.file_name()))
+                    .collect::<io::Result<Vec<_>>>()
+            })
+            .map_err(|error| archive_error("read", &directory, error))?;
+        names.sort();
+
+        for name in names {
+            let relative = relative.join(name);
+            let path = self.staging.join(&relative);
+            let meta =
+                fs::symlink_metadata(&path).map_err(|error| archive_error("stat", &path, error))?;
+            let Some(entry) = self
+                .entry_for(&relative, &path, &meta)
+                .map_err(|error| archive_error("read", &path, error))?
+            else {
+                continue;
+            };
+            let result = if *entry.kind() == TarEntryKind::File {
+                File::open(&path).and_then(|mut file| self.archive.append(&entry, &mut file))
+            } else {
+                self.archive.append(&entry, &mut io::empty())
+            };
+            result.map_err(|error| archive_error("pack", &path, error))?;
+            if meta.is_dir() {
+                self.pack_directory(&relative)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Describes one staged entry, or `None` for a kind tar cannot hold.
+    fn entry_for(
+        &mut self,
+        relative: &Path,
+        path: &Path,
+        meta: &fs::Metadata,
+    ) -> io::Result<Option<TarEntry>> {
+        let file_type = meta.file_type();
+        let recorded = recorded_stat(path, meta);
+        let kind = if file_type.is_dir() {
+            TarEntryKind::Directory
+        } else if file_type.is_symlink() {
+            TarEntryKind::Symlink(fs::read_link(path)?)
+        } else {
+            match staged_kind(meta, recorded.as_ref()) {
+                StagedKind::File => match self.hard_link_target(relative, meta) {
+                    Some(target) => TarEntryKind::HardLink(target),
+                    None => TarEntryKind::File,
+                },
+                StagedKind::Special(kind) => kind,
+                StagedKind::Unsupported => {
+                    debug_log!(Recv, 1, "archive: socket {} ignored", path.display());
+                    return Ok(None);
+                }
+            }
+        };
+
+        let mut entry = TarEntry::new(relative, kind).with_len(meta.len());
+        if let Ok(modified) = meta.modified() {
+            entry = entry.with_modified(modified);
+        }
+        entry = with_ownership(entry, meta, recorded.as_ref());
+        if self.xattrs {
+            let list = ::metadata::read_xattrs_for_wire(path, false, false, 0)
+                .map_err(|error| io::Error::other(error.to_string()))?;
+            for xattr in list.iter() {
+                entry = entry.with_xattr(xattr.name(), xattr.datum());
+            }
+        }
+        Ok(Some(entry))
+    }
+
+    /// Returns the archive path an earlier link to the same inode was
+    /// written under, remembering this one when it is the first.
+    #[cfg(unix)]
+    fn hard_link_target(&mut self, relative: &Path, meta: &fs::Metadata) -> Option<PathBuf> {
+        use std::os::unix::fs::MetadataExt;
+
+        if meta.nlink() < 2 {
+            return None;
+        }
+        let key = (meta.dev(), meta.ino());
+        if let Some(first) = self.links.get(&key) {
+            return Some(first.clone());
+        }
+        self.links.insert(key, relative.to_path_buf());
+        None
+    }
+
+    #[cfg(not(unix))]
+    fn hard_link_target(&mut self, _relative: &Path, _meta: &fs::Metadata) -> Option<PathBuf> {
+        None
+    }
+}
+
+/// Returns the file-list stat a `--fake-super` receive recorded for a staged
+/// entry, if any. Symlinks carry none: reading would follow the link.
+fn recorded_stat(path: &Path, meta: &fs::Metadata) -> Option<FakeSuperStat> {
+    if meta.file_type().is_symlink() {
+        return None;
+    }
+    ::metadata::load_fake_super(path).ok().flatten()
+}
+
+#[cfg(unix)]
+fn running_as_root() -> bool {
+    rustix::process::geteuid().is_root()
+}
+
+#[cfg(not(unix))]
+fn running_as_root() -> bool {
+    false
+}
+
+/// Records the permission bits, owner and group, with their names, preferring
+/// the `recorded` file-list stat over the staged inode.
+#[cfg(unix)]
+fn with_ownership(
+    entry: TarEntry,
+    meta: &fs::Metadata,
+    recorded: Option<&FakeSuperStat>,
+) -> TarEntry {
+    use ::metadata::id_lookup::{lookup_group_name_cached, lookup_user_name_cached};
+    use std::os::unix::fs::MetadataExt;
+
+    let (mode, uid, gid) = recorded.map_or((meta.mode(), meta.uid(), meta.gid()), |stat| {
+        (stat.mode, stat.uid, stat.gid)
+    });
+    let user = lookup_user_name_cached(uid).ok().flatten();
+    let group = lookup_group_name_cached(gid).ok().flatten();
+    entry
+        .with_mode(mode & 0o7777)
+        .with_owner(u64::from(uid), user)
+        .with_group(u64::from(gid), group)
+}
+
+#[cfg(not(unix))]
+fn with_ownership(
+    entry: TarEntry,
+    _meta: &fs::Metadata,
+    _recorded: Option<&FakeSuperStat>,
+) -> TarEntry {
+    entry
+}
+
+/// What a staged entry other than a directory or symlink is packed as.
+enum StagedKind {
+    /// Regular file data.
+    File,
+    /// A device or FIFO, possibly a `--fake-super` placeholder file.
+    Special(TarEntryKind),
+    /// A socket, which tar cannot hold.
+    Unsupported,
+}
+
+/// Classifies a staged entry by its recorded file-list type, falling back
+/// to the staged inode's own type.
+#[cfg(unix)]
+fn staged_kind(meta: &fs::Metadata, recorded: Option<&FakeSuperStat>) -> StagedKind {
+    use std::os::unix::fs::MetadataExt;
+
+    let mode = recorded.map_or(meta.mode(), |stat| stat.mode);
+    let (major, minor) = recorded
+        .and_then(|stat| stat.rdev)
+        .unwrap_or_else(|| (rdev_major(meta.rdev()), rdev_minor(meta.rdev())));
+    match mode & 0o170000 {
+        0o020000 => StagedKind::Special(TarEntryKind::CharDevice { major, minor }),
+        0o060000 => StagedKind::Special(TarEntryKind::BlockDevice { major, minor }),
+        0o010000 => StagedKind::Special(TarEntryKind::Fifo),
+        0o100000 => StagedKind::File,
+        _ => StagedKind::Unsupported,
+    }
+}
+
+#[cfg(not(unix))]
+fn staged_kind(meta: &fs::Metadata, _recorded: Option<&FakeSuperStat>) -> StagedKind {
+    if meta.is_file() {
+        StagedKind::File
+    } else {
+        StagedKind::Unsupported
+    }
+}
+
+/// Extracts the major device number from a combined rdev value (Linux glibc encoding).
+#[cfg(all(unix, target_os = "linux"))]
+fn rdev_major(rdev: u64) -> u32 {
+    ((rdev >> 8) & 0xfff) as u32 | (((rdev >> 32) & !0xfff) as u32)
+}
+
+/// Extracts the major device number from a combined rdev value (BSD/macOS encoding).
+#[cfg(all(unix, not(target_os = "linux")))]
+fn rdev_major(rdev: u64) -> u32 {
+    ((rdev >> 24) & 0xff) as u32
+}
+
+/// Extracts the minor device number from a combined rdev value (Linux glibc encoding).
+#[cfg(all(unix, target_os = "linux"))]
+fn rdev_minor(rdev: u64) -> u32 {
+    (rdev & 0xff) as u32 | (((rdev >> 12) & !0xff) as u32)
+}
+
+/// Extracts the minor device number from a combined rdev value (BSD/macOS encoding).
+#[cfg(all(unix, not(target_os = "linux")))]
+fn rdev_minor(rdev: u64) -> u32 {
+    (rdev & 0xffffff) as u32
+}
+
+/// The byte sink under the tar stream: the file itself, or a zstd encoder
+/// in front of it.
+enum ArchiveSink {
+    Plain(BufWriter<File>),
+    #[cfg(feature = "zstd")]
+    Zstd(compress::zstd::CountingZstdEncoder<BufWriter<File>>),
+}
+
+impl ArchiveSink {
+    fn new(file: BufWriter<File>, format: DestinationFormat) -> io::Result<Self> {
+        match format {
+            #[cfg(feature = "zstd")]
+            DestinationFormat::TarZstd => {
+                let level = compress::zlib::CompressionLevel::Default;
+                compress::zstd::CountingZstdEncoder::with_sink(file, level).map(Self::Zstd)
+            }
+            _ => Ok(Self::Plain(file)),
+        }
+    }
+
+    /// Ends the compressed stream, if any, and returns the flushed file.
+    fn finish(self) -> io::Result<File> {
+        let writer = match self {
+            Self::Plain(writer) => writer,
+            #[cfg(feature = "zstd")]
+            Self::Zstd(encoder) => encoder.finish_into_inner()?.0,
+        };
+        writer.into_inner().map_err(io::IntoInnerError::into_error)
+    }
+}
+
+impl Write for ArchiveSink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Plain(writer) => writer.write(buf),
+            #[cfg(feature = "zstd")]
+            Self::Zstd(encoder) => encoder.write(buf).map(|()| buf.len()),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Plain(writer) => writer.flush(),
+            #[cfg(feature = "zstd")]
+            Self::Zstd(encoder) => encoder.flush(),
+        }
+    }
+}
+
+/// Returns `<parent>/.<name><suffix>` beside `target`.
+fn sibling(target: &Path, name: &OsStr, suffix: &str) -> PathBuf {
+    let mut hidden = OsString::from(".");
+    hidden.push(name);
+    hidden.push(suffix);
+    target.with_file_name(hidden)
+}
+
+/// Removes a file or directory tree, treating absence as success.
+fn remove_entry(path: &Path) -> io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(meta) if meta.is_dir() => fs::remove_dir_all(path),
+        Ok(_) => fs::remove_file(path),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(error) => Err(error),
+    }
+}
+
+fn has_trailing_separator(operand: &OsStr) -> bool {
+    operand
+        .to_string_lossy()
+        .chars()
+        .last()
+        .is_some_and(std::path::is_separator)
+}
+
+#[cold]
+fn leftover_error(path: &Path) -> ClientError {
+    let code = ExitCode::FileIo;
+    let message = rsync_error!(
+        code.as_i32(),
+        "archive: {} already exists; another transfer may be writing this archive (remove it if not)",
+        path.display()
+    )
+    .with_role(Role::Receiver);
+    ClientError::with_code(code, message)
+}
+
+#[cold]
+fn incomplete_error(target: &Path, code: i32) -> ClientError {
+    let exit = ExitCode::from_i32(code).unwrap_or(ExitCode::PartialTransfer);
+    let message = rsync_error!(
+        code,
+        "archive: {} left unchanged because the transfer finished with errors",
+        target.display()
+    )
+    .with_role(Role::Receiver);
+    ClientError::with_code(exit, message)
+}
+
+#[cold]
+fn archive_error(action: &str, path: &Path, error: io::Error) -> ClientError {
+    let code = ExitCode::FileIo;
+    let message = rsync_error!(
+        code.as_i32(),
+        "archive: failed to {action} {}: {error}",
+        path.display()
+    )
+    .with_role(Role::Receiver);
+    ClientError::with_code(code, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn staging_siblings_are_hidden() {
+        let target = Path::new("/backups/host.tar");
+        let staging = sibling(target, OsStr::new("host.tar"), STAGING_SUFFIX);
+        assert_eq!(staging, PathBuf::from("/backups/.host.tar.~tar~"));
+    }
+
+    #[test]
+    fn prepare_rejects_directory_destinations() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(ArchiveOutput::prepare(dir.path(), DestinationFormat::Tar).is_err());
+
+        let mut operand = dir.path().join("out").into_os_string();
+        operand.push("/");
+        assert!(ArchiveOutput::prepare(Path::new(&operand), DestinationFormat::Tar).is_err());
+    }
+
+    #[test]
+    fn commit_packs_the_staged_tree() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("out.tar");
+        fs::write(&target, b"previous").unwrap();
+
+        let output = ArchiveOutput::prepare(&target, DestinationFormat::Tar).unwrap();
+        fs::create_dir(output.staging.join("sub")).unwrap();
+        fs::write(output.staging.join("sub/file"), b"payload").unwrap();
+        let staging = output.staging.clone();
+        output.commit(false).unwrap();
+
+        let archive = fs::read(&target).unwrap();
+        assert_eq!(archive.len() % 512, 0);
+        assert!(archive.windows(7).any(|window| window == b"payload"));
+        assert!(!staging.exists());
+        assert!(!dir.path().join(".out.tar.~tar-part~").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn commit_records_owners_and_hard_links() {
+        use std::os::unix::fs::MetadataExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("out.tar");
+        let output = ArchiveOutput::prepare(&target, DestinationFormat::Tar).unwrap();
+        fs::write(output.staging.join("a"), b"payload").unwrap();
+        fs::hard_link(output.staging.join("a"), output.staging.join("b")).unwrap();
+        let uid = fs::metadata(output.staging.join("a")).unwrap().uid();
+        output.commit(false).unwrap();
+
+        let archive = fs::read(&target).unwrap();
+        // One copy of the data: the second name is a hard-link member.
+        let copies = archive.windows(7).filter(|w| *w == b"payload").count();
+        assert_eq!(copies, 1);
+        let link = archive
+            .chunks(512)
+            .find(|block| block[156] == b'1')
+            .expect("hard-link member");
+        assert!(link[157..].starts_with(b"a\0"));
+        let owner = std::str::from_utf8(&link[108..115]).unwrap();
+        assert_eq!(u32::from_str_radix(owner, 8).unwrap(), uid);
+    }
+
+    #[cfg(all(unix, feature = "xattr"))]
+    #[test]
+    fn commit_prefers_the_recorded_file_list_stat() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("out.tar");
+        let output = ArchiveOutput::prepare(&target, DestinationFormat::Tar).unwrap();
+        let file = output.staging.join("file");
+        let device = output.staging.join("null");
+        fs::write(&file, b"payload").unwrap();
+        fs::write(&device, b"").unwrap();
+        let file_stat = FakeSuperStat {
+            mode: 0o100640,
+            uid: 4242,
+            gid: 4343,
+            rdev: None,
+        };
+        let device_stat = FakeSuperStat {
+            mode: 0o020666,
+            uid: 0,
+            gid: 0,
+            rdev: Some((1, 3)),
+        };
+        if ::metadata::store_fake_super(&file, &file_stat).is_err()
+            || ::metadata::store_fake_super(&device, &device_stat).is_err()
+        {
+            // The filesystem holding the temp directory has no user xattrs.
+            output.abort();
+            return;
+        }
+        output.commit(false).unwrap();
+
+        let archive = fs::read(&target).unwrap();
+        let header = |name: &[u8]| {
+            archive
+                .chunks(512)
+                .find(|block| block.starts_with(name) && block[name.len()] == 0)
+                .expect("member header")
+                .to_vec()
+        };
+        let octal = |field: &[u8]| {
+            let text = std::str::from_utf8(field)
+                .unwrap()
+                .trim_end_matches(['\0', ' ']);
+            u32::from_str_radix(text, 8).unwrap()
+        };
+        let file_header = header(b"file");
+        assert_eq!(octal(&file_header[100..107]), 0o640);
+        assert_eq!(octal(&file_header[108..115]), 4242);
+        assert_eq!(octal(&file_header[116..123]), 4343);
+        let device_header = header(b"null");
+        assert_eq!(device_header[156], b'3', "character device member");
+        assert_eq!(octal(&device_header[329..336]), 1);
+        assert_eq!(octal(&device_header[337..344]), 3);
+    }
+
+    #[test]
+    fn prepare_refuses_leftover_siblings() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("out.tar");
+        let staging = sibling(&target, OsStr::new("out.tar"), STAGING_SUFFIX);
+        fs::create_dir(&staging).unwrap();
+        fs::write(staging.join("keep"), b"x").unwrap();
+
+        assert!(ArchiveOutput::prepare(&target, DestinationFormat::Tar).is_err());
+        assert_eq!(fs::read(staging.join("keep")).unwrap(), b"x");
+
+        fs::remove_dir_all(&staging).unwrap();
+        let part = sibling(&target, OsStr::new("out.tar"), PART_SUFFIX);
+        fs::write(&part, b"partial").unwrap();
+        assert!(ArchiveOutput::prepare(&target, DestinationFormat::Tar).is_err());
+        assert_eq!(fs::read(&part).unwrap(), b"partial");
+    }
+
+    #[test]
+    fn abort_keeps_the_previous_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("out.tar");
+        fs::write(&target, b"previous").unwrap();
+
+        let output = ArchiveOutput::prepare(&target, DestinationFormat::Tar).unwrap();
+        let staging = output.staging.clone();
+        output.abort();
+
+        assert!(!staging.exists());
+        assert_eq!(fs::read(&target).unwrap(), b"previous");
+    }
+}
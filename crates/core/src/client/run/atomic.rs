@@ -0,0 +1,467 @@
+//! All-or-nothing destination replacement for local copies (`--atomic`).
+//!
+//! oc-rsync extension with no upstream equivalent. The transfer is received
+//! into a hidden sibling of the destination (`.<name>.~atomic~`). Only once
+//! every file has been committed is the staging tree swapped into place, with
+//! `renameat2(RENAME_EXCHANGE)` where the platform supports it and a two-step
+//! rename otherwise. A failed transfer removes the staging tree and leaves the
+//! destination untouched.
+//!
+//! The staging tree is seeded with a mirror of the live destination before the
+//! transfer starts. Every entry gets its own inode: directories, symlinks and
+//! special files are recreated, and regular files are copied, as a reflink
+//! where the filesystem supports one so the seed costs no file data. The
+//! transfer then sees the destination exactly as it would without `--atomic`
+//! (unchanged files are skipped, excluded and extraneous entries survive
+//! unless `--delete` removes them), while nothing it does, data or attribute
+//! change, reaches the live tree before the swap.
+//!
+//! A staging sibling that already exists belongs to another `--atomic` run
+//! into the same destination, or to one that was interrupted; either way it is
+//! refused rather than removed, and the operator clears it.
+
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use fast_io::{DefaultPlatformCopy, PlatformCopy};
+use filetime::FileTime;
+use logging::debug_log;
+
+use crate::exit_code::ExitCode;
+use crate::message::Role;
+use crate::rsync_error;
+
+use super::super::error::ClientError;
+
+/// Suffix of the hidden staging sibling that receives the transfer.
+const STAGING_SUFFIX: &str = ".~atomic~";
+
+/// Suffix of the sibling the previous tree is parked under when the
+/// platform cannot exchange the two entries atomically.
+const RETIRED_SUFFIX: &str = ".~atomic-old~";
+
+/// Staging state for one `--atomic` local copy.
+#[derive(Debug)]
+pub(super) struct AtomicSwap {
+    /// The destination the caller asked for.
+    target: PathBuf,
+    /// Hidden sibling the transfer writes into.
+    staging: PathBuf,
+    /// Whether `target` already existed when staging began.
+    target_exists: bool,
+}
+
+impl AtomicSwap {
+    /// Prepares the staging sibling for `destination`.
+    ///
+    /// Fails when a staging or retired sibling already exists. When the
+    /// destination is an existing directory, the staging directory is seeded
+    /// with a mirror of it (see the module docs).
+    pub(super) fn prepare(destination: &Path) -> Result<Self, ClientError> {
+        let target = if destination.file_name().is_some() {
+            destination.to_path_buf()
+        } else {
+            fs::canonicalize(destination)
+                .map_err(|error| atomic_error("resolve", destination, error))?
+        };
+        let Some(name) = target.file_name() else {
+            return Err(atomic_error(
+                "stage",
+                &target,
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "destination has no final path component",
+                ),
+            ));
+        };
+
+        let staging = sibling(&target, name, STAGING_SUFFIX);
+        for sibling in [&staging, &sibling(&target, name, RETIRED_SUFFIX)] {
+            if fs::symlink_metadata(sibling).is_ok() {
+                return Err(leftover_error(sibling));
+            }
+        }
+
+        let target_meta = fs::symlink_metadata(&target).ok();
+        if let Some(meta) = target_meta.as_ref().filter(|meta| meta.is_dir()) {
+            fs::create_dir(&staging).map_err(|error| match error.kind() {
+                io::ErrorKind::AlreadyExists => leftover_error(&staging),
+                _ => atomic_error("create", &staging, error),
+            })?;
+            if let Err(error) =
+                seed_tree(&target, &staging).and_then(|()| copy_attributes(meta, &staging))
+            {
+                let _ = remove_entry(&staging);
+                return Err(atomic_error("seed", &staging, error));
+            }
+        }
+
+        debug_log!(
+            Recv,
+            1,
+            "atomic: staging {} in {}",
+            target.display(),
+            staging.display()
+        );
+
+        Ok(Self {
+            target,
+            staging,
+            target_exists: target_meta.is_some(),
+        })
+    }
+
+    /// Rewrites the copy operands so the transfer lands in the staging tree.
+    ///
+    /// The destination operand's trailing separator is preserved so the
+    /// copy plan resolves sources exactly as it would against the target.
+    pub(super) fn staged_operands(&self, operands: &[OsString]) -> Vec<OsString> {
+        let mut staged = operands.to_vec();
+        if let Some(last) = staged.last_mut() {
+            let mut destination = self.staging.clone().into_os_string();
+            if has_trailing_separator(last) {
+                destination.push(std::path::MAIN_SEPARATOR_STR);
+            }
+            *last = destination;
+        }
+        staged
+    }
+
+    /// Swaps the completed staging tree into place and drops the old tree.
+    pub(super) fn commit(self) -> Result<(), ClientError> {
+        if fs::symlink_metadata(&self.staging).is_err() {
+            // Nothing was transferred (for example, every source was
+            // filtered out): the destination stays as it was.
+            return Ok(());
+        }
+
+        if !self.target_exists {
+            return fs::rename(&self.staging, &self.target)
+                .map_err(|error| atomic_error("rename", &self.staging, error));
+        }
+
+        match fast_io::exchange_paths(&self.staging, &self.target) {
+            Ok(()) => {
+                debug_log!(Recv, 1, "atomic: exchanged {}", self.target.display());
+            }
+            Err(error) if error.kind() == io::ErrorKind::Unsupported => {
+                self.swap_by_rename()?;
+                return Ok(());
+            }
+            Err(error) => return Err(atomic_error("exchange", &self.target, error)),
+        }
+
+        // The staging path now holds the previous tree.
+        remove_entry(&self.staging).map_err(|error| atomic_error("remove", &self.staging, error))
+    }
+
+    /// Discards the staging tree after a failed transfer.
+    pub(super) fn abort(self) {
+        if let Err(error) = remove_entry(&self.staging) {
+            debug_log!(
+                Recv,
+                1,
+                "atomic: failed to remove {}: {error}",
+                self.staging.display()
+            );
+        }
+    }
+
+    /// Two-step fallback: park the old tree, then move the new one in. If
+    /// the second rename fails the old tree is restored.
+    fn swap_by_rename(&self) -> Result<(), ClientError> {
+        let name = self.target.file_name().unwrap_or(OsStr::new(""));
+        let retired = sibling(&self.target, name, RETIRED_SUFFIX);
+        if fs::symlink_metadata(&retired).is_ok() {
+            return Err(leftover_error(&retired));
+        }
+
+        fs::rename(&self.target, &retired)
+            .map_err(|error| atomic_error("rename", &self.target, error))?;
+        if let Err(error) = fs::rename(&self.staging, &self.target) {
+            let _ = fs::rename(&retired, &self.target);
+            return Err(atomic_error("rename", &self.staging, error));
+        }
+        debug_log!(Recv, 1, "atomic: replaced {}", self.target.display());
+
+        remove_entry(&retired).map_err(|error| atomic_error("remove", &retired, error))
+    }
+}
+
+/// Returns `<parent>/.<name><suffix>` beside `target`.
+fn sibling(target: &Path, name: &OsStr, suffix: &str) -> PathBuf {
+    let mut hidden = OsString::from(".");
+    hidden.push(name);
+    hidden.push(suffix);
+    target.with_file_name(hidden)
+}
+
+/// Mirrors the entries of `source` into the existing directory `staging`.
+///
+/// Directories and special files are recreated and regular files copied, all
+/// with their permissions, ownership (best effort) and timestamps; symlinks
+/// are recreated. No entry shares an inode with the live tree.
+fn seed_tree(source: &Path, staging: &Path) -> io::Result<()> {
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let from = entry.path();
+        let to = staging.join(entry.file_name());
+        let meta = fs::symlink_metadata(&from)?;
+        let file_type = meta.file_type();
+        if file_type.is_dir() {
+            fs::create_dir(&to)?;
+            seed_tree(&from, &to)?;
+            copy_attributes(&meta, &to)?;
+        } else if file_type.is_symlink() {
+            create_symlink(&from, &to)?;
+            let _ = filetime::set_symlink_file_times(
+                &to,
+                FileTime::from_last_access_time(&meta),
+                FileTime::from_last_modification_time(&meta),
+            );
+        } else if file_type.is_file() {
+            DefaultPlatformCopy::new().copy_file(&from, &to, meta.len())?;
+            copy_attributes(&meta, &to)?;
+        } else {
+            create_special(&to, &meta)?;
+            copy_attributes(&meta, &to)?;
+        }
+    }
+    Ok(())
+}
+
+/// Applies a seeded entry's permissions, ownership and timestamps. A
+/// directory gets them once its contents are in place, so a read-only
+/// directory can still be filled.
+fn copy_attributes(meta: &fs::Metadata, path: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        // Only root can give the directory away; anyone else keeps it.
+        let _ = std::os::unix::fs::chown(path, Some(meta.uid()), Some(meta.gid()));
+    }
+    fs::set_permissions(path, meta.permissions())?;
+    filetime::set_file_times(
+        path,
+        FileTime::from_last_access_time(meta),
+        FileTime::from_last_modification_time(meta),
+    )
+}
+
+/// Recreates a device node, FIFO or socket described by `meta` as `to`.
+fn create_special(to: &Path, meta: &fs::Metadata) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+        let file_type = meta.file_type();
+        let created = if file_type.is_block_device() || file_type.is_char_device() {
+            metadata::create_device_node(to, meta)
+        } else {
+            metadata::create_fifo(to, meta)
+        };
+        created.map_err(|error| error.into_parts().2)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = meta;
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("cannot recreate special file {}", to.display()),
+        ))
+    }
+}
+
+/// Recreates the symlink at `from` as `to`, pointing at the same target.
+fn create_symlink(from: &Path, to: &Path) -> io::Result<()> {
+    let target = fs::read_link(from)?;
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(target, to)
+    }
+    #[cfg(windows)]
+    {
+        if fs::metadata(from).is_ok_and(|meta| meta.is_dir()) {
+            std::os::windows::fs::symlink_dir(target, to)
+        } else {
+            std::os::windows::fs::symlink_file(target, to)
+        }
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = (target, to);
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "symlinks are not supported on this platform",
+        ))
+    }
+}
+
+/// Removes a file, symlink or directory tree, treating absence as success.
+fn remove_entry(path: &Path) -> io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(meta) if meta.is_dir() => fs::remove_dir_all(path),
+        Ok(_) => fs::remove_file(path),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(error) => Err(error),
+    }
+}
+
+fn has_trailing_separator(operand: &OsStr) -> bool {
+    operand
+        .to_string_lossy()
+        .chars()
+        .last()
+        .is_some_and(std::path::is_separator)
+}
+
+#[cold]
+fn leftover_error(path: &Path) -> ClientError {
+    let code = ExitCode::FileIo;
+    let message = rsync_error!(
+        code.as_i32(),
+        "atomic: {} already exists; another transfer may be writing this destination (remove it if not)",
+        path.display()
+    )
+    .with_role(Role::Receiver);
+    ClientError::with_code(code, message)
+}
+
+#[cold]
+fn atomic_error(action: &str, path: &Path, error: io::Error) -> ClientError {
+    let code = ExitCode::PartialTransfer;
+    let message = rsync_error!(
+        code.as_i32(),
+        "atomic: failed to {action} {}: {error}",
+        path.display()
+    )
+    .with_role(Role::Receiver);
+    ClientError::with_code(code, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn staging_is_hidden_sibling() {
+        let swap = AtomicSwap {
+            target: PathBuf::from("/srv/site"),
+            staging: sibling(Path::new("/srv/site"), OsStr::new("site"), STAGING_SUFFIX),
+            target_exists: true,
+        };
+        assert_eq!(swap.staging, PathBuf::from("/srv/.site.~atomic~"));
+
+        let operands = [OsString::from("src/"), OsString::from("/srv/site/")];
+        let staged = swap.staged_operands(&operands);
+        assert_eq!(staged[0], OsString::from("src/"));
+        assert!(has_trailing_separator(&staged[1]));
+        assert!(Path::new(&staged[1]).starts_with("/srv/.site.~atomic~"));
+    }
+
+    #[test]
+    fn prepare_refuses_existing_staging() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("site");
+        fs::create_dir(&target).unwrap();
+        let busy = dir.path().join(".site.~atomic~");
+        fs::create_dir(&busy).unwrap();
+        fs::write(busy.join("in-flight"), b"other run").unwrap();
+
+        let error = AtomicSwap::prepare(&target).expect_err("staging is in use");
+        assert_eq!(error.code(), ExitCode::FileIo);
+        assert_eq!(fs::read(busy.join("in-flight")).unwrap(), b"other run");
+        assert!(target.is_dir());
+    }
+
+    #[test]
+    fn prepare_seeds_staging_from_destination() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("site");
+        fs::create_dir_all(target.join("nested")).unwrap();
+        fs::write(target.join("index.html"), b"index").unwrap();
+        fs::write(target.join("nested").join("page.html"), b"page").unwrap();
+
+        let swap = AtomicSwap::prepare(&target).unwrap();
+        assert_eq!(fs::read(swap.staging.join("index.html")).unwrap(), b"index");
+        assert_eq!(
+            fs::read(swap.staging.join("nested").join("page.html")).unwrap(),
+            b"page"
+        );
+        swap.abort();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn seeded_entries_do_not_share_live_inodes() {
+        use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("site");
+        fs::create_dir(&target).unwrap();
+        fs::write(target.join("index.html"), b"index").unwrap();
+        fs::set_permissions(target.join("index.html"), fs::Permissions::from_mode(0o640)).unwrap();
+        std::os::unix::fs::symlink("index.html", target.join("home.html")).unwrap();
+        metadata::create_fifo_node_from_parts(&target.join("pipe"), 0o644, false, false).unwrap();
+
+        let swap = AtomicSwap::prepare(&target).unwrap();
+        let live = fs::metadata(target.join("index.html")).unwrap();
+        let seeded = fs::metadata(swap.staging.join("index.html")).unwrap();
+        assert_ne!(live.ino(), seeded.ino());
+        assert_eq!(seeded.mode() & 0o7777, 0o640);
+        assert_eq!(seeded.mtime(), live.mtime());
+        assert_eq!(fs::read(swap.staging.join("index.html")).unwrap(), b"index");
+        assert_eq!(
+            fs::read_link(swap.staging.join("home.html")).unwrap(),
+            Path::new("index.html")
+        );
+        let live_pipe = fs::symlink_metadata(target.join("pipe")).unwrap();
+        let seeded_pipe = fs::symlink_metadata(swap.staging.join("pipe")).unwrap();
+        assert_ne!(live_pipe.ino(), seeded_pipe.ino());
+
+        // A metadata change in the staging tree stays out of the live tree.
+        fs::set_permissions(
+            swap.staging.join("index.html"),
+            fs::Permissions::from_mode(0o600),
+        )
+        .unwrap();
+        assert_eq!(
+            fs::metadata(target.join("index.html")).unwrap().mode() & 0o7777,
+            0o640
+        );
+        swap.abort();
+    }
+
+    #[test]
+    fn commit_replaces_existing_tree() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("site");
+        fs::create_dir(&target).unwrap();
+        fs::write(target.join("old.html"), b"old").unwrap();
+
+        let swap = AtomicSwap::prepare(&target).unwrap();
+        fs::remove_file(swap.staging.join("old.html")).unwrap();
+        fs::write(swap.staging.join("new.html"), b"new").unwrap();
+        let staging = swap.staging.clone();
+        swap.commit().unwrap();
+
+        assert_eq!(fs::read(target.join("new.html")).unwrap(), b"new");
+        assert!(!target.join("old.html").exists());
+        assert!(!staging.exists());
+        assert!(!dir.path().join(".site.~atomic-old~").exists());
+    }
+
+    #[test]
+    fn commit_moves_new_tree_into_missing_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("site");
+
+        let swap = AtomicSwap::prepare(&target).unwrap();
+        fs::create_dir(&swap.staging).unwrap();
+        fs::write(swap.staging.join("index.html"), b"new").unwrap();
+        swap.commit().unwrap();
+
+        assert_eq!(fs::read(target.join("index.html")).unwrap(), b"new");
+    }
+}
@@ -13,7 +13,7 @@ use crate::message::Role;
 use crate::rsync_error;
 
 use super::super::config::{ClientConfig, FilterRuleKind, FilterRuleSpec};
-use super::super::error::ClientError;
+use super::super::error::{ClientError, PARTIAL_TRANSFER_EXIT_CODE};
 use super::super::remote;
 use super::super::summary::ClientSummary;
 
@@ -348,10 +348,14 @@ fn replay_batch(
     // into the reader. numeric_ids is not a recorded stream flag (batch.c:59-76);
     // it comes from the replay invocation and gates the post-flist id-list region
     // (uidlist.c:465,473 `numeric_ids <= 0`).
+    //
+    // `--read-batch --dry-run` still walks and validates the whole batch
+    // against the destination but leaves it untouched.
     let replay_cfg = batch_cfg
         .clone()
         .with_active_flags(config_batch_flags(config))
-        .with_numeric_ids(config.numeric_ids());
+        .with_numeric_ids(config.numeric_ids())
+        .with_dry_run(config.dry_run());
 
     let result = engine::batch::replay::replay(&replay_cfg, &dest_root, config.verbosity().into())
         .map_err(|e| match e {
@@ -360,6 +364,12 @@ fn replay_batch(
             engine::batch::BatchError::FlagMismatch(msg) => {
                 ClientError::new(1, rsync_error!(1, "{}", msg).with_role(Role::Client))
             }
+            // upstream: receiver.c:970-985 - a file that fails whole-file
+            // verification ends the transfer with RERR_PARTIAL (exit 23).
+            engine::batch::BatchError::VerificationFailed(msg) => ClientError::new(
+                PARTIAL_TRANSFER_EXIT_CODE,
+                rsync_error!(PARTIAL_TRANSFER_EXIT_CODE, "{}", msg).with_role(Role::Client),
+            ),
             other => {
                 let msg = format!("batch replay failed: {other}");
                 ClientError::new(1, rsync_error!(1, "{}", msg).with_role(Role::Client))
@@ -51,8 +51,11 @@
 //! run_client_with_observer(config, Some(&mut observer))?;
 //! ```
 
+mod archive;
+mod atomic;
 mod batch;
 mod filters;
+mod remaining;
 
 use std::ffi::OsStr;
 use std::path::Path;
@@ -67,7 +70,10 @@ use engine::local_copy::{
 };
 
 use super::config::{BandwidthLimit, ClientConfig, DeleteMode};
-use super::error::{ClientError, map_local_copy_error, missing_operands_error, validate_temp_dir};
+use super::error::{
+    ClientError, invalid_argument_error_typed, map_local_copy_error, missing_operands_error,
+    validate_temp_dir,
+};
 use super::progress::{ClientProgressForwarder, ClientProgressObserver};
 use super::remote;
 use super::summary::ClientSummary;
@@ -185,6 +191,18 @@ fn run_client_internal(
         return Err(missing_operands_error());
     }
 
+    // oc-rsync extension: `--remaining-files` wraps the whole run so the
+    // hand-off list reflects where it stopped.
+    if let Some(list) = config.remaining_files() {
+        return remaining::run_recording_remaining(&config, observer, list);
+    }
+
+    // oc-rsync extension: `--dest-format=tar` receives into a staging
+    // directory through a nested run, then packs it into the archive.
+    if config.dest_format().is_archive() {
+        return archive::run_into_archive(config, observer);
+    }
+
     apply_max_alloc(&config);
 
     // upstream: main.c:1031-1046 do_recv() - the receiver validates --temp-dir
@@ -202,6 +220,20 @@ fn run_client_internal(
         }
     }
 
+    // `--atomic` swaps a local destination directory; a remote peer's
+    // destination cannot be staged and exchanged from this side.
+    if config.atomic()
+        && config
+            .transfer_args()
+            .iter()
+            .any(|arg| remote::operand_is_remote(arg) || is_daemon_operand(arg))
+    {
+        return Err(invalid_argument_error_typed(
+            "--atomic is only supported for local copies",
+            crate::exit_code::ExitCode::Syntax,
+        ));
+    }
+
     let batch_writer = if let Some(batch_cfg) = config.batch_config() {
         if let Some(result) = batch::handle_batch_read(batch_cfg, &config) {
             return result;
@@ -211,9 +243,10 @@ fn run_client_internal(
         None
     };
 
-    let has_daemon_url = config.transfer_args().iter().any(|arg| {
-        arg.to_string_lossy().starts_with("rsync://") || arg.to_string_lossy().contains("::")
-    });
+    let has_daemon_url = config
+        .transfer_args()
+        .iter()
+        .any(|arg| is_daemon_operand(arg));
 
     if has_daemon_url {
         // upstream: main.c:1593-1608 - when `-e`/`--rsh` is active with `::`,
@@ -308,7 +341,7 @@ fn run_client_internal(
         .as_deref()
         .unwrap_or_else(|| config.transfer_args());
 
-    let plan = match LocalCopyPlan::from_operands(plan_operands) {
+    let mut plan = match LocalCopyPlan::from_operands(plan_operands) {
         Ok(plan) => plan,
         Err(error) => return Err(map_local_copy_error(error)),
     };
@@ -331,6 +364,25 @@ fn run_client_internal(
         }
     }
 
+    // upstream: main.c:do_cmd() - a local transfer forks a receiver child
+    // connected over a socketpair. When the delta algorithm is requested, run
+    // the same split in-process so checksumming and disk writes overlap.
+    if remote::local_split::applies(&config) {
+        return remote::local_split::run_local_split_transfer(&config, observer);
+    }
+
+    // These options act on the receiver's disk-commit stage, which only the
+    // client/server split has; the single-process executor would silently
+    // ignore them, so refuse instead.
+    if let Some(option) = receiver_only_local_option(&config) {
+        return Err(invalid_argument_error_typed(
+            &format!(
+                "{option} is not supported for whole-file local copies; use --no-whole-file to run the copy through the receiver"
+            ),
+            crate::exit_code::ExitCode::Syntax,
+        ));
+    }
+
     let filter_program =
         filters::compile_filter_program(config.filter_rules(), config.delete_excluded())?;
     let mut options = build_local_copy_options(&config, filter_program);
@@ -367,6 +419,23 @@ fn run_client_internal(
         LocalCopyExecution::Apply
     };
 
+    // oc-rsync extension: `--atomic` receives into a hidden sibling of the
+    // destination and swaps it into place only after the copy succeeds.
+    let atomic_swap = if config.atomic() && matches!(mode, LocalCopyExecution::Apply) {
+        let swap = atomic::AtomicSwap::prepare(plan.destination())?;
+        let staged = swap.staged_operands(plan_operands);
+        plan = match LocalCopyPlan::from_operands(&staged) {
+            Ok(plan) => plan,
+            Err(error) => {
+                swap.abort();
+                return Err(map_local_copy_error(error));
+            }
+        };
+        Some(swap)
+    } else {
+        None
+    };
+
     let collect_events = config.collect_events();
 
     if collect_events {
@@ -405,7 +474,19 @@ fn run_client_internal(
         })
     };
 
-    let summary = summary.map_err(map_local_copy_error)?;
+    let summary = match (summary, atomic_swap) {
+        (Ok(summary), Some(swap)) => {
+            swap.commit()?;
+            summary
+        }
+        (Ok(summary), None) => summary,
+        (Err(error), swap) => {
+            if let Some(swap) = swap {
+                swap.abort();
+            }
+            return Err(map_local_copy_error(error));
+        }
+    };
 
     // upstream: receiver.c:674-676 - emit the progress2 end-of-transfer summary
     // line when the transfer moved no file data (a lone special/symlink or a
@@ -423,6 +504,12 @@ fn run_client_internal(
     Ok(summary)
 }
 
+/// Returns `true` for an `rsync://` URL or a `host::module` operand.
+pub(super) fn is_daemon_operand(arg: &OsStr) -> bool {
+    let text = arg.to_string_lossy();
+    text.starts_with("rsync://") || text.contains("::")
+}
+
 /// Applies the `--max-alloc` cap from the [`ClientConfig`] to the global
 /// buffer pool.
 ///
@@ -791,6 +878,7 @@ impl<'a> LocalCopyOptionsBuilder<'a> {
             .times(config.preserve_times())
             .atimes(config.preserve_atimes())
             .crtimes(config.preserve_crtimes())
+            .fileflags(config.preserve_fileflags())
             .omit_dir_times(config.omit_dir_times())
             .omit_link_times(config.omit_link_times())
             .with_user_mapping(config.user_mapping().cloned())
@@ -801,6 +889,15 @@ impl<'a> LocalCopyOptionsBuilder<'a> {
             options = options.acls(config.preserve_acls());
         }
 
+        // `-A` also carries NFSv4 ACLs (`system.nfs4_acl`). The engine copies a
+        // source NFSv4 ACL verbatim and translates POSIX ACLs only when the
+        // destination filesystem reports NFSv4 ACL support, so local POSIX
+        // destinations are unaffected.
+        #[cfg(all(unix, feature = "acl", feature = "xattr"))]
+        {
+            options = options.nfsv4_acls(config.preserve_acls());
+        }
+
         // `LocalCopyOptions::xattrs` is available on Unix and Windows (the
         // latter maps `-X` onto NTFS Alternate Data Streams); match the engine
         // crate's cfg so the flag reaches the local-copy executor on both.
@@ -829,7 +926,7 @@ impl<'a> LocalCopyOptionsBuilder<'a> {
             .ignore_missing_args(config.ignore_missing_args())
             .delete_missing_args(config.delete_missing_args())
             .update(config.update())
-            .with_modify_window(config.modify_window_setting())
+            .with_modify_window(config.local_modify_window())
             .numeric_ids(config.numeric_ids())
             .preallocate(config.preallocate())
             .fsync(config.fsync())
@@ -837,9 +934,13 @@ impl<'a> LocalCopyOptionsBuilder<'a> {
             .links(config.links())
             .sparse(config.sparse())
             .sparse_detect_strategy(config.sparse_detect())
+            .case_collision(config.case_collision())
+            .windows_names(config.windows_names())
+            .transfer_order(config.transfer_order())
             .copy_links(config.copy_links())
             .copy_dirlinks(config.copy_dirlinks())
             .copy_devices_as_files(config.copy_devices())
+            .write_devices(config.write_devices())
             .copy_unsafe_links(config.copy_unsafe_links())
             .keep_dirlinks(config.keep_dirlinks())
             .safe_links(config.safe_links())
@@ -1054,6 +1155,24 @@ mod cow_policy_wiring_tests {
     }
 }
 
+/// Returns the first receiver-only option that the single-process local copy
+/// executor cannot honour.
+fn receiver_only_local_option(config: &ClientConfig) -> Option<&'static str> {
+    if config.fsync_dir() {
+        Some("--fsync-dir")
+    } else if config.direct_write() {
+        Some("--direct-write")
+    } else if config.drop_cache() {
+        Some("--drop-cache")
+    } else if config.checkpoint().is_some() {
+        Some("--checkpoint")
+    } else if config.resume().is_some() {
+        Some("--resume")
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod run_client_tests {
     use std::fs;
@@ -1119,6 +1238,36 @@ mod run_client_tests {
         assert!(summary.files_copied() >= 1);
     }
 
+    #[test]
+    fn run_client_rejects_receiver_only_options_for_whole_file_copies() {
+        let tmp = tempdir().expect("tempdir");
+        let source = tmp.path().join("source.txt");
+        let destination = tmp.path().join("dest.txt");
+        fs::write(&source, b"data").expect("write source");
+
+        let error = run_client(
+            ClientConfig::builder()
+                .transfer_args([source.clone(), destination.clone()])
+                .fsync_dir(true)
+                .build(),
+        )
+        .expect_err("--fsync-dir must be refused");
+        assert_eq!(error.code(), crate::exit_code::ExitCode::Syntax);
+        assert!(error.to_string().contains("--fsync-dir"));
+        assert!(!destination.exists());
+
+        let summary = run_client(
+            ClientConfig::builder()
+                .transfer_args([source, destination.clone()])
+                .fsync_dir(true)
+                .whole_file_option(Some(false))
+                .build(),
+        )
+        .expect("split copy honours --fsync-dir");
+        assert_eq!(summary.files_copied(), 1);
+        assert_eq!(fs::read(destination).expect("read destination"), b"data");
+    }
+
     #[test]
     fn run_client_filter_clear_resets_previous_rules() {
         let tmp = tempdir().expect("tempdir");
@@ -1422,6 +1571,88 @@ mod run_client_tests {
         assert!(summary.files_copied() >= 1);
     }
 
+    #[test]
+    fn run_client_atomic_replaces_destination_tree() {
+        let tmp = tempdir().expect("tempdir");
+        let source_root = tmp.path().join("source");
+        fs::create_dir_all(&source_root).expect("create source");
+        fs::write(source_root.join("index.html"), b"new").expect("write source");
+
+        let dest_root = tmp.path().join("dest");
+        fs::create_dir_all(&dest_root).expect("create dest");
+        fs::write(dest_root.join("index.html"), b"old").expect("write dest");
+        fs::write(dest_root.join("stale.html"), b"stale").expect("write stale");
+
+        let mut source_arg = source_root.into_os_string();
+        source_arg.push(std::path::MAIN_SEPARATOR.to_string());
+        let config = ClientConfig::builder()
+            .transfer_args([source_arg, dest_root.clone().into_os_string()])
+            .recursive(true)
+            .delete(true)
+            .atomic(true)
+            .build();
+
+        run_client(config).expect("atomic copy succeeds");
+
+        assert_eq!(
+            fs::read(dest_root.join("index.html")).expect("read copied"),
+            b"new"
+        );
+        assert!(!dest_root.join("stale.html").exists());
+        assert!(!tmp.path().join(".dest.~atomic~").exists());
+    }
+
+    #[test]
+    fn run_client_atomic_keeps_excluded_and_extra_destination_files() {
+        let tmp = tempdir().expect("tempdir");
+        let source_root = tmp.path().join("source");
+        fs::create_dir_all(&source_root).expect("create source");
+        fs::write(source_root.join("index.html"), b"new").expect("write source");
+        fs::write(source_root.join("draft.tmp"), b"source draft").expect("write source");
+
+        let dest_root = tmp.path().join("dest");
+        fs::create_dir_all(&dest_root).expect("create dest");
+        fs::write(dest_root.join("index.html"), b"old").expect("write dest");
+        fs::write(dest_root.join("draft.tmp"), b"dest draft").expect("write excluded");
+        fs::write(dest_root.join("extra.html"), b"extra").expect("write extra");
+
+        let mut source_arg = source_root.into_os_string();
+        source_arg.push(std::path::MAIN_SEPARATOR.to_string());
+        let config = ClientConfig::builder()
+            .transfer_args([source_arg, dest_root.clone().into_os_string()])
+            .recursive(true)
+            .add_filter_rule(FilterRuleSpec::exclude("*.tmp"))
+            .atomic(true)
+            .build();
+
+        run_client(config).expect("atomic copy succeeds");
+
+        assert_eq!(
+            fs::read(dest_root.join("index.html")).expect("read"),
+            b"new"
+        );
+        assert_eq!(
+            fs::read(dest_root.join("draft.tmp")).expect("read excluded"),
+            b"dest draft"
+        );
+        assert_eq!(
+            fs::read(dest_root.join("extra.html")).expect("read extra"),
+            b"extra"
+        );
+        assert!(!tmp.path().join(".dest.~atomic~").exists());
+    }
+
+    #[test]
+    fn run_client_atomic_rejects_remote_destination() {
+        let config = ClientConfig::builder()
+            .transfer_args(["src/", "host:/srv/site"])
+            .atomic(true)
+            .build();
+
+        let error = run_client(config).expect_err("remote --atomic is rejected");
+        assert_eq!(error.code(), crate::exit_code::ExitCode::Syntax);
+    }
+
     #[test]
     fn sequential_runs_respect_ignore_times() {
         use std::time::Duration;
@@ -0,0 +1,344 @@
+//! `--remaining-files` hand-off list for transfers cut short by a deadline.
+//!
+//! oc-rsync extension with no upstream equivalent. After the run the list
+//! names, one per line, every path the transfer did not get to, relative to
+//! the source directory the next run names - the directory itself for
+//! `src/`, its parent for `src` - so passing the list as `--files-from` picks
+//! up where this run stopped. A run that completes truncates the list, which
+//! lets a nightly job chain windows until it comes back empty.
+//!
+//! Remote transfers take the list from whichever side walks the file list:
+//! a pulling receiver passes the entries from the one it stopped at, a
+//! pushing sender those past the furthest one the receiver asked about, and
+//! both reduce them with [`crate::server::shared::remaining_paths`]. A local
+//! copy stops with an error instead, so its list is rebuilt afterwards by
+//! walking the sources under the same rule - every entry but the root - and
+//! keeping those the destination lacks or holds with a different type, size
+//! or modification time. Filters are not applied to that walk; the next run
+//! applies its own.
+//!
+//! Any other failure leaves an existing list untouched, since everything it
+//! names still needs sending.
+
+use std::ffi::OsStr;
+use std::fs::{self, File, Metadata};
+use std::io::{self, BufWriter, Write};
+use std::path::{Component, Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use logging::debug_log;
+
+use crate::exit_code::ExitCode;
+use crate::message::Role;
+use crate::rsync_error;
+
+use super::super::config::{ClientConfig, DestinationFormat};
+use super::super::error::ClientError;
+use super::super::progress::ClientProgressObserver;
+use super::super::remote;
+use super::super::summary::ClientSummary;
+use super::{is_daemon_operand, run_client_internal};
+
+/// Runs the transfer described by `config`, then writes the paths it did not
+/// reach to `list`.
+pub(super) fn run_recording_remaining(
+    config: &ClientConfig,
+    observer: Option<&mut dyn ClientProgressObserver>,
+    list: &Path,
+) -> Result<ClientSummary, ClientError> {
+    let result = run_client_internal(config.without_remaining_files(), observer);
+    if config.dry_run() || config.list_only() {
+        return result;
+    }
+    match &result {
+        Ok(summary) => write_list(list, summary.remaining_files())?,
+        Err(error) if error.exit_code() == ExitCode::Timeout.as_i32() && is_local_copy(config) => {
+            write_list(list, &local_remaining(config))?;
+        }
+        Err(_) => {}
+    }
+    result
+}
+
+fn is_local_copy(config: &ClientConfig) -> bool {
+    config.dest_format() == DestinationFormat::Directory
+        && config
+            .transfer_args()
+            .iter()
+            .all(|arg| !remote::operand_is_remote(arg) && !is_daemon_operand(arg))
+}
+
+/// One source operand: where it is read from, the name its entries are
+/// listed under, and where the transfer puts it.
+#[derive(Debug)]
+struct SourceRoot {
+    path: PathBuf,
+    listed: PathBuf,
+    destination: PathBuf,
+    /// `src/`: the directory's contents are transferred, not the directory.
+    contents: bool,
+}
+
+impl SourceRoot {
+    fn new(operand: &OsStr, destination: &Path, into_directory: bool, relative: bool) -> Self {
+        let path = PathBuf::from(operand);
+        let contents = operand.to_string_lossy().ends_with('/') || path.file_name().is_none();
+        let listed = if relative {
+            relative_part(operand)
+        } else if contents {
+            PathBuf::new()
+        } else {
+            path.file_name().map(PathBuf::from).unwrap_or_default()
+        };
+        let destination = if relative || contents || into_directory || path.is_dir() {
+            destination.join(&listed)
+        } else {
+            destination.to_path_buf()
+        };
+        Self {
+            path,
+            listed,
+            destination,
+            contents,
+        }
+    }
+}
+
+/// The path `--relative` recreates for `operand`: what follows a `/./`
+/// marker, or the whole operand without its root and `.` components.
+fn relative_part(operand: &OsStr) -> PathBuf {
+    let text = operand.to_string_lossy();
+    let tail = match text.find("/./") {
+        Some(marker) => PathBuf::from(&text[marker + 3..]),
+        None => PathBuf::from(operand),
+    };
+    tail.components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .collect()
+}
+
+/// Collects the entries of a stopped local copy whose destination is
+/// missing or out of date.
+fn local_remaining(config: &ClientConfig) -> Vec<PathBuf> {
+    let Some((destination, sources)) = config.transfer_args().split_last() else {
+        return Vec::new();
+    };
+    let destination = Path::new(destination);
+    let into_directory =
+        sources.len() > 1 || destination.is_dir() || destination.to_string_lossy().ends_with('/');
+    let mut walk = RemainingWalk {
+        recursive: config.recursive(),
+        size_only: config.size_only(),
+        remaining: Vec::new(),
+    };
+    for source in sources {
+        let root = SourceRoot::new(source, destination, into_directory, config.relative_paths());
+        walk.root(&root);
+    }
+    walk.remaining
+}
+
+struct RemainingWalk {
+    recursive: bool,
+    size_only: bool,
+    remaining: Vec<PathBuf>,
+}
+
+impl RemainingWalk {
+    fn root(&mut self, root: &SourceRoot) {
+        let metadata = if root.contents {
+            fs::metadata(&root.path)
+        } else {
+            fs::symlink_metadata(&root.path)
+        };
+        let Ok(metadata) = metadata else {
+            // Unreadable sources are reported by the next run.
+            return;
+        };
+        if !metadata.is_dir() {
+            if self.out_of_date(&root.path, &metadata, &root.destination) {
+                self.remaining.push(root.listed.clone());
+            }
+            return;
+        }
+        if !self.recursive {
+            return;
+        }
+        if !root.contents && !root.destination.is_dir() {
+            self.remaining.push(root.listed.clone());
+        }
+        self.directory(&root.path, &root.listed, &root.destination);
+    }
+
+    /// Visits the entries of `dir` in name order, so the list follows the
+    /// order the transfer walks the tree in.
+    fn directory(&mut self, dir: &Path, listed: &Path, destination: &Path) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        let mut names: Vec<_> = entries.flatten().map(|entry| entry.file_name()).collect();
+        names.sort_unstable();
+        for name in names {
+            let path = dir.join(&name);
+            let Ok(metadata) = fs::symlink_metadata(&path) else {
+                continue;
+            };
+            let entry_listed = listed.join(&name);
+            let entry_destination = destination.join(&name);
+            if metadata.is_dir() {
+                if !entry_destination.is_dir() {
+                    self.remaining.push(entry_listed.clone());
+                }
+                self.directory(&path, &entry_listed, &entry_destination);
+            } else if self.out_of_date(&path, &metadata, &entry_destination) {
+                self.remaining.push(entry_listed);
+            }
+        }
+    }
+
+    /// Applies the quick check: a missing destination, a different type, or
+    /// for regular files a different size or modification time.
+    fn out_of_date(&self, source: &Path, metadata: &Metadata, destination: &Path) -> bool {
+        let Ok(existing) = fs::symlink_metadata(destination) else {
+            return true;
+        };
+        if metadata.file_type() != existing.file_type() {
+            return true;
+        }
+        if metadata.is_file() {
+            return metadata.len() != existing.len()
+                || (!self.size_only && mtime_secs(metadata) != mtime_secs(&existing));
+        }
+        if metadata.file_type().is_symlink() {
+            return fs::read_link(source).ok() != fs::read_link(destination).ok();
+        }
+        false
+    }
+}
+
+fn mtime_secs(metadata: &Metadata) -> Option<u64> {
+    let modified = metadata.modified().ok()?;
+    modified
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|age| age.as_secs())
+}
+
+/// Replaces `path` with `files`, one per line.
+fn write_list(path: &Path, files: &[PathBuf]) -> Result<(), ClientError> {
+    let write = || -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        for file in files {
+            out.write_all(file.as_os_str().as_encoded_bytes())?;
+            out.write_all(b"\n")?;
+        }
+        out.flush()
+    };
+    write().map_err(|error| list_error(path, error))?;
+    debug_log!(
+        Flist,
+        1,
+        "remaining-files: {} path(s) left in {}",
+        files.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+#[cold]
+fn list_error(path: &Path, error: io::Error) -> ClientError {
+    let code = ExitCode::FileIo;
+    let message = rsync_error!(
+        code.as_i32(),
+        "failed to write remaining-files list {}: {error}",
+        path.display()
+    )
+    .with_role(Role::Client);
+    ClientError::with_code(code, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use filetime::{FileTime, set_file_mtime};
+
+    fn copy_with_mtime(from: &Path, to: &Path) {
+        fs::copy(from, to).unwrap();
+        let mtime = FileTime::from_last_modification_time(&fs::metadata(from).unwrap());
+        set_file_mtime(to, mtime).unwrap();
+    }
+
+    fn config_for(source: &OsStr, destination: &Path) -> ClientConfig {
+        ClientConfig::builder()
+            .transfer_args([source.to_owned(), destination.as_os_str().to_owned()])
+            .recursive(true)
+            .build()
+    }
+
+    #[test]
+    fn source_roots_follow_the_trailing_slash_rule() {
+        let dest = Path::new("dest");
+        let contents = SourceRoot::new(OsStr::new("data/src/"), dest, false, false);
+        assert_eq!(contents.listed, PathBuf::new());
+        assert_eq!(contents.destination, PathBuf::from("dest"));
+        assert!(contents.contents);
+
+        let named = SourceRoot::new(OsStr::new("data/src"), dest, true, false);
+        assert_eq!(named.listed, PathBuf::from("src"));
+        assert_eq!(named.destination, PathBuf::from("dest/src"));
+
+        let file = SourceRoot::new(OsStr::new("data/notes.txt"), dest, false, false);
+        assert_eq!(file.destination, PathBuf::from("dest"));
+
+        let relative = SourceRoot::new(OsStr::new("/data/./src/sub"), dest, true, true);
+        assert_eq!(relative.listed, PathBuf::from("src/sub"));
+        assert_eq!(relative.destination, PathBuf::from("dest/src/sub"));
+    }
+
+    #[test]
+    fn local_walk_lists_entries_the_destination_lacks() {
+        let temp = tempfile::tempdir().unwrap();
+        let src = temp.path().join("src");
+        fs::create_dir_all(src.join("done")).unwrap();
+        fs::create_dir_all(src.join("todo")).unwrap();
+        fs::write(src.join("a.txt"), b"a").unwrap();
+        fs::write(src.join("b.txt"), b"b").unwrap();
+        fs::write(src.join("done/c.txt"), b"c").unwrap();
+        fs::write(src.join("todo/d.txt"), b"d").unwrap();
+
+        let dest = temp.path().join("dest");
+        fs::create_dir_all(dest.join("done")).unwrap();
+        copy_with_mtime(&src.join("a.txt"), &dest.join("a.txt"));
+        copy_with_mtime(&src.join("done/c.txt"), &dest.join("done/c.txt"));
+        fs::write(dest.join("b.txt"), b"stale!").unwrap();
+
+        let mut operand = src.clone().into_os_string();
+        operand.push("/");
+        let remaining = local_remaining(&config_for(&operand, &dest));
+        assert_eq!(
+            remaining,
+            [
+                PathBuf::from("b.txt"),
+                PathBuf::from("todo"),
+                PathBuf::from("todo/d.txt"),
+            ]
+        );
+
+        let named = local_remaining(&config_for(src.as_os_str(), &dest));
+        assert_eq!(named.first(), Some(&PathBuf::from("src")));
+    }
+
+    #[test]
+    fn write_list_puts_one_path_per_line() {
+        let temp = tempfile::tempdir().unwrap();
+        let list = temp.path().join("left.txt");
+        fs::write(&list, b"old\n").unwrap();
+
+        write_list(&list, &[PathBuf::from("a"), PathBuf::from("dir/b")]).unwrap();
+        assert_eq!(fs::read(&list).unwrap(), b"a\ndir/b\n");
+
+        write_list(&list, &[]).unwrap();
+        assert!(fs::read(&list).unwrap().is_empty());
+    }
+}
@@ -41,7 +41,7 @@ pub use self::metadata::{
     ClientEntryKind, ClientEntryMetadata, ListOnlyEntryFields, RemoteItemizeFields,
 };
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -65,6 +65,9 @@ pub struct ClientSummary {
     /// Set to the actual negotiated version for remote/daemon transfers.
     /// upstream: main.c:429-433 gates stats lines on protocol version.
     protocol_version: u8,
+    /// Paths a deadline-stopped remote transfer had not reached, relative
+    /// to the transfer root (oc-rsync extension, `--remaining-files`).
+    remaining_files: Vec<PathBuf>,
 }
 
 /// Newest protocol version, used as default for local copies.
@@ -77,6 +80,7 @@ impl Default for ClientSummary {
             events: Vec::new(),
             io_error_exit_code: None,
             protocol_version: DEFAULT_PROTOCOL_VERSION,
+            remaining_files: Vec::new(),
         }
     }
 }
@@ -101,6 +105,7 @@ impl ClientSummary {
             events,
             io_error_exit_code: None,
             protocol_version: DEFAULT_PROTOCOL_VERSION,
+            remaining_files: Vec::new(),
         }
     }
 
@@ -111,6 +116,7 @@ impl ClientSummary {
             events: Vec::new(),
             io_error_exit_code: None,
             protocol_version: DEFAULT_PROTOCOL_VERSION,
+            remaining_files: Vec::new(),
         }
     }
 
@@ -395,6 +401,13 @@ impl ClientSummary {
         self.stats.copy_method_breakdown()
     }
 
+    /// Returns the bytes a local copy shared through copy-on-write clones
+    /// (reflink, `clonefile`, ReFS block cloning).
+    #[must_use]
+    pub const fn cloned_bytes(&self) -> u64 {
+        self.stats.cloned_bytes()
+    }
+
     /// Returns whether any whole-file copy used a kernel acceleration
     /// (clonefile, reflink, io_uring, ...). Gates the `Copy method` stats line.
     #[must_use]
@@ -456,6 +469,21 @@ impl ClientSummary {
     pub(crate) fn set_protocol_version(&mut self, version: u8) {
         self.protocol_version = version;
     }
+
+    /// Returns the paths the transfer left for a later run because the
+    /// `--stop-at` / `--stop-after` deadline passed.
+    ///
+    /// Only remote transfers fill this in; it is empty when the run
+    /// completed.
+    #[must_use]
+    pub fn remaining_files(&self) -> &[PathBuf] {
+        &self.remaining_files
+    }
+
+    /// Records the paths a deadline-stopped transfer did not reach.
+    pub(crate) fn set_remaining_files(&mut self, files: Vec<PathBuf>) {
+        self.remaining_files = files;
+    }
 }
 
 #[cfg(test)]
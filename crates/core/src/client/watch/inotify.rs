@@ -0,0 +1,176 @@
+//! inotify change notification.
+//!
+//! inotify watches single directories, so a recursive root gets one watch per
+//! directory below it and directories created later are added as their
+//! `IN_CREATE` arrives. Files written into a new directory before its watch
+//! exists are still covered: the directory itself is reported as changed.
+
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::io;
+use std::mem::MaybeUninit;
+use std::os::fd::{AsFd, OwnedFd};
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use logging::debug_log;
+use rustix::event::{PollFd, PollFlags, Timespec, poll};
+use rustix::fs::inotify::{self, CreateFlags, ReadFlags, WatchFlags};
+use rustix::io::Errno;
+
+use super::WatchBatch;
+
+/// Events that can change what a transfer would send.
+const WATCH_MASK: WatchFlags = WatchFlags::CREATE
+    .union(WatchFlags::DELETE)
+    .union(WatchFlags::MODIFY)
+    .union(WatchFlags::CLOSE_WRITE)
+    .union(WatchFlags::ATTRIB)
+    .union(WatchFlags::MOVED_FROM)
+    .union(WatchFlags::MOVED_TO)
+    .union(WatchFlags::DELETE_SELF)
+    .union(WatchFlags::MOVE_SELF)
+    .union(WatchFlags::ONLYDIR)
+    .union(WatchFlags::DONT_FOLLOW);
+
+pub(super) struct InotifyBackend {
+    fd: OwnedFd,
+    /// Watched directory, and whether its subdirectories are watched too,
+    /// by watch descriptor.
+    dirs: HashMap<i32, (PathBuf, bool)>,
+    roots: Vec<(PathBuf, bool)>,
+}
+
+impl InotifyBackend {
+    /// Watches each `(directory, recursive)` root. Fails when inotify is
+    /// unavailable or the per-user watch limit is too low for the tree.
+    pub(super) fn new(roots: &[(PathBuf, bool)]) -> io::Result<Self> {
+        let fd = inotify::init(CreateFlags::CLOEXEC | CreateFlags::NONBLOCK)?;
+        let mut backend = Self {
+            fd,
+            dirs: HashMap::new(),
+            roots: roots.to_vec(),
+        };
+        for (dir, recursive) in roots {
+            backend.watch_tree(dir, *recursive)?;
+        }
+        Ok(backend)
+    }
+
+    fn watch_tree(&mut self, dir: &Path, recursive: bool) -> io::Result<()> {
+        let wd = inotify::add_watch(&self.fd, dir, WATCH_MASK)?;
+        self.dirs.insert(wd, (dir.to_path_buf(), recursive));
+        if !recursive {
+            return Ok(());
+        }
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                self.watch_tree(&entry.path(), true)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Waits up to `timeout` (forever when `None`) for events and records
+    /// everything pending. Returns whether anything arrived.
+    pub(super) fn collect(
+        &mut self,
+        timeout: Option<Duration>,
+        batch: &mut WatchBatch,
+    ) -> io::Result<bool> {
+        let timeout = timeout
+            .map(Timespec::try_from)
+            .transpose()
+            .map_err(io::Error::other)?;
+        let mut fds = [PollFd::new(&self.fd, PollFlags::IN)];
+        match poll(&mut fds, timeout.as_ref()) {
+            Ok(0) => return Ok(false),
+            Ok(_) => {}
+            // A signal handler ran; report an empty round so the caller
+            // re-checks its state and waits again.
+            Err(Errno::INTR) => return Ok(false),
+            Err(error) => return Err(error.into()),
+        }
+
+        let mut buf = [MaybeUninit::uninit(); 4096];
+        let mut reader = inotify::Reader::new(self.fd.as_fd(), &mut buf);
+        let mut new_dirs = Vec::new();
+        let mut gone_dirs = Vec::new();
+        let mut overflowed = false;
+        loop {
+            let event = match reader.next() {
+                Ok(event) => event,
+                Err(Errno::AGAIN) => break,
+                Err(Errno::INTR) => continue,
+                Err(error) => return Err(error.into()),
+            };
+            let flags = event.events();
+            if flags.contains(ReadFlags::QUEUE_OVERFLOW) {
+                batch.request_rescan();
+                overflowed = true;
+                continue;
+            }
+            if flags.contains(ReadFlags::IGNORED) {
+                self.dirs.remove(&event.wd());
+                continue;
+            }
+            let Some((dir, recursive)) = self.dirs.get(&event.wd()) else {
+                continue;
+            };
+            let path = match event.file_name() {
+                Some(name) => dir.join(OsStr::from_bytes(name.to_bytes())),
+                None => dir.clone(),
+            };
+            if flags.contains(ReadFlags::ISDIR) {
+                if flags.intersects(ReadFlags::CREATE | ReadFlags::MOVED_TO) && *recursive {
+                    new_dirs.push(path.clone());
+                } else if flags.contains(ReadFlags::MOVED_FROM) {
+                    gone_dirs.push(path.clone());
+                }
+            }
+            if flags.intersects(
+                ReadFlags::DELETE
+                    | ReadFlags::MOVED_FROM
+                    | ReadFlags::DELETE_SELF
+                    | ReadFlags::MOVE_SELF,
+            ) {
+                batch.record_removal(path);
+            } else {
+                batch.record(path);
+            }
+        }
+
+        // A directory moved elsewhere keeps its watches, which would report
+        // events under the old name; drop them and let `MOVED_TO` re-add
+        // the tree under the new one.
+        for gone in gone_dirs {
+            let fd = &self.fd;
+            self.dirs.retain(|&wd, (dir, _)| {
+                let keep = !dir.starts_with(&gone);
+                if !keep {
+                    let _ = inotify::remove_watch(fd, wd);
+                }
+                keep
+            });
+        }
+        for dir in new_dirs {
+            if let Err(error) = self.watch_tree(&dir, true) {
+                debug_log!(Flist, 1, "watch: cannot watch {}: {error}", dir.display());
+                batch.request_rescan();
+            }
+        }
+        if overflowed {
+            // Directories created while the queue overflowed have no watch
+            // yet; adding an existing watch again is harmless.
+            for (dir, recursive) in self.roots.clone() {
+                if let Err(error) = self.watch_tree(&dir, recursive) {
+                    debug_log!(Flist, 1, "watch: rescanning {}: {error}", dir.display());
+                }
+            }
+        }
+        Ok(true)
+    }
+}
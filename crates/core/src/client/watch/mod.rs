@@ -0,0 +1,370 @@
+//! Watch mode: keep a destination in step with local sources.
+//!
+//! oc-rsync extension with no upstream equivalent. After the initial
+//! transfer the front-end parks in [`SourceWatcher::wait`], which returns
+//! once the sources have changed and then stayed quiet for the debounce
+//! window, and runs the configuration [`SourceWatcher::resync_config`]
+//! derives from the batch of changes.
+//!
+//! When the transfer has a single local source directory the re-sync names
+//! only the changed paths, rooted with a `/./` marker under `--relative` so
+//! they land where the full transfer would put them. Anything the narrowed
+//! transfer could get wrong - a removal or rename, a lost event queue, a
+//! change to the source root itself, more than [`TARGETED_LIMIT`] paths, or
+//! a transfer shape that cannot be narrowed (several sources, `--relative`,
+//! `--files-from`, `--atomic`, archive output) - re-runs the whole transfer,
+//! and the quick check keeps that cheap.
+//!
+//! Each re-sync is an ordinary client run: credentials from the original
+//! configuration are reused, but remote destinations are reconnected every
+//! time. Linux uses inotify; other platforms, and Linux when the inotify
+//! watch limit is exhausted, fall back to rescanning the tree once a
+//! second. There is no FSEvents or ReadDirectoryChangesW backend yet, so
+//! the `--watch` help text advertises polling for non-Linux platforms.
+
+#[cfg(target_os = "linux")]
+mod inotify;
+mod poll;
+#[cfg(test)]
+mod tests;
+
+use std::collections::BTreeSet;
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use logging::debug_log;
+
+use crate::exit_code::ExitCode;
+use crate::message::Role;
+use crate::rsync_error;
+
+use super::config::{ClientConfig, DestinationFormat};
+use super::error::{ClientError, invalid_argument_error_typed};
+use super::remote::operand_is_remote;
+
+/// Quiet period that ends a batch when the caller does not choose one.
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Largest batch re-synced path by path; bigger batches re-run the whole
+/// transfer.
+pub const TARGETED_LIMIT: usize = 256;
+
+/// Paths that changed below the watched sources since the last batch.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct WatchBatch {
+    changed: BTreeSet<PathBuf>,
+    removed: bool,
+    rescan: bool,
+}
+
+impl WatchBatch {
+    /// Returns the changed paths, including removed ones, in path order.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.changed.iter().map(PathBuf::as_path)
+    }
+
+    /// Returns the number of changed paths.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.changed.len()
+    }
+
+    /// Reports whether nothing was recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && !self.rescan
+    }
+
+    /// Reports whether the batch can only be applied by re-running the whole
+    /// transfer: something was removed or renamed away, or events were lost.
+    #[must_use]
+    pub const fn needs_full_resync(&self) -> bool {
+        self.removed || self.rescan
+    }
+
+    pub(super) fn record(&mut self, path: PathBuf) {
+        self.changed.insert(path);
+    }
+
+    pub(super) fn record_removal(&mut self, path: PathBuf) {
+        self.changed.insert(path);
+        self.removed = true;
+    }
+
+    pub(super) fn request_rescan(&mut self) {
+        self.rescan = true;
+    }
+
+    fn retain(&mut self, mut keep: impl FnMut(&Path) -> bool) {
+        self.changed.retain(|path| keep(path));
+    }
+}
+
+/// One source operand as the watcher sees it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct WatchRoot {
+    /// Directory whose events are collected.
+    dir: PathBuf,
+    /// For a file source, the only entry of `dir` that matters.
+    name: Option<OsString>,
+    recursive: bool,
+}
+
+impl WatchRoot {
+    fn contains(&self, path: &Path) -> bool {
+        match &self.name {
+            Some(name) => path.parent() == Some(&self.dir) && path.file_name() == Some(name),
+            None if self.recursive => path.starts_with(&self.dir),
+            None => path == self.dir || path.parent() == Some(&self.dir),
+        }
+    }
+}
+
+/// How to name a changed path as a `--relative` source operand.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Narrowing {
+    /// The source directory as written on the command line.
+    root: PathBuf,
+    /// Everything before the `/./` marker; `None` when the operand is
+    /// already relative to the working directory.
+    base: Option<OsString>,
+    /// The part of the transfer path above `root`: empty for `src/`, `src`
+    /// for `src`.
+    prefix: PathBuf,
+}
+
+impl Narrowing {
+    fn for_directory(operand: &OsStr) -> Self {
+        let root = PathBuf::from(operand);
+        let text = operand.to_string_lossy();
+        if text.ends_with('/') {
+            let trimmed = text.trim_end_matches('/');
+            let base = if trimmed.is_empty() { "/" } else { trimmed };
+            return Self {
+                root,
+                base: Some(OsString::from(base)),
+                prefix: PathBuf::new(),
+            };
+        }
+        let prefix = root.file_name().map(PathBuf::from).unwrap_or_default();
+        let base = root
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .map(|parent| parent.as_os_str().to_owned());
+        Self { root, base, prefix }
+    }
+
+    /// The operand transferring `relative` (a path below `root`).
+    fn operand(&self, relative: &Path) -> OsString {
+        let path = self.prefix.join(relative);
+        let Some(base) = &self.base else {
+            return path.into_os_string();
+        };
+        let mut operand = base.clone();
+        if !base.to_string_lossy().ends_with('/') {
+            operand.push("/");
+        }
+        operand.push("./");
+        operand.push(path);
+        operand
+    }
+}
+
+enum Backend {
+    #[cfg(target_os = "linux")]
+    Inotify(inotify::InotifyBackend),
+    Poll(poll::PollBackend),
+}
+
+impl Backend {
+    fn start(dirs: &[(PathBuf, bool)]) -> io::Result<Self> {
+        #[cfg(target_os = "linux")]
+        match inotify::InotifyBackend::new(dirs) {
+            Ok(backend) => return Ok(Self::Inotify(backend)),
+            Err(error) => {
+                debug_log!(Flist, 1, "watch: inotify unavailable ({error}); polling");
+            }
+        }
+        Ok(Self::Poll(poll::PollBackend::new(dirs)))
+    }
+
+    fn collect(&mut self, timeout: Option<Duration>, batch: &mut WatchBatch) -> io::Result<bool> {
+        match self {
+            #[cfg(target_os = "linux")]
+            Self::Inotify(backend) => backend.collect(timeout, batch),
+            Self::Poll(backend) => backend.collect(timeout, batch),
+        }
+    }
+}
+
+/// Watches the local sources of a transfer and turns their changes into
+/// re-sync configurations.
+pub struct SourceWatcher {
+    config: ClientConfig,
+    roots: Vec<WatchRoot>,
+    narrowing: Option<Narrowing>,
+    backend: Backend,
+}
+
+impl std::fmt::Debug for SourceWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SourceWatcher")
+            .field("roots", &self.roots)
+            .field("narrowing", &self.narrowing)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SourceWatcher {
+    /// Starts watching the sources of `config`.
+    ///
+    /// Create the watcher before the initial transfer so changes made while
+    /// it runs are picked up by the first re-sync. Fails when the transfer
+    /// has no destination or a source is remote or missing.
+    pub fn new(config: &ClientConfig) -> Result<Self, ClientError> {
+        let operands = config.transfer_args();
+        let Some((_, sources)) = operands
+            .split_last()
+            .filter(|(_, sources)| !sources.is_empty())
+        else {
+            return Err(invalid_argument_error_typed(
+                "--watch requires a source and a destination",
+                ExitCode::Syntax,
+            ));
+        };
+        if config.batch_config().is_some() {
+            return Err(invalid_argument_error_typed(
+                "--watch cannot be combined with batch mode",
+                ExitCode::Syntax,
+            ));
+        }
+
+        let mut roots = Vec::with_capacity(sources.len());
+        for source in sources {
+            if operand_is_remote(source) {
+                return Err(invalid_argument_error_typed(
+                    &format!(
+                        "--watch needs local sources; {} is remote",
+                        source.to_string_lossy()
+                    ),
+                    ExitCode::Syntax,
+                ));
+            }
+            let path = Path::new(source);
+            let metadata = fs::metadata(path)
+                .map_err(|error| watch_error(&format!("stat {}", path.display()), error))?;
+            roots.push(if metadata.is_dir() {
+                WatchRoot {
+                    dir: path.to_path_buf(),
+                    name: None,
+                    recursive: config.recursive(),
+                }
+            } else {
+                let parent = path.parent().filter(|p| !p.as_os_str().is_empty());
+                WatchRoot {
+                    dir: parent.unwrap_or(Path::new(".")).to_path_buf(),
+                    name: path.file_name().map(OsStr::to_owned),
+                    recursive: false,
+                }
+            });
+        }
+
+        let narrowing = match (sources, roots.as_slice()) {
+            ([source], [root])
+                if root.name.is_none()
+                    && config.recursive()
+                    && !config.relative_paths()
+                    && !config.files_from().is_active()
+                    && !config.atomic()
+                    && config.dest_format() == DestinationFormat::Directory =>
+            {
+                Some(Narrowing::for_directory(source))
+            }
+            _ => None,
+        };
+
+        let mut dirs: Vec<(PathBuf, bool)> = Vec::new();
+        for root in &roots {
+            if !dirs.iter().any(|(dir, _)| *dir == root.dir) {
+                dirs.push((root.dir.clone(), root.recursive));
+            }
+        }
+        let backend =
+            Backend::start(&dirs).map_err(|error| watch_error("watch the source tree", error))?;
+
+        Ok(Self {
+            config: config.clone(),
+            roots,
+            narrowing,
+            backend,
+        })
+    }
+
+    /// Blocks until the sources change, then keeps collecting until nothing
+    /// has changed for `debounce`.
+    pub fn wait(&mut self, debounce: Duration) -> Result<WatchBatch, ClientError> {
+        loop {
+            let mut batch = WatchBatch::default();
+            self.collect(None, &mut batch)?;
+            while self.collect(Some(debounce), &mut batch)? {}
+            let roots = &self.roots;
+            batch.retain(|path| roots.iter().any(|root| root.contains(path)));
+            if !batch.is_empty() {
+                debug_log!(Flist, 1, "watch: {} changed path(s)", batch.len());
+                return Ok(batch);
+            }
+        }
+    }
+
+    fn collect(
+        &mut self,
+        timeout: Option<Duration>,
+        batch: &mut WatchBatch,
+    ) -> Result<bool, ClientError> {
+        self.backend
+            .collect(timeout, batch)
+            .map_err(|error| watch_error("read source changes", error))
+    }
+
+    /// Returns the configuration that brings the destination up to date
+    /// with `batch`: the original transfer narrowed to the changed paths
+    /// where that is safe, the original transfer otherwise.
+    #[must_use]
+    pub fn resync_config(&self, batch: &WatchBatch) -> ClientConfig {
+        let Some(narrowing) = &self.narrowing else {
+            return self.config.clone();
+        };
+        if batch.needs_full_resync() || batch.len() > TARGETED_LIMIT {
+            return self.config.clone();
+        }
+
+        let mut sources = Vec::with_capacity(batch.len());
+        let mut covered: Option<&Path> = None;
+        // Path order puts a directory's descendants right after it, and the
+        // recursive transfer of the directory already covers them.
+        for path in batch.paths() {
+            if covered.is_some_and(|dir| path.starts_with(dir)) {
+                continue;
+            }
+            match path.strip_prefix(&narrowing.root) {
+                Ok(relative) if !relative.as_os_str().is_empty() => {
+                    sources.push(narrowing.operand(relative));
+                }
+                _ => return self.config.clone(),
+            }
+            covered = Some(path);
+        }
+        self.config.narrowed_to(sources)
+    }
+}
+
+#[cold]
+fn watch_error(action: &str, error: io::Error) -> ClientError {
+    let code = ExitCode::FileIo;
+    let message =
+        rsync_error!(code.as_i32(), "watch: failed to {action}: {error}").with_role(Role::Client);
+    ClientError::with_code(code, message)
+}
@@ -0,0 +1,111 @@
+//! Polling change detection, for platforms without a native backend and for
+//! trees too large for the inotify watch limit.
+//!
+//! Each round walks the watched directories and compares every entry's type,
+//! size and modification time with the previous walk. Directory timestamps
+//! are ignored: an entry added or removed below a directory is reported
+//! under its own path.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use super::WatchBatch;
+
+/// How often to walk the tree while waiting for the first change.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Stamp {
+    Directory,
+    Other {
+        len: u64,
+        modified: Option<SystemTime>,
+    },
+}
+
+pub(super) struct PollBackend {
+    roots: Vec<(PathBuf, bool)>,
+    snapshot: BTreeMap<PathBuf, Stamp>,
+}
+
+impl PollBackend {
+    /// Takes the first snapshot of each `(directory, recursive)` root.
+    pub(super) fn new(roots: &[(PathBuf, bool)]) -> Self {
+        let roots = roots.to_vec();
+        let snapshot = scan(&roots);
+        Self { roots, snapshot }
+    }
+
+    /// Sleeps for `timeout` and reports whether anything changed, or when
+    /// `timeout` is `None`, polls until something does.
+    pub(super) fn collect(
+        &mut self,
+        timeout: Option<Duration>,
+        batch: &mut WatchBatch,
+    ) -> io::Result<bool> {
+        loop {
+            thread::sleep(timeout.unwrap_or(POLL_INTERVAL));
+            let changed = self.compare(batch);
+            if changed || timeout.is_some() {
+                return Ok(changed);
+            }
+        }
+    }
+
+    fn compare(&mut self, batch: &mut WatchBatch) -> bool {
+        let current = scan(&self.roots);
+        let mut changed = false;
+        for (path, stamp) in &current {
+            if self.snapshot.get(path) != Some(stamp) {
+                batch.record(path.clone());
+                changed = true;
+            }
+        }
+        for path in self.snapshot.keys() {
+            if !current.contains_key(path) {
+                batch.record_removal(path.clone());
+                changed = true;
+            }
+        }
+        self.snapshot = current;
+        changed
+    }
+}
+
+fn scan(roots: &[(PathBuf, bool)]) -> BTreeMap<PathBuf, Stamp> {
+    let mut snapshot = BTreeMap::new();
+    for (dir, recursive) in roots {
+        scan_dir(dir, *recursive, &mut snapshot);
+    }
+    snapshot
+}
+
+/// Records the entries of `dir`. Unreadable entries are skipped: they show
+/// up as removed, and the transfer reports the real error.
+fn scan_dir(dir: &Path, recursive: bool, snapshot: &mut BTreeMap<PathBuf, Stamp>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(metadata) = entry.path().symlink_metadata() else {
+            continue;
+        };
+        let path = entry.path();
+        if metadata.is_dir() {
+            if recursive {
+                scan_dir(&path, true, snapshot);
+            }
+            snapshot.insert(path, Stamp::Directory);
+        } else {
+            let stamp = Stamp::Other {
+                len: metadata.len(),
+                modified: metadata.modified().ok(),
+            };
+            snapshot.insert(path, stamp);
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::*;
+
+fn config_for(sources: &[&Path], destination: &Path) -> ClientConfig {
+    let mut args: Vec<OsString> = sources.iter().map(|s| s.as_os_str().to_owned()).collect();
+    args.push(destination.as_os_str().to_owned());
+    ClientConfig::builder()
+        .transfer_args(args)
+        .recursive(true)
+        .build()
+}
+
+fn with_slash(path: &Path) -> PathBuf {
+    let mut text = path.as_os_str().to_owned();
+    text.push("/");
+    PathBuf::from(text)
+}
+
+fn batch_of(paths: &[PathBuf]) -> WatchBatch {
+    let mut batch = WatchBatch::default();
+    for path in paths {
+        batch.record(path.clone());
+    }
+    batch
+}
+
+#[test]
+fn narrowing_roots_paths_below_the_transfer_root() {
+    let contents = Narrowing::for_directory(OsStr::new("data/src/"));
+    assert_eq!(contents.operand(Path::new("a/b")), "data/src/./a/b");
+
+    let named = Narrowing::for_directory(OsStr::new("data/src"));
+    assert_eq!(named.operand(Path::new("a")), "data/./src/a");
+
+    let bare = Narrowing::for_directory(OsStr::new("src"));
+    assert_eq!(bare.operand(Path::new("a")), "src/a");
+
+    let root = Narrowing::for_directory(OsStr::new("/"));
+    assert_eq!(root.operand(Path::new("etc")), "/./etc");
+}
+
+#[test]
+fn watch_root_filters_by_source_shape() {
+    let tree = WatchRoot {
+        dir: PathBuf::from("src"),
+        name: None,
+        recursive: true,
+    };
+    assert!(tree.contains(Path::new("src/a/b")));
+    assert!(!tree.contains(Path::new("other/a")));
+
+    let shallow = WatchRoot {
+        recursive: false,
+        ..tree.clone()
+    };
+    assert!(shallow.contains(Path::new("src/a")));
+    assert!(!shallow.contains(Path::new("src/a/b")));
+
+    let file = WatchRoot {
+        dir: PathBuf::from("."),
+        name: Some(OsString::from("notes.txt")),
+        recursive: false,
+    };
+    assert!(file.contains(Path::new("./notes.txt")));
+    assert!(!file.contains(Path::new("./other.txt")));
+}
+
+#[test]
+fn resync_narrows_to_changed_paths() {
+    let temp = tempfile::tempdir().unwrap();
+    let src = temp.path().join("src");
+    fs::create_dir_all(src.join("sub")).unwrap();
+    let source = with_slash(&src);
+    let dest = temp.path().join("dest");
+    let watcher = SourceWatcher::new(&config_for(&[&source], &dest)).unwrap();
+
+    let batch = batch_of(&[
+        source.join("sub"),
+        source.join("sub/inner.txt"),
+        source.join("top.txt"),
+    ]);
+    let config = watcher.resync_config(&batch);
+
+    let mut sub = src.clone().into_os_string();
+    sub.push("/./sub");
+    let mut top = src.clone().into_os_string();
+    top.push("/./top.txt");
+    assert_eq!(
+        config.transfer_args(),
+        [sub, top, dest.clone().into_os_string()]
+    );
+    assert!(config.relative_paths());
+}
+
+#[test]
+fn resync_runs_the_full_transfer_when_narrowing_is_unsafe() {
+    let temp = tempfile::tempdir().unwrap();
+    let src = temp.path().join("src");
+    fs::create_dir(&src).unwrap();
+    let source = with_slash(&src);
+    let dest = temp.path().join("dest");
+    let config = config_for(&[&source], &dest);
+    let watcher = SourceWatcher::new(&config).unwrap();
+
+    let mut removed = batch_of(&[source.join("a")]);
+    removed.record_removal(source.join("b"));
+    assert_eq!(watcher.resync_config(&removed), config);
+
+    let root = batch_of(&[source.clone()]);
+    assert_eq!(watcher.resync_config(&root), config);
+
+    let many: Vec<_> = (0..=TARGETED_LIMIT)
+        .map(|n| source.join(format!("f{n}")))
+        .collect();
+    assert_eq!(watcher.resync_config(&batch_of(&many)), config);
+
+    let other = temp.path().join("other");
+    fs::create_dir(&other).unwrap();
+    let both = config_for(&[&source, &other], &dest);
+    let watcher = SourceWatcher::new(&both).unwrap();
+    assert_eq!(watcher.resync_config(&batch_of(&[source.join("a")])), both);
+}
+
+#[test]
+fn watcher_rejects_remote_and_missing_sources() {
+    let temp = tempfile::tempdir().unwrap();
+    let dest = temp.path().join("dest");
+
+    let remote = config_for(&[Path::new("host:src/")], &dest);
+    let error = SourceWatcher::new(&remote).unwrap_err();
+    assert_eq!(error.exit_code(), ExitCode::Syntax.as_i32());
+
+    let missing = config_for(&[&temp.path().join("missing")], &dest);
+    assert!(SourceWatcher::new(&missing).is_err());
+
+    let lonely = ClientConfig::builder()
+        .transfer_args([temp.path().as_os_str().to_owned()])
+        .build();
+    assert!(SourceWatcher::new(&lonely).is_err());
+}
+
+#[test]
+fn wait_reports_changes_below_the_source() {
+    let temp = tempfile::tempdir().unwrap();
+    let src = temp.path().join("src");
+    fs::create_dir_all(src.join("sub")).unwrap();
+    fs::write(temp.path().join("outside.txt"), b"x").unwrap();
+    let dest = temp.path().join("dest");
+    let mut watcher = SourceWatcher::new(&config_for(&[&src], &dest)).unwrap();
+
+    fs::write(temp.path().join("outside.txt"), b"ignored").unwrap();
+    fs::write(src.join("sub/new.txt"), b"hello").unwrap();
+    let batch = watcher.wait(Duration::from_millis(50)).unwrap();
+
+    assert!(!batch.needs_full_resync());
+    let paths: Vec<_> = batch.paths().collect();
+    assert_eq!(paths, [src.join("sub/new.txt").as_path()]);
+
+    let config = watcher.resync_config(&batch);
+    let mut operand = temp.path().as_os_str().to_owned();
+    operand.push("/./src/sub/new.txt");
+    assert_eq!(config.transfer_args()[0], operand);
+}
@@ -374,4 +374,16 @@ impl ExitCode {
             _ => Self::FileIo,
         }
     }
+
+    /// Maps an error that ended a running transfer to its exit code.
+    ///
+    /// Unlike [`from_io_error`](Self::from_io_error), which classifies local
+    /// and connection-setup failures, this follows the transfer-phase rules of
+    /// [`transfer::error::transfer_exit_code`]: a peer's `MSG_ERROR_EXIT` code
+    /// wins, a reset or closed socket mid-transfer is `StreamIo` (12) rather
+    /// than `SocketIo` (10), and an unclassified failure is `PartialTransfer`.
+    #[must_use]
+    pub fn from_transfer_error(error: &std::io::Error) -> Self {
+        Self::from_raw(crate::server::error::transfer_exit_code(error))
+    }
 }
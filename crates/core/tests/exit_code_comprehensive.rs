@@ -1634,3 +1634,102 @@ mod exit_code_formatting {
         }
     }
 }
+
+/// Transfer-phase failures must end with the same `RERR_*` code upstream
+/// rsync exits with for the equivalent condition, whichever side (client or
+/// daemon) observes the failure.
+mod transfer_failure_parity {
+    use super::*;
+    use core::server::RemoteExitError;
+    use core::server::error::{DeltaFatalError, DeltaTransferError};
+    use core::server::io_error_flags;
+    use std::io::{Error, ErrorKind};
+    use std::path::PathBuf;
+
+    #[test]
+    fn transfer_errors_map_like_upstream() {
+        // (condition, error, upstream exit code)
+        let table: Vec<(&str, Error, ExitCode)> = vec![
+            (
+                "io.c:check_timeout() - no data within --timeout",
+                Error::from(ErrorKind::TimedOut),
+                ExitCode::Timeout,
+            ),
+            (
+                "io.c - connection unexpectedly closed",
+                Error::from(ErrorKind::UnexpectedEof),
+                ExitCode::StreamIo,
+            ),
+            (
+                "io.c - read error on the socket mid-transfer",
+                Error::from(ErrorKind::ConnectionReset),
+                ExitCode::StreamIo,
+            ),
+            (
+                "io.c - write error on the socket mid-transfer",
+                Error::from(ErrorKind::BrokenPipe),
+                ExitCode::StreamIo,
+            ),
+            (
+                "io.c - corrupt multiplex frame",
+                Error::new(ErrorKind::InvalidData, "unexpected tag 99"),
+                ExitCode::StreamIo,
+            ),
+            (
+                "sender.c:316 - protocol violation",
+                protocol::protocol_violation("got transfer request in phase 2"),
+                ExitCode::Protocol,
+            ),
+            (
+                "receiver.c - write failed: no space left on device",
+                Error::other(DeltaTransferError::Fatal(DeltaFatalError::DiskFull {
+                    path: PathBuf::from("dest/file"),
+                    bytes_needed: None,
+                })),
+                ExitCode::FileIo,
+            ),
+            (
+                "io.c:1684-1722 - peer MSG_ERROR_EXIT(RERR_SYNTAX)",
+                Error::new(ErrorKind::ConnectionAborted, RemoteExitError { code: 1 }),
+                ExitCode::Syntax,
+            ),
+            (
+                "io.c:1684-1722 - peer MSG_ERROR_EXIT with a raw code",
+                Error::new(ErrorKind::ConnectionAborted, RemoteExitError { code: 42 }),
+                ExitCode::Other(42),
+            ),
+            (
+                "unclassified failure",
+                Error::other("transfer pipeline desync"),
+                ExitCode::PartialTransfer,
+            ),
+        ];
+
+        for (condition, error, expected) in &table {
+            assert_eq!(
+                ExitCode::from_transfer_error(error),
+                *expected,
+                "{condition}: {error}"
+            );
+        }
+    }
+
+    #[test]
+    fn io_error_bits_map_like_upstream() {
+        // upstream: log.c:log_exit() via cleanup.c:215-226.
+        let table = [
+            (0, ExitCode::Ok),
+            (io_error_flags::IOERR_GENERAL, ExitCode::PartialTransfer),
+            (io_error_flags::IOERR_VANISHED, ExitCode::Vanished),
+            (io_error_flags::IOERR_DEL_LIMIT, ExitCode::DeleteLimit),
+        ];
+
+        for (bits, expected) in table {
+            assert_eq!(
+                io_error_flags::to_exit_code(bits),
+                expected.as_i32(),
+                "io_error bits {bits:#x}"
+            );
+        }
+    }
+}
@@ -101,10 +101,12 @@ fn run_client_with_retry(config_fn: impl Fn() -> ClientConfig) -> Result<(), Cli
                 // Only retry on transient connection failures.
                 // ssh exits 255 on a transient connection failure, which now
                 // propagates raw as ExitCode::Other(255) instead of the old
-                // collapsed CommandFailed (124).
+                // collapsed CommandFailed (124). A connection dropped once the
+                // protocol is running is RERR_STREAMIO (12).
                 let is_transient = matches!(
                     code,
                     ExitCode::SocketIo
+                        | ExitCode::StreamIo
                         | ExitCode::CommandFailed
                         | ExitCode::Ipc
                         | ExitCode::Other(255)
@@ -31,6 +31,15 @@
 //! - The worker is the caller-supplied sync closure; this module does not
 //!   embed any knowledge of the daemon session state machine.
 //!
+//! # Limits
+//!
+//! Only the accept loop is asynchronous. Every session still performs
+//! blocking I/O and holds one OS thread until it ends, so concurrency is
+//! bounded by `max_inflight` (see [`DEFAULT_MAX_INFLIGHT_WORKERS`]) and by the
+//! host's thread limits; connections beyond the cap wait in the accept task.
+//! Idle or slow peers keep their thread, so this model does not scale past
+//! what the thread-per-connection daemon can already sustain.
+//!
 //! # Cross-platform
 //!
 //! Tokio supports Linux, macOS, and Windows. Stream conversion via
@@ -123,6 +132,28 @@ async fn accept_loop(
     worker: SyncWorker,
 ) -> io::Result<()> {
     let listener = TokioTcpListener::bind(bind_addr).await?;
+    serve_listener(listener, max_inflight, shutdown, worker).await
+}
+
+/// Runs the accept loop over an already-bound listener on the current
+/// tokio runtime.
+///
+/// The in-runtime form of [`run_hybrid_listener`] for embedders that own
+/// their runtime and listener: each accepted connection is dispatched to
+/// `worker` on a dedicated OS thread, bounded by `max_inflight`, and the
+/// future resolves once `shutdown` is set. The runtime must have its I/O and
+/// time drivers enabled.
+///
+/// # Errors
+///
+/// Returns the underlying `io::Error` when accepting fails for a reason
+/// other than an interrupted call.
+pub async fn serve_listener(
+    listener: TokioTcpListener,
+    max_inflight: usize,
+    shutdown: Arc<AtomicBool>,
+    worker: SyncWorker,
+) -> io::Result<()> {
     let permits = Arc::new(tokio::sync::Semaphore::new(max_inflight.max(1)));
 
     loop {
@@ -363,6 +394,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn serve_listener_runs_on_caller_runtime() {
+        let runtime = tokio::runtime::Runtime::new().expect("build runtime");
+        let invocations = Arc::new(AtomicUsize::new(0));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let invocations_for_worker = Arc::clone(&invocations);
+        let worker: SyncWorker = Arc::new(move |mut stream: TcpStream, _peer| {
+            invocations_for_worker.fetch_add(1, Ordering::SeqCst);
+            let mut sink = Vec::new();
+            let _ = stream.read_to_end(&mut sink);
+            Ok(())
+        });
+
+        let (listener, local_addr) = runtime.block_on(async {
+            let any = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0);
+            let listener = TokioTcpListener::bind(any).await.expect("bind");
+            let addr = listener.local_addr().expect("local addr");
+            (listener, addr)
+        });
+        let server = runtime.spawn(serve_listener(
+            listener,
+            DEFAULT_MAX_INFLIGHT_WORKERS,
+            Arc::clone(&shutdown),
+            worker,
+        ));
+
+        let _ = StdTcpStream::connect(local_addr).expect("connect");
+        thread::sleep(Duration::from_millis(200));
+
+        shutdown.store(true, Ordering::Release);
+        let result = runtime.block_on(server).expect("accept task");
+        assert!(result.is_ok(), "accept loop error: {result:?}");
+        assert_eq!(invocations.load(Ordering::SeqCst), 1);
+    }
+
     #[test]
     fn run_hybrid_listener_shuts_down_promptly() {
         let bind_addr = reserve_port();
@@ -57,7 +57,9 @@ use core::{
 use logging_sink::MessageSink;
 use protocol::{
     LEGACY_DAEMON_PREFIX_LEN, LegacyDaemonMessage, MessageCode, MessageFrame, ProtocolVersion,
-    filters::FilterRuleWireFormat, format_legacy_daemon_message, iconv::FilenameConverter,
+    filters::FilterRuleWireFormat,
+    format_legacy_daemon_message,
+    iconv::{FilenameConverter, converter_from_locale},
     missing_greeting_token, parse_legacy_daemon_message,
 };
 
@@ -283,8 +285,9 @@ pub fn run_daemon(mut config: DaemonConfig) -> Result<(), DaemonError> {
 
     // upstream: clientserver.c:1548 - `if (is_a_socket(STDIN_FILENO))`
     // When stdin is a socket, serve a single session over stdio (inetd mode)
-    // instead of binding a TCP listener.
-    if is_stdin_socket() {
+    // instead of binding a TCP listener. `--inetd` forces the same path for
+    // piped stdio, which the socket check cannot recognise.
+    if options.inetd() || is_stdin_socket() {
         return serve_inetd_session(options);
     }
 
@@ -487,7 +490,61 @@ pub fn run_daemon_stdio(config: DaemonConfig) -> Result<(), DaemonError> {
 /// a privileged module is configured (see the limitation above).
 #[cfg(feature = "async-daemon")]
 #[cfg_attr(docsrs, doc(cfg(feature = "async-daemon")))]
-pub fn run_async_daemon(mut config: DaemonConfig) -> Result<(), DaemonError> {
+pub fn run_async_daemon(config: DaemonConfig) -> Result<(), DaemonError> {
+    let plan = plan_async_daemon(config)?;
+    crate::async_listener::run_hybrid_listener(
+        plan.bind_addr,
+        plan.worker_threads,
+        plan.max_inflight,
+        plan.shutdown,
+        plan.worker,
+    )
+    .map_err(async_listener_error)
+}
+
+/// Runs the daemon's accept loop on the caller's tokio runtime.
+///
+/// The async counterpart of [`run_async_daemon`] for embedders that already
+/// run a tokio runtime: configuration, privileged-module rejection, and
+/// per-connection dispatch are identical, but the listener is bound and
+/// polled on the current runtime instead of a runtime built for the daemon.
+/// The runtime must have its I/O and time drivers enabled. The future
+/// resolves once the daemon's shutdown flag is raised.
+///
+/// Sessions are still served synchronously, one OS thread per connection for
+/// its full duration; only accepting is asynchronous, so concurrency remains
+/// bounded by the in-flight worker cap.
+///
+/// # Errors
+///
+/// Returns the same errors as [`run_async_daemon`].
+#[cfg(feature = "async-daemon")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async-daemon")))]
+pub async fn serve_async_daemon(config: DaemonConfig) -> Result<(), DaemonError> {
+    let plan = plan_async_daemon(config)?;
+    let listener = tokio::net::TcpListener::bind(plan.bind_addr)
+        .await
+        .map_err(async_listener_error)?;
+    crate::async_listener::serve_listener(listener, plan.max_inflight, plan.shutdown, plan.worker)
+        .await
+        .map_err(async_listener_error)
+}
+
+/// Listener inputs the async daemon entry points derive from a
+/// [`DaemonConfig`].
+#[cfg(feature = "async-daemon")]
+struct AsyncDaemonPlan {
+    bind_addr: SocketAddr,
+    worker_threads: usize,
+    max_inflight: usize,
+    shutdown: Arc<AtomicBool>,
+    worker: crate::async_listener::SyncWorker,
+}
+
+/// Parses `config` and builds the per-connection worker shared by
+/// [`run_async_daemon`] and [`serve_async_daemon`].
+#[cfg(feature = "async-daemon")]
+fn plan_async_daemon(mut config: DaemonConfig) -> Result<AsyncDaemonPlan, DaemonError> {
     let external_signal_flags = config.take_signal_flags();
     let _ = config.take_pre_bound_listener();
     let brand = config.brand();
@@ -638,25 +695,26 @@ pub fn run_async_daemon(mut config: DaemonConfig) -> Result<(), DaemonError> {
         context.serve_one_connection(stream, peer)
     });
 
-    let max_inflight = async_max_inflight(max_connections);
-
-    crate::async_listener::run_hybrid_listener(
+    Ok(AsyncDaemonPlan {
         bind_addr,
         worker_threads,
-        max_inflight,
+        max_inflight: async_max_inflight(max_connections),
         shutdown,
         worker,
-    )
-    .map_err(|error| {
-        DaemonError::new(
+    })
+}
+
+/// Wraps an async listener failure as a daemon error.
+#[cfg(feature = "async-daemon")]
+fn async_listener_error(error: io::Error) -> DaemonError {
+    DaemonError::new(
+        FEATURE_UNAVAILABLE_EXIT_CODE,
+        rsync_error!(
             FEATURE_UNAVAILABLE_EXIT_CODE,
-            rsync_error!(
-                FEATURE_UNAVAILABLE_EXIT_CODE,
-                format!("async-daemon listener failed: {error}")
-            )
-            .with_role(Role::Daemon),
+            format!("async-daemon listener failed: {error}")
         )
-    })
+        .with_role(Role::Daemon),
+    )
 }
 
 /// Builds the fail-closed error returned when the async daemon is asked to
@@ -770,6 +828,8 @@ include!("daemon/sections/symlink_munge.rs");
 
 include!("daemon/sections/proxy_protocol.rs");
 
+include!("daemon/sections/metrics.rs");
+
 include!("daemon/sections/auth_helpers.rs");
 
 include!("daemon/sections/module_parsing.rs");
@@ -24,12 +24,14 @@ pub(crate) fn help_text(brand: Brand) -> String {
             "  --max-sessions N    Accept N connections before exiting (N > 0).\n",
             "  --detach            Fork and run in the background (default on Unix).\n",
             "  --no-detach         Stay in the foreground; do not fork.\n",
+            "  --inetd             Serve one session over stdin/stdout (inetd, ssh forced command).\n",
             "  --config FILE      Load module definitions from FILE (packages install {default_config}).\n",
             "  --module SPEC      Register an in-memory module (NAME=PATH[,COMMENT]).\n",
             "  --motd-file FILE   Append MOTD lines from FILE before module listings.\n",
             "  --motd-line TEXT   Append TEXT as an additional MOTD line.\n",
             "  --lock-file FILE   Track module connection limits across processes using FILE.\n",
             "  --pid-file FILE    Write the daemon PID to FILE for process supervision.\n",
+            "  --dparam, -M NAME=VALUE  Override the global 'lock file', 'pid file' or 'log file'.\n",
             "  --bwlimit=RATE[:BURST]  Limit per-connection bandwidth in KiB/s.\n",
             "                          Optional :BURST caps the token bucket; 0 = unlimited.\n",
             "  --no-bwlimit       Remove any per-connection bandwidth limit configured so far.\n",
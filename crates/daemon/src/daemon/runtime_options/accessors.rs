@@ -7,6 +7,11 @@ impl RuntimeOptions {
         self.detach
     }
 
+    /// Returns whether `--inetd` asked for a single stdin/stdout session.
+    pub(crate) fn inetd(&self) -> bool {
+        self.inetd
+    }
+
     /// Returns the configured TCP listen backlog.
     ///
     /// Upstream: `daemon-parm.txt` - `listen_backlog` INTEGER (upstream default 5,
@@ -21,6 +26,21 @@ impl RuntimeOptions {
         self.acceptor_threads.map_or(1, NonZeroU32::get)
     }
 
+    /// Returns the `metrics port` the Prometheus listener binds, if enabled.
+    pub(crate) const fn metrics_port(&self) -> Option<u16> {
+        self.metrics_port
+    }
+
+    /// Returns the `metrics address` override for the metrics listener.
+    pub(crate) const fn metrics_address(&self) -> Option<IpAddr> {
+        self.metrics_address
+    }
+
+    /// Returns the `metrics hosts allow` patterns; empty means loopback only.
+    pub(crate) fn metrics_hosts_allow(&self) -> &[HostPattern] {
+        &self.metrics_hosts_allow
+    }
+
     /// Returns the configured socket options string.
     ///
     /// Upstream: `daemon-parm.txt` - `socket options` STRING. Comma-separated
@@ -107,6 +127,10 @@ impl RuntimeOptions {
         self.bind_address
     }
 
+    pub(super) fn bind_host(&self) -> Option<&str> {
+        self.bind_host.as_deref()
+    }
+
     pub(super) fn address_family(&self) -> Option<AddressFamily> {
         self.address_family
     }
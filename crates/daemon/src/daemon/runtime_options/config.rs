@@ -65,7 +65,12 @@ impl RuntimeOptions {
             if !self.bind_address_overridden {
                 self.bind_address = addr;
                 self.bind_address_overridden = true;
-                self.address_family = Some(AddressFamily::from_ip(addr));
+                // A hostname may resolve to both families; leave the family
+                // open so the listener binds every resolved address.
+                if parsed.bind_host.is_none() {
+                    self.address_family = Some(AddressFamily::from_ip(addr));
+                }
+                self.bind_host = parsed.bind_host;
             }
         }
 
@@ -87,6 +92,17 @@ impl RuntimeOptions {
             self.acceptor_threads = Some(threads);
         }
 
+        // Config-only option, like `acceptor threads`.
+        if let Some((port, _origin)) = parsed.metrics_port {
+            self.metrics_port = Some(port);
+        }
+        if let Some((addr, _origin)) = parsed.metrics_address {
+            self.metrics_address = Some(addr);
+        }
+        if let Some((patterns, _origin)) = parsed.metrics_hosts_allow {
+            self.metrics_hosts_allow = patterns;
+        }
+
         // upstream: clientserver.c - config `port` overrides the default
         // listening port unless CLI `--port` was already given.
         if let Some((port, _origin)) = parsed.rsync_port {
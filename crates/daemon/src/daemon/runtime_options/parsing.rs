@@ -48,10 +48,10 @@ impl RuntimeOptions {
                 options.port_overridden = options.port != 0;
             } else if let Some(value) = take_option_value(argument, &mut iter, "--bind")? {
                 let addr = parse_bind_address(&value)?;
-                options.set_bind_address(addr)?;
+                options.set_bind_address(addr, bind_host_name(&value.to_string_lossy()))?;
             } else if let Some(value) = take_option_value(argument, &mut iter, "--address")? {
                 let addr = parse_bind_address(&value)?;
-                options.set_bind_address(addr)?;
+                options.set_bind_address(addr, bind_host_name(&value.to_string_lossy()))?;
             } else if let Some(value) = take_option_value(argument, &mut iter, "--config")? {
                 options.load_config_modules(&value, &mut seen_modules)?;
             } else if let Some(value) = take_option_value(argument, &mut iter, "--motd-file")? {
@@ -71,6 +71,8 @@ impl RuntimeOptions {
                 options.detach = false;
             } else if argument == "--detach" {
                 options.detach = true;
+            } else if argument == "--inetd" {
+                options.inetd = true;
             } else if let Some(value) =
                 take_option_value(argument, &mut iter, "--max-sessions")?
             {
@@ -100,6 +102,10 @@ impl RuntimeOptions {
                 options.set_cli_secrets_file(validated)?;
             } else if let Some(value) = take_option_value(argument, &mut iter, "--pid-file")? {
                 options.set_pid_file(PathBuf::from(value))?;
+            } else if let Some(value) = take_option_value(argument, &mut iter, "--dparam")? {
+                options.apply_daemon_param(&value)?;
+            } else if let Some(value) = take_option_value(argument, &mut iter, "-M")? {
+                options.apply_daemon_param(&value)?;
             } else if argument == "--verbose" {
                 options.verbosity = options.verbosity.saturating_add(1);
             } else if argument == "--no-verbose" || argument == "--no-v" {
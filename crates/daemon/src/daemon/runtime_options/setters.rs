@@ -80,7 +80,50 @@ impl RuntimeOptions {
         Ok(())
     }
 
-    fn set_bind_address(&mut self, addr: IpAddr) -> Result<(), DaemonError> {
+    /// Applies one `--dparam`/`-M` `NAME=VALUE` override of a global
+    /// parameter.
+    ///
+    /// upstream: options.c `-M` collects overrides that loadparm.c
+    /// `set_dparams()` applies over the config file's global section, which
+    /// lets a container relocate files such as the `lock file` without
+    /// editing the shared config. The overrides take precedence over the
+    /// config just as the matching `--lock-file`, `--pid-file` and
+    /// `--log-file` flags do.
+    fn apply_daemon_param(&mut self, param: &OsString) -> Result<(), DaemonError> {
+        let text = param.to_string_lossy();
+        let (name, value) = text
+            .split_once('=')
+            .ok_or_else(|| config_error(format!("daemon param '{text}' is missing '='")))?;
+        let name = name.trim();
+        let value = value.trim();
+        if value.is_empty() {
+            return Err(config_error(format!(
+                "daemon param '{name}' must not be empty"
+            )));
+        }
+
+        let path = PathBuf::from(value);
+        match normalize_param_name(name).as_str() {
+            "lockfile" => self.set_lock_file(path),
+            "pidfile" => self.set_pid_file(path),
+            "logfile" => self.set_log_file(path),
+            _ => Err(config_error(format!(
+                "daemon param '{name}' cannot be overridden via --dparam"
+            ))),
+        }
+    }
+
+    fn set_bind_address(&mut self, addr: IpAddr, host: Option<String>) -> Result<(), DaemonError> {
+        // upstream: socket.c:open_socket_in() passes the bind name through
+        // getaddrinfo with the `-4`/`-6` hint, so a hostname is filtered by
+        // family at bind time instead of being pinned to its first address.
+        if host.is_some() {
+            self.bind_address = addr;
+            self.bind_address_overridden = true;
+            self.bind_host = host;
+            return Ok(());
+        }
+
         if let Some(family) = self.address_family {
             if !family.matches(addr) {
                 return Err(match family {
@@ -98,10 +141,15 @@ impl RuntimeOptions {
 
         self.bind_address = addr;
         self.bind_address_overridden = true;
+        self.bind_host = None;
         Ok(())
     }
 
     fn force_address_family(&mut self, family: AddressFamily) -> Result<(), DaemonError> {
+        // A literal bind address pins the family; a hostname is filtered by
+        // the forced family when the listener resolves it.
+        let literal_bind = self.bind_address_overridden && self.bind_host.is_none();
+
         if let Some(existing) = self.address_family {
             if existing != family {
                 // `--ipv4 --ipv6` together (or `--ipv6 --ipv4`) requests a
@@ -112,7 +160,7 @@ impl RuntimeOptions {
                 // iterates them. The bind-address conflict still errors
                 // because an explicit IPv4/IPv6 address can only match one
                 // family.
-                if self.bind_address_overridden {
+                if literal_bind {
                     let text = match existing {
                         AddressFamily::Ipv4 => {
                             "cannot use --ipv6 with an IPv4 bind address".to_owned()
@@ -130,6 +178,10 @@ impl RuntimeOptions {
             self.address_family = Some(family);
         }
 
+        if self.bind_host.is_some() {
+            return Ok(());
+        }
+
         match family {
             AddressFamily::Ipv4 => {
                 if matches!(self.bind_address, IpAddr::V6(_)) {
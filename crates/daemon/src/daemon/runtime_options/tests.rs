@@ -353,6 +353,46 @@ mod runtime_options_tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn dparam_relocates_lock_file() {
+        let args = vec![
+            OsString::from("--dparam"),
+            OsString::from("lock file=/run/oc/rsyncd.lock"),
+        ];
+        let options = RuntimeOptions::parse(&args).expect("parse");
+        assert_eq!(options.lock_file(), Some(Path::new("/run/oc/rsyncd.lock")));
+
+        let args = vec![
+            OsString::from("-M"),
+            OsString::from("LockFile = /tmp/a.lock"),
+        ];
+        let options = RuntimeOptions::parse(&args).expect("parse");
+        assert_eq!(options.lock_file(), Some(Path::new("/tmp/a.lock")));
+    }
+
+    #[test]
+    fn dparam_overrides_config_lock_file() {
+        let dir = TempDir::new().expect("tempdir");
+        let config = dir.path().join("rsyncd.conf");
+        fs::write(&config, "lock file = /var/run/rsyncd.lock\n").expect("write config");
+
+        let args = vec![
+            OsString::from("--dparam=lock file=/run/container.lock"),
+            OsString::from("--config"),
+            config.into_os_string(),
+        ];
+        let options = RuntimeOptions::parse(&args).expect("parse");
+        assert_eq!(options.lock_file(), Some(Path::new("/run/container.lock")));
+    }
+
+    #[test]
+    fn dparam_rejects_unsupported_or_malformed_params() {
+        for param in ["hosts allow=*", "lock file", "lock file= "] {
+            let args = vec![OsString::from("-M"), OsString::from(param)];
+            assert!(RuntimeOptions::parse(&args).is_err(), "{param}");
+        }
+    }
+
     #[test]
     fn parse_pid_file_option() {
         let args = vec![
@@ -392,6 +432,16 @@ mod runtime_options_tests {
         assert!(!options.detach());
     }
 
+    #[test]
+    fn inetd_flag_requests_stdio_session() {
+        let options = RuntimeOptions::parse(&[]).expect("parse");
+        assert!(!options.inetd());
+
+        let args = vec![OsString::from("--inetd")];
+        let options = RuntimeOptions::parse(&args).expect("parse");
+        assert!(options.inetd());
+    }
+
     #[test]
     fn detach_default_matches_platform() {
         let args: Vec<OsString> = vec![];
@@ -726,6 +776,35 @@ mod runtime_options_tests {
         );
     }
 
+    #[test]
+    fn hostname_address_keeps_family_open() {
+        let args = vec![OsString::from("--address"), OsString::from("localhost")];
+        let options = RuntimeOptions::parse(&args).expect("parse");
+        assert_eq!(options.bind_host(), Some("localhost"));
+        assert!(options.bind_address_overridden);
+        assert!(options.address_family().is_none());
+    }
+
+    #[test]
+    fn hostname_address_accepts_forced_family() {
+        let args = vec![
+            OsString::from("--address"),
+            OsString::from("localhost"),
+            OsString::from("--ipv4"),
+        ];
+        let options = RuntimeOptions::parse(&args).expect("parse");
+        assert_eq!(options.bind_host(), Some("localhost"));
+        assert_eq!(options.address_family(), Some(AddressFamily::Ipv4));
+    }
+
+    #[test]
+    fn literal_address_records_no_bind_host() {
+        let args = vec![OsString::from("--address"), OsString::from("[::1]")];
+        let options = RuntimeOptions::parse(&args).expect("parse");
+        assert_eq!(options.bind_host(), None);
+        assert_eq!(options.address_family(), Some(AddressFamily::Ipv6));
+    }
+
     #[test]
     fn config_without_address_keeps_default() {
         let mut file = NamedTempFile::new().expect("config file");
@@ -32,6 +32,11 @@ pub(crate) struct RuntimeOptions {
     /// (`open_socket_in`) for the family-iteration loop oc-rsync reproduces.
     pub(crate) dual_stack: bool,
     bind_address_overridden: bool,
+    /// Hostname from `--address`/`address =` when it was not a literal IP.
+    ///
+    /// `bind_address` holds its first resolved address; the accept loop
+    /// re-resolves the name and binds every address of the requested family.
+    bind_host: Option<String>,
     port_overridden: bool,
     log_file: Option<PathBuf>,
     log_file_configured: bool,
@@ -74,6 +79,18 @@ pub(crate) struct RuntimeOptions {
     /// (upstream forks one child per accepted connection from a single
     /// listener); it changes only kernel socket behaviour, never the wire.
     acceptor_threads: Option<NonZeroU32>,
+    /// TCP port of the Prometheus metrics listener from `metrics port`.
+    ///
+    /// oc-rsync extension with no upstream equivalent. When set, the daemon
+    /// serves its session, authentication, and transfer counters over plain
+    /// HTTP on this port. `None` disables the listener.
+    metrics_port: Option<u16>,
+    /// Address the metrics listener binds from `metrics address`; loopback
+    /// when `None`.
+    metrics_address: Option<IpAddr>,
+    /// Peers allowed to scrape the metrics listener from
+    /// `metrics hosts allow`; only loopback peers when empty.
+    metrics_hosts_allow: Vec<HostPattern>,
     /// TCP port from the `port` / `rsync port` global config parameter.
     ///
     /// upstream: daemon-parm.txt - `port` INTEGER, P_GLOBAL, default 0.
@@ -101,6 +118,12 @@ pub(crate) struct RuntimeOptions {
     /// upstream: daemon-parm.h - `daemon chroot` STRING, P_GLOBAL.
     daemon_chroot: Option<PathBuf>,
     detach: bool,
+    /// Serve one session over stdin/stdout even when stdin is not a socket.
+    ///
+    /// oc-rsync extension: upstream only takes the inetd path when
+    /// `is_a_socket(STDIN_FILENO)` holds, which rules out pipes such as an
+    /// ssh forced command running the daemon directly.
+    inetd: bool,
     /// Path to the config file loaded at startup, retained for SIGHUP reload.
     ///
     /// When the daemon receives SIGHUP, this path is re-read and re-parsed so
@@ -131,6 +154,7 @@ impl Default for RuntimeOptions {
             address_family: None,
             dual_stack: false,
             bind_address_overridden: false,
+            bind_host: None,
             port_overridden: false,
             log_file: None,
             log_file_configured: false,
@@ -155,6 +179,9 @@ impl Default for RuntimeOptions {
             listen_backlog: None,
             listen_backlog_from_config: false,
             acceptor_threads: None,
+            metrics_port: None,
+            metrics_address: None,
+            metrics_hosts_allow: Vec::new(),
             rsync_port: None,
             socket_options: None,
             socket_options_from_config: false,
@@ -162,6 +189,7 @@ impl Default for RuntimeOptions {
             proxy_protocol: false,
             daemon_chroot: None,
             detach: cfg!(unix),
+            inetd: false,
             config_path: None,
             verbosity: 0,
         }
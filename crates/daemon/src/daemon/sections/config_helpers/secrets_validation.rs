@@ -1,7 +1,10 @@
 /// Validates a secrets file path from a config directive.
 ///
-/// Checks that the file exists, is a regular file, and has secure permissions
-/// (0600 on Unix). Returns a [`DaemonError`] with config context on failure.
+/// Checks that the file exists and is a regular file. The permission check is
+/// deferred to module finalization because it depends on the module's
+/// effective `strict modes` setting, which may appear after the `secrets file`
+/// directive or be inherited from the global section. Returns a
+/// [`DaemonError`] with config context on failure.
 fn validate_secrets_file(
     path: &Path,
     config_path: &Path,
@@ -19,7 +22,7 @@ fn validate_secrets_file(
         )
     })?;
 
-    if let Err(detail) = ensure_secrets_file(path, &metadata) {
+    if let Err(detail) = ensure_secrets_file_is_regular(path, &metadata) {
         return Err(config_parse_error(config_path, line, detail));
     }
 
@@ -61,6 +64,12 @@ fn validate_secrets_file_from_env(
 /// Verifies the file is a regular file and (on Unix) is not other-accessible.
 /// Group access (e.g. mode 0640) is allowed, matching upstream.
 fn ensure_secrets_file(path: &Path, metadata: &fs::Metadata) -> Result<(), String> {
+    ensure_secrets_file_is_regular(path, metadata)?;
+    ensure_secrets_file_mode(path, metadata)
+}
+
+/// Ensures a secrets path names a regular file.
+fn ensure_secrets_file_is_regular(path: &Path, metadata: &fs::Metadata) -> Result<(), String> {
     if !metadata.is_file() {
         return Err(format!(
             "secrets file '{}' must be a regular file",
@@ -68,6 +77,15 @@ fn ensure_secrets_file(path: &Path, metadata: &fs::Metadata) -> Result<(), Strin
         ));
     }
 
+    Ok(())
+}
+
+/// Ensures a secrets file is not other-accessible.
+///
+/// Only enforced for modules with `strict modes` enabled (the default).
+/// Always succeeds on non-Unix platforms.
+#[cfg_attr(not(unix), allow(unused_variables))]
+fn ensure_secrets_file_mode(path: &Path, metadata: &fs::Metadata) -> Result<(), String> {
     #[cfg(unix)]
     {
         let mode = metadata.permissions().mode();
@@ -76,6 +76,23 @@ mod config_helpers_tests {
         assert_eq!(result[3].access_level, UserAccessLevel::Default);
     }
 
+    #[test]
+    fn parse_auth_user_list_suffix_is_case_insensitive() {
+        let result = parse_auth_user_list("alice:RO, bob:Rw, @staff:DENY").unwrap();
+        assert_eq!(usernames(&result), vec!["alice", "bob", "@staff"]);
+        assert_eq!(result[0].access_level, UserAccessLevel::ReadOnly);
+        assert_eq!(result[1].access_level, UserAccessLevel::ReadWrite);
+        assert_eq!(result[2].access_level, UserAccessLevel::Deny);
+    }
+
+    #[test]
+    fn parse_auth_user_list_unknown_suffix_is_stripped() {
+        let result = parse_auth_user_list("alice:admin, bob:").unwrap();
+        assert_eq!(usernames(&result), vec!["alice", "bob"]);
+        assert_eq!(result[0].access_level, UserAccessLevel::Default);
+        assert_eq!(result[1].access_level, UserAccessLevel::Default);
+    }
+
     #[test]
     fn parse_refuse_option_list_single() {
         let result = parse_refuse_option_list("delete").unwrap();
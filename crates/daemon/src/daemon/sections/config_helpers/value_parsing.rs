@@ -118,16 +118,26 @@ pub(crate) fn parse_auth_user_list(value: &str) -> Result<Vec<AuthUser>, String>
 /// Parses the access level suffix from a username entry.
 ///
 /// Returns the username (without suffix) and the corresponding access level.
+/// The suffix is everything after the first `:` and is matched
+/// case-insensitively: `ro` and `rw` select read-only and read-write, any word
+/// starting with `d` denies, and any other suffix is stripped and ignored.
+///
+/// upstream: authenticate.c `auth_server()` - `*opts++ = '\0'` cuts
+/// the token at the colon, then `opt_ch` is lowered and compared against
+/// `r`+`o`/`w` and `d`; unrecognised options reset `opt_ch` to `'\0'`.
 fn parse_access_suffix(entry: &str) -> (&str, UserAccessLevel) {
-    if let Some(name) = entry.strip_suffix(":ro") {
-        (name, UserAccessLevel::ReadOnly)
-    } else if let Some(name) = entry.strip_suffix(":rw") {
-        (name, UserAccessLevel::ReadWrite)
-    } else if let Some(name) = entry.strip_suffix(":deny") {
-        (name, UserAccessLevel::Deny)
-    } else {
-        (entry, UserAccessLevel::Default)
-    }
+    let Some((name, opts)) = entry.split_once(':') else {
+        return (entry, UserAccessLevel::Default);
+    };
+
+    let mut chars = opts.chars().map(|ch| ch.to_ascii_lowercase());
+    let level = match (chars.next(), chars.next()) {
+        (Some('r'), Some('o')) => UserAccessLevel::ReadOnly,
+        (Some('r'), Some('w')) => UserAccessLevel::ReadWrite,
+        (Some('d'), _) => UserAccessLevel::Deny,
+        _ => UserAccessLevel::Default,
+    };
+    (name, level)
 }
 
 /// Parses a comma/whitespace-separated list of refused options with deduplication.
@@ -449,6 +449,7 @@ fn apply_global_directive(
                 }
             } else {
                 state.bind_address = Some((parsed_addr, origin));
+                state.bind_host = bind_host_name(value);
             }
         }
         // upstream: daemon-parm.txt `Locals:` `uid` is P_LOCAL. A value in the
@@ -617,6 +618,79 @@ fn apply_global_directive(
                 state.acceptor_threads = Some((threads, origin));
             }
         }
+        // oc-rsync extension - TCP port of the plain-HTTP Prometheus metrics
+        // listener. Has no upstream equivalent; the rsync wire is untouched.
+        "metricsport" => {
+            let port: u16 = value.parse().map_err(|_| {
+                config_parse_error(
+                    path,
+                    line_number,
+                    format!("invalid port '{value}' for 'metrics port'"),
+                )
+            })?;
+            if port == 0 {
+                return Err(config_parse_error(
+                    path,
+                    line_number,
+                    "'metrics port' must be between 1 and 65535".to_string(),
+                ));
+            }
+
+            let origin = ConfigDirectiveOrigin {
+                path: canonical.to_path_buf(),
+                line: line_number,
+            };
+
+            if let Some((existing, existing_origin)) = &state.metrics_port {
+                if *existing != port {
+                    let existing_line = existing_origin.line;
+                    return Err(config_parse_error(
+                        path,
+                        line_number,
+                        format!(
+                            "duplicate 'metrics port' directive in global section (previously defined on line {existing_line})"
+                        ),
+                    ));
+                }
+            } else {
+                state.metrics_port = Some((port, origin));
+            }
+        }
+        // oc-rsync extension - address the metrics listener binds. Loopback
+        // when unset, so the endpoint is reachable from other hosts only when
+        // the operator asks for it.
+        "metricsaddress" => {
+            let parsed_addr = parse_bind_address(&OsString::from(value)).map_err(|_| {
+                config_parse_error(
+                    path,
+                    line_number,
+                    format!("invalid bind address '{value}' for 'metrics address'"),
+                )
+            })?;
+            let origin = ConfigDirectiveOrigin {
+                path: canonical.to_path_buf(),
+                line: line_number,
+            };
+            merge_optional_directive(
+                &mut state.metrics_address,
+                Some((parsed_addr, origin)),
+                "metrics address",
+            )?;
+        }
+        // oc-rsync extension - peers allowed to scrape the metrics listener.
+        // When unset only loopback peers are answered.
+        "metricshostsallow" => {
+            let patterns = parse_host_list(value, path, line_number, "metrics hosts allow")?;
+            let origin = ConfigDirectiveOrigin {
+                path: canonical.to_path_buf(),
+                line: line_number,
+            };
+            merge_optional_directive(
+                &mut state.metrics_hosts_allow,
+                Some((patterns, origin)),
+                "metrics hosts allow",
+            )?;
+        }
         // upstream: daemon-parm.txt - port INTEGER, P_GLOBAL, default 0.
         // Controls the TCP port the daemon listens on.
         "port" | "rsyncport" => {
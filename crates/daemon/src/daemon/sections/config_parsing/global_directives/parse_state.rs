@@ -23,10 +23,14 @@ struct GlobalParseState {
     syslog_facility: Option<(String, ConfigDirectiveOrigin)>,
     syslog_tag: Option<(String, ConfigDirectiveOrigin)>,
     bind_address: Option<(IpAddr, ConfigDirectiveOrigin)>,
+    bind_host: Option<String>,
     daemon_uid: Option<(String, ConfigDirectiveOrigin)>,
     daemon_gid: Option<(String, ConfigDirectiveOrigin)>,
     listen_backlog: Option<(u32, ConfigDirectiveOrigin)>,
     acceptor_threads: Option<(NonZeroU32, ConfigDirectiveOrigin)>,
+    metrics_port: Option<(u16, ConfigDirectiveOrigin)>,
+    metrics_address: Option<(IpAddr, ConfigDirectiveOrigin)>,
+    metrics_hosts_allow: Option<(Vec<HostPattern>, ConfigDirectiveOrigin)>,
     socket_options: Option<(String, ConfigDirectiveOrigin)>,
     proxy_protocol: Option<(bool, ConfigDirectiveOrigin)>,
     rsync_port: Option<(u16, ConfigDirectiveOrigin)>,
@@ -66,10 +70,14 @@ impl GlobalParseState {
             syslog_facility: None,
             syslog_tag: None,
             bind_address: None,
+            bind_host: None,
             daemon_uid: None,
             daemon_gid: None,
             listen_backlog: None,
             acceptor_threads: None,
+            metrics_port: None,
+            metrics_address: None,
+            metrics_hosts_allow: None,
             socket_options: None,
             proxy_protocol: None,
             rsync_port: None,
@@ -140,10 +148,14 @@ impl GlobalParseState {
             syslog_facility: self.syslog_facility,
             syslog_tag: self.syslog_tag,
             bind_address: self.bind_address,
+            bind_host: self.bind_host,
             daemon_uid: self.daemon_uid,
             daemon_gid: self.daemon_gid,
             listen_backlog: self.listen_backlog,
             acceptor_threads: self.acceptor_threads,
+            metrics_port: self.metrics_port,
+            metrics_address: self.metrics_address,
+            metrics_hosts_allow: self.metrics_hosts_allow,
             socket_options: self.socket_options,
             proxy_protocol: self.proxy_protocol,
             rsync_port: self.rsync_port,
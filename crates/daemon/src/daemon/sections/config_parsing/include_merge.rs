@@ -172,6 +172,7 @@ fn merge_included_globals(
         included.bind_address,
         "address",
     )?;
+    state.bind_host = state.bind_host.take().or(included.bind_host);
 
     merge_optional_directive(
         &mut state.daemon_uid,
@@ -226,6 +227,24 @@ fn merge_included_globals(
         "daemon chroot",
     )?;
 
+    merge_optional_directive(
+        &mut state.metrics_port,
+        included.metrics_port,
+        "metrics port",
+    )?;
+
+    merge_optional_directive(
+        &mut state.metrics_address,
+        included.metrics_address,
+        "metrics address",
+    )?;
+
+    merge_optional_directive(
+        &mut state.metrics_hosts_allow,
+        included.metrics_hosts_allow,
+        "metrics hosts allow",
+    )?;
+
     Ok(())
 }
 
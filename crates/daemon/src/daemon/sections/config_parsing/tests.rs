@@ -2644,6 +2644,51 @@ mod config_parsing_tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn parse_global_metrics_port() {
+        let dir = TempDir::new().expect("create temp dir");
+        let path = dir.path().join("data");
+        fs::create_dir(&path).expect("create dir");
+
+        let config = format!("metrics port = 9387\n[mod]\npath = {}\n", path.display());
+        let file = write_config(&config);
+        let result = parse_config_modules(file.path()).unwrap();
+        assert_eq!(result.metrics_port.unwrap().0, 9387);
+    }
+
+    #[test]
+    fn parse_global_metrics_address_and_hosts_allow() {
+        let dir = TempDir::new().expect("create temp dir");
+        let path = dir.path().join("data");
+        fs::create_dir(&path).expect("create dir");
+
+        let config = format!(
+            "metrics port = 9387\nmetrics address = 0.0.0.0\n\
+             metrics hosts allow = 10.0.0.0/8 127.0.0.1\n[mod]\npath = {}\n",
+            path.display()
+        );
+        let file = write_config(&config);
+        let result = parse_config_modules(file.path()).unwrap();
+        assert_eq!(
+            result.metrics_address.unwrap().0,
+            IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+        );
+        assert_eq!(result.metrics_hosts_allow.unwrap().0.len(), 2);
+    }
+
+    #[test]
+    fn parse_global_metrics_port_rejects_zero_and_garbage() {
+        let dir = TempDir::new().expect("create temp dir");
+        let path = dir.path().join("data");
+        fs::create_dir(&path).expect("create dir");
+
+        for value in ["0", "70000", "abc"] {
+            let config = format!("metrics port = {value}\n[mod]\npath = {}\n", path.display());
+            let file = write_config(&config);
+            assert!(parse_config_modules(file.path()).is_err(), "accepted {value}");
+        }
+    }
+
     #[test]
     fn parse_global_acceptor_threads_zero_rejected() {
         // Zero replicas would bind no listeners; the directive must reject it.
@@ -33,6 +33,11 @@ pub(crate) struct ParsedConfigModules {
     /// upstream: loadparm.c - `bind address` / `address` parameter sets the
     /// interface the daemon listens on.
     bind_address: Option<(IpAddr, ConfigDirectiveOrigin)>,
+    /// Hostname given to the `address` directive when it is not a literal IP.
+    ///
+    /// The listener re-resolves it at startup and binds every returned
+    /// address, as upstream `open_socket_in()` walks the getaddrinfo list.
+    bind_host: Option<String>,
     /// Process-wide daemon uid from the `daemon uid` global directive.
     ///
     /// upstream: daemon-parm.txt `Globals:` `daemon_uid`; clientserver.c:1376
@@ -50,6 +55,16 @@ pub(crate) struct ParsedConfigModules {
     /// Number of SO_REUSEPORT listener replicas per family from the
     /// `acceptor threads` directive (oc-rsync extension, default 1).
     acceptor_threads: Option<(NonZeroU32, ConfigDirectiveOrigin)>,
+    /// TCP port of the Prometheus metrics listener from the `metrics port`
+    /// directive (oc-rsync extension, disabled when unset).
+    metrics_port: Option<(u16, ConfigDirectiveOrigin)>,
+    /// Bind address of the metrics listener from the `metrics address`
+    /// directive (oc-rsync extension, loopback when unset).
+    metrics_address: Option<(IpAddr, ConfigDirectiveOrigin)>,
+    /// Peers allowed to scrape the metrics listener from the
+    /// `metrics hosts allow` directive (oc-rsync extension, loopback only
+    /// when unset).
+    metrics_hosts_allow: Option<(Vec<HostPattern>, ConfigDirectiveOrigin)>,
     /// Global socket options from the `socket options` directive.
     ///
     /// upstream: daemon-parm.txt - `socket options` STRING. Comma-separated list
@@ -0,0 +1,640 @@
+// Prometheus metrics endpoint for the daemon (`metrics port`).
+//
+// oc-rsync extension with no upstream equivalent. Session workers feed a
+// process-wide set of counters - active and total sessions, per-module
+// authentication failures, transfer outcomes, bytes, files, and durations
+// taken from the `ServerStats` each transfer returns - and a dedicated
+// listener thread serves them in the Prometheus text exposition format
+// (version 0.0.4) over plain HTTP. The rsync wire protocol is untouched.
+//
+// The listener binds loopback unless `metrics address` says otherwise and
+// answers only the peers `metrics hosts allow` names (loopback by default).
+// Modules hidden from the module listing are never named in a scrape.
+
+/// Name prefix shared by every exported metric.
+const METRICS_PREFIX: &str = "oc_rsyncd";
+
+/// Upper bound on the HTTP request head read from a scraper.
+const METRICS_MAX_REQUEST: usize = 8 * 1024;
+
+/// Read/write timeout applied to each scrape connection so a stalled client
+/// cannot wedge the single listener thread.
+const METRICS_IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Counters kept for one module.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct ModuleMetrics {
+    auth_failures: u64,
+    transfers_succeeded: u64,
+    transfers_failed: u64,
+    /// Bytes the daemon read from clients.
+    bytes_received: u64,
+    /// Bytes the daemon wrote to clients.
+    bytes_sent: u64,
+    files_transferred: u64,
+    /// Summed wall time of every finished transfer, successful or not.
+    transfer_time: Duration,
+}
+
+impl ModuleMetrics {
+    /// Adds `other`'s counters into these.
+    fn absorb(&mut self, other: &Self) {
+        self.auth_failures = self.auth_failures.saturating_add(other.auth_failures);
+        self.transfers_succeeded = self
+            .transfers_succeeded
+            .saturating_add(other.transfers_succeeded);
+        self.transfers_failed = self.transfers_failed.saturating_add(other.transfers_failed);
+        self.bytes_received = self.bytes_received.saturating_add(other.bytes_received);
+        self.bytes_sent = self.bytes_sent.saturating_add(other.bytes_sent);
+        self.files_transferred = self
+            .files_transferred
+            .saturating_add(other.files_transferred);
+        self.transfer_time += other.transfer_time;
+    }
+}
+
+/// What the metrics listener reveals and to whom.
+#[derive(Clone, Debug, Default)]
+struct MetricsExposure {
+    /// `metrics hosts allow` patterns; only loopback peers when empty.
+    hosts_allow: Vec<HostPattern>,
+    /// Modules the daemon lists to clients; only these are labelled.
+    listed_modules: std::collections::BTreeSet<String>,
+}
+
+impl MetricsExposure {
+    fn from_options(options: &RuntimeOptions) -> Self {
+        Self {
+            hosts_allow: options.metrics_hosts_allow().to_vec(),
+            listed_modules: options
+                .modules
+                .iter()
+                .filter(|module| module.listable())
+                .map(|module| module.name.clone())
+                .collect(),
+        }
+    }
+
+    /// Returns whether `peer` may scrape the endpoint. Hostname patterns
+    /// never match because the listener does no reverse lookups.
+    fn permits(&self, peer: IpAddr) -> bool {
+        if self.hosts_allow.is_empty() {
+            return peer.is_loopback();
+        }
+        self.hosts_allow
+            .iter()
+            .any(|pattern| pattern.matches(peer, None))
+    }
+}
+
+/// Process-wide counters exported by the metrics listener.
+///
+/// Updated from every session worker whether or not `metrics port` is set;
+/// each update is an atomic add or a short critical section, negligible next
+/// to the transfer it records.
+struct DaemonMetrics {
+    active_sessions: std::sync::atomic::AtomicU64,
+    sessions_total: std::sync::atomic::AtomicU64,
+    modules: Mutex<std::collections::BTreeMap<String, ModuleMetrics>>,
+}
+
+/// The daemon's single [`DaemonMetrics`] instance.
+static DAEMON_METRICS: DaemonMetrics = DaemonMetrics::new();
+
+/// Returns the process-wide daemon metrics.
+fn daemon_metrics() -> &'static DaemonMetrics {
+    &DAEMON_METRICS
+}
+
+/// Decrements the active-session gauge when a session ends, including by
+/// panic unwinding.
+struct ActiveSessionGuard<'a> {
+    metrics: &'a DaemonMetrics,
+}
+
+impl Drop for ActiveSessionGuard<'_> {
+    fn drop(&mut self) {
+        self.metrics.active_sessions.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl DaemonMetrics {
+    const fn new() -> Self {
+        Self {
+            active_sessions: std::sync::atomic::AtomicU64::new(0),
+            sessions_total: std::sync::atomic::AtomicU64::new(0),
+            modules: Mutex::new(std::collections::BTreeMap::new()),
+        }
+    }
+
+    /// Counts a new client session; the session stays active until the
+    /// returned guard drops.
+    fn session_started(&self) -> ActiveSessionGuard<'_> {
+        self.sessions_total.fetch_add(1, Ordering::Relaxed);
+        self.active_sessions.fetch_add(1, Ordering::Relaxed);
+        ActiveSessionGuard { metrics: self }
+    }
+
+    fn with_module(&self, module: &str, update: impl FnOnce(&mut ModuleMetrics)) {
+        let mut modules = self
+            .modules
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        match modules.get_mut(module) {
+            Some(entry) => update(entry),
+            None => update(modules.entry(module.to_owned()).or_default()),
+        }
+    }
+
+    /// Records a rejected module authentication.
+    fn record_auth_failure(&self, module: &str) {
+        self.with_module(module, |entry| entry.auth_failures += 1);
+    }
+
+    /// Records a finished transfer from its `ServerStats`, or a failure when
+    /// the transfer returned an error.
+    fn record_transfer(
+        &self,
+        module: &str,
+        stats: Option<&core::server::ServerStats>,
+        elapsed: Duration,
+    ) {
+        self.with_module(module, |entry| {
+            entry.transfer_time += elapsed;
+            let Some(stats) = stats else {
+                entry.transfers_failed += 1;
+                return;
+            };
+            entry.transfers_succeeded += 1;
+            let (received, sent, files) = match stats {
+                core::server::ServerStats::Receiver(stats) => (
+                    stats.bytes_received,
+                    stats.bytes_sent,
+                    stats.files_transferred,
+                ),
+                core::server::ServerStats::Generator(stats) => {
+                    (stats.bytes_read, stats.bytes_sent, stats.files_transferred)
+                }
+            };
+            entry.bytes_received = entry.bytes_received.saturating_add(received);
+            entry.bytes_sent = entry.bytes_sent.saturating_add(sent);
+            entry.files_transferred = entry.files_transferred.saturating_add(files as u64);
+        });
+    }
+
+    /// Renders every counter in the Prometheus text exposition format.
+    ///
+    /// Modules listed in `exposure` get a `module` label. Counters of modules
+    /// hidden with `list = no` are summed into one unlabelled series, so a
+    /// scrape never reveals a name the module listing withholds.
+    fn render(&self, exposure: &MetricsExposure) -> String {
+        use std::fmt::Write as _;
+
+        let mut out = String::with_capacity(2048);
+        push_metric_header(
+            &mut out,
+            "active_sessions",
+            "gauge",
+            "Client sessions being served.",
+        );
+        let _ = writeln!(
+            out,
+            "{METRICS_PREFIX}_active_sessions {}",
+            self.active_sessions.load(Ordering::Relaxed)
+        );
+        push_metric_header(
+            &mut out,
+            "sessions_total",
+            "counter",
+            "Client sessions accepted.",
+        );
+        let _ = writeln!(
+            out,
+            "{METRICS_PREFIX}_sessions_total {}",
+            self.sessions_total.load(Ordering::Relaxed)
+        );
+
+        let modules = self
+            .modules
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let mut labelled: Vec<(String, ModuleMetrics)> = Vec::with_capacity(modules.len());
+        let mut hidden: Option<ModuleMetrics> = None;
+        for (name, metrics) in modules.iter() {
+            if exposure.listed_modules.contains(name) {
+                let label = format!("module=\"{}\"", escape_metric_label(name));
+                labelled.push((label, metrics.clone()));
+            } else {
+                hidden
+                    .get_or_insert_with(ModuleMetrics::default)
+                    .absorb(metrics);
+            }
+        }
+        drop(modules);
+        labelled.extend(hidden.map(|metrics| (String::new(), metrics)));
+
+        push_metric_header(
+            &mut out,
+            "auth_failures_total",
+            "counter",
+            "Rejected module authentications.",
+        );
+        for (module, m) in &labelled {
+            let _ = writeln!(
+                out,
+                "{METRICS_PREFIX}_auth_failures_total{} {}",
+                metric_labels(module, ""),
+                m.auth_failures
+            );
+        }
+
+        push_metric_header(
+            &mut out,
+            "transfers_total",
+            "counter",
+            "Finished transfers.",
+        );
+        for (module, m) in &labelled {
+            let results = [
+                ("success", m.transfers_succeeded),
+                ("failure", m.transfers_failed),
+            ];
+            for (result, count) in results {
+                let labels = metric_labels(module, &format!("result=\"{result}\""));
+                let _ = writeln!(out, "{METRICS_PREFIX}_transfers_total{labels} {count}");
+            }
+        }
+
+        push_metric_header(
+            &mut out,
+            "transferred_bytes_total",
+            "counter",
+            "Bytes exchanged with clients.",
+        );
+        for (module, m) in &labelled {
+            for (direction, bytes) in [("received", m.bytes_received), ("sent", m.bytes_sent)] {
+                let labels = metric_labels(module, &format!("direction=\"{direction}\""));
+                let _ = writeln!(
+                    out,
+                    "{METRICS_PREFIX}_transferred_bytes_total{labels} {bytes}"
+                );
+            }
+        }
+
+        push_metric_header(
+            &mut out,
+            "transferred_files_total",
+            "counter",
+            "Files transferred by successful transfers.",
+        );
+        for (module, m) in &labelled {
+            let _ = writeln!(
+                out,
+                "{METRICS_PREFIX}_transferred_files_total{} {}",
+                metric_labels(module, ""),
+                m.files_transferred
+            );
+        }
+
+        push_metric_header(
+            &mut out,
+            "transfer_duration_seconds",
+            "summary",
+            "Wall time of finished transfers.",
+        );
+        for (module, m) in &labelled {
+            let labels = metric_labels(module, "");
+            let _ = writeln!(
+                out,
+                "{METRICS_PREFIX}_transfer_duration_seconds_sum{labels} {:.6}",
+                m.transfer_time.as_secs_f64()
+            );
+            let _ = writeln!(
+                out,
+                "{METRICS_PREFIX}_transfer_duration_seconds_count{labels} {}",
+                m.transfers_succeeded + m.transfers_failed
+            );
+        }
+        out
+    }
+}
+
+/// Wraps a module label (empty for the hidden-module aggregate) and any
+/// further labels in a `{...}` selector; empty when there are no labels.
+fn metric_labels(module: &str, extra: &str) -> String {
+    match (module.is_empty(), extra.is_empty()) {
+        (true, true) => String::new(),
+        (false, true) => format!("{{{module}}}"),
+        (true, false) => format!("{{{extra}}}"),
+        (false, false) => format!("{{{module},{extra}}}"),
+    }
+}
+
+/// Appends the `# HELP` and `# TYPE` lines that open a metric family.
+fn push_metric_header(out: &mut String, name: &str, kind: &str, help: &str) {
+    use std::fmt::Write as _;
+
+    let _ = writeln!(out, "# HELP {METRICS_PREFIX}_{name} {help}");
+    let _ = writeln!(out, "# TYPE {METRICS_PREFIX}_{name} {kind}");
+}
+
+/// Escapes a label value as the exposition format requires.
+fn escape_metric_label(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Binds the metrics listener on `addr:port`.
+///
+/// Called before the daemon detaches so a bind failure reaches stderr; the
+/// serving thread is spawned afterwards by [`spawn_metrics_listener`] because
+/// threads do not survive the detach fork.
+fn bind_metrics_listener(addr: IpAddr, port: u16) -> Result<TcpListener, DaemonError> {
+    let requested = SocketAddr::new(addr, port);
+    TcpListener::bind(requested).map_err(|error| {
+        DaemonError::new(
+            FEATURE_UNAVAILABLE_EXIT_CODE,
+            rsync_error!(
+                FEATURE_UNAVAILABLE_EXIT_CODE,
+                format!("failed to bind metrics listener on {requested}: {error}")
+            )
+            .with_role(Role::Daemon),
+        )
+    })
+}
+
+/// Serves scrapes from `listener` on a background thread for the rest of the
+/// daemon's life.
+fn spawn_metrics_listener(
+    listener: TcpListener,
+    exposure: MetricsExposure,
+    log_sink: Option<SharedLogSink>,
+) {
+    let log_sink_for_error = log_sink.clone();
+    let spawned = thread::Builder::new()
+        .name("daemon-metrics".to_owned())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else {
+                    continue;
+                };
+                let peer = match stream.peer_addr() {
+                    Ok(addr) => normalize_peer_address(addr).ip(),
+                    Err(_) => continue,
+                };
+                if !exposure.permits(peer) {
+                    if let Some(log) = log_sink.as_ref() {
+                        let text = format!("metrics request from {peer} refused");
+                        log_message(log, &rsync_warning!(text).with_role(Role::Daemon));
+                    }
+                    continue;
+                }
+                if let Err(error) = serve_metrics_request(&mut stream, daemon_metrics(), &exposure)
+                    && let Some(log) = log_sink.as_ref()
+                {
+                    let text = format!("metrics request failed: {error}");
+                    log_message(log, &rsync_warning!(text).with_role(Role::Daemon));
+                }
+            }
+        });
+    // The endpoint is diagnostic only; keep serving rsync clients without it.
+    if let Err(error) = spawned
+        && let Some(log) = log_sink_for_error.as_ref()
+    {
+        let text = format!("failed to start metrics listener: {error}");
+        log_message(log, &rsync_warning!(text).with_role(Role::Daemon));
+    }
+}
+
+/// Answers one HTTP request on `stream`.
+///
+/// Only `GET /metrics` (and `GET /`) succeed; anything else gets a minimal
+/// error response. The connection is closed after each reply.
+fn serve_metrics_request<S>(
+    stream: &mut S,
+    metrics: &DaemonMetrics,
+    exposure: &MetricsExposure,
+) -> io::Result<()>
+where
+    S: Read + Write + MetricsStream,
+{
+    stream.set_metrics_timeouts();
+    let mut head = Vec::with_capacity(512);
+    let mut buf = [0u8; 512];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") && !head.ends_with(b"\n\n") {
+        let n = stream.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        head.extend_from_slice(&buf[..n]);
+        if head.len() > METRICS_MAX_REQUEST {
+            break;
+        }
+    }
+    let request_line = head.split(|&b| b == b'\n').next().unwrap_or_default();
+    let request_line = String::from_utf8_lossy(request_line);
+    let mut parts = request_line.split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics" | "/")) => (
+            "200 OK",
+            "text/plain; version=0.0.4; charset=utf-8",
+            metrics.render(exposure),
+        ),
+        (Some("GET"), Some(_)) => ("404 Not Found", "text/plain", "not found\n".to_owned()),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain",
+            "method not allowed\n".to_owned(),
+        ),
+    };
+    let header = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n",
+        body.len()
+    );
+    stream.write_all(header.as_bytes())?;
+    stream.write_all(body.as_bytes())?;
+    stream.flush()
+}
+
+/// Applies the scrape I/O timeouts; a no-op for in-memory test streams.
+trait MetricsStream {
+    fn set_metrics_timeouts(&self);
+}
+
+impl MetricsStream for TcpStream {
+    fn set_metrics_timeouts(&self) {
+        let _ = self.set_read_timeout(Some(METRICS_IO_TIMEOUT));
+        let _ = self.set_write_timeout(Some(METRICS_IO_TIMEOUT));
+    }
+}
+
+#[cfg(test)]
+mod metrics_tests {
+    use super::*;
+
+    struct FakeStream {
+        input: io::Cursor<Vec<u8>>,
+        output: Vec<u8>,
+    }
+
+    impl FakeStream {
+        fn new(request: &str) -> Self {
+            Self {
+                input: io::Cursor::new(request.as_bytes().to_vec()),
+                output: Vec::new(),
+            }
+        }
+    }
+
+    impl Read for FakeStream {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.input.read(buf)
+        }
+    }
+
+    impl Write for FakeStream {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.output.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl MetricsStream for FakeStream {
+        fn set_metrics_timeouts(&self) {}
+    }
+
+    fn receiver_stats(received: u64, files: usize) -> core::server::ServerStats {
+        let stats = core::server::TransferStats {
+            bytes_received: received,
+            bytes_sent: 7,
+            files_transferred: files,
+            ..Default::default()
+        };
+        core::server::ServerStats::Receiver(stats)
+    }
+
+    fn exposing(modules: &[&str]) -> MetricsExposure {
+        MetricsExposure {
+            listed_modules: modules.iter().map(|name| (*name).to_owned()).collect(),
+            ..MetricsExposure::default()
+        }
+    }
+
+    #[test]
+    fn session_guard_tracks_active_and_total() {
+        let metrics = DaemonMetrics::new();
+        let first = metrics.session_started();
+        let second = metrics.session_started();
+        assert_eq!(metrics.active_sessions.load(Ordering::Relaxed), 2);
+        drop(first);
+        drop(second);
+        assert_eq!(metrics.active_sessions.load(Ordering::Relaxed), 0);
+        assert_eq!(metrics.sessions_total.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn render_reports_per_module_counters() {
+        let metrics = DaemonMetrics::new();
+        metrics.record_auth_failure("backup");
+        metrics.record_transfer(
+            "backup",
+            Some(&receiver_stats(1000, 3)),
+            Duration::from_millis(1500),
+        );
+        metrics.record_transfer("backup", None, Duration::from_millis(500));
+
+        let text = metrics.render(&exposing(&["backup"]));
+        assert!(text.contains("oc_rsyncd_active_sessions 0\n"));
+        assert!(text.contains("oc_rsyncd_auth_failures_total{module=\"backup\"} 1\n"));
+        assert!(
+            text.contains("oc_rsyncd_transfers_total{module=\"backup\",result=\"success\"} 1\n")
+        );
+        assert!(
+            text.contains("oc_rsyncd_transfers_total{module=\"backup\",result=\"failure\"} 1\n")
+        );
+        assert!(text.contains(
+            "oc_rsyncd_transferred_bytes_total{module=\"backup\",direction=\"received\"} 1000\n"
+        ));
+        assert!(text.contains("oc_rsyncd_transferred_files_total{module=\"backup\"} 3\n"));
+        assert!(
+            text.contains("oc_rsyncd_transfer_duration_seconds_sum{module=\"backup\"} 2.000000\n")
+        );
+        assert!(text.contains("oc_rsyncd_transfer_duration_seconds_count{module=\"backup\"} 2\n"));
+        assert!(text.contains("# TYPE oc_rsyncd_transfers_total counter\n"));
+    }
+
+    #[test]
+    fn unlisted_modules_are_aggregated_without_labels() {
+        let metrics = DaemonMetrics::new();
+        metrics.record_auth_failure("public");
+        metrics.record_auth_failure("secret-a");
+        metrics.record_auth_failure("secret-b");
+        metrics.record_transfer("secret-a", Some(&receiver_stats(10, 1)), Duration::ZERO);
+
+        let text = metrics.render(&exposing(&["public"]));
+        assert!(text.contains("oc_rsyncd_auth_failures_total{module=\"public\"} 1\n"));
+        assert!(text.contains("oc_rsyncd_auth_failures_total 2\n"));
+        assert!(text.contains("oc_rsyncd_transfers_total{result=\"success\"} 1\n"));
+        assert!(!text.contains("secret"));
+    }
+
+    #[test]
+    fn only_loopback_peers_are_permitted_by_default() {
+        let exposure = MetricsExposure::default();
+        assert!(exposure.permits(IpAddr::V4(Ipv4Addr::LOCALHOST)));
+        assert!(exposure.permits(IpAddr::V6(Ipv6Addr::LOCALHOST)));
+        assert!(!exposure.permits(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 7))));
+
+        let exposure = MetricsExposure {
+            hosts_allow: vec![HostPattern::parse("192.0.2.0/24").expect("pattern")],
+            ..MetricsExposure::default()
+        };
+        assert!(exposure.permits(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 7))));
+        assert!(!exposure.permits(IpAddr::V4(Ipv4Addr::LOCALHOST)));
+    }
+
+    #[test]
+    fn module_labels_are_escaped() {
+        assert_eq!(escape_metric_label("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+
+    #[test]
+    fn get_metrics_returns_exposition_text() {
+        let metrics = DaemonMetrics::new();
+        let mut stream = FakeStream::new("GET /metrics HTTP/1.1\r\nHost: x\r\n\r\n");
+        let exposure = MetricsExposure::default();
+        serve_metrics_request(&mut stream, &metrics, &exposure).expect("serve");
+        let response = String::from_utf8(stream.output).expect("utf8");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Content-Type: text/plain; version=0.0.4"));
+        assert!(response.ends_with(&metrics.render(&exposure)));
+    }
+
+    #[test]
+    fn other_paths_and_methods_are_rejected() {
+        let metrics = DaemonMetrics::new();
+        let mut stream = FakeStream::new("GET /admin HTTP/1.1\r\n\r\n");
+        serve_metrics_request(&mut stream, &metrics, &MetricsExposure::default()).expect("serve");
+        assert!(stream.output.starts_with(b"HTTP/1.1 404 Not Found\r\n"));
+
+        let mut stream = FakeStream::new("POST /metrics HTTP/1.1\r\n\r\n");
+        serve_metrics_request(&mut stream, &metrics, &MetricsExposure::default()).expect("serve");
+        assert!(
+            stream
+                .output
+                .starts_with(b"HTTP/1.1 405 Method Not Allowed\r\n")
+        );
+    }
+}
@@ -311,6 +311,7 @@ fn send_auth_failed(
     module: &ModuleDefinition,
     limiter: &mut Option<BandwidthLimiter>,
 ) -> io::Result<()> {
+    daemon_metrics().record_auth_failure(&module.name);
     let module_display = sanitize_module_identifier(&module.name);
     let payload = AUTH_FAILED_PAYLOAD.replace("{module}", module_display.as_ref());
     send_error(stream, limiter, &payload)
@@ -68,6 +68,10 @@ fn apply_long_form_args(client_args: &[String], config: &mut ServerConfig) -> Op
             "--size-only" => {
                 config.file_selection.size_only = true;
             }
+            // upstream: options.c:2848-2849
+            "--force" => {
+                config.deletion.force_delete = true;
+            }
             // upstream: options.c:2896-2897
             "--ignore-errors" => {
                 config.deletion.ignore_errors = true;
@@ -146,6 +150,12 @@ fn apply_long_form_args(client_args: &[String], config: &mut ServerConfig) -> Op
             "--no-zero-copy" => {
                 config.write.zero_copy_policy = fast_io::ZeroCopyPolicy::Disabled;
             }
+            // upstream: patches/fileflags.diff - `--fileflags` (oc-rsync
+            // extension) adds the BSD file-flags word to every file-list entry,
+            // so the daemon must read and write it in lockstep with the client.
+            "--fileflags" => {
+                config.flags.fileflags = true;
+            }
             // upstream: options.c:2996-2997 - --mkpath forwarded to the daemon
             // receiver on a push. Gates dest-arg path creation (main.c:738
             // make_path vs main.c:796 single do_mkdir).
@@ -262,12 +272,23 @@ fn apply_long_form_args(client_args: &[String], config: &mut ServerConfig) -> Op
                             config.deletion.max_delete = Some(n as u64);
                         }
                     }
+                // upstream: options.c:2880-2884 - `--checksum-seed=%d`. The
+                // daemon is always the protocol server, so this is the seed
+                // setup_protocol() writes after the compat flags and
+                // negotiated strings (compat.c:811-814). Upstream prints the
+                // int32 with `%d`, so a negative value is its two's
+                // complement bit pattern.
+                } else if let Some(val) = arg.strip_prefix("--checksum-seed=") {
+                    if let Some(seed) = parse_checksum_seed(val) {
+                        config.checksum_seed = Some(seed);
+                    }
                 // upstream: options.c - server_options() forwards `--modify-window=NUM`.
                 // The daemon receiver's quick-check honours it via same_time() so
                 // files within the window are not needlessly re-transferred.
                 } else if let Some(val) = arg.strip_prefix("--modify-window=") {
                     if let Ok(n) = val.trim_start_matches('+').parse::<i64>() {
                         config.file_selection.modify_window = ::metadata::ModifyWindow::from_secs(n);
+                        config.file_selection.modify_window_set = true;
                     }
                 // upstream: options.c:2874 - a negative modify_window is
                 // forwarded via the short `-@%d` spelling (e.g. `-@-1`) for
@@ -275,6 +296,7 @@ fn apply_long_form_args(client_args: &[String], config: &mut ServerConfig) -> Op
                 } else if let Some(val) = arg.strip_prefix("-@") {
                     if let Ok(n) = val.parse::<i64>() {
                         config.file_selection.modify_window = ::metadata::ModifyWindow::from_secs(n);
+                        config.file_selection.modify_window_set = true;
                     }
                 // Fallback: =value format for reference directories and backup options.
                 // Handles both upstream (two-arg) and legacy (=value) formats.
@@ -304,6 +326,55 @@ fn apply_long_form_args(client_args: &[String], config: &mut ServerConfig) -> Op
                     config.temp_dir = Some(std::path::PathBuf::from(dir));
                 } else if let Some(path) = arg.strip_prefix("--files-from=") {
                     config.file_selection.files_from_path = Some(path.to_owned());
+                // upstream: options.c:2832-2835 - `--min-size=N` / `--max-size=N`
+                // bound the files a daemon receiver accepts, parsed with the
+                // same grammar the client uses.
+                } else if let Some(size) = arg.strip_prefix("--min-size=") {
+                    if let Ok(size) = core::client::parse_size_limit(size) {
+                        config.file_selection.min_file_size = Some(size);
+                    }
+                } else if let Some(size) = arg.strip_prefix("--max-size=") {
+                    if let Ok(size) = core::client::parse_size_limit(size) {
+                        config.file_selection.max_file_size = Some(size);
+                    }
+                // upstream: options.c:2787-2790 - the block size arrives as
+                // `-B%u`; oc-rsync clients send `--block-size=N`. The signature
+                // layout clamps it to the negotiated protocol's maximum.
+                } else if let Some(size) = arg.strip_prefix("--block-size=").or_else(|| {
+                    arg.strip_prefix("-B")
+                        .filter(|r| !r.is_empty() && r.bytes().all(|b| b.is_ascii_digit()))
+                }) {
+                    if let Ok(size) = core::client::parse_size_limit(size) {
+                        config.block_size =
+                            u32::try_from(size).ok().and_then(std::num::NonZeroU32::new);
+                    }
+                // upstream: options.c:server_options() - `--stop-at` carries
+                // the client's deadline (`--stop-after` already resolved to
+                // an absolute time) so the daemon half stops with it.
+                } else if let Some(when) = arg.strip_prefix("--stop-at=") {
+                    if let Some(deadline) = core::client::parse_forwarded_stop_at(when) {
+                        config.stop_at = Some(deadline);
+                    }
+                // oc-rsync extension: `--case-collision=MODE` makes the daemon
+                // receiver fold received names and warn about, or rename,
+                // entries that collide on a case-insensitive module path.
+                } else if let Some(mode) = arg.strip_prefix("--case-collision=") {
+                    if let Ok(policy) = core::client::CaseCollisionPolicy::parse(mode) {
+                        config.file_selection.case_collision = policy;
+                    }
+                // oc-rsync extension: `--windows-names=MODE` makes the daemon
+                // receiver sanitize, skip, or reject names its (Windows) module
+                // path cannot store.
+                } else if let Some(mode) = arg.strip_prefix("--windows-names=") {
+                    if let Ok(policy) = core::client::WindowsNamesPolicy::parse(mode) {
+                        config.file_selection.windows_names = policy;
+                    }
+                // oc-rsync extension: `--order=MODE` makes the daemon receiver
+                // request regular files newest- or smallest-first.
+                } else if let Some(mode) = arg.strip_prefix("--order=") {
+                    if let Ok(order) = core::client::TransferOrder::parse(mode) {
+                        config.file_selection.transfer_order = order;
+                    }
                 // upstream: options.c:2912 / 2915 - --usermap=SPEC / --groupmap=SPEC.
                 // After unbackslash_arg / secluded-args delivery the spec arrives
                 // verbatim (`*:1234` wildcards intact) so we hand it directly to
@@ -366,6 +437,15 @@ fn apply_long_form_args(client_args: &[String], config: &mut ServerConfig) -> Op
     unknown
 }
 
+/// Parses a forwarded `--checksum-seed` value, accepting both the unsigned
+/// spelling oc-rsync clients send and upstream's signed `%d` spelling.
+fn parse_checksum_seed(value: &str) -> Option<u32> {
+    value
+        .parse::<u32>()
+        .ok()
+        .or_else(|| value.parse::<i32>().ok().map(|seed| seed as u32))
+}
+
 /// Reports whether `arg` is a client-only flag that should never reach the
 /// daemon.
 ///
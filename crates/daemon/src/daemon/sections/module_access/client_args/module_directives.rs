@@ -50,10 +50,8 @@ fn resolve_module_charset_converter(charset: Option<&str>) -> Option<FilenameCon
     };
 
     // upstream: rsync.c:125-126 - empty or "." means "use locale default".
-    // Our converter treats UTF-8 as the locale default, matching
-    // `converter_from_locale`.
     if local_part.is_empty() || local_part == "." {
-        return Some(FilenameConverter::identity());
+        return Some(converter_from_locale());
     }
 
     FilenameConverter::new(local_part, "UTF-8")
@@ -45,13 +45,49 @@ fn clamp_verbose_flags(flag_string: &str, max_verbosity: i32) -> String {
 ///
 /// The input is expected to be the output of [`clamp_verbose_flags`], so the
 /// count already respects the module's `max verbosity`. The result seeds the
-/// worker thread's [`logging::VerbosityConfig`] via `apply_verbosity`,
+/// worker thread's [`logging::VerbosityConfig`] via [`session_verbosity`],
 /// matching upstream's `limit_output_verbosity(lp_max_verbosity(i))`
 /// (clientserver.c:1127). Saturates at [`u8::MAX`].
 fn clamped_verbose_level(flag_string: &str) -> u8 {
     let count = flag_string.chars().filter(|&ch| ch == 'v').count();
     u8::try_from(count).unwrap_or(u8::MAX)
 }
+
+/// Builds the per-connection verbosity from the clamped `-v` count plus any
+/// `--info=`/`--debug=` words the client forwarded.
+///
+/// The words are applied on top of the `-v` levels and the result is then
+/// capped at what `max verbosity` would allow, so a client cannot use
+/// `--debug=deltasum4` to bypass the module limit. Unknown words are skipped;
+/// the client already validated them against its own tables.
+///
+/// upstream: options.c:parse_output_words() handles the forwarded words during
+/// the daemon's `parse_arguments()`, then clientserver.c:1141
+/// `limit_output_verbosity(lp_max_verbosity(i))` (options.c:527-552) lowers
+/// every info/debug level to the `max verbosity` tables.
+fn session_verbosity(
+    flag_string: &str,
+    client_args: &[String],
+    max_verbosity: i32,
+) -> logging::VerbosityConfig {
+    let level = clamped_verbose_level(flag_string);
+    let mut config = logging::VerbosityConfig::from_verbose_level(level);
+    // Positional path args follow the standalone `.` separator.
+    for arg in client_args.iter().take_while(|arg| arg.as_str() != ".") {
+        if let Some(words) = arg.strip_prefix("--info=") {
+            for word in words.split(',').filter(|word| !word.is_empty()) {
+                let _ = config.apply_info_flag(word);
+            }
+        } else if let Some(words) = arg.strip_prefix("--debug=") {
+            for word in words.split(',').filter(|word| !word.is_empty()) {
+                let _ = config.apply_debug_flag(word);
+            }
+        }
+    }
+    let limit = u8::try_from(max_verbosity.max(0)).unwrap_or(u8::MAX);
+    config.limit_output_verbosity(limit);
+    config
+}
 /// Applies module directives that force transfer-time behavior onto the
 /// per-session [`ServerConfig`], mirroring the daemon-only overrides upstream
 /// applies in `rsync_module()` after the client argv is parsed.
@@ -134,11 +170,12 @@ fn build_server_config(
     // caps the per-connection log verbosity once the module is selected. Each
     // oc-rsync connection runs on its own worker thread whose thread-local
     // `logging::VerbosityConfig` starts at level 0, so seed it from the clamped
-    // client request here. Without this, daemon-side `info_log!`/`debug_log!`
-    // emissions during the transfer stay silent regardless of the client's
-    // `-v`/`-vv` and the module's `max verbosity`, since the daemon's own
-    // startup `apply_verbosity` only seeded the main accept-loop thread.
-    crate::daemon::apply_verbosity(clamped_verbose_level(&flag_string));
+    // client request here, including any forwarded `--info=`/`--debug=` words.
+    // Without this, daemon-side `info_log!`/`debug_log!` emissions during the
+    // transfer stay silent regardless of the client's `-v`/`-vv` and the
+    // module's `max verbosity`, since the daemon's own startup
+    // `apply_verbosity` only seeded the main accept-loop thread.
+    logging::init(session_verbosity(&flag_string, client_args, module.max_verbosity));
 
     // upstream: main.c:1203-1204 + util1.c:804 (glob_expand_module) - receivers
     // resolve their destination by joining the module path with the client's
@@ -120,7 +120,7 @@ mod iconv_charset_converter_tests {
 
 #[cfg(test)]
 mod clamped_verbosity_tests {
-    use super::{clamp_verbose_flags, clamped_verbose_level};
+    use super::{clamp_verbose_flags, clamped_verbose_level, session_verbosity};
     use crate::daemon::apply_verbosity;
     use logging::{InfoFlag, info_gte};
 
@@ -188,4 +188,43 @@ mod clamped_verbosity_tests {
         .join()
         .expect("clamp thread");
     }
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| (*arg).to_owned()).collect()
+    }
+
+    #[test]
+    fn forwarded_info_and_debug_words_raise_levels() {
+        let client_args = args(&[
+            "--server",
+            "-logDtpr",
+            "--info=progress2",
+            "--debug=del2",
+            ".",
+        ]);
+        let config = session_verbosity("-logDtpr", &client_args, 5);
+        // PROGRESS appears in no `info_verbosity[]` entry, so any module cap
+        // up to 5 clears it, as upstream does.
+        assert_eq!(config.info.progress, 0);
+        assert_eq!(config.debug.del, 2);
+    }
+
+    #[test]
+    fn forwarded_words_are_capped_by_max_verbosity() {
+        // `max verbosity = 2` allows DEL at 1 and MISC/NAME at 2; deeper
+        // debug levels the client asked for are lowered to that ceiling.
+        let client_args = args(&["--server", "--info=name2,skip", "--debug=del3,deltasum4"]);
+        let config = session_verbosity("-logDtpr", &client_args, 2);
+        assert_eq!(config.info.name, 2);
+        assert_eq!(config.info.skip, 1);
+        assert_eq!(config.debug.del, 1);
+        assert_eq!(config.debug.deltasum, 1);
+    }
+
+    #[test]
+    fn words_after_path_separator_are_ignored() {
+        let client_args = args(&["--server", ".", "--debug=del2"]);
+        let config = session_verbosity("-logDtpr", &client_args, 5);
+        assert_eq!(config.debug.del, 0);
+    }
 }
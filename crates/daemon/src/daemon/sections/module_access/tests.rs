@@ -318,6 +318,92 @@ mod module_access_tests {
         );
     }
 
+    // upstream: options.c:2848-2849 - a pushing client forwards `--force` so
+    // the daemon receiver may replace a non-empty directory with a file.
+    #[test]
+    fn apply_long_form_args_maps_force_to_force_delete() {
+        let mut cfg = ServerConfig::default();
+        assert!(apply_long_form_args(&["--force".to_owned()], &mut cfg).is_none());
+        assert!(cfg.deletion.force_delete);
+    }
+
+    #[test]
+    fn apply_long_form_args_maps_case_collision() {
+        let mut cfg = ServerConfig::default();
+        let args = ["--case-collision=rename".to_owned()];
+        assert!(apply_long_form_args(&args, &mut cfg).is_none());
+        assert_eq!(
+            cfg.file_selection.case_collision,
+            core::client::CaseCollisionPolicy::Rename
+        );
+    }
+
+    #[test]
+    fn apply_long_form_args_maps_windows_names() {
+        let mut cfg = ServerConfig::default();
+        let args = ["--windows-names=skip".to_owned()];
+        assert!(apply_long_form_args(&args, &mut cfg).is_none());
+        assert_eq!(
+            cfg.file_selection.windows_names,
+            core::client::WindowsNamesPolicy::Skip
+        );
+    }
+
+    #[test]
+    fn apply_long_form_args_maps_size_bounds() {
+        let mut cfg = ServerConfig::default();
+        let args = ["--min-size=1K".to_owned(), "--max-size=1048576".to_owned()];
+        assert!(apply_long_form_args(&args, &mut cfg).is_none());
+        assert_eq!(cfg.file_selection.min_file_size, Some(1024));
+        assert_eq!(cfg.file_selection.max_file_size, Some(1_048_576));
+    }
+
+    #[test]
+    fn apply_long_form_args_maps_block_size() {
+        let mut cfg = ServerConfig::default();
+        assert!(apply_long_form_args(&["-B8192".to_owned()], &mut cfg).is_none());
+        assert_eq!(cfg.block_size.map(std::num::NonZeroU32::get), Some(8192));
+
+        let args = ["--block-size=4K".to_owned()];
+        assert!(apply_long_form_args(&args, &mut cfg).is_none());
+        assert_eq!(cfg.block_size.map(std::num::NonZeroU32::get), Some(4096));
+    }
+
+    #[test]
+    fn apply_long_form_args_maps_stop_at() {
+        let mut cfg = ServerConfig::default();
+        let args = ["--stop-at=2000/03/01T12:34".to_owned()];
+        assert!(apply_long_form_args(&args, &mut cfg).is_none());
+        assert_eq!(
+            cfg.stop_at,
+            Some(std::time::UNIX_EPOCH + std::time::Duration::from_secs(951_914_040))
+        );
+
+        let mut cfg = ServerConfig::default();
+        assert!(apply_long_form_args(&["--stop-at=noon".to_owned()], &mut cfg).is_none());
+        assert_eq!(cfg.stop_at, None);
+    }
+
+    #[test]
+    fn apply_long_form_args_maps_transfer_order() {
+        let mut cfg = ServerConfig::default();
+        let args = ["--order=mtime".to_owned()];
+        assert!(apply_long_form_args(&args, &mut cfg).is_none());
+        assert_eq!(
+            cfg.file_selection.transfer_order,
+            core::client::TransferOrder::Mtime
+        );
+    }
+
+    // upstream: patches/fileflags.diff - a client forwards `--fileflags` so the
+    // daemon carries the file-flags word in the file list.
+    #[test]
+    fn apply_long_form_args_maps_fileflags() {
+        let mut cfg = ServerConfig::default();
+        assert!(apply_long_form_args(&["--fileflags".to_owned()], &mut cfg).is_none());
+        assert!(cfg.flags.fileflags);
+    }
+
     // UTS-8.REOPEN regression: the client's actual phase-1 wire for
     // secluded-args daemon push is `[--server, --sender, --secluded-args]`
     // (no standalone `.` or bare `-s`), and phase 2 carries the real
@@ -1383,6 +1469,44 @@ mod module_access_tests {
         assert!(offender.is_none(), "no unknown should be reported: {offender:?}");
     }
 
+    // upstream: options.c:2880-2884 forwards `--checksum-seed=%d`; the daemon
+    // must adopt it as the seed it writes during setup_protocol().
+    #[test]
+    fn apply_long_form_args_checksum_seed() {
+        let mut config = ServerConfig::default();
+        let args = vec!["--checksum-seed=12345".to_owned(), ".".to_owned()];
+        assert!(apply_long_form_args(&args, &mut config).is_none());
+        assert_eq!(config.checksum_seed, Some(12345));
+
+        let mut config = ServerConfig::default();
+        let args = vec!["--checksum-seed=-1".to_owned(), ".".to_owned()];
+        apply_long_form_args(&args, &mut config);
+        assert_eq!(config.checksum_seed, Some(u32::MAX));
+
+        let mut config = ServerConfig::default();
+        let args = vec!["--checksum-seed=bogus".to_owned(), ".".to_owned()];
+        apply_long_form_args(&args, &mut config);
+        assert!(config.checksum_seed.is_none());
+    }
+
+    // An explicit `--modify-window=0` is recorded as set so the receiver does
+    // not widen it for a FAT-like destination; an absent window stays unset.
+    #[test]
+    fn apply_long_form_args_explicit_zero_modify_window_is_set() {
+        let mut config = ServerConfig::default();
+        let args = vec!["--modify-window=0".to_owned(), ".".to_owned()];
+        assert!(apply_long_form_args(&args, &mut config).is_none());
+        assert_eq!(
+            config.file_selection.modify_window,
+            ::metadata::ModifyWindow::ZERO
+        );
+        assert!(config.file_selection.modify_window_set);
+
+        let mut config = ServerConfig::default();
+        apply_long_form_args(&[".".to_owned()], &mut config);
+        assert!(!config.file_selection.modify_window_set);
+    }
+
     // Positional path arguments past the `.` separator must not be
     // mis-classified as unknown options - they are dispatched through
     // upstream's `glob_expand_module()` (util1.c:804), not popt.
@@ -663,6 +663,10 @@ fn process_approved_module(
     let final_protocol = handshake.protocol;
 
     let supports_tcp_shutdown = streams.supports_tcp_shutdown;
+    // Under `--zero-copy` the sender serves uncompressed whole files straight
+    // from the source into this socket with sendfile(2) (see
+    // `ConnectionConfig::sendfile_socket`).
+    config.connection.sendfile_socket = streams.sendfile_socket;
     let exit_status = execute_transfer(
         ctx,
         config,
@@ -20,6 +20,9 @@ struct TransferStreams {
     /// the transfer engine returns and before the goodbye drain reads the
     /// socket via another clone.
     drain_handle: Option<DrainHandle>,
+    /// Raw fd of the plaintext TCP write side, handed to the sender's
+    /// whole-file `sendfile(2)` path. `None` for stdio and on non-Unix.
+    sendfile_socket: Option<i32>,
 }
 
 /// Decides whether the #503 background delta-drain thread should be armed.
@@ -182,6 +185,7 @@ fn setup_transfer_streams(
             // single-socket write-write deadlock (#503). Read the pipe
             // directly - no drain thread.
             drain_handle: None,
+            sendfile_socket: None,
         }));
     }
 
@@ -206,6 +210,7 @@ fn setup_transfer_streams(
             )));
         }
     };
+    let sendfile_socket = socket_raw_fd(&write_stream);
 
     // #503: wrap the read-clone fd in a `DrainingReader` so a background thread
     // continuously drains the peer's send buffer during the delta phase. This
@@ -240,6 +245,7 @@ fn setup_transfer_streams(
             write: daemon_socket_writer(write_stream, zero_copy_policy),
             supports_tcp_shutdown: true,
             drain_handle: None,
+            sendfile_socket,
         }));
     }
 
@@ -250,9 +256,24 @@ fn setup_transfer_streams(
         write: daemon_socket_writer(write_stream, zero_copy_policy),
         supports_tcp_shutdown: true,
         drain_handle: Some(drain_handle),
+        sendfile_socket,
     }))
 }
 
+/// Raw fd of a TCP write clone. The writer built from the clone keeps the
+/// `TcpStream` alive, so the fd stays valid for the whole transfer.
+#[cfg(unix)]
+fn socket_raw_fd(stream: &TcpStream) -> Option<i32> {
+    use std::os::unix::io::AsRawFd;
+    Some(stream.as_raw_fd())
+}
+
+/// Non-Unix: there is no raw-fd `sendfile` path.
+#[cfg(not(unix))]
+fn socket_raw_fd(_stream: &TcpStream) -> Option<i32> {
+    None
+}
+
 /// Builds the handshake result for the transfer.
 fn build_handshake_result(
     reader: &BufReader<DaemonStream>,
@@ -328,7 +349,9 @@ fn execute_transfer(
     // exchanges (NDX_DONE, stats, goodbye) when TCP backpressure occurs,
     // causing 10-second hangs. Standard I/O handles partial writes correctly,
     // matching upstream rsync's socket I/O model.
+    let started = std::time::Instant::now();
     let result = run_daemon_transfer(config, handshake, read_stream, write_stream);
+    daemon_metrics().record_transfer(&module.name, result.as_ref().ok(), started.elapsed());
 
     match result {
         Ok(_server_stats) => {
@@ -379,6 +402,10 @@ fn execute_transfer(
             0
         }
         Err(err) => {
+            // upstream: the daemon child exits with the same RERR_* code a
+            // client would for the failure (log.c:log_exit()), which is what
+            // the post-xfer exec sees as RSYNC_EXIT_STATUS.
+            let code = ExitCode::from_transfer_error(&err).as_i32();
             if let Some(log) = ctx.log_sink {
                 let text = format!(
                     "transfer failed to {} ({}): module={} error={}",
@@ -387,10 +414,10 @@ fn execute_transfer(
                     ctx.request,
                     err
                 );
-                let message = rsync_error!(1, text).with_role(Role::Daemon);
+                let message = rsync_error!(code, text).with_role(Role::Daemon);
                 log_message(log, &message);
             }
-            1
+            code
         }
     }
 }
@@ -14,6 +14,9 @@ struct ModuleDefinitionBuilder {
     hosts_deny: Option<Vec<HostPattern>>,
     auth_users: Option<Vec<AuthUser>>,
     secrets_file: Option<PathBuf>,
+    /// Line of the module's `secrets file` directive, used to report a
+    /// deferred strict-modes violation against the directive itself.
+    secrets_file_line: Option<usize>,
     declaration_line: usize,
     bandwidth_limit: Option<NonZeroU64>,
     bandwidth_limit_specified: bool,
@@ -80,6 +83,7 @@ impl ModuleDefinitionBuilder {
             hosts_deny: None,
             auth_users: None,
             secrets_file: None,
+            secrets_file_line: None,
             declaration_line: line,
             bandwidth_limit: None,
             bandwidth_limit_specified: false,
@@ -118,6 +118,21 @@ impl ModuleDefinitionBuilder {
             ));
         };
 
+        // upstream: authenticate.c:119-131 check_secret() only refuses an
+        // other-accessible secrets file when `lp_strict_modes(module)` is set,
+        // so `strict modes = no` permits a world-readable file. The module's
+        // effective setting is only known once the section is complete.
+        let strict_modes = self.strict_modes.or(defaults.strict_modes).unwrap_or(true);
+        if strict_modes {
+            if let Some(secrets) = secrets_file.as_deref() {
+                if let Ok(metadata) = fs::metadata(secrets) {
+                    let line = self.secrets_file_line.unwrap_or(self.declaration_line);
+                    ensure_secrets_file_mode(secrets, &metadata)
+                        .map_err(|detail| config_parse_error(config_path, line, detail))?;
+                }
+            }
+        }
+
         // upstream: loadparm.c - exclude/include/filter are STRING parameters.
         // In the global section they set defaults; per-module directives override
         // (not append to) the defaults. If the module has its own exclude rules,
@@ -207,7 +222,7 @@ impl ModuleDefinitionBuilder {
             temp_dir: self.temp_dir.unwrap_or_else(|| defaults.temp_dir.clone()),
             charset: self.charset.unwrap_or_else(|| defaults.charset.clone()),
             forward_lookup: self.forward_lookup.or(defaults.forward_lookup).unwrap_or(true),
-            strict_modes: self.strict_modes.or(defaults.strict_modes).unwrap_or(true),
+            strict_modes,
             exclude_from: self.exclude_from.or_else(|| defaults.exclude_from.clone()),
             include_from: self.include_from.or_else(|| defaults.include_from.clone()),
             open_noatime: self.open_noatime.or(defaults.open_noatime).unwrap_or(false),
@@ -128,6 +128,7 @@ impl ModuleDefinitionBuilder {
 
         let validated = validate_secrets_file(&path, config_path, line)?;
         self.secrets_file = Some(validated);
+        self.secrets_file_line = Some(line);
         Ok(())
     }
 
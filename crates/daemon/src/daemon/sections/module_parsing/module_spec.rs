@@ -53,6 +53,26 @@ fn parse_bind_address(value: &OsString) -> Result<IpAddr, DaemonError> {
         .ok_or_else(|| config_error(format!("invalid bind address '{text}'")))
 }
 
+/// Returns the hostname in a bind address value, or `None` when the value is a
+/// literal (optionally bracketed) IP address.
+///
+/// A hostname may resolve to several addresses across both families; the
+/// listener binds each of them rather than just the first one that
+/// [`parse_bind_address`] returns.
+fn bind_host_name(value: &str) -> Option<String> {
+    let trimmed = value.trim();
+    let candidate = trimmed
+        .strip_prefix('[')
+        .and_then(|inner| inner.strip_suffix(']'))
+        .unwrap_or(trimmed);
+
+    if candidate.is_empty() || candidate.parse::<IpAddr>().is_ok() {
+        None
+    } else {
+        Some(candidate.to_owned())
+    }
+}
+
 fn parse_max_sessions(value: &OsString) -> Result<NonZeroUsize, DaemonError> {
     let text = value.to_string_lossy();
     let parsed: usize = text
@@ -36,6 +36,9 @@ fn serve_connections(
     let detach = options.detach();
     let listen_backlog = options.listen_backlog();
     let acceptor_threads = options.acceptor_threads();
+    let metrics_port = options.metrics_port();
+    let metrics_address = options.metrics_address();
+    let metrics_exposure = MetricsExposure::from_options(&options);
     let socket_options_str = options.socket_options().map(str::to_string);
     let tcp_fastopen_mode = options.tcp_fastopen();
     let RuntimeOptions {
@@ -54,6 +57,7 @@ fn serve_connections(
         address_family,
         dual_stack,
         bind_address_overridden,
+        bind_host,
         config_path,
         syslog_facility,
         syslog_tag,
@@ -145,7 +149,9 @@ fn serve_connections(
     // getaddrinfo result, binds one socket per family, and only returns
     // NULL when zero sockets bound.
     let env_family = read_address_family_env_override();
-    let bind_addresses: Vec<IpAddr> = if bind_address_overridden {
+    let bind_addresses: Vec<IpAddr> = if let Some(host) = bind_host.as_deref() {
+        resolve_bind_host(host, address_family, dual_stack)?
+    } else if bind_address_overridden {
         vec![bind_address]
     } else if let Some(env) = env_family {
         match env {
@@ -211,6 +217,22 @@ fn serve_connections(
         apply_socket_options_to_listener(&listener, &parsed_socket_options, log_sink.as_ref());
         bound_addresses = vec![local_addr];
         listeners = vec![listener];
+    } else if let Some(activated) = socket_activated_listeners(log_sink.as_ref())? {
+        // systemd socket activation: the `.socket` unit already bound and
+        // listens on the port, so the bind address, port, backlog and TFO
+        // settings belong to the unit. Socket options are applied post-hoc
+        // exactly as for an injected listener.
+        let mut addresses = Vec::with_capacity(activated.len());
+        for listener in &activated {
+            apply_socket_options_to_listener(listener, &parsed_socket_options, log_sink.as_ref());
+            addresses.push(
+                listener
+                    .local_addr()
+                    .unwrap_or_else(|_| SocketAddr::new(bind_addresses[0], port)),
+            );
+        }
+        bound_addresses = addresses;
+        listeners = activated;
     } else {
         let backlog = listen_backlog.map_or(DEFAULT_LISTEN_BACKLOG, |v| v as i32);
 
@@ -242,6 +264,22 @@ fn serve_connections(
         }
     }
 
+    // oc-rsync extension: the `metrics port` listener binds alongside the
+    // rsync listener - on `metrics address`, else the loopback address of the
+    // first bound family - while a privileged port is still bindable and
+    // before detaching so failures reach stderr. Its serving thread starts
+    // after detach because threads do not survive fork.
+    let metrics_listener = match metrics_port {
+        Some(metrics_port) => {
+            let addr = metrics_address.unwrap_or(match bind_addresses[0] {
+                IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::LOCALHOST),
+                IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::LOCALHOST),
+            });
+            Some(bind_metrics_listener(addr, metrics_port)?)
+        }
+        None => None,
+    };
+
     // LSM-CAP.2: CAP_NET_BIND_SERVICE is no longer needed once the listener
     // has bound. Drop it from effective, permitted, and bounding sets so a
     // compromised worker cannot rebind another privileged port. No-op on
@@ -282,6 +320,16 @@ fn serve_connections(
         None
     };
 
+    if let Some(listener) = metrics_listener {
+        if let Some(log) = log_sink.as_ref()
+            && let Ok(addr) = listener.local_addr()
+        {
+            let text = format!("metrics listener on {addr}");
+            log_message(log, &rsync_info!(text).with_role(Role::Daemon));
+        }
+        spawn_metrics_listener(listener, metrics_exposure, log_sink.clone());
+    }
+
     // Apply daemon-level chroot and drop daemon-level privileges after binding
     // (which may require root for ports < 1024), daemonizing, and writing the
     // PID file. Order matches upstream: chroot first (while still root), then
@@ -359,6 +407,7 @@ fn serve_connections(
         motd_lines,
         log_sink: &log_sink,
         notifier: &notifier,
+        watchdog: systemd::Watchdog::from_env(),
         client_socket_options,
         bandwidth_limit,
         bandwidth_burst,
@@ -396,3 +445,37 @@ fn serve_connections(
 
     result
 }
+
+/// Adopts the listeners passed by systemd socket activation, if any.
+///
+/// oc-rsync extension: upstream rsync only supports inetd-style activation
+/// (`Accept=yes`, one process per connection), which [`serve_inetd_session`]
+/// already covers. With `Accept=no` the unit hands over the listening
+/// sockets themselves through `LISTEN_FDS` and the daemon serves them for
+/// its whole lifetime instead of binding its own.
+fn socket_activated_listeners(
+    log: Option<&SharedLogSink>,
+) -> Result<Option<Vec<TcpListener>>, DaemonError> {
+    let listeners = fast_io::socket_activation::inherited_listeners().map_err(|error| {
+        DaemonError::new(
+            FEATURE_UNAVAILABLE_EXIT_CODE,
+            rsync_error!(
+                FEATURE_UNAVAILABLE_EXIT_CODE,
+                format!("failed to adopt socket-activated listeners: {error}")
+            )
+            .with_role(Role::Daemon),
+        )
+    })?;
+    if listeners.is_empty() {
+        return Ok(None);
+    }
+
+    if let Some(sink) = log {
+        let text = format!(
+            "using {} listener(s) passed by socket activation",
+            listeners.len()
+        );
+        log_message(sink, &rsync_info!(text).with_role(Role::Daemon));
+    }
+    Ok(Some(listeners))
+}
@@ -23,6 +23,8 @@ struct AcceptLoopState<'a> {
     motd_lines: Arc<Vec<String>>,
     log_sink: &'a Option<SharedLogSink>,
     notifier: &'a systemd::ServiceNotifier,
+    /// Keep-alive pacing for units with `WatchdogSec=`; `None` otherwise.
+    watchdog: Option<systemd::Watchdog>,
     client_socket_options: Arc<Vec<SocketOption>>,
     bandwidth_limit: Option<NonZeroU64>,
     bandwidth_burst: Option<NonZeroU64>,
@@ -39,6 +41,12 @@ fn check_signals_and_maintain(
 ) -> Result<Option<bool>, DaemonError> {
     reap_finished_workers(&mut state.workers)?;
 
+    if let Some(watchdog) = state.watchdog.as_mut()
+        && let Err(error) = watchdog.tick(state.notifier)
+    {
+        log_sd_notify_failure(state.log_sink.as_ref(), "watchdog keep-alive", &error);
+    }
+
     if state.signal_flags.shutdown.load(Ordering::Relaxed) {
         if let Some(log) = state.log_sink.as_ref() {
             let message =
@@ -75,6 +75,7 @@ impl ConnectionContext {
     ) -> io::Result<()> {
         let peer_addr = normalize_peer_address(raw_peer_addr);
         let log_for_worker = self.log_sink.clone();
+        let _active_session = daemon_metrics().session_started();
 
         let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
             handle_session(
@@ -48,6 +48,45 @@ fn read_address_family_env_override() -> Option<AddressFamilyOverride> {
         .and_then(parse_address_family_env)
 }
 
+/// Resolves a bind hostname to every listener address of the requested family.
+///
+/// Addresses are returned in resolver order with duplicates removed, so the
+/// listener binds one socket per address exactly as upstream walks the
+/// getaddrinfo result list. `family` is ignored when `dual_stack` requests
+/// both families.
+///
+/// upstream: socket.c:402-499 `open_socket_in()` - `getaddrinfo(bind_addr,
+/// portbuf, &hints, &all_ai)` with `hints.ai_family = af_hint`, then one
+/// `socket()`/`bind()` per result.
+fn resolve_bind_host(
+    host: &str,
+    family: Option<AddressFamily>,
+    dual_stack: bool,
+) -> Result<Vec<IpAddr>, DaemonError> {
+    let family = if dual_stack { None } else { family };
+    let resolved = lookup_host(host).map_err(|error| {
+        config_error(format!("failed to resolve bind address '{host}': {error}"))
+    })?;
+
+    let mut addresses: Vec<IpAddr> = Vec::new();
+    for addr in resolved {
+        if family.is_none_or(|family| family.matches(addr)) && !addresses.contains(&addr) {
+            addresses.push(addr);
+        }
+    }
+
+    if addresses.is_empty() {
+        let text = match family {
+            Some(AddressFamily::Ipv4) => format!("bind address '{host}' has no IPv4 addresses"),
+            Some(AddressFamily::Ipv6) => format!("bind address '{host}' has no IPv6 addresses"),
+            None => format!("bind address '{host}' did not resolve to any addresses"),
+        };
+        return Err(config_error(text));
+    }
+
+    Ok(addresses)
+}
+
 /// Logs a systemd notification failure if a log sink is available.
 fn log_sd_notify_failure(log: Option<&SharedLogSink>, context: &str, error: &io::Error) {
     if let Some(sink) = log {
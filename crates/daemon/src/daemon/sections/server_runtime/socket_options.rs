@@ -9,6 +9,10 @@ enum SocketOption {
     TcpNoDelay(bool),
     /// `SO_KEEPALIVE` - enable TCP keepalive probes.
     SoKeepAlive(bool),
+    /// `SO_REUSEADDR` - allow rebinding an address in `TIME_WAIT`.
+    ///
+    /// upstream: socket.c:socket_options[] `SO_REUSEADDR` (OPT_BOOL).
+    SoReuseAddr(bool),
     /// `SO_SNDBUF=<size>` - set the send buffer size.
     SoSndBuf(usize),
     /// `SO_RCVBUF=<size>` - set the receive buffer size.
@@ -59,6 +63,7 @@ impl SocketOption {
         match self {
             SocketOption::TcpNoDelay(_) => "TCP_NODELAY",
             SocketOption::SoKeepAlive(_) => "SO_KEEPALIVE",
+            SocketOption::SoReuseAddr(_) => "SO_REUSEADDR",
             SocketOption::SoSndBuf(_) => "SO_SNDBUF",
             SocketOption::SoRcvBuf(_) => "SO_RCVBUF",
             SocketOption::IpTos(_) => "IP_TOS",
@@ -75,10 +80,12 @@ impl SocketOption {
     }
 }
 
-/// Parses a comma-separated socket options string into typed option values.
+/// Parses a socket options string into typed option values.
 ///
-/// Accepts the upstream `rsyncd.conf` format: comma-separated option names with
-/// optional `=value` suffixes. Boolean options default to `true` when no value
+/// Accepts the upstream `rsyncd.conf` format: option names separated by commas,
+/// spaces, or tabs, each with an optional `=value` suffix. Tokenization is
+/// shared with the client `--sockopts` path via
+/// [`core::client::socket_option_tokens`]. Boolean options default to `true` when no value
 /// is given, and accept `0`/`1` or `true`/`false` as values.
 ///
 /// upstream: socket.c:set_socket_options() is `void`. An unknown option name
@@ -92,20 +99,9 @@ fn parse_socket_options(
 ) -> Result<Vec<SocketOption>, String> {
     let mut result = Vec::new();
 
-    for part in options.split(',') {
-        let trimmed = part.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
-
-        let (name, value) = if let Some(eq_pos) = trimmed.find('=') {
-            let n = trimmed[..eq_pos].trim();
-            let v = trimmed[eq_pos + 1..].trim();
-            (n, Some(v))
-        } else {
-            (trimmed, None)
-        };
-
+    for core::client::SocketOptionToken { name, value } in
+        core::client::socket_option_tokens(options)
+    {
         let upper = name.to_uppercase();
         let upper = upper.replace('-', "_");
         match upper.as_str() {
@@ -117,6 +113,10 @@ fn parse_socket_options(
                 let enabled = parse_bool_option_value(value, "SO_KEEPALIVE")?;
                 result.push(SocketOption::SoKeepAlive(enabled));
             }
+            "SO_REUSEADDR" => {
+                let enabled = parse_bool_option_value(value, "SO_REUSEADDR")?;
+                result.push(SocketOption::SoReuseAddr(enabled));
+            }
             "SO_SNDBUF" => {
                 let size = parse_size_option_value(value, "SO_SNDBUF")?;
                 result.push(SocketOption::SoSndBuf(size));
@@ -331,6 +331,7 @@ fn apply_socket_options_impl(
         let result: io::Result<()> = match opt {
             SocketOption::TcpNoDelay(enabled) => sock.set_tcp_nodelay(*enabled),
             SocketOption::SoKeepAlive(enabled) => sock.set_keepalive(*enabled),
+            SocketOption::SoReuseAddr(enabled) => sock.set_reuse_address(*enabled),
             SocketOption::SoBroadcast(enabled) => sock.set_broadcast(*enabled),
             SocketOption::SoSndBuf(size) => sock.set_send_buffer_size(*size),
             SocketOption::SoRcvBuf(size) => sock.set_recv_buffer_size(*size),
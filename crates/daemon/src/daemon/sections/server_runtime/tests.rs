@@ -658,6 +658,27 @@ fn parse_socket_options_whitespace_around_values() {
     assert_eq!(opts[1], SocketOption::SoSndBuf(4096));
 }
 
+#[test]
+fn parse_socket_options_whitespace_separated_list() {
+    // upstream: socket.c:set_socket_options() tokenizes with strtok(" \t,").
+    let opts = parse_socket_options("SO_KEEPALIVE TCP_NODELAY\tSO_SNDBUF=4194304", None)
+        .expect("parse succeeds");
+    assert_eq!(
+        opts,
+        vec![
+            SocketOption::SoKeepAlive(true),
+            SocketOption::TcpNoDelay(true),
+            SocketOption::SoSndBuf(4_194_304),
+        ]
+    );
+}
+
+#[test]
+fn parse_socket_options_so_reuseaddr() {
+    let opts = parse_socket_options("SO_REUSEADDR=0", None).expect("parse succeeds");
+    assert_eq!(opts, vec![SocketOption::SoReuseAddr(false)]);
+}
+
 #[test]
 fn apply_listener_socket_options_nodelay_keepalive() {
     let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
@@ -953,6 +974,7 @@ fn test_accept_loop_state<'a>(
         motd_lines: Arc::new(Vec::new()),
         log_sink,
         notifier,
+        watchdog: None,
         client_socket_options: Arc::new(Vec::new()),
         bandwidth_limit: None,
         bandwidth_burst: None,
@@ -2008,3 +2030,24 @@ fn kqueue_engine_delivers_queued_connection_without_sleep_floor() {
     engine.shutdown();
     drop(client);
 }
+
+#[test]
+fn resolve_bind_host_filters_by_family() {
+    let addresses = resolve_bind_host("localhost", Some(AddressFamily::Ipv4), false)
+        .expect("localhost resolves to IPv4");
+    assert!(!addresses.is_empty());
+    assert!(addresses.iter().all(IpAddr::is_ipv4));
+}
+
+#[test]
+fn resolve_bind_host_deduplicates_addresses() {
+    let addresses = resolve_bind_host("localhost", None, false).expect("localhost resolves");
+    for (index, addr) in addresses.iter().enumerate() {
+        assert!(!addresses[index + 1..].contains(addr));
+    }
+}
+
+#[test]
+fn resolve_bind_host_rejects_unresolvable_name() {
+    assert!(resolve_bind_host("nonexistent.invalid", None, false).is_err());
+}
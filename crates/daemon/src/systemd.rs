@@ -3,11 +3,17 @@
 //! Helpers for emitting `sd_notify` state transitions when the daemon is built
 //! with the optional `sd-notify` feature. The notifier is intentionally thin:
 //! it records whether `NOTIFY_SOCKET` was present at start-up and provides
-//! convenience methods for the `READY=1`, `STATUS=...`, and `STOPPING=1` messages
-//! used by the systemd service unit. When the feature is disabled the helpers
-//! compile down to no-ops so the rest of the daemon can call them unconditionally.
+//! convenience methods for the `READY=1`, `STATUS=...`, `WATCHDOG=1`, and
+//! `STOPPING=1` messages used by the systemd service unit. When the feature is
+//! disabled the helpers compile down to no-ops so the rest of the daemon can
+//! call them unconditionally.
+//!
+//! [`Watchdog`] paces the keep-alive pings a unit with `WatchdogSec=` expects;
+//! the accept loop ticks it between polls, so a wedged loop stops the pings
+//! and lets the service manager restart the daemon.
 
 use std::io;
+use std::time::{Duration, Instant};
 
 #[cfg(all(feature = "sd-notify", target_os = "linux"))]
 use sd_notify::NotifyState;
@@ -71,6 +77,19 @@ impl ServiceNotifier {
         }
     }
 
+    /// Tells the service manager the daemon is still alive.
+    pub(crate) fn watchdog(&self) -> io::Result<()> {
+        #[cfg(all(feature = "sd-notify", target_os = "linux"))]
+        {
+            self.send_states(&[NotifyState::Watchdog])
+        }
+
+        #[cfg(not(all(feature = "sd-notify", target_os = "linux")))]
+        {
+            Ok(())
+        }
+    }
+
     /// Indicates that the daemon is shutting down.
     pub(crate) fn stopping(&self) -> io::Result<()> {
         #[cfg(all(feature = "sd-notify", target_os = "linux"))]
@@ -94,10 +113,55 @@ impl ServiceNotifier {
     }
 }
 
+/// Paces `WATCHDOG=1` pings at half the interval the service manager asked
+/// for, as `sd_watchdog_enabled(3)` recommends.
+#[derive(Debug)]
+pub(crate) struct Watchdog {
+    period: Duration,
+    last_ping: Instant,
+}
+
+impl Watchdog {
+    /// Reads `WATCHDOG_USEC` and `WATCHDOG_PID`. Returns `None` when the unit
+    /// has no watchdog or the variables name another process.
+    pub(crate) fn from_env() -> Option<Self> {
+        let usec = std::env::var("WATCHDOG_USEC").ok();
+        let pid = std::env::var("WATCHDOG_PID").ok();
+        let interval = watchdog_interval(usec.as_deref(), pid.as_deref(), std::process::id())?;
+        Some(Self {
+            period: interval / 2,
+            last_ping: Instant::now(),
+        })
+    }
+
+    /// Pings through `notifier` once half the interval has passed since the
+    /// previous ping.
+    pub(crate) fn tick(&mut self, notifier: &ServiceNotifier) -> io::Result<()> {
+        if self.last_ping.elapsed() < self.period {
+            return Ok(());
+        }
+        self.last_ping = Instant::now();
+        notifier.watchdog()
+    }
+}
+
+/// The watchdog interval granted to the process `own_pid`, following the
+/// `sd_watchdog_enabled(3)` rules for `WATCHDOG_USEC` and `WATCHDOG_PID`.
+fn watchdog_interval(usec: Option<&str>, pid: Option<&str>, own_pid: u32) -> Option<Duration> {
+    let usec: u64 = usec?.trim().parse().ok().filter(|&usec| usec > 0)?;
+    if let Some(pid) = pid
+        && pid.trim().parse::<u32>().ok() != Some(own_pid)
+    {
+        return None;
+    }
+    Some(Duration::from_micros(usec))
+}
+
 #[cfg(test)]
 mod tests {
-    use super::ServiceNotifier;
+    use super::{ServiceNotifier, Watchdog, watchdog_interval};
     use crate::test_env::{ENV_LOCK, EnvGuard};
+    use std::time::{Duration, Instant};
 
     #[test]
     fn notifier_behaves_as_noop_without_notify_socket() {
@@ -191,6 +255,36 @@ mod tests {
         assert!(notifier.stopping().is_ok());
     }
 
+    #[test]
+    fn watchdog_interval_follows_sd_watchdog_enabled() {
+        let interval = Some(Duration::from_secs(30));
+        assert_eq!(watchdog_interval(Some("30000000"), None, 7), interval);
+        assert_eq!(watchdog_interval(Some("30000000"), Some("7"), 7), interval);
+        assert_eq!(watchdog_interval(Some("30000000"), Some("8"), 7), None);
+        assert_eq!(watchdog_interval(Some("0"), None, 7), None);
+        assert_eq!(watchdog_interval(Some("soon"), None, 7), None);
+        assert_eq!(watchdog_interval(None, Some("7"), 7), None);
+    }
+
+    #[test]
+    fn watchdog_pings_at_half_the_interval() {
+        let notifier = ServiceNotifier::default();
+        let mut watchdog = Watchdog {
+            period: Duration::from_secs(15),
+            last_ping: Instant::now(),
+        };
+        let started = watchdog.last_ping;
+        assert!(watchdog.tick(&notifier).is_ok());
+        assert_eq!(watchdog.last_ping, started);
+
+        let Some(overdue) = started.checked_sub(Duration::from_secs(16)) else {
+            return;
+        };
+        watchdog.last_ping = overdue;
+        assert!(watchdog.tick(&notifier).is_ok());
+        assert!(watchdog.last_ping >= started);
+    }
+
     #[test]
     fn notifier_full_lifecycle() {
         let _env_lock = ENV_LOCK.lock().expect("lock environment guard");
@@ -200,6 +294,7 @@ mod tests {
         assert!(notifier.ready(Some("Listening on port 873")).is_ok());
         assert!(notifier.status("Active connections: 0").is_ok());
         assert!(notifier.status("Active connections: 5").is_ok());
+        assert!(notifier.watchdog().is_ok());
         assert!(notifier.status("Shutting down...").is_ok());
         assert!(notifier.stopping().is_ok());
     }
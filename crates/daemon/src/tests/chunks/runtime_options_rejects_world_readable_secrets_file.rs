@@ -101,3 +101,66 @@ fn runtime_options_rejects_other_writable_secrets_file() {
     );
 }
 
+
+/// `strict modes = no` disables the other-access check, even when the
+/// directive follows `secrets file` in the module section, matching
+/// upstream authenticate.c check_secret() gating on `lp_strict_modes()`.
+#[cfg(unix)]
+#[test]
+fn runtime_options_strict_modes_no_accepts_world_readable_secrets_file() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempdir().expect("config dir");
+    let module_dir = dir.path().join("module");
+    fs::create_dir_all(&module_dir).expect("module dir");
+    let secrets_path = dir.path().join("secrets.txt");
+    fs::write(&secrets_path, "alice:password\n").expect("write secrets");
+    fs::set_permissions(&secrets_path, PermissionsExt::from_mode(0o644)).expect("chmod secrets");
+
+    let mut file = NamedTempFile::new().expect("config file");
+    writeln!(
+        file,
+        "[secure]\npath = {}\nauth users = alice:ro\nsecrets file = {}\nstrict modes = no\n",
+        module_dir.display(),
+        secrets_path.display()
+    )
+    .expect("write config");
+
+    let options = RuntimeOptions::parse(&[
+        OsString::from("--config"),
+        file.path().as_os_str().to_os_string(),
+    ])
+    .expect("strict modes = no should accept world-readable secrets file");
+
+    assert!(!options.modules()[0].strict_modes());
+}
+
+/// A global `strict modes = no` is inherited by modules that use the global
+/// secrets file.
+#[cfg(unix)]
+#[test]
+fn runtime_options_global_strict_modes_no_accepts_world_readable_secrets_file() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempdir().expect("config dir");
+    let module_dir = dir.path().join("module");
+    fs::create_dir_all(&module_dir).expect("module dir");
+    let secrets_path = dir.path().join("secrets.txt");
+    fs::write(&secrets_path, "alice:password\n").expect("write secrets");
+    fs::set_permissions(&secrets_path, PermissionsExt::from_mode(0o604)).expect("chmod secrets");
+
+    let mut file = NamedTempFile::new().expect("config file");
+    writeln!(
+        file,
+        "strict modes = no\nsecrets file = {}\n[secure]\npath = {}\nauth users = alice\n",
+        secrets_path.display(),
+        module_dir.display()
+    )
+    .expect("write config");
+
+    RuntimeOptions::parse(&[
+        OsString::from("--config"),
+        file.path().as_os_str().to_os_string(),
+    ])
+    .expect("global strict modes = no should accept world-readable secrets file");
+}
@@ -13,6 +13,12 @@ use std::ffi::OsString;
 use std::fmt;
 use std::io::Write;
 
+pub mod session;
+
+pub use session::{
+    ClientProgressUpdate, CompressionSetting, FilterRuleSpec, SyncError, SyncOptions, SyncSession,
+};
+
 /// Captured output produced by an embedded entry point.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CommandOutput {
@@ -268,6 +274,26 @@ pub use core::server::{
 /// streams instead of constructing CLI arguments.
 pub use core::server::run_server_stdio;
 
+/// Re-export the in-process loopback runner.
+///
+/// Runs a client and a server configuration against each other over
+/// in-memory pipes, exercising the full wire protocol without sockets.
+pub use core::server::{LoopbackStats, run_loopback};
+
+/// Re-export the async server entry point for embedders running tokio.
+///
+/// Hosts the server over `AsyncRead`/`AsyncWrite` streams on the caller's
+/// runtime; the transfer itself runs on the runtime's blocking pool and holds
+/// one of its threads for the whole session.
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub use core::server::run_server_async;
+
+/// Re-export the daemon accept loop that runs on the caller's tokio runtime.
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub use daemon::serve_async_daemon;
+
 /// Executes the server with a pre-built configuration and stdio streams.
 ///
 /// Convenience wrapper around `run_server_stdio` for embedders that want to
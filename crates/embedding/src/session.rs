@@ -0,0 +1,601 @@
+//! Typed transfer sessions.
+//!
+//! [`SyncSession`] runs a client transfer from typed settings instead of an
+//! argument vector: the caller names the source and destination endpoints,
+//! describes the transfer with [`SyncOptions`], optionally attaches a
+//! progress callback, and receives the outcome as [`TransferStats`].
+//! Endpoints use the same operand syntax as the command line, so local
+//! paths, `host:path` shell operands, and `rsync://` daemon URLs all work,
+//! including the trailing-slash rule for source directories.
+//!
+//! The session drives [`core::client::run_client_with_observer`] directly;
+//! no argv is formatted or parsed along the way. Note that this module's
+//! [`TransferStats`] summarises a whole client session and is distinct from
+//! the receiver statistics re-exported at the crate root for server mode.
+
+use std::error::Error;
+use std::ffi::OsString;
+use std::fmt;
+use std::time::Duration;
+
+use core::client::{
+    ClientConfig, ClientConfigBuilder, ClientError, ClientProgressObserver, ClientSummary,
+    ConfigConflict,
+};
+
+pub use core::client::{ClientProgressUpdate, CompressionSetting, FilterRuleSpec};
+
+/// Settings describing how a [`SyncSession`] transfers its endpoints.
+///
+/// Every option defaults to off, matching a bare `rsync SRC DEST`; use
+/// [`SyncOptions::archive`] for the usual `-a` set.
+#[derive(Clone, Debug, Default)]
+pub struct SyncOptions {
+    recursive: bool,
+    links: bool,
+    permissions: bool,
+    times: bool,
+    owner: bool,
+    group: bool,
+    devices: bool,
+    specials: bool,
+    hard_links: bool,
+    checksum: bool,
+    delete: bool,
+    delete_excluded: bool,
+    dry_run: bool,
+    whole_file: Option<bool>,
+    compression: CompressionSetting,
+    filters: Vec<FilterRuleSpec>,
+}
+
+impl SyncOptions {
+    /// Returns options with every setting off.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the archive option set (`-a`, i.e. `-rlptgoD`).
+    #[must_use]
+    pub fn archive() -> Self {
+        Self::default()
+            .recursive(true)
+            .links(true)
+            .permissions(true)
+            .times(true)
+            .group(true)
+            .owner(true)
+            .devices(true)
+            .specials(true)
+    }
+
+    /// Recurses into directories (`--recursive`).
+    #[must_use]
+    pub const fn recursive(mut self, enabled: bool) -> Self {
+        self.recursive = enabled;
+        self
+    }
+
+    /// Recreates symlinks as symlinks (`--links`).
+    #[must_use]
+    pub const fn links(mut self, enabled: bool) -> Self {
+        self.links = enabled;
+        self
+    }
+
+    /// Preserves permissions (`--perms`).
+    #[must_use]
+    pub const fn permissions(mut self, enabled: bool) -> Self {
+        self.permissions = enabled;
+        self
+    }
+
+    /// Preserves modification times (`--times`).
+    #[must_use]
+    pub const fn times(mut self, enabled: bool) -> Self {
+        self.times = enabled;
+        self
+    }
+
+    /// Preserves file ownership (`--owner`).
+    #[must_use]
+    pub const fn owner(mut self, enabled: bool) -> Self {
+        self.owner = enabled;
+        self
+    }
+
+    /// Preserves group ownership (`--group`).
+    #[must_use]
+    pub const fn group(mut self, enabled: bool) -> Self {
+        self.group = enabled;
+        self
+    }
+
+    /// Recreates device files (`--devices`).
+    #[must_use]
+    pub const fn devices(mut self, enabled: bool) -> Self {
+        self.devices = enabled;
+        self
+    }
+
+    /// Recreates FIFOs and sockets (`--specials`).
+    #[must_use]
+    pub const fn specials(mut self, enabled: bool) -> Self {
+        self.specials = enabled;
+        self
+    }
+
+    /// Preserves hard links (`--hard-links`).
+    #[must_use]
+    pub const fn hard_links(mut self, enabled: bool) -> Self {
+        self.hard_links = enabled;
+        self
+    }
+
+    /// Compares files by checksum rather than size and time (`--checksum`).
+    #[must_use]
+    pub const fn checksum(mut self, enabled: bool) -> Self {
+        self.checksum = enabled;
+        self
+    }
+
+    /// Deletes destination entries missing from the source (`--delete`).
+    #[must_use]
+    pub const fn delete(mut self, enabled: bool) -> Self {
+        self.delete = enabled;
+        self
+    }
+
+    /// Also deletes excluded destination entries (`--delete-excluded`).
+    #[must_use]
+    pub const fn delete_excluded(mut self, enabled: bool) -> Self {
+        self.delete_excluded = enabled;
+        self
+    }
+
+    /// Reports what would change without writing anything (`--dry-run`).
+    #[must_use]
+    pub const fn dry_run(mut self, enabled: bool) -> Self {
+        self.dry_run = enabled;
+        self
+    }
+
+    /// Forces (`Some(true)`) or forbids (`Some(false)`) whole-file transfers;
+    /// `None` keeps the default choice (`--whole-file` / `--no-whole-file`).
+    #[must_use]
+    pub const fn whole_file(mut self, whole_file: Option<bool>) -> Self {
+        self.whole_file = whole_file;
+        self
+    }
+
+    /// Sets the compression used on the wire (`--compress`,
+    /// `--compress-level`).
+    #[must_use]
+    pub const fn compression(mut self, setting: CompressionSetting) -> Self {
+        self.compression = setting;
+        self
+    }
+
+    /// Appends a filter rule; rules apply in the order added (`--filter`).
+    #[must_use]
+    pub fn filter(mut self, rule: FilterRuleSpec) -> Self {
+        self.filters.push(rule);
+        self
+    }
+
+    /// Appends several filter rules in order.
+    #[must_use]
+    pub fn filters<I>(mut self, rules: I) -> Self
+    where
+        I: IntoIterator<Item = FilterRuleSpec>,
+    {
+        self.filters.extend(rules);
+        self
+    }
+
+    fn apply(self, builder: ClientConfigBuilder) -> ClientConfigBuilder {
+        builder
+            .recursive(self.recursive)
+            .links(self.links)
+            .permissions(self.permissions)
+            .times(self.times)
+            .owner(self.owner)
+            .group(self.group)
+            .devices(self.devices)
+            .specials(self.specials)
+            .hard_links(self.hard_links)
+            .checksum(self.checksum)
+            .delete(self.delete)
+            .delete_excluded(self.delete_excluded)
+            .dry_run(self.dry_run)
+            .whole_file_option(self.whole_file)
+            .compression_setting(self.compression)
+            .extend_filter_rules(self.filters)
+    }
+}
+
+/// A client transfer described by typed endpoints and options.
+///
+/// # Examples
+///
+/// ```no_run
+/// use embedding::{FilterRuleSpec, SyncOptions, SyncSession};
+///
+/// let options = SyncOptions::archive()
+///     .delete(true)
+///     .filter(FilterRuleSpec::exclude("*.tmp"));
+///
+/// let stats = SyncSession::new("backup/")
+///     .source("projects/")
+///     .options(options)
+///     .on_progress(|update| println!("{}/{}", update.index(), update.total()))
+///     .run()
+///     .expect("transfer succeeds");
+///
+/// println!("{} files, {} bytes", stats.files_transferred(), stats.literal_bytes());
+/// ```
+pub struct SyncSession<'a> {
+    sources: Vec<OsString>,
+    destination: OsString,
+    options: SyncOptions,
+    progress: Option<Box<dyn ClientProgressObserver + 'a>>,
+}
+
+impl<'a> SyncSession<'a> {
+    /// Starts a session that transfers into `destination`.
+    pub fn new(destination: impl Into<OsString>) -> Self {
+        Self {
+            sources: Vec::new(),
+            destination: destination.into(),
+            options: SyncOptions::default(),
+            progress: None,
+        }
+    }
+
+    /// Adds a source endpoint.
+    #[must_use]
+    pub fn source(mut self, source: impl Into<OsString>) -> Self {
+        self.sources.push(source.into());
+        self
+    }
+
+    /// Adds several source endpoints in order.
+    #[must_use]
+    pub fn sources<I, S>(mut self, sources: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<OsString>,
+    {
+        self.sources.extend(sources.into_iter().map(Into::into));
+        self
+    }
+
+    /// Replaces the transfer options.
+    #[must_use]
+    pub fn options(mut self, options: SyncOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Registers a callback invoked for each progress update.
+    #[must_use]
+    pub fn on_progress<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(&ClientProgressUpdate) + 'a,
+    {
+        self.progress = Some(Box::new(callback));
+        self
+    }
+
+    /// Runs the transfer and returns its statistics.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SyncError::NoSources`] when no source was added,
+    /// [`SyncError::Conflict`] when the options combine mutually exclusive
+    /// settings, and [`SyncError::Transfer`] when the transfer itself fails.
+    pub fn run(self) -> Result<TransferStats, SyncError> {
+        let config = self.config()?;
+        let mut progress = self.progress;
+        let observer = progress
+            .as_deref_mut()
+            .map(|observer| observer as &mut dyn ClientProgressObserver);
+        let summary = core::client::run_client_with_observer(config, observer)
+            .map_err(SyncError::Transfer)?;
+        Ok(TransferStats::from_summary(&summary))
+    }
+
+    fn config(&self) -> Result<ClientConfig, SyncError> {
+        if self.sources.is_empty() {
+            return Err(SyncError::NoSources);
+        }
+        let operands = self
+            .sources
+            .iter()
+            .chain(std::iter::once(&self.destination));
+        let builder = self
+            .options
+            .clone()
+            .apply(ClientConfig::builder().transfer_args(operands.cloned()))
+            .progress(self.progress.is_some());
+        builder.validate().map_err(SyncError::Conflict)?;
+        Ok(builder.build())
+    }
+}
+
+impl fmt::Debug for SyncSession<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SyncSession")
+            .field("sources", &self.sources)
+            .field("destination", &self.destination)
+            .field("options", &self.options)
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
+/// Statistics reported by a completed [`SyncSession`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TransferStats {
+    files_transferred: u64,
+    regular_files_total: u64,
+    directories_created: u64,
+    symlinks_copied: u64,
+    items_deleted: u64,
+    literal_bytes: u64,
+    matched_bytes: u64,
+    bytes_sent: u64,
+    bytes_received: u64,
+    total_source_bytes: u64,
+    transferred_file_size: u64,
+    elapsed: Duration,
+    io_error_exit_code: Option<i32>,
+}
+
+impl TransferStats {
+    fn from_summary(summary: &ClientSummary) -> Self {
+        Self {
+            files_transferred: summary.files_copied(),
+            regular_files_total: summary.regular_files_total(),
+            directories_created: summary.directories_created(),
+            symlinks_copied: summary.symlinks_copied(),
+            items_deleted: summary.items_deleted(),
+            literal_bytes: summary.bytes_copied(),
+            matched_bytes: summary.matched_bytes(),
+            bytes_sent: summary.bytes_sent(),
+            bytes_received: summary.bytes_received(),
+            total_source_bytes: summary.total_source_bytes(),
+            transferred_file_size: summary.transferred_file_size(),
+            elapsed: summary.total_elapsed(),
+            io_error_exit_code: summary.io_error_exit_code(),
+        }
+    }
+
+    /// Returns the number of regular files whose contents were transferred.
+    #[must_use]
+    pub const fn files_transferred(&self) -> u64 {
+        self.files_transferred
+    }
+
+    /// Returns the number of regular files the source side listed.
+    #[must_use]
+    pub const fn regular_files_total(&self) -> u64 {
+        self.regular_files_total
+    }
+
+    /// Returns the number of directories created at the destination.
+    #[must_use]
+    pub const fn directories_created(&self) -> u64 {
+        self.directories_created
+    }
+
+    /// Returns the number of symlinks created at the destination.
+    #[must_use]
+    pub const fn symlinks_copied(&self) -> u64 {
+        self.symlinks_copied
+    }
+
+    /// Returns the number of destination entries deleted.
+    #[must_use]
+    pub const fn items_deleted(&self) -> u64 {
+        self.items_deleted
+    }
+
+    /// Returns the bytes sent as literal data (`Literal data` in `--stats`).
+    #[must_use]
+    pub const fn literal_bytes(&self) -> u64 {
+        self.literal_bytes
+    }
+
+    /// Returns the bytes reused from basis files (`Matched data` in `--stats`).
+    #[must_use]
+    pub const fn matched_bytes(&self) -> u64 {
+        self.matched_bytes
+    }
+
+    /// Returns the bytes written to the peer.
+    #[must_use]
+    pub const fn bytes_sent(&self) -> u64 {
+        self.bytes_sent
+    }
+
+    /// Returns the bytes read from the peer.
+    #[must_use]
+    pub const fn bytes_received(&self) -> u64 {
+        self.bytes_received
+    }
+
+    /// Returns the summed size of every source file (`Total file size`).
+    #[must_use]
+    pub const fn total_source_bytes(&self) -> u64 {
+        self.total_source_bytes
+    }
+
+    /// Returns the summed size of the transferred files
+    /// (`Total transferred file size`).
+    #[must_use]
+    pub const fn transferred_file_size(&self) -> u64 {
+        self.transferred_file_size
+    }
+
+    /// Returns the time the transfer took.
+    #[must_use]
+    pub const fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the exit code the CLI would report for a transfer that
+    /// completed with errors (23 or 24), or `None` after a clean run.
+    #[must_use]
+    pub const fn io_error_exit_code(&self) -> Option<i32> {
+        self.io_error_exit_code
+    }
+}
+
+/// Error returned by [`SyncSession::run`].
+#[derive(Debug)]
+pub enum SyncError {
+    /// The session names no source endpoint.
+    NoSources,
+    /// The options combine mutually exclusive settings.
+    Conflict(ConfigConflict),
+    /// The transfer failed.
+    Transfer(ClientError),
+}
+
+impl SyncError {
+    /// Returns the exit code the CLI would report for this failure.
+    #[must_use]
+    pub const fn exit_code(&self) -> i32 {
+        match self {
+            Self::NoSources | Self::Conflict(_) => 1,
+            Self::Transfer(error) => error.exit_code(),
+        }
+    }
+}
+
+impl fmt::Display for SyncError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSources => f.write_str("transfer session has no source endpoint"),
+            Self::Conflict(conflict) => fmt::Display::fmt(conflict, f),
+            Self::Transfer(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl Error for SyncError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::NoSources => None,
+            Self::Conflict(conflict) => Some(conflict),
+            Self::Transfer(error) => Some(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn copies_a_tree_and_reports_stats() {
+        let temp = tempfile::tempdir().unwrap();
+        let src = temp.path().join("src");
+        let dst = temp.path().join("dst");
+        fs::create_dir_all(src.join("sub")).unwrap();
+        fs::write(src.join("a.txt"), b"alpha").unwrap();
+        fs::write(src.join("sub/b.txt"), b"beta").unwrap();
+
+        let stats = SyncSession::new(&dst)
+            .source(format!("{}/", src.display()))
+            .options(SyncOptions::archive())
+            .run()
+            .expect("local transfer succeeds");
+
+        assert_eq!(fs::read(dst.join("a.txt")).unwrap(), b"alpha");
+        assert_eq!(fs::read(dst.join("sub/b.txt")).unwrap(), b"beta");
+        assert_eq!(stats.files_transferred(), 2);
+        assert_eq!(stats.total_source_bytes(), 9);
+        assert_eq!(stats.io_error_exit_code(), None);
+    }
+
+    #[test]
+    fn filters_and_delete_apply() {
+        let temp = tempfile::tempdir().unwrap();
+        let src = temp.path().join("src");
+        let dst = temp.path().join("dst");
+        fs::create_dir_all(&src).unwrap();
+        fs::create_dir_all(&dst).unwrap();
+        fs::write(src.join("keep.txt"), b"keep").unwrap();
+        fs::write(src.join("skip.tmp"), b"skip").unwrap();
+        fs::write(dst.join("stale.txt"), b"stale").unwrap();
+
+        let options = SyncOptions::archive()
+            .delete(true)
+            .filter(FilterRuleSpec::exclude("*.tmp"));
+        let stats = SyncSession::new(&dst)
+            .source(format!("{}/", src.display()))
+            .options(options)
+            .run()
+            .expect("local transfer succeeds");
+
+        assert!(dst.join("keep.txt").exists());
+        assert!(!dst.join("skip.tmp").exists());
+        assert!(!dst.join("stale.txt").exists());
+        assert_eq!(stats.items_deleted(), 1);
+    }
+
+    #[test]
+    fn dry_run_writes_nothing() {
+        let temp = tempfile::tempdir().unwrap();
+        let src = temp.path().join("a.txt");
+        let dst = temp.path().join("b.txt");
+        fs::write(&src, b"data").unwrap();
+
+        SyncSession::new(&dst)
+            .source(&src)
+            .options(SyncOptions::new().dry_run(true))
+            .run()
+            .expect("dry run succeeds");
+
+        assert!(!dst.exists());
+    }
+
+    #[test]
+    fn progress_callback_sees_each_file() {
+        let temp = tempfile::tempdir().unwrap();
+        let src = temp.path().join("a.txt");
+        fs::write(&src, b"data").unwrap();
+
+        let mut updates = 0;
+        SyncSession::new(temp.path().join("b.txt"))
+            .source(&src)
+            .on_progress(|_| updates += 1)
+            .run()
+            .expect("local transfer succeeds");
+
+        assert!(updates > 0);
+    }
+
+    #[test]
+    fn missing_source_is_rejected() {
+        let error = SyncSession::new("dst").run().unwrap_err();
+        assert!(matches!(error, SyncError::NoSources));
+        assert_eq!(error.exit_code(), 1);
+    }
+
+    #[test]
+    fn transfer_failure_carries_exit_code() {
+        let temp = tempfile::tempdir().unwrap();
+        let error = SyncSession::new(temp.path().join("dst"))
+            .source(temp.path().join("absent"))
+            .run()
+            .unwrap_err();
+
+        assert!(matches!(error, SyncError::Transfer(_)));
+        assert_ne!(error.exit_code(), 0);
+    }
+}
@@ -22,7 +22,8 @@
 /// - [`generate_delta`] is the high-level convenience wrapper around
 ///   [`DeltaGenerator`].
 pub use matching::{
-    DeltaGenerator, DeltaScript, DeltaSignatureIndex, DeltaToken, apply_delta, generate_delta,
+    BestBasisDelta, DeltaGenerator, DeltaScript, DeltaSignatureIndex, DeltaToken,
+    MMAP_INPUT_THRESHOLD, apply_delta, generate_delta,
 };
 
 /// Signature-layout primitives mirroring upstream `generator.c:sum_sizes_sqroot()`.
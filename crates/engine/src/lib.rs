@@ -61,6 +61,20 @@
 //! batch file with [`BatchWriter`], replay it later with [`BatchReader`] /
 //! `batch::replay::replay`.
 //!
+//! ## Tree verification
+//!
+//! [`verify::verify_trees`] compares two directory trees read-only, hashing
+//! equal-size files in parallel SIMD MD5 batches, and reports each difference
+//! as an itemize line - an oc-rsync extension for backup validation.
+//!
+//! ## Virtual filesystems
+//!
+//! The [`vfs::Vfs`] trait abstracts the storage a sync reads from and writes
+//! to; [`vfs::sync_trees`] mirrors a tree between any two backends, such as
+//! [`vfs::StdFs`] and the in-memory [`vfs::MemFs`], or into a tar stream
+//! through [`vfs::TarFs`]. The `s3` feature adds an object-storage
+//! destination under `vfs::s3`.
+//!
 //! ## Async I/O (optional)
 //!
 //! When compiled with the `async` feature, [`AsyncFileCopier`] and
@@ -134,14 +148,16 @@ pub mod delta;
 pub mod error;
 pub mod local_copy;
 pub mod util;
+pub mod verify;
+pub mod vfs;
 pub mod walk;
 
 #[doc(hidden)]
 pub mod batch {
     //! Re-exports from the [`batch`] crate for backward compatibility.
     pub use batch::{
-        BatchConfig, BatchError, BatchFlags, BatchHeader, BatchMode, BatchReader, BatchResult,
-        BatchStats, BatchWriter, DeltaOp, FileEntry, ReplayResult,
+        BatchCompression, BatchConfig, BatchError, BatchFlags, BatchHeader, BatchMode, BatchReader,
+        BatchResult, BatchStats, BatchWriter, DeltaOp, FileEntry, ReplayResult,
     };
 
     /// Batch replay functions for applying recorded delta operations.
@@ -218,12 +234,15 @@ pub use local_copy::{
 
 /// Local filesystem copy operations.
 pub use local_copy::{
-    BuilderError, DeleteTiming, HardlinkApplyResult, HardlinkApplyTracker, LocalCopyArgumentError,
-    LocalCopyError, LocalCopyErrorKind, LocalCopyOptions, LocalCopyOptionsBuilder, LocalCopyPlan,
-    LocalCopySummary, ReferenceDirectory, ReferenceDirectoryKind, SkipCompressList,
-    SkipCompressParseError, SparseDetectStrategy, SparseDetector, SparseReader, SparseRegion,
-    compute_backup_path, trace_make_backup_copy, trace_make_backup_device, trace_make_backup_hlink,
-    trace_make_backup_rename, trace_make_backup_symlink,
+    BuilderError, CaseCollision, CaseCollisionDetector, CaseCollisionPolicy, DeleteTiming,
+    HardlinkApplyResult, HardlinkApplyTracker, LocalCopyArgumentError, LocalCopyError,
+    LocalCopyErrorKind, LocalCopyOptions, LocalCopyOptionsBuilder, LocalCopyPlan, LocalCopySummary,
+    ReferenceDirectory, ReferenceDirectoryKind, SkipCompressList, SkipCompressParseError,
+    SparseDetectStrategy, SparseDetector, SparseReader, SparseRegion, TransferOrder,
+    WindowsNameAction, WindowsNameSanitizer, WindowsNamesPolicy, compute_backup_path,
+    invalid_windows_name_error, is_valid_windows_name, sanitize_windows_name,
+    sanitize_windows_path, trace_make_backup_copy, trace_make_backup_device,
+    trace_make_backup_hlink, trace_make_backup_rename, trace_make_backup_symlink,
 };
 
 /// File signature generation for delta transfers.
@@ -0,0 +1,271 @@
+//! Case-collision detection for case-insensitive destinations.
+//!
+//! NTFS, default APFS/HFS+, vfat, and exFAT fold case when resolving names,
+//! so two source entries that differ only in case (`Makefile` and
+//! `makefile`) land on the same destination inode: the second write
+//! truncates the first and nothing reports it. Upstream rsync behaves the
+//! same way under Cygwin. `--case-collision` is an oc-rsync extension that
+//! folds every transferred relative path and reacts when two distinct names
+//! fold to the same key:
+//!
+//! - [`CaseCollisionPolicy::Warn`] (also selected by `--ignore-case`) keeps
+//!   upstream's outcome but names both entries in a warning.
+//! - [`CaseCollisionPolicy::Rename`] writes the later non-directory entry
+//!   under a disambiguated name (`makefile~1`) so both survive.
+//!
+//! Colliding directories are always merged with a warning rather than
+//! renamed: their children still fold onto the same destination directory,
+//! and any child that then collides is handled individually.
+//!
+//! [`CaseCollisionDetector`] is shared by the local-copy executor and the
+//! remote receiver so both report identical diagnostics.
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// How entries that collide after case folding are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CaseCollisionPolicy {
+    /// No detection; the destination filesystem decides (upstream behaviour).
+    #[default]
+    Off,
+    /// Transfer as usual but warn about each colliding pair.
+    Warn,
+    /// Write the later colliding file under a disambiguated name.
+    Rename,
+}
+
+impl CaseCollisionPolicy {
+    /// Returns the canonical lowercase token used on the command line.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Warn => "warn",
+            Self::Rename => "rename",
+        }
+    }
+
+    /// Parses a CLI token into a [`CaseCollisionPolicy`].
+    ///
+    /// The match is case-insensitive. Unknown tokens are returned as the
+    /// original input for the caller to surface in an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns the original input string when no variant matches.
+    pub fn parse(value: &str) -> Result<Self, &str> {
+        let lowered = value.trim().to_ascii_lowercase();
+        match lowered.as_str() {
+            "off" => Ok(Self::Off),
+            "warn" => Ok(Self::Warn),
+            "rename" => Ok(Self::Rename),
+            _ => Err(value),
+        }
+    }
+
+    /// Returns `true` unless the policy is [`CaseCollisionPolicy::Off`].
+    #[must_use]
+    pub const fn is_enabled(self) -> bool {
+        !matches!(self, Self::Off)
+    }
+}
+
+impl fmt::Display for CaseCollisionPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A relative path that folds onto a name already seen in the transfer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaseCollision {
+    /// The earlier entry the path collides with.
+    pub existing: PathBuf,
+    /// The file name to write the entry under, when the policy renames it.
+    pub renamed: Option<OsString>,
+}
+
+impl CaseCollision {
+    /// Returns the case-conflict warning for `path`.
+    ///
+    /// The remote receiver sends this text as `MSG_WARNING`; the local copy
+    /// prints it through [`report`](Self::report).
+    #[must_use]
+    pub fn message(&self, path: &Path) -> String {
+        match &self.renamed {
+            Some(renamed) => format!(
+                "WARNING: case-conflict: \"{}\" collides with \"{}\"; writing it as \"{}\"",
+                path.display(),
+                self.existing.display(),
+                Path::new(renamed).display()
+            ),
+            None => format!(
+                "WARNING: case-conflict: \"{}\" collides with \"{}\" \
+                 on a case-insensitive destination",
+                path.display(),
+                self.existing.display()
+            ),
+        }
+    }
+
+    /// Prints the case-conflict warning for `path` to stderr.
+    pub fn report(&self, path: &Path) {
+        eprintln!("{}", self.message(path));
+    }
+}
+
+/// Tracks case-folded relative paths across a transfer.
+#[derive(Debug, Default)]
+pub struct CaseCollisionDetector {
+    policy: CaseCollisionPolicy,
+    seen: HashMap<String, PathBuf>,
+}
+
+impl CaseCollisionDetector {
+    /// Creates a detector applying `policy`.
+    #[must_use]
+    pub fn new(policy: CaseCollisionPolicy) -> Self {
+        Self {
+            policy,
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Returns the policy the detector applies.
+    #[must_use]
+    pub const fn policy(&self) -> CaseCollisionPolicy {
+        self.policy
+    }
+
+    /// Records `path` (relative to the transfer root) and reports whether it
+    /// collides with an earlier, differently-cased entry.
+    ///
+    /// Under [`CaseCollisionPolicy::Rename`] a colliding non-directory gets a
+    /// `~N` suffix before its extension, choosing the first `N` whose folded
+    /// path is still unused; the renamed path is recorded so later entries
+    /// cannot collide with it either.
+    pub fn record(&mut self, path: &Path, is_dir: bool) -> Option<CaseCollision> {
+        if !self.policy.is_enabled() {
+            return None;
+        }
+        let key = fold(path);
+        let existing = match self.seen.get(&key) {
+            None => {
+                self.seen.insert(key, path.to_path_buf());
+                return None;
+            }
+            Some(existing) if existing == path => return None,
+            Some(existing) => existing.clone(),
+        };
+
+        let renamed = if self.policy == CaseCollisionPolicy::Rename && !is_dir {
+            let mut n = 1;
+            let (name, candidate) = loop {
+                let (name, candidate) = disambiguated(path, n);
+                if !self.seen.contains_key(&fold(&candidate)) {
+                    break (name, candidate);
+                }
+                n += 1;
+            };
+            self.seen.insert(fold(&candidate), candidate);
+            Some(name)
+        } else {
+            None
+        };
+        Some(CaseCollision { existing, renamed })
+    }
+}
+
+fn fold(path: &Path) -> String {
+    path.to_string_lossy().to_lowercase()
+}
+
+/// Returns the `~n`-suffixed file name for `path` and the full renamed path.
+pub(super) fn disambiguated(path: &Path, n: usize) -> (OsString, PathBuf) {
+    let stem = path.file_stem().unwrap_or(path.as_os_str());
+    let mut name = stem.to_os_string();
+    name.push(format!("~{n}"));
+    if let Some(extension) = path.extension() {
+        name.push(".");
+        name.push(extension);
+    }
+    let renamed = path.with_file_name(&name);
+    (name, renamed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_each_policy_case_insensitively() {
+        assert_eq!(
+            CaseCollisionPolicy::parse("off"),
+            Ok(CaseCollisionPolicy::Off)
+        );
+        assert_eq!(
+            CaseCollisionPolicy::parse("WARN"),
+            Ok(CaseCollisionPolicy::Warn)
+        );
+        assert_eq!(
+            CaseCollisionPolicy::parse(" rename "),
+            Ok(CaseCollisionPolicy::Rename)
+        );
+        assert_eq!(CaseCollisionPolicy::parse("skip"), Err("skip"));
+    }
+
+    #[test]
+    fn off_policy_never_reports() {
+        let mut detector = CaseCollisionDetector::new(CaseCollisionPolicy::Off);
+        assert!(detector.record(Path::new("Makefile"), false).is_none());
+        assert!(detector.record(Path::new("makefile"), false).is_none());
+    }
+
+    #[test]
+    fn warn_policy_reports_without_renaming() {
+        let mut detector = CaseCollisionDetector::new(CaseCollisionPolicy::Warn);
+        assert!(detector.record(Path::new("Makefile"), false).is_none());
+        let collision = detector.record(Path::new("makefile"), false).unwrap();
+        assert_eq!(collision.existing, PathBuf::from("Makefile"));
+        assert_eq!(collision.renamed, None);
+        assert_eq!(
+            collision.message(Path::new("makefile")),
+            "WARNING: case-conflict: \"makefile\" collides with \"Makefile\" \
+             on a case-insensitive destination"
+        );
+    }
+
+    #[test]
+    fn identical_names_do_not_collide() {
+        let mut detector = CaseCollisionDetector::new(CaseCollisionPolicy::Warn);
+        assert!(detector.record(Path::new("dir/a"), false).is_none());
+        assert!(detector.record(Path::new("dir/a"), false).is_none());
+    }
+
+    #[test]
+    fn collisions_through_merged_directories_are_detected() {
+        let mut detector = CaseCollisionDetector::new(CaseCollisionPolicy::Rename);
+        assert!(detector.record(Path::new("Dir"), true).is_none());
+        assert!(detector.record(Path::new("Dir/a.txt"), false).is_none());
+
+        let dir = detector.record(Path::new("dir"), true).unwrap();
+        assert_eq!(dir.renamed, None, "directories merge instead of renaming");
+        let file = detector.record(Path::new("dir/A.txt"), false).unwrap();
+        assert_eq!(file.existing, PathBuf::from("Dir/a.txt"));
+        assert_eq!(file.renamed, Some(OsString::from("A~1.txt")));
+    }
+
+    #[test]
+    fn rename_skips_suffixes_already_in_use() {
+        let mut detector = CaseCollisionDetector::new(CaseCollisionPolicy::Rename);
+        detector.record(Path::new("README"), false);
+        detector.record(Path::new("readme~1"), false);
+        let collision = detector.record(Path::new("readme"), false).unwrap();
+        assert_eq!(collision.renamed, Some(OsString::from("readme~2")));
+        let again = detector.record(Path::new("ReadMe"), false).unwrap();
+        assert_eq!(again.renamed, Some(OsString::from("ReadMe~3")));
+    }
+}
@@ -13,12 +13,15 @@ use std::sync::Arc;
 
 use super::ActiveCompressor;
 use super::buffer_pool::{BufferPool, global_buffer_pool};
+use super::case_collision::{CaseCollision, CaseCollisionDetector};
 use super::deferred_sync::{DeferredSync, SyncStrategy};
+use super::deletion::DeletionPolicy;
 use super::filter_program::{
     ExcludeIfPresentLayers, ExcludeIfPresentRule, ExcludeIfPresentStack, FilterContext,
     FilterOutcome, FilterProgram, FilterSegment, FilterSegmentLayers, FilterSegmentStack,
     directory_has_marker,
 };
+use super::windows_names::{WindowsNameAction, WindowsNameSanitizer};
 
 #[cfg(all(unix, feature = "xattr"))]
 use super::store_effective_fake_super_if_requested;
@@ -30,9 +33,9 @@ use super::sync_nfsv4_acls_if_requested;
 use super::sync_xattrs_if_requested;
 
 use super::{
-    CopyComparison, DeleteTiming, DestinationWriteGuard, HardLinkTracker, LocalCopyAction,
-    LocalCopyArgumentError, LocalCopyError, LocalCopyErrorKind, LocalCopyExecution,
-    LocalCopyMetadata, LocalCopyOptions, LocalCopyProgress, LocalCopyRecord,
+    CopyComparison, CopyMethodKind, DeleteTiming, DestinationWriteGuard, HardLinkTracker,
+    LocalCopyAction, LocalCopyArgumentError, LocalCopyError, LocalCopyErrorKind,
+    LocalCopyExecution, LocalCopyMetadata, LocalCopyOptions, LocalCopyProgress, LocalCopyRecord,
     LocalCopyRecordHandler, LocalCopyReport, LocalCopySummary, NestedDirMerge, ReferenceDirectory,
     SparseWriteState, compute_backup_path, copy_entry_to_backup, create_backup_parents,
     delete_extraneous_entries, filter_program_local_error, follow_symlink_metadata,
@@ -259,6 +262,10 @@ pub(crate) struct CopyContext<'a> {
     /// allocation for the intermediate `(OsString, PathBuf)` collection per
     /// directory during recursive traversal.
     readdir_buf: Vec<(OsString, PathBuf)>,
+    /// Case-folded relative paths seen so far, for `--case-collision`.
+    case_collisions: CaseCollisionDetector,
+    /// Names claimed so far, for `--windows-names`.
+    windows_names: WindowsNameSanitizer,
     /// Adaptive compression level controller that adjusts compression level
     /// between files based on observed compression ratios.
     adaptive_level: Option<AdaptiveLevelController>,
@@ -354,6 +361,7 @@ impl<'a> FinalizeMetadataParams<'a> {
 pub(crate) struct FileCopyOutcome {
     literal_bytes: u64,
     compressed_bytes: Option<u64>,
+    copy_method: CopyMethodKind,
 }
 
 impl FileCopyOutcome {
@@ -362,9 +370,16 @@ impl FileCopyOutcome {
         Self {
             literal_bytes,
             compressed_bytes,
+            copy_method: CopyMethodKind::Standard,
         }
     }
 
+    /// Records the I/O tier that moved the bytes.
+    const fn with_copy_method(mut self, copy_method: CopyMethodKind) -> Self {
+        self.copy_method = copy_method;
+        self
+    }
+
     /// Returns the number of literal (unmatched) bytes transferred.
     pub(crate) const fn literal_bytes(self) -> u64 {
         self.literal_bytes
@@ -374,6 +389,11 @@ impl FileCopyOutcome {
     pub(crate) const fn compressed_bytes(self) -> Option<u64> {
         self.compressed_bytes
     }
+
+    /// Returns the I/O tier that moved the bytes.
+    pub(crate) const fn copy_method(self) -> CopyMethodKind {
+        self.copy_method
+    }
 }
 
 /// Describes a block matched against the existing destination during delta copy.
@@ -160,7 +160,7 @@ impl<'a> CopyContext<'a> {
 
                 let block = index.block(block_index);
                 let block_len = block.len();
-                let matched = MatchedBlock::new(block, index.block_length());
+                let matched = MatchedBlock::new(&block, index.block_length());
                 let basis_offset = matched.offset();
 
                 // upstream: receiver.c:468-477. The skip-fast-path only fires
@@ -281,7 +281,7 @@ impl<'a> CopyContext<'a> {
 
             let block = index.block(block_index);
             let block_len = block.len();
-            let matched = MatchedBlock::new(block, index.block_length());
+            let matched = MatchedBlock::new(&block, index.block_length());
             let basis_offset = matched.offset();
 
             if inplace_mode && basis_offset == output_position {
@@ -63,17 +63,13 @@ impl<'a> CopyContext<'a> {
     /// Evaluates filter rules to determine whether a destination entry may be
     /// deleted. Respects `--delete-excluded` when enabled.
     pub(super) fn allows_deletion(&self, relative: &Path, is_dir: bool) -> bool {
-        let delete_excluded = self.options.delete_excluded_enabled();
+        let policy = self.deletion_policy();
         if let Some(program) = &self.filter_program {
             if let Some(outcome) =
                 self.evaluate_dynamic_segments(relative, is_dir, FilterContext::Deletion)
                 && outcome.transfer_decided()
             {
-                return if delete_excluded {
-                    outcome.allows_deletion() || outcome.allows_deletion_when_excluded_removed()
-                } else {
-                    outcome.allows_deletion()
-                };
+                return policy.allows_outcome(outcome);
             }
             let layers = self.delete_dir_merge.layers.borrow();
             let ephemeral = self.delete_dir_merge.ephemeral.borrow();
@@ -85,23 +81,17 @@ impl<'a> CopyContext<'a> {
                 temp_layers,
                 FilterContext::Deletion,
             );
-            if delete_excluded {
-                outcome.allows_deletion() || outcome.allows_deletion_when_excluded_removed()
-            } else {
-                outcome.allows_deletion()
-            }
-        } else if let Some(filters) = self.options.filter_set() {
-            if delete_excluded {
-                filters.allows_deletion(relative, is_dir)
-                    || filters.allows_deletion_when_excluded_removed(relative, is_dir)
-            } else {
-                filters.allows_deletion(relative, is_dir)
-            }
+            policy.allows_outcome(outcome)
         } else {
-            true
+            policy.allows(relative, is_dir)
         }
     }
 
+    /// Returns the deletion policy configured for this run.
+    pub(super) const fn deletion_policy(&self) -> DeletionPolicy<'_> {
+        DeletionPolicy::from_options(&self.options)
+    }
+
     /// Freezes the effective deletion filter chain for the current directory
     /// into an immutable, `Send + Sync` [`DeletionFilterSnapshot`].
     ///
@@ -210,16 +200,13 @@ impl DeletionFilterSnapshot {
     /// against the frozen chain. Byte-for-byte equivalent to
     /// [`CopyContext::allows_deletion`].
     pub(crate) fn allows_deletion(&self, relative: &Path, is_dir: bool) -> bool {
-        let delete_excluded = self.delete_excluded;
+        let policy =
+            DeletionPolicy::new(self.filter_set.as_ref()).delete_excluded(self.delete_excluded);
         if let Some(program) = &self.program {
             if let Some(outcome) = self.evaluate_dynamic_segments(relative, is_dir)
                 && outcome.transfer_decided()
             {
-                return if delete_excluded {
-                    outcome.allows_deletion() || outcome.allows_deletion_when_excluded_removed()
-                } else {
-                    outcome.allows_deletion()
-                };
+                return policy.allows_outcome(outcome);
             }
             let temp_layers = self.ephemeral_last.as_deref();
             let outcome = program.evaluate(
@@ -229,20 +216,9 @@ impl DeletionFilterSnapshot {
                 temp_layers,
                 FilterContext::Deletion,
             );
-            if delete_excluded {
-                outcome.allows_deletion() || outcome.allows_deletion_when_excluded_removed()
-            } else {
-                outcome.allows_deletion()
-            }
-        } else if let Some(filters) = &self.filter_set {
-            if delete_excluded {
-                filters.allows_deletion(relative, is_dir)
-                    || filters.allows_deletion_when_excluded_removed(relative, is_dir)
-            } else {
-                filters.allows_deletion(relative, is_dir)
-            }
+            policy.allows_outcome(outcome)
         } else {
-            true
+            policy.allows(relative, is_dir)
         }
     }
 
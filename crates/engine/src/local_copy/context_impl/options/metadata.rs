@@ -9,6 +9,7 @@ impl<'a> CopyContext<'a> {
             .preserve_times(self.options.preserve_times())
             .preserve_atimes(self.options.preserve_atimes())
             .preserve_crtimes(self.options.preserve_crtimes())
+            .preserve_fileflags(self.options.preserve_fileflags())
             .numeric_ids(self.options.numeric_ids_enabled())
             .fake_super(self.options.fake_super_enabled())
             .with_owner_override(self.options.owner_override())
@@ -29,8 +29,8 @@ impl<'a> CopyContext<'a> {
         self.options.keep_dirlinks_enabled()
     }
 
-    pub(super) const fn whole_file_enabled(&self) -> bool {
-        self.options.whole_file_enabled()
+    pub(super) const fn whole_file_for(&self, source_len: u64, basis_len: Option<u64>) -> bool {
+        self.options.whole_file_for(source_len, basis_len)
     }
 
     /// Returns whether the active copy-on-write policy permits reflink
@@ -66,7 +66,6 @@ impl<'a> CopyContext<'a> {
         self.options.preallocate_enabled()
     }
 
-    #[allow(dead_code)] // Accessor retained for future use; DeferredSync handles runtime selection
     pub(super) const fn fsync_enabled(&self) -> bool {
         self.options.fsync_enabled()
     }
@@ -79,6 +78,10 @@ impl<'a> CopyContext<'a> {
         self.options.copy_devices_as_files_enabled()
     }
 
+    pub(super) const fn write_devices_enabled(&self) -> bool {
+        self.options.write_devices_enabled()
+    }
+
     /// Returns the readable byte length to use when `--copy-devices` should
     /// stream `source` (a block/char device) as a regular file, or `None` when
     /// `source` is not a copy-devices device and the caller should use the stat
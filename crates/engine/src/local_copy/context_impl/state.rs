@@ -22,6 +22,8 @@ impl<'a> CopyContext<'a> {
         });
         let filter_program = options.filter_program().cloned();
         let timeout = options.timeout();
+        let case_collisions = CaseCollisionDetector::new(options.case_collision_policy());
+        let windows_names = WindowsNameSanitizer::new(options.windows_names_policy());
 
         let buffer_pool = global_buffer_pool();
 
@@ -152,6 +154,8 @@ impl<'a> CopyContext<'a> {
             batch_flist_index: 0,
             batch_ndx_codec,
             readdir_buf: Vec::new(),
+            case_collisions,
+            windows_names,
             adaptive_level,
         }
     }
@@ -719,6 +723,32 @@ impl<'a> CopyContext<'a> {
         &mut self.readdir_buf
     }
 
+    /// Records `relative` with the `--case-collision` detector and returns the
+    /// collision, if any, with an earlier differently-cased entry.
+    pub(super) fn record_case_collision(
+        &mut self,
+        relative: &Path,
+        is_dir: bool,
+    ) -> Option<CaseCollision> {
+        self.case_collisions.record(relative, is_dir)
+    }
+
+    /// Reserves `relative` with the `--windows-names` sanitizer so a
+    /// sanitized sibling is not written under the same name.
+    pub(super) fn reserve_windows_name(&mut self, relative: &Path) {
+        self.windows_names.reserve(relative);
+    }
+
+    /// Checks `relative` against the `--windows-names` policy, returning the
+    /// action to take when the name is invalid on Windows.
+    pub(super) fn check_windows_name(
+        &mut self,
+        relative: &Path,
+        is_dir: bool,
+    ) -> Option<WindowsNameAction> {
+        self.windows_names.check(relative, is_dir)
+    }
+
     /// Clears the checksum cache to free memory after directory processing.
     pub(super) fn clear_checksum_cache(&mut self) {
         if let Some(ref mut cache) = self.checksum_cache {
@@ -1245,7 +1275,11 @@ impl<'a> CopyContext<'a> {
         let _ = &path_context.source;
 
         // Deferred updates have no open fd, so the cross-device flag is unused.
-        let _cross_device = guard.commit()?;
+        let _cross_device = if metadata_options.fileflags() {
+            guard.commit_mutable()?
+        } else {
+            guard.commit()?
+        };
 
         self.apply_metadata_and_finalize(
             destination.as_path(),
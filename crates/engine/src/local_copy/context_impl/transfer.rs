@@ -618,12 +618,15 @@ impl<'a> CopyContext<'a> {
 
         // Fast path: use copy_file_range for simple whole-file copies.
         // Requires no sparse detection, no compression, no bandwidth limiter.
+        // Same-filesystem copies try copy_file_range ahead of io_uring so
+        // Btrfs/XFS can share extents; the tier that ran is reported back
+        // for the `Copy method` stats line.
         // Disabled for append mode (initial_bytes > 0) because copy_file_range
         // and io_uring on Linux do not reliably respect the seeked file position
         // when both source and destination have been seeked to non-zero offsets.
         // upstream: receiver.c - append path uses standard read/write loop.
         if !sparse && !compress && self.limiter.is_none() && initial_bytes == 0 {
-            let copied = fast_io::copy_file_range::copy_file_contents_buffered(
+            let (copied, method) = fast_io::copy_file_range::copy_file_contents_tracked(
                 reader,
                 writer,
                 expected_remaining,
@@ -634,7 +637,8 @@ impl<'a> CopyContext<'a> {
                 let progressed = initial_bytes.saturating_add(copied);
                 self.notify_progress(relative, Some(total_size), progressed, start.elapsed());
             }
-            return Ok(FileCopyOutcome::new(copied, None));
+            return Ok(FileCopyOutcome::new(copied, None)
+                .with_copy_method(CopyMethodKind::from_content_copy(method)));
         }
 
         if sparse {
@@ -6,14 +6,19 @@
 //! - `--delete-excluded`: Also delete files matching exclude patterns
 //! - `--max-delete=NUM`: Don't delete more than NUM files
 //! - `--force`: Force deletion of non-empty directories
-//! - Filter rules and exclusion patterns
+//! - Filter rules and exclusion patterns, including `protect`/`risk` rules
+//!
+//! [`DeletionPolicy`] bundles the filter, `--delete-excluded`, and `--force`
+//! inputs into the per-entry verdict every deletion site shares.
 //!
 //! Deletion timing (`--delete-before`, `--delete-during`, `--delete-after`,
 //! `--delete-delay`) is represented by the `DeleteTiming` enum and handled
 //! by the caller.
 
+mod policy;
 mod strategy;
 
+pub use policy::DeletionPolicy;
 pub use strategy::{
     DeletionContext, DeletionError, DeletionResult, build_keep_set, is_extraneous_entry,
     should_delete_entry,
@@ -0,0 +1,121 @@
+//! Per-entry deletion policy.
+//!
+//! Combines the receiver-side filter rules with `--delete-excluded` and
+//! `--force` so every site that removes a destination entry reaches the same
+//! verdict, mirroring upstream rsync's `delete_in_dir()` / `delete_item()`.
+//!
+//! The local copy and the [`vfs`](crate::vfs) sync use the whole policy. The
+//! wire-protocol receiver uses only its `--delete`/`--force` half: its sweep
+//! evaluates protect and risk rules against the filter list received from the
+//! sender and the destination's per-directory merge files, which it holds as
+//! a `FilterChain` rather than a [`FilterSet`].
+
+use std::path::Path;
+
+use filters::FilterSet;
+
+use crate::local_copy::LocalCopyOptions;
+use crate::local_copy::filter_program::FilterOutcome;
+
+/// Decides whether an extraneous destination entry may be deleted.
+///
+/// A `protect` (`P`) rule shields the entry from every delete pass, including
+/// `--delete-excluded`, while a later-matching `risk` (`R`) rule lifts an
+/// inherited protect - per-directory merge files included, since their rules
+/// arrive in the same [`FilterSet`] or [`FilterOutcome`] evaluation.
+///
+/// # Upstream Reference
+///
+/// - `generator.c:delete_in_dir()` - `check_filter(&daemon_filter_list, ...)`
+///   and `check_filter(cur_filter_list, ...)` gate each candidate
+/// - `exclude.c:rule_matches()` - `delete_excluded` turns an excluded entry
+///   into a deletable one, but never a protected one
+/// - `generator.c:recv_generator()` - `del_opts` carries `DEL_RECURSE` under
+///   `--delete` or `--force`, so a non-empty directory in the way of a
+///   non-directory is removed
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DeletionPolicy<'a> {
+    filters: Option<&'a FilterSet>,
+    delete: bool,
+    delete_excluded: bool,
+    force: bool,
+}
+
+impl<'a> DeletionPolicy<'a> {
+    /// Creates a policy that consults `filters` (or allows every deletion when
+    /// `None`), with `--delete`, `--delete-excluded`, and `--force` off.
+    #[must_use]
+    pub const fn new(filters: Option<&'a FilterSet>) -> Self {
+        Self {
+            filters,
+            delete: false,
+            delete_excluded: false,
+            force: false,
+        }
+    }
+
+    /// Builds the policy configured by `options`.
+    #[must_use]
+    pub const fn from_options(options: &'a LocalCopyOptions) -> Self {
+        Self::new(options.filter_set())
+            .delete(options.delete_extraneous())
+            .delete_excluded(options.delete_excluded_enabled())
+            .force(options.force_replacements_enabled())
+    }
+
+    /// Records whether extraneous destination entries are removed (`--delete`).
+    #[must_use]
+    #[doc(alias = "--delete")]
+    pub const fn delete(mut self, enabled: bool) -> Self {
+        self.delete = enabled;
+        self
+    }
+
+    /// Records whether excluded destination entries are also removed.
+    #[must_use]
+    #[doc(alias = "--delete-excluded")]
+    pub const fn delete_excluded(mut self, enabled: bool) -> Self {
+        self.delete_excluded = enabled;
+        self
+    }
+
+    /// Records whether non-empty directories may be replaced by non-directories.
+    #[must_use]
+    #[doc(alias = "--force")]
+    pub const fn force(mut self, enabled: bool) -> Self {
+        self.force = enabled;
+        self
+    }
+
+    /// Reports whether `--force` is in effect.
+    #[must_use]
+    pub const fn force_enabled(&self) -> bool {
+        self.force
+    }
+
+    /// Reports whether a non-empty directory standing where a non-directory
+    /// must go is removed recursively (`--delete` or `--force`).
+    #[must_use]
+    pub const fn removes_conflicting_directories(&self) -> bool {
+        self.delete || self.force
+    }
+
+    /// Returns `true` when the entry at `relative` may be deleted.
+    ///
+    /// Without a filter set every entry is deletable.
+    #[must_use]
+    pub fn allows(&self, relative: &Path, is_dir: bool) -> bool {
+        let Some(filters) = self.filters else {
+            return true;
+        };
+        filters.allows_deletion(relative, is_dir)
+            || (self.delete_excluded
+                && filters.allows_deletion_when_excluded_removed(relative, is_dir))
+    }
+
+    /// Applies the policy to an already evaluated filter-program outcome.
+    pub(crate) const fn allows_outcome(&self, outcome: FilterOutcome) -> bool {
+        outcome.allows_deletion()
+            || (self.delete_excluded && outcome.allows_deletion_when_excluded_removed())
+    }
+}
@@ -425,3 +425,85 @@ mod integration_tests {
         assert_eq!(ctx.deletions_performed, 2);
     }
 }
+
+mod deletion_policy_tests {
+    use super::*;
+    use filters::{FilterRule, FilterSet};
+
+    fn filter_set(rules: Vec<FilterRule>) -> FilterSet {
+        FilterSet::from_rules(rules).expect("valid rules")
+    }
+
+    #[test]
+    fn without_filters_everything_is_deletable() {
+        let policy = DeletionPolicy::new(None);
+        assert!(policy.allows(Path::new("any.txt"), false));
+        assert!(policy.allows(Path::new("dir"), true));
+    }
+
+    #[test]
+    fn protect_rule_shields_entry() {
+        let set = filter_set(vec![FilterRule::protect("*.log")]);
+        let policy = DeletionPolicy::new(Some(&set));
+        assert!(!policy.allows(Path::new("app.log"), false));
+        assert!(policy.allows(Path::new("app.txt"), false));
+    }
+
+    #[test]
+    fn earlier_risk_rule_overrides_protect() {
+        let set = filter_set(vec![
+            FilterRule::risk("scratch.log"),
+            FilterRule::protect("*.log"),
+        ]);
+        let policy = DeletionPolicy::new(Some(&set));
+        assert!(policy.allows(Path::new("scratch.log"), false));
+        assert!(!policy.allows(Path::new("app.log"), false));
+    }
+
+    #[test]
+    fn delete_excluded_removes_excluded_but_not_protected() {
+        let set = filter_set(vec![
+            FilterRule::protect("keep.tmp"),
+            FilterRule::exclude("*.tmp"),
+        ]);
+        let policy = DeletionPolicy::new(Some(&set));
+        assert!(!policy.allows(Path::new("build.tmp"), false));
+
+        let policy = policy.delete_excluded(true);
+        assert!(policy.allows(Path::new("build.tmp"), false));
+        assert!(!policy.allows(Path::new("keep.tmp"), false));
+    }
+
+    #[test]
+    fn conflicting_directories_removed_under_delete_or_force() {
+        assert!(!DeletionPolicy::new(None).removes_conflicting_directories());
+        assert!(
+            DeletionPolicy::new(None)
+                .delete(true)
+                .removes_conflicting_directories()
+        );
+        let forced = DeletionPolicy::new(None).force(true);
+        assert!(forced.force_enabled());
+        assert!(forced.removes_conflicting_directories());
+    }
+
+    #[test]
+    fn from_options_carries_force() {
+        let options = crate::local_copy::LocalCopyOptions::new().force_replacements(true);
+        let policy = DeletionPolicy::from_options(&options);
+        assert!(policy.force_enabled());
+        assert!(policy.removes_conflicting_directories());
+    }
+
+    #[test]
+    fn from_options_carries_delete_excluded() {
+        let set = filter_set(vec![FilterRule::exclude("*.tmp")]);
+        let options = crate::local_copy::LocalCopyOptions::new()
+            .delete(true)
+            .filters(Some(set));
+        assert!(!DeletionPolicy::from_options(&options).allows(Path::new("a.tmp"), false));
+
+        let options = options.delete_excluded(true);
+        assert!(DeletionPolicy::from_options(&options).allows(Path::new("a.tmp"), false));
+    }
+}
@@ -158,6 +158,15 @@ impl LocalCopyError {
         })
     }
 
+    /// Constructs the error raised by `--windows-names=fail` for a name NTFS
+    /// cannot store (exit code 23, `RERR_PARTIAL`).
+    ///
+    /// Unlike a per-entry I/O error, the copy does not continue past it.
+    #[must_use]
+    pub fn invalid_windows_name(path: impl Into<PathBuf>) -> Self {
+        Self::new(LocalCopyErrorKind::InvalidWindowsName { path: path.into() })
+    }
+
     /// Returns the exit code that mirrors upstream rsync's behaviour.
     ///
     /// See the struct-level documentation for mappings to `core::exit_code::ExitCode`.
@@ -186,7 +195,9 @@ impl LocalCopyError {
             }
             LocalCopyErrorKind::DeleteLimitExceeded { .. } => MAX_DELETE_EXIT_CODE,
             LocalCopyErrorKind::FilterSyntax { .. } => MISSING_OPERANDS_EXIT_CODE,
-            LocalCopyErrorKind::PartialTransfer => INVALID_OPERAND_EXIT_CODE,
+            LocalCopyErrorKind::PartialTransfer | LocalCopyErrorKind::InvalidWindowsName { .. } => {
+                INVALID_OPERAND_EXIT_CODE
+            }
             LocalCopyErrorKind::Interrupted => SIGNAL_EXIT_CODE,
         }
     }
@@ -212,7 +223,9 @@ impl LocalCopyError {
             }
             LocalCopyErrorKind::DeleteLimitExceeded { .. } => "RERR_DEL_LIMIT",
             LocalCopyErrorKind::FilterSyntax { .. } => "RERR_SYNTAX",
-            LocalCopyErrorKind::PartialTransfer => "RERR_PARTIAL",
+            LocalCopyErrorKind::PartialTransfer | LocalCopyErrorKind::InvalidWindowsName { .. } => {
+                "RERR_PARTIAL"
+            }
             LocalCopyErrorKind::Interrupted => "RERR_SIGNAL",
         }
     }
@@ -354,6 +367,13 @@ pub enum LocalCopyErrorKind {
     /// `received SIGINT, SIGTERM, or SIGHUP`, emitted once by `log.c:log_exit()`.
     #[error("received SIGINT, SIGTERM, or SIGHUP")]
     Interrupted,
+    /// `--windows-names=fail` met a name that cannot be created on Windows
+    /// (oc-rsync extension). The transfer stops before the entry is written.
+    #[error("\"{}\" is not a valid Windows file name (--windows-names=fail)", path.display())]
+    InvalidWindowsName {
+        /// Relative path of the offending entry.
+        path: PathBuf,
+    },
 }
 
 impl LocalCopyErrorKind {
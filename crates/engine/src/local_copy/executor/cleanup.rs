@@ -192,9 +192,13 @@ where
     //    contents - a silent data-loss gap. The leaf executor recurses
     //    depth-first and calls `delete_leaf`, which backs up each file, matching
     //    upstream's per-entry recursion.
+    // 4. `--fileflags`: an immutable or append-only entry refuses removal
+    //    until its flags are cleared (upstream patches/fileflags.diff
+    //    `make_mutable()` in `delete_item`), which `delete_leaf` does per entry.
     if context.options().max_deletion_limit().is_some()
         || context.one_file_system_enabled()
         || context.options().backup_enabled()
+        || context.options().preserve_fileflags()
     {
         return delete_extraneous_entries_capped(context, destination, relative, source_entries);
     }
@@ -239,7 +243,10 @@ where
     // in `delete_extraneous_entries_via_emitter`; this guard covers the deferred
     // `--delete-delay` plan, whose decided extras are consumed here rather than
     // rescanned.
-    if context.one_file_system_enabled() || context.options().backup_enabled() {
+    if context.one_file_system_enabled()
+        || context.options().backup_enabled()
+        || context.options().preserve_fileflags()
+    {
         let boundary_dev = delete_boundary_device(context, destination);
         let mut skipped = 0u64;
         for entry in &plan.extras {
@@ -645,9 +652,20 @@ fn delete_leaf(
     // directory that is actually removed. Files keep the print-before-unlink
     // order (the backup already renamed them; the unlink cannot leave a
     // survivor).
+    // upstream: patches/fileflags.diff - delete_item() clears an immutable
+    // entry's flags first; they are restored if the removal fails.
+    let fileflags = context.options().preserve_fileflags();
+    let remove = |op: &dyn Fn() -> io::Result<()>| {
+        if fileflags {
+            ::metadata::while_mutable(path, op)
+        } else {
+            op()
+        }
+    };
+
     if is_dir {
         if !context.mode().is_dry_run() {
-            match RealDeleteFs.rmdir(path) {
+            match remove(&|| RealDeleteFs.rmdir(path)) {
                 Ok(()) => {}
                 Err(error) if error.kind() == io::ErrorKind::NotFound => {}
                 Err(error) if is_dir_not_empty(&error) => {
@@ -680,9 +698,9 @@ fn delete_leaf(
         if !context.mode().is_dry_run() {
             let fs = RealDeleteFs;
             let result = if file_type.is_symlink() {
-                fs.unlink_symlink(path)
+                remove(&|| fs.unlink_symlink(path))
             } else {
-                fs.unlink_file(path)
+                remove(&|| fs.unlink_file(path))
             };
             if let Err(error) = result {
                 // A vanished entry is a benign no-op (upstream ENOENT path); any
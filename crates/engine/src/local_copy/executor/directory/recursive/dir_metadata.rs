@@ -17,9 +17,9 @@ use std::path::{Path, PathBuf};
 use crate::local_copy::LocalCopyExecution;
 #[cfg(all(any(unix, windows), feature = "acl"))]
 use crate::local_copy::sync_acls_if_requested;
-#[cfg(all(unix, feature = "xattr"))]
-use crate::local_copy::sync_xattrs_if_requested;
 use crate::local_copy::{CopyContext, LocalCopyError, LocalCopyRecord, map_metadata_error};
+#[cfg(all(unix, feature = "xattr"))]
+use crate::local_copy::{sync_nfsv4_acls_if_requested, sync_xattrs_if_requested};
 use ::metadata::apply_directory_metadata_with_options;
 
 /// Applies final metadata to a directory after all contents have been processed.
@@ -97,6 +97,15 @@ pub(super) fn apply_final_directory_metadata(
         context.filter_program(),
     )?;
 
+    #[cfg(all(unix, feature = "xattr"))]
+    sync_nfsv4_acls_if_requested(
+        context.options().preserve_nfsv4_acls(),
+        mode,
+        source,
+        destination,
+        true,
+    )?;
+
     #[cfg(all(any(unix, windows), feature = "acl"))]
     sync_acls_if_requested(
         preserve_acls,
@@ -3,12 +3,14 @@
 //! Dispatches each [`PlannedEntry`] to the appropriate copy handler based on
 //! its [`EntryAction`]. Mirrors the per-entry dispatch in upstream
 //! `generator.c:recv_generator()`.
+use std::borrow::Cow;
 use std::path::{Path, PathBuf};
 
 use logging::info_log;
 
 use crate::local_copy::{
-    CopyContext, LocalCopyError, copy_device, copy_fifo, copy_file, copy_symlink,
+    CopyContext, LocalCopyError, WindowsNameAction, copy_device, copy_fifo, copy_file,
+    copy_symlink, is_valid_windows_name,
 };
 
 use super::super::super::non_empty_path;
@@ -77,8 +79,45 @@ pub(super) fn process_planned_entry(
     // local-copy the two contexts compose to LOCAL -> REMOTE; apply that
     // transcoding here before joining onto the destination directory.
     let file_name = &planned.entry.file_name;
-    let dest_name = transcode_filename_component(file_name, context.options().iconv());
-    target_buf.push(Path::new(&*dest_name));
+    let mut dest_name = transcode_filename_component(file_name, context.options().iconv());
+    // oc-rsync extension: `--windows-names` checks the name that will be
+    // written. A sanitized directory name also becomes the parent of every
+    // entry below it, since recursion builds on `target_buf`.
+    let mut relative = Cow::Borrowed(planned.relative.as_path());
+    let is_dir = matches!(planned.action, EntryAction::CopyDirectory);
+    // Entries below a sanitized directory come back renamed as well; only an
+    // invalid name of its own changes what this level writes.
+    let windows_action = if context.options().windows_names_policy().is_enabled() {
+        context
+            .check_windows_name(&relative.with_file_name(&*dest_name), is_dir)
+            .filter(|_| !is_valid_windows_name(&dest_name))
+    } else {
+        None
+    };
+    if let Some(action) = windows_action {
+        action.report(&relative);
+        match action {
+            WindowsNameAction::Rename(sanitized) => {
+                let sanitized = sanitized.file_name().unwrap_or_default().to_os_string();
+                relative = Cow::Owned(relative.with_file_name(&sanitized));
+                dest_name = Cow::Owned(sanitized);
+            }
+            WindowsNameAction::Skip => return Ok(false),
+            WindowsNameAction::Fail => {
+                return Err(LocalCopyError::invalid_windows_name(relative.into_owned()));
+            }
+        }
+    }
+    let renamed = context
+        .record_case_collision(&relative, is_dir)
+        .and_then(|collision| {
+            collision.report(&relative);
+            collision.renamed
+        });
+    match renamed {
+        Some(name) => target_buf.push(name),
+        None => target_buf.push(Path::new(&*dest_name)),
+    }
 
     let result = dispatch_copy_action(
         context,
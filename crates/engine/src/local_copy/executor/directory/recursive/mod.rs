@@ -37,8 +37,10 @@ use dir_metadata::{
 use entry::process_planned_entry;
 
 use super::planner::{
-    apply_pre_transfer_deletions, plan_directory_entries, reorder_hardlink_group_holders,
+    EntryAction, apply_pre_transfer_deletions, plan_directory_entries,
+    reorder_hardlink_group_holders,
 };
+use super::super::transcode_filename_component;
 use super::support::read_directory_entries_sorted_reuse;
 
 /// Maximum directory nesting depth the recursive executor descends before
@@ -496,6 +498,19 @@ fn copy_directory_recursive_inner(
         destination,
         &mut plan.planned_entries,
     );
+    // oc-rsync extension: `--order` queues this directory's regular files by
+    // mtime or size. Runs after the hard-link reorder; cohort members share
+    // both keys, so the stable sort keeps the holder ahead of its aliases.
+    context
+        .options()
+        .transfer_order()
+        .apply(&mut plan.planned_entries, |planned| {
+            matches!(planned.action, EntryAction::CopyFile).then(|| {
+                let metadata = planned.metadata();
+                let mtime = filetime::FileTime::from_last_modification_time(metadata);
+                (mtime.unix_seconds(), metadata.len())
+            })
+        });
     apply_pre_transfer_deletions(context, destination, relative, &plan)?;
     // upstream: generator.c:1532-1537 - a non-INC_RECURSE `--delete-during`
     // sweep runs while the generator itemizes the directory entry, before it
@@ -543,6 +558,29 @@ fn copy_directory_recursive_inner(
     // a per-entry PathBuf allocation from Path::join.
     let mut target_buf = destination.to_path_buf();
 
+    // oc-rsync extension: claim this directory's names before any is checked,
+    // so a `--windows-names` rewrite cannot land on a sibling sorted after it.
+    if context.options().windows_names_policy().is_enabled() {
+        let written = plan.planned_entries.iter().filter(|planned| {
+            !matches!(
+                planned.action,
+                EntryAction::SkipExcluded
+                    | EntryAction::SkipNonRegular
+                    | EntryAction::SkipMountPoint
+            )
+        });
+        for planned in written {
+            let relative = {
+                let name = transcode_filename_component(
+                    &planned.entry.file_name,
+                    context.options().iconv(),
+                );
+                planned.relative.with_file_name(&*name)
+            };
+            context.reserve_windows_name(&relative);
+        }
+    }
+
     let mut first_entry_io_error: Option<LocalCopyError> = None;
     for planned in &plan.planned_entries {
         let result = process_planned_entry(
@@ -271,6 +271,29 @@ pub(crate) fn should_skip_copy(params: CopyComparison<'_>) -> bool {
         });
     }
 
+    quick_check_times_match(
+        source.modified().ok(),
+        destination.modified().ok(),
+        size_only,
+        ignore_times,
+        modify_window,
+    )
+}
+
+/// Finishes the quick check for a file whose size already matches, without
+/// `--checksum`.
+///
+/// `--size-only` accepts the file, `--ignore-times` rejects it, and otherwise
+/// the modification times must agree under `--modify-window`; a side without
+/// a modification time never matches. Shared by [`should_skip_copy`] and the
+/// [`vfs`](crate::vfs) tree sync.
+pub(crate) fn quick_check_times_match(
+    source: Option<SystemTime>,
+    destination: Option<SystemTime>,
+    size_only: bool,
+    ignore_times: bool,
+    modify_window: ModifyWindow,
+) -> bool {
     // Upstream: generator.c:unchanged_file() checks size_only before ignore_times.
     // When both flags are set, size_only wins (skip if sizes match).
     if size_only {
@@ -281,8 +304,8 @@ pub(crate) fn should_skip_copy(params: CopyComparison<'_>) -> bool {
         return false;
     }
 
-    match (source.modified(), destination.modified()) {
-        (Ok(src), Ok(dst)) => system_time_within_window(src, dst, modify_window),
+    match (source, destination) {
+        (Some(src), Some(dst)) => system_time_within_window(src, dst, modify_window),
         _ => false,
     }
 }
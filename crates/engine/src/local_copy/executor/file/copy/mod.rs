@@ -16,13 +16,13 @@ use std::time::Duration;
 use logging::debug_log;
 
 use crate::local_copy::{
-    CopyContext, LocalCopyAction, LocalCopyError, LocalCopyMetadata, LocalCopyRecord,
+    CopyContext, LocalCopyAction, LocalCopyError, LocalCopyMetadata, LocalCopyRecord, is_device,
 };
 
 #[cfg(test)]
 #[allow(unused_imports)]
 pub(crate) use transfer::take_fsync_call_count;
-use transfer::{TransferFlags, execute_transfer};
+use transfer::{TransferFlags, copy_into_device, execute_transfer};
 
 /// Copies a single file from source to destination.
 ///
@@ -106,8 +106,9 @@ pub(crate) fn copy_file(
         // directory, so a file contributed by one source must never blow away a
         // directory contributed by another. Guard the delete-mode removal on
         // !multi_source to preserve that, keeping the explicit --force override.
-        let replace_conflicting_directory = context.force_replacements_enabled()
-            || (!context.multi_source() && context.options().delete_extraneous());
+        let policy = context.deletion_policy();
+        let replace_conflicting_directory = policy.force_enabled()
+            || (!context.multi_source() && policy.removes_conflicting_directories());
         if replace_conflicting_directory {
             context.force_remove_destination(destination, relative, existing)?;
             // The conflicting directory is gone, so the incoming file is created
@@ -224,7 +225,11 @@ pub(crate) fn copy_file(
     let ignore_times_enabled = context.ignore_times_enabled();
     let append_allowed = context.append_enabled();
     let append_verify = context.append_verify_enabled();
-    let whole_file_enabled = context.whole_file_enabled();
+    let basis_len = existing_metadata
+        .as_ref()
+        .filter(|existing| existing.is_file())
+        .map(fs::Metadata::len);
+    let whole_file_enabled = context.whole_file_for(file_size, basis_len);
     let compress_enabled = context.should_compress(record_path.as_path());
     let relative_for_link = relative.unwrap_or(record_path.as_path());
 
@@ -274,6 +279,27 @@ pub(crate) fn copy_file(
         preserve_acls,
     };
 
+    // upstream: generator.c:recv_generator() keeps a destination device under
+    // --write-devices, and receiver.c writes the file data into it in place.
+    if context.write_devices_enabled()
+        && let Some(existing) = existing_metadata
+            .as_ref()
+            .filter(|existing| is_device(existing.file_type()))
+    {
+        copy_into_device(
+            context,
+            source,
+            destination,
+            metadata,
+            &metadata_options,
+            record_path.as_path(),
+            existing,
+            file_size,
+            transfer_flags,
+        )?;
+        return Ok(true);
+    }
+
     execute_transfer(
         context,
         source,
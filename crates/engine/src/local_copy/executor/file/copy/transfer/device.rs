@@ -0,0 +1,113 @@
+//! Writes file data straight into an existing destination device.
+//!
+//! With `--write-devices`, a block or character device already present at the
+//! destination is treated as a regular file: the source contents are written
+//! into it from offset zero. The device node is opened without `O_CREAT` or
+//! `O_TRUNC` and is never replaced, so there is no temp file and no rename.
+//! Its permissions and ownership are left alone, as in the remote receiver.
+//!
+//! # Upstream Reference
+//!
+//! - `options.c` - `--write-devices` implies `--inplace`
+//! - `generator.c:recv_generator()` - `write_devices && IS_DEVICE()` keeps the
+//!   device rather than deleting it to make room for the file
+//! - `receiver.c:recv_files()` - the device is opened for writing in place
+
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::time::Instant;
+
+use ::metadata::MetadataOptions;
+
+use crate::local_copy::{
+    CopyContext, LocalCopyAction, LocalCopyChangeSet, LocalCopyError, LocalCopyMetadata,
+    LocalCopyRecord,
+};
+
+use super::TransferFlags;
+use super::open::open_source_file;
+
+/// Copies `source` into the device node at `destination`.
+///
+/// `file_size` is the number of bytes to copy (the readable length for a
+/// `--copy-devices` source). A block device too small to hold them is refused
+/// before anything is written; character devices report no capacity and take
+/// whatever is written.
+#[allow(clippy::too_many_arguments)]
+pub(in crate::local_copy) fn copy_into_device(
+    context: &mut CopyContext,
+    source: &Path,
+    destination: &Path,
+    metadata: &fs::Metadata,
+    metadata_options: &MetadataOptions,
+    record_path: &Path,
+    existing_metadata: &fs::Metadata,
+    file_size: u64,
+    flags: TransferFlags,
+) -> Result<(), LocalCopyError> {
+    let start = Instant::now();
+
+    #[cfg(unix)]
+    {
+        let capacity = ::metadata::device_readable_size(destination)
+            .map_err(|error| LocalCopyError::io("inspect device", destination, error))?;
+        if capacity > 0 && file_size > capacity {
+            return Err(LocalCopyError::io(
+                "write device",
+                destination,
+                io::Error::new(
+                    io::ErrorKind::StorageFull,
+                    format!("device holds {capacity} bytes but {file_size} are needed"),
+                ),
+            ));
+        }
+    }
+
+    let reader = open_source_file(source, context.open_noatime_enabled())
+        .map_err(|error| LocalCopyError::io("copy file", source, error))?;
+    let mut device = fs::OpenOptions::new()
+        .write(true)
+        .open(destination)
+        .map_err(|error| LocalCopyError::io("write device", destination, error))?;
+    let copied = io::copy(&mut reader.take(file_size), &mut device)
+        .map_err(|error| LocalCopyError::io("write device", destination, error))?;
+    if context.fsync_enabled() {
+        device
+            .sync_all()
+            .map_err(|error| LocalCopyError::io("fsync device", destination, error))?;
+    }
+
+    let elapsed = start.elapsed();
+    context.summary_mut().record_file(file_size, copied, None);
+    context.summary_mut().record_elapsed(elapsed);
+
+    let mut metadata_snapshot = LocalCopyMetadata::from_metadata(metadata, None);
+    if file_size != metadata.len() {
+        metadata_snapshot = metadata_snapshot.virtualize_copy_device_as_file(file_size);
+    }
+    let total_bytes = Some(metadata_snapshot.len());
+    let change_set = LocalCopyChangeSet::for_file(
+        metadata,
+        Some(existing_metadata),
+        metadata_options,
+        true,
+        copied > 0,
+        flags.xattrs_enabled(),
+        flags.acls_enabled(),
+        context.options().modify_window(),
+    );
+    context.record(
+        LocalCopyRecord::new(
+            record_path.to_path_buf(),
+            LocalCopyAction::DataCopied,
+            copied,
+            total_bytes,
+            elapsed,
+            Some(metadata_snapshot),
+        )
+        .with_change_set(change_set),
+    );
+
+    context.enforce_timeout()
+}
@@ -167,6 +167,9 @@ pub(super) fn try_clone(
     context
         .summary_mut()
         .record_copy_method(CopyMethodKind::from_platform(clone_method));
+    if clone_method == fast_io::CopyMethod::Clonefile {
+        context.summary_mut().record_cloned_bytes(file_size);
+    }
     context.summary_mut().record_elapsed(start.elapsed());
 
     let metadata_snapshot = LocalCopyMetadata::from_metadata(metadata, None)
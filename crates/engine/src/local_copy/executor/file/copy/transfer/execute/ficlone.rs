@@ -154,6 +154,7 @@ pub(super) fn try_clone(
     context
         .summary_mut()
         .record_copy_method(CopyMethodKind::Ficlone);
+    context.summary_mut().record_cloned_bytes(file_size);
     context.summary_mut().record_elapsed(start.elapsed());
 
     let metadata_snapshot = LocalCopyMetadata::from_metadata(metadata, None)
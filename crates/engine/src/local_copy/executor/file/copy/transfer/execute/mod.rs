@@ -24,7 +24,7 @@ mod skip;
 mod wincopy;
 
 use std::fs;
-use std::io::{Seek, SeekFrom};
+use std::io::{BufReader, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
@@ -32,10 +32,11 @@ use logging::{debug_log, info_log};
 
 use ::metadata::MetadataOptions;
 
-use crate::fuzzy::{FuzzyMatcher, trace_fuzzy_basis_selected};
+use crate::delta::{DeltaGenerator, DeltaSignatureIndex};
+use crate::fuzzy::{FUZZY_LEVEL_2, FuzzyMatcher, trace_fuzzy_basis_selected};
 use crate::local_copy::{
-    CopyContext, CopyMethodKind, CreatedEntryKind, LocalCopyAction, LocalCopyChangeSet,
-    LocalCopyError, LocalCopyExecution, LocalCopyMetadata, LocalCopyRecord,
+    CopyContext, CreatedEntryKind, LocalCopyAction, LocalCopyChangeSet, LocalCopyError,
+    LocalCopyExecution, LocalCopyMetadata, LocalCopyRecord, resolve_reference_candidate,
 };
 
 use super::super::super::append::{AppendMode, determine_append_mode};
@@ -56,7 +57,8 @@ use skip::{record_metadata_only_skip, try_skip_up_to_date};
 ///
 /// This is the core transfer function that handles all write strategies
 /// (append, inplace, temp-file, direct-write) and integrates delta transfer
-/// when a usable basis file exists at the destination. The caller is
+/// when a usable basis file exists at the destination, as a fuzzy match, or
+/// in a reference directory. The caller is
 /// responsible for pre-checks (dry-run, size filters, link processing).
 #[allow(clippy::too_many_arguments)]
 pub(in crate::local_copy) fn execute_transfer(
@@ -151,18 +153,20 @@ pub(in crate::local_copy) fn execute_transfer(
     // upstream: receiver.c - the basis file must be read while it still exists
     // at the destination path. If backup runs first, the rename causes ENOENT
     // which is_vanished_error() misclassifies as a source vanish (exit 24).
-    let delta_signature = if !whole_file_enabled {
-        match existing_metadata {
+    let delta_basis = if !whole_file_enabled {
+        let primary = match existing_metadata {
             Some(existing) if existing.is_file() => {
-                build_delta_signature(destination, existing, context.block_size_override())?
+                Some((destination.to_path_buf(), existing.clone()))
             }
-            _ => match fuzzy_basis {
-                Some((ref path, ref meta)) => {
-                    build_delta_signature(path, meta, context.block_size_override())?
-                }
-                None => None,
-            },
-        }
+            _ => fuzzy_basis,
+        };
+        select_delta_basis(
+            context,
+            copy_source_override.as_deref().unwrap_or(source),
+            destination,
+            relative,
+            primary,
+        )?
     } else {
         None
     };
@@ -174,11 +178,17 @@ pub(in crate::local_copy) fn execute_transfer(
     // here we track the new path because we cannot hold the fd across the
     // temp-file/inplace writer setup.
     //
-    // A fuzzy basis is likewise a file separate from the (absent) destination:
-    // the writer creates a fresh destination while matched blocks are read from
-    // the fuzzy candidate, so seed the override with its path.
-    let mut delta_basis_override: Option<PathBuf> =
-        fuzzy_basis.as_ref().map(|(path, _)| path.clone());
+    // A fuzzy or reference-directory basis is likewise a file separate from
+    // the destination: the writer creates a fresh destination while matched
+    // blocks are read from the chosen candidate, so seed the override with
+    // its path.
+    let (delta_signature, mut delta_basis_override) = match delta_basis {
+        Some((path, index)) => {
+            let separate = (path != destination).then_some(path);
+            (Some(index), separate)
+        }
+        None => (None, None),
+    };
     if let Some(existing) = existing_metadata {
         // upstream: generator.c:1862,1898 - under --inplace, --backup COPIES the
         // pre-image aside (preserving the destination inode for the in-place
@@ -202,6 +212,7 @@ pub(in crate::local_copy) fn execute_transfer(
             // only built for regular files, so this condition is sufficient.
             // upstream: receiver.c:872-876 (FNAMECMP_BACKUP).
             if delta_signature.is_some()
+                && delta_basis_override.is_none()
                 && context.options().backup_enabled()
                 && !context.mode().is_dry_run()
             {
@@ -711,7 +722,7 @@ pub(in crate::local_copy) fn execute_transfer(
         .record_file(file_size, outcome.literal_bytes(), compressed_bytes);
     context
         .summary_mut()
-        .record_copy_method(CopyMethodKind::Standard);
+        .record_copy_method(outcome.copy_method());
     context.summary_mut().record_elapsed(elapsed);
 
     let mut metadata_snapshot = LocalCopyMetadata::from_metadata(metadata, None)
@@ -851,7 +862,26 @@ fn find_fuzzy_basis(
     let target_name = destination.file_name()?;
     let dest_dir = destination.parent()?;
 
-    let matcher = FuzzyMatcher::with_level(context.fuzzy_level_enabled());
+    // upstream: options.c - `-yy` sets `fuzzy_basis = basis_dir_cnt + 1`, so
+    // the same directory inside every --compare-dest/--copy-dest/--link-dest
+    // is searched after the destination directory.
+    let level = context.fuzzy_level_enabled();
+    let mut matcher = FuzzyMatcher::with_level(level);
+    if level >= FUZZY_LEVEL_2
+        && let Some(relative) = relative
+    {
+        let basis_dirs = context
+            .reference_directories()
+            .iter()
+            .filter_map(|reference| {
+                resolve_reference_candidate(reference.path(), relative, destination)
+                    .parent()
+                    .map(Path::to_path_buf)
+            })
+            .filter(|dir| dir.is_dir())
+            .collect();
+        matcher = matcher.with_fuzzy_basis_dirs(basis_dirs);
+    }
     let candidate = matcher.find_fuzzy_basis(target_name, dest_dir, file_size, target_mtime)?;
 
     let meta = fs::symlink_metadata(&candidate.path).ok()?;
@@ -879,6 +909,66 @@ fn find_fuzzy_basis(
     Some((candidate.path, meta))
 }
 
+/// Chooses the delta basis among `primary` (the destination or a fuzzy match)
+/// and the same path inside every reference directory.
+///
+/// A snapshot backup run with several `--link-dest` directories often finds
+/// an older copy of a changed file in each of them. Upstream's generator
+/// takes the first basis that exists (generator.c:975-995 `try_dests_reg()`);
+/// this oc-rsync extension scans the source against every candidate with
+/// [`DeltaGenerator::generate_best_of_reader`] and keeps the one that leaves
+/// the fewest literal bytes. `primary` is listed first so it wins ties.
+///
+/// Returns the chosen basis path with its signature index, or `None` when no
+/// candidate yields a signature.
+fn select_delta_basis(
+    context: &CopyContext,
+    source: &Path,
+    destination: &Path,
+    relative: Option<&Path>,
+    primary: Option<(PathBuf, fs::Metadata)>,
+) -> Result<Option<(PathBuf, DeltaSignatureIndex)>, LocalCopyError> {
+    let references = relative
+        .filter(|relative| !relative.as_os_str().is_empty())
+        .into_iter()
+        .flat_map(|relative| {
+            context
+                .reference_directories()
+                .iter()
+                .filter_map(move |reference| {
+                    let path = resolve_reference_candidate(reference.path(), relative, destination);
+                    let meta = fs::symlink_metadata(&path).ok()?;
+                    meta.is_file().then_some((path, meta))
+                })
+        });
+
+    let mut candidates: Vec<(PathBuf, DeltaSignatureIndex)> = Vec::new();
+    for (path, meta) in primary.into_iter().chain(references) {
+        if let Some(index) = build_delta_signature(&path, &meta, context.block_size_override())? {
+            candidates.push((path, index));
+        }
+    }
+    if candidates.len() <= 1 {
+        return Ok(candidates.pop());
+    }
+
+    let reader = open_source_file(source, context.open_noatime_enabled())
+        .map_err(|error| LocalCopyError::io("copy file", source, error))?;
+    let indexes: Vec<&DeltaSignatureIndex> = candidates.iter().map(|(_, index)| index).collect();
+    let best = DeltaGenerator::new()
+        .generate_best_of_reader(BufReader::new(reader), &indexes)
+        .map_err(|error| LocalCopyError::io("copy file", source, error))?;
+    let position = best.map_or(0, |best| best.basis);
+    debug_log!(
+        Deltasum,
+        1,
+        "basis for {}: {}",
+        relative.unwrap_or(destination).display(),
+        candidates[position].0.display()
+    );
+    Ok(Some(candidates.swap_remove(position)))
+}
+
 /// Converts a [`std::time::SystemTime`] to whole seconds since the Unix epoch
 /// for the fuzzy size/modtime fast-path comparison. upstream: generator.c:858
 /// `same_time(fp->modtime, 0, file->modtime, 0)`.
@@ -141,6 +141,9 @@ pub(super) fn try_copy(
     context
         .summary_mut()
         .record_copy_method(CopyMethodKind::from_platform(dispatched_method));
+    if dispatched_method == fast_io::CopyMethod::ReFsReflink {
+        context.summary_mut().record_cloned_bytes(file_size);
+    }
     context.summary_mut().record_elapsed(start.elapsed());
 
     // Normalize copied metadata to match what open()-created files have.
@@ -81,7 +81,11 @@ pub(in crate::local_copy) fn finalize_guard_and_metadata(
                 "renaming temp file to {}",
                 destination_path.display()
             );
-            let cross_device = guard.commit()?;
+            let cross_device = if metadata_options.fileflags() {
+                guard.commit_mutable()?
+            } else {
+                guard.commit()?
+            };
             // After a cross-device commit the destination is a new inode created
             // by fs::copy - any open fd still refers to the now-unlinked temp
             // file. Invalidate it so metadata is applied via path-based syscalls
@@ -7,18 +7,21 @@
 //!
 //! # Submodules
 //!
+//! - `device` - `--write-devices` writes into an existing destination device
 //! - `execute` - main transfer pipeline (`execute_transfer`)
 //! - `finalize` - guard commit and metadata application
 //! - `open` - source file opening with `O_NOATIME` support
 //! - `special` - non-regular files copied as empty regular files
 //! - `write_strategy` - write strategy selection (append, inplace, direct, temp-file)
 
+mod device;
 mod execute;
 mod finalize;
 mod open;
 mod special;
 mod write_strategy;
 
+pub(super) use device::copy_into_device;
 pub(super) use execute::execute_transfer;
 #[cfg(test)]
 pub(crate) use open::take_fsync_call_count;
@@ -370,6 +370,31 @@ impl DestinationWriteGuard {
         Ok(cross_device)
     }
 
+    /// Commits like [`commit`](Self::commit) with the destination's immutable
+    /// and append-only flags cleared first, restoring them if the commit
+    /// fails. Used under `--fileflags`; the committed file receives the
+    /// source's flags when its metadata is applied.
+    ///
+    /// upstream: patches/fileflags.diff - `make_mutable()` before the rename.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the flags cannot be cleared or the commit fails.
+    pub fn commit_mutable(self) -> Result<bool, LocalCopyError> {
+        let destination = self.final_path.clone();
+        let saved = ::metadata::make_mutable(&destination).map_err(|error| {
+            let (_, path, source) = error.into_parts();
+            LocalCopyError::io("clear file flags on", path, source)
+        })?;
+        let result = self.commit();
+        if result.is_err()
+            && let Some(flags) = saved
+        {
+            let _ = ::metadata::undo_make_mutable(&destination, flags);
+        }
+        result
+    }
+
     /// Commits a named temp file via rename with retry logic.
     ///
     /// On Linux 5.11+ with io_uring available, the rename is submitted as an
@@ -21,7 +21,8 @@ pub use backup_trace::{
 #[cfg(test)]
 pub(crate) use comparison::files_checksum_match;
 pub(crate) use comparison::{
-    CopyComparison, DEFAULT_XXH64_DEDUP_SIZE_LIMIT, should_skip_copy, system_time_within_window,
+    CopyComparison, DEFAULT_XXH64_DEDUP_SIZE_LIMIT, quick_check_times_match, should_skip_copy,
+    system_time_within_window,
 };
 pub(crate) use copy::copy_file;
 #[cfg(test)]
@@ -23,8 +23,8 @@ pub(crate) use directory::{
 pub(crate) use file::take_fsync_call_count;
 pub(crate) use file::{
     CopyComparison, DEFAULT_XXH64_DEDUP_SIZE_LIMIT, SparseWriteState, copy_entry_to_backup,
-    copy_file, create_backup_parents, should_skip_copy, system_time_within_window,
-    write_sparse_chunk,
+    copy_file, create_backup_parents, quick_check_times_match, should_skip_copy,
+    system_time_within_window, write_sparse_chunk,
 };
 pub use file::{
     DestinationWriteGuard, PartialFileManager, PartialMode, SparseDetectStrategy, SparseDetector,
@@ -43,6 +43,7 @@ pub(crate) use iconv::{
 pub(crate) use reference::{
     ReferenceDecision, ReferenceQuery, find_compare_dest_symlink, find_copy_dest_basis,
     find_copy_dest_symlink, find_reference_action, reference_attrs_unchanged,
+    resolve_reference_candidate,
 };
 pub(crate) use sources::copy_sources;
 pub(crate) use special::{
@@ -71,7 +71,9 @@ pub(crate) fn copy_sources(
     // upstream: generator.c:2290-2295 - the generator prints the
     // delta-transmission status once, gated on DEBUG_GTE(FLIST, 1) (first
     // active at -vv). Local copies default to whole-file mode; delta is
-    // used only when --no-whole-file is explicitly set.
+    // used when --no-whole-file is set and, as an oc-rsync extension, per
+    // file for a large destination resembling its source (see
+    // `LocalCopyOptions::whole_file_for`).
     debug_log!(
         Flist,
         1,
@@ -104,10 +104,11 @@ pub(crate) fn sync_acls_if_requested(
 ///
 /// NFSv4 ACLs are stored in the `system.nfs4_acl` extended attribute and use
 /// a different permission model than POSIX ACLs (ACE-based with inheritance).
-/// This function copies the NFSv4 ACL from source to destination when:
+/// This function synchronizes the NFSv4 ACL when:
 /// - `preserve_nfsv4_acls` is true
 /// - The operation is not a dry run
-/// - The source has an NFSv4 ACL
+/// - The source has an NFSv4 ACL, or the destination filesystem reports NFSv4
+///   ACL support, in which case the source's POSIX ACL is translated
 #[cfg(all(unix, feature = "xattr"))]
 pub(crate) fn sync_nfsv4_acls_if_requested(
     preserve_nfsv4_acls: bool,
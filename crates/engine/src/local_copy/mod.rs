@@ -50,6 +50,7 @@
 //! ```
 
 pub mod buffer_pool;
+mod case_collision;
 pub mod clonefile;
 mod compressor;
 mod context;
@@ -78,7 +79,9 @@ pub mod pipelined_state;
 mod plan;
 pub(crate) mod prefetch;
 mod skip_compress;
+mod transfer_order;
 pub mod win_copy;
+mod windows_names;
 
 pub use buffer_pool::{
     BorrowedBufferGuard, BufferAllocator, BufferGuard, BufferPool, BufferPoolStats,
@@ -86,6 +89,7 @@ pub use buffer_pool::{
     MAX_BUFFER_POOL_BLOCK_SIZE, PageAlignedBufferGuard, PageAlignedBufferPool, ThroughputTracker,
     global_buffer_pool, init_global_buffer_pool,
 };
+pub use case_collision::{CaseCollision, CaseCollisionDetector, CaseCollisionPolicy};
 pub use deferred_sync::{DeferredSync, SyncStrategy};
 
 pub use plan::{
@@ -95,8 +99,8 @@ pub use plan::{
 };
 
 pub use options::{
-    BuilderError, DeleteTiming, LocalCopyOptions, LocalCopyOptionsBuilder, ReferenceDirectory,
-    ReferenceDirectoryKind,
+    AUTO_DELTA_MIN_SIZE, BuilderError, DeleteTiming, LocalCopyOptions, LocalCopyOptionsBuilder,
+    ReferenceDirectory, ReferenceDirectoryKind,
 };
 
 pub use error::{LocalCopyArgumentError, LocalCopyError, LocalCopyErrorKind, upstream_io_error};
@@ -105,6 +109,11 @@ pub use error::{LocalCopyArgumentError, LocalCopyError, LocalCopyErrorKind, upst
 pub(crate) use plan::FilterOutcome;
 
 pub use skip_compress::{SkipCompressList, SkipCompressParseError};
+pub use transfer_order::TransferOrder;
+pub use windows_names::{
+    WindowsNameAction, WindowsNameSanitizer, WindowsNamesPolicy, invalid_windows_name_error,
+    is_valid_windows_name, sanitize_windows_name, sanitize_windows_path,
+};
 
 pub(crate) use compressor::ActiveCompressor;
 pub(crate) use context::{
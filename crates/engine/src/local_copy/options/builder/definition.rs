@@ -14,10 +14,13 @@ use filters::FilterSet;
 use protocol::iconv::FilenameConverter;
 
 use crate::batch::BatchWriter;
+use crate::local_copy::case_collision::CaseCollisionPolicy;
 use crate::local_copy::executor::{DEFAULT_XXH64_DEDUP_SIZE_LIMIT, SparseDetectStrategy};
 use crate::local_copy::filter_program::FilterProgram;
 use crate::local_copy::options::types::{DeleteTiming, LinkDestEntry, ReferenceDirectory};
 use crate::local_copy::skip_compress::SkipCompressList;
+use crate::local_copy::transfer_order::TransferOrder;
+use crate::local_copy::windows_names::WindowsNamesPolicy;
 use crate::signature::SignatureAlgorithm;
 
 /// Builder for constructing [`LocalCopyOptions`](crate::local_copy::LocalCopyOptions) with validation.
@@ -83,6 +86,7 @@ pub struct LocalCopyOptionsBuilder {
     pub(super) preserve_times: bool,
     pub(super) preserve_atimes: bool,
     pub(super) preserve_crtimes: bool,
+    pub(super) preserve_fileflags: bool,
     pub(super) omit_link_times: bool,
     pub(super) owner_override: Option<u32>,
     pub(super) group_override: Option<u32>,
@@ -130,16 +134,20 @@ pub struct LocalCopyOptionsBuilder {
     pub(super) dirs: bool,
     pub(super) devices: bool,
     pub(super) copy_devices_as_files: bool,
+    pub(super) write_devices: bool,
     pub(super) specials: bool,
     pub(super) force_replacements: bool,
     pub(super) implied_dirs: bool,
     pub(super) mkpath: bool,
     pub(super) fuzzy_level: u8,
     pub(super) prune_empty_dirs: bool,
+    pub(super) case_collision: CaseCollisionPolicy,
+    pub(super) windows_names: WindowsNamesPolicy,
 
     pub(super) timeout: Option<Duration>,
     pub(super) contimeout: Option<Duration>,
     pub(super) stop_at: Option<SystemTime>,
+    pub(super) transfer_order: TransferOrder,
 
     #[cfg(all(any(unix, windows), feature = "xattr"))]
     pub(super) preserve_xattrs: bool,
@@ -218,6 +226,7 @@ impl LocalCopyOptionsBuilder {
             preserve_times: false,
             preserve_atimes: false,
             preserve_crtimes: false,
+            preserve_fileflags: false,
             owner_override: None,
             group_override: None,
             copy_as: None,
@@ -259,15 +268,19 @@ impl LocalCopyOptionsBuilder {
             dirs: false,
             devices: false,
             copy_devices_as_files: false,
+            write_devices: false,
             specials: false,
             force_replacements: false,
             implied_dirs: true,
             mkpath: false,
             fuzzy_level: 0,
             prune_empty_dirs: false,
+            case_collision: CaseCollisionPolicy::Off,
+            windows_names: WindowsNamesPolicy::Off,
             timeout: None,
             contimeout: None,
             stop_at: None,
+            transfer_order: TransferOrder::Name,
             #[cfg(all(any(unix, windows), feature = "xattr"))]
             preserve_xattrs: false,
             #[cfg(all(unix, feature = "xattr"))]
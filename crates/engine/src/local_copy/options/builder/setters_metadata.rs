@@ -101,6 +101,14 @@ impl LocalCopyOptionsBuilder {
         self
     }
 
+    /// Enables BSD file flag (`chflags`) preservation.
+    #[must_use]
+    #[doc(alias = "--fileflags")]
+    pub fn preserve_fileflags(mut self, enabled: bool) -> Self {
+        self.preserve_fileflags = enabled;
+        self
+    }
+
     /// Enables omitting link times from preservation.
     #[must_use]
     pub fn omit_link_times(mut self, enabled: bool) -> Self {
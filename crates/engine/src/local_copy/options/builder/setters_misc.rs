@@ -10,6 +10,7 @@ use metadata::ModifyWindow;
 use super::LocalCopyOptionsBuilder;
 use crate::batch::BatchWriter;
 use crate::local_copy::executor::SparseDetectStrategy;
+use crate::local_copy::transfer_order::TransferOrder;
 use crate::signature::SignatureAlgorithm;
 
 impl LocalCopyOptionsBuilder {
@@ -150,6 +151,13 @@ impl LocalCopyOptionsBuilder {
         self
     }
 
+    /// Sets the order regular files are queued for transfer.
+    #[must_use]
+    pub fn transfer_order(mut self, order: TransferOrder) -> Self {
+        self.transfer_order = order;
+        self
+    }
+
     /// Tells `--delete` to proceed even when I/O errors occurred during the transfer.
     #[must_use]
     pub fn ignore_errors(mut self, enabled: bool) -> Self {
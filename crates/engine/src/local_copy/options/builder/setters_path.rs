@@ -1,6 +1,8 @@
 //! Setter methods for path behavior, symlink handling, and traversal options.
 
 use super::LocalCopyOptionsBuilder;
+use crate::local_copy::case_collision::CaseCollisionPolicy;
+use crate::local_copy::windows_names::WindowsNamesPolicy;
 
 impl LocalCopyOptionsBuilder {
     /// Enables opening files without updating access time.
@@ -144,6 +146,13 @@ impl LocalCopyOptionsBuilder {
         self
     }
 
+    /// Enables writing file data into existing destination devices.
+    #[must_use]
+    pub fn write_devices(mut self, enabled: bool) -> Self {
+        self.write_devices = enabled;
+        self
+    }
+
     /// Enables special file handling.
     #[must_use]
     pub fn specials(mut self, enabled: bool) -> Self {
@@ -185,4 +194,18 @@ impl LocalCopyOptionsBuilder {
         self.prune_empty_dirs = enabled;
         self
     }
+
+    /// Sets how entries that collide after case folding are handled.
+    #[must_use]
+    pub fn case_collision(mut self, policy: CaseCollisionPolicy) -> Self {
+        self.case_collision = policy;
+        self
+    }
+
+    /// Sets how names that are invalid on Windows are handled.
+    #[must_use]
+    pub fn windows_names(mut self, policy: WindowsNamesPolicy) -> Self {
+        self.windows_names = policy;
+        self
+    }
 }
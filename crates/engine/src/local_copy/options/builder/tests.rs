@@ -10,6 +10,7 @@ use compress::zlib::CompressionLevel;
 
 use super::*;
 use crate::local_copy::options::types::{DeleteTiming, LocalCopyOptions};
+use crate::local_copy::transfer_order::TransferOrder;
 
 mod builder_creation {
     use super::*;
@@ -827,4 +828,14 @@ mod timeout_options {
 
         assert!(options.stop_at().is_some());
     }
+
+    #[test]
+    fn transfer_order_sets_value() {
+        let options = LocalCopyOptionsBuilder::new()
+            .transfer_order(TransferOrder::Mtime)
+            .build()
+            .expect("valid options");
+
+        assert_eq!(options.transfer_order(), TransferOrder::Mtime);
+    }
 }
@@ -142,6 +142,7 @@ impl LocalCopyOptionsBuilder {
             preserve_times: self.preserve_times,
             preserve_atimes: self.preserve_atimes,
             preserve_crtimes: self.preserve_crtimes,
+            preserve_fileflags: self.preserve_fileflags,
             omit_link_times: self.omit_link_times,
             owner_override: self.owner_override,
             group_override: self.group_override,
@@ -183,15 +184,19 @@ impl LocalCopyOptionsBuilder {
             dirs: self.dirs,
             devices: self.devices,
             copy_devices_as_files: self.copy_devices_as_files,
+            write_devices: self.write_devices,
             specials: self.specials,
             force_replacements: self.force_replacements,
             implied_dirs: self.implied_dirs,
             mkpath: self.mkpath,
             fuzzy_level: self.fuzzy_level,
             prune_empty_dirs: self.prune_empty_dirs,
+            case_collision: self.case_collision,
+            windows_names: self.windows_names,
             timeout: self.timeout,
             contimeout: self.contimeout,
             stop_at: self.stop_at,
+            transfer_order: self.transfer_order,
             #[cfg(all(any(unix, windows), feature = "xattr"))]
             preserve_xattrs: self.preserve_xattrs,
             #[cfg(all(unix, feature = "xattr"))]
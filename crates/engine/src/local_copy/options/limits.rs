@@ -5,6 +5,7 @@ use std::num::NonZeroU64;
 use std::time::{Duration, SystemTime};
 
 use super::types::LocalCopyOptions;
+use crate::local_copy::transfer_order::TransferOrder;
 
 impl LocalCopyOptions {
     /// Applies a minimum size filter for regular files.
@@ -81,6 +82,17 @@ impl LocalCopyOptions {
         self
     }
 
+    /// Queues regular files for transfer in `order` instead of name order.
+    ///
+    /// This corresponds to `--order=MODE`, an oc-rsync extension that lets a
+    /// time-boxed run move the newest (or smallest) files first.
+    #[must_use]
+    #[doc(alias = "--order")]
+    pub const fn with_transfer_order(mut self, order: TransferOrder) -> Self {
+        self.transfer_order = order;
+        self
+    }
+
     /// Returns the minimum file size filter configured for the run.
     pub const fn min_file_size_limit(&self) -> Option<u64> {
         self.min_file_size
@@ -127,6 +139,11 @@ impl LocalCopyOptions {
     pub const fn stop_at(&self) -> Option<SystemTime> {
         self.stop_at
     }
+
+    /// Returns the order regular files are queued for transfer.
+    pub const fn transfer_order(&self) -> TransferOrder {
+        self.transfer_order
+    }
 }
 
 #[cfg(test)]
@@ -262,5 +279,12 @@ mod tests {
         assert!(opts.bandwidth_burst_bytes().is_none());
         assert!(opts.timeout().is_none());
         assert!(opts.stop_at().is_none());
+        assert_eq!(opts.transfer_order(), TransferOrder::Name);
+    }
+
+    #[test]
+    fn with_transfer_order_sets_value() {
+        let opts = LocalCopyOptions::new().with_transfer_order(TransferOrder::Size);
+        assert_eq!(opts.transfer_order(), TransferOrder::Size);
     }
 }
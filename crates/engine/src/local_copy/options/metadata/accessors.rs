@@ -86,6 +86,12 @@ impl LocalCopyOptions {
         self.preserve_crtimes
     }
 
+    /// Reports whether BSD file flags should be preserved.
+    #[must_use]
+    pub const fn preserve_fileflags(&self) -> bool {
+        self.preserve_fileflags
+    }
+
     /// Reports whether directory modification times should be skipped during metadata preservation.
     #[must_use]
     pub const fn omit_dir_times_enabled(&self) -> bool {
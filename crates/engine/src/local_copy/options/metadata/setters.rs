@@ -129,6 +129,19 @@ impl LocalCopyOptions {
         self
     }
 
+    /// Requests that BSD file flags (`chflags`) be preserved when applying
+    /// metadata.
+    ///
+    /// This corresponds to the `--fileflags` option, an oc-rsync extension.
+    /// File flags exist only on macOS and FreeBSD; on other platforms the flag
+    /// is accepted but has no effect.
+    #[must_use]
+    #[doc(alias = "--fileflags")]
+    pub const fn fileflags(mut self, preserve: bool) -> Self {
+        self.preserve_fileflags = preserve;
+        self
+    }
+
     /// Skips preserving directory modification times even when [`Self::times`] is enabled.
     #[must_use]
     #[doc(alias = "--omit-dir-times")]
@@ -71,6 +71,13 @@ fn crtimes_defaults_to_false() {
     assert!(!options.preserve_crtimes());
 }
 
+#[test]
+fn fileflags_preservation() {
+    let options = LocalCopyOptions::new().fileflags(true);
+    assert!(options.preserve_fileflags());
+    assert!(!LocalCopyOptions::new().preserve_fileflags());
+}
+
 #[test]
 fn omit_dir_times() {
     let options = LocalCopyOptions::new().omit_dir_times(true);
@@ -22,4 +22,5 @@ pub(crate) mod staging;
 mod types;
 
 pub use builder::{BuilderError, LocalCopyOptionsBuilder};
+pub use path_behavior::AUTO_DELTA_MIN_SIZE;
 pub use types::{DeleteTiming, LocalCopyOptions, ReferenceDirectory, ReferenceDirectoryKind};
@@ -2,6 +2,15 @@
 //! whole-file transfer behaviour on [`LocalCopyOptions`].
 
 use super::types::LocalCopyOptions;
+use crate::local_copy::case_collision::CaseCollisionPolicy;
+use crate::local_copy::windows_names::WindowsNamesPolicy;
+
+/// Smallest destination file that auto mode delta-transfers in place of a
+/// whole-file copy (64 MiB).
+///
+/// oc-rsync extension: upstream always copies local files whole
+/// (options.c - `whole_file` defaults on when neither side is remote).
+pub const AUTO_DELTA_MIN_SIZE: u64 = 64 * 1024 * 1024;
 
 impl LocalCopyOptions {
     /// Controls whether whole-file transfers are forced even when delta mode is requested.
@@ -177,6 +186,31 @@ impl LocalCopyOptions {
         self
     }
 
+    /// Detects entries whose relative paths collide after case folding.
+    ///
+    /// This corresponds to `--case-collision=MODE` (and `--ignore-case`, which
+    /// selects [`CaseCollisionPolicy::Warn`]), an oc-rsync extension guarding
+    /// against silent overwrites on case-insensitive destinations.
+    #[must_use]
+    #[doc(alias = "--case-collision")]
+    #[doc(alias = "--ignore-case")]
+    pub const fn case_collision(mut self, policy: CaseCollisionPolicy) -> Self {
+        self.case_collision = policy;
+        self
+    }
+
+    /// Checks every destination name against the Windows filename rules.
+    ///
+    /// This corresponds to `--windows-names=MODE`, an oc-rsync extension that
+    /// sanitizes, skips, or fails on names NTFS cannot store instead of
+    /// letting the transfer abort midway.
+    #[must_use]
+    #[doc(alias = "--windows-names")]
+    pub const fn windows_names(mut self, policy: WindowsNamesPolicy) -> Self {
+        self.windows_names = policy;
+        self
+    }
+
     /// Requests that device nodes be copied.
     #[must_use]
     #[doc(alias = "--devices")]
@@ -193,6 +227,15 @@ impl LocalCopyOptions {
         self
     }
 
+    /// Writes file data into a block or character device already present at
+    /// the destination instead of replacing the device node.
+    #[must_use]
+    #[doc(alias = "--write-devices")]
+    pub const fn write_devices(mut self, write: bool) -> Self {
+        self.write_devices = write;
+        self
+    }
+
     /// Requests that special files such as FIFOs be copied.
     #[must_use]
     #[doc(alias = "--specials")]
@@ -269,6 +312,29 @@ impl LocalCopyOptions {
         }
     }
 
+    /// Decides whole-file versus delta transfer for one file.
+    ///
+    /// An explicit `--whole-file` / `--no-whole-file` and the batch-writing
+    /// default of [`whole_file_enabled`](Self::whole_file_enabled) apply
+    /// unchanged. Otherwise a local copy is whole-file unless the destination
+    /// already holds a regular file (`basis_len`) of at least
+    /// [`AUTO_DELTA_MIN_SIZE`] bytes whose length is within 1/8 of
+    /// `source_len`: such a file is most likely an earlier version of the
+    /// source, so matching its blocks saves rewriting the unchanged data.
+    #[must_use]
+    pub const fn whole_file_for(&self, source_len: u64, basis_len: Option<u64>) -> bool {
+        if self.whole_file.is_some() || !self.whole_file_enabled() {
+            return self.whole_file_enabled();
+        }
+        let Some(basis_len) = basis_len else {
+            return true;
+        };
+        if basis_len < AUTO_DELTA_MIN_SIZE || source_len < AUTO_DELTA_MIN_SIZE {
+            return true;
+        }
+        source_len.abs_diff(basis_len) > source_len / 8
+    }
+
     /// Returns the raw tri-state whole-file setting.
     ///
     /// - `Some(true)`: explicitly forced whole-file mode.
@@ -379,6 +445,18 @@ impl LocalCopyOptions {
         self.prune_empty_dirs
     }
 
+    /// Returns the configured case-collision policy.
+    #[must_use]
+    pub const fn case_collision_policy(&self) -> CaseCollisionPolicy {
+        self.case_collision
+    }
+
+    /// Returns the configured Windows filename policy.
+    #[must_use]
+    pub const fn windows_names_policy(&self) -> WindowsNamesPolicy {
+        self.windows_names
+    }
+
     /// Reports whether copying of device nodes has been requested.
     #[must_use]
     pub const fn devices_enabled(&self) -> bool {
@@ -392,6 +470,13 @@ impl LocalCopyOptions {
         self.copy_devices_as_files
     }
 
+    /// Returns whether file data is written into existing destination devices.
+    #[must_use]
+    #[doc(alias = "--write-devices")]
+    pub const fn write_devices_enabled(&self) -> bool {
+        self.write_devices
+    }
+
     /// Reports whether copying of special files has been requested.
     #[must_use]
     pub const fn specials_enabled(&self) -> bool {
@@ -629,6 +714,13 @@ mod tests {
         assert!(!opts.copy_devices_as_files_enabled());
     }
 
+    #[test]
+    fn write_devices_enables() {
+        let opts = LocalCopyOptions::new().write_devices(true);
+        assert!(opts.write_devices_enabled());
+        assert!(!LocalCopyOptions::new().write_devices_enabled());
+    }
+
     #[test]
     fn specials_enables() {
         let opts = LocalCopyOptions::new().specials(true);
@@ -698,12 +790,61 @@ mod tests {
         assert!(opts.recursive_enabled());
     }
 
+    #[test]
+    fn case_collision_defaults_off_and_round_trips() {
+        assert_eq!(
+            LocalCopyOptions::new().case_collision_policy(),
+            CaseCollisionPolicy::Off
+        );
+        let opts = LocalCopyOptions::new().case_collision(CaseCollisionPolicy::Rename);
+        assert_eq!(opts.case_collision_policy(), CaseCollisionPolicy::Rename);
+    }
+
+    #[test]
+    fn windows_names_defaults_off_and_round_trips() {
+        assert_eq!(
+            LocalCopyOptions::new().windows_names_policy(),
+            WindowsNamesPolicy::Off
+        );
+        let opts = LocalCopyOptions::new().windows_names(WindowsNamesPolicy::Skip);
+        assert_eq!(opts.windows_names_policy(), WindowsNamesPolicy::Skip);
+    }
+
     #[test]
     fn default_implied_dirs_is_true() {
         let opts = LocalCopyOptions::new();
         assert!(opts.implied_dirs_enabled());
     }
 
+    #[test]
+    fn auto_mode_deltas_large_similar_files() {
+        let opts = LocalCopyOptions::new();
+        let big = AUTO_DELTA_MIN_SIZE * 2;
+        assert!(!opts.whole_file_for(big, Some(big + big / 16)));
+        assert!(opts.whole_file_for(big, Some(big / 2)), "dissimilar sizes");
+        assert!(opts.whole_file_for(big, None), "no basis");
+        let small = AUTO_DELTA_MIN_SIZE - 1;
+        assert!(
+            opts.whole_file_for(small, Some(small)),
+            "below the threshold"
+        );
+    }
+
+    #[test]
+    fn explicit_whole_file_overrides_auto_delta() {
+        let big = AUTO_DELTA_MIN_SIZE * 2;
+        assert!(
+            LocalCopyOptions::new()
+                .whole_file(true)
+                .whole_file_for(big, Some(big))
+        );
+        assert!(
+            !LocalCopyOptions::new()
+                .whole_file(false)
+                .whole_file_for(1, None)
+        );
+    }
+
     #[test]
     fn default_whole_file_is_auto() {
         let opts = LocalCopyOptions::new();
@@ -19,9 +19,12 @@ use filters::FilterSet;
 use protocol::iconv::FilenameConverter;
 
 use crate::batch::BatchWriter;
+use crate::local_copy::case_collision::CaseCollisionPolicy;
 use crate::local_copy::executor::{DEFAULT_XXH64_DEDUP_SIZE_LIMIT, SparseDetectStrategy};
 use crate::local_copy::filter_program::FilterProgram;
 use crate::local_copy::skip_compress::SkipCompressList;
+use crate::local_copy::transfer_order::TransferOrder;
+use crate::local_copy::windows_names::WindowsNamesPolicy;
 use crate::signature::SignatureAlgorithm;
 
 /// Controls when deletion sweeps run relative to content transfers.
@@ -137,6 +140,7 @@ pub struct LocalCopyOptions {
     pub(super) preserve_times: bool,
     pub(super) preserve_atimes: bool,
     pub(super) preserve_crtimes: bool,
+    pub(super) preserve_fileflags: bool,
     pub(super) omit_link_times: bool,
     pub(super) owner_override: Option<u32>,
     pub(super) group_override: Option<u32>,
@@ -215,6 +219,7 @@ pub struct LocalCopyOptions {
     pub(super) dirs: bool,
     pub(super) devices: bool,
     pub(super) copy_devices_as_files: bool,
+    pub(super) write_devices: bool,
     pub(super) specials: bool,
     pub(super) force_replacements: bool,
     pub(super) implied_dirs: bool,
@@ -226,9 +231,12 @@ pub struct LocalCopyOptions {
     /// use as the delta basis. Mirrors upstream `fuzzy_basis` in `options.c`.
     pub(super) fuzzy_level: u8,
     pub(super) prune_empty_dirs: bool,
+    pub(super) case_collision: CaseCollisionPolicy,
+    pub(super) windows_names: WindowsNamesPolicy,
     pub(super) timeout: Option<Duration>,
     pub(super) contimeout: Option<Duration>,
     pub(super) stop_at: Option<SystemTime>,
+    pub(super) transfer_order: TransferOrder,
     #[cfg(all(any(unix, windows), feature = "xattr"))]
     pub(super) preserve_xattrs: bool,
     #[cfg(all(unix, feature = "xattr"))]
@@ -310,6 +318,7 @@ impl LocalCopyOptions {
             preserve_times: false,
             preserve_atimes: false,
             preserve_crtimes: false,
+            preserve_fileflags: false,
             owner_override: None,
             group_override: None,
             copy_as: None,
@@ -351,15 +360,19 @@ impl LocalCopyOptions {
             dirs: false,
             devices: false,
             copy_devices_as_files: false,
+            write_devices: false,
             specials: false,
             force_replacements: false,
             implied_dirs: true,
             mkpath: false,
             fuzzy_level: 0,
             prune_empty_dirs: false,
+            case_collision: CaseCollisionPolicy::Off,
+            windows_names: WindowsNamesPolicy::Off,
             timeout: None,
             contimeout: None,
             stop_at: None,
+            transfer_order: TransferOrder::Name,
             #[cfg(all(any(unix, windows), feature = "xattr"))]
             preserve_xattrs: false,
             #[cfg(all(unix, feature = "xattr"))]
@@ -28,7 +28,7 @@ pub struct FileTypeTotals {
 /// acceleration ran. Upstream rsync has no equivalent - it always reconstructs
 /// files from the wire - so this is oc-rsync-specific and only populated by the
 /// local-copy fast paths, never by remote/protocol transfers.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 pub enum CopyMethodKind {
     /// macOS `clonefile` copy-on-write clone.
     Clonefile,
@@ -43,6 +43,7 @@ pub enum CopyMethodKind {
     /// Linux io_uring registered-buffer data write.
     IoUring,
     /// Portable userspace read/write loop (or delta reconstruction).
+    #[default]
     Standard,
 }
 
@@ -95,6 +96,18 @@ impl CopyMethodKind {
             CopyMethod::Copyfile | CopyMethod::StandardCopy => Self::Standard,
         }
     }
+
+    /// Maps the tier [`fast_io::copy_file_range::copy_file_contents_tracked`]
+    /// used onto the tracked kind.
+    #[must_use]
+    pub const fn from_content_copy(method: fast_io::copy_file_range::ContentCopyMethod) -> Self {
+        use fast_io::copy_file_range::ContentCopyMethod;
+        match method {
+            ContentCopyMethod::IoUring => Self::IoUring,
+            ContentCopyMethod::CopyFileRange => Self::CopyFileRange,
+            ContentCopyMethod::ReadWrite => Self::Standard,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
@@ -162,6 +175,10 @@ pub struct LocalCopySummary {
     // Per-method copy counts, indexed by `CopyMethodKind as usize`. Populated
     // only by the local-copy fast paths; drives the `Copy method` stats line.
     copy_methods: [u64; 7],
+    // Bytes shared through copy-on-write clones (reflink, `clonefile`, ReFS
+    // block cloning). `copy_file_range` is not counted: on filesystems
+    // without extent sharing it is an in-kernel copy, not a clone.
+    cloned_bytes: u64,
 }
 
 impl LocalCopySummary {
@@ -438,6 +455,20 @@ impl LocalCopySummary {
         self.copy_methods[index] = self.copy_methods[index].saturating_add(1);
     }
 
+    /// Records bytes shared through a copy-on-write clone (reflink,
+    /// `clonefile`, ReFS block cloning).
+    pub(in crate::local_copy) const fn record_cloned_bytes(&mut self, bytes: u64) {
+        self.cloned_bytes = self.cloned_bytes.saturating_add(bytes);
+    }
+
+    /// Returns the bytes shared through copy-on-write clones. Copies made
+    /// with `copy_file_range` appear under their own `Copy method` entry
+    /// instead, since the kernel may have duplicated the data.
+    #[must_use]
+    pub const fn cloned_bytes(&self) -> u64 {
+        self.cloned_bytes
+    }
+
     /// Returns the per-method copy breakdown as `(label, count)` pairs, in
     /// display order, omitting methods that were never used. Empty when no
     /// local-copy fast path ran (e.g. a remote/protocol transfer).
@@ -714,6 +745,7 @@ impl LocalCopySummary {
             file_list_transfer: Duration::ZERO,
             destination_root_created: false,
             copy_methods: [0; 7],
+            cloned_bytes: 0,
         }
     }
 
@@ -1394,6 +1426,32 @@ mod tests {
         assert!(!summary.used_copy_acceleration());
     }
 
+    #[test]
+    fn cloned_bytes_accumulate() {
+        let mut summary = LocalCopySummary::default();
+        assert_eq!(summary.cloned_bytes(), 0);
+        summary.record_cloned_bytes(4096);
+        summary.record_cloned_bytes(1 << 20);
+        assert_eq!(summary.cloned_bytes(), 4096 + (1 << 20));
+    }
+
+    #[test]
+    fn copy_method_from_content_copy_maps_tiers() {
+        use fast_io::copy_file_range::ContentCopyMethod;
+        assert_eq!(
+            CopyMethodKind::from_content_copy(ContentCopyMethod::CopyFileRange),
+            CopyMethodKind::CopyFileRange
+        );
+        assert_eq!(
+            CopyMethodKind::from_content_copy(ContentCopyMethod::IoUring),
+            CopyMethodKind::IoUring
+        );
+        assert_eq!(
+            CopyMethodKind::from_content_copy(ContentCopyMethod::ReadWrite),
+            CopyMethodKind::Standard
+        );
+    }
+
     #[test]
     fn copy_method_from_platform_maps_zero_copy_and_fallbacks() {
         use fast_io::CopyMethod;
@@ -0,0 +1,80 @@
+// Tests for --case-collision detection in the local copy path.
+//
+// The source trees below hold names that differ only in case, so they can
+// only be created on a case-sensitive filesystem; the destination is the
+// same tempdir, which lets the tests observe where each entry was written.
+
+#[cfg(target_os = "linux")]
+#[test]
+fn case_collision_rename_writes_later_entry_under_suffixed_name() {
+    let temp = tempdir().expect("tempdir");
+    let source_root = temp.path().join("src");
+    fs::create_dir_all(source_root.join("Docs")).expect("create Docs");
+    fs::create_dir_all(source_root.join("docs")).expect("create docs");
+    fs::write(source_root.join("Makefile"), b"upper").expect("write Makefile");
+    fs::write(source_root.join("makefile"), b"lower").expect("write makefile");
+    fs::write(source_root.join("Docs/a.txt"), b"first").expect("write Docs/a.txt");
+    fs::write(source_root.join("docs/A.txt"), b"second").expect("write docs/A.txt");
+
+    let dest_root = temp.path().join("dest");
+    let mut source_operand = source_root.into_os_string();
+    source_operand.push("/");
+    let operands = vec![source_operand, dest_root.clone().into_os_string()];
+    let plan = LocalCopyPlan::from_operands(&operands).expect("plan");
+
+    plan.execute_with_options(
+        LocalCopyExecution::Apply,
+        LocalCopyOptions::default()
+            .recursive(true)
+            .case_collision(CaseCollisionPolicy::Rename),
+    )
+    .expect("copy succeeds");
+
+    assert_eq!(
+        fs::read(dest_root.join("Makefile")).expect("read"),
+        b"upper"
+    );
+    assert_eq!(
+        fs::read(dest_root.join("makefile~1")).expect("read"),
+        b"lower"
+    );
+    assert!(!dest_root.join("makefile").exists());
+
+    // Colliding directories merge; only the colliding child is renamed.
+    assert_eq!(
+        fs::read(dest_root.join("Docs/a.txt")).expect("read"),
+        b"first"
+    );
+    assert_eq!(
+        fs::read(dest_root.join("docs/A~1.txt")).expect("read"),
+        b"second"
+    );
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn case_collision_warn_keeps_original_names() {
+    let temp = tempdir().expect("tempdir");
+    let source_root = temp.path().join("src");
+    fs::create_dir_all(&source_root).expect("create source");
+    fs::write(source_root.join("README"), b"upper").expect("write README");
+    fs::write(source_root.join("readme"), b"lower").expect("write readme");
+
+    let dest_root = temp.path().join("dest");
+    let mut source_operand = source_root.into_os_string();
+    source_operand.push("/");
+    let operands = vec![source_operand, dest_root.clone().into_os_string()];
+    let plan = LocalCopyPlan::from_operands(&operands).expect("plan");
+
+    plan.execute_with_options(
+        LocalCopyExecution::Apply,
+        LocalCopyOptions::default()
+            .recursive(true)
+            .case_collision(CaseCollisionPolicy::Warn),
+    )
+    .expect("copy succeeds");
+
+    assert_eq!(fs::read(dest_root.join("README")).expect("read"), b"upper");
+    assert_eq!(fs::read(dest_root.join("readme")).expect("read"), b"lower");
+    assert!(!dest_root.join("readme~1").exists());
+}
@@ -0,0 +1,88 @@
+// Tests for --fuzzy basis selection in local copies.
+//
+// A missing destination file may be delta-transferred against a
+// similarly-named file. `-y` searches the file's own destination directory;
+// `-yy` additionally searches the same directory inside every reference
+// directory (--compare-dest/--copy-dest/--link-dest).
+
+fn fuzzy_fixture_content() -> Vec<u8> {
+    (0..8192u32).map(|i| (i % 251) as u8).collect()
+}
+
+#[test]
+fn fuzzy_level_one_uses_sibling_in_destination_directory() {
+    let temp = tempdir().expect("tempdir");
+    let source_dir = temp.path().join("src");
+    let dest_dir = temp.path().join("dest");
+    fs::create_dir_all(source_dir.join("sub")).expect("create source");
+    fs::create_dir_all(dest_dir.join("sub")).expect("create dest");
+
+    let content = fuzzy_fixture_content();
+    fs::write(source_dir.join("sub/data.txt"), &content).expect("write source");
+    fs::write(dest_dir.join("sub/data2.txt"), &content).expect("write fuzzy basis");
+
+    let mut source_operand = source_dir.into_os_string();
+    source_operand.push("/");
+    let operands = vec![source_operand, dest_dir.clone().into_os_string()];
+    let plan = LocalCopyPlan::from_operands(&operands).expect("plan");
+    let summary = plan
+        .execute_with_options(
+            LocalCopyExecution::Apply,
+            LocalCopyOptions::default().whole_file(false).fuzzy_level(1),
+        )
+        .expect("copy succeeds");
+
+    assert_eq!(
+        fs::read(dest_dir.join("sub/data.txt")).expect("read dest"),
+        content
+    );
+    assert_eq!(summary.matched_bytes(), content.len() as u64);
+}
+
+#[test]
+fn fuzzy_level_two_searches_reference_directories() {
+    let temp = tempdir().expect("tempdir");
+    let source_dir = temp.path().join("src");
+    let dest_dir = temp.path().join("dest");
+    let reference_dir = temp.path().join("ref");
+    fs::create_dir_all(source_dir.join("sub")).expect("create source");
+    fs::create_dir_all(&dest_dir).expect("create dest");
+    fs::create_dir_all(reference_dir.join("sub")).expect("create reference");
+
+    let content = fuzzy_fixture_content();
+    fs::write(source_dir.join("sub/data.txt"), &content).expect("write source");
+    fs::write(reference_dir.join("sub/data2.txt"), &content).expect("write fuzzy basis");
+
+    let mut source_operand = source_dir.into_os_string();
+    source_operand.push("/");
+    let operands = vec![source_operand, dest_dir.clone().into_os_string()];
+    let copy = |level: u8| {
+        let plan = LocalCopyPlan::from_operands(&operands).expect("plan");
+        plan.execute_with_options(
+            LocalCopyExecution::Apply,
+            LocalCopyOptions::default()
+                .whole_file(false)
+                .fuzzy_level(level)
+                .push_reference_directory(ReferenceDirectory::new(
+                    ReferenceDirectoryKind::Compare,
+                    &reference_dir,
+                )),
+        )
+        .expect("copy succeeds")
+    };
+
+    let summary = copy(1);
+    assert_eq!(
+        summary.matched_bytes(),
+        0,
+        "-y must not look in reference dirs"
+    );
+
+    fs::remove_file(dest_dir.join("sub/data.txt")).expect("remove copied file");
+    let summary = copy(2);
+    assert_eq!(
+        fs::read(dest_dir.join("sub/data.txt")).expect("read dest"),
+        content
+    );
+    assert_eq!(summary.matched_bytes(), content.len() as u64);
+}
@@ -13,6 +13,7 @@
 // 6. Link-dest interaction with --times
 // 7. Link-dest with content differences
 // 8. Relative vs absolute link-dest paths
+// 9. Delta transfers pick the link-dest basis with the fewest literals
 
 #[cfg(unix)]
 use std::os::unix::fs::MetadataExt;
@@ -1403,3 +1404,55 @@ fn link_dest_copies_when_only_xattrs_differ() {
         .expect("basis xattr present");
     assert_eq!(basis_xattr, b"v1", "link-dest basis must not be modified");
 }
+
+#[test]
+fn link_dest_delta_uses_closest_basis() {
+    let temp = tempdir().expect("tempdir");
+    let source_dir = temp.path().join("source");
+    let older = temp.path().join("older");
+    let newer = temp.path().join("newer");
+    for dir in [&source_dir, &older, &newer] {
+        fs::create_dir_all(dir).expect("create dir");
+    }
+
+    let content: Vec<u8> = (0..64 * 1024u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+        .collect();
+    let mut stale = content.clone();
+    stale[..48 * 1024].fill(0x5a);
+    let mut recent = content.clone();
+    recent[1_000] ^= 0xFF;
+    fs::write(source_dir.join("data.bin"), &content).expect("write source");
+    fs::write(older.join("data.bin"), &stale).expect("write older");
+    fs::write(newer.join("data.bin"), &recent).expect("write newer");
+
+    let dest_dir = temp.path().join("dest");
+    let mut source_operand = source_dir.into_os_string();
+    source_operand.push("/");
+    let operands = vec![source_operand, dest_dir.clone().into_os_string()];
+    let plan = LocalCopyPlan::from_operands(&operands).expect("plan");
+    let summary = plan
+        .execute_with_options(
+            LocalCopyExecution::Apply,
+            LocalCopyOptions::default()
+                .whole_file(false)
+                .extend_reference_directories([
+                    ReferenceDirectory::new(ReferenceDirectoryKind::Link, &older),
+                    ReferenceDirectory::new(ReferenceDirectoryKind::Link, &newer),
+                ]),
+        )
+        .expect("copy succeeds");
+
+    assert_eq!(
+        fs::read(dest_dir.join("data.bin")).expect("read dest"),
+        content
+    );
+    assert!(
+        summary.matched_bytes() > 48 * 1024,
+        "the later, closer link-dest copy should serve as the basis"
+    );
+    assert_eq!(
+        fs::read(newer.join("data.bin")).expect("read basis"),
+        recent
+    );
+}
@@ -0,0 +1,68 @@
+// Tests for --order in the local copy path.
+
+fn transfer_order_fixture(temp: &Path) -> Vec<OsString> {
+    let source_root = temp.join("src");
+    fs::create_dir_all(source_root.join("sub")).expect("create sub");
+    for (name, size, mtime) in [
+        ("a.txt", 100, 1_000),
+        ("b.txt", 300, 3_000),
+        ("c.txt", 200, 2_000),
+    ] {
+        let path = source_root.join(name);
+        fs::write(&path, vec![b'x'; size]).expect("write source");
+        set_file_mtime(&path, FileTime::from_unix_time(mtime, 0)).expect("set mtime");
+    }
+
+    let mut source_operand = source_root.into_os_string();
+    source_operand.push(std::path::MAIN_SEPARATOR.to_string());
+    vec![source_operand, temp.join("dest").into_os_string()]
+}
+
+fn copied_file_order(temp: &Path, order: TransferOrder) -> Vec<String> {
+    let operands = transfer_order_fixture(temp);
+    let plan = LocalCopyPlan::from_operands(&operands).expect("plan");
+    let report = plan
+        .execute_with_report(
+            LocalCopyExecution::Apply,
+            LocalCopyOptions::default()
+                .recursive(true)
+                .with_transfer_order(order)
+                .collect_events(true),
+        )
+        .expect("copy succeeds");
+
+    report
+        .records()
+        .iter()
+        .filter(|record| record.action() == &LocalCopyAction::DataCopied)
+        .map(|record| record.relative_path().to_string_lossy().into_owned())
+        .collect()
+}
+
+#[test]
+fn transfer_order_name_copies_in_file_list_order() {
+    let temp = tempdir().expect("tempdir");
+    assert_eq!(
+        copied_file_order(temp.path(), TransferOrder::Name),
+        ["a.txt", "b.txt", "c.txt"]
+    );
+}
+
+#[test]
+fn transfer_order_mtime_copies_newest_first() {
+    let temp = tempdir().expect("tempdir");
+    assert_eq!(
+        copied_file_order(temp.path(), TransferOrder::Mtime),
+        ["b.txt", "c.txt", "a.txt"]
+    );
+    assert!(temp.path().join("dest/sub").is_dir());
+}
+
+#[test]
+fn transfer_order_size_copies_smallest_first() {
+    let temp = tempdir().expect("tempdir");
+    assert_eq!(
+        copied_file_order(temp.path(), TransferOrder::Size),
+        ["a.txt", "c.txt", "b.txt"]
+    );
+}
@@ -0,0 +1,96 @@
+// Tests for --windows-names in the local copy path.
+//
+// The source names below are valid on Unix but not on NTFS; the destination
+// is a Unix tempdir, which lets the tests observe the name each policy picks.
+
+#[cfg(unix)]
+fn windows_names_fixture(temp: &Path) -> (PathBuf, Vec<OsString>) {
+    let source_root = temp.join("src");
+    fs::create_dir_all(source_root.join("dir:1")).expect("create dir:1");
+    fs::write(source_root.join("dir:1/inner?.txt"), b"inner").expect("write inner");
+    fs::write(source_root.join("notes."), b"notes").expect("write notes.");
+    fs::write(source_root.join("plain.txt"), b"plain").expect("write plain");
+
+    let dest_root = temp.join("dest");
+    let mut source_operand = source_root.into_os_string();
+    source_operand.push("/");
+    let operands = vec![source_operand, dest_root.clone().into_os_string()];
+    (dest_root, operands)
+}
+
+#[cfg(unix)]
+#[test]
+fn windows_names_sanitize_rewrites_invalid_components() {
+    let temp = tempdir().expect("tempdir");
+    let (dest_root, operands) = windows_names_fixture(temp.path());
+    let plan = LocalCopyPlan::from_operands(&operands).expect("plan");
+
+    plan.execute_with_options(
+        LocalCopyExecution::Apply,
+        LocalCopyOptions::default()
+            .recursive(true)
+            .windows_names(WindowsNamesPolicy::Sanitize),
+    )
+    .expect("copy succeeds");
+
+    assert_eq!(
+        fs::read(dest_root.join("dir_1/inner_.txt")).expect("read"),
+        b"inner"
+    );
+    assert_eq!(fs::read(dest_root.join("notes_")).expect("read"), b"notes");
+    assert_eq!(
+        fs::read(dest_root.join("plain.txt")).expect("read"),
+        b"plain"
+    );
+    assert!(!dest_root.join("dir:1").exists());
+    assert!(!dest_root.join("notes.").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn windows_names_skip_leaves_invalid_entries_out() {
+    let temp = tempdir().expect("tempdir");
+    let (dest_root, operands) = windows_names_fixture(temp.path());
+    let plan = LocalCopyPlan::from_operands(&operands).expect("plan");
+
+    plan.execute_with_options(
+        LocalCopyExecution::Apply,
+        LocalCopyOptions::default()
+            .recursive(true)
+            .windows_names(WindowsNamesPolicy::Skip),
+    )
+    .expect("copy succeeds");
+
+    assert_eq!(
+        fs::read(dest_root.join("plain.txt")).expect("read"),
+        b"plain"
+    );
+    assert!(!dest_root.join("dir:1").exists());
+    assert!(!dest_root.join("dir_1").exists());
+    assert!(!dest_root.join("notes.").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn windows_names_fail_aborts_before_writing_invalid_name() {
+    let temp = tempdir().expect("tempdir");
+    let (dest_root, operands) = windows_names_fixture(temp.path());
+    let plan = LocalCopyPlan::from_operands(&operands).expect("plan");
+
+    let error = plan
+        .execute_with_options(
+            LocalCopyExecution::Apply,
+            LocalCopyOptions::default()
+                .recursive(true)
+                .windows_names(WindowsNamesPolicy::Fail),
+        )
+        .expect_err("invalid name aborts the copy");
+
+    assert!(matches!(
+        error.kind(),
+        LocalCopyErrorKind::InvalidWindowsName { .. }
+    ));
+    assert_eq!(error.exit_code(), 23);
+    assert!(!dest_root.join("dir:1").exists());
+    assert!(!dest_root.join("notes.").exists());
+}
@@ -984,9 +984,13 @@ include!("execute_directories.rs");
 #[cfg(unix)]
 include!("execute_created_stats.rs");
 include!("execute_prune_empty_dirs.rs");
+include!("execute_case_collision.rs");
+include!("execute_windows_names.rs");
+include!("execute_transfer_order.rs");
 include!("execute_hardlinks.rs");
 include!("execute_link_dest.rs");
 include!("execute_copy_dest.rs");
+include!("execute_fuzzy.rs");
 include!("execute_sparse.rs");
 include!("execute_min_size.rs");
 include!("max_size_filter.rs");
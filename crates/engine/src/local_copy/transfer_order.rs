@@ -0,0 +1,181 @@
+//! Transfer queue ordering for `--order`.
+//!
+//! Upstream rsync requests files in file-list order, which is sorted by name
+//! so both peers agree on every index. When a sync window is time-boxed
+//! (`--stop-after`, `--stop-at`, or an operator's Ctrl-C) the files that make
+//! it across are therefore whichever sort first alphabetically. `--order` is
+//! an oc-rsync extension that changes only the order regular files are
+//! *queued* for transfer:
+//!
+//! - [`TransferOrder::Name`] keeps upstream's file-list order.
+//! - [`TransferOrder::Mtime`] queues the most recently modified files first.
+//! - [`TransferOrder::Size`] queues the smallest files first, so the most
+//!   files complete within the window.
+//!
+//! The wire file list is untouched and stays canonically sorted; the receiver
+//! simply requests indices in a different order. Directories, symlinks, and
+//! special files keep their positions, and ties keep name order, so the
+//! members of a hard-link cohort (which share mtime and size) stay together.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+/// Order in which regular files are queued for transfer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TransferOrder {
+    /// File-list (name) order, matching upstream rsync.
+    #[default]
+    Name,
+    /// Newest modification time first.
+    Mtime,
+    /// Smallest size first.
+    Size,
+}
+
+impl TransferOrder {
+    /// Returns the canonical lowercase token used on the command line.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::Mtime => "mtime",
+            Self::Size => "size",
+        }
+    }
+
+    /// Parses a CLI token into a [`TransferOrder`].
+    ///
+    /// The match is case-insensitive. Unknown tokens are returned as the
+    /// original input for the caller to surface in an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns the original input string when no variant matches.
+    pub fn parse(value: &str) -> Result<Self, &str> {
+        let lowered = value.trim().to_ascii_lowercase();
+        match lowered.as_str() {
+            "name" => Ok(Self::Name),
+            "mtime" => Ok(Self::Mtime),
+            "size" => Ok(Self::Size),
+            _ => Err(value),
+        }
+    }
+
+    /// Returns `true` unless the order is [`TransferOrder::Name`].
+    #[must_use]
+    pub const fn reorders(self) -> bool {
+        !matches!(self, Self::Name)
+    }
+
+    /// Reorders the regular files in `items`, leaving every other item in
+    /// its slot.
+    ///
+    /// `key` returns `(mtime_seconds, size)` for an item that is queued as a
+    /// regular file and `None` for anything else. The sort is stable, so
+    /// items with equal keys keep their existing (name) order.
+    pub fn apply<T>(self, items: &mut Vec<T>, key: impl Fn(&T) -> Option<(i64, u64)>) {
+        if !self.reorders() {
+            return;
+        }
+        let mut queued: Vec<(usize, (i64, u64))> = items
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, item)| key(item).map(|k| (idx, k)))
+            .collect();
+        if queued.len() < 2 {
+            return;
+        }
+        let slots: Vec<usize> = queued.iter().map(|&(idx, _)| idx).collect();
+        queued.sort_by(|a, b| self.compare(a.1, b.1));
+
+        let mut source_for_slot: Vec<usize> = (0..items.len()).collect();
+        for (&slot, &(source, _)) in slots.iter().zip(&queued) {
+            source_for_slot[slot] = source;
+        }
+        let mut taken: Vec<Option<T>> = items.drain(..).map(Some).collect();
+        items.extend(
+            source_for_slot
+                .into_iter()
+                .filter_map(|source| taken[source].take()),
+        );
+    }
+
+    fn compare(self, (a_mtime, a_size): (i64, u64), (b_mtime, b_size): (i64, u64)) -> Ordering {
+        match self {
+            Self::Name => Ordering::Equal,
+            Self::Mtime => b_mtime.cmp(&a_mtime),
+            Self::Size => a_size.cmp(&b_size),
+        }
+    }
+}
+
+impl fmt::Display for TransferOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `(name, Some((mtime, size)))` for a file, `None` key for a directory.
+    type Item = (&'static str, Option<(i64, u64)>);
+
+    fn ordered(order: TransferOrder, mut items: Vec<Item>) -> Vec<&'static str> {
+        order.apply(&mut items, |item| item.1);
+        items.into_iter().map(|item| item.0).collect()
+    }
+
+    fn sample() -> Vec<Item> {
+        vec![
+            ("a.txt", Some((100, 30))),
+            ("b", None),
+            ("c.txt", Some((300, 10))),
+            ("d.txt", Some((200, 20))),
+        ]
+    }
+
+    #[test]
+    fn parse_accepts_each_order_case_insensitively() {
+        assert_eq!(TransferOrder::parse("name"), Ok(TransferOrder::Name));
+        assert_eq!(TransferOrder::parse("MTime"), Ok(TransferOrder::Mtime));
+        assert_eq!(TransferOrder::parse(" size "), Ok(TransferOrder::Size));
+        assert_eq!(TransferOrder::parse("atime"), Err("atime"));
+    }
+
+    #[test]
+    fn name_order_is_left_alone() {
+        assert_eq!(
+            ordered(TransferOrder::Name, sample()),
+            ["a.txt", "b", "c.txt", "d.txt"]
+        );
+    }
+
+    #[test]
+    fn mtime_queues_newest_first_around_directories() {
+        assert_eq!(
+            ordered(TransferOrder::Mtime, sample()),
+            ["c.txt", "b", "d.txt", "a.txt"]
+        );
+    }
+
+    #[test]
+    fn size_queues_smallest_first() {
+        assert_eq!(
+            ordered(TransferOrder::Size, sample()),
+            ["c.txt", "b", "d.txt", "a.txt"]
+        );
+    }
+
+    #[test]
+    fn equal_keys_keep_name_order() {
+        let items = vec![
+            ("x", Some((5, 1))),
+            ("y", Some((9, 1))),
+            ("z", Some((5, 1))),
+        ];
+        assert_eq!(ordered(TransferOrder::Size, items.clone()), ["x", "y", "z"]);
+        assert_eq!(ordered(TransferOrder::Mtime, items), ["y", "x", "z"]);
+    }
+}
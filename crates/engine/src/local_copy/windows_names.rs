@@ -0,0 +1,439 @@
+//! Windows filename validity checks for `--windows-names`.
+//!
+//! NTFS rejects names containing `<>:"/\|?*` or control characters, and the
+//! Win32 layer silently strips trailing dots and spaces, so a Unix tree with
+//! `a:b` or `notes.` cannot be mirrored onto a Windows destination: upstream
+//! rsync (and oc-rsync without this option) fails each such entry with an
+//! I/O error, often after much of the transfer has already been written.
+//! `--windows-names` is an oc-rsync extension that checks every name before
+//! it is written and applies one policy:
+//!
+//! - [`WindowsNamesPolicy::Sanitize`] replaces each offending character
+//!   (including every trailing dot or space) with `_`.
+//! - [`WindowsNamesPolicy::Skip`] leaves the entry, and anything below it,
+//!   out of the transfer.
+//! - [`WindowsNamesPolicy::Fail`] aborts the transfer at the first invalid
+//!   name, before any file under that name is created.
+//!
+//! Sanitized and skipped entries are reported with a `windows-names:`
+//! warning. A sanitized name that a sibling already uses (`a:b` next to
+//! `a_b`) gets a `~N` suffix, as [`super::CaseCollisionDetector`] renames, so
+//! neither entry overwrites the other. The checks are pure string transforms
+//! and run on every platform, so a Unix receiver writing to an SMB share can
+//! opt in as well. Reserved
+//! device names (`CON`, `NUL`, ...) are not rewritten: the destination path
+//! layer already opens them through the `\\?\` namespace.
+
+use std::collections::{HashMap, HashSet};
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use super::case_collision::disambiguated;
+
+/// Characters NTFS rejects anywhere in a file name, besides controls.
+const INVALID_CHARACTERS: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// Character substituted for each invalid character under
+/// [`WindowsNamesPolicy::Sanitize`].
+const REPLACEMENT: char = '_';
+
+/// How names that are invalid on Windows are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WindowsNamesPolicy {
+    /// No checks; the destination filesystem decides (upstream behaviour).
+    #[default]
+    Off,
+    /// Rewrite invalid characters so the entry can be created.
+    Sanitize,
+    /// Leave entries with invalid names out of the transfer.
+    Skip,
+    /// Abort the transfer at the first invalid name.
+    Fail,
+}
+
+impl WindowsNamesPolicy {
+    /// Returns the canonical lowercase token used on the command line.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Sanitize => "sanitize",
+            Self::Skip => "skip",
+            Self::Fail => "fail",
+        }
+    }
+
+    /// Parses a CLI token into a [`WindowsNamesPolicy`].
+    ///
+    /// The match is case-insensitive. Unknown tokens are returned as the
+    /// original input for the caller to surface in an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns the original input string when no variant matches.
+    pub fn parse(value: &str) -> Result<Self, &str> {
+        let lowered = value.trim().to_ascii_lowercase();
+        match lowered.as_str() {
+            "off" => Ok(Self::Off),
+            "sanitize" => Ok(Self::Sanitize),
+            "skip" => Ok(Self::Skip),
+            "fail" => Ok(Self::Fail),
+            _ => Err(value),
+        }
+    }
+
+    /// Returns `true` unless the policy is [`WindowsNamesPolicy::Off`].
+    #[must_use]
+    pub const fn is_enabled(self) -> bool {
+        !matches!(self, Self::Off)
+    }
+
+    /// Checks every component of `path` and returns the action the policy
+    /// takes, or `None` when the path is valid on Windows (or the policy is
+    /// off).
+    ///
+    /// Under [`WindowsNamesPolicy::Sanitize`] every invalid component is
+    /// rewritten, so an entry below a sanitized directory lands under the
+    /// same sanitized directory name.
+    #[must_use]
+    pub fn check(self, path: &Path) -> Option<WindowsNameAction> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let sanitized = sanitize_windows_path(path)?;
+        Some(match self {
+            Self::Sanitize => WindowsNameAction::Rename(sanitized),
+            Self::Skip => WindowsNameAction::Skip,
+            Self::Off | Self::Fail => WindowsNameAction::Fail,
+        })
+    }
+}
+
+impl fmt::Display for WindowsNamesPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What [`WindowsNamesPolicy::check`] decided for an invalid path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WindowsNameAction {
+    /// Write the entry under this sanitized path.
+    Rename(PathBuf),
+    /// Leave the entry out of the transfer.
+    Skip,
+    /// Abort the transfer.
+    Fail,
+}
+
+impl WindowsNameAction {
+    /// Returns the `windows-names:` warning for `path`.
+    ///
+    /// [`WindowsNameAction::Fail`] has no warning; the caller surfaces
+    /// [`invalid_windows_name_error`] instead. The remote receiver sends the
+    /// text as `MSG_WARNING`; the local copy prints it through
+    /// [`report`](Self::report).
+    #[must_use]
+    pub fn message(&self, path: &Path) -> Option<String> {
+        match self {
+            Self::Rename(renamed) => Some(format!(
+                "WARNING: windows-names: \"{}\" is not a valid Windows name; \
+                 writing it as \"{}\"",
+                path.display(),
+                renamed.display()
+            )),
+            Self::Skip => Some(format!(
+                "WARNING: windows-names: skipping \"{}\": not a valid Windows name",
+                path.display()
+            )),
+            Self::Fail => None,
+        }
+    }
+
+    /// Prints the `windows-names:` warning for `path` to stderr.
+    pub fn report(&self, path: &Path) {
+        if let Some(message) = self.message(path) {
+            eprintln!("{message}");
+        }
+    }
+}
+
+/// Applies a [`WindowsNamesPolicy`] across a transfer, keeping sanitized
+/// names from landing on a sibling's name.
+///
+/// The caller [`reserve`](Self::reserve)s every entry of a directory before
+/// it [`check`](Self::check)s any of them, so a sanitized name is compared
+/// against siblings that sort after it too. Sanitized directories are
+/// remembered, so entries below them are written under the same name.
+#[derive(Debug, Default)]
+pub struct WindowsNameSanitizer {
+    policy: WindowsNamesPolicy,
+    /// Sibling paths already in use: the sender's parent joined with the
+    /// name the entry is written under.
+    taken: HashSet<PathBuf>,
+    /// Sanitized directories, from the sender's path to the written path.
+    renamed_dirs: HashMap<PathBuf, PathBuf>,
+}
+
+impl WindowsNameSanitizer {
+    /// Creates a sanitizer applying `policy`.
+    #[must_use]
+    pub fn new(policy: WindowsNamesPolicy) -> Self {
+        Self {
+            policy,
+            taken: HashSet::new(),
+            renamed_dirs: HashMap::new(),
+        }
+    }
+
+    /// Returns the policy the sanitizer applies.
+    #[must_use]
+    pub const fn policy(&self) -> WindowsNamesPolicy {
+        self.policy
+    }
+
+    /// Records that the entry at `path` (the sender's relative path) keeps
+    /// its name, so no sanitized sibling is written under it.
+    pub fn reserve(&mut self, path: &Path) {
+        if self.policy == WindowsNamesPolicy::Sanitize
+            && path.file_name().is_some_and(is_valid_windows_name)
+        {
+            self.taken.insert(path.to_path_buf());
+        }
+    }
+
+    /// Checks `path` (the sender's relative path) and returns the action the
+    /// policy takes, or `None` when it is written unchanged.
+    ///
+    /// Under [`WindowsNamesPolicy::Sanitize`] the returned path is the full
+    /// relative path the entry is written under. When the sanitized name is
+    /// already in use, a `~N` suffix is added before its extension, choosing
+    /// the first `N` that is still free.
+    pub fn check(&mut self, path: &Path, is_dir: bool) -> Option<WindowsNameAction> {
+        if self.policy != WindowsNamesPolicy::Sanitize {
+            return self.policy.check(path);
+        }
+        let name = path.file_name()?;
+        let parent = path.parent().unwrap_or(Path::new(""));
+        let written_parent = match self.renamed_dirs.get(parent) {
+            Some(renamed) => renamed.clone(),
+            None => sanitize_windows_path(parent).unwrap_or_else(|| parent.to_path_buf()),
+        };
+        let written_name = match sanitize_windows_name(name) {
+            Some(sanitized) => self.claim(parent, sanitized),
+            None if written_parent == parent => return None,
+            None => name.to_os_string(),
+        };
+        let written = written_parent.join(written_name);
+        if is_dir {
+            self.renamed_dirs
+                .insert(path.to_path_buf(), written.clone());
+        }
+        Some(WindowsNameAction::Rename(written))
+    }
+
+    /// Claims the first free `~N` variant of `sanitized` under `parent`.
+    fn claim(&mut self, parent: &Path, sanitized: OsString) -> OsString {
+        let base = parent.join(&sanitized);
+        let (mut name, mut candidate) = (sanitized, base.clone());
+        let mut n = 1;
+        while self.taken.contains(&candidate) {
+            (name, candidate) = disambiguated(&base, n);
+            n += 1;
+        }
+        self.taken.insert(candidate);
+        name
+    }
+}
+
+/// Returns the error raised for `path` under [`WindowsNamesPolicy::Fail`].
+#[must_use]
+pub fn invalid_windows_name_error(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("\"{}\" is not a valid Windows file name", path.display()),
+    )
+}
+
+/// Reports whether the single path component `name` can be created on NTFS.
+///
+/// `.` and `..` are accepted; they name directories rather than entries.
+#[must_use]
+pub fn is_valid_windows_name(name: &OsStr) -> bool {
+    sanitize_windows_name(name).is_none()
+}
+
+/// Returns `name` with every character NTFS rejects replaced by `_`, or
+/// `None` when the name is already valid.
+///
+/// Names that are not valid UTF-8 are checked through their lossy form; the
+/// rewritten name then carries U+FFFD in place of the undecodable bytes.
+#[must_use]
+pub fn sanitize_windows_name(name: &OsStr) -> Option<OsString> {
+    let text = name.to_string_lossy();
+    if text == "." || text == ".." {
+        return None;
+    }
+    let trailing = text.len() - text.trim_end_matches(['.', ' ']).len();
+    let body = &text[..text.len() - trailing];
+    if trailing == 0 && !body.chars().any(is_invalid_character) {
+        return None;
+    }
+    let mut sanitized: String = body
+        .chars()
+        .map(|c| {
+            if is_invalid_character(c) {
+                REPLACEMENT
+            } else {
+                c
+            }
+        })
+        .collect();
+    sanitized.extend(std::iter::repeat_n(REPLACEMENT, trailing));
+    Some(OsString::from(sanitized))
+}
+
+/// Sanitizes each normal component of `path`, returning `None` when all of
+/// them are already valid.
+#[must_use]
+pub fn sanitize_windows_path(path: &Path) -> Option<PathBuf> {
+    let mut changed = false;
+    let mut sanitized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(name) => match sanitize_windows_name(name) {
+                Some(replacement) => {
+                    changed = true;
+                    sanitized.push(replacement);
+                }
+                None => sanitized.push(name),
+            },
+            other => sanitized.push(other.as_os_str()),
+        }
+    }
+    changed.then_some(sanitized)
+}
+
+fn is_invalid_character(c: char) -> bool {
+    matches!(c, '\0'..='\x1f') || INVALID_CHARACTERS.contains(&c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sanitize(name: &str) -> Option<String> {
+        sanitize_windows_name(OsStr::new(name)).map(|s| s.to_string_lossy().into_owned())
+    }
+
+    #[test]
+    fn parse_accepts_each_policy_case_insensitively() {
+        assert_eq!(
+            WindowsNamesPolicy::parse("off"),
+            Ok(WindowsNamesPolicy::Off)
+        );
+        assert_eq!(
+            WindowsNamesPolicy::parse("Sanitize"),
+            Ok(WindowsNamesPolicy::Sanitize)
+        );
+        assert_eq!(
+            WindowsNamesPolicy::parse(" SKIP "),
+            Ok(WindowsNamesPolicy::Skip)
+        );
+        assert_eq!(
+            WindowsNamesPolicy::parse("fail"),
+            Ok(WindowsNamesPolicy::Fail)
+        );
+        assert_eq!(WindowsNamesPolicy::parse("rename"), Err("rename"));
+    }
+
+    #[test]
+    fn valid_names_are_left_alone() {
+        assert_eq!(sanitize("report.txt"), None);
+        assert_eq!(sanitize(".hidden"), None);
+        assert_eq!(sanitize("with space"), None);
+        assert_eq!(sanitize("."), None);
+        assert_eq!(sanitize(".."), None);
+        assert!(is_valid_windows_name(OsStr::new("CON")));
+    }
+
+    #[test]
+    fn invalid_characters_are_replaced() {
+        assert_eq!(sanitize("a:b"), Some("a_b".to_owned()));
+        assert_eq!(sanitize("<why?>"), Some("_why__".to_owned()));
+        assert_eq!(sanitize("pipe|star*"), Some("pipe_star_".to_owned()));
+        assert_eq!(sanitize("back\\slash\"q"), Some("back_slash_q".to_owned()));
+        assert_eq!(sanitize("tab\there"), Some("tab_here".to_owned()));
+    }
+
+    #[test]
+    fn trailing_dots_and_spaces_are_replaced() {
+        assert_eq!(sanitize("notes."), Some("notes_".to_owned()));
+        assert_eq!(sanitize("dir. ."), Some("dir___".to_owned()));
+        assert_eq!(sanitize("..."), Some("___".to_owned()));
+        assert_eq!(sanitize("a.b"), None);
+    }
+
+    #[test]
+    fn path_sanitizes_every_component() {
+        let sanitized = sanitize_windows_path(Path::new("c/a:b/d?")).unwrap();
+        assert_eq!(sanitized, PathBuf::from("c/a_b/d_"));
+        assert_eq!(sanitize_windows_path(Path::new("a/b/c")), None);
+    }
+
+    #[test]
+    fn check_maps_policy_to_action() {
+        let path = Path::new("dir/x:y");
+        assert_eq!(WindowsNamesPolicy::Off.check(path), None);
+        assert_eq!(
+            WindowsNamesPolicy::Sanitize.check(path),
+            Some(WindowsNameAction::Rename(PathBuf::from("dir/x_y")))
+        );
+        assert_eq!(
+            WindowsNamesPolicy::Skip.check(path),
+            Some(WindowsNameAction::Skip)
+        );
+        assert_eq!(
+            WindowsNamesPolicy::Fail.check(path),
+            Some(WindowsNameAction::Fail)
+        );
+        assert_eq!(WindowsNamesPolicy::Fail.check(Path::new("dir/ok")), None);
+    }
+
+    #[test]
+    fn sanitized_names_do_not_land_on_siblings() {
+        let mut sanitizer = WindowsNameSanitizer::new(WindowsNamesPolicy::Sanitize);
+        for path in ["a:b", "a_b", "a?b", "a:b/x.txt", "a:b/x?.txt", "a:b/x_.txt"] {
+            sanitizer.reserve(Path::new(path));
+        }
+        let rename = |path: &str| Some(WindowsNameAction::Rename(PathBuf::from(path)));
+
+        assert_eq!(sanitizer.check(Path::new("a:b"), true), rename("a_b~1"));
+        assert_eq!(sanitizer.check(Path::new("a_b"), true), None);
+        assert_eq!(sanitizer.check(Path::new("a?b"), false), rename("a_b~2"));
+        assert_eq!(
+            sanitizer.check(Path::new("a:b/x.txt"), false),
+            rename("a_b~1/x.txt")
+        );
+        assert_eq!(
+            sanitizer.check(Path::new("a:b/x?.txt"), false),
+            rename("a_b~1/x_~1.txt")
+        );
+        assert_eq!(
+            sanitizer.check(Path::new("a:b/x_.txt"), false),
+            rename("a_b~1/x_.txt")
+        );
+    }
+
+    #[test]
+    fn sanitizer_defers_to_policy_for_skip_and_fail() {
+        let mut sanitizer = WindowsNameSanitizer::new(WindowsNamesPolicy::Skip);
+        assert_eq!(
+            sanitizer.check(Path::new("a:b/ok"), false),
+            Some(WindowsNameAction::Skip)
+        );
+        assert_eq!(sanitizer.check(Path::new("ok"), false), None);
+    }
+}
@@ -0,0 +1,39 @@
+//! Error type for tree verification.
+
+use std::io;
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+/// Failure while comparing two trees.
+#[derive(Debug, Error)]
+pub enum VerifyError {
+    /// An I/O error occurred while accessing a path.
+    #[error("failed to {action} '{path}': {source}", path = path.display())]
+    Io {
+        /// The action being performed (e.g., "read directory", "open").
+        action: &'static str,
+        /// The path where the error occurred.
+        path: PathBuf,
+        /// The underlying I/O error.
+        #[source]
+        source: io::Error,
+    },
+
+    /// A compared root is not a directory.
+    #[error("'{path}' is not a directory", path = path.display())]
+    NotADirectory {
+        /// The offending root.
+        path: PathBuf,
+    },
+}
+
+impl VerifyError {
+    pub(super) fn io(action: &'static str, path: impl Into<PathBuf>, source: io::Error) -> Self {
+        Self::Io {
+            action,
+            path: path.into(),
+            source,
+        }
+    }
+}
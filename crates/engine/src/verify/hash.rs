@@ -0,0 +1,104 @@
+//! Batched content comparison.
+//!
+//! Each rayon task takes [`PAIRS_PER_BATCH`] file pairs and hashes both sides
+//! of every pair as lanes of one [`Md5BatchContext`], reading all lanes in
+//! step [`CHUNK_LEN`] bytes at a time. Lanes advance together through the
+//! SIMD kernel, so memory stays bounded at one chunk per lane however large
+//! the files are.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::PathBuf;
+
+use checksums::Md5BatchContext;
+use rayon::prelude::*;
+
+use super::VerifyError;
+
+/// File pairs hashed together; both sides make 16 lanes, the AVX-512 width.
+const PAIRS_PER_BATCH: usize = 8;
+
+/// Bytes read from each lane per batch update.
+const CHUNK_LEN: usize = 64 * 1024;
+
+/// Per-pair verdicts from [`compare_contents`].
+pub(super) struct ContentOutcome {
+    /// Whether each pair's digests match, in input order.
+    pub(super) matches: Vec<bool>,
+    /// Bytes read across both sides of every pair.
+    pub(super) bytes: u64,
+}
+
+/// Hashes both files of every `(source, destination)` pair and reports
+/// which pairs hold identical contents.
+pub(super) fn compare_contents(
+    pairs: &[(PathBuf, PathBuf)],
+) -> Result<ContentOutcome, VerifyError> {
+    let batches: Vec<(Vec<bool>, u64)> = pairs
+        .par_chunks(PAIRS_PER_BATCH)
+        .map(hash_batch)
+        .collect::<Result<_, _>>()?;
+    let mut outcome = ContentOutcome {
+        matches: Vec::with_capacity(pairs.len()),
+        bytes: 0,
+    };
+    for (matches, bytes) in batches {
+        outcome.matches.extend(matches);
+        outcome.bytes += bytes;
+    }
+    Ok(outcome)
+}
+
+fn hash_batch(pairs: &[(PathBuf, PathBuf)]) -> Result<(Vec<bool>, u64), VerifyError> {
+    let paths: Vec<&PathBuf> = pairs.iter().flat_map(|(src, dst)| [src, dst]).collect();
+    let mut files = paths
+        .iter()
+        .map(|path| File::open(path).map_err(|e| VerifyError::io("open", *path, e)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut context = Md5BatchContext::new(files.len());
+    let mut buffers = vec![vec![0u8; CHUNK_LEN]; files.len()];
+    let mut filled = vec![0usize; files.len()];
+    let mut done = vec![false; files.len()];
+    let mut bytes = 0u64;
+    while done.iter().any(|finished| !finished) {
+        for (lane, file) in files.iter_mut().enumerate() {
+            filled[lane] = 0;
+            if done[lane] {
+                continue;
+            }
+            let read = read_full(file, &mut buffers[lane])
+                .map_err(|e| VerifyError::io("read", paths[lane], e))?;
+            filled[lane] = read;
+            done[lane] = read < CHUNK_LEN;
+            bytes += read as u64;
+        }
+        let chunks: Vec<&[u8]> = buffers
+            .iter()
+            .zip(&filled)
+            .map(|(buffer, &len)| &buffer[..len])
+            .collect();
+        context.update(&chunks);
+    }
+
+    let digests = context.finalize();
+    let matches = digests
+        .chunks_exact(2)
+        .map(|pair| pair[0] == pair[1])
+        .collect();
+    Ok((matches, bytes))
+}
+
+/// Fills `buf` unless the file ends first, returning the bytes read.
+fn read_full(file: &mut File, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut total = 0;
+    while total < buf.len() {
+        match file.read(&mut buf[total..]) {
+            Ok(0) => break,
+            Ok(n) => total += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(total)
+}
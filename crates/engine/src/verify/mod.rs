@@ -0,0 +1,273 @@
+//! Read-only comparison of two directory trees.
+//!
+//! [`verify_trees`] answers the question `rsync -rlc --dry-run -i src/ dst/`
+//! answers - which entries would a checksum transfer touch - without building
+//! a file list, spawning a receiver, or writing anything. Both trees are
+//! walked, regular files of equal size are hashed with MD5 through the SIMD
+//! batch engine (several files per [`checksums::Md5BatchContext`], batches
+//! spread across the rayon pool), and every difference is reported as the
+//! itemize line upstream would print for it.
+//!
+//! oc-rsync extension: upstream has no verification-only mode.
+//!
+//! # Examples
+//!
+//! ```
+//! # let temp = tempfile::tempdir().unwrap();
+//! # let src = temp.path().join("src");
+//! # let dst = temp.path().join("dst");
+//! # std::fs::create_dir_all(&src).unwrap();
+//! # std::fs::create_dir_all(&dst).unwrap();
+//! std::fs::write(src.join("a.txt"), b"new").unwrap();
+//! std::fs::write(dst.join("a.txt"), b"old").unwrap();
+//!
+//! let report = engine::verify::verify_trees(&src, &dst).unwrap();
+//! let lines: Vec<String> = report.differences().iter().map(|d| d.to_string()).collect();
+//! assert_eq!(lines, [">fc........ a.txt"]);
+//! ```
+
+mod error;
+mod hash;
+mod tree;
+
+#[cfg(test)]
+mod tests;
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+pub use error::VerifyError;
+
+use tree::TreeEntry;
+
+/// The kind of a compared entry.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VerifyEntryKind {
+    /// A regular file.
+    File,
+    /// A directory.
+    Directory,
+    /// A symbolic link.
+    Symlink,
+    /// A block or character device.
+    Device,
+    /// A FIFO or socket.
+    Special,
+}
+
+impl VerifyEntryKind {
+    /// Returns the itemize file-type character (`f`, `d`, `L`, `D`, `S`).
+    const fn itemize_char(self) -> char {
+        match self {
+            Self::File => 'f',
+            Self::Directory => 'd',
+            Self::Symlink => 'L',
+            Self::Device => 'D',
+            Self::Special => 'S',
+        }
+    }
+}
+
+/// How a destination entry differs from its source counterpart.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Difference {
+    /// The entry exists only in the source tree.
+    Missing(VerifyEntryKind),
+    /// The entry exists only in the destination tree.
+    Extraneous(VerifyEntryKind),
+    /// Both trees hold the entry, but as different kinds; carries the
+    /// source's kind.
+    TypeChanged(VerifyEntryKind),
+    /// Regular files whose sizes differ.
+    Size,
+    /// Regular files of equal size whose contents differ.
+    Checksum,
+    /// Symbolic links pointing at different targets.
+    SymlinkTarget,
+}
+
+/// One differing entry, addressed relative to the compared roots.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerifyDifference {
+    relative: PathBuf,
+    difference: Difference,
+}
+
+impl VerifyDifference {
+    /// Returns the entry's path relative to the compared roots.
+    #[must_use]
+    pub fn relative_path(&self) -> &Path {
+        &self.relative
+    }
+
+    /// Returns how the entry differs.
+    #[must_use]
+    pub const fn difference(&self) -> Difference {
+        self.difference
+    }
+
+    /// Returns the 11-character itemize field, or `*deleting` for an entry
+    /// only the destination holds.
+    ///
+    /// upstream: log.c:log_formatted() `%i` - `YXcstpoguax`, with `+` in every
+    /// attribute column for a new entry.
+    #[must_use]
+    pub fn itemize_flags(&self) -> String {
+        let (update, kind, attrs) = match self.difference {
+            Difference::Extraneous(_) => return "*deleting".to_owned(),
+            Difference::Missing(kind) | Difference::TypeChanged(kind) => {
+                (new_entry_update(kind), kind, "+++++++++")
+            }
+            Difference::Size => ('>', VerifyEntryKind::File, ".s......."),
+            Difference::Checksum => ('>', VerifyEntryKind::File, "c........"),
+            Difference::SymlinkTarget => ('c', VerifyEntryKind::Symlink, "c........"),
+        };
+        format!("{update}{}{attrs}", kind.itemize_char())
+    }
+
+    const fn entry_kind(&self) -> VerifyEntryKind {
+        match self.difference {
+            Difference::Missing(kind)
+            | Difference::Extraneous(kind)
+            | Difference::TypeChanged(kind) => kind,
+            Difference::Size | Difference::Checksum => VerifyEntryKind::File,
+            Difference::SymlinkTarget => VerifyEntryKind::Symlink,
+        }
+    }
+}
+
+/// `>` for a file that would be received, `c` for an entry created locally.
+const fn new_entry_update(kind: VerifyEntryKind) -> char {
+    match kind {
+        VerifyEntryKind::File => '>',
+        _ => 'c',
+    }
+}
+
+impl fmt::Display for VerifyDifference {
+    /// Renders the itemize line, directories with a trailing slash.
+    ///
+    /// upstream: log.c:log_formatted() - `%n` appends `/` to a directory.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let slash = if self.entry_kind() == VerifyEntryKind::Directory {
+            "/"
+        } else {
+            ""
+        };
+        write!(
+            f,
+            "{:<11} {}{slash}",
+            self.itemize_flags(),
+            self.relative.display()
+        )
+    }
+}
+
+/// The outcome of [`verify_trees`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct VerifyReport {
+    differences: Vec<VerifyDifference>,
+    files_hashed: u64,
+    bytes_hashed: u64,
+}
+
+impl VerifyReport {
+    /// Returns the differences in sorted path order.
+    #[must_use]
+    pub fn differences(&self) -> &[VerifyDifference] {
+        &self.differences
+    }
+
+    /// Reports whether the destination matches the source.
+    #[must_use]
+    pub fn is_identical(&self) -> bool {
+        self.differences.is_empty()
+    }
+
+    /// Returns the number of file pairs whose contents were hashed.
+    #[must_use]
+    pub const fn files_hashed(&self) -> u64 {
+        self.files_hashed
+    }
+
+    /// Returns the bytes read from both trees while hashing.
+    #[must_use]
+    pub const fn bytes_hashed(&self) -> u64 {
+        self.bytes_hashed
+    }
+}
+
+/// Compares the tree at `destination` against the tree at `source`.
+///
+/// Symlinks are compared by target and never followed; ownership,
+/// permissions, and timestamps are ignored, as `--checksum` alone would.
+///
+/// # Errors
+///
+/// Returns [`VerifyError`] when either root cannot be read or when a
+/// directory or file inside them cannot be listed or opened.
+pub fn verify_trees(source: &Path, destination: &Path) -> Result<VerifyReport, VerifyError> {
+    let source_tree = tree::scan(source)?;
+    let destination_tree = tree::scan(destination)?;
+
+    let mut differences = Vec::new();
+    let mut to_hash = Vec::new();
+    for (relative, src) in &source_tree {
+        let Some(dst) = destination_tree.get(relative) else {
+            differences.push((relative.clone(), Some(Difference::Missing(src.kind()))));
+            continue;
+        };
+        let difference = match (src, dst) {
+            (TreeEntry::File { len: a }, TreeEntry::File { len: b }) if a != b => {
+                Some(Difference::Size)
+            }
+            (TreeEntry::File { len }, TreeEntry::File { .. }) => {
+                if *len > 0 {
+                    to_hash.push(differences.len());
+                }
+                None
+            }
+            (TreeEntry::Symlink { target: a }, TreeEntry::Symlink { target: b }) => {
+                (a != b).then_some(Difference::SymlinkTarget)
+            }
+            _ if src.kind() != dst.kind() => Some(Difference::TypeChanged(src.kind())),
+            _ => None,
+        };
+        differences.push((relative.clone(), difference));
+    }
+    for (relative, dst) in &destination_tree {
+        if !source_tree.contains_key(relative) {
+            differences.push((relative.clone(), Some(Difference::Extraneous(dst.kind()))));
+        }
+    }
+
+    let pairs: Vec<(PathBuf, PathBuf)> = to_hash
+        .iter()
+        .map(|&slot| {
+            let relative = &differences[slot].0;
+            (source.join(relative), destination.join(relative))
+        })
+        .collect();
+    let outcome = hash::compare_contents(&pairs)?;
+    for (&slot, &same) in to_hash.iter().zip(&outcome.matches) {
+        if !same {
+            differences[slot].1 = Some(Difference::Checksum);
+        }
+    }
+
+    let mut differences: Vec<VerifyDifference> = differences
+        .into_iter()
+        .filter_map(|(relative, difference)| {
+            difference.map(|difference| VerifyDifference {
+                relative,
+                difference,
+            })
+        })
+        .collect();
+    differences.sort_by(|a, b| a.relative.cmp(&b.relative));
+    Ok(VerifyReport {
+        differences,
+        files_hashed: pairs.len() as u64,
+        bytes_hashed: outcome.bytes,
+    })
+}
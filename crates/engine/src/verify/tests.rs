@@ -0,0 +1,117 @@
+use std::fs;
+use std::path::Path;
+
+use super::*;
+
+fn roots() -> (tempfile::TempDir, PathBuf, PathBuf) {
+    let temp = tempfile::tempdir().unwrap();
+    let src = temp.path().join("src");
+    let dst = temp.path().join("dst");
+    fs::create_dir_all(&src).unwrap();
+    fs::create_dir_all(&dst).unwrap();
+    (temp, src, dst)
+}
+
+fn lines(src: &Path, dst: &Path) -> Vec<String> {
+    verify_trees(src, dst)
+        .unwrap()
+        .differences()
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn identical_trees_report_nothing() {
+    let (_temp, src, dst) = roots();
+    for root in [&src, &dst] {
+        fs::create_dir(root.join("sub")).unwrap();
+        fs::write(root.join("sub/file"), b"same").unwrap();
+        fs::write(root.join("empty"), b"").unwrap();
+    }
+
+    let report = verify_trees(&src, &dst).unwrap();
+    assert!(report.is_identical());
+    assert_eq!(report.files_hashed(), 1);
+    assert_eq!(report.bytes_hashed(), 8);
+}
+
+#[test]
+fn reports_missing_extraneous_and_changed_entries() {
+    let (_temp, src, dst) = roots();
+    fs::create_dir(src.join("newdir")).unwrap();
+    fs::write(src.join("new.txt"), b"x").unwrap();
+    fs::write(src.join("grown"), b"longer").unwrap();
+    fs::write(dst.join("grown"), b"short").unwrap();
+    fs::write(src.join("edited"), b"aaaa").unwrap();
+    fs::write(dst.join("edited"), b"bbbb").unwrap();
+    fs::write(dst.join("stale"), b"gone").unwrap();
+
+    assert_eq!(
+        lines(&src, &dst),
+        [
+            ">fc........ edited",
+            ">f.s....... grown",
+            ">f+++++++++ new.txt",
+            "cd+++++++++ newdir/",
+            "*deleting   stale",
+        ]
+    );
+}
+
+#[test]
+fn type_change_reports_the_source_kind() {
+    let (_temp, src, dst) = roots();
+    fs::write(src.join("entry"), b"file").unwrap();
+    fs::create_dir(dst.join("entry")).unwrap();
+
+    assert_eq!(lines(&src, &dst), [">f+++++++++ entry"]);
+}
+
+#[cfg(unix)]
+#[test]
+fn symlinks_compare_by_target() {
+    let (_temp, src, dst) = roots();
+    std::os::unix::fs::symlink("one", src.join("link")).unwrap();
+    std::os::unix::fs::symlink("two", dst.join("link")).unwrap();
+    std::os::unix::fs::symlink("same", src.join("kept")).unwrap();
+    std::os::unix::fs::symlink("same", dst.join("kept")).unwrap();
+
+    assert_eq!(lines(&src, &dst), ["cLc........ link"]);
+}
+
+#[test]
+fn many_files_span_several_batches() {
+    let (_temp, src, dst) = roots();
+    let big = vec![7u8; 3 * 64 * 1024 + 11];
+    for i in 0..20 {
+        fs::write(src.join(format!("f{i:02}")), &big).unwrap();
+        let mut copy = big.clone();
+        if i % 7 == 3 {
+            *copy.last_mut().unwrap() = 8;
+        }
+        fs::write(dst.join(format!("f{i:02}")), &copy).unwrap();
+    }
+
+    let report = verify_trees(&src, &dst).unwrap();
+    let changed: Vec<String> = report
+        .differences()
+        .iter()
+        .map(|d| d.relative_path().display().to_string())
+        .collect();
+    assert_eq!(changed, ["f03", "f10", "f17"]);
+    assert_eq!(report.files_hashed(), 20);
+    assert_eq!(report.bytes_hashed(), 2 * 20 * big.len() as u64);
+}
+
+#[test]
+fn non_directory_root_is_rejected() {
+    let (_temp, src, dst) = roots();
+    let file = src.join("file");
+    fs::write(&file, b"x").unwrap();
+
+    assert!(matches!(
+        verify_trees(&file, &dst),
+        Err(VerifyError::NotADirectory { .. })
+    ));
+}
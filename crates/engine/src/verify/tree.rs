@@ -0,0 +1,83 @@
+//! Snapshot of one side of the comparison.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{VerifyEntryKind, VerifyError};
+
+/// One entry of a scanned tree, holding just what the comparison needs.
+#[derive(Debug)]
+pub(super) enum TreeEntry {
+    File { len: u64 },
+    Directory,
+    Symlink { target: PathBuf },
+    Device,
+    Special,
+}
+
+impl TreeEntry {
+    pub(super) const fn kind(&self) -> VerifyEntryKind {
+        match self {
+            Self::File { .. } => VerifyEntryKind::File,
+            Self::Directory => VerifyEntryKind::Directory,
+            Self::Symlink { .. } => VerifyEntryKind::Symlink,
+            Self::Device => VerifyEntryKind::Device,
+            Self::Special => VerifyEntryKind::Special,
+        }
+    }
+}
+
+/// Lists every entry below `root`, keyed by its path relative to `root`.
+///
+/// Symlinks are recorded by target and never followed, so a link to a
+/// directory does not pull its contents into the snapshot.
+pub(super) fn scan(root: &Path) -> Result<BTreeMap<PathBuf, TreeEntry>, VerifyError> {
+    let meta = fs::metadata(root).map_err(|e| VerifyError::io("stat", root, e))?;
+    if !meta.is_dir() {
+        return Err(VerifyError::NotADirectory {
+            path: root.to_path_buf(),
+        });
+    }
+    let mut entries = BTreeMap::new();
+    let mut pending = vec![PathBuf::new()];
+    while let Some(relative) = pending.pop() {
+        let dir = root.join(&relative);
+        let listing = fs::read_dir(&dir).map_err(|e| VerifyError::io("read directory", &dir, e))?;
+        for item in listing {
+            let item = item.map_err(|e| VerifyError::io("read directory", &dir, e))?;
+            let path = item.path();
+            let child = relative.join(item.file_name());
+            let meta =
+                fs::symlink_metadata(&path).map_err(|e| VerifyError::io("stat", &path, e))?;
+            let entry = classify(&path, &meta)?;
+            if matches!(entry, TreeEntry::Directory) {
+                pending.push(child.clone());
+            }
+            entries.insert(child, entry);
+        }
+    }
+    Ok(entries)
+}
+
+fn classify(path: &Path, meta: &fs::Metadata) -> Result<TreeEntry, VerifyError> {
+    let file_type = meta.file_type();
+    if file_type.is_file() {
+        return Ok(TreeEntry::File { len: meta.len() });
+    }
+    if file_type.is_dir() {
+        return Ok(TreeEntry::Directory);
+    }
+    if file_type.is_symlink() {
+        let target = fs::read_link(path).map_err(|e| VerifyError::io("read link", path, e))?;
+        return Ok(TreeEntry::Symlink { target });
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+        if file_type.is_block_device() || file_type.is_char_device() {
+            return Ok(TreeEntry::Device);
+        }
+    }
+    Ok(TreeEntry::Special)
+}
@@ -0,0 +1,369 @@
+//! [`Vfs`] over an in-memory tree, for tests.
+
+use std::collections::BTreeMap;
+use std::io::{self, Cursor, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use super::{Vfs, VfsDirEntry, VfsMetadata, VfsWriter};
+use crate::util::poison::lock_or_recover;
+
+#[derive(Clone, Debug)]
+enum Node {
+    File {
+        data: Vec<u8>,
+        modified: Option<SystemTime>,
+        mode: Option<u32>,
+    },
+    Directory {
+        modified: Option<SystemTime>,
+        mode: Option<u32>,
+    },
+    Symlink {
+        target: PathBuf,
+    },
+}
+
+impl Node {
+    fn metadata(&self) -> VfsMetadata {
+        let (meta, modified, mode) = match self {
+            Self::File {
+                data,
+                modified,
+                mode,
+            } => (VfsMetadata::file(data.len() as u64), *modified, *mode),
+            Self::Directory { modified, mode } => (VfsMetadata::directory(), *modified, *mode),
+            Self::Symlink { .. } => (VfsMetadata::symlink(), None, None),
+        };
+        let meta = match modified {
+            Some(modified) => meta.with_modified(modified),
+            None => meta,
+        };
+        match mode {
+            Some(mode) => meta.with_mode(mode),
+            None => meta,
+        }
+    }
+}
+
+/// An in-memory tree.
+///
+/// Paths are keys relative to the tree's root: leading `/` and `.`
+/// components are ignored and `..` is rejected, so `""`, `"/"` and `"."`
+/// all name the root directory, which always exists. Nothing touches the
+/// disk, which makes the backend suited to exercising [`super::sync_trees`]
+/// and custom [`Vfs`] consumers in tests.
+#[derive(Debug, Default)]
+pub struct MemFs {
+    nodes: Mutex<BTreeMap<PathBuf, Node>>,
+}
+
+impl MemFs {
+    /// Creates an empty tree holding only the root directory.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the contents of the regular file at `path`.
+    #[must_use]
+    pub fn contents(&self, path: &Path) -> Option<Vec<u8>> {
+        let key = key(path).ok()?;
+        match lock_or_recover(&self.nodes).get(&key) {
+            Some(Node::File { data, .. }) => Some(data.clone()),
+            _ => None,
+        }
+    }
+
+    fn insert(&self, path: &Path, node: Node, replace: bool) -> io::Result<()> {
+        let key = key(path)?;
+        if is_root(&key) {
+            return Err(already_exists(path));
+        }
+        let mut nodes = lock_or_recover(&self.nodes);
+        require_directory(&nodes, key.parent().unwrap_or(Path::new("")), path)?;
+        match nodes.get(&key) {
+            Some(Node::Directory { .. }) => return Err(is_a_directory(path)),
+            Some(_) if !replace => return Err(already_exists(path)),
+            _ => {}
+        }
+        nodes.insert(key, node);
+        Ok(())
+    }
+}
+
+/// Normalises `path` to a key relative to the root.
+/// Normalises a backend path into a root-relative key, rejecting `..`.
+pub(super) fn key(path: &Path) -> io::Result<PathBuf> {
+    let mut key = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => key.push(part),
+            Component::RootDir | Component::CurDir | Component::Prefix(_) => {}
+            Component::ParentDir => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("'{}' escapes the tree root", path.display()),
+                ));
+            }
+        }
+    }
+    Ok(key)
+}
+
+fn is_root(key: &Path) -> bool {
+    key.as_os_str().is_empty()
+}
+
+fn require_directory(nodes: &BTreeMap<PathBuf, Node>, key: &Path, path: &Path) -> io::Result<()> {
+    if is_root(key) {
+        return Ok(());
+    }
+    match nodes.get(key) {
+        Some(Node::Directory { .. }) => Ok(()),
+        Some(_) => Err(io::Error::new(
+            io::ErrorKind::NotADirectory,
+            format!("parent of '{}' is not a directory", path.display()),
+        )),
+        None => Err(not_found(path)),
+    }
+}
+
+fn not_found(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("'{}' does not exist", path.display()),
+    )
+}
+
+fn already_exists(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        format!("'{}' already exists", path.display()),
+    )
+}
+
+fn is_a_directory(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::IsADirectory,
+        format!("'{}' is a directory", path.display()),
+    )
+}
+
+impl Vfs for MemFs {
+    fn stat(&self, path: &Path) -> io::Result<VfsMetadata> {
+        let key = key(path)?;
+        if is_root(&key) {
+            return Ok(VfsMetadata::directory());
+        }
+        lock_or_recover(&self.nodes)
+            .get(&key)
+            .map(Node::metadata)
+            .ok_or_else(|| not_found(path))
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<VfsDirEntry>> {
+        let key = key(path)?;
+        let nodes = lock_or_recover(&self.nodes);
+        require_directory(&nodes, &key, path)?;
+        Ok(nodes
+            .iter()
+            .filter(|(child, _)| child.parent() == Some(key.as_path()))
+            .filter_map(|(child, node)| {
+                let name = child.file_name()?;
+                Some(VfsDirEntry::new(name, node.metadata().file_type()))
+            })
+            .collect())
+    }
+
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read + Send + '_>> {
+        match self.contents(path) {
+            Some(data) => Ok(Box::new(Cursor::new(data))),
+            None => match self.stat(path) {
+                Ok(_) => Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("'{}' is not a regular file", path.display()),
+                )),
+                Err(err) => Err(err),
+            },
+        }
+    }
+
+    fn create(&self, path: &Path, metadata: &VfsMetadata) -> io::Result<Box<dyn VfsWriter + '_>> {
+        let key = key(path)?;
+        require_directory(
+            &lock_or_recover(&self.nodes),
+            key.parent().unwrap_or(Path::new("")),
+            path,
+        )?;
+        Ok(Box::new(MemFsWriter {
+            fs: self,
+            path: path.to_path_buf(),
+            data: Vec::with_capacity(usize::try_from(metadata.len()).unwrap_or(0)),
+            modified: metadata.modified(),
+            mode: metadata.mode(),
+        }))
+    }
+
+    fn create_dir(&self, path: &Path) -> io::Result<()> {
+        let node = Node::Directory {
+            modified: Some(SystemTime::now()),
+            mode: None,
+        };
+        self.insert(path, node, false)
+    }
+
+    fn symlink(&self, target: &Path, link: &Path) -> io::Result<()> {
+        let node = Node::Symlink {
+            target: target.to_path_buf(),
+        };
+        self.insert(link, node, false)
+    }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        let key = key(path)?;
+        match lock_or_recover(&self.nodes).get(&key) {
+            Some(Node::Symlink { target }) => Ok(target.clone()),
+            Some(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("'{}' is not a symlink", path.display()),
+            )),
+            None => Err(not_found(path)),
+        }
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        let from_key = key(from)?;
+        let to_key = key(to)?;
+        let mut nodes = lock_or_recover(&self.nodes);
+        require_directory(&nodes, to_key.parent().unwrap_or(Path::new("")), to)?;
+        if matches!(nodes.get(&to_key), Some(Node::Directory { .. })) {
+            return Err(is_a_directory(to));
+        }
+        if is_root(&from_key) {
+            return Err(is_a_directory(from));
+        }
+        let moved: Vec<PathBuf> = nodes
+            .keys()
+            .filter(|key| key.starts_with(&from_key))
+            .cloned()
+            .collect();
+        if moved.is_empty() {
+            return Err(not_found(from));
+        }
+        for old in moved {
+            if let (Some(node), Ok(suffix)) = (nodes.remove(&old), old.strip_prefix(&from_key)) {
+                nodes.insert(to_key.join(suffix), node);
+            }
+        }
+        Ok(())
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        let key = key(path)?;
+        let mut nodes = lock_or_recover(&self.nodes);
+        match nodes.get(&key) {
+            Some(Node::Directory { .. }) => Err(is_a_directory(path)),
+            Some(_) => {
+                nodes.remove(&key);
+                Ok(())
+            }
+            None if is_root(&key) => Err(is_a_directory(path)),
+            None => Err(not_found(path)),
+        }
+    }
+
+    fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        let key = key(path)?;
+        let mut nodes = lock_or_recover(&self.nodes);
+        match nodes.get(&key) {
+            Some(Node::Directory { .. }) => {}
+            Some(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotADirectory,
+                    format!("'{}' is not a directory", path.display()),
+                ));
+            }
+            None => return Err(not_found(path)),
+        }
+        if nodes
+            .keys()
+            .any(|child| child.parent() == Some(key.as_path()))
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::DirectoryNotEmpty,
+                format!("'{}' is not empty", path.display()),
+            ));
+        }
+        nodes.remove(&key);
+        Ok(())
+    }
+
+    fn set_modified(&self, path: &Path, modified: SystemTime) -> io::Result<()> {
+        let key = key(path)?;
+        match lock_or_recover(&self.nodes).get_mut(&key) {
+            Some(Node::File { modified: slot, .. } | Node::Directory { modified: slot, .. }) => {
+                *slot = Some(modified);
+                Ok(())
+            }
+            Some(Node::Symlink { .. }) => Ok(()),
+            None if is_root(&key) => Ok(()),
+            None => Err(not_found(path)),
+        }
+    }
+
+    fn set_mode(&self, path: &Path, mode: u32) -> io::Result<()> {
+        let key = key(path)?;
+        match lock_or_recover(&self.nodes).get_mut(&key) {
+            Some(Node::File { mode: slot, .. } | Node::Directory { mode: slot, .. }) => {
+                *slot = Some(mode);
+                Ok(())
+            }
+            Some(Node::Symlink { .. }) => Ok(()),
+            None if is_root(&key) => Ok(()),
+            None => Err(not_found(path)),
+        }
+    }
+}
+
+/// Buffers a file's bytes until [`VfsWriter::finish`] inserts them.
+struct MemFsWriter<'a> {
+    fs: &'a MemFs,
+    path: PathBuf,
+    data: Vec<u8>,
+    modified: Option<SystemTime>,
+    mode: Option<u32>,
+}
+
+impl Write for MemFsWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl VfsWriter for MemFsWriter<'_> {
+    fn finish(self: Box<Self>) -> io::Result<()> {
+        let Self {
+            fs,
+            path,
+            data,
+            modified,
+            mode,
+        } = *self;
+        fs.insert(
+            &path,
+            Node::File {
+                data,
+                modified,
+                mode,
+            },
+            true,
+        )
+    }
+}
@@ -0,0 +1,472 @@
+//! Object-storage destination backend.
+//!
+//! [`S3Fs`] presents a bucket prefix as a [`Vfs`] so [`super::sync_trees`]
+//! can back a tree up into object storage. Objects cannot be patched in
+//! place, so there is no delta application: every changed file is uploaded
+//! whole, in a single `PutObject` when it fits in one part and as a
+//! multipart upload otherwise. The quick check still skips unchanged files
+//! because modification times and permissions ride along as object
+//! metadata.
+//!
+//! The backend speaks to the store through the [`ObjectStore`] trait, which
+//! the embedding application implements over the S3 client it already
+//! uses; this crate carries no HTTP or signing stack of its own.
+//!
+//! # Layout
+//!
+//! - A file `dir/name` under prefix `backup/` is the object
+//!   `backup/dir/name`.
+//! - A directory is the zero-byte marker object `backup/dir/`. Directories
+//!   that only exist implicitly, because keys below them exist, are
+//!   reported as directories too.
+//! - A symlink is a zero-byte object whose `rsync-symlink` metadata holds
+//!   the target.
+//! - `mtime` metadata holds the modification time as decimal seconds since
+//!   the epoch, the convention rclone uses; `mode` holds the permission
+//!   bits in octal.
+//!
+//! Renames are a server-side copy followed by a delete per object, so they
+//! are not atomic.
+//!
+//! oc-rsync extension: upstream has no object-storage support.
+
+mod store;
+mod upload;
+
+#[cfg(test)]
+mod tests;
+
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub use store::{CompletedPart, ObjectInfo, ObjectListing, ObjectMetadata, ObjectStore};
+
+use super::{Vfs, VfsDirEntry, VfsFileType, VfsMetadata, VfsWriter};
+use upload::Upload;
+
+/// Metadata key holding the modification time.
+pub const MTIME_METADATA_KEY: &str = "mtime";
+
+/// Metadata key holding the octal permission bits.
+pub const MODE_METADATA_KEY: &str = "mode";
+
+/// Metadata key marking a symlink object and holding its target.
+pub const SYMLINK_METADATA_KEY: &str = "rsync-symlink";
+
+/// Smallest part size S3 accepts for every part but the last.
+pub const MIN_PART_SIZE: usize = 5 * 1024 * 1024;
+
+/// Part size used unless [`S3Fs::with_part_size`] overrides it.
+pub const DEFAULT_PART_SIZE: usize = 16 * 1024 * 1024;
+
+/// Most parts a single multipart upload may have.
+const MAX_PARTS: u64 = 10_000;
+
+/// A bucket prefix exposed as a [`Vfs`].
+#[derive(Debug)]
+pub struct S3Fs<C> {
+    store: C,
+    prefix: String,
+    part_size: usize,
+}
+
+impl<C: ObjectStore> S3Fs<C> {
+    /// Creates a backend rooted at `prefix` within the bucket `store`
+    /// addresses. Leading and trailing slashes on `prefix` are ignored; an
+    /// empty prefix roots the tree at the top of the bucket.
+    #[must_use]
+    pub fn new(store: C, prefix: impl Into<String>) -> Self {
+        let prefix = prefix.into();
+        let trimmed = prefix.trim_matches('/');
+        let prefix = if trimmed.is_empty() {
+            String::new()
+        } else {
+            format!("{trimmed}/")
+        };
+        Self {
+            store,
+            prefix,
+            part_size: DEFAULT_PART_SIZE,
+        }
+    }
+
+    /// Sets the multipart part size, raised to [`MIN_PART_SIZE`] if lower.
+    ///
+    /// Files no larger than one part are uploaded with a single
+    /// `PutObject`. For very large files the size grows further so the
+    /// upload stays within S3's 10,000-part limit.
+    #[must_use]
+    pub fn with_part_size(mut self, bytes: usize) -> Self {
+        self.part_size = bytes.max(MIN_PART_SIZE);
+        self
+    }
+
+    /// Returns the object-store client.
+    #[must_use]
+    pub const fn store(&self) -> &C {
+        &self.store
+    }
+
+    /// Returns the normalised key prefix, empty or ending in `/`.
+    #[must_use]
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Part size for a file of `len` bytes.
+    fn part_size_for(&self, len: u64) -> usize {
+        let needed = usize::try_from(len.div_ceil(MAX_PARTS)).unwrap_or(usize::MAX);
+        self.part_size.max(needed)
+    }
+
+    fn object_key(&self, relative: &str) -> String {
+        format!("{}{relative}", self.prefix)
+    }
+
+    fn dir_prefix(&self, relative: &str) -> String {
+        if relative.is_empty() {
+            self.prefix.clone()
+        } else {
+            format!("{}{relative}/", self.prefix)
+        }
+    }
+
+    /// Looks up `relative` as an object, a marker, or an implied directory.
+    fn lookup(&self, relative: &str) -> io::Result<Option<Entry>> {
+        if relative.is_empty() {
+            return Ok(Some(Entry::Directory(ObjectMetadata::new())));
+        }
+        if let Some(info) = self.store.head_object(&self.object_key(relative))? {
+            if let Some(target) = info.metadata.get(SYMLINK_METADATA_KEY) {
+                return Ok(Some(Entry::Symlink(PathBuf::from(target))));
+            }
+            return Ok(Some(Entry::File(info)));
+        }
+        let marker = self.dir_prefix(relative);
+        if let Some(info) = self.store.head_object(&marker)? {
+            return Ok(Some(Entry::Directory(info.metadata)));
+        }
+        let listing = self.store.list_objects(&marker, Some("/"))?;
+        if listing.objects.is_empty() && listing.common_prefixes.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(Entry::Directory(ObjectMetadata::new())))
+    }
+
+    fn require(&self, path: &Path) -> io::Result<(String, Entry)> {
+        let relative = relative_key(path)?;
+        match self.lookup(&relative)? {
+            Some(entry) => Ok((relative, entry)),
+            None => Err(not_found(path)),
+        }
+    }
+
+    /// Rewrites one metadata entry of the object or marker behind `path`.
+    fn update_metadata(&self, path: &Path, name: &str, value: String) -> io::Result<()> {
+        let (relative, entry) = self.require(path)?;
+        match entry {
+            Entry::File(mut info) => {
+                info.metadata.insert(name.to_owned(), value);
+                self.store.copy_object(&info.key, &info.key, &info.metadata)
+            }
+            // Roots carry no marker and symlinks keep no attributes.
+            Entry::Directory(_) if relative.is_empty() => Ok(()),
+            Entry::Symlink(_) => Ok(()),
+            Entry::Directory(mut metadata) => {
+                metadata.insert(name.to_owned(), value);
+                self.store
+                    .put_object(&self.dir_prefix(&relative), Vec::new(), &metadata)
+            }
+        }
+    }
+}
+
+/// What a path resolved to in the bucket.
+enum Entry {
+    File(ObjectInfo),
+    Directory(ObjectMetadata),
+    Symlink(PathBuf),
+}
+
+/// Converts a backend path into a `/`-separated key relative to the prefix.
+fn relative_key(path: &Path) -> io::Result<String> {
+    let mut parts = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_str().ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("'{}' is not valid UTF-8", path.display()),
+                )
+            })?),
+            Component::RootDir | Component::CurDir | Component::Prefix(_) => {}
+            Component::ParentDir => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("'{}' escapes the bucket prefix", path.display()),
+                ));
+            }
+        }
+    }
+    Ok(parts.join("/"))
+}
+
+fn not_found(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("'{}' does not exist", path.display()),
+    )
+}
+
+/// Renders `time` as decimal seconds since the epoch with nanosecond digits.
+fn format_mtime(time: SystemTime) -> String {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(after) => format!("{}.{:09}", after.as_secs(), after.subsec_nanos()),
+        Err(before) => {
+            let before = before.duration();
+            format!("-{}.{:09}", before.as_secs(), before.subsec_nanos())
+        }
+    }
+}
+
+/// Parses the [`format_mtime`] form; any decimal fraction is accepted.
+fn parse_mtime(value: &str) -> Option<SystemTime> {
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value),
+    };
+    let (secs, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    let secs: u64 = secs.parse().ok()?;
+    let fraction = fraction.get(..9).unwrap_or(fraction);
+    if !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let nanos: u32 = format!("{fraction:0<9}").parse().ok()?;
+    let offset = Duration::new(secs, nanos);
+    if negative {
+        UNIX_EPOCH.checked_sub(offset)
+    } else {
+        UNIX_EPOCH.checked_add(offset)
+    }
+}
+
+fn metadata_to_vfs(base: VfsMetadata, metadata: &ObjectMetadata) -> VfsMetadata {
+    let mut out = base;
+    if let Some(modified) = metadata
+        .get(MTIME_METADATA_KEY)
+        .and_then(|v| parse_mtime(v))
+    {
+        out = out.with_modified(modified);
+    }
+    if let Some(mode) = metadata
+        .get(MODE_METADATA_KEY)
+        .and_then(|v| u32::from_str_radix(v, 8).ok())
+    {
+        out = out.with_mode(mode);
+    }
+    out
+}
+
+fn vfs_to_metadata(meta: &VfsMetadata) -> ObjectMetadata {
+    let mut metadata = ObjectMetadata::new();
+    if let Some(modified) = meta.modified() {
+        metadata.insert(MTIME_METADATA_KEY.to_owned(), format_mtime(modified));
+    }
+    if let Some(mode) = meta.mode() {
+        metadata.insert(MODE_METADATA_KEY.to_owned(), format!("{mode:o}"));
+    }
+    metadata
+}
+
+impl<C: ObjectStore> Vfs for S3Fs<C> {
+    fn stat(&self, path: &Path) -> io::Result<VfsMetadata> {
+        Ok(match self.require(path)?.1 {
+            Entry::File(info) => metadata_to_vfs(VfsMetadata::file(info.size), &info.metadata),
+            Entry::Directory(metadata) => metadata_to_vfs(VfsMetadata::directory(), &metadata),
+            Entry::Symlink(_) => VfsMetadata::symlink(),
+        })
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<VfsDirEntry>> {
+        let (relative, entry) = self.require(path)?;
+        if !matches!(entry, Entry::Directory(_)) {
+            return Err(io::Error::new(
+                io::ErrorKind::NotADirectory,
+                format!("'{}' is not a directory", path.display()),
+            ));
+        }
+        let prefix = self.dir_prefix(&relative);
+        let listing = self.store.list_objects(&prefix, Some("/"))?;
+        let mut entries = Vec::new();
+        for object in listing.objects {
+            let Some(name) = object.key.strip_prefix(&prefix) else {
+                continue;
+            };
+            if name.is_empty() {
+                continue;
+            }
+            // Listings omit user metadata; only a zero-byte object can be a
+            // symlink, so only those cost an extra HEAD.
+            let file_type = if object.size == 0
+                && self
+                    .store
+                    .head_object(&object.key)?
+                    .is_some_and(|info| info.metadata.contains_key(SYMLINK_METADATA_KEY))
+            {
+                VfsFileType::Symlink
+            } else {
+                VfsFileType::File
+            };
+            entries.push(VfsDirEntry::new(name, file_type));
+        }
+        for common in listing.common_prefixes {
+            if let Some(name) = common
+                .strip_prefix(&prefix)
+                .and_then(|rest| rest.strip_suffix('/'))
+                .filter(|name| !name.is_empty())
+            {
+                entries.push(VfsDirEntry::new(name, VfsFileType::Directory));
+            }
+        }
+        Ok(entries)
+    }
+
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read + Send + '_>> {
+        let relative = relative_key(path)?;
+        self.store.get_object(&self.object_key(&relative))
+    }
+
+    fn create(&self, path: &Path, metadata: &VfsMetadata) -> io::Result<Box<dyn VfsWriter + '_>> {
+        let relative = relative_key(path)?;
+        if relative.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::IsADirectory,
+                "the bucket prefix is a directory",
+            ));
+        }
+        Ok(Box::new(Upload::new(
+            &self.store,
+            self.object_key(&relative),
+            vfs_to_metadata(metadata),
+            self.part_size_for(metadata.len()),
+        )))
+    }
+
+    fn create_dir(&self, path: &Path) -> io::Result<()> {
+        let relative = relative_key(path)?;
+        if self.lookup(&relative)?.is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("'{}' already exists", path.display()),
+            ));
+        }
+        let metadata = vfs_to_metadata(&VfsMetadata::directory().with_modified(SystemTime::now()));
+        self.store
+            .put_object(&self.dir_prefix(&relative), Vec::new(), &metadata)
+    }
+
+    fn symlink(&self, target: &Path, link: &Path) -> io::Result<()> {
+        let relative = relative_key(link)?;
+        let target = target.to_str().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("symlink target '{}' is not valid UTF-8", target.display()),
+            )
+        })?;
+        let mut metadata = ObjectMetadata::new();
+        metadata.insert(SYMLINK_METADATA_KEY.to_owned(), target.to_owned());
+        self.store
+            .put_object(&self.object_key(&relative), Vec::new(), &metadata)
+    }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        match self.require(path)?.1 {
+            Entry::Symlink(target) => Ok(target),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("'{}' is not a symlink", path.display()),
+            )),
+        }
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        let (from_relative, entry) = self.require(from)?;
+        let to_relative = relative_key(to)?;
+        if from_relative.is_empty() || to_relative.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the bucket prefix itself cannot be renamed",
+            ));
+        }
+        let moves: Vec<(String, String)> = if matches!(entry, Entry::Directory(_)) {
+            let from_prefix = self.dir_prefix(&from_relative);
+            let to_prefix = self.dir_prefix(&to_relative);
+            self.store
+                .list_objects(&from_prefix, None)?
+                .objects
+                .into_iter()
+                .filter_map(|object| {
+                    let rest = object.key.strip_prefix(&from_prefix)?.to_owned();
+                    Some((object.key, format!("{to_prefix}{rest}")))
+                })
+                .collect()
+        } else {
+            vec![(
+                self.object_key(&from_relative),
+                self.object_key(&to_relative),
+            )]
+        };
+        for (source, destination) in moves {
+            let metadata = self
+                .store
+                .head_object(&source)?
+                .map(|info| info.metadata)
+                .unwrap_or_default();
+            self.store.copy_object(&source, &destination, &metadata)?;
+            self.store.delete_object(&source)?;
+        }
+        Ok(())
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        let (relative, entry) = self.require(path)?;
+        if matches!(entry, Entry::Directory(_)) {
+            return Err(io::Error::new(
+                io::ErrorKind::IsADirectory,
+                format!("'{}' is a directory", path.display()),
+            ));
+        }
+        self.store.delete_object(&self.object_key(&relative))
+    }
+
+    fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        let (relative, entry) = self.require(path)?;
+        if !matches!(entry, Entry::Directory(_)) {
+            return Err(io::Error::new(
+                io::ErrorKind::NotADirectory,
+                format!("'{}' is not a directory", path.display()),
+            ));
+        }
+        let marker = self.dir_prefix(&relative);
+        let listing = self.store.list_objects(&marker, Some("/"))?;
+        if !listing.common_prefixes.is_empty() || listing.objects.iter().any(|o| o.key != marker) {
+            return Err(io::Error::new(
+                io::ErrorKind::DirectoryNotEmpty,
+                format!("'{}' is not empty", path.display()),
+            ));
+        }
+        if relative.is_empty() {
+            return Ok(());
+        }
+        self.store.delete_object(&marker)
+    }
+
+    fn set_modified(&self, path: &Path, modified: SystemTime) -> io::Result<()> {
+        self.update_metadata(path, MTIME_METADATA_KEY, format_mtime(modified))
+    }
+
+    fn set_mode(&self, path: &Path, mode: u32) -> io::Result<()> {
+        self.update_metadata(path, MODE_METADATA_KEY, format!("{mode:o}"))
+    }
+}
@@ -0,0 +1,140 @@
+//! The object-store client interface [`S3Fs`](super::S3Fs) drives.
+
+use std::collections::BTreeMap;
+use std::io::{self, Read};
+
+/// User-defined object metadata (`x-amz-meta-*`), keyed without the prefix.
+pub type ObjectMetadata = BTreeMap<String, String>;
+
+/// One object as reported by [`ObjectStore::head_object`] or a listing.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ObjectInfo {
+    /// The object's full key.
+    pub key: String,
+    /// The object's size in bytes.
+    pub size: u64,
+    /// The object's user metadata. Listings may leave this empty.
+    pub metadata: ObjectMetadata,
+}
+
+/// The result of [`ObjectStore::list_objects`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ObjectListing {
+    /// Objects whose keys start with the prefix, up to the delimiter.
+    pub objects: Vec<ObjectInfo>,
+    /// Key prefixes rolled up at the delimiter, each ending with it.
+    pub common_prefixes: Vec<String>,
+}
+
+/// A part accepted by [`ObjectStore::upload_part`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CompletedPart {
+    /// The 1-based part number.
+    pub part_number: u32,
+    /// The entity tag the store returned for the part.
+    pub etag: String,
+}
+
+/// The S3 operations an object-store destination needs.
+///
+/// Implement this over whichever client the embedding application already
+/// uses (an AWS SDK, a MinIO client, a signed-request HTTP layer); every
+/// method maps one-to-one onto the S3 API call of the same name. Methods
+/// block until the request completes. Missing objects are reported as
+/// `Ok(None)` from [`head_object`](Self::head_object) and as
+/// [`io::ErrorKind::NotFound`] elsewhere.
+pub trait ObjectStore: Send + Sync {
+    /// `HeadObject`: returns the object's size and metadata, if it exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns the transport or service error.
+    fn head_object(&self, key: &str) -> io::Result<Option<ObjectInfo>>;
+
+    /// `GetObject`: streams the object's body.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`io::ErrorKind::NotFound`] for a missing key, or the
+    /// transport or service error.
+    fn get_object(&self, key: &str) -> io::Result<Box<dyn Read + Send + '_>>;
+
+    /// `PutObject`: stores `body` under `key` in one request.
+    ///
+    /// # Errors
+    ///
+    /// Returns the transport or service error.
+    fn put_object(&self, key: &str, body: Vec<u8>, metadata: &ObjectMetadata) -> io::Result<()>;
+
+    /// `CreateMultipartUpload`: starts an upload and returns its id.
+    ///
+    /// # Errors
+    ///
+    /// Returns the transport or service error.
+    fn create_multipart_upload(&self, key: &str, metadata: &ObjectMetadata) -> io::Result<String>;
+
+    /// `UploadPart`: uploads one part and returns its entity tag.
+    ///
+    /// # Errors
+    ///
+    /// Returns the transport or service error.
+    fn upload_part(
+        &self,
+        key: &str,
+        upload_id: &str,
+        part_number: u32,
+        body: Vec<u8>,
+    ) -> io::Result<String>;
+
+    /// `CompleteMultipartUpload`: assembles the parts into the object.
+    ///
+    /// # Errors
+    ///
+    /// Returns the transport or service error.
+    fn complete_multipart_upload(
+        &self,
+        key: &str,
+        upload_id: &str,
+        parts: &[CompletedPart],
+    ) -> io::Result<()>;
+
+    /// `AbortMultipartUpload`: discards an unfinished upload's parts.
+    ///
+    /// # Errors
+    ///
+    /// Returns the transport or service error.
+    fn abort_multipart_upload(&self, key: &str, upload_id: &str) -> io::Result<()>;
+
+    /// `ListObjectsV2`: lists every key under `prefix`, following
+    /// continuation tokens until the listing is complete. With a
+    /// `delimiter`, keys are rolled up into
+    /// [`ObjectListing::common_prefixes`] at its first occurrence after the
+    /// prefix.
+    ///
+    /// # Errors
+    ///
+    /// Returns the transport or service error.
+    fn list_objects(&self, prefix: &str, delimiter: Option<&str>) -> io::Result<ObjectListing>;
+
+    /// `DeleteObject`: removes `key`; deleting a missing key succeeds.
+    ///
+    /// # Errors
+    ///
+    /// Returns the transport or service error.
+    fn delete_object(&self, key: &str) -> io::Result<()>;
+
+    /// `CopyObject` with `MetadataDirective: REPLACE`: copies `source` to
+    /// `destination` server-side, giving the copy `metadata`. Copying a key
+    /// onto itself rewrites its metadata in place.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`io::ErrorKind::NotFound`] for a missing source, or the
+    /// transport or service error.
+    fn copy_object(
+        &self,
+        source: &str,
+        destination: &str,
+        metadata: &ObjectMetadata,
+    ) -> io::Result<()>;
+}
@@ -0,0 +1,372 @@
+use std::collections::BTreeMap;
+use std::io::{Cursor, Read, Write};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use super::*;
+use crate::vfs::{MemFs, VfsSyncOptions, sync_trees};
+
+/// Calls the tests inspect, in the order the backend made them.
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Call {
+    Put(String),
+    CreateMultipart(String),
+    UploadPart(u32, usize),
+    Complete(String),
+    Abort(String),
+    Copy(String, String),
+}
+
+/// An in-memory bucket implementing the S3 semantics the backend relies on.
+#[derive(Default)]
+struct FakeStore {
+    objects: Mutex<BTreeMap<String, (Vec<u8>, ObjectMetadata)>>,
+    uploads: Mutex<BTreeMap<String, (String, ObjectMetadata, BTreeMap<u32, Vec<u8>>)>>,
+    calls: Mutex<Vec<Call>>,
+}
+
+impl FakeStore {
+    fn record(&self, call: Call) {
+        self.calls.lock().unwrap().push(call);
+    }
+
+    fn calls(&self) -> Vec<Call> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.objects.lock().unwrap().keys().cloned().collect()
+    }
+
+    fn body(&self, key: &str) -> Vec<u8> {
+        self.objects.lock().unwrap()[key].0.clone()
+    }
+
+    fn metadata(&self, key: &str) -> ObjectMetadata {
+        self.objects.lock().unwrap()[key].1.clone()
+    }
+}
+
+impl ObjectStore for FakeStore {
+    fn head_object(&self, key: &str) -> io::Result<Option<ObjectInfo>> {
+        let objects = self.objects.lock().unwrap();
+        Ok(objects.get(key).map(|(body, metadata)| ObjectInfo {
+            key: key.to_owned(),
+            size: body.len() as u64,
+            metadata: metadata.clone(),
+        }))
+    }
+
+    fn get_object(&self, key: &str) -> io::Result<Box<dyn Read + Send + '_>> {
+        let objects = self.objects.lock().unwrap();
+        let (body, _) = objects
+            .get(key)
+            .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+        Ok(Box::new(Cursor::new(body.clone())))
+    }
+
+    fn put_object(&self, key: &str, body: Vec<u8>, metadata: &ObjectMetadata) -> io::Result<()> {
+        self.record(Call::Put(key.to_owned()));
+        self.objects
+            .lock()
+            .unwrap()
+            .insert(key.to_owned(), (body, metadata.clone()));
+        Ok(())
+    }
+
+    fn create_multipart_upload(&self, key: &str, metadata: &ObjectMetadata) -> io::Result<String> {
+        self.record(Call::CreateMultipart(key.to_owned()));
+        let mut uploads = self.uploads.lock().unwrap();
+        let id = format!("upload-{}", uploads.len());
+        uploads.insert(
+            id.clone(),
+            (key.to_owned(), metadata.clone(), BTreeMap::new()),
+        );
+        Ok(id)
+    }
+
+    fn upload_part(
+        &self,
+        _key: &str,
+        upload_id: &str,
+        part_number: u32,
+        body: Vec<u8>,
+    ) -> io::Result<String> {
+        self.record(Call::UploadPart(part_number, body.len()));
+        let mut uploads = self.uploads.lock().unwrap();
+        uploads
+            .get_mut(upload_id)
+            .unwrap()
+            .2
+            .insert(part_number, body);
+        Ok(format!("etag-{part_number}"))
+    }
+
+    fn complete_multipart_upload(
+        &self,
+        key: &str,
+        upload_id: &str,
+        parts: &[CompletedPart],
+    ) -> io::Result<()> {
+        self.record(Call::Complete(key.to_owned()));
+        let (key, metadata, stored) = self.uploads.lock().unwrap().remove(upload_id).unwrap();
+        let mut body = Vec::new();
+        for part in parts {
+            assert_eq!(part.etag, format!("etag-{}", part.part_number));
+            body.extend_from_slice(&stored[&part.part_number]);
+        }
+        self.objects.lock().unwrap().insert(key, (body, metadata));
+        Ok(())
+    }
+
+    fn abort_multipart_upload(&self, key: &str, upload_id: &str) -> io::Result<()> {
+        self.record(Call::Abort(key.to_owned()));
+        self.uploads.lock().unwrap().remove(upload_id);
+        Ok(())
+    }
+
+    fn list_objects(&self, prefix: &str, delimiter: Option<&str>) -> io::Result<ObjectListing> {
+        let mut listing = ObjectListing::default();
+        for (key, (body, _)) in self.objects.lock().unwrap().iter() {
+            let Some(rest) = key.strip_prefix(prefix) else {
+                continue;
+            };
+            if let Some(end) = delimiter.and_then(|d| rest.find(d).map(|at| at + d.len())) {
+                let common = format!("{prefix}{}", &rest[..end]);
+                if listing.common_prefixes.last() != Some(&common) {
+                    listing.common_prefixes.push(common);
+                }
+                continue;
+            }
+            listing.objects.push(ObjectInfo {
+                key: key.clone(),
+                size: body.len() as u64,
+                metadata: ObjectMetadata::new(),
+            });
+        }
+        Ok(listing)
+    }
+
+    fn delete_object(&self, key: &str) -> io::Result<()> {
+        self.objects.lock().unwrap().remove(key);
+        Ok(())
+    }
+
+    fn copy_object(
+        &self,
+        source: &str,
+        destination: &str,
+        metadata: &ObjectMetadata,
+    ) -> io::Result<()> {
+        self.record(Call::Copy(source.to_owned(), destination.to_owned()));
+        let mut objects = self.objects.lock().unwrap();
+        let body = objects
+            .get(source)
+            .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?
+            .0
+            .clone();
+        objects.insert(destination.to_owned(), (body, metadata.clone()));
+        Ok(())
+    }
+}
+
+fn epoch(secs: u64) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+fn put(vfs: &dyn Vfs, path: &str, data: &[u8], modified: SystemTime) {
+    let meta = VfsMetadata::file(data.len() as u64).with_modified(modified);
+    let mut writer = vfs.create(Path::new(path), &meta).unwrap();
+    writer.write_all(data).unwrap();
+    writer.finish().unwrap();
+}
+
+fn sample_source() -> MemFs {
+    let src = MemFs::new();
+    src.create_dir(Path::new("dir")).unwrap();
+    put(&src, "top", b"top", epoch(1_000));
+    put(&src, "dir/a", b"alpha", epoch(2_000));
+    src.symlink(Path::new("dir/a"), Path::new("link")).unwrap();
+    src
+}
+
+fn sync(src: &MemFs, dst: &S3Fs<FakeStore>, options: VfsSyncOptions) -> VfsSyncStats {
+    let root = Path::new("");
+    sync_trees(src, root, dst, root, &options).unwrap()
+}
+
+/// A backend whose part size is below the S3 minimum, to exercise
+/// multipart uploads without megabytes of test data.
+fn tiny_parts(prefix: &str) -> S3Fs<FakeStore> {
+    let mut fs = S3Fs::new(FakeStore::default(), prefix);
+    fs.part_size = 4;
+    fs
+}
+
+#[test]
+fn sync_lays_out_objects_under_the_prefix() {
+    let src = sample_source();
+    let dst = S3Fs::new(FakeStore::default(), "/backups/host/");
+    let stats = sync(&src, &dst, VfsSyncOptions::new());
+
+    assert_eq!(stats.files_transferred(), 2);
+    assert_eq!(
+        dst.store().keys(),
+        [
+            "backups/host/dir/",
+            "backups/host/dir/a",
+            "backups/host/link",
+            "backups/host/top"
+        ]
+    );
+    assert_eq!(dst.store().body("backups/host/dir/a"), b"alpha");
+    assert_eq!(
+        dst.store().metadata("backups/host/top")[MTIME_METADATA_KEY],
+        "1000.000000000"
+    );
+    assert_eq!(
+        dst.read_link(Path::new("link")).unwrap(),
+        Path::new("dir/a")
+    );
+    assert_eq!(
+        dst.stat(Path::new("dir")).unwrap().modified(),
+        src.stat(Path::new("dir")).unwrap().modified()
+    );
+}
+
+#[test]
+fn second_sync_only_uploads_changed_files() {
+    let src = sample_source();
+    let dst = S3Fs::new(FakeStore::default(), "");
+    sync(&src, &dst, VfsSyncOptions::new());
+
+    put(&src, "top", b"TOP", epoch(1_001));
+    let before = dst.store().calls().len();
+    let stats = sync(&src, &dst, VfsSyncOptions::new());
+
+    assert_eq!(stats.files_transferred(), 1);
+    assert_eq!(stats.files_up_to_date(), 1);
+    assert!(
+        dst.store().calls()[before..].contains(&Call::Put("top".to_owned())),
+        "only the changed file is uploaded"
+    );
+    assert_eq!(dst.store().body("top"), b"TOP");
+}
+
+#[test]
+fn large_files_use_multipart_uploads() {
+    let dst = tiny_parts("");
+    put(&dst, "big", b"0123456789", epoch(5));
+
+    assert_eq!(
+        dst.store().calls(),
+        [
+            Call::CreateMultipart("big".to_owned()),
+            Call::UploadPart(1, 4),
+            Call::UploadPart(2, 4),
+            Call::UploadPart(3, 2),
+            Call::Complete("big".to_owned()),
+        ]
+    );
+    assert_eq!(dst.store().body("big"), b"0123456789");
+    assert_eq!(
+        dst.stat(Path::new("big")).unwrap().modified(),
+        Some(epoch(5))
+    );
+}
+
+#[test]
+fn single_part_files_use_one_put() {
+    let dst = tiny_parts("");
+    put(&dst, "small", b"1234", epoch(5));
+
+    assert_eq!(dst.store().calls(), [Call::Put("small".to_owned())]);
+}
+
+#[test]
+fn dropped_writer_aborts_the_upload() {
+    let dst = tiny_parts("");
+    let mut writer = dst
+        .create(Path::new("big"), &VfsMetadata::file(10))
+        .unwrap();
+    writer.write_all(b"0123456789").unwrap();
+    drop(writer);
+
+    assert_eq!(
+        dst.store().calls().last(),
+        Some(&Call::Abort("big".to_owned()))
+    );
+    assert!(dst.store().keys().is_empty());
+    assert!(dst.store().uploads.lock().unwrap().is_empty());
+}
+
+#[test]
+fn part_size_grows_to_stay_within_the_part_limit() {
+    let fs = S3Fs::new(FakeStore::default(), "").with_part_size(1);
+    assert_eq!(fs.part_size, MIN_PART_SIZE);
+    assert_eq!(fs.part_size_for(1024), MIN_PART_SIZE);
+
+    let huge = MIN_PART_SIZE as u64 * 20_000;
+    assert_eq!(fs.part_size_for(huge), 2 * MIN_PART_SIZE);
+}
+
+#[test]
+fn delete_removes_objects_and_markers() {
+    let src = sample_source();
+    let dst = S3Fs::new(FakeStore::default(), "root");
+    dst.create_dir(Path::new("stale")).unwrap();
+    put(&dst, "stale/old", b"old", epoch(1));
+
+    let stats = sync(&src, &dst, VfsSyncOptions::new().delete(true));
+    assert_eq!(stats.entries_deleted(), 2);
+    assert!(dst.stat(Path::new("stale")).is_err());
+    assert!(!dst.store().keys().iter().any(|key| key.contains("stale")));
+}
+
+#[test]
+fn implicit_directories_are_listed() {
+    let fs = S3Fs::new(FakeStore::default(), "");
+    fs.store()
+        .put_object("a/b/c", b"x".to_vec(), &ObjectMetadata::new())
+        .unwrap();
+
+    assert_eq!(
+        fs.stat(Path::new("a/b")).unwrap().file_type(),
+        VfsFileType::Directory
+    );
+    let entries = fs.read_dir(Path::new("a")).unwrap();
+    assert_eq!(entries, [VfsDirEntry::new("b", VfsFileType::Directory)]);
+    assert_eq!(
+        fs.stat(Path::new("a/missing")).unwrap_err().kind(),
+        io::ErrorKind::NotFound
+    );
+}
+
+#[test]
+fn rename_moves_every_key_below_a_directory() {
+    let fs = S3Fs::new(FakeStore::default(), "");
+    fs.create_dir(Path::new("old")).unwrap();
+    put(&fs, "old/file", b"x", epoch(7));
+    fs.rename(Path::new("old"), Path::new("new")).unwrap();
+
+    assert_eq!(fs.store().keys(), ["new/", "new/file"]);
+    assert_eq!(
+        fs.stat(Path::new("new/file")).unwrap().modified(),
+        Some(epoch(7))
+    );
+}
+
+#[test]
+fn mtime_metadata_round_trips() {
+    let time = SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 120_000_000);
+    assert_eq!(format_mtime(time), "1700000000.120000000");
+    assert_eq!(parse_mtime("1700000000.12"), Some(time));
+    assert_eq!(parse_mtime("1700000000"), Some(epoch(1_700_000_000)));
+    assert_eq!(
+        parse_mtime(&format_mtime(
+            SystemTime::UNIX_EPOCH - Duration::from_secs(5)
+        )),
+        Some(SystemTime::UNIX_EPOCH - Duration::from_secs(5))
+    );
+    assert_eq!(parse_mtime("soon"), None);
+}
@@ -0,0 +1,101 @@
+//! Streaming object upload behind [`S3Fs::create`](super::S3Fs).
+
+use std::io::{self, Write};
+use std::mem;
+
+use super::store::{CompletedPart, ObjectMetadata, ObjectStore};
+use crate::vfs::VfsWriter;
+
+/// Buffers one part at a time, switching from a single `PutObject` to a
+/// multipart upload once the data outgrows a part.
+///
+/// Dropping an upload before [`VfsWriter::finish`] aborts any multipart
+/// upload it started, so the store keeps no orphaned parts.
+pub(super) struct Upload<'a, C: ObjectStore> {
+    store: &'a C,
+    key: String,
+    metadata: ObjectMetadata,
+    part_size: usize,
+    buffer: Vec<u8>,
+    upload_id: Option<String>,
+    parts: Vec<CompletedPart>,
+}
+
+impl<'a, C: ObjectStore> Upload<'a, C> {
+    pub(super) fn new(
+        store: &'a C,
+        key: String,
+        metadata: ObjectMetadata,
+        part_size: usize,
+    ) -> Self {
+        Self {
+            store,
+            key,
+            metadata,
+            part_size,
+            buffer: Vec::new(),
+            upload_id: None,
+            parts: Vec::new(),
+        }
+    }
+
+    fn upload_part(&mut self, body: Vec<u8>) -> io::Result<()> {
+        if self.upload_id.is_none() {
+            let upload_id = self
+                .store
+                .create_multipart_upload(&self.key, &self.metadata)?;
+            self.upload_id = Some(upload_id);
+        }
+        let upload_id = self.upload_id.as_deref().unwrap_or_default();
+        let part_number = u32::try_from(self.parts.len() + 1)
+            .map_err(|_| io::Error::other("too many upload parts"))?;
+        let etag = self
+            .store
+            .upload_part(&self.key, upload_id, part_number, body)?;
+        self.parts.push(CompletedPart { part_number, etag });
+        Ok(())
+    }
+}
+
+impl<C: ObjectStore> Write for Upload<'_, C> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        // Hold back a full part until more data arrives: a file that is
+        // exactly one part long still goes up as a single PutObject.
+        while self.buffer.len() > self.part_size {
+            let rest = self.buffer.split_off(self.part_size);
+            let part = mem::replace(&mut self.buffer, rest);
+            self.upload_part(part)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<C: ObjectStore> VfsWriter for Upload<'_, C> {
+    fn finish(mut self: Box<Self>) -> io::Result<()> {
+        let body = mem::take(&mut self.buffer);
+        if self.upload_id.is_none() {
+            return self.store.put_object(&self.key, body, &self.metadata);
+        }
+        self.upload_part(body)?;
+        let upload_id = self.upload_id.as_deref().unwrap_or_default();
+        self.store
+            .complete_multipart_upload(&self.key, upload_id, &self.parts)?;
+        self.upload_id = None;
+        Ok(())
+    }
+}
+
+impl<C: ObjectStore> Drop for Upload<'_, C> {
+    fn drop(&mut self) {
+        if let Some(upload_id) = self.upload_id.take() {
+            // Best effort: a failed abort leaves parts for the bucket's
+            // lifecycle rules to reap.
+            let _ = self.store.abort_multipart_upload(&self.key, &upload_id);
+        }
+    }
+}
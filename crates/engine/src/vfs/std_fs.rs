@@ -0,0 +1,198 @@
+//! [`Vfs`] over the local filesystem.
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use filetime::FileTime;
+use tempfile::NamedTempFile;
+
+use super::{Vfs, VfsDirEntry, VfsFileType, VfsMetadata, VfsWriter};
+
+/// The local filesystem, through `std::fs`.
+///
+/// New files are written to a hidden temp file beside the destination and
+/// renamed into place on [`VfsWriter::finish`], so readers never observe a
+/// partially written file.
+///
+/// upstream: receiver.c:recv_files() - `get_tmpname()` then
+/// `finish_transfer()` renames the temp file over the destination.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StdFs;
+
+impl StdFs {
+    /// Creates the local-filesystem backend.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+fn file_type_of(file_type: fs::FileType) -> VfsFileType {
+    if file_type.is_file() {
+        VfsFileType::File
+    } else if file_type.is_dir() {
+        VfsFileType::Directory
+    } else if file_type.is_symlink() {
+        VfsFileType::Symlink
+    } else {
+        VfsFileType::Other
+    }
+}
+
+fn metadata_of(meta: &fs::Metadata) -> VfsMetadata {
+    let file_type = file_type_of(meta.file_type());
+    let len = if file_type == VfsFileType::File {
+        meta.len()
+    } else {
+        0
+    };
+    let mut out = VfsMetadata::new(file_type, len);
+    if let Ok(modified) = meta.modified() {
+        out = out.with_modified(modified);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        out = out.with_mode(meta.permissions().mode() & 0o7777);
+    }
+    out
+}
+
+impl Vfs for StdFs {
+    fn stat(&self, path: &Path) -> io::Result<VfsMetadata> {
+        fs::symlink_metadata(path).map(|meta| metadata_of(&meta))
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<VfsDirEntry>> {
+        fs::read_dir(path)?
+            .map(|item| {
+                let item = item?;
+                Ok(VfsDirEntry::new(
+                    item.file_name(),
+                    file_type_of(item.file_type()?),
+                ))
+            })
+            .collect()
+    }
+
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read + Send + '_>> {
+        Ok(Box::new(fs::File::open(path)?))
+    }
+
+    fn create(&self, path: &Path, metadata: &VfsMetadata) -> io::Result<Box<dyn VfsWriter + '_>> {
+        let parent = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        let mut prefix = std::ffi::OsString::from(".");
+        if let Some(name) = path.file_name() {
+            prefix.push(name);
+        }
+        let temp = tempfile::Builder::new()
+            .prefix(&prefix)
+            .tempfile_in(parent)?;
+        Ok(Box::new(StdFsWriter {
+            temp,
+            path: path.to_path_buf(),
+            metadata: *metadata,
+        }))
+    }
+
+    fn create_dir(&self, path: &Path) -> io::Result<()> {
+        fs::create_dir(path)
+    }
+
+    fn symlink(&self, target: &Path, link: &Path) -> io::Result<()> {
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(target, link)
+        }
+        #[cfg(windows)]
+        {
+            std::os::windows::fs::symlink_file(target, link)
+        }
+        #[cfg(not(any(unix, windows)))]
+        {
+            let _ = (target, link);
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "symlinks are not supported on this platform",
+            ))
+        }
+    }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        fs::read_link(path)
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        fs::rename(from, to)
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        fs::remove_file(path)
+    }
+
+    fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        fs::remove_dir(path)
+    }
+
+    fn set_modified(&self, path: &Path, modified: SystemTime) -> io::Result<()> {
+        filetime::set_symlink_file_times(
+            path,
+            FileTime::now(),
+            FileTime::from_system_time(modified),
+        )
+    }
+
+    fn set_mode(&self, path: &Path, mode: u32) -> io::Result<()> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(path, fs::Permissions::from_mode(mode))
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = (path, mode);
+            Ok(())
+        }
+    }
+}
+
+/// Temp file that becomes the destination on [`VfsWriter::finish`].
+struct StdFsWriter {
+    temp: NamedTempFile,
+    path: PathBuf,
+    metadata: VfsMetadata,
+}
+
+impl Write for StdFsWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.temp.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.temp.flush()
+    }
+}
+
+impl VfsWriter for StdFsWriter {
+    fn finish(self: Box<Self>) -> io::Result<()> {
+        let Self {
+            mut temp,
+            path,
+            metadata,
+        } = *self;
+        temp.flush()?;
+        if let Some(mode) = metadata.mode() {
+            StdFs.set_mode(temp.path(), mode)?;
+        }
+        if let Some(modified) = metadata.modified() {
+            StdFs.set_modified(temp.path(), modified)?;
+        }
+        temp.persist(&path).map_err(|err| err.error)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,621 @@
+//! Whole-file tree sync between two [`Vfs`] backends.
+
+use std::collections::BTreeSet;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use filters::FilterSet;
+use metadata::ModifyWindow;
+use thiserror::Error;
+
+use super::{Vfs, VfsFileType, VfsMetadata};
+use crate::local_copy::deletion::DeletionPolicy;
+use crate::local_copy::quick_check_times_match;
+
+/// Failure while syncing between backends.
+#[derive(Debug, Error)]
+pub enum VfsSyncError {
+    /// A backend operation failed.
+    #[error("failed to {action} '{path}': {source}", path = path.display())]
+    Io {
+        /// The action being performed (e.g., "read directory", "create").
+        action: &'static str,
+        /// The backend path where the error occurred.
+        path: PathBuf,
+        /// The underlying I/O error.
+        #[source]
+        source: io::Error,
+    },
+
+    /// A sync root is not a directory.
+    #[error("'{path}' is not a directory", path = path.display())]
+    NotADirectory {
+        /// The offending root.
+        path: PathBuf,
+    },
+
+    /// A non-empty destination directory stands where the source has a
+    /// non-directory, and neither `--delete` nor `--force` allows removing it.
+    #[error("cannot delete non-empty directory: '{path}'", path = path.display())]
+    DirectoryNotEmpty {
+        /// The directory that was left in place.
+        path: PathBuf,
+    },
+}
+
+impl VfsSyncError {
+    fn io(action: &'static str, path: impl Into<PathBuf>, source: io::Error) -> Self {
+        Self::Io {
+            action,
+            path: path.into(),
+            source,
+        }
+    }
+}
+
+/// Which parts of the tree [`sync_trees`] reproduces.
+///
+/// Traversal is always recursive. [`VfsSyncOptions::new`] matches
+/// `rsync -rlt`: symlinks are recreated, modification times are preserved,
+/// permissions and extraneous destination entries are left alone, and a
+/// non-empty directory is never removed to make room for a file.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct VfsSyncOptions {
+    links: bool,
+    times: bool,
+    perms: bool,
+    delete: bool,
+    delete_excluded: bool,
+    force: bool,
+    size_only: bool,
+    ignore_times: bool,
+    modify_window: ModifyWindow,
+}
+
+impl Default for VfsSyncOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VfsSyncOptions {
+    /// Options equivalent to `rsync -rlt`.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            links: true,
+            times: true,
+            perms: false,
+            delete: false,
+            delete_excluded: false,
+            force: false,
+            size_only: false,
+            ignore_times: false,
+            modify_window: ModifyWindow::ZERO,
+        }
+    }
+
+    /// Recreates symlinks (`--links`); when off they are skipped.
+    #[must_use]
+    pub const fn links(mut self, enabled: bool) -> Self {
+        self.links = enabled;
+        self
+    }
+
+    /// Preserves modification times (`--times`).
+    ///
+    /// Without it every file fails the quick check on the next run, as
+    /// upstream's does, because the destination keeps its write time.
+    #[must_use]
+    pub const fn times(mut self, enabled: bool) -> Self {
+        self.times = enabled;
+        self
+    }
+
+    /// Preserves permission bits (`--perms`).
+    #[must_use]
+    pub const fn perms(mut self, enabled: bool) -> Self {
+        self.perms = enabled;
+        self
+    }
+
+    /// Removes destination entries absent from the source (`--delete`).
+    #[must_use]
+    pub const fn delete(mut self, enabled: bool) -> Self {
+        self.delete = enabled;
+        self
+    }
+
+    /// Also removes destination entries the filter rules exclude
+    /// (`--delete-excluded`); only meaningful together with [`delete`].
+    ///
+    /// [`delete`]: Self::delete
+    #[must_use]
+    pub const fn delete_excluded(mut self, enabled: bool) -> Self {
+        self.delete_excluded = enabled;
+        self
+    }
+
+    /// Removes a non-empty destination directory that stands where the
+    /// source has a file or symlink (`--force`).
+    ///
+    /// [`delete`](Self::delete) implies the same.
+    #[must_use]
+    pub const fn force(mut self, enabled: bool) -> Self {
+        self.force = enabled;
+        self
+    }
+
+    /// Treats a file whose size matches as up to date, whatever its
+    /// modification time (`--size-only`).
+    #[must_use]
+    pub const fn size_only(mut self, enabled: bool) -> Self {
+        self.size_only = enabled;
+        self
+    }
+
+    /// Transfers every file, even when size and modification time match
+    /// (`--ignore-times`). [`size_only`](Self::size_only) takes precedence.
+    #[must_use]
+    pub const fn ignore_times(mut self, enabled: bool) -> Self {
+        self.ignore_times = enabled;
+        self
+    }
+
+    /// Tolerance for the modification-time comparison (`--modify-window`).
+    ///
+    /// The default compares whole seconds; a negative window compares
+    /// nanoseconds too, which only helps on backends that store them.
+    #[must_use]
+    pub const fn modify_window(mut self, window: ModifyWindow) -> Self {
+        self.modify_window = window;
+        self
+    }
+
+    /// The engine deletion policy these options select, consulting `filters`.
+    const fn deletion_policy<'a>(&self, filters: Option<&'a FilterSet>) -> DeletionPolicy<'a> {
+        DeletionPolicy::new(filters)
+            .delete(self.delete)
+            .delete_excluded(self.delete_excluded)
+            .force(self.force)
+    }
+}
+
+/// Counters reported by [`sync_trees`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct VfsSyncStats {
+    files_transferred: u64,
+    files_up_to_date: u64,
+    bytes_transferred: u64,
+    directories_created: u64,
+    symlinks_created: u64,
+    entries_deleted: u64,
+}
+
+impl VfsSyncStats {
+    /// Returns the number of regular files written to the destination.
+    #[must_use]
+    pub const fn files_transferred(&self) -> u64 {
+        self.files_transferred
+    }
+
+    /// Returns the number of regular files that passed the quick check.
+    #[must_use]
+    pub const fn files_up_to_date(&self) -> u64 {
+        self.files_up_to_date
+    }
+
+    /// Returns the bytes copied into transferred files.
+    #[must_use]
+    pub const fn bytes_transferred(&self) -> u64 {
+        self.bytes_transferred
+    }
+
+    /// Returns the number of directories created.
+    #[must_use]
+    pub const fn directories_created(&self) -> u64 {
+        self.directories_created
+    }
+
+    /// Returns the number of symlinks created or retargeted.
+    #[must_use]
+    pub const fn symlinks_created(&self) -> u64 {
+        self.symlinks_created
+    }
+
+    /// Returns the number of destination entries removed, counting every
+    /// entry inside a removed directory.
+    #[must_use]
+    pub const fn entries_deleted(&self) -> u64 {
+        self.entries_deleted
+    }
+}
+
+/// Makes the tree at `destination_root` match the tree at `source_root`.
+///
+/// The contents of `source_root` land directly in `destination_root`, as
+/// with a trailing-slash source (`rsync -rlt src/ dst`); a missing
+/// destination root is created. Regular files whose size and modification
+/// time already match are left untouched; every other file is rewritten
+/// whole. An entry whose kind changed is removed before the new one is
+/// created; a non-empty directory only with [`VfsSyncOptions::delete`] or
+/// [`VfsSyncOptions::force`]. Devices, FIFOs and sockets are skipped.
+///
+/// # Errors
+///
+/// Returns [`VfsSyncError`] when a root is not a directory, when a non-empty
+/// directory blocks a file without `--delete`/`--force`, or when a backend
+/// operation fails; entries already synced stay in place.
+pub fn sync_trees(
+    source: &dyn Vfs,
+    source_root: &Path,
+    destination: &dyn Vfs,
+    destination_root: &Path,
+    options: &VfsSyncOptions,
+) -> Result<VfsSyncStats, VfsSyncError> {
+    sync_trees_filtered(
+        source,
+        source_root,
+        destination,
+        destination_root,
+        options,
+        None,
+    )
+}
+
+/// Like [`sync_trees`], applying the engine's filter rules.
+///
+/// `filters` is evaluated exactly as the local-copy engine evaluates it:
+/// excluded source entries are left out of the file list (an excluded
+/// directory is not descended into), and the `--delete` pass consults the
+/// engine's [`DeletionPolicy`], so protect rules and
+/// [`VfsSyncOptions::delete_excluded`] behave as they do for a directory
+/// destination.
+///
+/// # Errors
+///
+/// Returns the same errors as [`sync_trees`].
+pub fn sync_trees_filtered(
+    source: &dyn Vfs,
+    source_root: &Path,
+    destination: &dyn Vfs,
+    destination_root: &Path,
+    options: &VfsSyncOptions,
+    filters: Option<&FilterSet>,
+) -> Result<VfsSyncStats, VfsSyncError> {
+    let root_meta = stat(source, source_root)?;
+    if root_meta.file_type() != VfsFileType::Directory {
+        return Err(VfsSyncError::NotADirectory {
+            path: source_root.to_path_buf(),
+        });
+    }
+
+    let mut stats = VfsSyncStats::default();
+    match lookup(destination, destination_root)? {
+        Some(meta) if meta.file_type() == VfsFileType::Directory => {}
+        Some(_) => {
+            return Err(VfsSyncError::NotADirectory {
+                path: destination_root.to_path_buf(),
+            });
+        }
+        None => {
+            destination
+                .create_dir(destination_root)
+                .map_err(|e| VfsSyncError::io("create directory", destination_root, e))?;
+            stats.directories_created += 1;
+        }
+    }
+
+    let file_list = build_file_list(source, source_root, options, filters)?;
+    let mut sync = Syncer {
+        source,
+        source_root,
+        destination,
+        destination_root,
+        options,
+        policy: options.deletion_policy(filters),
+        stats,
+    };
+    for (relative, meta) in &file_list {
+        sync.apply(relative, meta)?;
+    }
+
+    if options.delete {
+        let listed: BTreeSet<&Path> = file_list.iter().map(|(rel, _)| rel.as_path()).collect();
+        let directories = std::iter::once(Path::new("")).chain(
+            file_list
+                .iter()
+                .filter(|(_, meta)| meta.file_type() == VfsFileType::Directory)
+                .map(|(rel, _)| rel.as_path()),
+        );
+        for directory in directories {
+            sync.delete_extraneous(directory, &listed)?;
+        }
+    }
+
+    // upstream: generator.c:touch_up_dirs() - directory times are restored
+    // last, deepest first, since creating children rewrote them.
+    let directories = file_list
+        .iter()
+        .filter(|(_, meta)| meta.file_type() == VfsFileType::Directory)
+        .map(|(rel, meta)| (rel.as_path(), meta))
+        .rev()
+        .chain(std::iter::once((Path::new(""), &root_meta)));
+    for (relative, meta) in directories {
+        sync.restore_directory_attrs(relative, meta)?;
+    }
+    Ok(sync.stats)
+}
+
+/// Walks `root` depth-first, names sorted within each directory, returning
+/// every entry the options and filters transfer with its path relative to
+/// `root`.
+///
+/// upstream: flist.c:send_directory() - children are listed, sorted, and
+/// recursed into; entries that cannot be sent, or that `is_excluded()`
+/// rejects, are dropped from the list.
+fn build_file_list(
+    source: &dyn Vfs,
+    root: &Path,
+    options: &VfsSyncOptions,
+    filters: Option<&FilterSet>,
+) -> Result<Vec<(PathBuf, VfsMetadata)>, VfsSyncError> {
+    let mut list = Vec::new();
+    let mut pending = vec![PathBuf::new()];
+    while let Some(directory) = pending.pop() {
+        let path = root.join(&directory);
+        let mut children = source
+            .read_dir(&path)
+            .map_err(|e| VfsSyncError::io("read directory", &path, e))?;
+        children.sort_by(|a, b| a.name().cmp(b.name()));
+        let mut subdirectories = Vec::new();
+        for child in children {
+            let relative = directory.join(child.name());
+            let meta = stat(source, &root.join(&relative))?;
+            let is_dir = meta.file_type() == VfsFileType::Directory;
+            if filters.is_some_and(|set| !set.allows_during_traversal(&relative, is_dir)) {
+                continue;
+            }
+            match meta.file_type() {
+                VfsFileType::Directory => subdirectories.push(relative.clone()),
+                VfsFileType::Symlink if !options.links => continue,
+                VfsFileType::Other => continue,
+                _ => {}
+            }
+            list.push((relative, meta));
+        }
+        // Visit subdirectories in name order once the listing is recorded.
+        pending.extend(subdirectories.into_iter().rev());
+    }
+    Ok(list)
+}
+
+struct Syncer<'a> {
+    source: &'a dyn Vfs,
+    source_root: &'a Path,
+    destination: &'a dyn Vfs,
+    destination_root: &'a Path,
+    options: &'a VfsSyncOptions,
+    policy: DeletionPolicy<'a>,
+    stats: VfsSyncStats,
+}
+
+impl Syncer<'_> {
+    /// Brings one destination entry in line with its source entry.
+    fn apply(&mut self, relative: &Path, meta: &VfsMetadata) -> Result<(), VfsSyncError> {
+        let target = self.destination_root.join(relative);
+        let mut existing = lookup(self.destination, &target)?;
+        // upstream: generator.c:recv_generator() - delete_item() clears an
+        // entry of the wrong kind before the new one is created. Without
+        // DEL_RECURSE (`--delete` or `--force`) a non-empty directory
+        // survives and the entry fails.
+        if let Some(current) = existing.filter(|current| current.file_type() != meta.file_type()) {
+            if current.file_type() == VfsFileType::Directory
+                && !self.policy.removes_conflicting_directories()
+                && !self.is_empty_directory(&target)?
+            {
+                return Err(VfsSyncError::DirectoryNotEmpty { path: target });
+            }
+            self.remove_tree(&target, current)?;
+            existing = None;
+        }
+
+        match meta.file_type() {
+            VfsFileType::Directory => {
+                if existing.is_none() {
+                    self.destination
+                        .create_dir(&target)
+                        .map_err(|e| VfsSyncError::io("create directory", &target, e))?;
+                    self.stats.directories_created += 1;
+                }
+            }
+            VfsFileType::File => {
+                if existing.is_some_and(|current| quick_check_ok(&current, meta, self.options)) {
+                    self.stats.files_up_to_date += 1;
+                } else {
+                    self.transfer_file(relative, &target, meta)?;
+                }
+            }
+            VfsFileType::Symlink => self.sync_symlink(relative, &target, existing.is_some())?,
+            VfsFileType::Other => {}
+        }
+        Ok(())
+    }
+
+    fn transfer_file(
+        &mut self,
+        relative: &Path,
+        target: &Path,
+        meta: &VfsMetadata,
+    ) -> Result<(), VfsSyncError> {
+        let origin = self.source_root.join(relative);
+        let mut written = VfsMetadata::file(meta.len());
+        if let (true, Some(modified)) = (self.options.times, meta.modified()) {
+            written = written.with_modified(modified);
+        }
+        if let (true, Some(mode)) = (self.options.perms, meta.mode()) {
+            written = written.with_mode(mode);
+        }
+
+        let mut reader = self
+            .source
+            .open(&origin)
+            .map_err(|e| VfsSyncError::io("open", &origin, e))?;
+        let mut writer = self
+            .destination
+            .create(target, &written)
+            .map_err(|e| VfsSyncError::io("create", target, e))?;
+        let copied = io::copy(&mut reader, &mut writer)
+            .map_err(|e| VfsSyncError::io("copy into", target, e))?;
+        writer
+            .finish()
+            .map_err(|e| VfsSyncError::io("finish", target, e))?;
+        self.stats.files_transferred += 1;
+        self.stats.bytes_transferred += copied;
+        Ok(())
+    }
+
+    fn sync_symlink(
+        &mut self,
+        relative: &Path,
+        target: &Path,
+        exists: bool,
+    ) -> Result<(), VfsSyncError> {
+        let origin = self.source_root.join(relative);
+        let link_target = self
+            .source
+            .read_link(&origin)
+            .map_err(|e| VfsSyncError::io("read link", &origin, e))?;
+        if exists {
+            let current = self
+                .destination
+                .read_link(target)
+                .map_err(|e| VfsSyncError::io("read link", target, e))?;
+            if current == link_target {
+                return Ok(());
+            }
+            self.destination
+                .remove_file(target)
+                .map_err(|e| VfsSyncError::io("remove", target, e))?;
+        }
+        self.destination
+            .symlink(&link_target, target)
+            .map_err(|e| VfsSyncError::io("create symlink", target, e))?;
+        self.stats.symlinks_created += 1;
+        Ok(())
+    }
+
+    /// Removes destination children of `directory` that the file list lacks
+    /// and the deletion policy allows deleting.
+    ///
+    /// upstream: generator.c:delete_in_dir() - extraneous entries the
+    /// filters do not protect are removed, directories together with their
+    /// contents.
+    fn delete_extraneous(
+        &mut self,
+        directory: &Path,
+        listed: &BTreeSet<&Path>,
+    ) -> Result<(), VfsSyncError> {
+        let path = self.destination_root.join(directory);
+        let children = self
+            .destination
+            .read_dir(&path)
+            .map_err(|e| VfsSyncError::io("read directory", &path, e))?;
+        for child in children {
+            let relative = directory.join(child.name());
+            if listed.contains(relative.as_path()) {
+                continue;
+            }
+            let target = self.destination_root.join(&relative);
+            let meta = stat(self.destination, &target)?;
+            if !self
+                .policy
+                .allows(&relative, meta.file_type() == VfsFileType::Directory)
+            {
+                continue;
+            }
+            self.remove_tree(&target, meta)?;
+        }
+        Ok(())
+    }
+
+    fn is_empty_directory(&self, path: &Path) -> Result<bool, VfsSyncError> {
+        self.destination
+            .read_dir(path)
+            .map(|children| children.is_empty())
+            .map_err(|e| VfsSyncError::io("read directory", path, e))
+    }
+
+    /// Removes `path`, emptying it first when it is a directory.
+    fn remove_tree(&mut self, path: &Path, meta: VfsMetadata) -> Result<(), VfsSyncError> {
+        if meta.file_type() == VfsFileType::Directory {
+            let children = self
+                .destination
+                .read_dir(path)
+                .map_err(|e| VfsSyncError::io("read directory", path, e))?;
+            for child in children {
+                let child_path = path.join(child.name());
+                let child_meta = stat(self.destination, &child_path)?;
+                self.remove_tree(&child_path, child_meta)?;
+            }
+            self.destination
+                .remove_dir(path)
+                .map_err(|e| VfsSyncError::io("remove directory", path, e))?;
+        } else {
+            self.destination
+                .remove_file(path)
+                .map_err(|e| VfsSyncError::io("remove", path, e))?;
+        }
+        self.stats.entries_deleted += 1;
+        Ok(())
+    }
+
+    fn restore_directory_attrs(
+        &self,
+        relative: &Path,
+        meta: &VfsMetadata,
+    ) -> Result<(), VfsSyncError> {
+        let target = self.destination_root.join(relative);
+        if let (true, Some(mode)) = (self.options.perms, meta.mode()) {
+            self.destination
+                .set_mode(&target, mode)
+                .map_err(|e| VfsSyncError::io("set permissions on", &target, e))?;
+        }
+        if let (true, Some(modified)) = (self.options.times, meta.modified()) {
+            self.destination
+                .set_modified(&target, modified)
+                .map_err(|e| VfsSyncError::io("set times on", &target, e))?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether `existing` already matches `source`.
+///
+/// upstream: generator.c:quick_check_ok() - sizes must agree, then the same
+/// `--size-only`, `--ignore-times` and `--modify-window` steps a local copy
+/// applies. There is no `--checksum` step: a file is never read to decide.
+fn quick_check_ok(existing: &VfsMetadata, source: &VfsMetadata, options: &VfsSyncOptions) -> bool {
+    existing.len() == source.len()
+        && quick_check_times_match(
+            source.modified(),
+            existing.modified(),
+            options.size_only,
+            options.ignore_times,
+            options.modify_window,
+        )
+}
+
+fn stat(vfs: &dyn Vfs, path: &Path) -> Result<VfsMetadata, VfsSyncError> {
+    vfs.stat(path)
+        .map_err(|e| VfsSyncError::io("stat", path, e))
+}
+
+/// Like [`stat`], mapping a missing entry to `None`.
+fn lookup(vfs: &dyn Vfs, path: &Path) -> Result<Option<VfsMetadata>, VfsSyncError> {
+    match vfs.stat(path) {
+        Ok(meta) => Ok(Some(meta)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(VfsSyncError::io("stat", path, e)),
+    }
+}
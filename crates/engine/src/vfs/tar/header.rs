@@ -0,0 +1,257 @@
+//! ustar header blocks and pax extended records.
+//!
+//! Every entry is preceded by a pax extended header carrying its full path,
+//! link target, sub-second modification time and extended attributes (as
+//! `SCHILY.xattr.*` records, the form GNU tar and bsdtar restore), plus any
+//! owner id or name too large for its ustar field. The fixed-width ustar
+//! fields therefore only need to hold values that fit; readers that predate
+//! pax still see a truncated but well-formed ustar entry.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Size of one tar block.
+pub(super) const BLOCK: usize = 512;
+
+/// The two zero blocks that terminate an archive.
+pub(super) const END_OF_ARCHIVE: [u8; 2 * BLOCK] = [0; 2 * BLOCK];
+
+/// Name given to pax extended header entries, as GNU tar does.
+const PAX_HEADER_NAME: &[u8] = b"././@PaxHeader";
+
+/// ustar type flags.
+const REGULAR: u8 = b'0';
+const HARD_LINK: u8 = b'1';
+const SYMLINK: u8 = b'2';
+const CHAR_DEVICE: u8 = b'3';
+const BLOCK_DEVICE: u8 = b'4';
+const DIRECTORY: u8 = b'5';
+const FIFO: u8 = b'6';
+const PAX_EXTENDED: u8 = b'x';
+
+/// Width of the ustar `uname` and `gname` fields, including the NUL.
+const NAME_FIELD: usize = 32;
+
+/// The kind of an archive entry.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum EntryKind {
+    File,
+    Directory,
+    Symlink,
+    HardLink,
+    CharDevice,
+    BlockDevice,
+    Fifo,
+}
+
+/// Numeric owner and group of an entry, with their names when known.
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct Owner<'a> {
+    pub(super) uid: u64,
+    pub(super) gid: u64,
+    pub(super) user_name: Option<&'a [u8]>,
+    pub(super) group_name: Option<&'a [u8]>,
+}
+
+/// One entry's header fields.
+pub(super) struct EntryHeader<'a> {
+    /// `/`-separated path; directories carry a trailing `/`.
+    pub(super) path: &'a [u8],
+    pub(super) kind: EntryKind,
+    pub(super) size: u64,
+    pub(super) mode: u32,
+    pub(super) modified: Option<SystemTime>,
+    /// Target of a symlink, or the archive path a hard link refers to.
+    pub(super) link_target: Option<&'a [u8]>,
+    pub(super) owner: Owner<'a>,
+    /// Major and minor numbers of a device entry.
+    pub(super) device: (u32, u32),
+    /// Extended attributes as `(name, value)` pairs.
+    pub(super) xattrs: &'a [(Vec<u8>, Vec<u8>)],
+}
+
+impl EntryHeader<'_> {
+    /// Encodes the pax extended header followed by the ustar header.
+    pub(super) fn encode(&self) -> Vec<u8> {
+        let mut records = Vec::new();
+        pax_record(&mut records, "path", self.path);
+        if let Some(target) = self.link_target {
+            pax_record(&mut records, "linkpath", target);
+        }
+        if let Some(modified) = self.modified {
+            pax_record(&mut records, "mtime", format_pax_time(modified).as_bytes());
+        }
+        if self.size > max_octal(12) {
+            pax_record(&mut records, "size", self.size.to_string().as_bytes());
+        }
+        for (key, id) in [("uid", self.owner.uid), ("gid", self.owner.gid)] {
+            if id > max_octal(8) {
+                pax_record(&mut records, key, id.to_string().as_bytes());
+            }
+        }
+        let names = [
+            ("uname", self.owner.user_name),
+            ("gname", self.owner.group_name),
+        ];
+        for (key, name) in names {
+            if let Some(name) = name.filter(|name| name.len() >= NAME_FIELD) {
+                pax_record(&mut records, key, name);
+            }
+        }
+        for (name, value) in self.xattrs {
+            let mut key = b"SCHILY.xattr.".to_vec();
+            key.extend_from_slice(name);
+            pax_record(&mut records, key, value);
+        }
+
+        let mtime = self.modified.map_or(0, header_time);
+        let mut out = Vec::with_capacity(3 * BLOCK + records.len());
+        let pax = Ustar {
+            name: PAX_HEADER_NAME,
+            typeflag: PAX_EXTENDED,
+            size: records.len() as u64,
+            mode: 0o644,
+            mtime,
+            linkname: b"",
+            owner: Owner::default(),
+            device: (0, 0),
+        };
+        out.extend_from_slice(&pax.encode());
+        out.extend_from_slice(&records);
+        out.resize(out.len() + padding(records.len() as u64), 0);
+
+        let typeflag = match self.kind {
+            EntryKind::File => REGULAR,
+            EntryKind::Directory => DIRECTORY,
+            EntryKind::Symlink => SYMLINK,
+            EntryKind::HardLink => HARD_LINK,
+            EntryKind::CharDevice => CHAR_DEVICE,
+            EntryKind::BlockDevice => BLOCK_DEVICE,
+            EntryKind::Fifo => FIFO,
+        };
+        let entry = Ustar {
+            name: self.path,
+            typeflag,
+            size: self.size,
+            mode: self.mode,
+            mtime,
+            linkname: self.link_target.unwrap_or_default(),
+            owner: self.owner,
+            device: self.device,
+        };
+        out.extend_from_slice(&entry.encode());
+        out
+    }
+}
+
+/// Zero bytes needed after `len` bytes of data to reach a block boundary.
+pub(super) fn padding(len: u64) -> usize {
+    // The remainder is below BLOCK, so the cast cannot truncate.
+    ((BLOCK as u64 - len % BLOCK as u64) % BLOCK as u64) as usize
+}
+
+/// The fields of one ustar header block.
+struct Ustar<'a> {
+    name: &'a [u8],
+    typeflag: u8,
+    size: u64,
+    mode: u32,
+    mtime: u64,
+    linkname: &'a [u8],
+    owner: Owner<'a>,
+    device: (u32, u32),
+}
+
+impl Ustar<'_> {
+    /// Builds the header block. Over-long names and link targets are
+    /// truncated and over-large numbers saturate; the preceding pax header
+    /// carries the exact values.
+    fn encode(&self) -> [u8; BLOCK] {
+        let mut block = [0u8; BLOCK];
+        put_bytes(&mut block[0..100], self.name);
+        put_octal(&mut block[100..108], u64::from(self.mode & 0o7777));
+        put_octal(&mut block[108..116], self.owner.uid);
+        put_octal(&mut block[116..124], self.owner.gid);
+        put_octal(&mut block[124..136], self.size);
+        put_octal(&mut block[136..148], self.mtime);
+        block[156] = self.typeflag;
+        put_bytes(&mut block[157..257], self.linkname);
+        block[257..263].copy_from_slice(b"ustar\0");
+        block[263..265].copy_from_slice(b"00");
+        // The name fields keep a terminating NUL; longer names are in pax.
+        let user_name = self.owner.user_name.unwrap_or_default();
+        put_bytes(&mut block[265..296], user_name);
+        let group_name = self.owner.group_name.unwrap_or_default();
+        put_bytes(&mut block[297..328], group_name);
+        put_octal(&mut block[329..337], u64::from(self.device.0));
+        put_octal(&mut block[337..345], u64::from(self.device.1));
+
+        // The checksum is computed with its own field read as spaces, then
+        // stored as six octal digits, a NUL and a space.
+        block[148..156].fill(b' ');
+        let sum: u64 = block.iter().map(|&byte| u64::from(byte)).sum();
+        put_octal(&mut block[148..155], sum);
+        block
+    }
+}
+
+fn put_bytes(field: &mut [u8], value: &[u8]) {
+    let len = value.len().min(field.len());
+    field[..len].copy_from_slice(&value[..len]);
+}
+
+/// Writes `value` as zero-padded octal followed by a NUL.
+fn put_octal(field: &mut [u8], value: u64) {
+    let width = field.len() - 1;
+    let digits = format!("{:0width$o}", value.min(max_octal(field.len())));
+    field[..width].copy_from_slice(digits.as_bytes());
+    field[width] = 0;
+}
+
+/// Largest value an octal field of `len` bytes (including its NUL) holds.
+const fn max_octal(len: usize) -> u64 {
+    (1 << (3 * (len - 1))) - 1
+}
+
+/// Appends one `"<len> <key>=<value>\n"` record, where `<len>` counts the
+/// whole record including its own digits.
+pub(super) fn pax_record(out: &mut Vec<u8>, key: impl AsRef<[u8]>, value: &[u8]) {
+    let key = key.as_ref();
+    let base = key.len() + value.len() + 3;
+    let mut len = base;
+    loop {
+        let total = base + len.to_string().len();
+        if total == len {
+            break;
+        }
+        len = total;
+    }
+    out.extend_from_slice(format!("{len} ").as_bytes());
+    out.extend_from_slice(key);
+    out.push(b'=');
+    out.extend_from_slice(value);
+    out.push(b'\n');
+}
+
+/// Seconds since the epoch with the fraction trimmed of trailing zeros.
+fn format_pax_time(time: SystemTime) -> String {
+    let (sign, offset) = match time.duration_since(UNIX_EPOCH) {
+        Ok(after) => ("", after),
+        Err(before) => ("-", before.duration()),
+    };
+    let nanos = offset.subsec_nanos();
+    if nanos == 0 {
+        return format!("{sign}{}", offset.as_secs());
+    }
+    let fraction = format!("{nanos:09}");
+    format!(
+        "{sign}{}.{}",
+        offset.as_secs(),
+        fraction.trim_end_matches('0')
+    )
+}
+
+/// Whole seconds for the ustar `mtime` field, which cannot go negative.
+fn header_time(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |after| after.as_secs())
+}
@@ -0,0 +1,492 @@
+//! Write-only tar archive destination.
+//!
+//! [`TarFs`] turns the [`Vfs`] calls a sync makes into a pax-format tar
+//! stream. Each file's header is written when [`Vfs::create`] is called -
+//! the declared length sizes it - and the data follows as the writer
+//! streams it, so nothing is buffered beyond one entry's header. Full
+//! paths, link targets and sub-second modification times travel in pax
+//! extended headers; owner fields are written as 0 with empty names.
+//!
+//! Directory entries are held back and written by [`TarFs::finish`], after
+//! every file, carrying the modification times and modes [`sync_trees`]
+//! restores last - the archive counterpart of upstream deferring directory
+//! times until their contents are complete. Extractors create parent
+//! directories on demand and then apply the directory entry's attributes.
+//!
+//! An archive is append-only: renames and removals fail with
+//! [`io::ErrorKind::Unsupported`], which [`sync_trees`] never needs when
+//! the archive starts empty.
+//!
+//! Callers that walk a tree themselves and need owners, devices, hard links
+//! or extended attributes in the archive write through [`TarWriter`]
+//! instead, which streams each [`TarEntry`] straight to the sink.
+//!
+//! [`sync_trees`]: super::sync_trees
+
+mod header;
+mod writer;
+
+#[cfg(test)]
+mod tests;
+
+use std::collections::BTreeMap;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use super::mem::key;
+use super::{Vfs, VfsDirEntry, VfsMetadata, VfsWriter};
+use crate::util::poison::lock_or_recover;
+use header::{BLOCK, END_OF_ARCHIVE, EntryHeader, EntryKind, Owner, padding};
+pub use writer::{TarEntry, TarEntryKind, TarWriter};
+
+/// Mode written for entries whose metadata carries none.
+const DEFAULT_FILE_MODE: u32 = 0o644;
+const DEFAULT_DIRECTORY_MODE: u32 = 0o755;
+const SYMLINK_MODE: u32 = 0o777;
+
+#[derive(Clone, Debug)]
+enum Entry {
+    File(VfsMetadata),
+    Directory(VfsMetadata),
+    Symlink(PathBuf),
+}
+
+impl Entry {
+    fn metadata(&self) -> VfsMetadata {
+        match self {
+            Self::File(meta) | Self::Directory(meta) => *meta,
+            Self::Symlink(_) => VfsMetadata::symlink(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct State<W> {
+    writer: W,
+    entries: BTreeMap<PathBuf, Entry>,
+    /// A file entry's data is being streamed.
+    writing: bool,
+    /// A write failed or an entry was abandoned part-way; the stream can no
+    /// longer be completed.
+    broken: bool,
+}
+
+impl<W: Write> State<W> {
+    fn ready(&self) -> io::Result<()> {
+        if self.broken {
+            return Err(broken_error());
+        }
+        if self.writing {
+            return Err(io::Error::new(
+                io::ErrorKind::ResourceBusy,
+                "another archive entry is still being written",
+            ));
+        }
+        Ok(())
+    }
+
+    fn write_all(&mut self, bytes: &[u8]) -> io::Result<()> {
+        let result = self.writer.write_all(bytes);
+        if result.is_err() {
+            self.broken = true;
+        }
+        result
+    }
+
+    /// Fails unless the parent of `key` is a directory already recorded.
+    fn require_parent(&self, key: &Path, path: &Path) -> io::Result<()> {
+        let parent = key.parent().unwrap_or(Path::new(""));
+        if parent.as_os_str().is_empty()
+            || matches!(self.entries.get(parent), Some(Entry::Directory(_)))
+        {
+            return Ok(());
+        }
+        Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("parent of '{}' is not in the archive", path.display()),
+        ))
+    }
+
+    fn require_absent(&self, key: &Path, path: &Path) -> io::Result<()> {
+        if key.as_os_str().is_empty() || self.entries.contains_key(key) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("'{}' is already in the archive", path.display()),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A tar stream being written through the [`Vfs`] interface.
+///
+/// Create one over any [`Write`] sink, run [`super::sync_trees`] into it,
+/// then call [`finish`](Self::finish) to write the directory entries and
+/// the end-of-archive marker. Dropping a `TarFs` without finishing leaves
+/// a truncated archive.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+/// use std::path::Path;
+///
+/// use engine::vfs::{MemFs, TarFs, Vfs, VfsMetadata, VfsSyncOptions, sync_trees};
+///
+/// let src = MemFs::new();
+/// let mut file = src.create(Path::new("a.txt"), &VfsMetadata::file(5)).unwrap();
+/// file.write_all(b"hello").unwrap();
+/// file.finish().unwrap();
+///
+/// let archive = TarFs::new(Vec::new());
+/// let root = Path::new("");
+/// sync_trees(&src, root, &archive, root, &VfsSyncOptions::new()).unwrap();
+/// let bytes = archive.finish().unwrap();
+/// assert_eq!(bytes.len() % 512, 0);
+/// ```
+#[derive(Debug)]
+pub struct TarFs<W> {
+    state: Mutex<State<W>>,
+}
+
+impl<W: Write + Send> TarFs<W> {
+    /// Starts an empty archive written to `writer`.
+    #[must_use]
+    pub fn new(writer: W) -> Self {
+        Self {
+            state: Mutex::new(State {
+                writer,
+                entries: BTreeMap::new(),
+                writing: false,
+                broken: false,
+            }),
+        }
+    }
+
+    /// Writes the directory entries and the end-of-archive marker, flushes
+    /// the sink and returns it.
+    ///
+    /// # Errors
+    ///
+    /// Fails when an earlier write failed or a file entry was abandoned,
+    /// since the stream is then corrupt, or when the final writes fail.
+    pub fn finish(self) -> io::Result<W> {
+        let mut state = self
+            .state
+            .into_inner()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if state.broken {
+            return Err(broken_error());
+        }
+        let directories: Vec<(PathBuf, VfsMetadata)> = state
+            .entries
+            .iter()
+            .filter_map(|(key, entry)| match entry {
+                Entry::Directory(meta) => Some((key.clone(), *meta)),
+                _ => None,
+            })
+            .collect();
+        for (key, meta) in directories {
+            let mut path = path_bytes(&key);
+            path.push(b'/');
+            let header = EntryHeader {
+                path: &path,
+                kind: EntryKind::Directory,
+                size: 0,
+                mode: meta.mode().unwrap_or(DEFAULT_DIRECTORY_MODE),
+                modified: meta.modified(),
+                link_target: None,
+                owner: Owner::default(),
+                device: (0, 0),
+                xattrs: &[],
+            };
+            state.write_all(&header.encode())?;
+        }
+        state.write_all(&END_OF_ARCHIVE)?;
+        state.writer.flush()?;
+        Ok(state.writer)
+    }
+
+    /// Updates a pending directory entry's metadata.
+    fn update_directory(
+        &self,
+        path: &Path,
+        update: impl FnOnce(VfsMetadata) -> VfsMetadata,
+    ) -> io::Result<()> {
+        let key = key(path)?;
+        if key.as_os_str().is_empty() {
+            // The root itself has no entry in the archive.
+            return Ok(());
+        }
+        let mut state = lock_or_recover(&self.state);
+        match state.entries.get_mut(&key) {
+            Some(Entry::Directory(meta)) => {
+                *meta = update(*meta);
+                Ok(())
+            }
+            Some(_) => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("'{}' is already written to the archive", path.display()),
+            )),
+            None => Err(not_found(path)),
+        }
+    }
+}
+
+/// Streams one file's data into the archive.
+struct TarEntryWriter<'a, W: Write + Send> {
+    fs: &'a TarFs<W>,
+    key: PathBuf,
+    metadata: VfsMetadata,
+    remaining: u64,
+    finished: bool,
+}
+
+impl<W: Write + Send> Write for TarEntryWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut state = lock_or_recover(&self.fs.state);
+        if buf.len() as u64 > self.remaining {
+            state.broken = true;
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "'{}' grew past the {} bytes its header declared",
+                    self.key.display(),
+                    self.metadata.len()
+                ),
+            ));
+        }
+        state.write_all(buf)?;
+        self.remaining -= buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: Write + Send> VfsWriter for TarEntryWriter<'_, W> {
+    fn finish(mut self: Box<Self>) -> io::Result<()> {
+        self.finished = true;
+        let mut state = lock_or_recover(&self.fs.state);
+        state.writing = false;
+        if self.remaining != 0 {
+            state.broken = true;
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!(
+                    "'{}' ended {} bytes short of its declared length",
+                    self.key.display(),
+                    self.remaining
+                ),
+            ));
+        }
+        let pad = padding(self.metadata.len());
+        state.write_all(&[0u8; BLOCK][..pad])?;
+        state
+            .entries
+            .insert(self.key.clone(), Entry::File(self.metadata));
+        Ok(())
+    }
+}
+
+impl<W: Write + Send> Drop for TarEntryWriter<'_, W> {
+    fn drop(&mut self) {
+        if !self.finished {
+            // The header is already out; the data can no longer be taken back.
+            let mut state = lock_or_recover(&self.fs.state);
+            state.writing = false;
+            state.broken = true;
+        }
+    }
+}
+
+impl<W: Write + Send> Vfs for TarFs<W> {
+    fn stat(&self, path: &Path) -> io::Result<VfsMetadata> {
+        let key = key(path)?;
+        if key.as_os_str().is_empty() {
+            return Ok(VfsMetadata::directory());
+        }
+        lock_or_recover(&self.state)
+            .entries
+            .get(&key)
+            .map(Entry::metadata)
+            .ok_or_else(|| not_found(path))
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<VfsDirEntry>> {
+        let key = key(path)?;
+        let state = lock_or_recover(&self.state);
+        if !key.as_os_str().is_empty() {
+            match state.entries.get(&key) {
+                Some(Entry::Directory(_)) => {}
+                Some(_) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::NotADirectory,
+                        format!("'{}' is not a directory", path.display()),
+                    ));
+                }
+                None => return Err(not_found(path)),
+            }
+        }
+        Ok(state
+            .entries
+            .iter()
+            .filter(|(child, _)| child.parent() == Some(key.as_path()))
+            .filter_map(|(child, entry)| {
+                let name = child.file_name()?;
+                Some(VfsDirEntry::new(name, entry.metadata().file_type()))
+            })
+            .collect())
+    }
+
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read + Send + '_>> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("cannot read '{}' back from a tar stream", path.display()),
+        ))
+    }
+
+    fn create(&self, path: &Path, metadata: &VfsMetadata) -> io::Result<Box<dyn VfsWriter + '_>> {
+        let key = key(path)?;
+        let mut state = lock_or_recover(&self.state);
+        state.ready()?;
+        state.require_parent(&key, path)?;
+        if key.as_os_str().is_empty()
+            || matches!(state.entries.get(&key), Some(Entry::Directory(_)))
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::IsADirectory,
+                format!("'{}' is a directory", path.display()),
+            ));
+        }
+        let name = path_bytes(&key);
+        let header = EntryHeader {
+            path: &name,
+            kind: EntryKind::File,
+            size: metadata.len(),
+            mode: metadata.mode().unwrap_or(DEFAULT_FILE_MODE),
+            modified: metadata.modified(),
+            link_target: None,
+            owner: Owner::default(),
+            device: (0, 0),
+            xattrs: &[],
+        };
+        state.write_all(&header.encode())?;
+        state.writing = true;
+        Ok(Box::new(TarEntryWriter {
+            fs: self,
+            key,
+            metadata: *metadata,
+            remaining: metadata.len(),
+            finished: false,
+        }))
+    }
+
+    fn create_dir(&self, path: &Path) -> io::Result<()> {
+        let key = key(path)?;
+        let mut state = lock_or_recover(&self.state);
+        state.ready()?;
+        state.require_absent(&key, path)?;
+        state.require_parent(&key, path)?;
+        state
+            .entries
+            .insert(key, Entry::Directory(VfsMetadata::directory()));
+        Ok(())
+    }
+
+    fn symlink(&self, target: &Path, link: &Path) -> io::Result<()> {
+        let key = key(link)?;
+        let mut state = lock_or_recover(&self.state);
+        state.ready()?;
+        state.require_absent(&key, link)?;
+        state.require_parent(&key, link)?;
+        let name = path_bytes(&key);
+        let link_target = path_bytes(target);
+        let header = EntryHeader {
+            path: &name,
+            kind: EntryKind::Symlink,
+            size: 0,
+            mode: SYMLINK_MODE,
+            modified: None,
+            link_target: Some(&link_target),
+            owner: Owner::default(),
+            device: (0, 0),
+            xattrs: &[],
+        };
+        state.write_all(&header.encode())?;
+        state
+            .entries
+            .insert(key, Entry::Symlink(target.to_path_buf()));
+        Ok(())
+    }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        let key = key(path)?;
+        match lock_or_recover(&self.state).entries.get(&key) {
+            Some(Entry::Symlink(target)) => Ok(target.clone()),
+            Some(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("'{}' is not a symlink", path.display()),
+            )),
+            None => Err(not_found(path)),
+        }
+    }
+
+    fn rename(&self, from: &Path, _to: &Path) -> io::Result<()> {
+        Err(append_only(from))
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        Err(append_only(path))
+    }
+
+    fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        Err(append_only(path))
+    }
+
+    fn set_modified(&self, path: &Path, modified: SystemTime) -> io::Result<()> {
+        self.update_directory(path, |meta| meta.with_modified(modified))
+    }
+
+    fn set_mode(&self, path: &Path, mode: u32) -> io::Result<()> {
+        self.update_directory(path, |meta| meta.with_mode(mode))
+    }
+}
+
+/// Raw bytes of a `/`-separated archive path.
+fn path_bytes(path: &Path) -> Vec<u8> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        path.as_os_str().as_bytes().to_vec()
+    }
+    #[cfg(not(unix))]
+    {
+        path.to_string_lossy().replace('\\', "/").into_bytes()
+    }
+}
+
+fn not_found(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("'{}' is not in the archive", path.display()),
+    )
+}
+
+fn append_only(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!(
+            "cannot change '{}': tar archives are append-only",
+            path.display()
+        ),
+    )
+}
+
+fn broken_error() -> io::Error {
+    io::Error::other("the archive stream is incomplete after an earlier failure")
+}
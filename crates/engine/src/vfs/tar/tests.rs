@@ -0,0 +1,269 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::time::{Duration, SystemTime};
+
+use super::*;
+use crate::vfs::{MemFs, VfsSyncOptions, sync_trees};
+
+/// One archive member as a pax-aware reader sees it.
+#[derive(Debug)]
+struct Member {
+    typeflag: u8,
+    path: String,
+    mode: u32,
+    pax: BTreeMap<String, String>,
+    data: Vec<u8>,
+}
+
+fn octal(field: &[u8]) -> u64 {
+    let text = std::str::from_utf8(field).unwrap();
+    u64::from_str_radix(text.trim_end_matches(['\0', ' ']), 8).unwrap()
+}
+
+fn parse_pax(mut records: &[u8]) -> BTreeMap<String, String> {
+    let mut map = BTreeMap::new();
+    while !records.is_empty() {
+        let space = records.iter().position(|&b| b == b' ').unwrap();
+        let len: usize = std::str::from_utf8(&records[..space])
+            .unwrap()
+            .parse()
+            .unwrap();
+        let record = std::str::from_utf8(&records[space + 1..len - 1]).unwrap();
+        let (key, value) = record.split_once('=').unwrap();
+        map.insert(key.to_owned(), value.to_owned());
+        assert_eq!(records[len - 1], b'\n');
+        records = &records[len..];
+    }
+    map
+}
+
+/// Reads every member, checking each header checksum and the end marker.
+fn members(archive: &[u8]) -> Vec<Member> {
+    assert_eq!(archive.len() % BLOCK, 0);
+    let mut out = Vec::new();
+    let mut pax = BTreeMap::new();
+    let mut offset = 0;
+    loop {
+        let block = &archive[offset..offset + BLOCK];
+        offset += BLOCK;
+        if block.iter().all(|&b| b == 0) {
+            assert!(archive[offset..].iter().all(|&b| b == 0));
+            assert_eq!(archive.len() - offset, BLOCK);
+            return out;
+        }
+        let mut summed = block.to_vec();
+        summed[148..156].fill(b' ');
+        let sum: u64 = summed.iter().map(|&b| u64::from(b)).sum();
+        assert_eq!(octal(&block[148..155]), sum, "header checksum");
+        assert_eq!(&block[257..263], b"ustar\0");
+
+        let size = octal(&block[124..136]) as usize;
+        let data = archive[offset..offset + size].to_vec();
+        offset += size + padding(size as u64);
+        if block[156] == b'x' {
+            pax = parse_pax(&data);
+            continue;
+        }
+        let name = &block[..100];
+        let name = &name[..name.iter().position(|&b| b == 0).unwrap_or(100)];
+        let pax = std::mem::take(&mut pax);
+        out.push(Member {
+            typeflag: block[156],
+            path: pax
+                .get("path")
+                .cloned()
+                .unwrap_or_else(|| String::from_utf8(name.to_vec()).unwrap()),
+            mode: octal(&block[100..108]) as u32,
+            pax,
+            data,
+        });
+    }
+}
+
+fn put(vfs: &dyn Vfs, path: &str, data: &[u8], modified: SystemTime) {
+    let meta = VfsMetadata::file(data.len() as u64)
+        .with_modified(modified)
+        .with_mode(0o600);
+    let mut writer = vfs.create(Path::new(path), &meta).unwrap();
+    writer.write_all(data).unwrap();
+    writer.finish().unwrap();
+}
+
+fn epoch(secs: u64, nanos: u32) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::new(secs, nanos)
+}
+
+#[test]
+fn sync_writes_files_links_and_deferred_directories() {
+    let src = MemFs::new();
+    src.create_dir(Path::new("dir")).unwrap();
+    put(&src, "dir/a", b"alpha", epoch(2_000, 500_000_000));
+    src.symlink(Path::new("dir/a"), Path::new("link")).unwrap();
+    src.set_modified(Path::new("dir"), epoch(3_000, 0)).unwrap();
+
+    let archive = TarFs::new(Vec::new());
+    let root = Path::new("");
+    let options = VfsSyncOptions::new().perms(true);
+    sync_trees(&src, root, &archive, root, &options).unwrap();
+    let members = members(&archive.finish().unwrap());
+
+    let summary: Vec<(u8, &str)> = members
+        .iter()
+        .map(|m| (m.typeflag, m.path.as_str()))
+        .collect();
+    assert_eq!(summary, [(b'0', "dir/a"), (b'2', "link"), (b'5', "dir/")]);
+
+    let file = &members[0];
+    assert_eq!(file.data, b"alpha");
+    assert_eq!(file.mode, 0o600);
+    assert_eq!(file.pax["mtime"], "2000.5");
+    assert_eq!(members[1].pax["linkpath"], "dir/a");
+    assert_eq!(members[2].pax["mtime"], "3000");
+}
+
+#[test]
+fn long_paths_travel_in_pax_headers() {
+    let archive = TarFs::new(Vec::new());
+    let long = "d".repeat(150);
+    archive.create_dir(Path::new(&long)).unwrap();
+    let file = format!("{long}/{}", "f".repeat(120));
+    put(&archive, &file, b"x", epoch(1, 0));
+
+    let members = members(&archive.finish().unwrap());
+    assert_eq!(members[0].path, file);
+    assert_eq!(members[1].path, format!("{long}/"));
+}
+
+#[test]
+fn short_writes_and_abandoned_entries_break_the_archive() {
+    let archive = TarFs::new(Vec::new());
+    let mut writer = archive
+        .create(Path::new("f"), &VfsMetadata::file(4))
+        .unwrap();
+    writer.write_all(b"ab").unwrap();
+    assert_eq!(
+        writer.finish().unwrap_err().kind(),
+        io::ErrorKind::UnexpectedEof
+    );
+    assert!(archive.create_dir(Path::new("d")).is_err());
+    assert!(archive.finish().is_err());
+
+    let archive = TarFs::new(Vec::new());
+    let writer = archive
+        .create(Path::new("f"), &VfsMetadata::file(4))
+        .unwrap();
+    drop(writer);
+    assert!(archive.finish().is_err());
+}
+
+#[test]
+fn overlong_writes_are_rejected() {
+    let archive = TarFs::new(Vec::new());
+    let mut writer = archive
+        .create(Path::new("f"), &VfsMetadata::file(1))
+        .unwrap();
+    assert_eq!(
+        writer.write_all(b"ab").unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+}
+
+#[test]
+fn archive_is_append_only() {
+    let archive = TarFs::new(Vec::new());
+    put(&archive, "f", b"x", epoch(1, 0));
+    assert_eq!(
+        archive.remove_file(Path::new("f")).unwrap_err().kind(),
+        io::ErrorKind::Unsupported
+    );
+    assert_eq!(
+        archive.open(Path::new("f")).err().unwrap().kind(),
+        io::ErrorKind::Unsupported
+    );
+    let orphan = archive.create(Path::new("missing/f"), &VfsMetadata::file(0));
+    assert_eq!(orphan.err().unwrap().kind(), io::ErrorKind::NotFound);
+}
+
+#[test]
+fn pax_record_length_counts_its_own_digits() {
+    let mut out = Vec::new();
+    header::pax_record(&mut out, "path", &[b'a'; 92]);
+    assert_eq!(out.len(), 102);
+    assert!(out.starts_with(b"102 path="));
+}
+
+#[test]
+fn writer_records_owners_devices_hard_links_and_xattrs() {
+    let mut writer = TarWriter::new(Vec::new());
+    let file = TarEntry::new("dir/a", TarEntryKind::File)
+        .with_len(5)
+        .with_mode(0o640)
+        .with_owner(1000, Some(b"alice".to_vec()))
+        .with_group(3_000_000, Some(b"g".repeat(40)))
+        .with_xattr("user.note", "hi");
+    writer
+        .append(
+            &TarEntry::new("dir", TarEntryKind::Directory),
+            &mut io::empty(),
+        )
+        .unwrap();
+    writer.append(&file, &mut &b"alpha"[..]).unwrap();
+    let link = TarEntry::new("b", TarEntryKind::HardLink(PathBuf::from("dir/a")));
+    writer.append(&link, &mut io::empty()).unwrap();
+    let device = TarEntry::new("null", TarEntryKind::CharDevice { major: 1, minor: 3 });
+    writer.append(&device, &mut io::empty()).unwrap();
+    let archive = writer.finish().unwrap();
+    let members = members(&archive);
+
+    let summary: Vec<(u8, &str)> = members
+        .iter()
+        .map(|m| (m.typeflag, m.path.as_str()))
+        .collect();
+    assert_eq!(
+        summary,
+        [(b'5', "dir/"), (b'0', "dir/a"), (b'1', "b"), (b'3', "null")]
+    );
+    assert_eq!(members[1].data, b"alpha");
+    assert_eq!(members[1].mode, 0o640);
+    assert_eq!(members[1].pax["SCHILY.xattr.user.note"], "hi");
+    assert_eq!(members[1].pax["gname"], "g".repeat(40));
+    assert!(!members[1].pax.contains_key("uname"));
+    assert_eq!(members[2].pax["linkpath"], "dir/a");
+
+    // The ustar fields of the file header, found by its data offset.
+    let header = archive
+        .windows(5)
+        .position(|window| window == b"alpha")
+        .unwrap()
+        - BLOCK;
+    let block = &archive[header..header + BLOCK];
+    assert_eq!(octal(&block[108..116]), 1000);
+    // A gid past the octal field saturates there and travels in pax.
+    assert_eq!(octal(&block[116..124]), 0o7777777);
+    assert_eq!(members[1].pax["gid"], "3000000");
+    assert!(block[265..].starts_with(b"alice\0"));
+    let device = archive.len() - 3 * BLOCK;
+    assert_eq!(octal(&archive[device + 329..device + 337]), 1);
+    assert_eq!(octal(&archive[device + 337..device + 345]), 3);
+}
+
+#[test]
+fn writer_refuses_to_finish_after_a_short_file() {
+    let mut writer = TarWriter::new(Vec::new());
+    let entry = TarEntry::new("f", TarEntryKind::File).with_len(4);
+    let error = writer.append(&entry, &mut &b"ab"[..]).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    assert!(
+        writer
+            .append(
+                &TarEntry::new("d", TarEntryKind::Directory),
+                &mut io::empty()
+            )
+            .is_err()
+    );
+    assert!(writer.finish().is_err());
+
+    let mut writer = TarWriter::new(Vec::new());
+    let error = writer.append(&entry, &mut &b"abcde"[..]).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+}
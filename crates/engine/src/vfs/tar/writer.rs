@@ -0,0 +1,287 @@
+//! Streaming tar writer for callers that walk a tree themselves.
+//!
+//! [`TarFs`](super::TarFs) only records what the [`Vfs`](crate::vfs::Vfs)
+//! interface carries: kind, length, mode and modification time. [`TarWriter`]
+//! takes each entry's full metadata - owner and group, device numbers,
+//! hard-link target and extended attributes - and writes the entry straight
+//! to the sink as it is appended, so nothing beyond one header is buffered.
+
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use super::header::{BLOCK, END_OF_ARCHIVE, EntryHeader, EntryKind, Owner, padding};
+use super::{DEFAULT_DIRECTORY_MODE, DEFAULT_FILE_MODE, SYMLINK_MODE, broken_error, path_bytes};
+
+/// The kind of a [`TarEntry`], with the data each kind carries.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TarEntryKind {
+    /// A regular file; its bytes follow the header.
+    File,
+    /// A directory.
+    Directory,
+    /// A symbolic link to the given target.
+    Symlink(PathBuf),
+    /// A hard link to an entry already written at the given archive path.
+    HardLink(PathBuf),
+    /// A character device.
+    CharDevice {
+        /// Major device number.
+        major: u32,
+        /// Minor device number.
+        minor: u32,
+    },
+    /// A block device.
+    BlockDevice {
+        /// Major device number.
+        major: u32,
+        /// Minor device number.
+        minor: u32,
+    },
+    /// A named pipe.
+    Fifo,
+}
+
+/// One archive member and the metadata recorded for it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TarEntry {
+    path: PathBuf,
+    kind: TarEntryKind,
+    len: u64,
+    mode: Option<u32>,
+    modified: Option<SystemTime>,
+    uid: u64,
+    gid: u64,
+    user_name: Option<Vec<u8>>,
+    group_name: Option<Vec<u8>>,
+    xattrs: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl TarEntry {
+    /// Describes the member at the `/`-separated archive `path`.
+    ///
+    /// Owner and group default to 0 with no names, and the mode to the
+    /// kind's usual default.
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>, kind: TarEntryKind) -> Self {
+        Self {
+            path: path.into(),
+            kind,
+            len: 0,
+            mode: None,
+            modified: None,
+            uid: 0,
+            gid: 0,
+            user_name: None,
+            group_name: None,
+            xattrs: Vec::new(),
+        }
+    }
+
+    /// Sets the number of bytes a [`TarEntryKind::File`] holds.
+    #[must_use]
+    pub const fn with_len(mut self, len: u64) -> Self {
+        self.len = len;
+        self
+    }
+
+    /// Sets the permission bits.
+    #[must_use]
+    pub const fn with_mode(mut self, mode: u32) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Sets the modification time.
+    #[must_use]
+    pub const fn with_modified(mut self, modified: SystemTime) -> Self {
+        self.modified = Some(modified);
+        self
+    }
+
+    /// Sets the numeric owner and, when known, its name.
+    #[must_use]
+    pub fn with_owner(mut self, uid: u64, name: Option<Vec<u8>>) -> Self {
+        self.uid = uid;
+        self.user_name = name;
+        self
+    }
+
+    /// Sets the numeric group and, when known, its name.
+    #[must_use]
+    pub fn with_group(mut self, gid: u64, name: Option<Vec<u8>>) -> Self {
+        self.gid = gid;
+        self.group_name = name;
+        self
+    }
+
+    /// Adds one extended attribute, recorded as `SCHILY.xattr.<name>`.
+    #[must_use]
+    pub fn with_xattr(mut self, name: impl Into<Vec<u8>>, value: impl Into<Vec<u8>>) -> Self {
+        self.xattrs.push((name.into(), value.into()));
+        self
+    }
+
+    /// Returns the archive path.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the entry kind.
+    #[must_use]
+    pub const fn kind(&self) -> &TarEntryKind {
+        &self.kind
+    }
+
+    /// Returns the data length; zero for anything but a regular file.
+    #[must_use]
+    pub const fn len(&self) -> u64 {
+        match self.kind {
+            TarEntryKind::File => self.len,
+            _ => 0,
+        }
+    }
+
+    /// Reports whether the entry carries no data.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A tar stream written one [`TarEntry`] at a time.
+///
+/// Entries are written in the order they are appended. A failed append
+/// leaves the stream unusable: later appends and [`finish`](Self::finish)
+/// fail, so a caller never mistakes a truncated archive for a complete one.
+#[derive(Debug)]
+pub struct TarWriter<W> {
+    writer: W,
+    broken: bool,
+}
+
+impl<W: Write> TarWriter<W> {
+    /// Starts an empty archive written to `writer`.
+    #[must_use]
+    pub const fn new(writer: W) -> Self {
+        Self {
+            writer,
+            broken: false,
+        }
+    }
+
+    /// Writes `entry`, followed for a regular file by exactly
+    /// [`TarEntry::len`] bytes read from `data`.
+    ///
+    /// `data` is not read for other kinds; pass [`io::empty`].
+    ///
+    /// # Errors
+    ///
+    /// Fails when an earlier append failed, when `data` yields fewer or more
+    /// bytes than the entry declares, or when the sink fails. The archive
+    /// cannot be completed after any of these.
+    pub fn append(&mut self, entry: &TarEntry, data: &mut dyn Read) -> io::Result<()> {
+        if self.broken {
+            return Err(broken_error());
+        }
+        let result = self.write_entry(entry, data);
+        if result.is_err() {
+            self.broken = true;
+        }
+        result
+    }
+
+    /// Writes the end-of-archive marker, flushes the sink and returns it.
+    ///
+    /// # Errors
+    ///
+    /// Fails when an earlier append failed or the final writes fail.
+    pub fn finish(mut self) -> io::Result<W> {
+        if self.broken {
+            return Err(broken_error());
+        }
+        self.writer.write_all(&END_OF_ARCHIVE)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn write_entry(&mut self, entry: &TarEntry, data: &mut dyn Read) -> io::Result<()> {
+        let mut path = path_bytes(&entry.path);
+        let (kind, default_mode, device, link_target) = match &entry.kind {
+            TarEntryKind::File => (EntryKind::File, DEFAULT_FILE_MODE, (0, 0), None),
+            TarEntryKind::Directory => {
+                path.push(b'/');
+                (EntryKind::Directory, DEFAULT_DIRECTORY_MODE, (0, 0), None)
+            }
+            TarEntryKind::Symlink(target) => (
+                EntryKind::Symlink,
+                SYMLINK_MODE,
+                (0, 0),
+                Some(path_bytes(target)),
+            ),
+            TarEntryKind::HardLink(target) => (
+                EntryKind::HardLink,
+                DEFAULT_FILE_MODE,
+                (0, 0),
+                Some(path_bytes(target)),
+            ),
+            TarEntryKind::CharDevice { major, minor } => (
+                EntryKind::CharDevice,
+                DEFAULT_FILE_MODE,
+                (*major, *minor),
+                None,
+            ),
+            TarEntryKind::BlockDevice { major, minor } => (
+                EntryKind::BlockDevice,
+                DEFAULT_FILE_MODE,
+                (*major, *minor),
+                None,
+            ),
+            TarEntryKind::Fifo => (EntryKind::Fifo, DEFAULT_FILE_MODE, (0, 0), None),
+        };
+        let header = EntryHeader {
+            path: &path,
+            kind,
+            size: entry.len(),
+            mode: entry.mode.unwrap_or(default_mode),
+            modified: entry.modified,
+            link_target: link_target.as_deref(),
+            owner: Owner {
+                uid: entry.uid,
+                gid: entry.gid,
+                user_name: entry.user_name.as_deref(),
+                group_name: entry.group_name.as_deref(),
+            },
+            device,
+            xattrs: &entry.xattrs,
+        };
+        self.writer.write_all(&header.encode())?;
+        if kind != EntryKind::File {
+            return Ok(());
+        }
+
+        let declared = entry.len();
+        let copied = io::copy(&mut Read::take(&mut *data, declared), &mut self.writer)?;
+        if copied != declared {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!(
+                    "'{}' ended {} bytes short of its declared length",
+                    entry.path.display(),
+                    declared - copied
+                ),
+            ));
+        }
+        if data.read(&mut [0u8; 1])? != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "'{}' grew past the {declared} bytes its header declared",
+                    entry.path.display()
+                ),
+            ));
+        }
+        self.writer.write_all(&[0u8; BLOCK][..padding(declared)])
+    }
+}
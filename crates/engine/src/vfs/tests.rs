@@ -0,0 +1,277 @@
+use std::fs;
+use std::io::Read;
+use std::time::{Duration, SystemTime};
+
+use super::*;
+
+const ROOT: &str = "";
+
+fn put(vfs: &dyn Vfs, path: &str, data: &[u8], modified: SystemTime) {
+    let meta = VfsMetadata::file(data.len() as u64).with_modified(modified);
+    let mut writer = vfs.create(Path::new(path), &meta).unwrap();
+    writer.write_all(data).unwrap();
+    writer.finish().unwrap();
+}
+
+fn read(vfs: &dyn Vfs, path: &str) -> Vec<u8> {
+    let mut data = Vec::new();
+    vfs.open(Path::new(path))
+        .unwrap()
+        .read_to_end(&mut data)
+        .unwrap();
+    data
+}
+
+fn epoch(secs: u64) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+fn run(src: &MemFs, dst: &MemFs, options: VfsSyncOptions) -> VfsSyncStats {
+    sync_trees(src, Path::new(ROOT), dst, Path::new(ROOT), &options).unwrap()
+}
+
+fn sample_source() -> MemFs {
+    let src = MemFs::new();
+    src.create_dir(Path::new("dir")).unwrap();
+    src.create_dir(Path::new("dir/sub")).unwrap();
+    put(&src, "top", b"top", epoch(1_000));
+    put(&src, "dir/a", b"alpha", epoch(2_000));
+    put(&src, "dir/sub/b", b"bravo!", epoch(3_000));
+    src.symlink(Path::new("dir/a"), Path::new("link")).unwrap();
+    src
+}
+
+#[test]
+fn memfs_rejects_missing_parent_and_escaping_paths() {
+    let fs = MemFs::new();
+    let meta = VfsMetadata::file(0);
+    assert_eq!(
+        fs.create(Path::new("no/such"), &meta).err().unwrap().kind(),
+        io::ErrorKind::NotFound
+    );
+    assert_eq!(
+        fs.stat(Path::new("../x")).unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+    assert_eq!(
+        fs.stat(Path::new("/")).unwrap().file_type(),
+        VfsFileType::Directory
+    );
+}
+
+#[test]
+fn memfs_unfinished_writer_leaves_nothing() {
+    let fs = MemFs::new();
+    let mut writer = fs.create(Path::new("f"), &VfsMetadata::file(3)).unwrap();
+    writer.write_all(b"abc").unwrap();
+    drop(writer);
+    assert_eq!(
+        fs.stat(Path::new("f")).unwrap_err().kind(),
+        io::ErrorKind::NotFound
+    );
+}
+
+#[test]
+fn memfs_rename_moves_a_subtree() {
+    let fs = MemFs::new();
+    fs.create_dir(Path::new("old")).unwrap();
+    put(&fs, "old/file", b"x", epoch(1));
+    fs.rename(Path::new("old"), Path::new("new")).unwrap();
+
+    assert_eq!(fs.contents(Path::new("new/file")).unwrap(), b"x");
+    assert!(fs.stat(Path::new("old")).is_err());
+}
+
+#[test]
+fn sync_copies_tree_and_preserves_times() {
+    let src = sample_source();
+    let dst = MemFs::new();
+    let stats = run(&src, &dst, VfsSyncOptions::new());
+
+    assert_eq!(stats.files_transferred(), 3);
+    assert_eq!(stats.bytes_transferred(), 14);
+    assert_eq!(stats.directories_created(), 2);
+    assert_eq!(stats.symlinks_created(), 1);
+    assert_eq!(read(&dst, "dir/sub/b"), b"bravo!");
+    assert_eq!(
+        dst.read_link(Path::new("link")).unwrap(),
+        Path::new("dir/a")
+    );
+    assert_eq!(
+        dst.stat(Path::new("dir/a")).unwrap().modified(),
+        Some(epoch(2_000))
+    );
+}
+
+#[test]
+fn second_run_passes_the_quick_check() {
+    let src = sample_source();
+    let dst = MemFs::new();
+    run(&src, &dst, VfsSyncOptions::new());
+
+    put(&src, "top", b"TOP", epoch(1_001));
+    let stats = run(&src, &dst, VfsSyncOptions::new());
+    assert_eq!(stats.files_transferred(), 1);
+    assert_eq!(stats.files_up_to_date(), 2);
+    assert_eq!(stats.symlinks_created(), 0);
+    assert_eq!(read(&dst, "top"), b"TOP");
+}
+
+#[test]
+fn without_times_every_file_is_resent() {
+    let src = sample_source();
+    let dst = MemFs::new();
+    let options = VfsSyncOptions::new().times(false);
+    run(&src, &dst, options);
+
+    assert_eq!(run(&src, &dst, options).files_transferred(), 3);
+}
+
+#[test]
+fn quick_check_honours_modify_window_size_only_and_ignore_times() {
+    let src = sample_source();
+    let dst = MemFs::new();
+    run(&src, &dst, VfsSyncOptions::new());
+    put(&src, "top", b"TOP", epoch(1_002));
+
+    let narrow = VfsSyncOptions::new().modify_window(metadata::ModifyWindow::from_secs(1));
+    let wide = VfsSyncOptions::new().modify_window(metadata::ModifyWindow::from_secs(2));
+    let scratch = MemFs::new();
+    run(&src, &scratch, VfsSyncOptions::new());
+    put(&scratch, "top", b"old", epoch(1_000));
+    assert_eq!(run(&src, &scratch, wide).files_transferred(), 0);
+    assert_eq!(run(&src, &scratch, narrow).files_transferred(), 1);
+
+    let size_only = VfsSyncOptions::new().size_only(true);
+    assert_eq!(run(&src, &dst, size_only).files_transferred(), 0);
+    assert_eq!(read(&dst, "top"), b"top");
+
+    let ignore_times = VfsSyncOptions::new().ignore_times(true);
+    assert_eq!(run(&src, &dst, ignore_times).files_transferred(), 3);
+    assert_eq!(read(&dst, "top"), b"TOP");
+}
+
+#[test]
+fn delete_removes_extraneous_entries() {
+    let src = sample_source();
+    let dst = MemFs::new();
+    dst.create_dir(Path::new("stale")).unwrap();
+    put(&dst, "stale/old", b"old", epoch(1));
+    put(&dst, "dir_extra", b"x", epoch(1));
+
+    let stats = run(&src, &dst, VfsSyncOptions::new().delete(true));
+    assert_eq!(stats.entries_deleted(), 3);
+    assert!(dst.stat(Path::new("stale")).is_err());
+    assert!(dst.stat(Path::new("dir_extra")).is_err());
+}
+
+#[test]
+fn type_change_replaces_the_destination_entry() {
+    let src = sample_source();
+    let dst = MemFs::new();
+    dst.create_dir(Path::new("top")).unwrap();
+    put(&dst, "top/inner", b"i", epoch(1));
+
+    let stats = run(&src, &dst, VfsSyncOptions::new().force(true));
+    assert_eq!(stats.entries_deleted(), 2);
+    assert_eq!(read(&dst, "top"), b"top");
+}
+
+#[test]
+fn non_empty_directory_survives_without_delete_or_force() {
+    let src = sample_source();
+    let dst = MemFs::new();
+    dst.create_dir(Path::new("top")).unwrap();
+    put(&dst, "top/inner", b"i", epoch(1));
+
+    let result = sync_trees(
+        &src,
+        Path::new(ROOT),
+        &dst,
+        Path::new(ROOT),
+        &VfsSyncOptions::new(),
+    );
+    assert!(matches!(
+        result,
+        Err(VfsSyncError::DirectoryNotEmpty { .. })
+    ));
+    assert_eq!(read(&dst, "top/inner"), b"i");
+
+    // An empty directory is replaced without either option.
+    let dst = MemFs::new();
+    dst.create_dir(Path::new("top")).unwrap();
+    run(&src, &dst, VfsSyncOptions::new());
+    assert_eq!(read(&dst, "top"), b"top");
+}
+
+#[test]
+fn filters_prune_the_file_list_and_protect_deletions() {
+    let src = sample_source();
+    let dst = MemFs::new();
+    put(&dst, "keep.log", b"k", epoch(1));
+    put(&dst, "stale", b"s", epoch(1));
+    let filters = filters::FilterSet::from_rules([
+        filters::FilterRule::exclude("sub/".to_owned()),
+        filters::FilterRule::exclude("*.log".to_owned()),
+    ])
+    .unwrap();
+
+    let options = VfsSyncOptions::new().delete(true);
+    let root = Path::new(ROOT);
+    sync_trees_filtered(&src, root, &dst, root, &options, Some(&filters)).unwrap();
+    assert!(dst.stat(Path::new("dir/sub")).is_err());
+    assert_eq!(read(&dst, "dir/a"), b"alpha");
+    assert_eq!(read(&dst, "keep.log"), b"k");
+    assert!(dst.stat(Path::new("stale")).is_err());
+
+    let options = options.delete_excluded(true);
+    sync_trees_filtered(&src, root, &dst, root, &options, Some(&filters)).unwrap();
+    assert!(dst.stat(Path::new("keep.log")).is_err());
+}
+
+#[test]
+fn non_directory_source_root_is_rejected() {
+    let src = MemFs::new();
+    put(&src, "file", b"x", epoch(1));
+    let dst = MemFs::new();
+
+    assert!(matches!(
+        sync_trees(
+            &src,
+            Path::new("file"),
+            &dst,
+            Path::new(ROOT),
+            &VfsSyncOptions::new(),
+        ),
+        Err(VfsSyncError::NotADirectory { .. })
+    ));
+}
+
+#[test]
+fn std_fs_round_trips_through_memfs() {
+    let temp = tempfile::tempdir().unwrap();
+    let disk = temp.path().join("tree");
+    fs::create_dir_all(disk.join("dir/sub")).unwrap();
+    fs::write(disk.join("dir/sub/file"), b"on disk").unwrap();
+
+    let mem = MemFs::new();
+    sync_trees(&StdFs, &disk, &mem, Path::new(ROOT), &VfsSyncOptions::new()).unwrap();
+    assert_eq!(mem.contents(Path::new("dir/sub/file")).unwrap(), b"on disk");
+
+    let copy = temp.path().join("copy");
+    let stats = sync_trees(&mem, Path::new(ROOT), &StdFs, &copy, &VfsSyncOptions::new()).unwrap();
+    assert_eq!(stats.files_transferred(), 1);
+    assert_eq!(fs::read(copy.join("dir/sub/file")).unwrap(), b"on disk");
+    assert_eq!(
+        fs::metadata(copy.join("dir/sub/file"))
+            .unwrap()
+            .modified()
+            .unwrap(),
+        fs::metadata(disk.join("dir/sub/file"))
+            .unwrap()
+            .modified()
+            .unwrap()
+    );
+    let leftovers: Vec<_> = fs::read_dir(copy.join("dir/sub")).unwrap().collect();
+    assert_eq!(leftovers.len(), 1, "temp files must be renamed away");
+}
@@ -155,4 +155,38 @@ fn executor_uses_ficlone_for_plain_archive_without_xattrs() {
         "plain -a copy on a reflink-capable fs must engage FICLONE, got methods {:?}",
         summary.copy_method_breakdown()
     );
+    assert_eq!(summary.cloned_bytes(), payload.len() as u64);
+}
+
+#[test]
+fn executor_reports_no_cloned_bytes_without_reflink() {
+    let dir = tempdir().expect("tempdir");
+    if detect_reflink_support(dir.path()) {
+        eprintln!(
+            "skipping non-reflink accounting test - {:?} supports reflinks",
+            dir.path()
+        );
+        return;
+    }
+
+    let src = dir.path().join("src.bin");
+    let dst = dir.path().join("dst.bin");
+    let payload: Vec<u8> = (0..256u32 * 1024).map(|i| (i & 0xff) as u8).collect();
+    fs::write(&src, &payload).expect("write source");
+
+    let operands = vec![src.into_os_string(), dst.clone().into_os_string()];
+    let plan = LocalCopyPlan::from_operands(&operands).expect("plan");
+    let summary = plan
+        .execute_with_options(LocalCopyExecution::Apply, LocalCopyOptions::default())
+        .expect("copy succeeds");
+
+    assert_eq!(fs::read(&dst).expect("read dst"), payload);
+    // copy_file_range on a filesystem without extent sharing duplicates the
+    // data in the kernel; it is reported under its own method, not as a clone.
+    assert_eq!(
+        summary.cloned_bytes(),
+        0,
+        "methods {:?}",
+        summary.copy_method_breakdown()
+    );
 }
@@ -109,7 +109,7 @@ fn single_byte_file_signature() {
     assert_eq!(signature.blocks().len(), 1, "should have exactly one block");
     assert_eq!(signature.total_bytes(), 1, "total bytes should be 1");
 
-    let block = &signature.blocks()[0];
+    let block = signature.block(0);
     assert_eq!(block.index(), 0, "block index should be 0");
     assert_eq!(block.len(), 1, "block length should be 1");
 }
@@ -153,7 +153,7 @@ fn single_byte_rolling_checksum() {
     let signature = generate_file_signature(Cursor::new(&data), layout, SignatureAlgorithm::Md4)
         .expect("signature");
 
-    let block = &signature.blocks()[0];
+    let block = signature.block(0);
     let expected_rolling = RollingDigest::from_bytes(&data);
     assert_eq!(
         block.rolling(),
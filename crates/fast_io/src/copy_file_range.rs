@@ -14,6 +14,8 @@
 //! - For files < 64KB: Uses read/write directly (lower syscall overhead)
 //! - For files 64KB to 256KB: Attempts `copy_file_range`, then read/write
 //! - For files >= 256KB: Attempts io_uring, then `copy_file_range`, then read/write
+//! - Same-filesystem copies via [`copy_file_contents_tracked`] attempt
+//!   `copy_file_range` first, so the kernel can share extents
 //! - Fallback path uses 256KB buffer for efficient bulk transfer
 //!
 //! # Example
@@ -111,17 +113,55 @@ pub fn copy_file_contents_buffered(
     length: u64,
     buffer: &mut [u8],
 ) -> io::Result<u64> {
+    copy_file_contents_tracked(source, destination, length, buffer).map(|(copied, _)| copied)
+}
+
+/// Mechanism [`copy_file_contents_tracked`] used to move the bytes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ContentCopyMethod {
+    /// Batched io_uring read/write.
+    IoUring,
+    /// In-kernel `copy_file_range`. On Btrfs and XFS a same-filesystem copy
+    /// may share extents; elsewhere the kernel duplicates the data, so this
+    /// does not by itself mean the file was cloned.
+    CopyFileRange,
+    /// Userspace read/write loop.
+    ReadWrite,
+}
+
+/// Like [`copy_file_contents_buffered`] but also reports which mechanism
+/// moved the bytes, so callers can account for in-kernel copies.
+///
+/// When both files live on the same filesystem, `copy_file_range` is tried
+/// before io_uring: the kernel can then clone extents (Btrfs, XFS) or offload
+/// the copy to the server (NFS 4.2), neither of which a userspace ring can
+/// match. Across filesystems the size-tiered order of
+/// [`copy_file_contents`] applies.
+pub fn copy_file_contents_tracked(
+    source: &File,
+    destination: &File,
+    length: u64,
+    buffer: &mut [u8],
+) -> io::Result<(u64, ContentCopyMethod)> {
+    let same_filesystem = length >= COPY_FILE_RANGE_THRESHOLD
+        && crate::same_fs::files_same_device(source, destination) == Some(true);
+    if same_filesystem {
+        if let Ok(copied) = try_copy_file_range(source, destination, length) {
+            return Ok((copied, ContentCopyMethod::CopyFileRange));
+        }
+    }
     if length >= IO_URING_COPY_THRESHOLD {
         if let Ok(copied) = try_io_uring_copy(source, destination, length) {
-            return Ok(copied);
+            return Ok((copied, ContentCopyMethod::IoUring));
         }
     }
-    if length >= COPY_FILE_RANGE_THRESHOLD {
+    if length >= COPY_FILE_RANGE_THRESHOLD && !same_filesystem {
         if let Ok(copied) = try_copy_file_range(source, destination, length) {
-            return Ok(copied);
+            return Ok((copied, ContentCopyMethod::CopyFileRange));
         }
     }
     copy_file_contents_readwrite_with_buffer(source, destination, length, buffer)
+        .map(|copied| (copied, ContentCopyMethod::ReadWrite))
 }
 
 /// Attempts file copy using io_uring batched read/write operations.
@@ -660,7 +700,8 @@ mod tests {
     #[test]
     fn test_buffered_copy_above_io_uring_threshold() {
         // 512KB exceeds IO_URING_COPY_THRESHOLD so the buffered path exercises
-        // tier 1 (io_uring), then 2 (copy_file_range), then 3 (read/write).
+        // the full tier chain (copy_file_range first here, as both temp files
+        // share a filesystem, then io_uring, then read/write).
         let size = 512 * 1024;
         let content: Vec<u8> = (0..size).map(|i| (i % 256) as u8).collect();
         let source = create_temp_file(&content).unwrap();
@@ -676,4 +717,45 @@ mod tests {
         let dest_content = read_file_contents(dest.as_file()).unwrap();
         assert_eq!(dest_content, content);
     }
+
+    #[test]
+    fn test_tracked_copy_small_file_uses_read_write() {
+        let content = b"below every kernel-copy threshold";
+        let source = create_temp_file(content).unwrap();
+        let mut dest = NamedTempFile::new().unwrap();
+        let mut buffer = vec![0u8; 4096];
+
+        let (copied, method) = copy_file_contents_tracked(
+            source.as_file(),
+            dest.as_file(),
+            content.len() as u64,
+            &mut buffer,
+        )
+        .unwrap();
+
+        assert_eq!(copied, content.len() as u64);
+        assert_eq!(method, ContentCopyMethod::ReadWrite);
+        dest.seek(SeekFrom::Start(0)).unwrap();
+        assert_eq!(read_file_contents(dest.as_file()).unwrap(), content);
+    }
+
+    #[test]
+    fn test_tracked_copy_same_filesystem() {
+        // Both temp files share a directory, so copy_file_range is tried
+        // first; whichever tier wins, the bytes must match.
+        let size = 512 * 1024;
+        let content: Vec<u8> = (0..size).map(|i| (i % 251) as u8).collect();
+        let source = create_temp_file(&content).unwrap();
+        let mut dest = NamedTempFile::new().unwrap();
+        let mut buffer = vec![0u8; 256 * 1024];
+
+        let (copied, method) =
+            copy_file_contents_tracked(source.as_file(), dest.as_file(), size as u64, &mut buffer)
+                .unwrap();
+
+        assert_eq!(copied, size as u64);
+        assert!(cfg!(target_os = "linux") || method != ContentCopyMethod::CopyFileRange);
+        dest.seek(SeekFrom::Start(0)).unwrap();
+        assert_eq!(read_file_contents(dest.as_file()).unwrap(), content);
+    }
 }
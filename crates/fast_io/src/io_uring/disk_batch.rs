@@ -16,14 +16,18 @@
 //! - `fileio.c:write_file()` - upstream writes use a single static buffer
 //!   (`wf_writeBuf`) and write(2). We batch these into io_uring SQEs.
 
+use std::ffi::CString;
 use std::fs::File;
 use std::io::{self, Write};
+use std::os::unix::ffi::OsStrExt;
 use std::os::unix::io::AsRawFd;
+use std::path::Path;
 
 use io_uring::{IoUring as RawIoUring, opcode};
 
 use super::batching::{NO_FIXED_FD, maybe_fixed_file, sqe_fd, submit_write_batch, try_register_fd};
 use super::config::{IoUringConfig, is_io_uring_available};
+use super::renameat2::{RenameAt2Args, build_renameat2_sqe};
 
 /// Default write buffer capacity for the batched disk writer (256 KB).
 ///
@@ -186,6 +190,58 @@ impl IoUringDiskBatch {
         Ok((active.file, bytes_written))
     }
 
+    /// Renames `old_path` to `new_path` with an `IORING_OP_RENAMEAT` SQE on
+    /// this batch's ring.
+    ///
+    /// Lets the commit phase put a finished temp file into place without
+    /// building a transient ring per file (as
+    /// [`crate::try_rename_via_io_uring`] does). Both paths resolve against
+    /// the current directory and an existing `new_path` is replaced, matching
+    /// `rename(2)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`io::ErrorKind::Unsupported`] when the kernel lacks the
+    /// opcode, so the caller can fall back to `std::fs::rename`, and the
+    /// kernel's error when the rename itself fails (e.g. `EXDEV`, `ENOENT`).
+    pub fn rename(&mut self, old_path: &Path, new_path: &Path) -> io::Result<()> {
+        let old_c = CString::new(old_path.as_os_str().as_bytes())
+            .map_err(|_| io::Error::other("path contains interior NUL"))?;
+        let new_c = CString::new(new_path.as_os_str().as_bytes())
+            .map_err(|_| io::Error::other("path contains interior NUL"))?;
+        let entry = build_renameat2_sqe(RenameAt2Args {
+            old_dir_fd: libc::AT_FDCWD,
+            old_path: &old_c,
+            new_dir_fd: libc::AT_FDCWD,
+            new_path: &new_c,
+            flags: 0,
+        })?
+        .user_data(0);
+
+        // SAFETY: The SQE borrows `old_c` and `new_c`, which live until this
+        // function returns; submit_and_wait(1) reaps the completion first.
+        unsafe {
+            self.ring
+                .submission()
+                .push(&entry)
+                .map_err(|_| io::Error::other("submission queue full"))?;
+        }
+
+        self.ring.submit_and_wait(1)?;
+
+        let cqe = self
+            .ring
+            .completion()
+            .next()
+            .ok_or_else(|| io::Error::other("no completion for rename"))?;
+
+        let result = cqe.result();
+        if result < 0 {
+            return Err(io::Error::from_raw_os_error(-result));
+        }
+        Ok(())
+    }
+
     /// Returns the number of bytes written to the current file (flushed only).
     #[must_use]
     pub fn bytes_written(&self) -> u64 {
@@ -368,6 +424,28 @@ mod tests {
         assert_eq!(content, data);
     }
 
+    #[test]
+    fn rename_on_batch_ring_moves_file() {
+        let config = IoUringConfig::default();
+        let Some(mut batch) = IoUringDiskBatch::try_new(&config) else {
+            return;
+        };
+
+        let dir = tempdir().unwrap();
+        let temp = dir.path().join(".file.XXXXXX");
+        let dest = dir.path().join("file");
+        std::fs::write(&temp, b"committed").unwrap();
+        std::fs::write(&dest, b"old").unwrap();
+
+        match batch.rename(&temp, &dest) {
+            Ok(()) => {
+                assert!(!temp.exists());
+                assert_eq!(std::fs::read(&dest).unwrap(), b"committed");
+            }
+            Err(e) => assert_eq!(e.kind(), io::ErrorKind::Unsupported),
+        }
+    }
+
     #[test]
     fn multi_file_sequential_writes() {
         let config = IoUringConfig::default();
@@ -58,6 +58,18 @@ impl IoUringDiskBatch {
         ))
     }
 
+    /// Renames a file on the batch ring (always fails on this platform).
+    pub fn rename(
+        &mut self,
+        _old_path: &std::path::Path,
+        _new_path: &std::path::Path,
+    ) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "io_uring is not available on this platform",
+        ))
+    }
+
     /// Returns bytes written (always 0 on this platform).
     #[must_use]
     pub fn bytes_written(&self) -> u64 {
@@ -118,6 +118,8 @@ pub mod page_aligned;
 pub mod parallel;
 /// Total physical memory detection (for the buffer pool's RAM-derived cap).
 pub mod physical_memory;
+/// Atomic exchange of two directory entries (`renameat2(RENAME_EXCHANGE)`).
+pub mod rename_exchange;
 /// Same-filesystem (device) detection for reflink / copy-on-write gating.
 pub mod same_fs;
 /// Strict-resolution directory open for the SEC-1 dirfd sandbox.
@@ -154,8 +156,12 @@ pub mod copy_basis_range;
 pub mod copy_file_ex;
 /// High-performance file copying with tiered fallback.
 pub mod copy_file_range;
+/// Unbuffered destination writer that lands page-aligned blocks via `O_DIRECT`.
+pub mod direct_writer;
 /// Uncached bulk file writer that lands chunks via `pwritev2` + `RWF_DONTCACHE`.
 pub mod dontcache_writer;
+/// Page-cache eviction via `posix_fadvise(DONTNEED)` for `--drop-cache`.
+pub mod drop_cache;
 /// Anonymous temporary file creation via `O_TMPFILE` and finalization via `linkat`.
 pub mod o_tmpfile;
 /// Platform-abstracted file copy trait with automatic optimization selection.
@@ -181,6 +187,8 @@ pub mod sendfile;
 pub mod sendfile_macos;
 /// Safe wrappers around platform signal-handler installation.
 pub mod signal;
+/// Listening sockets inherited through systemd socket activation.
+pub mod socket_activation;
 /// Safe wrappers around platform `setsockopt` for integer-valued options.
 pub mod socket_options;
 /// Zero-copy socket-to-disk transfer using `splice`/`vmsplice` syscalls.
@@ -369,6 +377,7 @@ pub use kernel_version::{
 pub use linux_capabilities::openat2_supported;
 pub use nofollow_open::open_basis_nofollow;
 pub use refs_detect::{clear_refs_cache, is_refs_filesystem};
+pub use rename_exchange::exchange_paths;
 #[cfg(unix)]
 pub use secure_dir::secure_open_dir;
 // Non-unix `send_file_to_fd_with_policy` stub silently routes bytes to
@@ -396,7 +405,9 @@ pub use stdio_blocking::force_blocking_stdio;
 pub use stdio_shutdown::shutdown_stdio_write;
 // Non-unix `recv_fd_to_file` stub returns `Unsupported`; gate the public
 // re-export on unix to remove the dead surface area (see WIN-S.LAND.1.a).
+pub use direct_writer::{DIRECT_WRITE_MIN_BYTES, DirectFileWriter};
 pub use dontcache_writer::{DontcacheFileWriter, dontcache_read_exact, dontcache_supported};
+pub use drop_cache::drop_file_cache;
 #[cfg(unix)]
 pub use splice::recv_fd_to_file;
 pub use vmsplice_writer::{VMSPLICE_MIN_CHUNK, VmspliceFileWriter};
@@ -422,7 +433,7 @@ pub use temp_file_strategy::{
 };
 #[cfg(windows)]
 pub use win_atomic_commit::{create_new_no_follow, rename_no_follow};
-pub use win_path::to_extended_path;
+pub use win_path::{is_reserved_device_name, to_extended_path, to_native_path};
 pub use win_sparse::mark_file_sparse;
 pub use win_symlink::{
     WindowsDirLink, create_directory_symlink_or_junction, create_file_symlink,
@@ -0,0 +1,144 @@
+//! Atomic exchange of two directory entries.
+//!
+//! Swapping a freshly populated tree with the live one in a single syscall
+//! leaves no window in which readers observe a missing or half-written
+//! destination. Linux 3.15+ exposes this as `renameat2(RENAME_EXCHANGE)` and
+//! macOS as `renamex_np(RENAME_SWAP)`; other platforms, and filesystems that
+//! reject the flag, report [`io::ErrorKind::Unsupported`] so the caller can
+//! fall back to a two-step rename.
+
+use std::io;
+use std::path::Path;
+
+/// Atomically exchanges the entries at `a` and `b`.
+///
+/// Both paths must exist and live on the same filesystem. After a successful
+/// call, the inode previously reachable as `a` is reachable as `b` and vice
+/// versa.
+///
+/// # Errors
+///
+/// Returns [`io::ErrorKind::Unsupported`] when the platform, kernel, or
+/// filesystem cannot exchange entries atomically. Other errors (`ENOENT`,
+/// `EXDEV`, `EACCES`, ...) are surfaced verbatim.
+#[cfg(target_os = "linux")]
+pub fn exchange_paths(a: &Path, b: &Path) -> io::Result<()> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    /// `RENAME_EXCHANGE` from `renameat2(2)`.
+    const RENAME_EXCHANGE: libc::c_uint = 2;
+
+    let c_a = CString::new(a.as_os_str().as_bytes())
+        .map_err(|_| io::Error::from_raw_os_error(libc::EINVAL))?;
+    let c_b = CString::new(b.as_os_str().as_bytes())
+        .map_err(|_| io::Error::from_raw_os_error(libc::EINVAL))?;
+
+    // SAFETY: both `CString`s are valid NUL-terminated strings that outlive
+    // the call, `AT_FDCWD` resolves them against the working directory, and
+    // the kernel does not retain either pointer past return.
+    #[allow(unsafe_code)]
+    let rc = unsafe {
+        libc::syscall(
+            libc::SYS_renameat2,
+            libc::AT_FDCWD,
+            c_a.as_ptr(),
+            libc::AT_FDCWD,
+            c_b.as_ptr(),
+            RENAME_EXCHANGE,
+        )
+    };
+    if rc == 0 {
+        return Ok(());
+    }
+    let err = io::Error::last_os_error();
+    match err.raw_os_error() {
+        // Pre-3.15 kernels lack the syscall; several filesystems (older NFS,
+        // FUSE backends) reject the flag.
+        Some(libc::ENOSYS) | Some(libc::EINVAL) | Some(libc::EOPNOTSUPP) => {
+            Err(io::Error::new(io::ErrorKind::Unsupported, err))
+        }
+        _ => Err(err),
+    }
+}
+
+/// Atomically exchanges the entries at `a` and `b` via `renamex_np`.
+///
+/// # Errors
+///
+/// Returns [`io::ErrorKind::Unsupported`] when the volume does not support
+/// `RENAME_SWAP`; other errors are surfaced verbatim.
+#[cfg(target_os = "macos")]
+pub fn exchange_paths(a: &Path, b: &Path) -> io::Result<()> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_a = CString::new(a.as_os_str().as_bytes())
+        .map_err(|_| io::Error::from_raw_os_error(libc::EINVAL))?;
+    let c_b = CString::new(b.as_os_str().as_bytes())
+        .map_err(|_| io::Error::from_raw_os_error(libc::EINVAL))?;
+
+    // SAFETY: both `CString`s are valid NUL-terminated strings that outlive
+    // the call; `renamex_np` does not retain either pointer.
+    #[allow(unsafe_code)]
+    let rc = unsafe { libc::renamex_np(c_a.as_ptr(), c_b.as_ptr(), libc::RENAME_SWAP) };
+    if rc == 0 {
+        return Ok(());
+    }
+    let err = io::Error::last_os_error();
+    match err.raw_os_error() {
+        Some(libc::ENOTSUP) | Some(libc::EINVAL) => {
+            Err(io::Error::new(io::ErrorKind::Unsupported, err))
+        }
+        _ => Err(err),
+    }
+}
+
+/// Platforms without an exchange primitive always report
+/// [`io::ErrorKind::Unsupported`].
+///
+/// # Errors
+///
+/// Always returns [`io::ErrorKind::Unsupported`].
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn exchange_paths(_a: &Path, _b: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "atomic rename exchange is not supported on this platform",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn exchange_swaps_directories_or_reports_unsupported() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a");
+        let b = dir.path().join("b");
+        fs::create_dir(&a).unwrap();
+        fs::create_dir(&b).unwrap();
+        fs::write(a.join("marker"), b"a").unwrap();
+        fs::write(b.join("marker"), b"b").unwrap();
+
+        match exchange_paths(&a, &b) {
+            Ok(()) => {
+                assert_eq!(fs::read(a.join("marker")).unwrap(), b"b");
+                assert_eq!(fs::read(b.join("marker")).unwrap(), b"a");
+            }
+            Err(err) => assert_eq!(err.kind(), io::ErrorKind::Unsupported),
+        }
+    }
+
+    #[test]
+    fn exchange_with_missing_entry_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a");
+        fs::create_dir(&a).unwrap();
+
+        assert!(exchange_paths(&a, &dir.path().join("missing")).is_err());
+        assert!(a.is_dir());
+    }
+}
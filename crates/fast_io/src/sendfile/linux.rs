@@ -76,3 +76,47 @@ pub(super) fn try_sendfile(source: &File, dest_fd: i32, length: u64) -> io::Resu
 
     Ok(total)
 }
+
+/// Positioned variant of [`try_sendfile`] over raw descriptors.
+///
+/// Passes an explicit offset pointer so the kernel reads from `offset`
+/// without moving the source's file position - a caller that also reads the
+/// file sequentially (e.g. to checksum it) keeps its own position intact.
+/// Same partial-progress contract as [`try_sendfile`].
+pub(super) fn try_sendfile_at(
+    src_fd: i32,
+    offset: u64,
+    dest_fd: i32,
+    length: u64,
+) -> io::Result<u64> {
+    let mut off = libc::off_t::try_from(offset)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "offset exceeds off_t"))?;
+    let mut total: u64 = 0;
+    let mut remaining = length;
+
+    while remaining > 0 {
+        let chunk = remaining.min(SENDFILE_CHUNK_SIZE as u64) as usize;
+        // SAFETY: The caller guarantees both descriptors stay open for the
+        // call. `off` is a live local the kernel reads and advances.
+        let result = unsafe { libc::sendfile(dest_fd, src_fd, &mut off, chunk) };
+
+        if result < 0 {
+            let err = io::Error::last_os_error();
+            if err.kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+            if total == 0 {
+                return Err(err);
+            }
+            return Ok(total);
+        }
+        if result == 0 {
+            break;
+        }
+
+        total += result as u64;
+        remaining -= result as u64;
+    }
+
+    Ok(total)
+}
@@ -53,7 +53,7 @@ mod macos;
 mod fallback;
 
 #[cfg(target_os = "linux")]
-use linux::try_sendfile;
+use linux::{try_sendfile, try_sendfile_at};
 #[cfg(target_os = "macos")]
 use macos::try_sendfile_macos;
 
@@ -197,6 +197,47 @@ pub fn send_file_to_fd(source: &File, _dest_fd: i32, length: u64) -> io::Result<
     send_file_to_writer(source, &mut io::sink(), length)
 }
 
+/// Sends `length` bytes of `src_fd` starting at `offset` to `dest_fd` with
+/// `sendfile(2)`, leaving the source's file position unchanged.
+///
+/// Unlike [`send_file_to_fd`] there is no size threshold and no read/write
+/// fallback: callers use this when they already hold the bytes (for example
+/// to checksum them) and only want the kernel to skip the userspace copy
+/// into the socket. Both descriptors must stay open for the call.
+///
+/// Returns the number of bytes sent, which is short only when the source
+/// hit EOF or the socket failed after some bytes moved.
+///
+/// # Errors
+///
+/// Returns [`io::ErrorKind::Unsupported`] on platforms without a positioned
+/// `sendfile`, and the OS error when the first `sendfile` call fails
+/// (e.g. `EINVAL` for a destination the kernel cannot splice into).
+#[cfg(target_os = "linux")]
+pub fn send_file_range_to_fd(
+    src_fd: i32,
+    offset: u64,
+    dest_fd: i32,
+    length: u64,
+) -> io::Result<u64> {
+    try_sendfile_at(src_fd, offset, dest_fd, length)
+}
+
+/// Non-Linux stub for [`send_file_range_to_fd`]; the caller writes the
+/// bytes itself.
+#[cfg(not(target_os = "linux"))]
+pub fn send_file_range_to_fd(
+    _src_fd: i32,
+    _offset: u64,
+    _dest_fd: i32,
+    _length: u64,
+) -> io::Result<u64> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "positioned sendfile is not available on this platform",
+    ))
+}
+
 /// Policy-aware variant of [`send_file_to_fd`].
 ///
 /// When `policy` is [`ZeroCopyPolicy::Disabled`](crate::ZeroCopyPolicy::Disabled),
@@ -451,3 +451,43 @@ fn test_multiple_writes() {
     assert_eq!(sent2, content2.len() as u64);
     assert_eq!(output, b"First writeSecond");
 }
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_send_file_range_to_fd_keeps_file_position() {
+    use std::io::Read;
+    use std::os::fd::AsRawFd;
+
+    let content: Vec<u8> = (0..4096u32).map(|i| (i % 251) as u8).collect();
+    let source = create_temp_file(&content).unwrap();
+
+    let mut socket_fds = [0i32; 2];
+    // SAFETY: `socket_fds` is the two-int output slot the syscall fills.
+    let result =
+        unsafe { libc::socketpair(libc::AF_UNIX, libc::SOCK_STREAM, 0, socket_fds.as_mut_ptr()) };
+    assert_eq!(result, 0, "Failed to create socketpair");
+    let recv_fd = socket_fds[0];
+    let send_fd = socket_fds[1];
+
+    let sent = send_file_range_to_fd(source.as_file().as_raw_fd(), 1000, send_fd, 500).unwrap();
+    assert_eq!(sent, 500);
+
+    // SAFETY: `send_fd` was just opened by `socketpair` and is closed
+    // exactly once here.
+    unsafe { libc::close(send_fd) };
+
+    let mut received = vec![0u8; 500];
+    // SAFETY: `recv_fd` is still open; `received` provides 500 writable bytes.
+    let n = unsafe { libc::read(recv_fd, received.as_mut_ptr().cast::<libc::c_void>(), 500) };
+    assert_eq!(n, 500);
+    assert_eq!(received, &content[1000..1500]);
+
+    // SAFETY: `recv_fd` was opened by `socketpair` and is closed exactly
+    // once here.
+    unsafe { libc::close(recv_fd) };
+
+    // The positioned send must not have moved the sequential read cursor.
+    let mut head = [0u8; 16];
+    source.as_file().read_exact(&mut head).unwrap();
+    assert_eq!(head, content[..16]);
+}
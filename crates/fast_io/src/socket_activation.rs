@@ -0,0 +1,154 @@
+//! Listening sockets handed over by systemd socket activation.
+//!
+//! A `.socket` unit binds the daemon's port itself and starts the service
+//! with the listeners already open as descriptors `3..3+LISTEN_FDS`, naming
+//! the intended recipient in `LISTEN_PID`. [`inherited_listeners`] adopts
+//! those descriptors as [`TcpListener`]s so consumer crates can serve them
+//! without breaking their `#![deny(unsafe_code)]` discipline.
+//!
+//! The environment is left in place: every process spawned later sees a
+//! `LISTEN_PID` that is not its own and ignores the variables, exactly as
+//! `sd_listen_fds(0)` callers rely on. Adoption happens at most once per
+//! process so a descriptor can never end up with two owners.
+//!
+//! On non-Unix platforms no descriptors are ever inherited.
+
+use std::io;
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// First descriptor passed by the service manager (`SD_LISTEN_FDS_START`).
+pub const LISTEN_FDS_START: i32 = 3;
+
+static CLAIMED: AtomicBool = AtomicBool::new(false);
+
+/// Returns the listeners the service manager passed to this process.
+///
+/// Returns an empty vector when the process was not socket activated, when
+/// `LISTEN_PID` names another process, or when the listeners were already
+/// adopted by an earlier call. Each adopted descriptor is marked
+/// close-on-exec.
+///
+/// # Errors
+///
+/// Fails when `LISTEN_PID` or `LISTEN_FDS` is malformed, or when a passed
+/// descriptor is not a stream socket; a datagram or `Accept=yes` unit cannot
+/// be served by the daemon.
+pub fn inherited_listeners() -> io::Result<Vec<TcpListener>> {
+    let count = activated_fd_count(
+        std::env::var("LISTEN_PID").ok().as_deref(),
+        std::env::var("LISTEN_FDS").ok().as_deref(),
+        std::process::id(),
+    )?;
+    if count == 0 || CLAIMED.swap(true, Ordering::AcqRel) {
+        return Ok(Vec::new());
+    }
+
+    #[cfg(unix)]
+    {
+        (LISTEN_FDS_START..LISTEN_FDS_START + count as i32)
+            .map(adopt_listener)
+            .collect()
+    }
+
+    #[cfg(not(unix))]
+    {
+        Ok(Vec::new())
+    }
+}
+
+/// Number of descriptors passed to the process `own_pid`, following the
+/// `sd_listen_fds(3)` rules for the `LISTEN_PID` and `LISTEN_FDS` values.
+fn activated_fd_count(
+    listen_pid: Option<&str>,
+    listen_fds: Option<&str>,
+    own_pid: u32,
+) -> io::Result<usize> {
+    let Some(pid) = listen_pid else {
+        return Ok(0);
+    };
+    let pid: u32 = pid.trim().parse().map_err(|_| invalid("LISTEN_PID", pid))?;
+    if pid != own_pid {
+        return Ok(0);
+    }
+    let Some(fds) = listen_fds else {
+        return Ok(0);
+    };
+    let count: usize = fds.trim().parse().map_err(|_| invalid("LISTEN_FDS", fds))?;
+    if count > (i32::MAX - LISTEN_FDS_START) as usize {
+        return Err(invalid("LISTEN_FDS", fds));
+    }
+    Ok(count)
+}
+
+fn invalid(variable: &str, value: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("invalid {variable} value '{value}'"),
+    )
+}
+
+#[cfg(unix)]
+#[allow(unsafe_code)]
+fn adopt_listener(fd: libc::c_int) -> io::Result<TcpListener> {
+    use std::os::fd::FromRawFd;
+
+    // SAFETY: `fcntl(F_SETFD)` only updates the descriptor flags of `fd`;
+    // an fd that is not open fails with `EBADF`, which is reported below.
+    if unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let mut kind: libc::c_int = 0;
+    let mut len = std::mem::size_of::<libc::c_int>() as libc::socklen_t;
+    // SAFETY: `fd` was validated as open above. `kind` and `len` are live
+    // stack values whose sizes match what `SO_TYPE` writes back.
+    let ret = unsafe {
+        libc::getsockopt(
+            fd,
+            libc::SOL_SOCKET,
+            libc::SO_TYPE,
+            std::ptr::from_mut(&mut kind).cast::<libc::c_void>(),
+            &mut len,
+        )
+    };
+    if ret == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    if kind != libc::SOCK_STREAM {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("inherited descriptor {fd} is not a stream socket"),
+        ));
+    }
+
+    // SAFETY: `fd` is an open stream socket that the service manager passed
+    // to this process. The `CLAIMED` flag guarantees it is adopted once, so
+    // the returned listener is its sole owner.
+    Ok(unsafe { TcpListener::from_raw_fd(fd) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_requires_a_matching_pid() {
+        assert_eq!(activated_fd_count(None, Some("2"), 42).unwrap(), 0);
+        assert_eq!(activated_fd_count(Some("41"), Some("2"), 42).unwrap(), 0);
+        assert_eq!(activated_fd_count(Some("42"), None, 42).unwrap(), 0);
+        assert_eq!(activated_fd_count(Some("42"), Some("2"), 42).unwrap(), 2);
+        assert_eq!(
+            activated_fd_count(Some(" 42 "), Some("1\n"), 42).unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn malformed_values_are_rejected() {
+        assert!(activated_fd_count(Some("self"), Some("1"), 42).is_err());
+        assert!(activated_fd_count(Some("42"), Some("-1"), 42).is_err());
+        assert!(activated_fd_count(Some("42"), Some("many"), 42).is_err());
+        assert!(activated_fd_count(Some("42"), Some("4294967295"), 42).is_err());
+    }
+}
@@ -20,6 +20,10 @@
 //! - **Non-Unix (Windows)**: Portable fallbacks - `filetime` crate for timestamps,
 //!   readonly attribute mapping for permissions
 //!
+//! [`quick_stat`] skips `fs::Metadata` altogether and returns only the fields
+//! a quick-check compares, fetched on Linux with a narrow `statx()` mask and
+//! `AT_STATX_DONT_SYNC`.
+//!
 //! # Performance Characteristics
 //!
 //! - Individual path: Lower overhead for small operation counts (< 8)
@@ -298,6 +302,147 @@ fn try_statx(path: &Path, follow_symlinks: bool) -> io::Result<fs::Metadata> {
     }
 }
 
+/// The `stat` fields a quick-check consults, fetched by [`quick_stat`].
+///
+/// Carries the file type and permission bits, ownership, size and
+/// modification time - everything `size + mtime` comparison and the
+/// perms/owner/group/times "unchanged" test need, and nothing else.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuickStat {
+    /// Full `st_mode`, file type bits included.
+    pub mode: u32,
+    /// Owner user ID (0 where the platform has none).
+    pub uid: u32,
+    /// Owner group ID (0 where the platform has none).
+    pub gid: u32,
+    /// File size in bytes.
+    pub size: u64,
+    /// Modification time, whole seconds since the epoch.
+    pub mtime: i64,
+    /// Sub-second part of the modification time.
+    pub mtime_nsec: u32,
+}
+
+impl QuickStat {
+    /// File type mask of [`QuickStat::mode`] (`S_IFMT`).
+    const TYPE_MASK: u32 = 0o170_000;
+    /// Regular file type bits (`S_IFREG`).
+    const REGULAR: u32 = 0o100_000;
+    /// Directory type bits (`S_IFDIR`).
+    #[cfg(not(unix))]
+    const DIRECTORY: u32 = 0o040_000;
+
+    /// Returns `true` for a regular file.
+    #[must_use]
+    pub const fn is_file(&self) -> bool {
+        self.mode & Self::TYPE_MASK == Self::REGULAR
+    }
+
+    /// Builds a quick stat from full standard-library metadata.
+    #[must_use]
+    pub fn from_metadata(meta: &fs::Metadata) -> Self {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            Self {
+                mode: meta.mode(),
+                uid: meta.uid(),
+                gid: meta.gid(),
+                size: meta.len(),
+                mtime: meta.mtime(),
+                mtime_nsec: meta.mtime_nsec() as u32,
+            }
+        }
+        #[cfg(not(unix))]
+        {
+            let modified = meta
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .unwrap_or_default();
+            let kind = if meta.is_dir() {
+                Self::DIRECTORY
+            } else if meta.is_file() {
+                Self::REGULAR
+            } else {
+                0
+            };
+            let perms = if meta.permissions().readonly() {
+                0o444
+            } else {
+                0o644
+            };
+            Self {
+                mode: kind | perms,
+                uid: 0,
+                gid: 0,
+                size: meta.len(),
+                mtime: modified.as_secs() as i64,
+                mtime_nsec: modified.subsec_nanos(),
+            }
+        }
+    }
+}
+
+/// Fetches the quick-check fields of `path`.
+///
+/// On Linux this is one `statx()` asking only for type, mode, ownership, size
+/// and mtime with `AT_STATX_DONT_SYNC`, so a network filesystem answers from
+/// its attribute cache instead of revalidating with the server. A missing
+/// path is reported as-is; any other `statx()` failure (an old kernel, a
+/// seccomp filter, a filesystem that leaves a requested field unset) falls
+/// back to the standard library. Other platforms use the standard library
+/// directly.
+pub fn quick_stat(path: &Path, follow_symlinks: bool) -> io::Result<QuickStat> {
+    #[cfg(target_os = "linux")]
+    {
+        match try_quick_statx(path, follow_symlinks) {
+            Ok(stat) => return Ok(stat),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Err(err),
+            Err(_) => {}
+        }
+    }
+    let meta = if follow_symlinks {
+        fs::metadata(path)?
+    } else {
+        fs::symlink_metadata(path)?
+    };
+    Ok(QuickStat::from_metadata(&meta))
+}
+
+#[cfg(target_os = "linux")]
+fn try_quick_statx(path: &Path, follow_symlinks: bool) -> io::Result<QuickStat> {
+    use rustix::fs::{AtFlags, StatxFlags};
+
+    let mut flags = AtFlags::STATX_DONT_SYNC;
+    if !follow_symlinks {
+        flags |= AtFlags::SYMLINK_NOFOLLOW;
+    }
+    let mask = StatxFlags::TYPE
+        | StatxFlags::MODE
+        | StatxFlags::UID
+        | StatxFlags::GID
+        | StatxFlags::SIZE
+        | StatxFlags::MTIME;
+
+    let stx = rustix::fs::statx(rustix::fs::CWD, path, flags, mask)
+        .map_err(|e| io::Error::from_raw_os_error(e.raw_os_error()))?;
+    if !StatxFlags::from_bits_retain(stx.stx_mask).contains(mask) {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "statx left a requested field unset",
+        ));
+    }
+    Ok(QuickStat {
+        mode: u32::from(stx.stx_mode),
+        uid: stx.stx_uid,
+        gid: stx.stx_gid,
+        size: stx.stx_size,
+        mtime: stx.stx_mtime.tv_sec,
+        mtime_nsec: stx.stx_mtime.tv_nsec,
+    })
+}
+
 /// Set file times.
 ///
 /// On Unix this calls `utimensat(2)` directly; on non-Unix platforms it uses
@@ -401,6 +546,31 @@ mod tests {
         Ok(path)
     }
 
+    #[test]
+    fn quick_stat_matches_std_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = create_test_file(&temp_dir, "test.txt", b"hello").unwrap();
+
+        let quick = quick_stat(&path, true).unwrap();
+        let expected = QuickStat::from_metadata(&fs::metadata(&path).unwrap());
+        assert_eq!(quick, expected);
+        assert!(quick.is_file());
+        assert_eq!(quick.size, 5);
+    }
+
+    #[test]
+    fn quick_stat_reports_missing_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let err = quick_stat(&temp_dir.path().join("absent"), true).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn quick_stat_reports_directory_as_not_file() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(!quick_stat(temp_dir.path(), true).unwrap().is_file());
+    }
+
     #[test]
     fn test_individual_stat() {
         let temp_dir = TempDir::new().unwrap();
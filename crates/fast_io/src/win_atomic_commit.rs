@@ -72,13 +72,14 @@ mod imp {
     /// [`io::ErrorKind::AlreadyExists`]; a missing parent yields
     /// [`io::ErrorKind::NotFound`].
     pub fn create_new_no_follow(path: &Path) -> io::Result<File> {
+        let path = crate::win_path::to_native_path(path);
         OpenOptions::new()
             .create_new(true)
             .write(true)
             .access_mode(FILE_GENERIC_READ | FILE_GENERIC_WRITE | DELETE)
             .share_mode(SHARE_ALL)
             .custom_flags(FILE_FLAG_OPEN_REPARSE_POINT)
-            .open(path)
+            .open(&path)
     }
 
     /// Commits `temp_path` to `dest_path` with a handle-anchored rename that a
@@ -122,6 +123,10 @@ mod imp {
         dest_path: &Path,
         replace_existing: bool,
     ) -> io::Result<()> {
+        // Deep or reserved-name destinations need the `\\?\` form, and
+        // `FileRenameInfo` takes the name verbatim, so resolve both up front.
+        let temp_path = crate::win_path::to_native_path(temp_path);
+        let dest_path = crate::win_path::to_native_path(dest_path);
         let dest_dir = dest_path.parent().ok_or_else(|| {
             io::Error::new(
                 io::ErrorKind::InvalidInput,
@@ -160,7 +165,7 @@ mod imp {
             .access_mode(DELETE | FILE_GENERIC_READ)
             .share_mode(SHARE_ALL)
             .custom_flags(FILE_FLAG_OPEN_REPARSE_POINT)
-            .open(temp_path)?;
+            .open(&temp_path)?;
         if file_attributes(&src)? & FILE_ATTRIBUTE_REPARSE_POINT != 0 {
             return Err(io::Error::new(
                 io::ErrorKind::PermissionDenied,
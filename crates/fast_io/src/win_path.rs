@@ -13,6 +13,13 @@
 //! unchanged. On non-Windows targets the helper compiles to an identity
 //! pass-through so call sites do not need `#[cfg]` guards.
 //!
+//! [`to_native_path`] is the transparent variant used on hot paths: it only
+//! rewrites a path when Win32 would otherwise mishandle it - the absolute form
+//! reaches `MAX_PATH`, or a component is a reserved DOS device name (`CON`,
+//! `NUL`, `AUX`, ...) or ends in a dot or space that Win32 silently strips.
+//! [`is_reserved_device_name`] is portable so senders on any platform can
+//! recognise names that a Windows receiver must escape.
+//!
 //! References:
 //! - <https://learn.microsoft.com/windows/win32/fileio/naming-a-file>
 //! - <https://learn.microsoft.com/windows/win32/fileio/maximum-file-path-limitation>
@@ -28,8 +35,41 @@
 //! all non-Windows inputs return a [`std::borrow::Cow::Borrowed`].
 
 use std::borrow::Cow;
+use std::ffi::OsStr;
 use std::path::Path;
 
+/// Win32 `MAX_PATH`, counted in UTF-16 code units including the terminator.
+#[cfg(windows)]
+const MAX_PATH: usize = 260;
+
+/// DOS device names that Win32 resolves to a device in every directory.
+const RESERVED_DEVICE_NAMES: [&str; 6] = ["CON", "PRN", "AUX", "NUL", "CONIN$", "CONOUT$"];
+
+/// Returns `true` when `name` is a reserved DOS device name on Windows.
+///
+/// Win32 maps `CON`, `PRN`, `AUX`, `NUL`, `CONIN$`, `CONOUT$`, `COM1`-`COM9`,
+/// and `LPT1`-`LPT9` to devices regardless of case, of any extension, and of
+/// trailing spaces, so `nul.txt` and `Com1 ` name devices too. Such names can
+/// only be created as regular files through the `\\?\` prefix. The check is
+/// pure string matching and behaves identically on every platform.
+#[must_use]
+pub fn is_reserved_device_name(name: &OsStr) -> bool {
+    let Some(name) = name.to_str() else {
+        return false;
+    };
+    let stem = name.split('.').next().unwrap_or(name).trim_end_matches(' ');
+    if RESERVED_DEVICE_NAMES
+        .iter()
+        .any(|reserved| stem.eq_ignore_ascii_case(reserved))
+    {
+        return true;
+    }
+    let bytes = stem.as_bytes();
+    bytes.len() == 4
+        && (bytes[..3].eq_ignore_ascii_case(b"COM") || bytes[..3].eq_ignore_ascii_case(b"LPT"))
+        && (b'1'..=b'9').contains(&bytes[3])
+}
+
 /// Returns the input path with a `\\?\` (or `\\?\UNC\`) prefix added when
 /// needed for Windows long-path support.
 ///
@@ -105,6 +145,76 @@ fn to_extended_path_windows(p: &Path) -> Cow<'_, Path> {
     Cow::Borrowed(p)
 }
 
+/// Returns the path Win32 should be handed for `p`, adding the `\\?\` prefix
+/// only when the plain form would be mishandled.
+///
+/// On non-Windows targets the helper is an identity no-op. On Windows the
+/// path is returned unchanged unless its absolute form reaches `MAX_PATH` or
+/// one of its components is a reserved device name (see
+/// [`is_reserved_device_name`]) or ends in a dot or space. In those cases a
+/// relative path is resolved against the current directory, `.` and `..` are
+/// collapsed lexically (the prefix disables the kernel's own collapsing), and
+/// the result is passed through [`to_extended_path`].
+pub fn to_native_path(p: &Path) -> Cow<'_, Path> {
+    #[cfg(windows)]
+    {
+        to_native_path_windows(p)
+    }
+    #[cfg(not(windows))]
+    {
+        Cow::Borrowed(p)
+    }
+}
+
+#[cfg(windows)]
+fn to_native_path_windows(p: &Path) -> Cow<'_, Path> {
+    use std::os::windows::ffi::OsStrExt;
+    use std::path::{Component, PathBuf, Prefix};
+
+    if let Some(Component::Prefix(prefix)) = p.components().next()
+        && (prefix.kind().is_verbatim() || matches!(prefix.kind(), Prefix::DeviceNS(_)))
+    {
+        return Cow::Borrowed(p);
+    }
+
+    let needs_escape = p.components().any(|component| match component {
+        Component::Normal(name) => {
+            is_reserved_device_name(name)
+                || name
+                    .to_str()
+                    .is_some_and(|name| name.ends_with('.') || name.ends_with(' '))
+        }
+        _ => false,
+    });
+
+    let absolute: Cow<'_, Path> = if p.is_absolute() {
+        Cow::Borrowed(p)
+    } else {
+        match std::env::current_dir() {
+            Ok(cwd) => Cow::Owned(cwd.join(p)),
+            Err(_) => return Cow::Borrowed(p),
+        }
+    };
+    if !needs_escape && absolute.as_os_str().encode_wide().count() < MAX_PATH {
+        return Cow::Borrowed(p);
+    }
+
+    let mut normalised = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalised.pop();
+            }
+            other => normalised.push(other.as_os_str()),
+        }
+    }
+    match to_extended_path(&normalised) {
+        Cow::Owned(extended) => Cow::Owned(extended),
+        Cow::Borrowed(_) => Cow::Owned(normalised),
+    }
+}
+
 #[cfg(windows)]
 fn is_drive_letter_absolute(s: &str) -> bool {
     let bytes = s.as_bytes();
@@ -189,6 +299,52 @@ mod tests {
         assert_eq!(out.as_ref(), input);
     }
 
+    #[test]
+    fn native_short_path_is_identity() {
+        let input = Path::new(r"C:\short\file.txt");
+        let out = to_native_path(input);
+        assert!(matches!(out, Cow::Borrowed(_)));
+        assert_eq!(out.as_ref(), input);
+    }
+
+    #[test]
+    fn native_long_path_is_prefixed() {
+        let mut deep = PathBuf::from(r"C:\");
+        for i in 0..30 {
+            deep.push(format!("segment_{:0>5}", i));
+        }
+        let out = to_native_path(&deep);
+        let expected = format!(r"\\?\{}", deep.display());
+        assert_eq!(out.as_ref(), Path::new(&expected));
+    }
+
+    #[test]
+    fn native_reserved_name_is_prefixed() {
+        let out = to_native_path(Path::new(r"C:\dest\sub\..\NUL.txt"));
+        assert_eq!(out.as_ref(), Path::new(r"\\?\C:\dest\NUL.txt"));
+    }
+
+    #[test]
+    fn native_trailing_dot_is_prefixed() {
+        let out = to_native_path(Path::new("C:/dest/name."));
+        assert_eq!(out.as_ref(), Path::new(r"\\?\C:\dest\name."));
+    }
+
+    #[test]
+    fn native_relative_reserved_name_is_absolutised() {
+        let out = to_native_path(Path::new(r"dir\aux"));
+        let expected =
+            to_extended_path(&std::env::current_dir().unwrap().join(r"dir\aux")).into_owned();
+        assert_eq!(out.as_ref(), expected.as_path());
+    }
+
+    #[test]
+    fn native_already_extended_path_is_identity() {
+        let input = Path::new(r"\\?\C:\dest\CON");
+        let out = to_native_path(input);
+        assert!(matches!(out, Cow::Borrowed(_)));
+    }
+
     #[test]
     fn drive_letter_without_separator_is_identity() {
         // `C:foo` is a drive-relative path; the extended prefix cannot
@@ -222,6 +378,14 @@ mod tests {
         assert_eq!(out.as_ref(), input);
     }
 
+    #[test]
+    fn non_windows_native_path_is_identity_for_reserved_name() {
+        let input = Path::new("/srv/dest/NUL.txt");
+        let out = to_native_path(input);
+        assert!(matches!(out, Cow::Borrowed(_)));
+        assert_eq!(out.as_ref(), input);
+    }
+
     #[test]
     fn non_windows_is_identity_for_unc_like_path() {
         let input = Path::new(r"\\server\share\file");
@@ -230,3 +394,34 @@ mod tests {
         assert_eq!(out.as_ref(), input);
     }
 }
+
+#[cfg(test)]
+mod reserved_name_tests {
+    use super::is_reserved_device_name;
+    use std::ffi::OsStr;
+
+    #[test]
+    fn recognises_reserved_names_case_insensitively() {
+        for name in [
+            "CON", "prn", "Aux", "nul", "COM1", "com9", "LPT3", "CONIN$", "conout$",
+        ] {
+            assert!(is_reserved_device_name(OsStr::new(name)), "{name}");
+        }
+    }
+
+    #[test]
+    fn ignores_extension_and_trailing_spaces() {
+        for name in ["NUL.txt", "con.tar.gz", "AUX ", "com1 .log"] {
+            assert!(is_reserved_device_name(OsStr::new(name)), "{name}");
+        }
+    }
+
+    #[test]
+    fn accepts_ordinary_names() {
+        for name in [
+            "CONSOLE", "nullable", "COM0", "COM10", "LPT", "xcon", ".nul", "a.CON",
+        ] {
+            assert!(!is_reserved_device_name(OsStr::new(name)), "{name}");
+        }
+    }
+}
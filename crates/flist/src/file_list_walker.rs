@@ -1,11 +1,12 @@
 use crate::entry::FileListEntry;
 use crate::error::FileListError;
 use logging::debug_log;
+use std::borrow::Cow;
 use std::collections::HashSet;
 use std::env;
 use std::ffi::OsString;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Depth-first iterator over filesystem entries.
 pub struct FileListWalker {
@@ -104,9 +105,9 @@ impl FileListWalker {
         // set, lstat() otherwise. stat() follows symlinks so the metadata
         // reflects the target file/directory, not the symlink itself.
         let metadata = if self.copy_links {
-            fs::metadata(&full_path)
+            fs::metadata(native_path(&full_path))
         } else {
-            fs::symlink_metadata(&full_path)
+            fs::symlink_metadata(native_path(&full_path))
         }
         .map_err(|error| FileListError::metadata(full_path.clone(), error))?;
 
@@ -114,7 +115,7 @@ impl FileListWalker {
         // --safe-links is active, excluding them from the file list before
         // sending to prevent symlinks that escape the module root.
         if self.safe_links && metadata.file_type().is_symlink() {
-            if let Ok(target) = fs::read_link(&full_path) {
+            if let Ok(target) = fs::read_link(native_path(&full_path)) {
                 if crate::symlink_safety::is_unsafe_symlink(target.as_os_str(), &relative_path) {
                     debug_log!(
                         Flist,
@@ -136,7 +137,7 @@ impl FileListWalker {
         if metadata.file_type().is_dir() {
             next_state = Some((full_path.clone(), relative_path.clone(), depth));
         } else if metadata.file_type().is_symlink() && self.follow_symlinks {
-            match fs::metadata(&full_path) {
+            match fs::metadata(native_path(&full_path)) {
                 Ok(target) if target.is_dir() => {
                     let canonical = fs::canonicalize(&full_path)
                         .map_err(|error| FileListError::canonicalize(full_path.clone(), error))?;
@@ -211,6 +212,22 @@ impl Iterator for FileListWalker {
     }
 }
 
+/// Returns the form of `path` handed to the filesystem.
+///
+/// On Windows deep paths and reserved device names (`CON`, `NUL`, ...) are
+/// routed through the `\\?\` prefix so they can be stat'ed and listed;
+/// elsewhere the path is used as is.
+fn native_path(path: &Path) -> Cow<'_, Path> {
+    #[cfg(windows)]
+    {
+        fast_io::to_native_path(path)
+    }
+    #[cfg(not(windows))]
+    {
+        Cow::Borrowed(path)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub(crate) struct DirectoryState {
     fs_path: PathBuf,
@@ -226,7 +243,7 @@ impl DirectoryState {
         relative_prefix: PathBuf,
         depth: usize,
     ) -> Result<Self, FileListError> {
-        let read_dir = fs::read_dir(&fs_path)
+        let read_dir = fs::read_dir(native_path(&fs_path))
             .map_err(|error| FileListError::read_dir(fs_path.clone(), error))?;
 
         let mut entries = Vec::new();
@@ -542,4 +559,32 @@ mod tests {
             );
         }
     }
+
+    /// A junction is reported as a symlink and never descended into, so a
+    /// junction pointing back up the tree cannot loop the walk.
+    #[cfg(windows)]
+    #[test]
+    fn file_list_walker_reports_junction_as_symlink() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let target = temp.path().join("target");
+        std::fs::create_dir(&target).expect("create target");
+        std::fs::write(target.join("inner.txt"), b"data").expect("write");
+        let link = temp.path().join("link");
+        fast_io::create_directory_symlink_or_junction(temp.path(), &link).expect("create junction");
+
+        let walker = FileListWalker::new(temp.path().to_path_buf(), false, false, true, false)
+            .expect("create walker");
+        let entries: Vec<_> = walker.map(|entry| entry.expect("entry")).collect();
+
+        let link_entry = entries
+            .iter()
+            .find(|entry| entry.relative_path == Path::new("link"))
+            .expect("junction listed");
+        assert!(link_entry.metadata.file_type().is_symlink());
+        let under_link = entries
+            .iter()
+            .filter(|entry| entry.relative_path.starts_with("link"))
+            .count();
+        assert_eq!(under_link, 1, "walker descended into the junction");
+    }
 }
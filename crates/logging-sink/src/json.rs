@@ -0,0 +1,216 @@
+//! JSON-lines records for machine-readable output (`--json`).
+//!
+//! oc-rsync extension. Orchestration tools that drive transfers want per-file
+//! events, errors, and the final summary as data rather than as text to
+//! scrape. [`JsonLine`] builds one flat JSON object per record and
+//! [`MessageSink::write_json`] renders a [`Message`] through it, so every
+//! diagnostic the sink can print has a structured counterpart.
+//!
+//! Records are flat objects whose `type` key names the record kind. Only
+//! strings, unsigned and signed integers, booleans, and fixed-precision
+//! seconds are emitted, which keeps the encoder dependency-free.
+
+use std::borrow::Borrow;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use core::message::{Message, Role};
+
+use crate::MessageSink;
+
+/// Builder for a single JSON-lines record.
+///
+/// # Examples
+///
+/// ```
+/// use logging_sink::JsonLine;
+///
+/// let line = JsonLine::new("file")
+///     .string("path", "dir/a \"b\".txt")
+///     .unsigned("size", 42)
+///     .boolean("created", true)
+///     .finish();
+/// assert_eq!(
+///     line,
+///     r#"{"type":"file","path":"dir/a \"b\".txt","size":42,"created":true}"#
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct JsonLine {
+    buffer: String,
+}
+
+impl JsonLine {
+    /// Starts a record whose `type` key is `kind`.
+    #[must_use]
+    pub fn new(kind: &str) -> Self {
+        let mut buffer = String::with_capacity(128);
+        buffer.push_str("{\"type\":");
+        push_json_string(&mut buffer, kind);
+        Self { buffer }
+    }
+
+    fn key(&mut self, key: &str) {
+        self.buffer.push(',');
+        push_json_string(&mut self.buffer, key);
+        self.buffer.push(':');
+    }
+
+    /// Appends a string field, escaping it as JSON requires.
+    #[must_use]
+    pub fn string(mut self, key: &str, value: &str) -> Self {
+        self.key(key);
+        push_json_string(&mut self.buffer, value);
+        self
+    }
+
+    /// Appends an unsigned integer field.
+    #[must_use]
+    pub fn unsigned(mut self, key: &str, value: u64) -> Self {
+        self.key(key);
+        let _ = write!(self.buffer, "{value}");
+        self
+    }
+
+    /// Appends a signed integer field.
+    #[must_use]
+    pub fn signed(mut self, key: &str, value: i64) -> Self {
+        self.key(key);
+        let _ = write!(self.buffer, "{value}");
+        self
+    }
+
+    /// Appends a boolean field.
+    #[must_use]
+    pub fn boolean(mut self, key: &str, value: bool) -> Self {
+        self.key(key);
+        self.buffer.push_str(if value { "true" } else { "false" });
+        self
+    }
+
+    /// Appends a duration as seconds with millisecond precision.
+    #[must_use]
+    pub fn seconds(mut self, key: &str, value: Duration) -> Self {
+        self.key(key);
+        let _ = write!(self.buffer, "{:.3}", value.as_secs_f64());
+        self
+    }
+
+    /// Appends `value` when present and leaves the key out otherwise.
+    #[must_use]
+    pub fn optional_string(self, key: &str, value: Option<&str>) -> Self {
+        match value {
+            Some(value) => self.string(key, value),
+            None => self,
+        }
+    }
+
+    /// Closes the object and returns it without a trailing newline.
+    #[must_use]
+    pub fn finish(mut self) -> String {
+        self.buffer.push('}');
+        self.buffer
+    }
+
+    /// Writes the closed object followed by a newline to `writer`.
+    pub fn write_to<W: Write + ?Sized>(self, writer: &mut W) -> io::Result<()> {
+        let mut line = self.finish();
+        line.push('\n');
+        writer.write_all(line.as_bytes())
+    }
+}
+
+impl From<&Message> for JsonLine {
+    /// Maps a diagnostic onto a record whose `type` is its severity
+    /// (`info`, `warning`, or `error`); code and role are included when set.
+    fn from(message: &Message) -> Self {
+        let mut line = Self::new(message.severity().as_str());
+        if let Some(code) = message.code() {
+            line = line.signed("code", i64::from(code));
+        }
+        line.optional_string("role", message.role().map(Role::as_str))
+            .string("message", message.text())
+    }
+}
+
+impl<W> MessageSink<W>
+where
+    W: Write,
+{
+    /// Writes `message` as a single JSON-lines record instead of rendering
+    /// the upstream text form.
+    ///
+    /// The sink's [`LineMode`](crate::LineMode) and brand do not apply; each
+    /// record always ends with a newline so consumers can split on lines.
+    pub fn write_json<M>(&mut self, message: M) -> io::Result<()>
+    where
+        M: Borrow<Message>,
+    {
+        JsonLine::from(message.borrow()).write_to(self.writer_mut())
+    }
+}
+
+/// Appends `value` to `buffer` as a quoted JSON string.
+fn push_json_string(buffer: &mut String, value: &str) {
+    buffer.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => buffer.push_str("\\\""),
+            '\\' => buffer.push_str("\\\\"),
+            '\n' => buffer.push_str("\\n"),
+            '\r' => buffer.push_str("\\r"),
+            '\t' => buffer.push_str("\\t"),
+            ch if u32::from(ch) < 0x20 => {
+                let _ = write!(buffer, "\\u{:04x}", u32::from(ch));
+            }
+            ch => buffer.push(ch),
+        }
+    }
+    buffer.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_quotes_backslashes_and_controls() {
+        let line = JsonLine::new("t").string("v", "a\"b\\c\nd\u{1}").finish();
+        assert_eq!(line, r#"{"type":"t","v":"a\"b\\c\nd\u0001"}"#);
+    }
+
+    #[test]
+    fn numbers_booleans_and_seconds_render_bare() {
+        let line = JsonLine::new("summary")
+            .unsigned("files", 3)
+            .signed("code", -1)
+            .boolean("dry_run", false)
+            .seconds("elapsed", Duration::from_millis(1500))
+            .finish();
+        assert_eq!(
+            line,
+            r#"{"type":"summary","files":3,"code":-1,"dry_run":false,"elapsed":1.500}"#
+        );
+    }
+
+    #[test]
+    fn optional_string_omits_absent_values() {
+        let line = JsonLine::new("t").optional_string("role", None).finish();
+        assert_eq!(line, r#"{"type":"t"}"#);
+    }
+
+    #[test]
+    fn write_json_renders_error_with_code_and_role() {
+        let mut sink = MessageSink::new(Vec::new());
+        let message =
+            Message::error(23, "some files could not be transferred").with_role(Role::Client);
+        sink.write_json(&message).expect("write json");
+        let output = String::from_utf8(sink.into_inner()).expect("utf8");
+        assert_eq!(
+            output,
+            "{\"type\":\"error\",\"code\":23,\"role\":\"client\",\
+             \"message\":\"some files could not be transferred\"}\n"
+        );
+    }
+}
@@ -21,6 +21,9 @@
 //! buffers alive for the duration of a logging session. Callers can control
 //! whether rendered messages end with a newline by selecting a [`LineMode`].
 //!
+//! [`JsonLine`] and [`MessageSink::write_json`] provide the JSON-lines
+//! counterpart used by the oc-rsync `--json` output mode.
+//!
 //! On Unix platforms the crate also provides a [`syslog`] backend that routes
 //! daemon-mode diagnostics through `syslog(3)` with a configurable facility
 //! and tag, matching upstream rsync's `log.c` behaviour.
@@ -71,6 +74,7 @@
 //! - [`core::message`] for message construction and formatting helpers.
 //! - `logging` crate for verbosity flags and the `info_log!`/`debug_log!` macros.
 
+mod json;
 mod line_mode;
 mod sink;
 /// Syslog backend for daemon-mode logging.
@@ -81,6 +85,7 @@ mod sink;
 #[cfg(unix)]
 pub mod syslog;
 
+pub use json::JsonLine;
 pub use line_mode::LineMode;
 pub use sink::{LineModeGuard, MessageSink, TryMapWriterError};
 
@@ -193,6 +193,24 @@ impl VerbosityConfig {
         config
     }
 
+    /// Cap every info and debug level at what `-v` repeated `level` times
+    /// would enable.
+    ///
+    /// Used by the daemon to keep a client's forwarded `--info=`/`--debug=`
+    /// words within the module's `max verbosity`; a flag the level tables
+    /// never reach at `level` drops to 0. Levels above the last table entry
+    /// (5) impose no limit.
+    // upstream: options.c:527-552 limit_output_verbosity()
+    pub fn limit_output_verbosity(&mut self, level: u8) {
+        // upstream: options.c:532 `if (level > MAX_VERBOSITY) return;`
+        if level > 5 {
+            return;
+        }
+        let ceiling = Self::from_verbose_level(level);
+        self.info.limit_to(&ceiling.info);
+        self.debug.limit_to(&ceiling.debug);
+    }
+
     /// Apply a single info flag token (e.g., `"copy2"`, `"del"`).
     ///
     /// Parses the token into a flag name and optional numeric level suffix
@@ -270,6 +288,10 @@ impl VerbosityConfig {
             "clone" => DebugFlag::Clone,
             "sockopt" => DebugFlag::Sockopt,
             "iocp" => DebugFlag::Iocp,
+            // oc-specific checksum backend selection visibility.
+            "checksum" => DebugFlag::Checksum,
+            // oc-specific request pipeline window sizing.
+            "pipeline" => DebugFlag::Pipeline,
             _ => return Err(format!("unknown debug flag: {name}")),
         };
 
@@ -346,6 +368,34 @@ mod tests {
         assert_eq!(config.debug.iconv, 1);
     }
 
+    #[test]
+    fn test_limit_output_verbosity_caps_at_level_table() {
+        let mut config = VerbosityConfig::from_verbose_level(5);
+        config.apply_info_flag("progress2").unwrap();
+        config.apply_debug_flag("iouring").unwrap();
+
+        config.limit_output_verbosity(1);
+        assert_eq!(config.info.name, 1);
+        assert_eq!(config.info.misc, 1);
+        assert_eq!(config.info.progress, 0);
+        assert_eq!(config.debug.deltasum, 0);
+        assert_eq!(config.debug.iouring, 0);
+    }
+
+    #[test]
+    fn test_limit_output_verbosity_never_raises_levels() {
+        let mut config = VerbosityConfig::default();
+        config.apply_debug_flag("deltasum2").unwrap();
+
+        config.limit_output_verbosity(5);
+        assert_eq!(config.debug.deltasum, 2);
+        assert_eq!(config.info.name, 0);
+
+        config.apply_debug_flag("iouring").unwrap();
+        config.limit_output_verbosity(6);
+        assert_eq!(config.debug.iouring, 1);
+    }
+
     #[test]
     fn test_parse_flag_token() {
         assert_eq!(parse_flag_token("copy").unwrap(), ("copy", 1));
@@ -402,6 +452,20 @@ mod tests {
         assert_eq!(config.debug.iocp, 1);
     }
 
+    #[test]
+    fn test_apply_checksum_debug_flag() {
+        let mut config = VerbosityConfig::default();
+        config.apply_debug_flag("CHECKSUM2").unwrap();
+        assert_eq!(config.debug.checksum, 2);
+    }
+
+    #[test]
+    fn test_apply_pipeline_debug_flag() {
+        let mut config = VerbosityConfig::default();
+        config.apply_debug_flag("pipeline2").unwrap();
+        assert_eq!(config.debug.pipeline, 2);
+    }
+
     #[test]
     fn test_from_verbose_level_0() {
         let config = VerbosityConfig::from_verbose_level(0);
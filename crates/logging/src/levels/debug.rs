@@ -4,6 +4,8 @@
 //! rsync's `DEBUG_*` constants and `debug_levels[]` array
 //! (upstream: rsync.h, options.c:237).
 
+use super::min_level;
+
 /// Debug flags for developer-oriented diagnostic categories.
 ///
 /// These flags control detailed internal output - protocol negotiation,
@@ -71,6 +73,10 @@ pub enum DebugFlag {
     Sockopt,
     /// Windows IOCP dispatch and fallback (oc-specific).
     Iocp,
+    /// Checksum SIMD backend selection (oc-specific).
+    Checksum,
+    /// Request pipeline window sizing (oc-specific).
+    Pipeline,
 }
 
 /// Per-flag debug verbosity levels.
@@ -138,6 +144,10 @@ pub struct DebugLevels {
     pub sockopt: u8,
     /// Windows IOCP dispatch level (oc-specific).
     pub iocp: u8,
+    /// Checksum backend selection level (oc-specific).
+    pub checksum: u8,
+    /// Request pipeline window sizing level (oc-specific).
+    pub pipeline: u8,
 }
 
 impl DebugLevels {
@@ -173,6 +183,8 @@ impl DebugLevels {
             DebugFlag::Clone => self.clone,
             DebugFlag::Sockopt => self.sockopt,
             DebugFlag::Iocp => self.iocp,
+            DebugFlag::Checksum => self.checksum,
+            DebugFlag::Pipeline => self.pipeline,
         }
     }
 
@@ -207,6 +219,8 @@ impl DebugLevels {
             DebugFlag::Clone => self.clone = level,
             DebugFlag::Sockopt => self.sockopt = level,
             DebugFlag::Iocp => self.iocp = level,
+            DebugFlag::Checksum => self.checksum = level,
+            DebugFlag::Pipeline => self.pipeline = level,
         }
     }
 
@@ -240,6 +254,45 @@ impl DebugLevels {
         self.clone = level;
         self.sockopt = level;
         self.iocp = level;
+        self.checksum = level;
+        self.pipeline = level;
+    }
+
+    /// Lower every flag to at most the matching level in `ceiling`.
+    ///
+    /// Flags already at or below the ceiling are left unchanged.
+    // upstream: options.c:527 limit_output_verbosity()
+    pub const fn limit_to(&mut self, ceiling: &Self) {
+        self.acl = min_level(self.acl, ceiling.acl);
+        self.backup = min_level(self.backup, ceiling.backup);
+        self.bind = min_level(self.bind, ceiling.bind);
+        self.chdir = min_level(self.chdir, ceiling.chdir);
+        self.connect = min_level(self.connect, ceiling.connect);
+        self.cmd = min_level(self.cmd, ceiling.cmd);
+        self.del = min_level(self.del, ceiling.del);
+        self.deltasum = min_level(self.deltasum, ceiling.deltasum);
+        self.dup = min_level(self.dup, ceiling.dup);
+        self.exit = min_level(self.exit, ceiling.exit);
+        self.filter = min_level(self.filter, ceiling.filter);
+        self.flist = min_level(self.flist, ceiling.flist);
+        self.fuzzy = min_level(self.fuzzy, ceiling.fuzzy);
+        self.genr = min_level(self.genr, ceiling.genr);
+        self.hash = min_level(self.hash, ceiling.hash);
+        self.hlink = min_level(self.hlink, ceiling.hlink);
+        self.iconv = min_level(self.iconv, ceiling.iconv);
+        self.io = min_level(self.io, ceiling.io);
+        self.nstr = min_level(self.nstr, ceiling.nstr);
+        self.own = min_level(self.own, ceiling.own);
+        self.proto = min_level(self.proto, ceiling.proto);
+        self.recv = min_level(self.recv, ceiling.recv);
+        self.send = min_level(self.send, ceiling.send);
+        self.time = min_level(self.time, ceiling.time);
+        self.iouring = min_level(self.iouring, ceiling.iouring);
+        self.clone = min_level(self.clone, ceiling.clone);
+        self.sockopt = min_level(self.sockopt, ceiling.sockopt);
+        self.iocp = min_level(self.iocp, ceiling.iocp);
+        self.checksum = min_level(self.checksum, ceiling.checksum);
+        self.pipeline = min_level(self.pipeline, ceiling.pipeline);
     }
 }
 
@@ -301,6 +354,8 @@ mod tests {
                 DebugFlag::Clone,
                 DebugFlag::Sockopt,
                 DebugFlag::Iocp,
+                DebugFlag::Checksum,
+                DebugFlag::Pipeline,
             ] {
                 assert_eq!(levels.get(flag), 0);
                 levels.set(flag, 3);
@@ -311,6 +366,8 @@ mod tests {
             assert_eq!(levels.get(DebugFlag::Clone), 5);
             assert_eq!(levels.get(DebugFlag::Sockopt), 5);
             assert_eq!(levels.get(DebugFlag::Iocp), 5);
+            assert_eq!(levels.get(DebugFlag::Checksum), 5);
+            assert_eq!(levels.get(DebugFlag::Pipeline), 5);
         }
     }
 
@@ -377,6 +434,8 @@ mod tests {
                 clone: 26,
                 sockopt: 27,
                 iocp: 28,
+                checksum: 29,
+                pipeline: 30,
             };
 
             assert_eq!(levels.get(DebugFlag::Acl), 1);
@@ -407,6 +466,8 @@ mod tests {
             assert_eq!(levels.get(DebugFlag::Clone), 26);
             assert_eq!(levels.get(DebugFlag::Sockopt), 27);
             assert_eq!(levels.get(DebugFlag::Iocp), 28);
+            assert_eq!(levels.get(DebugFlag::Checksum), 29);
+            assert_eq!(levels.get(DebugFlag::Pipeline), 30);
         }
 
         #[test]
@@ -4,6 +4,8 @@
 //! rsync's `INFO_*` constants and `info_levels[]` array
 //! (upstream: rsync.h, options.c:228).
 
+use super::min_level;
+
 /// Info flags for user-visible diagnostic categories.
 ///
 /// These flags control output that end users see - file names, statistics,
@@ -135,6 +137,26 @@ impl InfoLevels {
         self.stats = level;
         self.symsafe = level;
     }
+
+    /// Lower every flag to at most the matching level in `ceiling`.
+    ///
+    /// Flags already at or below the ceiling are left unchanged.
+    // upstream: options.c:527 limit_output_verbosity()
+    pub const fn limit_to(&mut self, ceiling: &Self) {
+        self.backup = min_level(self.backup, ceiling.backup);
+        self.copy = min_level(self.copy, ceiling.copy);
+        self.del = min_level(self.del, ceiling.del);
+        self.flist = min_level(self.flist, ceiling.flist);
+        self.misc = min_level(self.misc, ceiling.misc);
+        self.mount = min_level(self.mount, ceiling.mount);
+        self.name = min_level(self.name, ceiling.name);
+        self.nonreg = min_level(self.nonreg, ceiling.nonreg);
+        self.progress = min_level(self.progress, ceiling.progress);
+        self.remove = min_level(self.remove, ceiling.remove);
+        self.skip = min_level(self.skip, ceiling.skip);
+        self.stats = min_level(self.stats, ceiling.stats);
+        self.symsafe = min_level(self.symsafe, ceiling.symsafe);
+    }
 }
 
 #[cfg(test)]
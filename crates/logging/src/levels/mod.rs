@@ -10,3 +10,8 @@ mod info;
 
 pub use debug::{DebugFlag, DebugLevels};
 pub use info::{InfoFlag, InfoLevels};
+
+/// `const` minimum of two levels, used by the `limit_to` ceilings.
+const fn min_level(level: u8, ceiling: u8) -> u8 {
+    if level > ceiling { ceiling } else { level }
+}
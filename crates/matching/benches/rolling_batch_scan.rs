@@ -0,0 +1,181 @@
+//! Batched miss scan throughput on low-similarity sources.
+//!
+//! The sender's hash search rolls the weak checksum one byte at a time and
+//! probes the tag table at every offset. When the source shares little with
+//! the basis almost every probe misses, so the per-byte bookkeeping around
+//! the probe dominates. The batched scan in
+//! `crates/matching/src/generator.rs` computes 64 offsets at once through
+//! [`checksums::RollingChecksum::rolled_values`] (SSE2 on x86), filters them
+//! through the tag table and bithash together, and emits the rejected
+//! offsets as literals in bulk.
+//!
+//! Run with:
+//! ```
+//! cargo bench -p matching --features bench-internal --bench rolling_batch_scan
+//! ```
+//!
+//! # Corpora
+//!
+//! All bases are 16 MiB of uncorrelated pseudo-random bytes.
+//!
+//! - `unrelated_16MiB`: the source is independent random data, so every
+//!   offset misses.
+//! - `sparse_shared_16MiB`: every tenth 64 KiB extent of the source is
+//!   copied from the basis and the rest is random.
+//! - `identical_16MiB`: the source equals the basis. Every block matches,
+//!   so the batched scan should be neutral; this is the regression control.
+//!
+//! # What is measured
+//!
+//! [`DeltaGenerator::generate`] runs with the batched scan enabled
+//! (production default) and disabled through the bench-only
+//! [`DeltaGenerator::with_batched_scan`] toggle. A one-shot stderr summary
+//! confirms that both configurations emit identical tokens.
+
+#![cfg(feature = "bench-internal")]
+
+use std::hint::black_box;
+use std::io::Cursor;
+use std::num::NonZeroU8;
+use std::sync::OnceLock;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+
+use matching::{DeltaGenerator, DeltaScript, DeltaSignatureIndex};
+use protocol::ProtocolVersion;
+use signature::{
+    SignatureAlgorithm, SignatureLayoutParams, calculate_signature_layout, generate_file_signature,
+};
+
+const BASIS_SIZE: usize = 16 << 20;
+const SHARED_EXTENT: usize = 64 * 1024;
+
+fn random_bytes(seed: u64, size: usize) -> Vec<u8> {
+    let mut state = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut out = vec![0u8; size];
+    for chunk in out.chunks_mut(8) {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        let bytes = z.to_le_bytes();
+        chunk.copy_from_slice(&bytes[..chunk.len()]);
+    }
+    out
+}
+
+/// Random source that carries every tenth `SHARED_EXTENT` of `basis`.
+fn sparse_shared_source(basis: &[u8]) -> Vec<u8> {
+    let mut source = random_bytes(0x5EED_5A7E, basis.len());
+    for (extent, (dst, src)) in source
+        .chunks_mut(SHARED_EXTENT)
+        .zip(basis.chunks(SHARED_EXTENT))
+        .enumerate()
+    {
+        if extent % 10 == 0 {
+            dst.copy_from_slice(src);
+        }
+    }
+    source
+}
+
+fn build_index(data: &[u8]) -> DeltaSignatureIndex {
+    let params = SignatureLayoutParams::new(
+        data.len() as u64,
+        None,
+        ProtocolVersion::NEWEST,
+        NonZeroU8::new(16).expect("non-zero"),
+    );
+    let layout = calculate_signature_layout(params).expect("layout");
+    let signature =
+        generate_file_signature(data, layout, SignatureAlgorithm::Md4).expect("signature");
+    DeltaSignatureIndex::from_signature(&signature, SignatureAlgorithm::Md4).expect("index")
+}
+
+struct Fixture {
+    label: &'static str,
+    source: Vec<u8>,
+    index: DeltaSignatureIndex,
+}
+
+fn fixtures() -> &'static [Fixture] {
+    static CACHE: OnceLock<Vec<Fixture>> = OnceLock::new();
+    CACHE.get_or_init(|| {
+        let basis = random_bytes(0xC0FF_EE00_FACE_F00D, BASIS_SIZE);
+        let index = build_index(&basis);
+        vec![
+            Fixture {
+                label: "unrelated_16MiB",
+                source: random_bytes(0x0DDB_A110, BASIS_SIZE),
+                index: index.clone(),
+            },
+            Fixture {
+                label: "sparse_shared_16MiB",
+                source: sparse_shared_source(&basis),
+                index: index.clone(),
+            },
+            Fixture {
+                label: "identical_16MiB",
+                source: basis,
+                index,
+            },
+        ]
+    })
+}
+
+fn run(fx: &Fixture, batched: bool) -> DeltaScript {
+    DeltaGenerator::new()
+        .with_batched_scan(batched)
+        .generate(Cursor::new(&fx.source), &fx.index)
+        .expect("generate")
+}
+
+fn report_summary() {
+    eprintln!("batched scan summary (corpus,source_bytes,literal_bytes,tokens_equal)");
+    for fx in fixtures() {
+        let batched = run(fx, true);
+        let per_byte = run(fx, false);
+        eprintln!(
+            "batch[{label}] source={size} lit_bytes={lit} tokens_equal={equal}",
+            label = fx.label,
+            size = fx.source.len(),
+            lit = batched.literal_bytes(),
+            equal = batched.tokens() == per_byte.tokens(),
+        );
+    }
+}
+
+fn bench_batched_scan(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rolling_batch_scan");
+    group.sample_size(10);
+
+    for fx in fixtures() {
+        group.throughput(Throughput::Bytes(fx.source.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("per_byte", fx.label), fx, |b, fx| {
+            b.iter(|| black_box(run(fx, false)));
+        });
+
+        group.bench_with_input(BenchmarkId::new("batched", fx.label), fx, |b, fx| {
+            b.iter(|| black_box(run(fx, true)));
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_batched_scan_entry(c: &mut Criterion) {
+    report_summary();
+    bench_batched_scan(c);
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default()
+        .sample_size(10)
+        .measurement_time(std::time::Duration::from_secs(5));
+    targets = bench_batched_scan_entry
+);
+
+criterion_main!(benches);
@@ -9,10 +9,11 @@
 //! - **Level 3**: Detailed checksum information (potential matches, search params)
 //! - **Level 4**: Per-iteration offset tracking (very verbose)
 
-use std::io::{self, Cursor, Read};
+use std::fs::File;
+use std::io::{self, BufReader, Cursor, Read, Seek, SeekFrom};
 
-use checksums::RollingChecksum;
-use logging::debug_log;
+use checksums::{RollingChecksum, RollingDigest};
+use logging::{DebugFlag, debug_gte, debug_log};
 use rayon::prelude::*;
 
 #[cfg(feature = "tracing")]
@@ -25,6 +26,13 @@ use crate::script::{DeltaScript, DeltaToken};
 /// Default buffer size used by [`DeltaGenerator::generate`].
 const DEFAULT_BUFFER_LEN: usize = 128 * 1024;
 
+/// Recommended source size for [`DeltaGenerator::with_mmap_threshold`] when a
+/// caller opts into memory-mapped scans.
+///
+/// Below this the mapping setup and page-table teardown cost more than the
+/// handful of reads they replace.
+pub const MMAP_INPUT_THRESHOLD: u64 = 1024 * 1024;
+
 /// Literal flush threshold matching upstream rsync's `CHUNK_SIZE` (32 KiB).
 ///
 /// When pending literals accumulate beyond `block_length + CHUNK_SIZE` bytes,
@@ -42,6 +50,10 @@ const CHUNK_SIZE: usize = 32 * 1024;
 /// See `docs/design/intra-file-parallelism.md`.
 const MIN_PARALLEL_CHUNK_BYTES: usize = 1024 * 1024;
 
+/// Window offsets rolled and prefiltered together by the batched miss scan in
+/// [`DeltaGenerator::generate`].
+const SCAN_BATCH: usize = 64;
+
 /// Emits a coalesced `DeltaToken::Copy` covering an open seq-match run.
 ///
 /// The seq-match optimization tracks `(start_basis_idx, run_len)` while the
@@ -220,6 +232,15 @@ fn merge_copy_runs(source: &[u8], mut runs: Vec<CopyRun>, block_len: usize) -> D
     DeltaScript::new(tokens, n, literal_bytes)
 }
 
+/// The basis chosen by [`DeltaGenerator::generate_best_of`] and its delta.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BestBasisDelta {
+    /// Position of the winning index in the candidate slice.
+    pub basis: usize,
+    /// Delta against that basis; `Copy` tokens reference its blocks.
+    pub script: DeltaScript,
+}
+
 /// Produces rsync-style delta tokens by comparing an input stream against a signature index.
 #[derive(Clone, Debug)]
 pub struct DeltaGenerator {
@@ -247,11 +268,19 @@ pub struct DeltaGenerator {
     /// byte-identical - only the unsafe seek-backwards read is avoided. `false`
     /// (default) leaves the scan byte-for-byte unchanged.
     updating_basis_file: bool,
+    /// Minimum source size for the memory-mapped input mode of
+    /// [`Self::generate_from_file`]. `u64::MAX` (the default) disables mapping.
+    mmap_threshold: u64,
     /// Test-only knob disabling the matched-block pruning bitmap so the
     /// property tests can compare prune-on against prune-off output. The
     /// production path always prunes; see `docs/design/zsync-prune.md`.
     #[cfg(any(test, feature = "bench-internal"))]
     prune_matched: bool,
+    /// Test-only knob disabling the batched miss scan so the parity tests
+    /// and `benches/rolling_batch_scan.rs` can compare it against the
+    /// per-byte scan. The production path always batches.
+    #[cfg(any(test, feature = "bench-internal"))]
+    batched_scan: bool,
 }
 
 impl DeltaGenerator {
@@ -262,8 +291,11 @@ impl DeltaGenerator {
             buffer_len: DEFAULT_BUFFER_LEN,
             consecutive_match_needed: 1,
             updating_basis_file: false,
+            mmap_threshold: u64::MAX,
             #[cfg(any(test, feature = "bench-internal"))]
             prune_matched: true,
+            #[cfg(any(test, feature = "bench-internal"))]
+            batched_scan: true,
         }
     }
 
@@ -302,6 +334,19 @@ impl DeltaGenerator {
         self
     }
 
+    /// Opts [`Self::generate_from_file`] into mapping sources of at least
+    /// `threshold` bytes instead of reading them.
+    ///
+    /// Mapping is off by default: if another process truncates a mapped
+    /// source, touching the vanished pages raises `SIGBUS` rather than
+    /// returning a short read. Only enable it for sources that are known not
+    /// to shrink during the transfer. Pass `u64::MAX` to disable it again.
+    #[must_use]
+    pub fn with_mmap_threshold(mut self, threshold: u64) -> Self {
+        self.mmap_threshold = threshold;
+        self
+    }
+
     /// Test-only switch that disables the matched-block pruning bitmap.
     ///
     /// Used by the property tests in `matched_blocks_tests.rs` to compare
@@ -329,6 +374,41 @@ impl DeltaGenerator {
         self
     }
 
+    /// Test-only switch that disables the batched miss scan.
+    ///
+    /// Used by the parity tests to check that batching leaves the emitted
+    /// tokens unchanged. Not exposed in production builds.
+    #[cfg(test)]
+    #[must_use]
+    pub(crate) fn with_batched_scan(mut self, enabled: bool) -> Self {
+        self.batched_scan = enabled;
+        self
+    }
+
+    /// Bench-only switch mirroring [`Self::with_batched_scan`], used by
+    /// `crates/matching/benches/rolling_batch_scan.rs` to compare the batched
+    /// scan against the per-byte scan. Behind the internal `bench-internal`
+    /// feature flag so the surface never reaches release builds.
+    #[cfg(all(not(test), feature = "bench-internal"))]
+    #[must_use]
+    pub fn with_batched_scan(mut self, enabled: bool) -> Self {
+        self.batched_scan = enabled;
+        self
+    }
+
+    /// Whether [`Self::generate_with_prune`] runs the batched miss scan.
+    #[inline]
+    const fn batched_scan(&self) -> bool {
+        #[cfg(any(test, feature = "bench-internal"))]
+        {
+            self.batched_scan
+        }
+        #[cfg(not(any(test, feature = "bench-internal")))]
+        {
+            true
+        }
+    }
+
     /// Generates a [`DeltaScript`] describing how to reconstruct the input from basis blocks.
     ///
     /// This implements rsync's delta generation algorithm:
@@ -367,6 +447,38 @@ impl DeltaGenerator {
         self.generate_with_prune(reader, index, prune_matched)
     }
 
+    /// Generates a [`DeltaScript`] for an open source file.
+    ///
+    /// The file is read through a buffered reader unless the caller opted in
+    /// with [`Self::with_mmap_threshold`]. Files of at least that size are then
+    /// scanned through a read-only [`fast_io::MmapReader`] with an
+    /// `madvise(MADV_SEQUENTIAL)` hint, so the rolling window pulls bytes
+    /// straight from the page cache rather than through per-buffer `read(2)`
+    /// calls. When the mapping cannot be created
+    /// (NFS, FUSE, procfs, or a zero-length file on some platforms) the scan
+    /// falls back to buffered reads of the same handle. Both modes feed the
+    /// identical scan, so the emitted tokens do not depend on the mode.
+    ///
+    /// The file must not be truncated while it is mapped.
+    pub fn generate_from_file(
+        &self,
+        file: File,
+        index: &DeltaSignatureIndex,
+    ) -> io::Result<DeltaScript> {
+        let len = file.metadata()?.len();
+        if len >= self.mmap_threshold
+            && let Ok(mapped) = file.try_clone().and_then(fast_io::MmapReader::from_file)
+        {
+            // The hint only tunes kernel readahead; a refusal changes nothing.
+            let _ = mapped.advise_sequential();
+            debug_log!(Deltasum, 2, "hash search input mmap len={}", len);
+            return self.generate(Cursor::new(mapped.as_slice()), index);
+        }
+
+        let capacity = self.buffer_len.max(index.block_length());
+        self.generate(BufReader::with_capacity(capacity, file), index)
+    }
+
     /// Reports whether emitting a `Copy` for basis block `idx` is safe at the
     /// current source `cursor` under the in-place guard.
     ///
@@ -445,6 +557,7 @@ impl DeltaGenerator {
         let mut buffer = vec![0u8; self.buffer_len.max(block_len)];
         let mut buffer_pos = 0usize;
         let mut buffer_len = 0usize;
+        let batched_scan = self.batched_scan();
 
         debug_log!(
             Deltasum,
@@ -453,6 +566,9 @@ impl DeltaGenerator {
             block_len,
             index.block_length()
         );
+        if debug_gte(DebugFlag::Deltasum, 2) {
+            debug_log!(Deltasum, 2, "hash table: {}", index.lookup_stats());
+        }
 
         debug_log!(
             Deltasum,
@@ -471,6 +587,81 @@ impl DeltaGenerator {
                 }
             }
 
+            // Batched miss scan: with the window full, compute the checksums
+            // of the next `SCAN_BATCH` offsets in one multi-offset roll and
+            // run their tag-table/bithash prefilters together. An offset the
+            // prefilters reject cannot match any block, the `want_i` hint
+            // included, so the offsets before the first survivor are emitted
+            // as literals in bulk and the per-byte path below resumes at the
+            // survivor. Low-similarity input spends nearly all of its scan
+            // here. The current window was already probed, so the batch
+            // starts one byte on.
+            if batched_scan && window.is_full() {
+                let batch = (buffer_len - buffer_pos).min(block_len).min(SCAN_BATCH);
+                let mut outgoing = [0u8; SCAN_BATCH];
+                let (first, second) = window.as_slices();
+                let head = first.len().min(batch);
+                outgoing[..head].copy_from_slice(&first[..head]);
+                outgoing[head..batch].copy_from_slice(&second[..batch - head]);
+                let incoming = &buffer[buffer_pos..buffer_pos + batch];
+
+                let mut values = [0u32; SCAN_BATCH];
+                rolling
+                    .rolled_values(&outgoing[..batch], incoming, &mut values)
+                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+                let skip = index.first_prefilter_hit(&values[..batch]).unwrap_or(batch);
+
+                if skip > 0 {
+                    if debug_gte(DebugFlag::Deltasum, 4) {
+                        for (step, &value) in (1u64..).zip(&values[..skip]) {
+                            debug_log!(
+                                Deltasum,
+                                4,
+                                "offset={} sum={:04x}{:04x}",
+                                offset + step,
+                                value & 0xffff,
+                                value >> 16
+                            );
+                        }
+                    }
+
+                    for &byte in &incoming[..skip] {
+                        window.push_back(byte);
+                    }
+                    // upstream: match.c:339-340 - flush at the same cadence
+                    // as the per-byte path so the tokens are unchanged.
+                    let cadence = literal_flush_cadence(block_len);
+                    let mut rest = &outgoing[..skip];
+                    while !rest.is_empty() {
+                        let room = cadence.saturating_sub(pending_literals.len());
+                        let (now, later) = rest.split_at(room.min(rest.len()));
+                        pending_literals.extend_from_slice(now);
+                        rest = later;
+                        if pending_literals.len() >= cadence {
+                            literal_bytes += pending_literals.len() as u64;
+                            total_bytes += pending_literals.len() as u64;
+                            let filled = std::mem::replace(
+                                &mut pending_literals,
+                                Vec::with_capacity(block_len),
+                            );
+                            tokens.push(DeltaToken::Literal(filled));
+                        }
+                    }
+
+                    rolling = RollingChecksum::from_digest(RollingDigest::from_value(
+                        values[skip - 1],
+                        block_len,
+                    ));
+                    offset += skip as u64;
+                    hash_hits += skip as u64;
+                    false_alarms += skip as u64;
+                    buffer_pos += skip;
+                    if skip == batch {
+                        continue;
+                    }
+                }
+            }
+
             let byte = buffer[buffer_pos];
             buffer_pos += 1;
 
@@ -1095,6 +1286,78 @@ impl DeltaGenerator {
 
         Ok(merge_copy_runs(source, runs, block_len))
     }
+
+    /// Generates a delta against each candidate basis and keeps the one that
+    /// sends the fewest literal bytes.
+    ///
+    /// Snapshot-style backups often have several plausible bases for a file:
+    /// the destination itself plus the same path under each `--link-dest` or
+    /// `--copy-dest` directory. Upstream rsync uses the first basis that
+    /// exists; this is an oc-rsync extension for callers that can read any
+    /// of them. Candidates are scanned in order of
+    /// [`DeltaSignatureIndex::aligned_match_bytes`] so the likely winner runs
+    /// first, and the search stops as soon as a basis covers the whole
+    /// source. Otherwise ties go to the earlier candidate, so list the
+    /// preferred basis (normally the destination) first.
+    ///
+    /// Returns `None` when `indexes` is empty.
+    pub fn generate_best_of(
+        &self,
+        source: &[u8],
+        indexes: &[&DeltaSignatureIndex],
+    ) -> io::Result<Option<BestBasisDelta>> {
+        self.generate_best_of_reader(Cursor::new(source), indexes)
+    }
+
+    /// Reader-based form of [`generate_best_of`](Self::generate_best_of) for a
+    /// source too large to hold in memory.
+    ///
+    /// `source` is rewound to its start before each estimate and each scan, so
+    /// it is read once per candidate plus once per full delta generated.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error reading or seeking `source`.
+    pub fn generate_best_of_reader<R: Read + Seek>(
+        &self,
+        mut source: R,
+        indexes: &[&DeltaSignatureIndex],
+    ) -> io::Result<Option<BestBasisDelta>> {
+        let mut order: Vec<(u64, usize)> = Vec::with_capacity(indexes.len());
+        for (position, index) in indexes.iter().enumerate() {
+            source.seek(SeekFrom::Start(0))?;
+            order.push((index.aligned_match_bytes_from(&mut source)?, position));
+        }
+        order.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+        let mut best: Option<BestBasisDelta> = None;
+        for (estimate, position) in order {
+            source.seek(SeekFrom::Start(0))?;
+            let script = self.generate(&mut source, indexes[position])?;
+            debug_log!(
+                Deltasum,
+                2,
+                "best-of basis candidate={} estimate={} literal={}",
+                position,
+                estimate,
+                script.literal_bytes()
+            );
+            let better = best.as_ref().is_none_or(|current| {
+                (script.literal_bytes(), position) < (current.script.literal_bytes(), current.basis)
+            });
+            if better {
+                let complete = script.literal_bytes() == 0;
+                best = Some(BestBasisDelta {
+                    basis: position,
+                    script,
+                });
+                if complete {
+                    break;
+                }
+            }
+        }
+        Ok(best)
+    }
 }
 
 impl Default for DeltaGenerator {
@@ -1920,4 +2183,149 @@ mod tests {
         assert!(copies_are_monotonic(&inplace, index.block_length()));
         assert_eq!(reconstruct(&basis, &index, &inplace), source);
     }
+
+    #[test]
+    fn generate_from_file_matches_stream_in_both_input_modes() {
+        use std::io::{Seek, SeekFrom, Write};
+
+        let basis = pseudo_random(256 * 1024, 0x5eed);
+        let index = build_index(&basis);
+        let mut source = basis[..100_000].to_vec();
+        source.extend_from_slice(b"inserted literal run");
+        source.extend_from_slice(&basis[100_000..]);
+
+        let mut file = tempfile::tempfile().expect("tempfile");
+        file.write_all(&source).expect("write source");
+
+        let expected = DeltaGenerator::new()
+            .generate(Cursor::new(&source[..]), &index)
+            .expect("stream");
+
+        for threshold in [0, u64::MAX] {
+            file.seek(SeekFrom::Start(0)).expect("rewind");
+            let script = DeltaGenerator::new()
+                .with_mmap_threshold(threshold)
+                .generate_from_file(file.try_clone().expect("clone"), &index)
+                .expect("file");
+            assert_eq!(script.tokens(), expected.tokens(), "threshold {threshold}");
+            assert_eq!(reconstruct(&basis, &index, &script), source);
+        }
+    }
+
+    #[test]
+    fn generate_from_file_reads_without_mapping_by_default() {
+        assert_eq!(DeltaGenerator::new().mmap_threshold, u64::MAX);
+        assert_eq!(DeltaGenerator::default().mmap_threshold, u64::MAX);
+    }
+
+    #[test]
+    fn batched_scan_matches_per_byte_scan() {
+        let basis = pseudo_random(192 * 1024, 0x5ca1);
+        let mut edited = basis.clone();
+        edited[10_000..10_037].copy_from_slice(&pseudo_random(37, 0xed17));
+        edited.splice(50_000..50_000, pseudo_random(1_003, 0x1a5e));
+        edited.drain(120_000..120_500);
+        let sources = [
+            edited,
+            pseudo_random(160 * 1024, 0x0ff5),
+            basis[7..100_007].to_vec(),
+        ];
+
+        for index in [build_index(&basis), build_index_fixed(&basis, 16)] {
+            let block_len = index.block_length();
+            for source in &sources {
+                for buffer_len in [DEFAULT_BUFFER_LEN, 1_000] {
+                    let generator = DeltaGenerator::new().with_buffer_len(buffer_len);
+                    let batched = generator
+                        .clone()
+                        .generate(Cursor::new(source), &index)
+                        .expect("batched");
+                    let per_byte = generator
+                        .with_batched_scan(false)
+                        .generate(Cursor::new(source), &index)
+                        .expect("per-byte");
+                    assert_eq!(
+                        batched.tokens(),
+                        per_byte.tokens(),
+                        "block_len {block_len} buffer_len {buffer_len}"
+                    );
+                    assert_eq!(batched.literal_bytes(), per_byte.literal_bytes());
+                    assert_eq!(reconstruct(&basis, &index, &batched), *source);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn generate_best_of_picks_basis_with_fewest_literals() {
+        let source = pseudo_random(64 * 1024, 0xb357);
+        let unrelated = pseudo_random(64 * 1024, 0x0dd);
+        let mut stale = source.clone();
+        stale[20_000..30_000].copy_from_slice(&unrelated[..10_000]);
+
+        let unrelated_index = build_index(&unrelated);
+        let stale_index = build_index(&stale);
+        let exact_index = build_index(&source);
+        let generator = DeltaGenerator::new();
+
+        let best = generator
+            .generate_best_of(&source, &[&unrelated_index, &stale_index])
+            .expect("generate")
+            .expect("candidate");
+        assert_eq!(best.basis, 1);
+        assert!(best.script.literal_bytes() < source.len() as u64);
+        assert_eq!(reconstruct(&stale, &stale_index, &best.script), source);
+
+        let best = generator
+            .generate_best_of(&source, &[&stale_index, &unrelated_index, &exact_index])
+            .expect("generate")
+            .expect("candidate");
+        assert_eq!(best.basis, 2);
+        assert_eq!(best.script.literal_bytes(), 0);
+        assert_eq!(reconstruct(&source, &exact_index, &best.script), source);
+    }
+
+    #[test]
+    fn generate_best_of_prefers_earlier_candidate_on_ties() {
+        let source = pseudo_random(32 * 1024, 0x7135);
+        let basis = pseudo_random(32 * 1024, 0x0ba5);
+        let first = build_index(&basis);
+        let second = build_index(&basis);
+
+        let best = DeltaGenerator::new()
+            .generate_best_of(&source, &[&first, &second])
+            .expect("generate")
+            .expect("candidate");
+        assert_eq!(best.basis, 0);
+        assert!(
+            DeltaGenerator::new()
+                .generate_best_of(&source, &[])
+                .expect("generate")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn generate_best_of_reader_rewinds_a_partly_read_source() {
+        let source = pseudo_random(48 * 1024, 0x5eed);
+        let unrelated = pseudo_random(48 * 1024, 0xfade);
+        let mut stale = source.clone();
+        stale[4_000..12_000].copy_from_slice(&unrelated[..8_000]);
+        let stale_index = build_index(&stale);
+        let unrelated_index = build_index(&unrelated);
+
+        let mut reader = Cursor::new(source.clone());
+        reader.set_position(1_000);
+        let best = DeltaGenerator::new()
+            .generate_best_of_reader(&mut reader, &[&unrelated_index, &stale_index])
+            .expect("generate")
+            .expect("candidate");
+        let expected = DeltaGenerator::new()
+            .generate_best_of(&source, &[&unrelated_index, &stale_index])
+            .expect("generate")
+            .expect("candidate");
+        assert_eq!(best, expected);
+        assert_eq!(best.basis, 1);
+        assert_eq!(reconstruct(&stale, &stale_index, &best.script), source);
+    }
 }
@@ -0,0 +1,159 @@
+//! Block table selection for [`super::DeltaSignatureIndex`].
+//!
+//! Small and medium signatures use the chained [`CompactLookup`] (ZSO-4),
+//! whose bucket array stays within 256 KiB. Past
+//! [`OPEN_ADDRESSING_MIN_BLOCKS`] that array is pinned at `2^16` buckets and
+//! every extra block lengthens the chains, so the index switches to the
+//! [`OpenLookup`] power-of-two table, which keeps growing with the basis and
+//! adds the second-level tag bitmap in front of its slots.
+
+use std::fmt;
+
+use super::compact_lookup::{CompactLookup, CompactLookupIter};
+use super::open_lookup::{OpenLookup, OpenLookupIter};
+
+/// Block count above which the index uses open addressing.
+///
+/// At `2^15` blocks the compact table reaches its `2^16`-bucket cap at the
+/// usual 50% load; beyond that its chains grow linearly with the basis.
+pub(super) const OPEN_ADDRESSING_MIN_BLOCKS: usize = 1 << 15;
+
+/// The `(sum1, sum2) -> block index` table backing a signature index.
+#[derive(Clone, Debug)]
+pub(super) enum BlockLookup {
+    /// Chained table keyed on `sum2` (ZSO-4).
+    Compact(CompactLookup),
+    /// Open-addressing table keyed on upstream's 16-bit tag.
+    Open(OpenLookup),
+}
+
+impl BlockLookup {
+    /// Builds the table suited to `n_entries` blocks.
+    pub(super) fn with_capacity(n_entries: usize) -> Self {
+        if n_entries > OPEN_ADDRESSING_MIN_BLOCKS {
+            Self::Open(OpenLookup::with_capacity(n_entries))
+        } else {
+            Self::Compact(CompactLookup::with_capacity(n_entries))
+        }
+    }
+
+    /// Empties the table ahead of re-indexing `n_entries` blocks.
+    ///
+    /// The allocation is reused when it still suits `n_entries`: a compact
+    /// table is kept for any compact-sized signature, and an open table
+    /// only when its slot count is unchanged. Otherwise the table is rebuilt
+    /// with the right layout.
+    pub(super) fn reset(&mut self, n_entries: usize) {
+        let open = n_entries > OPEN_ADDRESSING_MIN_BLOCKS;
+        match self {
+            Self::Compact(table) if !open => table.clear(),
+            Self::Open(table) if open && table.fits(n_entries) => table.clear(),
+            _ => *self = Self::with_capacity(n_entries),
+        }
+    }
+
+    /// Inserts a `(sum1, sum2) -> block_index` mapping.
+    #[inline]
+    pub(super) fn insert(&mut self, sum1: u16, sum2: u16, block_index: u32) {
+        match self {
+            Self::Compact(table) => table.insert(sum1, sum2, block_index),
+            Self::Open(table) => table.insert(sum1, sum2, block_index),
+        }
+    }
+
+    /// Returns the block indices stored under `(sum1, sum2)` in insertion
+    /// order.
+    #[inline]
+    pub(super) fn find_all(&self, sum1: u16, sum2: u16) -> BlockLookupIter<'_> {
+        match self {
+            Self::Compact(table) => BlockLookupIter::Compact(table.find_all(sum1, sum2)),
+            Self::Open(table) => BlockLookupIter::Open(table.find_all(sum1, sum2)),
+        }
+    }
+
+    /// Returns the bucket or slot count of the table.
+    pub(super) fn capacity(&self) -> usize {
+        match self {
+            Self::Compact(table) => table.capacity(),
+            Self::Open(table) => table.capacity(),
+        }
+    }
+
+    /// Returns the byte footprint of the table's hot array.
+    #[cfg(any(test, feature = "bench-internal"))]
+    pub(super) fn bucket_bytes(&self) -> usize {
+        match self {
+            Self::Compact(table) => table.bucket_bytes(),
+            Self::Open(table) => table.slot_bytes(),
+        }
+    }
+
+    /// Summarises the table layout for `--debug=deltasum`.
+    pub(super) fn stats(&self) -> LookupStats {
+        match self {
+            Self::Compact(table) => LookupStats {
+                entries: table.len() as usize,
+                slots: table.capacity(),
+                open_addressing: false,
+                longest_probe: table.longest_chain() as usize,
+            },
+            Self::Open(table) => LookupStats {
+                entries: table.len() as usize,
+                slots: table.capacity(),
+                open_addressing: true,
+                longest_probe: table.max_displacement() as usize + 1,
+            },
+        }
+    }
+}
+
+/// Iterator over the block indices stored under one key.
+pub(super) enum BlockLookupIter<'a> {
+    Compact(CompactLookupIter<'a>),
+    Open(OpenLookupIter<'a>),
+}
+
+impl Iterator for BlockLookupIter<'_> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        match self {
+            Self::Compact(iter) => iter.next(),
+            Self::Open(iter) => iter.next(),
+        }
+    }
+}
+
+/// Layout statistics for a signature index's block table.
+///
+/// Returned by [`super::DeltaSignatureIndex::lookup_stats`] and reported at
+/// `--debug=deltasum` level 2 when a hash search starts.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct LookupStats {
+    /// Number of indexed blocks.
+    pub entries: usize,
+    /// Bucket count (chained table) or slot count (open addressing).
+    pub slots: usize,
+    /// `true` when the index uses the open-addressing table and its
+    /// second-level tag bitmap.
+    pub open_addressing: bool,
+    /// Worst-case entries visited by one probe: the longest bucket chain, or
+    /// the longest run from an entry's home slot to the entry itself.
+    pub longest_probe: usize,
+}
+
+impl fmt::Display for LookupStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let layout = if self.open_addressing {
+            "open-addressing slots, two-level tags"
+        } else {
+            "chained buckets"
+        };
+        write!(
+            f,
+            "{} blocks in {} {layout}, longest probe {}",
+            self.entries, self.slots, self.longest_probe
+        )
+    }
+}
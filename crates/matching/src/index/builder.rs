@@ -2,11 +2,12 @@
 //!
 //! Provides [`from_signature`](DeltaSignatureIndex::from_signature) and
 //! [`rebuild`](DeltaSignatureIndex::rebuild) methods that populate the tag
-//! table, bithash prefilter, and [`CompactLookup`] from a [`FileSignature`].
+//! table, bithash prefilter, and [`BlockLookup`] from a [`FileSignature`].
 
-use signature::{FileSignature, SignatureAlgorithm, SignatureBlock};
+use checksums::RollingDigest;
+use signature::{FileSignature, SignatureAlgorithm};
 
-use super::compact_lookup::CompactLookup;
+use super::block_lookup::BlockLookup;
 use super::trace::{HashtableRole, trace_created, trace_growing};
 use super::{
     BitHash, CONSUMED_BITS_PER_WORD, DeltaSignatureIndex, NEXT_MATCH_NONE, TAG_TABLE_SIZE,
@@ -14,7 +15,7 @@ use super::{
 };
 
 /// Shared helper that indexes full-length blocks into the tag table, bithash,
-/// compact lookup table, and sequential-match successor links.
+/// block lookup table, and sequential-match successor links.
 ///
 /// The successor link table is written in a single pass: while walking the
 /// block list in order we remember the index of the previous full-length
@@ -24,21 +25,21 @@ use super::{
 ///
 /// Returns `true` if at least one full-length block was indexed.
 fn populate_index(
-    blocks: &[SignatureBlock],
+    signature: &FileSignature,
     block_length: usize,
     tag_table: &mut [bool],
     bithash: &mut BitHash,
-    lookup: &mut CompactLookup,
+    lookup: &mut BlockLookup,
     next_match: &mut [u32],
 ) -> bool {
     let mut has_full_blocks = false;
     let mut prev_full: Option<usize> = None;
-    for (index, block) in blocks.iter().enumerate() {
-        if block.len() != block_length {
+    for (index, &rsum) in signature.rolling_sums().iter().enumerate() {
+        if signature.block_len(index) != block_length {
             continue;
         }
         has_full_blocks = true;
-        let digest = block.rolling();
+        let digest = RollingDigest::from_value(rsum, block_length);
         tag_table[digest.sum1() as usize] = true;
         bithash.insert(digest.value());
         lookup.insert(digest.sum1(), digest.sum2(), index as u32);
@@ -62,18 +63,18 @@ fn populate_index(
 /// keeps the parallel-scan eligibility gate on the safe side. Runs in one
 /// linear pass over the blocks, so the cost is negligible next to signature
 /// generation.
-fn detect_duplicate_blocks(blocks: &[SignatureBlock], block_length: usize) -> bool {
+fn detect_duplicate_blocks(signature: &FileSignature, block_length: usize) -> bool {
     let mut seen: std::collections::HashSet<(u32, [u8; 16])> =
-        std::collections::HashSet::with_capacity(blocks.len());
-    for block in blocks {
-        if block.len() != block_length {
+        std::collections::HashSet::with_capacity(signature.block_count());
+    for (index, &rsum) in signature.rolling_sums().iter().enumerate() {
+        if signature.block_len(index) != block_length {
             continue;
         }
         let mut strong = [0u8; 16];
-        let bytes = block.strong();
+        let bytes = signature.strong_sum(index);
         let take = bytes.len().min(strong.len());
         strong[..take].copy_from_slice(&bytes[..take]);
-        if !seen.insert((block.rolling().value(), strong)) {
+        if !seen.insert((rsum, strong)) {
             return true;
         }
     }
@@ -113,10 +114,8 @@ impl DeltaSignatureIndex {
     ) -> Option<Self> {
         let block_length = signature.layout().block_length().get() as usize;
         let strong_length = usize::from(signature.layout().strong_sum_length().get());
-        let blocks: Vec<SignatureBlock> = signature.blocks().to_vec();
-
-        let requested = blocks.len();
-        let mut lookup = CompactLookup::with_capacity(requested);
+        let requested = signature.block_count();
+        let mut lookup = BlockLookup::with_capacity(requested);
         let mut tag_table = vec![false; TAG_TABLE_SIZE];
         let mut bithash = BitHash::with_block_count(requested);
         // The seq-match link table holds one slot per signature block, sized
@@ -125,7 +124,7 @@ impl DeltaSignatureIndex {
         let mut next_match = vec![NEXT_MATCH_NONE; requested];
 
         if !populate_index(
-            &blocks,
+            signature,
             block_length,
             &mut tag_table,
             &mut bithash,
@@ -136,13 +135,13 @@ impl DeltaSignatureIndex {
         }
 
         let size = lookup.capacity();
-        let consumed = build_consumed_words(blocks.len());
-        let has_duplicate_blocks = detect_duplicate_blocks(&blocks, block_length);
+        let consumed = build_consumed_words(requested);
+        let has_duplicate_blocks = detect_duplicate_blocks(signature, block_length);
         let index = Self {
             block_length,
             strong_length,
             algorithm,
-            blocks,
+            signature: signature.clone(),
             lookup,
             tag_table,
             bithash,
@@ -162,7 +161,7 @@ impl DeltaSignatureIndex {
     }
 
     /// Rebuilds the index in-place from a new signature, reusing the
-    /// existing `CompactLookup` allocation.
+    /// existing block table allocation when it suits the new block count.
     ///
     /// Mirrors upstream rsync's hash table reuse pattern (match.c):
     /// the table is cleared and repopulated rather than freed and
@@ -177,30 +176,30 @@ impl DeltaSignatureIndex {
         self.block_length = block_length;
         self.strong_length = strong_length;
         self.algorithm = algorithm;
-        self.blocks.clear();
-        self.blocks.extend_from_slice(signature.blocks());
-        self.lookup.clear();
+        self.signature.clone_from(signature);
+        let block_count = self.signature.block_count();
+        self.lookup.reset(block_count);
         self.tag_table.iter_mut().for_each(|v| *v = false);
         self.bithash.clear();
         // Per ZSO-7 isolation: every link from the prior segment must be
         // cleared before re-population so a stale successor never leaks
         // across the per-NDX `rebuild` boundary.
         self.next_match.clear();
-        self.next_match.resize(self.blocks.len(), NEXT_MATCH_NONE);
+        self.next_match.resize(block_count, NEXT_MATCH_NONE);
         // ZSO-7 per-segment lifecycle: reset prune state so a new
         // segment starts with no stale consumed bits. Resize when the
         // new signature's block count changes the required word count.
-        let words_needed = self.blocks.len().div_ceil(CONSUMED_BITS_PER_WORD);
+        let words_needed = block_count.div_ceil(CONSUMED_BITS_PER_WORD);
         if self.consumed.len() == words_needed {
             self.reset_consumed();
         } else {
-            self.consumed = build_consumed_words(self.blocks.len());
+            self.consumed = build_consumed_words(block_count);
         }
         #[cfg(any(test, feature = "bench-internal"))]
         self.seq_match_counters.reset();
 
         let ok = populate_index(
-            &self.blocks,
+            &self.signature,
             block_length,
             &mut self.tag_table,
             &mut self.bithash,
@@ -208,14 +207,14 @@ impl DeltaSignatureIndex {
             &mut self.next_match,
         );
 
-        self.has_duplicate_blocks = detect_duplicate_blocks(&self.blocks, block_length);
+        self.has_duplicate_blocks = detect_duplicate_blocks(&self.signature, block_length);
 
         if ok {
             let size = self.lookup.capacity();
             // upstream: hashtable.c:100-103 - emit when the bucket count
             // changes from the previously traced value. Our `rebuild`
             // reuses the same allocation when possible, so the size only
-            // ever differs when the caller's signature width changes.
+            // ever differs when the new signature needs a different table.
             if size != self.last_traced_size {
                 trace_growing(self.role, self.identifier(), size);
                 self.last_traced_size = size;
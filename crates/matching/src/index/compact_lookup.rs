@@ -198,7 +198,6 @@ impl CompactLookup {
     }
 
     /// Returns the number of stored entries.
-    #[allow(dead_code)]
     pub(super) fn len(&self) -> u32 {
         self.entries.len() as u32
     }
@@ -211,6 +210,25 @@ impl CompactLookup {
         (self.mask as usize) + 1
     }
 
+    /// Returns the length of the longest bucket chain.
+    ///
+    /// Walks every chain, so callers only query it for diagnostics.
+    pub(super) fn longest_chain(&self) -> u32 {
+        self.buckets
+            .iter()
+            .map(|slot| {
+                let mut length = 0;
+                let mut next = slot.head;
+                while next != CHAIN_END {
+                    length += 1;
+                    next = self.entries[next as usize].next;
+                }
+                length
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
     /// Returns the byte footprint of the bucket array allocation.
     ///
     /// The chain backing store is excluded so the figure tracks the
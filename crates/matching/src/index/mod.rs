@@ -10,11 +10,20 @@
 //! ZSO-4 translation of zsync's `librcksum/hash.c:45` `rsum_a_mask` trick:
 //! shrinking the bucket array to at most `2^16` slots keeps the hottest
 //! lookup table cache-line resident across rolling-hash advances.
+//!
+//! That cap stops paying off once a basis runs to millions of blocks, so
+//! signatures with more than 32 768 blocks are indexed in an open-addressing
+//! table ([`open_lookup::OpenLookup`]) instead: a power-of-two slot array homed on
+//! upstream's 16-bit `(sum1 + sum2)` tag, with a one-bit-per-slot occupancy
+//! bitmap as the second tag level. [`BlockLookup`] picks the layout, and
+//! [`DeltaSignatureIndex::lookup_stats`] reports it for `--debug=deltasum`.
 
 mod bithash;
+mod block_lookup;
 mod builder;
 mod compact_lookup;
 mod matched_blocks;
+mod open_lookup;
 mod trace;
 
 #[cfg(test)]
@@ -24,6 +33,8 @@ mod compact_key_tests;
 #[cfg(test)]
 mod matched_blocks_tests;
 #[cfg(test)]
+mod open_lookup_tests;
+#[cfg(test)]
 mod prune_tests;
 #[cfg(test)]
 mod seq_match_tests;
@@ -33,13 +44,16 @@ mod sparse_match_tests;
 mod tests;
 
 use std::collections::VecDeque;
+use std::io::{self, Read};
 use std::sync::atomic::{AtomicU64, Ordering};
 
 use checksums::RollingDigest;
 
-use signature::{SignatureAlgorithm, SignatureBlock};
+use signature::{FileSignature, SignatureAlgorithm, SignatureBlock};
 
 use bithash::BitHash;
+use block_lookup::BlockLookup;
+pub use block_lookup::LookupStats;
 use compact_lookup::CompactLookup;
 pub use matched_blocks::MatchedBlocks;
 pub use trace::{
@@ -68,20 +82,23 @@ const NEXT_MATCH_NONE: u32 = u32::MAX;
 /// half of the rolling sum (`sum2`) for O(1) block lookup with excellent
 /// cache locality. The lower half (`sum1`) lives inside each chain entry as
 /// an in-bucket discriminator, mirroring zsync's `librcksum`
-/// `rsum_a_mask` trick (ZSO-4). A tag table indexed by `sum1` still provides
-/// upstream-rsync-style fast-path rejection before the bucket walk, and the
-/// bithash prefilter (ZSO-1) rejects the bulk of post-tag misses before the
-/// chain probe.
+/// `rsum_a_mask` trick (ZSO-4). Signatures too large for the capped bucket
+/// array use an open-addressing table (`OpenLookup`) instead. A tag table
+/// indexed by `sum1` still provides upstream-rsync-style fast-path rejection
+/// before the bucket walk, and the bithash prefilter (ZSO-1) rejects the bulk
+/// of post-tag misses before the chain probe.
 #[derive(Debug)]
 pub struct DeltaSignatureIndex {
     block_length: usize,
     strong_length: usize,
     algorithm: SignatureAlgorithm,
-    blocks: Vec<SignatureBlock>,
-    /// Compact bucket lookup keyed on the upper half of the rolling sum
-    /// (`rsum >> 16`); the lower 16 bits live inside each chain entry as
-    /// the in-bucket discriminator. See the ZSO-4 module-level docs.
-    lookup: CompactLookup,
+    /// Column-wise copy of the basis signature: flat rolling sums and a
+    /// fixed-stride strong-sum buffer the probes below read directly.
+    signature: FileSignature,
+    /// Block table: the compact bucket lookup keyed on the upper half of the
+    /// rolling sum (`rsum >> 16`, see the ZSO-4 module-level docs), or the
+    /// open-addressing table for signatures past its bucket cap.
+    lookup: BlockLookup,
     /// Tag table for O(1) rejection using sum1 (low 16 bits of rolling checksum).
     /// upstream: match.c - `tag_table[s1]` check before hash probe.
     tag_table: Vec<bool>,
@@ -175,7 +192,7 @@ impl Clone for DeltaSignatureIndex {
             block_length: self.block_length,
             strong_length: self.strong_length,
             algorithm: self.algorithm,
-            blocks: self.blocks.clone(),
+            signature: self.signature.clone(),
             lookup: self.lookup.clone(),
             tag_table: self.tag_table.clone(),
             bithash: self.bithash.clone(),
@@ -251,6 +268,15 @@ impl DeltaSignatureIndex {
         CompactLookup::bucket_for(rsum)
     }
 
+    /// Returns the layout statistics of the block table.
+    ///
+    /// Walks the bucket chains when the compact table is in use, so callers
+    /// should only query it for diagnostics.
+    #[must_use]
+    pub fn lookup_stats(&self) -> LookupStats {
+        self.lookup.stats()
+    }
+
     /// Returns the role used for `--debug=HASH` `[<role>]` prefixes.
     #[must_use]
     pub const fn role(&self) -> HashtableRole {
@@ -274,7 +300,7 @@ impl DeltaSignatureIndex {
     /// Returns the total number of signature blocks.
     #[must_use]
     pub fn block_count(&self) -> usize {
-        self.blocks.len()
+        self.signature.block_count()
     }
 
     /// Returns the strong checksum length used by the signature.
@@ -301,8 +327,8 @@ impl DeltaSignatureIndex {
     /// Returns the [`SignatureBlock`] for the provided index.
     #[inline]
     #[must_use]
-    pub fn block(&self, index: usize) -> &SignatureBlock {
-        &self.blocks[index]
+    pub fn block(&self, index: usize) -> SignatureBlock {
+        self.signature.block(index)
     }
 
     /// Returns the sequential-match successor recorded for `block_index`, if any.
@@ -365,9 +391,7 @@ impl DeltaSignatureIndex {
                 .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
             return None;
         }
-        let block = &self.blocks[next];
-        let block_digest = block.rolling();
-        if digest.sum1() != block_digest.sum1() || digest.sum2() != block_digest.sum2() {
+        if digest.value() != self.signature.rolling_sums()[next] {
             #[cfg(any(test, feature = "bench-internal"))]
             self.seq_match_counters
                 .misses
@@ -375,7 +399,7 @@ impl DeltaSignatureIndex {
             return None;
         }
         let strong = self.algorithm.compute_truncated(window, self.strong_length);
-        if strong.as_slice() == block.strong() {
+        if strong.as_slice() == self.signature.strong_sum(next) {
             #[cfg(any(test, feature = "bench-internal"))]
             self.seq_match_counters
                 .hits
@@ -417,9 +441,7 @@ impl DeltaSignatureIndex {
                 .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
             return None;
         }
-        let block = &self.blocks[next];
-        let block_digest = block.rolling();
-        if digest.sum1() != block_digest.sum1() || digest.sum2() != block_digest.sum2() {
+        if digest.value() != self.signature.rolling_sums()[next] {
             #[cfg(any(test, feature = "bench-internal"))]
             self.seq_match_counters
                 .misses
@@ -429,7 +451,7 @@ impl DeltaSignatureIndex {
         let strong = self
             .algorithm
             .compute_truncated_slices(first, second, self.strong_length);
-        if strong.as_slice() == block.strong() {
+        if strong.as_slice() == self.signature.strong_sum(next) {
             #[cfg(any(test, feature = "bench-internal"))]
             self.seq_match_counters
                 .hits
@@ -474,7 +496,7 @@ impl DeltaSignatureIndex {
     #[must_use]
     pub fn is_consumed(&self, idx: u32) -> bool {
         let idx = idx as usize;
-        if idx >= self.blocks.len() {
+        if idx >= self.signature.block_count() {
             return false;
         }
         let word = idx / CONSUMED_BITS_PER_WORD;
@@ -499,7 +521,7 @@ impl DeltaSignatureIndex {
     #[inline]
     pub fn mark_consumed(&self, idx: u32) {
         let idx = idx as usize;
-        if idx >= self.blocks.len() {
+        if idx >= self.signature.block_count() {
             return;
         }
         let word = idx / CONSUMED_BITS_PER_WORD;
@@ -571,9 +593,8 @@ impl DeltaSignatureIndex {
             if self.is_consumed(index as u32) {
                 continue;
             }
-            let block = &self.blocks[index];
-            debug_assert_eq!(block.len(), self.block_length);
-            if strong.as_slice() == block.strong() {
+            debug_assert_eq!(self.signature.block_len(index), self.block_length);
+            if strong.as_slice() == self.signature.strong_sum(index) {
                 return Some(index);
             }
         }
@@ -635,15 +656,32 @@ impl DeltaSignatureIndex {
             if self.is_consumed(index as u32) {
                 continue;
             }
-            let block = &self.blocks[index];
-            debug_assert_eq!(block.len(), self.block_length);
-            if strong.as_slice() == block.strong() {
+            debug_assert_eq!(self.signature.block_len(index), self.block_length);
+            if strong.as_slice() == self.signature.strong_sum(index) {
                 return Some(index);
             }
         }
         None
     }
 
+    /// Returns the position of the first packed rolling checksum in `rsums`
+    /// that passes both the tag-table and bithash prefilters.
+    ///
+    /// Every indexed block's checksum passes both filters, so a value rejected
+    /// here cannot match any full-length block, including a `want_i` hint
+    /// target. The generator uses this to probe a batch of window offsets
+    /// computed by [`RollingChecksum::rolled_values`] before falling back to
+    /// the per-offset lookup at the first survivor.
+    ///
+    /// [`RollingChecksum::rolled_values`]: checksums::RollingChecksum::rolled_values
+    #[inline]
+    #[must_use]
+    pub fn first_prefilter_hit(&self, rsums: &[u32]) -> Option<usize> {
+        rsums.iter().position(|&rsum| {
+            self.tag_table[(rsum & 0xffff) as usize] && self.bithash.contains(rsum)
+        })
+    }
+
     /// Attempts to match a short trailing window against a basis block of the
     /// same (partial) length.
     ///
@@ -677,7 +715,7 @@ impl DeltaSignatureIndex {
         }
 
         let mut strong: Option<signature::DigestBuf> = None;
-        for (index, block) in self.blocks.iter().enumerate() {
+        for (index, block) in self.signature.blocks().iter().enumerate() {
             if block.len() != tail_len {
                 continue;
             }
@@ -719,21 +757,19 @@ impl DeltaSignatureIndex {
         first: &[u8],
         second: &[u8],
     ) -> bool {
-        if block_index >= self.blocks.len() {
+        if block_index >= self.signature.block_count() {
             return false;
         }
-        let block = &self.blocks[block_index];
-        if block.len() != self.block_length {
+        if self.signature.block_len(block_index) != self.block_length {
             return false;
         }
-        let block_digest = block.rolling();
-        if digest.sum1() != block_digest.sum1() || digest.sum2() != block_digest.sum2() {
+        if digest.value() != self.signature.rolling_sums()[block_index] {
             return false;
         }
         let strong = self
             .algorithm
             .compute_truncated_slices(first, second, self.strong_length);
-        strong.as_slice() == block.strong()
+        strong.as_slice() == self.signature.strong_sum(block_index)
     }
 
     /// Attempts to locate a matching block for a non-contiguous window backed by a [`VecDeque`].
@@ -815,7 +851,7 @@ impl DeltaSignatureIndex {
         let mut offset = 0usize;
         while run < max_blocks {
             let block_idx = start_block_index + run;
-            if block_idx >= self.blocks.len() {
+            if block_idx >= self.signature.block_count() {
                 break;
             }
             let end = match offset.checked_add(block_len) {
@@ -824,21 +860,17 @@ impl DeltaSignatureIndex {
             };
             let chunk = &target[offset..end];
 
-            let block = &self.blocks[block_idx];
-            if block.len() != block_len {
+            if self.signature.block_len(block_idx) != block_len {
                 break;
             }
 
             let chunk_digest = RollingDigest::from_bytes(chunk);
-            let block_digest = block.rolling();
-            if chunk_digest.sum1() != block_digest.sum1()
-                || chunk_digest.sum2() != block_digest.sum2()
-            {
+            if chunk_digest.value() != self.signature.rolling_sums()[block_idx] {
                 break;
             }
 
             let strong = self.algorithm.compute_truncated(chunk, self.strong_length);
-            if strong.as_slice() != block.strong() {
+            if strong.as_slice() != self.signature.strong_sum(block_idx) {
                 break;
             }
 
@@ -847,6 +879,66 @@ impl DeltaSignatureIndex {
         }
         run
     }
+
+    /// Counts the bytes of `source` whose block-aligned windows match some
+    /// basis block.
+    ///
+    /// This is a cheap estimate of how much of `source` the basis can supply,
+    /// used to rank candidate bases before the full rolling scan. Only offsets
+    /// that are multiples of the block length are probed, so a basis that is
+    /// an earlier version of the same file scores high while shifted content
+    /// is undercounted. The consumed-block bitset is neither read nor written,
+    /// so the estimate does not depend on earlier scans of this index.
+    #[must_use]
+    pub fn aligned_match_bytes(&self, source: &[u8]) -> u64 {
+        if self.block_length == 0 {
+            return 0;
+        }
+
+        source
+            .chunks_exact(self.block_length)
+            .filter(|window| self.matches_some_block(window))
+            .map(|window| window.len() as u64)
+            .sum()
+    }
+
+    /// Streaming form of [`aligned_match_bytes`](Self::aligned_match_bytes)
+    /// that reads the source from `reader` one block at a time.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error `reader` reports.
+    pub fn aligned_match_bytes_from<R: Read>(&self, mut reader: R) -> io::Result<u64> {
+        if self.block_length == 0 {
+            return Ok(0);
+        }
+
+        let mut window = vec![0u8; self.block_length];
+        let mut matched = 0u64;
+        loop {
+            match reader.read_exact(&mut window) {
+                Ok(()) => {}
+                Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(matched),
+                Err(error) => return Err(error),
+            }
+            if self.matches_some_block(&window) {
+                matched += window.len() as u64;
+            }
+        }
+    }
+
+    /// Reports whether `window` equals some basis block, ignoring the
+    /// consumed-block bitset.
+    fn matches_some_block(&self, window: &[u8]) -> bool {
+        let digest = RollingDigest::from_bytes(window);
+        if !self.tag_table[digest.sum1() as usize] || !self.bithash.contains(digest.value()) {
+            return false;
+        }
+        let strong = self.algorithm.compute_truncated(window, self.strong_length);
+        self.lookup
+            .find_all(digest.sum1(), digest.sum2())
+            .any(|index| self.signature.strong_sum(index) == strong.as_slice())
+    }
 }
 
 impl Drop for DeltaSignatureIndex {
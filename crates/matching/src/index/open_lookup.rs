@@ -0,0 +1,288 @@
+//! Open-addressing block table for large signature indexes.
+//!
+//! [`super::compact_lookup::CompactLookup`] caps its bucket array at `2^16`
+//! slots, so once a basis runs to hundreds of thousands of blocks every bucket
+//! chain holds several entries scattered across the chain backing store, and
+//! each probe pays one dependent cache miss per chain step. [`OpenLookup`]
+//! keeps every entry inline in a single power-of-two slot array instead:
+//!
+//! - The table holds at least twice as many slots as entries, so linear
+//!   probing stays short and a probe usually touches a single cache line.
+//! - The home slot is keyed by upstream's 16-bit tag, `(sum1 + sum2) & 0xffff`
+//!   (`match.c` `SUM2HASH2`), which forms the high bits of the slot index.
+//!   Tables larger than `2^16` slots extend the tag with bits mixed from the
+//!   full rolling sum, the power-of-two counterpart of upstream's
+//!   `BIG_SUM2HASH` (`sum % tablesize`).
+//! - A one-bit-per-slot occupancy bitmap forms the second tag level: a home
+//!   slot whose bit is clear cannot hold a matching entry, so the probe is
+//!   answered without touching the slot array.
+//!
+//! Slots are only ever appended, so entries sharing a key sit along the
+//! probe sequence in insertion order. The `MatchedBlocks` first-fit contract
+//! therefore holds exactly as it does for the chained table.
+
+/// Minimum slot-count exponent.
+const MIN_LOG2_SLOTS: u32 = 4;
+
+/// Maximum slot-count exponent. Block indices are `u32`, so `2^32` slots
+/// always leave room for every entry plus the empty slot that ends a probe.
+const MAX_LOG2_SLOTS: u32 = 32;
+
+/// Width of upstream's hash tag (`match.c` `TRADITIONAL_TABLESIZE`).
+const TAG_BITS: u32 = 16;
+
+/// Fibonacci-hashing multiplier used to extend the tag past 16 bits.
+const MIX: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// Block index marking an unused slot.
+const EMPTY: u32 = u32::MAX;
+
+/// One table slot: the packed rolling sum and the block it belongs to.
+#[derive(Clone, Copy, Debug)]
+struct Slot {
+    rsum: u32,
+    block_index: u32,
+}
+
+impl Slot {
+    const VACANT: Self = Self {
+        rsum: 0,
+        block_index: EMPTY,
+    };
+}
+
+/// Power-of-two open-addressing table mapping `(sum1, sum2)` to block indices.
+#[derive(Clone, Debug)]
+pub(super) struct OpenLookup {
+    slots: Vec<Slot>,
+    /// Second-level tag: bit `k` is set when some entry's home slot is `k`.
+    occupied_homes: Vec<u64>,
+    log2_slots: u32,
+    len: u32,
+    /// Longest distance, in slots, between an entry and its home slot.
+    max_displacement: u32,
+}
+
+impl OpenLookup {
+    /// Builds a table with the smallest power-of-two slot count of at least
+    /// `2 * n_entries`.
+    pub(super) fn with_capacity(n_entries: usize) -> Self {
+        let log2_slots = log2_slots_for(n_entries);
+        let n_slots = 1usize << log2_slots;
+        Self {
+            slots: vec![Slot::VACANT; n_slots],
+            occupied_homes: vec![0; n_slots.div_ceil(64)],
+            log2_slots,
+            len: 0,
+            max_displacement: 0,
+        }
+    }
+
+    /// Returns `true` when the table was sized for `n_entries`, so a rebuild
+    /// can reuse the allocation.
+    pub(super) fn fits(&self, n_entries: usize) -> bool {
+        self.log2_slots == log2_slots_for(n_entries)
+    }
+
+    /// Inserts a `(sum1, sum2) -> block_index` mapping at the first free slot
+    /// along the probe sequence.
+    pub(super) fn insert(&mut self, sum1: u16, sum2: u16, block_index: u32) {
+        debug_assert_ne!(
+            block_index, EMPTY,
+            "block_index u32::MAX is the empty marker"
+        );
+        assert!(
+            (self.len as usize) < self.slots.len() - 1,
+            "open lookup sized for fewer entries than inserted"
+        );
+        let home = self.home(sum1, sum2);
+        self.occupied_homes[home / 64] |= 1 << (home % 64);
+
+        let mask = self.mask();
+        let mut pos = home;
+        while self.slots[pos].block_index != EMPTY {
+            pos = (pos + 1) & mask;
+        }
+        self.slots[pos] = Slot {
+            rsum: pack(sum1, sum2),
+            block_index,
+        };
+        self.len += 1;
+        let displacement = (pos.wrapping_sub(home) & mask) as u32;
+        self.max_displacement = self.max_displacement.max(displacement);
+    }
+
+    /// Returns an iterator over all block indices stored under `(sum1, sum2)`,
+    /// in insertion order.
+    #[inline]
+    pub(super) fn find_all(&self, sum1: u16, sum2: u16) -> OpenLookupIter<'_> {
+        let home = self.home(sum1, sum2);
+        let occupied = self.occupied_homes[home / 64] & (1 << (home % 64)) != 0;
+        OpenLookupIter {
+            table: self,
+            rsum: pack(sum1, sum2),
+            pos: home,
+            done: !occupied,
+        }
+    }
+
+    /// Clears every slot, keeping the allocation.
+    pub(super) fn clear(&mut self) {
+        self.slots.fill(Slot::VACANT);
+        self.occupied_homes.fill(0);
+        self.len = 0;
+        self.max_displacement = 0;
+    }
+
+    /// Returns the number of stored entries.
+    pub(super) const fn len(&self) -> u32 {
+        self.len
+    }
+
+    /// Returns the slot count (always a power of two).
+    pub(super) fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns the longest distance between an entry and its home slot.
+    pub(super) const fn max_displacement(&self) -> u32 {
+        self.max_displacement
+    }
+
+    /// Returns the byte footprint of the slot array and occupancy bitmap.
+    #[cfg(any(test, feature = "bench-internal"))]
+    pub(super) fn slot_bytes(&self) -> usize {
+        self.slots.len() * core::mem::size_of::<Slot>()
+            + self.occupied_homes.len() * core::mem::size_of::<u64>()
+    }
+
+    #[inline]
+    fn mask(&self) -> usize {
+        self.slots.len() - 1
+    }
+
+    /// Home slot for `(sum1, sum2)`: upstream's 16-bit tag in the high bits,
+    /// extended with mixed rolling-sum bits when the table is wider.
+    #[inline]
+    fn home(&self, sum1: u16, sum2: u16) -> usize {
+        // upstream: match.c - SUM2HASH2(s1, s2) = ((s1) + (s2)) & 0xFFFF
+        let tag = usize::from(sum1.wrapping_add(sum2));
+        if self.log2_slots <= TAG_BITS {
+            return tag >> (TAG_BITS - self.log2_slots);
+        }
+        let extra = self.log2_slots - TAG_BITS;
+        let mixed = u64::from(pack(sum1, sum2)).wrapping_mul(MIX) >> (u64::BITS - extra);
+        (tag << extra) | mixed as usize
+    }
+}
+
+/// Iterator over the block indices stored under one key.
+pub(super) struct OpenLookupIter<'a> {
+    table: &'a OpenLookup,
+    rsum: u32,
+    pos: usize,
+    done: bool,
+}
+
+impl Iterator for OpenLookupIter<'_> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        while !self.done {
+            let slot = self.table.slots[self.pos];
+            if slot.block_index == EMPTY {
+                self.done = true;
+                break;
+            }
+            self.pos = (self.pos + 1) & self.table.mask();
+            if slot.rsum == self.rsum {
+                return Some(slot.block_index as usize);
+            }
+        }
+        None
+    }
+}
+
+#[inline]
+const fn pack(sum1: u16, sum2: u16) -> u32 {
+    ((sum2 as u32) << 16) | sum1 as u32
+}
+
+/// Returns the slot-count exponent for `n_entries`: the smallest `k` with
+/// `2^k >= 2 * n_entries`, clamped to `[MIN_LOG2_SLOTS, MAX_LOG2_SLOTS]`.
+fn log2_slots_for(n_entries: usize) -> u32 {
+    let target = (n_entries as u64).saturating_mul(2);
+    let raw = if target <= 1 {
+        MIN_LOG2_SLOTS
+    } else {
+        u64::BITS - (target - 1).leading_zeros()
+    };
+    raw.clamp(MIN_LOG2_SLOTS, MAX_LOG2_SLOTS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_and_find_single() {
+        let mut table = OpenLookup::with_capacity(16);
+        table.insert(100, 200, 42);
+        assert_eq!(table.find_all(100, 200).collect::<Vec<_>>(), vec![42]);
+        assert!(table.find_all(999, 999).next().is_none());
+    }
+
+    #[test]
+    fn duplicate_keys_keep_insertion_order() {
+        let mut table = OpenLookup::with_capacity(16);
+        table.insert(10, 20, 5);
+        table.insert(11, 19, 6);
+        table.insert(10, 20, 1);
+        table.insert(10, 20, 3);
+        assert_eq!(table.find_all(10, 20).collect::<Vec<_>>(), vec![5, 1, 3]);
+        assert_eq!(table.find_all(11, 19).collect::<Vec<_>>(), vec![6]);
+    }
+
+    #[test]
+    fn slot_count_keeps_load_at_most_half() {
+        assert_eq!(OpenLookup::with_capacity(0).capacity(), 1 << MIN_LOG2_SLOTS);
+        assert_eq!(OpenLookup::with_capacity(100_000).capacity(), 1 << 18);
+        assert_eq!(OpenLookup::with_capacity(1 << 17).capacity(), 1 << 18);
+        assert!(OpenLookup::with_capacity(100_000).fits(70_000));
+        assert!(!OpenLookup::with_capacity(100_000).fits(200_000));
+    }
+
+    #[test]
+    fn wide_table_finds_every_entry() {
+        let n = 200_000usize;
+        let mut table = OpenLookup::with_capacity(n);
+        let key = |i: usize| {
+            let x = (i as u32).wrapping_mul(2_654_435_761);
+            (x as u16, (x >> 16) as u16)
+        };
+        for i in 0..n {
+            let (sum1, sum2) = key(i);
+            table.insert(sum1, sum2, i as u32);
+        }
+        assert_eq!(table.len() as usize, n);
+        for i in 0..n {
+            let (sum1, sum2) = key(i);
+            assert!(
+                table.find_all(sum1, sum2).any(|idx| idx == i),
+                "missing {i}"
+            );
+        }
+        assert!(table.max_displacement() < 64);
+    }
+
+    #[test]
+    fn clear_resets_table() {
+        let mut table = OpenLookup::with_capacity(16);
+        table.insert(1, 2, 3);
+        table.clear();
+        assert_eq!(table.len(), 0);
+        assert_eq!(table.max_displacement(), 0);
+        assert!(table.find_all(1, 2).next().is_none());
+    }
+}
@@ -0,0 +1,127 @@
+//! Tests for the open-addressing block table used by large signatures.
+//!
+//! Pins the [`super::block_lookup`] selection contract end-to-end:
+//!
+//! - A signature past [`super::block_lookup::OPEN_ADDRESSING_MIN_BLOCKS`]
+//!   blocks is indexed with open addressing, and every block still resolves
+//!   through the generator and `apply_delta`.
+//! - [`super::DeltaSignatureIndex::rebuild`] switches layout when the next
+//!   segment crosses the threshold in either direction.
+
+use std::io::Cursor;
+use std::num::{NonZeroU8, NonZeroU32};
+
+use protocol::ProtocolVersion;
+use signature::{
+    FileSignature, SignatureAlgorithm, SignatureLayoutParams, calculate_signature_layout,
+    generate_file_signature,
+};
+
+use super::DeltaSignatureIndex;
+use super::block_lookup::OPEN_ADDRESSING_MIN_BLOCKS;
+use crate::generator::DeltaGenerator;
+use crate::script::{DeltaToken, apply_delta};
+
+const TEST_BLOCK_LENGTH: u32 = 128;
+const TEST_STRONG_LEN: u8 = 16;
+
+fn signature_for(basis: &[u8]) -> FileSignature {
+    let params = SignatureLayoutParams::new(
+        basis.len() as u64,
+        Some(NonZeroU32::new(TEST_BLOCK_LENGTH).unwrap()),
+        ProtocolVersion::NEWEST,
+        NonZeroU8::new(TEST_STRONG_LEN).unwrap(),
+    );
+    let layout = calculate_signature_layout(params).expect("layout");
+    generate_file_signature(basis, layout, SignatureAlgorithm::Md4).expect("signature")
+}
+
+/// Pseudo-random basis so every block carries distinct content.
+fn random_basis(n_blocks: usize, seed: u64) -> Vec<u8> {
+    let mut state = seed;
+    (0..n_blocks * TEST_BLOCK_LENGTH as usize)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 24) as u8
+        })
+        .collect()
+}
+
+#[test]
+fn large_signature_uses_open_addressing() {
+    let n_blocks = OPEN_ADDRESSING_MIN_BLOCKS + 1_000;
+    let basis = random_basis(n_blocks, 0x0A11_0CA7);
+    let index =
+        DeltaSignatureIndex::from_signature(&signature_for(&basis), SignatureAlgorithm::Md4)
+            .expect("index");
+
+    let stats = index.lookup_stats();
+    assert!(stats.open_addressing);
+    assert_eq!(stats.entries, n_blocks);
+    assert!(stats.slots >= 2 * n_blocks);
+    assert!(stats.slots.is_power_of_two());
+    assert!(stats.longest_probe >= 1);
+
+    let script = DeltaGenerator::new()
+        .generate(Cursor::new(basis.as_slice()), &index)
+        .expect("delta generation");
+    assert_eq!(script.literal_bytes(), 0);
+    let copied: usize = script
+        .tokens()
+        .iter()
+        .map(|token| match token {
+            DeltaToken::Copy { len, .. } => *len,
+            DeltaToken::Literal(_) => 0,
+        })
+        .sum();
+    assert_eq!(copied, basis.len());
+
+    let mut reconstructed = Vec::with_capacity(basis.len());
+    apply_delta(
+        Cursor::new(basis.as_slice()),
+        &mut reconstructed,
+        &index,
+        &script,
+    )
+    .expect("apply_delta");
+    assert_eq!(reconstructed, basis);
+}
+
+#[test]
+fn small_signature_keeps_chained_buckets() {
+    let basis = random_basis(64, 7);
+    let index =
+        DeltaSignatureIndex::from_signature(&signature_for(&basis), SignatureAlgorithm::Md4)
+            .expect("index");
+    let stats = index.lookup_stats();
+    assert!(!stats.open_addressing);
+    assert_eq!(stats.entries, 64);
+    assert_eq!(stats.slots, 128);
+}
+
+#[test]
+fn rebuild_switches_layout_across_threshold() {
+    let small = random_basis(32, 11);
+    let mut index =
+        DeltaSignatureIndex::from_signature(&signature_for(&small), SignatureAlgorithm::Md4)
+            .expect("index");
+    assert!(!index.lookup_stats().open_addressing);
+
+    let large = random_basis(OPEN_ADDRESSING_MIN_BLOCKS + 1, 13);
+    assert!(index.rebuild(&signature_for(&large), SignatureAlgorithm::Md4));
+    assert!(index.lookup_stats().open_addressing);
+    let last = index.block_count() - 1;
+    let offset = last * index.block_length();
+    let window = &large[offset..offset + index.block_length()];
+    assert_eq!(
+        index.find_match_bytes(index.block(last).rolling(), window),
+        Some(last)
+    );
+
+    assert!(index.rebuild(&signature_for(&small), SignatureAlgorithm::Md4));
+    let stats = index.lookup_stats();
+    assert!(!stats.open_addressing);
+    assert_eq!(stats.entries, 32);
+}
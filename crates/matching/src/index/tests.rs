@@ -491,3 +491,85 @@ fn extend_run_rejects_short_target() {
 
     assert_eq!(index.extend_run(0, &target, 4), 0);
 }
+
+/// `aligned_match_bytes` counts only block-aligned windows that match.
+#[test]
+fn aligned_match_bytes_counts_aligned_matching_windows() {
+    let data: Vec<u8> = (0..16384u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+        .collect();
+    let index = build_index_for_extend_run(&data);
+    let block_len = index.block_length();
+
+    assert_eq!(
+        index.aligned_match_bytes(&data),
+        (data.len() / block_len * block_len) as u64
+    );
+
+    let mut edited = data.clone();
+    edited[0] ^= 0xFF;
+    assert_eq!(
+        index.aligned_match_bytes(&edited),
+        ((data.len() / block_len - 1) * block_len) as u64
+    );
+
+    // A one-byte shift misaligns every window.
+    let shifted: Vec<u8> = std::iter::once(0u8).chain(data.iter().copied()).collect();
+    assert_eq!(index.aligned_match_bytes(&shifted), 0);
+}
+
+/// The streaming estimate agrees with the slice form, including a short tail.
+#[test]
+fn aligned_match_bytes_from_matches_slice_form() {
+    let data: Vec<u8> = (0..10_000u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+        .collect();
+    let index = build_index_for_extend_run(&data);
+
+    let mut edited = data.clone();
+    edited[index.block_length() + 3] ^= 0xFF;
+    for source in [&data, &edited] {
+        assert_eq!(
+            index
+                .aligned_match_bytes_from(source.as_slice())
+                .expect("read"),
+            index.aligned_match_bytes(source)
+        );
+    }
+}
+
+/// The estimate ignores blocks an earlier scan marked consumed.
+#[test]
+fn aligned_match_bytes_ignores_consumed_blocks() {
+    let data: Vec<u8> = (0..8192).map(|i| ((i * 13 + 5) % 251) as u8).collect();
+    let index = build_index_for_extend_run(&data);
+    let before = index.aligned_match_bytes(&data);
+    for idx in 0..index.block_count() {
+        index.mark_consumed(idx as u32);
+    }
+    assert_eq!(index.aligned_match_bytes(&data), before);
+}
+
+/// `first_prefilter_hit` admits every indexed block checksum, so the first
+/// block-aligned value in a batch is reported even behind misses that the
+/// tag table rejects.
+#[test]
+fn first_prefilter_hit_finds_indexed_checksum() {
+    let data: Vec<u8> = (0..8192u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+        .collect();
+    let index = build_index_for_extend_run(&data);
+    let block_len = index.block_length();
+    let indexed = RollingDigest::from_bytes(&data[block_len..2 * block_len]).value();
+
+    let miss = (0..=u16::MAX)
+        .find(|&sum1| !index.tag_admits(sum1))
+        .map(u32::from)
+        .expect("tag table has a free slot");
+    assert_eq!(
+        index.first_prefilter_hit(&[miss, miss, indexed, miss]),
+        Some(2)
+    );
+    assert_eq!(index.first_prefilter_hit(&[miss; 8]), None);
+    assert_eq!(index.first_prefilter_hit(&[]), None);
+}
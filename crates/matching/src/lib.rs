@@ -34,9 +34,9 @@ pub use fuzzy::{
     FUZZY_LEVEL_1, FUZZY_LEVEL_2, FuzzyMatch, FuzzyMatcher, trace_fuzzy_basis_selected,
     trace_fuzzy_distance, trace_fuzzy_size_mtime_match,
 };
-pub use generator::{DeltaGenerator, generate_delta};
+pub use generator::{BestBasisDelta, DeltaGenerator, MMAP_INPUT_THRESHOLD, generate_delta};
 pub use index::{
-    DeltaSignatureIndex, HASH_KEY_BITS, HashtableRole, MatchedBlocks, trace_hashtable_created,
-    trace_hashtable_destroyed, trace_hashtable_growing,
+    DeltaSignatureIndex, HASH_KEY_BITS, HashtableRole, LookupStats, MatchedBlocks,
+    trace_hashtable_created, trace_hashtable_destroyed, trace_hashtable_growing,
 };
 pub use script::{DeltaScript, DeltaToken, apply_delta};
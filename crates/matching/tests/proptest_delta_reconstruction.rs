@@ -0,0 +1,283 @@
+//! Property-based signature -> delta -> apply round trips.
+//!
+//! Every case builds a basis and a target, generates the basis signature with
+//! a randomly negotiated layout (protocol version, forced or derived block
+//! length, phase checksum length, strong checksum algorithm), computes the
+//! delta of the target against that signature, and applies it back onto the
+//! basis. Reconstruction must be byte-identical whatever the layout; any
+//! divergence is a wire-compat correctness bug.
+//!
+//! Targets are derived from the basis by the edits that stress the rolling
+//! search at block boundaries: insertions and deletions a few bytes either
+//! side of a block edge, overwrites straddling an edge, block-sized duplicate
+//! runs, truncation to a partial trailing block, and fully unrelated data.
+//!
+//! The parallel scan ([`DeltaGenerator::generate_chunked`]) and small input
+//! buffers take the same properties, since both change where windows are
+//! split without being allowed to change what they reconstruct.
+//!
+//! # Upstream Reference
+//!
+//! - `generator.c:697` - `sum_sizes_sqroot()` block and strong-sum sizing
+//! - `match.c:362` - `match_sums()` delta generation
+//! - `receiver.c` - `receive_data()` delta application
+
+use checksums::strong::Md5Seed;
+use matching::{DeltaGenerator, DeltaScript, DeltaSignatureIndex, apply_delta, generate_delta};
+use proptest::prelude::*;
+use protocol::ProtocolVersion;
+use signature::{
+    MAX_SUM_LENGTH, SHORT_SUM_LENGTH, SignatureAlgorithm, SignatureLayoutParams,
+    calculate_signature_layout, generate_file_signature,
+};
+use std::io::Cursor;
+use std::num::{NonZeroU8, NonZeroU32};
+
+/// Signature parameters a sender and receiver could have negotiated.
+#[derive(Clone, Copy, Debug)]
+struct Negotiated {
+    protocol: ProtocolVersion,
+    block_length: Option<NonZeroU32>,
+    checksum_length: NonZeroU8,
+    algorithm: SignatureAlgorithm,
+}
+
+/// Edit applied to the basis to produce the target.
+#[derive(Clone, Debug)]
+enum Edit {
+    /// Inserts `bytes` at `block * block_len + skew`.
+    Insert {
+        block: usize,
+        skew: i8,
+        bytes: Vec<u8>,
+    },
+    /// Deletes `len` bytes starting at `block * block_len + skew`.
+    Delete { block: usize, skew: i8, len: usize },
+    /// Overwrites bytes straddling the end of `block`.
+    Overwrite { block: usize, bytes: Vec<u8> },
+    /// Appends another copy of `block`, keeping its alignment.
+    DuplicateBlock { block: usize },
+    /// Truncates to `blocks` whole blocks plus `skew` bytes.
+    Truncate { blocks: usize, skew: i8 },
+    /// Replaces the target with unrelated bytes.
+    Replace { bytes: Vec<u8> },
+}
+
+fn algorithm_strategy() -> impl Strategy<Value = SignatureAlgorithm> {
+    prop_oneof![
+        Just(SignatureAlgorithm::Md4),
+        any::<i32>().prop_map(|seed| SignatureAlgorithm::Md4Seeded { seed }),
+        (any::<i32>(), any::<bool>()).prop_map(|(seed, proper)| SignatureAlgorithm::Md5 {
+            seed_config: if proper {
+                Md5Seed::proper(seed)
+            } else {
+                Md5Seed::legacy(seed)
+            },
+        }),
+        Just(SignatureAlgorithm::Md5 {
+            seed_config: Md5Seed::none(),
+        }),
+        Just(SignatureAlgorithm::Sha1),
+        any::<u64>().prop_map(|seed| SignatureAlgorithm::Xxh64 { seed }),
+        any::<u64>().prop_map(|seed| SignatureAlgorithm::Xxh3 { seed }),
+        any::<u64>().prop_map(|seed| SignatureAlgorithm::Xxh3_128 { seed }),
+    ]
+}
+
+/// Block lengths: derived from the file size, tiny (including 1), the
+/// upstream default, and powers of two that line up with buffer refills.
+fn block_length_strategy() -> impl Strategy<Value = Option<NonZeroU32>> {
+    prop_oneof![
+        Just(None),
+        (1u32..=64).prop_map(NonZeroU32::new),
+        prop::sample::select(vec![127u32, 128, 700, 1024, 2048]).prop_map(NonZeroU32::new),
+    ]
+}
+
+fn negotiated_strategy() -> impl Strategy<Value = Negotiated> {
+    (
+        prop::sample::select(vec![
+            ProtocolVersion::V28,
+            ProtocolVersion::V29,
+            ProtocolVersion::V30,
+            ProtocolVersion::V31,
+            ProtocolVersion::V32,
+        ]),
+        block_length_strategy(),
+        SHORT_SUM_LENGTH..=MAX_SUM_LENGTH,
+        algorithm_strategy(),
+    )
+        .prop_map(
+            |(protocol, block_length, checksum_length, algorithm)| Negotiated {
+                protocol,
+                block_length,
+                checksum_length: NonZeroU8::new(checksum_length).unwrap(),
+                algorithm,
+            },
+        )
+}
+
+fn edit_strategy() -> impl Strategy<Value = Edit> {
+    let skew = -3i8..=3;
+    let bytes = prop::collection::vec(any::<u8>(), 1..=96);
+    prop_oneof![
+        (0usize..16, skew.clone(), bytes.clone()).prop_map(|(block, skew, bytes)| Edit::Insert {
+            block,
+            skew,
+            bytes
+        }),
+        (0usize..16, skew.clone(), 1usize..=2100).prop_map(|(block, skew, len)| Edit::Delete {
+            block,
+            skew,
+            len
+        }),
+        (0usize..16, bytes.clone()).prop_map(|(block, bytes)| Edit::Overwrite { block, bytes }),
+        (0usize..16).prop_map(|block| Edit::DuplicateBlock { block }),
+        (0usize..16, skew).prop_map(|(blocks, skew)| Edit::Truncate { blocks, skew }),
+        prop::collection::vec(any::<u8>(), 0..=4096).prop_map(|bytes| Edit::Replace { bytes }),
+    ]
+}
+
+/// Basis bytes: random, or a short random pattern repeated so that many
+/// blocks share identical content.
+fn basis_strategy() -> impl Strategy<Value = Vec<u8>> {
+    prop_oneof![
+        prop::collection::vec(any::<u8>(), 0..=12_000),
+        (prop::collection::vec(any::<u8>(), 1..=8), 0usize..=12_000)
+            .prop_map(|(pattern, len)| pattern.iter().copied().cycle().take(len).collect()),
+    ]
+}
+
+/// Clamps `block * block_len + skew` into `0..=len`.
+fn edge(block: usize, skew: i8, block_len: usize, len: usize) -> usize {
+    (block * block_len)
+        .saturating_add_signed(isize::from(skew))
+        .min(len)
+}
+
+fn apply_edits(basis: &[u8], edits: &[Edit], block_len: usize) -> Vec<u8> {
+    let mut target = basis.to_vec();
+    for edit in edits {
+        match edit {
+            Edit::Insert { block, skew, bytes } => {
+                let at = edge(*block, *skew, block_len, target.len());
+                target.splice(at..at, bytes.iter().copied());
+            }
+            Edit::Delete { block, skew, len } => {
+                let at = edge(*block, *skew, block_len, target.len());
+                let end = at.saturating_add(*len).min(target.len());
+                target.drain(at..end);
+            }
+            Edit::Overwrite { block, bytes } => {
+                let end = edge(block + 1, 0, block_len, target.len());
+                let at = end.saturating_sub(bytes.len() / 2);
+                for (slot, byte) in target[at..].iter_mut().zip(bytes) {
+                    *slot = byte.wrapping_add(1);
+                }
+            }
+            Edit::DuplicateBlock { block } => {
+                let start = edge(*block, 0, block_len, basis.len());
+                let end = edge(block + 1, 0, block_len, basis.len());
+                target.extend_from_slice(&basis[start..end]);
+            }
+            Edit::Truncate { blocks, skew } => {
+                let at = edge(*blocks, *skew, block_len, target.len());
+                target.truncate(at);
+            }
+            Edit::Replace { bytes } => target.clone_from(bytes),
+        }
+    }
+    target
+}
+
+/// Builds the basis index for `negotiated`, or `None` when the basis has no
+/// full block to index.
+fn build_index(basis: &[u8], negotiated: Negotiated) -> Option<DeltaSignatureIndex> {
+    let digest_len = u8::try_from(negotiated.algorithm.digest_len()).unwrap();
+    let params = SignatureLayoutParams::new(
+        basis.len() as u64,
+        negotiated.block_length,
+        negotiated.protocol,
+        negotiated.checksum_length,
+    )
+    .with_transfer_digest_length(NonZeroU8::new(digest_len).unwrap());
+    let layout = calculate_signature_layout(params).expect("layout");
+    let signature =
+        generate_file_signature(basis, layout, negotiated.algorithm).expect("signature");
+    DeltaSignatureIndex::from_signature(&signature, negotiated.algorithm)
+}
+
+fn reconstruct(basis: &[u8], index: &DeltaSignatureIndex, script: &DeltaScript) -> Vec<u8> {
+    let mut output = Vec::with_capacity(script.total_bytes() as usize);
+    apply_delta(Cursor::new(basis), &mut output, index, script).expect("apply");
+    output
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(256))]
+
+    /// Applying the generated delta reproduces the target exactly.
+    #[test]
+    fn delta_reconstructs_target(
+        basis in basis_strategy(),
+        edits in prop::collection::vec(edit_strategy(), 0..=4),
+        negotiated in negotiated_strategy(),
+    ) {
+        let Some(index) = build_index(&basis, negotiated) else {
+            return Ok(());
+        };
+        let target = apply_edits(&basis, &edits, index.block_length());
+
+        let script = generate_delta(&target[..], &index).expect("delta");
+        prop_assert_eq!(script.total_bytes(), target.len() as u64);
+        prop_assert_eq!(script.literal_bytes() + script.copy_bytes(), target.len() as u64);
+        prop_assert_eq!(reconstruct(&basis, &index, &script), target);
+    }
+
+    /// An unchanged file whose length is a whole number of blocks is sent
+    /// entirely as block copies.
+    #[test]
+    fn identical_aligned_file_needs_no_literals(
+        blocks in 1usize..=12,
+        block_length in 1u32..=1024,
+        seed in any::<u64>(),
+        mut negotiated in negotiated_strategy(),
+    ) {
+        negotiated.block_length = NonZeroU32::new(block_length);
+        let basis: Vec<u8> = (0..blocks * block_length as usize)
+            .map(|i| (seed.rotate_left(i as u32 % 64) ^ i as u64) as u8)
+            .collect();
+        let index = build_index(&basis, negotiated).expect("index");
+
+        let script = generate_delta(&basis[..], &index).expect("delta");
+        prop_assert_eq!(script.literal_bytes(), 0);
+        prop_assert_eq!(reconstruct(&basis, &index, &script), basis);
+    }
+
+    /// Small read buffers and the parallel range split reconstruct the
+    /// same target as the default sequential scan.
+    #[test]
+    fn buffer_and_stripe_splits_reconstruct_target(
+        basis in basis_strategy(),
+        edits in prop::collection::vec(edit_strategy(), 1..=3),
+        negotiated in negotiated_strategy(),
+        buffer_len in 1usize..=4096,
+        stripes in 1usize..=8,
+    ) {
+        let Some(index) = build_index(&basis, negotiated) else {
+            return Ok(());
+        };
+        let target = apply_edits(&basis, &edits, index.block_length());
+
+        let buffered = DeltaGenerator::new()
+            .with_buffer_len(buffer_len)
+            .generate(&target[..], &index)
+            .expect("buffered delta");
+        prop_assert_eq!(reconstruct(&basis, &index, &buffered), target.clone());
+
+        let striped = DeltaGenerator::new()
+            .generate_chunked(&target, &index, stripes)
+            .expect("striped delta");
+        prop_assert_eq!(reconstruct(&basis, &index, &striped), target);
+    }
+}
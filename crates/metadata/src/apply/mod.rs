@@ -19,6 +19,41 @@ use std::fs;
 use std::os::fd::BorrowedFd;
 use std::path::Path;
 
+/// Clears an immutable destination's flags while its metadata is updated and
+/// restores them when the update fails.
+///
+/// A successful update ends with the source's flags applied, so
+/// [`close`](Self::close) keeps those instead.
+// upstream: patches/fileflags.diff - set_file_attrs() wraps the chown and
+// chmod in make_mutable() / undo_make_mutable().
+struct MutableWindow<'a> {
+    path: &'a Path,
+    saved: Option<u32>,
+}
+
+impl<'a> MutableWindow<'a> {
+    fn open(path: &'a Path, options: &MetadataOptions) -> Result<Self, MetadataError> {
+        let saved = if options.fileflags() {
+            crate::make_mutable(path)?
+        } else {
+            None
+        };
+        Ok(Self { path, saved })
+    }
+
+    fn close(mut self) {
+        self.saved = None;
+    }
+}
+
+impl Drop for MutableWindow<'_> {
+    fn drop(&mut self) {
+        if let Some(flags) = self.saved.take() {
+            let _ = crate::undo_make_mutable(self.path, flags);
+        }
+    }
+}
+
 /// Applies metadata from `metadata` to the destination directory.
 ///
 /// Preserves permission bits (best-effort on non-Unix targets) and
@@ -42,6 +77,7 @@ pub fn apply_directory_metadata_with_options(
     metadata: &fs::Metadata,
     options: MetadataOptions,
 ) -> Result<(), MetadataError> {
+    let window = MutableWindow::open(destination, &options)?;
     ownership::set_owner_like(metadata, destination, true, &options, None)?;
     permissions::apply_permissions_with_chmod(destination, metadata, &options, None)?;
     if options.times() {
@@ -49,6 +85,10 @@ pub fn apply_directory_metadata_with_options(
     }
     // upstream: rsync.c:589 - directories skip atime (`ATTRS_SKIP_ATIME`)
     // regardless of `--atimes`; only files get atime preservation.
+    if options.fileflags() {
+        crate::set_file_flags(destination, crate::file_flags(metadata))?;
+    }
+    window.close();
     Ok(())
 }
 
@@ -67,14 +107,15 @@ pub fn apply_file_metadata(
 
 /// Applies file metadata using explicit [`MetadataOptions`].
 ///
-/// Applies ownership, permissions, timestamps, and creation time in the
-/// same order as upstream rsync's `set_file_attrs()`.
+/// Applies ownership, permissions, timestamps, creation time, and file flags
+/// in the same order as upstream rsync's `set_file_attrs()`.
 // upstream: rsync.c:set_file_attrs() - order: chown → chmod → utimensat → crtime
 pub fn apply_file_metadata_with_options(
     destination: &Path,
     metadata: &fs::Metadata,
     options: &MetadataOptions,
 ) -> Result<(), MetadataError> {
+    let window = MutableWindow::open(destination, options)?;
     ownership::set_owner_like(metadata, destination, true, options, None)?;
     permissions::apply_permissions_with_chmod(destination, metadata, options, None)?;
     // upstream: rsync.c:587-612 - mtime and atime are handled independently
@@ -92,6 +133,12 @@ pub fn apply_file_metadata_with_options(
     if options.crtimes() {
         timestamps::apply_crtime_from_source_metadata(destination, metadata)?;
     }
+    // upstream: patches/fileflags.diff - flags go last so an immutable flag
+    // does not block the updates above.
+    if options.fileflags() {
+        crate::set_file_flags(destination, crate::file_flags(metadata))?;
+    }
+    window.close();
     Ok(())
 }
 
@@ -155,6 +202,7 @@ pub fn apply_file_metadata_with_fd(
     options: &MetadataOptions,
     fd: BorrowedFd<'_>,
 ) -> Result<(), MetadataError> {
+    let window = MutableWindow::open(destination, options)?;
     ownership::set_owner_like_with_fd(metadata, destination, options, fd, None)?;
     permissions::apply_permissions_with_chmod_fd(destination, metadata, options, Some(fd), None)?;
     // upstream: rsync.c:587-612 - mtime and atime are handled independently
@@ -167,6 +215,12 @@ pub fn apply_file_metadata_with_fd(
     if options.crtimes() {
         timestamps::apply_crtime_from_source_metadata(destination, metadata)?;
     }
+    // upstream: patches/fileflags.diff - flags go last so an immutable flag
+    // does not block the updates above.
+    if options.fileflags() {
+        crate::set_file_flags(destination, crate::file_flags(metadata))?;
+    }
+    window.close();
     Ok(())
 }
 
@@ -182,6 +236,7 @@ pub fn apply_file_metadata_if_changed(
     existing: &fs::Metadata,
     options: &MetadataOptions,
 ) -> Result<(), MetadataError> {
+    let window = MutableWindow::open(destination, options)?;
     let restat_after_chown =
         ownership::set_owner_like(metadata, destination, true, options, Some(existing))?;
     // upstream: rsync.c:564-567 - the chown may have cleared setuid/setgid bits,
@@ -210,6 +265,12 @@ pub fn apply_file_metadata_if_changed(
     if options.crtimes() {
         timestamps::apply_crtime_from_source_metadata(destination, metadata)?;
     }
+    // upstream: patches/fileflags.diff - flags go last so an immutable flag
+    // does not block the updates above.
+    if options.fileflags() {
+        crate::set_file_flags(destination, crate::file_flags(metadata))?;
+    }
+    window.close();
     Ok(())
 }
 
@@ -226,6 +287,7 @@ pub fn apply_file_metadata_with_fd_if_changed(
     options: &MetadataOptions,
     fd: BorrowedFd<'_>,
 ) -> Result<(), MetadataError> {
+    let window = MutableWindow::open(destination, options)?;
     let restat_after_chown =
         ownership::set_owner_like_with_fd(metadata, destination, options, fd, Some(existing))?;
     // upstream: rsync.c:564-567 - the chown may have cleared setuid/setgid bits,
@@ -267,6 +329,12 @@ pub fn apply_file_metadata_with_fd_if_changed(
     if options.crtimes() {
         timestamps::apply_crtime_from_source_metadata(destination, metadata)?;
     }
+    // upstream: patches/fileflags.diff - flags go last so an immutable flag
+    // does not block the updates above.
+    if options.fileflags() {
+        crate::set_file_flags(destination, crate::file_flags(metadata))?;
+    }
+    window.close();
     Ok(())
 }
 
@@ -357,6 +425,15 @@ pub fn metadata_unchanged(
         }
     }
 
+    // upstream: patches/fileflags.diff - unchanged_attrs() also compares the
+    // file flags word.
+    if options.fileflags()
+        && crate::FILEFLAGS_SUPPORTED
+        && crate::file_flags(cached_meta) != entry.fileflags()
+    {
+        return false;
+    }
+
     // upstream: generator.c:495-502 - chmod modifiers applied on top of the
     // entry's mode. Evaluate the modifier against the current stat and only
     // fall through to the full apply path when the result would differ.
@@ -604,6 +681,13 @@ pub fn apply_metadata_with_attrs_flags_and_pre_transfer(
     attrs_flags: AttrsFlags,
     pre_transfer_meta: Option<fs::Metadata>,
 ) -> Result<(), MetadataError> {
+    // Windows: deep trees and reserved names (`CON`, `NUL`) need the `\\?\` form.
+    #[cfg(windows)]
+    let native = fast_io::to_native_path(destination);
+    #[cfg(windows)]
+    let destination = native.as_ref();
+
+    let window = MutableWindow::open(destination, options)?;
     let restat_after_chown =
         ownership::apply_ownership_from_entry(destination, entry, options, cached_meta.as_ref())?;
 
@@ -644,5 +728,12 @@ pub fn apply_metadata_with_attrs_flags_and_pre_transfer(
         timestamps::apply_crtime_from_entry(destination, entry)?;
     }
 
+    // upstream: patches/fileflags.diff - flags go last so an immutable flag
+    // does not block the updates above.
+    if options.fileflags() {
+        crate::set_file_flags(destination, entry.fileflags())?;
+    }
+    window.close();
+
     Ok(())
 }
@@ -0,0 +1,260 @@
+//! BSD file flag (`chflags`) preservation for `--fileflags`.
+//!
+//! macOS and FreeBSD attach a word of flags to every inode (`st_flags`):
+//! `uchg`/`schg` (immutable), `uappnd`, `nodump`, `hidden`, and friends.
+//! `--fileflags` is an oc-rsync extension that carries this word in the file
+//! list, like the rsync-patches `fileflags.diff`, and applies it with
+//! `lchflags(2)` after every other attribute so an immutable flag cannot block
+//! the chmod, chown, or utimes that precede it.
+//!
+//! An immutable or append-only flag already on the destination also blocks
+//! the rename that replaces it, the chmod and chown that update it, and the
+//! unlink that deletes it. [`make_mutable`] clears those flags first and
+//! [`undo_make_mutable`] puts them back when the operation fails.
+//!
+//! Other platforms have no file flags: [`file_flags`] reports `0`,
+//! [`set_file_flags`] does nothing and [`make_mutable`] never clears anything,
+//! so a flags word received from a BSD peer is dropped rather than failing the
+//! transfer.
+//!
+//! # Upstream Reference
+//!
+//! - `patches/fileflags.diff` - `F_FFLAGS()`, `XMIT_SAME_FLAGS`, and the
+//!   `set_file_attrs()` call to `set_fileflags()` after the times are set
+//! - `patches/fileflags.diff` - `make_mutable()` / `undo_make_mutable()`
+//!   around `set_file_attrs()`, `do_rename()` and `delete_item()`
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::error::MetadataError;
+
+/// Whether this platform can read and set file flags.
+pub const FILEFLAGS_SUPPORTED: bool = cfg!(any(target_os = "macos", target_os = "freebsd"));
+
+/// The flags that stop an inode from being renamed over, chmodded, chowned
+/// or unlinked: `uchg`, `uappnd`, `schg` and `sappnd`, plus FreeBSD's
+/// `uunlnk` and `sunlnk`.
+///
+/// upstream: patches/fileflags.diff - `ALL_IMMUTABLE`.
+pub const IMMUTABLE_FLAGS: u32 = UF_IMMUTABLE | UF_APPEND | SF_IMMUTABLE | SF_APPEND | NOUNLINK;
+
+// Values from <sys/stat.h>, shared by macOS and FreeBSD.
+const UF_IMMUTABLE: u32 = 0x0000_0002;
+const UF_APPEND: u32 = 0x0000_0004;
+const SF_IMMUTABLE: u32 = 0x0002_0000;
+const SF_APPEND: u32 = 0x0004_0000;
+/// FreeBSD's `UF_NOUNLINK | SF_NOUNLINK`; macOS has no such flags.
+const NOUNLINK: u32 = if cfg!(target_os = "freebsd") {
+    0x0000_0010 | 0x0010_0000
+} else {
+    0
+};
+
+/// Returns the file flags (`st_flags`) recorded in `metadata`, or `0` on
+/// platforms without file flags.
+#[must_use]
+pub fn file_flags(metadata: &fs::Metadata) -> u32 {
+    #[cfg(target_os = "macos")]
+    {
+        use std::os::macos::fs::MetadataExt;
+        metadata.st_flags()
+    }
+    #[cfg(target_os = "freebsd")]
+    {
+        use std::os::freebsd::fs::MetadataExt;
+        metadata.st_flags()
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "freebsd")))]
+    {
+        let _ = metadata;
+        0
+    }
+}
+
+/// Sets the file flags of `path` to `flags` without following a final
+/// symlink.
+///
+/// The current flags are read first and the `lchflags(2)` call is skipped when
+/// they already match, so unchanged files cost no extra syscall beyond the
+/// stat. A no-op on platforms without file flags.
+///
+/// # Errors
+///
+/// Returns a [`MetadataError`] when the destination cannot be inspected or
+/// `lchflags(2)` fails, for example when setting a system flag without
+/// privileges.
+#[cfg(any(target_os = "macos", target_os = "freebsd"))]
+#[allow(unsafe_code)] // REASON: lchflags(2) has no safe wrapper in nix or std.
+pub fn set_file_flags(path: &Path, flags: u32) -> Result<(), MetadataError> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let existing = fs::symlink_metadata(path)
+        .map_err(|error| MetadataError::new("inspect file flags", path, error))?;
+    if file_flags(&existing) == flags {
+        return Ok(());
+    }
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).map_err(|_| {
+        MetadataError::new(
+            "set file flags",
+            path,
+            io::Error::new(io::ErrorKind::InvalidInput, "path contains NUL byte"),
+        )
+    })?;
+    // SAFETY: `c_path` is a valid NUL-terminated C string that outlives the
+    // call, and `lchflags(2)` only reads it.
+    let ret = unsafe { libc::lchflags(c_path.as_ptr(), flags as _) };
+    if ret != 0 {
+        return Err(MetadataError::new(
+            "set file flags",
+            path,
+            io::Error::last_os_error(),
+        ));
+    }
+    Ok(())
+}
+
+/// No-op on platforms without file flags.
+///
+/// # Errors
+///
+/// Never fails.
+#[cfg(not(any(target_os = "macos", target_os = "freebsd")))]
+pub fn set_file_flags(_path: &Path, _flags: u32) -> Result<(), MetadataError> {
+    Ok(())
+}
+
+/// Clears the [`IMMUTABLE_FLAGS`] on `path` so it can be replaced, updated
+/// or deleted, without following a final symlink.
+///
+/// Returns the flags word `path` had when something was cleared, for
+/// [`undo_make_mutable`], and `None` when nothing needed clearing: `path`
+/// does not exist, carries none of those flags, or the platform has no file
+/// flags.
+///
+/// # Errors
+///
+/// Returns a [`MetadataError`] when `path` cannot be inspected or its flags
+/// cannot be changed, for example a system flag without privileges.
+pub fn make_mutable(path: &Path) -> Result<Option<u32>, MetadataError> {
+    if !FILEFLAGS_SUPPORTED {
+        return Ok(None);
+    }
+    let existing = match fs::symlink_metadata(path) {
+        Ok(existing) => existing,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(MetadataError::new("inspect file flags", path, error)),
+    };
+    let flags = file_flags(&existing);
+    if flags & IMMUTABLE_FLAGS == 0 {
+        return Ok(None);
+    }
+    set_file_flags(path, flags & !IMMUTABLE_FLAGS)?;
+    Ok(Some(flags))
+}
+
+/// Restores the flags word [`make_mutable`] returned for `path`.
+///
+/// # Errors
+///
+/// Returns a [`MetadataError`] when the flags cannot be set.
+pub fn undo_make_mutable(path: &Path, flags: u32) -> Result<(), MetadataError> {
+    set_file_flags(path, flags)
+}
+
+/// Runs `op`, which renames over or removes `path`, with the
+/// [`IMMUTABLE_FLAGS`] on `path` cleared, and restores them when `op` fails.
+///
+/// A successful `op` leaves nothing to restore: the old inode is gone, and a
+/// replacement receives its own flags when its metadata is applied.
+///
+/// # Errors
+///
+/// Returns the error of [`make_mutable`] or of `op`.
+pub fn while_mutable<T>(path: &Path, op: impl FnOnce() -> io::Result<T>) -> io::Result<T> {
+    let saved = make_mutable(path).map_err(|error| error.into_parts().2)?;
+    let result = op();
+    if result.is_err()
+        && let Some(flags) = saved
+    {
+        let _ = undo_make_mutable(path, flags);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(any(target_os = "macos", target_os = "freebsd")))]
+    #[test]
+    fn unsupported_platform_reports_no_flags_and_ignores_sets() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file");
+        fs::write(&path, b"data").unwrap();
+
+        assert!(!FILEFLAGS_SUPPORTED);
+        assert_eq!(file_flags(&fs::metadata(&path).unwrap()), 0);
+        set_file_flags(&path, 0x8000).unwrap();
+        assert_eq!(make_mutable(&path).unwrap(), None);
+        assert_eq!(make_mutable(&dir.path().join("missing")).unwrap(), None);
+        while_mutable(&path, || fs::remove_file(&path)).unwrap();
+        assert!(!path.exists());
+    }
+
+    #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+    #[test]
+    fn user_flags_round_trip() {
+        // UF_NODUMP is settable by the owner and does not block cleanup.
+        const UF_NODUMP: u32 = 0x0000_0001;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file");
+        fs::write(&path, b"data").unwrap();
+
+        set_file_flags(&path, UF_NODUMP).unwrap();
+        assert_eq!(file_flags(&fs::metadata(&path).unwrap()), UF_NODUMP);
+
+        set_file_flags(&path, 0).unwrap();
+        assert_eq!(file_flags(&fs::metadata(&path).unwrap()), 0);
+    }
+
+    #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+    #[test]
+    fn make_mutable_clears_and_restores_user_immutable() {
+        const UF_NODUMP: u32 = 0x0000_0001;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file");
+        fs::write(&path, b"data").unwrap();
+        assert_eq!(make_mutable(&path).unwrap(), None);
+
+        set_file_flags(&path, UF_IMMUTABLE | UF_NODUMP).unwrap();
+        let saved = make_mutable(&path).unwrap();
+        assert_eq!(saved, Some(UF_IMMUTABLE | UF_NODUMP));
+        assert_eq!(file_flags(&fs::metadata(&path).unwrap()), UF_NODUMP);
+        fs::write(&path, b"changed").unwrap();
+
+        undo_make_mutable(&path, saved.unwrap()).unwrap();
+        assert_eq!(
+            file_flags(&fs::metadata(&path).unwrap()),
+            UF_IMMUTABLE | UF_NODUMP
+        );
+
+        // A failed operation puts the flags back; a successful one removes
+        // the inode they belonged to.
+        let failed = while_mutable(&path, || -> io::Result<()> {
+            Err(io::Error::other("fail"))
+        });
+        assert!(failed.is_err());
+        assert_eq!(
+            file_flags(&fs::metadata(&path).unwrap()),
+            UF_IMMUTABLE | UF_NODUMP
+        );
+        while_mutable(&path, || fs::remove_file(&path)).unwrap();
+        assert!(!path.exists());
+    }
+}
@@ -110,7 +110,7 @@ mod error;
 
 /// Signed `--modify-window` tolerance and the `same_time()` mtime comparison.
 pub mod modify_window;
-pub use modify_window::ModifyWindow;
+pub use modify_window::{ModifyWindow, is_fat_like_filesystem};
 
 /// UID/GID lookup and mapping utilities.
 pub mod id_lookup;
@@ -142,6 +142,13 @@ pub mod device_size;
 #[cfg(unix)]
 pub use device_size::device_readable_size;
 
+/// BSD file flag (`chflags`) preservation for `--fileflags`.
+pub mod fileflags;
+pub use fileflags::{
+    FILEFLAGS_SUPPORTED, IMMUTABLE_FLAGS, file_flags, make_mutable, set_file_flags,
+    undo_make_mutable, while_mutable,
+};
+
 /// Symlink munging helpers for daemon-mode security.
 ///
 /// Provides `munge_symlink` and `unmunge_symlink` which prepend or strip the
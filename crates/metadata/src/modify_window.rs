@@ -5,6 +5,13 @@
 //! destination file is already up to date. The value is deliberately signed:
 //! a negative window requests nanosecond-exact comparison rather than the
 //! default whole-second tolerance.
+//!
+//! FAT-family filesystems store mtimes with two-second granularity, so a file
+//! copied onto (or off) such a volume can never compare equal under a zero
+//! window. [`ModifyWindow::resolve`] widens the default to one second when any
+//! of the transfer's roots lives on one, unless the user chose a window.
+
+use std::path::Path;
 
 /// Signed whole-second tolerance for the mtime quick-check.
 ///
@@ -22,6 +29,12 @@ impl ModifyWindow {
     /// The default zero-second window (whole-second equality).
     pub const ZERO: Self = Self(0);
 
+    /// The window applied by default when a FAT-like filesystem is involved.
+    ///
+    /// Matches the `--modify-window=1` that upstream's manpage recommends for
+    /// FAT volumes.
+    pub const FAT: Self = Self(1);
+
     /// Builds a window from a signed whole-second count.
     ///
     /// A negative `secs` selects nanosecond-exact comparison, matching
@@ -65,11 +78,109 @@ impl ModifyWindow {
         // positive here, so the cast to u64 for `abs_diff` is lossless.
         f1_sec.abs_diff(f2_sec) <= self.0 as u64
     }
+
+    /// Resolves the window for a transfer whose roots are `paths`.
+    ///
+    /// An explicit `--modify-window` always wins. Otherwise the window is
+    /// [`ModifyWindow::FAT`] when any path sits on a FAT-like filesystem (see
+    /// [`is_fat_like_filesystem`]) and [`ModifyWindow::ZERO`] elsewhere.
+    #[must_use]
+    pub fn resolve<'a, I>(explicit: Option<i64>, paths: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Path>,
+    {
+        if let Some(secs) = explicit {
+            return Self::from_secs(secs);
+        }
+        if paths.into_iter().any(is_fat_like_filesystem) {
+            Self::FAT
+        } else {
+            Self::ZERO
+        }
+    }
+}
+
+/// Reports whether `path` lives on a FAT-family filesystem (FAT12/16/32,
+/// VFAT or exFAT), whose two-second mtime granularity defeats exact
+/// quick-check comparisons.
+///
+/// A destination that does not exist yet is classified by its nearest
+/// existing ancestor. Filesystems that cannot be queried, and platforms
+/// without a probe, report `false`.
+#[must_use]
+pub fn is_fat_like_filesystem(path: &Path) -> bool {
+    path.ancestors()
+        .filter(|candidate| !candidate.as_os_str().is_empty())
+        .find(|candidate| candidate.exists())
+        .or_else(|| path.is_relative().then_some(Path::new(".")))
+        .is_some_and(fs_probe::is_fat_like)
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod fs_probe {
+    use std::path::Path;
+
+    /// `MSDOS_SUPER_MAGIC` from `<linux/magic.h>` (msdos and vfat).
+    const MSDOS_SUPER_MAGIC: i64 = 0x4D44;
+    /// `EXFAT_SUPER_MAGIC` from `<linux/magic.h>`.
+    const EXFAT_SUPER_MAGIC: i64 = 0x2011_BAB0;
+
+    pub(super) fn is_fat_like(path: &Path) -> bool {
+        rustix::fs::statfs(path).is_ok_and(|buf| classify(buf.f_type as i64))
+    }
+
+    pub(super) fn classify(fs_type: i64) -> bool {
+        matches!(fs_type, MSDOS_SUPER_MAGIC | EXFAT_SUPER_MAGIC)
+    }
+}
+
+#[cfg(any(
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "openbsd"
+))]
+mod fs_probe {
+    use std::path::Path;
+
+    pub(super) fn is_fat_like(path: &Path) -> bool {
+        rustix::fs::statfs(path).is_ok_and(|buf| {
+            let name: Vec<u8> = buf
+                .f_fstypename
+                .iter()
+                .take_while(|&&c| c != 0)
+                .map(|&c| c as u8)
+                .collect();
+            classify(&name)
+        })
+    }
+
+    pub(super) fn classify(name: &[u8]) -> bool {
+        matches!(name, b"msdos" | b"msdosfs" | b"exfat")
+    }
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "openbsd"
+)))]
+mod fs_probe {
+    use std::path::Path;
+
+    pub(super) fn is_fat_like(_path: &Path) -> bool {
+        false
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::ModifyWindow;
+    use std::path::Path;
+
+    use super::{ModifyWindow, is_fat_like_filesystem};
 
     #[test]
     fn zero_window_compares_whole_seconds_only() {
@@ -103,4 +214,52 @@ mod tests {
         assert!(!w.same_time(100, 500, 100, 501));
         assert!(!w.same_time(100, 0, 101, 0));
     }
+
+    #[test]
+    fn explicit_window_overrides_detection() {
+        let dir = std::env::temp_dir();
+        assert_eq!(
+            ModifyWindow::resolve(Some(0), [dir.as_path()]),
+            ModifyWindow::ZERO
+        );
+        assert_eq!(
+            ModifyWindow::resolve(Some(-1), [dir.as_path()]),
+            ModifyWindow::from_secs(-1)
+        );
+    }
+
+    #[test]
+    fn missing_path_is_classified_by_existing_ancestor() {
+        // Why: the destination root is often created by the transfer itself,
+        // so the probe must fall back to the parent's filesystem instead of
+        // giving up.
+        let dir = std::env::temp_dir();
+        let missing = dir.join("modify-window-probe-missing").join("deeper");
+        assert_eq!(
+            is_fat_like_filesystem(&missing),
+            is_fat_like_filesystem(&dir)
+        );
+        assert_eq!(
+            ModifyWindow::resolve(None, [missing.as_path()]),
+            ModifyWindow::resolve(None, [dir.as_path()])
+        );
+    }
+
+    #[test]
+    fn resolve_without_paths_is_zero() {
+        assert_eq!(
+            ModifyWindow::resolve(None, std::iter::empty::<&Path>()),
+            ModifyWindow::ZERO
+        );
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[test]
+    fn linux_probe_recognises_fat_magics() {
+        assert!(super::fs_probe::classify(0x4D44));
+        assert!(super::fs_probe::classify(0x2011_BAB0));
+        // ext4 and tmpfs are exact-mtime filesystems.
+        assert!(!super::fs_probe::classify(0xEF53));
+        assert!(!super::fs_probe::classify(0x0102_1994));
+    }
 }
@@ -33,9 +33,13 @@
 //! ```
 
 use std::ffi::OsStr;
+use std::fs;
 use std::io;
+use std::os::unix::fs::MetadataExt;
 use std::path::Path;
 
+use protocol::acl::{NO_ENTRY, RsyncAcl};
+
 use crate::MetadataError;
 
 /// The extended attribute name for NFSv4 ACLs on Linux.
@@ -447,6 +451,123 @@ impl Nfs4Acl {
     pub const fn is_empty(&self) -> bool {
         self.aces.is_empty()
     }
+
+    /// Translates a POSIX draft ACL into the equivalent NFSv4 ACL.
+    ///
+    /// Follows the POSIX-to-NFSv4 mapping used by Linux `nfsd`: every owner
+    /// and named-user entry gets an ALLOW ACE followed by a DENY ACE for the
+    /// rwx bits it lacks, the group-class entries get all their ALLOW ACEs
+    /// before their DENY ACEs, and `other` becomes a trailing `EVERYONE@`
+    /// ALLOW. Named and group-class entries are limited by the ACL mask when
+    /// one is present. Named principals are written as numeric ids, which
+    /// NFSv4 accepts whenever id mapping is disabled for `AUTH_SYS` mounts.
+    #[must_use]
+    pub fn from_posix_acl(acl: &RsyncAcl) -> Self {
+        let masked = |perms: u8| {
+            if acl.mask_obj == NO_ENTRY {
+                perms
+            } else {
+                perms & acl.mask_obj
+            }
+        };
+        let mut aces = Vec::new();
+
+        if acl.user_obj != NO_ENTRY {
+            push_allow_deny(&mut aces, "OWNER@", 0, acl.user_obj, OWNER_EXTRA_MASK);
+        }
+        for entry in acl.names.iter().filter(|entry| entry.is_user()) {
+            let perms = masked(entry.permissions() as u8);
+            push_allow_deny(&mut aces, &entry.id.to_string(), 0, perms, 0);
+        }
+
+        let mut groups: Vec<(String, u32, u8)> = Vec::new();
+        if acl.group_obj != NO_ENTRY {
+            groups.push((
+                "GROUP@".to_owned(),
+                AceFlags::IDENTIFIER_GROUP,
+                masked(acl.group_obj),
+            ));
+        }
+        for entry in acl.names.iter().filter(|entry| !entry.is_user()) {
+            groups.push((
+                entry.id.to_string(),
+                AceFlags::IDENTIFIER_GROUP,
+                masked(entry.permissions() as u8),
+            ));
+        }
+        for (who, flags, perms) in &groups {
+            aces.push(Nfs4Ace {
+                ace_type: AceType::Allow,
+                flags: AceFlags::from_raw(*flags),
+                mask: AccessMask::from_raw(rwx_to_mask(*perms) | BASE_ALLOW_MASK),
+                who: who.clone(),
+            });
+        }
+        for (who, flags, perms) in &groups {
+            let denied = rwx_to_mask(!*perms & 0o7);
+            if denied != 0 {
+                aces.push(Nfs4Ace {
+                    ace_type: AceType::Deny,
+                    flags: AceFlags::from_raw(*flags),
+                    mask: AccessMask::from_raw(denied),
+                    who: who.clone(),
+                });
+            }
+        }
+
+        if acl.other_obj != NO_ENTRY {
+            aces.push(Nfs4Ace {
+                ace_type: AceType::Allow,
+                flags: AceFlags::default(),
+                mask: AccessMask::from_raw(rwx_to_mask(acl.other_obj) | BASE_ALLOW_MASK),
+                who: "EVERYONE@".to_owned(),
+            });
+        }
+
+        Self { aces }
+    }
+}
+
+/// Access bits every principal is granted regardless of its rwx bits.
+const BASE_ALLOW_MASK: u32 =
+    AccessMask::READ_ATTRIBUTES | AccessMask::READ_ACL | AccessMask::SYNCHRONIZE;
+
+/// Extra access the owner always holds: changing its attributes and ACL.
+const OWNER_EXTRA_MASK: u32 = AccessMask::WRITE_ATTRIBUTES | AccessMask::WRITE_ACL;
+
+/// Maps POSIX rwx bits (`0o4`/`0o2`/`0o1`) onto the NFSv4 data access bits.
+fn rwx_to_mask(perms: u8) -> u32 {
+    let mut mask = 0;
+    if perms & 0o4 != 0 {
+        mask |= AccessMask::READ_DATA;
+    }
+    if perms & 0o2 != 0 {
+        mask |= AccessMask::WRITE_DATA | AccessMask::APPEND_DATA;
+    }
+    if perms & 0o1 != 0 {
+        mask |= AccessMask::EXECUTE;
+    }
+    mask
+}
+
+/// Pushes an ALLOW ACE for `perms` followed by a DENY ACE for the missing
+/// rwx bits, so later `EVERYONE@` grants cannot widen the principal's access.
+fn push_allow_deny(aces: &mut Vec<Nfs4Ace>, who: &str, flags: u32, perms: u8, extra: u32) {
+    aces.push(Nfs4Ace {
+        ace_type: AceType::Allow,
+        flags: AceFlags::from_raw(flags),
+        mask: AccessMask::from_raw(rwx_to_mask(perms) | BASE_ALLOW_MASK | extra),
+        who: who.to_owned(),
+    });
+    let denied = rwx_to_mask(!perms & 0o7);
+    if denied != 0 {
+        aces.push(Nfs4Ace {
+            ace_type: AceType::Deny,
+            flags: AceFlags::from_raw(flags),
+            mask: AccessMask::from_raw(denied),
+            who: who.to_owned(),
+        });
+    }
 }
 
 /// Reads the NFSv4 ACL from a file.
@@ -543,8 +664,12 @@ pub fn set_nfsv4_acl(
 
 /// Synchronizes NFSv4 ACLs from `source` to `destination`.
 ///
-/// When the source has no NFSv4 ACL, any existing ACL on the destination is
-/// removed. Requires both filesystems to support `system.nfs4_acl`.
+/// A source NFSv4 ACL is copied verbatim. Otherwise the destination is only
+/// touched when it already reports an NFSv4 ACL, which is how NFSv4 mounts
+/// expose every file: the source's POSIX ACL (or its mode when it has none)
+/// is then translated with [`Nfs4Acl::from_posix_acl`]. Destinations without
+/// NFSv4 ACL support are left to the POSIX ACL path. The write is skipped when
+/// the destination ACL already matches.
 ///
 /// # Errors
 ///
@@ -556,8 +681,24 @@ pub fn sync_nfsv4_acls(
     destination: &Path,
     follow_symlinks: bool,
 ) -> Result<(), MetadataError> {
-    let acl = get_nfsv4_acl(source, follow_symlinks)?;
-    set_nfsv4_acl(destination, acl.as_ref(), follow_symlinks)
+    let existing = get_nfsv4_acl(destination, follow_symlinks)?;
+    let acl = match get_nfsv4_acl(source, follow_symlinks)? {
+        Some(acl) => acl,
+        None if existing.is_some() => {
+            let metadata = if follow_symlinks {
+                fs::metadata(source)
+            } else {
+                fs::symlink_metadata(source)
+            }
+            .map_err(|e| MetadataError::new("inspect NFSv4 ACL source", source, e))?;
+            Nfs4Acl::from_posix_acl(&crate::get_rsync_acl(source, metadata.mode(), false))
+        }
+        None => return Ok(()),
+    };
+    if existing.as_ref() == Some(&acl) {
+        return Ok(());
+    }
+    set_nfsv4_acl(destination, Some(&acl), follow_symlinks)
 }
 
 /// Returns `true` if the path has a non-empty NFSv4 ACL.
@@ -617,6 +758,84 @@ mod tests {
         assert_eq!(parsed.aces[1].who, "GROUP@");
     }
 
+    /// The access bits derived from POSIX rwx, ignoring the base grants.
+    const DATA_BITS: u32 = AccessMask::READ_DATA
+        | AccessMask::WRITE_DATA
+        | AccessMask::APPEND_DATA
+        | AccessMask::EXECUTE;
+
+    #[test]
+    fn from_posix_acl_maps_mode_only_acl() {
+        let acl = Nfs4Acl::from_posix_acl(&RsyncAcl::from_mode(0o640));
+        let summary: Vec<_> = acl
+            .aces
+            .iter()
+            .map(|ace| {
+                (
+                    ace.ace_type,
+                    ace.who.as_str(),
+                    ace.mask.as_raw() & DATA_BITS,
+                )
+            })
+            .collect();
+
+        let rw = AccessMask::READ_DATA | AccessMask::WRITE_DATA | AccessMask::APPEND_DATA;
+        assert_eq!(
+            summary,
+            vec![
+                (AceType::Allow, "OWNER@", rw),
+                (AceType::Deny, "OWNER@", AccessMask::EXECUTE),
+                (AceType::Allow, "GROUP@", AccessMask::READ_DATA),
+                (
+                    AceType::Deny,
+                    "GROUP@",
+                    AccessMask::WRITE_DATA | AccessMask::APPEND_DATA | AccessMask::EXECUTE
+                ),
+                (AceType::Allow, "EVERYONE@", 0),
+            ]
+        );
+        assert!(acl.aces[2].flags.contains(AceFlags::IDENTIFIER_GROUP));
+        assert_ne!(acl.aces[0].mask.as_raw() & AccessMask::WRITE_ACL, 0);
+    }
+
+    #[test]
+    fn from_posix_acl_applies_mask_to_named_entries() {
+        use protocol::acl::IdAccess;
+
+        let mut posix = RsyncAcl::from_mode(0o755);
+        posix.mask_obj = 0o5;
+        posix.names.push(IdAccess::user(1000, 0o7));
+        posix.names.push(IdAccess::group(100, 0o6));
+        let acl = Nfs4Acl::from_posix_acl(&posix);
+
+        let user_allow = acl.aces.iter().find(|ace| ace.who == "1000").unwrap();
+        assert_eq!(user_allow.ace_type, AceType::Allow);
+        assert_eq!(
+            user_allow.mask.as_raw() & DATA_BITS,
+            AccessMask::READ_DATA | AccessMask::EXECUTE
+        );
+
+        let group_allow = acl.aces.iter().find(|ace| ace.who == "100").unwrap();
+        assert!(group_allow.flags.contains(AceFlags::IDENTIFIER_GROUP));
+        assert_eq!(group_allow.mask.as_raw() & DATA_BITS, AccessMask::READ_DATA);
+        assert_eq!(acl.aces.last().unwrap().who, "EVERYONE@");
+    }
+
+    #[test]
+    fn sync_leaves_destination_without_nfsv4_support_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("source");
+        let dest = dir.path().join("dest");
+        std::fs::write(&source, b"a").unwrap();
+        std::fs::write(&dest, b"b").unwrap();
+
+        if has_nfsv4_acl(&dest, true) {
+            return;
+        }
+        sync_nfsv4_acls(&source, &dest, true).unwrap();
+        assert!(!has_nfsv4_acl(&dest, true));
+    }
+
     #[test]
     fn ace_type_conversion() {
         assert_eq!(AceType::try_from(0).unwrap(), AceType::Allow);
@@ -52,6 +52,12 @@ impl MetadataOptions {
         self.preserve_crtimes
     }
 
+    /// Reports whether BSD file flags should be preserved.
+    #[must_use]
+    pub const fn fileflags(&self) -> bool {
+        self.preserve_fileflags
+    }
+
     /// Reports whether numeric UID/GID preservation was requested.
     #[must_use]
     pub const fn numeric_ids_enabled(&self) -> bool {
@@ -130,6 +136,7 @@ impl MetadataOptions {
             || self.preserve_times
             || self.preserve_atimes
             || self.preserve_crtimes
+            || self.preserve_fileflags
             || self.fake_super
             || self.owner_override.is_some()
             || self.group_override.is_some()
@@ -159,6 +166,7 @@ impl MetadataOptions {
             || self.preserve_times
             || self.preserve_atimes
             || self.preserve_crtimes
+            || self.preserve_fileflags
             || self.fake_super
             || self.owner_override.is_some()
             || self.group_override.is_some()
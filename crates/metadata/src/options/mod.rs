@@ -27,6 +27,7 @@ pub struct MetadataOptions {
     pub(crate) preserve_times: bool,
     pub(crate) preserve_atimes: bool,
     pub(crate) preserve_crtimes: bool,
+    pub(crate) preserve_fileflags: bool,
     pub(crate) numeric_ids: bool,
     pub(crate) fake_super: bool,
     pub(crate) owner_override: Option<u32>,
@@ -66,6 +67,7 @@ impl MetadataOptions {
             preserve_times: true,
             preserve_atimes: false,
             preserve_crtimes: false,
+            preserve_fileflags: false,
             numeric_ids: false,
             fake_super: false,
             owner_override: None,
@@ -79,6 +79,20 @@ impl MetadataOptions {
         self
     }
 
+    /// Requests that BSD file flags (`chflags`) be preserved when applying
+    /// metadata.
+    ///
+    /// This is the `--fileflags` oc-rsync extension. Flags such as `uchg`,
+    /// `hidden`, and `nodump` are applied last so an immutable flag does not
+    /// block the other attribute updates. Only macOS and FreeBSD have file
+    /// flags; elsewhere the option is accepted but has no effect.
+    #[must_use]
+    #[doc(alias = "--fileflags")]
+    pub const fn preserve_fileflags(mut self, preserve: bool) -> Self {
+        self.preserve_fileflags = preserve;
+        self
+    }
+
     /// Requests that UID/GID preservation use numeric identifiers instead of mapping by name.
     #[must_use]
     #[doc(alias = "--numeric-ids")]
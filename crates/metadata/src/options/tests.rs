@@ -158,6 +158,7 @@ fn defaults_match_expected_configuration() {
     assert!(options.times());
     assert!(!options.atimes());
     assert!(!options.crtimes());
+    assert!(!options.fileflags());
     assert!(!options.numeric_ids_enabled());
     assert!(!options.fake_super_enabled());
     assert!(options.owner_override().is_none());
@@ -273,6 +274,7 @@ fn has_any_preservation_detects_each_flag() {
     assert!(base.clone().preserve_times(true).has_any_preservation());
     assert!(base.clone().preserve_atimes(true).has_any_preservation());
     assert!(base.clone().preserve_crtimes(true).has_any_preservation());
+    assert!(base.clone().preserve_fileflags(true).has_any_preservation());
     assert!(base.clone().fake_super(true).has_any_preservation());
     assert!(
         base.clone()
@@ -349,6 +351,7 @@ fn requires_apply_true_for_each_flag_independently() {
     assert!(base.clone().preserve_times(true).requires_apply());
     assert!(base.clone().preserve_atimes(true).requires_apply());
     assert!(base.clone().preserve_crtimes(true).requires_apply());
+    assert!(base.clone().preserve_fileflags(true).requires_apply());
     assert!(base.clone().fake_super(true).requires_apply());
     assert!(base.clone().with_owner_override(Some(0)).requires_apply());
     assert!(base.clone().with_group_override(Some(0)).requires_apply());
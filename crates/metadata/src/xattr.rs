@@ -892,6 +892,7 @@ mod tests {
             is_xattr_permitted("security.selinux", false),
             "a non-root sender must transmit security.* xattrs (SELinux labels)"
         );
+        assert!(is_xattr_permitted("security.capability", false));
         assert!(is_xattr_permitted("trusted.test", false));
         assert!(
             !is_xattr_permitted("system.posix_acl_access", false),
@@ -137,6 +137,13 @@ pub fn read_attribute(
 }
 
 /// Writes a single xattr value, replacing any existing value.
+///
+/// On macOS two attributes need special care. `com.apple.FinderInfo` is a
+/// fixed 32-byte block, so a value of any other length is refused up front
+/// with [`io::ErrorKind::InvalidData`] instead of surfacing the kernel's bare
+/// `ERANGE`. `com.apple.ResourceFork` is written in place from offset zero and
+/// never shrinks, so an existing fork is removed first; otherwise a shorter
+/// fork would keep the tail of the old one.
 pub fn write_attribute(
     path: &Path,
     name: &[u8],
@@ -144,6 +151,34 @@ pub fn write_attribute(
     follow_symlinks: bool,
 ) -> io::Result<()> {
     let os_name = OsStr::from_bytes(name);
+    #[cfg(target_os = "macos")]
+    {
+        if name == apple_fs::FINDER_INFO_XATTR.as_bytes()
+            && value.len() != apple_fs::FINDER_INFO_LEN
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "{} value is {} bytes; expected {}",
+                    apple_fs::FINDER_INFO_XATTR,
+                    value.len(),
+                    apple_fs::FINDER_INFO_LEN
+                ),
+            ));
+        }
+        if name == apple_fs::RESOURCE_FORK_XATTR.as_bytes() {
+            let removed = if follow_symlinks {
+                xattr::remove_deref(path, os_name)
+            } else {
+                xattr::remove(path, os_name)
+            };
+            if let Err(error) = removed
+                && error.raw_os_error() != Some(libc::ENOATTR)
+            {
+                return Err(error);
+            }
+        }
+    }
     if follow_symlinks {
         xattr::set_deref(path, os_name, value)
     } else {
@@ -229,4 +264,36 @@ mod macos_tests {
         );
         assert_eq!(got, value, "resource fork bytes must round-trip exactly");
     }
+
+    #[test]
+    fn write_attribute_shrinks_resource_fork() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("fork");
+        std::fs::write(&path, b"payload").unwrap();
+
+        let name = b"com.apple.ResourceFork";
+        write_attribute(&path, name, &patterned(8192), false).unwrap();
+        let shorter = vec![0x5a; 100];
+        write_attribute(&path, name, &shorter, false).unwrap();
+
+        let got = read_attribute(&path, name, false).unwrap();
+        assert_eq!(got.as_deref(), Some(shorter.as_slice()));
+    }
+
+    #[test]
+    fn write_attribute_rejects_malformed_finder_info() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("finder");
+        std::fs::write(&path, b"payload").unwrap();
+
+        let name = b"com.apple.FinderInfo";
+        let error = write_attribute(&path, name, &[1; 16], false).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+        let mut info = [0u8; 32];
+        info[..8].copy_from_slice(b"TEXTttxt");
+        write_attribute(&path, name, &info, false).unwrap();
+        let got = read_attribute(&path, name, false).unwrap();
+        assert_eq!(got.as_deref(), Some(info.as_slice()));
+    }
 }
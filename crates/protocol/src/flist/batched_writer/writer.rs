@@ -144,6 +144,13 @@ impl BatchedFileListWriter {
         self
     }
 
+    /// Sets whether BSD file flags should be written to the wire.
+    #[must_use]
+    pub fn with_preserve_fileflags(mut self, preserve: bool) -> Self {
+        self.writer = self.writer.with_preserve_fileflags(preserve);
+        self
+    }
+
     /// Sets whether ACLs should be written to the wire.
     #[must_use]
     pub fn with_preserve_acls(mut self, preserve: bool) -> Self {
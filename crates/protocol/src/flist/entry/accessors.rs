@@ -461,6 +461,18 @@ impl FileEntry {
         self.extras_mut().crtime = secs;
     }
 
+    /// Returns the BSD file flags (`st_flags`), or 0 when none were recorded.
+    #[inline]
+    #[must_use]
+    pub fn fileflags(&self) -> u32 {
+        self.extras.as_ref().map_or(0, |e| e.fileflags)
+    }
+
+    /// Sets the BSD file flags.
+    pub fn set_fileflags(&mut self, flags: u32) {
+        self.extras_mut().fileflags = flags;
+    }
+
     /// Returns whether this directory has content to transfer.
     ///
     /// Only meaningful for directories. Returns true for non-directories.
@@ -19,10 +19,10 @@ pub(super) const EXTRAS_PRESENT_HARDLINK_DEV: u16 = 1 << 5;
 ///
 /// These fields are only populated when specific flags are active (e.g.
 /// `--hard-links`, `--devices`, `--acls`, `--xattrs`, `--atimes`, `--crtimes`,
-/// `--checksum`). Storing them behind `Option<Box<...>>` in `FileEntry` avoids
-/// ~200 bytes of inline overhead per entry when they're unused - matching
-/// upstream rsync's conditional field allocation in `file_struct`
-/// (upstream: flist.c:make_file()).
+/// `--fileflags`, `--checksum`). Storing them behind `Option<Box<...>>` in
+/// `FileEntry` avoids ~200 bytes of inline overhead per entry when they're
+/// unused - matching upstream rsync's conditional field allocation in
+/// `file_struct` (upstream: flist.c:make_file()).
 ///
 /// Fields that have a load-bearing `None` vs `Some(0)` distinction (rdev,
 /// hardlink_idx, acl_ndx, def_acl_ndx, xattr_ndx, hardlink_dev, hardlink_ino)
@@ -82,6 +82,8 @@ pub(super) struct FileEntryExtras {
     /// Extended attribute index for --xattrs mode (index into xattr list).
     /// Meaningful only when `EXTRAS_PRESENT_XATTR_NDX` is set.
     pub(super) xattr_ndx: u32,
+    /// BSD file flags (`st_flags`) for `--fileflags` (oc-rsync extension).
+    pub(super) fileflags: u32,
 
     // 2-byte aligned fields.
     /// Presence bitfield for compacted Option fields.
@@ -147,6 +147,13 @@ pub const XMIT_UNUSED_15: u8 = 1 << 7;
 /// Upstream: `XMIT_RESERVED_16 (1<<16)`
 pub const XMIT_RESERVED_16: u8 = 1 << 0;
 
+/// Extended flag: same file flags as previous entry (bit 16).
+///
+/// Claims the bit upstream reserves for fileflags, as the fileflags patch
+/// does. Only set under `--fileflags` (oc-rsync extension).
+/// Patch: `XMIT_SAME_FLAGS (1<<16)`
+pub const XMIT_SAME_FLAGS: u8 = XMIT_RESERVED_16;
+
 /// Extended flag: creation time equals mtime (bit 17).
 ///
 /// Used when `--crtimes` is enabled. If set, crtime equals mtime and is not
@@ -386,6 +393,13 @@ impl FileFlags {
     pub const fn crtime_eq_mtime(&self) -> bool {
         self.extended16 & XMIT_CRTIME_EQ_MTIME != 0
     }
+
+    /// Returns true if file flags match the previous entry (bits 16+, varint mode).
+    #[inline]
+    #[must_use]
+    pub const fn same_fileflags(&self) -> bool {
+        self.extended16 & XMIT_SAME_FLAGS != 0
+    }
 }
 
 #[cfg(test)]
@@ -515,6 +529,7 @@ mod tests {
     fn extended16_constants_have_expected_values() {
         assert_eq!(XMIT_RESERVED_16, 0b0000_0001);
         assert_eq!(XMIT_CRTIME_EQ_MTIME, 0b0000_0010);
+        assert_eq!(XMIT_SAME_FLAGS, XMIT_RESERVED_16);
     }
 
     #[test]
@@ -590,6 +605,13 @@ mod tests {
         assert!(flags.crtime_eq_mtime());
     }
 
+    #[test]
+    fn flags_same_fileflags() {
+        let flags = FileFlags::new_with_extended16(0, 0, XMIT_SAME_FLAGS);
+        assert!(flags.same_fileflags());
+        assert!(!flags.crtime_eq_mtime());
+    }
+
     #[test]
     fn flags_from_u32() {
         let value: u32 = 0x020103; // extended16=0x02, extended=0x01, primary=0x03
@@ -11,6 +11,7 @@ use crate::flist::flags::{FileFlags, XMIT_HLINK_FIRST, XMIT_HLINKED};
 use crate::varint::{read_varint, read_varint30_int};
 
 use super::FileListReader;
+use super::limits::limit_exceeded;
 
 impl FileListReader {
     /// Reads symlink target if mode indicates a symlink AND preserve_links is enabled.
@@ -43,15 +44,13 @@ impl FileListReader {
             return Ok(None);
         }
 
-        // upstream: rsync.h MAXPATHLEN - reject targets that exceed PATH_MAX to
-        // prevent unbounded allocation from a malicious sender.
-        if len > crate::wire::file_entry_decode::MAX_SYMLINK_TARGET_LEN {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!(
-                    "symlink target length {len} exceeds maximum {}",
-                    crate::wire::file_entry_decode::MAX_SYMLINK_TARGET_LEN
-                ),
+        // upstream: rsync.h MAXPATHLEN - reject targets longer than the
+        // configured cap to prevent unbounded allocation from a malicious sender.
+        if len > self.limits.max_symlink_target_len() {
+            return Err(limit_exceeded(
+                "symlink target length",
+                len,
+                self.limits.max_symlink_target_len(),
             ));
         }
 
@@ -0,0 +1,144 @@
+//! Decoding caps applied to sender-declared lengths and counts.
+//!
+//! Every length in a file-list entry is chosen by the sender. A hostile peer
+//! can declare a multi-gigabyte name or symlink target, or stream entries
+//! forever, hoping the receiver allocates whatever it is told. The reader
+//! checks each declared length against [`FileListLimits`] before allocating
+//! and fails with [`io::ErrorKind::InvalidData`], which the exit-code mapper
+//! reports as `RERR_STREAMIO` (12).
+//!
+//! The defaults reproduce the bounds upstream rsync enforces implicitly, so a
+//! default reader accepts exactly what upstream accepts.
+//!
+//! # Upstream Reference
+//!
+//! - `rsync.h` - `MAXPATHLEN` bounds names and symlink targets
+//! - `flist.c:recv_file_entry()` - `l2 >= MAXPATHLEN - l1` overflow exit
+//! - `uidlist.c:recv_user_name()` - owner names carry a single length byte
+//! - `flist.c:flist_expand()` - `realloc_array()` of entry pointers, bounded
+//!   by `max_alloc`
+
+use std::io;
+
+use crate::max_alloc::effective_max_alloc;
+use crate::wire::file_entry_decode::MAX_SYMLINK_TARGET_LEN;
+
+/// Upstream `MAXPATHLEN`; a decoded name must be strictly shorter.
+///
+/// upstream: rsync.h `MAXPATHLEN`
+const MAXPATHLEN: usize = 4096;
+
+/// Longest owner name the wire format can carry (one length byte).
+///
+/// upstream: uidlist.c:recv_user_name() `len = read_byte(f)`
+const MAX_WIRE_OWNER_NAME_LEN: usize = u8::MAX as usize;
+
+/// Caps enforced by [`FileListReader`](super::FileListReader) while decoding.
+///
+/// # Examples
+///
+/// ```
+/// use protocol::ProtocolVersion;
+/// use protocol::flist::{FileListLimits, FileListReader};
+///
+/// let limits = FileListLimits::default()
+///     .with_max_entries(100_000)
+///     .with_max_path_len(1024);
+/// let reader = FileListReader::new(ProtocolVersion::NEWEST).with_limits(limits);
+/// assert_eq!(reader.limits().max_entries(), 100_000);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FileListLimits {
+    max_path_len: usize,
+    max_entries: usize,
+    max_symlink_target_len: usize,
+    max_owner_name_len: usize,
+}
+
+impl FileListLimits {
+    /// Sets the longest accepted file name in bytes, after prefix expansion.
+    ///
+    /// The cap can only be lowered: the prefix-compression buffer holds
+    /// `MAXPATHLEN` bytes, so larger values are clamped to `MAXPATHLEN - 1`.
+    #[inline]
+    #[must_use]
+    pub const fn with_max_path_len(mut self, len: usize) -> Self {
+        self.max_path_len = if len < MAXPATHLEN {
+            len
+        } else {
+            MAXPATHLEN - 1
+        };
+        self
+    }
+
+    /// Sets the most entries accepted across the whole file list, including
+    /// every incremental segment.
+    #[inline]
+    #[must_use]
+    pub const fn with_max_entries(mut self, count: usize) -> Self {
+        self.max_entries = count;
+        self
+    }
+
+    /// Sets the longest accepted symlink target in bytes.
+    #[inline]
+    #[must_use]
+    pub const fn with_max_symlink_target_len(mut self, len: usize) -> Self {
+        self.max_symlink_target_len = len;
+        self
+    }
+
+    /// Sets the longest accepted user or group name in bytes.
+    #[inline]
+    #[must_use]
+    pub const fn with_max_owner_name_len(mut self, len: usize) -> Self {
+        self.max_owner_name_len = len;
+        self
+    }
+
+    /// Longest accepted file name in bytes.
+    #[must_use]
+    pub const fn max_path_len(&self) -> usize {
+        self.max_path_len
+    }
+
+    /// Most entries accepted across the whole file list.
+    #[must_use]
+    pub const fn max_entries(&self) -> usize {
+        self.max_entries
+    }
+
+    /// Longest accepted symlink target in bytes.
+    #[must_use]
+    pub const fn max_symlink_target_len(&self) -> usize {
+        self.max_symlink_target_len
+    }
+
+    /// Longest accepted user or group name in bytes.
+    #[must_use]
+    pub const fn max_owner_name_len(&self) -> usize {
+        self.max_owner_name_len
+    }
+}
+
+impl Default for FileListLimits {
+    /// Upstream's bounds. The entry cap is the number of entry pointers that
+    /// fit in the current `--max-alloc` ceiling, read when the limits are
+    /// built.
+    fn default() -> Self {
+        Self {
+            max_path_len: MAXPATHLEN - 1,
+            max_entries: effective_max_alloc() / size_of::<usize>(),
+            max_symlink_target_len: MAX_SYMLINK_TARGET_LEN,
+            max_owner_name_len: MAX_WIRE_OWNER_NAME_LEN,
+        }
+    }
+}
+
+/// Builds the error reported when a decoded `what` of `value` exceeds `max`.
+pub(super) fn limit_exceeded(what: &str, value: usize, max: usize) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{what} {value} exceeds maximum {max}"),
+    )
+}
@@ -16,6 +16,7 @@ use crate::flist::flags::{FileFlags, XMIT_NO_CONTENT_DIR};
 use crate::varint::read_varint;
 
 use super::FileListReader;
+use super::limits::limit_exceeded;
 
 /// Maximum wire-encoded nanosecond value accepted for `modtime_nsec`.
 ///
@@ -50,6 +51,8 @@ pub(crate) struct MetadataResult {
     pub atime_nsec: u32,
     /// Creation time (when preserve_crtimes is enabled).
     pub crtime: Option<i64>,
+    /// BSD file flags (when preserve_fileflags is enabled).
+    pub fileflags: Option<u32>,
     /// Whether directory has content to transfer (protocol 30+, directories only).
     pub content_dir: bool,
 }
@@ -65,6 +68,7 @@ impl FileListReader {
     /// | 2 | nsec | `XMIT_MOD_NSEC` (proto 31+) | varint30 |
     /// | 3 | crtime | `preserve_crtimes && !XMIT_CRTIME_EQ_MTIME` | varlong(4) |
     /// | 4 | mode | `!XMIT_SAME_MODE` | i32 LE (proto <30) or varint |
+    /// | 4a | fileflags | `preserve_fileflags && !XMIT_SAME_FLAGS` | i32 LE |
     /// | 5 | atime | `preserve_atimes && !is_dir && !XMIT_SAME_ATIME` | varlong(4) |
     /// | 6 | uid | `preserve_uid && !XMIT_SAME_UID` | i32 LE (proto <30) or varint |
     /// | 6a | user_name | `XMIT_USER_NAME_FOLLOWS` (proto 30+) | u8 len + bytes |
@@ -144,6 +148,23 @@ impl FileListReader {
             )));
         }
 
+        // 4a. Read file flags if preserving them (oc-rsync extension).
+        // upstream: patches/fileflags.diff recv_file_entry() - `fileflags = read_int(f)`
+        // straight after the mode unless XMIT_SAME_FLAGS is set.
+        let fileflags = if self.preserve_fileflags {
+            if flags.same_fileflags() {
+                Some(self.state.prev_fileflags())
+            } else {
+                let mut flag_bytes = [0u8; 4];
+                reader.read_exact(&mut flag_bytes)?;
+                let fileflags = u32::from_le_bytes(flag_bytes);
+                self.state.update_fileflags(fileflags);
+                Some(fileflags)
+            }
+        } else {
+            None
+        };
+
         // Determine if this is a directory (needed for atime and content_dir)
         let is_dir = (mode & 0o170000) == 0o040000;
 
@@ -175,6 +196,7 @@ impl FileListReader {
                 uid_name_follows,
                 self.state.prev_uid(),
                 self.protocol.uses_fixed_encoding(),
+                self.limits.max_owner_name_len(),
             )?;
             self.state.update_uid(id);
             (Some(id), name)
@@ -194,6 +216,7 @@ impl FileListReader {
                 gid_name_follows,
                 self.state.prev_gid(),
                 self.protocol.uses_fixed_encoding(),
+                self.limits.max_owner_name_len(),
             )?;
             self.state.update_gid(id);
             (Some(id), name)
@@ -231,6 +254,7 @@ impl FileListReader {
             atime,
             atime_nsec,
             crtime,
+            fileflags,
             content_dir,
         })
     }
@@ -251,7 +275,8 @@ impl FileListReader {
 ///
 /// Returns `(id, optional_name)`. When `same` is true, returns the previous
 /// value unchanged. Otherwise reads the ID using fixed or varint encoding,
-/// and optionally reads a name string if `name_follows` is set.
+/// and optionally reads a name string if `name_follows` is set, rejecting
+/// names longer than `max_name_len`.
 ///
 /// upstream: flist.c:recv_file_entry() lines 908-938 - uid/gid reading
 fn read_owner_id<R: Read + ?Sized>(
@@ -260,6 +285,7 @@ fn read_owner_id<R: Read + ?Sized>(
     name_follows: bool,
     prev_id: u32,
     fixed_encoding: bool,
+    max_name_len: usize,
 ) -> io::Result<(u32, Option<String>)> {
     if same {
         return Ok((prev_id, None));
@@ -277,6 +303,9 @@ fn read_owner_id<R: Read + ?Sized>(
         let mut len_buf = [0u8; 1];
         reader.read_exact(&mut len_buf)?;
         let len = len_buf[0] as usize;
+        if len > max_name_len {
+            return Err(limit_exceeded("owner name length", len, max_name_len));
+        }
         if len > 0 {
             let mut name_bytes = vec![0u8; len];
             reader.read_exact(&mut name_bytes)?;
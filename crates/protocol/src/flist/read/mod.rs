@@ -12,6 +12,7 @@
 mod async_read;
 mod extras;
 mod flags;
+mod limits;
 mod metadata;
 mod name;
 mod step;
@@ -20,6 +21,7 @@ mod tests;
 
 #[cfg(feature = "tokio-transfer")]
 pub use async_read::read_entry_with_flist_async;
+pub use limits::FileListLimits;
 pub use step::EntryStep;
 
 use std::io::{self, Read};
@@ -81,6 +83,8 @@ pub struct FileListReader {
     preserve_atimes: bool,
     /// Whether to preserve (and thus read) creation times from the wire.
     preserve_crtimes: bool,
+    /// Whether to preserve (and thus read) BSD file flags from the wire.
+    preserve_fileflags: bool,
     /// Whether `--delete-missing-args` is active (upstream `missing_args == 2`).
     ///
     /// When true, a mode-0 sentinel entry is legitimate and bypasses the
@@ -157,6 +161,11 @@ pub struct FileListReader {
     /// Each file entry stores an index into this cache rather than duplicating
     /// the full xattr list. Mirrors upstream rsync's `rsync_xal_l`.
     xattr_cache: XattrCache,
+    /// Caps on sender-declared lengths and the total entry count.
+    limits: FileListLimits,
+    /// Entries decoded so far across every segment, checked against
+    /// [`FileListLimits::max_entries`].
+    entries_read: usize,
 }
 
 impl FileListReader {
@@ -179,6 +188,7 @@ impl FileListReader {
             preserve_hard_links: false,
             preserve_atimes: false,
             preserve_crtimes: false,
+            preserve_fileflags: false,
             delete_missing_args: false,
             always_checksum: false,
             preserve_acls: false,
@@ -194,6 +204,8 @@ impl FileListReader {
             io_error: 0,
             acl_cache: AclCache::new(),
             xattr_cache: XattrCache::new(),
+            limits: FileListLimits::default(),
+            entries_read: 0,
         }
     }
 
@@ -216,6 +228,7 @@ impl FileListReader {
             preserve_hard_links: false,
             preserve_atimes: false,
             preserve_crtimes: false,
+            preserve_fileflags: false,
             delete_missing_args: false,
             always_checksum: false,
             preserve_acls: false,
@@ -231,6 +244,8 @@ impl FileListReader {
             io_error: 0,
             acl_cache: AclCache::new(),
             xattr_cache: XattrCache::new(),
+            limits: FileListLimits::default(),
+            entries_read: 0,
         }
     }
 
@@ -301,6 +316,16 @@ impl FileListReader {
         self
     }
 
+    /// Sets whether BSD file flags should be read from the wire.
+    ///
+    /// Must match the sender's `--fileflags` setting (oc-rsync extension).
+    #[inline]
+    #[must_use]
+    pub const fn with_preserve_fileflags(mut self, preserve: bool) -> Self {
+        self.preserve_fileflags = preserve;
+        self
+    }
+
     /// Sets whether `--delete-missing-args` is active.
     ///
     /// When enabled, a mode-0 sentinel entry (upstream `missing_args == 2`) is
@@ -410,6 +435,23 @@ impl FileListReader {
         self
     }
 
+    /// Sets the caps applied to sender-declared lengths and the entry count.
+    ///
+    /// A sender exceeding any cap fails the read with
+    /// [`io::ErrorKind::InvalidData`] before anything is allocated for it.
+    #[inline]
+    #[must_use]
+    pub const fn with_limits(mut self, limits: FileListLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Returns the caps enforced while decoding.
+    #[must_use]
+    pub const fn limits(&self) -> &FileListLimits {
+        &self.limits
+    }
+
     /// Returns the statistics collected during file list reading.
     #[must_use]
     pub const fn stats(&self) -> &FileListStats {
@@ -557,6 +599,16 @@ impl FileListReader {
             FlagsResult::Flags(f) => f,
         };
 
+        // Refuse to grow the list past the cap; upstream's `flist_expand()`
+        // dies once the pointer array would exceed `max_alloc`.
+        if self.entries_read >= self.limits.max_entries() {
+            return Err(limits::limit_exceeded(
+                "file list entry count",
+                self.entries_read + 1,
+                self.limits.max_entries(),
+            ));
+        }
+
         let name = self.read_name(reader, flags)?;
 
         // upstream: flist.c:1909 - sender rejects empty names; we enforce the
@@ -649,6 +701,7 @@ impl FileListReader {
                         },
                         atime_nsec: leader.atime_nsec(),
                         crtime: None,
+                        fileflags: self.preserve_fileflags.then(|| leader.fileflags()),
                         content_dir: (leader_mode & 0o170000) == 0o040000,
                     },
                     leader.link_target().cloned(),
@@ -735,6 +788,9 @@ impl FileListReader {
         if let Some(crtime) = metadata.crtime {
             entry.set_crtime(crtime);
         }
+        if let Some(fileflags) = metadata.fileflags {
+            entry.set_fileflags(fileflags);
+        }
         if entry.is_dir() {
             entry.set_content_dir(metadata.content_dir);
         }
@@ -769,6 +825,7 @@ impl FileListReader {
         }
 
         self.update_stats(&entry);
+        self.entries_read += 1;
 
         debug_log!(
             Flist,
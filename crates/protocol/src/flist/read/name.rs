@@ -18,14 +18,7 @@ use crate::codec::ProtocolCodec;
 use crate::flist::flags::FileFlags;
 
 use super::FileListReader;
-
-/// Maximum total file name length on the wire.
-///
-/// Matches upstream rsync's `MAXPATHLEN` (4096). Upstream checks
-/// `l2 >= MAXPATHLEN - l1` before allocating the name buffer.
-///
-/// upstream: rsync.h `MAXPATHLEN`, flist.c:recv_file_entry()
-const MAXPATHLEN: usize = 4096;
+use super::limits::limit_exceeded;
 
 impl FileListReader {
     /// Reads the file name with path compression.
@@ -85,24 +78,22 @@ impl FileListReader {
         }
 
         // upstream: flist.c `l2 >= MAXPATHLEN - l1` overflow exit
-        // Defence-in-depth: reject names that exceed MAXPATHLEN to prevent
-        // unbounded allocation from a malicious sender. checked_add guards
-        // against arithmetic overflow when a malicious sender supplies a
-        // wire-encoded suffix length near usize::MAX.
+        // Defence-in-depth: reject names longer than the configured cap
+        // (MAXPATHLEN - 1 by default) to prevent unbounded allocation from a
+        // malicious sender. checked_add guards against arithmetic overflow
+        // when a malicious sender supplies a wire-encoded suffix length near
+        // usize::MAX.
         let total_len = same_len.checked_add(suffix_len).ok_or_else(|| {
             io::Error::new(
                 io::ErrorKind::InvalidData,
                 format!("filename length overflow: same_len={same_len} suffix_len={suffix_len}"),
             )
         })?;
-        if total_len >= MAXPATHLEN {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!(
-                    "filename length {} exceeds maximum {}",
-                    total_len,
-                    MAXPATHLEN - 1,
-                ),
+        if total_len > self.limits.max_path_len() {
+            return Err(limit_exceeded(
+                "filename length",
+                total_len,
+                self.limits.max_path_len(),
             ));
         }
 
@@ -399,6 +399,75 @@ fn read_write_round_trip_with_crtime_eq_mtime() {
     assert_eq!(read_entry.crtime(), read_entry.mtime());
 }
 
+#[test]
+fn read_write_round_trip_with_fileflags() {
+    use crate::flist::write::FileListWriter;
+
+    let protocol = test_protocol();
+    let flags = CompatibilityFlags::VARINT_FLIST_FLAGS;
+
+    let mut data = Vec::new();
+    let mut writer =
+        FileListWriter::with_compat_flags(protocol, flags).with_preserve_fileflags(true);
+
+    let mut first = FileEntry::new_file("a.txt".into(), 100, 0o100644);
+    first.set_fileflags(0x0002); // UF_IMMUTABLE
+    let mut second = FileEntry::new_file("b.txt".into(), 200, 0o100644);
+    second.set_fileflags(0x0002);
+    let third = FileEntry::new_file("c.txt".into(), 300, 0o100644);
+
+    writer.write_entry(&mut data, &first).unwrap();
+    let first_len = data.len();
+    writer.write_entry(&mut data, &second).unwrap();
+    let second_len = data.len() - first_len;
+    writer.write_entry(&mut data, &third).unwrap();
+
+    let mut cursor = Cursor::new(&data[..]);
+    let mut reader =
+        FileListReader::with_compat_flags(protocol, flags).with_preserve_fileflags(true);
+
+    assert_eq!(
+        reader.read_entry(&mut cursor).unwrap().unwrap().fileflags(),
+        0x0002
+    );
+    assert_eq!(
+        reader.read_entry(&mut cursor).unwrap().unwrap().fileflags(),
+        0x0002
+    );
+    assert_eq!(
+        reader.read_entry(&mut cursor).unwrap().unwrap().fileflags(),
+        0
+    );
+    // XMIT_SAME_FLAGS drops the repeated 4-byte flags word.
+    assert!(second_len < first_len);
+}
+
+#[test]
+fn read_write_round_trip_with_fileflags_two_byte_flags() {
+    use crate::flist::write::FileListWriter;
+
+    // Without varint flags XMIT_SAME_FLAGS cannot be sent, so every entry
+    // carries its flags word.
+    let protocol = ProtocolVersion::try_from(29u8).unwrap();
+
+    let mut data = Vec::new();
+    let mut writer = FileListWriter::new(protocol).with_preserve_fileflags(true);
+    for name in ["a.txt", "b.txt"] {
+        let mut entry = FileEntry::new_file(name.into(), 100, 0o100644);
+        entry.set_fileflags(0x8000); // UF_HIDDEN
+        writer.write_entry(&mut data, &entry).unwrap();
+    }
+
+    let mut cursor = Cursor::new(&data[..]);
+    let mut reader = FileListReader::new(protocol).with_preserve_fileflags(true);
+    for _ in 0..2 {
+        assert_eq!(
+            reader.read_entry(&mut cursor).unwrap().unwrap().fileflags(),
+            0x8000
+        );
+    }
+}
+
 #[test]
 fn read_write_round_trip_directory_with_content() {
     use crate::flist::write::FileListWriter;
@@ -1364,6 +1433,154 @@ fn read_entry_accepts_name_below_maxpathlen() {
     assert_eq!(read_entry.name().len(), 255);
 }
 
+// Configurable decoding limits (FileListLimits).
+// A hostile sender must be refused before the declared length is allocated.
+
+#[test]
+fn default_limits_match_upstream_bounds() {
+    let limits = FileListLimits::default();
+    assert_eq!(limits.max_path_len(), 4095);
+    assert_eq!(
+        limits.max_symlink_target_len(),
+        crate::wire::file_entry_decode::MAX_SYMLINK_TARGET_LEN
+    );
+    assert_eq!(limits.max_owner_name_len(), 255);
+    assert!(limits.max_entries() > 0);
+    assert_eq!(
+        FileListReader::new(test_protocol()).limits().max_path_len(),
+        4095
+    );
+}
+
+#[test]
+fn read_entry_enforces_configured_path_limit() {
+    use crate::flist::write::FileListWriter;
+
+    let protocol = test_protocol();
+    let mut data = Vec::new();
+    let mut writer = FileListWriter::new(protocol);
+    for name in ["a".repeat(16), "b".repeat(17)] {
+        let mut entry = FileEntry::new_file(name.into(), 1, 0o100644);
+        entry.set_mtime(1700000000, 0);
+        writer.write_entry(&mut data, &entry).unwrap();
+    }
+
+    let mut cursor = Cursor::new(&data[..]);
+    let mut reader =
+        FileListReader::new(protocol).with_limits(FileListLimits::default().with_max_path_len(16));
+    let first = reader.read_entry(&mut cursor).unwrap().unwrap();
+    assert_eq!(first.name().len(), 16);
+
+    let err = reader.read_entry(&mut cursor).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(err.to_string(), "filename length 17 exceeds maximum 16");
+}
+
+#[test]
+fn read_entry_enforces_entry_count_limit() {
+    use crate::flist::write::FileListWriter;
+
+    let protocol = test_protocol();
+    let mut data = Vec::new();
+    let mut writer = FileListWriter::new(protocol);
+    for name in ["one", "two", "three"] {
+        let mut entry = FileEntry::new_file(name.into(), 1, 0o100644);
+        entry.set_mtime(1700000000, 0);
+        writer.write_entry(&mut data, &entry).unwrap();
+    }
+
+    let mut cursor = Cursor::new(&data[..]);
+    let mut reader =
+        FileListReader::new(protocol).with_limits(FileListLimits::default().with_max_entries(2));
+    assert!(reader.read_entry(&mut cursor).unwrap().is_some());
+    // The cap spans segments: starting a new one does not reset the count.
+    reader.reset_for_new_segment(1);
+    assert!(reader.read_entry(&mut cursor).unwrap().is_some());
+
+    let err = reader.read_entry(&mut cursor).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(err.to_string(), "file list entry count 3 exceeds maximum 2");
+}
+
+#[test]
+fn read_entry_accepts_end_of_list_at_entry_count_limit() {
+    use crate::flist::write::FileListWriter;
+
+    let protocol = test_protocol();
+    let mut data = Vec::new();
+    let mut writer = FileListWriter::new(protocol);
+    let mut entry = FileEntry::new_file("only".into(), 1, 0o100644);
+    entry.set_mtime(1700000000, 0);
+    writer.write_entry(&mut data, &entry).unwrap();
+    writer.write_end(&mut data, None).unwrap();
+
+    let mut cursor = Cursor::new(&data[..]);
+    let mut reader =
+        FileListReader::new(protocol).with_limits(FileListLimits::default().with_max_entries(1));
+    assert!(reader.read_entry(&mut cursor).unwrap().is_some());
+    assert!(reader.read_entry(&mut cursor).unwrap().is_none());
+}
+
+#[test]
+fn read_entry_enforces_symlink_target_limit() {
+    use crate::flist::write::FileListWriter;
+
+    let protocol = test_protocol();
+    let mut data = Vec::new();
+    let mut writer = FileListWriter::new(protocol).with_preserve_links(true);
+    let mut entry = FileEntry::new_symlink("link".into(), "far/away/target".into());
+    entry.set_mtime(1700000000, 0);
+    writer.write_entry(&mut data, &entry).unwrap();
+
+    let mut cursor = Cursor::new(&data[..]);
+    let mut reader = FileListReader::new(protocol)
+        .with_preserve_links(true)
+        .with_limits(FileListLimits::default().with_max_symlink_target_len(8));
+
+    let err = reader.read_entry(&mut cursor).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(
+        err.to_string(),
+        "symlink target length 15 exceeds maximum 8"
+    );
+}
+
+#[test]
+fn read_entry_enforces_owner_name_limit() {
+    use crate::flist::flags::XMIT_USER_NAME_FOLLOWS;
+    use crate::varint::{encode_varint_to_vec, write_varlong};
+
+    let flags_value = 0x01 | ((XMIT_USER_NAME_FOLLOWS as i32) << 8);
+    let mut data = Vec::new();
+    encode_varint_to_vec(flags_value, &mut data);
+    data.push(4u8);
+    data.extend_from_slice(b"file");
+    write_varlong(&mut data, 100, 3).unwrap(); // size
+    write_varlong(&mut data, 0, 4).unwrap(); // mtime
+    data.extend_from_slice(&0o100644u32.to_le_bytes()); // mode: regular file
+    encode_varint_to_vec(100, &mut data); // UID
+    data.push(10u8); // User name length
+    data.extend_from_slice(b"longername");
+
+    let limits = FileListLimits::default().with_max_owner_name_len(8);
+    let mut cursor = Cursor::new(&data[..]);
+    let mut reader =
+        FileListReader::with_compat_flags(test_protocol(), CompatibilityFlags::VARINT_FLIST_FLAGS)
+            .with_preserve_uid(true)
+            .with_limits(limits);
+
+    let err = reader.read_entry(&mut cursor).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(err.to_string(), "owner name length 10 exceeds maximum 8");
+
+    let mut cursor = Cursor::new(&data[..]);
+    let mut reader =
+        FileListReader::with_compat_flags(test_protocol(), CompatibilityFlags::VARINT_FLIST_FLAGS)
+            .with_preserve_uid(true);
+    let entry = reader.read_entry(&mut cursor).unwrap().unwrap();
+    assert_eq!(entry.user_name(), Some("longername"));
+}
+
 // Zero-length filename validation tests
 // upstream: flist.c:1909 - sender rejects empty names. These tests verify
 // that the receiver also rejects zero-length filenames as defense-in-depth.
@@ -43,6 +43,8 @@ pub struct FileListCompressionState {
     prev_rdev: u64,
     /// Previous hardlink device number (for XMIT_SAME_DEV_pre30, protocols 26-29).
     prev_hardlink_dev: i64,
+    /// Previous entry's BSD file flags (for XMIT_SAME_FLAGS).
+    prev_fileflags: u32,
 }
 
 impl std::fmt::Debug for FileListCompressionState {
@@ -58,6 +60,7 @@ impl std::fmt::Debug for FileListCompressionState {
             .field("prev_rdev_major", &self.prev_rdev_major)
             .field("prev_rdev", &self.prev_rdev)
             .field("prev_hardlink_dev", &self.prev_hardlink_dev)
+            .field("prev_fileflags", &self.prev_fileflags)
             .finish()
     }
 }
@@ -75,6 +78,7 @@ impl Default for FileListCompressionState {
             prev_rdev_major: 0,
             prev_rdev: 0,
             prev_hardlink_dev: 0,
+            prev_fileflags: 0,
         }
     }
 }
@@ -163,6 +167,12 @@ impl FileListCompressionState {
         self.prev_hardlink_dev
     }
 
+    /// Returns the previous entry's BSD file flags.
+    #[must_use]
+    pub const fn prev_fileflags(&self) -> u32 {
+        self.prev_fileflags
+    }
+
     /// Calculates the common prefix length between the previous name and a new name.
     ///
     /// Returns the number of bytes that can be shared, capped at 255
@@ -267,6 +277,11 @@ impl FileListCompressionState {
         self.prev_hardlink_dev = dev;
     }
 
+    /// Updates only the file flags portion of the state.
+    pub const fn update_fileflags(&mut self, flags: u32) {
+        self.prev_fileflags = flags;
+    }
+
     /// Resets the compression state to initial values.
     pub fn reset(&mut self) {
         *self = Self::default();
@@ -289,6 +304,7 @@ mod tests {
         assert_eq!(state.prev_rdev_major(), 0);
         assert_eq!(state.prev_rdev(), 0);
         assert_eq!(state.prev_hardlink_dev(), 0);
+        assert_eq!(state.prev_fileflags(), 0);
     }
 
     #[test]
@@ -397,6 +413,13 @@ mod tests {
         assert_eq!(state.prev_hardlink_dev(), 0x1234_5678_9ABC);
     }
 
+    #[test]
+    fn update_fileflags() {
+        let mut state = FileListCompressionState::new();
+        state.update_fileflags(0x8002);
+        assert_eq!(state.prev_fileflags(), 0x8002);
+    }
+
     #[test]
     fn update_all_sets_all_fields() {
         let mut state = FileListCompressionState::new();
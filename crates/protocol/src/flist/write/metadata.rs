@@ -14,8 +14,8 @@ use crate::varint::write_varint;
 
 use super::super::entry::FileEntry;
 use super::super::flags::{
-    XMIT_CRTIME_EQ_MTIME, XMIT_GROUP_NAME_FOLLOWS, XMIT_MOD_NSEC, XMIT_SAME_ATIME, XMIT_SAME_GID,
-    XMIT_SAME_MODE, XMIT_SAME_TIME, XMIT_SAME_UID, XMIT_USER_NAME_FOLLOWS,
+    XMIT_CRTIME_EQ_MTIME, XMIT_GROUP_NAME_FOLLOWS, XMIT_MOD_NSEC, XMIT_SAME_ATIME, XMIT_SAME_FLAGS,
+    XMIT_SAME_GID, XMIT_SAME_MODE, XMIT_SAME_TIME, XMIT_SAME_UID, XMIT_USER_NAME_FOLLOWS,
 };
 use super::FileListWriter;
 
@@ -28,9 +28,10 @@ impl FileListWriter {
     /// 3. nsec (if XMIT_MOD_NSEC, protocol 31+)
     /// 4. crtime (if preserving, not XMIT_CRTIME_EQ_MTIME)
     /// 5. mode (if not XMIT_SAME_MODE)
-    /// 6. atime (if preserving, non-dir, not XMIT_SAME_ATIME)
-    /// 7. uid + user name (if preserving, not XMIT_SAME_UID)
-    /// 8. gid + group name (if preserving, not XMIT_SAME_GID)
+    /// 6. file flags (if preserving, not XMIT_SAME_FLAGS)
+    /// 7. atime (if preserving, non-dir, not XMIT_SAME_ATIME)
+    /// 8. uid + user name (if preserving, not XMIT_SAME_UID)
+    /// 9. gid + group name (if preserving, not XMIT_SAME_GID)
     pub(super) fn write_metadata<W: Write + ?Sized>(
         &mut self,
         writer: &mut W,
@@ -40,6 +41,7 @@ impl FileListWriter {
         self.write_size(writer, entry)?;
         self.write_time_fields(writer, entry, xflags)?;
         self.write_mode(writer, entry, xflags)?;
+        self.write_fileflags(writer, entry, xflags)?;
         self.write_atime(writer, entry, xflags)?;
         self.write_uid_field(writer, entry, xflags)?;
         self.write_gid_field(writer, entry, xflags)?;
@@ -90,6 +92,24 @@ impl FileListWriter {
         Ok(())
     }
 
+    /// Writes the BSD file flags word if preserving and different.
+    ///
+    /// upstream: patches/fileflags.diff `send_file_entry()` - `write_int(f, fileflags)`
+    /// straight after the mode, unless `XMIT_SAME_FLAGS` is set.
+    #[inline]
+    fn write_fileflags<W: Write + ?Sized>(
+        &mut self,
+        writer: &mut W,
+        entry: &FileEntry,
+        xflags: u32,
+    ) -> io::Result<()> {
+        if self.preserve.fileflags && (xflags & ((XMIT_SAME_FLAGS as u32) << 16)) == 0 {
+            writer.write_all(&(entry.fileflags() as i32).to_le_bytes())?;
+            self.state.update_fileflags(entry.fileflags());
+        }
+        Ok(())
+    }
+
     /// Writes atime field if preserving and different (non-directories only).
     ///
     /// upstream: `flist.c:607-608` - atime is encoded as a single
@@ -61,6 +61,8 @@ pub struct PreserveFlags {
     pub acls: bool,
     /// Whether to preserve (and thus write) extended attributes to the wire.
     pub xattrs: bool,
+    /// Whether to preserve (and thus write) BSD file flags to the wire.
+    pub fileflags: bool,
 }
 
 /// State maintained while writing a file list to the wire.
@@ -269,6 +271,17 @@ impl FileListWriter {
         self
     }
 
+    /// Sets whether BSD file flags should be written to the wire.
+    ///
+    /// This is the `--fileflags` oc-rsync extension; both peers must enable it
+    /// because the flags word is not self-describing on the wire.
+    #[inline]
+    #[must_use]
+    pub const fn with_preserve_fileflags(mut self, preserve: bool) -> Self {
+        self.preserve.fileflags = preserve;
+        self
+    }
+
     /// Sets whether ACLs should be written to the wire.
     ///
     /// When enabled, ACL data is written after the checksum for each entry.
@@ -457,11 +470,12 @@ impl FileListWriter {
     /// 6. Nsec (if XMIT_MOD_NSEC)
     /// 7. Crtime (if preserving and not XMIT_CRTIME_EQ_MTIME)
     /// 8. Mode (if not XMIT_SAME_MODE)
-    /// 9. Atime (if preserving, non-dir, not XMIT_SAME_ATIME)
-    /// 10. UID (if preserving, not XMIT_SAME_UID) + user name
-    /// 11. GID (if preserving, not XMIT_SAME_GID) + group name
-    /// 12. Device numbers (if device/special file)
-    /// 13. Symlink target (if symlink)
+    /// 9. File flags (if preserving, not XMIT_SAME_FLAGS)
+    /// 10. Atime (if preserving, non-dir, not XMIT_SAME_ATIME)
+    /// 11. UID (if preserving, not XMIT_SAME_UID) + user name
+    /// 12. GID (if preserving, not XMIT_SAME_GID) + group name
+    /// 13. Device numbers (if device/special file)
+    /// 14. Symlink target (if symlink)
     ///
     /// # Upstream Reference
     ///
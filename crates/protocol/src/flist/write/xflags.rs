@@ -11,8 +11,8 @@ use super::super::entry::FileEntry;
 use super::super::flags::{
     XMIT_CRTIME_EQ_MTIME, XMIT_GROUP_NAME_FOLLOWS, XMIT_HLINK_FIRST, XMIT_HLINKED, XMIT_LONG_NAME,
     XMIT_MOD_NSEC, XMIT_NO_CONTENT_DIR, XMIT_RDEV_MINOR_8_PRE30, XMIT_SAME_ATIME,
-    XMIT_SAME_DEV_PRE30, XMIT_SAME_GID, XMIT_SAME_MODE, XMIT_SAME_NAME, XMIT_SAME_RDEV_MAJOR,
-    XMIT_SAME_TIME, XMIT_SAME_UID, XMIT_TOP_DIR, XMIT_USER_NAME_FOLLOWS,
+    XMIT_SAME_DEV_PRE30, XMIT_SAME_FLAGS, XMIT_SAME_GID, XMIT_SAME_MODE, XMIT_SAME_NAME,
+    XMIT_SAME_RDEV_MAJOR, XMIT_SAME_TIME, XMIT_SAME_UID, XMIT_TOP_DIR, XMIT_USER_NAME_FOLLOWS,
 };
 use super::FileListWriter;
 
@@ -28,7 +28,8 @@ impl FileListWriter {
     /// The xflags are divided into three bytes:
     /// - **Byte 0 (bits 0-7)**: Basic flags, always present
     /// - **Byte 1 (bits 8-15)**: Extended flags, present when `XMIT_EXTENDED_FLAGS` is set
-    /// - **Byte 2 (bits 16-23)**: Extra flags, used in varint mode for creation time
+    /// - **Byte 2 (bits 16-23)**: Extra flags, used in varint mode for file flags
+    ///   and creation time
     ///
     /// # Basic Flags (byte 0)
     ///
@@ -79,6 +80,7 @@ impl FileListWriter {
         xflags |= self.calculate_hardlink_flags(entry);
         xflags |= self.calculate_owner_name_flags(entry, xflags);
         xflags |= self.calculate_time_flags(entry);
+        xflags |= self.calculate_fileflags_flag(entry);
         xflags |= self.calculate_directory_flags(entry);
         xflags
     }
@@ -271,6 +273,22 @@ impl FileListWriter {
         xflags
     }
 
+    /// Calculates XMIT_SAME_FLAGS for `--fileflags`.
+    ///
+    /// Like XMIT_CRTIME_EQ_MTIME the bit lives in the third flag byte, so it is
+    /// only set in varint flag encoding; otherwise the flags word is always sent.
+    #[inline]
+    fn calculate_fileflags_flag(&self, entry: &FileEntry) -> u32 {
+        if self.use_varint_flags()
+            && self.preserve.fileflags
+            && entry.fileflags() == self.state.prev_fileflags()
+        {
+            (XMIT_SAME_FLAGS as u32) << 16
+        } else {
+            0
+        }
+    }
+
     /// Calculates directory-specific flags for protocol 30+.
     ///
     /// Handles XMIT_NO_CONTENT_DIR flag which indicates a directory
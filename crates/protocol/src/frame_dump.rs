@@ -0,0 +1,171 @@
+//! Capture of multiplexed frames for `--debug-protocol-dump`.
+//!
+//! oc-rsync extension with no upstream equivalent. Once [`start`] opens the
+//! dump file, every `MSG_*` frame this process sends or receives through the
+//! multiplex helpers is appended as one JSON object per line:
+//!
+//! ```text
+//! {"ts_us":1718000000123456,"pid":4242,"dir":"send","tag":"MSG_DATA","code":0,"len":5,"payload":"68656c6c6f"}
+//! ```
+//!
+//! `payload` holds the first [`SNIPPET_LEN`] bytes in hex; `len` is always
+//! the full payload length, so a snippet shorter than `len` marks a
+//! truncated frame. Each line is flushed as it is written, which keeps the
+//! capture usable after a crash and lets several threads of a local copy
+//! share the file. `cargo xtask trace-dump FILE` renders a capture as an
+//! aligned table.
+//!
+//! The dump is process-wide and off by default; when it is inactive the
+//! hooks cost one relaxed atomic load per frame.
+
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, LineWriter, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::envelope::MessageCode;
+
+/// Number of leading payload bytes recorded for each frame.
+pub const SNIPPET_LEN: usize = 64;
+
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+static SINK: OnceLock<Mutex<LineWriter<File>>> = OnceLock::new();
+
+/// Which way a recorded frame travelled.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FrameDirection {
+    /// Written to the peer.
+    Send,
+    /// Read from the peer.
+    Recv,
+}
+
+impl FrameDirection {
+    /// Returns the `dir` value used in the dump.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Send => "send",
+            Self::Recv => "recv",
+        }
+    }
+}
+
+/// Creates (or truncates) `path` and starts recording frames into it.
+///
+/// # Errors
+///
+/// Fails when the file cannot be created, or when a dump is already being
+/// recorded by this process.
+pub fn start(path: &Path) -> io::Result<()> {
+    let file = File::create(path)?;
+    SINK.set(Mutex::new(LineWriter::new(file)))
+        .map_err(|_| io::Error::other("a protocol dump is already being recorded"))?;
+    ACTIVE.store(true, Ordering::Release);
+    Ok(())
+}
+
+/// Reports whether frames are being recorded.
+#[inline]
+#[must_use]
+pub fn is_active() -> bool {
+    ACTIVE.load(Ordering::Relaxed)
+}
+
+/// Records one frame of `len` payload bytes whose payload starts with
+/// `payload`. Does nothing unless [`start`] succeeded.
+///
+/// Callers that stream a frame's payload separately from its header pass
+/// whatever prefix they hold; only the first [`SNIPPET_LEN`] bytes are kept.
+#[inline]
+pub fn record(direction: FrameDirection, code: MessageCode, len: usize, payload: &[u8]) {
+    if is_active() {
+        write_record(direction, code, len, payload);
+    }
+}
+
+#[cold]
+fn write_record(direction: FrameDirection, code: MessageCode, len: usize, payload: &[u8]) {
+    let Some(sink) = SINK.get() else {
+        return;
+    };
+    let ts_us = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_micros());
+    let line = format_record(ts_us, std::process::id(), direction, code, len, payload);
+    if let Ok(mut sink) = sink.lock() {
+        // A failing dump must never disturb the transfer it observes.
+        let _ = sink.write_all(line.as_bytes());
+    }
+}
+
+fn format_record(
+    ts_us: u128,
+    pid: u32,
+    direction: FrameDirection,
+    code: MessageCode,
+    len: usize,
+    payload: &[u8],
+) -> String {
+    let snippet = &payload[..payload.len().min(SNIPPET_LEN).min(len)];
+    let mut line = format!(
+        "{{\"ts_us\":{ts_us},\"pid\":{pid},\"dir\":\"{}\",\"tag\":\"{}\",\"code\":{},\
+         \"len\":{len},\"payload\":\"",
+        direction.as_str(),
+        code.name(),
+        code.as_u8(),
+    );
+    for byte in snippet {
+        let _ = write!(line, "{byte:02x}");
+    }
+    line.push_str("\"}\n");
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_are_single_json_lines() {
+        let line = format_record(17, 42, FrameDirection::Send, MessageCode::Data, 5, b"hello");
+        assert_eq!(
+            line,
+            "{\"ts_us\":17,\"pid\":42,\"dir\":\"send\",\"tag\":\"MSG_DATA\",\"code\":0,\
+             \"len\":5,\"payload\":\"68656c6c6f\"}\n"
+        );
+
+        let value: serde_json::Value = serde_json::from_str(line.trim_end()).unwrap();
+        assert_eq!(value["dir"], "send");
+        assert_eq!(value["len"], 5);
+    }
+
+    #[test]
+    fn payload_snippets_are_truncated() {
+        let payload = vec![0xab; SNIPPET_LEN * 2];
+        let line = format_record(
+            0,
+            1,
+            FrameDirection::Recv,
+            MessageCode::Info,
+            payload.len(),
+            &payload,
+        );
+        let value: serde_json::Value = serde_json::from_str(line.trim_end()).unwrap();
+        assert_eq!(value["dir"], "recv");
+        assert_eq!(value["tag"], "MSG_INFO");
+        assert_eq!(value["len"], SNIPPET_LEN * 2);
+        assert_eq!(value["payload"].as_str().unwrap().len(), SNIPPET_LEN * 2);
+    }
+
+    #[test]
+    fn inactive_dump_records_nothing() {
+        if !is_active() {
+            record(FrameDirection::Send, MessageCode::Data, 1, b"x");
+            assert!(SINK.get().is_none());
+        }
+    }
+}
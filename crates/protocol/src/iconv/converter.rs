@@ -109,7 +109,8 @@ impl FilenameConverter {
     /// * `remote_charset` - The character set used on the remote system
     ///
     /// Special values:
-    /// - "." or "" means "use local system encoding" (defaults to UTF-8)
+    /// - "." or "" means "use local system encoding": the codeset named by
+    ///   the locale (see [`locale_charset`]), or UTF-8 when it names none
     ///
     /// Supported encoding names include:
     /// - UTF-8: "utf-8", "utf8", "UTF-8"
@@ -655,15 +656,48 @@ fn flush_encoder(
 /// Normalizes encoding names for lookup.
 ///
 /// Special cases:
-/// - "." or "" means UTF-8 (local system encoding)
+/// - "." or "" means the local system encoding: the locale's codeset when
+///   `encoding_rs` knows it, UTF-8 otherwise (and always without the
+///   `iconv` feature)
 pub(super) fn normalize_encoding_name(name: &str) -> Cow<'_, str> {
     let trimmed = name.trim();
     if trimmed.is_empty() || trimmed == "." {
+        #[cfg(feature = "iconv")]
+        if let Some(codeset) = locale_charset()
+            && encoding_rs::Encoding::for_label(codeset.as_bytes()).is_some()
+        {
+            return Cow::Owned(codeset);
+        }
         return Cow::Borrowed("utf-8");
     }
     Cow::Borrowed(trimmed)
 }
 
+/// Returns the codeset named by the process locale, if any.
+///
+/// Stands in for upstream's `default_charset()` (`rsync.c:63-72`), which
+/// reports `nl_langinfo(CODESET)` after `setlocale(LC_CTYPE, "")`: the first
+/// non-empty of `LC_ALL`, `LC_CTYPE`, and `LANG` decides, and its codeset is
+/// the text between the `.` and any `@modifier` (`de_DE.ISO-8859-15@euro`
+/// yields `ISO-8859-15`). Locales that name no codeset (`C`, `POSIX`, or
+/// unset) yield `None`, which callers treat as UTF-8 rather than upstream's
+/// ASCII so unset environments keep passing UTF-8 names through.
+#[must_use]
+pub fn locale_charset() -> Option<String> {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|locale| locale_codeset(&locale).map(str::to_owned))
+}
+
+/// Extracts the codeset from a locale name such as `en_US.UTF-8@euro`.
+pub(super) fn locale_codeset(locale: &str) -> Option<&str> {
+    let (_, rest) = locale.split_once('.')?;
+    let codeset = rest.split('@').next().unwrap_or(rest);
+    (!codeset.is_empty()).then_some(codeset)
+}
+
 /// Checks if an encoding name refers to UTF-8.
 #[cfg(not(feature = "iconv"))]
 pub(super) fn is_utf8_name(name: &str) -> bool {
@@ -675,14 +709,15 @@ pub(super) fn is_utf8_name(name: &str) -> bool {
 
 /// Creates a [`FilenameConverter`] from the locale, using UTF-8 as the remote encoding.
 ///
-/// This is used when `--iconv=.` is specified (locale on one side, UTF-8 on the other).
+/// This is used when `--iconv=.` is specified (locale on one side, UTF-8 on the
+/// wire). On a UTF-8 locale, the common case, the result is the identity.
 #[cfg(feature = "iconv")]
 #[must_use]
 pub fn converter_from_locale() -> FilenameConverter {
-    // On most modern systems, the locale is UTF-8, so this is often a no-op.
-    // For truly accurate locale detection, we would need to query LC_CTYPE,
-    // but UTF-8 is a reasonable default for modern systems.
-    FilenameConverter::identity()
+    // upstream: rsync.c:125-126 - "." selects default_charset(); the wire
+    // side is always UTF8_CHARSET. normalize_encoding_name() only yields
+    // labels encoding_rs accepts, so this cannot fail in practice.
+    FilenameConverter::new(".", "UTF-8").unwrap_or_else(|_| FilenameConverter::identity())
 }
 
 /// Creates a [`FilenameConverter`] from the locale (no-op when iconv is disabled).
@@ -33,7 +33,7 @@ pub mod skip;
 pub mod trace;
 
 pub use converter::{
-    ConversionOutcome, EncodingConverter, FilenameConverter, converter_from_locale,
+    ConversionOutcome, EncodingConverter, FilenameConverter, converter_from_locale, locale_charset,
 };
 pub use error::{ConversionError, EncodingError};
 pub use pair::EncodingPair;
@@ -327,6 +327,22 @@ mod tests {
         assert!(converter.is_identity());
     }
 
+    #[test]
+    fn locale_codeset_strips_territory_and_modifier() {
+        use super::converter::locale_codeset;
+
+        assert_eq!(locale_codeset("en_US.UTF-8"), Some("UTF-8"));
+        assert_eq!(
+            locale_codeset("de_DE.ISO-8859-15@euro"),
+            Some("ISO-8859-15")
+        );
+        assert_eq!(locale_codeset("C.UTF-8"), Some("UTF-8"));
+        assert_eq!(locale_codeset("C"), None);
+        assert_eq!(locale_codeset("POSIX"), None);
+        assert_eq!(locale_codeset("sr_RS@latin"), None);
+        assert_eq!(locale_codeset("en_US."), None);
+    }
+
     #[test]
     fn test_converter_equality() {
         let conv1 = EncodingConverter::identity();
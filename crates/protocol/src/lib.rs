@@ -128,6 +128,8 @@ pub mod filters;
 pub mod flist;
 /// Basis file comparison type constants for alternate basis selection.
 pub mod fnamecmp;
+/// Multiplexed frame capture for `--debug-protocol-dump`.
+pub mod frame_dump;
 /// Filename encoding conversion (iconv) for cross-platform transfers.
 pub mod iconv;
 /// UID/GID mapping lists for name-based ownership transfer.
@@ -174,7 +176,7 @@ pub use files_from::{
 pub use fnamecmp::{FnameCmpType, InvalidFnameCmpType};
 pub use iconv::{
     ConversionError, EncodingConverter, EncodingError, EncodingPair, FilenameConverter,
-    converter_from_locale,
+    converter_from_locale, locale_charset,
 };
 pub use legacy::{
     DigestListTokens, LEGACY_DAEMON_PREFIX, LEGACY_DAEMON_PREFIX_BYTES, LEGACY_DAEMON_PREFIX_LEN,
@@ -198,6 +200,9 @@ pub use multiplex::{
     BorrowedMessageFrame, BorrowedMessageFrames, MessageFrame, MplexReader, MplexWriter, recv_msg,
     recv_msg_into, send_frame, send_keepalive, send_msg, send_msgs_vectored,
 };
+#[cfg(any(test, feature = "test-support"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-support")))]
+pub use multiplex::{FaultyTransport, FrameFault};
 pub use negotiation::{
     BufferedPrefixTooSmall, ChecksumAlgorithm, CompressionAlgorithm, NegotiationConfig,
     NegotiationPrologue, NegotiationPrologueDetector, NegotiationPrologueSniffer,
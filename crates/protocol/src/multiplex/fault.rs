@@ -0,0 +1,348 @@
+//! Frame-level fault injection for multiplexed streams.
+//!
+//! [`FaultyTransport`] wraps a peer connection and splits the bytes passing
+//! through it into multiplex frames, so a regression test can truncate,
+//! delay, duplicate, or corrupt the Nth frame read or written and assert that
+//! the sender or receiver on the other side fails with the expected error
+//! instead of hanging. Frames are counted separately per direction, starting
+//! at zero, and are split on the raw length prefix without validating the
+//! tag, so the wrapped stream itself must be well formed. Written bytes are
+//! held back until their frame is complete.
+//!
+//! Only available with the `test-support` feature.
+
+use std::collections::VecDeque;
+use std::io::{self, Read, Write};
+use std::time::Duration;
+
+use crate::envelope::{HEADER_LEN, PAYLOAD_MASK};
+
+/// Damage applied to one multiplex frame.
+///
+/// Offsets count from the start of the frame: bytes `0..3` hold the
+/// little-endian payload length, byte `3` the tag (`MPLEX_BASE + code`), and
+/// the payload starts at [`HEADER_LEN`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FrameFault {
+    /// Delivers the first `keep` bytes of the frame, then behaves like a
+    /// peer that hung up: reads report end of stream and writes fail with
+    /// [`io::ErrorKind::BrokenPipe`].
+    Truncate {
+        /// Bytes of the frame, header included, that still get through.
+        keep: usize,
+    },
+    /// Holds the frame back for the given time before delivering it.
+    Delay(Duration),
+    /// Delivers the frame twice.
+    Duplicate,
+    /// XORs the frame byte at `offset` with `mask`; an offset past the end
+    /// of the frame leaves it untouched.
+    Corrupt {
+        /// Position of the byte to flip, header included.
+        offset: usize,
+        /// Bits to flip.
+        mask: u8,
+    },
+}
+
+/// Transport wrapper that injects [`FrameFault`]s into selected frames.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::{Cursor, ErrorKind};
+/// use protocol::{FaultyTransport, FrameFault, MessageCode, recv_msg, send_msg};
+///
+/// let mut wire = Vec::new();
+/// send_msg(&mut wire, MessageCode::Data, b"hello")?;
+///
+/// let mut peer = FaultyTransport::new(Cursor::new(wire))
+///     .on_read(0, FrameFault::Truncate { keep: 6 });
+/// let error = recv_msg(&mut peer).unwrap_err();
+/// assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct FaultyTransport<T> {
+    inner: T,
+    read_faults: Vec<(usize, FrameFault)>,
+    write_faults: Vec<(usize, FrameFault)>,
+    incoming: VecDeque<u8>,
+    frames_read: usize,
+    read_cut: bool,
+    outgoing: Vec<u8>,
+    frames_written: usize,
+    write_cut: bool,
+}
+
+impl<T> FaultyTransport<T> {
+    /// Wraps `inner` without any faults configured.
+    #[must_use]
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            read_faults: Vec::new(),
+            write_faults: Vec::new(),
+            incoming: VecDeque::new(),
+            frames_read: 0,
+            read_cut: false,
+            outgoing: Vec::new(),
+            frames_written: 0,
+            write_cut: false,
+        }
+    }
+
+    /// Applies `fault` to the frame with index `frame` read from the peer.
+    #[must_use]
+    pub fn on_read(mut self, frame: usize, fault: FrameFault) -> Self {
+        self.read_faults.push((frame, fault));
+        self
+    }
+
+    /// Applies `fault` to the frame with index `frame` written to the peer.
+    #[must_use]
+    pub fn on_write(mut self, frame: usize, fault: FrameFault) -> Self {
+        self.write_faults.push((frame, fault));
+        self
+    }
+
+    /// Number of frames taken from the wrapped reader so far.
+    #[must_use]
+    pub const fn frames_read(&self) -> usize {
+        self.frames_read
+    }
+
+    /// Number of complete frames passed to the wrapped writer so far.
+    #[must_use]
+    pub const fn frames_written(&self) -> usize {
+        self.frames_written
+    }
+
+    /// Returns a reference to the wrapped transport.
+    #[must_use]
+    pub const fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the wrapped transport.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Unwraps the transport, discarding buffered bytes.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Read> FaultyTransport<T> {
+    /// Reads the next whole frame from the peer, or `None` at a clean end of
+    /// stream between frames.
+    fn next_incoming_frame(&mut self) -> io::Result<Option<Vec<u8>>> {
+        let mut header = [0u8; HEADER_LEN];
+        let mut filled = 0;
+        while filled < HEADER_LEN {
+            match self.inner.read(&mut header[filled..]) {
+                Ok(0) if filled == 0 => return Ok(None),
+                Ok(0) => break,
+                Ok(read) => filled += read,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+        }
+        let mut frame = header[..filled].to_vec();
+        if filled == HEADER_LEN {
+            // A short payload is passed on as is; the reader above reports it.
+            let len = u32::from_le_bytes(header) & PAYLOAD_MASK;
+            self.inner
+                .by_ref()
+                .take(u64::from(len))
+                .read_to_end(&mut frame)?;
+        }
+        Ok(Some(frame))
+    }
+}
+
+impl<T: Read> Read for FaultyTransport<T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.incoming.is_empty() && !self.read_cut {
+            let Some(frame) = self.next_incoming_frame()? else {
+                return Ok(0);
+            };
+            let index = self.frames_read;
+            self.frames_read += 1;
+            let cut = apply_faults(&self.read_faults, index, frame, |bytes| {
+                self.incoming.extend(bytes);
+                Ok(())
+            })?;
+            self.read_cut = cut;
+        }
+        let count = buf.len().min(self.incoming.len());
+        for (slot, byte) in buf.iter_mut().zip(self.incoming.drain(..count)) {
+            *slot = byte;
+        }
+        Ok(count)
+    }
+}
+
+impl<T: Write> FaultyTransport<T> {
+    /// Forwards every complete frame buffered in `outgoing`.
+    fn forward_complete_frames(&mut self) -> io::Result<()> {
+        while !self.write_cut {
+            let Some(header) = self.outgoing.first_chunk::<HEADER_LEN>() else {
+                break;
+            };
+            let len = (u32::from_le_bytes(*header) & PAYLOAD_MASK) as usize;
+            if self.outgoing.len() < HEADER_LEN + len {
+                break;
+            }
+            let frame: Vec<u8> = self.outgoing.drain(..HEADER_LEN + len).collect();
+            let index = self.frames_written;
+            self.frames_written += 1;
+            let inner = &mut self.inner;
+            self.write_cut = apply_faults(&self.write_faults, index, frame, |bytes| {
+                inner.write_all(bytes)
+            })?;
+        }
+        if self.write_cut {
+            self.outgoing.clear();
+        }
+        Ok(())
+    }
+}
+
+impl<T: Write> Write for FaultyTransport<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.write_cut {
+            return Err(peer_gone());
+        }
+        self.outgoing.extend_from_slice(buf);
+        self.forward_complete_frames()?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.write_cut {
+            return Err(peer_gone());
+        }
+        self.inner.flush()
+    }
+}
+
+/// Delivers `frame` through `deliver` after applying every fault registered
+/// for `index`. Returns `true` once a truncation has cut the stream.
+fn apply_faults(
+    faults: &[(usize, FrameFault)],
+    index: usize,
+    mut frame: Vec<u8>,
+    mut deliver: impl FnMut(&[u8]) -> io::Result<()>,
+) -> io::Result<bool> {
+    let mut copies = 1;
+    let mut cut = false;
+    for &(_, fault) in faults.iter().filter(|(target, _)| *target == index) {
+        match fault {
+            FrameFault::Truncate { keep } => {
+                frame.truncate(keep);
+                cut = true;
+            }
+            FrameFault::Delay(delay) => std::thread::sleep(delay),
+            FrameFault::Duplicate => copies = 2,
+            FrameFault::Corrupt { offset, mask } => {
+                if let Some(byte) = frame.get_mut(offset) {
+                    *byte ^= mask;
+                }
+            }
+        }
+    }
+    if cut {
+        copies = 1;
+    }
+    for _ in 0..copies {
+        deliver(&frame)?;
+    }
+    Ok(cut)
+}
+
+fn peer_gone() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::BrokenPipe,
+        "injected fault: peer closed the connection",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::envelope::MessageCode;
+    use crate::multiplex::{recv_msg, send_msg};
+
+    fn wire(frames: &[(MessageCode, &[u8])]) -> Vec<u8> {
+        let mut wire = Vec::new();
+        for (code, payload) in frames {
+            send_msg(&mut wire, *code, payload).unwrap();
+        }
+        wire
+    }
+
+    #[test]
+    fn untouched_frames_pass_through() {
+        let bytes = wire(&[(MessageCode::Data, b"abc"), (MessageCode::Info, b"hi\n")]);
+        let mut transport = FaultyTransport::new(Cursor::new(bytes.clone()));
+        let mut read_back = Vec::new();
+        transport.read_to_end(&mut read_back).unwrap();
+        assert_eq!(read_back, bytes);
+        assert_eq!(transport.frames_read(), 2);
+
+        let mut transport = FaultyTransport::new(Vec::new());
+        for chunk in bytes.chunks(3) {
+            transport.write_all(chunk).unwrap();
+        }
+        assert_eq!(transport.frames_written(), 2);
+        assert_eq!(transport.into_inner(), bytes);
+    }
+
+    #[test]
+    fn read_faults_apply_to_the_selected_frame() {
+        let bytes = wire(&[(MessageCode::Data, b"one"), (MessageCode::Data, b"two")]);
+
+        let mut duplicated =
+            FaultyTransport::new(Cursor::new(bytes.clone())).on_read(1, FrameFault::Duplicate);
+        let payloads: Vec<Vec<u8>> = (0..3)
+            .map(|_| recv_msg(&mut duplicated).unwrap().payload().to_vec())
+            .collect();
+        assert_eq!(
+            payloads,
+            [b"one".to_vec(), b"two".to_vec(), b"two".to_vec()]
+        );
+
+        let mut corrupted = FaultyTransport::new(Cursor::new(bytes.clone())).on_read(
+            0,
+            FrameFault::Corrupt {
+                offset: HEADER_LEN,
+                mask: 0x20,
+            },
+        );
+        assert_eq!(recv_msg(&mut corrupted).unwrap().payload(), b"One");
+
+        let mut truncated =
+            FaultyTransport::new(Cursor::new(bytes)).on_read(1, FrameFault::Truncate { keep: 2 });
+        assert_eq!(recv_msg(&mut truncated).unwrap().payload(), b"one");
+        let error = recv_msg(&mut truncated).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn write_truncation_breaks_the_pipe() {
+        let bytes = wire(&[(MessageCode::Data, b"one"), (MessageCode::Data, b"two")]);
+        let mut transport =
+            FaultyTransport::new(Vec::new()).on_write(0, FrameFault::Truncate { keep: 5 });
+        transport.write_all(&bytes).unwrap();
+        assert_eq!(transport.get_ref().as_slice(), &bytes[..5]);
+
+        let error = transport.write_all(&bytes).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::BrokenPipe);
+        assert!(transport.flush().is_err());
+    }
+}
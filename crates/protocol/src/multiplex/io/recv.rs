@@ -3,6 +3,7 @@ use std::io::{self, Read};
 use logging::debug_log;
 
 use crate::envelope::{HEADER_LEN, MessageCode, MessageHeader};
+use crate::frame_dump::{self, FrameDirection};
 
 use super::super::frame::MessageFrame;
 use super::super::helpers::{decode_header, read_payload, read_payload_into};
@@ -17,6 +18,7 @@ pub fn recv_msg<R: Read>(reader: &mut R) -> io::Result<MessageFrame> {
     debug_log!(Io, 3, "mux recv: code={:?} len={}", header.code(), len);
 
     let payload = read_payload(reader, len)?;
+    frame_dump::record(FrameDirection::Recv, header.code(), len, &payload);
 
     MessageFrame::new(header.code(), payload)
 }
@@ -33,6 +35,7 @@ pub fn recv_msg_into<R: Read>(reader: &mut R, buffer: &mut Vec<u8>) -> io::Resul
     let len = header.payload_len_usize();
 
     read_payload_into(reader, buffer, len)?;
+    frame_dump::record(FrameDirection::Recv, header.code(), len, buffer);
 
     Ok(header.code())
 }
@@ -4,6 +4,7 @@ use std::slice;
 use logging::debug_log;
 
 use crate::envelope::{HEADER_LEN, MessageCode, MessageHeader};
+use crate::frame_dump::{self, FrameDirection};
 
 use super::super::frame::MessageFrame;
 use super::super::helpers::{ensure_payload_length, map_envelope_error_for_input};
@@ -79,6 +80,7 @@ pub fn send_msgs_vectored<W: Write>(
         let payload_len = ensure_payload_length(payload.len())?;
         let header =
             MessageHeader::new(*code, payload_len).map_err(map_envelope_error_for_input)?;
+        frame_dump::record(FrameDirection::Send, *code, payload.len(), payload);
         headers.push(header);
     }
 
@@ -126,6 +128,7 @@ fn write_validated_message<W: Write + ?Sized>(
     header: MessageHeader,
     payload: &[u8],
 ) -> io::Result<()> {
+    frame_dump::record(FrameDirection::Send, header.code(), payload.len(), payload);
     let header_bytes = header.encode();
 
     if payload.is_empty() {
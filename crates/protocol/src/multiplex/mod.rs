@@ -20,6 +20,8 @@
 mod borrowed;
 #[cfg(feature = "async")]
 mod codec;
+#[cfg(any(test, feature = "test-support"))]
+mod fault;
 mod frame;
 mod helpers;
 mod io;
@@ -32,6 +34,8 @@ mod tests;
 pub use borrowed::{BorrowedMessageFrame, BorrowedMessageFrames};
 #[cfg(feature = "async")]
 pub use codec::MultiplexCodec;
+#[cfg(any(test, feature = "test-support"))]
+pub use fault::{FaultyTransport, FrameFault};
 pub use frame::MessageFrame;
 #[cfg(feature = "tokio-transfer")]
 pub use io::recv_msg_into_async;
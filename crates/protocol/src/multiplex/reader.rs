@@ -9,6 +9,7 @@ use std::io::{self, Read};
 
 use super::helpers::{map_envelope_error, read_payload_into};
 use crate::envelope::{HEADER_LEN, MessageCode, MessageHeader};
+use crate::frame_dump::{self, FrameDirection};
 
 /// Type alias for the message handler callback.
 type MessageHandler = Box<dyn FnMut(MessageCode, &[u8]) + Send>;
@@ -200,6 +201,7 @@ impl<R: Read> MplexReader<R> {
         self.buffer.clear();
         self.pos = 0;
         read_payload_into(&mut self.inner, &mut self.buffer, len)?;
+        frame_dump::record(FrameDirection::Recv, code, len, &self.buffer);
 
         match code {
             MessageCode::Data => Ok(true),
@@ -0,0 +1,174 @@
+//! Regression tests driving the multiplex layer through injected frame faults.
+//!
+//! Each scenario damages one frame on its way between two peers and asserts
+//! that the side reading or writing it fails with the documented error kind,
+//! or recovers, rather than blocking forever.
+
+use std::io::{Cursor, ErrorKind, Read};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use protocol::{
+    FaultyTransport, FrameFault, MESSAGE_HEADER_LEN, MessageCode, MplexReader, MplexWriter,
+    recv_msg, send_msg,
+};
+
+fn wire(frames: &[(MessageCode, &[u8])]) -> Vec<u8> {
+    let mut wire = Vec::new();
+    for (code, payload) in frames {
+        send_msg(&mut wire, *code, payload).expect("encode frame");
+    }
+    wire
+}
+
+fn reader_with(bytes: Vec<u8>, frame: usize, fault: FrameFault) -> MplexReader<impl Read> {
+    MplexReader::new(FaultyTransport::new(Cursor::new(bytes)).on_read(frame, fault))
+}
+
+#[test]
+fn truncated_header_reports_unexpected_eof() {
+    let bytes = wire(&[
+        (MessageCode::Data, b"first"),
+        (MessageCode::Data, b"second"),
+    ]);
+    let mut reader = reader_with(bytes, 1, FrameFault::Truncate { keep: 3 });
+
+    let mut received = Vec::new();
+    let error = reader.read_to_end(&mut received).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(received, b"first");
+}
+
+#[test]
+fn truncated_payload_reports_unexpected_eof() {
+    let bytes = wire(&[(MessageCode::Data, b"payload")]);
+    let mut reader = reader_with(
+        bytes,
+        0,
+        FrameFault::Truncate {
+            keep: MESSAGE_HEADER_LEN + 3,
+        },
+    );
+
+    let mut buf = [0u8; 16];
+    let error = reader.read(&mut buf).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn corrupted_tag_reports_invalid_data() {
+    let bytes = wire(&[(MessageCode::Data, b"data")]);
+    // Clearing the low tag bits drops the tag below MPLEX_BASE.
+    let mut transport = FaultyTransport::new(Cursor::new(bytes)).on_read(
+        0,
+        FrameFault::Corrupt {
+            offset: 3,
+            mask: 0x07,
+        },
+    );
+
+    let error = recv_msg(&mut transport).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn corrupted_length_overruns_into_eof() {
+    let bytes = wire(&[(MessageCode::Data, b"short")]);
+    let mut transport = FaultyTransport::new(Cursor::new(bytes)).on_read(
+        0,
+        FrameFault::Corrupt {
+            offset: 1,
+            mask: 0x01,
+        },
+    );
+
+    let error = recv_msg(&mut transport).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn duplicated_data_frame_is_delivered_twice() {
+    let bytes = wire(&[(MessageCode::Data, b"ab"), (MessageCode::Data, b"cd")]);
+    let mut reader = reader_with(bytes, 0, FrameFault::Duplicate);
+
+    let mut received = Vec::new();
+    let error = reader.read_to_end(&mut received).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(received, b"ababcd");
+}
+
+#[test]
+fn duplicated_out_of_band_frame_reaches_the_handler_twice() {
+    let bytes = wire(&[
+        (MessageCode::Warning, b"careful\n"),
+        (MessageCode::Data, b"x"),
+    ]);
+    let mut reader = reader_with(bytes, 0, FrameFault::Duplicate);
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&seen);
+    reader.set_message_handler(move |code, _| sink.lock().unwrap().push(code));
+
+    let mut buf = [0u8; 1];
+    assert_eq!(reader.read(&mut buf).unwrap(), 1);
+    assert_eq!(*seen.lock().unwrap(), [MessageCode::Warning; 2]);
+}
+
+#[test]
+fn delayed_frame_still_arrives() {
+    let delay = Duration::from_millis(20);
+    let bytes = wire(&[(MessageCode::Data, b"late")]);
+    let mut transport =
+        FaultyTransport::new(Cursor::new(bytes)).on_read(0, FrameFault::Delay(delay));
+
+    let started = Instant::now();
+    let frame = recv_msg(&mut transport).expect("delayed frame");
+    assert!(started.elapsed() >= delay);
+    assert_eq!(frame.payload(), b"late");
+}
+
+#[test]
+fn writer_fails_once_the_peer_hangs_up() {
+    let transport = FaultyTransport::new(Vec::new()).on_write(1, FrameFault::Truncate { keep: 0 });
+    let mut writer = MplexWriter::new(transport);
+
+    writer
+        .write_info("starting\n")
+        .expect("first frame goes through");
+    writer.write_info("cut\n").expect("cut frame is swallowed");
+    let error = writer.write_info("after\n").unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::BrokenPipe);
+
+    let sent = writer.into_inner().into_inner();
+    assert_eq!(
+        recv_msg(&mut Cursor::new(sent)).unwrap().payload(),
+        b"starting\n"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn receiver_on_a_socket_sees_eof_when_the_sender_stops_mid_frame() {
+    use std::os::unix::net::UnixStream;
+
+    let (sender, receiver) = UnixStream::pair().expect("socket pair");
+    receiver
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .expect("read timeout");
+
+    let writer = std::thread::spawn(move || {
+        let mut peer = FaultyTransport::new(sender).on_write(
+            0,
+            FrameFault::Truncate {
+                keep: MESSAGE_HEADER_LEN + 2,
+            },
+        );
+        send_msg(&mut peer, MessageCode::Data, b"never finished").expect("cut write");
+        // Dropping the transport closes the socket behind the partial frame.
+    });
+
+    let mut reader = MplexReader::new(receiver);
+    let mut buf = [0u8; 32];
+    let error = reader.read(&mut buf).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    writer.join().unwrap();
+}
@@ -0,0 +1,218 @@
+//! Blocking `std::io` views of tokio `AsyncRead` / `AsyncWrite` streams.
+//!
+//! The transfer engine speaks `std::io::Read` / `std::io::Write`. An async
+//! service that accepts connections on its own tokio runtime can still host
+//! an rsync endpoint by handing the engine these adapters: each
+//! [`BlockingReader::read`] and [`BlockingWriter::write`] parks the calling
+//! thread on the runtime [`Handle`] until the underlying async operation
+//! completes. The synchronous engine runs unchanged; these adapters let it be
+//! called from async code but do not make its I/O asynchronous.
+//!
+//! [`run_blocking`] packages the usual wiring: it moves both halves onto
+//! tokio's blocking pool, runs the synchronous body there, shuts the write
+//! half down afterwards, and resolves once the body returns.
+//!
+//! # Limits
+//!
+//! This is a bridge, not async I/O. Every byte still moves through a
+//! blocking call, and each hosted transfer pins one blocking-pool OS thread
+//! for its whole lifetime - including the time it spends waiting on the
+//! peer. Concurrent transfers are therefore bounded by the runtime's
+//! `max_blocking_threads` (512 by default); further [`run_blocking`] calls
+//! queue until a thread frees up. Only socket readiness is multiplexed by the
+//! reactor.
+//!
+//! # Invariants
+//!
+//! - The adapters must only be driven from a thread outside the runtime's
+//!   async workers (a `spawn_blocking` task or a plain OS thread);
+//!   [`Handle::block_on`] panics when called from within an async context.
+//! - A closed peer surfaces as `Ok(0)` from `read`, matching `std::io::Read`.
+
+use std::io::{self, Read, Write};
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::runtime::Handle;
+
+/// `std::io::Read` over a tokio [`AsyncRead`], blocking on a runtime handle.
+pub struct BlockingReader<R> {
+    inner: R,
+    handle: Handle,
+}
+
+impl<R> BlockingReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Wraps `inner`, driving its reads on `handle`.
+    pub const fn new(inner: R, handle: Handle) -> Self {
+        Self { inner, handle }
+    }
+
+    /// Returns the wrapped async reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> Read for BlockingReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.handle.block_on(self.inner.read(buf))
+    }
+}
+
+/// `std::io::Write` over a tokio [`AsyncWrite`], blocking on a runtime handle.
+pub struct BlockingWriter<W> {
+    inner: W,
+    handle: Handle,
+}
+
+impl<W> BlockingWriter<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    /// Wraps `inner`, driving its writes on `handle`.
+    pub const fn new(inner: W, handle: Handle) -> Self {
+        Self { inner, handle }
+    }
+
+    /// Flushes and shuts down the write half, signalling EOF to the peer.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error reported by the underlying stream.
+    pub fn shutdown(&mut self) -> io::Result<()> {
+        self.handle.block_on(self.inner.shutdown())
+    }
+
+    /// Returns the wrapped async writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W> Write for BlockingWriter<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.handle.block_on(self.inner.write(buf))
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.handle.block_on(self.inner.write_all(buf))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.handle.block_on(self.inner.flush())
+    }
+}
+
+/// Runs a synchronous body against async stream halves on tokio's blocking
+/// pool.
+///
+/// `body` receives blocking views of `reader` and `writer`. Once it returns,
+/// the write half is shut down so the peer observes EOF, and the body's
+/// result is returned; a body error takes precedence over a shutdown error.
+///
+/// Must be awaited from within a tokio runtime.
+///
+/// The body occupies one blocking-pool thread until it returns, so a
+/// long-lived transfer holds that thread for its full duration and the
+/// number of concurrent calls is capped by the runtime's
+/// `max_blocking_threads`; see the module-level limits.
+///
+/// # Errors
+///
+/// Returns the body's error, the shutdown error, or an error describing a
+/// panicked or cancelled blocking task.
+pub async fn run_blocking<R, W, T, F>(reader: R, writer: W, body: F) -> io::Result<T>
+where
+    R: AsyncRead + Unpin + Send + 'static,
+    W: AsyncWrite + Unpin + Send + 'static,
+    T: Send + 'static,
+    F: FnOnce(&mut BlockingReader<R>, &mut BlockingWriter<W>) -> io::Result<T> + Send + 'static,
+{
+    let handle = Handle::current();
+    let task = tokio::task::spawn_blocking(move || {
+        let mut reader = BlockingReader::new(reader, handle.clone());
+        let mut writer = BlockingWriter::new(writer, handle);
+        let result = body(&mut reader, &mut writer);
+        let shutdown = writer.shutdown();
+        let value = result?;
+        shutdown?;
+        Ok(value)
+    });
+    task.await
+        .map_err(|error| io::Error::other(format!("blocking transfer task failed: {error}")))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .enable_all()
+            .build()
+            .expect("runtime")
+    }
+
+    #[test]
+    fn body_reads_and_writes_through_the_adapters() {
+        runtime().block_on(async {
+            let (local, mut remote) = tokio::io::duplex(64);
+            let (read_half, write_half) = tokio::io::split(local);
+
+            remote.write_all(b"ping").await.unwrap();
+            let served = tokio::spawn(run_blocking(read_half, write_half, |reader, writer| {
+                let mut buf = [0u8; 4];
+                reader.read_exact(&mut buf)?;
+                writer.write_all(b"pong")?;
+                Ok(buf)
+            }));
+
+            let mut reply = Vec::new();
+            remote.read_to_end(&mut reply).await.unwrap();
+            assert_eq!(reply, b"pong", "shutdown must deliver EOF after the reply");
+            assert_eq!(&served.await.unwrap().unwrap(), b"ping");
+        });
+    }
+
+    #[test]
+    fn closed_peer_reads_as_eof() {
+        runtime().block_on(async {
+            let (local, remote) = tokio::io::duplex(64);
+            drop(remote);
+            let (read_half, write_half) = tokio::io::split(local);
+
+            let read = run_blocking(read_half, write_half, |reader, _writer| {
+                let mut buf = [0u8; 8];
+                reader.read(&mut buf)
+            })
+            .await;
+
+            assert_eq!(read.unwrap(), 0);
+        });
+    }
+
+    #[test]
+    fn body_error_is_returned() {
+        runtime().block_on(async {
+            let (local, _remote) = tokio::io::duplex(64);
+            let (read_half, write_half) = tokio::io::split(local);
+
+            let error = run_blocking(read_half, write_half, |_reader, _writer| {
+                Err::<(), _>(io::Error::new(io::ErrorKind::InvalidData, "bad"))
+            })
+            .await
+            .unwrap_err();
+
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        });
+    }
+}
@@ -130,7 +130,15 @@ where
 
     let remote_compatibility_flags = if negotiated_protocol.uses_binary_negotiation() {
         let mut first = [0u8; 1];
-        match stream.read(&mut first)? {
+        // upstream: io.c:read_buf() - an interrupted read is retried, not
+        // treated as end of stream.
+        let read = loop {
+            match stream.read(&mut first) {
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                result => break result?,
+            }
+        };
+        match read {
             0 => CompatibilityFlags::EMPTY,
             read @ 1 => {
                 let mut chained = (&first[..read]).chain(&mut stream);
@@ -24,6 +24,8 @@
 //!   sessions like any other stream.
 //! - [`SessionHandshake`] builds on top of both flows to expose a high-level
 //!   session negotiation entry point.
+//! - [`loopback`] connects two in-process endpoints through in-memory pipes,
+//!   so a client and a server can speak the wire protocol without sockets.
 //!
 //! Each module is structured as a facade over the `protocol` crate, making
 //! it possible to slot different transports (SSH stdio vs TCP daemon) behind the
@@ -66,12 +68,17 @@
 //! - `core` for the message helpers used when transport-level errors are
 //!   reported to the user.
 
+/// Blocking `std::io` adapters over tokio `AsyncRead` / `AsyncWrite` streams.
+#[cfg(feature = "tokio")]
+pub mod async_bridge;
 mod binary;
 /// In-process channel adapters bridging `tokio::sync::mpsc` to `AsyncRead` / `AsyncWrite`.
 #[cfg(feature = "async-ssh")]
 pub mod channel_adapter;
 mod daemon;
 mod handshake_util;
+/// In-memory duplex pipes connecting in-process client and server sessions.
+pub mod loopback;
 mod negotiation;
 mod session;
 /// SSH transport implementations and helpers.
@@ -86,6 +93,7 @@ pub use daemon::{
     negotiate_legacy_daemon_session_from_stream, negotiate_legacy_daemon_session_with_sniffer,
 };
 pub use handshake_util::{RemoteProtocolAdvertisement, local_cap_reduced_protocol};
+pub use loopback::{LoopbackReader, LoopbackStream, LoopbackWriter, loopback};
 pub use negotiation::{
     BufferedCopyTooSmall, CopyToSliceError, NegotiatedStream, NegotiatedStreamParts,
     TryMapInnerError, sniff_negotiation_stream, sniff_negotiation_stream_with_sniffer,
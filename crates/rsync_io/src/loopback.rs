@@ -0,0 +1,259 @@
+//! In-memory duplex pipes connecting two in-process rsync endpoints.
+//!
+//! [`loopback`] returns a connected pair of [`LoopbackStream`]s: bytes written
+//! to one end are read from the other, in both directions. A native client
+//! session and a native server session can run against the two ends on
+//! separate threads and exchange the real wire protocol - handshake, compat
+//! flags, multiplexed file list and deltas - without sockets or a subprocess.
+//!
+//! oc-rsync extension: upstream always connects client and server through a
+//! pipe to a child process or a socket.
+//!
+//! # Invariants
+//!
+//! - Each direction buffers at most [`LOOPBACK_CAPACITY`] bytes; a writer
+//!   blocks until the reader drains room, so a stalled peer exerts
+//!   backpressure instead of growing memory without bound.
+//! - Dropping a write half (or a whole stream) signals EOF: once the buffer
+//!   drains, the peer's `read` returns `Ok(0)`.
+//! - Dropping a read half makes further writes from the peer fail with
+//!   [`io::ErrorKind::BrokenPipe`], matching a closed pipe.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::io::{Read, Write};
+//!
+//! let (mut client, server) = rsync_io::loopback();
+//! let (mut server_read, mut server_write) = server.split();
+//!
+//! let echo = std::thread::spawn(move || {
+//!     let mut buf = [0u8; 4];
+//!     server_read.read_exact(&mut buf).unwrap();
+//!     server_write.write_all(&buf).unwrap();
+//! });
+//!
+//! client.write_all(b"ping").unwrap();
+//! let mut reply = [0u8; 4];
+//! client.read_exact(&mut reply).unwrap();
+//! assert_eq!(&reply, b"ping");
+//! echo.join().unwrap();
+//! ```
+
+use std::collections::VecDeque;
+use std::io::{self, Read, Write};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+/// Bytes buffered in each direction before a writer blocks.
+pub const LOOPBACK_CAPACITY: usize = 256 * 1024;
+
+/// Creates a connected pair of in-memory duplex streams.
+#[must_use]
+pub fn loopback() -> (LoopbackStream, LoopbackStream) {
+    let a_to_b = Arc::new(Pipe::default());
+    let b_to_a = Arc::new(Pipe::default());
+    let a = LoopbackStream {
+        reader: LoopbackReader {
+            pipe: Arc::clone(&b_to_a),
+        },
+        writer: LoopbackWriter {
+            pipe: Arc::clone(&a_to_b),
+        },
+    };
+    let b = LoopbackStream {
+        reader: LoopbackReader { pipe: a_to_b },
+        writer: LoopbackWriter { pipe: b_to_a },
+    };
+    (a, b)
+}
+
+#[derive(Default)]
+struct PipeState {
+    buf: VecDeque<u8>,
+    writer_closed: bool,
+    reader_closed: bool,
+}
+
+/// One direction of a loopback connection.
+#[derive(Default)]
+struct Pipe {
+    state: Mutex<PipeState>,
+    changed: Condvar,
+}
+
+impl Pipe {
+    fn lock(&self) -> MutexGuard<'_, PipeState> {
+        // A panicking peer leaves the buffer itself consistent; keep serving.
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn wait<'a>(&self, guard: MutexGuard<'a, PipeState>) -> MutexGuard<'a, PipeState> {
+        self.changed
+            .wait(guard)
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// One end of a [`loopback`] connection.
+pub struct LoopbackStream {
+    reader: LoopbackReader,
+    writer: LoopbackWriter,
+}
+
+impl LoopbackStream {
+    /// Splits the stream into independently owned read and write halves.
+    #[must_use]
+    pub fn split(self) -> (LoopbackReader, LoopbackWriter) {
+        (self.reader, self.writer)
+    }
+}
+
+impl Read for LoopbackStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.reader.read(buf)
+    }
+}
+
+impl Write for LoopbackStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Read half of a [`LoopbackStream`].
+pub struct LoopbackReader {
+    pipe: Arc<Pipe>,
+}
+
+impl Read for LoopbackReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let mut state = self.pipe.lock();
+        while state.buf.is_empty() {
+            if state.writer_closed {
+                return Ok(0);
+            }
+            state = self.pipe.wait(state);
+        }
+        let len = buf.len().min(state.buf.len());
+        for (dst, src) in buf.iter_mut().zip(state.buf.drain(..len)) {
+            *dst = src;
+        }
+        self.pipe.changed.notify_all();
+        Ok(len)
+    }
+}
+
+impl Drop for LoopbackReader {
+    fn drop(&mut self) {
+        let mut state = self.pipe.lock();
+        state.reader_closed = true;
+        state.buf.clear();
+        self.pipe.changed.notify_all();
+    }
+}
+
+/// Write half of a [`LoopbackStream`].
+pub struct LoopbackWriter {
+    pipe: Arc<Pipe>,
+}
+
+impl Write for LoopbackWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let mut state = self.pipe.lock();
+        loop {
+            if state.reader_closed {
+                return Err(io::Error::new(
+                    io::ErrorKind::BrokenPipe,
+                    "loopback peer closed its read half",
+                ));
+            }
+            if state.buf.len() < LOOPBACK_CAPACITY {
+                break;
+            }
+            state = self.pipe.wait(state);
+        }
+        let len = buf.len().min(LOOPBACK_CAPACITY - state.buf.len());
+        state.buf.extend(&buf[..len]);
+        self.pipe.changed.notify_all();
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for LoopbackWriter {
+    fn drop(&mut self) {
+        self.pipe.lock().writer_closed = true;
+        self.pipe.changed.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn bytes_flow_in_both_directions() {
+        let (mut a, mut b) = loopback();
+        a.write_all(b"to b").unwrap();
+        b.write_all(b"to a").unwrap();
+
+        let mut buf = [0u8; 4];
+        b.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"to b");
+        a.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"to a");
+    }
+
+    #[test]
+    fn dropped_writer_reads_as_eof_after_drain() {
+        let (a, b) = loopback();
+        let (_a_read, mut a_write) = a.split();
+        a_write.write_all(b"tail").unwrap();
+        drop(a_write);
+
+        let mut received = Vec::new();
+        let (mut b_read, _b_write) = b.split();
+        b_read.read_to_end(&mut received).unwrap();
+        assert_eq!(received, b"tail");
+    }
+
+    #[test]
+    fn write_to_dropped_reader_is_broken_pipe() {
+        let (mut a, b) = loopback();
+        drop(b);
+        let err = a.write(b"x").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+    }
+
+    #[test]
+    fn full_pipe_blocks_until_drained() {
+        let (a, b) = loopback();
+        let payload = vec![0x5au8; 3 * LOOPBACK_CAPACITY + 17];
+        let expected = payload.clone();
+
+        let (_a_read, mut a_write) = a.split();
+        let sender = thread::spawn(move || a_write.write_all(&payload));
+
+        let (mut b_read, _b_write) = b.split();
+        let mut received = Vec::new();
+        b_read.read_to_end(&mut received).unwrap();
+        sender.join().unwrap().unwrap();
+        assert_eq!(received, expected);
+    }
+}
@@ -1,7 +1,7 @@
 use std::ffi::{OsStr, OsString};
 use std::io;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
-use std::process::{Command, Stdio};
+use std::process::{ChildStdout, Command, Stdio};
 use std::str::FromStr;
 use std::time::Duration;
 
@@ -68,6 +68,82 @@ fn program_forces_blocking_io(basename: &str) -> bool {
     matches!(basename, "rsh" | "remsh")
 }
 
+/// Returns the basename of a remote-shell program, lowercased on Windows.
+///
+/// Handles both forward slash (Unix) and backslash (Windows) separators.
+fn remote_shell_basename(program: &OsStr) -> String {
+    let program = program.to_string_lossy();
+    let basename = program.rsplit(['/', '\\']).next().unwrap_or(&program);
+    if cfg!(windows) {
+        basename.to_ascii_lowercase()
+    } else {
+        basename.to_string()
+    }
+}
+
+/// Resolves rsync's tri-state `--blocking-io`/`--no-blocking-io` preference
+/// for an arbitrary remote-shell program.
+///
+/// An explicit `Some(value)` always wins; `None` enables blocking I/O only for
+/// the `rsh`/`remsh` shells. Shared by [`SshCommand`] and callers that spawn
+/// the remote shell themselves (daemon-over-rsh).
+///
+/// upstream: main.c:600-601 `do_cmd()`.
+#[must_use]
+pub fn resolve_remote_shell_blocking_io(program: &OsStr, requested: Option<bool>) -> bool {
+    match requested {
+        Some(explicit) => explicit,
+        None => program_forces_blocking_io(&remote_shell_basename(program)),
+    }
+}
+
+/// Installs the remote-shell child's stdout pipe on `command`, applying the
+/// resolved blocking-I/O mode to the child's end before it is spawned.
+///
+/// On Unix the pipe is created here rather than through `Stdio::piped()` so
+/// the child's write end can be forced blocking when `blocking_io` is set,
+/// exactly as upstream does between `fork()` and `execvp()`. The returned
+/// handle is the parent's read end; `command` keeps the child's end until it
+/// is dropped, so callers must drop the command once the child is spawned to
+/// observe EOF. Other platforms have no per-descriptor blocking mode and fall
+/// back to `Stdio::piped()`, returning `None` so the caller takes the child's
+/// own stdout handle.
+///
+/// upstream: pipe.c:80-82 `piped_child()` - `if (blocking_io > 0)
+/// set_blocking(STDOUT_FILENO);`
+///
+/// # Errors
+///
+/// Returns the underlying [`io::Error`] when the pipe cannot be created or
+/// its mode cannot be changed.
+pub fn pipe_remote_shell_stdout(
+    command: &mut Command,
+    blocking_io: bool,
+) -> io::Result<Option<ChildStdout>> {
+    #[cfg(unix)]
+    {
+        use std::os::fd::OwnedFd;
+        use std::os::unix::net::UnixStream;
+
+        let (reader, writer) = io::pipe()?;
+        let writer = OwnedFd::from(writer);
+        if blocking_io {
+            // `O_NONBLOCK` lives on the open-file description, so toggling it
+            // through a duplicate reaches the descriptor the child inherits.
+            UnixStream::from(writer.try_clone()?).set_nonblocking(false)?;
+        }
+        command.stdout(Stdio::from(writer));
+        Ok(Some(ChildStdout::from(OwnedFd::from(reader))))
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = blocking_io;
+        command.stdout(Stdio::piped());
+        Ok(None)
+    }
+}
+
 /// Builder used to configure and spawn an SSH subprocess.
 #[derive(Clone, Debug)]
 pub struct SshCommand {
@@ -441,21 +517,20 @@ impl SshCommand {
         }
 
         // upstream: main.c:600-601 do_cmd() forces blocking_io for rsh/remsh
-        // when unset; pipe.c:80-82 then sets the child stdout blocking. Our
-        // `Stdio::piped()` child descriptors are already unconditionally
-        // blocking, so this only records the resolved mode for diagnostics.
+        // when unset; pipe.c:80-82 then sets the child stdout blocking.
+        let blocking_io = self.resolved_blocking_io();
         if logging::debug_gte(logging::DebugFlag::Cmd, 2) {
             debug_log!(
                 Connect,
                 2,
                 "remote-shell blocking_io resolved to {}",
-                self.resolved_blocking_io()
+                blocking_io
             );
         }
 
         let mut command = Command::new(&program);
         command.stdin(Stdio::piped());
-        command.stdout(Stdio::piped());
+        let parent_stdout = pipe_remote_shell_stdout(&mut command, blocking_io)?;
         command.args(args.iter());
 
         for (key, value) in &self.envs {
@@ -469,6 +544,9 @@ impl SshCommand {
         let parent_socketpair_end = configure_stderr_channel(&mut command);
 
         let mut child = command.spawn()?;
+        // Release the child's stdout end held by the command so the parent
+        // observes EOF when the child exits.
+        drop(command);
 
         debug_log!(Connect, 2, "ssh process spawned successfully");
 
@@ -478,12 +556,15 @@ impl SshCommand {
                 "ssh command did not expose a writable stdin",
             )
         })?;
-        let stdout = child.stdout.take().ok_or_else(|| {
-            io::Error::new(
-                io::ErrorKind::BrokenPipe,
-                "ssh command did not expose a readable stdout",
-            )
-        })?;
+        let stdout = match parent_stdout {
+            Some(stdout) => stdout,
+            None => child.stdout.take().ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::BrokenPipe,
+                    "ssh command did not expose a readable stdout",
+                )
+            })?,
+        };
 
         let stderr_channel = build_stderr_channel(parent_socketpair_end, child.stderr.take());
 
@@ -687,14 +768,7 @@ impl SshCommand {
     /// upstream: main.c:564-567 `do_cmd()` - `if ((t = strrchr(cmd, '/')) !=
     /// NULL) t++; else t = cmd;`.
     fn program_basename(&self) -> String {
-        let program = self.program.to_string_lossy();
-        // Handle both forward slash (Unix) and backslash (Windows) path separators.
-        let basename = program.rsplit(['/', '\\']).next().unwrap_or(&program);
-        if cfg!(windows) {
-            basename.to_ascii_lowercase()
-        } else {
-            basename.to_string()
-        }
+        remote_shell_basename(&self.program)
     }
 
     /// Checks whether the configured program appears to be an SSH client.
@@ -713,21 +787,16 @@ impl SshCommand {
     /// `rsh`/`remsh` shells - which mishandle a non-blocking child stdout - and
     /// leaves every other program (`ssh`, custom `-e` wrappers) non-blocking.
     ///
-    /// oc-rsync spawns the remote shell with `std::process::Command` and
-    /// `Stdio::piped()`, whose child pipe descriptors are unconditionally
-    /// blocking on every platform, so the forced-blocking outcome already holds
-    /// by construction; this resolution keeps the semantic state faithful to
-    /// upstream. `blocking_io` is a purely local child-fd concern and is never
-    /// forwarded on the wire (upstream `server_options()` does not emit it).
+    /// [`SshCommand::spawn`] applies the result to the child's stdout through
+    /// [`pipe_remote_shell_stdout`]. `blocking_io` is a purely local child-fd
+    /// concern and is never forwarded on the wire (upstream `server_options()`
+    /// does not emit it).
     ///
     /// upstream: main.c:600-601 `do_cmd()` - `if (blocking_io < 0 && (strcmp(t,
     /// "rsh") == 0 || strcmp(t, "remsh") == 0)) blocking_io = 1;` and
     /// pipe.c:80-82 where `blocking_io > 0` sets the child stdout blocking.
     pub(crate) fn resolved_blocking_io(&self) -> bool {
-        match self.blocking_io {
-            Some(explicit) => explicit,
-            None => program_forces_blocking_io(&self.program_basename()),
-        }
+        resolve_remote_shell_blocking_io(&self.program, self.blocking_io)
     }
 
     /// Checks whether any existing option already specifies the `-c` cipher flag.
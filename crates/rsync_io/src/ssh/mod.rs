@@ -107,7 +107,9 @@ mod stall;
 pub use async_stderr_drain::{ASYNC_STDERR_BUFFER_CAP, AsyncStderrDrain, RingBuffer};
 #[cfg(feature = "async-ssh")]
 pub use async_transport::AsyncSshTransport;
-pub use builder::{SshAddressFamily, SshCommand};
+pub use builder::{
+    SshAddressFamily, SshCommand, pipe_remote_shell_stdout, resolve_remote_shell_blocking_io,
+};
 pub use connect::{
     DEFAULT_CONNECT_TIMEOUT, DEFAULT_KEEPALIVE_INTERVAL, DEFAULT_KEEPALIVE_MAX_FAILURES,
     KeepAliveConfig, SshConnectConfig,
@@ -2,6 +2,7 @@ use super::SshCommand;
 #[cfg(unix)]
 use super::SshConnection;
 use super::builder::SshAddressFamily;
+use super::{pipe_remote_shell_stdout, resolve_remote_shell_blocking_io};
 use std::ffi::{OsStr, OsString};
 #[cfg(unix)]
 use std::io::{Read, Write};
@@ -2525,3 +2526,47 @@ fn explicit_blocking_io_forces_for_ssh() {
     command.set_blocking_io(Some(true));
     assert!(command.resolved_blocking_io());
 }
+
+#[test]
+fn resolve_remote_shell_blocking_io_matches_builder_rules() {
+    // WHY: daemon-over-rsh spawns the shell without `SshCommand`, so the free
+    // resolver must apply the same rsh/remsh auto-enable and explicit overrides.
+    // upstream: main.c:600-601 do_cmd().
+    assert!(resolve_remote_shell_blocking_io(
+        OsStr::new("/usr/bin/rsh"),
+        None
+    ));
+    assert!(resolve_remote_shell_blocking_io(OsStr::new("remsh"), None));
+    assert!(!resolve_remote_shell_blocking_io(OsStr::new("ssh"), None));
+    assert!(!resolve_remote_shell_blocking_io(
+        OsStr::new("rsh"),
+        Some(false)
+    ));
+    assert!(resolve_remote_shell_blocking_io(
+        OsStr::new("ssh"),
+        Some(true)
+    ));
+}
+
+#[cfg(unix)]
+#[test]
+fn pipe_remote_shell_stdout_carries_child_output_to_eof() {
+    // WHY: the parent read end returned for a forced-blocking spawn must
+    // deliver the child's output and reach EOF once the child exits, which
+    // requires the command's copy of the write end to be released.
+    // upstream: pipe.c:80-82 piped_child() `set_blocking(STDOUT_FILENO)`.
+    for blocking in [true, false] {
+        let mut command = std::process::Command::new("sh");
+        command.args(["-c", "printf blocking-io"]);
+        let mut stdout = pipe_remote_shell_stdout(&mut command, blocking)
+            .expect("create pipe")
+            .expect("unix creates the parent end");
+        let mut child = command.spawn().expect("spawn sh");
+        drop(command);
+
+        let mut output = String::new();
+        stdout.read_to_string(&mut output).expect("read to EOF");
+        assert_eq!(output, "blocking-io");
+        assert!(child.wait().expect("wait").success());
+    }
+}
@@ -4,7 +4,7 @@ use std::fmt;
 
 use checksums::strong::{
     Md4, Md5, Md5Seed, Sha1, StrongDigest, Xxh3, Xxh3_128, Xxh64, md4_digest_batch,
-    md5_digest_batch,
+    md4_digest_batch_seeded, md5_digest_batch_seeded,
 };
 
 /// Stack-allocated buffer for strong checksum digests, avoiding heap allocation
@@ -166,45 +166,30 @@ impl SignatureAlgorithm {
 
     /// Computes strong digests for a batch of data slices, each truncated to `len` bytes.
     ///
-    /// For MD4 and unseeded MD5, this uses SIMD-accelerated batch hashing (AVX2/AVX-512/NEON)
-    /// to process multiple blocks in parallel. Other algorithms fall back to sequential
-    /// per-element computation.
+    /// For MD4 and MD5, seeded or not, this uses SIMD-accelerated batch hashing
+    /// (AVX2/AVX-512/NEON) to process multiple blocks in parallel. Other algorithms fall
+    /// back to sequential per-element computation.
     ///
     /// Returns a `Vec` of [`DigestBuf`] in the same order as the input slices.
     pub fn compute_truncated_batch(self, blocks: &[&[u8]], len: usize) -> Vec<DigestBuf> {
         let effective_len = len.min(self.digest_len()).min(DigestBuf::MAX_LEN);
 
-        match self {
-            SignatureAlgorithm::Md4 => {
-                let digests = md4_digest_batch(blocks);
-                digests
-                    .into_iter()
-                    .map(|d| DigestBuf::from_slice(d.as_ref(), effective_len))
-                    .collect()
-            }
-            SignatureAlgorithm::Md5 {
-                seed_config:
-                    Md5Seed {
-                        value: None,
-                        proper_order: _,
-                    },
-            } => {
-                let digests = md5_digest_batch(blocks);
-                digests
-                    .into_iter()
-                    .map(|d| DigestBuf::from_slice(d.as_ref(), effective_len))
-                    .collect()
-            }
+        let digests = match self {
+            SignatureAlgorithm::Md4 => md4_digest_batch(blocks),
+            // upstream: checksum.c:377-380 - the seed is appended after the data.
+            SignatureAlgorithm::Md4Seeded { seed } => md4_digest_batch_seeded(blocks, seed),
+            SignatureAlgorithm::Md5 { seed_config } => md5_digest_batch_seeded(blocks, seed_config),
             _ => {
-                // Seeded MD4 cannot use the SIMD batch path because the per-block
-                // input includes a 4-byte LE seed suffix appended after the data.
-                // upstream: checksum.c:377-380.
-                blocks
+                return blocks
                     .iter()
                     .map(|data| self.compute_truncated(data, len))
-                    .collect()
+                    .collect();
             }
-        }
+        };
+        digests
+            .into_iter()
+            .map(|d| DigestBuf::from_slice(d.as_ref(), effective_len))
+            .collect()
     }
 
     /// Computes a strong digest over two non-contiguous slices, truncated to `len` bytes.
@@ -468,9 +453,13 @@ mod tests {
             SignatureAlgorithm::Md5 {
                 seed_config: Md5Seed::none(),
             },
+            SignatureAlgorithm::Md4Seeded { seed: 0x5eed },
             SignatureAlgorithm::Md5 {
                 seed_config: Md5Seed::proper(42),
             },
+            SignatureAlgorithm::Md5 {
+                seed_config: Md5Seed::legacy(42),
+            },
             SignatureAlgorithm::Sha1,
             SignatureAlgorithm::Xxh64 { seed: 0 },
             SignatureAlgorithm::Xxh3 { seed: 99 },
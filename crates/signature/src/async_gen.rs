@@ -42,6 +42,14 @@
 //!
 //! For transfers with many files and CPU-intensive checksums (MD4/MD5/SHA1),
 //! this can reduce total transfer time by overlapping computation with I/O.
+//!
+//! # Tokio
+//!
+//! With the `tokio` feature, `generate_file_signature_async` produces the
+//! same signature from a tokio `AsyncRead` without
+//! blocking the reactor: the basis is read asynchronously in chunks of whole
+//! blocks and each chunk is hashed on the runtime's blocking pool while the
+//! next one is read.
 
 use std::fs;
 use std::io;
@@ -59,6 +67,19 @@ use crate::algorithm::SignatureAlgorithm;
 use crate::file::FileSignature;
 use crate::generation::generate_file_signature;
 use crate::layout::{SignatureLayoutParams, calculate_signature_layout};
+#[cfg(feature = "tokio")]
+use crate::{algorithm::DigestBuf, generation::SignatureError, layout::SignatureLayout};
+#[cfg(feature = "tokio")]
+use checksums::RollingDigest;
+#[cfg(feature = "tokio")]
+use tokio::io::{AsyncRead, AsyncReadExt};
+#[cfg(feature = "tokio")]
+use tokio::task::{self, JoinHandle as TaskHandle};
+
+/// Bytes read per blocking hash task by [`generate_file_signature_async`],
+/// rounded down to whole blocks.
+#[cfg(feature = "tokio")]
+const ASYNC_CHUNK_BYTES: usize = 1 << 20;
 
 /// Request to generate a signature asynchronously.
 #[derive(Debug)]
@@ -390,6 +411,120 @@ fn generate_signature_sync(request: SignatureRequest) -> SignatureResult {
     }
 }
 
+/// Generates an rsync-compatible file signature from an async reader.
+///
+/// The tokio counterpart of [`generate_file_signature`], yielding an
+/// identical signature. The basis is read with async I/O in chunks of whole
+/// blocks (about 1 MiB each); each chunk's rolling and strong sums are
+/// computed by [`task::spawn_blocking`] while the next chunk is read, so a
+/// large basis neither blocks the reactor nor is held in memory at once.
+///
+/// # Errors
+///
+/// - Returns [`SignatureError::DigestLengthMismatch`] when the layout requests
+///   a strong checksum length that exceeds the algorithm's digest width.
+/// - Returns [`SignatureError::TooManyBlocks`] if the layout describes more
+///   blocks than can be addressed on the current platform.
+/// - Returns [`SignatureError::TrailingData`] when the reader yields more bytes
+///   than `layout` describes.
+/// - Propagates any I/O error surfaced by the reader, and reports a hashing
+///   task that panicked or was cancelled as [`SignatureError::Io`].
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub async fn generate_file_signature_async<R>(
+    mut reader: R,
+    layout: SignatureLayout,
+    algorithm: SignatureAlgorithm,
+) -> Result<FileSignature, SignatureError>
+where
+    R: AsyncRead + Unpin,
+{
+    let strong_len = usize::from(layout.strong_sum_length().get());
+    if strong_len > algorithm.digest_len() {
+        return Err(SignatureError::DigestLengthMismatch {
+            algorithm,
+            requested: layout.strong_sum_length().into(),
+        });
+    }
+
+    let block_len = layout.block_length().get() as usize;
+    let expected_blocks = layout.block_count();
+    let expected_blocks_usize = usize::try_from(expected_blocks)
+        .map_err(|_| SignatureError::TooManyBlocks(expected_blocks))?;
+    let blocks_per_chunk = (ASYNC_CHUNK_BYTES / block_len).max(1);
+
+    let mut signature = FileSignature::with_capacity(layout, expected_blocks_usize);
+    let mut total_bytes: u64 = 0;
+    let mut hashing: Option<TaskHandle<Vec<(RollingDigest, DigestBuf)>>> = None;
+    let mut index = 0;
+
+    while index < expected_blocks_usize {
+        let chunk_blocks = blocks_per_chunk.min(expected_blocks_usize - index);
+        let is_last = index + chunk_blocks == expected_blocks_usize;
+        let mut chunk_len = chunk_blocks * block_len;
+        if is_last && layout.remainder() != 0 {
+            chunk_len -= block_len - layout.remainder() as usize;
+        }
+
+        let mut chunk = vec![0u8; chunk_len];
+        reader.read_exact(&mut chunk).await?;
+        total_bytes = total_bytes.saturating_add(chunk_len as u64);
+
+        let hashed =
+            task::spawn_blocking(move || hash_chunk(&chunk, block_len, strong_len, algorithm));
+        if let Some(previous) = hashing.replace(hashed) {
+            push_hashed(&mut signature, previous).await?;
+        }
+        index += chunk_blocks;
+    }
+    if let Some(last) = hashing {
+        push_hashed(&mut signature, last).await?;
+    }
+
+    let mut extra = [0u8; 1];
+    if reader.read(&mut extra).await? != 0 {
+        return Err(SignatureError::TrailingData { bytes: 1 });
+    }
+
+    signature.set_total_bytes(total_bytes);
+    Ok(signature)
+}
+
+/// Computes the rolling and truncated strong sums of every block in `chunk`.
+#[cfg(feature = "tokio")]
+fn hash_chunk(
+    chunk: &[u8],
+    block_len: usize,
+    strong_len: usize,
+    algorithm: SignatureAlgorithm,
+) -> Vec<(RollingDigest, DigestBuf)> {
+    let blocks: Vec<&[u8]> = chunk.chunks(block_len).collect();
+    let mut sums = Vec::with_capacity(blocks.len());
+    for batch in blocks.chunks(crate::generation::BATCH_SIZE) {
+        let strong = algorithm.compute_truncated_batch(batch, strong_len);
+        sums.extend(
+            batch
+                .iter()
+                .map(|block| RollingDigest::from_bytes(block))
+                .zip(strong),
+        );
+    }
+    sums
+}
+
+/// Waits for a chunk's hashing task and appends its blocks to `signature`.
+#[cfg(feature = "tokio")]
+async fn push_hashed(
+    signature: &mut FileSignature,
+    hashed: TaskHandle<Vec<(RollingDigest, DigestBuf)>>,
+) -> Result<(), SignatureError> {
+    let sums = hashed.await.map_err(io::Error::from)?;
+    for (rolling, strong) in sums {
+        signature.push(rolling, strong.as_slice());
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -520,6 +655,85 @@ mod tests {
         assert_eq!(config.max_pending, 32);
     }
 
+    #[cfg(feature = "tokio")]
+    fn tokio_layout(len: usize, block_size: u32) -> SignatureLayout {
+        calculate_signature_layout(SignatureLayoutParams::new(
+            len as u64,
+            std::num::NonZeroU32::new(block_size),
+            ProtocolVersion::NEWEST,
+            NonZeroU8::new(16).unwrap(),
+        ))
+        .unwrap()
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_async_file_signature_matches_sync() {
+        // Several hashing chunks plus a short final block.
+        let data: Vec<u8> = (0..3 * ASYNC_CHUNK_BYTES + 1234)
+            .map(|i| (i % 241) as u8)
+            .collect();
+        let layout = tokio_layout(data.len(), 4096);
+
+        let expected =
+            generate_file_signature(data.as_slice(), layout, SignatureAlgorithm::Md4).unwrap();
+        let actual =
+            generate_file_signature_async(data.as_slice(), layout, SignatureAlgorithm::Md4)
+                .await
+                .unwrap();
+
+        assert_eq!(actual, expected);
+        assert_eq!(actual.total_bytes(), data.len() as u64);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_async_file_signature_reads_tokio_file() {
+        let test_file = create_test_file(10_000).unwrap();
+        let layout = tokio_layout(10_000, 700);
+
+        let file = tokio::fs::File::open(test_file.path()).await.unwrap();
+        let signature = generate_file_signature_async(file, layout, SignatureAlgorithm::Md4)
+            .await
+            .unwrap();
+
+        let expected = generate_file_signature(
+            fs::File::open(test_file.path()).unwrap(),
+            layout,
+            SignatureAlgorithm::Md4,
+        )
+        .unwrap();
+        assert_eq!(signature, expected);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_async_file_signature_rejects_length_mismatch() {
+        let layout = tokio_layout(4096, 512);
+
+        let short = generate_file_signature_async(
+            vec![0u8; 4000].as_slice(),
+            layout,
+            SignatureAlgorithm::Md4,
+        )
+        .await;
+        assert!(matches!(
+            short,
+            Err(SignatureError::Io(ref err)) if err.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        let long = generate_file_signature_async(
+            vec![0u8; 4097].as_slice(),
+            layout,
+            SignatureAlgorithm::Md4,
+        )
+        .await;
+        assert!(matches!(
+            long,
+            Err(SignatureError::TrailingData { bytes: 1 })
+        ));
+    }
+
     #[test]
     fn test_next_request_id_increments() {
         let config = AsyncSignatureConfig::default();
@@ -1,33 +1,102 @@
 //! Aggregated file signature container.
+//!
+//! A signature for a multi-gigabyte file holds millions of blocks, so the
+//! container stores them column-wise rather than as a `Vec<SignatureBlock>`:
+//!
+//! - rolling sums live in one flat `u32` array (`sum1 | sum2 << 16`, the wire
+//!   value);
+//! - strong sums live back to back in one byte buffer with a fixed stride
+//!   (the negotiated `s2length`, 2 to 16 bytes);
+//! - block lengths are implied by the layout, with an exception list for the
+//!   blocks that differ from it (normally just the trailing partial block);
+//! - block indices are implied by position.
+//!
+//! A block therefore costs `4 + s2length` bytes instead of the 48 bytes of a
+//! [`SignatureBlock`], and the matcher walks dense arrays. [`SignatureBlock`]
+//! remains the by-value view handed out by [`FileSignature::block`] and
+//! [`FileSignature::blocks`].
 
+use std::fmt;
+use std::iter::FusedIterator;
+
+use checksums::RollingDigest;
+
+use crate::algorithm::DigestBuf;
 use crate::block::SignatureBlock;
 use crate::layout::SignatureLayout;
 
 /// Aggregated signature for a file produced by [`crate::generate_file_signature`].
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq)]
 pub struct FileSignature {
     layout: SignatureLayout,
-    blocks: Vec<SignatureBlock>,
+    /// Packed rolling sum of each block, in block order.
+    rolling: Vec<u32>,
+    /// Strong sums of every block, `strong_stride` bytes each.
+    strong: Vec<u8>,
+    strong_stride: usize,
+    /// `(index, len)` for each block whose length differs from the layout's
+    /// block length, in ascending index order.
+    irregular_lengths: Vec<(usize, usize)>,
     total_bytes: u64,
 }
 
 impl FileSignature {
     /// Constructs a signature from a computed layout and block list.
-    pub(crate) const fn new(
+    pub(crate) fn new(
         layout: SignatureLayout,
         blocks: Vec<SignatureBlock>,
         total_bytes: u64,
     ) -> Self {
+        let mut signature = Self::with_capacity(layout, blocks.len());
+        for block in &blocks {
+            signature.push(block.rolling(), block.strong());
+        }
+        signature.total_bytes = total_bytes;
+        signature
+    }
+
+    /// Creates an empty signature with room for `block_count` blocks.
+    pub(crate) fn with_capacity(layout: SignatureLayout, block_count: usize) -> Self {
+        let stride = usize::from(layout.strong_sum_length().get()).min(DigestBuf::MAX_LEN);
         Self {
             layout,
-            blocks,
-            total_bytes,
+            rolling: Vec::with_capacity(block_count),
+            strong: Vec::with_capacity(block_count.saturating_mul(stride)),
+            strong_stride: 0,
+            irregular_lengths: Vec::new(),
+            total_bytes: 0,
+        }
+    }
+
+    /// Appends the next block.
+    ///
+    /// The first block fixes the strong-sum stride. A later, longer strong
+    /// sum widens the stride; shorter ones are zero-padded to it.
+    pub(crate) fn push(&mut self, rolling: RollingDigest, strong: &[u8]) {
+        let index = self.rolling.len();
+        if strong.len() > self.strong_stride {
+            self.restride(strong.len());
+        }
+        self.rolling.push(rolling.value());
+        self.strong.extend_from_slice(strong);
+        self.strong
+            .resize(self.strong.len() + self.strong_stride - strong.len(), 0);
+        if rolling.len() != self.layout.block_length().get() as usize {
+            self.irregular_lengths.push((index, rolling.len()));
         }
     }
 
+    /// Records the number of source bytes the signature covers.
+    pub(crate) const fn set_total_bytes(&mut self, total_bytes: u64) {
+        self.total_bytes = total_bytes;
+    }
+
     /// Creates a signature from raw components (for wire protocol reconstruction).
+    ///
+    /// Blocks are stored in order and each block's index is its position in
+    /// `blocks`. Strong sums shorter than the longest one are zero-padded.
     #[must_use]
-    pub const fn from_raw_parts(
+    pub fn from_raw_parts(
         layout: SignatureLayout,
         blocks: Vec<SignatureBlock>,
         total_bytes: u64,
@@ -42,11 +111,69 @@ impl FileSignature {
         self.layout
     }
 
-    /// Returns the list of block entries in the order they were generated.
+    /// Returns a view over the block entries in the order they were generated.
+    #[inline]
+    #[must_use]
+    pub const fn blocks(&self) -> SignatureBlocks<'_> {
+        SignatureBlocks { signature: self }
+    }
+
+    /// Returns the number of blocks.
+    #[inline]
+    #[must_use]
+    pub const fn block_count(&self) -> usize {
+        self.rolling.len()
+    }
+
+    /// Returns block `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not below [`Self::block_count`].
+    #[must_use]
+    pub fn block(&self, index: usize) -> SignatureBlock {
+        let rolling = RollingDigest::from_value(self.rolling[index], self.block_len(index));
+        SignatureBlock::new(
+            index as u64,
+            rolling,
+            DigestBuf::from_slice(self.strong_sum(index), self.strong_stride),
+        )
+    }
+
+    /// Packed rolling sums of every block, in block order.
     #[inline]
     #[must_use]
-    pub fn blocks(&self) -> &[SignatureBlock] {
-        &self.blocks
+    pub const fn rolling_sums(&self) -> &[u32] {
+        self.rolling.as_slice()
+    }
+
+    /// Strong sum bytes of block `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not below [`Self::block_count`].
+    #[inline]
+    #[must_use]
+    pub fn strong_sum(&self, index: usize) -> &[u8] {
+        let start = index * self.strong_stride;
+        &self.strong[start..start + self.strong_stride]
+    }
+
+    /// Number of bytes each block's strong sum occupies.
+    #[inline]
+    #[must_use]
+    pub const fn strong_stride(&self) -> usize {
+        self.strong_stride
+    }
+
+    /// Byte length of block `index`.
+    #[must_use]
+    pub fn block_len(&self, index: usize) -> usize {
+        self.irregular_lengths
+            .binary_search_by_key(&index, |&(irregular, _)| irregular)
+            .map_or(self.layout.block_length().get() as usize, |slot| {
+                self.irregular_lengths[slot].1
+            })
     }
 
     /// Source file size consumed while computing the signature.
@@ -55,8 +182,180 @@ impl FileSignature {
     pub const fn total_bytes(&self) -> u64 {
         self.total_bytes
     }
+
+    /// Widens the strong buffer to `stride` bytes per block, zero-padding
+    /// the blocks already stored.
+    fn restride(&mut self, stride: usize) {
+        let old = self.strong_stride;
+        if self.rolling.is_empty() {
+            self.strong_stride = stride;
+            return;
+        }
+        let mut strong =
+            Vec::with_capacity(self.strong.capacity().max(self.rolling.len() * stride));
+        for index in 0..self.rolling.len() {
+            strong.extend_from_slice(&self.strong[index * old..(index + 1) * old]);
+            strong.resize((index + 1) * stride, 0);
+        }
+        self.strong = strong;
+        self.strong_stride = stride;
+    }
 }
 
+impl Clone for FileSignature {
+    fn clone(&self) -> Self {
+        Self {
+            layout: self.layout,
+            rolling: self.rolling.clone(),
+            strong: self.strong.clone(),
+            strong_stride: self.strong_stride,
+            irregular_lengths: self.irregular_lengths.clone(),
+            total_bytes: self.total_bytes,
+        }
+    }
+
+    /// Reuses the column allocations, so a per-file index rebuild does not
+    /// reallocate once it has seen a signature of similar size.
+    fn clone_from(&mut self, source: &Self) {
+        self.layout = source.layout;
+        self.rolling.clone_from(&source.rolling);
+        self.strong.clone_from(&source.strong);
+        self.strong_stride = source.strong_stride;
+        self.irregular_lengths.clone_from(&source.irregular_lengths);
+        self.total_bytes = source.total_bytes;
+    }
+}
+
+/// Borrowed view over the blocks of a [`FileSignature`].
+///
+/// Yields [`SignatureBlock`] values assembled from the signature's columns.
+#[derive(Clone, Copy)]
+pub struct SignatureBlocks<'a> {
+    signature: &'a FileSignature,
+}
+
+impl<'a> SignatureBlocks<'a> {
+    /// Returns the number of blocks.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.signature.block_count()
+    }
+
+    /// Reports whether the signature has no blocks.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns block `index`, or `None` when it is out of range.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<SignatureBlock> {
+        (index < self.len()).then(|| self.signature.block(index))
+    }
+
+    /// Returns the first block.
+    #[must_use]
+    pub fn first(&self) -> Option<SignatureBlock> {
+        self.get(0)
+    }
+
+    /// Returns the last block.
+    #[must_use]
+    pub fn last(&self) -> Option<SignatureBlock> {
+        self.len().checked_sub(1).and_then(|index| self.get(index))
+    }
+
+    /// Iterates over the blocks in order.
+    #[must_use]
+    pub fn iter(&self) -> SignatureBlockIter<'a> {
+        SignatureBlockIter {
+            signature: self.signature,
+            front: 0,
+            back: self.len(),
+        }
+    }
+
+    /// Collects the blocks into a vector.
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<SignatureBlock> {
+        self.iter().collect()
+    }
+}
+
+impl<'a> IntoIterator for SignatureBlocks<'a> {
+    type Item = SignatureBlock;
+    type IntoIter = SignatureBlockIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &SignatureBlocks<'a> {
+    type Item = SignatureBlock;
+    type IntoIter = SignatureBlockIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl PartialEq for SignatureBlocks<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for SignatureBlocks<'_> {}
+
+impl fmt::Debug for SignatureBlocks<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Iterator over the blocks of a [`FileSignature`].
+#[derive(Clone, Debug)]
+pub struct SignatureBlockIter<'a> {
+    signature: &'a FileSignature,
+    front: usize,
+    back: usize,
+}
+
+impl Iterator for SignatureBlockIter<'_> {
+    type Item = SignatureBlock;
+
+    #[inline]
+    fn next(&mut self) -> Option<SignatureBlock> {
+        (self.front < self.back).then(|| {
+            self.front += 1;
+            self.signature.block(self.front - 1)
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.back - self.front;
+        (remaining, Some(remaining))
+    }
+}
+
+impl DoubleEndedIterator for SignatureBlockIter<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<SignatureBlock> {
+        (self.front < self.back).then(|| {
+            self.back -= 1;
+            self.signature.block(self.back)
+        })
+    }
+}
+
+impl ExactSizeIterator for SignatureBlockIter<'_> {}
+
+impl FusedIterator for SignatureBlockIter<'_> {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,7 +377,7 @@ mod tests {
         let blocks = vec![];
         let sig = FileSignature::from_raw_parts(layout, blocks.clone(), 100);
         assert_eq!(sig.layout(), layout);
-        assert_eq!(sig.blocks(), &blocks);
+        assert_eq!(sig.blocks().to_vec(), blocks);
         assert_eq!(sig.total_bytes(), 100);
     }
 
@@ -97,4 +396,35 @@ mod tests {
         let debug = format!("{sig:?}");
         assert!(debug.contains("FileSignature"));
     }
+
+    #[test]
+    fn blocks_round_trip_through_columns() {
+        let blocks = vec![
+            SignatureBlock::from_raw_parts(0, RollingDigest::from_value(0x1234_5678, 700), &[1; 4]),
+            SignatureBlock::from_raw_parts(1, RollingDigest::from_value(0x9abc_def0, 700), &[2; 4]),
+            SignatureBlock::from_raw_parts(2, RollingDigest::from_value(0x0bad_f00d, 50), &[3; 4]),
+        ];
+        let sig = FileSignature::from_raw_parts(test_layout(), blocks.clone(), 1450);
+        assert_eq!(sig.blocks().to_vec(), blocks);
+        assert_eq!(sig.blocks().last(), Some(blocks[2]));
+        assert_eq!(sig.blocks().get(3), None);
+        assert_eq!(sig.rolling_sums(), &[0x1234_5678, 0x9abc_def0, 0x0bad_f00d]);
+        assert_eq!(sig.strong_stride(), 4);
+        assert_eq!(sig.strong_sum(1), &[2; 4]);
+        assert_eq!(sig.block_len(1), 700);
+        assert_eq!(sig.block_len(2), 50);
+        assert_eq!(sig.blocks().iter().rev().next(), Some(blocks[2]));
+    }
+
+    #[test]
+    fn wider_strong_sum_restrides_earlier_blocks() {
+        let blocks = vec![
+            SignatureBlock::from_raw_parts(0, RollingDigest::from_value(1, 700), &[7; 2]),
+            SignatureBlock::from_raw_parts(1, RollingDigest::from_value(2, 700), &[8; 4]),
+        ];
+        let sig = FileSignature::from_raw_parts(test_layout(), blocks, 1400);
+        assert_eq!(sig.strong_stride(), 4);
+        assert_eq!(sig.strong_sum(0), &[7, 7, 0, 0]);
+        assert_eq!(sig.strong_sum(1), &[8; 4]);
+    }
 }
@@ -10,7 +10,6 @@ use tracing::instrument;
 use checksums::RollingDigest;
 
 use crate::algorithm::SignatureAlgorithm;
-use crate::block::SignatureBlock;
 use crate::file::FileSignature;
 use crate::layout::SignatureLayout;
 
@@ -58,7 +57,7 @@ impl SignatureError {
 ///
 /// Chosen to match the widest SIMD lane count (AVX-512 = 16 lanes for MD5) while keeping
 /// the batch buffer small enough to stay in L1 cache for typical rsync block sizes.
-const BATCH_SIZE: usize = 16;
+pub(crate) const BATCH_SIZE: usize = 16;
 
 /// Generates an rsync-compatible file signature using the provided layout and strong checksum.
 ///
@@ -93,7 +92,7 @@ pub fn generate_file_signature<R: Read>(
     let expected_blocks_usize = usize::try_from(expected_blocks)
         .map_err(|_| SignatureError::TooManyBlocks(expected_blocks))?;
 
-    let mut blocks = Vec::with_capacity(expected_blocks_usize);
+    let mut signature = FileSignature::with_capacity(layout, expected_blocks_usize);
     let mut total_bytes: u64 = 0;
 
     // Reusable per-block buffers; cleared and refilled on every batch iteration.
@@ -139,12 +138,8 @@ pub fn generate_file_signature<R: Read>(
 
         let strong_digests = algorithm.compute_truncated_batch(&batch_slices, strong_len);
 
-        for (i, (rolling, strong)) in batch_rolling
-            .iter()
-            .zip(strong_digests.into_iter())
-            .enumerate()
-        {
-            blocks.push(SignatureBlock::new((index + i) as u64, *rolling, strong));
+        for (rolling, strong) in batch_rolling.iter().zip(strong_digests.iter()) {
+            signature.push(*rolling, strong.as_slice());
         }
 
         index = batch_end;
@@ -155,7 +150,8 @@ pub fn generate_file_signature<R: Read>(
         return Err(SignatureError::TrailingData { bytes: 1 });
     }
 
-    Ok(FileSignature::new(layout, blocks, total_bytes))
+    signature.set_total_bytes(total_bytes);
+    Ok(signature)
 }
 
 #[cfg(test)]
@@ -185,7 +181,7 @@ mod tests {
             .expect("signature generation succeeds");
 
         assert_eq!(signature.blocks().len(), 1);
-        let block = &signature.blocks()[0];
+        let block = signature.block(0);
         assert_eq!(block.index(), 0);
         assert_eq!(block.len(), 11);
         assert_eq!(block.rolling(), RollingDigest::from_bytes(b"hello world"));
@@ -286,7 +282,7 @@ mod tests {
 
         assert_eq!(signature.blocks().len(), 1);
         // SHA-1 emits 20 bytes; the layout truncates the digest to 16.
-        assert_eq!(signature.blocks()[0].strong().len(), 16);
+        assert_eq!(signature.block(0).strong().len(), 16);
     }
 
     #[test]
@@ -298,7 +294,7 @@ mod tests {
                 .expect("signature generation succeeds");
 
         assert_eq!(signature.blocks().len(), 1);
-        assert_eq!(signature.blocks()[0].strong().len(), 8);
+        assert_eq!(signature.block(0).strong().len(), 8);
     }
 
     #[test]
@@ -313,7 +309,7 @@ mod tests {
         .expect("signature generation succeeds");
 
         assert_eq!(signature.blocks().len(), 1);
-        assert_eq!(signature.blocks()[0].strong().len(), 16);
+        assert_eq!(signature.block(0).strong().len(), 16);
     }
 
     #[test]
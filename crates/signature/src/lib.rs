@@ -107,9 +107,11 @@ pub use block_size::{
     DEFAULT_BLOCK_SIZE, MAX_BLOCK_SIZE_OLD, MAX_BLOCK_SIZE_V30, MAX_SUM_LENGTH, MIN_BLOCK_SIZE,
     SHORT_SUM_LENGTH, calculate_block_length, calculate_checksum_count,
 };
-pub use file::FileSignature;
+pub use file::{FileSignature, SignatureBlockIter, SignatureBlocks};
 pub use generation::{SignatureError, generate_file_signature};
 pub use layout::{
     SignatureLayout, SignatureLayoutError, SignatureLayoutParams, calculate_signature_layout,
 };
-pub use pipelined_gen::{PipelinedSignatureConfig, generate_signature_pipelined};
+pub use pipelined_gen::{
+    PipelinedSignatureConfig, generate_signature_pipelined, stream_signature_pipelined,
+};
@@ -285,7 +285,7 @@ pub fn generate_file_signature_windowed<R: Read>(
         .min(budget_blocks)
         .min(expected_blocks_usize);
 
-    let mut blocks: Vec<SignatureBlock> = Vec::with_capacity(expected_blocks_usize);
+    let mut signature = FileSignature::with_capacity(layout, expected_blocks_usize);
     let mut total_bytes: u64 = 0;
     let mut window_data: Vec<Vec<u8>> = Vec::with_capacity(window_blocks);
     let mut base_index = 0usize;
@@ -325,7 +325,9 @@ pub fn generate_file_signature_windowed<R: Read>(
                     .map(move |(i, (r, s))| SignatureBlock::new((chunk_base + i) as u64, r, s))
             })
             .collect();
-        blocks.extend(window_out);
+        for block in &window_out {
+            signature.push(block.rolling(), block.strong());
+        }
         base_index = window_end;
     }
 
@@ -334,7 +336,8 @@ pub fn generate_file_signature_windowed<R: Read>(
         return Err(SignatureError::TrailingData { bytes: 1 });
     }
 
-    Ok(FileSignature::new(layout, blocks, total_bytes))
+    signature.set_total_bytes(total_bytes);
+    Ok(signature)
 }
 
 /// SIMD batch width shared by the parallel signature generators: the number of
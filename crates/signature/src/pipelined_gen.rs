@@ -9,6 +9,13 @@
 //! For CPU-intensive checksums (MD4/MD5/SHA1) with large files on fast storage,
 //! pipelined generation can provide 20-40% throughput improvement by hiding
 //! I/O latency behind computation.
+//!
+//! # Streaming
+//!
+//! [`stream_signature_pipelined`] hands each block's sums to a callback as soon
+//! as its batch is hashed instead of collecting them into a [`FileSignature`].
+//! Memory then stays bounded by one batch regardless of the basis size, which
+//! lets the receiver write a 100 GB basis signature straight to the wire.
 
 use std::io::{self, Read};
 
@@ -16,7 +23,6 @@ use checksums::RollingDigest;
 use checksums::pipelined::{DoubleBufferedReader, PipelineConfig};
 
 use crate::algorithm::SignatureAlgorithm;
-use crate::block::SignatureBlock;
 use crate::file::FileSignature;
 use crate::generation::SignatureError;
 use crate::layout::SignatureLayout;
@@ -88,6 +94,49 @@ pub fn generate_signature_pipelined<R: Read + Send + 'static>(
     algorithm: SignatureAlgorithm,
     config: PipelinedSignatureConfig,
 ) -> Result<FileSignature, SignatureError> {
+    let expected_blocks = layout.block_count();
+    let expected_blocks_usize = usize::try_from(expected_blocks)
+        .map_err(|_| SignatureError::TooManyBlocks(expected_blocks))?;
+
+    let mut signature = FileSignature::with_capacity(layout, expected_blocks_usize);
+    let total_bytes =
+        stream_signature_pipelined(reader, layout, algorithm, config, |rolling, strong| {
+            signature.push(rolling, strong);
+            Ok(())
+        })?;
+    signature.set_total_bytes(total_bytes);
+    Ok(signature)
+}
+
+/// Streams an rsync-compatible file signature block by block using pipelined
+/// I/O.
+///
+/// Blocks are read and hashed exactly as [`generate_signature_pipelined`]
+/// does, but each block's rolling sum and truncated strong sum are passed to
+/// `emit` in block order once its batch is hashed, and nothing is retained
+/// afterwards. At most one batch of block data is held at a time.
+///
+/// Returns the number of input bytes consumed.
+///
+/// # Errors
+///
+/// - Returns [`SignatureError::DigestLengthMismatch`] when the layout requests
+///   a strong checksum length that exceeds the algorithm's digest width.
+/// - Returns [`SignatureError::TrailingData`] or an `UnexpectedEof`
+///   [`SignatureError::Io`] when the input does not match the layout.
+/// - Propagates any I/O error from the reader or from `emit`. Blocks already
+///   passed to `emit` are not retracted.
+pub fn stream_signature_pipelined<R, F>(
+    reader: R,
+    layout: SignatureLayout,
+    algorithm: SignatureAlgorithm,
+    config: PipelinedSignatureConfig,
+    mut emit: F,
+) -> Result<u64, SignatureError>
+where
+    R: Read + Send + 'static,
+    F: FnMut(RollingDigest, &[u8]) -> io::Result<()>,
+{
     let strong_len = usize::from(layout.strong_sum_length().get());
     if strong_len > algorithm.digest_len() {
         return Err(SignatureError::DigestLengthMismatch {
@@ -98,8 +147,6 @@ pub fn generate_signature_pipelined<R: Read + Send + 'static>(
 
     let block_len = layout.block_length().get() as usize;
     let expected_blocks = layout.block_count();
-    let expected_blocks_usize = usize::try_from(expected_blocks)
-        .map_err(|_| SignatureError::TooManyBlocks(expected_blocks))?;
 
     let file_size = layout.file_size();
 
@@ -115,23 +162,23 @@ pub fn generate_signature_pipelined<R: Read + Send + 'static>(
     // Must match generation.rs::BATCH_SIZE so SIMD batch hashing widens identically.
     const BATCH_SIZE: usize = 16;
 
-    let mut blocks = Vec::with_capacity(expected_blocks_usize);
     let mut total_bytes: u64 = 0;
-    let mut block_index: usize = 0;
+    let mut block_index: u64 = 0;
 
-    let batch_cap = BATCH_SIZE.min(expected_blocks_usize).max(1);
+    let batch_cap = usize::try_from(expected_blocks)
+        .map_or(BATCH_SIZE, |blocks| BATCH_SIZE.min(blocks))
+        .max(1);
     let mut batch_data: Vec<Vec<u8>> = Vec::with_capacity(batch_cap);
     let mut batch_rolling: Vec<RollingDigest> = Vec::with_capacity(batch_cap);
-    let mut batch_start_index: usize = 0;
 
     while let Some(chunk) = buffered_reader.next_block().map_err(SignatureError::Io)? {
-        if block_index >= expected_blocks_usize {
+        if block_index >= expected_blocks {
             return Err(SignatureError::TrailingData {
                 bytes: chunk.len() as u64,
             });
         }
 
-        let is_last = block_index + 1 == expected_blocks_usize;
+        let is_last = block_index + 1 == expected_blocks;
         let expected_len = if is_last && layout.remainder() != 0 {
             layout.remainder() as usize
         } else {
@@ -162,32 +209,27 @@ pub fn generate_signature_pipelined<R: Read + Send + 'static>(
         batch_data.push(chunk.to_vec());
         block_index += 1;
 
-        if batch_data.len() >= BATCH_SIZE || block_index == expected_blocks_usize {
+        if batch_data.len() >= BATCH_SIZE || block_index == expected_blocks {
             let batch_slices: Vec<&[u8]> = batch_data.iter().map(|v| v.as_slice()).collect();
             let strong_digests = algorithm.compute_truncated_batch(&batch_slices, strong_len);
 
-            for (i, strong) in strong_digests.into_iter().enumerate() {
-                blocks.push(SignatureBlock::new(
-                    (batch_start_index + i) as u64,
-                    batch_rolling[i],
-                    strong,
-                ));
+            for (rolling, strong) in batch_rolling.iter().zip(strong_digests.iter()) {
+                emit(*rolling, strong.as_slice())?;
             }
 
-            batch_start_index = block_index;
             batch_data.clear();
             batch_rolling.clear();
         }
     }
 
-    if block_index < expected_blocks_usize {
+    if block_index < expected_blocks {
         return Err(SignatureError::Io(io::Error::new(
             io::ErrorKind::UnexpectedEof,
-            format!("expected {expected_blocks_usize} blocks, got {block_index}"),
+            format!("expected {expected_blocks} blocks, got {block_index}"),
         )));
     }
 
-    Ok(FileSignature::new(layout, blocks, total_bytes))
+    Ok(total_bytes)
 }
 
 #[cfg(test)]
@@ -350,6 +392,70 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn streamed_blocks_match_generated_signature() {
+        let data: Vec<u8> = (0..4096 + 77).map(|i| (i % 253) as u8).collect();
+        let layout = make_layout(data.len() as u64, Some(256), 16);
+        let config = PipelinedSignatureConfig::default().with_min_file_size(0);
+
+        let signature = generate_signature_pipelined(
+            Cursor::new(data.clone()),
+            layout,
+            SignatureAlgorithm::Md4,
+            config,
+        )
+        .expect("signature");
+
+        let mut streamed = Vec::new();
+        let total = stream_signature_pipelined(
+            Cursor::new(data),
+            layout,
+            SignatureAlgorithm::Md4,
+            config,
+            |rolling, strong| {
+                streamed.push((rolling, strong.to_vec()));
+                Ok(())
+            },
+        )
+        .expect("stream");
+
+        assert_eq!(total, signature.total_bytes());
+        assert_eq!(streamed.len(), signature.block_count());
+        for (i, (rolling, strong)) in streamed.iter().enumerate() {
+            let block = signature.block(i);
+            assert_eq!(*rolling, block.rolling(), "block {i} rolling mismatch");
+            assert_eq!(
+                strong.as_slice(),
+                block.strong(),
+                "block {i} strong mismatch"
+            );
+        }
+    }
+
+    #[test]
+    fn stream_stops_on_emit_error() {
+        let data = vec![0x5A; 2048];
+        let layout = make_layout(data.len() as u64, Some(128), 16);
+        let mut emitted = 0;
+
+        let result = stream_signature_pipelined(
+            Cursor::new(data),
+            layout,
+            SignatureAlgorithm::Md4,
+            PipelinedSignatureConfig::default(),
+            |_, _| {
+                emitted += 1;
+                Err(io::Error::new(io::ErrorKind::BrokenPipe, "peer closed"))
+            },
+        );
+
+        assert!(matches!(
+            result,
+            Err(SignatureError::Io(ref err)) if err.kind() == io::ErrorKind::BrokenPipe
+        ));
+        assert_eq!(emitted, 1);
+    }
+
     #[test]
     fn pipelined_config_builder() {
         let config = PipelinedSignatureConfig::new()
@@ -287,7 +287,7 @@ mod block_size_limits {
             .expect("signature");
 
         assert_eq!(signature.blocks().len(), 1);
-        assert_eq!(signature.blocks()[0].len(), 1);
+        assert_eq!(signature.block(0).len(), 1);
     }
 
     /// Very small forced block sizes work correctly.
@@ -589,8 +589,8 @@ mod signature_accuracy {
         // Even though data2 contains much of data1's content shifted,
         // block boundaries cause different signatures
         assert_ne!(
-            sig1.blocks()[0].rolling(),
-            sig2.blocks()[0].rolling(),
+            sig1.block(0).rolling(),
+            sig2.block(0).rolling(),
             "different content at same position"
         );
     }
@@ -776,17 +776,17 @@ mod round_trip {
 
         for i in 0..sig1.blocks().len() {
             assert_eq!(
-                sig1.blocks()[i].rolling(),
-                sig2.blocks()[i].rolling(),
+                sig1.block(i).rolling(),
+                sig2.block(i).rolling(),
                 "rolling checksums should be deterministic"
             );
             assert_eq!(
-                sig1.blocks()[i].strong(),
-                sig2.blocks()[i].strong(),
+                sig1.block(i).strong(),
+                sig2.block(i).strong(),
                 "strong checksums should be deterministic"
             );
-            assert_eq!(sig2.blocks()[i].rolling(), sig3.blocks()[i].rolling());
-            assert_eq!(sig2.blocks()[i].strong(), sig3.blocks()[i].strong());
+            assert_eq!(sig2.block(i).rolling(), sig3.block(i).rolling());
+            assert_eq!(sig2.block(i).strong(), sig3.block(i).strong());
         }
     }
 
@@ -942,10 +942,10 @@ mod round_trip {
                     .expect("signature");
 
             assert_eq!(signature.blocks().len(), 1);
-            assert_eq!(signature.blocks()[0].len(), 1);
+            assert_eq!(signature.block(0).len(), 1);
             assert_eq!(signature.total_bytes(), 1);
             assert_eq!(
-                signature.blocks()[0].rolling(),
+                signature.block(0).rolling(),
                 RollingDigest::from_bytes(&data)
             );
         }
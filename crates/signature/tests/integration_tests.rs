@@ -142,7 +142,7 @@ mod file_size_variations {
         assert_eq!(signature.blocks().len(), 1);
         assert_eq!(signature.total_bytes(), 1);
 
-        let block = &signature.blocks()[0];
+        let block = signature.block(0);
         assert_eq!(block.index(), 0);
         assert_eq!(block.len(), 1);
         assert_eq!(block.rolling(), RollingDigest::from_bytes(&data));
@@ -188,7 +188,7 @@ mod file_size_variations {
 
         assert_eq!(signature.blocks().len(), 1);
         assert_eq!(signature.total_bytes(), 700);
-        assert_eq!(signature.blocks()[0].len(), 700);
+        assert_eq!(signature.block(0).len(), 700);
     }
 
     /// Files spanning multiple blocks with a partial final block.
@@ -214,9 +214,9 @@ mod file_size_variations {
             assert_eq!(block.index(), i as u64);
         }
 
-        assert_eq!(signature.blocks()[0].len(), 700);
-        assert_eq!(signature.blocks()[1].len(), 700);
-        assert_eq!(signature.blocks()[2].len(), 100);
+        assert_eq!(signature.block(0).len(), 700);
+        assert_eq!(signature.block(1).len(), 700);
+        assert_eq!(signature.block(2).len(), 100);
     }
 
     /// Files spanning multiple blocks with exact block alignment.
@@ -335,7 +335,7 @@ mod block_size_variations {
             .expect("signature");
 
         assert_eq!(signature.blocks().len(), 1);
-        assert_eq!(signature.blocks()[0].len(), 500);
+        assert_eq!(signature.block(0).len(), 500);
     }
 
     /// Default block size heuristics follow upstream rsync's sum_sizes_sqroot().
@@ -431,10 +431,7 @@ mod checksum_algorithms {
         let (sig_unseeded, _) = generate_signature_from_data(&data, unseeded);
         let (sig_seeded, _) = generate_signature_from_data(&data, seeded);
 
-        assert_ne!(
-            sig_unseeded.blocks()[0].strong(),
-            sig_seeded.blocks()[0].strong()
-        );
+        assert_ne!(sig_unseeded.block(0).strong(), sig_seeded.block(0).strong());
     }
 
     /// SHA1 produces 20-byte digests.
@@ -499,7 +496,7 @@ mod checksum_algorithms {
         )
         .expect("signature");
 
-        assert_ne!(sig1.blocks()[0].strong(), sig2.blocks()[0].strong());
+        assert_ne!(sig1.block(0).strong(), sig2.block(0).strong());
     }
 
     /// XXH3/64 produces 8-byte digests.
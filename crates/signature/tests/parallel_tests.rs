@@ -560,14 +560,8 @@ mod block_configurations {
             generate_file_signature_parallel(Cursor::new(data), layout, SignatureAlgorithm::Md4)
                 .expect("parallel");
 
-        assert_eq!(
-            sequential.blocks()[0].rolling(),
-            parallel.blocks()[0].rolling()
-        );
-        assert_eq!(
-            sequential.blocks()[0].strong(),
-            parallel.blocks()[0].strong()
-        );
+        assert_eq!(sequential.block(0).rolling(), parallel.block(0).rolling());
+        assert_eq!(sequential.block(0).strong(), parallel.block(0).strong());
     }
 }
 
@@ -683,8 +677,8 @@ mod determinism {
         assert_eq!(sig2.blocks().len(), sig3.blocks().len());
 
         for i in 0..sig1.blocks().len() {
-            assert_eq!(sig1.blocks()[i].strong(), sig2.blocks()[i].strong());
-            assert_eq!(sig2.blocks()[i].strong(), sig3.blocks()[i].strong());
+            assert_eq!(sig1.block(i).strong(), sig2.block(i).strong());
+            assert_eq!(sig2.block(i).strong(), sig3.block(i).strong());
         }
     }
 
@@ -18,6 +18,7 @@
 //! ```
 
 use std::ffi::OsString;
+use std::num::NonZeroU32;
 use std::path::PathBuf;
 use std::time::SystemTime;
 
@@ -73,6 +74,7 @@ pub struct ServerConfigBuilder {
     write: WriteConfig,
     checksum_seed: Option<u32>,
     checksum_choice: Option<protocol::ChecksumAlgorithm>,
+    block_size: Option<NonZeroU32>,
     trust_sender: bool,
     stop_at: Option<SystemTime>,
     qsort: bool,
@@ -115,6 +117,7 @@ impl ServerConfigBuilder {
             write: WriteConfig::default(),
             checksum_seed: None,
             checksum_choice: None,
+            block_size: None,
             trust_sender: false,
             stop_at: None,
             qsort: false,
@@ -171,6 +174,16 @@ impl ServerConfigBuilder {
         self
     }
 
+    /// Enables or disables memory-mapped reads of large delta sources.
+    ///
+    /// Local sender option only (see [`ParsedServerFlags::mmap_source`]).
+    /// Like [`Self::parallel_delta_scan`], it must be called after
+    /// [`Self::flags`]. Defaults to off.
+    pub fn mmap_source(&mut self, enabled: bool) -> &mut Self {
+        self.flags.mmap_source = enabled;
+        self
+    }
+
     /// Sets the positional arguments passed to the server.
     pub fn args(&mut self, args: Vec<OsString>) -> &mut Self {
         self.args = args;
@@ -274,6 +287,13 @@ impl ServerConfigBuilder {
         self
     }
 
+    /// Enables or disables recursive removal of a non-empty directory that
+    /// blocks a non-directory (`--force`).
+    pub fn force_delete(&mut self, enabled: bool) -> &mut Self {
+        self.deletion.force_delete = enabled;
+        self
+    }
+
     /// Sets the full write configuration.
     pub fn write(&mut self, write: WriteConfig) -> &mut Self {
         self.write = write;
@@ -286,6 +306,13 @@ impl ServerConfigBuilder {
         self
     }
 
+    /// Enables or disables fsync of the parent directory after each rename
+    /// (`--fsync-dir`).
+    pub fn fsync_dir(&mut self, enabled: bool) -> &mut Self {
+        self.write.fsync_dir = enabled;
+        self
+    }
+
     /// Enables or disables in-place writes (`--inplace`).
     pub fn inplace(&mut self, enabled: bool) -> &mut Self {
         self.write.inplace = enabled;
@@ -325,6 +352,26 @@ impl ServerConfigBuilder {
         self
     }
 
+    /// Sets how many received files are committed to disk concurrently.
+    ///
+    /// Values below 1 are treated as 1.
+    pub fn parallel_commit(&mut self, count: usize) -> &mut Self {
+        self.write.parallel_commit = count.max(1);
+        self
+    }
+
+    /// Enables `O_DIRECT` writes of received file data (`--direct-write`).
+    pub fn direct_write(&mut self, enabled: bool) -> &mut Self {
+        self.write.direct_write = enabled;
+        self
+    }
+
+    /// Enables page-cache eviction of transferred files (`--drop-cache`).
+    pub fn drop_cache(&mut self, enabled: bool) -> &mut Self {
+        self.write.drop_cache = enabled;
+        self
+    }
+
     /// Enables `--open-noatime` propagation for source-file reads.
     ///
     /// Linux/Android only; ignored on other platforms. Mirrors upstream
@@ -358,6 +405,12 @@ impl ServerConfigBuilder {
         self
     }
 
+    /// Sets the fixed signature block length (`--block-size`).
+    pub fn block_size(&mut self, size: Option<NonZeroU32>) -> &mut Self {
+        self.block_size = size;
+        self
+    }
+
     /// Enables or disables `--trust-sender`.
     pub fn trust_sender(&mut self, enabled: bool) -> &mut Self {
         self.trust_sender = enabled;
@@ -451,6 +504,45 @@ impl ServerConfigBuilder {
     /// (`modify_window < 0`, util1.c:1482).
     pub fn modify_window(&mut self, seconds: i64) -> &mut Self {
         self.file_selection.modify_window = ModifyWindow::from_secs(seconds);
+        self.file_selection.modify_window_set = true;
+        self
+    }
+
+    /// Sets the directory holding the destination scan cache (`--cache-dir`).
+    pub fn cache_dir(&mut self, dir: Option<PathBuf>) -> &mut Self {
+        self.file_selection.cache_dir = dir;
+        self
+    }
+
+    /// Sets how entries colliding after case folding are handled
+    /// (`--case-collision`).
+    pub fn case_collision(&mut self, policy: engine::CaseCollisionPolicy) -> &mut Self {
+        self.file_selection.case_collision = policy;
+        self
+    }
+
+    /// Sets how names that are invalid on Windows are handled
+    /// (`--windows-names`).
+    pub fn windows_names(&mut self, policy: engine::WindowsNamesPolicy) -> &mut Self {
+        self.file_selection.windows_names = policy;
+        self
+    }
+
+    /// Sets the order regular files are requested in (`--order`).
+    pub fn transfer_order(&mut self, order: engine::TransferOrder) -> &mut Self {
+        self.file_selection.transfer_order = order;
+        self
+    }
+
+    /// Sets the file confirmed commits are logged to (`--checkpoint`).
+    pub fn checkpoint(&mut self, file: Option<PathBuf>) -> &mut Self {
+        self.file_selection.checkpoint = file;
+        self
+    }
+
+    /// Sets the checkpoint of an interrupted run to resume (`--resume`).
+    pub fn resume(&mut self, file: Option<PathBuf>) -> &mut Self {
+        self.file_selection.resume = file;
         self
     }
 
@@ -598,6 +690,21 @@ impl ServerConfigBuilder {
             }
         }
 
+        // upstream: rsync.h:160-161 - the block length may not exceed the
+        // negotiated protocol's MAX_BLOCK_SIZE (OLD_MAX_BLOCK_SIZE before 30).
+        if let Some(size) = self.block_size {
+            let max = if self.protocol.as_u8() < 30 {
+                signature::MAX_BLOCK_SIZE_OLD
+            } else {
+                signature::MAX_BLOCK_SIZE_V30
+            };
+            if size.get() > max {
+                return Err(BuilderError::InvalidCombination {
+                    message: format!("block_size ({size}) exceeds the protocol maximum ({max})"),
+                });
+            }
+        }
+
         Ok(())
     }
 
@@ -610,6 +717,7 @@ impl ServerConfigBuilder {
     /// - `--append` and `--partial-dir` are both enabled
     /// - `--inplace` and `--partial-dir` are both enabled
     /// - `min_file_size` exceeds `max_file_size`
+    /// - `block_size` exceeds the protocol's maximum block length
     pub fn build(&self) -> Result<ServerConfig, BuilderError> {
         self.validate()?;
         Ok(self.build_unchecked())
@@ -633,6 +741,7 @@ impl ServerConfigBuilder {
             write: self.write.clone(),
             checksum_seed: self.checksum_seed,
             checksum_choice: self.checksum_choice,
+            block_size: self.block_size,
             trust_sender: self.trust_sender,
             stop_at: self.stop_at,
             qsort: self.qsort,
@@ -1,6 +1,7 @@
 //! Tests for [`ServerConfigBuilder`].
 
 use std::ffi::OsString;
+use std::num::NonZeroU32;
 
 use protocol::ProtocolVersion;
 
@@ -84,12 +85,14 @@ mod chaining {
             .max_delete(Some(100))
             .ignore_errors(true)
             .late_delete(true)
+            .force_delete(true)
             .build()
             .expect("valid config");
 
         assert_eq!(config.deletion.max_delete, Some(100));
         assert!(config.deletion.ignore_errors);
         assert!(config.deletion.late_delete);
+        assert!(config.deletion.force_delete);
     }
 
     #[test]
@@ -162,6 +165,7 @@ mod defaults {
         assert!(config.deletion.max_delete.is_none());
         assert!(!config.deletion.ignore_errors);
         assert!(!config.deletion.late_delete);
+        assert!(!config.deletion.force_delete);
     }
 
     #[test]
@@ -359,6 +363,27 @@ mod validation {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn block_size_above_protocol_maximum_fails() {
+        let result = ServerConfigBuilder::new()
+            .block_size(NonZeroU32::new(signature::MAX_BLOCK_SIZE_V30 + 1))
+            .build();
+        assert!(matches!(
+            result,
+            Err(BuilderError::InvalidCombination { .. })
+        ));
+
+        let config = ServerConfigBuilder::new()
+            .protocol(ProtocolVersion::from_supported(29).expect("29 is supported"))
+            .block_size(NonZeroU32::new(signature::MAX_BLOCK_SIZE_V30 + 1))
+            .build()
+            .expect("protocol 29 allows the older, larger maximum");
+        assert_eq!(
+            config.block_size.map(NonZeroU32::get),
+            Some(signature::MAX_BLOCK_SIZE_V30 + 1)
+        );
+    }
+
     #[test]
     fn only_inplace_without_delay_updates_passes() {
         let result = ServerConfigBuilder::new().inplace(true).build();
@@ -408,6 +433,7 @@ mod composite_setters {
             late_delete: true,
             delete_after: false,
             delete_excluded: false,
+            force_delete: true,
         };
         let config = ServerConfigBuilder::new()
             .deletion(deletion.clone())
@@ -7,6 +7,7 @@ pub use builder::ServerConfigBuilder;
 pub use error::BuilderError;
 
 use std::ffi::OsString;
+use std::num::NonZeroU32;
 use std::time::SystemTime;
 
 use compress::zlib::CompressionLevel;
@@ -26,6 +27,13 @@ pub use engine::{ReferenceDirectory, ReferenceDirectoryKind};
 pub struct WriteConfig {
     /// Call fsync() after writing each file (`--fsync`).
     pub fsync: bool,
+    /// Fsync the parent directory after each file is renamed into place
+    /// (`--fsync-dir`).
+    ///
+    /// oc-rsync extension. `--fsync` makes the file data durable, but the
+    /// directory entry the rename creates is only persisted once the parent
+    /// directory itself is synced.
+    pub fsync_dir: bool,
     /// Write directly to destination without temp-file + rename (`--inplace`).
     pub inplace: bool,
     /// Per-file inplace for partial-dir basis files (CF_INPLACE_PARTIAL_DIR).
@@ -80,12 +88,33 @@ pub struct WriteConfig {
     /// - `syscall.c:228` - `do_open()` ORs `O_NOATIME` into flags.
     /// - `syscall.c:687` - `do_open_nofollow()` (added in 3.4.2).
     pub open_noatime: bool,
+    /// Number of received files committed to disk concurrently
+    /// (`--parallel-commit=N`).
+    ///
+    /// oc-rsync extension. Only the disk commit stage (writes, checksum
+    /// verification, `fsync`, rename, metadata) is parallel; delta
+    /// reconstruction stays on the network thread. `1` keeps the single disk
+    /// commit thread; results are reported in file-index order either way.
+    pub parallel_commit: usize,
+    /// Land received file data with `O_DIRECT` (`--direct-write`).
+    ///
+    /// oc-rsync extension that keeps a large receive out of the page cache.
+    /// Linux only; the disk commit thread falls back to buffered writes when
+    /// the filesystem cannot honour the alignment contract.
+    pub direct_write: bool,
+    /// Evict transferred files from the page cache (`--drop-cache`).
+    ///
+    /// oc-rsync extension. The sender drops each source file's pages after
+    /// sending it and the receiver each destination file's pages after
+    /// committing it, via `posix_fadvise(POSIX_FADV_DONTNEED)`. Linux only.
+    pub drop_cache: bool,
 }
 
 impl Default for WriteConfig {
     fn default() -> Self {
         Self {
             fsync: false,
+            fsync_dir: false,
             inplace: false,
             inplace_partial: false,
             write_devices: false,
@@ -94,6 +123,9 @@ impl Default for WriteConfig {
             io_uring_depth: None,
             zero_copy_policy: fast_io::ZeroCopyPolicy::Auto,
             open_noatime: false,
+            parallel_commit: 1,
+            direct_write: false,
+            drop_cache: false,
         }
     }
 }
@@ -146,6 +178,15 @@ pub struct DeletionConfig {
     /// upstream: `options.c` `delete_excluded` global; `exclude.c:rule_matches()`
     /// drops the protection an exclude would otherwise grant during deletion.
     pub delete_excluded: bool,
+    /// Remove a non-empty destination directory that stands where a
+    /// non-directory must be created (`--force`).
+    ///
+    /// An active `--delete` pass implies the same recursive removal; without
+    /// either, only an empty directory makes way.
+    ///
+    /// upstream: `options.c` `force_delete`; `generator.c` `del_opts` carries
+    /// `DEL_RECURSE` when `delete_mode || force_delete`.
+    pub force_delete: bool,
 }
 
 /// Connection and protocol context configuration.
@@ -258,6 +299,14 @@ pub struct ConnectionConfig {
     /// [`implied_source_args`]: Self::implied_source_args
     /// [`files_from_data`]: Self::files_from_data
     pub implied_skip_daemon_module: bool,
+    /// Raw fd of the plaintext TCP socket the daemon sender writes to.
+    ///
+    /// oc-rsync extension: when set (Unix daemon connections only) and the
+    /// client sent `--zero-copy`, the sender streams uncompressed whole files
+    /// with `sendfile(2)` straight from the source file into the socket
+    /// instead of copying each chunk through the writer stack. `None` for SSH,
+    /// stdio and every client-side transfer.
+    pub sendfile_socket: Option<i32>,
 }
 
 /// File selection and filtering options for transfer candidates.
@@ -282,6 +331,29 @@ pub struct FileSelectionConfig {
     /// `util1.c:same_time()` (the signed `int modify_window`) consulted via
     /// `generator.c:quick_check_ok()`.
     pub modify_window: ModifyWindow,
+    /// Whether `--modify-window` was given explicitly (upstream
+    /// `modify_window_set`).
+    ///
+    /// An explicit window, including `0`, is never widened for a FAT-like
+    /// destination.
+    pub modify_window_set: bool,
+    /// Directory holding the destination scan cache (`--cache-dir=DIR`).
+    ///
+    /// oc-rsync extension. When set under `--checksum`, the receiver reuses
+    /// the previous run's destination checksums for files whose device,
+    /// inode, size, mtime and ctime are unchanged instead of re-reading them.
+    pub cache_dir: Option<std::path::PathBuf>,
+    /// File the receiver logs confirmed commits to (`--checkpoint=FILE`).
+    ///
+    /// oc-rsync extension, rewritten periodically and when the transfer
+    /// stops, so an interrupted run can be continued with `--resume`.
+    pub checkpoint: Option<std::path::PathBuf>,
+    /// Checkpoint of an interrupted run to continue from (`--resume=FILE`).
+    ///
+    /// oc-rsync extension. Files it records as committed from an unchanged
+    /// source entry, and still unchanged at the destination, are treated as
+    /// up to date without the quick-check.
+    pub resume: Option<std::path::PathBuf>,
     /// Path for `--files-from` when the server reads the file list directly.
     pub files_from_path: Option<String>,
     /// Use NUL bytes as delimiters for `--files-from` input (`--from0`).
@@ -314,6 +386,23 @@ pub struct FileSelectionConfig {
     /// - `flist.c:send_file_list()` - `missing_args == 2`
     /// - `options.c:818` - `--delete-missing-args`
     pub delete_missing_args: bool,
+    /// Handling of entries that collide after case folding (`--case-collision`).
+    ///
+    /// oc-rsync extension. When enabled, the receiver folds every received
+    /// name and warns about, or renames, entries that would overwrite each
+    /// other on a case-insensitive destination.
+    pub case_collision: engine::CaseCollisionPolicy,
+    /// Handling of names that cannot be created on Windows (`--windows-names`).
+    ///
+    /// oc-rsync extension. When enabled, the receiver sanitizes, skips, or
+    /// fails on received names containing `<>:"|?*`, control characters, or
+    /// trailing dots and spaces.
+    pub windows_names: engine::WindowsNamesPolicy,
+    /// Order regular files are requested in (`--order`).
+    ///
+    /// oc-rsync extension. The receiver queues its transfer requests newest
+    /// or smallest first; the file list itself keeps its canonical order.
+    pub transfer_order: engine::TransferOrder,
 }
 
 /// Configuration supplied to the server entry point.
@@ -359,6 +448,17 @@ pub struct ServerConfig {
     /// protocol instead of using automatic negotiation. Propagated from
     /// the client configuration to ensure both sides agree on the algorithm.
     pub checksum_choice: Option<protocol::ChecksumAlgorithm>,
+    /// Fixed signature block length from `--block-size` / `-B`.
+    ///
+    /// When `Some`, the receiver uses this length for every basis signature
+    /// instead of the square-root heuristic; the layout still clamps it to the
+    /// negotiated protocol's maximum.
+    ///
+    /// # Upstream Reference
+    ///
+    /// - `generator.c:sum_sizes_sqroot()` - `if (block_size) blength = block_size;`
+    /// - `options.c:2787-2790` - forwarded as `-B%u`
+    pub block_size: Option<NonZeroU32>,
     /// Disables sender path safety checks when true (`--trust-sender`).
     ///
     /// When false (default), the receiver validates file list entries from the
@@ -612,6 +712,7 @@ impl Default for ServerConfig {
             write: WriteConfig::default(),
             checksum_seed: None,
             checksum_choice: None,
+            block_size: None,
             trust_sender: false,
             stop_at: None,
             qsort: false,
@@ -656,6 +757,25 @@ impl ServerConfig {
         }
     }
 
+    /// Returns the partial directory in effect for basis lookup and staging.
+    ///
+    /// An explicit `--partial-dir` wins. Otherwise `--delay-updates` implies
+    /// the per-directory `.~tmp~` staging area, so files left there by an
+    /// interrupted run are found again as a basis on the next one.
+    ///
+    /// # Upstream Reference
+    ///
+    /// - `options.c`: `if (delay_updates && !partial_dir) partial_dir = tmp_partialdir;`
+    pub fn effective_partial_dir(&self) -> Option<&std::path::Path> {
+        match self.partial_dir.as_deref() {
+            Some(dir) => Some(dir),
+            None if self.write.delay_updates => Some(std::path::Path::new(
+                crate::disk_commit::DELAY_UPDATES_PARTIAL_DIR,
+            )),
+            None => None,
+        }
+    }
+
     /// Promotes plain `--append` to `--append-verify` semantics for legacy
     /// (protocol < 30) peers.
     ///
@@ -929,6 +1049,24 @@ mod tests {
         assert_eq!(config.effective_backup_suffix(), "");
     }
 
+    #[test]
+    fn effective_partial_dir_defaults_to_staging_dir_under_delay_updates() {
+        let mut config = ServerConfig::default();
+        assert_eq!(config.effective_partial_dir(), None);
+
+        config.write.delay_updates = true;
+        assert_eq!(
+            config.effective_partial_dir(),
+            Some(std::path::Path::new(".~tmp~"))
+        );
+
+        config.partial_dir = Some(std::path::PathBuf::from(".rsync-partial"));
+        assert_eq!(
+            config.effective_partial_dir(),
+            Some(std::path::Path::new(".rsync-partial"))
+        );
+    }
+
     /// Builds a `ServerConfig` with the given append flags for promotion tests.
     fn append_config(append: bool, append_verify: bool) -> ServerConfig {
         let mut config = ServerConfig::default();
@@ -8,8 +8,10 @@ use std::fs::File;
 use std::io::{self, Read, Seek, SeekFrom, Write};
 use std::path::Path;
 
-use engine::signature::FileSignature;
 use logging::debug_log;
+use signature::SignatureLayout;
+#[cfg(feature = "tracing")]
+use tracing::instrument;
 
 use super::checksum::ChecksumVerifier;
 use super::sparse::SparseWriteState;
@@ -188,7 +190,8 @@ pub struct DeltaApplicator<'a> {
     output: File,
     sparse_state: Option<SparseWriteState>,
     checksum_verifier: ChecksumVerifier,
-    basis_signature: Option<&'a FileSignature>,
+    /// Layout of the signature sent for the basis, locating each block.
+    basis_layout: Option<SignatureLayout>,
     /// Cached basis file mapper, opened once and reused for all block
     /// references. Strategy resolved per [`BasisWriterKind`] and target OS.
     basis_map: Option<MapFile<BasisMapStrategy>>,
@@ -228,7 +231,7 @@ impl<'a> DeltaApplicator<'a> {
         output: File,
         config: &DeltaApplyConfig,
         checksum_verifier: ChecksumVerifier,
-        basis_signature: Option<&'a FileSignature>,
+        basis_layout: Option<SignatureLayout>,
         basis_path: Option<&'a Path>,
     ) -> io::Result<Self> {
         let basis_map = if let Some(path) = basis_path {
@@ -259,7 +262,7 @@ impl<'a> DeltaApplicator<'a> {
             output,
             sparse_state: config.sparse.then(SparseWriteState::new),
             checksum_verifier,
-            basis_signature,
+            basis_layout,
             basis_map,
             token_buffer: TokenBuffer::with_default_capacity(),
             same_fs: SameFsCache::Unresolved,
@@ -335,15 +338,13 @@ impl<'a> DeltaApplicator<'a> {
     /// - No basis file is available
     /// - The block index is out of bounds (>= block count)
     pub fn apply_block_ref(&mut self, block_idx: usize) -> io::Result<()> {
-        let (Some(signature), Some(basis_map)) = (&self.basis_signature, self.basis_map.as_mut())
-        else {
+        let (Some(layout), Some(basis_map)) = (self.basis_layout, self.basis_map.as_mut()) else {
             return Err(io::Error::new(
                 io::ErrorKind::InvalidData,
                 format!("block reference {block_idx} without basis file"),
             ));
         };
 
-        let layout = signature.layout();
         let block_len = layout.block_length().get() as u64;
         let block_count = layout.block_count() as usize;
 
@@ -734,14 +735,12 @@ impl<'a> DeltaApplicator<'a> {
         block_idx: usize,
         token_reader: &mut TokenReader,
     ) -> io::Result<()> {
-        let (Some(signature), Some(basis_map)) = (&self.basis_signature, self.basis_map.as_mut())
-        else {
+        let (Some(layout), Some(basis_map)) = (self.basis_layout, self.basis_map.as_mut()) else {
             return Err(io::Error::new(
                 io::ErrorKind::InvalidData,
                 format!("block reference {block_idx} without basis file"),
             ));
         };
-        let layout = signature.layout();
         let block_len = layout.block_length().get() as u64;
         let block_count = layout.block_count() as usize;
         let offset = block_idx as u64 * block_len;
@@ -853,6 +852,14 @@ impl<'a> DeltaApplicator<'a> {
 /// inflate dictionary stays synchronized via `see_token` after each block
 /// match. Mirrors the live receiver loop in
 /// `receiver/transfer/sync.rs:518-634`.
+#[cfg_attr(
+    feature = "tracing",
+    instrument(
+        skip_all,
+        name = "delta_apply",
+        fields(literal_bytes = tracing::field::Empty, matched_bytes = tracing::field::Empty)
+    )
+)]
 pub fn apply_delta_stream<R: Read>(
     reader: &mut R,
     applicator: &mut DeltaApplicator<'_>,
@@ -862,6 +869,10 @@ pub fn apply_delta_stream<R: Read>(
     debug_log!(Deltasum, 2, "recv delta stream start");
 
     while applicator.apply_token(reader, token_reader)? {}
+    #[cfg(feature = "tracing")]
+    tracing::Span::current()
+        .record("literal_bytes", applicator.stats.literal_bytes)
+        .record("matched_bytes", applicator.stats.matched_bytes);
     Ok(())
 }
 
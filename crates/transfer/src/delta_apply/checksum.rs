@@ -2,7 +2,8 @@
 //!
 //! upstream: `receiver.c` end-of-file digest verification.
 
-use checksums::strong::{Md4, Md5, Sha1, StrongDigest, Xxh3, Xxh3_128, Xxh64};
+use checksums::Md5Context;
+use checksums::strong::{Md4, Sha1, StrongDigest, Xxh3, Xxh3_128, Xxh64};
 use protocol::{ChecksumAlgorithm, CompatibilityFlags, NegotiationResult, ProtocolVersion};
 
 /// Whole-file checksum verifier with enum dispatch for zero-allocation runtime
@@ -15,8 +16,9 @@ pub enum ChecksumVerifier {
     None,
     /// MD4 checksum (legacy, protocol < 30).
     Md4(Md4),
-    /// MD5 checksum (protocol 30+ default).
-    Md5(Md5),
+    /// MD5 checksum (protocol 30+ default), hashed through the `checksums`
+    /// MD5 dispatcher.
+    Md5(Md5Context),
     /// SHA1 checksum.
     Sha1(Sha1),
     /// XXH64 checksum (fast non-cryptographic).
@@ -50,7 +52,7 @@ impl ChecksumVerifier {
             .map(|n| Self::for_algorithm_seeded(n.checksum, seed, protocol))
             .unwrap_or_else(|| {
                 if protocol.uses_varint_encoding() {
-                    Self::Md5(Md5::new())
+                    Self::Md5(Md5Context::new())
                 } else {
                     // upstream: checksum.c:125 - protocol >= 27 uses CSUM_MD4_OLD
                     // which prepends the 4-byte seed before file data.
@@ -95,7 +97,7 @@ impl ChecksumVerifier {
         match algorithm {
             ChecksumAlgorithm::None => Self::None,
             ChecksumAlgorithm::MD4 => Self::Md4(Md4::new()),
-            ChecksumAlgorithm::MD5 => Self::Md5(Md5::new()),
+            ChecksumAlgorithm::MD5 => Self::Md5(Md5Context::new()),
             ChecksumAlgorithm::SHA1 => Self::Sha1(Sha1::new()),
             ChecksumAlgorithm::XXH64 => Self::Xxh64(Xxh64::with_seed(0)),
             ChecksumAlgorithm::XXH3 => Self::Xxh3(Xxh3::with_seed(0)),
@@ -220,6 +222,21 @@ mod tests {
         assert_eq!(v.finalize_into(&mut buf), 16);
     }
 
+    #[test]
+    fn verifier_md5_matches_one_shot_digest_across_chunks() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i % 251) as u8).collect();
+        let mut v = ChecksumVerifier::for_algorithm(ChecksumAlgorithm::MD5);
+        for chunk in data.chunks(37) {
+            v.update(chunk);
+        }
+        let mut buf = [0u8; ChecksumVerifier::MAX_DIGEST_LEN];
+        let len = v.finalize_into(&mut buf);
+        assert_eq!(
+            &buf[..len],
+            checksums::strong::Md5::digest(&data).as_slice()
+        );
+    }
+
     #[test]
     fn verifier_protocol_defaults() {
         let v29 = ChecksumVerifier::new(None, ProtocolVersion::try_from(29u8).unwrap(), 0, None);
@@ -365,7 +365,7 @@ mod tests {
             .expected_strong
             .as_ref()
             .expect("expected_strong populated for copy chunks");
-        assert_eq!(expected.as_bytes(), sig.blocks()[0].strong());
+        assert_eq!(expected.as_bytes(), sig.block(0).strong());
         // The digest the applier would compute from chunk.data must match
         // the populated expected digest, otherwise the test fixture is
         // self-contradictory.
@@ -511,7 +511,7 @@ mod tests {
             .expected_strong
             .as_ref()
             .expect("expected_strong populated");
-        assert_eq!(expected.as_bytes(), sig.blocks()[0].strong());
+        assert_eq!(expected.as_bytes(), sig.block(0).strong());
 
         // End token: produces no chunk and does not bump the counter.
         let before = builder.next_sequence();
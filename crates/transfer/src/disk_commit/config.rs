@@ -80,6 +80,9 @@ const MAX_CHANNEL_CAPACITY: usize = 4096;
 pub struct DiskCommitConfig {
     /// Whether to fsync files after writing.
     pub do_fsync: bool,
+    /// Whether to fsync the parent directory once a file is renamed into
+    /// place (`--fsync-dir`), persisting the new directory entry.
+    pub fsync_dir: bool,
     /// Whether to use sparse file writing.
     pub use_sparse: bool,
     /// Whether to preallocate each destination file to its eventual length
@@ -198,12 +201,31 @@ pub struct DiskCommitConfig {
     ///
     /// - `receiver.c:357-373` - `if (append_mode == 2 && mapbuf)` prefix `sum_update`
     pub append_verify: bool,
+    /// Number of files committed concurrently (`--parallel-commit=N`).
+    ///
+    /// oc-rsync extension. `1` (default) keeps the single disk thread; larger
+    /// values spread whole files across that many worker threads while
+    /// results are still reported in file-index order.
+    pub parallel_commit: usize,
+    /// Whether to land file data with `O_DIRECT` (`--direct-write`).
+    ///
+    /// oc-rsync extension for backup targets that should not fill the page
+    /// cache. Linux only; sparse, append and in-place writes and files below
+    /// [`fast_io::DIRECT_WRITE_MIN_BYTES`] keep the buffered writer.
+    pub direct_write: bool,
+    /// Whether to evict each committed file from the page cache
+    /// (`--drop-cache`).
+    ///
+    /// oc-rsync extension. Dirty pages are written back first unless the file
+    /// was already fsynced, so the eviction covers the whole file.
+    pub drop_cache: bool,
 }
 
 impl Default for DiskCommitConfig {
     fn default() -> Self {
         Self {
             do_fsync: false,
+            fsync_dir: false,
             use_sparse: false,
             preallocate: false,
             dest_dir: None,
@@ -223,6 +245,9 @@ impl Default for DiskCommitConfig {
             partial_mode: PartialMode::None,
             delay_updates: false,
             append_verify: false,
+            parallel_commit: 1,
+            direct_write: false,
+            drop_cache: false,
         }
     }
 }
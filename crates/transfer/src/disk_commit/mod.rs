@@ -21,11 +21,15 @@
 
 /// Configuration types for the disk commit thread.
 mod config;
+/// Concurrent per-file workers behind `--parallel-commit=N`.
+mod pool;
 /// File processing: chunked writes, whole-file writes, output file opening,
 /// backup creation, and post-commit metadata application.
 mod process;
 /// Thread spawning, main loop, and channel handle.
 mod thread;
+/// Batched MD5 finalization for runs of small whole-file messages.
+mod verify_batch;
 /// Buffered writer with vectored I/O and direct-write bypass.
 mod writer;
 
@@ -38,5 +42,6 @@ pub use self::config::{
     BackupConfig, DEFAULT_CHANNEL_CAPACITY, DELAY_UPDATES_PARTIAL_DIR, DiskCommitConfig,
     PartialMode,
 };
+pub(crate) use self::process::sync_parent_dir;
 pub use self::process::{DelayedUpdateEntry, delay_updates_staging_path, handle_delayed_updates};
 pub use self::thread::{DiskThreadHandle, spawn_disk_thread};
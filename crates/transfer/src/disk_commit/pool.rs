@@ -0,0 +1,197 @@
+//! Concurrent per-file disk workers (`--parallel-commit=N`).
+//!
+//! oc-rsync extension with no upstream equivalent. The wire still delivers
+//! one file at a time, but finishing a file - writing its data, verifying the
+//! whole-file checksum, `fsync`, rename and metadata - can overlap with
+//! receiving the next ones. Token decoding and delta reconstruction (copying
+//! matched blocks out of the basis file) stay on the network thread, so only
+//! the disk side of the pipeline runs in parallel. With
+//! `N > 1` the disk commit thread becomes a dispatcher: each file's message
+//! sequence (`Begin .. Commit | Abort`, or a coalesced `WholeFile`) is routed
+//! to the next of `N` workers in round-robin order, and a collector thread
+//! forwards their results in dispatch order. The network thread therefore
+//! still sees exactly one [`CommitResult`] per file, in file-index order, and
+//! its FIFO of expected checksums stays aligned.
+//!
+//! This is not a multi-file wire pipeline: the generator still keeps a single
+//! request window and the sender answers one file index at a time, so the
+//! option does not hide link latency; it only keeps slow storage from
+//! stalling the network thread.
+//!
+//! ```text
+//! Network thread ──▶ dispatcher ──▶ worker 0 ──┐
+//!                         │   └──▶ worker 1 ──┤ results
+//!                         │        ...         ▼
+//!                         └── order ──▶ collector ──▶ Network thread
+//! ```
+
+use std::io;
+use std::thread::{self, JoinHandle};
+
+use logging::debug_log;
+
+use crate::pipeline::messages::{CommitResult, FileMessage};
+use crate::pipeline::spsc::{self, TryRecvError};
+
+use super::config::DiskCommitConfig;
+use super::thread::disk_thread_main;
+
+/// One disk worker as seen by the dispatcher.
+struct Worker {
+    file_tx: spsc::Sender<FileMessage>,
+    buf_return_rx: spsc::Receiver<Vec<u8>>,
+    join_handle: JoinHandle<()>,
+}
+
+/// Main loop of the dispatching disk thread when `parallel_commit > 1`.
+///
+/// Falls back to the single-threaded loop if the workers cannot be spawned.
+pub(super) fn dispatch_main(
+    file_rx: spsc::Receiver<FileMessage>,
+    result_tx: spsc::Sender<io::Result<CommitResult>>,
+    buf_return_tx: spsc::Sender<Vec<u8>>,
+    config: DiskCommitConfig,
+) {
+    let count = config.parallel_commit;
+    let capacity = config.effective_channel_capacity();
+
+    let mut workers = Vec::with_capacity(count);
+    let mut result_rxs = Vec::with_capacity(count);
+    for index in 0..count {
+        let (worker_file_tx, worker_file_rx) = spsc::channel::<FileMessage>(capacity);
+        let (worker_result_tx, worker_result_rx) = spsc::channel(capacity * 2);
+        let (worker_buf_tx, worker_buf_rx) = spsc::channel::<Vec<u8>>(capacity * 2);
+        let worker_config = config.clone();
+        let spawned = thread::Builder::new()
+            .name(format!("disk-commit-{index}"))
+            .spawn(move || {
+                disk_thread_main(
+                    worker_file_rx,
+                    worker_result_tx,
+                    worker_buf_tx,
+                    worker_config,
+                );
+            });
+        match spawned {
+            Ok(join_handle) => {
+                workers.push(Worker {
+                    file_tx: worker_file_tx,
+                    buf_return_rx: worker_buf_rx,
+                    join_handle,
+                });
+                result_rxs.push(worker_result_rx);
+            }
+            Err(error) => {
+                debug_log!(
+                    Io,
+                    1,
+                    "parallel-commit: cannot spawn disk worker {index} ({error}), \
+                     using a single disk thread"
+                );
+                shutdown_workers(workers);
+                disk_thread_main(file_rx, result_tx, buf_return_tx, config);
+                return;
+            }
+        }
+    }
+
+    let (order_tx, order_rx) = spsc::channel::<usize>(capacity * count);
+    let collector = match thread::Builder::new()
+        .name("disk-collect".into())
+        .spawn(move || collect_main(order_rx, result_rxs, result_tx))
+    {
+        Ok(handle) => handle,
+        Err(error) => {
+            // The result sender moved into the failed closure and is gone, so
+            // the network thread observes a disconnected result channel.
+            debug_log!(Io, 1, "parallel-commit: cannot spawn collector ({error})");
+            shutdown_workers(workers);
+            return;
+        }
+    };
+
+    debug_log!(Io, 1, "parallel-commit: {count} disk workers");
+
+    let mut next = 0usize;
+    let mut current: Option<usize> = None;
+    while let Ok(msg) = file_rx.recv() {
+        let target = match &msg {
+            FileMessage::Shutdown => break,
+            FileMessage::Chunk(_) | FileMessage::SkipMatched(_) if current.is_some() => current,
+            FileMessage::Commit { .. } | FileMessage::Abort { .. } if current.is_some() => {
+                current.take()
+            }
+            // Begin opens a file on the next worker; WholeFile and stray
+            // messages are complete units that yield exactly one result.
+            _ => {
+                let worker = next;
+                next = (next + 1) % count;
+                if order_tx.send(worker).is_err() {
+                    break;
+                }
+                if matches!(msg, FileMessage::Begin(_)) {
+                    current = Some(worker);
+                }
+                Some(worker)
+            }
+        };
+        let Some(worker) = target else { break };
+        if workers[worker].file_tx.send(msg).is_err() {
+            break;
+        }
+        recycle_buffers(&workers, &buf_return_tx);
+    }
+
+    drop(order_tx);
+    shutdown_workers(workers);
+    let _ = collector.join();
+}
+
+/// Forwards results to the network thread in the order files were
+/// dispatched.
+fn collect_main(
+    order_rx: spsc::Receiver<usize>,
+    result_rxs: Vec<spsc::Receiver<io::Result<CommitResult>>>,
+    result_tx: spsc::Sender<io::Result<CommitResult>>,
+) {
+    while let Ok(worker) = order_rx.recv() {
+        let result = result_rxs[worker].recv().unwrap_or_else(|_| {
+            Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "disk worker exited with a file pending",
+            ))
+        });
+        let is_terminal = matches!(
+            &result,
+            Err(e) if matches!(e.kind(), io::ErrorKind::Interrupted | io::ErrorKind::BrokenPipe)
+        );
+        if result_tx.send(result).is_err() || is_terminal {
+            break;
+        }
+    }
+}
+
+/// Moves spare buffers returned by the workers onto the shared return
+/// channel. Both ends are best-effort: a full ring simply drops the spare.
+fn recycle_buffers(workers: &[Worker], buf_return_tx: &spsc::Sender<Vec<u8>>) {
+    for worker in workers {
+        loop {
+            match worker.buf_return_rx.try_recv() {
+                Ok(buf) => {
+                    let _ = buf_return_tx.try_send(buf);
+                }
+                Err(TryRecvError::Empty | TryRecvError::Disconnected) => break,
+            }
+        }
+    }
+}
+
+/// Tells every worker to stop after its current file and waits for it.
+fn shutdown_workers(workers: Vec<Worker>) {
+    for worker in &workers {
+        let _ = worker.file_tx.send(FileMessage::Shutdown);
+    }
+    for worker in workers {
+        let _ = worker.join_handle.join();
+    }
+}
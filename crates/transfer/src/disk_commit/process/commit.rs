@@ -2,9 +2,10 @@
 //! truncation, cross-device fallback, and partial-file retention.
 //!
 //! Mirrors upstream `receiver.c` finalization and `cleanup.c` partial
-//! handling. Rename uses io_uring `IORING_OP_RENAMEAT` when available, falling
-//! back to `std::fs::rename` with a copy+remove EXDEV path
-//! (`util1.c:robust_rename()`).
+//! handling. Rename uses io_uring `IORING_OP_RENAMEAT` when available -
+//! on the disk thread's batch ring when one is active, so the writes, `fsync`
+//! and rename of a file share a single ring - falling back to
+//! `std::fs::rename` with a copy+remove EXDEV path (`util1.c:robust_rename()`).
 
 use std::fs;
 use std::io;
@@ -13,6 +14,7 @@ use std::path::{Path, PathBuf};
 use engine::{
     CleanupManager, compute_backup_path, trace_make_backup_copy, trace_make_backup_rename,
 };
+use metadata::MetadataOptions;
 
 use crate::pipeline::messages::{BackupNotice, BeginMessage};
 use crate::temp_guard::TempFileGuard;
@@ -73,8 +75,9 @@ pub(super) struct CommitOutcome {
 ///
 /// When io_uring is available (Linux 5.11+ with `IORING_OP_RENAMEAT`), the
 /// temp-file rename is submitted as an io_uring SQE instead of a synchronous
-/// `rename(2)` syscall. Falls back to `std::fs::rename` on all other
-/// platforms or when the kernel lacks the opcode.
+/// `rename(2)` syscall, reusing `disk_batch`'s ring when the disk thread has
+/// one. Falls back to `std::fs::rename` on all other platforms, when the
+/// kernel lacks the opcode, or under `--no-io-uring`.
 ///
 /// # Upstream Reference
 ///
@@ -87,6 +90,7 @@ pub(super) fn commit_file(
     needs_rename: bool,
     bytes_written: u64,
     sparse_final: Option<SparseFinalize>,
+    disk_batch: Option<&mut fast_io::IoUringDiskBatch>,
 ) -> io::Result<CommitOutcome> {
     // upstream: fileio.c:43 sparse_end() - the temp+rename path truncates and
     // punches the temp file in the caller BEFORE applying metadata, so the
@@ -115,11 +119,12 @@ pub(super) fn commit_file(
 
     if needs_rename && config.delay_updates {
         // upstream: receiver.c:1039-1052 - stage to partial dir (.~tmp~)
-        let staging_path = partial_dir_path(&begin.file_path);
+        let staging_path = delay_updates_staging(config, &begin.file_path);
         if let Some(parent) = staging_path.parent() {
             fs::create_dir_all(parent)?;
         }
-        let result = rename_config_sandboxed(config, cleanup_guard.path(), &staging_path)?;
+        let result =
+            rename_config_batched(config, disk_batch, cleanup_guard.path(), &staging_path)?;
         CleanupManager::global().unregister_temp_file(cleanup_guard.path());
         cleanup_guard.keep();
         return Ok(CommitOutcome {
@@ -130,8 +135,24 @@ pub(super) fn commit_file(
     }
 
     let was_copy = if needs_rename {
-        let result = rename_config_sandboxed(config, cleanup_guard.path(), &begin.file_path)?;
+        let rename =
+            || rename_config_batched(config, disk_batch, cleanup_guard.path(), &begin.file_path);
+        // upstream: patches/fileflags.diff - an immutable or append-only
+        // destination refuses to be renamed over, so its flags are cleared
+        // first and restored if the rename fails.
+        let result = if config
+            .metadata_opts
+            .as_ref()
+            .is_some_and(MetadataOptions::fileflags)
+        {
+            ::metadata::while_mutable(&begin.file_path, rename)?
+        } else {
+            rename()?
+        };
         CleanupManager::global().unregister_temp_file(cleanup_guard.path());
+        if config.fsync_dir {
+            sync_parent_dir(&begin.file_path)?;
+        }
         result
     } else if begin.is_inplace && !begin.is_device_target {
         // upstream: receiver.c:496 gates the in-place ftruncate on
@@ -172,6 +193,35 @@ pub(super) fn commit_file(
     })
 }
 
+/// Fsyncs the directory containing `path` (`--fsync-dir`).
+///
+/// A rename only updates the parent directory, so the new entry survives a
+/// crash once that directory is synced, independent of the file's own
+/// `fsync`. oc-rsync extension; directories cannot be opened for syncing on
+/// Windows, where this is a no-op.
+pub(crate) fn sync_parent_dir(path: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        let parent = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        fs::File::open(parent)
+            .and_then(|dir| dir.sync_all())
+            .map_err(|e| {
+                io::Error::new(
+                    e.kind(),
+                    format!("fsync failed for directory {parent:?}: {e}"),
+                )
+            })
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        Ok(())
+    }
+}
+
 /// Removes the `--partial-dir` basis file after a successful commit and
 /// rmdir's the (now-possibly-empty) partial directory for a relative
 /// `--partial-dir`, mirroring upstream `handle_partial_dir(PDIR_DELETE)`.
@@ -212,6 +262,23 @@ pub(super) fn partial_dir_path(file_path: &Path) -> PathBuf {
     parent.join(DELAY_UPDATES_PARTIAL_DIR).join(basename)
 }
 
+/// Computes where `--delay-updates` stages a destination file.
+///
+/// An explicit `--partial-dir` replaces the default `.~tmp~` staging area,
+/// so the staged file lands exactly where the next run's basis lookup will
+/// look for it.
+///
+/// upstream: options.c - `if (delay_updates && !partial_dir) partial_dir =
+/// tmp_partialdir;`
+pub(super) fn delay_updates_staging(config: &DiskCommitConfig, file_path: &Path) -> PathBuf {
+    if let PartialMode::PartialDir(ref dir) = config.partial_mode
+        && let Some(staging) = crate::temp_guard::partial_dir_fname(file_path, dir)
+    {
+        return staging;
+    }
+    partial_dir_path(file_path)
+}
+
 /// Retains a partial temp file instead of deleting it on interrupt.
 ///
 /// Depending on the `PartialMode`:
@@ -325,9 +392,45 @@ pub(super) fn retain_partial_file(
 /// happens when `--temp-dir` points to a different filesystem than the
 /// destination.
 pub(super) fn rename_with_io_uring_fallback(old_path: &Path, new_path: &Path) -> io::Result<bool> {
-    if let Some(result) = fast_io::try_rename_via_io_uring(old_path, new_path) {
-        return result.map(|()| false);
+    match fast_io::try_rename_via_io_uring(old_path, new_path) {
+        Some(Ok(())) => Ok(false),
+        Some(Err(e)) if !is_cross_device(&e) => Err(e),
+        _ => rename_or_copy(old_path, new_path),
+    }
+}
+
+/// Renames on the disk thread's batch ring when it has one, otherwise via
+/// [`rename_with_io_uring_fallback`].
+///
+/// oc-rsync extension: with a [`fast_io::IoUringDiskBatch`] active the
+/// `IORING_OP_RENAMEAT` SQE goes on the same ring that carried the file's
+/// writes and `fsync`, instead of a transient ring built per commit. Under
+/// `--no-io-uring` no ring is touched at all. A kernel without the opcode, or
+/// an `EXDEV` from `--temp-dir` on another mount, takes the plain
+/// `std::fs::rename` / copy+remove path.
+fn rename_with_batch(
+    config: &DiskCommitConfig,
+    disk_batch: Option<&mut fast_io::IoUringDiskBatch>,
+    old_path: &Path,
+    new_path: &Path,
+) -> io::Result<bool> {
+    if let Some(batch) = disk_batch {
+        match batch.rename(old_path, new_path) {
+            Ok(()) => return Ok(false),
+            Err(e) if e.kind() != io::ErrorKind::Unsupported && !is_cross_device(&e) => {
+                return Err(e);
+            }
+            Err(_) => return rename_or_copy(old_path, new_path),
+        }
     }
+    if config.io_uring_policy == fast_io::IoUringPolicy::Disabled {
+        return rename_or_copy(old_path, new_path);
+    }
+    rename_with_io_uring_fallback(old_path, new_path)
+}
+
+/// `std::fs::rename` with the EXDEV copy+remove backstop.
+fn rename_or_copy(old_path: &Path, new_path: &Path) -> io::Result<bool> {
     match fs::rename(old_path, new_path) {
         Ok(()) => Ok(false),
         Err(e) if is_cross_device(&e) => {
@@ -374,11 +477,23 @@ pub(super) fn rename_with_io_uring_fallback(old_path: &Path, new_path: &Path) ->
 ///
 /// Returns `Ok(false)` for an in-place rename, `Ok(true)` when the EXDEV
 /// copy+remove fallback ran.
-#[cfg(unix)]
 pub(super) fn rename_config_sandboxed(
     config: &DiskCommitConfig,
     old_path: &Path,
     new_path: &Path,
+) -> io::Result<bool> {
+    rename_config_batched(config, None, old_path, new_path)
+}
+
+/// [`rename_config_sandboxed`] for the commit path, which may hand over the
+/// disk thread's io_uring batch. The sandbox-anchored rename always wins; the
+/// batch ring only serves the path-based fallback (see [`rename_with_batch`]).
+#[cfg(unix)]
+fn rename_config_batched(
+    config: &DiskCommitConfig,
+    disk_batch: Option<&mut fast_io::IoUringDiskBatch>,
+    old_path: &Path,
+    new_path: &Path,
 ) -> io::Result<bool> {
     if let (Some(sandbox), Some(dest_dir)) = (config.sandbox.as_ref(), config.dest_dir.as_deref())
         && let (Ok(old_rel), Ok(new_rel)) = (
@@ -403,7 +518,7 @@ pub(super) fn rename_config_sandboxed(
         )?;
         return Ok(false);
     }
-    rename_with_io_uring_fallback(old_path, new_path)
+    rename_with_batch(config, disk_batch, old_path, new_path)
 }
 
 /// Non-Unix: the `*at` sandbox helpers do not exist. On Windows the commit
@@ -412,20 +527,22 @@ pub(super) fn rename_config_sandboxed(
 /// Unix `renameat` anchoring, so a junction/mount-point swap on the commit
 /// parent between temp-create and rename cannot redirect the committed file
 /// (CVE-2024-12747 residual). Other non-Unix targets keep the path-based
-/// [`rename_with_io_uring_fallback`] with no behavior change.
+/// [`rename_with_batch`] path with no behavior change.
 #[cfg(not(unix))]
-pub(super) fn rename_config_sandboxed(
-    _config: &DiskCommitConfig,
+fn rename_config_batched(
+    config: &DiskCommitConfig,
+    disk_batch: Option<&mut fast_io::IoUringDiskBatch>,
     old_path: &Path,
     new_path: &Path,
 ) -> io::Result<bool> {
     #[cfg(windows)]
     {
+        let _ = (config, disk_batch);
         crate::temp_guard::commit_rename_no_follow(old_path, new_path)
     }
     #[cfg(not(windows))]
     {
-        rename_with_io_uring_fallback(old_path, new_path)
+        rename_with_batch(config, disk_batch, old_path, new_path)
     }
 }
 
@@ -8,6 +8,8 @@ use std::fs;
 use std::io;
 
 use engine::CleanupManager;
+#[cfg(feature = "tracing")]
+use tracing::instrument;
 
 use crate::delta_apply::{ChecksumVerifier, SparseWriteState};
 use crate::pipeline::messages::{
@@ -21,6 +23,7 @@ use crate::temp_guard::open_tmpfile;
 use crate::temp_guard::open_tmpfile_sandboxed;
 
 use super::super::config::DiskCommitConfig;
+use super::super::verify_batch::QueuedWholeFile;
 use super::super::writer::{ReusableBufWriter, Writer};
 use super::commit::{
     SparseFinalize, commit_file, finalize_sparse, make_backup_copy, retain_partial_file,
@@ -85,7 +88,15 @@ fn verify_whole_file_checksum(
     verifier: Option<ChecksumVerifier>,
     expected: &ExpectedChecksum,
 ) -> (Option<ComputedChecksum>, bool) {
-    let computed = finalize_checksum(verifier);
+    verify_computed_checksum(finalize_checksum(verifier), expected)
+}
+
+/// Compares an already finalized digest against the sender's trailing sum,
+/// with the same pass rules as [`verify_whole_file_checksum`].
+fn verify_computed_checksum(
+    computed: Option<ComputedChecksum>,
+    expected: &ExpectedChecksum,
+) -> (Option<ComputedChecksum>, bool) {
     let verify_ok = match computed {
         Some(ref c) if expected.len > 0 => {
             c.len == expected.len && c.bytes[..c.len] == expected.bytes[..expected.len]
@@ -135,14 +146,23 @@ fn withhold_failed_commit(
 /// (Windows/IOCP) and sparse mode is disabled, writes are submitted via the
 /// shared batched writer. Sparse mode requires `Seek`, which neither batch
 /// writer provides, so it always falls back to buffered writes. Only one of
-/// the two batched writers can be active at a time.
+/// the two batched writers can be active at a time. The io_uring batch is
+/// handed back to [`commit_file`] so the temp-file rename runs on its ring.
+#[cfg_attr(
+    feature = "tracing",
+    instrument(
+        skip_all,
+        name = "disk_commit",
+        fields(file = begin.file_entry_index, size = begin.target_size)
+    )
+)]
 pub(in crate::disk_commit) fn process_file(
     file_rx: &spsc::Receiver<FileMessage>,
     buf_return_tx: &spsc::Sender<Vec<u8>>,
     config: &DiskCommitConfig,
     mut begin: BeginMessage,
     write_buf: &mut Vec<u8>,
-    disk_batch: Option<&mut fast_io::IoUringDiskBatch>,
+    mut disk_batch: Option<&mut fast_io::IoUringDiskBatch>,
     iocp_batch: Option<&mut fast_io::IocpDiskBatch>,
 ) -> io::Result<CommitResult> {
     // upstream: receiver.c:999-1006 - when open_tmpfile() fails (e.g. EACCES
@@ -197,16 +217,18 @@ pub(in crate::disk_commit) fn process_file(
     // becomes preallocated_len, overriding the inplace basis for sparse hole
     // decisions; a failure warns and continues (never aborts).
     let preallocated_len = maybe_preallocate(&file, config, &begin, basis_len);
+    let cache_handle = cache_drop_handle(&file, config);
 
     let mut output = make_writer(
         file,
         write_buf,
-        disk_batch,
+        disk_batch.as_deref_mut(),
         iocp_batch,
         config.use_sparse,
         begin.append_offset,
         begin.is_inplace,
         begin.target_size,
+        config.direct_write,
     )?;
 
     let mut sparse_state = if config.use_sparse {
@@ -309,6 +331,7 @@ pub(in crate::disk_commit) fn process_file(
 
                 output.flush_and_sync(config.do_fsync, &begin.file_path)?;
                 output.finish(config.do_fsync, &begin.file_path)?;
+                drop_committed_cache(cache_handle.as_ref(), config);
 
                 // upstream: receiver.c:505-519 - compute the whole-file checksum
                 // and compare it against the sender's trailing sum BEFORE the
@@ -358,6 +381,7 @@ pub(in crate::disk_commit) fn process_file(
                     needs_rename,
                     bytes_written,
                     sparse_final,
+                    disk_batch,
                 )?;
 
                 // Temp file has been renamed to its final destination (or
@@ -450,17 +474,31 @@ pub(in crate::disk_commit) fn process_file(
 /// Avoids the per-message channel recv loop of [`process_file`], reducing
 /// futex overhead from 3+ sends/recvs to 1 for small files. When
 /// `disk_batch` (io_uring) or `iocp_batch` (IOCP) is `Some` and sparse mode
-/// is disabled, the chunk is submitted via the shared batched writer.
+/// is disabled, the chunk is submitted via the shared batched writer. A
+/// digest already batched with neighbouring files replaces hashing the data.
+#[cfg_attr(
+    feature = "tracing",
+    instrument(
+        skip_all,
+        name = "disk_commit",
+        fields(file = file.begin.file_entry_index, size = file.begin.target_size)
+    )
+)]
 pub(in crate::disk_commit) fn process_whole_file(
     buf_return_tx: &spsc::Sender<Vec<u8>>,
     config: &DiskCommitConfig,
-    mut begin: BeginMessage,
-    data: Vec<u8>,
-    expected_checksum: ExpectedChecksum,
+    file: QueuedWholeFile,
     write_buf: &mut Vec<u8>,
-    disk_batch: Option<&mut fast_io::IoUringDiskBatch>,
+    mut disk_batch: Option<&mut fast_io::IoUringDiskBatch>,
     iocp_batch: Option<&mut fast_io::IocpDiskBatch>,
 ) -> io::Result<CommitResult> {
+    let QueuedWholeFile {
+        begin,
+        data,
+        expected_checksum,
+        precomputed: precomputed_checksum,
+    } = file;
+    let mut begin = *begin;
     // upstream: receiver.c:999-1006 - open failure is a benign per-file partial,
     // not a fatal abort. The coalesced WholeFile carries its data inline, so
     // there are no queued channel messages to drain (unlike process_file); the
@@ -485,25 +523,30 @@ pub(in crate::disk_commit) fn process_whole_file(
     // upstream: receiver.c:319-336 - preallocate the destination before writing
     // when --preallocate is set (see process_file).
     let preallocated_len = maybe_preallocate(&file, config, &begin, basis_len);
+    let cache_handle = cache_drop_handle(&file, config);
 
     let mut output = make_writer(
         file,
         write_buf,
-        disk_batch,
+        disk_batch.as_deref_mut(),
         iocp_batch,
         config.use_sparse,
         begin.append_offset,
         begin.is_inplace,
         begin.target_size,
+        config.direct_write,
     )?;
     let bytes_written = data.len() as u64;
 
     let mut checksum_verifier = begin.checksum_verifier.take();
-    // upstream: receiver.c:357-373 - fold the existing prefix into the
-    // whole-file checksum under --append-verify before hashing the tail.
-    sum_append_prefix(config, &begin, &mut checksum_verifier)?;
-    if let Some(ref mut verifier) = checksum_verifier {
-        verifier.update(&data);
+    // A digest batched with neighbouring small files already covers `data`.
+    if precomputed_checksum.is_none() {
+        // upstream: receiver.c:357-373 - fold the existing prefix into the
+        // whole-file checksum under --append-verify before hashing the tail.
+        sum_append_prefix(config, &begin, &mut checksum_verifier)?;
+        if let Some(ref mut verifier) = checksum_verifier {
+            verifier.update(&data);
+        }
     }
 
     let sparse_final = if config.use_sparse {
@@ -524,12 +567,15 @@ pub(in crate::disk_commit) fn process_whole_file(
 
     output.flush_and_sync(config.do_fsync, &begin.file_path)?;
     output.finish(config.do_fsync, &begin.file_path)?;
+    drop_committed_cache(cache_handle.as_ref(), config);
 
     // upstream: receiver.c:505-519 - verify the whole-file checksum before the
     // file is put into place (see process_file for the full rationale). A
     // temp+rename mismatch is retained/discarded, never renamed over dest.
-    let (computed_checksum, verify_ok) =
-        verify_whole_file_checksum(checksum_verifier.take(), &expected_checksum);
+    let (computed_checksum, verify_ok) = match precomputed_checksum {
+        Some(computed) => verify_computed_checksum(Some(computed), &expected_checksum),
+        None => verify_whole_file_checksum(checksum_verifier.take(), &expected_checksum),
+    };
     if !verify_ok && needs_rename {
         return Ok(withhold_failed_commit(
             config,
@@ -564,6 +610,7 @@ pub(in crate::disk_commit) fn process_whole_file(
         needs_rename,
         bytes_written,
         sparse_final,
+        disk_batch,
     )?;
 
     if needs_rename && outcome.delayed_path.is_none() {
@@ -685,7 +732,8 @@ fn make_inplace_backup(
 ///
 /// When `begin.is_device_target` is set, the device file is opened with `O_WRONLY`
 /// (no create, no truncate). Device files cannot use temp+rename since you cannot
-/// rename onto a device node.
+/// rename onto a device node. A target larger than the device's capacity is
+/// rejected with [`io::ErrorKind::StorageFull`].
 ///
 /// # Inplace mode
 ///
@@ -701,6 +749,22 @@ fn open_output_file(
     config: &DiskCommitConfig,
 ) -> io::Result<(fs::File, TempFileGuard, bool)> {
     if begin.is_device_target {
+        // A block device smaller than the file is refused before any byte
+        // lands on it; character devices report no capacity.
+        #[cfg(unix)]
+        {
+            let capacity = ::metadata::device_readable_size(&begin.file_path)?;
+            if capacity > 0 && begin.target_size > capacity {
+                return Err(io::Error::new(
+                    io::ErrorKind::StorageFull,
+                    format!(
+                        "device {} holds {capacity} bytes but {} are needed",
+                        begin.file_path.display(),
+                        begin.target_size
+                    ),
+                ));
+            }
+        }
         let file = fs::OpenOptions::new().write(true).open(&begin.file_path)?;
         // The guard wraps the real device node, not a temp file. A
         // mid-transfer error must NOT unlink it (upstream never unlinks an
@@ -812,6 +876,31 @@ fn maybe_preallocate(
     }
 }
 
+/// Duplicates the output handle for `--drop-cache`.
+///
+/// The writer consumes `file`, and the batched backends close it inside
+/// `finish`, so the eviction after the commit needs a handle of its own.
+fn cache_drop_handle(file: &fs::File, config: &DiskCommitConfig) -> Option<fs::File> {
+    if config.drop_cache {
+        file.try_clone().ok()
+    } else {
+        None
+    }
+}
+
+/// Evicts a committed file's pages from the page cache (`--drop-cache`).
+///
+/// Dirty pages are written back first unless `--fsync` already did so. The
+/// advice is best effort; a failure leaves the pages cached and is not an
+/// error.
+fn drop_committed_cache(handle: Option<&fs::File>, config: &DiskCommitConfig) {
+    if let Some(handle) = handle
+        && let Err(err) = fast_io::drop_file_cache(handle, !config.do_fsync)
+    {
+        logging::debug_log!(Io, 2, "drop-cache: {err}");
+    }
+}
+
 /// Constructs the per-file [`Writer`] dispatching between batched async
 /// submission (io_uring on Linux, IOCP on Windows), the macOS `F_NOCACHE` +
 /// `writev` writer, and the buffered fallback.
@@ -835,6 +924,9 @@ fn maybe_preallocate(
 ///
 /// On the batched paths, `batch.begin_file(file)` registers the file with the
 /// backend; the matching `commit_file` happens via [`Writer::finish`].
+///
+/// `direct_write` (`--direct-write`) selects [`fast_io::DirectFileWriter`]
+/// ahead of every other backend on Linux and is ignored elsewhere.
 #[allow(unused_variables)] // batch params are unused on platforms without their backend
 pub(super) fn make_writer<'a>(
     file: fs::File,
@@ -845,7 +937,24 @@ pub(super) fn make_writer<'a>(
     append_offset: u64,
     is_inplace: bool,
     size_hint: u64,
+    direct_write: bool,
 ) -> io::Result<Writer<'a>> {
+    // --direct-write: an explicit request to keep the receive out of the page
+    // cache, so it outranks every other backend. O_DIRECT writes land at the
+    // file offset in whole aligned blocks, which rules out the seeking sparse,
+    // append and in-place modes; files smaller than one staging block would
+    // be all unaligned tail and keep the buffered writer.
+    #[cfg(target_os = "linux")]
+    {
+        if direct_write
+            && !use_sparse
+            && append_offset == 0
+            && !is_inplace
+            && size_hint >= fast_io::DIRECT_WRITE_MIN_BYTES
+        {
+            return Ok(Writer::Direct(fast_io::DirectFileWriter::new(file)?));
+        }
+    }
     // In-place updates must use the buffered writer: they seek past matched
     // basis bytes already in the destination (upstream skip_matched, fileio.c:
     // 202-209), and the batched backends submit at their own internally
@@ -23,6 +23,7 @@ mod metadata;
 #[cfg(test)]
 mod tests;
 
+pub(crate) use self::commit::sync_parent_dir;
 pub use self::delayed::{DelayedUpdateEntry, delay_updates_staging_path, handle_delayed_updates};
 pub(super) use self::file_ops::{process_file, process_whole_file};
 
@@ -34,11 +35,12 @@ use self::commit::ForceExdev;
 use self::commit::rename_config_sandboxed;
 #[cfg(test)]
 use self::commit::{
-    is_cross_device, make_backup, make_backup_copy, partial_dir_path, rename_with_io_uring_fallback,
+    delay_updates_staging, is_cross_device, make_backup, make_backup_copy, partial_dir_path,
+    rename_with_io_uring_fallback,
 };
-#[cfg(all(test, target_os = "macos"))]
+#[cfg(all(test, any(target_os = "macos", target_os = "linux")))]
 use self::file_ops::make_writer;
 #[cfg(test)]
 use super::config::{BackupConfig, DiskCommitConfig};
-#[cfg(all(test, target_os = "macos"))]
+#[cfg(all(test, any(target_os = "macos", target_os = "linux")))]
 use super::writer::Writer;
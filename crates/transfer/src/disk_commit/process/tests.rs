@@ -91,6 +91,7 @@ fn make_writer_selects_macos_for_non_sparse_zero_offset() {
         /* append_offset */ 0,
         /* is_inplace */ false,
         /* size_hint */ 0,
+        /* direct_write */ false,
     )
     .unwrap();
 
@@ -120,6 +121,7 @@ fn make_writer_falls_back_to_buffered_when_seek_required() {
         /* append_offset */ 0,
         /* is_inplace */ false,
         /* size_hint */ 0,
+        /* direct_write */ false,
     )
     .unwrap();
     assert!(
@@ -140,6 +142,7 @@ fn make_writer_falls_back_to_buffered_when_seek_required() {
         /* append_offset */ 4096,
         /* is_inplace */ false,
         /* size_hint */ 0,
+        /* direct_write */ false,
     )
     .unwrap();
     assert!(
@@ -168,6 +171,7 @@ fn make_writer_selects_macos_gcd_when_feature_enabled() {
         /* append_offset */ 0,
         /* is_inplace */ false,
         /* size_hint */ 0,
+        /* direct_write */ false,
     )
     .unwrap();
 
@@ -197,6 +201,7 @@ fn make_writer_macos_gcd_falls_back_to_buffered_for_seek() {
         /* append_offset */ 0,
         /* is_inplace */ false,
         /* size_hint */ 0,
+        /* direct_write */ false,
     )
     .unwrap();
     assert!(
@@ -216,6 +221,7 @@ fn make_writer_macos_gcd_falls_back_to_buffered_for_seek() {
         /* append_offset */ 4096,
         /* is_inplace */ false,
         /* size_hint */ 0,
+        /* direct_write */ false,
     )
     .unwrap();
     assert!(
@@ -265,6 +271,7 @@ fn macos_gcd_writer_matches_buffered_byte_for_byte() {
         /* append_offset */ 0,
         /* is_inplace */ false,
         /* size_hint */ 0,
+        /* direct_write */ false,
     )
     .unwrap();
     assert!(matches!(buffered_writer, Writer::Buffered(_)));
@@ -282,6 +289,68 @@ fn macos_gcd_writer_matches_buffered_byte_for_byte() {
     );
 }
 
+/// Verifies `make_writer` honours `--direct-write` for files of at least one
+/// staging block and keeps the buffered writer for smaller ones.
+#[cfg(target_os = "linux")]
+#[test]
+fn make_writer_selects_direct_for_large_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut write_buf = Vec::with_capacity(256 * 1024);
+
+    let large = fs::File::create(dir.path().join("direct_large.bin")).unwrap();
+    let writer = make_writer(
+        large,
+        &mut write_buf,
+        None,
+        None,
+        /* use_sparse */ false,
+        /* append_offset */ 0,
+        /* is_inplace */ false,
+        /* size_hint */ fast_io::DIRECT_WRITE_MIN_BYTES,
+        /* direct_write */ true,
+    )
+    .unwrap();
+    assert!(matches!(writer, Writer::Direct(_)));
+    drop(writer);
+
+    let small = fs::File::create(dir.path().join("direct_small.bin")).unwrap();
+    let writer = make_writer(
+        small,
+        &mut write_buf,
+        None,
+        None,
+        /* use_sparse */ false,
+        /* append_offset */ 0,
+        /* is_inplace */ false,
+        /* size_hint */ 4096,
+        /* direct_write */ true,
+    )
+    .unwrap();
+    assert!(!matches!(writer, Writer::Direct(_)));
+}
+
+/// Verifies the direct writer lands the same bytes as the buffered writer,
+/// unaligned tail included.
+#[cfg(target_os = "linux")]
+#[test]
+fn direct_writer_output_matches_buffered_writer() {
+    let dir = tempfile::tempdir().unwrap();
+    let data: Vec<u8> = (0..1_500_007u32).map(|i| (i % 241) as u8).collect();
+
+    let direct_path = dir.path().join("parity_direct.bin");
+    let file = fs::File::create(&direct_path).unwrap();
+    let mut writer = Writer::Direct(fast_io::DirectFileWriter::new(file).unwrap());
+    for chunk in data.chunks(64 * 1024 + 3) {
+        writer.write_chunk(chunk).unwrap();
+    }
+    writer
+        .flush_and_sync(/* do_fsync */ true, &direct_path)
+        .unwrap();
+    writer.finish(/* do_fsync */ true, &direct_path).unwrap();
+
+    assert_eq!(fs::read(&direct_path).unwrap(), data);
+}
+
 /// Verifies consistent io_uring availability for RENAMEAT2 across calls.
 #[test]
 fn rename_io_uring_availability_consistent() {
@@ -321,6 +390,38 @@ fn partial_dir_path_root_level_file() {
     assert_eq!(staging, PathBuf::from("/dest/.~tmp~/file.txt"));
 }
 
+/// Verifies `--delay-updates` stages into `.~tmp~` by default but into an
+/// explicit `--partial-dir` when one is configured, relative or absolute.
+#[test]
+fn delay_updates_staging_honours_partial_dir() {
+    use super::super::config::PartialMode;
+
+    let path = Path::new("/dest/subdir/file.txt");
+    let config = DiskCommitConfig::default();
+    assert_eq!(
+        delay_updates_staging(&config, path),
+        PathBuf::from("/dest/subdir/.~tmp~/file.txt")
+    );
+
+    let config = DiskCommitConfig {
+        partial_mode: PartialMode::PartialDir(PathBuf::from(".rsync-partial")),
+        ..DiskCommitConfig::default()
+    };
+    assert_eq!(
+        delay_updates_staging(&config, path),
+        PathBuf::from("/dest/subdir/.rsync-partial/file.txt")
+    );
+
+    let config = DiskCommitConfig {
+        partial_mode: PartialMode::PartialDir(PathBuf::from("/var/partial")),
+        ..DiskCommitConfig::default()
+    };
+    assert_eq!(
+        delay_updates_staging(&config, path),
+        PathBuf::from("/var/partial/file.txt")
+    );
+}
+
 /// Verifies `make_backup` returns the upstream-format backup notice with
 /// destination-relative paths so the main thread can surface upstream's
 /// `INFO_GTE(BACKUP, 1)` line during wire transfers.
@@ -674,3 +775,25 @@ fn commit_rename_without_sandbox_uses_path_fallback() {
     assert!(final_path.exists());
     assert_eq!(fs::read(&final_path).unwrap(), b"fallback content");
 }
+
+/// `--no-io-uring` keeps the commit rename off io_uring entirely; the plain
+/// `std::fs::rename` path still replaces the destination.
+#[test]
+fn commit_rename_with_io_uring_disabled_uses_std_rename() {
+    let dir = tempfile::tempdir().unwrap();
+    let temp_path = dir.path().join(".tmp.payload");
+    let final_path = dir.path().join("payload.bin");
+    fs::write(&temp_path, b"new content").unwrap();
+    fs::write(&final_path, b"old content").unwrap();
+
+    let config = DiskCommitConfig {
+        io_uring_policy: fast_io::IoUringPolicy::Disabled,
+        ..DiskCommitConfig::default()
+    };
+
+    let was_copy =
+        rename_config_sandboxed(&config, &temp_path, &final_path).expect("std rename succeeds");
+    assert!(!was_copy);
+    assert!(!temp_path.exists());
+    assert_eq!(fs::read(&final_path).unwrap(), b"new content");
+}
@@ -137,6 +137,58 @@ fn write_file_with_io_uring_auto_policy() {
     h.join_handle.join().unwrap();
 }
 
+#[test]
+fn parallel_commit_report_results_in_dispatch_order() {
+    let _registry_lock = test_support::cleanup_registry_test_guard();
+    let dir = test_support::create_tempdir();
+
+    let config = DiskCommitConfig {
+        parallel_commit: 3,
+        ..DiskCommitConfig::default()
+    };
+    let h = spawn_disk_thread(config).unwrap();
+
+    let paths: Vec<_> = (0..7)
+        .map(|i| dir.path().join(format!("parallel_{i}.dat")))
+        .collect();
+    for (index, path) in paths.iter().enumerate() {
+        let data = vec![b'a' + index as u8; 1000 * (index + 1)];
+        h.file_tx
+            .send(FileMessage::Begin(Box::new(BeginMessage {
+                file_path: path.clone(),
+                target_size: data.len() as u64,
+                file_entry_index: index,
+                checksum_verifier: None,
+                is_device_target: false,
+                is_inplace: false,
+                append_offset: 0,
+                xattr_list: None,
+            })))
+            .unwrap();
+        for chunk in data.chunks(300) {
+            h.file_tx.send(FileMessage::Chunk(chunk.to_vec())).unwrap();
+        }
+        h.file_tx
+            .send(FileMessage::Commit {
+                expected_checksum: Default::default(),
+            })
+            .unwrap();
+    }
+
+    for (index, path) in paths.iter().enumerate() {
+        let result = h.result_rx.recv().unwrap().unwrap();
+        assert_eq!(result.file_entry_index, index);
+        assert_eq!(result.bytes_written, 1000 * (index as u64 + 1));
+        assert_eq!(
+            fs::read(path).unwrap(),
+            vec![b'a' + index as u8; 1000 * (index + 1)]
+        );
+    }
+
+    h.file_tx.send(FileMessage::Shutdown).unwrap();
+    h.join_handle.join().unwrap();
+}
+
 #[test]
 fn device_target_inplace_commit_does_not_truncate() {
     // upstream: receiver.c:496 gates the in-place ftruncate on !IS_DEVICE, so
@@ -188,6 +240,46 @@ fn device_target_inplace_commit_does_not_truncate() {
     h.join_handle.join().unwrap();
 }
 
+#[cfg(unix)]
+#[test]
+fn device_target_smaller_than_file_is_refused() {
+    // --write-devices must not start writing a file that cannot fit; a
+    // regular file stands in for the device, its length as the capacity.
+    let _registry_lock = test_support::cleanup_registry_test_guard();
+    let dir = test_support::create_tempdir();
+    let file_path = dir.path().join("small_device.dat");
+    fs::write(&file_path, vec![b'x'; 16]).unwrap();
+
+    let h = spawn_disk_thread(DiskCommitConfig::default()).unwrap();
+    h.file_tx
+        .send(FileMessage::Begin(Box::new(BeginMessage {
+            file_path: file_path.clone(),
+            target_size: 32,
+            file_entry_index: 0,
+            checksum_verifier: None,
+            is_device_target: true,
+            is_inplace: true,
+            append_offset: 0,
+            xattr_list: None,
+        })))
+        .unwrap();
+    h.file_tx.send(FileMessage::Chunk(vec![b'A'; 32])).unwrap();
+    h.file_tx
+        .send(FileMessage::Commit {
+            expected_checksum: Default::default(),
+        })
+        .unwrap();
+
+    let Err(error) = h.result_rx.recv().unwrap() else {
+        panic!("oversized device write must fail");
+    };
+    assert_eq!(error.kind(), std::io::ErrorKind::StorageFull);
+    assert_eq!(fs::read(&file_path).unwrap(), vec![b'x'; 16]);
+
+    h.file_tx.send(FileMessage::Shutdown).unwrap();
+    h.join_handle.join().unwrap();
+}
+
 #[test]
 fn channel_capacity_default_passthrough() {
     let config = DiskCommitConfig::default();
@@ -3340,6 +3432,109 @@ fn whole_file_checksum_match_commits_to_dest() {
     h.join_handle.join().unwrap();
 }
 
+fn md5_whole_file(path: std::path::PathBuf, data: &[u8]) -> FileMessage {
+    FileMessage::WholeFile {
+        begin: Box::new(BeginMessage {
+            file_path: path,
+            target_size: data.len() as u64,
+            file_entry_index: 0,
+            checksum_verifier: Some(md5_verifier()),
+            is_device_target: false,
+            is_inplace: false,
+            append_offset: 0,
+            xattr_list: None,
+        }),
+        data: data.to_vec(),
+        expected_checksum: expected_md5(data),
+    }
+}
+
+#[test]
+fn batched_md5_digests_match_incremental_verifier() {
+    use super::verify_batch::{MAX_BATCH_FILE_LEN, QueuedWholeFile, digest_batch};
+
+    let payloads: [&[u8]; 4] = [b"", b"abc", &[0x5A; 1000], &[0x11; MAX_BATCH_FILE_LEN + 1]];
+    let mut batch: Vec<QueuedWholeFile> = payloads
+        .iter()
+        .map(|data| {
+            let FileMessage::WholeFile {
+                begin,
+                data,
+                expected_checksum,
+            } = md5_whole_file("unused".into(), data)
+            else {
+                unreachable!()
+            };
+            QueuedWholeFile::new(begin, data, expected_checksum)
+        })
+        .collect();
+
+    digest_batch(&mut batch, &DiskCommitConfig::default());
+
+    for (file, data) in batch.iter().zip(payloads) {
+        let expected = expected_md5(data);
+        match &file.precomputed {
+            Some(computed) if data.len() <= MAX_BATCH_FILE_LEN => {
+                assert_eq!(
+                    computed.bytes[..computed.len],
+                    expected.bytes[..expected.len]
+                );
+            }
+            None => assert!(data.len() > MAX_BATCH_FILE_LEN, "small file left unbatched"),
+            Some(_) => panic!("oversized file must keep its incremental verifier"),
+        }
+    }
+}
+
+/// A burst of small MD5 files queued back to back is verified through the
+/// batch path: each commits with its own result, in order, and a corrupted
+/// file in the middle is still withheld.
+#[test]
+fn queued_small_files_verify_and_commit_in_order() {
+    let _registry_lock = test_support::cleanup_registry_test_guard();
+    let dir = test_support::create_tempdir();
+    let h = spawn_disk_thread(DiskCommitConfig::default()).unwrap();
+
+    let files: Vec<(std::path::PathBuf, Vec<u8>)> = (0..8)
+        .map(|i| {
+            (
+                dir.path().join(format!("small{i}.dat")),
+                format!("payload number {i}").into_bytes(),
+            )
+        })
+        .collect();
+    for (i, (path, data)) in files.iter().enumerate() {
+        let mut msg = md5_whole_file(path.clone(), data);
+        if i == 5
+            && let FileMessage::WholeFile {
+                expected_checksum, ..
+            } = &mut msg
+        {
+            *expected_checksum = expected_md5(b"something else");
+        }
+        h.file_tx.send(msg).unwrap();
+    }
+
+    for (i, (path, data)) in files.iter().enumerate() {
+        let result = h.result_rx.recv().unwrap().unwrap();
+        assert_eq!(result.bytes_written, data.len() as u64);
+        let computed = result.computed_checksum.expect("digest reported");
+        let expected = expected_md5(data);
+        assert_eq!(
+            computed.bytes[..computed.len],
+            expected.bytes[..expected.len]
+        );
+        if i == 5 {
+            assert!(!path.exists(), "checksum-failed file must not be committed");
+        } else {
+            assert_eq!(fs::read(path).unwrap(), *data);
+        }
+    }
+
+    h.file_tx.send(FileMessage::Shutdown).unwrap();
+    h.join_handle.join().unwrap();
+}
+
 /// Same invariant for the multi-message chunked path (Begin + Chunk + Commit).
 #[test]
 fn chunked_checksum_mismatch_is_not_committed_to_dest() {
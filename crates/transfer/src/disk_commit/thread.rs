@@ -13,6 +13,10 @@
 //! batch is available or sparse mode is requested, the thread falls back to
 //! the buffered writer using a reusable 256 KB scratch buffer that mirrors
 //! upstream's static `wf_writeBuf` (fileio.c:161).
+//!
+//! Small files that are already queued as consecutive `WholeFile` messages
+//! are drained together so their MD5 whole-file sums are computed in one SIMD
+//! batch (see [`super::verify_batch`]).
 
 use std::io;
 use std::thread::{self, JoinHandle};
@@ -23,7 +27,9 @@ use crate::pipeline::messages::{CommitResult, FileMessage};
 use crate::pipeline::spsc;
 
 use super::config::DiskCommitConfig;
+use super::pool;
 use super::process::{process_file, process_whole_file};
+use super::verify_batch::{self, QueuedWholeFile};
 use super::writer::WRITE_BUF_SIZE;
 
 /// Channels and handle returned by [`spawn_disk_thread`].
@@ -57,9 +63,21 @@ pub fn spawn_disk_thread(config: DiskCommitConfig) -> io::Result<DiskThreadHandl
     let (result_tx, result_rx) = spsc::channel::<io::Result<CommitResult>>(capacity * 2);
     let (buf_return_tx, buf_return_rx) = spsc::channel::<Vec<u8>>(capacity * 2);
 
+    // The disk thread's per-file spans nest under whichever transfer span
+    // spawned it rather than starting detached traces.
+    #[cfg(feature = "tracing")]
+    let parent_span = tracing::Span::current();
     let join_handle = thread::Builder::new()
         .name("disk-commit".into())
-        .spawn(move || disk_thread_main(file_rx, result_tx, buf_return_tx, config))?;
+        .spawn(move || {
+            #[cfg(feature = "tracing")]
+            let _span = parent_span.entered();
+            if config.parallel_commit > 1 {
+                pool::dispatch_main(file_rx, result_tx, buf_return_tx, config);
+            } else {
+                disk_thread_main(file_rx, result_tx, buf_return_tx, config);
+            }
+        })?;
 
     Ok(DiskThreadHandle {
         file_tx,
@@ -197,7 +215,7 @@ fn log_iocp_status(policy: fast_io::IocpPolicy, batch_created: bool) {
 /// upstream rsync's static `wf_writeBuf` (fileio.c:161). On Linux 5.6+
 /// with io_uring support, a batched ring writer is created once and reused
 /// across all files for reduced syscall overhead.
-fn disk_thread_main(
+pub(super) fn disk_thread_main(
     file_rx: spsc::Receiver<FileMessage>,
     result_tx: spsc::Sender<io::Result<CommitResult>>,
     buf_return_tx: spsc::Sender<Vec<u8>>,
@@ -217,7 +235,9 @@ fn disk_thread_main(
     log_io_uring_status(config.io_uring_policy, disk_batch.is_some());
     log_iocp_status(config.iocp_policy, iocp_batch.is_some());
 
-    while let Ok(msg) = file_rx.recv() {
+    // A non-WholeFile message drained while collecting a batch of small files.
+    let mut deferred = None;
+    'messages: while let Some(msg) = deferred.take().or_else(|| file_rx.recv().ok()) {
         match msg {
             FileMessage::Shutdown => break,
             FileMessage::Begin(begin) => {
@@ -247,18 +267,23 @@ fn disk_thread_main(
                 data,
                 expected_checksum,
             } => {
-                let result = process_whole_file(
-                    &buf_return_tx,
-                    &config,
-                    *begin,
-                    data,
-                    expected_checksum,
-                    &mut write_buf,
-                    disk_batch.as_mut(),
-                    iocp_batch.as_mut(),
-                );
-                if result_tx.send(result).is_err() {
-                    break;
+                let mut batch = vec![QueuedWholeFile::new(begin, data, expected_checksum)];
+                if batch[0].is_batchable(&config) {
+                    deferred = verify_batch::drain_queued(&file_rx, &mut batch);
+                    verify_batch::digest_batch(&mut batch, &config);
+                }
+                for file in batch {
+                    let result = process_whole_file(
+                        &buf_return_tx,
+                        &config,
+                        file,
+                        &mut write_buf,
+                        disk_batch.as_mut(),
+                        iocp_batch.as_mut(),
+                    );
+                    if result_tx.send(result).is_err() {
+                        break 'messages;
+                    }
                 }
             }
             FileMessage::Chunk(_)
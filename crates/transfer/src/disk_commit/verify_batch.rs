@@ -0,0 +1,113 @@
+//! Batched MD5 finalization for runs of small coalesced files.
+//!
+//! A file that fits in one literal token reaches the disk thread as a single
+//! [`FileMessage::WholeFile`], and from protocol 30 its whole-file sum is
+//! plain MD5. When several such messages are already queued, the disk thread
+//! drains them together and digests their payloads with one
+//! [`md5_digest_batch`] call, so the SIMD backend hashes up to one file per
+//! lane instead of running a scalar MD5 per file. The files are then written,
+//! verified, and committed one by one in arrival order with their digests
+//! already known, so results are reported exactly as before.
+
+use checksums::strong::md5_digest_batch;
+
+use crate::delta_apply::ChecksumVerifier;
+use crate::pipeline::messages::{BeginMessage, ComputedChecksum, ExpectedChecksum, FileMessage};
+use crate::pipeline::spsc;
+
+use super::config::DiskCommitConfig;
+
+/// Most files hashed together; the widest MD5 backend (AVX-512) has 16 lanes.
+pub(super) const MAX_BATCH_FILES: usize = 16;
+
+/// Largest payload hashed in a batch. Lanes run in lockstep, so one large
+/// file would hold the rest of the batch until it finished.
+pub(super) const MAX_BATCH_FILE_LEN: usize = 64 * 1024;
+
+/// A drained [`FileMessage::WholeFile`] awaiting commit.
+pub(super) struct QueuedWholeFile {
+    pub(super) begin: Box<BeginMessage>,
+    pub(super) data: Vec<u8>,
+    pub(super) expected_checksum: ExpectedChecksum,
+    /// Whole-file digest computed by [`digest_batch`], if this file was
+    /// hashed as part of a batch.
+    pub(super) precomputed: Option<ComputedChecksum>,
+}
+
+impl QueuedWholeFile {
+    pub(super) const fn new(
+        begin: Box<BeginMessage>,
+        data: Vec<u8>,
+        expected_checksum: ExpectedChecksum,
+    ) -> Self {
+        Self {
+            begin,
+            data,
+            expected_checksum,
+            precomputed: None,
+        }
+    }
+
+    /// Returns `true` when the file's digest is unseeded MD5 over `data`
+    /// alone. `--append-verify` folds the on-disk prefix into the sum first,
+    /// so such a file keeps its incremental verifier.
+    pub(super) fn is_batchable(&self, config: &DiskCommitConfig) -> bool {
+        self.data.len() <= MAX_BATCH_FILE_LEN
+            && matches!(self.begin.checksum_verifier, Some(ChecksumVerifier::Md5(_)))
+            && !(config.append_verify && self.begin.append_offset != 0)
+    }
+}
+
+/// Moves every [`FileMessage::WholeFile`] already queued behind `batch` into
+/// it, up to [`MAX_BATCH_FILES`], without blocking.
+///
+/// Returns the first other message drained, which the caller must process
+/// after the batch.
+pub(super) fn drain_queued(
+    file_rx: &spsc::Receiver<FileMessage>,
+    batch: &mut Vec<QueuedWholeFile>,
+) -> Option<FileMessage> {
+    while batch.len() < MAX_BATCH_FILES {
+        match file_rx.try_recv() {
+            Ok(FileMessage::WholeFile {
+                begin,
+                data,
+                expected_checksum,
+            }) => batch.push(QueuedWholeFile::new(begin, data, expected_checksum)),
+            Ok(other) => return Some(other),
+            Err(_) => break,
+        }
+    }
+    None
+}
+
+/// Digests the batchable files of `batch` together and records each result
+/// in [`QueuedWholeFile::precomputed`].
+///
+/// Leaves the batch untouched when fewer than two files qualify; a lone file
+/// gains nothing over its incremental verifier.
+pub(super) fn digest_batch(batch: &mut [QueuedWholeFile], config: &DiskCommitConfig) {
+    let lanes: Vec<usize> = batch
+        .iter()
+        .enumerate()
+        .filter(|(_, file)| file.is_batchable(config))
+        .map(|(index, _)| index)
+        .collect();
+    if lanes.len() < 2 {
+        return;
+    }
+
+    let inputs: Vec<&[u8]> = lanes
+        .iter()
+        .map(|&index| batch[index].data.as_slice())
+        .collect();
+    let digests = md5_digest_batch(&inputs);
+    for (&index, digest) in lanes.iter().zip(digests) {
+        let mut bytes = [0u8; ChecksumVerifier::MAX_DIGEST_LEN];
+        bytes[..digest.len()].copy_from_slice(&digest);
+        batch[index].precomputed = Some(ComputedChecksum {
+            bytes,
+            len: digest.len(),
+        });
+    }
+}
@@ -5,7 +5,9 @@
 //! data corruption risks.
 //!
 //! It also provides retry utilities for transient errors like EINTR (interrupted
-//! system calls), matching upstream rsync's behavior.
+//! system calls), matching upstream rsync's behavior, and
+//! [`transfer_exit_code`], which maps a failed transfer onto the upstream
+//! `RERR_*` exit code it ends with.
 
 use std::io::{self, Read, Seek, Write};
 use std::path::PathBuf;
@@ -156,6 +158,111 @@ pub fn categorize_io_error(
     }
 }
 
+/// Upstream `RERR_*` exit codes a failed transfer can end with.
+///
+/// upstream: errcode.h. Mirrored here because the transfer crate sits below
+/// the `core` exit-code enum; `core::exit_code::ExitCode` carries the same
+/// values.
+pub mod rerr {
+    /// `RERR_PROTOCOL` - the peer violated the wire protocol.
+    pub const PROTOCOL: i32 = 2;
+    /// `RERR_FILEIO` - a local file could not be written.
+    pub const FILEIO: i32 = 11;
+    /// `RERR_STREAMIO` - the protocol data stream failed or was truncated.
+    pub const STREAMIO: i32 = 12;
+    /// `RERR_SIGNAL` - interrupted by SIGINT, SIGTERM, or SIGHUP.
+    pub const SIGNAL: i32 = 20;
+    /// `RERR_PARTIAL` - some files or attributes were not transferred.
+    pub const PARTIAL: i32 = 23;
+    /// `RERR_TIMEOUT` - no data was sent or received within `--timeout`.
+    pub const TIMEOUT: i32 = 30;
+}
+
+impl DeltaTransferError {
+    /// Returns the upstream exit code the transfer ends with for this error.
+    ///
+    /// upstream: receiver.c and fileio.c report local write failures with
+    /// `exit_cleanup(RERR_FILEIO)`; a per-file failure only sets
+    /// `got_xfer_error`, ending the run with `RERR_PARTIAL`.
+    #[must_use]
+    pub const fn exit_code(&self) -> i32 {
+        match self {
+            Self::Fatal(DeltaFatalError::ProtocolError { .. }) | Self::DataCorruption(_) => {
+                rerr::STREAMIO
+            }
+            Self::Fatal(_) => rerr::FILEIO,
+            Self::Recoverable(_) => rerr::PARTIAL,
+        }
+    }
+}
+
+/// Returns the exit code a peer requested via `MSG_ERROR_EXIT`, if the error
+/// carries one anywhere in its source chain.
+///
+/// upstream: io.c:1684-1722 - `MSG_ERROR_EXIT` drives the NORETURN
+/// `_exit_cleanup(val)`, so the local exit code is the peer's code.
+#[must_use]
+pub fn remote_exit_code(error: &io::Error) -> Option<i32> {
+    let mut source: Option<&(dyn std::error::Error + 'static)> = Some(error.get_ref()?);
+    while let Some(err) = source {
+        if let Some(remote) = err.downcast_ref::<crate::RemoteExitError>() {
+            return Some(remote.code);
+        }
+        source = err.source();
+    }
+    None
+}
+
+/// Classifies a failed transfer into the upstream exit code it ends with.
+///
+/// This covers errors raised once the protocol is running; connection setup
+/// failures (refused, unreachable) are classified by the caller as
+/// `RERR_SOCKETIO`. In priority order:
+///
+/// - a SIGINT, SIGTERM, or SIGHUP received by this process is `RERR_SIGNAL`
+///   whatever error the interrupted transfer surfaced (rsync.c:sig_int());
+/// - a peer's `MSG_ERROR_EXIT` code wins (io.c:1684-1722);
+/// - a tagged [`protocol::ProtocolViolation`] is `RERR_PROTOCOL`;
+/// - a wrapped [`DeltaTransferError`] uses [`DeltaTransferError::exit_code`];
+/// - `TimedOut`/`WouldBlock` is `RERR_TIMEOUT` (io.c:check_timeout(), and
+///   `--stop-at`/`--time-limit` in io.c:2104);
+/// - a truncated, corrupt, reset, or closed stream is `RERR_STREAMIO`
+///   (io.c read/write error sites and "connection unexpectedly closed");
+/// - a full or read-only filesystem is `RERR_FILEIO`;
+/// - anything else, including a stray `Interrupted` that no signal explains,
+///   is `RERR_PARTIAL`.
+#[must_use]
+pub fn transfer_exit_code(error: &io::Error) -> i32 {
+    classify_transfer_exit(error, fast_io::signal::shutdown_requested())
+}
+
+/// [`transfer_exit_code`] with the termination-signal state passed in.
+fn classify_transfer_exit(error: &io::Error, signalled: bool) -> i32 {
+    if signalled {
+        return rerr::SIGNAL;
+    }
+    if let Some(code) = remote_exit_code(error) {
+        return code;
+    }
+    if let Some(inner) = error.get_ref() {
+        if inner.is::<protocol::ProtocolViolation>() {
+            return rerr::PROTOCOL;
+        }
+        if let Some(delta) = inner.downcast_ref::<DeltaTransferError>() {
+            return delta.exit_code();
+        }
+    }
+
+    use io::ErrorKind::*;
+    match error.kind() {
+        TimedOut | WouldBlock => rerr::TIMEOUT,
+        UnexpectedEof | InvalidData | ConnectionReset | ConnectionAborted | BrokenPipe
+        | NotConnected => rerr::STREAMIO,
+        StorageFull | ReadOnlyFilesystem | QuotaExceeded | FileTooLarge => rerr::FILEIO,
+        _ => rerr::PARTIAL,
+    }
+}
+
 /// Reads exactly `buf.len()` bytes, retrying on EINTR (interrupted system call).
 ///
 /// This matches upstream rsync's behavior in `util1.c:315-317` where reads are
@@ -382,6 +489,76 @@ mod tests {
         assert!(s.contains("/tmp/test.txt"));
     }
 
+    #[test]
+    fn delta_errors_map_to_upstream_exit_codes() {
+        let path = PathBuf::from("/dest/file");
+        let disk_full =
+            categorize_io_error(io::Error::from(io::ErrorKind::StorageFull), &path, "write");
+        assert_eq!(disk_full.exit_code(), rerr::FILEIO);
+        let denied = categorize_io_error(
+            io::Error::from(io::ErrorKind::PermissionDenied),
+            &path,
+            "open",
+        );
+        assert_eq!(denied.exit_code(), rerr::PARTIAL);
+        let protocol = DeltaTransferError::Fatal(DeltaFatalError::ProtocolError {
+            message: "bad token".to_owned(),
+        });
+        assert_eq!(protocol.exit_code(), rerr::STREAMIO);
+    }
+
+    #[test]
+    fn transfer_exit_code_prefers_remote_exit_code() {
+        let err = io::Error::new(
+            io::ErrorKind::ConnectionAborted,
+            crate::RemoteExitError { code: 5 },
+        );
+        assert_eq!(remote_exit_code(&err), Some(5));
+        assert_eq!(transfer_exit_code(&err), 5);
+    }
+
+    #[test]
+    fn transfer_exit_code_is_signal_only_after_a_termination_signal() {
+        let interrupted = io::Error::from(io::ErrorKind::Interrupted);
+        assert_eq!(classify_transfer_exit(&interrupted, false), rerr::PARTIAL);
+        assert_eq!(classify_transfer_exit(&interrupted, true), rerr::SIGNAL);
+
+        let reset = io::Error::from(io::ErrorKind::ConnectionReset);
+        assert_eq!(classify_transfer_exit(&reset, true), rerr::SIGNAL);
+    }
+
+    #[test]
+    fn transfer_exit_code_classifies_by_kind() {
+        use io::ErrorKind::*;
+        for (kind, expected) in [
+            (TimedOut, rerr::TIMEOUT),
+            (UnexpectedEof, rerr::STREAMIO),
+            (ConnectionReset, rerr::STREAMIO),
+            (BrokenPipe, rerr::STREAMIO),
+            (StorageFull, rerr::FILEIO),
+            (Interrupted, rerr::PARTIAL),
+            (Other, rerr::PARTIAL),
+        ] {
+            assert_eq!(
+                transfer_exit_code(&io::Error::from(kind)),
+                expected,
+                "{kind:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn transfer_exit_code_honours_wrapped_errors() {
+        let violation = protocol::protocol_violation("phase 2 request");
+        assert_eq!(transfer_exit_code(&violation), rerr::PROTOCOL);
+
+        let delta = DeltaTransferError::Fatal(DeltaFatalError::DiskFull {
+            path: PathBuf::from("/dest"),
+            bytes_needed: None,
+        });
+        assert_eq!(transfer_exit_code(&io::Error::other(delta)), rerr::FILEIO);
+    }
+
     #[test]
     fn read_exact_retry_succeeds_on_normal_read() {
         let data = b"hello world";
@@ -220,6 +220,11 @@ pub struct ParsedServerFlags {
     pub update: bool,
     /// Preserve creation times (`N` flag, `--crtimes`).
     pub crtimes: bool,
+    /// Preserve BSD file flags (long-form `--fileflags`, oc-rsync extension).
+    ///
+    /// Not part of the compact flag string; set via long-form args. Adds a
+    /// field to every file-list entry, so both sides must agree on it.
+    pub fileflags: bool,
     /// Ignore modification times for quick-check (`I` flag, `--ignore-times`).
     pub ignore_times: bool,
     /// Copy symlinks as the referent file/dir (`L` flag, `--copy-links`).
@@ -385,6 +390,15 @@ pub struct ParsedServerFlags {
     /// byte-identical output on arbitrary inputs.
     pub parallel_delta_scan: bool,
 
+    /// Scan large delta sources through a memory mapping (long-form
+    /// `--mmap-source`).
+    ///
+    /// Local sender-side option only, never forwarded to a remote peer. Off
+    /// by default: a source truncated by another process while it is mapped
+    /// raises `SIGBUS` instead of a read error, so buffered reads stay the
+    /// default and the mapping is used only when the caller asks for it.
+    pub mmap_source: bool,
+
     /// Info flags after the first `.` separator.
     pub info_flags: InfoFlags,
 }
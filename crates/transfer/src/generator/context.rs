@@ -29,9 +29,9 @@ use crate::transfer_state::TransferPipeline;
 
 /// Concrete source-file descriptor surfaced by `open_source_unbuffered`.
 ///
-/// NSV-1: `RawFd` on Unix so the daemon SERVE path can later engage a zero-copy
-/// file->socket sender. `!()` collapses to unit on non-unix where no fd exists;
-/// the value is always `None` there.
+/// `RawFd` on Unix so the daemon SERVE path can hand the source to
+/// `sendfile(2)`. Collapses to unit on non-unix where no fd exists; the value
+/// is always `None` there.
 #[cfg(unix)]
 pub(crate) type SourceFd = std::os::fd::RawFd;
 /// Non-unix placeholder for the source descriptor (never populated).
@@ -108,6 +108,13 @@ pub struct GeneratorContext {
     /// I/O error flags accumulated during file list building and transfer.
     /// Uses [`io_error_flags`] constants (IOERR_GENERAL, IOERR_VANISHED, etc.).
     pub(crate) io_error: i32,
+    /// Device of the top-level source being walked, captured when
+    /// `--one-file-system` is active so directories on another filesystem
+    /// are recognised as mount points.
+    ///
+    /// upstream: flist.c:send_file_list() - `filesystem_dev = st.st_dev` per
+    /// source argument.
+    pub(crate) filesystem_dev: Option<u64>,
     /// Flat file-list indices whose `--remove-source-files` unlink is deferred
     /// until the peer confirms the commit via `MSG_SUCCESS`. Empty and unused
     /// unless `--remove-source-files` is active.
@@ -180,6 +187,7 @@ impl GeneratorContext {
             uid_list: IdList::new(),
             gid_list: IdList::new(),
             io_error: 0,
+            filesystem_dev: None,
             pending_source_removals: super::pending_removal::PendingSourceRemovals::default(),
             incremental: IncrementalState::new(initial_ndx_start),
             delete_stats: DeleteStats::new(),
@@ -360,6 +368,7 @@ impl GeneratorContext {
         .with_preserve_hard_links(self.config.flags.hard_links)
         .with_preserve_atimes(self.config.flags.atimes)
         .with_preserve_crtimes(self.config.flags.crtimes)
+        .with_preserve_fileflags(self.config.flags.fileflags)
         .with_preserve_acls(self.config.flags.acls)
         .with_acl_send_names(acl_send_names)
         // upstream: flist.c:481-482,491-492 - inline XMIT_*_NAME_FOLLOWS owner
@@ -673,13 +682,13 @@ impl GeneratorContext {
     /// The io_uring fast path is unchanged - it already returns a reader with
     /// its own internal buffering strategy.
     ///
-    /// # NSV-1
+    /// # Source descriptor
     ///
     /// Returns the reader together with the concrete source `RawFd` when the
     /// fallback path opens a plain `File` (Unix only). The io_uring fast path
     /// yields `None` for the fd because its reader owns the descriptor behind an
-    /// abstraction; a later zero-copy SERVE gate applies only to the plain-file
-    /// case. The fd is purely informational here - the byte stream is identical.
+    /// abstraction, so the sendfile SERVE path applies only to the plain-file
+    /// case.
     pub(crate) fn open_source_unbuffered(
         &self,
         path: &std::path::Path,
@@ -690,11 +699,16 @@ impl GeneratorContext {
         const IO_URING_READ_THRESHOLD: u64 = 1024 * 1024;
 
         let use_noatime = self.config.write.open_noatime;
+        // A daemon socket eligible for sendfile wants the plain `File` and its
+        // fd rather than the io_uring reader.
+        let sendfile_serve = self.config.connection.sendfile_socket.is_some()
+            && self.config.write.zero_copy_policy == fast_io::ZeroCopyPolicy::Enabled;
 
         // upstream: `--copy-devices` streams through the plain read path; a
         // device's stat size is 0, so the io_uring fast path would short-read.
         // See `source_is_copy_device` / `open_source_reader`.
         if !use_noatime
+            && !sendfile_serve
             && file_size >= IO_URING_READ_THRESHOLD
             && self.config.write.io_uring_policy != fast_io::IoUringPolicy::Disabled
             && !self.source_is_copy_device(path)
@@ -24,12 +24,16 @@ use protocol::wire::{
 };
 use protocol::{ChecksumAlgorithm, CompressionAlgorithm};
 
-use engine::delta::{DeltaGenerator, DeltaScript, DeltaSignatureIndex, DeltaToken};
+use engine::delta::{
+    DeltaGenerator, DeltaScript, DeltaSignatureIndex, DeltaToken, MMAP_INPUT_THRESHOLD,
+};
 
 use super::super::delta_apply::ChecksumVerifier;
 use super::super::delta_config::DeltaGeneratorConfig;
 use super::super::shared::ChecksumFactory;
 use crate::role_trailer::error_location;
+#[cfg(unix)]
+use crate::writer::ServerWriter;
 
 /// Creates a `CompressedTokenEncoder` for the given compression algorithm.
 ///
@@ -122,37 +126,27 @@ const ZLIB_TOKEN_PROTOCOL_VERSION: u32 = 31;
 /// with a basis file are strongly preferred to reduce bandwidth.
 pub(super) const LARGE_FILE_WARNING_THRESHOLD: u64 = 8 * 1024 * 1024 * 1024; // 8 GB
 
-/// Concrete source-file and destination-socket descriptors for the SERVE path.
-///
-/// NSV-1 plumbing: threads the raw source `File` descriptor and, when available,
-/// the raw destination socket descriptor down to [`stream_whole_file_transfer`]
-/// so a later applicability gate (NSV-3) and platform sender (NSV-6..10) can
-/// engage a zero-copy file->socket path (sendfile / TransmitFile / splice).
+/// Minimum file size for the sendfile SERVE path.
 ///
-/// Passed as `Option<ServeFds>`: `None` for transports without a usable fd pair
-/// (SSH pipe or stdio) or callers that never touch a socket (tests). When the
-/// source is a plain `File` but the socket fd is not reachable through the writer
-/// abstraction, `dst_fd` is `None` and the source fd is still surfaced so the
-/// future gate can decide.
+/// Below this the extra frame-header write per chunk outweighs the saved
+/// copy; matches the `fast_io::sendfile` dispatch threshold.
+#[cfg(unix)]
+pub(super) const SENDFILE_MIN_FILE_SIZE: u64 = 64 * 1024;
+
+/// Source-file and destination-socket descriptors for the SERVE path.
 ///
-/// This struct is purely additive: the current transfer never reads these fds,
-/// so wire bytes, stats, and output are byte-for-byte unchanged. The fields are
-/// intentionally unread until the NSV-3 zero-copy gate consumes them.
-#[allow(dead_code)]
+/// Built by the transfer loop only when a whole file can be served with
+/// `sendfile(2)`: the source is a plain `File`, the daemon supplied its
+/// plaintext TCP socket, `--zero-copy` was given explicitly, and no
+/// compression or batch recording needs to see the bytes. See
+/// [`stream_whole_file_sendfile`].
+#[cfg(unix)]
+#[derive(Clone, Copy, Debug)]
 pub(super) struct ServeFds {
-    /// Raw descriptor of the concrete source `File`, when the source is a plain
-    /// file (not an io_uring reader or a `BufReader`).
-    #[cfg(unix)]
+    /// Raw descriptor of the source `File`.
     pub src_fd: std::os::fd::RawFd,
-    /// Raw descriptor of the destination socket, when the writer wraps a
-    /// concrete `TcpStream`. `None` for pipe/stdio writers or any writer
-    /// that erases its fd behind `dyn Write`.
-    #[cfg(unix)]
-    pub dst_fd: Option<std::os::fd::RawFd>,
-    /// Windows placeholder so the struct stays constructible on all platforms
-    /// while zero-copy remains Unix-only for now (NSV-6..10 add TransmitFile).
-    #[cfg(not(unix))]
-    pub _unsupported: (),
+    /// Raw descriptor of the destination socket.
+    pub dst_fd: std::os::fd::RawFd,
 }
 
 /// Result of streaming a whole file to the wire.
@@ -200,6 +194,36 @@ pub fn generate_delta_from_signature<R: Read>(
     })
 }
 
+/// Generates a delta script from a received signature for an open source file.
+///
+/// Same contract as [`generate_delta_from_signature`], but hands the file to
+/// [`DeltaGenerator::generate_from_file`] opted into mapping sources of at
+/// least [`MMAP_INPUT_THRESHOLD`] bytes. The generator scans a
+/// sequentially-advised memory mapping and falls back to buffered reads when
+/// the filesystem refuses the mapping (NFS, FUSE). The emitted tokens are
+/// identical in both modes. Only called under the opt-in `--mmap-source`,
+/// because a source truncated mid-scan raises `SIGBUS`.
+pub fn generate_delta_from_signature_file(
+    source: std::fs::File,
+    config: DeltaGeneratorConfig<'_>,
+) -> io::Result<DeltaScript> {
+    let needed = consecutive_match_needed(&config);
+    let updating_basis_file = config.updating_basis_file;
+    let index = build_signature_index(config)?;
+
+    let generator = DeltaGenerator::new()
+        .with_consecutive_match_needed(needed)
+        .with_updating_basis_file(updating_basis_file)
+        .with_mmap_threshold(MMAP_INPUT_THRESHOLD);
+    generator.generate_from_file(source, &index).map_err(|e| {
+        io::Error::other(format!(
+            "delta generation failed: {e} {}{}",
+            error_location!(),
+            crate::role_trailer::sender()
+        ))
+    })
+}
+
 /// Generates a delta script from a received signature using the opt-in
 /// parallel scan when the basis is duplicate-free.
 ///
@@ -449,18 +473,7 @@ pub(super) fn stream_whole_file_transfer<R: Read, W: Write>(
     protocol: protocol::ProtocolVersion,
     encoder: Option<&mut CompressedTokenEncoder>,
     buf: &mut Vec<u8>,
-    serve_fds: Option<ServeFds>,
 ) -> io::Result<StreamResult> {
-    // NSV-1: `serve_fds` carries the concrete source-file and destination-socket
-    // descriptors for the daemon SERVE path. It is plumbed but unused here. A
-    // future zero-copy gate (NSV-3) will branch at this point: when `serve_fds`
-    // yields both a source fd and a socket fd, no compression/checksum-only
-    // constraints apply, and the platform supports it, dispatch to a
-    // sendfile/TransmitFile/splice sender (NSV-6..10) instead of the read->hash->
-    // write loop below. Until then the bytes flow through the existing path
-    // unchanged, so wire output and stats are byte-for-byte identical.
-    let _ = &serve_fds;
-
     if file_size > LARGE_FILE_WARNING_THRESHOLD {
         debug_log!(
             Send,
@@ -531,6 +544,102 @@ pub(super) fn stream_whole_file_transfer<R: Read, W: Write>(
     })
 }
 
+/// Streams a whole file as plain literal tokens, letting `sendfile(2)` carry
+/// each token's data from the source file straight into the socket.
+///
+/// oc-rsync extension for the daemon SERVE path. The token stream is
+/// byte-identical to [`stream_whole_file_transfer`] without an encoder: each
+/// `CHUNK_SIZE` literal is a 4-byte length prefix plus its data, then the end
+/// marker. The writer emits each prefix - inside a `MSG_DATA` header sized for
+/// the data when multiplexed - and the data itself bypasses it. The file is
+/// still read once into `buf` for the whole-file checksum; only the userspace
+/// copy into the socket is saved. If `sendfile` fails or comes up short, the
+/// remaining bytes are written from `buf` and the rest of the file stays on
+/// that buffered path.
+///
+/// Each range is read twice - into `buf` for the checksum, then by the kernel
+/// for the socket - so a file modified mid-send can hash differently from the
+/// bytes that went out. The receiver rejects such a file and requests a redo,
+/// but the transfer loop still takes this path only under an explicit
+/// `--zero-copy`, never under the default `Auto` policy.
+///
+/// Returns the stream result and the token-stream byte count, which excludes
+/// multiplex headers just like the `CountingWriter` around the regular path.
+///
+/// # Upstream Reference
+///
+/// - `token.c:simple_send_token()` - the plain literal token framing.
+#[cfg(unix)]
+#[allow(clippy::too_many_arguments)]
+pub(super) fn stream_whole_file_sendfile<R: Read, W: Write>(
+    writer: &mut ServerWriter<W>,
+    mut source: R,
+    fds: ServeFds,
+    file_size: u64,
+    checksum_algorithm: ChecksumAlgorithm,
+    checksum_seed: i32,
+    protocol: protocol::ProtocolVersion,
+    buf: &mut Vec<u8>,
+) -> io::Result<(StreamResult, u64)> {
+    let mut verifier =
+        ChecksumVerifier::for_algorithm_seeded(checksum_algorithm, checksum_seed, protocol);
+
+    const MAX_READ_SIZE: usize = 256 * 1024;
+    let read_size = (file_size as usize).clamp(1, MAX_READ_SIZE);
+    buf.resize(read_size, 0);
+
+    let mut offset = 0u64;
+    let mut token_bytes = 0u64;
+    let mut use_sendfile = true;
+    while offset < file_size {
+        let to_read = buf.len().min((file_size - offset) as usize);
+        source.read_exact(&mut buf[..to_read])?;
+        verifier.update(&buf[..to_read]);
+
+        let mut wire_off = 0;
+        while wire_off < to_read {
+            let chunk = (to_read - wire_off).min(CHUNK_SIZE);
+            writer.begin_passthrough(&(chunk as i32).to_le_bytes(), chunk)?;
+            let sent = if use_sendfile {
+                match fast_io::sendfile::send_file_range_to_fd(
+                    fds.src_fd,
+                    offset + wire_off as u64,
+                    fds.dst_fd,
+                    chunk as u64,
+                ) {
+                    Ok(n) => n as usize,
+                    Err(e) => {
+                        debug_log!(Send, 2, "sendfile unavailable ({e}), sending from buffer");
+                        use_sendfile = false;
+                        0
+                    }
+                }
+            } else {
+                0
+            };
+            if sent < chunk {
+                writer.write_passthrough(&buf[wire_off + sent..wire_off + chunk])?;
+            }
+            token_bytes += 4 + chunk as u64;
+            wire_off += chunk;
+        }
+        offset += to_read as u64;
+    }
+    write_token_end(writer)?;
+    token_bytes += 4;
+
+    let mut checksum_buf = [0u8; ChecksumVerifier::MAX_DIGEST_LEN];
+    let checksum_len = verifier.finalize_into(&mut checksum_buf);
+
+    Ok((
+        StreamResult {
+            checksum_buf,
+            checksum_len,
+        },
+        token_bytes,
+    ))
+}
+
 /// Streams the appended tail of a file to the wire in append mode.
 ///
 /// In append mode the receiver already holds the first `flength` bytes, so the
@@ -978,7 +1087,6 @@ mod tests {
             protocol::ProtocolVersion::NEWEST,
             None,
             &mut buf,
-            None,
         )
         .expect("plain stream");
         assert!(
@@ -1004,7 +1112,6 @@ mod tests {
             protocol::ProtocolVersion::NEWEST,
             Some(&mut enc),
             &mut buf2,
-            None,
         )
         .expect("deflated stream");
 
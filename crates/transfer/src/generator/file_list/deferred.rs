@@ -0,0 +1,307 @@
+//! On-demand directory listing for INC_RECURSE sends.
+//!
+//! With INC_RECURSE negotiated the receiver only needs a directory's contents
+//! once that directory's sub-list arrives, so the initial walk stops at the
+//! top-level entries and queues every directory it meets instead of
+//! recursing. The transfer loop then lists one queued directory at a time as
+//! the `MIN_FILECNT_LOOKAHEAD` window drains and ships the sorted result as
+//! the next sub-list. Memory tracks the directories still queued plus the
+//! unreclaimed segments rather than the whole tree, and the first file starts
+//! moving as soon as the top level has been listed.
+//!
+//! The eager walk followed by `partition_file_list_for_inc_recurse()` stays
+//! in use whenever a later pass needs the complete list: `--hard-links`
+//! (leaders are matched across the whole sorted list), `--relative` (implied
+//! parent directories), a transcoding `--iconv` (unconvertible names are
+//! dropped before NDX assignment) and `--files-from`.
+//!
+//! # Upstream Reference
+//!
+//! - `flist.c:send_file_list()` - under `inc_recurse` directories are sent
+//!   without their contents
+//! - `flist.c:send_extra_file_list()` - lists pending directories in
+//!   `add_dirs_to_tree()` order until `at_least` entries are queued
+//! - `flist.c:send1extra()` - reads one directory via `send_directory()`
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use logging::debug_log;
+use protocol::flist::DualFileList;
+
+use crate::role_trailer::error_location;
+
+use super::super::segments::MIN_FILECNT_LOOKAHEAD;
+use super::super::{DeferredDir, GeneratorContext, PendingSegment, SegmentScheduler};
+
+impl GeneratorContext {
+    /// Returns `true` when directory contents may be listed on demand.
+    ///
+    /// Requires INC_RECURSE and rules out the modes whose post-walk passes
+    /// operate on the complete file list.
+    pub(in crate::generator) fn lazy_scan_eligible(&self) -> bool {
+        if !self.inc_recurse() || !self.config.flags.recursive {
+            return false;
+        }
+        // upstream: hlink.c:match_hard_links() - leaders are matched across
+        // the whole sorted list.
+        if self.config.flags.hard_links {
+            return false;
+        }
+        // upstream: flist.c:send_implied_dirs() - implied parents are emitted
+        // around each walked source.
+        if self.config.flags.relative {
+            return false;
+        }
+        // upstream: flist.c:1344-1356 - mount points are detected against the
+        // device of the source argument being walked.
+        if self.config.flags.one_file_system > 0 {
+            return false;
+        }
+        // upstream: flist.c:1614-1638 - unconvertible names are dropped before
+        // NDX assignment.
+        self.config
+            .connection
+            .iconv
+            .as_ref()
+            .is_none_or(|converter| converter.is_identity())
+    }
+
+    /// Seeds the deferred-directory stack from the top-level file list.
+    ///
+    /// Counterpart of `partition_file_list_for_inc_recurse()` for a lazy walk:
+    /// the whole list is the initial segment, every directory in it (`.`
+    /// included) takes the next wire `dir_ndx` in sorted order, and each one
+    /// except `.` is queued so its contents become a sub-list later.
+    pub(in crate::generator) fn seed_deferred_directories(&mut self) {
+        let count = self.file_list.len();
+        self.incremental.initial_segment_count = Some(count);
+
+        // A directory found while scanning a `src/` transfer root inherits that
+        // root's per-directory merge files; a named `src` operand does not.
+        let dot_bases: Vec<Arc<Path>> = self
+            .file_list
+            .iter()
+            .zip(&self.source_bases)
+            .filter(|(entry, _)| entry.name() == ".")
+            .map(|(_, base)| Arc::clone(base))
+            .collect();
+
+        let mut dir_ndx: i32 = 0;
+        let mut top_dirs = Vec::new();
+        for flat_idx in 0..count {
+            let entry = &self.file_list[flat_idx];
+            if !entry.is_dir() {
+                continue;
+            }
+            if entry.name() != "." {
+                let base = Arc::clone(&self.source_bases[flat_idx]);
+                let path = self.reconstruct_source_path(flat_idx);
+                let scope_root = if dot_bases.contains(&base) {
+                    Arc::clone(&base)
+                } else {
+                    Arc::from(path.as_path())
+                };
+                top_dirs.push(DeferredDir {
+                    dir_ndx,
+                    flat_idx,
+                    path,
+                    base,
+                    scope_root,
+                });
+            }
+            // Count ALL dirs including "." for correct dir_flist alignment.
+            dir_ndx += 1;
+        }
+        top_dirs.reverse();
+        self.incremental.deferred_dirs = top_dirs;
+        self.incremental.next_dir_ndx = dir_ndx;
+
+        // upstream: flist.c:2572 - the initial list's gap NDX itemizes `.`
+        // when it is the first sorted entry (see reorder_and_build_segments).
+        self.incremental.segment_parent_flat[0] =
+            if self.file_list.get(0).is_some_and(|e| e.name() == ".") {
+                0
+            } else {
+                -1
+            };
+
+        debug_log!(
+            Flist,
+            2,
+            "lazy file list: {} initial entries, {} directories deferred",
+            count,
+            self.incremental.deferred_dirs.len()
+        );
+    }
+
+    /// Returns `true` while queued directories remain to be listed.
+    pub(in crate::generator) fn has_deferred_directories(&self) -> bool {
+        !self.incremental.deferred_dirs.is_empty()
+    }
+
+    /// Lists the next queued directory and appends its contents as a sub-list.
+    ///
+    /// The new entries are sorted and cleaned like any sub-list, each child
+    /// directory takes the next wire `dir_ndx` and is pushed so the stack
+    /// keeps depth-first order. Returns `None` once every directory has been
+    /// listed.
+    pub(in crate::generator) fn scan_next_deferred_directory(
+        &mut self,
+    ) -> io::Result<Option<PendingSegment>> {
+        let Some(dir) = self.incremental.deferred_dirs.pop() else {
+            self.leave_deferred_scopes();
+            return Ok(None);
+        };
+
+        self.filter_chain.set_transfer_root(dir.base.to_path_buf());
+        self.enter_deferred_scopes(&dir)?;
+
+        let flist_start = self.file_list.len();
+        self.scan_directory_batched(&dir.base, &dir.path)?;
+        self.sort_deferred_segment(flist_start);
+
+        let mut children = Vec::new();
+        for flat_idx in flist_start..self.file_list.len() {
+            if !self.file_list[flat_idx].is_dir() {
+                continue;
+            }
+            children.push(DeferredDir {
+                dir_ndx: self.incremental.next_dir_ndx,
+                flat_idx,
+                path: self.reconstruct_source_path(flat_idx),
+                base: Arc::clone(&self.source_bases[flat_idx]),
+                scope_root: Arc::clone(&dir.scope_root),
+            });
+            self.incremental.next_dir_ndx += 1;
+        }
+        children.reverse();
+        self.incremental.deferred_dirs.extend(children);
+
+        if self.incremental.deferred_dirs.is_empty() {
+            self.leave_deferred_scopes();
+        }
+
+        let count = self.file_list.len() - flist_start;
+        debug_log!(
+            Flist,
+            2,
+            "listed deferred dir {} (dir_ndx={}): {} entries",
+            dir.path.display(),
+            dir.dir_ndx,
+            count
+        );
+
+        Ok(Some(PendingSegment {
+            parent_dir_ndx: dir.dir_ndx,
+            parent_flat_idx: dir.flat_idx,
+            flist_start,
+            count,
+        }))
+    }
+
+    /// Queues the next lazily listed sub-list once `scheduler` has run dry and
+    /// the receiver's lookahead window wants more.
+    pub(in crate::generator) fn refill_segment_scheduler(
+        &mut self,
+        scheduler: &mut SegmentScheduler,
+        remaining: usize,
+    ) -> io::Result<()> {
+        if remaining < MIN_FILECNT_LOOKAHEAD
+            && scheduler.is_exhausted()
+            && let Some(segment) = self.scan_next_deferred_directory()?
+        {
+            scheduler.push(segment);
+        }
+        Ok(())
+    }
+
+    /// Lookahead figure for the next lazy dispatch decision.
+    ///
+    /// `remaining` counts entries the receiver knows about but has not been
+    /// sent yet. When no dispatched sub-list is awaiting release the receiver
+    /// may be idle at the end of its last list, waiting for more, so the
+    /// window is reported empty and one more directory goes out.
+    pub(in crate::generator) const fn lazy_lookahead(
+        remaining: usize,
+        unreleased_sublists: usize,
+    ) -> usize {
+        if unreleased_sublists == 0 {
+            0
+        } else {
+            remaining
+        }
+    }
+
+    /// Brings the filter chain's per-directory scopes in line with `dir`.
+    ///
+    /// Leaves the scopes of directories that are not ancestors of `dir`, then
+    /// enters every directory from the innermost scope still held (or `dir`'s
+    /// scope root) down to `dir` itself, so the children see exactly the
+    /// merge rules an eager walk would have active.
+    ///
+    /// upstream: exclude.c:push_local_filters() / pop_local_filters()
+    fn enter_deferred_scopes(&mut self, dir: &DeferredDir) -> io::Result<()> {
+        while let Some((scoped, _)) = self.incremental.scan_scopes.last() {
+            if dir.path.starts_with(scoped) && scoped.starts_with(&*dir.scope_root) {
+                break;
+            }
+            let (_, guard) = self
+                .incremental
+                .scan_scopes
+                .pop()
+                .expect("scope stack is non-empty");
+            self.filter_chain.leave_directory(guard);
+        }
+
+        let innermost = self.incremental.scan_scopes.last().map(|(p, _)| p.clone());
+        let mut missing: Vec<PathBuf> = dir
+            .path
+            .ancestors()
+            .take_while(|a| a.starts_with(&*dir.scope_root) && innermost.as_deref() != Some(*a))
+            .map(Path::to_path_buf)
+            .collect();
+        missing.reverse();
+
+        for path in missing {
+            let guard = self.filter_chain.enter_directory(&path).map_err(|e| {
+                io::Error::other(format!(
+                    "filter chain error in \"{}\": {e} {}{}",
+                    path.display(),
+                    error_location!(),
+                    crate::role_trailer::sender()
+                ))
+            })?;
+            self.incremental.scan_scopes.push((path, guard));
+        }
+        Ok(())
+    }
+
+    /// Leaves every per-directory scope entered for lazy listing.
+    fn leave_deferred_scopes(&mut self) {
+        while let Some((_, guard)) = self.incremental.scan_scopes.pop() {
+            self.filter_chain.leave_directory(guard);
+        }
+    }
+
+    /// Sorts and cleans the entries appended from `start` as one sub-list.
+    ///
+    /// upstream: flist.c:send_extra_file_list() - `flist_sort_and_clean()`
+    /// runs on each sub-list.
+    fn sort_deferred_segment(&mut self, start: usize) {
+        if self.file_list.len() - start < 2 {
+            return;
+        }
+        let entries = self.file_list.as_mut_vec().split_off(start);
+        let mut bases = self.source_bases.split_off(start);
+        let mut segment = DualFileList::with_capacity(entries.len());
+        for entry in entries {
+            segment.push(entry);
+        }
+        segment.sort_with_parallel(&mut bases, self.config.qsort);
+        segment.dedup_with_parallel(&mut bases, true, true);
+        self.file_list.as_mut_vec().extend(segment.into_vec());
+        self.source_bases.extend(bases);
+    }
+}
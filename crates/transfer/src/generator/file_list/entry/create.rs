@@ -189,12 +189,23 @@ impl GeneratorContext {
             // to decide whether we trust `read_link` (Symlink/Junction) or
             // emit an empty target (OneDrive/AfUnix/Other); the on-wire
             // type is always SYMLINK for Cygwin parity.
+            //
+            // Native targets (`..\lib`, `\\?\C:\src\lib`) are rewritten into
+            // the POSIX form a Cygwin sender would emit so they resolve on a
+            // Unix peer; see `crate::windows_link`.
             #[cfg(windows)]
             let raw_target = match reparse_kind {
                 Some(metadata::windows::ReparseKind::Symlink)
                 | Some(metadata::windows::ReparseKind::Junction)
                 | Some(metadata::windows::ReparseKind::MountPoint)
-                | None => std::fs::read_link(full_path).unwrap_or_else(|_| PathBuf::from("")),
+                | None => match std::fs::read_link(full_path) {
+                    Ok(target) => {
+                        let depth = relative_path.components().count();
+                        let root = full_path.ancestors().nth(depth).unwrap_or(full_path);
+                        crate::windows_link::target_to_wire(&target, full_path, root)
+                    }
+                    Err(_) => PathBuf::from(""),
+                },
                 Some(metadata::windows::ReparseKind::OneDrive)
                 | Some(metadata::windows::ReparseKind::AfUnix)
                 | Some(metadata::windows::ReparseKind::Other(_)) => PathBuf::from(""),
@@ -289,6 +300,11 @@ impl GeneratorContext {
             }
         }
 
+        // upstream: patches/fileflags.diff make_file() - F_FFLAGS(file) = st_flags
+        if self.config.flags.fileflags {
+            entry.set_fileflags(::metadata::file_flags(metadata));
+        }
+
         // upstream: flist.c:make_file() - set uid/gid
         // When the fake-super xattr overrode the stat values, prefer the
         // decoded uid/gid so a round-trip through a fake-super sender
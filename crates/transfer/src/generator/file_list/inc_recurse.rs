@@ -35,10 +35,17 @@ impl GeneratorContext {
     /// Reorders `file_list` and `source_bases` so that initial (top-level)
     /// entries come first, then sub-directory entries in depth-first order. This
     /// makes NDX values correspond directly to indices in the reordered list.
+    ///
+    /// After a lazy walk the list holds only the top level; its directories
+    /// are queued for on-demand listing instead (see `deferred`).
     pub(in crate::generator) fn partition_file_list_for_inc_recurse(&mut self) {
         if !self.inc_recurse() || self.file_list.is_empty() {
             return;
         }
+        if self.incremental.lazy_scan {
+            self.seed_deferred_directories();
+            return;
+        }
 
         let classification = Self::classify_file_list_entries(self.file_list.as_slice());
         self.reorder_and_build_segments(classification);
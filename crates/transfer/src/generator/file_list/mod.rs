@@ -11,6 +11,7 @@
 //! - `entry` - `FileEntry` construction from filesystem metadata
 //! - `hardlinks` - Hardlink index assignment and UID/GID collection
 //! - `inc_recurse` - INC_RECURSE file list partitioning
+//! - `deferred` - On-demand directory listing for INC_RECURSE sub-lists
 //!
 //! # Upstream Reference
 //!
@@ -19,6 +20,7 @@
 //! - `hlink.c:match_hard_links()` - post-sort hardlink index assignment
 
 mod batch_stat;
+mod deferred;
 mod entry;
 mod hardlinks;
 mod iconv;
@@ -77,6 +79,11 @@ impl GeneratorContext {
 
         self.clear_file_list();
 
+        // upstream: flist.c:send_file_list() - under INC_RECURSE directory
+        // contents are left to send_extra_file_list(); the walk then lists only
+        // the top level and the transfer loop reads the rest on demand.
+        self.incremental.lazy_scan = self.lazy_scan_eligible();
+
         // upstream: flist.c:2192 - pre-allocate FLIST_START pointer slots
         const FLIST_START: usize = 4096;
         self.file_list.reserve(FLIST_START);
@@ -105,12 +112,26 @@ impl GeneratorContext {
             } else {
                 non_relative_walk_base(base_path)
             };
+            if self.skip_directory_operand(base_path, &base, &path) {
+                continue;
+            }
             // upstream: flist.c:2254-2272 - pre-stat each top-level source and
             // apply missing_args handling. Separates "source never existed" from
             // "source vanished during recursive walk".
             if !self.try_walk_source_entry(&base, &path)? {
                 continue;
             }
+            // upstream: flist.c:2329 - a trailing-slash operand sends the
+            // directory's immediate contents even without -r. The
+            // non-relative walk already lists them under `.`; a --relative
+            // walk keeps the directory's own name, so add its children here.
+            if relative_paths
+                && !self.config.flags.recursive
+                && names_directory_contents(base_path)
+                && path.is_dir()
+            {
+                self.scan_files_from_marker_dir(&base, &path)?;
+            }
             // upstream: flist.c:2257-2258 - `if (relative_paths &&
             // protocol_version >= 30) implied_dirs = 1;` forces the sender to
             // emit flagged implied parent dirs at protocol >= 30 regardless of
@@ -195,6 +216,8 @@ impl GeneratorContext {
         self.timing.flist_build_start = Some(Instant::now());
 
         self.clear_file_list();
+        // --files-from entries carry their own bases; always walk them eagerly.
+        self.incremental.lazy_scan = false;
 
         const FLIST_START: usize = 4096;
         self.file_list.reserve(FLIST_START);
@@ -529,6 +552,12 @@ fn find_dot_dir_anchor(path: &Path) -> Option<usize> {
     s.find("/./")
 }
 
+/// Reports whether a source operand ends in `/`, naming a directory's
+/// contents rather than the directory itself (upstream `DOTDIR_NAME`).
+fn names_directory_contents(path: &Path) -> bool {
+    path.as_os_str().as_encoded_bytes().last() == Some(&b'/')
+}
+
 /// Picks the `(base, path)` pair for a non-`--relative` positional, matching
 /// upstream `flist.c:2338-2349`: split the path on its LAST `/`, take the
 /// prefix as the base directory and the suffix as the file name. The full
@@ -549,9 +578,7 @@ fn non_relative_walk_base(path: &Path) -> (PathBuf, PathBuf) {
     // trailing slash to signal "transfer the contents only". Preserve
     // base == path so `walk_path_with_metadata`'s `relative.is_empty()`
     // branch still emits `.` for the source root.
-    let s = path.as_os_str();
-    let bytes = s.as_encoded_bytes();
-    if bytes.last() == Some(&b'/') {
+    if names_directory_contents(path) {
         return (path.to_path_buf(), path.to_path_buf());
     }
     // `Path::parent()` returns the parent directory or `None` for a path
@@ -85,6 +85,11 @@ impl GeneratorContext {
         // inside walk_path_with_metadata.
         match self.resolve_symlink_metadata(path, base) {
             Ok(metadata) => {
+                // upstream: flist.c:send_file_list() - each source argument
+                // re-anchors the `--one-file-system` boundary at its own device.
+                if self.config.flags.one_file_system > 0 {
+                    self.filesystem_dev = device_id(&metadata);
+                }
                 // If a prior pass already emitted this directory (e.g. the
                 // implied-parent loop in build_file_list_with_base), skip the
                 // top-level walk so we do not produce a duplicate file-list
@@ -160,6 +165,44 @@ impl GeneratorContext {
         Ok(())
     }
 
+    /// Returns `true` when a named directory operand must be left out of the
+    /// file list because neither `--recursive` nor `--dirs` is active.
+    ///
+    /// Only a named directory (`src`) is skipped; the trailing-slash form
+    /// `operand` (`src/`, upstream `DOTDIR_NAME`) names the directory's
+    /// contents and keeps listing them. A source that cannot be stat'd is left to
+    /// [`try_walk_source_entry`](Self::try_walk_source_entry), which owns the
+    /// missing-source reporting.
+    ///
+    /// # Upstream Reference
+    ///
+    /// - `flist.c:2450-2453` - `if (S_ISDIR(st.st_mode) && !xfer_dirs)`
+    ///   prints `skipping directory %s` and moves on to the next argument
+    pub(in crate::generator) fn skip_directory_operand(
+        &self,
+        operand: &Path,
+        base: &Path,
+        path: &Path,
+    ) -> bool {
+        if self.config.flags.recursive || self.config.flags.dirs {
+            return false;
+        }
+        if super::names_directory_contents(operand) {
+            return false;
+        }
+        let relative = path.strip_prefix(base).unwrap_or(path);
+        if relative.as_os_str().is_empty() {
+            return false;
+        }
+        let is_dir = self
+            .resolve_symlink_metadata(path, base)
+            .is_ok_and(|metadata| metadata.is_dir());
+        if is_dir {
+            info_log!(Nonreg, 1, "skipping directory {}", relative.display());
+        }
+        is_dir
+    }
+
     /// Walks a path with pre-resolved metadata, skipping the initial stat call.
     ///
     /// `is_top_level` is `true` only for the direct source arguments; recursive
@@ -239,6 +282,18 @@ impl GeneratorContext {
             }
         }
 
+        // upstream: flist.c:1344-1356 - under `--one-file-system` a directory
+        // on another device is a mount point. `-xx` drops it entirely; `-x`
+        // still sends the directory (FLAG_MOUNT_DIR) but not its contents, so
+        // the receiver creates it empty. Checked before the filter rules,
+        // matching make_file().
+        let mount_dir = !is_top_level && metadata.is_dir() && self.is_mount_point(&metadata);
+        if mount_dir && self.config.flags.one_file_system > 1 {
+            // The role prefix (`[sender]`) is added downstream by the renderer.
+            info_log!(Mount, 1, "skipping mount-point dir {}", relative.display());
+            return Ok(());
+        }
+
         // upstream: flist.c:1360 - is_excluded() applied during make_file()
         // FilterChain evaluates per-directory scoped rules (innermost first)
         // then global rules. If no rules are configured, allows() returns true.
@@ -260,6 +315,15 @@ impl GeneratorContext {
             }
         }
 
+        // upstream: generator.c:1704-1718 - the `--max-size` / `--min-size`
+        // window is applied per file by recv_generator(). Evaluating it here
+        // keeps out-of-window files off the wire entirely; the receiver still
+        // checks the window for senders that do not.
+        if let Some(notice) = self.size_bound_notice(&metadata) {
+            info_log!(Skip, 1, "{} {notice}", relative.display());
+            return Ok(());
+        }
+
         let mut entry = match self.create_entry(&path, relative, &metadata) {
             Ok(e) => e,
             Err(e) => {
@@ -282,8 +346,13 @@ impl GeneratorContext {
             entry.set_top_dir(true);
         }
 
-        // upstream: flist.c:send_file_list() - scan directory before recording entry
-        let should_recurse = metadata.is_dir() && self.config.flags.recursive;
+        // upstream: flist.c:send_file_list() - scan directory before recording entry.
+        // A lazy INC_RECURSE walk records the directory only; its contents are
+        // read when the transfer loop asks for its sub-list (see `deferred`).
+        let should_recurse = metadata.is_dir()
+            && self.config.flags.recursive
+            && !self.incremental.lazy_scan
+            && !mount_dir;
         let dir_read = if should_recurse {
             match std::fs::read_dir(&path) {
                 Ok(entries) => Some(entries),
@@ -383,7 +452,11 @@ impl GeneratorContext {
     /// # Upstream Reference
     ///
     /// - `flist.c:send_directory()` - reads directory and stats each child
-    fn scan_directory_batched(&mut self, base: &Path, dir_path: &Path) -> io::Result<()> {
+    pub(super) fn scan_directory_batched(
+        &mut self,
+        base: &Path,
+        dir_path: &Path,
+    ) -> io::Result<()> {
         match std::fs::read_dir(dir_path) {
             Ok(entries) => self.process_dir_entries_batched(base, dir_path, entries),
             Err(e) => {
@@ -597,6 +670,52 @@ impl GeneratorContext {
 
         Ok(meta)
     }
+
+    /// Returns `true` when `--one-file-system` is active and `metadata`
+    /// lives on a different device than the current top-level source.
+    fn is_mount_point(&self, metadata: &std::fs::Metadata) -> bool {
+        if self.config.flags.one_file_system == 0 {
+            return false;
+        }
+        match (self.filesystem_dev, device_id(metadata)) {
+            (Some(boundary), Some(dev)) => dev != boundary,
+            _ => false,
+        }
+    }
+
+    /// Returns the skip notice for a regular file outside the `--min-size` /
+    /// `--max-size` window, or `None` when it belongs in the file list.
+    ///
+    /// Over max-size is tested before under min-size, matching upstream.
+    /// Nothing is pruned under `--delete`: the receiver must still see the
+    /// file, or it would delete the destination copy as extraneous.
+    fn size_bound_notice(&self, metadata: &std::fs::Metadata) -> Option<&'static str> {
+        if !metadata.is_file() || self.config.flags.delete {
+            return None;
+        }
+        let selection = &self.config.file_selection;
+        let size = metadata.len();
+        if selection.max_file_size.is_some_and(|max| size > max) {
+            Some("is over max-size")
+        } else if selection.min_file_size.is_some_and(|min| size < min) {
+            Some("is under min-size")
+        } else {
+            None
+        }
+    }
+}
+
+/// Device ID of `metadata`, or `None` where the platform exposes none.
+#[cfg(unix)]
+fn device_id(metadata: &std::fs::Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.dev())
+}
+
+/// Device ID of `metadata`, or `None` where the platform exposes none.
+#[cfg(not(unix))]
+fn device_id(_metadata: &std::fs::Metadata) -> Option<u64> {
+    None
 }
 
 #[cfg(test)]
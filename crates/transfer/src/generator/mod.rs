@@ -71,6 +71,9 @@
 //!
 //! The sender-side state machine and segment scheduler are implemented
 //! (see `IncrementalState`, `SegmentScheduler`, `PendingSegment`).
+//! Unless `--hard-links`, `--relative`, a transcoding `--iconv` or
+//! `--files-from` needs the whole list up front, the walk lists only the top
+//! level and ScanDir reads each queued directory on demand (`DeferredDir`).
 //! oc-rsync advertises the `'i'` capability in both transfer directions
 //! by default, mirroring upstream's `allow_inc_recurse = 1`
 //! initialization. `--no-inc-recursive` (or
@@ -96,7 +99,10 @@ mod timing;
 mod transfer;
 
 pub use self::context::GeneratorContext;
-pub use self::delta::{generate_delta_from_signature, generate_delta_from_signature_chunked};
+pub use self::delta::{
+    generate_delta_from_signature, generate_delta_from_signature_chunked,
+    generate_delta_from_signature_file,
+};
 pub use self::diagnostics::{
     flush_rate_totals, ndx_convert_totals, prepare_acl_totals, segment_dispatch_totals,
 };
@@ -110,7 +116,9 @@ pub use self::stats::GeneratorStats;
 // Re-exports for sibling submodules accessing diagnostics, segments, and stats
 // through `super::*` (matches the pre-decomposition import surface).
 pub(crate) use self::diagnostics::{flush_with_count, record_prepare_acl, record_segment_dispatch};
-pub(crate) use self::segments::{DirSegment, PendingSegment, SegmentScheduler, TaggedIndex};
+pub(crate) use self::segments::{
+    DeferredDir, DirSegment, PendingSegment, SegmentScheduler, TaggedIndex,
+};
 pub(crate) use self::stats::{FlistSendStats, TransferLoopResult, is_early_close_error};
 
 #[cfg(test)]
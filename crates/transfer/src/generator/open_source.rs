@@ -34,6 +34,19 @@ pub(super) fn open_source_with_noatime(path: &Path, use_noatime: bool) -> io::Re
     fs::File::open(path)
 }
 
+/// Evicts a sent source file's pages from the page cache (`--drop-cache`).
+///
+/// The send paths each own (and close) their handle, so the file is reopened
+/// here; the advice applies to the inode's cache whichever descriptor gives
+/// it. Best effort: open and advice failures are only logged.
+pub(super) fn drop_source_cache(path: &Path, use_noatime: bool) {
+    let result = open_source_with_noatime(path, use_noatime)
+        .and_then(|file| fast_io::drop_file_cache(&file, false));
+    if let Err(err) = result {
+        logging::debug_log!(Io, 2, "drop-cache {}: {err}", path.display());
+    }
+}
+
 #[cfg(any(target_os = "linux", target_os = "android"))]
 fn try_open_noatime(path: &Path) -> io::Result<Option<fs::File>> {
     let mut options = fs::OpenOptions::new();
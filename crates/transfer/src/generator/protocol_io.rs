@@ -597,7 +597,8 @@ impl GeneratorContext {
     /// per-directory segments are dispatched by `encode_and_send_segment` via the
     /// `SegmentScheduler` during the transfer loop.
     ///
-    /// Returns the total file list length (all segments).
+    /// Returns the total file list length (all segments listed so far; only
+    /// the top level when directories are listed on demand).
     ///
     /// # Upstream Reference
     ///
@@ -920,6 +921,8 @@ pub fn read_signature_blocks<R: Read>(
 /// - `sender.c:115-116` - `if (lull_mod && !(i % lull_mod)) maybe_send_keepalive(time(NULL), True)`
 /// - `io.c:1453` - `maybe_send_keepalive()` gates the actual emission on `allowed_lull`
 /// - `match.c:395` - Block format: rolling_sum (4 bytes) + strong_sum (s2length bytes)
+/// - `sender.c:98` - `new_array(struct sum_buf, s->count)`, bounded by
+///   `--max-alloc` in `util2.c:my_alloc()`
 pub fn read_signature_blocks_keepalive<R, F>(
     reader: &mut R,
     sum_head: &SumHead,
@@ -935,6 +938,21 @@ where
         return Ok(Vec::new());
     }
 
+    // upstream: util2.c:73-81 - my_alloc() refuses `num >= max_alloc / size`,
+    // so a peer-declared block count cannot force an unbounded allocation.
+    let max_alloc = protocol::effective_max_alloc();
+    if sum_head.count as usize >= max_alloc / size_of::<SignatureBlock>() {
+        return Err(io::Error::new(
+            io::ErrorKind::OutOfMemory,
+            format!(
+                "exceeded --max-alloc={max_alloc} setting reading {} checksum blocks {}{}",
+                sum_head.count,
+                crate::role_trailer::error_location!(),
+                crate::role_trailer::sender()
+            ),
+        ));
+    }
+
     let mut blocks = Vec::with_capacity(sum_head.count as usize);
 
     for i in 0..sum_head.count {
@@ -1,10 +1,10 @@
 //! Segment scheduling and incremental recursion state for the generator.
 //!
 //! Defines the per-directory sub-list types (`PendingSegment`, `DirSegment`,
-//! `TaggedIndex`), the cursor-based `SegmentScheduler` that throttles segment
-//! dispatch via `MIN_FILECNT_LOOKAHEAD`, and the `IncrementalState` mutable
-//! state carried by `GeneratorContext` for INC_RECURSE segmented file list
-//! sending.
+//! `TaggedIndex`, `DeferredDir`), the cursor-based `SegmentScheduler` that
+//! throttles segment dispatch via `MIN_FILECNT_LOOKAHEAD`, and the
+//! `IncrementalState` mutable state carried by `GeneratorContext` for
+//! INC_RECURSE segmented file list sending.
 //!
 //! # Upstream Reference
 //!
@@ -12,6 +12,11 @@
 //! - `flist.c:2498-2510` - `send_extra_file_list()` lookahead throttling
 //! - `sender.c:231,265` - send loop calls into segment scheduling at top/bottom
 
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use filters::DirFilterGuard;
+
 /// Minimum file count lookahead before the sender emits the next incremental
 /// sub-list. The sender accumulates at least this many unsent entries before
 /// flushing a new segment to the receiver, amortizing per-segment overhead.
@@ -79,6 +84,31 @@ pub(crate) struct DirSegment {
     pub(crate) children: Vec<TaggedIndex>,
 }
 
+/// A directory whose contents are listed on demand (lazy INC_RECURSE scan).
+///
+/// Recorded when the directory entry itself is sent; its children are read
+/// only when the transfer loop needs the next sub-list.
+///
+/// # Upstream Reference
+///
+/// - `flist.c:send1extra()` - reads one pending directory via `send_directory()`
+#[derive(Debug)]
+pub(crate) struct DeferredDir {
+    /// Wire `dir_ndx` of the directory in the receiver's `dir_flist`.
+    pub(crate) dir_ndx: i32,
+    /// Flat `GeneratorContext::file_list` index of the directory entry.
+    pub(crate) flat_idx: usize,
+    /// On-disk path of the directory.
+    ///
+    /// Kept here rather than rebuilt from `file_list` because the directory's
+    /// own segment may already be reclaimed when its contents are read.
+    pub(crate) path: PathBuf,
+    /// Walk base the directory's children are named relative to.
+    pub(crate) base: Arc<Path>,
+    /// Outermost directory whose per-directory merge files apply to this one.
+    pub(crate) scope_root: Arc<Path>,
+}
+
 /// Cursor-based scheduler that yields pending segments on demand.
 ///
 /// Controls *when* sub-lists are sent during the transfer loop using
@@ -125,6 +155,11 @@ impl SegmentScheduler {
         }
     }
 
+    /// Appends a segment produced after the scheduler was created.
+    pub(crate) fn push(&mut self, segment: PendingSegment) {
+        self.segments.push(segment);
+    }
+
     /// Returns a slice of all remaining unconsumed segments.
     pub(crate) fn remaining(&self) -> &[PendingSegment] {
         &self.segments[self.cursor..]
@@ -204,6 +239,21 @@ pub(crate) struct IncrementalState {
     /// - `flist.c:101` - `first_flist` pointer
     /// - `sender.c:248` - `flist_free(first_flist)` advances `first_flist`
     pub(crate) first_segment_idx: usize,
+    /// Whether directory contents are read on demand instead of by the walk.
+    ///
+    /// Set by `build_file_list()` when nothing downstream needs the whole
+    /// tree up front; the initial walk then stops at the top-level entries.
+    pub(crate) lazy_scan: bool,
+    /// Directories still to be listed, as a depth-first stack (next on top).
+    ///
+    /// upstream: flist.c:add_dirs_to_tree() - the traversal order of
+    /// `send_extra_file_list()`.
+    pub(crate) deferred_dirs: Vec<DeferredDir>,
+    /// Per-directory filter scopes entered for the current lazy-scan path,
+    /// outermost first, so sibling scans reuse their ancestors' merge rules.
+    pub(crate) scan_scopes: Vec<(PathBuf, DirFilterGuard)>,
+    /// Next wire `dir_ndx` to hand out to a lazily listed directory.
+    pub(crate) next_dir_ndx: i32,
 }
 
 impl IncrementalState {
@@ -217,6 +267,10 @@ impl IncrementalState {
             ndx_segments: vec![(0, initial_ndx_start)],
             segment_parent_flat: vec![-1],
             first_segment_idx: 0,
+            lazy_scan: false,
+            deferred_dirs: Vec::new(),
+            scan_scopes: Vec::new(),
+            next_dir_ndx: 0,
         }
     }
 }
@@ -5,6 +5,7 @@
 //! handshake, and [`is_early_close_error`] which classifies peer-disconnect
 //! `io::Error` kinds tolerated during dry-run and phase boundaries.
 
+use std::path::PathBuf;
 use std::time::Duration;
 
 use protocol::codec::{MonotonicNdxWriter, NdxCodecEnum};
@@ -100,6 +101,10 @@ pub(crate) struct TransferLoopResult {
     /// `ITEM_IS_NEW` iflags on the wire (upstream: `stats.created_*` in
     /// `sender.c:295-308`). Reconstructed locally, never sent over the wire.
     pub(crate) created_stats: CreatedStats,
+    /// Whether the stop deadline had passed when the send loop finished.
+    pub(crate) stop_limit_reached: bool,
+    /// Entries past the furthest one requested when the deadline passed.
+    pub(crate) remaining_files: Vec<PathBuf>,
     /// NDX read codec state carried over for the goodbye handshake.
     pub(crate) ndx_read_codec: NdxCodecEnum,
     /// NDX write codec state carried over for the goodbye handshake.
@@ -186,6 +191,23 @@ pub struct GeneratorStats {
     ///
     /// - `main.c:1338-1345`: `log_exit()` maps `io_error` to `RERR_VANISHED` (24).
     pub io_error: i32,
+    /// Whether the `--stop-at` / `--stop-after` deadline cut the run short.
+    ///
+    /// The sender answers every request the receiver makes; the receiver
+    /// stops requesting at the deadline and the transfer then finishes its
+    /// phases and goodbye normally. The caller should report exit code 30
+    /// (`RERR_TIMEOUT`).
+    ///
+    /// # Upstream Reference
+    ///
+    /// - `io.c:check_timeout()` - `stop_at_utime` exits with `RERR_TIMEOUT`
+    pub stop_limit_reached: bool,
+    /// Entries the receiver had not reached when the stop limit ended the
+    /// run, relative to the transfer root and in file-list order.
+    ///
+    /// oc-rsync extension backing `--remaining-files`; empty unless
+    /// [`Self::stop_limit_reached`] is set.
+    pub remaining_files: Vec<PathBuf>,
 }
 
 /// Returns `true` when the I/O error indicates an early connection close.
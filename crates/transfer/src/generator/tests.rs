@@ -529,6 +529,186 @@ fn inc_recurse_gap_ndx_itemizes_parent_directory() {
     );
 }
 
+/// Builds an INC_RECURSE `--recursive` generator over the contents of `base_path`.
+fn inc_recurse_generator_for(
+    base_path: &Path,
+    hard_links: bool,
+) -> (HandshakeResult, GeneratorContext) {
+    use protocol::CompatibilityFlags;
+
+    let mut handshake = test_handshake_with_protocol(32);
+    handshake.compat_flags = Some(CompatibilityFlags::INC_RECURSE);
+    let mut config = test_config();
+    config.flags.recursive = true;
+    config.flags.hard_links = hard_links;
+    let mut ctx = GeneratorContext::new_for_test(&handshake, config);
+    build_file_list_for_contents(&mut ctx, base_path);
+    ctx.partition_file_list_for_inc_recurse();
+    (handshake, ctx)
+}
+
+#[test]
+fn inc_recurse_lists_directories_on_demand_in_depth_first_order() {
+    // upstream: flist.c:send_extra_file_list() - sub-lists follow
+    // add_dirs_to_tree() order and each directory takes the next dir_flist
+    // slot as the receiver appends it, so `.`=0, a=1, b=2, a/x=3, a/y=4.
+    let temp_dir = create_test_structure(&["a/x/f1", "a/y/", "a/f2", "b/f3", "top.txt"]);
+    let (_handshake, mut ctx) = inc_recurse_generator_for(temp_dir.path(), false);
+
+    assert!(ctx.incremental.lazy_scan);
+    assert_eq!(ctx.incremental.initial_segment_count, Some(4));
+    assert!(ctx.incremental.pending_segments.is_empty());
+    assert!(ctx.file_list.iter().all(|e| !e.name().contains('/')));
+    assert_eq!(ctx.incremental.segment_parent_flat, vec![0]);
+
+    let mut listed = Vec::new();
+    while let Some(seg) = ctx.scan_next_deferred_directory().unwrap() {
+        assert_eq!(seg.flist_start + seg.count, ctx.file_list.len());
+        let mut names: Vec<String> = ctx.file_list.as_slice()[seg.flist_start..]
+            .iter()
+            .map(|e| e.name().to_owned())
+            .collect();
+        names.sort();
+        let owner = ctx.file_list[seg.parent_flat_idx].name().to_owned();
+        listed.push((seg.parent_dir_ndx, owner, names));
+    }
+
+    let expected: Vec<(i32, String, Vec<String>)> = vec![
+        (
+            1,
+            "a".into(),
+            vec!["a/f2".into(), "a/x".into(), "a/y".into()],
+        ),
+        (3, "a/x".into(), vec!["a/x/f1".into()]),
+        (4, "a/y".into(), vec![]),
+        (2, "b".into(), vec!["b/f3".into()]),
+    ];
+    assert_eq!(listed, expected);
+    assert!(!ctx.has_deferred_directories());
+    assert_eq!(ctx.filter_chain.current_depth(), 0);
+}
+
+#[test]
+fn inc_recurse_hard_links_keep_the_eager_walk() {
+    // Hard-link leaders are matched across the whole sorted list, so the walk
+    // must see every directory before the first sub-list is sent.
+    let temp_dir = create_test_structure(&["a/f1", "b/f2"]);
+    let (_handshake, ctx) = inc_recurse_generator_for(temp_dir.path(), true);
+
+    assert!(!ctx.incremental.lazy_scan);
+    assert!(!ctx.has_deferred_directories());
+    assert_eq!(ctx.incremental.pending_segments.len(), 2);
+    assert_eq!(ctx.file_list.len(), 5);
+}
+
+/// Lists the children of `base` with every directory below it treated as
+/// living on another filesystem.
+#[cfg(unix)]
+fn scan_with_foreign_subdirs(base: &Path, one_file_system: u8) -> Vec<String> {
+    let (_handshake, mut ctx) = test_generator_for_path(base, true);
+    ctx.config.flags.one_file_system = one_file_system;
+    ctx.filesystem_dev = Some(u64::MAX);
+    ctx.scan_files_from_marker_dir(base, base).unwrap();
+    let mut names: Vec<String> = ctx.file_list.iter().map(|e| e.name().to_owned()).collect();
+    names.sort();
+    names
+}
+
+#[cfg(unix)]
+#[test]
+fn one_file_system_sends_mount_point_without_its_contents() {
+    // upstream: flist.c:1344-1356 - `-x` marks the directory FLAG_MOUNT_DIR
+    // and send_if_directory() never descends into it.
+    let temp_dir = create_test_structure(&["mnt/inner.txt", "top.txt"]);
+    let names = scan_with_foreign_subdirs(temp_dir.path(), 1);
+    assert_eq!(names, vec!["mnt".to_owned(), "top.txt".to_owned()]);
+}
+
+#[cfg(unix)]
+#[test]
+fn double_one_file_system_skips_mount_point_directory() {
+    // upstream: flist.c:1345 - `one_file_system > 1` returns NULL from
+    // make_file(), so the mount point never reaches the file list.
+    let temp_dir = create_test_structure(&["mnt/inner.txt", "top.txt"]);
+    let names = scan_with_foreign_subdirs(temp_dir.path(), 2);
+    assert_eq!(names, vec!["top.txt".to_owned()]);
+}
+
+#[test]
+fn one_file_system_descends_into_same_device_directories() {
+    let temp_dir = create_test_structure(&["a/b/f1", "top.txt"]);
+    let (_handshake, mut ctx) = test_generator_for_path(temp_dir.path(), true);
+    ctx.config.flags.one_file_system = 2;
+    build_file_list_for_contents(&mut ctx, temp_dir.path());
+
+    let names: Vec<&str> = ctx.file_list.iter().map(|e| e.name()).collect();
+    assert!(names.contains(&"a/b/f1"), "names: {names:?}");
+    assert!(names.contains(&"top.txt"), "names: {names:?}");
+}
+
+/// Writes `(name, len)` files under a fresh directory and returns the sorted
+/// names the sender lists with the given size window and delete setting.
+fn scan_with_size_bounds(
+    files: &[(&str, usize)],
+    min: Option<u64>,
+    max: Option<u64>,
+    delete: bool,
+) -> Vec<String> {
+    let temp_dir = TempDir::new().unwrap();
+    for (name, len) in files {
+        std::fs::write(temp_dir.path().join(name), vec![b'x'; *len]).unwrap();
+    }
+    let (_handshake, mut ctx) = test_generator_for_path(temp_dir.path(), true);
+    ctx.config.file_selection.min_file_size = min;
+    ctx.config.file_selection.max_file_size = max;
+    ctx.config.flags.delete = delete;
+    build_file_list_for_contents(&mut ctx, temp_dir.path());
+    let mut names: Vec<String> = ctx
+        .file_list
+        .iter()
+        .filter(|e| e.is_file())
+        .map(|e| e.name().to_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn size_bounds_keep_out_of_window_files_off_the_wire() {
+    let files = [("tiny", 1), ("mid", 50), ("edge", 100), ("big", 101)];
+    let names = scan_with_size_bounds(&files, Some(10), Some(100), false);
+    assert_eq!(names, vec!["edge".to_owned(), "mid".to_owned()]);
+}
+
+#[test]
+fn size_bounds_are_left_to_the_receiver_under_delete() {
+    // Pruning would make the receiver delete the destination copies of the
+    // out-of-window files as extraneous.
+    let files = [("tiny", 1), ("big", 101)];
+    let names = scan_with_size_bounds(&files, Some(10), Some(100), true);
+    assert_eq!(names, vec!["big".to_owned(), "tiny".to_owned()]);
+}
+
+#[test]
+fn inc_recurse_one_file_system_keeps_the_eager_walk() {
+    use protocol::CompatibilityFlags;
+
+    let mut handshake = test_handshake_with_protocol(32);
+    handshake.compat_flags = Some(CompatibilityFlags::INC_RECURSE);
+    let mut config = test_config();
+    config.flags.recursive = true;
+    config.flags.one_file_system = 1;
+    let ctx = GeneratorContext::new_for_test(&handshake, config);
+
+    assert!(!ctx.lazy_scan_eligible());
+}
+
+#[test]
+fn lazy_lookahead_reports_empty_window_when_no_sublist_is_pending() {
+    assert_eq!(GeneratorContext::lazy_lookahead(5000, 0), 0);
+    assert_eq!(GeneratorContext::lazy_lookahead(5000, 2), 5000);
+}
+
 #[test]
 fn flush_with_count_increments_global_counter() {
     // INC_RECURSE diagnostic I3 (#2198): every flush on the generator
@@ -942,7 +1122,6 @@ fn stream_whole_file_produces_correct_wire_format() {
         ProtocolVersion::try_from(31u8).unwrap(),
         None,
         &mut buf,
-        None,
     )
     .unwrap();
 
@@ -971,7 +1150,6 @@ fn stream_whole_file_handles_empty_file() {
         ProtocolVersion::try_from(31u8).unwrap(),
         None,
         &mut buf,
-        None,
     )
     .unwrap();
 
@@ -1003,7 +1181,6 @@ fn stream_whole_file_computes_correct_checksum() {
         ProtocolVersion::try_from(31u8).unwrap(),
         None,
         &mut buf,
-        None,
     )
     .unwrap();
 
@@ -1019,6 +1196,106 @@ fn stream_whole_file_computes_correct_checksum() {
     );
 }
 
+/// The daemon `--zero-copy` SERVE path writes through the io_uring SEND_ZC
+/// socket writer (`ZeroCopyTcpWriter` in the daemon wraps the same factory).
+/// Bytes buffered before a chunk must be flushed ahead of the raw-fd
+/// `sendfile`, and every chunk must travel in its own `MSG_DATA` frame.
+#[cfg(unix)]
+#[test]
+fn stream_whole_file_sendfile_orders_frames_behind_zero_copy_writer() {
+    use super::delta::{ServeFds, stream_whole_file_sendfile};
+    use crate::writer::ServerWriter;
+    use protocol::MessageCode;
+    use protocol::wire::CHUNK_SIZE;
+    use std::io::Read;
+    use std::net::{Shutdown, TcpListener, TcpStream};
+    use std::os::unix::io::AsRawFd;
+    use tempfile::NamedTempFile;
+
+    let data: Vec<u8> = (0..2 * CHUNK_SIZE + 100).map(|i| (i % 251) as u8).collect();
+    let mut temp_file = NamedTempFile::new().unwrap();
+    temp_file.write_all(&data).unwrap();
+    temp_file.flush().unwrap();
+    let file = fs::File::open(temp_file.path()).unwrap();
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+    let reader = std::thread::spawn(move || {
+        let mut wire = Vec::new();
+        peer.read_to_end(&mut wire).unwrap();
+        wire
+    });
+
+    let zc = fast_io::socket_writer_from_fd_zero_copy(
+        client.as_raw_fd(),
+        64 * 1024,
+        fast_io::ZeroCopyPolicy::Enabled,
+    )
+    .unwrap();
+    let mut writer = ServerWriter::new_plain(zc).activate_multiplex().unwrap();
+    writer.write_all(b"hdr").unwrap();
+    assert!(writer.supports_passthrough());
+
+    // The checksum reader sees zeros while sendfile reads the real file, so
+    // the wire payload only matches `data` when sendfile carried it.
+    let hashed = Cursor::new(vec![0u8; data.len()]);
+    let mut buf = Vec::new();
+    let (result, token_bytes) = stream_whole_file_sendfile(
+        &mut writer,
+        hashed,
+        ServeFds {
+            src_fd: file.as_raw_fd(),
+            dst_fd: client.as_raw_fd(),
+        },
+        data.len() as u64,
+        ChecksumAlgorithm::MD5,
+        0,
+        ProtocolVersion::try_from(31u8).unwrap(),
+        &mut buf,
+    )
+    .unwrap();
+    writer
+        .write_all(&result.checksum_buf[..result.checksum_len])
+        .unwrap();
+    writer.flush().unwrap();
+    drop(writer);
+    client.shutdown(Shutdown::Write).unwrap();
+    let wire = reader.join().unwrap();
+
+    let mut frames = Vec::new();
+    let mut rest = &wire[..];
+    while !rest.is_empty() {
+        let header = protocol::MessageHeader::decode(rest).unwrap();
+        assert_eq!(header.code(), MessageCode::Data);
+        let end = 4 + header.payload_len();
+        frames.push(&rest[4..end]);
+        rest = &rest[end..];
+    }
+
+    let tail = data.len() - 2 * CHUNK_SIZE;
+    let lens: Vec<usize> = frames.iter().map(|frame| frame.len()).collect();
+    assert_eq!(
+        lens,
+        [
+            3,
+            4 + CHUNK_SIZE,
+            4 + CHUNK_SIZE,
+            4 + tail,
+            4 + result.checksum_len
+        ]
+    );
+    assert_eq!(frames[0], b"hdr");
+    for (frame, chunk) in frames[1..4].iter().zip(data.chunks(CHUNK_SIZE)) {
+        assert_eq!(&frame[..4], &(chunk.len() as i32).to_le_bytes());
+        #[cfg(target_os = "linux")]
+        assert_eq!(&frame[4..], chunk);
+    }
+    assert_eq!(&frames[4][..4], &[0u8; 4]);
+    assert_eq!(&frames[4][4..], &result.checksum_buf[..result.checksum_len]);
+    assert_eq!(token_bytes, (3 * 4 + data.len() + 4) as u64);
+}
+
 #[test]
 fn stream_whole_file_md4_prepends_seed_for_proto29() {
     // Regression (RP28 proto-29): the legacy MD4 whole-file sum must prepend
@@ -1049,7 +1326,6 @@ fn stream_whole_file_md4_prepends_seed_for_proto29() {
         ProtocolVersion::try_from(29u8).unwrap(),
         None,
         &mut buf,
-        None,
     )
     .unwrap();
 
@@ -1107,7 +1383,6 @@ fn stream_whole_file_reuses_buffer() {
         ProtocolVersion::try_from(31u8).unwrap(),
         None,
         &mut buf,
-        None,
     )
     .unwrap();
 
@@ -1127,7 +1402,6 @@ fn stream_whole_file_reuses_buffer() {
         ProtocolVersion::try_from(31u8).unwrap(),
         None,
         &mut buf,
-        None,
     )
     .unwrap();
 
@@ -1158,7 +1432,6 @@ fn stream_whole_file_none_checksum() {
         ProtocolVersion::try_from(31u8).unwrap(),
         None,
         &mut buf,
-        None,
     )
     .unwrap();
 
@@ -1491,6 +1764,18 @@ fn read_signature_blocks_single_block() {
     assert_eq!(blocks[0].strong_sum, vec![0xAA; 16]);
 }
 
+#[test]
+fn read_signature_blocks_rejects_count_over_max_alloc() {
+    // upstream: util2.c:my_alloc() - a declared block count whose array would
+    // reach --max-alloc (1 GiB by default) is refused before any allocation.
+    let mut cursor = Cursor::new(&[][..]);
+    let sum_head = SumHead::new(u32::MAX, 1024, 16, 0);
+    let err = read_signature_blocks(&mut cursor, &sum_head).unwrap_err();
+
+    assert_eq!(err.kind(), io::ErrorKind::OutOfMemory);
+    assert!(err.to_string().contains("exceeded --max-alloc="));
+}
+
 #[test]
 fn read_signature_blocks_multiple_blocks() {
     // Three blocks
@@ -5437,3 +5722,86 @@ fn remove_source_files_mid_commit_teardown_retains_unconfirmed_sources() {
         "the two unconfirmed removals stay pending after the drop"
     );
 }
+
+/// Without `--recursive` or `--dirs` a named directory operand is skipped
+/// entirely (upstream flist.c:2450 "skipping directory %s"), while a file
+/// operand in the same invocation is still sent.
+#[test]
+fn named_directory_operand_skipped_without_recursive_or_dirs() {
+    let temp = create_test_structure(&["src/", "src/inner.txt", "top.txt"]);
+    let src = temp.path().join("src");
+    let top = temp.path().join("top.txt");
+
+    let (_h, mut ctx) = test_generator_for_path(temp.path(), false);
+    let count = ctx.build_file_list(&[src, top]).unwrap();
+
+    let names: Vec<&str> = ctx.file_list().iter().map(|e| e.name()).collect();
+    assert_eq!(count, 1, "only the file operand is listed: {names:?}");
+    assert_eq!(names, vec!["top.txt"]);
+    assert_eq!(ctx.io_error(), 0, "a skipped directory is not an error");
+}
+
+/// With `--dirs` the named directory itself is sent but not descended into.
+#[test]
+fn named_directory_operand_sent_without_contents_under_dirs() {
+    let temp = create_test_structure(&["src/", "src/inner.txt", "src/sub/"]);
+    let src = temp.path().join("src");
+
+    let (_h, mut ctx) = test_generator_for_path(temp.path(), false);
+    ctx.config.flags.dirs = true;
+    ctx.build_file_list(&[src]).unwrap();
+
+    let names: Vec<&str> = ctx.file_list().iter().map(|e| e.name()).collect();
+    assert_eq!(names, vec!["src"]);
+    assert!(ctx.file_list()[0].is_dir());
+}
+
+/// With `--dirs` a trailing-slash operand lists the directory's immediate
+/// children, including subdirectories, without descending into them.
+#[test]
+fn trailing_slash_operand_lists_one_level_under_dirs() {
+    let temp = create_test_structure(&["src/", "src/inner.txt", "src/sub/deep.txt"]);
+    let src = temp.path().join("src");
+
+    let (_h, mut ctx) = test_generator_for_path(&src, false);
+    ctx.config.flags.dirs = true;
+    build_file_list_for_contents(&mut ctx, &src);
+
+    let names: Vec<&str> = ctx.file_list().iter().map(|e| e.name()).collect();
+    assert!(names.contains(&"inner.txt"), "{names:?}");
+    assert!(names.contains(&"sub"), "{names:?}");
+    assert!(!names.iter().any(|n| n.contains("deep.txt")), "{names:?}");
+}
+
+/// Without `--recursive` or `--dirs` a trailing-slash operand still sends
+/// the directory's immediate contents (upstream `DOTDIR_NAME`), listing a
+/// child directory without descending into it.
+#[test]
+fn trailing_slash_operand_lists_contents_without_recursive_or_dirs() {
+    let temp = create_test_structure(&["src/", "src/inner.txt", "src/sub/deep.txt"]);
+    let src = temp.path().join("src");
+
+    let (_h, mut ctx) = test_generator_for_path(&src, false);
+    let count = build_file_list_for_contents(&mut ctx, &src);
+
+    let names: Vec<&str> = ctx.file_list().iter().map(|e| e.name()).collect();
+    assert_eq!(names, vec![".", "inner.txt", "sub"]);
+    assert_eq!(count, 3);
+    assert_eq!(ctx.io_error(), 0);
+}
+
+/// The two spellings of the same directory differ without `--recursive` or
+/// `--dirs`: `src` is skipped while `src/` sends its contents.
+#[test]
+fn directory_operand_spellings_differ_without_recursive_or_dirs() {
+    let temp = create_test_structure(&["src/", "src/inner.txt"]);
+    let src = temp.path().join("src");
+
+    let (_h, mut ctx) = test_generator_for_path(temp.path(), false);
+    assert_eq!(ctx.build_file_list(std::slice::from_ref(&src)).unwrap(), 0);
+
+    let (_h, mut ctx) = test_generator_for_path(&src, false);
+    assert_eq!(build_file_list_for_contents(&mut ctx, &src), 2);
+    let names: Vec<&str> = ctx.file_list().iter().map(|e| e.name()).collect();
+    assert_eq!(names, vec![".", "inner.txt"]);
+}
@@ -342,6 +342,8 @@ impl GeneratorContext {
             delete_stats: self.delete_stats,
             created_stats: transfer_result.created_stats,
             io_error: self.io_error,
+            stop_limit_reached: transfer_result.stop_limit_reached,
+            remaining_files: transfer_result.remaining_files,
         })
     }
 }
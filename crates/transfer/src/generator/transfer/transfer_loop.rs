@@ -17,6 +17,8 @@ use protocol::codec::{
 };
 use protocol::stats::DeleteStats;
 
+#[cfg(unix)]
+use super::super::delta::{SENDFILE_MIN_FILE_SIZE, ServeFds, stream_whole_file_sendfile};
 use super::super::delta::{
     create_token_encoder, script_to_wire_delta, stream_append_transfer, stream_whole_file_transfer,
     whole_stream_compression_level, write_delta_with_inline_checksum,
@@ -75,6 +77,20 @@ fn open_source_mmap(path: &std::path::Path, use_noatime: bool) -> io::Result<fas
     fast_io::MmapReader::from_file(file)
 }
 
+/// How the sender reads the source for a delta scan.
+enum DeltaSource {
+    /// Whole-file mapping for the opt-in parallel scan.
+    Mapped(fast_io::MmapReader),
+    /// Open handle for the sequential scan under `--mmap-source`; the
+    /// matching crate maps it and falls back to buffered reads when the
+    /// mapping is refused.
+    File(std::fs::File),
+    /// Streaming reader (io_uring, IOCP or buffered): the default, and the
+    /// only mode for `--copy-devices` sources, whose `st_size` is not their
+    /// length.
+    Stream(Box<dyn Read>),
+}
+
 impl GeneratorContext {
     /// Runs the main file transfer loop, reading NDX requests from receiver.
     ///
@@ -92,10 +108,10 @@ impl GeneratorContext {
         progress: &mut Option<&mut dyn super::super::super::TransferProgressCallback>,
         itemize: &mut Option<&mut dyn super::super::super::ItemizeCallback>,
     ) -> io::Result<TransferLoopResult> {
-        use super::super::super::shared::TransferDeadline;
+        use super::super::super::shared::{TransferDeadline, remaining_paths};
         use super::super::delta::{
             generate_delta_from_signature, generate_delta_from_signature_chunked,
-            updating_basis_file,
+            generate_delta_from_signature_file, updating_basis_file,
         };
         use super::super::protocol_io::{read_signature_blocks_keepalive, signature_read_lull_mod};
 
@@ -117,6 +133,9 @@ impl GeneratorContext {
         };
 
         let deadline = TransferDeadline::from_system_time(self.config.stop_at);
+        // Furthest flist entry the receiver asked about. The receiver walks
+        // the list in order, so after a stop everything past it is unsent.
+        let mut furthest_requested: Option<usize> = None;
 
         let mut files_transferred = 0;
         // upstream: sender.c:343 - stats.total_transferred_size += F_LENGTH(file),
@@ -223,8 +242,22 @@ impl GeneratorContext {
             if inc_recurse {
                 // upstream: flist.c:2139 - file_total - file_old_total < at_least
                 // remaining = entries the receiver knows about minus transferred
-                let remaining = dispatched_entry_count.saturating_sub(files_transferred);
-                while let Some(seg) = scheduler.next_if_needed(remaining) {
+                let burst_remaining = dispatched_entry_count.saturating_sub(files_transferred);
+                loop {
+                    // A lazy walk lists directories one sub-list at a time, so the
+                    // window is re-measured after each dispatch.
+                    let remaining = if self.incremental.lazy_scan {
+                        Self::lazy_lookahead(
+                            dispatched_entry_count.saturating_sub(files_transferred),
+                            flist_done_remaining,
+                        )
+                    } else {
+                        burst_remaining
+                    };
+                    self.refill_segment_scheduler(&mut scheduler, remaining)?;
+                    let Some(seg) = scheduler.next_if_needed(remaining) else {
+                        break;
+                    };
                     self.encode_and_send_segment(
                         &mut *writer,
                         seg,
@@ -239,7 +272,10 @@ impl GeneratorContext {
                 // upstream: flist.c:2534-2545 - send NDX_FLIST_EOF when all sub-lists
                 // have been dispatched. Must happen inside the loop (not after) because
                 // the receiver waits for NDX_FLIST_EOF before sending NDX_DONE.
-                if !self.incremental.flist_eof_sent && scheduler.is_exhausted() {
+                if !self.incremental.flist_eof_sent
+                    && scheduler.is_exhausted()
+                    && !self.has_deferred_directories()
+                {
                     self.send_flist_eof(&mut *writer, ndx_write_codec.inner_mut(), segments_sent)?;
                 }
             }
@@ -421,6 +457,7 @@ impl GeneratorContext {
             if ndx < self.file_list.len() {
                 let entry_path = self.file_list[ndx].path().display().to_string();
                 debug_log!(Send, 1, "send_files({}, {})", wire_ndx, entry_path);
+                furthest_requested = furthest_requested.max(Some(ndx));
             }
 
             if !iflags.needs_transfer() {
@@ -661,24 +698,35 @@ impl GeneratorContext {
                     None
                 };
 
-                // For the sequential path only, open the streaming reader; this
+                // For the sequential path, buffered streaming is the default.
+                // Under the opt-in --mmap-source, large regular files go to
+                // the matching crate as an open handle so it can scan a
+                // sequentially-advised mapping; a mapping turns a concurrent
+                // truncation into SIGBUS, so it is never the default. This
                 // borrows `self` mutably, so it must happen before `config`
                 // (which borrows `self` immutably) is constructed.
-                let source_reader: Option<Box<dyn Read>> = if source_mmap.is_none() {
-                    match self.open_source_reader(&source_path, file_size) {
-                        Ok(r) => Some(r),
-                        Err(e) => {
-                            self.record_open_failure(
-                                &mut *writer,
-                                wire_ndx,
-                                &e,
-                                &source_path_display,
-                            )?;
-                            continue;
-                        }
+                let delta_source = match source_mmap {
+                    Some(mmap) => Ok(DeltaSource::Mapped(mmap)),
+                    None if self.config.flags.mmap_source
+                        && file_size >= engine::delta::MMAP_INPUT_THRESHOLD
+                        && !self.source_is_copy_device(&source_path) =>
+                    {
+                        super::super::open_source::open_source_with_noatime(
+                            &source_path,
+                            self.config.write.open_noatime,
+                        )
+                        .map(DeltaSource::File)
+                    }
+                    None => self
+                        .open_source_reader(&source_path, file_size)
+                        .map(DeltaSource::Stream),
+                };
+                let delta_source = match delta_source {
+                    Ok(source) => source,
+                    Err(e) => {
+                        self.record_open_failure(&mut *writer, wire_ndx, &e, &source_path_display)?;
+                        continue;
                     }
-                } else {
-                    None
                 };
 
                 // upstream: sender.c:337 - the per-file updating_basis_file flag
@@ -704,16 +752,14 @@ impl GeneratorContext {
                     checksum_seed: self.checksum_seed,
                     updating_basis_file,
                 };
-                let delta_script = match source_mmap.as_ref() {
-                    Some(mmap) => generate_delta_from_signature_chunked(
+                let delta_script = match delta_source {
+                    DeltaSource::Mapped(mmap) => generate_delta_from_signature_chunked(
                         mmap.as_slice(),
                         config,
                         cores.min(PARALLEL_DELTA_MAX_CHUNKS),
                     )?,
-                    None => generate_delta_from_signature(
-                        source_reader.expect("sequential reader opened when mmap is absent"),
-                        config,
-                    )?,
+                    DeltaSource::File(file) => generate_delta_from_signature_file(file, config)?,
+                    DeltaSource::Stream(reader) => generate_delta_from_signature(reader, config)?,
                 };
 
                 self.write_ndx_and_attrs(
@@ -807,43 +853,69 @@ impl GeneratorContext {
                 // receiver saw, matching the delta path above. The raw source
                 // size would over-report by the compression ratio under -z/-zz
                 // and trip the daemon-gzip "did -zz engage?" assertion on
-                // whole-file pushes.
-                // NSV-1: build the SERVE fd pair. The concrete source `File` fd
-                // is surfaced by `open_source_unbuffered`; the destination socket
-                // fd is not yet reachable because the daemon erases its
-                // `TcpStream` to `dyn Write` before it reaches this crate, so
-                // `dst_fd` is `None` for now (wired by a later rung). The pair is
-                // plumbed but unused, so the transfer stays byte-for-byte
-                // identical.
+                // whole-file pushes. The sendfile path counts the same token
+                // bytes itself.
+                //
+                // Daemon SERVE zero-copy: with the plaintext socket fd from the
+                // daemon, a plain `File` source and an explicit `--zero-copy`,
+                // the literal data goes out via sendfile(2). The checksum is
+                // taken from a separate read of the same range, so the default
+                // `Auto` policy keeps the regular path where the hashed bytes
+                // are the sent bytes. Token compression or a compressing or
+                // batch-recording writer keep the regular path too.
                 #[cfg(unix)]
-                let serve_fds = src_fd.map(|fd| super::super::delta::ServeFds {
-                    src_fd: fd,
-                    dst_fd: None,
-                });
+                let serve_fds = match (src_fd, self.config.connection.sendfile_socket) {
+                    (Some(src_fd), Some(dst_fd))
+                        if !use_compression
+                            && file_size >= SENDFILE_MIN_FILE_SIZE
+                            && self.config.write.zero_copy_policy
+                                == fast_io::ZeroCopyPolicy::Enabled
+                            && writer.supports_passthrough() =>
+                    {
+                        Some(ServeFds { src_fd, dst_fd })
+                    }
+                    _ => None,
+                };
                 #[cfg(not(unix))]
                 let serve_fds = {
                     let _ = src_fd;
-                    None::<super::super::delta::ServeFds>
+                    None::<()>
                 };
-                let wire_bytes = {
-                    let mut cw = crate::writer::CountingWriter::new(&mut *writer);
-                    let result = stream_whole_file_transfer(
-                        &mut cw,
-                        source,
-                        file_size,
-                        checksum_algorithm,
-                        self.checksum_seed,
-                        self.protocol,
-                        if use_compression {
-                            token_encoder.as_mut()
-                        } else {
-                            None
-                        },
-                        &mut stream_buf,
-                        serve_fds,
-                    )?;
-                    cw.write_all(&result.checksum_buf[..result.checksum_len])?;
-                    cw.bytes_written()
+                let wire_bytes = match serve_fds {
+                    #[cfg(unix)]
+                    Some(fds) => {
+                        let (result, token_bytes) = stream_whole_file_sendfile(
+                            writer,
+                            source,
+                            fds,
+                            file_size,
+                            checksum_algorithm,
+                            self.checksum_seed,
+                            self.protocol,
+                            &mut stream_buf,
+                        )?;
+                        writer.write_all(&result.checksum_buf[..result.checksum_len])?;
+                        token_bytes + result.checksum_len as u64
+                    }
+                    _ => {
+                        let mut cw = crate::writer::CountingWriter::new(&mut *writer);
+                        let result = stream_whole_file_transfer(
+                            &mut cw,
+                            source,
+                            file_size,
+                            checksum_algorithm,
+                            self.checksum_seed,
+                            self.protocol,
+                            if use_compression {
+                                token_encoder.as_mut()
+                            } else {
+                                None
+                            },
+                            &mut stream_buf,
+                        )?;
+                        cw.write_all(&result.checksum_buf[..result.checksum_len])?;
+                        cw.bytes_written()
+                    }
                 };
                 bytes_sent += wire_bytes;
                 // Whole-file transfer: the entire body is sent as literal data
@@ -860,6 +932,15 @@ impl GeneratorContext {
             // matching upstream which sets the flag only after a real send.
             sent_files.mark_sent(ndx);
 
+            // oc-rsync extension: --drop-cache evicts the pages this send read
+            // so a large backup does not displace the host's working set.
+            if self.config.write.drop_cache {
+                super::super::open_source::drop_source_cache(
+                    &source_path,
+                    self.config.write.open_noatime,
+                );
+            }
+
             // upstream: sender.c:131-182 successful_send() - the source unlink is
             // DEFERRED, never run inline at send time. Upstream waits for the
             // receiver/generator to confirm the commit with MSG_SUCCESS(ndx)
@@ -898,8 +979,20 @@ impl GeneratorContext {
 
             // upstream: sender.c:261 - send extra file lists at bottom of loop
             if inc_recurse {
-                let remaining = dispatched_entry_count.saturating_sub(files_transferred);
-                while let Some(seg) = scheduler.next_if_needed(remaining) {
+                let burst_remaining = dispatched_entry_count.saturating_sub(files_transferred);
+                loop {
+                    let remaining = if self.incremental.lazy_scan {
+                        Self::lazy_lookahead(
+                            dispatched_entry_count.saturating_sub(files_transferred),
+                            flist_done_remaining,
+                        )
+                    } else {
+                        burst_remaining
+                    };
+                    self.refill_segment_scheduler(&mut scheduler, remaining)?;
+                    let Some(seg) = scheduler.next_if_needed(remaining) else {
+                        break;
+                    };
                     self.encode_and_send_segment(
                         &mut *writer,
                         seg,
@@ -940,9 +1033,33 @@ impl GeneratorContext {
                 )?;
                 segments_sent += 1;
             }
+            // Directories a lazy walk has not listed yet still owe a sub-list.
+            while let Some(seg) = self.scan_next_deferred_directory()? {
+                self.encode_and_send_segment(
+                    &mut *writer,
+                    &seg,
+                    &mut flist_writer,
+                    ndx_write_codec.inner_mut(),
+                )?;
+                segments_sent += 1;
+            }
             self.send_flist_eof(&mut *writer, ndx_write_codec.inner_mut(), segments_sent)?;
         }
 
+        // upstream: io.c:check_timeout() exits with RERR_TIMEOUT as soon as
+        // `stop_at_utime` passes. The receiver, which gets the same deadline
+        // through `--stop-at`, usually stops requesting files first and ends
+        // the phases normally; a deadline that passes while the final request
+        // is answered still reaches here and earns RERR_TIMEOUT.
+        let stop_limit_reached = deadline.is_some_and(|dl| dl.is_reached());
+        let remaining_files = if stop_limit_reached {
+            debug_log!(Send, 1, "stop limit reached during send_files");
+            let next = furthest_requested.map_or(0, |ndx| ndx + 1);
+            remaining_paths(self.file_list.iter().skip(next))
+        } else {
+            Vec::new()
+        };
+
         // Cache flist_writer back for potential reuse (e.g., phase 2).
         self.incremental.flist_writer_cache = Some(flist_writer);
 
@@ -984,6 +1101,8 @@ impl GeneratorContext {
             matched_data,
             literal_data,
             created_stats,
+            stop_limit_reached,
+            remaining_files,
             ndx_read_codec,
             ndx_write_codec,
         })
@@ -16,6 +16,9 @@
 use std::io::{self, BufRead, BufReader, Read, Write};
 use std::sync::Arc;
 
+#[cfg(feature = "tracing")]
+use tracing::instrument;
+
 use protocol::{
     CompatibilityFlags, NegotiationResult, ProtocolVersion, check_sub_protocol,
     get_subprotocol_version, select_highest_mutual,
@@ -159,6 +162,14 @@ fn reconcile_subprotocol(max_version: ProtocolVersion, client_flags: &str) -> Pr
 /// the peer's version, then clamps with `protocol_version = MIN(protocol_version,
 /// remote_protocol)` (compat.c:604-607). Passing `ProtocolVersion::NEWEST` (the
 /// default) reproduces the uncapped behaviour.
+#[cfg_attr(
+    feature = "tracing",
+    instrument(
+        skip(stdin, stdout),
+        name = "handshake",
+        fields(max = %max_version, protocol = tracing::field::Empty)
+    )
+)]
 pub fn perform_handshake_with_max(
     stdin: &mut dyn Read,
     stdout: &mut dyn Write,
@@ -186,6 +197,8 @@ pub fn perform_handshake_with_max(
     // remote_protocol). Clamp the mutually-supported version to our advertised
     // ceiling so `--protocol=N` caps the negotiated version.
     let negotiated = negotiated.min(max_version);
+    #[cfg(feature = "tracing")]
+    tracing::Span::current().record("protocol", negotiated.as_u8());
 
     Ok(HandshakeResult {
         protocol: negotiated,
@@ -233,6 +246,7 @@ fn write_server_version(stdout: &mut dyn Write, version: ProtocolVersion) -> io:
 ///
 /// Some older rsync clients (protocol < 30) use an ASCII-based greeting format
 /// instead of the binary handshake.
+#[cfg_attr(feature = "tracing", instrument(skip_all, name = "legacy_handshake"))]
 pub fn perform_legacy_handshake(
     stdin: &mut dyn Read,
     stdout: &mut dyn Write,
@@ -130,6 +130,7 @@ pub mod error;
 pub mod flags;
 pub mod generator;
 pub mod handshake;
+pub mod loopback;
 mod reader;
 pub mod receiver;
 pub mod role;
@@ -140,6 +141,7 @@ pub mod shared;
 pub mod symlink_safety;
 pub mod temp_cleanup;
 pub mod temp_guard;
+pub mod windows_link;
 pub mod writer;
 
 mod parallel_io;
@@ -176,6 +178,7 @@ pub use self::handshake::{
     HandshakeResult, IoTimeoutReapply, perform_handshake, perform_handshake_with_max,
     perform_legacy_handshake, perform_server_handshake,
 };
+pub use self::loopback::{LoopbackStats, run_loopback, run_loopback_with_hooks};
 pub use self::reader::RemoteExitError;
 pub use self::receiver::{ListOnlyEntry, ReceiverContext, SumHead, TransferStats};
 pub use self::role::ServerRole;
@@ -459,6 +462,40 @@ pub fn run_server_stdio(
     run_server_with_handshake(config, handshake, stdin, stdout, progress, None, None)
 }
 
+/// Executes the native server over tokio streams.
+///
+/// The async counterpart of [`run_server_stdio`] for services that accept
+/// connections on their own tokio runtime. The synchronous transfer engine
+/// runs on the runtime's blocking pool behind
+/// [`rsync_io::async_bridge`] adapters; once the transfer finishes, `writer`
+/// is shut down so the client observes EOF.
+///
+/// The I/O is not asynchronous: each call holds one blocking-pool thread for
+/// the whole transfer, so concurrent sessions are limited by the runtime's
+/// `max_blocking_threads`. Basis signatures the session computes on that
+/// thread are read through tokio and hashed on further blocking-pool tasks
+/// (see [`signature::async_gen::generate_file_signature_async`]).
+///
+/// # Errors
+///
+/// Returns the same errors as [`run_server_stdio`], plus an error when the
+/// blocking task panics or is cancelled.
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub async fn run_server_async<R, W>(config: ServerConfig, reader: R, writer: W) -> ServerResult
+where
+    R: tokio::io::AsyncRead + Unpin + Send + 'static,
+    W: tokio::io::AsyncWrite + Unpin + Send + 'static,
+{
+    let handle = tokio::runtime::Handle::current();
+    rsync_io::async_bridge::run_blocking(reader, writer, move |reader, writer| {
+        receiver::with_async_signature_runtime(handle, || {
+            run_server_stdio(config, reader, writer, None)
+        })
+    })
+    .await
+}
+
 /// Executes the native server with a pre-negotiated protocol version.
 ///
 /// This variant is used when the handshake has already been performed (e.g., by
@@ -0,0 +1,119 @@
+//! Runs a client session and a server session against each other in-process.
+//!
+//! [`run_loopback`] wires two [`ServerConfig`]s together over
+//! [`rsync_io::loopback`] pipes: the server half runs [`run_server_stdio`] on a
+//! worker thread exactly as a remote-shell server would, and the client half
+//! runs on the calling thread as the local side of an SSH transfer would. Every
+//! byte crosses the real wire protocol - version exchange, compat flags,
+//! filter list, multiplexed file list, and delta stream - so the pair behaves
+//! like `rsync -e ssh` without the subprocess.
+//!
+//! oc-rsync extension: upstream always connects the two sides through a pipe
+//! to a child process or a socket.
+
+use std::io::{self, BufReader};
+use std::thread;
+
+use protocol::ProtocolVersion;
+
+use crate::config::ServerConfig;
+use crate::handshake::perform_handshake_with_max;
+use crate::{
+    ServerStats, ServerTransferHooks, run_server_stdio, run_server_with_handshake_adopting,
+};
+
+/// Read-ahead for the client half, matching the SSH transport's buffer.
+///
+/// upstream: io.c read_buf() - 32KB read-ahead.
+const CLIENT_READ_BUFFER: usize = 32 * 1024;
+
+/// Statistics from both halves of a [`run_loopback`] transfer.
+#[derive(Debug, Clone)]
+pub struct LoopbackStats {
+    /// Statistics reported by the client half.
+    pub client: ServerStats,
+    /// Statistics reported by the server half.
+    pub server: ServerStats,
+    /// Protocol version the two halves negotiated.
+    pub protocol: ProtocolVersion,
+}
+
+/// Transfers between a client and a server session over in-memory pipes.
+///
+/// `client` is the local side of the transfer: a [`ServerRole::Receiver`]
+/// whose args hold the destination for a pull, or a
+/// [`ServerRole::Generator`] whose args hold the sources for a push. It is
+/// switched into client mode here. `server` is the opposite role as a remote
+/// `rsync --server` would be configured from its argv; its `flag_string`
+/// carries the `-e` capability string the client would have sent.
+///
+/// [`ServerRole::Receiver`]: crate::ServerRole::Receiver
+/// [`ServerRole::Generator`]: crate::ServerRole::Generator
+///
+/// # Errors
+///
+/// Returns the first failing half's error. When the client fails only
+/// because the server hung up, the server's error is returned instead since
+/// it names the cause. A panicking server thread surfaces as an
+/// [`io::ErrorKind::Other`] error.
+pub fn run_loopback(client: ServerConfig, server: ServerConfig) -> io::Result<LoopbackStats> {
+    run_loopback_with_hooks(client, server, ServerTransferHooks::default())
+}
+
+/// Like [`run_loopback`], but hands `hooks` to the client half.
+///
+/// Lets a local transfer driven through the loopback report progress and
+/// itemized output the same way the client side of an SSH transfer does.
+///
+/// # Errors
+///
+/// Same as [`run_loopback`].
+pub fn run_loopback_with_hooks(
+    mut client: ServerConfig,
+    server: ServerConfig,
+    hooks: ServerTransferHooks<'_, '_>,
+) -> io::Result<LoopbackStats> {
+    let (client_stream, server_stream) = rsync_io::loopback();
+
+    let server_thread = thread::Builder::new()
+        .name("loopback-server".to_owned())
+        .spawn(move || {
+            let (mut reader, mut writer) = server_stream.split();
+            run_server_stdio(server, &mut reader, &mut writer, None)
+        })?;
+
+    client.connection.client_mode = true;
+    let (reader, mut writer) = client_stream.split();
+    let mut reader = BufReader::with_capacity(CLIENT_READ_BUFFER, reader);
+    let client_result = perform_handshake_with_max(&mut reader, &mut writer, client.protocol)
+        .and_then(|handshake| {
+            let protocol = handshake.protocol;
+            run_server_with_handshake_adopting(client, handshake, &mut reader, &mut writer, hooks)
+                .map(|stats| (stats, protocol))
+        });
+    // Close both client halves so a server still waiting on us sees EOF.
+    drop(writer);
+    drop(reader);
+
+    let server_result = server_thread
+        .join()
+        .map_err(|_| io::Error::other("loopback server thread panicked"))?;
+
+    match (client_result, server_result) {
+        (Ok((client, protocol)), Ok(server)) => Ok(LoopbackStats {
+            client,
+            server,
+            protocol,
+        }),
+        (Err(client_err), Err(server_err)) if is_hangup(&client_err) => Err(server_err),
+        (Err(err), _) | (Ok(_), Err(err)) => Err(err),
+    }
+}
+
+/// Reports whether an error only reflects the peer closing the connection.
+fn is_hangup(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::UnexpectedEof | io::ErrorKind::BrokenPipe
+    )
+}
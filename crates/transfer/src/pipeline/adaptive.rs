@@ -0,0 +1,183 @@
+//! RTT-driven auto-tuning of the pipeline window.
+//!
+//! oc-rsync extension with no upstream equivalent. Upstream rsync has no
+//! request window at all - the generator and receiver are separate processes
+//! joined by socket buffers. The pipelined receiver bounds its in-flight
+//! requests instead, and a fixed bound is either too small for a long-haul
+//! link (the sender idles waiting for requests) or needlessly large on a
+//! local one (requests queue at the sender and hold signature memory).
+//!
+//! [`AdaptiveWindow`] sizes the window from the request-to-first-byte
+//! latency with a delay-based rule in the style of TCP Vegas. Each round is
+//! one flushed batch of requests, and only its first response is timed: from
+//! the flush until that response's header has been read. Later responses in
+//! the batch wait behind the file data of earlier ones and the receiver's
+//! own processing of it, so their latency says nothing about the link. The
+//! lowest latency seen approximates an unloaded round trip; the smoothed
+//! latency exceeds it by the time requests spend queued along the path. From
+//! the two, `window * (1 - base / smoothed)` estimates how many requests are
+//! queued:
+//!
+//! - fewer than [`QUEUE_LOW`]: the sender may run dry, so the window grows -
+//!   doubling until the first queueing signal, then one slot per round;
+//! - more than [`QUEUE_HIGH`]: requests are only waiting, so it shrinks by one;
+//! - otherwise the window holds.
+//!
+//! The window is re-evaluated on every sample, so each decision sees the
+//! latency produced by the previous one.
+
+use std::time::Duration;
+
+/// Estimated queued requests below which the window grows.
+pub const QUEUE_LOW: f64 = 2.0;
+
+/// Estimated queued requests above which the window shrinks.
+pub const QUEUE_HIGH: f64 = 6.0;
+
+/// Weight of a new sample in the smoothed latency (1/8, as in TCP's SRTT).
+const SRTT_GAIN: f64 = 0.125;
+
+/// Delay-based pipeline window controller.
+#[derive(Debug, Clone)]
+pub struct AdaptiveWindow {
+    min: usize,
+    max: usize,
+    window: usize,
+    base_rtt: Option<Duration>,
+    srtt: Option<Duration>,
+    slow_start: bool,
+}
+
+impl AdaptiveWindow {
+    /// Creates a controller starting at `initial`, bounded to `[min, max]`.
+    #[must_use]
+    pub fn new(initial: usize, min: usize, max: usize) -> Self {
+        let min = min.max(1);
+        let max = max.max(min);
+        Self {
+            min,
+            max,
+            window: initial.clamp(min, max),
+            base_rtt: None,
+            srtt: None,
+            slow_start: true,
+        }
+    }
+
+    /// Returns the current window size.
+    #[must_use]
+    pub const fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Returns the smoothed request-to-first-byte latency, once measured.
+    #[must_use]
+    pub const fn smoothed_rtt(&self) -> Option<Duration> {
+        self.srtt
+    }
+
+    /// Returns the lowest request-to-first-byte latency seen so far.
+    #[must_use]
+    pub const fn base_rtt(&self) -> Option<Duration> {
+        self.base_rtt
+    }
+
+    /// Feeds the request-to-first-byte latency measured for one round.
+    ///
+    /// Returns the new window when the sample changed it.
+    pub fn record(&mut self, latency: Duration) -> Option<usize> {
+        self.base_rtt = Some(self.base_rtt.map_or(latency, |base| base.min(latency)));
+        self.srtt = Some(match self.srtt {
+            Some(srtt) => srtt.mul_f64(1.0 - SRTT_GAIN) + latency.mul_f64(SRTT_GAIN),
+            None => latency,
+        });
+
+        let queued = self.estimated_queue();
+        let next = if queued < QUEUE_LOW {
+            if self.slow_start {
+                self.window.saturating_mul(2)
+            } else {
+                self.window + 1
+            }
+        } else {
+            self.slow_start = false;
+            if queued > QUEUE_HIGH {
+                self.window - 1
+            } else {
+                self.window
+            }
+        }
+        .clamp(self.min, self.max);
+
+        if next == self.window {
+            return None;
+        }
+        self.window = next;
+        Some(next)
+    }
+
+    /// Estimates how many in-flight requests are queued rather than in transit.
+    fn estimated_queue(&self) -> f64 {
+        match (self.base_rtt, self.srtt) {
+            (Some(base), Some(srtt)) if !srtt.is_zero() => {
+                self.window as f64 * (1.0 - base.as_secs_f64() / srtt.as_secs_f64())
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn initial_window_is_clamped() {
+        assert_eq!(AdaptiveWindow::new(500, 1, 256).window(), 256);
+        assert_eq!(AdaptiveWindow::new(0, 4, 256).window(), 4);
+    }
+
+    #[test]
+    fn steady_latency_doubles_until_max() {
+        let mut ctl = AdaptiveWindow::new(8, 1, 64);
+        let rtt = Duration::from_millis(20);
+        assert_eq!(ctl.record(rtt), Some(16));
+        assert_eq!(ctl.record(rtt), Some(32));
+        assert_eq!(ctl.record(rtt), Some(64));
+        assert_eq!(ctl.record(rtt), None);
+        assert_eq!(ctl.window(), 64);
+    }
+
+    #[test]
+    fn queueing_latency_shrinks_window() {
+        let mut ctl = AdaptiveWindow::new(32, 1, 256);
+        ctl.record(Duration::from_millis(1));
+        let mut rounds = 0;
+        while ctl.window() > 8 && rounds < 200 {
+            ctl.record(Duration::from_millis(10));
+            rounds += 1;
+        }
+        assert!(ctl.window() < 32, "window {} did not shrink", ctl.window());
+        assert_eq!(ctl.base_rtt(), Some(Duration::from_millis(1)));
+    }
+
+    #[test]
+    fn window_never_leaves_bounds() {
+        let mut ctl = AdaptiveWindow::new(4, 4, 8);
+        for _ in 0..50 {
+            ctl.record(Duration::from_millis(1));
+        }
+        assert_eq!(ctl.window(), 8);
+        for _ in 0..200 {
+            ctl.record(Duration::from_millis(500));
+        }
+        assert_eq!(ctl.window(), 4);
+    }
+
+    #[test]
+    fn every_sample_closes_a_round() {
+        let mut ctl = AdaptiveWindow::new(4, 1, 256);
+        assert_eq!(ctl.record(Duration::from_millis(5)), Some(8));
+        assert_eq!(ctl.record(Duration::from_millis(5)), Some(16));
+    }
+}
@@ -36,7 +36,12 @@
 //! 2. **Bounded pipeline window**: Limits memory usage and prevents overwhelming
 //!    the sender. Configurable via `--pipeline-window` CLI option.
 //!
-//! 3. **Signature generation during wait**: While waiting for responses, we can
+//! 3. **Adaptive window (opt-in)**: With `OC_RSYNC_ADAPTIVE_PIPELINE=1` the
+//!    window is auto-tuned between its bounds from the measured
+//!    request-to-first-byte latency (see [`adaptive`]); the chosen size is
+//!    reported under `--debug=pipeline`. The default window is fixed.
+//!
+//! 4. **Signature generation during wait**: While waiting for responses, we can
 //!    generate signatures for upcoming files, utilizing otherwise idle CPU time.
 //!
 //! # Performance Impact
@@ -53,6 +58,7 @@
 //! The sender doesn't need to know about pipelining - it simply processes
 //! requests as they arrive and sends responses in order.
 
+pub mod adaptive;
 pub mod async_signature;
 pub mod job;
 pub mod messages;
@@ -61,10 +67,22 @@ pub mod receiver;
 pub mod spsc;
 mod state;
 
+pub use adaptive::AdaptiveWindow;
 pub use job::{FileJob, FileList, MAX_RETRY_COUNT, TransferFlags};
 pub use pending::PendingTransfer;
 pub use state::PipelineState;
 
+/// Returns whether this process opted in to adaptive window sizing.
+///
+/// Default-off switch driven by the `OC_RSYNC_ADAPTIVE_PIPELINE=1`
+/// environment variable. The wire is unaffected either way; only the number
+/// of requests kept in flight changes.
+#[must_use]
+pub fn adaptive_opt_in() -> bool {
+    std::env::var_os("OC_RSYNC_ADAPTIVE_PIPELINE")
+        .is_some_and(|value| value == "1" || value == "true")
+}
+
 /// Default pipeline window size.
 ///
 /// 64 concurrent requests provides good latency hiding without
@@ -84,30 +102,68 @@ pub const MAX_PIPELINE_WINDOW: usize = 256;
 /// Configuration for pipelined transfers.
 #[derive(Debug, Clone)]
 pub struct PipelineConfig {
-    /// Number of concurrent requests to keep in flight.
+    /// Number of concurrent requests to keep in flight. With `adaptive` set
+    /// this is the starting window.
     pub window_size: usize,
+    /// Auto-tunes the window from measured request-to-first-byte latency.
+    /// Off by default.
+    pub adaptive: bool,
+    /// Smallest window the adaptive controller may choose.
+    pub min_window: usize,
+    /// Largest window the adaptive controller may choose.
+    pub max_window: usize,
 }
 
 impl Default for PipelineConfig {
     fn default() -> Self {
         Self {
             window_size: DEFAULT_PIPELINE_WINDOW,
+            adaptive: false,
+            min_window: MIN_PIPELINE_WINDOW,
+            max_window: MAX_PIPELINE_WINDOW,
         }
     }
 }
 
 impl PipelineConfig {
     /// Creates a new pipeline configuration with the specified window size.
+    ///
+    /// An explicit size pins the window: auto-tuning is switched off. Chain
+    /// [`with_adaptive(true)`](Self::with_adaptive) to use it as the starting
+    /// window instead.
     #[must_use]
     pub fn with_window_size(mut self, window_size: usize) -> Self {
         self.window_size = window_size.clamp(MIN_PIPELINE_WINDOW, MAX_PIPELINE_WINDOW);
+        self.adaptive = false;
+        self
+    }
+
+    /// Enables or disables RTT-driven window auto-tuning.
+    #[must_use]
+    pub fn with_adaptive(mut self, adaptive: bool) -> Self {
+        self.adaptive = adaptive;
+        self
+    }
+
+    /// Sets the bounds the adaptive controller keeps the window within.
+    ///
+    /// Both ends are clamped to `[MIN_PIPELINE_WINDOW, MAX_PIPELINE_WINDOW]`
+    /// and `max` is raised to `min` when given out of order.
+    #[must_use]
+    pub fn with_window_bounds(mut self, min: usize, max: usize) -> Self {
+        self.min_window = min.clamp(MIN_PIPELINE_WINDOW, MAX_PIPELINE_WINDOW);
+        self.max_window = max.clamp(self.min_window, MAX_PIPELINE_WINDOW);
         self
     }
 
-    /// Creates a synchronous configuration (window size = 1).
+    /// Creates a synchronous configuration (window size = 1, not adaptive).
     #[must_use]
     pub fn synchronous() -> Self {
-        Self { window_size: 1 }
+        Self {
+            window_size: 1,
+            adaptive: false,
+            ..Self::default()
+        }
     }
 }
 
@@ -137,6 +193,26 @@ mod tests {
     fn synchronous_config() {
         let config = PipelineConfig::synchronous();
         assert_eq!(config.window_size, 1);
+        assert!(!config.adaptive);
+    }
+
+    #[test]
+    fn default_config_is_fixed_with_full_adaptive_range() {
+        let config = PipelineConfig::default();
+        assert!(!config.adaptive);
+        assert_eq!(config.min_window, MIN_PIPELINE_WINDOW);
+        assert_eq!(config.max_window, MAX_PIPELINE_WINDOW);
+    }
+
+    #[test]
+    fn with_window_bounds_clamps_and_orders() {
+        let config = PipelineConfig::default().with_window_bounds(16, 4);
+        assert_eq!((config.min_window, config.max_window), (16, 16));
+        let config = PipelineConfig::default().with_window_bounds(0, 10_000);
+        assert_eq!(
+            (config.min_window, config.max_window),
+            (MIN_PIPELINE_WINDOW, MAX_PIPELINE_WINDOW)
+        );
     }
 
     #[test]
@@ -145,6 +221,15 @@ mod tests {
         assert_eq!(config.window_size, 128);
     }
 
+    #[test]
+    fn with_window_size_pins_window() {
+        let config = PipelineConfig::default().with_window_size(32);
+        assert!(!config.adaptive);
+        let config = config.with_adaptive(true);
+        assert!(config.adaptive);
+        assert_eq!(config.window_size, 32);
+    }
+
     #[test]
     fn builder_method_chaining() {
         let config = PipelineConfig::default().with_window_size(100);
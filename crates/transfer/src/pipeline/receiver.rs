@@ -454,15 +454,21 @@ impl PipelinedReceiver {
     ///
     /// Call this after `drain_ready_results` or `drain_all_results` to
     /// retrieve file indices that failed checksum verification since the
-    /// last drain. The caller should send `MSG_REDO` for each index over
-    /// the multiplexed writer to signal the generator.
+    /// last drain.
     ///
     /// Unlike [`Self::take_redo_indices`], this does NOT disable redo mode.
     ///
+    /// The indices must never be sent to the peer as `MSG_REDO`. Upstream's
+    /// receiver sends that message only to its own generator process over the
+    /// local pipe; a sender treats an incoming `MSG_REDO` as an invalid message
+    /// and aborts. Here the generator role lives in the same process, so the
+    /// redo pass consumes these indices directly.
+    ///
     /// # Upstream Reference
     ///
     /// - `receiver.c:1093-1097`: `send_msg_int(MSG_REDO, ndx)` sent immediately
     ///   when a checksum mismatch is detected during phase 1.
+    /// - `io.c:1535-1540`: `MSG_REDO` is accepted only when `am_generator`.
     pub fn drain_new_redo_indices(&mut self) -> Vec<usize> {
         std::mem::take(&mut self.redo_indices)
     }
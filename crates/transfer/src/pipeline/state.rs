@@ -4,8 +4,11 @@
 //! methods for adding new requests and processing responses in order.
 
 use std::collections::VecDeque;
+use std::time::Instant;
 
-use super::{PendingTransfer, PipelineConfig};
+use logging::debug_log;
+
+use super::{AdaptiveWindow, PendingTransfer, PipelineConfig};
 
 /// Manages the state of pipelined file transfer requests.
 ///
@@ -39,6 +42,17 @@ pub struct PipelineState {
     /// Queue of outstanding requests awaiting responses.
     /// Responses must be processed in FIFO order to match NDX delta encoding.
     pending: VecDeque<PendingTransfer>,
+    /// Leading entries of `pending` already flushed to the sender.
+    flushed: usize,
+    /// Flush time of the head request when its response is the round's
+    /// latency probe.
+    rtt_probe: Option<Instant>,
+    /// Flush time of the popped probe request, until its first byte is read.
+    awaiting_first_byte: Option<Instant>,
+    /// Current window size; fixed unless `adaptive` is set.
+    window: usize,
+    /// RTT-driven window controller when `config.adaptive` is set.
+    adaptive: Option<AdaptiveWindow>,
     /// Total number of requests sent (for statistics).
     total_sent: u64,
     /// Total number of responses processed (for statistics).
@@ -49,8 +63,28 @@ impl PipelineState {
     /// Creates a new pipeline state with the given configuration.
     #[must_use]
     pub fn new(config: PipelineConfig) -> Self {
+        let adaptive = config
+            .adaptive
+            .then(|| AdaptiveWindow::new(config.window_size, config.min_window, config.max_window));
+        let window = adaptive
+            .as_ref()
+            .map_or(config.window_size, AdaptiveWindow::window);
+        debug_log!(
+            Pipeline,
+            1,
+            "pipeline window={} adaptive={} bounds={}..={}",
+            window,
+            config.adaptive,
+            config.min_window,
+            config.max_window
+        );
         Self {
-            pending: VecDeque::with_capacity(config.window_size),
+            pending: VecDeque::with_capacity(window),
+            flushed: 0,
+            rtt_probe: None,
+            awaiting_first_byte: None,
+            window,
+            adaptive,
             config,
             total_sent: 0,
             total_processed: 0,
@@ -60,7 +94,7 @@ impl PipelineState {
     /// Returns true if we can send another request without exceeding the window.
     #[must_use]
     pub fn can_send(&self) -> bool {
-        self.pending.len() < self.config.window_size
+        self.pending.len() < self.window
     }
 
     /// Returns the number of currently outstanding requests.
@@ -75,16 +109,25 @@ impl PipelineState {
         self.pending.is_empty()
     }
 
-    /// Returns the configured window size.
+    /// Returns the current window size.
+    ///
+    /// Starts at the configured size and moves within the configured bounds
+    /// when the window is adaptive.
     #[must_use]
     pub fn window_size(&self) -> usize {
-        self.config.window_size
+        self.window
+    }
+
+    /// Returns the configuration this pipeline was created with.
+    #[must_use]
+    pub fn config(&self) -> &PipelineConfig {
+        &self.config
     }
 
     /// Returns the number of available slots in the pipeline window.
     #[must_use]
     pub fn available_slots(&self) -> usize {
-        self.config.window_size.saturating_sub(self.pending.len())
+        self.window.saturating_sub(self.pending.len())
     }
 
     /// Adds a pending transfer to the queue.
@@ -98,21 +141,61 @@ impl PipelineState {
             self.can_send(),
             "pipeline full: {} outstanding, window {}",
             self.pending.len(),
-            self.config.window_size
+            self.window
         );
         self.pending.push_back(transfer);
         self.total_sent += 1;
     }
 
+    /// Records that every queued request has been flushed to the sender.
+    ///
+    /// When nothing flushed earlier is still outstanding, the head request's
+    /// response is the first the sender produces for this batch and nothing
+    /// delays reading it, so it becomes the round's latency probe.
+    pub fn mark_flushed(&mut self) {
+        if self.adaptive.is_some() && self.flushed == 0 && !self.pending.is_empty() {
+            self.rtt_probe = Some(Instant::now());
+        }
+        self.flushed = self.pending.len();
+    }
+
     /// Removes and returns the oldest pending transfer.
     ///
     /// Returns `None` if there are no outstanding requests.
     pub fn pop(&mut self) -> Option<PendingTransfer> {
-        let transfer = self.pending.pop_front();
-        if transfer.is_some() {
-            self.total_processed += 1;
+        let transfer = self.pending.pop_front()?;
+        self.total_processed += 1;
+        self.flushed = self.flushed.saturating_sub(1);
+        self.awaiting_first_byte = self.rtt_probe.take();
+        Some(transfer)
+    }
+
+    /// Records when the first bytes of the last popped response were read.
+    ///
+    /// When that response was the round's latency probe, the time from its
+    /// request's flush to `read_at` is fed to the adaptive controller, which
+    /// may resize the window for subsequent sends. Otherwise this does
+    /// nothing.
+    pub fn record_first_byte(&mut self, read_at: Instant) {
+        let Some(flushed_at) = self.awaiting_first_byte.take() else {
+            return;
+        };
+        if let Some(ctl) = self.adaptive.as_mut() {
+            let latency = read_at.saturating_duration_since(flushed_at);
+            debug_log!(Pipeline, 2, "pipeline first byte rtt={:?}", latency);
+            if let Some(window) = ctl.record(latency) {
+                debug_log!(
+                    Pipeline,
+                    1,
+                    "pipeline window {} -> {} (srtt={:?} base={:?})",
+                    self.window,
+                    window,
+                    ctl.smoothed_rtt().unwrap_or_default(),
+                    ctl.base_rtt().unwrap_or_default()
+                );
+                self.window = window;
+            }
         }
-        transfer
     }
 
     /// Peeks at the oldest pending transfer without removing it.
@@ -143,7 +226,7 @@ impl PipelineState {
     #[must_use]
     pub fn stats(&self) -> PipelineStats {
         PipelineStats {
-            window_size: self.config.window_size,
+            window_size: self.window,
             currently_outstanding: self.pending.len(),
             total_sent: self.total_sent,
             total_processed: self.total_processed,
@@ -154,6 +237,9 @@ impl PipelineState {
     ///
     /// Used for cleanup when an error occurs during transfer.
     pub fn drain(&mut self) -> impl Iterator<Item = PendingTransfer> + '_ {
+        self.flushed = 0;
+        self.rtt_probe = None;
+        self.awaiting_first_byte = None;
         self.pending.drain(..)
     }
 }
@@ -161,7 +247,7 @@ impl PipelineState {
 /// Statistics about pipeline operation.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct PipelineStats {
-    /// Configured window size.
+    /// Current window size.
     pub window_size: usize,
     /// Number of currently outstanding requests.
     pub currently_outstanding: usize,
@@ -643,4 +729,75 @@ mod tests {
         assert_eq!(state.pop().unwrap().ndx(), -100);
         assert_eq!(state.pop().unwrap().ndx(), 0);
     }
+
+    #[test]
+    fn fixed_window_ignores_latency() {
+        let mut state = PipelineState::new(PipelineConfig::default().with_window_size(2));
+        for ndx in 0..10 {
+            state.push(make_transfer(ndx));
+            let _ = state.pop();
+        }
+        assert_eq!(state.window_size(), 2);
+    }
+
+    #[test]
+    fn adaptive_window_grows_within_bounds() {
+        let config = PipelineConfig::default()
+            .with_window_size(2)
+            .with_adaptive(true)
+            .with_window_bounds(2, 8);
+        let mut state = PipelineState::new(config);
+        assert_eq!(state.window_size(), 2);
+
+        // Back-to-back responses with no queueing build-up: the controller
+        // doubles each round until it reaches the upper bound.
+        let mut ndx = 0;
+        for _ in 0..20 {
+            while state.can_send() {
+                state.push(make_transfer(ndx));
+                ndx += 1;
+            }
+            state.mark_flushed();
+            while state.pop().is_some() {
+                state.record_first_byte(Instant::now());
+            }
+        }
+        assert!(state.window_size() > 2);
+        assert!(state.window_size() <= 8);
+        assert_eq!(state.stats().window_size, state.window_size());
+    }
+
+    #[test]
+    fn only_first_response_of_a_flushed_batch_is_timed() {
+        let config = PipelineConfig::default().with_adaptive(true);
+        let mut state = PipelineState::new(config);
+        state.push(make_transfer(0));
+        state.push(make_transfer(1));
+        state.mark_flushed();
+
+        state.pop().unwrap();
+        assert!(state.awaiting_first_byte.is_some());
+        state.record_first_byte(Instant::now());
+        assert!(state.awaiting_first_byte.is_none());
+
+        // A batch flushed while an earlier response is still unread queues
+        // behind it, so it is not probed.
+        state.push(make_transfer(2));
+        state.mark_flushed();
+        state.pop().unwrap();
+        assert!(state.awaiting_first_byte.is_none());
+        state.pop().unwrap();
+        assert!(state.awaiting_first_byte.is_none());
+    }
+
+    #[test]
+    fn fixed_window_never_probes() {
+        let mut state = PipelineState::new(PipelineConfig::default());
+        state.push(make_transfer(0));
+        state.mark_flushed();
+        assert!(state.rtt_probe.is_none());
+        state.pop().unwrap();
+        state.record_first_byte(Instant::now());
+        assert_eq!(state.window_size(), super::super::DEFAULT_PIPELINE_WINDOW);
+    }
 }
@@ -1072,3 +1072,86 @@ mod success_dispatch_tests {
         assert_eq!(reader.take_success_indices(), vec![1, 5, 9]);
     }
 }
+
+/// Tests for the severity routing of forwarded log frames: `MSG_INFO` and
+/// `MSG_CLIENT` reach stdout, every warning/error category reaches stderr, and
+/// only `MSG_ERROR_XFER` counts towards `got_xfer_error` (exit 23).
+#[cfg(test)]
+mod log_frame_routing_tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct RecordingSink {
+        info: Vec<String>,
+        error: Vec<String>,
+    }
+
+    impl MuxSink for RecordingSink {
+        fn info(&mut self, msg: &str) {
+            self.info.push(msg.to_owned());
+        }
+        fn error(&mut self, msg: &str) {
+            self.error.push(msg.to_owned());
+        }
+    }
+
+    fn dispatch(reader: &mut MultiplexReader<io::Empty>, code: protocol::MessageCode, text: &str) {
+        let mut sink = RecordingSink::default();
+        reader.buffer = text.as_bytes().to_vec();
+        assert!(!reader.dispatch_message_with(code, &mut sink));
+        let to_stdout = matches!(
+            code,
+            protocol::MessageCode::Info | protocol::MessageCode::Client
+        );
+        let (routed, other) = if to_stdout {
+            (sink.info, sink.error)
+        } else {
+            (sink.error, sink.info)
+        };
+        assert_eq!(
+            routed,
+            vec![text.to_owned()],
+            "{code:?} went to the wrong stream"
+        );
+        assert!(other.is_empty(), "{code:?} leaked into the other stream");
+    }
+
+    #[test]
+    fn frames_reach_the_stream_matching_their_severity() {
+        let mut reader = MultiplexReader::new(io::empty());
+        dispatch(
+            &mut reader,
+            protocol::MessageCode::Info,
+            "sending incremental file list\n",
+        );
+        dispatch(&mut reader, protocol::MessageCode::Client, "client note\n");
+        dispatch(
+            &mut reader,
+            protocol::MessageCode::Warning,
+            "file has vanished: \"a\"\n",
+        );
+        dispatch(
+            &mut reader,
+            protocol::MessageCode::Error,
+            "rsync: [sender] failed\n",
+        );
+        assert_eq!(reader.xfer_error_count(), 0);
+    }
+
+    #[test]
+    fn only_error_xfer_sets_got_xfer_error() {
+        // upstream: log.c:311 - FERROR_XFER, not FERROR, sets got_xfer_error.
+        let mut reader = MultiplexReader::new(io::empty());
+        dispatch(
+            &mut reader,
+            protocol::MessageCode::ErrorXfer,
+            "rsync: send_files failed\n",
+        );
+        dispatch(
+            &mut reader,
+            protocol::MessageCode::ErrorXfer,
+            "rsync: read errors\n",
+        );
+        assert_eq!(reader.xfer_error_count(), 2);
+    }
+}
@@ -5,7 +5,7 @@
 //! the file signature used by the sender to compute deltas.
 
 use std::fs;
-use std::num::NonZeroU8;
+use std::num::{NonZeroU8, NonZeroU32};
 use std::path::PathBuf;
 use std::sync::OnceLock;
 
@@ -113,6 +113,9 @@ pub struct BasisFileConfig<'a> {
     /// [`protocol::effective_s2length`]). `None` (local copy / no negotiation)
     /// leaves the strong sum at full length, byte-identical to upstream.
     pub compat_flags: Option<protocol::CompatibilityFlags>,
+    /// Fixed block length from `--block-size`, or `None` for the
+    /// square-root heuristic. upstream: generator.c:sum_sizes_sqroot().
+    pub block_length: Option<NonZeroU32>,
 }
 
 /// Configuration for generating a signature from a basis file.
@@ -130,6 +133,8 @@ struct SignatureGenerationConfig {
     checksum_algorithm: engine::signature::SignatureAlgorithm,
     /// Mutually negotiated compatibility flags (see [`BasisFileConfig::compat_flags`]).
     compat_flags: Option<protocol::CompatibilityFlags>,
+    /// Fixed block length (see [`BasisFileConfig::block_length`]).
+    block_length: Option<NonZeroU32>,
 }
 
 impl SignatureGenerationConfig {
@@ -140,6 +145,7 @@ impl SignatureGenerationConfig {
             checksum_length: config.checksum_length,
             checksum_algorithm: config.checksum_algorithm,
             compat_flags: config.compat_flags,
+            block_length: config.block_length,
         }
     }
 }
@@ -212,8 +218,8 @@ fn try_open_file(path: &std::path::Path) -> Option<(fs::File, u64, PathBuf)> {
     Some((file, size, path.to_path_buf()))
 }
 
-/// A fuzzy basis the generator selected, ready to be signed and advertised.
-struct FuzzyBasis {
+/// A basis the generator selected, ready to be signed and advertised.
+struct LocatedBasis {
     /// The opened basis file, its size, and path (for signature generation).
     file: fs::File,
     size: u64,
@@ -222,7 +228,7 @@ struct FuzzyBasis {
     /// basis, where `i` names the dest dir (0) or reference dir `k` (`k + 1`).
     /// upstream: generator.c:861,903.
     fnamecmp_type: protocol::FnameCmpType,
-    /// The `ITEM_XNAME_FOLLOWS` basename, always present for a fuzzy basis
+    /// The `ITEM_XNAME_FOLLOWS` basename, present only for a fuzzy basis
     /// (upstream: generator.c:1948 `fuzzy_file->basename`).
     xname: Option<Vec<u8>>,
 }
@@ -257,7 +263,7 @@ fn try_fuzzy_match(
     target_mtime: i64,
     fuzzy_level: u8,
     reference_directories: &[ReferenceDirectory],
-) -> Option<FuzzyBasis> {
+) -> Option<LocatedBasis> {
     let target_name = relative_path.file_name()?;
 
     // Build the search directory for reference dirs: join each reference
@@ -282,10 +288,17 @@ fn try_fuzzy_match(
         .filter(|p| p.is_dir())
         .cloned()
         .collect();
+    // upstream: generator.c recv_generator() - fuzzy_dirlist[0] lists the
+    // file's own destination directory (`dn`), not the transfer root.
+    let search_dir = dest_dir.join(parent_dir);
 
     let fuzzy_matcher = FuzzyMatcher::with_level(fuzzy_level).with_fuzzy_basis_dirs(basis_dirs);
-    let fuzzy_match =
-        fuzzy_matcher.find_fuzzy_basis(target_name, dest_dir, target_size, Some(target_mtime))?;
+    let fuzzy_match = fuzzy_matcher.find_fuzzy_basis(
+        target_name,
+        &search_dir,
+        target_size,
+        Some(target_mtime),
+    )?;
     // upstream: generator.c:1788 - announce the selected fuzzy basis at
     // FUZZY,1 the moment the matcher returns a candidate, before we attempt
     // to open it.
@@ -299,13 +312,13 @@ fn try_fuzzy_match(
     // matching FNAMECMP_FUZZY + i tag with the basis basename as the xname.
     // upstream: generator.c:843/868 iterate dirlist_array[0] (dest dir, index 0)
     // then basis_dir[i-1] (reference dir k -> index k + 1).
-    let fnamecmp_type = fuzzy_index(&path, dest_dir, &ref_search_dirs)?;
+    let fnamecmp_type = fuzzy_index(&path, &search_dir, &ref_search_dirs)?;
     let basename = path
         .file_name()
         .map(basename_wire_bytes)
         .unwrap_or_default();
 
-    Some(FuzzyBasis {
+    Some(LocatedBasis {
         file,
         size,
         path,
@@ -378,58 +391,30 @@ fn generate_basis_signature(
     xname: Option<Vec<u8>>,
     config: SignatureGenerationConfig,
 ) -> BasisFileResult {
-    // Cap the per-file strong-sum length by the negotiated transfer checksum's
-    // digest width. `sum_sizes_sqroot()` clamps s2length to
-    // `max_s2length = MIN(SUM_LENGTH, xfer_sum_len)`, so it never exceeds the
-    // negotiated digest length. `calculate_signature_layout` applies both halves
-    // once the digest width is threaded in: the `SUM_LENGTH` (16) cap plus the
-    // `xfer_sum_len` cap from the negotiated algorithm's digest width. For the
-    // default 16-byte digests (MD5, MD4, XXH3-128) this is a no-op and the
-    // SumHead stays byte-identical to upstream; a short digest (XXH64 / XXH3-64 =
-    // 8 bytes) bounds s2length so we never write a zero-padded strong sum wider
-    // than the checksum the sender expects. This runs before the private
-    // CAP_CONSECUTIVE_MATCH halving below, mirroring upstream's order (cap in
-    // sum_sizes_sqroot, then any extension).
-    // upstream: generator.c:705 sum_sizes_sqroot() `max_s2length`,
-    // checksum.c:214 csum_len_for_type().
-    let digest_len =
-        NonZeroU8::new(config.checksum_algorithm.digest_len().min(u8::MAX as usize) as u8)
-            .expect("negotiated digest length is at least one byte");
-    let params =
-        SignatureLayoutParams::new(basis_size, None, config.protocol, config.checksum_length)
-            .with_transfer_digest_length(digest_len);
-
-    let layout = match calculate_signature_layout(params) {
-        Ok(layout) => layout,
-        Err(_) => return BasisFileResult::EMPTY,
+    let Some(layout) = basis_signature_layout(basis_size, config) else {
+        return BasisFileResult::EMPTY;
     };
 
-    // Iron invariant choke-point: the per-block strong-sum length is shrunk
-    // here, and ONLY here, and ONLY when the mutually negotiated compat flags
-    // carry the private CAP_CONSECUTIVE_MATCH bit. That bit can only survive the
-    // negotiation AND when both peers are oc and both opted in, in which case
-    // the sender applies seq_matches=2 gating to compensate for the shorter
-    // checksum. Against any upstream peer, or without the opt-in, the mutual bit
-    // is absent and `effective_s2length` returns the full length verbatim,
-    // yielding a SumHead byte-identical to upstream.
-    let layout = {
-        let base_len = layout.strong_sum_length().get();
-        let negotiated = config
-            .compat_flags
-            .unwrap_or(protocol::CompatibilityFlags::EMPTY);
-        let eff_len = protocol::effective_s2length(negotiated, base_len);
-        if eff_len == base_len {
-            layout
-        } else {
-            let eff_nz = NonZeroU8::new(eff_len).expect("effective_s2length floors at 1");
-            SignatureLayout::from_raw_parts(
-                layout.block_length(),
-                layout.remainder(),
-                layout.block_count(),
-                eff_nz,
-            )
-        }
-    };
+    // Under `run_server_async` the session thread holds the runtime handle:
+    // read the basis through tokio and hash it on the blocking pool rather
+    // than tying this thread up for the whole file.
+    #[cfg(feature = "tokio")]
+    if let Some(handle) = async_signature_runtime() {
+        let signature = handle.block_on(signature::async_gen::generate_file_signature_async(
+            tokio::fs::File::from_std(basis_file),
+            layout,
+            config.checksum_algorithm,
+        ));
+        return match signature {
+            Ok(sig) => BasisFileResult {
+                signature: Some(sig),
+                basis_path: Some(basis_path),
+                fnamecmp_type,
+                xname,
+            },
+            Err(_) => BasisFileResult::EMPTY,
+        };
+    }
 
     let parallel = parallel_checksum_enabled();
 
@@ -496,6 +481,104 @@ fn generate_basis_signature(
     }
 }
 
+#[cfg(feature = "tokio")]
+thread_local! {
+    /// Runtime that basis signatures on this thread are generated on, set by
+    /// [`with_async_signature_runtime`].
+    static ASYNC_SIGNATURE_RUNTIME: std::cell::RefCell<Option<tokio::runtime::Handle>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// Runs `body` with basis signatures on the current thread generated by
+/// [`signature::async_gen::generate_file_signature_async`] on `handle`.
+///
+/// Only the calling thread is affected: signatures computed on rayon workers
+/// inside `body` keep the synchronous path. The calling thread must not be a
+/// runtime worker, since each signature blocks on `handle`.
+#[cfg(feature = "tokio")]
+pub(crate) fn with_async_signature_runtime<T>(
+    handle: tokio::runtime::Handle,
+    body: impl FnOnce() -> T,
+) -> T {
+    struct Restore(Option<tokio::runtime::Handle>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            ASYNC_SIGNATURE_RUNTIME.with(|runtime| *runtime.borrow_mut() = previous);
+        }
+    }
+
+    let _restore =
+        Restore(ASYNC_SIGNATURE_RUNTIME.with(|runtime| runtime.borrow_mut().replace(handle)));
+    body()
+}
+
+#[cfg(feature = "tokio")]
+fn async_signature_runtime() -> Option<tokio::runtime::Handle> {
+    ASYNC_SIGNATURE_RUNTIME.with(|runtime| runtime.borrow().clone())
+}
+
+/// Computes the signature layout for a basis of `basis_size` bytes.
+///
+/// Returns `None` when the layout cannot be computed, in which case the basis
+/// is treated as absent and the file is sent whole.
+fn basis_signature_layout(
+    basis_size: u64,
+    config: SignatureGenerationConfig,
+) -> Option<SignatureLayout> {
+    // Cap the per-file strong-sum length by the negotiated transfer checksum's
+    // digest width. `sum_sizes_sqroot()` clamps s2length to
+    // `max_s2length = MIN(SUM_LENGTH, xfer_sum_len)`, so it never exceeds the
+    // negotiated digest length. `calculate_signature_layout` applies both halves
+    // once the digest width is threaded in: the `SUM_LENGTH` (16) cap plus the
+    // `xfer_sum_len` cap from the negotiated algorithm's digest width. For the
+    // default 16-byte digests (MD5, MD4, XXH3-128) this is a no-op and the
+    // SumHead stays byte-identical to upstream; a short digest (XXH64 / XXH3-64 =
+    // 8 bytes) bounds s2length so we never write a zero-padded strong sum wider
+    // than the checksum the sender expects. This runs before the private
+    // CAP_CONSECUTIVE_MATCH halving below, mirroring upstream's order (cap in
+    // sum_sizes_sqroot, then any extension).
+    // upstream: generator.c:705 sum_sizes_sqroot() `max_s2length`,
+    // checksum.c:214 csum_len_for_type().
+    let digest_len =
+        NonZeroU8::new(config.checksum_algorithm.digest_len().min(u8::MAX as usize) as u8)
+            .expect("negotiated digest length is at least one byte");
+    let params = SignatureLayoutParams::new(
+        basis_size,
+        config.block_length,
+        config.protocol,
+        config.checksum_length,
+    )
+    .with_transfer_digest_length(digest_len);
+
+    let layout = calculate_signature_layout(params).ok()?;
+
+    // Iron invariant choke-point: the per-block strong-sum length is shrunk
+    // here, and ONLY here, and ONLY when the mutually negotiated compat flags
+    // carry the private CAP_CONSECUTIVE_MATCH bit. That bit can only survive the
+    // negotiation AND when both peers are oc and both opted in, in which case
+    // the sender applies seq_matches=2 gating to compensate for the shorter
+    // checksum. Against any upstream peer, or without the opt-in, the mutual bit
+    // is absent and `effective_s2length` returns the full length verbatim,
+    // yielding a SumHead byte-identical to upstream.
+    let base_len = layout.strong_sum_length().get();
+    let negotiated = config
+        .compat_flags
+        .unwrap_or(protocol::CompatibilityFlags::EMPTY);
+    let eff_len = protocol::effective_s2length(negotiated, base_len);
+    if eff_len == base_len {
+        return Some(layout);
+    }
+    let eff_nz = NonZeroU8::new(eff_len).expect("effective_s2length floors at 1");
+    Some(SignatureLayout::from_raw_parts(
+        layout.block_length(),
+        layout.remainder(),
+        layout.block_count(),
+        eff_nz,
+    ))
+}
+
 /// Minimum basis-file size at which the receiver memory-maps the basis for
 /// signature hashing instead of reading it through a raw `File`.
 ///
@@ -619,26 +702,83 @@ fn compute_basis_signature<R: std::io::Read>(
 /// - `generator.c:1580` - Fuzzy matching via `find_fuzzy_basis()`
 /// - `generator.c:1400` - Reference directory checking
 pub fn find_basis_file_with_config(config: &BasisFileConfig<'_>) -> BasisFileResult {
+    match locate_basis_file(config) {
+        Some(basis) => generate_basis_signature(
+            basis.file,
+            basis.size,
+            basis.path,
+            basis.fnamecmp_type,
+            basis.xname,
+            SignatureGenerationConfig::from_basis_config(config),
+        ),
+        None => BasisFileResult::EMPTY,
+    }
+}
+
+/// A basis file found by [`find_basis_file_for_stream`], opened but not yet
+/// hashed.
+///
+/// The receiver writes its signature straight to the wire with
+/// [`write_signature_stream`](super::write_signature_stream) instead of
+/// holding a [`FileSignature`] for the whole basis in memory.
+#[derive(Debug)]
+pub struct BasisFileStream {
+    /// The opened basis file, positioned at its start.
+    pub file: fs::File,
+    /// The layout the `sum_head` announces and the streamed blocks follow.
+    pub layout: SignatureLayout,
+    /// Path to the basis file, reopened when block references are applied.
+    pub basis_path: PathBuf,
+    /// See [`BasisFileResult::fnamecmp_type`].
+    pub fnamecmp_type: protocol::FnameCmpType,
+    /// See [`BasisFileResult::xname`].
+    pub xname: Option<Vec<u8>>,
+}
+
+/// Finds a basis file exactly as [`find_basis_file_with_config`] does but
+/// leaves hashing to the caller.
+///
+/// Returns `None` when no basis is used, so the file is requested whole.
+///
+/// # Upstream Reference
+///
+/// - `generator.c:generate_and_send_sums()` - the generator hashes each block
+///   as it writes it rather than building the signature first
+pub fn find_basis_file_for_stream(config: &BasisFileConfig<'_>) -> Option<BasisFileStream> {
+    let basis = locate_basis_file(config)?;
+    let layout = basis_signature_layout(
+        basis.size,
+        SignatureGenerationConfig::from_basis_config(config),
+    )?;
+    Some(BasisFileStream {
+        file: basis.file,
+        layout,
+        basis_path: basis.path,
+        fnamecmp_type: basis.fnamecmp_type,
+        xname: basis.xname,
+    })
+}
+
+/// Runs the basis search shared by [`find_basis_file_with_config`] and
+/// [`find_basis_file_for_stream`].
+fn locate_basis_file(config: &BasisFileConfig<'_>) -> Option<LocatedBasis> {
     // Upstream `generator.c:1962`: when `whole_file` is set, no basis file
     // is used - the entire file is sent as literals.
     if config.whole_file {
-        return BasisFileResult::EMPTY;
+        return None;
     }
 
-    let sig_config = SignatureGenerationConfig::from_basis_config(config);
-
     // Try sources in priority order: exact match -> reference dirs -> fuzzy ->
     // partial-dir. The exact destination basis is reported to the sender as
     // FNAMECMP_FNAME (no basis-type byte).
     if let Some((file, size, path)) = try_open_file(config.file_path) {
-        return generate_basis_signature(
+        return Some(LocatedBasis {
             file,
             size,
             path,
-            protocol::FnameCmpType::Fname,
-            None,
-            sig_config,
-        );
+            fnamecmp_type: protocol::FnameCmpType::Fname,
+            xname: None,
+        });
     }
 
     // upstream: generator.c:1054 - when the destination is absent, a basis found
@@ -650,14 +790,13 @@ pub fn find_basis_file_with_config(config: &BasisFileConfig<'_>) -> BasisFileRes
     if let Some((file, size, path, index)) =
         try_reference_directories(config.relative_path, config.reference_directories)
     {
-        return generate_basis_signature(
+        return Some(LocatedBasis {
             file,
             size,
             path,
-            basis_dir_fnamecmp_type(index),
-            None,
-            sig_config,
-        );
+            fnamecmp_type: basis_dir_fnamecmp_type(index),
+            xname: None,
+        });
     }
 
     // upstream: generator.c:861,903,1945-1948 - a fuzzy match is tagged
@@ -674,35 +813,23 @@ pub fn find_basis_file_with_config(config: &BasisFileConfig<'_>) -> BasisFileRes
             config.reference_directories,
         )
     {
-        return generate_basis_signature(
-            fuzzy.file,
-            fuzzy.size,
-            fuzzy.path,
-            fuzzy.fnamecmp_type,
-            fuzzy.xname,
-            sig_config,
-        );
+        return Some(fuzzy);
     }
 
     // upstream: generator.c:1759-1765 - when the destination stat fails and
     // --partial-dir is set, fall back to the same-named regular file inside
     // the partial directory as the delta basis and tag it FNAMECMP_PARTIAL_DIR
     // (prepare_to_open at generator.c:1850-1855).
-    if let Some(dir) = config.partial_dir
-        && let Some(partial) = crate::temp_guard::partial_dir_fname(config.file_path, dir)
-        && let Some((file, size, path)) = try_open_file(&partial)
-    {
-        return generate_basis_signature(
-            file,
-            size,
-            path,
-            protocol::FnameCmpType::PartialDir,
-            None,
-            sig_config,
-        );
-    }
-
-    BasisFileResult::EMPTY
+    let dir = config.partial_dir?;
+    let partial = crate::temp_guard::partial_dir_fname(config.file_path, dir)?;
+    let (file, size, path) = try_open_file(&partial)?;
+    Some(LocatedBasis {
+        file,
+        size,
+        path,
+        fnamecmp_type: protocol::FnameCmpType::PartialDir,
+        xname: None,
+    })
 }
 
 #[cfg(test)]
@@ -995,6 +1122,7 @@ mod tests {
             checksum_length: NonZeroU8::new(16).unwrap(),
             checksum_algorithm: SignatureAlgorithm::Md4,
             compat_flags: None,
+            block_length: None,
         };
 
         // Production path (mmap default engaged because size >= threshold).
@@ -1071,6 +1199,7 @@ mod tests {
             checksum_algorithm: SignatureAlgorithm::Md4,
             whole_file: false,
             compat_flags: None,
+            block_length: None,
         };
 
         let result = find_basis_file_with_config(&config);
@@ -1090,6 +1219,158 @@ mod tests {
         );
     }
 
+    /// `--block-size` replaces the square-root heuristic for the basis
+    /// signature (upstream: generator.c:sum_sizes_sqroot()).
+    #[test]
+    fn forced_block_length_overrides_heuristic() {
+        use std::io::Write;
+
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let dest_dir = tmp.path();
+        let data: Vec<u8> = (0..8192u32).map(|i| (i % 251) as u8).collect();
+        let dest_file = dest_dir.join("file.bin");
+        {
+            let mut f = fs::File::create(&dest_file).expect("create basis");
+            f.write_all(&data).expect("write basis");
+            f.flush().expect("flush");
+        }
+
+        let config = BasisFileConfig {
+            file_path: &dest_file,
+            dest_dir,
+            relative_path: std::path::Path::new("file.bin"),
+            target_size: data.len() as u64,
+            target_mtime: 0,
+            fuzzy_level: 0,
+            reference_directories: &[],
+            partial_dir: None,
+            protocol: ProtocolVersion::NEWEST,
+            checksum_length: NonZeroU8::new(16).unwrap(),
+            checksum_algorithm: SignatureAlgorithm::Md4,
+            whole_file: false,
+            compat_flags: None,
+            block_length: NonZeroU32::new(1024),
+        };
+
+        let result = find_basis_file_with_config(&config);
+        let signature = result.signature.expect("basis yields a signature");
+        assert_eq!(signature.layout().block_length().get(), 1024);
+        assert_eq!(signature.layout().block_count(), 8);
+    }
+
+    /// The streaming search picks the same basis and layout as the hashing one
+    /// and streams the same blocks onto the wire.
+    #[test]
+    fn stream_search_matches_materialised_signature() {
+        use std::io::Write;
+
+        use crate::receiver::wire::{write_signature_blocks, write_signature_stream};
+
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let dest_dir = tmp.path();
+        let data: Vec<u8> = (0..20_000u32).map(|i| (i * 7 % 251) as u8).collect();
+        let dest_file = dest_dir.join("file.bin");
+        {
+            let mut f = fs::File::create(&dest_file).expect("create basis");
+            f.write_all(&data).expect("write basis");
+            f.flush().expect("flush");
+        }
+
+        let config = BasisFileConfig {
+            file_path: &dest_file,
+            dest_dir,
+            relative_path: std::path::Path::new("file.bin"),
+            target_size: data.len() as u64,
+            target_mtime: 0,
+            fuzzy_level: 0,
+            reference_directories: &[],
+            partial_dir: None,
+            protocol: ProtocolVersion::NEWEST,
+            checksum_length: NonZeroU8::new(16).unwrap(),
+            checksum_algorithm: SignatureAlgorithm::Md4,
+            whole_file: false,
+            compat_flags: None,
+            block_length: None,
+        };
+
+        let signature = find_basis_file_with_config(&config)
+            .signature
+            .expect("basis yields a signature");
+        let stream = find_basis_file_for_stream(&config).expect("basis found");
+        assert_eq!(stream.layout, signature.layout());
+        assert_eq!(stream.basis_path, dest_file);
+        assert_eq!(stream.fnamecmp_type, protocol::FnameCmpType::Fname);
+
+        let s2length = u32::from(stream.layout.strong_sum_length().get());
+        let mut expected = Vec::new();
+        write_signature_blocks(&mut expected, &signature, s2length).expect("write blocks");
+        let mut streamed = Vec::new();
+        write_signature_stream(
+            &mut streamed,
+            stream.file,
+            stream.layout,
+            SignatureAlgorithm::Md4,
+            s2length,
+        )
+        .expect("stream blocks");
+        assert_eq!(streamed, expected);
+
+        let whole = BasisFileConfig {
+            whole_file: true,
+            ..config
+        };
+        assert!(find_basis_file_for_stream(&whole).is_none());
+    }
+
+    /// With a runtime installed, the basis is hashed by the async generator
+    /// and yields the signature the synchronous path computes.
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn async_runtime_signature_matches_blocking_signature() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let dest_dir = tmp.path().to_path_buf();
+        let data: Vec<u8> = (0..300_000u32).map(|i| (i * 13 % 251) as u8).collect();
+        fs::write(dest_dir.join("file.bin"), &data).expect("write basis");
+
+        let basis_signature = move || {
+            let file_path = dest_dir.join("file.bin");
+            let config = BasisFileConfig {
+                file_path: &file_path,
+                dest_dir: &dest_dir,
+                relative_path: std::path::Path::new("file.bin"),
+                target_size: data.len() as u64,
+                target_mtime: 0,
+                fuzzy_level: 0,
+                reference_directories: &[],
+                partial_dir: None,
+                protocol: ProtocolVersion::NEWEST,
+                checksum_length: NonZeroU8::new(16).unwrap(),
+                checksum_algorithm: SignatureAlgorithm::Md4,
+                whole_file: false,
+                compat_flags: None,
+                block_length: None,
+            };
+            find_basis_file_with_config(&config).signature
+        };
+
+        let expected = basis_signature.clone()().expect("blocking signature");
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .expect("runtime");
+        let handle = runtime.handle().clone();
+        let via_async = runtime
+            .block_on(tokio::task::spawn_blocking(move || {
+                with_async_signature_runtime(handle, basis_signature)
+            }))
+            .expect("blocking task")
+            .expect("async signature");
+
+        assert_eq!(via_async, expected);
+        assert!(async_signature_runtime().is_none());
+    }
+
     /// The partial-dir fallback must not fire when the destination file itself
     /// exists: the ordinary destination basis wins and is tagged FNAMECMP_FNAME
     /// (no basis-type byte on the wire), preserving the pre-existing encoding.
@@ -1124,6 +1405,7 @@ mod tests {
             checksum_algorithm: SignatureAlgorithm::Md4,
             whole_file: false,
             compat_flags: None,
+            block_length: None,
         };
 
         let result = find_basis_file_with_config(&config);
@@ -1166,6 +1448,7 @@ mod tests {
             checksum_algorithm: SignatureAlgorithm::Md4,
             whole_file: false,
             compat_flags: None,
+            block_length: None,
         };
 
         let result = find_basis_file_with_config(&config);
@@ -1190,6 +1473,45 @@ mod tests {
         );
     }
 
+    /// A file in a subdirectory draws its fuzzy basis from that subdirectory,
+    /// never from a same-suffix sibling at the transfer root.
+    /// upstream: generator.c recv_generator() - `fuzzy_dirlist[0]` lists `dn`.
+    #[test]
+    fn fuzzy_basis_searches_file_parent_directory() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let dest_dir = tmp.path();
+        let sub = dest_dir.join("sub");
+        fs::create_dir(&sub).expect("mkdir sub");
+        let data: Vec<u8> = (0..8192u32).map(|i| (i % 251) as u8).collect();
+        fs::write(sub.join("data2.txt"), &data).expect("write nested candidate");
+        fs::write(dest_dir.join("data3.txt"), b"decoy").expect("write root decoy");
+
+        let dest_file = sub.join("data.txt");
+        let config = BasisFileConfig {
+            file_path: &dest_file,
+            dest_dir,
+            relative_path: std::path::Path::new("sub/data.txt"),
+            target_size: data.len() as u64,
+            target_mtime: 0,
+            fuzzy_level: 1,
+            reference_directories: &[],
+            partial_dir: None,
+            protocol: ProtocolVersion::NEWEST,
+            checksum_length: NonZeroU8::new(16).unwrap(),
+            checksum_algorithm: SignatureAlgorithm::Md4,
+            whole_file: false,
+            compat_flags: None,
+            block_length: None,
+        };
+
+        let result = find_basis_file_with_config(&config);
+        assert_eq!(
+            result.basis_path.as_deref(),
+            Some(sub.join("data2.txt").as_path())
+        );
+        assert_eq!(result.fnamecmp_type, protocol::FnameCmpType::Fuzzy(0));
+    }
+
     /// #204: with the destination absent, a basis found in reference dir `j`
     /// (`--compare-dest`/`--copy-dest`/`--link-dest`) whose content differs is
     /// selected as a delta basis and tagged FNAMECMP_BASIS_DIR_LOW + j
@@ -1242,6 +1564,7 @@ mod tests {
             checksum_algorithm: SignatureAlgorithm::Md4,
             whole_file: false,
             compat_flags: None,
+            block_length: None,
         };
 
         let result = find_basis_file_with_config(&config);
@@ -1313,6 +1636,7 @@ mod tests {
             checksum_algorithm: SignatureAlgorithm::Md4,
             whole_file: false,
             compat_flags: None,
+            block_length: None,
         };
 
         let result = find_basis_file_with_config(&config);
@@ -1373,6 +1697,7 @@ mod tests {
             checksum_algorithm: SignatureAlgorithm::Md4,
             whole_file: false,
             compat_flags: None,
+            block_length: None,
         };
 
         let result = find_basis_file_with_config(&config);
@@ -1433,6 +1758,7 @@ mod tests {
             // Not --whole-file: the redo path must still search for a basis.
             whole_file: false,
             compat_flags: None,
+            block_length: None,
         };
 
         let strong_len = |result: &BasisFileResult| -> u8 {
@@ -1508,6 +1834,7 @@ mod tests {
             checksum_length: NonZeroU8::new(16).unwrap(),
             checksum_algorithm: SignatureAlgorithm::Md4,
             compat_flags: None,
+            block_length: None,
         };
 
         // No flags -> full length (byte-identical to upstream).
@@ -1571,6 +1898,7 @@ mod tests {
                 checksum_length: NonZeroU8::new(phase_len).unwrap(),
                 checksum_algorithm: algo,
                 compat_flags: None,
+                block_length: None,
             };
             let params = SignatureLayoutParams::new(
                 file_size,
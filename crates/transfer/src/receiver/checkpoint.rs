@@ -0,0 +1,437 @@
+//! Transfer checkpoints for resuming an interrupted run (`--checkpoint` /
+//! `--resume`).
+//!
+//! oc-rsync extension with no upstream equivalent. A very large transfer that
+//! dies part-way leaves its committed files in place, yet the next run still
+//! re-negotiates every one of them: under `--checksum` or `--ignore-times`
+//! that means re-reading or re-sending the whole destination, and `--partial`
+//! only rescues the single file that was in flight. With `--checkpoint=FILE`
+//! the receiver periodically records every file whose commit was confirmed,
+//! keyed by its path below the destination root, together with the sender's
+//! size and mtime and the destination mtime the commit left behind. The file
+//! also carries the last committed file index and running totals. A later run
+//! given `--resume=FILE` treats a file as up to date when both the sender's
+//! entry and the destination still match their record, without running the
+//! quick-check.
+//!
+//! Entries a resumed run relies on are carried into the checkpoint it writes,
+//! so passing the same file to both options keeps it current across repeated
+//! interruptions. A checkpoint written for another destination root is
+//! ignored, and a damaged or foreign file is treated as empty.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use protocol::flist::FileEntry;
+
+/// Leading bytes of every checkpoint file.
+const MAGIC: &[u8; 4] = b"OCCK";
+
+/// On-disk format version.
+const VERSION: u32 = 1;
+
+/// Minimum time between two periodic checkpoint writes.
+const SAVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// What a confirmed commit looked like on both sides.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+struct CommitRecord {
+    size: u64,
+    source_mtime: i64,
+    source_mtime_nsec: u32,
+    dest_mtime: i64,
+    dest_mtime_nsec: u32,
+}
+
+/// Progress summary stored in the checkpoint header.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub(in crate::receiver) struct CheckpointProgress {
+    /// Wire NDX of the most recently committed file, if any.
+    pub(in crate::receiver) last_ndx: Option<i32>,
+    /// Files recorded as committed.
+    pub(in crate::receiver) files: u64,
+    /// Sum of the recorded files' sizes.
+    pub(in crate::receiver) bytes: u64,
+}
+
+#[derive(Debug)]
+struct CheckpointState {
+    /// Records loaded from `--resume`.
+    previous: HashMap<Vec<u8>, CommitRecord>,
+    /// Records confirmed or reused during this run.
+    current: HashMap<Vec<u8>, CommitRecord>,
+    last_ndx: Option<i32>,
+    last_save: Instant,
+}
+
+/// Committed-file log backing `--checkpoint` and `--resume`.
+///
+/// Lookups and records take `&self` because both the quick-check and the
+/// commit confirmation run behind shared receiver borrows; the maps sit
+/// behind a mutex.
+#[derive(Debug)]
+pub(in crate::receiver) struct TransferCheckpoint {
+    /// Where `--checkpoint` writes; `None` for a resume-only run.
+    file: Option<PathBuf>,
+    root: PathBuf,
+    canonical_root: Vec<u8>,
+    resumed: CheckpointProgress,
+    state: Mutex<CheckpointState>,
+}
+
+impl TransferCheckpoint {
+    /// Opens the checkpoint for `dest_root`, loading `resume` when given.
+    ///
+    /// A missing, unreadable or foreign resume file yields an empty record
+    /// set; the checkpoint only ever saves work, so it never fails the
+    /// transfer.
+    pub(in crate::receiver) fn open(
+        checkpoint: Option<&Path>,
+        resume: Option<&Path>,
+        dest_root: &Path,
+    ) -> Self {
+        let canonical = fs::canonicalize(dest_root).unwrap_or_else(|_| dest_root.to_path_buf());
+        let canonical_root = canonical.as_os_str().as_encoded_bytes().to_vec();
+        let (resumed, previous) = resume
+            .and_then(|path| fs::read(path).ok())
+            .and_then(|bytes| decode(&bytes, &canonical_root))
+            .unwrap_or_default();
+        Self {
+            file: checkpoint.map(Path::to_path_buf),
+            root: dest_root.to_path_buf(),
+            canonical_root,
+            resumed,
+            state: Mutex::new(CheckpointState {
+                previous,
+                current: HashMap::new(),
+                last_ndx: resumed.last_ndx,
+                last_save: Instant::now(),
+            }),
+        }
+    }
+
+    /// Progress recorded by the run being resumed.
+    pub(in crate::receiver) fn resumed(&self) -> CheckpointProgress {
+        self.resumed
+    }
+
+    /// Reports whether `path` was committed from an identical `entry` by the
+    /// resumed run and is still as that commit left it.
+    ///
+    /// A match is carried forward into the checkpoint written by
+    /// [`Self::save`].
+    pub(in crate::receiver) fn covers(
+        &self,
+        entry: &FileEntry,
+        path: &Path,
+        meta: &fs::Metadata,
+    ) -> bool {
+        let Some(key) = self.key(path) else {
+            return false;
+        };
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(record) = state.previous.get(&key).copied() else {
+            return false;
+        };
+        let (dest_mtime, dest_mtime_nsec) = mtime_of(meta);
+        let matches = record.size == entry.size()
+            && meta.len() == entry.size()
+            && record.source_mtime == entry.mtime()
+            && record.source_mtime_nsec == entry.mtime_nsec()
+            && record.dest_mtime == dest_mtime
+            && record.dest_mtime_nsec == dest_mtime_nsec;
+        if matches {
+            state.current.insert(key, record);
+        }
+        matches
+    }
+
+    /// Records the confirmed commit of `entry`, sent as `ndx`.
+    ///
+    /// The destination is stat'ed here so a later resume can tell whether it
+    /// changed since; a file that vanished again is not recorded.
+    pub(in crate::receiver) fn record(&self, entry: &FileEntry, ndx: i32) {
+        let path = self.root.join(entry.path());
+        let Some(key) = self.key(&path) else {
+            return;
+        };
+        let Ok(meta) = fs::metadata(&path) else {
+            return;
+        };
+        let (dest_mtime, dest_mtime_nsec) = mtime_of(&meta);
+        let record = CommitRecord {
+            size: entry.size(),
+            source_mtime: entry.mtime(),
+            source_mtime_nsec: entry.mtime_nsec(),
+            dest_mtime,
+            dest_mtime_nsec,
+        };
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.current.insert(key, record);
+        state.last_ndx = Some(ndx);
+    }
+
+    /// Writes the checkpoint when [`SAVE_INTERVAL`] has passed since the last
+    /// write.
+    pub(in crate::receiver) fn save_if_due(&self) -> io::Result<()> {
+        let due = {
+            let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            state.last_save.elapsed() >= SAVE_INTERVAL
+        };
+        if due { self.save() } else { Ok(()) }
+    }
+
+    /// Writes this run's records, replacing the checkpoint file.
+    ///
+    /// Does nothing for a resume-only run. The file is written beside its
+    /// final name and renamed into place, so an interruption mid-write leaves
+    /// the previous checkpoint intact.
+    pub(in crate::receiver) fn save(&self) -> io::Result<()> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.last_save = Instant::now();
+        if let Some(dir) = file.parent()
+            && !dir.as_os_str().is_empty()
+        {
+            fs::create_dir_all(dir)?;
+        }
+        let bytes = encode(&state.current, state.last_ndx, &self.canonical_root);
+        let tmp = file.with_extension(format!("tmp.{}", std::process::id()));
+        if let Err(err) = fs::write(&tmp, bytes).and_then(|()| fs::rename(&tmp, file)) {
+            let _ = fs::remove_file(&tmp);
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Number of records the next [`Self::save`] writes.
+    pub(in crate::receiver) fn len(&self) -> usize {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.current.len()
+    }
+
+    /// Keys records by their path below the destination root.
+    fn key(&self, path: &Path) -> Option<Vec<u8>> {
+        path.strip_prefix(&self.root)
+            .ok()
+            .map(|rel| rel.as_os_str().as_encoded_bytes().to_vec())
+    }
+}
+
+/// Splits the destination mtime into seconds and nanoseconds; times before
+/// the epoch are stored negated, which is all the equality check needs.
+fn mtime_of(meta: &fs::Metadata) -> (i64, u32) {
+    match meta.modified().map(|t| t.duration_since(UNIX_EPOCH)) {
+        Ok(Ok(since)) => (since.as_secs() as i64, since.subsec_nanos()),
+        Ok(Err(before)) => {
+            let before = before.duration();
+            (-(before.as_secs() as i64), before.subsec_nanos())
+        }
+        Err(_) => (0, 0),
+    }
+}
+
+fn encode(
+    records: &HashMap<Vec<u8>, CommitRecord>,
+    last_ndx: Option<i32>,
+    canonical_root: &[u8],
+) -> Vec<u8> {
+    let bytes: u64 = records.values().map(|r| r.size).sum();
+    let mut out = Vec::with_capacity(40 + canonical_root.len() + records.len() * 80);
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&VERSION.to_le_bytes());
+    out.extend_from_slice(&(canonical_root.len() as u32).to_le_bytes());
+    out.extend_from_slice(canonical_root);
+    out.extend_from_slice(&last_ndx.unwrap_or(-1).to_le_bytes());
+    out.extend_from_slice(&(records.len() as u64).to_le_bytes());
+    out.extend_from_slice(&bytes.to_le_bytes());
+    for (key, record) in records {
+        out.extend_from_slice(&(key.len() as u32).to_le_bytes());
+        out.extend_from_slice(key);
+        out.extend_from_slice(&record.size.to_le_bytes());
+        out.extend_from_slice(&record.source_mtime.to_le_bytes());
+        out.extend_from_slice(&record.source_mtime_nsec.to_le_bytes());
+        out.extend_from_slice(&record.dest_mtime.to_le_bytes());
+        out.extend_from_slice(&record.dest_mtime_nsec.to_le_bytes());
+    }
+    out
+}
+
+/// Parses a checkpoint file, returning `None` when it is damaged, was
+/// written by another format version, or belongs to another destination.
+fn decode(
+    bytes: &[u8],
+    canonical_root: &[u8],
+) -> Option<(CheckpointProgress, HashMap<Vec<u8>, CommitRecord>)> {
+    let mut cursor = Cursor(bytes);
+    if cursor.take(MAGIC.len())? != MAGIC || cursor.u32()? != VERSION {
+        return None;
+    }
+    let root_len = cursor.u32()? as usize;
+    if cursor.take(root_len)? != canonical_root {
+        return None;
+    }
+    let last_ndx = cursor.u32()? as i32;
+    let progress = CheckpointProgress {
+        last_ndx: (last_ndx >= 0).then_some(last_ndx),
+        files: cursor.u64()?,
+        bytes: cursor.u64()?,
+    };
+    let mut records = HashMap::new();
+    while !cursor.0.is_empty() {
+        let key_len = cursor.u32()? as usize;
+        let key = cursor.take(key_len)?.to_vec();
+        let record = CommitRecord {
+            size: cursor.u64()?,
+            source_mtime: cursor.u64()? as i64,
+            source_mtime_nsec: cursor.u32()?,
+            dest_mtime: cursor.u64()? as i64,
+            dest_mtime_nsec: cursor.u32()?,
+        };
+        records.insert(key, record);
+    }
+    Some((progress, records))
+}
+
+/// Bounds-checked little-endian reader over the checkpoint bytes.
+struct Cursor<'a>(&'a [u8]);
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None;
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Some(head)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.take(4)?.try_into().ok().map(u32::from_le_bytes)
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        self.take(8)?.try_into().ok().map(u64::from_le_bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> (tempfile::TempDir, PathBuf, PathBuf) {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let checkpoint = tmp.path().join("state").join("run.ckpt");
+        let dest = tmp.path().join("dest");
+        fs::create_dir(&dest).expect("mkdir dest");
+        (tmp, checkpoint, dest)
+    }
+
+    fn entry(name: &str, size: u64, mtime: i64) -> FileEntry {
+        let mut entry = FileEntry::new_file(name.into(), size, 0o644);
+        entry.set_mtime(mtime, 0);
+        entry
+    }
+
+    fn meta(path: &Path) -> fs::Metadata {
+        fs::metadata(path).expect("stat")
+    }
+
+    #[test]
+    fn committed_file_is_covered_after_resume() {
+        let (_tmp, checkpoint, dest) = setup();
+        let file = dest.join("a.bin");
+        fs::write(&file, b"payload").expect("write");
+        let source = entry("a.bin", 7, 1_700_000_000);
+
+        let first = TransferCheckpoint::open(Some(&checkpoint), None, &dest);
+        first.record(&source, 3);
+        first.save().expect("save");
+
+        let resumed = TransferCheckpoint::open(Some(&checkpoint), Some(&checkpoint), &dest);
+        assert_eq!(
+            resumed.resumed(),
+            CheckpointProgress {
+                last_ndx: Some(3),
+                files: 1,
+                bytes: 7,
+            }
+        );
+        assert!(resumed.covers(&source, &file, &meta(&file)));
+        assert_eq!(resumed.len(), 1);
+    }
+
+    #[test]
+    fn changed_source_or_destination_is_not_covered() {
+        let (_tmp, checkpoint, dest) = setup();
+        let file = dest.join("a.bin");
+        fs::write(&file, b"payload").expect("write");
+        let source = entry("a.bin", 7, 1_700_000_000);
+
+        let first = TransferCheckpoint::open(Some(&checkpoint), None, &dest);
+        first.record(&source, 0);
+        first.save().expect("save");
+
+        let resumed = TransferCheckpoint::open(None, Some(&checkpoint), &dest);
+        let newer = entry("a.bin", 7, 1_700_000_001);
+        assert!(!resumed.covers(&newer, &file, &meta(&file)));
+
+        fs::write(&file, b"payload!").expect("rewrite");
+        assert!(!resumed.covers(&source, &file, &meta(&file)));
+    }
+
+    #[test]
+    fn checkpoint_for_another_destination_is_ignored() {
+        let (tmp, checkpoint, dest) = setup();
+        let file = dest.join("a.bin");
+        fs::write(&file, b"payload").expect("write");
+        let source = entry("a.bin", 7, 1_700_000_000);
+
+        let first = TransferCheckpoint::open(Some(&checkpoint), None, &dest);
+        first.record(&source, 0);
+        first.save().expect("save");
+
+        let other = tmp.path().join("other");
+        fs::create_dir(&other).expect("mkdir other");
+        let other_file = other.join("a.bin");
+        fs::write(&other_file, b"payload").expect("write");
+        let resumed = TransferCheckpoint::open(None, Some(&checkpoint), &other);
+        assert_eq!(resumed.resumed(), CheckpointProgress::default());
+        assert!(!resumed.covers(&source, &other_file, &meta(&other_file)));
+    }
+
+    #[test]
+    fn resume_only_run_writes_nothing() {
+        let (_tmp, checkpoint, dest) = setup();
+        let file = dest.join("a.bin");
+        fs::write(&file, b"payload").expect("write");
+
+        let run = TransferCheckpoint::open(None, Some(&checkpoint), &dest);
+        run.record(&entry("a.bin", 7, 0), 0);
+        run.save().expect("save");
+        assert!(!checkpoint.exists());
+    }
+
+    #[test]
+    fn damaged_checkpoint_loads_empty() {
+        let (_tmp, checkpoint, dest) = setup();
+        let file = dest.join("a.bin");
+        fs::write(&file, b"payload").expect("write");
+        let source = entry("a.bin", 7, 1_700_000_000);
+
+        let first = TransferCheckpoint::open(Some(&checkpoint), None, &dest);
+        first.record(&source, 0);
+        first.save().expect("save");
+        let bytes = fs::read(&checkpoint).expect("read checkpoint");
+        fs::write(&checkpoint, &bytes[..bytes.len() - 3]).expect("truncate");
+
+        let resumed = TransferCheckpoint::open(None, Some(&checkpoint), &dest);
+        assert!(!resumed.covers(&source, &file, &meta(&file)));
+    }
+}
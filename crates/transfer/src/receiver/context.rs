@@ -17,8 +17,8 @@ use protocol::idlist::IdList;
 use protocol::stats::DeleteStats;
 use protocol::{CompatibilityFlags, NegotiationResult, ProtocolVersion};
 
-use engine::HardlinkApplyTracker;
 use engine::delete::DeleteContext;
+use engine::{CaseCollisionDetector, HardlinkApplyTracker, WindowsNameSanitizer};
 
 use crate::config::ServerConfig;
 use crate::handshake::HandshakeResult;
@@ -26,6 +26,8 @@ use crate::shared::ChecksumFactory;
 use crate::transfer_state::TransferPipeline;
 
 use super::basis::BasisFileConfig;
+use super::checkpoint::TransferCheckpoint;
+use super::scan_cache::DestScanCache;
 use super::{
     NDX_CONVERT_CALLS, NDX_CONVERT_CMPS, ParallelThresholds, compile_daemon_filter_set,
     partition_point_depth,
@@ -189,6 +191,19 @@ pub struct ReceiverContext {
     ///
     /// - `hlink.c:match_gnums()` - `prior_hlinks` hashtable persists across segments
     pub(in crate::receiver) prior_hlinks: HashMap<u32, bool>,
+    /// Case-folded names seen so far, carried across INC_RECURSE segments so
+    /// `--case-collision` catches names that differ only in case even when
+    /// they arrive in different sub-lists (oc-rsync extension).
+    pub(in crate::receiver) case_collisions: CaseCollisionDetector,
+    /// Sender names already claimed, carried across INC_RECURSE segments so
+    /// `--windows-names` never sanitizes a name onto a sibling and writes
+    /// entries below a sanitized directory under its new name (oc-rsync
+    /// extension).
+    pub(in crate::receiver) windows_names: WindowsNameSanitizer,
+    /// `--case-collision` and `--windows-names` warnings raised while a
+    /// file-list segment was read, held until the transfer loop has a writer
+    /// to send them on.
+    pub(in crate::receiver) file_list_warnings: Vec<String>,
     /// Accumulated I/O error flags from the sender's file list for protocol < 30.
     ///
     /// For protocol < 30, the sender writes a 4-byte LE io_error flag after the
@@ -343,6 +358,23 @@ pub struct ReceiverContext {
     ///
     /// upstream: receiver.c:733-746 - `stats.created_*++` under `ITEM_IS_NEW`.
     pub(in crate::receiver) created_stats: std::cell::Cell<protocol::stats::CreatedStats>,
+    /// Transfer errors this receiver reported itself (`FERROR_XFER`), such as a
+    /// directory that could not make way for a non-directory. Added to the
+    /// peer-forwarded `MSG_ERROR_XFER` count so the run exits 23. `Cell` for
+    /// the same `&self` reason as [`Self::created_stats`].
+    ///
+    /// upstream: log.c:rwrite() - `FERROR_XFER` sets `got_xfer_error`.
+    pub(in crate::receiver) local_xfer_errors: std::cell::Cell<u32>,
+    /// Whether the `--stop-at` / `--stop-after` deadline passed before every
+    /// file was requested. The run still finishes its phases and goodbye so
+    /// the peer exits cleanly; the flag only turns the exit code into
+    /// `RERR_TIMEOUT`. `Cell` for the same `&self` reason as
+    /// [`Self::created_stats`].
+    pub(in crate::receiver) stop_limit_reached: std::cell::Cell<bool>,
+    /// Files the deadline kept from being requested, by relative path, for
+    /// the `--remaining-files` hand-off. `RefCell` for the same `&self`
+    /// reason as [`Self::created_stats`].
+    pub(in crate::receiver) remaining_files: RefCell<Vec<PathBuf>>,
     /// Extraneous-entry victims decided during the transfer walk for a
     /// `--delete-delay` run, awaiting execution after the transfer completes.
     ///
@@ -360,6 +392,18 @@ pub struct ReceiverContext {
     /// [`execute_delayed_deletions`]: Self::execute_delayed_deletions
     pub(in crate::receiver) delayed_delete_victims:
         Vec<crate::receiver::directory::deletion::DeletedEntry>,
+    /// Destination scan cache consulted by the `--checksum` quick-check.
+    ///
+    /// Opened by `setup_transfer` when `--cache-dir` is set and written back
+    /// once the pipelined drivers finish. oc-rsync extension; see
+    /// [`super::scan_cache`].
+    pub(in crate::receiver) scan_cache: Option<DestScanCache>,
+    /// Committed-file log for `--checkpoint` / `--resume`.
+    ///
+    /// Opened by `setup_transfer`, consulted by the quick-check, fed from
+    /// each confirmed commit and saved periodically and when the pipeline
+    /// stops. oc-rsync extension; see [`super::checkpoint`].
+    pub(in crate::receiver) checkpoint: Option<TransferCheckpoint>,
 }
 
 impl ReceiverContext {
@@ -399,6 +443,8 @@ impl ReceiverContext {
         // upstream: clientserver.c:876-895 - daemon_filter_list is built from
         // module filter/exclude/include directives and used by all roles.
         let daemon_filter_set = compile_daemon_filter_set(&config.daemon_filter_rules);
+        let case_collisions = CaseCollisionDetector::new(config.file_selection.case_collision);
+        let windows_names = WindowsNameSanitizer::new(config.file_selection.windows_names);
 
         Self {
             protocol: handshake.protocol,
@@ -420,6 +466,9 @@ impl ReceiverContext {
             deletion_filter_chain: FilterChain::empty(),
             hardlink_tracker,
             prior_hlinks: HashMap::new(),
+            case_collisions,
+            windows_names,
+            file_list_warnings: Vec::new(),
             flist_io_error: 0,
             parallel_thresholds: ParallelThresholds::default(),
             delete_ctx: None,
@@ -438,7 +487,12 @@ impl ReceiverContext {
             progress_active: false,
             hardlink_follower_echoes: std::cell::Cell::new(0),
             created_stats: std::cell::Cell::new(protocol::stats::CreatedStats::new()),
+            local_xfer_errors: std::cell::Cell::new(0),
+            stop_limit_reached: std::cell::Cell::new(false),
+            remaining_files: RefCell::new(Vec::new()),
             delayed_delete_victims: Vec::new(),
+            scan_cache: None,
+            checkpoint: None,
         }
     }
 
@@ -593,12 +647,13 @@ impl ReceiverContext {
             target_mtime,
             fuzzy_level: self.config.flags.fuzzy_level,
             reference_directories: &self.config.reference_directories,
-            partial_dir: self.config.partial_dir.as_deref(),
+            partial_dir: self.config.effective_partial_dir(),
             protocol: self.protocol,
             checksum_length,
             checksum_algorithm,
             whole_file: self.config.flags.whole_file,
             compat_flags: self.compat_flags,
+            block_length: self.config.block_size,
         }
     }
 
@@ -633,6 +688,7 @@ impl ReceiverContext {
         .with_preserve_acls(self.config.flags.acls)
         .with_preserve_xattrs(self.config.flags.xattrs)
         .with_preserve_atimes(self.config.flags.atimes)
+        .with_preserve_fileflags(self.config.flags.fileflags)
         .with_delete_missing_args(self.config.file_selection.delete_missing_args)
         .with_relative_paths(self.config.flags.relative);
 
@@ -298,7 +298,7 @@ impl ReceiverContext {
                     }
                 });
                 #[cfg(not(unix))]
-                let create_result = fs::create_dir_all(dir_path);
+                let create_result = fs::create_dir_all(fast_io::to_native_path(dir_path));
                 if let Err(e) = create_result {
                     if e.kind() == io::ErrorKind::PermissionDenied {
                         // upstream: receiver.c - permission denied on mkdir is non-fatal,
@@ -512,15 +512,23 @@ impl ReceiverContext {
     /// Uses a set to track already-created paths, avoiding redundant `mkdir` syscalls
     /// when many entries share common parent directories.
     ///
+    /// Returns one `(path, message)` pair per parent that could not be created so
+    /// the caller folds it into the transfer's metadata errors (exit code 23),
+    /// matching upstream's `FERROR_XFER` report.
+    ///
     /// # Upstream Reference
     ///
     /// - `generator.c:1329-1338` - `make_path()` for missing parents when
     ///   `relative_paths && !implied_dirs`
     /// - `generator.c:1484-1487` - retry `mkdir` after `make_path()` when
     ///   `relative_paths` and initial `mkdir` returns `ENOENT`
-    pub(in crate::receiver) fn ensure_relative_parents(&self, dest_dir: &Path) {
+    pub(in crate::receiver) fn ensure_relative_parents(
+        &self,
+        dest_dir: &Path,
+    ) -> Vec<(PathBuf, String)> {
+        let mut errors = Vec::new();
         if !self.config.flags.relative || self.config.flags.skip_dest_writes() {
-            return;
+            return errors;
         }
 
         let mut created: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
@@ -567,7 +575,8 @@ impl ReceiverContext {
 
             // Create from shallowest to deepest.
             for dir_path in ancestors_to_create.into_iter().rev() {
-                if let Err(e) = fs::create_dir(&dir_path) {
+                // Windows: deep trees and reserved names (`CON`, `NUL`) need the `\\?\` form.
+                if let Err(e) = fs::create_dir(fast_io::to_native_path(&dir_path)) {
                     if e.kind() != io::ErrorKind::AlreadyExists {
                         debug_log!(
                             Recv,
@@ -576,12 +585,18 @@ impl ReceiverContext {
                             dir_path.display(),
                             e
                         );
+                        // upstream: generator.c:1335-1337 - rprintf(FERROR_XFER,
+                        // "recv_generator: mkdir %s failed: %s\n", ...)
+                        let msg =
+                            format!("recv_generator: mkdir {} failed: {}", dir_path.display(), e);
+                        errors.push((dir_path, msg));
                         break;
                     }
                 }
                 created.insert(dir_path);
             }
         }
+        errors
     }
 
     /// Creates a single directory during incremental processing.
@@ -707,7 +722,7 @@ impl ReceiverContext {
                 }
             });
             #[cfg(not(unix))]
-            let create_result = fs::create_dir_all(&dir_path);
+            let create_result = fs::create_dir_all(fast_io::to_native_path(&dir_path));
             if let Err(e) = create_result {
                 if e.kind() == io::ErrorKind::PermissionDenied {
                     // upstream: receiver.c:693-700 - permission denied on
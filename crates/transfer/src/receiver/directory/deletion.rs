@@ -5,6 +5,9 @@
 //! removes them. Parallel scanning via `map_blocking` when directory count
 //! exceeds threshold. Respects `--max-delete` via an atomic counter shared
 //! across workers, and `FilterChain::allows_deletion()` for protect/risk rules.
+//! The engine's `DeletionPolicy` takes a `FilterSet`, which cannot carry the
+//! sender's wire rules or the per-directory merge layers this sweep evaluates,
+//! so only its `--delete`/`--force` half is shared (see `make_room.rs`).
 //!
 //! SEC-1.q2: when the receiver carries a [`fast_io::DirSandbox`], the scan
 //! and removal syscalls route through the `*_via_sandbox_or_fallback`
@@ -43,7 +46,7 @@ const MAXPATHLEN: usize = 4096;
 ///   len < MAXPATHLEN` emits `send_msg(MSG_DELETED, fname, len, ...)`, otherwise
 ///   `log_formatted(FCLIENT, "deleting %n" | stdout_format, ...)`.
 /// - `log.c:867-868` - a directory bumps `len` to include its trailing NUL.
-fn emit_delete_notification<W: crate::writer::MsgInfoSender + ?Sized>(
+pub(super) fn emit_delete_notification<W: crate::writer::MsgInfoSender + ?Sized>(
     writer: &mut W,
     rel: &Path,
     is_dir: bool,
@@ -106,10 +109,10 @@ fn path_wire_bytes(rel: &Path) -> Vec<u8> {
 pub(in crate::receiver) struct DeletedEntry {
     /// Path relative to the deletion root, as printed by upstream
     /// `log_delete()` (top-level entries are bare names, not `./name`).
-    rel: PathBuf,
+    pub(super) rel: PathBuf,
     /// Whether the entry is a directory. Directories sort after files at a
     /// given level (upstream `t_PATH`) and print with a trailing slash.
-    is_dir: bool,
+    pub(super) is_dir: bool,
     /// Whether the entry is a symlink. Carried so the deferred `--delete-delay`
     /// executor can classify the removal into `DeleteStats.symlinks`, matching
     /// the inline per-type counting the immediate pass performs from `read_dir`.
@@ -149,7 +152,7 @@ pub(in crate::receiver) struct DeletedEntry {
 /// - `generator.c:2328` generate_files loop - one delete_in_dir() per
 ///   directory, in ascending file-list order
 /// - `flist.c:fsort()` / `f_name_cmp()` - the ascending comparator
-fn order_deletions_upstream(entries: Vec<DeletedEntry>) -> Vec<DeletedEntry> {
+pub(super) fn order_deletions_upstream(entries: Vec<DeletedEntry>) -> Vec<DeletedEntry> {
     use std::collections::{BTreeMap, HashMap, HashSet};
 
     if entries.len() < 2 {
@@ -240,7 +243,7 @@ fn push_post_order(
 ///   directory; a subdirectory is expanded before it is itself deleted.
 /// - `delete.c:178-181 delete_item()` -> `log.c:845 log_delete()` - one
 ///   `deleting` line per removed entry.
-fn record_doomed_dir_descendants(
+pub(super) fn record_doomed_dir_descendants(
     #[cfg(unix)] sandbox: Option<&fast_io::DirSandbox>,
     dest_dir: &Path,
     rel: &Path,
@@ -446,48 +449,60 @@ impl ReceiverContext {
         let protocol = self.protocol.as_u8();
         for entry in victims {
             let path = dest_dir.join(&entry.rel);
-            let result = if entry.is_dir {
-                // upstream: delete.c:delete_item() -> delete_dir_contents() for a
-                // directory victim; recursive removal mirrors the immediate pass.
-                #[cfg(unix)]
-                {
-                    fast_io::recursive_unlinkat_via_sandbox_or_fallback(
-                        sandbox_ref,
-                        dest_dir,
-                        &entry.rel,
-                        &path,
-                    )
-                }
-                #[cfg(not(unix))]
-                {
-                    std::fs::remove_dir_all(&path)
+            let remove = || {
+                if entry.is_dir {
+                    // upstream: delete.c:delete_item() -> delete_dir_contents() for a
+                    // directory victim; recursive removal mirrors the immediate pass.
+                    #[cfg(unix)]
+                    {
+                        fast_io::recursive_unlinkat_via_sandbox_or_fallback(
+                            sandbox_ref,
+                            dest_dir,
+                            &entry.rel,
+                            &path,
+                        )
+                    }
+                    #[cfg(not(unix))]
+                    {
+                        std::fs::remove_dir_all(&path)
+                    }
+                } else {
+                    // upstream: delete.c:165-174 - back up the victim (when
+                    // --backup) before unlinking; a preserved victim is already
+                    // gone, so the direct unlink is skipped.
+                    #[cfg(unix)]
+                    {
+                        super::backup::remove_file_victim(
+                            self.config.flags.backup,
+                            self.config.backup_dir.as_deref().map(Path::new),
+                            self.config.effective_backup_suffix(),
+                            &path,
+                            &entry.rel,
+                            dest_dir,
+                            sandbox_ref,
+                        )
+                    }
+                    #[cfg(not(unix))]
+                    {
+                        super::backup::remove_file_victim(
+                            self.config.flags.backup,
+                            self.config.backup_dir.as_deref().map(Path::new),
+                            self.config.effective_backup_suffix(),
+                            &path,
+                            dest_dir,
+                        )
+                    }
                 }
+            };
+            // upstream: syscall.c do_unlink()/do_rmdir() - `if (dry_run) return 0;`
+            // patches/fileflags.diff - an immutable victim's flags are cleared
+            // before the removal and restored if it fails.
+            let result = if self.config.flags.dry_run {
+                Ok(())
+            } else if self.config.flags.fileflags {
+                ::metadata::while_mutable(&path, remove)
             } else {
-                // upstream: delete.c:165-174 - back up the victim (when
-                // --backup) before unlinking; a preserved victim is already
-                // gone, so the direct unlink is skipped.
-                #[cfg(unix)]
-                {
-                    super::backup::remove_file_victim(
-                        self.config.flags.backup,
-                        self.config.backup_dir.as_deref().map(Path::new),
-                        self.config.effective_backup_suffix(),
-                        &path,
-                        &entry.rel,
-                        dest_dir,
-                        sandbox_ref,
-                    )
-                }
-                #[cfg(not(unix))]
-                {
-                    super::backup::remove_file_victim(
-                        self.config.flags.backup,
-                        self.config.backup_dir.as_deref().map(Path::new),
-                        self.config.effective_backup_suffix(),
-                        &path,
-                        dest_dir,
-                    )
-                }
+                remove()
             };
             match result {
                 Ok(()) => {
@@ -844,6 +859,13 @@ impl ReceiverContext {
         // --backup) before it is unlinked. The workers hold no `self`, so carry
         // the backup settings as owned values the `move` closure can consult.
         let backup_enabled = self.config.flags.backup;
+        // upstream: syscall.c do_unlink()/do_rmdir() return success without
+        // touching the filesystem under dry_run, so the victim is still
+        // counted and logged as deleted.
+        let dry_run = self.config.flags.dry_run;
+        // upstream: patches/fileflags.diff - immutable victims are made
+        // mutable before their removal.
+        let fileflags = self.config.flags.fileflags;
         let backup_dir_owned: Option<PathBuf> =
             self.config.backup_dir.as_deref().map(PathBuf::from);
         let backup_suffix: String = self.config.effective_backup_suffix().to_owned();
@@ -1066,54 +1088,65 @@ impl ReceiverContext {
                             type_bits
                         );
 
+                        let remove = || {
+                            if is_dir {
+                                // SEC-1.q2 audit row #6
+                                #[cfg(unix)]
+                                {
+                                    fast_io::recursive_unlinkat_via_sandbox_or_fallback(
+                                        sandbox_ref,
+                                        &dest_dir_owned,
+                                        &entry_rel,
+                                        &path,
+                                    )
+                                }
+                                #[cfg(not(unix))]
+                                {
+                                    std::fs::remove_dir_all(&path)
+                                }
+                            } else {
+                                // upstream: delete.c:165-174 - back up the file
+                                // victim (when --backup) before unlinking it.
+                                // SEC-1.q2 audit row #7: the fallback unlink still
+                                // routes through the sandbox dirfd.
+                                #[cfg(unix)]
+                                {
+                                    super::backup::remove_file_victim(
+                                        backup_enabled,
+                                        backup_dir_owned.as_deref(),
+                                        &backup_suffix,
+                                        &path,
+                                        &entry_rel,
+                                        &dest_dir_owned,
+                                        sandbox_ref,
+                                    )
+                                }
+                                #[cfg(not(unix))]
+                                {
+                                    super::backup::remove_file_victim(
+                                        backup_enabled,
+                                        backup_dir_owned.as_deref(),
+                                        &backup_suffix,
+                                        &path,
+                                        &dest_dir_owned,
+                                    )
+                                }
+                            }
+                        };
                         // upstream: generator.c:345 `delete_during == 2` records
                         // the victim via remember_delete() and defers the unlink;
                         // in collect_only mode the worker only records the entry so
                         // the physical removal runs later in do_delayed_deletions().
-                        let result = if collect_only {
+                        // A dry run reports the deletion without removing anything.
+                        // patches/fileflags.diff - an immutable victim's flags
+                        // are cleared before the removal and restored if it
+                        // fails.
+                        let result = if collect_only || dry_run {
                             Ok(())
-                        } else if is_dir {
-                            // SEC-1.q2 audit row #6
-                            #[cfg(unix)]
-                            {
-                                fast_io::recursive_unlinkat_via_sandbox_or_fallback(
-                                    sandbox_ref,
-                                    &dest_dir_owned,
-                                    &entry_rel,
-                                    &path,
-                                )
-                            }
-                            #[cfg(not(unix))]
-                            {
-                                std::fs::remove_dir_all(&path)
-                            }
+                        } else if fileflags {
+                            ::metadata::while_mutable(&path, remove)
                         } else {
-                            // upstream: delete.c:165-174 - back up the file
-                            // victim (when --backup) before unlinking it.
-                            // SEC-1.q2 audit row #7: the fallback unlink still
-                            // routes through the sandbox dirfd.
-                            #[cfg(unix)]
-                            {
-                                super::backup::remove_file_victim(
-                                    backup_enabled,
-                                    backup_dir_owned.as_deref(),
-                                    &backup_suffix,
-                                    &path,
-                                    &entry_rel,
-                                    &dest_dir_owned,
-                                    sandbox_ref,
-                                )
-                            }
-                            #[cfg(not(unix))]
-                            {
-                                super::backup::remove_file_victim(
-                                    backup_enabled,
-                                    backup_dir_owned.as_deref(),
-                                    &backup_suffix,
-                                    &path,
-                                    &dest_dir_owned,
-                                )
-                            }
+                            remove()
                         };
 
                         match result {
@@ -1289,6 +1322,8 @@ impl ReceiverContext {
             backup: self.config.flags.backup,
             backup_dir: self.config.backup_dir.as_deref().map(PathBuf::from),
             backup_suffix: self.config.effective_backup_suffix().to_owned(),
+            dry_run: self.config.flags.dry_run,
+            fileflags: self.config.flags.fileflags,
             writer,
         };
 
@@ -1460,6 +1495,10 @@ struct CappedDeleteState<'w, W: ?Sized> {
     backup_dir: Option<PathBuf>,
     /// Effective backup suffix (`~` by default, `""` when `--backup-dir` is set).
     backup_suffix: String,
+    /// `--dry-run`: count and report each victim without removing it.
+    dry_run: bool,
+    /// `--fileflags`: clear an immutable victim's flags before removing it.
+    fileflags: bool,
     writer: &'w mut W,
 }
 
@@ -1605,7 +1644,13 @@ impl<W: crate::writer::MsgInfoSender + ?Sized> CappedDeleteState<'_, W> {
         is_dir: bool,
         is_symlink: bool,
     ) -> io::Result<bool> {
-        let result = self.raw_unlink(rel, path, is_dir);
+        // upstream: patches/fileflags.diff - an immutable victim's flags are
+        // cleared before the removal and restored if it fails.
+        let result = if self.fileflags && !self.dry_run {
+            ::metadata::while_mutable(path, || self.raw_unlink(rel, path, is_dir))
+        } else {
+            self.raw_unlink(rel, path, is_dir)
+        };
         match result {
             Ok(()) => {
                 self.deleted = self.deleted.saturating_add(1);
@@ -1646,6 +1691,10 @@ impl<W: crate::writer::MsgInfoSender + ?Sized> CappedDeleteState<'_, W> {
     /// A file victim is first backed up when `--backup` is set (upstream
     /// delete.c:165-174); directories are never backed up here.
     fn raw_unlink(&self, rel: &Path, path: &Path, is_dir: bool) -> io::Result<()> {
+        // upstream: syscall.c do_unlink()/do_rmdir() - `if (dry_run) return 0;`
+        if self.dry_run {
+            return Ok(());
+        }
         #[cfg(unix)]
         {
             if is_dir {
@@ -1749,7 +1798,7 @@ fn fail_loud_unlink_error(e: io::Error) -> Option<io::Error> {
 ///   `FLAG_MOUNT_DIR` on the dest dirlist entry.
 /// - `generator.c:331` - `delete_in_dir()` skips a `FLAG_MOUNT_DIR` directory.
 #[cfg(unix)]
-fn crosses_mount_boundary(boundary_dev: u64, entry_dev: u64) -> bool {
+pub(super) fn crosses_mount_boundary(boundary_dev: u64, entry_dev: u64) -> bool {
     entry_dev != boundary_dev
 }
 
@@ -13,6 +13,8 @@ use logging::{debug_log, info_log};
 use metadata::{MetadataOptions, apply_symlink_metadata_from_entry};
 use protocol::flist::{trace_leader_is, trace_looking_for_leader, trace_virtual_first};
 
+#[cfg(unix)]
+use super::make_room::MakeRoomFor;
 use crate::generator::ItemFlags;
 use crate::receiver::ReceiverContext;
 
@@ -201,37 +203,53 @@ impl ReceiverContext {
                 // `link_path` cannot redirect the probe to a different
                 // inode. Falls back to `symlink_metadata` otherwise.
                 //
-                // upstream: generator.c:2018-2020 atomic_create - back the old
-                // obstacle up before removal when --backup is set.
-                match self.backup_existing_before_replace(
-                    &link_path,
-                    relative_path,
-                    dest_dir,
-                    sandbox,
-                ) {
-                    Ok(true) => {}
-                    Ok(false) => {
-                        // SEC-1.g: matching unlink also goes through the sandbox
-                        // dirfd via `unlinkat` so the obstacle-remove syscall is
-                        // anchored on the same parent the stat just observed.
-                        let _ = fast_io::unlink_via_sandbox_or_fallback(
-                            sandbox,
-                            dest_dir,
-                            relative_path,
-                            &link_path,
-                            fast_io::UnlinkFlags::File,
-                        );
-                    }
-                    Err(error) => {
-                        debug_log!(
-                            Recv,
-                            1,
-                            "failed to back up existing obstacle {}: {}",
-                            link_path.display(),
-                            error
-                        );
+                // upstream: generator.c:recv_generator() - a directory in the
+                // way goes through delete_item(..., DEL_FOR_SYMLINK) rather
+                // than the atomic_create replace.
+                if fs::symlink_metadata(&link_path).is_ok_and(|meta| meta.is_dir()) {
+                    if !self.make_room_for_non_dir(
+                        dest_dir,
+                        sandbox,
+                        relative_path,
+                        &link_path,
+                        MakeRoomFor::Symlink,
+                        writer,
+                    ) {
                         continue;
                     }
+                } else {
+                    // upstream: generator.c:2018-2020 atomic_create - back the old
+                    // obstacle up before removal when --backup is set.
+                    match self.backup_existing_before_replace(
+                        &link_path,
+                        relative_path,
+                        dest_dir,
+                        sandbox,
+                    ) {
+                        Ok(true) => {}
+                        Ok(false) => {
+                            // SEC-1.g: matching unlink also goes through the sandbox
+                            // dirfd via `unlinkat` so the obstacle-remove syscall is
+                            // anchored on the same parent the stat just observed.
+                            let _ = fast_io::unlink_via_sandbox_or_fallback(
+                                sandbox,
+                                dest_dir,
+                                relative_path,
+                                &link_path,
+                                fast_io::UnlinkFlags::File,
+                            );
+                        }
+                        Err(error) => {
+                            debug_log!(
+                                Recv,
+                                1,
+                                "failed to back up existing obstacle {}: {}",
+                                link_path.display(),
+                                error
+                            );
+                            continue;
+                        }
+                    }
                 }
             } else if !self.config.reference_directories.is_empty() {
                 // upstream: generator.c:1586 - `else if (basis_dir[0] != NULL)`
@@ -966,10 +984,15 @@ pub(in crate::receiver) fn apply_symlink_munge_prefix(target: &Path) -> std::pat
 /// so the caller can skip the entry. A target that does not resolve to an
 /// existing directory (including a dangling link) is treated as a file link.
 ///
+/// The wire target is first mapped back to native form by
+/// [`crate::windows_link::target_from_wire`] (`/` to `\`, `/cygdrive/c/` to
+/// `C:\`), since Windows does not resolve `/`-separated link targets.
+///
 /// Mirrors the local-copy executor's `create_symlink`, which distinguishes
 /// directory from file links via the source's metadata.
 #[cfg(windows)]
 fn create_windows_symlink(target: &Path, link_path: &Path) -> std::io::Result<()> {
+    let target = &crate::windows_link::target_from_wire(target);
     let resolved = match link_path.parent() {
         Some(parent) => parent.join(target),
         None => target.to_path_buf(),
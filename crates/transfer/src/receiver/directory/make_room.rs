@@ -0,0 +1,256 @@
+//! Removal of a destination directory standing where a non-directory goes.
+//!
+//! When the sender's entry is a regular file, symlink, device, or special
+//! file but the destination holds a directory, the directory must go before
+//! the new entry can be created. An empty directory is always removed; a
+//! non-empty one only under `--delete` or `--force`, in which case its whole
+//! subtree is removed and each descendant is reported as deleted.
+//!
+//! # Upstream Reference
+//!
+//! - `generator.c:recv_generator()` - `del_opts` gains `DEL_RECURSE` under
+//!   `delete_mode || force_delete`; a mismatched directory is removed through
+//!   `delete_item(fname, sx.st.st_mode, del_opts | DEL_FOR_FILE)` (and the
+//!   `DEL_FOR_SYMLINK` / `DEL_FOR_DEVICE` / `DEL_FOR_SPECIAL` variants)
+//! - `delete.c:delete_item()` - `DEL_MAKE_ROOM` suppresses the `deleting`
+//!   line for the directory itself, and a failure reports
+//!   `"could not make way for new %s: %s"` as `FERROR_XFER`
+//! - `delete.c:delete_dir_contents()` - descendants are removed without
+//!   `DEL_MAKE_ROOM`, so each one is logged
+
+use std::path::Path;
+
+use engine::local_copy::deletion::DeletionPolicy;
+use logging::{debug_log, info_log};
+
+use super::deletion::{
+    DeletedEntry, emit_delete_notification, order_deletions_upstream, record_doomed_dir_descendants,
+};
+use crate::receiver::ReceiverContext;
+
+/// The kind of entry a directory is being removed for.
+///
+/// upstream: rsync.h - `DEL_FOR_FILE`, `DEL_FOR_SYMLINK`, `DEL_FOR_DEVICE`,
+/// `DEL_FOR_SPECIAL`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(in crate::receiver) enum MakeRoomFor {
+    /// A regular file.
+    File,
+    /// A symbolic link.
+    Symlink,
+    /// A block or character device.
+    Device,
+    /// A FIFO or socket.
+    Special,
+}
+
+impl MakeRoomFor {
+    /// Returns the description upstream prints in the failure message.
+    ///
+    /// upstream: delete.c:delete_item() - the `desc` switch over `DEL_MAKE_ROOM`.
+    const fn description(self) -> &'static str {
+        match self {
+            Self::File => "regular file",
+            Self::Symlink => "symlink",
+            Self::Device => "device file",
+            Self::Special => "special file",
+        }
+    }
+}
+
+impl ReceiverContext {
+    /// Reports whether a non-empty directory in the way of a non-directory is
+    /// removed recursively (`--delete` or `--force`).
+    ///
+    /// upstream: generator.c:recv_generator() - `if (delete_mode || force_delete)
+    /// del_opts |= DEL_RECURSE`.
+    pub(in crate::receiver) const fn make_room_recurses(&self) -> bool {
+        DeletionPolicy::new(None)
+            .delete(self.config.flags.delete)
+            .force(self.config.deletion.force_delete)
+            .removes_conflicting_directories()
+    }
+
+    /// Removes the directory at `path` so a `kind` entry can be created there.
+    ///
+    /// Returns `true` when the path is free - nothing was there, it was not a
+    /// directory, or the directory was removed - and `false` when the entry
+    /// must be skipped. A skipped entry is reported as a transfer error and
+    /// counted towards exit code 23.
+    ///
+    /// Under `--one-file-system` a subtree holding a mount point is kept
+    /// whole. Every removed descendant is reported in upstream's
+    /// children-before-directory order; the directory itself is not.
+    pub(in crate::receiver) fn make_room_for_non_dir<W: crate::writer::MsgInfoSender + ?Sized>(
+        &self,
+        dest_dir: &Path,
+        #[cfg(unix)] sandbox: Option<&fast_io::DirSandbox>,
+        rel: &Path,
+        path: &Path,
+        kind: MakeRoomFor,
+        writer: &mut W,
+    ) -> bool {
+        match std::fs::symlink_metadata(path) {
+            Ok(meta) if meta.is_dir() => {}
+            _ => return true,
+        }
+        // The transfer root is never removed to make room for its own entry.
+        if rel.as_os_str().is_empty() || rel == Path::new(".") {
+            self.report_make_room_failure(writer, rel, kind);
+            return false;
+        }
+
+        let mut doomed: Vec<DeletedEntry> = Vec::new();
+        record_doomed_dir_descendants(
+            #[cfg(unix)]
+            sandbox,
+            dest_dir,
+            rel,
+            path,
+            &mut doomed,
+        );
+
+        if !doomed.is_empty() {
+            if !self.make_room_recurses() {
+                self.report_make_room_failure(writer, rel, kind);
+                return false;
+            }
+            if self.make_room_crosses_mount(dest_dir, rel, path, &doomed) {
+                self.report_make_room_failure(writer, rel, kind);
+                return false;
+            }
+        }
+
+        // upstream: syscall.c do_unlink()/do_rmdir() - `if (dry_run) return 0;`
+        if !self.config.flags.dry_run {
+            let result = if doomed.is_empty() {
+                #[cfg(unix)]
+                {
+                    fast_io::unlink_via_sandbox_or_fallback(
+                        sandbox,
+                        dest_dir,
+                        rel,
+                        path,
+                        fast_io::UnlinkFlags::Dir,
+                    )
+                }
+                #[cfg(not(unix))]
+                {
+                    std::fs::remove_dir(path)
+                }
+            } else {
+                #[cfg(unix)]
+                {
+                    fast_io::recursive_unlinkat_via_sandbox_or_fallback(
+                        sandbox, dest_dir, rel, path,
+                    )
+                }
+                #[cfg(not(unix))]
+                {
+                    std::fs::remove_dir_all(path)
+                }
+            };
+            if let Err(error) = result {
+                debug_log!(Del, 1, "failed to remove {}: {}", path.display(), error);
+                self.report_make_room_failure(writer, rel, kind);
+                return false;
+            }
+        }
+
+        let server_mode = !self.config.connection.client_mode;
+        let protocol = self.protocol.as_u8();
+        let emit_itemize = self.should_emit_itemize();
+        for entry in order_deletions_upstream(doomed) {
+            emit_delete_notification(
+                writer,
+                &entry.rel,
+                entry.is_dir,
+                server_mode,
+                protocol,
+                emit_itemize,
+            );
+        }
+        true
+    }
+
+    /// Reports whether removing the directory at `path` would cross the
+    /// `--one-file-system` boundary of the transfer root.
+    ///
+    /// upstream: delete.c:delete_dir_contents() - a `FLAG_MOUNT_DIR` entry
+    /// "pins parent directory", so the make-room removal fails.
+    #[cfg(unix)]
+    fn make_room_crosses_mount(
+        &self,
+        dest_dir: &Path,
+        rel: &Path,
+        path: &Path,
+        doomed: &[DeletedEntry],
+    ) -> bool {
+        use std::os::unix::fs::MetadataExt;
+
+        if self.config.flags.one_file_system == 0 {
+            return false;
+        }
+        let Ok(boundary) = std::fs::metadata(dest_dir).map(|meta| meta.dev()) else {
+            return false;
+        };
+        let dirs = std::iter::once((rel, path.to_path_buf())).chain(
+            doomed
+                .iter()
+                .filter(|entry| entry.is_dir)
+                .map(|entry| (entry.rel.as_path(), dest_dir.join(&entry.rel))),
+        );
+        for (dir_rel, dir_path) in dirs {
+            if let Ok(meta) = std::fs::symlink_metadata(&dir_path)
+                && super::deletion::crosses_mount_boundary(boundary, meta.dev())
+            {
+                info_log!(
+                    Mount,
+                    1,
+                    "mount point, {}, pins parent directory",
+                    dir_rel.display()
+                );
+                return true;
+            }
+        }
+        false
+    }
+
+    #[cfg(not(unix))]
+    fn make_room_crosses_mount(
+        &self,
+        _dest_dir: &Path,
+        _rel: &Path,
+        _path: &Path,
+        _doomed: &[DeletedEntry],
+    ) -> bool {
+        false
+    }
+
+    /// Reports a failed make-room removal and counts it as a transfer error.
+    ///
+    /// A server receiver forwards the line as `MSG_ERROR_XFER` so the client
+    /// sets its exit code; a client receiver writes it to stderr.
+    ///
+    /// upstream: delete.c:delete_item() - `rprintf(FERROR_XFER, "could not make
+    /// way for %s %s: %s\n", ...)`.
+    fn report_make_room_failure<W: crate::writer::MsgInfoSender + ?Sized>(
+        &self,
+        writer: &mut W,
+        rel: &Path,
+        kind: MakeRoomFor,
+    ) {
+        let line = format!(
+            "could not make way for new {}: {}\n",
+            kind.description(),
+            rel.display()
+        );
+        if self.config.connection.client_mode {
+            eprint!("{line}");
+        } else {
+            let _ = writer.send_msg_error_xfer(line.as_bytes());
+        }
+        self.local_xfer_errors
+            .set(self.local_xfer_errors.get().saturating_add(1));
+    }
+}
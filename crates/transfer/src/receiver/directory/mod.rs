@@ -12,9 +12,12 @@ mod creation;
 // for its deferred `--delete-delay` victim queue.
 pub(in crate::receiver) mod deletion;
 mod links;
+mod make_room;
 mod missing_args;
 mod special;
 
+pub(in crate::receiver) use make_room::MakeRoomFor;
+
 /// Normalizes a filename for cross-platform comparison.
 ///
 /// On macOS, converts NFD (decomposed) filenames to NFC (composed) so that
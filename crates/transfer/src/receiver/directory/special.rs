@@ -34,6 +34,8 @@ use metadata::{
 #[cfg(unix)]
 use protocol::flist::{FileEntry, FileType};
 
+#[cfg(unix)]
+use super::make_room::MakeRoomFor;
 #[cfg(unix)]
 use crate::generator::ItemFlags;
 use crate::receiver::ReceiverContext;
@@ -137,6 +139,7 @@ impl ReceiverContext {
                         .preserve_times(self.config.flags.times)
                         .preserve_atimes(self.config.flags.atimes)
                         .preserve_crtimes(self.config.flags.crtimes)
+                        .preserve_fileflags(self.config.flags.fileflags)
                         .numeric_ids(self.config.flags.numeric_ids.maps_numeric())
                         .fake_super(self.config.fake_super);
                     if crate::receiver::quick_check::try_reference_dest_non(
@@ -150,44 +153,65 @@ impl ReceiverContext {
                     }
                 }
 
-                // upstream: generator.c:2018-2020 atomic_create - when --backup
-                // is set and an existing item is being replaced, preserve it to
-                // the backup location before it is removed. On backup-mechanism
-                // failure upstream returns 0 from atomic_create (skips the
-                // entry); mirror that by logging and continuing.
-                match self.backup_existing_before_replace(
-                    &node_path,
-                    relative_path,
-                    dest_dir,
-                    sandbox,
-                ) {
-                    Ok(true) => {}
-                    Ok(false) => {
-                        // SEC-1.g: route the obstacle unlink through the sandbox
-                        // dirfd when the destination parent is the sandbox root
-                        // so a TOCTOU swap between the stat above and this
-                        // unlink cannot redirect the syscall. Falls back to
-                        // path-based removal otherwise. The result is
-                        // intentionally ignored: a dangling obstacle simply
-                        // surfaces as a create failure below.
-                        let _ = fast_io::unlink_via_sandbox_or_fallback(
-                            sandbox,
-                            dest_dir,
-                            relative_path,
-                            &node_path,
-                            fast_io::UnlinkFlags::File,
-                        );
-                    }
-                    Err(error) => {
-                        debug_log!(
-                            Recv,
-                            1,
-                            "failed to back up existing special file {}: {}",
-                            node_path.display(),
-                            error
-                        );
+                // upstream: generator.c:recv_generator() - a directory in the
+                // way goes through delete_item(..., DEL_FOR_DEVICE or
+                // DEL_FOR_SPECIAL) rather than the atomic_create replace.
+                if fs::symlink_metadata(&node_path).is_ok_and(|meta| meta.is_dir()) {
+                    let kind = if is_device {
+                        MakeRoomFor::Device
+                    } else {
+                        MakeRoomFor::Special
+                    };
+                    if !self.make_room_for_non_dir(
+                        dest_dir,
+                        sandbox,
+                        relative_path,
+                        &node_path,
+                        kind,
+                        writer,
+                    ) {
                         continue;
                     }
+                } else {
+                    // upstream: generator.c:2018-2020 atomic_create - when --backup
+                    // is set and an existing item is being replaced, preserve it to
+                    // the backup location before it is removed. On backup-mechanism
+                    // failure upstream returns 0 from atomic_create (skips the
+                    // entry); mirror that by logging and continuing.
+                    match self.backup_existing_before_replace(
+                        &node_path,
+                        relative_path,
+                        dest_dir,
+                        sandbox,
+                    ) {
+                        Ok(true) => {}
+                        Ok(false) => {
+                            // SEC-1.g: route the obstacle unlink through the sandbox
+                            // dirfd when the destination parent is the sandbox root
+                            // so a TOCTOU swap between the stat above and this
+                            // unlink cannot redirect the syscall. Falls back to
+                            // path-based removal otherwise. The result is
+                            // intentionally ignored: a dangling obstacle simply
+                            // surfaces as a create failure below.
+                            let _ = fast_io::unlink_via_sandbox_or_fallback(
+                                sandbox,
+                                dest_dir,
+                                relative_path,
+                                &node_path,
+                                fast_io::UnlinkFlags::File,
+                            );
+                        }
+                        Err(error) => {
+                            debug_log!(
+                                Recv,
+                                1,
+                                "failed to back up existing special file {}: {}",
+                                node_path.display(),
+                                error
+                            );
+                            continue;
+                        }
+                    }
                 }
 
                 // upstream: generator.c:1675 atomic_create -> do_mknod_at
@@ -233,6 +257,7 @@ impl ReceiverContext {
                 .preserve_times(self.config.flags.times)
                 .preserve_atimes(self.config.flags.atimes)
                 .preserve_crtimes(self.config.flags.crtimes)
+                .preserve_fileflags(self.config.flags.fileflags)
                 .numeric_ids(self.config.flags.numeric_ids.maps_numeric())
                 .fake_super(self.config.fake_super);
             if let Err(error) = apply_metadata_from_file_entry(&node_path, entry, &options) {
@@ -0,0 +1,79 @@
+//! Receiver-side `--case-collision` pass.
+//!
+//! oc-rsync extension with no upstream counterpart. After the receiver has
+//! sorted and cleaned a file-list segment, every surviving entry is folded
+//! through the [`CaseCollisionDetector`] held on the receiver context, so
+//! names that differ only in case are caught even when they arrive in
+//! different INC_RECURSE segments. Each collision is queued as a warning;
+//! under [`engine::CaseCollisionPolicy::Rename`] the later file is renamed in
+//! place via [`FileEntry::set_name`], so the generator and receiver write it
+//! under the disambiguated name while its NDX still matches the sender's.
+
+use engine::CaseCollisionDetector;
+use protocol::flist::FileEntry;
+
+/// Records each live entry of `file_list` with `detector`, queueing a
+/// warning per collision in `warnings` and applying any rename the policy
+/// chooses.
+///
+/// Entries cleared by the dedup or `--prune-empty-dirs` passes (mode zero)
+/// are skipped, as is every entry when the policy is off.
+pub(in crate::receiver) fn case_collision_pass(
+    file_list: &mut [FileEntry],
+    detector: &mut CaseCollisionDetector,
+    warnings: &mut Vec<String>,
+) {
+    if !detector.policy().is_enabled() {
+        return;
+    }
+    for entry in file_list.iter_mut().filter(|entry| entry.mode() != 0) {
+        let Some(collision) = detector.record(entry.path(), entry.is_dir()) else {
+            continue;
+        };
+        warnings.push(collision.message(entry.path()));
+        if let Some(renamed) = collision.renamed {
+            let renamed = entry.path().with_file_name(renamed);
+            entry.set_name(renamed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use engine::CaseCollisionPolicy;
+    use std::path::PathBuf;
+
+    fn file(name: &str) -> FileEntry {
+        FileEntry::new_file(PathBuf::from(name), 1, 0o644)
+    }
+
+    #[test]
+    fn rename_policy_renames_later_entry_across_segments() {
+        let mut detector = CaseCollisionDetector::new(CaseCollisionPolicy::Rename);
+        let mut first = vec![
+            FileEntry::new_directory(PathBuf::from("src"), 0o755),
+            file("src/Main.c"),
+        ];
+        let mut warnings = Vec::new();
+        case_collision_pass(&mut first, &mut detector, &mut warnings);
+        assert_eq!(first[1].path(), &PathBuf::from("src/Main.c"));
+        assert!(warnings.is_empty());
+
+        let mut second = vec![file("src/main.c")];
+        case_collision_pass(&mut second, &mut detector, &mut warnings);
+        assert_eq!(second[0].path(), &PathBuf::from("src/main~1.c"));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("WARNING: case-conflict: \"src/main.c\""));
+    }
+
+    #[test]
+    fn warn_policy_keeps_names() {
+        let mut detector = CaseCollisionDetector::new(CaseCollisionPolicy::Warn);
+        let mut list = vec![file("README"), file("readme")];
+        let mut warnings = Vec::new();
+        case_collision_pass(&mut list, &mut detector, &mut warnings);
+        assert_eq!(list[1].path(), &PathBuf::from("readme"));
+        assert_eq!(warnings.len(), 1);
+    }
+}
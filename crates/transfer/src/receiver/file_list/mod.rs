@@ -19,7 +19,13 @@
 //! - `hardlinks` - post-sort hardlink leader/follower assignment for
 //!   protocol 30+ and pre-30 normalization from (dev, ino) pairs.
 //! - `incremental` - the streaming [`IncrementalFileListReceiver`] type.
+//! - `case_collision` - `--case-collision` detection of entries whose names
+//!   collide after case folding (oc-rsync extension).
+//! - `windows_names` - `--windows-names` handling of names that cannot be
+//!   created on Windows (oc-rsync extension).
+//! - `warnings` - flushing of the warnings the two passes above queue.
 
+mod case_collision;
 mod filter_recheck;
 mod hardlinks;
 mod id_lists;
@@ -28,5 +34,7 @@ mod on_demand;
 mod prune;
 mod receive;
 mod sanitize;
+mod warnings;
+mod windows_names;
 
 pub use incremental::IncrementalFileListReceiver;
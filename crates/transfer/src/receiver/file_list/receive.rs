@@ -12,11 +12,15 @@ use logging::debug_log;
 use protocol::CompatibilityFlags;
 use protocol::codec::{NDX_FLIST_EOF, NDX_FLIST_OFFSET, NdxCodec, create_ndx_codec};
 use protocol::flist::{FileEntry, IncrementalFileListBuilder, sort_and_clean_file_list};
+#[cfg(feature = "tracing")]
+use tracing::instrument;
 
 use super::super::ReceiverContext;
+use super::case_collision::case_collision_pass;
 use super::hardlinks::{match_hard_links, normalize_pre30_hardlinks};
 use super::incremental::IncrementalFileListReceiver;
 use super::prune::prune_empty_dirs_pass;
+use super::windows_names::windows_names_pass;
 
 impl ReceiverContext {
     /// Receives the file list from the sender.
@@ -30,6 +34,10 @@ impl ReceiverContext {
     ///
     /// After the file list entries, this also consumes the UID/GID lists that follow
     /// (unless using incremental recursion). See upstream `recv_id_list()` in uidlist.c.
+    #[cfg_attr(
+        feature = "tracing",
+        instrument(skip_all, name = "flist_receive", fields(entries = tracing::field::Empty))
+    )]
     pub fn receive_file_list<R: Read + ?Sized>(&mut self, reader: &mut R) -> io::Result<usize> {
         let mut flist_reader = self.build_flist_reader();
 
@@ -181,6 +189,25 @@ impl ReceiverContext {
             prune_empty_dirs_pass(&mut self.file_list, &self.filter_chain);
         }
 
+        // oc-rsync extension: check the surviving names against the Windows
+        // filename rules (`--windows-names`) before anything is written. The
+        // dir_flist names above were recorded first, so sub-list validation
+        // still sees the sender's spelling.
+        windows_names_pass(
+            &mut self.file_list,
+            &mut self.windows_names,
+            &mut self.file_list_warnings,
+        )?;
+
+        // oc-rsync extension: fold the surviving names so entries that would
+        // overwrite each other on a case-insensitive destination are reported
+        // (and renamed under `--case-collision=rename`) before any is written.
+        case_collision_pass(
+            &mut self.file_list,
+            &mut self.case_collisions,
+            &mut self.file_list_warnings,
+        );
+
         match_hard_links(&mut self.file_list, &mut self.prior_hlinks);
 
         // For protocol < 30, normalize (dev, ino) pairs into hardlink_idx and
@@ -196,6 +223,8 @@ impl ReceiverContext {
         // across recv_file_list() calls - cache the reader to preserve that state.
         self.flist_reader_cache = Some(flist_reader);
 
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("entries", count);
         Ok(count)
     }
 
@@ -363,6 +392,11 @@ impl ReceiverContext {
             let (cleaned, _clean) = sort_and_clean_file_list(tail, true, false, false, true);
             self.file_list.extend(cleaned);
         }
+        case_collision_pass(
+            &mut self.file_list[flat_start..],
+            &mut self.case_collisions,
+            &mut self.file_list_warnings,
+        );
         match_hard_links(&mut self.file_list[flat_start..], &mut self.prior_hlinks);
 
         // Normalize pre-30 hardlinks in this segment.
@@ -378,6 +412,16 @@ impl ReceiverContext {
         self.dir_flist_used += count_directories(&self.file_list[flat_start..]);
         self.record_dir_flist_names(flat_start);
 
+        // oc-rsync extension: `--windows-names` runs only after this segment's
+        // directories are recorded, so a skipped directory still counts toward
+        // dir_ndx and a deeper sub-list is validated against the sender's
+        // spelling of its parent.
+        windows_names_pass(
+            &mut self.file_list[flat_start..],
+            &mut self.windows_names,
+            &mut self.file_list_warnings,
+        )?;
+
         // upstream: flist.c:2966 - ndx_start = prev->ndx_start + prev->used + 1
         self.ndx_segments.push((flat_start, seg_ndx_start));
 
@@ -0,0 +1,31 @@
+//! Warnings raised while a file-list segment is processed.
+//!
+//! The `--case-collision` and `--windows-names` passes run as each segment
+//! arrives, before the transfer loop holds a writer, so their warnings wait
+//! on the receiver context until the loop flushes them.
+
+use std::io;
+
+use super::super::ReceiverContext;
+use crate::writer::MsgInfoSender;
+
+impl ReceiverContext {
+    /// Emits the queued file-list warnings.
+    ///
+    /// A client receiver prints them to stderr itself; a server receiver sends
+    /// each as `MSG_WARNING` so the client prints it, as upstream's `rwrite()`
+    /// routes `FWARNING` when `am_server`.
+    pub(in crate::receiver) fn flush_file_list_warnings<W: MsgInfoSender + ?Sized>(
+        &mut self,
+        writer: &mut W,
+    ) -> io::Result<()> {
+        for warning in self.file_list_warnings.drain(..) {
+            if self.config.connection.client_mode {
+                eprintln!("{warning}");
+            } else {
+                writer.send_msg_warning(format!("{warning}\n").as_bytes())?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,137 @@
+//! Receiver-side `--windows-names` pass.
+//!
+//! oc-rsync extension with no upstream counterpart. After the receiver has
+//! sorted and cleaned a file-list segment, every live entry is checked
+//! against the Windows filename rules before the generator creates
+//! anything. Because each entry carries its full relative path, every
+//! component is checked: an entry below a sanitized directory is rewritten
+//! under the same sanitized name, and an entry below a skipped directory is
+//! skipped with it. Only the entry whose own name is invalid is reported, so
+//! a skipped directory produces one warning rather than one per child.
+//!
+//! A sanitized name that a sibling already uses gets a `~N` suffix; the
+//! [`WindowsNameSanitizer`] on the receiver context remembers every claimed
+//! name and sanitized directory across INC_RECURSE segments. All of a
+//! directory's entries arrive in one segment, so each segment is reserved
+//! before any of its entries is checked.
+//!
+//! Renamed entries keep their NDX via [`FileEntry::set_name`]; skipped
+//! entries are tombstoned in place like the duplicate-name clean, so the list
+//! stays aligned with the sender's numbering. Warnings are queued in the same
+//! way as the `--case-collision` pass.
+
+use std::io;
+
+use engine::{
+    WindowsNameAction, WindowsNameSanitizer, invalid_windows_name_error, is_valid_windows_name,
+};
+use protocol::flist::FileEntry;
+
+/// Applies `sanitizer`'s policy to each live entry of `file_list`, queueing
+/// a warning in `warnings` for each entry whose own name is invalid.
+///
+/// # Errors
+///
+/// Under [`engine::WindowsNamesPolicy::Fail`], returns an `InvalidInput`
+/// error naming the first entry that cannot be created on Windows.
+pub(in crate::receiver) fn windows_names_pass(
+    file_list: &mut [FileEntry],
+    sanitizer: &mut WindowsNameSanitizer,
+    warnings: &mut Vec<String>,
+) -> io::Result<()> {
+    if !sanitizer.policy().is_enabled() {
+        return Ok(());
+    }
+    for entry in file_list.iter().filter(|entry| entry.mode() != 0) {
+        sanitizer.reserve(entry.path());
+    }
+    for entry in file_list.iter_mut().filter(|entry| entry.mode() != 0) {
+        let Some(action) = sanitizer.check(entry.path(), entry.is_dir()) else {
+            continue;
+        };
+        let own_name_invalid = entry
+            .path()
+            .file_name()
+            .is_some_and(|name| !is_valid_windows_name(name));
+        if own_name_invalid {
+            warnings.extend(action.message(entry.path()));
+        }
+        match action {
+            WindowsNameAction::Rename(sanitized) => entry.set_name(sanitized),
+            WindowsNameAction::Skip => entry.tombstone(),
+            WindowsNameAction::Fail => return Err(invalid_windows_name_error(entry.path())),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use engine::WindowsNamesPolicy;
+    use std::path::PathBuf;
+
+    fn file(name: &str) -> FileEntry {
+        FileEntry::new_file(PathBuf::from(name), 1, 0o644)
+    }
+
+    fn sample() -> Vec<FileEntry> {
+        vec![
+            FileEntry::new_directory(PathBuf::from("a:b"), 0o755),
+            file("a:b/ok.txt"),
+            file("plain.txt"),
+        ]
+    }
+
+    fn run(list: &mut [FileEntry], policy: WindowsNamesPolicy) -> io::Result<Vec<String>> {
+        let mut sanitizer = WindowsNameSanitizer::new(policy);
+        let mut warnings = Vec::new();
+        windows_names_pass(list, &mut sanitizer, &mut warnings)?;
+        Ok(warnings)
+    }
+
+    #[test]
+    fn sanitize_rewrites_entries_below_renamed_directory() {
+        let mut list = sample();
+        let warnings = run(&mut list, WindowsNamesPolicy::Sanitize).unwrap();
+        assert_eq!(list[0].path(), &PathBuf::from("a_b"));
+        assert_eq!(list[1].path(), &PathBuf::from("a_b/ok.txt"));
+        assert_eq!(list[2].path(), &PathBuf::from("plain.txt"));
+        assert_eq!(
+            warnings.len(),
+            1,
+            "only the invalid name itself is reported"
+        );
+        assert!(warnings[0].starts_with("WARNING: windows-names: \"a:b\""));
+    }
+
+    #[test]
+    fn sanitize_disambiguates_names_taken_by_siblings() {
+        let mut list = vec![
+            FileEntry::new_directory(PathBuf::from("a:b"), 0o755),
+            file("a:b/ok.txt"),
+            FileEntry::new_directory(PathBuf::from("a_b"), 0o755),
+        ];
+        run(&mut list, WindowsNamesPolicy::Sanitize).unwrap();
+        assert_eq!(list[0].path(), &PathBuf::from("a_b~1"));
+        assert_eq!(list[1].path(), &PathBuf::from("a_b~1/ok.txt"));
+        assert_eq!(list[2].path(), &PathBuf::from("a_b"));
+    }
+
+    #[test]
+    fn skip_tombstones_directory_and_children() {
+        let mut list = sample();
+        run(&mut list, WindowsNamesPolicy::Skip).unwrap();
+        assert!(!list[0].is_active());
+        assert!(!list[1].is_active());
+        assert!(list[2].is_active());
+    }
+
+    #[test]
+    fn fail_reports_first_invalid_entry() {
+        let mut list = sample();
+        let error = run(&mut list, WindowsNamesPolicy::Fail).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(error.to_string().contains("a:b"));
+    }
+}
@@ -30,6 +30,7 @@
 //! - [`protocol::wire`] - Wire format for signatures and deltas
 
 mod basis;
+mod checkpoint;
 mod context;
 mod dest_root;
 mod directory;
@@ -37,6 +38,8 @@ mod file_list;
 mod itemize;
 mod pipeline_setup;
 mod quick_check;
+mod scan_cache;
+mod skip_decision;
 mod stats;
 #[cfg(test)]
 mod tests;
@@ -51,9 +54,11 @@ pub(crate) use crate::parallel_io::ParallelThresholds;
 use signature;
 
 pub use self::basis::{
-    BasisFileConfig, BasisFileResult, ChecksumThreadsPolicy, find_basis_file_with_config,
-    set_checksum_threads_policy,
+    BasisFileConfig, BasisFileResult, BasisFileStream, ChecksumThreadsPolicy,
+    find_basis_file_for_stream, find_basis_file_with_config, set_checksum_threads_policy,
 };
+#[cfg(feature = "tokio")]
+pub(crate) use self::basis::with_async_signature_runtime;
 pub use self::context::ReceiverContext;
 pub(in crate::receiver) use self::dest_root::dest_arg_has_trailing_slash;
 pub use self::dest_root::ensure_dest_root_exists;
@@ -64,7 +69,7 @@ pub(in crate::receiver) use self::pipeline_setup::{
 pub use self::stats::{ListOnlyEntry, SenderStats, TransferStats};
 pub use self::wire::{
     SenderAttrs, SumHead, apply_xattr_abbreviation_values, write_signature_blocks,
-    write_xattr_request,
+    write_signature_stream, write_xattr_request,
 };
 
 /// Phase 1 checksum length (2 bytes) - reduced signature overhead.
@@ -20,6 +20,7 @@ use metadata::{
 use protocol::acl::AclCache;
 
 use super::apply_acls_from_receiver_cache;
+use super::scan_cache::DestScanCache;
 
 /// Returns true if this file entry is a hardlink follower that should be
 /// created as a hard link rather than transferred via delta.
@@ -45,6 +46,9 @@ pub(super) fn is_hardlink_follower(entry: &FileEntry) -> bool {
 /// 3. `size_only` - size matched, skip transfer
 /// 4. `!preserve_times` (implies `ignore_times`) - force transfer
 /// 5. mtime comparison, tolerating `--modify-window` seconds of drift
+///
+/// `scan_cache` lets step 2 reuse a digest from a previous run (`--cache-dir`).
+#[allow(clippy::too_many_arguments)]
 pub(super) fn quick_check_matches(
     entry: &FileEntry,
     dest_path: &Path,
@@ -53,6 +57,7 @@ pub(super) fn quick_check_matches(
     size_only: bool,
     always_checksum: Option<protocol::ChecksumAlgorithm>,
     modify_window: ModifyWindow,
+    scan_cache: Option<&DestScanCache>,
 ) -> bool {
     // upstream: generator.c:621 - size check first
     if dest_meta.len() != entry.size() {
@@ -63,7 +68,7 @@ pub(super) fn quick_check_matches(
     if let Some(algorithm) = always_checksum {
         return match entry.checksum() {
             Some(expected) => {
-                file_checksum_matches(dest_path, dest_meta.len(), algorithm, expected)
+                file_checksum_matches(dest_path, dest_meta, algorithm, expected, scan_cache)
             }
             None => false,
         };
@@ -106,6 +111,66 @@ pub(super) fn quick_check_matches(
     }
 }
 
+/// Decides that a destination file is up to date from its quick-check fields
+/// alone.
+///
+/// Returns `true` only when the size + mtime quick-check passes (as in
+/// [`quick_check_matches`] without `--checksum`) and every attribute
+/// `metadata_unchanged()` compares already matches, so the file needs neither
+/// a transfer nor a metadata apply. Anything else returns `false` and the
+/// caller falls back to a full stat. Only valid when [`quick_stat_covers`]
+/// holds for the active metadata options.
+pub(super) fn quick_stat_settles(
+    entry: &FileEntry,
+    quick: &fast_io::syscall_batch::QuickStat,
+    metadata_opts: &MetadataOptions,
+    preserve_times: bool,
+    size_only: bool,
+    modify_window: ModifyWindow,
+) -> bool {
+    if !quick.is_file() || quick.size != entry.size() {
+        return false;
+    }
+    // upstream: generator.c:639-645 - size_only, ignore_times, same_time()
+    if !size_only
+        && (!preserve_times
+            || !modify_window.same_time(
+                quick.mtime,
+                quick.mtime_nsec,
+                entry.mtime(),
+                entry.mtime_nsec(),
+            ))
+    {
+        return false;
+    }
+    // upstream: generator.c:492-497 - the unchanged_attrs() checks mirrored
+    // by metadata_unchanged(): perms, ownership, then mtime.
+    if metadata_opts.permissions() && (quick.mode & 0o7777) != (entry.permissions() & 0o7777) {
+        return false;
+    }
+    if metadata_opts.owner() && entry.uid().is_some_and(|uid| uid != quick.uid) {
+        return false;
+    }
+    if metadata_opts.group() && entry.gid().is_some_and(|gid| gid != quick.gid) {
+        return false;
+    }
+    !(metadata_opts.times()
+        && (quick.mtime != entry.mtime() || quick.mtime_nsec != entry.mtime_nsec()))
+}
+
+/// Returns `true` when [`quick_stat_settles`] can stand in for
+/// `metadata_unchanged()` under `metadata_opts`.
+///
+/// `--atimes`, `--fileflags`, `--chmod` and the ownership overrides compare
+/// fields or apply rules a quick stat does not carry.
+pub(super) fn quick_stat_covers(metadata_opts: &MetadataOptions) -> bool {
+    !metadata_opts.atimes()
+        && !metadata_opts.fileflags()
+        && metadata_opts.chmod().is_none()
+        && metadata_opts.owner_override().is_none()
+        && metadata_opts.group_override().is_none()
+}
+
 /// Returns `true` when the destination file's mtime is strictly newer than the source.
 ///
 /// Used by `--update` (`-u`) to skip files where the destination is already newer.
@@ -292,6 +357,7 @@ fn best_reference_match<'a>(
             size_only,
             always_checksum,
             modify_window,
+            None,
         ) {
             continue;
         }
@@ -766,14 +832,22 @@ fn io_error_message(error: &std::io::Error) -> String {
 ///
 /// Used by `--checksum` (`-c`) mode to compare file contents instead of
 /// mtime+size quick-check. Returns `true` when checksums match (skip transfer).
+/// A digest still valid in `scan_cache` is used without reading the file, and
+/// a freshly computed one is recorded there.
 ///
 /// upstream: checksum.c:402 `file_checksum()` - plain hash, no seed
 fn file_checksum_matches(
     path: &Path,
-    file_size: u64,
+    dest_meta: &fs::Metadata,
     algorithm: protocol::ChecksumAlgorithm,
     expected: &[u8],
+    scan_cache: Option<&DestScanCache>,
 ) -> bool {
+    if let Some(cached) = scan_cache.and_then(|cache| cache.lookup(path, dest_meta)) {
+        let cmp_len = expected.len().min(cached.len());
+        return cached[..cmp_len] == expected[..cmp_len];
+    }
+    let file_size = dest_meta.len();
     let Ok(mut file) = fs::File::open(path) else {
         return false;
     };
@@ -791,6 +865,9 @@ fn file_checksum_matches(
     }
     let mut digest = [0u8; ChecksumVerifier::MAX_DIGEST_LEN];
     let len = hasher.finalize_into(&mut digest);
+    if let Some(cache) = scan_cache {
+        cache.record(path, dest_meta, &digest[..len]);
+    }
     // upstream: flist_csum_len determines comparison length
     let cmp_len = expected.len().min(len);
     digest[..cmp_len] == expected[..cmp_len]
@@ -1316,7 +1393,9 @@ mod modify_window_tests {
         let mut entry = FileEntry::new_file("payload.bin".into(), payload.len() as u64, 0o644);
         entry.set_mtime(src_secs, src_nsec);
 
-        quick_check_matches(&entry, &dest_path, &dest_meta, true, false, None, window)
+        quick_check_matches(
+            &entry, &dest_path, &dest_meta, true, false, None, window, None,
+        )
     }
 
     /// A destination whose mtime is within `--modify-window=2` of the source
@@ -2001,3 +2080,21 @@ mod non_regular_alt_dest_tests {
         assert!(!handled2, "a differing device rdev must not match");
     }
 }
+
+#[cfg(test)]
+mod quick_stat_covers_tests {
+    use metadata::MetadataOptions;
+
+    use super::quick_stat_covers;
+
+    #[test]
+    fn fields_outside_the_quick_stat_force_a_full_stat() {
+        assert!(quick_stat_covers(&MetadataOptions::new()));
+        assert!(!quick_stat_covers(
+            &MetadataOptions::new().preserve_fileflags(true)
+        ));
+        assert!(!quick_stat_covers(
+            &MetadataOptions::new().preserve_atimes(true)
+        ));
+    }
+}
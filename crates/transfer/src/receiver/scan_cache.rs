@@ -0,0 +1,383 @@
+//! Destination scan cache for `--checksum` quick-checks (`--cache-dir`).
+//!
+//! oc-rsync extension with no upstream equivalent. Under `--checksum` the
+//! quick-check reads every same-sized destination file in full to compare its
+//! digest with the sender's, so re-syncing an unchanged multi-million-file
+//! tree costs a complete read of the destination on every run. The cache
+//! remembers each digest together with the file's device, inode, size, mtime
+//! and ctime; a later run that finds all five unchanged reuses the digest
+//! instead of reading the file. The destination is still stat'ed - the stat
+//! is what validates the entry - but the reads are skipped.
+//!
+//! One cache file per destination root lives under the cache directory, named
+//! by the XXH64 of the canonical root path. Only entries consulted during a
+//! run are written back, so files that left the transfer are pruned. A cache
+//! built with a different checksum algorithm is discarded on load, and a
+//! damaged or foreign file is treated as empty.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use checksums::strong::Xxh64;
+
+/// Leading bytes of every cache file.
+const MAGIC: &[u8; 4] = b"OCSC";
+
+/// On-disk format version.
+const VERSION: u32 = 1;
+
+/// Stat fields that must be unchanged for a cached digest to be reused.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+struct FileStamp {
+    dev: u64,
+    ino: u64,
+    size: u64,
+    mtime: i64,
+    mtime_nsec: u32,
+    ctime: i64,
+    ctime_nsec: u32,
+}
+
+impl FileStamp {
+    #[cfg(unix)]
+    fn from_metadata(meta: &fs::Metadata) -> Self {
+        use std::os::unix::fs::MetadataExt;
+        Self {
+            dev: meta.dev(),
+            ino: meta.ino(),
+            size: meta.len(),
+            mtime: meta.mtime(),
+            mtime_nsec: meta.mtime_nsec() as u32,
+            ctime: meta.ctime(),
+            ctime_nsec: meta.ctime_nsec() as u32,
+        }
+    }
+
+    /// Without inode numbers the stamp falls back to size and mtime.
+    #[cfg(not(unix))]
+    fn from_metadata(meta: &fs::Metadata) -> Self {
+        let modified = meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .unwrap_or_default();
+        Self {
+            dev: 0,
+            ino: 0,
+            size: meta.len(),
+            mtime: modified.as_secs() as i64,
+            mtime_nsec: modified.subsec_nanos(),
+            ctime: 0,
+            ctime_nsec: 0,
+        }
+    }
+}
+
+/// A cached digest and the stamp it was computed under.
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct CacheEntry {
+    stamp: FileStamp,
+    digest: Vec<u8>,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    /// Entries loaded from the previous run.
+    previous: HashMap<Vec<u8>, CacheEntry>,
+    /// Entries confirmed or computed during this run.
+    current: HashMap<Vec<u8>, CacheEntry>,
+}
+
+/// Persistent per-destination cache of whole-file checksums.
+///
+/// Lookups and records take `&self` because the quick-check runs behind
+/// shared receiver borrows; the maps sit behind a mutex.
+#[derive(Debug)]
+pub(in crate::receiver) struct DestScanCache {
+    file: PathBuf,
+    root: PathBuf,
+    algorithm: &'static str,
+    state: Mutex<CacheState>,
+}
+
+impl DestScanCache {
+    /// Opens the cache for `dest_root` under `cache_dir`.
+    ///
+    /// A missing, unreadable or incompatible cache file yields an empty cache;
+    /// the cache only ever saves work, so it never fails the transfer.
+    pub(in crate::receiver) fn load(
+        cache_dir: &Path,
+        dest_root: &Path,
+        algorithm: protocol::ChecksumAlgorithm,
+    ) -> Self {
+        let canonical = fs::canonicalize(dest_root).unwrap_or_else(|_| dest_root.to_path_buf());
+        let key = Xxh64::digest(0, canonical.as_os_str().as_encoded_bytes());
+        let file = cache_dir.join(format!("{:016x}", u64::from_le_bytes(key)));
+        let algorithm = algorithm.as_str();
+        let previous = fs::read(&file)
+            .ok()
+            .and_then(|bytes| decode(&bytes, algorithm))
+            .unwrap_or_default();
+        Self {
+            file,
+            root: dest_root.to_path_buf(),
+            algorithm,
+            state: Mutex::new(CacheState {
+                previous,
+                current: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Returns the cached digest of `path` when its stat matches `meta`.
+    ///
+    /// A hit is carried forward into the cache written by [`Self::save`].
+    pub(in crate::receiver) fn lookup(&self, path: &Path, meta: &fs::Metadata) -> Option<Vec<u8>> {
+        let key = self.key(path)?;
+        let stamp = FileStamp::from_metadata(meta);
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = state
+            .previous
+            .get(&key)
+            .filter(|e| e.stamp == stamp)?
+            .clone();
+        let digest = entry.digest.clone();
+        state.current.insert(key, entry);
+        Some(digest)
+    }
+
+    /// Records the digest of `path` computed while it had the stat `meta`.
+    pub(in crate::receiver) fn record(&self, path: &Path, meta: &fs::Metadata, digest: &[u8]) {
+        let Some(key) = self.key(path) else {
+            return;
+        };
+        let entry = CacheEntry {
+            stamp: FileStamp::from_metadata(meta),
+            digest: digest.to_vec(),
+        };
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.current.insert(key, entry);
+    }
+
+    /// Number of entries that will be written by [`Self::save`].
+    pub(in crate::receiver) fn len(&self) -> usize {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.current.len()
+    }
+
+    /// Writes this run's entries back, replacing the previous cache file.
+    ///
+    /// Does nothing when the run consulted no entry, so a transfer that never
+    /// reached the quick-check leaves the previous cache intact. The file is
+    /// written beside its final name and renamed into place.
+    pub(in crate::receiver) fn save(&self) -> io::Result<()> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.current.is_empty() {
+            return Ok(());
+        }
+        if let Some(dir) = self.file.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let bytes = encode(&state.current, self.algorithm);
+        let tmp = self
+            .file
+            .with_extension(format!("tmp.{}", std::process::id()));
+        if let Err(err) = fs::write(&tmp, bytes).and_then(|()| fs::rename(&tmp, &self.file)) {
+            let _ = fs::remove_file(&tmp);
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Keys entries by their path below the destination root so the cache
+    /// stays valid however the root itself was spelled.
+    fn key(&self, path: &Path) -> Option<Vec<u8>> {
+        path.strip_prefix(&self.root)
+            .ok()
+            .map(|rel| rel.as_os_str().as_encoded_bytes().to_vec())
+    }
+}
+
+fn encode(entries: &HashMap<Vec<u8>, CacheEntry>, algorithm: &str) -> Vec<u8> {
+    let mut out = Vec::with_capacity(16 + entries.len() * 96);
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&VERSION.to_le_bytes());
+    out.push(algorithm.len() as u8);
+    out.extend_from_slice(algorithm.as_bytes());
+    for (key, entry) in entries {
+        let stamp = &entry.stamp;
+        out.extend_from_slice(&(key.len() as u32).to_le_bytes());
+        out.extend_from_slice(key);
+        out.extend_from_slice(&stamp.dev.to_le_bytes());
+        out.extend_from_slice(&stamp.ino.to_le_bytes());
+        out.extend_from_slice(&stamp.size.to_le_bytes());
+        out.extend_from_slice(&stamp.mtime.to_le_bytes());
+        out.extend_from_slice(&stamp.mtime_nsec.to_le_bytes());
+        out.extend_from_slice(&stamp.ctime.to_le_bytes());
+        out.extend_from_slice(&stamp.ctime_nsec.to_le_bytes());
+        out.push(entry.digest.len() as u8);
+        out.extend_from_slice(&entry.digest);
+    }
+    out
+}
+
+/// Parses a cache file, returning `None` when it is damaged or was written
+/// for another format version or checksum algorithm.
+fn decode(bytes: &[u8], algorithm: &str) -> Option<HashMap<Vec<u8>, CacheEntry>> {
+    let mut cursor = Cursor(bytes);
+    if cursor.take(MAGIC.len())? != MAGIC || cursor.u32()? != VERSION {
+        return None;
+    }
+    let alg_len = usize::from(cursor.u8()?);
+    if cursor.take(alg_len)? != algorithm.as_bytes() {
+        return None;
+    }
+    let mut entries = HashMap::new();
+    while !cursor.0.is_empty() {
+        let key_len = cursor.u32()? as usize;
+        let key = cursor.take(key_len)?.to_vec();
+        let stamp = FileStamp {
+            dev: cursor.u64()?,
+            ino: cursor.u64()?,
+            size: cursor.u64()?,
+            mtime: cursor.u64()? as i64,
+            mtime_nsec: cursor.u32()?,
+            ctime: cursor.u64()? as i64,
+            ctime_nsec: cursor.u32()?,
+        };
+        let digest_len = usize::from(cursor.u8()?);
+        let digest = cursor.take(digest_len)?.to_vec();
+        entries.insert(key, CacheEntry { stamp, digest });
+    }
+    Some(entries)
+}
+
+/// Bounds-checked little-endian reader over the cache file bytes.
+struct Cursor<'a>(&'a [u8]);
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None;
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Some(head)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take(1).map(|b| b[0])
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.take(4)?.try_into().ok().map(u32::from_le_bytes)
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        self.take(8)?.try_into().ok().map(u64::from_le_bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use protocol::ChecksumAlgorithm;
+
+    fn setup() -> (tempfile::TempDir, PathBuf, PathBuf) {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let cache_dir = tmp.path().join("cache");
+        let dest = tmp.path().join("dest");
+        fs::create_dir(&dest).expect("mkdir dest");
+        (tmp, cache_dir, dest)
+    }
+
+    fn meta(path: &Path) -> fs::Metadata {
+        fs::symlink_metadata(path).expect("stat")
+    }
+
+    #[test]
+    fn recorded_digest_survives_a_reload() {
+        let (_tmp, cache_dir, dest) = setup();
+        let file = dest.join("a.txt");
+        fs::write(&file, b"payload").expect("write");
+
+        let cache = DestScanCache::load(&cache_dir, &dest, ChecksumAlgorithm::MD5);
+        assert_eq!(cache.lookup(&file, &meta(&file)), None);
+        cache.record(&file, &meta(&file), &[1, 2, 3, 4]);
+        cache.save().expect("save");
+
+        let reloaded = DestScanCache::load(&cache_dir, &dest, ChecksumAlgorithm::MD5);
+        assert_eq!(reloaded.lookup(&file, &meta(&file)), Some(vec![1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn changed_file_misses() {
+        let (_tmp, cache_dir, dest) = setup();
+        let file = dest.join("a.txt");
+        fs::write(&file, b"payload").expect("write");
+
+        let cache = DestScanCache::load(&cache_dir, &dest, ChecksumAlgorithm::MD5);
+        cache.record(&file, &meta(&file), &[9; 16]);
+        cache.save().expect("save");
+
+        fs::write(&file, b"payload, longer").expect("rewrite");
+        let reloaded = DestScanCache::load(&cache_dir, &dest, ChecksumAlgorithm::MD5);
+        assert_eq!(reloaded.lookup(&file, &meta(&file)), None);
+    }
+
+    #[test]
+    fn other_algorithm_discards_the_cache() {
+        let (_tmp, cache_dir, dest) = setup();
+        let file = dest.join("a.txt");
+        fs::write(&file, b"payload").expect("write");
+
+        let cache = DestScanCache::load(&cache_dir, &dest, ChecksumAlgorithm::MD5);
+        cache.record(&file, &meta(&file), &[9; 16]);
+        cache.save().expect("save");
+
+        let reloaded = DestScanCache::load(&cache_dir, &dest, ChecksumAlgorithm::XXH128);
+        assert_eq!(reloaded.lookup(&file, &meta(&file)), None);
+    }
+
+    #[test]
+    fn unconsulted_entries_are_pruned_on_save() {
+        let (_tmp, cache_dir, dest) = setup();
+        let kept = dest.join("kept");
+        let gone = dest.join("gone");
+        fs::write(&kept, b"k").expect("write");
+        fs::write(&gone, b"g").expect("write");
+
+        let cache = DestScanCache::load(&cache_dir, &dest, ChecksumAlgorithm::MD5);
+        cache.record(&kept, &meta(&kept), &[1]);
+        cache.record(&gone, &meta(&gone), &[2]);
+        cache.save().expect("save");
+
+        let second = DestScanCache::load(&cache_dir, &dest, ChecksumAlgorithm::MD5);
+        assert!(second.lookup(&kept, &meta(&kept)).is_some());
+        assert_eq!(second.len(), 1);
+        second.save().expect("save");
+
+        let third = DestScanCache::load(&cache_dir, &dest, ChecksumAlgorithm::MD5);
+        assert!(third.lookup(&gone, &meta(&gone)).is_none());
+        assert!(third.lookup(&kept, &meta(&kept)).is_some());
+    }
+
+    #[test]
+    fn damaged_cache_file_loads_empty() {
+        let (_tmp, cache_dir, dest) = setup();
+        let file = dest.join("a.txt");
+        fs::write(&file, b"payload").expect("write");
+
+        let cache = DestScanCache::load(&cache_dir, &dest, ChecksumAlgorithm::MD5);
+        cache.record(&file, &meta(&file), &[7; 16]);
+        cache.save().expect("save");
+        let bytes = fs::read(&cache.file).expect("read cache");
+        fs::write(&cache.file, &bytes[..bytes.len() - 3]).expect("truncate");
+
+        let reloaded = DestScanCache::load(&cache_dir, &dest, ChecksumAlgorithm::MD5);
+        assert_eq!(reloaded.lookup(&file, &meta(&file)), None);
+    }
+}
@@ -0,0 +1,259 @@
+//! File-selection predicates that decide whether a regular file is requested.
+//!
+//! Collects the `--ignore-existing`, `--existing`, `--max-size`/`--min-size`
+//! and `--update` checks the generator runs before quick-check, in upstream's
+//! evaluation order, so every candidate path applies them identically. The
+//! quick-check itself (`--size-only`, `--ignore-times`, `--checksum`,
+//! `--modify-window`) stays in [`quick_check`](super::quick_check).
+//!
+//! # Upstream Reference
+//!
+//! - `generator.c:1380-1395` - `--existing` skips an absent destination
+//! - `generator.c:1397-1411` - `--ignore-existing` skips a present destination
+//! - `generator.c:1704-1718` - `--max-size` / `--min-size` window
+//! - `generator.c:1720-1726` - `--update` skips a newer same-type destination
+
+use std::fs;
+use std::path::Path;
+
+use protocol::flist::FileEntry;
+
+use crate::config::ServerConfig;
+
+use super::quick_check::{dest_mtime_newer, dest_type_matches_source};
+
+/// Why a regular file is left out of the transfer before quick-check runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SkipReason {
+    /// `--existing`: the destination does not exist yet.
+    NotCreating,
+    /// `--ignore-existing`: the destination already exists.
+    Exists,
+    /// The source is larger than `--max-size`.
+    OverMaxSize,
+    /// The source is smaller than `--min-size`.
+    UnderMinSize,
+    /// `--update`: the destination is the same type and newer.
+    Newer,
+}
+
+impl SkipReason {
+    /// Returns the SKIP-gated notice upstream prints for this reason.
+    ///
+    /// `--ignore-existing` gains a parenthesised suffix at `SKIP2`; callers
+    /// pass it in `exists_suffix` (empty otherwise).
+    pub(super) fn notice(self, name: &str, exists_suffix: &str) -> String {
+        match self {
+            Self::NotCreating => format!("not creating new file \"{name}\"\n"),
+            Self::Exists => format!("{name} exists{exists_suffix}\n"),
+            Self::OverMaxSize => format!("{name} is over max-size\n"),
+            Self::UnderMinSize => format!("{name} is under min-size\n"),
+            Self::Newer => format!("{name} is newer\n"),
+        }
+    }
+}
+
+/// Snapshot of the file-selection options consulted per candidate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) struct FileSelection {
+    /// `--ignore-existing`.
+    pub(super) ignore_existing: bool,
+    /// `--existing` / `--ignore-non-existing`.
+    pub(super) existing_only: bool,
+    /// `--update`.
+    pub(super) update_only: bool,
+    /// `--min-size`.
+    pub(super) min_size: Option<u64>,
+    /// `--max-size`.
+    pub(super) max_size: Option<u64>,
+}
+
+impl FileSelection {
+    /// Extracts the selection options from the receiver's configuration.
+    pub(super) fn from_config(config: &ServerConfig) -> Self {
+        Self {
+            ignore_existing: config.file_selection.ignore_existing,
+            existing_only: config.file_selection.existing_only,
+            update_only: config.flags.update,
+            min_size: config.file_selection.min_file_size,
+            max_size: config.file_selection.max_file_size,
+        }
+    }
+
+    /// Returns `true` when either size bound is set.
+    pub(super) const fn has_size_bounds(&self) -> bool {
+        self.min_size.is_some() || self.max_size.is_some()
+    }
+
+    /// Applies the `--max-size` / `--min-size` window to a source length.
+    ///
+    /// Over max-size is tested before under min-size, matching upstream.
+    pub(super) fn size_skip(&self, size: u64) -> Option<SkipReason> {
+        if self.max_size.is_some_and(|max| size > max) {
+            return Some(SkipReason::OverMaxSize);
+        }
+        if self.min_size.is_some_and(|min| size < min) {
+            return Some(SkipReason::UnderMinSize);
+        }
+        None
+    }
+
+    /// Runs every pre-quick-check predicate for `entry` against the
+    /// destination stat, returning the first one that rejects the file.
+    ///
+    /// `dest_meta` is `None` when the destination does not exist. The order
+    /// is upstream's: `--existing` or `--ignore-existing` first (they depend
+    /// only on whether the destination exists), then the size window, then
+    /// `--update`.
+    pub(super) fn skip_reason(
+        &self,
+        entry: &FileEntry,
+        dest_path: &Path,
+        dest_meta: Option<&fs::Metadata>,
+    ) -> Option<SkipReason> {
+        match dest_meta {
+            None if self.existing_only => return Some(SkipReason::NotCreating),
+            Some(_) if self.ignore_existing => return Some(SkipReason::Exists),
+            _ => {}
+        }
+        if let Some(reason) = self.size_skip(entry.size()) {
+            return Some(reason);
+        }
+        // upstream: generator.c:1721 - the `-u` skip is guarded by
+        // `stype == ftype`, so a type mismatch always transfers.
+        if self.update_only
+            && let Some(meta) = dest_meta
+            && dest_type_matches_source(dest_path, entry)
+            && dest_mtime_newer(meta, entry)
+        {
+            return Some(SkipReason::Newer);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_entry(size: u64) -> FileEntry {
+        FileEntry::new_file("file.txt".into(), size, 0o644)
+    }
+
+    #[test]
+    fn default_selection_skips_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file.txt");
+        fs::write(&path, b"data").unwrap();
+        let meta = fs::metadata(&path).unwrap();
+
+        let selection = FileSelection::default();
+        assert_eq!(
+            selection.skip_reason(&file_entry(4), &path, Some(&meta)),
+            None
+        );
+        assert_eq!(selection.skip_reason(&file_entry(4), &path, None), None);
+    }
+
+    #[test]
+    fn existing_only_skips_absent_destination() {
+        let selection = FileSelection {
+            existing_only: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            selection.skip_reason(&file_entry(4), Path::new("missing"), None),
+            Some(SkipReason::NotCreating)
+        );
+    }
+
+    #[test]
+    fn ignore_existing_precedes_size_window() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file.txt");
+        fs::write(&path, b"data").unwrap();
+        let meta = fs::metadata(&path).unwrap();
+
+        let selection = FileSelection {
+            ignore_existing: true,
+            max_size: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            selection.skip_reason(&file_entry(100), &path, Some(&meta)),
+            Some(SkipReason::Exists)
+        );
+        assert_eq!(
+            selection.skip_reason(&file_entry(100), &path, None),
+            Some(SkipReason::OverMaxSize)
+        );
+    }
+
+    #[test]
+    fn size_window_checks_max_before_min() {
+        let selection = FileSelection {
+            min_size: Some(10),
+            max_size: Some(5),
+            ..Default::default()
+        };
+        assert_eq!(selection.size_skip(7), Some(SkipReason::OverMaxSize));
+
+        let selection = FileSelection {
+            min_size: Some(10),
+            max_size: Some(100),
+            ..Default::default()
+        };
+        assert_eq!(selection.size_skip(3), Some(SkipReason::UnderMinSize));
+        assert_eq!(selection.size_skip(10), None);
+        assert_eq!(selection.size_skip(100), None);
+        assert!(selection.has_size_bounds());
+    }
+
+    #[test]
+    fn update_skips_newer_destination_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file.txt");
+        fs::write(&path, b"data").unwrap();
+        let meta = fs::metadata(&path).unwrap();
+
+        let selection = FileSelection {
+            update_only: true,
+            ..Default::default()
+        };
+
+        let mut older_source = file_entry(4);
+        older_source.set_mtime(1, 0);
+        assert_eq!(
+            selection.skip_reason(&older_source, &path, Some(&meta)),
+            Some(SkipReason::Newer)
+        );
+
+        let mut newer_source = file_entry(4);
+        newer_source.set_mtime(i64::from(i32::MAX), 0);
+        assert_eq!(
+            selection.skip_reason(&newer_source, &path, Some(&meta)),
+            None
+        );
+    }
+
+    #[test]
+    fn notices_match_upstream_wording() {
+        assert_eq!(
+            SkipReason::NotCreating.notice("a", ""),
+            "not creating new file \"a\"\n"
+        );
+        assert_eq!(
+            SkipReason::Exists.notice("a", " (uptodate)"),
+            "a exists (uptodate)\n"
+        );
+        assert_eq!(
+            SkipReason::OverMaxSize.notice("a", ""),
+            "a is over max-size\n"
+        );
+        assert_eq!(
+            SkipReason::UnderMinSize.notice("a", ""),
+            "a is under min-size\n"
+        );
+        assert_eq!(SkipReason::Newer.notice("a", ""), "a is newer\n");
+    }
+}
@@ -123,11 +123,13 @@ pub struct TransferStats {
     ///
     /// - `flist.c:2553`: `write_int(f, ignore_errors ? 0 : io_error);`
     pub io_error: i32,
-    /// Number of `MSG_ERROR` messages received from the remote sender.
+    /// Number of `MSG_ERROR_XFER` messages received from the remote sender.
     ///
-    /// When the sender encounters per-file errors it sends `MSG_ERROR` frames
-    /// that the receiver tallies here. A non-zero count causes the exit code
-    /// to report a partial transfer (`RERR_PARTIAL`, exit 23).
+    /// When the sender encounters per-file errors it sends `MSG_ERROR_XFER`
+    /// frames that the receiver tallies here. A non-zero count causes the exit
+    /// code to report a partial transfer (`RERR_PARTIAL`, exit 23).
+    ///
+    /// upstream: log.c:311 `got_xfer_error = 1`, main.c:1635.
     pub error_count: u32,
 
     // Incremental mode statistics
@@ -163,6 +165,25 @@ pub struct TransferStats {
     /// - `main.c:1367` - `deletion_count >= max_delete` triggers exit 25
     pub delete_limit_exceeded: bool,
 
+    /// Whether the `--stop-at` / `--stop-after` deadline cut the run short.
+    ///
+    /// Files not yet requested when the deadline passed were skipped, but the
+    /// phase and goodbye exchange completed normally, so a restarted run picks
+    /// up where this one stopped. The caller should report exit code 30
+    /// (`RERR_TIMEOUT`).
+    ///
+    /// # Upstream Reference
+    ///
+    /// - `io.c:check_timeout()` - `stop_at_utime` exits with `RERR_TIMEOUT`
+    pub stop_limit_reached: bool,
+
+    /// Files left untransferred because the stop limit was reached, relative
+    /// to the transfer root and in file-list order.
+    ///
+    /// oc-rsync extension backing `--remaining-files`; empty unless
+    /// [`Self::stop_limit_reached`] is set.
+    pub remaining_files: Vec<PathBuf>,
+
     /// Total literal (new) data bytes written during delta application.
     ///
     /// Accumulated from per-file delta token processing. Literal tokens carry
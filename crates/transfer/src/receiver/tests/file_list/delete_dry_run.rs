@@ -0,0 +1,137 @@
+//! `--dry-run` on the network receiver's delete pass.
+//!
+//! Upstream still walks the destination, counts every extraneous entry and
+//! logs its `deleting` line under `--dry-run`; only the unlink itself is a
+//! no-op (`syscall.c` `do_unlink()`/`do_rmdir()` return 0 when `dry_run`).
+//! These tests pin that at each removal site the receiver uses: the immediate
+//! parallel pass, the deferred `--delete-delay` executor, and the capped serial
+//! executor (`--max-delete`).
+
+use std::ffi::OsString;
+
+use protocol::flist::FileEntry;
+
+use super::super::super::ReceiverContext;
+use super::super::support::{CapturingDeletionWriter, test_config, test_handshake};
+
+/// Builds a dry-run receiver whose file list keeps only `keep.txt`.
+fn build_receiver(
+    dest: &std::path::Path,
+    late_delete: bool,
+    max_delete: Option<u64>,
+) -> ReceiverContext {
+    let handshake = test_handshake();
+    let mut config = test_config();
+    config.flags.delete = true;
+    config.flags.dry_run = true;
+    config.flags.info_flags.itemize = true;
+    config.deletion.delete_after = false;
+    config.deletion.late_delete = late_delete;
+    config.deletion.max_delete = max_delete;
+    config.args = vec![OsString::from(dest.to_str().unwrap())];
+
+    let mut ctx = ReceiverContext::new_for_test(&handshake, config);
+    ctx.file_list
+        .push(FileEntry::new_directory(".".into(), 0o755));
+    ctx.file_list
+        .push(FileEntry::new_file("keep.txt".into(), 6, 0o644));
+    ctx
+}
+
+/// Populates `dest` with the listed file, one extraneous file, and an
+/// extraneous directory holding one file.
+fn populate(dest: &std::path::Path) {
+    std::fs::write(dest.join("keep.txt"), b"listed").unwrap();
+    std::fs::write(dest.join("stale.txt"), b"extraneous").unwrap();
+    std::fs::create_dir(dest.join("stale_dir")).unwrap();
+    std::fs::write(dest.join("stale_dir/inner.txt"), b"nested").unwrap();
+}
+
+/// The `*deleting` lines every removal site reports for [`populate`]: the
+/// doomed directory's contents before the directory, root entries in
+/// reverse-sorted order (upstream delete.c:delete_dir_contents() and
+/// generator.c:delete_in_dir()).
+const EXPECTED_LINES: [&str; 3] = [
+    "*deleting   stale_dir/inner.txt",
+    "*deleting   stale_dir/",
+    "*deleting   stale.txt",
+];
+
+fn assert_untouched(dest: &std::path::Path) {
+    assert!(dest.join("keep.txt").exists(), "listed file must survive");
+    assert!(
+        dest.join("stale.txt").exists(),
+        "dry run must not unlink an extraneous file"
+    );
+    assert!(
+        dest.join("stale_dir/inner.txt").exists(),
+        "dry run must not remove an extraneous directory"
+    );
+}
+
+#[test]
+fn immediate_dry_run_counts_without_deleting() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let dest = dir.path();
+    populate(dest);
+
+    let ctx = build_receiver(dest, false, None);
+    let mut writer = CapturingDeletionWriter::default();
+    let (stats, limit_exceeded, io_bits) = ctx
+        .delete_extraneous_files(dest, None, &mut writer)
+        .unwrap();
+
+    assert_untouched(dest);
+    assert!(!limit_exceeded);
+    assert_eq!(io_bits, 0);
+    assert_eq!(stats.files, 2, "stale.txt and stale_dir/inner.txt reported");
+    assert_eq!(stats.dirs, 1, "stale_dir reported");
+    assert_eq!(stats.symlinks, 0);
+    assert_eq!(writer.lines, EXPECTED_LINES);
+}
+
+#[test]
+fn delayed_dry_run_counts_without_deleting() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let dest = dir.path();
+    populate(dest);
+
+    let ctx = build_receiver(dest, true, None);
+    let mut writer = CapturingDeletionWriter::default();
+    let (victims, collect_bits) = ctx
+        .collect_delayed_deletions(dest, None, &mut writer)
+        .unwrap();
+    assert_eq!(collect_bits, 0);
+    assert!(writer.lines.is_empty(), "collecting must not report yet");
+    let (stats, io_bits) = ctx
+        .execute_delayed_deletions(dest, None, &victims, &mut writer)
+        .unwrap();
+
+    assert_untouched(dest);
+    assert_eq!(io_bits, 0);
+    assert_eq!(stats.files, 2, "stale.txt and stale_dir/inner.txt reported");
+    assert_eq!(stats.dirs, 1, "stale_dir reported");
+    assert_eq!(stats.symlinks, 0);
+    assert_eq!(writer.lines, EXPECTED_LINES);
+}
+
+#[test]
+fn capped_dry_run_counts_without_deleting() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let dest = dir.path();
+    populate(dest);
+
+    let ctx = build_receiver(dest, false, Some(100));
+    let mut writer = CapturingDeletionWriter::default();
+    let (stats, limit_exceeded, io_bits) = ctx
+        .delete_extraneous_files(dest, None, &mut writer)
+        .unwrap();
+
+    assert_untouched(dest);
+    assert!(!limit_exceeded);
+    assert_eq!(io_bits, 0);
+    assert_eq!(stats.files, 2, "stale.txt and stale_dir/inner.txt reported");
+    assert_eq!(stats.dirs, 1, "stale_dir reported");
+    assert_eq!(stats.symlinks, 0);
+    assert_eq!(writer.lines, EXPECTED_LINES);
+}
@@ -0,0 +1,94 @@
+//! The receiver's delete pass after an I/O error.
+//!
+//! Upstream refuses to delete once `IOERR_GENERAL` is set: an unreadable source
+//! entry may be absent from the file list, so its destination counterpart would
+//! look extraneous. `--ignore-errors` restores the sweep (generator.c:298-305
+//! `delete_in_dir()`).
+
+use std::ffi::OsString;
+
+use protocol::flist::FileEntry;
+
+use super::super::super::ReceiverContext;
+use super::super::super::stats::TransferStats;
+use super::super::super::transfer::DeletePassPhase;
+use super::super::support::{TestDeletionWriter, test_config, test_handshake};
+use crate::generator::io_error_flags::{IOERR_GENERAL, IOERR_VANISHED};
+
+/// Builds a `--delete-before` receiver whose file list keeps only `keep.txt`
+/// and populates `dest` with it plus one extraneous `stale.txt`.
+fn build_receiver(dest: &std::path::Path, ignore_errors: bool) -> ReceiverContext {
+    std::fs::write(dest.join("keep.txt"), b"listed").unwrap();
+    std::fs::write(dest.join("stale.txt"), b"extraneous").unwrap();
+
+    let handshake = test_handshake();
+    let mut config = test_config();
+    config.flags.delete = true;
+    config.deletion.ignore_errors = ignore_errors;
+    config.args = vec![OsString::from(dest.to_str().unwrap())];
+    let mut ctx = ReceiverContext::new_for_test(&handshake, config);
+    ctx.file_list
+        .push(FileEntry::new_directory(".".into(), 0o755));
+    ctx.file_list
+        .push(FileEntry::new_file("keep.txt".into(), 6, 0o644));
+    ctx
+}
+
+fn run_early_pass(ctx: &mut ReceiverContext, dest: &std::path::Path, io_error: i32) {
+    let mut stats = TransferStats {
+        io_error,
+        ..Default::default()
+    };
+    ctx.run_receiver_delete_pass(
+        DeletePassPhase::Early,
+        dest,
+        #[cfg(unix)]
+        None,
+        &mut TestDeletionWriter,
+        &mut stats,
+    )
+    .unwrap();
+}
+
+#[test]
+fn general_io_error_skips_deletion() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let dest = temp_dir.path();
+    let mut ctx = build_receiver(dest, false);
+
+    run_early_pass(&mut ctx, dest, IOERR_GENERAL);
+
+    assert!(
+        dest.join("stale.txt").exists(),
+        "IOERR_GENERAL without --ignore-errors must skip the delete pass"
+    );
+}
+
+#[test]
+fn ignore_errors_deletes_despite_io_error() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let dest = temp_dir.path();
+    let mut ctx = build_receiver(dest, true);
+
+    run_early_pass(&mut ctx, dest, IOERR_GENERAL);
+
+    assert!(
+        !dest.join("stale.txt").exists(),
+        "--ignore-errors must delete"
+    );
+    assert!(dest.join("keep.txt").exists(), "listed file must survive");
+}
+
+#[test]
+fn vanished_source_does_not_block_deletion() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let dest = temp_dir.path();
+    let mut ctx = build_receiver(dest, false);
+
+    run_early_pass(&mut ctx, dest, IOERR_VANISHED);
+
+    assert!(
+        !dest.join("stale.txt").exists(),
+        "only IOERR_GENERAL gates deletion"
+    );
+}
@@ -168,6 +168,8 @@ fn transfer_stats_has_incremental_fields() {
         delete_stats: DeleteStats::new(),
         created_stats: protocol::stats::CreatedStats::new(),
         delete_limit_exceeded: false,
+        stop_limit_reached: false,
+        remaining_files: Vec::new(),
         literal_data: 0,
         matched_data: 0,
         redo_count: 0,
@@ -29,6 +29,10 @@
 //! - [`delete_backup`] - `--backup` / `--backup-dir` preservation of each
 //!   extraneous file victim before the receiver's delete pass unlinks it,
 //!   across the immediate, delayed, and capped removal sites.
+//! - [`delete_dry_run`] - `--dry-run` delete passes that count and report
+//!   every victim without removing it.
+//! - [`delete_io_error`] - the delete pass skipped after `IOERR_GENERAL`
+//!   unless `--ignore-errors` is set.
 //! - [`iconv_wire_order`] - regression coverage for the receiver-side
 //!   `--iconv` ordering invariant (file_list stays in sender wire-emit
 //!   order, never re-sorted on local-charset bytes).
@@ -36,6 +40,9 @@
 mod dedup;
 #[cfg(unix)]
 mod delete_backup;
+#[cfg(unix)]
+mod delete_dry_run;
+mod delete_io_error;
 mod delete_pipeline_hook;
 mod delete_timing;
 mod filter_chain;
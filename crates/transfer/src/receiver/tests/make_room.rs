@@ -0,0 +1,140 @@
+//! Directories in the way of regular files and symlinks.
+//!
+//! An empty destination directory always makes way for a non-directory. A
+//! non-empty one is removed only under `--delete` or `--force`, with each
+//! removed descendant reported as deleted; otherwise the entry is skipped and
+//! counted as a transfer error (generator.c:recv_generator() `DEL_RECURSE`,
+//! delete.c:delete_item() `DEL_MAKE_ROOM`).
+
+use std::path::Path;
+
+use metadata::MetadataOptions;
+use protocol::flist::FileEntry;
+
+use super::super::ReceiverContext;
+use super::super::stats::TransferStats;
+use super::support::{CapturingDeletionWriter, test_config, test_handshake};
+
+fn receiver(entries: Vec<FileEntry>, force_delete: bool) -> ReceiverContext {
+    let mut config = test_config();
+    config.flags.links = true;
+    config.deletion.force_delete = force_delete;
+    let mut ctx = ReceiverContext::new_for_test(&test_handshake(), config);
+    ctx.file_list = entries;
+    ctx
+}
+
+/// Returns the file-list indices the candidate pass queued for transfer.
+fn queued(ctx: &ReceiverContext, dest: &Path, writer: &mut CapturingDeletionWriter) -> Vec<usize> {
+    let mut errors = Vec::new();
+    let mut stats = TransferStats::default();
+    ctx.build_files_to_transfer(
+        writer,
+        dest,
+        None,
+        &MetadataOptions::default(),
+        None,
+        &mut errors,
+        &mut stats,
+        None,
+        None,
+    )
+    .into_iter()
+    .map(|(idx, ..)| idx)
+    .collect()
+}
+
+fn populate(dir: &Path) {
+    std::fs::create_dir_all(dir.join("sub")).unwrap();
+    std::fs::write(dir.join("inner.txt"), b"x").unwrap();
+    std::fs::write(dir.join("sub/deep.txt"), b"y").unwrap();
+}
+
+#[test]
+fn empty_directory_makes_way_for_file() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let dest = temp_dir.path();
+    std::fs::create_dir(dest.join("a")).unwrap();
+    let ctx = receiver(vec![FileEntry::new_file("a".into(), 4, 0o644)], false);
+    let mut writer = CapturingDeletionWriter::default();
+
+    assert_eq!(queued(&ctx, dest, &mut writer), vec![0]);
+    assert!(!dest.join("a").exists());
+    assert!(writer.lines.is_empty(), "{:?}", writer.lines);
+    assert_eq!(ctx.local_xfer_errors.get(), 0);
+}
+
+#[test]
+fn non_empty_directory_blocks_file_without_force() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let dest = temp_dir.path();
+    populate(&dest.join("a"));
+    let ctx = receiver(vec![FileEntry::new_file("a".into(), 4, 0o644)], false);
+    let mut writer = CapturingDeletionWriter::default();
+
+    assert!(queued(&ctx, dest, &mut writer).is_empty());
+    assert!(dest.join("a/sub/deep.txt").exists());
+    assert_eq!(
+        ctx.local_xfer_errors.get(),
+        1,
+        "skip must count for exit 23"
+    );
+}
+
+#[test]
+fn force_removes_non_empty_directory_for_file() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let dest = temp_dir.path();
+    populate(&dest.join("a"));
+    let ctx = receiver(vec![FileEntry::new_file("a".into(), 4, 0o644)], true);
+    let mut writer = CapturingDeletionWriter::default();
+
+    assert_eq!(queued(&ctx, dest, &mut writer), vec![0]);
+    assert!(!dest.join("a").exists());
+    // Descendants are reported children-first; the directory itself is not
+    // (DEL_MAKE_ROOM suppresses its log_delete()).
+    assert_eq!(
+        writer.lines,
+        vec![
+            "*deleting   a/sub/deep.txt",
+            "*deleting   a/sub/",
+            "*deleting   a/inner.txt",
+        ]
+    );
+    assert_eq!(ctx.local_xfer_errors.get(), 0);
+}
+
+#[test]
+fn force_removes_non_empty_directory_for_symlink() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let dest = temp_dir.path();
+    populate(&dest.join("link"));
+    let ctx = receiver(
+        vec![FileEntry::new_symlink("link".into(), "target".into())],
+        true,
+    );
+    let mut writer = CapturingDeletionWriter::default();
+
+    ctx.create_symlinks(dest, None, &mut writer).unwrap();
+
+    let target = std::fs::read_link(dest.join("link")).expect("symlink created");
+    assert_eq!(target, Path::new("target"));
+    assert_eq!(writer.lines.len(), 3, "{:?}", writer.lines);
+}
+
+#[test]
+fn non_empty_directory_blocks_symlink_without_force() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let dest = temp_dir.path();
+    populate(&dest.join("link"));
+    let ctx = receiver(
+        vec![FileEntry::new_symlink("link".into(), "target".into())],
+        false,
+    );
+    let mut writer = CapturingDeletionWriter::default();
+
+    ctx.create_symlinks(dest, None, &mut writer).unwrap();
+
+    assert!(dest.join("link").is_dir());
+    assert_eq!(ctx.local_xfer_errors.get(), 1);
+}
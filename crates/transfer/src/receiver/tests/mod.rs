@@ -7,10 +7,13 @@
 //!   conversion, sparse writes, and checksum verifier coverage.
 //! - [`hard_links`] - `create_hardlinks` behaviour and the
 //!   `HardlinkApplyTracker` lifecycle.
+//! - [`make_room`] - directories removed (or kept) in the way of regular
+//!   files and symlinks under `--force`.
 //! - [`symlinks_and_devices`] - itemize emission for files, directories,
 //!   symlinks, and other special entries.
 //! - [`partial_resume`] - temp-file guard, relative-parent creation, and
 //!   reference-directory lookups used during partial/resume transfers.
+//! - [`transfer_order`] - `--order` reordering of the transfer queue.
 //! - [`errors_and_timeouts`] - error categorization, failed-directory
 //!   propagation, legacy goodbye handling, input-multiplex activation,
 //!   daemon filter set, and path-traversal rejection.
@@ -24,6 +27,8 @@ mod generator_keepalive;
 mod hard_links;
 mod incremental_flist_banner;
 #[cfg(unix)]
+mod make_room;
+#[cfg(unix)]
 mod munge_symlinks;
 mod parallel_delta_notice;
 mod partial_resume;
@@ -31,6 +36,8 @@ mod post_decision_name_emission;
 mod support;
 mod symlinks_and_devices;
 #[cfg(unix)]
+mod transfer_order;
+#[cfg(unix)]
 mod verbose_dir_names;
 #[cfg(windows)]
 mod windows_receiver_symlinks;
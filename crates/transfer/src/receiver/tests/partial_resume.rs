@@ -343,6 +343,25 @@ mod relative_parents {
         assert!(!dest.join("deep").exists());
     }
 
+    #[test]
+    fn ensure_relative_parents_reports_blocked_parent() {
+        let tmp = test_support::create_tempdir();
+        let dest = tmp.path();
+
+        // A regular file where the implied parent directory should go.
+        std::fs::write(dest.join("a"), "not a dir").unwrap();
+
+        let entries = vec![FileEntry::new_file("a/b/file.txt".into(), 100, 0o644)];
+        let ctx = receiver_with_relative(entries);
+
+        let errors = ctx.ensure_relative_parents(dest);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, dest.join("a/b"));
+        assert!(errors[0].1.starts_with("recv_generator: mkdir "));
+        assert!(dest.join("a").is_file());
+    }
+
     #[test]
     fn ensure_relative_parents_single_component_path() {
         let tmp = test_support::create_tempdir();
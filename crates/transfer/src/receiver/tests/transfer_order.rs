@@ -0,0 +1,69 @@
+//! `--order` reorders the receiver's transfer queue without touching the
+//! file list the NDX requests index into.
+
+use std::path::Path;
+
+use metadata::MetadataOptions;
+use protocol::flist::FileEntry;
+
+use super::super::ReceiverContext;
+use super::super::stats::TransferStats;
+use super::support::{CapturingDeletionWriter, test_config, test_handshake};
+
+fn file(name: &str, size: u64, mtime: i64) -> FileEntry {
+    let mut entry = FileEntry::new_file(name.into(), size, 0o644);
+    entry.set_mtime(mtime, 0);
+    entry
+}
+
+/// Returns the names of the entries queued for transfer, in request order.
+fn queued_names(order: engine::TransferOrder, dest: &Path) -> Vec<String> {
+    let mut config = test_config();
+    config.file_selection.transfer_order = order;
+    let mut ctx = ReceiverContext::new_for_test(&test_handshake(), config);
+    ctx.file_list = vec![
+        file("a.txt", 100, 1_000),
+        FileEntry::new_directory("d".into(), 0o755),
+        file("d/b.txt", 300, 3_000),
+        file("d/c.txt", 200, 2_000),
+    ];
+
+    let mut writer = CapturingDeletionWriter::default();
+    let mut errors = Vec::new();
+    let mut stats = TransferStats::default();
+    ctx.build_files_to_transfer(
+        &mut writer,
+        dest,
+        None,
+        &MetadataOptions::default(),
+        None,
+        &mut errors,
+        &mut stats,
+        None,
+        None,
+    )
+    .into_iter()
+    .map(|(_, entry, ..)| entry.name().to_owned())
+    .collect()
+}
+
+#[test]
+fn name_order_requests_in_file_list_order() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let names = queued_names(engine::TransferOrder::Name, temp_dir.path());
+    assert_eq!(names, ["a.txt", "d/b.txt", "d/c.txt"]);
+}
+
+#[test]
+fn mtime_order_requests_newest_first() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let names = queued_names(engine::TransferOrder::Mtime, temp_dir.path());
+    assert_eq!(names, ["d/b.txt", "d/c.txt", "a.txt"]);
+}
+
+#[test]
+fn size_order_requests_smallest_first() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let names = queued_names(engine::TransferOrder::Size, temp_dir.path());
+    assert_eq!(names, ["a.txt", "d/c.txt", "d/b.txt"]);
+}
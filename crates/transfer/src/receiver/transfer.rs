@@ -53,7 +53,8 @@ impl ReceiverContext {
             self.run_pipelined_incremental(
                 reader,
                 writer,
-                crate::pipeline::PipelineConfig::default(),
+                crate::pipeline::PipelineConfig::default()
+                    .with_adaptive(crate::pipeline::adaptive_opt_in()),
                 progress,
             )
         }
@@ -62,7 +63,8 @@ impl ReceiverContext {
             self.run_pipelined(
                 reader,
                 writer,
-                crate::pipeline::PipelineConfig::default(),
+                crate::pipeline::PipelineConfig::default()
+                    .with_adaptive(crate::pipeline::adaptive_opt_in()),
                 progress,
             )
         }
@@ -113,6 +115,9 @@ impl ReceiverContext {
             // IOERR_GENERAL so the transfer exits 23 (RERR_PARTIAL) instead
             // of reporting success while the file was never updated.
             self.flist_io_error |= handle_delayed_updates(all_delayed_updates, backup_cfg);
+            if self.config.write.fsync_dir {
+                self.flist_io_error |= sync_delayed_parents(all_delayed_updates);
+            }
         }
 
         #[cfg(unix)]
@@ -120,9 +125,49 @@ impl ReceiverContext {
         #[cfg(not(unix))]
         self.create_hardlinks(dest_dir, writer)?;
 
+        self.save_scan_cache();
+
         Ok(())
     }
 
+    /// Writes the `--checkpoint` log of confirmed commits.
+    ///
+    /// Like the scan cache, a failure only costs a later `--resume` its
+    /// skips, so it is logged rather than surfaced.
+    pub(in crate::receiver) fn save_checkpoint(&self) {
+        let Some(checkpoint) = &self.checkpoint else {
+            return;
+        };
+        match checkpoint.save() {
+            Ok(()) => debug_log!(
+                Recv,
+                1,
+                "saved checkpoint with {} entries",
+                checkpoint.len()
+            ),
+            Err(err) => debug_log!(Recv, 1, "failed to write checkpoint: {err}"),
+        }
+    }
+
+    /// Writes the `--cache-dir` destination scan cache back for the next run.
+    ///
+    /// A failure only costs the next run its cache hits, so it is logged
+    /// rather than surfaced.
+    fn save_scan_cache(&self) {
+        let Some(cache) = &self.scan_cache else {
+            return;
+        };
+        match cache.save() {
+            Ok(()) => debug_log!(
+                Recv,
+                1,
+                "saved {} destination scan cache entries",
+                cache.len()
+            ),
+            Err(err) => debug_log!(Recv, 1, "failed to save destination scan cache: {err}"),
+        }
+    }
+
     /// True when the delete pass has work to do at the EARLY site, before the
     /// per-file transfer loop.
     ///
@@ -202,6 +247,16 @@ impl ReceiverContext {
         let delay = self.config.deletion.late_delete && !self.config.deletion.delete_after;
         let deferrable_delay = delay && !self.delete_pass_uses_serial_executor();
 
+        // Only the walking phases consult io_error; a `--delete-delay` late
+        // phase executes whatever the (possibly skipped) early walk recorded.
+        let walks = match phase {
+            DeletePassPhase::Early => true,
+            DeletePassPhase::Late => self.config.deletion.delete_after,
+        };
+        if walks && self.delete_pass_blocked_by_io_error(stats.io_error) {
+            return Ok(());
+        }
+
         match phase {
             DeletePassPhase::Early => {
                 if deferrable_delay {
@@ -257,6 +312,27 @@ impl ReceiverContext {
         Ok(())
     }
 
+    /// Returns `true` when an I/O error seen so far forbids the delete pass.
+    ///
+    /// An unreadable source entry may be missing from the file list, so
+    /// deleting its destination counterpart would destroy data; `--ignore-errors`
+    /// lifts the guard. The notice is printed once per run, and only one walking
+    /// phase ever runs, so no extra warned-once state is needed.
+    // upstream: generator.c:298-305 delete_in_dir() prints "IO error
+    // encountered -- skipping file deletion" and returns without deleting
+    // whenever `io_error & IOERR_GENERAL && !ignore_errors`.
+    pub(in crate::receiver) fn delete_pass_blocked_by_io_error(&self, io_error: i32) -> bool {
+        use crate::generator::io_error_flags::IOERR_GENERAL;
+
+        if self.config.deletion.ignore_errors
+            || (io_error | self.flist_io_error) & IOERR_GENERAL == 0
+        {
+            return false;
+        }
+        info_log!(Nonreg, 1, "IO error encountered -- skipping file deletion");
+        true
+    }
+
     /// Immediate delete sweep: scan, unlink, emit, and fold the stats into
     /// `stats`. Used by `--delete-before` / `--delete-during` / `--delete-after`
     /// and a capped/`--one-file-system` `--delete-delay`.
@@ -300,8 +376,9 @@ pub(in crate::receiver) enum DeletePassPhase {
     Late,
 }
 
-/// Renames all delayed-update files from their `.~tmp~` staging paths to
-/// their final destinations, then removes the empty `.~tmp~` directories.
+/// Renames all delayed-update files from their staging paths (`.~tmp~` or
+/// the `--partial-dir`) to their final destinations, then removes the
+/// emptied per-directory staging directories.
 ///
 /// Mirrors upstream `receiver.c:529-557 handle_delayed_updates()` which
 /// iterates `delayed_bits`, renames each file from its `partial_dir_fname()`
@@ -416,14 +493,21 @@ pub(in crate::receiver) fn handle_delayed_updates(
             continue;
         }
 
-        // Track parent staging directories for cleanup.
-        if let Some(parent) = staging_path.parent() {
+        // Track parent staging directories for cleanup. Only a per-directory
+        // (relative) partial-dir lives beside the file it staged; an absolute
+        // `--partial-dir` is shared and never removed.
+        // upstream: util1.c:1343 - `if (!create && *partial_dir == '/')`
+        if let Some(parent) = staging_path.parent()
+            && final_path
+                .parent()
+                .is_some_and(|final_parent| is_per_directory_staging(parent, final_parent))
+        {
             staging_dirs.insert(parent.to_path_buf());
         }
     }
 
     // upstream: receiver.c:553 - handle_partial_dir(partialptr, PDIR_DELETE)
-    // Remove empty .~tmp~ staging directories.
+    // Remove the emptied per-directory staging directories.
     for dir in &staging_dirs {
         let _ = fs::remove_dir(dir);
     }
@@ -431,6 +515,52 @@ pub(in crate::receiver) fn handle_delayed_updates(
     io_error
 }
 
+/// Fsyncs each directory the delayed-update sweep renamed files into
+/// (`--fsync-dir`), once per directory.
+///
+/// A directory that cannot be synced sets `IOERR_GENERAL`, so the transfer
+/// exits 23 just as it would for a failed rename.
+fn sync_delayed_parents(delayed: &[(PathBuf, PathBuf)]) -> i32 {
+    use std::collections::HashSet;
+
+    use crate::generator::io_error_flags::IOERR_GENERAL;
+
+    let mut synced: HashSet<&Path> = HashSet::new();
+    let mut io_error = 0;
+    for (_, final_path) in delayed {
+        let Some(parent) = final_path.parent() else {
+            continue;
+        };
+        if !synced.insert(parent) {
+            continue;
+        }
+        if let Err(e) = crate::disk_commit::sync_parent_dir(final_path) {
+            eprintln!("rsync: {e}");
+            io_error |= IOERR_GENERAL;
+        }
+    }
+    io_error
+}
+
+/// Returns `true` when `staging_dir` is a per-directory partial-dir of
+/// `final_dir`: `final_dir` followed by one or more plain name components.
+///
+/// The comparison walks both paths component by component, so a sibling whose
+/// name merely extends the destination directory's (`sub2` against `sub`) is
+/// not mistaken for a child, and a `..` in the remainder disqualifies it.
+fn is_per_directory_staging(staging_dir: &Path, final_dir: &Path) -> bool {
+    use std::path::Component;
+
+    let mut staging = staging_dir.components();
+    for component in final_dir.components() {
+        if staging.next() != Some(component) {
+            return false;
+        }
+    }
+    let mut rest = staging.peekable();
+    rest.peek().is_some() && rest.all(|component| matches!(component, Component::Normal(_)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -582,6 +712,38 @@ mod tests {
         assert_eq!(handle_delayed_updates(&[], None), 0);
     }
 
+    /// Verifies the `--fsync-dir` pass syncs the renamed-into directories
+    /// without flagging an error.
+    #[test]
+    fn sync_delayed_parents_succeeds_for_existing_dirs() {
+        let dir = test_support::create_tempdir();
+        let sub = dir.path().join("sub");
+        fs::create_dir(&sub).unwrap();
+        fs::write(dir.path().join("a.txt"), b"a").unwrap();
+        fs::write(sub.join("b.txt"), b"b").unwrap();
+
+        let delayed = vec![
+            (PathBuf::from("unused-a"), dir.path().join("a.txt")),
+            (PathBuf::from("unused-b"), sub.join("b.txt")),
+            (PathBuf::from("unused-c"), sub.join("c.txt")),
+        ];
+        assert_eq!(sync_delayed_parents(&delayed), 0);
+    }
+
+    /// Verifies a destination directory that vanished before the sync is
+    /// reported through `IOERR_GENERAL`.
+    #[cfg(unix)]
+    #[test]
+    fn sync_delayed_parents_flags_missing_dir() {
+        use crate::generator::io_error_flags::IOERR_GENERAL;
+
+        let dir = test_support::create_tempdir();
+        let final_path = dir.path().join("gone").join("a.txt");
+
+        let io_error = sync_delayed_parents(&[(PathBuf::from("unused"), final_path)]);
+        assert_eq!(io_error & IOERR_GENERAL, IOERR_GENERAL);
+    }
+
     /// Verifies that `handle_delayed_updates` backs up a pre-existing
     /// destination file before renaming the staged file into place when a
     /// `BackupConfig` is supplied.
@@ -922,4 +1084,38 @@ mod tests {
         assert_eq!(fs::read_to_string(&final_a).unwrap(), "staged-a");
         assert_eq!(fs::read_to_string(&final_b).unwrap(), "staged-b");
     }
+
+    #[test]
+    fn per_directory_staging_compares_whole_components() {
+        let per_dir =
+            |staging: &str| is_per_directory_staging(Path::new(staging), Path::new("/dest/sub"));
+        assert!(per_dir("/dest/sub/.~tmp~"));
+        assert!(per_dir("/dest/sub/a/b"));
+        assert!(!per_dir("/dest/sub2/.~tmp~"));
+        assert!(!per_dir("/dest/sub"));
+        assert!(!per_dir("/dest/sub/../p"));
+        assert!(!per_dir("/partial"));
+    }
+
+    /// An absolute `--partial-dir` is shared by every destination directory,
+    /// so the sweep must leave it in place even once it is empty.
+    #[test]
+    fn handle_delayed_updates_keeps_absolute_partial_dir() {
+        let dir = test_support::create_tempdir();
+        let dest = dir.path().join("dest");
+        let partial = dir.path().join("partial");
+        fs::create_dir(&dest).unwrap();
+        fs::create_dir(&partial).unwrap();
+
+        let staged = partial.join("file.txt");
+        fs::write(&staged, b"staged").unwrap();
+        let final_path = dest.join("file.txt");
+
+        let io_error = handle_delayed_updates(&[(staged.clone(), final_path.clone())], None);
+
+        assert_eq!(io_error, 0);
+        assert_eq!(fs::read_to_string(&final_path).unwrap(), "staged");
+        assert!(!staged.exists());
+        assert!(partial.is_dir(), "absolute partial-dir must not be removed");
+    }
 }
@@ -18,11 +18,12 @@ use logging::{debug_gte, debug_log, info_log};
 use metadata::{MetadataOptions, apply_metadata_with_cached_stat, metadata_unchanged};
 use protocol::flist::FileEntry;
 
-use crate::receiver::directory::FailedDirectories;
+use crate::receiver::directory::{FailedDirectories, MakeRoomFor};
 use crate::receiver::quick_check::{
-    dest_mtime_newer, dest_type_matches_source, is_hardlink_follower, quick_check_matches,
-    try_reference_dest,
+    dest_type_matches_source, is_hardlink_follower, quick_check_matches, quick_stat_covers,
+    quick_stat_settles, try_reference_dest,
 };
+use crate::receiver::skip_decision::{FileSelection, SkipReason};
 use crate::receiver::stats::{ListOnlyEntry, TransferStats};
 use crate::receiver::{ReceiverContext, apply_acls_from_receiver_cache};
 
@@ -86,6 +87,7 @@ impl ReceiverContext {
         &'a self,
         writer: &mut W,
         dest_dir: &Path,
+        #[cfg(unix)] sandbox: Option<&fast_io::DirSandbox>,
         metadata_opts: &MetadataOptions,
         failed_dirs: Option<&FailedDirectories>,
         metadata_errors: &mut Vec<(PathBuf, String)>,
@@ -114,9 +116,8 @@ impl ReceiverContext {
         // Pre-extract config values to avoid repeated field access in the
         // filter closures at 100K scale.
         let daemon_filters = self.daemon_filter_set();
-        let min_size = self.config.file_selection.min_file_size;
-        let max_size = self.config.file_selection.max_file_size;
-        let has_size_bounds = min_size.is_some() || max_size.is_some();
+        let selection = FileSelection::from_config(&self.config);
+        let has_size_bounds = selection.has_size_bounds();
         let has_daemon_filters = daemon_filters.is_some();
         let has_failed_dirs = failed_dirs.is_some();
         let verbose_client = self.config.flags.verbose && self.config.connection.client_mode;
@@ -168,15 +169,14 @@ impl ReceiverContext {
             // upstream: generator.c:1938-1939 - dry-run still itemizes with
             // ITEM_TRANSFER; the dry-run loop writes the bare ITEM_TRANSFER
             // attrs over the wire and does not consume this precomputed value.
-            return candidates
+            let mut queued: Vec<_> = candidates
                 .into_iter()
                 .filter(|(_, entry)| {
                     // upstream: generator.c:1704-1718 - the max/min-size skip
                     // (`goto cleanup`) fires before the `do_xfers` gate, so a
                     // dry run still excludes out-of-range files and emits the
                     // SKIP-gated notice in flist order.
-                    !has_size_bounds
-                        || !self.emit_size_bound_skip(writer, entry, min_size, max_size)
+                    !has_size_bounds || !self.emit_size_bound_skip(writer, entry, &selection)
                 })
                 .map(|(idx, entry)| {
                     (
@@ -187,6 +187,8 @@ impl ReceiverContext {
                     )
                 })
                 .collect();
+            self.order_transfer_queue(&mut queued);
+            return queued;
         }
 
         let preserve_times = self.config.flags.times && !self.config.flags.ignore_times;
@@ -194,9 +196,6 @@ impl ReceiverContext {
         // upstream: generator.c:quick_check_ok() -> same_time() honours the
         // `--modify-window` tolerance for every transfer, not just local copies.
         let modify_window = self.config.file_selection.modify_window;
-        let ignore_existing = self.config.file_selection.ignore_existing;
-        let existing_only = self.config.file_selection.existing_only;
-        let update_only = self.config.flags.update;
         let always_checksum = if self.config.flags.checksum {
             Some(self.get_checksum_algorithm())
         } else {
@@ -222,22 +221,40 @@ impl ReceiverContext {
             .map(|&(idx, entry)| (idx, dest_dir.join(entry.path())))
             .collect();
 
-        let stat_results: Vec<(usize, PathBuf, Option<fs::Metadata>)> =
-            crate::parallel_io::map_blocking(
-                stat_paths,
-                self.parallel_thresholds
-                    .for_op(crate::parallel_io::ParallelOp::Stat),
-                move |(idx, file_path)| {
-                    let meta = fs::metadata(&file_path).ok();
-                    (idx, file_path, meta)
-                },
-            );
+        // When nothing but the size + mtime quick-check and the perms/owner/
+        // group/times comparison looks at the destination stat, Phase B fetches
+        // just those fields (statx with AT_STATX_DONT_SYNC on Linux). An
+        // unchanged file is then settled without a full stat; the rest take
+        // one below. Saves the attribute revalidation round trip per file on
+        // NFS destinations.
+        let quick_stat_only = always_checksum.is_none()
+            && !emit_itemize
+            && !has_acls
+            && !has_xattrs
+            && !selection.ignore_existing
+            && !selection.update_only
+            && !has_size_bounds
+            && quick_stat_covers(metadata_opts);
+
+        let stat_results: Vec<(usize, PathBuf, DestStat)> = crate::parallel_io::map_blocking(
+            stat_paths,
+            self.parallel_thresholds
+                .for_op(crate::parallel_io::ParallelOp::Stat),
+            move |(idx, file_path)| {
+                let stat = if quick_stat_only {
+                    DestStat::quick(&file_path)
+                } else {
+                    DestStat::Full(fs::metadata(&file_path).ok())
+                };
+                (idx, file_path, stat)
+            },
+        );
 
         // Phase C: Sequential post-processing with stat results.
         // Pre-size for the expected minority that need transfer.
         let needs_metadata_apply = metadata_opts.requires_apply();
         let mut files_to_transfer = Vec::with_capacity(stat_results.len() / 4 + 1);
-        for (idx, file_path, dest_meta) in stat_results {
+        for (idx, file_path, dest_stat) in stat_results {
             // upstream: generator.c:2348-2353 generate_files() - the per-file
             // generate loop pokes maybe_send_keepalive once the I/O lull has
             // elapsed so a remote sender's --timeout does not fire while the
@@ -246,20 +263,33 @@ impl ReceiverContext {
             // (allowed_lull None), keeping the default path wire-identical.
             let _ = writer.maybe_send_keepalive();
             let entry = &self.file_list[idx];
-            if let Some(ref meta) = dest_meta {
-                if ignore_existing {
-                    // upstream: generator.c:1409 - `if (ignore_existing > 0 &&
-                    // statret == 0 && (!is_dir || stype != FT_DIR)) { if
-                    // (INFO_GTE(SKIP, 1) ...) rprintf(FINFO, "%s exists\n",
-                    // fname); }`. An already-present file is skipped with a
-                    // SKIP-gated notice; existing directories stay silent.
-                    if !entry.is_dir() && logging::info_gte(logging::InfoFlag::Skip, 1) {
-                        let name = entry.path().to_string_lossy();
-                        // upstream: generator.c:1398-1408 - the notice is
-                        // "%s exists%s"; the suffix is empty at SKIP1 and gains
-                        // a parenthesised reason (type/sum/file/attr change or
-                        // uptodate) at SKIP2.
-                        let suffix = self.ignore_existing_suffix(
+            let mut dest_meta = match dest_stat {
+                DestStat::Full(meta) => meta,
+                DestStat::Quick(quick) => {
+                    if quick_stat_settles(
+                        entry,
+                        &quick,
+                        metadata_opts,
+                        preserve_times,
+                        size_only,
+                        modify_window,
+                    ) {
+                        continue;
+                    }
+                    fs::metadata(&file_path).ok()
+                }
+            };
+            // upstream: generator.c:1380-1726 - the file-selection predicates
+            // run per file in flist order, so each SKIP-gated notice
+            // interleaves with the itemize rows exactly as upstream's do.
+            if let Some(reason) = selection.skip_reason(entry, &file_path, dest_meta.as_ref()) {
+                if logging::info_gte(logging::InfoFlag::Skip, 1) {
+                    let name = entry.path().to_string_lossy();
+                    // upstream: generator.c:1398-1408 - the "%s exists%s"
+                    // suffix is empty at SKIP1 and gains a parenthesised
+                    // reason (type/sum/file/attr change or uptodate) at SKIP2.
+                    let suffix = match (reason, dest_meta.as_ref()) {
+                        (SkipReason::Exists, Some(meta)) => self.ignore_existing_suffix(
                             entry,
                             &file_path,
                             meta,
@@ -268,47 +298,59 @@ impl ReceiverContext {
                             always_checksum,
                             modify_window,
                             metadata_opts,
-                        );
-                        let _ = self.emit_info_line(writer, &format!("{name} exists{suffix}\n"));
-                    }
-                    continue;
+                        ),
+                        _ => "",
+                    };
+                    let _ = self.emit_info_line(writer, &reason.notice(&name, suffix));
                 }
-                // upstream: generator.c:1704-1718 - the max/min-size skip is
-                // tested per file after the `--ignore-existing` "exists" notice
-                // (1395) and before the `--update` "is newer" notice (1721), so
-                // the size notices interleave with the other skip notices in
-                // strict flist order rather than as a separate batch.
-                if has_size_bounds && self.emit_size_bound_skip(writer, entry, min_size, max_size) {
+                continue;
+            }
+            // upstream: generator.c:recv_generator() - a directory where the
+            // regular file goes is removed via delete_item(..., DEL_FOR_FILE),
+            // recursively under --delete or --force; otherwise the file is
+            // skipped. Runs after the selection predicates, as upstream's
+            // ignore_existing/update_only checks precede it; a removed
+            // directory leaves the file new.
+            if dest_meta.as_ref().is_some_and(fs::Metadata::is_dir) {
+                if !self.make_room_for_non_dir(
+                    dest_dir,
+                    #[cfg(unix)]
+                    sandbox,
+                    entry.path(),
+                    &file_path,
+                    MakeRoomFor::File,
+                    writer,
+                ) {
                     continue;
                 }
-                if update_only
-                    && dest_type_matches_source(&file_path, entry)
-                    && dest_mtime_newer(meta, entry)
+                // Under --dry-run the directory is still on disk; the file
+                // is new as far as the rest of the pass is concerned.
+                dest_meta = if self.config.flags.dry_run {
+                    None
+                } else {
+                    fs::metadata(&file_path).ok()
+                };
+            }
+            if let Some(ref meta) = dest_meta {
+                // oc-rsync extension: a file the `--resume` checkpoint shows
+                // committed from this very source entry is settled without
+                // the quick-check (and its --checksum read).
+                let resumed = self
+                    .checkpoint
+                    .as_ref()
+                    .is_some_and(|checkpoint| checkpoint.covers(entry, &file_path, meta));
+                if resumed
+                    || quick_check_matches(
+                        entry,
+                        &file_path,
+                        meta,
+                        preserve_times,
+                        size_only,
+                        always_checksum,
+                        modify_window,
+                        self.scan_cache.as_ref(),
+                    )
                 {
-                    // upstream: generator.c:1721 - the `-u` skip is guarded by
-                    // `stype == ftype`, so a newer destination only suppresses
-                    // the transfer when it is the SAME file type as the source.
-                    // A type mismatch (e.g. dest symlink vs source regular file)
-                    // always transfers regardless of mtime.
-                    //
-                    // upstream: generator.c:1723-1724 - `if (INFO_GTE(SKIP, 1))
-                    // rprintf(FINFO, "%s is newer\n", fname)`. Report the skip on
-                    // the same sink as itemize so the ordering matches upstream.
-                    if logging::info_gte(logging::InfoFlag::Skip, 1) {
-                        let name = entry.path().to_string_lossy();
-                        let _ = self.emit_info_line(writer, &format!("{name} is newer\n"));
-                    }
-                    continue;
-                }
-                if quick_check_matches(
-                    entry,
-                    &file_path,
-                    meta,
-                    preserve_times,
-                    size_only,
-                    always_checksum,
-                    modify_window,
-                ) {
                     // upstream: generator.c:1816 - itemize() with iflags=0 for an
                     // up-to-date file; the attr-comparison may still surface a
                     // metadata-only row (perms/owner/group differing while
@@ -339,45 +381,23 @@ impl ReceiverContext {
                     );
                     continue;
                 }
-            } else {
-                if existing_only {
-                    // upstream: generator.c:1380-1395 - --existing /
-                    // --ignore-non-existing never creates an absent
-                    // destination; a missing regular file is skipped with a
-                    // SKIP-gated "not creating new file" notice. Directories
-                    // take the same path in receiver/directory/creation.rs.
-                    if logging::info_gte(logging::InfoFlag::Skip, 1) {
-                        let name = entry.path().to_string_lossy();
-                        let _ = self
-                            .emit_info_line(writer, &format!("not creating new file \"{name}\"\n"));
-                    }
-                    continue;
-                }
-                // upstream: generator.c:1704-1718 - a not-yet-existing file
-                // still hits the max/min-size skip (after the not-creating
-                // check at 1368), so the size notice for an absent file appears
-                // in flist order alongside the other per-file notices.
-                if has_size_bounds && self.emit_size_bound_skip(writer, entry, min_size, max_size) {
-                    continue;
-                }
-                if has_reference_dirs
-                    && try_reference_dest(
-                        entry,
-                        dest_dir,
-                        &self.config.reference_directories,
-                        preserve_times,
-                        size_only,
-                        always_checksum,
-                        modify_window,
-                        self.config.flags.copy_links,
-                        metadata_opts,
-                        metadata_errors,
-                        acl_cache,
-                        acl_id_map,
-                    )
-                {
-                    continue;
-                }
+            } else if has_reference_dirs
+                && try_reference_dest(
+                    entry,
+                    dest_dir,
+                    &self.config.reference_directories,
+                    preserve_times,
+                    size_only,
+                    always_checksum,
+                    modify_window,
+                    self.config.flags.copy_links,
+                    metadata_opts,
+                    metadata_errors,
+                    acl_cache,
+                    acl_id_map,
+                )
+            {
+                continue;
             }
             // upstream: generator.c:511-579 itemize() - compute the base itemize
             // flags before the data transfer so the row reflects attribute
@@ -419,9 +439,24 @@ impl ReceiverContext {
             }
             files_to_transfer.push((idx, entry, file_path, base_iflags));
         }
+        self.order_transfer_queue(&mut files_to_transfer);
         files_to_transfer
     }
 
+    /// Reorders the transfer queue for `--order` (oc-rsync extension).
+    ///
+    /// Only the order NDX requests go out in changes: every index still names
+    /// its canonically sorted file-list entry, and the sender answers requests
+    /// in whatever order they arrive (upstream `sender.c:send_files()`).
+    fn order_transfer_queue(&self, queue: &mut Vec<(usize, &FileEntry, PathBuf, u32)>) {
+        self.config
+            .file_selection
+            .transfer_order
+            .apply(queue, |(_, entry, _, _)| {
+                Some((entry.mtime(), entry.size()))
+            });
+    }
+
     /// Records the deferred itemize rows for a `--dry-run` receive, one per
     /// file-list entry in flist-index order.
     ///
@@ -544,6 +579,7 @@ impl ReceiverContext {
                         size_only,
                         always_checksum,
                         modify_window,
+                        self.scan_cache.as_ref(),
                     ) {
                         0
                     } else {
@@ -685,29 +721,16 @@ impl ReceiverContext {
         &self,
         writer: &mut W,
         entry: &FileEntry,
-        min_size: Option<u64>,
-        max_size: Option<u64>,
+        selection: &FileSelection,
     ) -> bool {
-        let size = entry.size();
-        if let Some(max) = max_size {
-            if size > max {
-                if logging::info_gte(logging::InfoFlag::Skip, 1) {
-                    let name = entry.path().to_string_lossy();
-                    let _ = self.emit_info_line(writer, &format!("{name} is over max-size\n"));
-                }
-                return true;
-            }
-        }
-        if let Some(min) = min_size {
-            if size < min {
-                if logging::info_gte(logging::InfoFlag::Skip, 1) {
-                    let name = entry.path().to_string_lossy();
-                    let _ = self.emit_info_line(writer, &format!("{name} is under min-size\n"));
-                }
-                return true;
-            }
+        let Some(reason) = selection.size_skip(entry.size()) else {
+            return false;
+        };
+        if logging::info_gte(logging::InfoFlag::Skip, 1) {
+            let name = entry.path().to_string_lossy();
+            let _ = self.emit_info_line(writer, &reason.notice(&name, ""));
         }
-        false
+        true
     }
 
     /// Computes the parenthesised reason suffix for the `--ignore-existing`
@@ -746,6 +769,7 @@ impl ReceiverContext {
             size_only,
             always_checksum,
             modify_window,
+            self.scan_cache.as_ref(),
         ) {
             if always_checksum.is_some() {
                 " (sum change)"
@@ -846,6 +870,27 @@ impl ReceiverContext {
     }
 }
 
+/// Destination stat gathered by the parallel Phase B pass.
+enum DestStat {
+    /// Quick-check fields only; the file may still need a full stat.
+    Quick(fast_io::syscall_batch::QuickStat),
+    /// Full metadata, `None` when the destination is absent.
+    Full(Option<fs::Metadata>),
+}
+
+impl DestStat {
+    /// Quick-stats `path`, falling back to a full stat when the quick form is
+    /// unavailable. A missing destination maps to `Full(None)` without a
+    /// second syscall.
+    fn quick(path: &Path) -> Self {
+        match fast_io::syscall_batch::quick_stat(path, true) {
+            Ok(quick) => Self::Quick(quick),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Self::Full(None),
+            Err(_) => Self::Full(fs::metadata(path).ok()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod itemize_order_tests {
     use std::ffi::OsString;
@@ -1009,6 +1054,8 @@ mod itemize_order_tests {
         let _ = ctx.build_files_to_transfer(
             &mut writer,
             dest,
+            #[cfg(unix)]
+            None,
             &opts,
             None,
             &mut metadata_errors,
@@ -1246,6 +1293,8 @@ mod skip_notice_tests {
         let _ = ctx.build_files_to_transfer(
             &mut writer,
             dest,
+            #[cfg(unix)]
+            None,
             &opts,
             None,
             &mut errs,
@@ -13,7 +13,7 @@
 
 use std::collections::VecDeque;
 use std::io::{self, Read, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use logging::{debug_log, info_log};
@@ -52,6 +52,9 @@ impl ReceiverContext {
     /// safely landed at the destination. When the flag is off the confirmed
     /// indices are drained and discarded, keeping the accumulator bounded.
     ///
+    /// The same confirmations feed the `--checkpoint` log, which is written
+    /// out here whenever its save interval has elapsed.
+    ///
     /// # Upstream Reference
     ///
     /// - `receiver.c:1063-1069` - `send_msg_success(fname, ndx)` on `recv_ok == 1`.
@@ -65,6 +68,14 @@ impl ReceiverContext {
         W: crate::writer::MsgInfoSender + ?Sized,
     {
         let confirmed = pipelined_receiver.drain_new_success_indices();
+        if let Some(checkpoint) = &self.checkpoint {
+            for &flat_idx in &confirmed {
+                checkpoint.record(&self.file_list[flat_idx], self.flat_to_wire_ndx(flat_idx));
+            }
+            if let Err(err) = checkpoint.save_if_due() {
+                debug_log!(Recv, 1, "failed to write checkpoint: {err}");
+            }
+        }
         if !self.config.flags.remove_source_files {
             return Ok(());
         }
@@ -98,7 +109,7 @@ impl ReceiverContext {
     ) -> io::Result<PipelineResult> {
         use crate::disk_commit::{BackupConfig, DiskCommitConfig, PartialMode};
         use crate::pipeline::receiver::PipelinedReceiver;
-        use crate::shared::TransferDeadline;
+        use crate::shared::{TransferDeadline, remaining_paths};
 
         // Early return when there is nothing to transfer - avoids spawning
         // the disk-commit thread, creating codecs, and pipeline state.
@@ -187,6 +198,7 @@ impl ReceiverContext {
         };
         let disk_config = DiskCommitConfig {
             do_fsync: self.config.write.fsync,
+            fsync_dir: self.config.write.fsync_dir,
             use_sparse: self.config.flags.sparse,
             preallocate: self.config.flags.preallocate,
             dest_dir: Some(setup.dest_dir.clone()),
@@ -204,6 +216,9 @@ impl ReceiverContext {
             partial_mode,
             delay_updates: self.config.write.delay_updates,
             append_verify: self.config.flags.append_verify && !is_redo_pass,
+            parallel_commit: self.config.write.parallel_commit,
+            direct_write: self.config.write.direct_write,
+            drop_cache: self.config.write.drop_cache,
             ..DiskCommitConfig::default()
         };
         let mut pipelined_receiver = PipelinedReceiver::new(disk_config)?;
@@ -219,12 +234,16 @@ impl ReceiverContext {
             // upstream: io.c perform_io() uses select() for bidirectional I/O,
             // naturally batching writes until the output buffer is full.
             let mut flushed_pending: usize = 0;
+            // Set once the deadline passes. Requests already sent still get
+            // their responses read, otherwise the sender's replies would be
+            // left on the wire in front of the phase-end NDX_DONE.
+            let mut stopping = false;
 
             loop {
-                if let Some(ref dl) = deadline {
-                    if dl.is_reached() {
-                        break;
-                    }
+                if !stopping && deadline.as_ref().is_some_and(|dl| dl.is_reached()) {
+                    debug_log!(Recv, 1, "stop limit reached; not requesting further files");
+                    self.stop_limit_reached.set(true);
+                    stopping = true;
                 }
 
                 // Collect a batch of files, compute signatures (potentially in
@@ -232,10 +251,12 @@ impl ReceiverContext {
                 {
                     use rayon::prelude::*;
 
-                    let batch: Vec<_> = file_iter
-                        .by_ref()
-                        .take(pipeline.available_slots())
-                        .collect();
+                    let slots = if stopping {
+                        0
+                    } else {
+                        pipeline.available_slots()
+                    };
+                    let batch: Vec<_> = file_iter.by_ref().take(slots).collect();
 
                     if !batch.is_empty() && !is_redo_pass {
                         // Extract basis config fields for the closure to avoid
@@ -245,6 +266,7 @@ impl ReceiverContext {
                         let partial_dir = self.config.partial_dir.as_deref();
                         let protocol = self.protocol;
                         let compat_flags = self.compat_flags;
+                        let block_length = self.config.block_size;
                         let whole_file = self.config.flags.whole_file;
                         let dest_dir = &setup.dest_dir;
                         let checksum_length = setup.checksum_length;
@@ -274,6 +296,7 @@ impl ReceiverContext {
                                         checksum_algorithm,
                                         whole_file,
                                         compat_flags,
+                                        block_length,
                                     };
                                     find_basis_file_with_config(&basis_config)
                                 })
@@ -296,6 +319,7 @@ impl ReceiverContext {
                                         checksum_algorithm,
                                         whole_file,
                                         compat_flags,
+                                        block_length,
                                     };
                                     find_basis_file_with_config(&basis_config)
                                 })
@@ -357,12 +381,20 @@ impl ReceiverContext {
                 }
 
                 if pipeline.is_empty() {
+                    if stopping {
+                        // Nothing was taken from the iterator since the stop,
+                        // so what is left is exactly what the next run sends.
+                        self.remaining_files.borrow_mut().extend(remaining_paths(
+                            file_iter.by_ref().map(|(_, entry, _, _)| entry),
+                        ));
+                    }
                     break;
                 }
 
                 // Flush only when the sender has no queued requests left.
                 if flushed_pending == 0 {
                     writer.flush()?;
+                    pipeline.mark_flushed();
                     flushed_pending = pipeline.outstanding();
                 }
 
@@ -384,7 +416,10 @@ impl ReceiverContext {
                 };
 
                 let xattr_list = self.resolve_xattr_list(file_entry);
-                let is_device_target = self.config.write.write_devices && file_entry.is_device();
+                // upstream: receiver.c:recv_files() tests `IS_DEVICE(st.st_mode)`
+                // on the destination; the incoming entry is a regular file.
+                let is_device_target =
+                    self.config.write.write_devices && is_device_destination(&file_path);
                 let result = process_file_response_streaming(
                     reader,
                     &mut ndx_read_codec,
@@ -398,6 +433,9 @@ impl ReceiverContext {
                     xattr_list,
                     &mut token_reader,
                 )?;
+                if let Some(read_at) = result.header_read_at {
+                    pipeline.record_first_byte(read_at);
+                }
 
                 pipelined_receiver.note_commit_sent(
                     result.expected_checksum,
@@ -548,6 +586,9 @@ impl ReceiverContext {
 
         // Graceful shutdown regardless of success or failure.
         let _ = pipelined_receiver.shutdown();
+        // Persist every commit confirmed so far, above all when the transfer
+        // failed: that is the run `--resume` picks up from.
+        self.save_checkpoint();
 
         result
     }
@@ -715,12 +756,13 @@ impl ReceiverContext {
                 target_mtime: file_entry.mtime(),
                 fuzzy_level: self.config.flags.fuzzy_level,
                 reference_directories: &self.config.reference_directories,
-                partial_dir: self.config.partial_dir.as_deref(),
+                partial_dir: self.config.effective_partial_dir(),
                 protocol: self.protocol,
                 checksum_length: setup.checksum_length,
                 checksum_algorithm: setup.checksum_algorithm,
                 whole_file: self.config.flags.whole_file,
                 compat_flags: self.compat_flags,
+                block_length: self.config.block_size,
             };
             let basis = find_basis_file_with_config(&basis_config);
 
@@ -760,3 +802,23 @@ impl ReceiverContext {
         Ok(())
     }
 }
+
+/// Returns whether `path` names an existing block or character device.
+///
+/// Uses `lstat`, so a symlink to a device is not written through.
+fn is_device_destination(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+
+        std::fs::symlink_metadata(path).is_ok_and(|meta| {
+            let file_type = meta.file_type();
+            file_type.is_block_device() || file_type.is_char_device()
+        })
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        false
+    }
+}
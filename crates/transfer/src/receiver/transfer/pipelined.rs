@@ -66,6 +66,7 @@ impl ReceiverContext {
         // occurs). upstream: generator.c:2299-2368 fetches sub-lists on demand.
         let mut flist_ndx_codec = create_ndx_codec(self.protocol.as_u8());
         self.ensure_all_segments_loaded(reader, &mut flist_ndx_codec)?;
+        self.flush_file_list_warnings(writer)?;
 
         // Decide the plain-`-v` directory NAME lines from the PRE-transfer
         // state, before create_directories applies metadata or child mkdirs
@@ -82,7 +83,7 @@ impl ReceiverContext {
         };
 
         // upstream: generator.c:1329-1338 - make_path() for relative_paths
-        self.ensure_relative_parents(&setup.dest_dir);
+        let parent_errors = self.ensure_relative_parents(&setup.dest_dir);
         let mut metadata_errors = self.create_directories(
             &setup.dest_dir,
             &setup.metadata_opts,
@@ -92,6 +93,7 @@ impl ReceiverContext {
             #[cfg(unix)]
             setup.sandbox.as_deref(),
         )?;
+        metadata_errors.splice(0..0, parent_errors);
         #[cfg(unix)]
         self.create_symlinks(&setup.dest_dir, setup.sandbox.as_deref(), writer)?;
         #[cfg(not(unix))]
@@ -148,6 +150,8 @@ impl ReceiverContext {
         let files_to_transfer = self.build_files_to_transfer(
             writer,
             &setup.dest_dir,
+            #[cfg(unix)]
+            setup.sandbox.as_deref(),
             &setup.metadata_opts,
             None,
             &mut metadata_errors,
@@ -348,6 +352,12 @@ impl ReceiverContext {
         // into the exit-code io_error so the receiver reports 24/23; MSG_NO_SEND
         // alone only skips the file and carries no exit-code bits.
         stats.io_error |= reader.take_io_error();
+        // upstream: log.c:311 - each MSG_ERROR_XFER the sender forwards sets
+        // got_xfer_error, which main.c:1635 turns into _exit(RERR_PARTIAL).
+        // The receiver's own FERROR_XFER reports count the same way.
+        stats.error_count = reader
+            .xfer_error_count()
+            .saturating_add(self.local_xfer_errors.get());
 
         let total_source_bytes: u64 = self.total_source_size();
 
@@ -362,6 +372,8 @@ impl ReceiverContext {
         }
         stats.metadata_errors = metadata_errors;
         stats.redo_count = redo_count;
+        stats.stop_limit_reached = self.stop_limit_reached.get();
+        stats.remaining_files = self.remaining_files.take();
         // upstream: main.c:803-805 - count the pre-flight-created destination
         // root (FLAG_DIR_CREATED -> ITEM_IS_NEW) as a created dir; oc mkdir's it
         // out-of-band so the dir loop treats it as existing. See the incremental
@@ -49,6 +49,7 @@ impl ReceiverContext {
         // upstream: generator.c:2299-2368 fetches sub-lists on demand.
         let mut flist_ndx_codec = create_ndx_codec(self.protocol.as_u8());
         self.ensure_all_segments_loaded(reader, &mut flist_ndx_codec)?;
+        self.flush_file_list_warnings(writer)?;
 
         let mut stats = TransferStats {
             files_listed: file_count,
@@ -64,7 +65,7 @@ impl ReceiverContext {
         let mut metadata_errors: Vec<(PathBuf, String)> = Vec::new();
 
         // upstream: generator.c:1329-1338 - make_path() for relative_paths
-        self.ensure_relative_parents(&setup.dest_dir);
+        metadata_errors.extend(self.ensure_relative_parents(&setup.dest_dir));
 
         for (flist_idx, file_entry) in self.file_list.iter().enumerate() {
             if file_entry.is_dir() {
@@ -145,6 +146,8 @@ impl ReceiverContext {
         let files_to_transfer = self.build_files_to_transfer(
             writer,
             &setup.dest_dir,
+            #[cfg(unix)]
+            setup.sandbox.as_deref(),
             &setup.metadata_opts,
             Some(&failed_dirs),
             &mut metadata_errors,
@@ -309,6 +312,8 @@ impl ReceiverContext {
         }
         stats.metadata_errors = metadata_errors;
         stats.redo_count = redo_count;
+        stats.stop_limit_reached = self.stop_limit_reached.get();
+        stats.remaining_files = self.remaining_files.take();
         // upstream: main.c:803-805 - the pre-flight mkdir of the destination
         // root sets FLAG_DIR_CREATED on flist[0], so the generator itemizes it
         // with ITEM_IS_NEW and receiver.c:736-738 counts created_dirs for it.
@@ -337,6 +342,12 @@ impl ReceiverContext {
         // into the exit-code io_error so the receiver reports 24/23; MSG_NO_SEND
         // alone only skips the file and carries no exit-code bits.
         stats.io_error |= reader.take_io_error();
+        // upstream: log.c:311 - each MSG_ERROR_XFER the sender forwards sets
+        // got_xfer_error, which main.c:1635 turns into _exit(RERR_PARTIAL).
+        // The receiver's own FERROR_XFER reports count the same way.
+        stats.error_count = reader
+            .xfer_error_count()
+            .saturating_add(self.local_xfer_errors.get());
 
         Ok(stats)
     }
@@ -457,6 +468,8 @@ mod itemize_order_tests {
         let _ = ctx.build_files_to_transfer(
             &mut writer,
             dest,
+            #[cfg(unix)]
+            None,
             &opts,
             Some(&failed_dirs),
             &mut metadata_errors,
@@ -11,11 +11,13 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use logging::debug_log;
-use metadata::{ChmodModifiers, MetadataOptions};
+use metadata::{ChmodModifiers, MetadataOptions, ModifyWindow};
 use protocol::filters::{FilterRuleWireFormat, read_filter_list};
 
 use filters::FilterChain;
 
+use crate::receiver::checkpoint::TransferCheckpoint;
+use crate::receiver::scan_cache::DestScanCache;
 use crate::receiver::{
     PHASE1_CHECKSUM_LENGTH, PipelineSetup, ReceiverContext, dest_arg_has_trailing_slash,
     ensure_dest_root_exists,
@@ -238,6 +240,7 @@ impl ReceiverContext {
             .preserve_times(self.config.flags.times)
             .preserve_atimes(self.config.flags.atimes)
             .preserve_crtimes(self.config.flags.crtimes)
+            .preserve_fileflags(self.config.flags.fileflags)
             .preserve_owner(self.config.flags.owner)
             .preserve_group(self.config.flags.group)
             .numeric_ids(self.config.flags.numeric_ids.maps_numeric())
@@ -417,6 +420,60 @@ impl ReceiverContext {
             dest_dir
         };
 
+        // Widen an unset window for a FAT-like destination, whose two-second
+        // mtime granularity would otherwise defeat the quick-check on every
+        // file. An explicit `--modify-window`, even `0`, is kept as given.
+        if !self.config.file_selection.modify_window_set
+            && metadata::is_fat_like_filesystem(&dest_dir)
+        {
+            debug_log!(
+                Genr,
+                1,
+                "{} is on a FAT-like filesystem; using --modify-window=1",
+                dest_dir.display()
+            );
+            self.config.file_selection.modify_window = ModifyWindow::FAT;
+        }
+
+        // oc-rsync extension: `--cache-dir` lets the `--checksum` quick-check
+        // reuse the previous run's digests for unchanged destination files.
+        if self.config.flags.checksum
+            && let Some(cache_dir) = self.config.file_selection.cache_dir.as_deref()
+        {
+            let cache = DestScanCache::load(cache_dir, &dest_dir, self.get_checksum_algorithm());
+            debug_log!(
+                Recv,
+                1,
+                "destination scan cache for {} opened in {}",
+                dest_dir.display(),
+                cache_dir.display()
+            );
+            self.scan_cache = Some(cache);
+        }
+
+        // oc-rsync extension: `--checkpoint` logs confirmed commits and
+        // `--resume` skips files an interrupted run already committed. Under
+        // --delay-updates a confirmed commit is only staged, so nothing is
+        // recorded or trusted.
+        let checkpoint = self.config.file_selection.checkpoint.as_deref();
+        let resume = self.config.file_selection.resume.as_deref();
+        if (checkpoint.is_some() || resume.is_some()) && !self.config.write.delay_updates {
+            let log = TransferCheckpoint::open(checkpoint, resume, &dest_dir);
+            let resumed = log.resumed();
+            if resume.is_some() {
+                debug_log!(
+                    Recv,
+                    1,
+                    "resuming {}: {} files ({} bytes) committed, last ndx {:?}",
+                    dest_dir.display(),
+                    resumed.files,
+                    resumed.bytes,
+                    resumed.last_ndx
+                );
+            }
+            self.checkpoint = Some(log);
+        }
+
         let acl_cache = if self.config.flags.acls {
             self.flist_reader_cache
                 .as_ref()
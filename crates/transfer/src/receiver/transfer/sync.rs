@@ -17,11 +17,13 @@ use metadata::apply_metadata_with_cached_stat;
 use engine::CleanupManager;
 
 use crate::delta_apply::ChecksumVerifier;
-use crate::receiver::basis::find_basis_file_with_config;
+use crate::receiver::basis::find_basis_file_for_stream;
 use crate::receiver::quick_check::is_hardlink_follower;
+use crate::receiver::skip_decision::FileSelection;
 use crate::receiver::stats::TransferStats;
-use crate::receiver::wire::{SenderAttrs, SumHead, write_signature_blocks};
+use crate::receiver::wire::{SenderAttrs, SumHead, write_signature_stream};
 use crate::receiver::{PipelineSetup, ReceiverContext, apply_acls_from_receiver_cache};
+use crate::shared::remaining_paths;
 #[cfg(not(unix))]
 use crate::temp_guard::open_tmpfile;
 #[cfg(unix)]
@@ -61,6 +63,7 @@ impl ReceiverContext {
         // upstream: receiver.c:653-654 DEBUG_GTE(RECV, 1)
         debug_log!(Recv, 1, "recv_files({}) starting", file_count);
 
+        let selection = FileSelection::from_config(&self.config);
         let mut files_transferred = 0;
         // upstream: receiver.c:784 total_transferred_size, summed with files_transferred.
         let mut transferred_file_size = 0u64;
@@ -68,7 +71,7 @@ impl ReceiverContext {
 
         // First pass: create directories and symlinks from file list.
         // upstream: generator.c:1329-1338 - make_path() for relative_paths
-        self.ensure_relative_parents(&dest_dir);
+        let parent_errors = self.ensure_relative_parents(&dest_dir);
         let mut metadata_errors = self.create_directories(
             &dest_dir,
             &metadata_opts,
@@ -78,6 +81,7 @@ impl ReceiverContext {
             #[cfg(unix)]
             sandbox.as_deref(),
         )?;
+        metadata_errors.splice(0..0, parent_errors);
         #[cfg(unix)]
         self.create_symlinks(&dest_dir, sandbox.as_deref(), writer)?;
         #[cfg(not(unix))]
@@ -129,15 +133,21 @@ impl ReceiverContext {
         // this is a plain 0..len walk.
         let mut flat_idx = 0usize;
         while self.ensure_flat_idx(flat_idx, reader, &mut flist_ndx_codec)? {
+            self.flush_file_list_warnings(writer)?;
             let file_idx = flat_idx;
             flat_idx += 1;
             if self.config.flags.list_only {
                 break;
             }
-            if let Some(ref dl) = deadline {
-                if dl.is_reached() {
-                    break;
-                }
+            // Stop requesting files once the deadline passes, but leave the
+            // loop normally so the phase and goodbye exchange still runs.
+            if deadline.as_ref().is_some_and(|dl| dl.is_reached()) {
+                debug_log!(Recv, 1, "stop limit reached; not requesting further files");
+                self.stop_limit_reached.set(true);
+                self.remaining_files
+                    .borrow_mut()
+                    .extend(remaining_paths(&self.file_list[file_idx..]));
+                break;
             }
 
             let file_entry = &self.file_list[file_idx];
@@ -172,12 +182,7 @@ impl ReceiverContext {
 
             // upstream: generator.c:1716-1731 - skip files outside the
             // min-size/max-size window with a SKIP-gated notice on FINFO.
-            if self.emit_size_bound_skip(
-                writer,
-                file_entry,
-                self.config.file_selection.min_file_size,
-                self.config.file_selection.max_file_size,
-            ) {
+            if self.emit_size_bound_skip(writer, file_entry, &selection) {
                 continue;
             }
 
@@ -195,11 +200,11 @@ impl ReceiverContext {
                 checksum_length,
                 checksum_algorithm,
             );
-            let basis_result = find_basis_file_with_config(&basis_config);
-            let signature_opt = basis_result.signature;
-            let basis_path_opt = basis_result.basis_path;
-            let fnamecmp_type = basis_result.fnamecmp_type;
-            let xname = basis_result.xname;
+            let basis = find_basis_file_for_stream(&basis_config);
+            let fnamecmp_type = basis
+                .as_ref()
+                .map_or(protocol::FnameCmpType::Fname, |basis| basis.fnamecmp_type);
+            let xname = basis.as_ref().and_then(|basis| basis.xname.clone());
 
             // upstream: protocol >= 29 sender expects iflags after NDX.
             // upstream: generator.c:1942-1948 - a non-FNAME basis sets
@@ -231,18 +236,29 @@ impl ReceiverContext {
             }
 
             // upstream: write_sum_head()
-            let sum_head = match signature_opt {
-                Some(ref signature) => SumHead::from_signature(signature),
-                None => SumHead::empty(),
-            };
+            let sum_head = basis
+                .as_ref()
+                .map_or_else(SumHead::empty, |basis| SumHead::from_layout(basis.layout));
             sum_head.write(&mut *writer)?;
 
-            // upstream: generator.c:775-776 - skip signature blocks in append mode
-            if !self.config.flags.append {
-                if let Some(ref signature) = signature_opt {
-                    write_signature_blocks(&mut *writer, signature, sum_head.s2length)?;
+            // upstream: generator.c:775-776 - skip signature blocks in append
+            // mode. Otherwise each block is written as it is hashed, so the
+            // basis signature is never held in memory.
+            let (basis_layout, basis_path_opt) = match basis {
+                Some(basis) => {
+                    if !self.config.flags.append {
+                        write_signature_stream(
+                            &mut *writer,
+                            basis.file,
+                            basis.layout,
+                            checksum_algorithm,
+                            sum_head.s2length,
+                        )?;
+                    }
+                    (Some(basis.layout), Some(basis.basis_path))
                 }
-            }
+                None => (None, None),
+            };
             writer.flush()?;
 
             let (echoed_ndx, _sender_attrs) = SenderAttrs::read_with_codec_xattr(
@@ -361,7 +377,7 @@ impl ReceiverContext {
                 file,
                 &config,
                 file_verifier,
-                signature_opt.as_ref(),
+                basis_layout,
                 basis_path_opt.as_deref(),
             )?;
 
@@ -596,6 +612,12 @@ impl ReceiverContext {
         // into the exit-code io_error so the receiver reports 24/23; MSG_NO_SEND
         // alone only skips the file and carries no exit-code bits.
         let sender_io_error = reader.take_io_error();
+        // upstream: log.c:311 - each MSG_ERROR_XFER the sender forwards sets
+        // got_xfer_error, which main.c:1635 turns into _exit(RERR_PARTIAL).
+        // The receiver's own FERROR_XFER reports count the same way.
+        let xfer_errors = reader
+            .xfer_error_count()
+            .saturating_add(self.local_xfer_errors.get());
 
         let total_source_bytes: u64 = self.total_source_size();
 
@@ -625,7 +647,7 @@ impl ReceiverContext {
             io_error: self.flist_reader_cache.as_ref().map_or(0, |r| r.io_error())
                 | self.flist_io_error
                 | sender_io_error,
-            error_count: 0,
+            error_count: xfer_errors,
             entries_received: 0,
             directories_created: 0,
             directories_failed: 0,
@@ -636,6 +658,8 @@ impl ReceiverContext {
             // upstream: receiver.c:733-746.
             created_stats: self.created_stats.get(),
             delete_limit_exceeded: false,
+            stop_limit_reached: self.stop_limit_reached.get(),
+            remaining_files: self.remaining_files.take(),
             literal_data: 0,
             matched_data: 0,
             redo_count: 0,
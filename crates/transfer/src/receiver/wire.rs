@@ -1,7 +1,7 @@
 //! Wire protocol types for the receiver role.
 //!
 //! Defines the signature header (`SumHead`), sender response attributes
-//! (`SenderAttrs`), and the signature block writer used during the
+//! (`SenderAttrs`), and the signature block writers used during the
 //! request/response exchange between receiver and sender.
 
 use std::io::{self, Read, Write};
@@ -11,6 +11,10 @@ use protocol::codec::NdxCodec;
 use protocol::effective_max_alloc;
 use protocol::read_varint;
 use protocol::xattr::XattrList;
+use signature::{
+    PipelinedSignatureConfig, SignatureAlgorithm, SignatureError, SignatureLayout,
+    stream_signature_pipelined,
+};
 
 /// Upstream MAXPATHLEN ceiling for an xname vstring (io.c:1944-1960).
 const MAX_XNAME_LEN: usize = 4096;
@@ -157,7 +161,13 @@ impl SumHead {
     /// Creates a `SumHead` from a file signature.
     #[must_use]
     pub const fn from_signature(signature: &FileSignature) -> Self {
-        let layout = signature.layout();
+        Self::from_layout(signature.layout())
+    }
+
+    /// Creates a `SumHead` from a signature layout, for a signature that is
+    /// streamed with [`write_signature_stream`] rather than held in memory.
+    #[must_use]
+    pub const fn from_layout(layout: SignatureLayout) -> Self {
         Self {
             count: layout.block_count() as u32,
             blength: layout.block_length().get(),
@@ -537,20 +547,88 @@ pub fn write_signature_blocks<W: Write + ?Sized>(
     s2length: u32,
 ) -> io::Result<()> {
     let mut sum_buf = vec![0u8; s2length as usize];
-    for block in signature.blocks() {
-        // Write rolling_sum as int32 LE
-        writer.write_all(&(block.rolling().value() as i32).to_le_bytes())?;
-
-        // Write strong_sum, truncated or padded to s2length
-        let strong_bytes = block.strong();
-        sum_buf.fill(0);
-        let copy_len = std::cmp::min(strong_bytes.len(), s2length as usize);
-        sum_buf[..copy_len].copy_from_slice(&strong_bytes[..copy_len]);
-        writer.write_all(&sum_buf)?;
+    for index in 0..signature.block_count() {
+        write_block_sums(
+            writer,
+            signature.rolling_sums()[index],
+            signature.strong_sum(index),
+            &mut sum_buf,
+        )?;
     }
     Ok(())
 }
 
+/// Generates signature blocks from `basis` and writes each one as soon as it
+/// is hashed.
+///
+/// Produces the same bytes as [`write_signature_blocks`] without building a
+/// [`FileSignature`]: the pipelined generator hands over one batch of blocks
+/// at a time, so memory stays bounded by the batch rather than the basis
+/// size. The caller has already written the `sum_head` describing `layout`.
+///
+/// The basis is hashed at exactly the length `layout` describes: a basis that
+/// shrank after the layout was computed is padded with zeros and one that
+/// grew is cut short, so the blocks always match the `sum_head` already sent.
+///
+/// Returns the number of basis bytes hashed, padding included.
+///
+/// # Errors
+///
+/// Propagates read and write errors unchanged. A digest that cannot fill
+/// `layout`'s strong-sum length is reported as [`io::ErrorKind::InvalidData`].
+/// Blocks written before the failure stay on the wire, so the request cannot
+/// be retried on the same stream.
+///
+/// # Upstream Reference
+///
+/// - `generator.c:generate_and_send_sums()` - hashes and writes each block in
+///   turn while reading the basis through its map window
+/// - `fileio.c:map_ptr()` - a short read zero-fills the rest of the window
+pub fn write_signature_stream<W, R>(
+    writer: &mut W,
+    basis: R,
+    layout: SignatureLayout,
+    algorithm: SignatureAlgorithm,
+    s2length: u32,
+) -> io::Result<u64>
+where
+    W: Write + ?Sized,
+    R: Read + Send + 'static,
+{
+    let mut sum_buf = vec![0u8; s2length as usize];
+    let len = layout.file_size();
+    let basis = basis.take(len).chain(io::repeat(0)).take(len);
+    stream_signature_pipelined(
+        basis,
+        layout,
+        algorithm,
+        PipelinedSignatureConfig::default(),
+        |rolling, strong| write_block_sums(writer, rolling.value(), strong, &mut sum_buf),
+    )
+    .map_err(|error| match error {
+        SignatureError::Io(error) if error.kind() != io::ErrorKind::UnexpectedEof => error,
+        other => io::Error::new(io::ErrorKind::InvalidData, other),
+    })
+}
+
+/// Writes one block's rolling sum and its strong sum truncated or
+/// zero-padded to `sum_buf.len()` bytes.
+fn write_block_sums<W: Write + ?Sized>(
+    writer: &mut W,
+    rolling: u32,
+    strong: &[u8],
+    sum_buf: &mut [u8],
+) -> io::Result<()> {
+    // Write rolling_sum as int32 LE
+    writer.write_all(&(rolling as i32).to_le_bytes())?;
+
+    // Write strong_sum, truncated or padded to s2length
+    sum_buf.fill(0);
+    let copy_len = std::cmp::min(strong.len(), sum_buf.len());
+    sum_buf[..copy_len].copy_from_slice(&strong[..copy_len]);
+    writer.write_all(sum_buf)
+}
+
 /// Reads abbreviated xattr values from the sender.
 ///
 /// The sender transmits 1-based entry numbers (delta-encoded) followed by
@@ -882,3 +960,103 @@ mod xattr_abbrev_guard_tests {
         assert_eq!(values[0].1, b"abcd");
     }
 }
+
+#[cfg(test)]
+mod signature_stream_tests {
+    //! `write_signature_stream` must put exactly the bytes on the wire that
+    //! `write_signature_blocks` writes for the materialised signature.
+    use std::io::Cursor;
+    use std::num::{NonZeroU8, NonZeroU32};
+
+    use protocol::ProtocolVersion;
+    use signature::{
+        SignatureAlgorithm, SignatureLayout, SignatureLayoutParams, calculate_signature_layout,
+        generate_file_signature,
+    };
+
+    use super::{SumHead, write_signature_blocks, write_signature_stream};
+
+    fn layout_for(len: usize, block_size: u32) -> SignatureLayout {
+        calculate_signature_layout(SignatureLayoutParams::new(
+            len as u64,
+            NonZeroU32::new(block_size),
+            ProtocolVersion::NEWEST,
+            NonZeroU8::new(16).unwrap(),
+        ))
+        .expect("layout")
+    }
+
+    #[test]
+    fn streamed_blocks_match_materialised_signature() {
+        let basis: Vec<u8> = (0..10_000).map(|i| (i * 7 % 251) as u8).collect();
+        let layout = layout_for(basis.len(), 700);
+        let signature = generate_file_signature(basis.as_slice(), layout, SignatureAlgorithm::Md4)
+            .expect("signature");
+        let sum_head = SumHead::from_layout(layout);
+        assert_eq!(sum_head, SumHead::from_signature(&signature));
+
+        let mut materialised = Vec::new();
+        write_signature_blocks(&mut materialised, &signature, sum_head.s2length)
+            .expect("write blocks");
+        let mut streamed = Vec::new();
+        let hashed = write_signature_stream(
+            &mut streamed,
+            Cursor::new(basis.clone()),
+            layout,
+            SignatureAlgorithm::Md4,
+            sum_head.s2length,
+        )
+        .expect("write stream");
+
+        assert_eq!(hashed, basis.len() as u64);
+        assert_eq!(streamed, materialised);
+        assert_eq!(streamed.len(), sum_head.count as usize * (4 + 16));
+    }
+
+    #[test]
+    fn shrunk_basis_is_padded_with_zeros() {
+        let layout = layout_for(4096, 512);
+        let basis: Vec<u8> = (0..3000).map(|i| (i % 241) as u8).collect();
+        let mut padded = basis.clone();
+        padded.resize(4096, 0);
+        let signature = generate_file_signature(padded.as_slice(), layout, SignatureAlgorithm::Md4)
+            .expect("signature");
+        let mut expected = Vec::new();
+        write_signature_blocks(&mut expected, &signature, 16).expect("write blocks");
+
+        let mut out = Vec::new();
+        let hashed = write_signature_stream(
+            &mut out,
+            Cursor::new(basis),
+            layout,
+            SignatureAlgorithm::Md4,
+            16,
+        )
+        .expect("short basis is padded");
+        assert_eq!(hashed, 4096);
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn grown_basis_is_cut_at_the_layout_length() {
+        let layout = layout_for(4096, 512);
+        let mut grown: Vec<u8> = (0..4096).map(|i| (i % 239) as u8).collect();
+        let signature = generate_file_signature(grown.as_slice(), layout, SignatureAlgorithm::Md4)
+            .expect("signature");
+        let mut expected = Vec::new();
+        write_signature_blocks(&mut expected, &signature, 16).expect("write blocks");
+
+        grown.extend_from_slice(&[0xAB; 700]);
+        let mut out = Vec::new();
+        let hashed = write_signature_stream(
+            &mut out,
+            Cursor::new(grown),
+            layout,
+            SignatureAlgorithm::Md4,
+            16,
+        )
+        .expect("grown basis is cut");
+        assert_eq!(hashed, 4096);
+        assert_eq!(out, expected);
+    }
+}
@@ -7,10 +7,14 @@
 //!
 //! - [`ChecksumFactory`] - Factory for creating signature algorithms from negotiated parameters
 //! - [`TransferDeadline`] - Monotonic deadline for `--stop-at` / `--stop-after` enforcement
+//! - [`remaining_paths`] - Paths a deadline-stopped transfer did not reach
 
 pub mod checksum;
 /// Deadline enforcement for `--stop-at` / `--stop-after` / `--time-limit`.
 pub mod deadline;
+/// Paths left over when `--stop-at` / `--stop-after` ends a transfer.
+pub mod remaining;
 
 pub use checksum::ChecksumFactory;
 pub use deadline::TransferDeadline;
+pub use remaining::remaining_paths;
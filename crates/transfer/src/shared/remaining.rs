@@ -0,0 +1,58 @@
+//! Paths a transfer stopped by `--stop-at` / `--stop-after` did not reach.
+//!
+//! oc-rsync extension with no upstream equivalent, feeding the client's
+//! `--remaining-files` hand-off list. The receiver and the sender both stop
+//! at a file-list index and pass the entries from there on; this module
+//! applies the one rule both sides share, so a pull and a push of the same
+//! tree hand off the same list.
+
+use std::path::PathBuf;
+
+use protocol::flist::FileEntry;
+
+/// Returns the paths of `unreached` entries the next run must send.
+///
+/// Every entry is listed in file-list order except the transfer root (an
+/// empty or `.` path), which the next run recreates from its own operands.
+/// Directories and hard-link followers stay in: `--files-from` does not
+/// recurse into a listed directory, and a follower whose leader was already
+/// sent would otherwise never reach the destination.
+pub fn remaining_paths<'a, I>(unreached: I) -> Vec<PathBuf>
+where
+    I: IntoIterator<Item = &'a FileEntry>,
+{
+    unreached
+        .into_iter()
+        .map(FileEntry::path)
+        .filter(|path| !path.as_os_str().is_empty() && path.as_os_str() != ".")
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_every_entry_but_the_root() {
+        let mut follower = FileEntry::new_file("dir/b".into(), 1, 0o644);
+        follower.set_hlinked(true);
+        let entries = [
+            FileEntry::new_directory(".".into(), 0o755),
+            FileEntry::new_directory("dir".into(), 0o755),
+            FileEntry::new_file("dir/a".into(), 1, 0o644),
+            follower,
+            FileEntry::new_symlink("dir/link".into(), "a".into()),
+        ];
+
+        assert_eq!(
+            remaining_paths(&entries),
+            [
+                PathBuf::from("dir"),
+                PathBuf::from("dir/a"),
+                PathBuf::from("dir/b"),
+                PathBuf::from("dir/link")
+            ]
+        );
+    }
+}
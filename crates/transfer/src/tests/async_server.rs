@@ -0,0 +1,37 @@
+use std::ffi::OsString;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use crate::{ServerConfig, ServerRole, run_server_async};
+
+#[test]
+fn async_server_reports_handshake_failure_and_closes_stream() {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(1)
+        .enable_all()
+        .build()
+        .expect("runtime");
+    let config = ServerConfig::from_flag_string_and_args(
+        ServerRole::Receiver,
+        "-logDtpre.iLsfxC".to_owned(),
+        vec![OsString::from(".")],
+    )
+    .expect("config parses");
+
+    runtime.block_on(async {
+        let (server_side, mut client) = tokio::io::duplex(4096);
+        let (reader, writer) = tokio::io::split(server_side);
+        let server = tokio::spawn(run_server_async(config, reader, writer));
+
+        // A client that hangs up before sending its version.
+        client.shutdown().await.expect("client shutdown");
+        let mut received = Vec::new();
+        client
+            .read_to_end(&mut received)
+            .await
+            .expect("server side reaches EOF");
+
+        let result = server.await.expect("server task");
+        assert!(result.is_err(), "handshake must fail on EOF");
+    });
+}
@@ -1,6 +1,8 @@
 //! Tests for server module.
 
 mod allow_inc_recurse;
+#[cfg(feature = "tokio")]
+mod async_server;
 mod config;
 mod filter_list_gate;
 mod multiplex_protocol_version;
@@ -17,6 +17,8 @@ use fast_io::FileWriter;
 use protocol::codec::NdxCodec;
 
 use engine::CleanupManager;
+#[cfg(feature = "tracing")]
+use tracing::instrument;
 
 use crate::adaptive_buffer::adaptive_writer_capacity;
 use crate::delta_apply::{ChecksumVerifier, SparseWriteState};
@@ -63,6 +65,14 @@ use super::{ResponseContext, read_response_header};
 /// - `receiver.c:recv_files()` reads deltas
 /// - `receiver.c:receive_data()` applies delta tokens
 #[allow(clippy::too_many_arguments)]
+#[cfg_attr(
+    feature = "tracing",
+    instrument(
+        skip_all,
+        name = "file_transfer",
+        fields(ndx = pending.ndx(), bytes = tracing::field::Empty)
+    )
+)]
 pub fn process_file_response<R: Read>(
     reader: &mut ServerReader<R>,
     ndx_codec: &mut impl NdxCodec,
@@ -415,5 +425,7 @@ pub fn process_file_response<R: Read>(
     }
     cleanup_guard.keep();
 
+    #[cfg(feature = "tracing")]
+    tracing::Span::current().record("bytes", total_bytes);
     Ok(total_bytes)
 }
@@ -10,8 +10,11 @@
 //! - `receiver.c:receive_data()` applies delta tokens
 
 use std::io::{self, Read};
+use std::time::Instant;
 
 use protocol::codec::NdxCodec;
+#[cfg(feature = "tracing")]
+use tracing::instrument;
 
 use crate::delta_apply::ChecksumVerifier;
 use crate::map_file::MapFile;
@@ -48,6 +51,11 @@ pub struct StreamingResult {
     /// upstream `keptstr` wording on a verification failure (an in-place update
     /// is "retained", not "discarded").
     pub is_inplace: bool,
+    /// When the response header (its first bytes on the wire) had been read.
+    ///
+    /// Set by [`process_file_response_streaming`]; feeds the pipeline's
+    /// request-to-first-byte latency probe.
+    pub header_read_at: Option<Instant>,
 }
 
 /// Processes a file transfer response, streaming chunks to the disk thread.
@@ -77,6 +85,14 @@ pub struct StreamingResult {
 ///   upstream rsync uses a single continuous zstd stream for the entire session.
 ///   The caller must call `token_reader.reset()` between files.
 #[allow(clippy::too_many_arguments)]
+#[cfg_attr(
+    feature = "tracing",
+    instrument(
+        skip_all,
+        name = "file_transfer",
+        fields(ndx = pending.ndx(), size = pending.target_size())
+    )
+)]
 pub fn process_file_response_streaming<R: Read>(
     reader: &mut ServerReader<R>,
     ndx_codec: &mut impl NdxCodec,
@@ -91,6 +107,7 @@ pub fn process_file_response_streaming<R: Read>(
     token_reader: &mut TokenReader,
 ) -> io::Result<StreamingResult> {
     let header = read_response_header(reader, ndx_codec, pending, ctx)?;
+    let header_read_at = Instant::now();
 
     // upstream: receiver.c:911-912 - updating_basis_or_equiv is set when the
     // basis file IS the destination being updated in place (fnamecmp == fname).
@@ -189,6 +206,7 @@ pub fn process_file_response_streaming<R: Read>(
                     expected_checksum,
                     checksum_len,
                     is_inplace,
+                    header_read_at: Some(header_read_at),
                 });
             }
 
@@ -219,6 +237,10 @@ pub fn process_file_response_streaming<R: Read>(
                 updating_basis,
                 is_inplace,
             )
+            .map(|result| StreamingResult {
+                header_read_at: Some(header_read_at),
+                ..result
+            })
         }
         first_delta => {
             // First token was not a simple literal - send Begin and process normally.
@@ -240,6 +262,10 @@ pub fn process_file_response_streaming<R: Read>(
                 updating_basis,
                 is_inplace,
             )
+            .map(|result| StreamingResult {
+                header_read_at: Some(header_read_at),
+                ..result
+            })
         }
     }
 }
@@ -13,6 +13,8 @@
 use std::io::{self, Read};
 
 use engine::signature::FileSignature;
+#[cfg(feature = "tracing")]
+use tracing::instrument;
 
 use crate::delta_apply::ChecksumVerifier;
 use crate::map_file::MapFile;
@@ -74,6 +76,14 @@ pub(super) fn literal_to_buf<R: Read>(
 /// If `pending_delta` is `Some`, it is processed first without reading from
 /// the wire. Then the regular token loop continues until end-of-file.
 #[allow(clippy::too_many_arguments)]
+#[cfg_attr(
+    feature = "tracing",
+    instrument(
+        skip_all,
+        name = "delta_apply",
+        fields(literal_bytes = tracing::field::Empty, matched_bytes = tracing::field::Empty)
+    )
+)]
 pub(super) fn process_remaining_tokens<R: Read>(
     reader: &mut ServerReader<R>,
     file_tx: &spsc::Sender<FileMessage>,
@@ -131,6 +141,10 @@ pub(super) fn process_remaining_tokens<R: Read>(
                         )
                     })?;
 
+                #[cfg(feature = "tracing")]
+                tracing::Span::current()
+                    .record("literal_bytes", literal_bytes)
+                    .record("matched_bytes", matched_bytes);
                 return Ok(StreamingResult {
                     total_bytes,
                     literal_bytes,
@@ -138,6 +152,7 @@ pub(super) fn process_remaining_tokens<R: Read>(
                     expected_checksum,
                     checksum_len,
                     is_inplace,
+                    header_read_at: None,
                 });
             }
             DeltaToken::Literal(literal_data) => {
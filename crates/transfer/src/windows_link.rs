@@ -0,0 +1,218 @@
+#![deny(unsafe_code)]
+//! Wire representation of Windows symlink and junction targets.
+//!
+//! A Windows sender reads link targets in native form: relative symlinks use
+//! `\` separators (`..\lib`), and junctions always store an absolute,
+//! often extended-length target (`\\?\C:\src\lib`). Neither resolves on a
+//! Unix peer. Under `--links` this module rewrites the target into the
+//! POSIX form upstream rsync produces when it runs under Cygwin:
+//!
+//! - separators become `/`;
+//! - an absolute target inside the transfer root becomes a path relative to
+//!   the link's directory, so a junction that points back into the tree
+//!   still resolves after the copy;
+//! - any other absolute target uses the Cygwin spelling (`/cygdrive/c/...`,
+//!   `//server/share/...`), which stays a dangling but recognisable link
+//!   on a Unix peer and is rejected by `--safe-links` like any absolute link.
+//!
+//! [`target_from_wire`] is the receiving half: a Windows receiver turns `/`
+//! back into `\` and maps `/cygdrive/<drive>/` to `<drive>:\` before the link
+//! is created, so a tree round-trips between Windows hosts.
+//!
+//! The helpers are pure string transforms and behave identically on every
+//! platform; only the Windows generator and receiver call them. Targets that
+//! are not valid UTF-8 are passed through unchanged.
+
+use std::path::{Path, PathBuf};
+
+/// Cygwin's mount point for drive letters (`/cygdrive/c` is `C:\`).
+const CYGDRIVE_PREFIX: &str = "/cygdrive/";
+
+/// Rewrites a native Windows link `target` into its wire form.
+///
+/// `link_path` is the full path of the link on the sender and `root` is the
+/// directory the transfer's relative paths are anchored at; an absolute
+/// target under `root` is made relative to the link's parent directory.
+/// Path comparisons are ASCII case-insensitive, matching NTFS.
+#[must_use]
+pub fn target_to_wire(target: &Path, link_path: &Path, root: &Path) -> PathBuf {
+    let Some(raw) = target.to_str() else {
+        return target.to_path_buf();
+    };
+    let target = strip_verbatim_prefix(raw).replace('\\', "/");
+    if !is_windows_absolute(&target) {
+        return PathBuf::from(target);
+    }
+
+    if let (Some(link), Some(root)) = (link_path.to_str(), root.to_str()) {
+        let link = strip_verbatim_prefix(link).replace('\\', "/");
+        let root = strip_verbatim_prefix(root).replace('\\', "/");
+        if is_within(&target, &root) && is_within(&link, &root) {
+            let link_dir = link.rsplit_once('/').map_or("", |(dir, _)| dir);
+            return PathBuf::from(relative_to(&target, link_dir));
+        }
+    }
+
+    PathBuf::from(cygwin_spelling(&target))
+}
+
+/// Rewrites a wire link target into the form a Windows receiver hands to
+/// `CreateSymbolicLinkW`.
+///
+/// `/cygdrive/<drive>/rest` becomes `<DRIVE>:\rest` and every `/` becomes
+/// `\`; other targets keep their shape.
+#[must_use]
+pub fn target_from_wire(target: &Path) -> PathBuf {
+    let Some(raw) = target.to_str() else {
+        return target.to_path_buf();
+    };
+    if let Some(rest) = raw.strip_prefix(CYGDRIVE_PREFIX) {
+        let mut chars = rest.chars();
+        if let Some(drive) = chars.next()
+            && drive.is_ascii_alphabetic()
+        {
+            let tail = chars.as_str();
+            if tail.is_empty() || tail.starts_with('/') {
+                let tail = tail.trim_start_matches('/').replace('/', "\\");
+                return PathBuf::from(format!("{}:\\{tail}", drive.to_ascii_uppercase()));
+            }
+        }
+    }
+    PathBuf::from(raw.replace('/', "\\"))
+}
+
+/// Strips the `\\?\`, `\??\`, and `\\?\UNC\` prefixes that junction targets
+/// and canonicalised paths carry.
+fn strip_verbatim_prefix(path: &str) -> String {
+    if let Some(rest) = path.strip_prefix(r"\\?\UNC\") {
+        return format!(r"\\{rest}");
+    }
+    path.strip_prefix(r"\\?\")
+        .or_else(|| path.strip_prefix(r"\??\"))
+        .unwrap_or(path)
+        .to_string()
+}
+
+/// Whether a `/`-separated path is drive-absolute (`C:/`) or UNC (`//`).
+fn is_windows_absolute(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    path.starts_with("//")
+        || (bytes.len() >= 3
+            && bytes[0].is_ascii_alphabetic()
+            && bytes[1] == b':'
+            && bytes[2] == b'/')
+}
+
+fn components(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/')
+        .filter(|part| !part.is_empty() && *part != ".")
+}
+
+fn is_within(path: &str, root: &str) -> bool {
+    let mut path = components(path);
+    components(root).all(|part| path.next().is_some_and(|p| p.eq_ignore_ascii_case(part)))
+}
+
+/// Returns `target` relative to `dir`; both are absolute and `/`-separated.
+fn relative_to(target: &str, dir: &str) -> String {
+    let target: Vec<&str> = components(target).collect();
+    let dir: Vec<&str> = components(dir).collect();
+    let common = target
+        .iter()
+        .zip(&dir)
+        .take_while(|(a, b)| a.eq_ignore_ascii_case(b))
+        .count();
+
+    let mut parts: Vec<&str> = vec![".."; dir.len() - common];
+    parts.extend(&target[common..]);
+    if parts.is_empty() {
+        ".".to_string()
+    } else {
+        parts.join("/")
+    }
+}
+
+/// Spells an absolute Windows path the way Cygwin presents it.
+fn cygwin_spelling(path: &str) -> String {
+    if path.starts_with("//") {
+        return path.to_string();
+    }
+    let drive = path[..1].to_ascii_lowercase();
+    let rest = path[2..].trim_start_matches('/');
+    if rest.is_empty() {
+        format!("{CYGDRIVE_PREFIX}{drive}")
+    } else {
+        format!("{CYGDRIVE_PREFIX}{drive}/{rest}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_wire(target: &str, link: &str, root: &str) -> PathBuf {
+        target_to_wire(Path::new(target), Path::new(link), Path::new(root))
+    }
+
+    #[test]
+    fn relative_symlink_uses_forward_slashes() {
+        let wire = to_wire(r"..\lib\util.dll", r"C:\src\bin\util.dll", r"C:\src");
+        assert_eq!(wire, PathBuf::from("../lib/util.dll"));
+    }
+
+    #[test]
+    fn junction_inside_root_becomes_relative() {
+        let wire = to_wire(r"\\?\C:\src\data\shared", r"C:\src\app\shared", r"C:\src");
+        assert_eq!(wire, PathBuf::from("../data/shared"));
+    }
+
+    #[test]
+    fn junction_comparison_ignores_case() {
+        let wire = to_wire(r"\??\c:\SRC\Data", r"C:\src\link", r"C:\Src");
+        assert_eq!(wire, PathBuf::from("Data"));
+    }
+
+    #[test]
+    fn junction_to_link_directory_is_dot() {
+        let wire = to_wire(r"C:\src\app", r"C:\src\app\self", r"C:\src");
+        assert_eq!(wire, PathBuf::from("."));
+    }
+
+    #[test]
+    fn junction_outside_root_uses_cygdrive_spelling() {
+        let wire = to_wire(r"\\?\D:\Users\Public", r"C:\src\public", r"C:\src");
+        assert_eq!(wire, PathBuf::from("/cygdrive/d/Users/Public"));
+    }
+
+    #[test]
+    fn root_prefix_must_match_whole_components() {
+        let wire = to_wire(r"C:\src2\x", r"C:\src\link", r"C:\src");
+        assert_eq!(wire, PathBuf::from("/cygdrive/c/src2/x"));
+    }
+
+    #[test]
+    fn unc_target_keeps_double_slash() {
+        let wire = to_wire(r"\\?\UNC\server\share\dir", r"C:\src\link", r"C:\src");
+        assert_eq!(wire, PathBuf::from("//server/share/dir"));
+    }
+
+    #[test]
+    fn from_wire_maps_cygdrive_to_drive_letter() {
+        let native = target_from_wire(Path::new("/cygdrive/d/Users/Public"));
+        assert_eq!(native, PathBuf::from(r"D:\Users\Public"));
+        let native = target_from_wire(Path::new("/cygdrive/c"));
+        assert_eq!(native, PathBuf::from(r"C:\"));
+    }
+
+    #[test]
+    fn from_wire_converts_relative_separators() {
+        let native = target_from_wire(Path::new("../lib/util.dll"));
+        assert_eq!(native, PathBuf::from(r"..\lib\util.dll"));
+    }
+
+    #[test]
+    fn from_wire_leaves_other_cygdrive_like_names() {
+        let native = target_from_wire(Path::new("/cygdrive/data/x"));
+        assert_eq!(native, PathBuf::from(r"\cygdrive\data\x"));
+    }
+}
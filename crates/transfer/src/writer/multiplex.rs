@@ -8,6 +8,7 @@ use std::io::{self, IoSlice, Write};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+use protocol::frame_dump::{self, FrameDirection};
 use protocol::{MessageCode, MessageHeader};
 
 /// Writer that wraps data in multiplex `MSG_DATA` frames.
@@ -177,6 +178,46 @@ impl<W: Write> MultiplexWriter<W> {
         self.last_io_out = Instant::now();
         Ok(())
     }
+
+    /// Opens a `MSG_DATA` frame carrying `prefix` plus `payload_len` bytes
+    /// that the caller delivers to the socket itself (e.g. via `sendfile`).
+    ///
+    /// Flushes the buffer, writes the frame header and `prefix`, and flushes
+    /// `inner` so the payload lands right behind them on the wire. The caller
+    /// must then deliver exactly `payload_len` bytes, falling back to
+    /// [`Self::write_passthrough`] for any it cannot send directly.
+    pub(crate) fn begin_passthrough(
+        &mut self,
+        prefix: &[u8],
+        payload_len: usize,
+    ) -> io::Result<()> {
+        let frame_len = u32::try_from(prefix.len() + payload_len)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "frame too large"))?;
+        let header = MessageHeader::new(MessageCode::Data, frame_len)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        self.flush_buffer()?;
+        frame_dump::record(
+            FrameDirection::Send,
+            MessageCode::Data,
+            frame_len as usize,
+            prefix,
+        );
+        self.inner.write_all(&header.encode())?;
+        self.inner.write_all(prefix)?;
+        self.inner.flush()?;
+        self.dirty = false;
+        self.last_io_out = Instant::now();
+        Ok(())
+    }
+
+    /// Writes payload bytes of a frame opened by [`Self::begin_passthrough`]
+    /// straight to `inner`.
+    pub(crate) fn write_passthrough(&mut self, data: &[u8]) -> io::Result<()> {
+        self.inner.write_all(data)?;
+        self.inner.flush()?;
+        self.last_io_out = Instant::now();
+        Ok(())
+    }
 }
 
 impl<W: Write> Write for MultiplexWriter<W> {
@@ -256,6 +297,11 @@ impl<W: Write> Write for MultiplexWriter<W> {
             // Vec allocation.
             let header = MessageHeader::new(MessageCode::Data, total_len as u32)
                 .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            let snippet = bufs
+                .iter()
+                .find(|buf| !buf.is_empty())
+                .map_or(&[][..], |buf| buf);
+            frame_dump::record(FrameDirection::Send, MessageCode::Data, total_len, snippet);
             let header_bytes = header.encode();
             self.inner.write_all(&header_bytes)?;
             for buf in bufs {
@@ -432,6 +432,66 @@ impl<W: Write> ServerWriter<W> {
         }
     }
 
+    /// Returns true when payload bytes can bypass this writer and go straight
+    /// to the socket (see [`Self::begin_passthrough`]).
+    ///
+    /// Compression must see every byte, and so must a batch recorder teeing
+    /// the pre-mux stream, so both rule the passthrough out.
+    pub(crate) fn supports_passthrough(&self) -> bool {
+        match self {
+            Self::Plain(_) => true,
+            Self::Multiplex(mux) => mux.batch_recorder.is_none(),
+            Self::Compressed(_) | Self::Taken => false,
+        }
+    }
+
+    /// Writes `prefix` and readies the stream for `payload_len` bytes that the
+    /// caller sends directly to the underlying socket.
+    ///
+    /// In multiplex mode the prefix opens a `MSG_DATA` frame sized for prefix
+    /// plus payload. Everything is flushed first, so the payload follows the
+    /// prefix on the wire. Bytes the caller cannot send directly go through
+    /// [`Self::write_passthrough`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` when [`Self::supports_passthrough`] is false.
+    pub(crate) fn begin_passthrough(
+        &mut self,
+        prefix: &[u8],
+        payload_len: usize,
+    ) -> io::Result<()> {
+        match self {
+            Self::Plain(w) => {
+                w.write_all(prefix)?;
+                w.flush()
+            }
+            Self::Multiplex(mux) if mux.batch_recorder.is_none() => {
+                mux.begin_passthrough(prefix, payload_len)
+            }
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "passthrough requires an uncompressed, unrecorded stream",
+            )),
+        }
+    }
+
+    /// Writes passthrough payload bytes (see [`Self::begin_passthrough`])
+    /// without any further framing.
+    pub(crate) fn write_passthrough(&mut self, data: &[u8]) -> io::Result<()> {
+        match self {
+            Self::Plain(w) => {
+                w.write_all(data)?;
+                w.flush()
+            }
+            Self::Multiplex(mux) => mux.write_passthrough(data),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "passthrough requires an uncompressed, unrecorded stream",
+            )),
+        }
+    }
+
     /// Writes raw bytes directly to the underlying stream, bypassing multiplexing.
     ///
     /// Used for protocol exchanges like the final goodbye handshake where
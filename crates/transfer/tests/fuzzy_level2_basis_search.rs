@@ -76,6 +76,7 @@ fn fuzzy_level2_finds_basis_in_reference_directory() {
         checksum_algorithm: signature::SignatureAlgorithm::Md4,
         whole_file: false,
         compat_flags: None,
+        block_length: None,
     };
 
     let result = find_basis_file_with_config(&config);
@@ -136,6 +137,7 @@ fn fuzzy_level1_does_not_search_reference_directories() {
         checksum_algorithm: signature::SignatureAlgorithm::Md4,
         whole_file: false,
         compat_flags: None,
+        block_length: None,
     };
 
     let result = find_basis_file_with_config(&config);
@@ -199,6 +201,7 @@ fn fuzzy_level2_selects_best_match_across_reference_dirs() {
         checksum_algorithm: signature::SignatureAlgorithm::Md4,
         whole_file: false,
         compat_flags: None,
+        block_length: None,
     };
 
     let result = find_basis_file_with_config(&config);
@@ -255,6 +258,7 @@ fn fuzzy_level2_generates_valid_signature_from_basis() {
         checksum_algorithm: signature::SignatureAlgorithm::Md4,
         whole_file: false,
         compat_flags: None,
+        block_length: None,
     };
 
     let result = find_basis_file_with_config(&config);
@@ -314,6 +318,7 @@ fn fuzzy_level0_skips_search() {
         checksum_algorithm: signature::SignatureAlgorithm::Md4,
         whole_file: false,
         compat_flags: None,
+        block_length: None,
     };
 
     let result = find_basis_file_with_config(&config);
@@ -363,6 +368,7 @@ fn whole_file_bypasses_fuzzy_search() {
         checksum_algorithm: signature::SignatureAlgorithm::Md4,
         whole_file: true,
         compat_flags: None,
+        block_length: None,
     };
 
     let result = find_basis_file_with_config(&config);
@@ -414,6 +420,7 @@ fn fuzzy_basis_selection_emits_debug_fuzzy_line() {
         checksum_algorithm: signature::SignatureAlgorithm::Md4,
         whole_file: false,
         compat_flags: None,
+        block_length: None,
     };
 
     let result = find_basis_file_with_config(&config);
@@ -37,6 +37,8 @@ fn transfer_stats_incremental_fields_exist() {
         delete_stats: DeleteStats::new(),
         created_stats: CreatedStats::new(),
         delete_limit_exceeded: false,
+        stop_limit_reached: false,
+        remaining_files: Vec::new(),
         literal_data: 0,
         matched_data: 0,
         redo_count: 0,
@@ -0,0 +1,105 @@
+//! End-to-end transfers through `run_loopback`.
+//!
+//! Both halves are the production entry points - the server half runs
+//! `run_server_stdio` as `rsync --server` would, the client half runs the
+//! handshake plus `run_server_with_handshake` as the SSH transport does - so
+//! these tests cover the full wire protocol without spawning a process.
+
+use std::ffi::OsString;
+use std::fs;
+use std::path::Path;
+
+use tempfile::TempDir;
+use transfer::{
+    ServerConfig, ServerRole, ServerStats, ServerTransferHooks, TransferProgressEvent,
+    run_loopback, run_loopback_with_hooks,
+};
+
+/// Recursive, links, perms, times, plus the `-e` capability string a current
+/// client sends.
+const FLAGS: &str = "-rlpte.iLsfxC";
+
+fn config(role: ServerRole, arg: OsString) -> ServerConfig {
+    ServerConfig::from_flag_string_and_args(role, FLAGS.to_owned(), vec![arg]).expect("config")
+}
+
+fn with_slash(path: &Path) -> OsString {
+    let mut arg = path.as_os_str().to_owned();
+    arg.push("/");
+    arg
+}
+
+fn seed(src: &Path) {
+    fs::create_dir_all(src.join("nested/deeper")).unwrap();
+    fs::write(src.join("top.txt"), b"top level").unwrap();
+    fs::write(src.join("nested/mid.bin"), vec![0xa5u8; 300 * 1024]).unwrap();
+    fs::write(src.join("nested/deeper/leaf"), b"leaf").unwrap();
+    fs::write(src.join("empty"), b"").unwrap();
+}
+
+fn assert_copied(src: &Path, dst: &Path) {
+    for file in ["top.txt", "nested/mid.bin", "nested/deeper/leaf", "empty"] {
+        assert_eq!(
+            fs::read(dst.join(file)).unwrap(),
+            fs::read(src.join(file)).unwrap(),
+            "{file} differs"
+        );
+    }
+}
+
+#[test]
+fn pull_copies_tree_through_loopback() {
+    let temp = TempDir::new().unwrap();
+    let src = temp.path().join("src");
+    let dst = temp.path().join("dst");
+    seed(&src);
+
+    let client = config(ServerRole::Receiver, dst.clone().into_os_string());
+    let server = config(ServerRole::Generator, with_slash(&src));
+    let stats = run_loopback(client, server).expect("loopback pull");
+
+    assert_copied(&src, &dst);
+    assert!(matches!(stats.client, ServerStats::Receiver(_)));
+    assert!(matches!(stats.server, ServerStats::Generator(_)));
+}
+
+#[test]
+fn push_copies_tree_through_loopback() {
+    let temp = TempDir::new().unwrap();
+    let src = temp.path().join("src");
+    let dst = temp.path().join("dst");
+    seed(&src);
+
+    let client = config(ServerRole::Generator, with_slash(&src));
+    let server = config(ServerRole::Receiver, dst.clone().into_os_string());
+    let stats = run_loopback(client, server).expect("loopback push");
+
+    assert_copied(&src, &dst);
+    assert!(matches!(stats.server, ServerStats::Receiver(_)));
+}
+
+#[test]
+fn push_reports_client_progress_through_hooks() {
+    let temp = TempDir::new().unwrap();
+    let src = temp.path().join("src");
+    let dst = temp.path().join("dst");
+    seed(&src);
+
+    let mut sent = Vec::new();
+    let mut record = |event: &TransferProgressEvent<'_>| sent.push(event.path.to_path_buf());
+    let client = config(ServerRole::Generator, with_slash(&src));
+    let server = config(ServerRole::Receiver, dst.clone().into_os_string());
+    let hooks = ServerTransferHooks {
+        progress: Some(&mut record),
+        ..ServerTransferHooks::default()
+    };
+    run_loopback_with_hooks(client, server, hooks).expect("loopback push");
+
+    assert_copied(&src, &dst);
+    sent.sort();
+    let expected: Vec<_> = ["empty", "nested/deeper/leaf", "nested/mid.bin", "top.txt"]
+        .iter()
+        .map(Path::new)
+        .collect();
+    assert_eq!(sent, expected);
+}
@@ -0,0 +1,36 @@
+#![deny(unsafe_code)]
+
+//! `oc-rsync-mount`: mounts an rsync daemon module as a read-only FUSE
+//! filesystem. oc-rsync extension; see `core::client::mount`.
+
+// Same allocator as `oc-rsync`; the page-return tuning there matters less for
+// a long-lived, mostly idle mount, so it is not repeated here.
+#[cfg(unix)]
+#[global_allocator]
+static GLOBAL: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
+
+#[cfg(target_os = "linux")]
+mod support;
+
+use std::process::ExitCode;
+
+#[cfg(target_os = "linux")]
+fn main() -> ExitCode {
+    use std::{env, io};
+
+    let mut stdout = io::stdout().lock();
+    let mut stderr = io::stderr().lock();
+    support::dispatch(
+        env::args_os(),
+        &mut stdout,
+        &mut stderr,
+        cli::run_mount,
+        cli::exit_code_from,
+    )
+}
+
+#[cfg(not(target_os = "linux"))]
+fn main() -> ExitCode {
+    eprintln!("oc-rsync-mount: FUSE mounts are only supported on Linux");
+    ExitCode::from(4)
+}
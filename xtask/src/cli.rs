@@ -72,6 +72,9 @@ pub enum Command {
     /// Run the workspace test suite (prefers cargo-nextest).
     Test(TestArgs),
 
+    /// Pretty-print a `--debug-protocol-dump` frame capture.
+    TraceDump(TraceDumpArgs),
+
     /// Validate drop-in fidelity vs upstream rsync across all client transports.
     Validate(ValidateMatrixArgs),
 }
@@ -460,6 +463,18 @@ pub struct SbomArgs {
     pub output: Option<PathBuf>,
 }
 
+/// Arguments for the `trace-dump` command.
+#[derive(Parser, Debug, Default)]
+pub struct TraceDumpArgs {
+    /// Capture written by `oc-rsync --debug-protocol-dump=FILE`.
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    /// Show only frames with this tag, e.g. `MSG_DATA`.
+    #[arg(long, value_name = "TAG")]
+    pub tag: Option<String>,
+}
+
 /// Arguments for the `test` command.
 #[derive(Parser, Debug, Default)]
 pub struct TestArgs {
@@ -503,6 +518,7 @@ impl CommandExt for Command {
             Command::ReleaseNotes(_) => Box::new(ReleaseNotesTask),
             Command::Sbom(_) => Box::new(SbomTask),
             Command::Test(args) => args.as_task(),
+            Command::TraceDump(_) => Box::new(TraceDumpTask),
             Command::Validate(_) => Box::new(ValidateTask),
         }
     }
@@ -700,6 +716,23 @@ impl Task for GapReportTask {
     }
 }
 
+/// Task for protocol frame capture rendering.
+struct TraceDumpTask;
+
+impl Task for TraceDumpTask {
+    fn name(&self) -> &'static str {
+        "trace-dump"
+    }
+
+    fn description(&self) -> &'static str {
+        "Pretty-print a protocol frame capture"
+    }
+
+    fn explicit_duration(&self) -> Option<Duration> {
+        Some(Duration::from_secs(1))
+    }
+}
+
 /// Task for release notes management.
 struct ReleaseNotesTask;
 
@@ -747,6 +780,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_trace_dump_file_and_tag() {
+        let cli = Cli::parse_from([
+            "cargo-xtask",
+            "trace-dump",
+            "frames.jsonl",
+            "--tag",
+            "MSG_DATA",
+        ]);
+        match cli.command {
+            Command::TraceDump(args) => {
+                assert_eq!(args.file, PathBuf::from("frames.jsonl"));
+                assert_eq!(args.tag.as_deref(), Some("MSG_DATA"));
+            }
+            _ => panic!("expected trace-dump command"),
+        }
+    }
+
     #[test]
     fn parse_interop_exit_codes() {
         let cli = Cli::parse_from([
@@ -14,4 +14,5 @@ pub mod release;
 pub mod release_notes;
 pub mod sbom;
 pub mod test;
+pub mod trace_dump;
 pub mod validate;
@@ -0,0 +1,190 @@
+#![deny(unsafe_code)]
+
+//! Protocol frame capture pretty-printer.
+//!
+//! The `trace-dump` command renders a capture written by
+//! `oc-rsync --debug-protocol-dump=FILE` as an aligned table: time since the
+//! first frame, process id, direction, message tag, payload length, and the
+//! recorded payload prefix in hex with a printable-ASCII column. Frames whose
+//! payload was longer than the recorded prefix are marked with `...`.
+
+use crate::cli::TraceDumpArgs;
+use crate::error::{TaskError, TaskResult};
+use crate::util::{read_file_with_context, resolve_workspace_path};
+use serde_json::Value;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// Number of payload bytes shown per row before the snippet is cut short.
+const SHOWN_BYTES: usize = 16;
+
+/// Options accepted by the `trace-dump` command.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TraceDumpOptions {
+    /// Capture file to render (relative to workspace root unless absolute).
+    pub file: PathBuf,
+    /// Show only frames with this tag, e.g. `MSG_DATA`.
+    pub tag: Option<String>,
+}
+
+impl From<TraceDumpArgs> for TraceDumpOptions {
+    fn from(args: TraceDumpArgs) -> Self {
+        Self {
+            file: args.file,
+            tag: args.tag,
+        }
+    }
+}
+
+/// One decoded capture line.
+#[derive(Debug, PartialEq)]
+struct Frame {
+    ts_us: u64,
+    pid: u64,
+    dir: String,
+    tag: String,
+    len: u64,
+    payload: Vec<u8>,
+}
+
+/// Executes the `trace-dump` command.
+pub fn execute(workspace: &Path, options: TraceDumpOptions) -> TaskResult<()> {
+    let path = resolve_workspace_path(workspace, &options.file);
+    let capture = read_file_with_context(&path)?;
+    let frames = parse_capture(&capture)?;
+    print!("{}", render(&frames, options.tag.as_deref()));
+    Ok(())
+}
+
+fn parse_capture(capture: &str) -> TaskResult<Vec<Frame>> {
+    capture
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            parse_frame(line).ok_or_else(|| {
+                TaskError::Validation(format!("line {}: not a protocol dump record", index + 1))
+            })
+        })
+        .collect()
+}
+
+fn parse_frame(line: &str) -> Option<Frame> {
+    let value: Value = serde_json::from_str(line).ok()?;
+    Some(Frame {
+        ts_us: value["ts_us"].as_u64()?,
+        pid: value["pid"].as_u64()?,
+        dir: value["dir"].as_str()?.to_owned(),
+        tag: value["tag"].as_str()?.to_owned(),
+        len: value["len"].as_u64()?,
+        payload: decode_hex(value["payload"].as_str()?)?,
+    })
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|start| u8::from_str_radix(text.get(start..start + 2)?, 16).ok())
+        .collect()
+}
+
+fn render(frames: &[Frame], tag: Option<&str>) -> String {
+    let start = frames.first().map_or(0, |frame| frame.ts_us);
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{:>12}  {:>7}  {:<4}  {:<20}  {:>8}  payload",
+        "ms", "pid", "dir", "tag", "len"
+    );
+    let mut shown = 0usize;
+    for frame in frames
+        .iter()
+        .filter(|frame| tag.is_none_or(|tag| frame.tag == tag))
+    {
+        let elapsed_ms = frame.ts_us.saturating_sub(start) as f64 / 1000.0;
+        let arrow = if frame.dir == "send" { "->" } else { "<-" };
+        let _ = writeln!(
+            out,
+            "{elapsed_ms:>12.3}  {:>7}  {arrow:<4}  {:<20}  {:>8}  {}",
+            frame.pid,
+            frame.tag,
+            frame.len,
+            snippet(&frame.payload, frame.len)
+        );
+        shown += 1;
+    }
+    let _ = writeln!(out, "{shown} of {} frame(s)", frames.len());
+    out
+}
+
+fn snippet(payload: &[u8], len: u64) -> String {
+    let bytes = &payload[..payload.len().min(SHOWN_BYTES)];
+    let mut hex = String::with_capacity(bytes.len() * 3);
+    for byte in bytes {
+        let _ = write!(hex, "{byte:02x} ");
+    }
+    let ascii: String = bytes
+        .iter()
+        .map(|&byte| {
+            if byte.is_ascii_graphic() || byte == b' ' {
+                char::from(byte)
+            } else {
+                '.'
+            }
+        })
+        .collect();
+    let more = if (bytes.len() as u64) < len {
+        "..."
+    } else {
+        ""
+    };
+    format!("{hex}|{ascii}|{more}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CAPTURE: &str = concat!(
+        "{\"ts_us\":1000000,\"pid\":7,\"dir\":\"send\",\"tag\":\"MSG_DATA\",\"code\":0,",
+        "\"len\":5,\"payload\":\"68656c6c6f\"}\n",
+        "\n",
+        "{\"ts_us\":1002500,\"pid\":7,\"dir\":\"recv\",\"tag\":\"MSG_INFO\",\"code\":2,",
+        "\"len\":300,\"payload\":\"0001\"}\n",
+    );
+
+    #[test]
+    fn parse_capture_decodes_records() {
+        let frames = parse_capture(CAPTURE).expect("parse capture");
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].payload, b"hello");
+        assert_eq!(frames[1].dir, "recv");
+        assert_eq!(frames[1].len, 300);
+    }
+
+    #[test]
+    fn parse_capture_rejects_foreign_lines() {
+        let error = parse_capture("not json\n").unwrap_err();
+        assert!(matches!(error, TaskError::Validation(message) if message.contains("line 1")));
+        assert!(decode_hex("abc").is_none());
+        assert!(decode_hex("zz").is_none());
+    }
+
+    #[test]
+    fn render_aligns_rows_and_marks_truncation() {
+        let frames = parse_capture(CAPTURE).expect("parse capture");
+        let table = render(&frames, None);
+        let rows: Vec<&str> = table.lines().collect();
+        assert_eq!(rows.len(), 4);
+        assert!(rows[1].contains("->") && rows[1].ends_with("68 65 6c 6c 6f |hello|"));
+        assert!(rows[2].contains("2.500") && rows[2].ends_with("00 01 |..|..."));
+        assert_eq!(rows[3], "2 of 2 frame(s)");
+
+        let filtered = render(&frames, Some("MSG_INFO"));
+        assert!(filtered.ends_with("1 of 2 frame(s)\n"));
+        assert!(!filtered.contains("MSG_DATA"));
+    }
+}
@@ -61,7 +61,7 @@ use crate::cli::{Cli, Command, CommandExt};
 use crate::commands::{
     benchmark, branding, doc_package, docs, gap_report, interop, man_page, no_binaries,
     no_placeholders, package, preflight, readme_version, release, release_notes, sbom, test,
-    validate,
+    trace_dump, validate,
 };
 use crate::error::TaskError;
 use crate::task::TreeRenderer;
@@ -118,6 +118,7 @@ fn run_command(cli: Cli) -> Result<(), TaskError> {
         Command::ReleaseNotes(args) => release_notes::execute(&workspace, args.into()),
         Command::Sbom(args) => sbom::execute(&workspace, args.into()),
         Command::Test(args) => test::execute(&workspace, args.into()),
+        Command::TraceDump(args) => trace_dump::execute(&workspace, args.into()),
         Command::Validate(args) => validate::execute(&workspace, args.into()),
     }
 }